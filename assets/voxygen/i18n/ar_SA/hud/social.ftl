@@ -1,5 +1,4 @@
 hud-social = لاعبين آخرين
-hud-social-online = متصل:
 hud-social-friends = اصدقاء
 hud-social-name = اسم
 hud-social-level = مستوى
\ No newline at end of file
@@ -1,5 +1,4 @@
 hud-social = Іншыя гульцы
-hud-social-online = У сетцы:
 hud-social-friends = Сябры
 hud-social-not_yet_available = Пакуль не даступны
 hud-social-faction = Фракцыя
@@ -1,5 +1,4 @@
 hud-social = Altres Jugadors
-hud-social-online = Connectats:
 hud-social-friends = Amics
 hud-social-not_yet_available = Encara no disponible
 hud-social-faction = Facció
@@ -1,4 +1,5 @@
 buff-remove = Click to remove
+buff-stacks = Stacked { $count } times
 buff-title-missing = Missing Title
 buff-desc-missing = Missing Description
 buff-title-heal = Heal
@@ -17,6 +18,8 @@ buff-title-frenzied = Frenzied
 buff-desc-frenzied = You are imbued with unnatural speed and can ignore minor injuries.
 buff-title-hastened = Hastened
 buff-desc-hastened = Your movements and attacks are faster.
+buff-title-rested = Rested
+buff-desc-rested = A good night's sleep raises your maximum health and energy.
 buff-title-bleed = Bleeding
 buff-desc-bleed = Inflicts regular damage.
 buff-title-cursed = Cursed
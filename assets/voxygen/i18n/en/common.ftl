@@ -12,6 +12,8 @@ common-video = Graphics
 common-sound = Sound
 common-chat = Chat
 common-networking = Networking
+common-accessibility = Accessibility
+common-profiles = Profiles
 common-resume = Resume
 common-characters = Characters
 common-close = Close
@@ -42,6 +44,8 @@ common-sound_settings = Sound Settings
 common-language_settings = Language Settings
 common-chat_settings = Chat Settings
 common-networking_settings = Networking Settings
+common-accessibility_settings = Accessibility Settings
+common-profiles_settings = Settings Profiles
 common-connection_lost =
     Connection lost!
     Did the server restart?
@@ -109,3 +113,4 @@ common-material-stone = Stone
 common-material-cloth = Cloth
 common-material-hide = Hide
 common-sprite-chest = Chest
+common-sprite-bed = Bed
@@ -19,6 +19,7 @@ gameinput-toggledebug = Toggle FPS and Debug Info
 gameinput-toggle_egui_debug = Toggle EGUI Debug Info
 gameinput-togglechat = Toggle Chat
 gameinput-screenshot = Take Screenshot
+gameinput-recordclip = Record Clip
 gameinput-toggleingameui = Toggle Nametags
 gameinput-fullscreen = Toggle Fullscreen
 gameinput-moveforward = Move Forward
@@ -32,14 +33,19 @@ gameinput-climb = Climb
 gameinput-climbdown = Climb Down
 gameinput-wallleap = Wall Leap
 gameinput-togglelantern = Toggle Lantern
+gameinput-togglebuilding = Toggle Building
 gameinput-mount = Mount
 gameinput-chat = Chat
+gameinput-pushtotalk = Push to Talk
 gameinput-command = Command
 gameinput-escape = Escape
 gameinput-map = Map
 gameinput-bag = Bag
 gameinput-trade = Trade
 gameinput-social = Social
+gameinput-guild = Guild
+gameinput-combatlog = Combat Log
+gameinput-questlog = Quest Log
 gameinput-sit = Sit
 gameinput-spellbook = Spells
 gameinput-settings = Settings
@@ -55,6 +61,10 @@ gameinput-select = Select Entity
 gameinput-acceptgroupinvite = Accept Group Invite
 gameinput-declinegroupinvite = Decline Group Invite
 gameinput-cyclecamera = Cycle camera
+gameinput-zoomin = Zoom in
+gameinput-zoomout = Zoom out
+gameinput-firstperson = Toggle first person
+gameinput-photomode = Photo mode
 gameinput-crafting = Crafting
 gameinput-fly = Fly
 gameinput-sneak = Sneak
@@ -38,4 +38,10 @@ hud-bag-stun_res_desc =
     Regenerates like Energy.
 hud-bag-sort_by_name = Sort by Name
 hud-bag-sort_by_quality = Sort by Quality
-hud-bag-sort_by_category = Sort by Category
\ No newline at end of file
+hud-bag-sort_by_category = Sort by Category
+hud-bag-reputation = Reputation
+hud-bag-faction_standing = { $faction }: { $standing }
+hud-bag-faction-villagers = Villagers
+hud-bag-faction-cultists = Cultists
+hud-bag-faction-gnarlings = Gnarlings
+hud-bag-faction-beasts = Beasts
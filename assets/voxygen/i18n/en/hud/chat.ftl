@@ -26,4 +26,5 @@ hud-chat-npc_other_kill_msg = { $attacker } killed [{ $victim }]
 hud-chat-loot_msg = You picked up [{ $item }]
 hud-chat-loot_fail = Your Inventory is full!
 hud-chat-goodbye = Goodbye!
-hud-chat-connection_lost = Connection lost. Kicking in { $time } seconds.
\ No newline at end of file
+hud-chat-connection_lost = Connection lost. Kicking in { $time } seconds.
+hud-chat-message_too_long = Your message is too long, it can be { $max } bytes at most.
@@ -0,0 +1,14 @@
+hud-combat_log = Combat Log
+hud-combat_log-damage_dealt = Damage dealt
+hud-combat_log-damage_taken = Damage taken
+hud-combat_log-healing = Healing
+hud-combat_log-kills = Kills
+hud-combat_log-empty = Nothing happened yet.
+hud-combat_log-clear = Clear
+hud-combat_log-unknown = something
+hud-combat_log-hit = You hit { $name } for { $amount }
+hud-combat_log-hit_by = { $name } hit you for { $amount }
+hud-combat_log-healed = You healed { $name } for { $amount }
+hud-combat_log-healed_by = { $name } healed you for { $amount }
+hud-combat_log-killed = You defeated { $name }
+hud-combat_log-killed_by = You were defeated by { $name }
@@ -1,6 +1,7 @@
 hud-group = Group
 hud-group-invite_to_join = [{ $name }] invited you to their group!
 hud-group-invite_to_trade = [{ $name }] would like to trade with you.
+hud-group-invite_to_guild = [{ $name }] invited you to their guild.
 hud-group-invite = Invite
 hud-group-kick = Kick
 hud-group-assign_leader = Assign Leader
@@ -0,0 +1,10 @@
+hud-guild = Guild
+hud-guild-none = You are not in a guild. Found one with /guild_create <name> or ask an officer of a guild to invite you.
+hud-guild-members = Members online:
+hud-guild-promote = Promote
+hud-guild-demote = Demote
+hud-guild-kick = Kick
+hud-guild-leave = Leave
+hud-guild-rank-member = Member
+hud-guild-rank-officer = Officer
+hud-guild-rank-leader = Leader
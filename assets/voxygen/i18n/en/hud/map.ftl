@@ -8,8 +8,14 @@ hud-map-dungeons = Dungeons
 hud-map-caves = Caves
 hud-map-cave = Cave
 hud-map-peaks = Mountains
+hud-map-portals = Portals
+hud-map-minimap_entities = Creatures on minimap
 hud-map-biomes = Biomes
 hud-map-voxel_map = Voxel map
+hud-map-season-spring = Spring
+hud-map-season-summer = Summer
+hud-map-season-autumn = Autumn
+hud-map-season-winter = Winter
 hud-map-trees = Giant Trees
 hud-map-tree = Giant Tree
 hud-map-town = Town
@@ -32,4 +38,10 @@ hud-map-zoom_minimap_explanation =
     the area around you in higher detail
 hud-map-gnarling = Gnarling Fortification
 hud-map-chapel_site = Sea Chapel
-hud-map-placed_by = Placed by { $name }
\ No newline at end of file
+hud-map-placed_by = Placed by { $name }
+hud-map-markers = Markers
+hud-map-name_waypoint = Name Waypoint
+hud-map-default_marker_name = Marker { $number }
+hud-map-share_marker = Share with group
+hud-map-share_marker_desc = Group members see the marker on their map
+hud-map-shared_marker = Shared with your group
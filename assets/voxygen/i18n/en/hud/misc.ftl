@@ -2,7 +2,14 @@ hud-do_not_show_on_startup = Don't show this on Startup
 hud-show_tips = Show Tips
 hud-quests = Quests
 hud-you_died = You Died
+hud-respawn_at_waypoint = Respawn at waypoint
 hud-waypoint_saved = Waypoint Saved
+hud-night_falls = Night falls, creatures grow bolder in the dark
+hud-cant_sleep = You can only sleep at night
+hud-sleeping = { $sleeping }/{ $needed } players are sleeping
+hud-woke_up = You wake up well rested
+hud-travelling = Travelling to { $destination }, stand still...
+hud-travel_interrupted = Travel interrupted
 hud-sp_arrow_txt = SP
 hud-inventory_full = Inventory Full
 hud-someone_else = someone else
@@ -35,6 +42,7 @@ hud-diary = Diary
 hud-free_look_indicator = Free look active. Press { $key } to disable.
 hud-camera_clamp_indicator = Camera vertical clamp active. Press { $key } to disable.
 hud-auto_walk_indicator = Auto walk/swim active
+hud-damage_report = Training dummy: { $dps } DPS over { $window }s, { $total } damage in { $hits } hits
 hud-collect = Collect
 hud-pick_up = Pick up
 hud-open = Open
@@ -43,4 +51,65 @@ hud-mine = Mine
 hud-talk = Talk
 hud-trade = Trade
 hud-mount = Mount
-hud-sit = Sit
\ No newline at end of file
+hud-sit = Sit
+hud-sleep = Sleep
+hud-write = Write
+hud-portal-use = Use portal
+hud-portal-travel = Click to travel here
+hud-session-summary =
+    Session summary
+    Time played: { $time }
+    Experience gained: { $xp }
+    Deaths: { $deaths }
+    Items looted: { $items }
+    Distance traveled: { $distance } km
+hud-dialogue-trade = Let's trade
+hud-dialogue-mood = How are you?
+hud-dialogue-work = Any work for me?
+hud-dialogue-farewell = Farewell
+hud-dialogue-something_else = Something else...
+hud-quest-started = New quest: { $title }
+hud-quest-completed = Quest completed: { $title }
+hud-quest-kill = Kill { $name }: { $progress }/{ $required }
+hud-quest-fetch = Collect { $item }: { $progress }/{ $required }
+hud-quest-reach = Travel to { $x }, { $y }
+hud-quest_log = Quest Log
+hud-quest_log-empty = You have no quests yet. Ask around town for work.
+hud-quest_log-completed = Completed
+hud-quest_log-reward = Reward: { $rewards }
+hud-quest_log-reward_exp = { $exp } exp
+hud-building-enabled = Building with the materials in your inventory
+hud-building-disabled = Stopped building
+hud-writing-sign = Sign
+hud-writing-book = Book
+hud-writing-write = Write
+hud-minigame-three_in_a_row = Three in a Row
+hud-minigame-play = Play
+hud-minigame-join = Take a seat
+hud-minigame-leave = Leave the game
+hud-minigame-new_game = New game
+hud-minigame-waiting = Waiting for a second player
+hud-minigame-turn = { $player }'s turn
+hud-minigame-won = { $player } won!
+hud-minigame-draw = It's a draw
+hud-photo_mode-title = Photo Mode
+hud-photo_mode-focus = Focus distance
+hud-photo_mode-blur = Blur
+hud-photo_mode-filter-none = No filter
+hud-photo_mode-filter-black_and_white = Black and white
+hud-photo_mode-filter-sepia = Sepia
+hud-photo_mode-filter-vivid = Vivid
+hud-photo_mode-filter-warm = Warm
+hud-photo_mode-filter-cold = Cold
+hud-photo_mode-resolution = Resolution: { $factor }×
+hud-photo_mode-take_photo = Take photo
+hud-world_border-near = The world border is close
+hud-world_border-beyond = You are beyond the world border!
+hud-subtitle-explosion = Explosion
+hud-subtitle-thunder = Thunder rumbles
+hud-subtitle-projectile = Projectile fired
+hud-subtitle-fire = Fire crackles
+hud-subtitle-block = Attack blocked
+hud-subtitle-ground_slam = Ground shakes
+hud-subtitle-creature = Creature calls
+hud-subtitle-glider = Glider flaps
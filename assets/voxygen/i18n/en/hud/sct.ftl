@@ -1,2 +1,3 @@
 hud-sct-experience = { $amount } Exp
+hud-sct-damage = { $amount }
 hud-sct-block = BLOCKED
\ No newline at end of file
@@ -7,6 +7,7 @@ hud-settings-debug_info = Debug Info
 hud-settings-show_hitboxes = Show hitboxes
 hud-settings-show_chat = Show chat
 hud-settings-show_hotkey_hints = Show hotkey hints
+hud-settings-streamer_mode = Streamer mode
 hud-settings-tips_on_startup = Tips-On-Startup
 hud-settings-ui_scale = UI-Scale
 hud-settings-relative_scaling = Relative Scaling
@@ -27,15 +28,30 @@ hud-settings-speech_bubble = Speech Bubble
 hud-settings-speech_bubble_self = Show Own Speech Bubbles
 hud-settings-speech_bubble_dark_mode = Speech Bubble Dark Mode
 hud-settings-speech_bubble_icon = Speech Bubble Icon
+hud-settings-speech_bubble_portrait = Speech Bubble Portrait
 hud-settings-energybar_numbers = Energybar Numbers
 hud-settings-always_show_bars = Always show Energybars
 hud-settings-experience_numbers = Experience Numbers
 hud-settings-accumulate_experience = Accumulate Experience Numbers
+hud-settings-combat_effects = Combat Effects
+hud-settings-damage_indicators = Show where hits come from
+hud-settings-low_health_vignette = Red frame at low health
+hud-settings-hit_shake = Shake the camera when hit
+hud-settings-compass = Compass
+hud-settings-compass_show = Show the compass
+hud-settings-nameplates = Nameplates
+hud-settings-nameplate_player_names = Player names
+hud-settings-nameplate_npc_names = Creature and NPC names
+hud-settings-nameplate_levels = Combat ratings
+hud-settings-nameplate_health_bars = Health bars
+hud-settings-nameplate_distance_fade = Fade with distance
+hud-settings-nameplate_hostility_colors = Colour hostile and friendly names
 hud-settings-values = Values
 hud-settings-percentages = Percentages
 hud-settings-chat = Chat
 hud-settings-background_opacity = Background Opacity
 hud-settings-chat_character_name = Character Names in Chat
+hud-settings-chat_history = Chat Lines Kept Between Sessions
 hud-settings-loading_tips = Loading Screen Tips
 hud-settings-reset_interface = Reset to Defaults
 hud-settings-pan_sensitivity = Pan Sensitivity
@@ -52,12 +68,14 @@ hud-settings-player_physics_behavior = Player physics (experimental)
 hud-settings-stop_auto_walk_on_input = Stop auto walk on movement
 hud-settings-auto_camera = Auto camera
 hud-settings-bow_zoom = Zoom in when charging bow
+hud-settings-save_session_history = Keep a history of session summaries
 hud-settings-reset_gameplay = Reset to Defaults
 hud-settings-view_distance = View Distance
 hud-settings-entity_view_distance = Entities View Distance
 hud-settings-lod_distance = LoD Distance
 hud-settings-sprites_view_distance = Sprites View Distance
 hud-settings-entities_detail_distance = Entities Detail Distance
+hud-settings-sprite_density = Foliage Density
 hud-settings-maximum_fps = Maximum FPS
 hud-settings-background_fps = Background FPS
 hud-settings-present_mode = Present Mode
@@ -79,6 +97,11 @@ hud-settings-cloud_rendering_mode-low = Low
 hud-settings-cloud_rendering_mode-medium = Medium
 hud-settings-cloud_rendering_mode-high = High
 hud-settings-cloud_rendering_mode-ultra = Ultra
+hud-settings-preset-potato = Potato
+hud-settings-preset-low = Low
+hud-settings-preset-medium = Medium
+hud-settings-preset-high = High
+hud-settings-preset-ultra = Ultra
 hud-settings-fullscreen = Fullscreen
 hud-settings-fullscreen_mode = Fullscreen Mode
 hud-settings-fullscreen_mode-exclusive = Exclusive
@@ -89,6 +112,13 @@ hud-settings-lossy_terrain_compression = Lossy terrain compression
 hud-settings-weapon_trails = Weapon trails
 hud-settings-flashing_lights = Flashing lights
 hud-settings-flashing_lights_info = Disables all kinds of flashing, e.g. flickering or lightning strikes
+hud-settings-adaptive_quality = Adaptive quality
+hud-settings-adaptive_quality_off = Keeps your settings even when the framerate drops
+hud-settings-adaptive_quality_full = Using your settings
+hud-settings-adaptive_quality_lowered = Quality lowered by { $level }/{ $max } steps to keep up the framerate
+hud-settings-adaptive_min_fps = Minimum FPS
+hud-settings-adaptive_min_view_distance = Lowest View Distance
+hud-settings-adaptive_particles = Allow disabling particles
 hud-settings-resolution = Resolution
 hud-settings-bit_depth = Bit Depth
 hud-settings-refresh_rate = Refresh Rate
@@ -96,29 +126,45 @@ hud-settings-lighting_rendering_mode = Lighting Rendering Mode
 hud-settings-lighting_rendering_mode-ashikhmin = Type A - High    
 hud-settings-lighting_rendering_mode-blinnphong = Type B - Medium
 hud-settings-lighting_rendering_mode-lambertian = Type L - Cheap
+hud-settings-lighting_rendering_mode-flat = Type F - Flat
+hud-settings-tonemapping = Tonemapping
+hud-settings-tonemapping-exponential = Exponential
+hud-settings-tonemapping-reinhard = Reinhard
+hud-settings-tonemapping-aces = ACES Filmic
 hud-settings-shadow_rendering_mode = Shadow Rendering Mode
 hud-settings-shadow_rendering_mode-none = None
 hud-settings-shadow_rendering_mode-cheap = Cheap
 hud-settings-shadow_rendering_mode-map = Map
 hud-settings-shadow_rendering_mode-map-resolution = Resolution
+hud-settings-shadow_rendering_mode-map-soft = Soft
+hud-settings-shadow_rendering_mode-map-point_lights = Point Light Shadows
 hud-settings-rain_occlusion-resolution = Rain Occlusion Resolution
 hud-settings-lod_detail = LoD Detail
 hud-settings-save_window_size = Save window size
 hud-settings-reset_graphics = Reset to Defaults
 hud-settings-bloom = Bloom
 hud-settings-point_glow = Point Glow
+hud-settings-ambient_occlusion = Ambient Occlusion
+hud-settings-volumetric_fog = Volumetric Fog
+hud-settings-screen_space_reflections = Reflections
+hud-settings-color_grading = Color Grading
 hud-settings-master_volume = Master Volume
 hud-settings-inactive_master_volume_perc = Inactive Window Volume
 hud-settings-music_volume = Music Volume
 hud-settings-sound_effect_volume = Sound Effects Volume
 hud-settings-ambience_volume = Ambience Volume
 hud-settings-music_spacing = Music Spacing
+hud-settings-voice_chat = Join the Voice Chat of Servers
+hud-settings-voice_volume = Voice Chat Volume
 hud-settings-audio_device = Audio Device
+hud-settings-default_audio_device = Default Device
+hud-settings-unavailable_audio_device = { $device } (unavailable)
 hud-settings-reset_sound = Reset to Defaults
 hud-settings-english_fallback = Display English for missing translations
 hud-settings-awaitingkey = Press a key...
 hud-settings-unbound = None
 hud-settings-reset_keybinds = Reset to Defaults
+hud-settings-keybinding_scheme = Keybinding Scheme
 hud-settings-chat_tabs = Chat Tabs
 hud-settings-label = Label:
 hud-settings-delete = Delete
@@ -128,11 +174,50 @@ hud-settings-activity = Activity
 hud-settings-death = Death
 hud-settings-group = Group
 hud-settings-faction = Faction
+hud-settings-guild = Guild
 hud-settings-world = World
 hud-settings-region = Region
 hud-settings-say = Say
+hud-settings-whispers = Whispers
+hud-settings-system = System
 hud-settings-all = All
 hud-settings-group_only = Group only
 hud-settings-reset_chat = Reset to Defaults
 hud-settings-third_party_integrations = Third-party Integrations
 hud-settings-enable_discord_integration = Enable Discord Integration
+hud-settings-color_palette = Health Colors
+hud-settings-palette-default = Default
+hud-settings-palette-deuteranopia = Deuteranopia (red-green)
+hud-settings-palette-protanopia = Protanopia (red-green, dark reds)
+hud-settings-palette-tritanopia = Tritanopia (blue-yellow)
+hud-settings-text_scale = Text Scale
+hud-settings-reduce_camera_shake = Reduce Camera Shake
+hud-settings-reduce_flashes = Reduce Flashes
+hud-settings-subtitles = Subtitles for Sounds
+hud-settings-reset_accessibility = Reset to Defaults
+hud-settings-search = Search settings
+hud-settings-search_no_results = No matching settings
+hud-settings-saved_profiles = Saved Profiles
+hud-settings-load_profile = Load
+hud-settings-delete_profile = Delete
+hud-settings-profile_name = Save Current Settings As
+hud-settings-save_profile = Save
+hud-settings-profile_file = Profile File
+hud-settings-import_profile = Import
+hud-settings-export_profile = Export Selected
+hud-settings-profile_saved = Saved the settings profile "{ $name }"
+hud-settings-profile_loaded = Loaded the settings profile "{ $name }"
+hud-settings-profile_deleted = Deleted the settings profile "{ $name }"
+hud-settings-profile_imported = Imported the settings profile "{ $name }"
+hud-settings-profile_exported = Exported the settings profile to { $name }
+hud-settings-profile_error = Settings profile error: { $error }
+hud-settings-keyboard_mouse = Keyboard & Mouse
+hud-settings-controller = Controller
+hud-settings-controller_pan_sensitivity = Camera Sensitivity
+hud-settings-stick_deadzone = Stick Dead Zone
+hud-settings-mouse_emulation_sensitivity = Menu Cursor Speed
+hud-settings-radial_menu = Skillbar Radial Menu
+hud-settings-reset_gamepad = Reset Controller
+hud-settings-binding_conflict = { $key } is also bound to { $inputs }
+hud-settings-swap_bindings = Swap Bindings
+hud-settings-keep_bindings = Keep Both
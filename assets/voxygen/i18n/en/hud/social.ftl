@@ -1,10 +1,14 @@
 hud-social = Other Players
-hud-social-online = Online:
 hud-social-friends = Friends
 hud-social-not_yet_available = Not yet available
 hud-social-faction = Faction
-hud-social-play_online_fmt = { $nb_player } player(s) online
+hud-social-play_online_fmt = { $nb_player ->
+    [one] { $nb_player } player online
+   *[other] { $nb_player } players online
+}
 hud-social-name = Name
 hud-social-level = Level
 hud-social-zone = Zone
-hud-social-account = Account
\ No newline at end of file
+hud-social-account = Account
+hud-social-mute = Mute
+hud-social-unmute = Unmute
\ No newline at end of file
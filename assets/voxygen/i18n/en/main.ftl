@@ -38,14 +38,25 @@ main-login-internal_error = Internal error on client (most likely, player charac
 main-login-failed_auth_server_url_invalid = Failed to connect to auth server
 main-login-insecure_auth_scheme = The auth Scheme HTTP is NOT supported. It's insecure! For development purposes, HTTP is allowed for 'localhost' or debug builds
 main-login-server_full = Server is full
+main-login-queue_position = The server is full, you are number { $position } in the login queue
+main-login-queue_wait = Estimated wait: { $minutes ->
+    [one] { $minutes } minute
+   *[other] { $minutes } minutes
+}
 main-login-untrusted_auth_server = Auth server not trusted
 main-login-outdated_client_or_server = ServerWentMad: Probably versions are incompatible, check for updates.
 main-login-timeout = Timeout: Server did not respond in time. (Overloaded or network issues).
 main-login-server_shut_down = Server shut down
 main-login-network_error = Network error
 main-login-network_wrong_version = Mismatched server and client version, please update your game client.
+main-login-server_requires_newer_version = This server requires a newer version of the game, please update your game client.
+main-login-server_requires_update = This server requires a newer version of the game. Version { $version } is available, please update your game client.
+main-login-outdated_version_prompt = Version { $version } of the game is available and servers running it will refuse the connection of your game client. Connect anyway?
+main-login-connect_anyway = Connect anyway
+main-login-update_available = A new version of Veloren is available: { $version }
 main-login-failed_sending_request = Request to Auth server failed
 main-login-invalid_character = The selected character is invalid
+main-login-session_expired = The session could not be resumed, please log in again
 main-login-client_crashed = Client crashed
 main-login-not_on_whitelist = You need a Whitelist entry by an Admin to join
 main-login-banned = You have been banned with the following reason
@@ -70,6 +81,25 @@ main-credits-music = Music
 main-credits-fonts = Fonts
 main-credits-other_art = Other Art
 main-credits-contributors = Contributors
+main-changelog = Changelog
+main-changelog-title = Changelog for { $version }
+main-setup = First Time Setup
+main-setup-step_language = Choose your language
+main-setup-step_graphics = Graphics quality
+main-setup-step_audio = Audio
+main-setup-step_controls = Keybindings
+main-setup-benchmarking = Measuring performance...
+main-setup-benchmark_result = Measured { $fps } FPS on a { $device } device, the suggested preset is { $preset }.
+main-setup-audio_found = An audio output device was found.
+main-setup-audio_missing = No audio output device was found, sound will be disabled.
+main-setup-test_sound = Play test sound
+main-setup-scheme-default = Default (WASD)
+main-setup-scheme-alternate = Alternate (ESDF)
+main-setup-scheme-left_handed = Left-handed (IJKL)
+main-setup-scheme-controller_hybrid = Controller hybrid (movement on the gamepad)
+main-setup-next = Next
+main-setup-finish = Finish
+main-setup-skip = Skip
 loading-tips =
     .a0 = Press '{ $gameinput-togglelantern }' to light your lantern.
     .a1 = Press '{ $gameinput-help }' to see all default keybindings.
@@ -68,6 +68,10 @@ npc-speech-villager_decline_trade =
     .a0 = Sorry, I don't have anything to trade.
     .a1 = Trade? Like I got anything that may interest you.
     .a2 = My house is mine, I won't trade it for anything.
+npc-speech-villager_no_work =
+    .a0 = I don't have anything for you to do, sorry.
+    .a1 = Work? Ask around in town, maybe someone needs a hand.
+    .a2 = Nothing to do here, come back another day.
 npc-speech-villager_busy =
     .a0 = Sorry, I can't speak with you right now.
     .a1 = We'll talk later, I'm busy.
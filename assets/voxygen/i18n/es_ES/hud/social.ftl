@@ -1,5 +1,4 @@
 hud-social = Otros jugadores
-hud-social-online = Conectados:
 hud-social-friends = Amigos
 hud-social-not_yet_available = No disponible todavía
 hud-social-faction = Facción
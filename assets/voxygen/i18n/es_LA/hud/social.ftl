@@ -1,5 +1,4 @@
 hud-social = Lista de jugadores
-hud-social-online = En Línea
 hud-social-friends = Amigos
 hud-social-not_yet_available = Aún no esta disponible
 hud-social-faction = Facción
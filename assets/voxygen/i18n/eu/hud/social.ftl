@@ -1,5 +1,4 @@
 hud-social = Jokalari gehiago
-hud-social-online = Online:
 hud-social-friends = Lagunak
 hud-social-not_yet_available = Oraindik ez dago erabilgarri
 hud-social-faction = Taldea
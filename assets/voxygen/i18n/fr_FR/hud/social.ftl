@@ -1,9 +1,11 @@
 hud-social = Social
-hud-social-online = En ligne
 hud-social-friends = Amis
 hud-social-not_yet_available = Pas encore disponible
 hud-social-faction = Faction
-hud-social-play_online_fmt = { $nb_player } joueur(s) en ligne
+hud-social-play_online_fmt = { $nb_player ->
+    [one] { $nb_player } joueur en ligne
+   *[other] { $nb_player } joueurs en ligne
+}
 hud-social-name = Nom
 hud-social-level = Niveau
 hud-social-zone = Zone
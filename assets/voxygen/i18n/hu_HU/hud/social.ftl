@@ -1,5 +1,4 @@
 hud-social = Más játékosok
-hud-social-online = Online:
 hud-social-friends = Barátok
 hud-social-not_yet_available = Még nem elérhető
 hud-social-faction = Frakció
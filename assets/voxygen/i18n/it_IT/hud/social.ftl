@@ -1,5 +1,4 @@
 hud-social = Altri Giocatori
-hud-social-online = Online:
 hud-social-friends = Amici
 hud-social-not_yet_available = Non ancora disponibile
 hud-social-faction = Fazione
@@ -1,5 +1,4 @@
 hud-social = ほかのプレーヤー
-hud-social-online = オンライン:
 hud-social-friends = 友達
 hud-social-not_yet_available = 利用不可
 hud-social-faction = 陣営
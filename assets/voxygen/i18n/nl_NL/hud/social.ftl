@@ -1,5 +1,4 @@
 hud-social = Andere spelers
-hud-social-online = Online:
 hud-social-friends = Vrienden
 hud-social-not_yet_available = Nog niet beschikbaar
 hud-social-faction = Factie
@@ -1,5 +1,4 @@
 hud-social = Andre spillere
-hud-social-online = Tilstede:
 hud-social-friends = Venner
 hud-social-not_yet_available = Ikke tilgjengelig enda
 hud-social-faction = Fraksjon
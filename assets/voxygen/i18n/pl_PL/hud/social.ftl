@@ -1,5 +1,4 @@
 hud-social = Inni gracze
-hud-social-online = Zalogowani:
 hud-social-friends = Przyjaciele
 hud-social-not_yet_available = Jeszcze niedostępne
 hud-social-faction = Frakcja
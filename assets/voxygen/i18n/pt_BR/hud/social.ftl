@@ -1,5 +1,4 @@
 hud-social = Outros Jogadores
-hud-social-online = Online:
 hud-social-friends = Amigos
 hud-social-not_yet_available = Indisponível
 hud-social-faction = Facção
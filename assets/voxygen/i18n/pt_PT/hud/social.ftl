@@ -1,5 +1,4 @@
 hud-social = Social
-hud-social-online = Online
 hud-social-friends = Amigos
 hud-social-not_yet_available = Indisponível de momento
 hud-social-faction = Facção
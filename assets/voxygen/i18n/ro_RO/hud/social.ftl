@@ -1,5 +1,4 @@
 hud-social = Alți jucători
-hud-social-online = Conectați:
 hud-social-friends = Prieteni
 hud-social-not_yet_available = Nu este valabil încă
 hud-social-faction = Facțiune
@@ -1,5 +1,4 @@
 hud-social = Другие игроки
-hud-social-online = В сети:
 hud-social-friends = Друзья
 hud-social-not_yet_available = Пока недоступно
 hud-social-faction = Фракция
@@ -1,5 +1,4 @@
 hud-social = Остали Играчи
-hud-social-online = На-мрежи:
 hud-social-friends = Пријатељи
 hud-social-not_yet_available = Није још Доступно
 hud-social-faction = Фракција
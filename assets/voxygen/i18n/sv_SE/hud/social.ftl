@@ -1,5 +1,4 @@
 hud-social = Andra spelare
-hud-social-online = Inloggade:
 hud-social-friends = Vänner
 hud-social-not_yet_available = Inte tillgängligt än
 hud-social-faction = Fraktion
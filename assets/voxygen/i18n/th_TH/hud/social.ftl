@@ -1,5 +1,4 @@
 hud-social = ผู้เล่นอื่น
-hud-social-online = กำลังออนไลน์:
 hud-social-friends = เพื่อน
 hud-social-not_yet_available = ยังไม่สามารถใช้การได้
 hud-social-faction = ฝ่าย
@@ -1,5 +1,4 @@
 hud-social = Diğer Oyuncular
-hud-social-online = Çevrimiçi:
 hud-social-friends = Arkadaşlar
 hud-social-not_yet_available = Şu anda kullanılabilir değil
 hud-social-faction = Klan
@@ -1,5 +1,4 @@
 hud-social = Người Chơi Khác
-hud-social-online = Trực tuyến:
 hud-social-friends = Bạn bè
 hud-social-not_yet_available = Chưa khả dụng
 hud-social-play_online_fmt = { $nb_player } người chơi trực tuyến
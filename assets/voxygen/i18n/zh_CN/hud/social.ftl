@@ -1,5 +1,4 @@
 hud-social = 社交
-hud-social-online = 在线:
 hud-social-friends = 好友
 hud-social-not_yet_available = 尚不可用
 hud-social-faction = 帮派
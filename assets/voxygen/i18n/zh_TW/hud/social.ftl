@@ -1,5 +1,4 @@
 hud-social = 社交
-hud-social-online = 線上
 hud-social-friends = 朋友
 hud-social-not_yet_available = 尚未開放
 hud-social-faction = 陣營
@@ -60,6 +60,105 @@ vec3 wpos_at(vec2 uv) {
     }
 }
 
+#ifdef AO_SAMPLES
+// Radius, in blocks, of the geometry occluding a pixel
+const float AO_RADIUS = 1.5;
+const float AO_STRENGTH = 0.8;
+
+// Screen-space ambient occlusion: the geometry found around a pixel in the
+// depth buffer darkens it as much as it hides the surroundings from it, so
+// that creases, corners and interiors don't look flat
+float ambient_occlusion(vec2 uv, vec3 wpos, float dist) {
+    vec2 texel = 1.0 / screen_res.xy;
+    vec3 norm = normalize(cross(wpos_at(uv + vec2(texel.x, 0)) - wpos, wpos_at(uv + vec2(0, texel.y)) - wpos));
+    // The normal faces the camera
+    norm *= sign(dot(norm, cam_pos.xyz - wpos));
+
+    // Size of the radius on the screen, in uv units
+    float uv_radius = AO_RADIUS * proj_mat[1][1] * 0.5 / max(dist, 1.0);
+    // Each pixel starts the spiral of samples at a different angle, which turns
+    // the banding into noise
+    float angle = 6.2831853 * fract(sin(dot(gl_FragCoord.xy, vec2(12.9898, 78.233))) * 43758.5453);
+    float occlusion = 0.0;
+    for (int i = 0; i < AO_SAMPLES; i ++) {
+        angle += 2.3999632; // Golden angle
+        float frac = (float(i) + 0.5) / float(AO_SAMPLES);
+        vec3 diff = wpos_at(uv + vec2(cos(angle), sin(angle)) * frac * uv_radius) - wpos;
+        float diff_len = max(length(diff), 0.001);
+        // Geometry well in front of the pixel, like a hill seen over a valley, doesn't occlude it
+        occlusion += max(dot(norm, diff) / diff_len - 0.1, 0.0) * smoothstep(AO_RADIUS * 2.0, AO_RADIUS, diff_len);
+    }
+    return 1.0 - occlusion / float(AO_SAMPLES) * AO_STRENGTH;
+}
+#endif
+
+#ifdef FOG_STEPS
+// Extinction of the fog, per block, down on the ground in dry weather
+const float FOG_DENSITY = 0.0008;
+// Height, in blocks, over which the fog thins out by a factor of e
+const float FOG_HEIGHT = 40.0;
+// Fog further away than this is left to the clouds
+const float FOG_MAX_DIST = 2000.0;
+const float GOD_RAY_STRENGTH = 0.6;
+
+// Fog settles in the valleys and thickens with the moisture of the clouds and
+// the rain of the region
+float fog_density_at(vec3 wpos) {
+    float above_ground = max(wpos.z - alt_at(wpos.xy), 0.0);
+    vec4 weather = sample_weather(wpos.xy + focus_off.xy);
+    float moisture = 1.0 + weather.r * 3.0 + weather.g * 6.0;
+    return FOG_DENSITY * moisture * exp(-above_ground / FOG_HEIGHT);
+}
+
+// Fraction of the way from the pixel to the sun, on the screen, that is open
+// sky in the depth buffer. Between terrain and trees this gives the shafts of
+// crepuscular rays
+float sun_shafts(vec2 uv, float jitter) {
+    // The sun is at infinity, in the opposite direction to its light
+    vec4 sun_clip = all_mat * vec4(-sun_dir.xyz, 0.0);
+    if (sun_clip.w <= 0.0) {
+        return 0.0;
+    }
+    vec2 sun_uv = sun_clip.xy / sun_clip.w * vec2(0.5, -0.5) + 0.5;
+
+    float open_sky = 0.0;
+    for (int i = 0; i < FOG_STEPS; i ++) {
+        vec2 sample_uv = mix(uv, sun_uv, (float(i) + jitter) / float(FOG_STEPS));
+        if (any(lessThan(sample_uv, vec2(0))) || any(greaterThan(sample_uv, vec2(1)))) {
+            break;
+        }
+        open_sky += float(textureLod(sampler2D(t_src_depth, s_src_depth), sample_uv, 0).x == 0.0);
+    }
+    return open_sky / float(FOG_STEPS);
+}
+
+// Ray-marches the fog between the camera and the pixel. The fog scatters the
+// light of the sky, and the sunlight where it isn't blocked, mostly forwards,
+// so the rays stand out when looking towards a low sun at dawn and dusk
+vec3 apply_volumetric_fog(vec3 color, vec2 uv, vec3 dir, float dist) {
+    // Each pixel starts marching at a different offset, which turns the banding
+    // into noise
+    float jitter = fract(sin(dot(gl_FragCoord.xy, vec2(12.9898, 78.233))) * 43758.5453);
+
+    float step_len = min(dist, FOG_MAX_DIST) / float(FOG_STEPS);
+    float optical_depth = 0.0;
+    for (int i = 0; i < FOG_STEPS; i ++) {
+        vec3 pos = cam_pos.xyz + dir * step_len * (float(i) + jitter);
+        optical_depth += fog_density_at(pos) * step_len;
+    }
+    float fog = 1.0 - exp(-optical_depth);
+
+    float sun_up = max(-sun_dir.z, 0.0);
+    float forward_scatter = pow(max(dot(dir, -sun_dir.xyz), 0.0), 8.0);
+    // The sun is lowest, and its rays the longest, at dawn and dusk
+    float low_sun = sun_up > 0.0 ? 1.0 - sun_up : 0.0;
+    vec3 sunlight = get_sun_color() * get_sun_brightness() * sun_shafts(uv, jitter) * forward_scatter;
+
+    vec3 fog_color = get_sky_color() * 0.8 + get_moon_color() * get_moon_brightness() * 0.1 + sunlight;
+    return mix(color, fog_color, fog) + sunlight * low_sun * GOD_RAY_STRENGTH * (0.2 + fog);
+}
+#endif
+
 void main() {
     vec4 color = texture(sampler2D(t_src_color, s_src_color), uv);
 
@@ -72,6 +171,13 @@ void main() {
     float dist = distance(wpos, cam_pos.xyz);
     vec3 dir = (wpos - cam_pos.xyz) / dist;
 
+    #ifdef AO_SAMPLES
+        // The sky isn't occluded
+        if (color.a >= 1.0 && dist < DIST_CAP) {
+            color.rgb *= ambient_occlusion(uv, wpos, dist);
+        }
+    #endif
+
     // Apply clouds
     float cloud_blend = 1.0;
     if (color.a < 1.0) {
@@ -80,10 +186,18 @@ void main() {
     }
     color.rgb = mix(color.rgb, get_cloud_color(color.rgb, dir, cam_pos.xyz, time_of_day.x, dist, 1.0), cloud_blend);
 
+    #ifdef FOG_STEPS
+        if (medium.x == MEDIUM_AIR) {
+            color.rgb = apply_volumetric_fog(color.rgb, uv, dir, dist);
+        }
+    #endif
+
     #if (CLOUD_MODE == CLOUD_MODE_NONE)
         color.rgb = apply_point_glow(cam_pos.xyz + focus_off.xyz, dir, dist, color.rgb);
     #else
-        if (medium.x == MEDIUM_AIR && rain_density > 0.001) {
+        float fall_density = max(rain_density, snow_density);
+        bool is_snow = snow_density > rain_density;
+        if (medium.x == MEDIUM_AIR && fall_density > 0.001) {
             vec3 cam_wpos = cam_pos.xyz + focus_off.xyz;
 
             vec3 adjusted_dir = (vec4(dir, 0) * rain_dir_mat).xyz;
@@ -109,7 +223,8 @@ void main() {
 
                 vec2 diff = abs(round(rpos.xy) - rpos.xy);
                 vec3 wall_pos = vec3((diff.x > diff.y) ? rpos.xy : rpos.yx, rpos.z + integrated_rain_vel);
-                wall_pos.xz *= vec2(4, 0.3);
+                // Snowflakes are round, raindrops are streaks
+                wall_pos.xz *= is_snow ? vec2(4, 4) : vec2(4, 0.3);
                 wall_pos.z += hash_two(uvec2(wall_pos.xy + vec2(0, 0.5)));
 
                 float depth_adjust = fract(hash_two(uvec2(wall_pos.xz) + 500u));
@@ -118,12 +233,13 @@ void main() {
 
                 if (wpos_dist > dist) { break; }
                 if (length((fract(wall_pos.xz) - 0.5)) < 0.1 + pow(max(0.0, wpos_dist - (dist - 0.25)) / 0.25, 4.0) * 0.2) {
-                    float density = rain_density * rain_occlusion_at(wpos);
+                    float density = fall_density * rain_occlusion_at(wpos);
                     if (fract(hash_two(uvec2(wall_pos.xz) + 1000u)) >= density) { continue; }
 
                     float alpha = 0.5 * clamp((wpos_dist - 1.0) * 0.5, 0.0, 1.0);
                     float light = dot(color.rgb, vec3(1)) + 0.05 + (get_sun_brightness() + get_moon_brightness()) * 0.2;
-                    color.rgb = mix(color.rgb, vec3(0.3, 0.35, 0.5) * light, alpha);
+                    vec3 drop_color = is_snow ? vec3(0.9, 0.92, 1.0) : vec3(0.3, 0.35, 0.5);
+                    color.rgb = mix(color.rgb, drop_color * light, alpha);
                 }
             }
         }
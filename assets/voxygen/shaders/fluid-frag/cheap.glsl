@@ -23,6 +23,7 @@
 
 layout(location = 0) in vec3 f_pos;
 layout(location = 1) flat in uint f_pos_norm;
+layout(location = 2) flat in vec2 f_flow;
 // in vec3 f_col;
 // in float f_light;
 // in vec3 light_pos[2];
@@ -25,6 +25,7 @@
 
 layout(location = 0) in vec3 f_pos;
 layout(location = 1) flat in uint f_pos_norm;
+layout(location = 2) flat in vec2 f_flow;
 // in vec3 f_col;
 // in float f_light;
 // in vec3 light_pos[2];
@@ -52,6 +53,59 @@ layout(location = 0) out vec4 tgt_color;
 #include <light.glsl>
 #include <lod.glsl>
 
+#ifdef SCREEN_SPACE_REFLECTIONS
+// Copies of the opaque scene, taken before the fluids are drawn
+layout(set = 3, binding = 0)
+uniform texture2D t_opaque_color;
+layout(set = 3, binding = 1)
+uniform sampler s_opaque_color;
+
+layout(set = 3, binding = 2)
+uniform texture2D t_opaque_depth;
+layout(set = 3, binding = 3)
+uniform sampler s_opaque_depth;
+
+const int SSR_STEPS = 32;
+// Furthest the reflected terrain is looked for, in blocks
+const float SSR_MAX_DIST = 128.0;
+
+// Screen-space reflections: the reflected ray is marched across the screen
+// until it goes behind the opaque scene, whose colour is then reflected. The
+// alpha is how much of the reflection was found on the screen, the sky being
+// reflected instead when the ray leaves the screen.
+vec4 screen_space_reflection(vec3 origin, vec3 dir) {
+    float step_len = SSR_MAX_DIST / float(SSR_STEPS);
+    // Each pixel starts the march at a different offset, which turns the
+    // banding into noise
+    float dist = step_len * hash(fract(vec4(floor(gl_FragCoord.xy), tick.x, 0) * 0.01));
+    for (int i = 0; i < SSR_STEPS; i ++) {
+        dist += step_len;
+        vec4 clip = all_mat * vec4(origin + dir * dist, 1);
+        // The ray went behind the camera
+        if (clip.w <= 0.0) {
+            break;
+        }
+        vec3 ndc = clip.xyz / clip.w;
+        vec2 uv = ndc.xy * vec2(0.5, -0.5) + 0.5;
+        if (any(lessThan(uv, vec2(0))) || any(greaterThan(uv, vec2(1)))) {
+            break;
+        }
+        // The depth is reversed, the ray is behind the scene once it is lower.
+        // The sky is at 0 and is never hit.
+        if (ndc.z < texture(sampler2D(t_opaque_depth, s_opaque_depth), uv).x) {
+            vec3 color = texture(sampler2D(t_opaque_color, s_opaque_color), uv).rgb;
+            // Fade out towards the edges of the screen and the end of the march,
+            // so that the reflections don't pop in and out
+            vec2 edge = min(uv, 1.0 - uv);
+            float fade = clamp(min(edge.x, edge.y) * 10.0, 0.0, 1.0)
+                * (1.0 - float(i) / float(SSR_STEPS));
+            return vec4(color, fade);
+        }
+    }
+    return vec4(0);
+}
+#endif
+
 vec3 warp_normal(vec3 norm, vec3 pos, float time) {
     return normalize(norm
         + smooth_rand(pos * 1.0, time * 1.0) * 0.05
@@ -132,7 +186,8 @@ void main() {
     }
     vec3 c_norm = cross(f_norm, b_norm);
 
-    vec3 wave_pos = mod(f_pos + focus_off.xyz, vec3(100.0));
+    // Waves drift along with the current of flowing water
+    vec3 wave_pos = mod(f_pos + focus_off.xyz - vec3(f_flow * tick.x * 2.0, 0), vec3(100.0));
     float wave_sample_dist = 0.025;
     float wave00 = wave_height(wave_pos);
     float wave10 = wave_height(wave_pos + vec3(wave_sample_dist, 0, 0));
@@ -220,6 +275,14 @@ void main() {
     // Prevent the sky affecting light when underground
     float not_underground = clamp((f_pos.z - f_alt) / 32.0 + 1.0, 0.0, 1.0);
     reflect_color *= not_underground;
+
+#ifdef SCREEN_SPACE_REFLECTIONS
+    // Underwater, the light is refracted through the surface instead
+    if (medium.x != MEDIUM_WATER) {
+        vec4 ssr = screen_space_reflection(f_pos, reflect_ray_dir);
+        reflect_color = mix(reflect_color, ssr.rgb, ssr.a);
+    }
+#endif
     // /*const */vec3 water_color = srgb_to_linear(vec3(0.2, 0.5, 1.0));
     // /*const */vec3 water_color = srgb_to_linear(vec3(0.8, 0.9, 1.0));
     // NOTE: Linear RGB, attenuation coefficients for water at roughly R, G, B wavelengths.
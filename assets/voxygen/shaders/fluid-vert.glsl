@@ -21,6 +21,8 @@
 #include <random.glsl>
 
 layout(location = 0) in uint v_pos_norm;
+// 0 for still fluids, 1 more than the direction of the flow otherwise
+layout(location = 1) in uint v_flow;
 // in uint v_col_light;
 
 layout(std140, set = 2, binding = 0)
@@ -42,12 +44,16 @@ uniform u_locals {
 
 layout(location = 0) out vec3 f_pos;
 layout(location = 1) flat out uint f_pos_norm;
+layout(location = 2) flat out vec2 f_flow;
 // out vec3 f_col;
 // out float f_light;
 // out vec3 light_pos[2];
 
 const float EXTRA_NEG_Z = 65536.0/*65536.1*/;
 
+// Horizontal direction of each flow, falling fluids only flowing down
+const vec2 FLOW_DIRS[6] = vec2[](vec2(0), vec2(1, 0), vec2(-1, 0), vec2(0, 1), vec2(0, -1), vec2(0));
+
 void main() {
     f_pos = vec3(v_pos_norm & 0x3Fu, (v_pos_norm >> 6) & 0x3Fu, float((v_pos_norm >> 12) & 0x1FFFFu) - EXTRA_NEG_Z) + model_offs - focus_off.xyz;
 
@@ -89,6 +95,7 @@ void main() {
     } */
 
     f_pos_norm = v_pos_norm;
+    f_flow = FLOW_DIRS[min(v_flow, 5u)];
 
     gl_Position =
         all_mat *
@@ -0,0 +1,13 @@
+#version 420 core
+
+#include <globals.glsl>
+
+layout (location = 0)
+in vec4 f_color;
+
+layout (location = 0)
+out vec4 tgt_color;
+
+void main() {
+    tgt_color = f_color;
+}
@@ -0,0 +1,28 @@
+#version 420 core
+
+#include <globals.glsl>
+
+layout (location = 0)
+in vec3 v_pos;
+layout (location = 1)
+in vec3 v_norm;
+layout (location = 2)
+in vec3 v_col;
+
+layout (std140, set = 1, binding = 0)
+uniform u_locals {
+    vec4 w_pos;
+    vec4 w_tint;
+};
+
+layout (location = 0)
+out vec4 f_color;
+
+void main() {
+    // Faces are shaded by the direction they face in rather than lit, the ghost
+    // not being part of the world yet
+    float shade = 0.6 + 0.25 * v_norm.z + 0.15 * abs(v_norm.x);
+    f_color = vec4(v_col * w_tint.rgb * shade, w_tint.a);
+
+    gl_Position = all_mat * vec4(v_pos + w_pos.xyz - focus_off.xyz, 1);
+}
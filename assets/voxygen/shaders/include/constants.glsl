@@ -19,11 +19,16 @@
 #define LIGHTING_ALGORITHM_LAMBERTIAN 0
 #define LIGHTING_ALGORITHM_BLINN_PHONG 1
 #define LIGHTING_ALGORITHM_ASHIKHMIN 2
+#define LIGHTING_ALGORITHM_FLAT 3
 
 #define SHADOW_MODE_NONE 0
 #define SHADOW_MODE_CHEAP 1
 #define SHADOW_MODE_MAP 2
 
+#define TONEMAP_EXPONENTIAL 0
+#define TONEMAP_REINHARD 1
+#define TONEMAP_ACES 2
+
 /* Unlike the other flags (for now anyway), these are bitmask values */
 #define LIGHTING_TYPE_REFLECTION 0x01
 #define LIGHTING_TYPE_TRANSMISSION 0x02
@@ -59,6 +64,7 @@
 #define CLOUD_MODE <mode>
 #define LIGHTING_ALGORITHM <algorithm>
 #define SHADOW_MODE <mode>
+#define TONEMAP <operator>
 */
 
 /* Constants possibly defined automatically by configuration: */
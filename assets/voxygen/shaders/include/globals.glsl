@@ -20,12 +20,14 @@ layout(std140, set = 0, binding = 0) uniform u_globals {
     ivec4 select_pos;
     vec4 gamma_exposure;
     vec4 last_lightning;
+    vec4 wind_vel;
     float ambiance;
     // 0 - FirstPerson
     // 1 - ThirdPerson
     uint cam_mode;
     float sprite_render_distance;
-    float globals_dummy; // Fix alignment.
+    // Share of the decorative sprites which are rendered
+    float sprite_density;
 };
 
 // Specifies the pattern used in the player dithering
@@ -19,9 +19,15 @@ uniform samplerShadow s_directed_shadow_maps;
 
 // uniform samplerCubeArrayShadow t_shadow_maps;
 // uniform samplerCubeArray t_shadow_maps;
-// Use with samplerCubeShadow
+#ifdef POINT_SHADOW_CUBE_ARRAY
+// Use with samplerCubeArrayShadow, one cube per point light casting shadows
+layout(set = 1, binding = 0)
+uniform textureCubeArray t_point_shadow_maps;
+#else
+// Use with samplerCubeShadow, only the first point light casts shadows
 layout(set = 1, binding = 0)
 uniform textureCube t_point_shadow_maps;
+#endif
 layout(set = 1, binding = 1)
 uniform samplerShadow s_point_shadow_maps;
 // uniform samplerCube t_shadow_maps;
@@ -68,7 +74,8 @@ const vec3 sampleOffsetDirections[20] = vec3[]
 
 float ShadowCalculationPoint(uint lightIndex, vec3 fragToLight, vec3 fragNorm, /*float currentDepth*/vec3 fragPos)
 {
-    if (lightIndex != 0u) {
+    // Only the first lights cast shadows, the strongest ones are sorted first
+    if (lightIndex >= uint(POINT_SHADOW_COUNT)) {
         return 1.0;
     };
 
@@ -77,7 +84,11 @@ float ShadowCalculationPoint(uint lightIndex, vec3 fragToLight, vec3 fragNorm, /
 
         // currentDepth = -currentDepth * 0.5 + 0.5;
 
-        float visibility = textureGrad(samplerCubeShadow(t_point_shadow_maps, s_point_shadow_maps), vec4(fragToLight, currentDepth), vec3(0), vec3(0));// / (screen_res.w/* - screen_res.z*/)/*1.0 -bias*//*-(currentDepth - bias) / screen_res.w*//*-screen_res.w*/);
+#ifdef POINT_SHADOW_CUBE_ARRAY
+        float visibility = texture(samplerCubeArrayShadow(t_point_shadow_maps, s_point_shadow_maps), vec4(fragToLight, float(lightIndex)), currentDepth);
+#else
+        float visibility = textureGrad(samplerCubeShadow(t_point_shadow_maps, s_point_shadow_maps), vec4(fragToLight, currentDepth), vec3(0), vec3(0));
+#endif
         /* if (visibility == 1.0 || visibility == 0.0) {
             return visibility;
         } */
@@ -205,7 +216,19 @@ float ShadowCalculationDirected(in vec3 fragPos)//in vec4 /*light_pos[2]*/sun_po
     // orig_pos.y = n: warp_pos.y = 2*(1-f/n)*n/(f-n) + 1 = 2*(n-f)/(f-n) + 1 = 2 * -1 + 1 = -1, sun_pos.y = (-1 - -1) / 2 = 0
     // orig_pos.y = f: warp_pos.y = 2*(1-f/f)*n/(f-n) + 1 = 2*(1-1)*n/(f-n) + 1 = 2 * 0 * n/(f-n) + 1 = 1, sun_pos.y = (1 - -1) / 2 = 1
     //
+#ifdef SHADOW_SOFT
+    // Percentage-closer filtering over the neighbouring texels, to soften the edges of the shadows
+    vec2 texel = sun_pos.w / vec2(textureSize(sampler2DShadow(t_directed_shadow_maps, s_directed_shadow_maps), 0));
+    float visibility = 0.0;
+    for (int x = -1; x <= 1; x ++) {
+        for (int y = -1; y <= 1; y ++) {
+            visibility += textureProj(sampler2DShadow(t_directed_shadow_maps, s_directed_shadow_maps), sun_pos + vec4(vec2(x, y) * texel, 0.0, 0.0));
+        }
+    }
+    visibility /= 9.0;
+#else
     float visibility = textureProj(sampler2DShadow(t_directed_shadow_maps, s_directed_shadow_maps), sun_pos);
+#endif
     /* float visibilityLeft = textureProj(t_directed_shadow_maps, sun_shadow.texture_mat * vec4(fragPos + vec3(0.0, -diskRadius, 0.0), 1.0));
     float visibilityRight = textureProj(t_directed_shadow_maps, sun_shadow.texture_mat * vec4(fragPos + vec3(0.0, diskRadius, 0.0), 1.0)); */
     // float nearVisibility = textureProj(t_directed_shadow_maps + vec3(0.001, sun_pos));
@@ -125,6 +125,10 @@ float rain_density_at(vec2 wpos) {
     return sample_weather(wpos).g;
 }
 
+float snow_density_at(vec2 wpos) {
+    return sample_weather(wpos).b;
+}
+
 float cloud_shadow(vec3 pos, vec3 light_dir) {
     #if (CLOUD_MODE <= CLOUD_MODE_MINIMAL)
         return 1.0;
@@ -445,6 +445,15 @@ vec3 light_reflection_factor(vec3 norm, vec3 dir, vec3 light_dir, vec3 k_d, vec3
     //    return FresnelBlend_f(norm, dir, light_dir, k_d/* * max(dot(norm, -light_dir), 0.0)*/, k_s, alpha);
     //}
     #endif
+#elif (LIGHTING_ALGORITHM == LIGHTING_ALGORITHM_FLAT)
+    // Faces are either fully lit or not at all, whatever their angle to the light, so
+    // that each one has a single even colour
+    const float PI = 3.141592;
+    #if (LIGHTING_TYPE & LIGHTING_TYPE_TRANSMISSION) != 0
+    return k_d / PI;
+    #else
+    return k_d / PI * step(0.0, dot(norm, -light_dir));
+    #endif
 #endif
 }
 
@@ -76,6 +76,11 @@ const int ENERGY_BUFFING = 35;
 const int WEB_STRAND = 36;
 const int BLACK_SMOKE = 37;
 const int LIGHTNING = 38;
+const int HIT_SPARK = 40;
+const int LEVEL_UP = 41;
+const int FOOTSTEP_DUST = 42;
+const int ROPE = 43;
+const int WORLD_BORDER = 44;
 
 // meters per second squared (acceleration)
 const float earth_gravity = 9.807;
@@ -614,6 +619,63 @@ void main() {
                 identity()//spin_in_axis(perp_axis, asin(inst_dir.z / length(inst_dir)) + PI / 2.0)
             );
             break;
+        case HIT_SPARK:
+            f_reflect = 0.0;
+            attr = Attr(
+                linear_motion(
+                    vec3(0),
+                    normalize(vec3(rand0, rand1, rand2 + 0.5)) * (3.0 + rand3) + grav_vel(earth_gravity)
+                ),
+                vec3(0.6 * (1 - slow_start(0.2))),
+                vec4(4.0, 3.0 + rand4 * 0.5, 1.5, 1),
+                spin_in_axis(vec3(rand6, rand7, rand8), percent() * 10 + 3 * rand9)
+            );
+            break;
+        case LEVEL_UP:
+            f_reflect = 0.0;
+            float level_up_angle = rand0 * PI + lifetime * 3.0;
+            attr = Attr(
+                vec3(
+                    vec2(cos(level_up_angle), sin(level_up_angle)) * (0.8 + 0.2 * rand1),
+                    slow_end(0.5) * 2.5 + rand2 * 0.3
+                ),
+                vec3(0.8 * (1 - slow_start(0.5))),
+                vec4(3.0, 2.5, 0.5, 1),
+                spin_in_axis(vec3(rand6, rand7, rand8), lifetime * 5.0)
+            );
+            break;
+        case FOOTSTEP_DUST:
+            attr = Attr(
+                linear_motion(
+                    vec3(rand0 * 0.2, rand1 * 0.2, 0.1),
+                    vec3(rand2 * 0.6, rand3 * 0.6, 0.4 + rand4 * 0.2)
+                ),
+                vec3(0.3 + linear_scale(1.5)),
+                vec4(vec3(0.55, 0.45, 0.35), start_end(1.0, 0.0)),
+                spin_in_axis(vec3(rand6, rand7, rand8), rand9 * 3 + lifetime)
+            );
+            break;
+        case ROPE:
+            f_reflect = 0.0;
+            attr = Attr(
+                vec3(0),
+                vec3(0.5),
+                vec4(0.35, 0.25, 0.15, 1),
+                identity()
+            );
+            break;
+        case WORLD_BORDER:
+            f_reflect = 0.0;
+            attr = Attr(
+                linear_motion(
+                    vec3(rand0 * 0.5, rand1 * 0.5, 0.0),
+                    vec3(rand2 * 0.2, rand3 * 0.2, 1.5 + rand4 * 0.5)
+                ),
+                vec3(1.2 * start_end(1.0, 0.0)),
+                vec4(3.0, 0.6, 0.4, start_end(1.0, 0.0)),
+                spin_in_axis(vec3(rand6, rand7, rand8), lifetime * 2.0)
+            );
+            break;
         default:
             attr = Attr(
                 linear_motion(
@@ -34,6 +34,8 @@ layout (std140, set = 1, binding = 2)
 uniform u_locals {
     mat4 proj_mat_inv;
     mat4 view_mat_inv;
+    // x: distance in focus, y: aperture, the depth of field is off at zero
+    vec4 depth_of_field;
 };
 
 #ifdef BLOOM_FACTOR
@@ -41,6 +43,21 @@ layout(set = 1, binding = 3)
 uniform texture2D t_src_bloom;
 #endif
 
+layout(set = 1, binding = 4)
+uniform texture2D t_color_grading;
+
+// Levels of each channel in the colour grading lookup table, see `postprocess::LUT_SIZE`
+const float LUT_SIZE = 16.0;
+
+layout(set = 1, binding = 5)
+uniform texture2D t_src_depth;
+layout(set = 1, binding = 6)
+uniform sampler s_src_depth;
+
+// Largest radius of the blur of the depth of field, as a share of the screen height
+const float DOF_MAX_RADIUS = 0.02;
+const int DOF_SAMPLES = 24;
+
 layout(location = 0) out vec4 tgt_color;
 
 vec3 rgb2hsv(vec3 c) {
@@ -59,6 +76,63 @@ vec3 hsv2rgb(vec3 c) {
     return c.z * mix(K.xxx, clamp(p - K.xxx, 0.0, 1.0), c.y);
 }
 
+// Maps the exposed HDR color to the 0..1 range
+vec3 tonemap(vec3 color) {
+    #if (TONEMAP == TONEMAP_REINHARD)
+        // Scaling by the luminance rather than per channel keeps the hue of bright lights
+        return clamp(color / (1.0 + rel_luminance(color)), 0.0, 1.0);
+    #elif (TONEMAP == TONEMAP_ACES)
+        // From: https://knarkowicz.wordpress.com/2016/01/06/aces-filmic-tone-mapping-curve/
+        return clamp(color * (2.51 * color + 0.03) / (color * (2.43 * color + 0.59) + 0.14), 0.0, 1.0);
+    #else
+        return vec3(1.0) - exp(-color);
+    #endif
+}
+
+// Looks the colour up in the colour grading table, whose slices side by side
+// each hold a level of blue. The two nearest slices are blended together
+vec3 color_grade(vec3 color) {
+    vec3 texel = linear_to_srgb(clamp(color, 0.0, 1.0)) * (LUT_SIZE - 1.0);
+    float slice = min(floor(texel.b), LUT_SIZE - 2.0);
+    vec2 uv = vec2(
+        (slice * LUT_SIZE + texel.r + 0.5) / (LUT_SIZE * LUT_SIZE),
+        (texel.g + 0.5) / LUT_SIZE
+    );
+    vec3 lower = textureLod(sampler2D(t_color_grading, s_src_color), uv, 0).rgb;
+    vec3 upper = textureLod(sampler2D(t_color_grading, s_src_color), uv + vec2(1.0 / LUT_SIZE, 0.0), 0).rgb;
+    return mix(lower, upper, texel.b - slice);
+}
+
+// Distance from the camera to what is seen at `uv`
+float view_dist_at(vec2 uv) {
+    float buf_depth = textureLod(sampler2D(t_src_depth, s_src_depth), uv, 0).x;
+    // Nothing was drawn there, the sky is always out of focus
+    if (buf_depth == 0.0) {
+        return 100000.0;
+    }
+    vec4 view_space = proj_mat_inv * vec4((uv * 2.0 - 1.0) * vec2(1, -1), buf_depth, 1.0);
+    return length(view_space.xyz / view_space.w);
+}
+
+// Blurs the colour over a disc which grows with how far the pixel is from the
+// distance in focus, sampled along a spiral so that the samples cover it evenly
+vec4 apply_depth_of_field(vec4 color, vec2 uv) {
+    float dist = view_dist_at(uv);
+    float radius = min(abs(dist - depth_of_field.x) / max(dist, 0.1) * depth_of_field.y, 1.0) * DOF_MAX_RADIUS;
+    if (radius * screen_res.y < 0.5) {
+        return color;
+    }
+    vec2 aspect = vec2(screen_res.y / screen_res.x, 1.0);
+    vec4 sum = color;
+    for (int i = 0; i < DOF_SAMPLES; i++) {
+        float r = sqrt((float(i) + 0.5) / float(DOF_SAMPLES)) * radius;
+        // Golden angle
+        float angle = float(i) * 2.39996;
+        sum += textureLod(sampler2D(t_src_color, s_src_color), uv + vec2(cos(angle), sin(angle)) * r * aspect, 0);
+    }
+    return sum / float(DOF_SAMPLES + 1);
+}
+
 vec3 _illuminate(float max_light, vec3 view_dir, /*vec3 max_light, */vec3 emitted, vec3 reflected) {
     const float NIGHT_EXPOSURE = 10.0;
     const float DUSK_EXPOSURE = 2.0;//0.8;
@@ -211,6 +285,10 @@ void main() {
 
     vec4 aa_color = aa_apply(t_src_color, s_src_color, sample_uv * screen_res.xy, screen_res.xy);
 
+    if (depth_of_field.y > 0.0) {
+        aa_color = apply_depth_of_field(aa_color, sample_uv);
+    }
+
     #ifdef EXPERIMENTAL_SOBEL
         vec3 s[8];
         s[0] = aa_sample(uv, vec2(-1,  1));
@@ -241,9 +319,10 @@ void main() {
     float exposure_offset = 1.0;
     // Adding an in-code offset to gamma and exposure let us have more precise control over the game's look
     float gamma_offset = 0.3;
-    aa_color.rgb = vec3(1.0) - exp(-aa_color.rgb * (gamma_exposure.y + exposure_offset));
+    aa_color.rgb = tonemap(aa_color.rgb * (gamma_exposure.y + exposure_offset));
     // gamma correction
     aa_color.rgb = pow(aa_color.rgb, vec3(gamma_exposure.x + gamma_offset));
+    aa_color.rgb = color_grade(aa_color.rgb);
 
     /*
     // Apply clouds to `aa_color`
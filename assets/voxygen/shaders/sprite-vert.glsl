@@ -29,6 +29,7 @@ layout(location = 6) in float inst_light;
 layout(location = 7) in float inst_glow;
 layout(location = 8) in float model_wind_sway; // NOTE: this only varies per model
 layout(location = 9) in float model_z_scale; // NOTE: this only varies per model
+layout(location = 10) in float inst_density_rank;
 
 layout(set = 0, binding = 15) restrict readonly buffer sprite_verts {
     uvec2 verts[];
@@ -55,6 +56,12 @@ const float EXTRA_NEG_Z = 32768.0;
 const float VERT_EXTRA_NEG_Z = 128.0;
 const uint VERT_PAGE_SIZE = 256;
 
+// Distance over which sprites shrink into the ground before disappearing
+const float FADE_DIST = 24.0;
+// Chunks of sprites are culled by their centre, so the sprites at the edge of
+// the furthest chunks disappear this much closer than the render distance
+const float CULL_MARGIN = 4.0;
+
 void main() {
     // Matrix to transform this sprite instance from model space to chunk space
     mat4 inst_mat;
@@ -77,6 +84,16 @@ void main() {
     // Expand the model vertex position bits into float values
     vec3 v_pos = vec3(v_pos_norm & 0xFFu, (v_pos_norm >> 8) & 0xFFu, float((v_pos_norm >> 16) & 0x0FFFu) - VERT_EXTRA_NEG_Z);
 
+    #ifndef EXPERIMENTAL_BAREMINIMUM
+        // Distant sprites shrink into the ground instead of popping out of existence, the ones
+        // thinned out first by the density fading first too
+        float inst_dist = distance(focus_pos.xy, inst_mat[3].xy + chunk_offs.xy);
+        float fade_end = sprite_render_distance - CULL_MARGIN - inst_density_rank * FADE_DIST;
+        v_pos *= clamp((fade_end - inst_dist) / FADE_DIST, 0.0, 1.0);
+    #endif
+    // Sprites thinned out by the density collapse into a point, which isn't rasterized
+    v_pos *= step(inst_density_rank, sprite_density);
+
     // Transform into chunk space and scale
     f_pos = (inst_mat * vec4(v_pos, 1.0)).xyz;
     // Transform info world space
@@ -94,12 +111,14 @@ void main() {
     #endif
 
     #ifndef EXPERIMENTAL_BAREMINIMUM
-        // TODO: take wind_vel into account
-        // Wind sway effect
-        f_pos += model_wind_sway * vec3(
+        // Wind sway effect, sprites sway harder and lean with stronger winds
+        float wind_speed = min(length(wind_vel.xy), 30.0);
+        f_pos += model_wind_sway * (vec3(
             sin(tick.x * 1.5 + f_pos.y * 0.1) * sin(tick.x * 0.35),
             sin(tick.x * 1.5 + f_pos.x * 0.1) * sin(tick.x * 0.25),
             0.0
+            ) * (1.0 + wind_speed * 0.05)
+            + vec3(wind_vel.xy / max(length(wind_vel.xy), 1.0), 0.0) * wind_speed * 0.02
             // NOTE: could potentially replace `v_pos.z * model_z_scale` with a calculation using `inst_chunk_pos` from below
             //) * pow(abs(v_pos.z * model_z_scale), 1.3) * SCALE_FACTOR;
             ) * v_pos.z * model_z_scale * SCALE_FACTOR;
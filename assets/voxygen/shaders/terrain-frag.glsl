@@ -252,6 +252,11 @@ void main() {
                 const float puddle = 1.0;
             #endif
 
+            // Surfaces exposed to the rain look darker and shinier
+            float wetness = min(rain_density * 10.0, 1.0) * clamp((f_sky_exposure - 0.9) * 50.0, 0.0, 1.0);
+            f_col *= mix(1.0, 0.85, wetness);
+            k_s = mix(k_s, vec3(0.4), wetness * 0.5);
+
             #ifdef EXPERIMENTAL_WETNESS
                 if (puddle > 0.0) {
                     float h = (noise_2d((f_pos.xy + focus_off.xy) * 0.3) - 0.5) * sin(tick.x * 8.0 + f_pos.x * 3)
@@ -292,6 +297,14 @@ void main() {
                 }
             }
         }
+
+        if (snow_density > 0 && !faces_fluid && f_norm.z > 0.5) {
+            // Snow settles on the surfaces exposed to the sky
+            float settled = min(snow_density * 5.0, 1.0)
+                * clamp((f_sky_exposure - 0.9) * 50.0, 0.0, 1.0)
+                * rain_occlusion_at(f_pos.xyz + vec3(0, 0, 0.25));
+            f_col = mix(f_col, vec3(0.85, 0.9, 1.0), settled * 0.6);
+        }
     #endif
 
     // float sun_light = get_sun_brightness(sun_dir);
@@ -21,6 +21,7 @@
 #include <globals.glsl>
 
 layout(location = 0) in vec3 f_pos;
+layout(location = 1) in vec4 f_col;
 
 layout(location = 0) out vec4 tgt_color;
 
@@ -31,7 +32,6 @@ layout(location = 0) out vec4 tgt_color;
 const float FADE_DIST = 32.0;
 
 void main() {
-    vec3 trail_color = vec3(.55, .92, 1.0);
     float trail_alpha = 0.05;
     // Controls how much light affects alpha variation. TODO: Maybe a better name?
     float light_variable = 0.075;
@@ -40,5 +40,6 @@ void main() {
     // TODO: Trails should also eventually account for shadows, nearby lights, attenuation of sunlight in water, and block based lighting. Note: many of these will require alternative methods that don't require a normal.
     trail_alpha += get_sun_brightness() * light_variable;
 
-    tgt_color = vec4(trail_color, trail_alpha);
+    // The alpha of the trail colour is how much the trail has faded since it was left
+    tgt_color = vec4(f_col.rgb, trail_alpha * f_col.a);
 }
@@ -3,11 +3,14 @@
 #include <globals.glsl>
 
 layout(location = 0) in vec3 v_pos;
+layout(location = 1) in vec4 v_col;
 
 layout(location = 0) out vec3 f_pos;
+layout(location = 1) out vec4 f_col;
 
 void main() {
     f_pos = v_pos;
+    f_col = v_col;
 
     gl_Position = all_mat * vec4(f_pos - focus_off.xyz, 1);
 }
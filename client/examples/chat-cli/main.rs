@@ -1,19 +1,53 @@
 #![deny(unsafe_code)]
 #![deny(clippy::clone_on_ref_ptr)]
 
-use common::{clock::Clock, comp};
+mod table;
+mod terminal;
+
+use common::{
+    clock::Clock,
+    comp::{self, ChatType},
+};
 use std::{
-    io,
+    env, io,
+    process::exit,
     sync::{mpsc, Arc},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
+use structopt::StructOpt;
+use terminal::{ChatTui, Input};
 use tokio::runtime::Runtime;
-use tracing::{error, info};
+use tracing::info;
 use veloren_client::{addr::ConnectionArgs, Client, Event};
 
 const TPS: u64 = 10; // Low value is okay, just reading messages.
 
+/// How long `--exec` waits for the response to its command
+const EXEC_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(StructOpt)]
+struct Opt {
+    /// Address of the server, prompted for if not provided
+    #[structopt(short, long)]
+    server: Option<String>,
+    /// Username to log in with, prompted for if not provided
+    #[structopt(short, long)]
+    username: Option<String>,
+    /// Log in with the credentials in `VELOREN_ADMIN_USERNAME` and
+    /// `VELOREN_ADMIN_PASSWORD` and render command responses as tables
+    #[structopt(long)]
+    admin: bool,
+    /// Run a single command (e.g. "/players"), print its response and exit.
+    /// The exit code is non-zero if the command failed.
+    #[structopt(long)]
+    exec: Option<String>,
+    /// Read lines from stdin and print messages to stdout instead of using the
+    /// terminal interface
+    #[structopt(long)]
+    basic: bool,
+}
+
 fn read_input() -> String {
     let mut buffer = String::new();
 
@@ -24,23 +58,100 @@ fn read_input() -> String {
     buffer.trim().to_string()
 }
 
+/// Prompts go to stderr so that the output of `--exec` can be piped
+fn prompt(message: &str) -> String {
+    eprintln!("{}", message);
+    read_input()
+}
+
+/// Split a command line into the command name and its arguments, keeping
+/// quoted arguments together
+fn parse_command(line: &str) -> Option<(String, Vec<String>)> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in line.trim().strip_prefix('/')?.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    args.push(std::mem::take(&mut current));
+                }
+            },
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        args.push(current);
+    }
+    if args.is_empty() {
+        return None;
+    }
+    let name = args.remove(0);
+    Some((name, args))
+}
+
+fn send_input(client: &mut Client, input: String) {
+    match parse_command(&input) {
+        Some((name, args)) => client.send_command(name, args),
+        None => client.send_chat(input),
+    }
+}
+
+/// Where entered lines come from and where messages are shown
+enum Ui {
+    Basic(mpsc::Receiver<String>),
+    Terminal(ChatTui),
+}
+
+impl Ui {
+    fn output(&mut self, message: &str) {
+        match self {
+            Ui::Basic(_) => println!("{}", message),
+            Ui::Terminal(tui) => tui.push_message(message),
+        }
+    }
+}
+
 fn main() {
-    // Initialize logging.
-    common_frontend::init_stdout(None);
+    let opt = Opt::from_args();
+
+    // Keep stdout clean for the response when running a single command, and
+    // for the terminal interface
+    if opt.basic && opt.exec.is_none() {
+        common_frontend::init_stdout(None);
+    }
 
     info!("Starting chat-cli...");
 
     // Set up an fps clock.
     let mut clock = Clock::new(Duration::from_secs_f64(1.0 / TPS as f64));
 
-    println!("Enter your username");
-    let username = read_input();
-
-    println!("Enter the server address");
-    let server_addr = read_input();
+    let admin_credentials = (
+        env::var("VELOREN_ADMIN_USERNAME"),
+        env::var("VELOREN_ADMIN_PASSWORD"),
+    );
+    let (username, password) = match (opt.admin, admin_credentials) {
+        (true, (Ok(username), Ok(password))) => (username, password),
+        (true, _) => {
+            eprintln!(
+                "Admin mode requires VELOREN_ADMIN_USERNAME and VELOREN_ADMIN_PASSWORD to be set"
+            );
+            exit(2);
+        },
+        (false, _) => {
+            let username = opt
+                .username
+                .clone()
+                .unwrap_or_else(|| prompt("Enter your username"));
+            (username, prompt("Enter your password"))
+        },
+    };
 
-    println!("Enter your password");
-    let password = read_input();
+    let server_addr = opt
+        .server
+        .clone()
+        .unwrap_or_else(|| prompt("Enter the server address"));
 
     let runtime = Arc::new(Runtime::new().unwrap());
     let runtime2 = Arc::clone(&runtime);
@@ -54,9 +165,11 @@ fn main() {
         .block_on(Client::new(addr, runtime2, &mut None))
         .expect("Failed to create client instance");
 
-    println!("Server info: {:?}", client.server_info());
+    if opt.basic && opt.exec.is_none() {
+        println!("Server info: {:?}", client.server_info());
 
-    println!("Players online: {:?}", client.players().collect::<Vec<_>>());
+        println!("Players online: {:?}", client.players().collect::<Vec<_>>());
+    }
 
     runtime
         .block_on(client.register(username, password, |provider| {
@@ -64,31 +177,66 @@ fn main() {
         }))
         .unwrap();
 
-    let (tx, rx) = mpsc::channel();
-    thread::spawn(move || {
-        loop {
-            let msg = read_input();
-            tx.send(msg).unwrap();
-        }
-    });
+    if let Some(command) = opt.exec {
+        exit(exec(&mut client, &mut clock, command, opt.admin));
+    }
+
+    let mut ui = if opt.basic {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            loop {
+                let msg = read_input();
+                tx.send(msg).unwrap();
+            }
+        });
+        Ui::Basic(rx)
+    } else {
+        Ui::Terminal(ChatTui::new().expect("Failed to set up the terminal"))
+    };
 
-    loop {
-        for msg in rx.try_iter() {
-            client.send_chat(msg)
+    let mut checked_moderator = false;
+    let result = loop {
+        let inputs = match &mut ui {
+            Ui::Basic(rx) => rx.try_iter().map(Input::Line).collect(),
+            Ui::Terminal(tui) => match tui.poll_input(&client) {
+                Ok(inputs) => inputs,
+                Err(err) => break Err(format!("{:?}", err)),
+            },
+        };
+        let mut quit = false;
+        for input in inputs {
+            match input {
+                Input::Line(line) => send_input(&mut client, line),
+                Input::Quit => quit = true,
+            }
+        }
+        if quit {
+            break Ok(());
         }
 
         let events = match client.tick(comp::ControllerInputs::default(), clock.dt(), |_| {}) {
             Ok(events) => events,
-            Err(err) => {
-                error!("Error: {:?}", err);
-                break;
-            },
+            Err(err) => break Err(format!("{:?}", err)),
         };
 
+        // The player list is synced after registering, so the role can only be
+        // checked once we have ticked
+        if opt.admin && !checked_moderator && !client.player_list().is_empty() {
+            checked_moderator = true;
+            if !client.is_moderator() {
+                ui.output("Warning: logged in without moderator rights");
+            }
+        }
+
         const SHOW_NAME: bool = false;
         for event in events {
             match event {
-                Event::Chat(m) => println!("{}", client.format_message(&m, SHOW_NAME)),
+                Event::Chat(m) => match m.chat_type {
+                    ChatType::CommandInfo if opt.admin => {
+                        ui.output(&table::format_table(&m.message))
+                    },
+                    _ => ui.output(&client.format_message(&m, SHOW_NAME, false)),
+                },
                 Event::Disconnect => {}, // TODO
                 Event::DisconnectionNotification(time) => {
                     let message = match time {
@@ -96,16 +244,78 @@ fn main() {
                         _ => format!("Connection lost. Kicking in {} seconds", time),
                     };
 
-                    println!("{}", message)
+                    ui.output(&message)
+                },
+                Event::DamageTaken(info) => {
+                    ui.output(&format!("You took {:.0} damage", -info.amount))
                 },
+                Event::DamageReport(report) => ui.output(&format!(
+                    "Training dummy: {:.1} DPS over {:.0}s, {:.0} damage in {} hits",
+                    report.dps, report.window, report.total, report.hits
+                )),
                 _ => {},
             }
         }
 
+        if let Ui::Terminal(tui) = &mut ui {
+            if let Err(err) = tui.draw(&client) {
+                break Err(format!("{:?}", err));
+            }
+        }
+
         // Clean up the server after a tick.
         client.cleanup();
 
         // Wait for the next tick.
         clock.tick();
+    };
+
+    // Restore the terminal before reporting errors
+    drop(ui);
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        exit(1);
     }
 }
+
+/// Run a single command and print its response, returning the exit code
+fn exec(client: &mut Client, clock: &mut Clock, command: String, tables: bool) -> i32 {
+    send_input(client, command);
+
+    let start = Instant::now();
+    while start.elapsed() < EXEC_TIMEOUT {
+        let events = match client.tick(comp::ControllerInputs::default(), clock.dt(), |_| {}) {
+            Ok(events) => events,
+            Err(err) => {
+                eprintln!("Error: {:?}", err);
+                return 1;
+            },
+        };
+
+        for event in events {
+            if let Event::Chat(m) = event {
+                match m.chat_type {
+                    ChatType::CommandInfo => {
+                        if tables {
+                            println!("{}", table::format_table(&m.message));
+                        } else {
+                            println!("{}", m.message);
+                        }
+                        return 0;
+                    },
+                    ChatType::CommandError => {
+                        eprintln!("{}", m.message);
+                        return 1;
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        client.cleanup();
+        clock.tick();
+    }
+
+    eprintln!("No response to the command after {:?}", EXEC_TIMEOUT);
+    1
+}
@@ -0,0 +1,72 @@
+//! Rendering of structured command responses (player lists, metrics, ...)
+//! as aligned tables.
+
+/// Split a single response line into columns, understanding the `[alias]name`
+/// format used by player listings and the `key: value` format used by most
+/// informational commands.
+fn columns(line: &str) -> Vec<&str> {
+    let line = line.trim();
+    if let Some((alias, name)) = line.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+        vec![alias, name.trim()]
+    } else if line.contains('\t') {
+        line.split('\t').map(str::trim).collect()
+    } else if let Some((key, value)) = line.split_once(": ") {
+        vec![key, value.trim()]
+    } else {
+        vec![line]
+    }
+}
+
+/// Render a command response as a table. The first line is kept as a title
+/// if it doesn't split into columns. Responses that aren't tabular are
+/// returned unchanged.
+pub fn format_table(message: &str) -> String {
+    let mut lines = message.lines().filter(|line| !line.trim().is_empty());
+    let first = match lines.next() {
+        Some(first) => first,
+        None => return String::new(),
+    };
+    let (title, rows) = match columns(first) {
+        row if row.len() > 1 => (
+            None,
+            core::iter::once(row).chain(lines.map(columns)).collect(),
+        ),
+        _ => (Some(first.trim()), lines.map(columns).collect::<Vec<_>>()),
+    };
+    if rows.iter().all(|row| row.len() < 2) {
+        return message.to_owned();
+    }
+
+    let column_count = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths = (0..column_count)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let separator = widths
+        .iter()
+        .map(|width| "-".repeat(*width))
+        .collect::<Vec<_>>()
+        .join("-+-");
+
+    let mut table = String::new();
+    if let Some(title) = title {
+        table.push_str(title);
+        table.push('\n');
+    }
+    table.push_str(&separator);
+    for row in rows {
+        table.push('\n');
+        let cells = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>();
+        table.push_str(cells.join(" | ").trim_end());
+    }
+    table
+}
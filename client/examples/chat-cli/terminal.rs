@@ -0,0 +1,197 @@
+//! Terminal interface with a scrollable message pane, a player sidebar, a
+//! status bar and an input line that isn't clobbered by incoming messages,
+//! with tab completion of commands and player names.
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{
+    io::{self, Stdout},
+    time::Duration,
+};
+use tui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    text::Text,
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Terminal,
+};
+use veloren_client::{cmd, Client};
+
+/// Lines kept in the message pane, older ones are dropped
+const MAX_MESSAGES: usize = 1000;
+/// Lines scrolled by page up/down
+const SCROLL_STEP: u16 = 10;
+const SIDEBAR_WIDTH: u16 = 24;
+
+pub enum Input {
+    Line(String),
+    Quit,
+}
+
+pub struct ChatTui {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    messages: Vec<String>,
+    input: String,
+    /// Number of lines scrolled up from the newest message
+    scroll: u16,
+    /// Candidates for the word being completed, cycled through with tab
+    completions: Vec<String>,
+    completion_index: usize,
+    /// The input before the word being completed
+    completion_base: String,
+}
+
+impl ChatTui {
+    pub fn new() -> io::Result<Self> {
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        enable_raw_mode()?;
+
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+        terminal.clear()?;
+
+        Ok(Self {
+            terminal,
+            messages: Vec::new(),
+            input: String::new(),
+            scroll: 0,
+            completions: Vec::new(),
+            completion_index: 0,
+            completion_base: String::new(),
+        })
+    }
+
+    pub fn push_message(&mut self, message: &str) {
+        self.messages.extend(message.lines().map(str::to_owned));
+        let overflow = self.messages.len().saturating_sub(MAX_MESSAGES);
+        self.messages.drain(..overflow);
+    }
+
+    /// Replaces the last word of the input with the next completion, looking
+    /// the completions up when not cycling through them already
+    fn complete(&mut self, client: &Client) {
+        if self.completions.is_empty() {
+            self.completions = cmd::complete(&self.input, client, '/', &[]);
+            self.completion_index = 0;
+            let word_start = self.input.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+            self.completion_base = self.input[..word_start].to_string();
+        } else {
+            self.completion_index = (self.completion_index + 1) % self.completions.len();
+        }
+        if let Some(completion) = self.completions.get(self.completion_index) {
+            self.input = format!("{}{}", self.completion_base, completion.trim_start());
+        }
+    }
+
+    /// Handle pending key presses without blocking, returning entered lines
+    pub fn poll_input(&mut self, client: &Client) -> io::Result<Vec<Input>> {
+        let mut inputs = Vec::new();
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(key) = event::read()? {
+                if key.code == KeyCode::Tab {
+                    self.complete(client);
+                    continue;
+                }
+                // Any other key ends the completion
+                self.completions.clear();
+                match key.code {
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        inputs.push(Input::Quit)
+                    },
+                    KeyCode::Esc => inputs.push(Input::Quit),
+                    KeyCode::Char(c) => self.input.push(c),
+                    KeyCode::Backspace => {
+                        self.input.pop();
+                    },
+                    KeyCode::Enter if !self.input.is_empty() => {
+                        inputs.push(Input::Line(std::mem::take(&mut self.input)));
+                        // Jump back to the newest messages after sending
+                        self.scroll = 0;
+                    },
+                    KeyCode::PageUp => self.scroll = self.scroll.saturating_add(SCROLL_STEP),
+                    KeyCode::PageDown => self.scroll = self.scroll.saturating_sub(SCROLL_STEP),
+                    _ => {},
+                }
+            }
+        }
+        Ok(inputs)
+    }
+
+    pub fn draw(&mut self, client: &Client) -> io::Result<()> {
+        let Self {
+            terminal,
+            messages,
+            input,
+            scroll,
+            ..
+        } = self;
+        let scroll = *scroll;
+
+        let hint = cmd::argument_hint(input, '/', &[]);
+        let mut players = client.players().map(str::to_owned).collect::<Vec<_>>();
+        players.sort_unstable();
+        let status = format!(
+            " {} | ping {:.0} ms | {} online | Tab complete, PgUp/PgDn scroll, Esc quit",
+            client.server_info().name,
+            client.get_ping_ms_rolling_avg(),
+            players.len(),
+        );
+
+        terminal.draw(|f| {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(3),
+                    Constraint::Length(3),
+                    Constraint::Length(1),
+                ])
+                .split(f.size());
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(10), Constraint::Length(SIDEBAR_WIDTH)])
+                .split(rows[0]);
+
+            let wrap = Wrap {
+                scroll_callback: Some(Box::new(move |text_area, lines| {
+                    let len = lines.len() as u16;
+                    let bottom = len.saturating_sub(text_area.height);
+                    (bottom.saturating_sub(scroll), 0)
+                })),
+                ..Default::default()
+            };
+            let message_pane = Paragraph::new(Text::raw(messages.join("\n")))
+                .block(Block::default().borders(Borders::ALL).title("Chat"))
+                .wrap(wrap);
+            f.render_widget(message_pane, columns[0]);
+
+            let player_list = List::new(players.into_iter().map(ListItem::new).collect::<Vec<_>>())
+                .block(Block::default().borders(Borders::ALL).title("Players"));
+            f.render_widget(player_list, columns[1]);
+
+            // The usage of the command being typed
+            let block = match &hint {
+                Some(hint) => Block::default().borders(Borders::ALL).title(hint.as_str()),
+                None => Block::default().borders(Borders::ALL),
+            };
+            let input_area = block.inner(rows[1]);
+            let text: Text = input.as_str().into();
+            let cursor_x = (input_area.x + text.width() as u16)
+                .min(input_area.x + input_area.width.saturating_sub(1));
+            f.render_widget(Paragraph::new(text).block(block), rows[1]);
+            f.set_cursor(cursor_x, input_area.y);
+
+            f.render_widget(Paragraph::new(status.as_str()), rows[2]);
+        })?;
+        Ok(())
+    }
+}
+
+impl Drop for ChatTui {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
@@ -167,6 +167,7 @@ impl BotClient {
                 cred.username.clone(),
                 cred.password.clone(),
                 |_| true,
+                |_| {},
             )) {
                 warn!("error logging in {:?}: {:?}", cred.username, e);
             }
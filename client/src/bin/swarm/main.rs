@@ -115,7 +115,7 @@ fn run_client(
     // Login
     // NOTE: use a no-auth server
     runtime
-        .block_on(client.register(username.clone(), String::new(), |_| false))
+        .block_on(client.register(username.clone(), String::new(), |_| false, |_| {}))
         .expect("Failed to log in");
 
     let mut clock = common::clock::Clock::new(Duration::from_secs_f32(1.0 / 30.0));
@@ -0,0 +1,283 @@
+//! Tab completion and argument hints for chat commands, shared by the
+//! frontends. The server commands are known to every client, commands
+//! handled by the frontend itself are passed in as [`LocalCommand`]s.
+
+use crate::Client;
+use common::cmd::{ArgumentSpec, ChatCommandData, ServerChatCommand};
+use common_net::msg::world_msg::SiteKind;
+
+/// A command handled by the frontend instead of being sent to the server
+pub struct LocalCommand {
+    pub keyword: &'static str,
+    pub data: ChatCommandData,
+}
+
+/// Runs `f` with the arguments and description of the command, if there is
+/// one with this keyword
+fn with_command_data<R>(
+    keyword: &str,
+    local: &[LocalCommand],
+    f: impl FnOnce(&ChatCommandData) -> R,
+) -> Option<R> {
+    if let Some(cmd) = local.iter().find(|cmd| cmd.keyword == keyword) {
+        Some(f(&cmd.data))
+    } else {
+        keyword
+            .parse::<ServerChatCommand>()
+            .ok()
+            .map(|cmd| f(&cmd.data()))
+    }
+}
+
+fn complete_arg(
+    arg: &ArgumentSpec,
+    part: &str,
+    client: &Client,
+    local: &[LocalCommand],
+) -> Vec<String> {
+    match arg {
+        ArgumentSpec::PlayerName(_) => complete_player(part, client),
+        ArgumentSpec::SiteName(_) => complete_site(part, client),
+        ArgumentSpec::Float(_, x, _) => {
+            if part.is_empty() {
+                vec![format!("{:.1}", x)]
+            } else {
+                vec![]
+            }
+        },
+        ArgumentSpec::Integer(_, x, _) => {
+            if part.is_empty() {
+                vec![format!("{}", x)]
+            } else {
+                vec![]
+            }
+        },
+        ArgumentSpec::Any(_, _) => vec![],
+        ArgumentSpec::Command(_) => complete_command(part, ' ', client, local),
+        ArgumentSpec::Message(_) => complete_player(part, client),
+        ArgumentSpec::SubCommand => complete_command(part, ' ', client, local),
+        ArgumentSpec::Enum(_, strings, _) => strings
+            .iter()
+            .filter(|string| string.starts_with(part))
+            .map(|c| c.to_string())
+            .collect(),
+        ArgumentSpec::Boolean(_, part, _) => vec!["true", "false"]
+            .iter()
+            .filter(|string| string.starts_with(part))
+            .map(|c| c.to_string())
+            .collect(),
+    }
+}
+
+fn complete_player(part: &str, client: &Client) -> Vec<String> {
+    client
+        .player_list()
+        .values()
+        .map(|player_info| &player_info.player_alias)
+        .filter(|alias| alias.starts_with(part))
+        .cloned()
+        .collect()
+}
+
+fn complete_site(mut part: &str, client: &Client) -> Vec<String> {
+    if let Some(p) = part.strip_prefix('"') {
+        part = p;
+    }
+    client
+        .sites()
+        .values()
+        .filter_map(|site| match site.site.kind {
+            SiteKind::Cave => None,
+            _ => site.site.name.as_ref(),
+        })
+        .filter(|name| name.starts_with(part))
+        .map(|name| {
+            if name.contains(' ') {
+                format!("\"{}\"", name)
+            } else {
+                name.clone()
+            }
+        })
+        .collect()
+}
+
+// Get the byte index of the nth word. Used in completing "/sudo p /subcmd"
+fn nth_word(line: &str, n: usize) -> Option<usize> {
+    let mut is_space = false;
+    let mut j = 0;
+    for (i, c) in line.char_indices() {
+        match (is_space, c.is_whitespace()) {
+            (true, true) => {},
+            (true, false) => {
+                is_space = false;
+                j += 1;
+            },
+            (false, true) => {
+                is_space = true;
+            },
+            (false, false) => {},
+        }
+        if j == n {
+            return Some(i);
+        }
+    }
+    None
+}
+
+fn complete_command(
+    part: &str,
+    prefix: char,
+    client: &Client,
+    local: &[LocalCommand],
+) -> Vec<String> {
+    command_keywords(part, prefix, client.is_moderator(), local)
+}
+
+/// The commands the player can run, the ones needing a role only being
+/// offered to moderators
+fn command_keywords(
+    part: &str,
+    prefix: char,
+    moderator: bool,
+    local: &[LocalCommand],
+) -> Vec<String> {
+    ServerChatCommand::iter_with_keywords()
+        .filter(|(_, cmd)| moderator || cmd.needs_role().is_none())
+        .map(|(kwd, _)| kwd)
+        .chain(local.iter().map(|cmd| cmd.keyword))
+        .filter(|kwd| kwd.starts_with(part))
+        .map(|kwd| format!("{}{}", prefix, kwd))
+        .collect()
+}
+
+/// The words the last word of the line could be completed to, the names of
+/// the online players and the commands with the arguments they expect
+pub fn complete(
+    line: &str,
+    client: &Client,
+    cmd_prefix: char,
+    local: &[LocalCommand],
+) -> Vec<String> {
+    let word = if line.chars().last().map_or(true, char::is_whitespace) {
+        ""
+    } else {
+        line.split_whitespace().last().unwrap_or("")
+    };
+
+    if line.starts_with(cmd_prefix) {
+        let line = line.strip_prefix(cmd_prefix).unwrap_or(line);
+        let mut iter = line.split_whitespace();
+        let cmd = iter.next().unwrap_or("");
+        let i = iter.count() + if word.is_empty() { 1 } else { 0 };
+        if i == 0 {
+            // Completing chat command name. This is the start of the line so the prefix
+            // will be part of it
+            let word = word.strip_prefix(cmd_prefix).unwrap_or(word);
+            return complete_command(word, cmd_prefix, client, local);
+        }
+
+        with_command_data(cmd, local, |data| {
+            let args = &data.args;
+            if let Some(arg) = args.get(i - 1) {
+                // Complete ith argument
+                complete_arg(arg, word, client, local)
+            } else {
+                // Complete past the last argument
+                match args.last() {
+                    Some(ArgumentSpec::SubCommand) => {
+                        if let Some(index) = nth_word(line, args.len()) {
+                            complete(&line[index..], client, cmd_prefix, local)
+                        } else {
+                            vec![]
+                        }
+                    },
+                    Some(ArgumentSpec::Message(_)) => complete_player(word, client),
+                    _ => vec![], // End of command. Nothing to complete
+                }
+            }
+        })
+        // Completing for unknown chat command
+        .unwrap_or_else(|| complete_player(word, client))
+    } else {
+        // Not completing a command
+        complete_player(word, client)
+    }
+}
+
+/// Usage of the command being typed, with the argument being typed in
+/// uppercase, e.g. "/give_item <ITEM> [num]: Give yourself some items."
+pub fn argument_hint(line: &str, cmd_prefix: char, local: &[LocalCommand]) -> Option<String> {
+    let line = line.strip_prefix(cmd_prefix)?;
+    let mut words = line.split_whitespace();
+    let cmd = words.next()?;
+    // Only past the keyword, there are completions for the keyword itself
+    if !line.contains(char::is_whitespace) {
+        return None;
+    }
+    let typed = words.count();
+    let current = if line.ends_with(char::is_whitespace) {
+        typed
+    } else {
+        typed.saturating_sub(1)
+    };
+    with_command_data(cmd, local, |data| {
+        let usage = std::iter::once(format!("{}{}", cmd_prefix, cmd))
+            .chain(data.args.iter().enumerate().map(|(i, arg)| {
+                if i == current {
+                    arg.usage_string().to_uppercase()
+                } else {
+                    arg.usage_string()
+                }
+            }))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("{}: {}", usage, data.description)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::cmd::Requirement;
+
+    fn local() -> Vec<LocalCommand> {
+        vec![LocalCommand {
+            keyword: "mute",
+            data: ChatCommandData::new(
+                vec![ArgumentSpec::PlayerName(Requirement::Required)],
+                "Mutes chat messages from a player.",
+                None,
+            ),
+        }]
+    }
+
+    #[test]
+    fn test_complete_command() {
+        let local = local();
+        assert_eq!(command_keywords("mu", '/', true, &local), vec![
+            "/mute".to_string()
+        ]);
+        assert_eq!(command_keywords("unba", '/', true, &local), vec![
+            "/unban".to_string()
+        ]);
+        // Moderator commands aren't offered to everyone
+        assert!(command_keywords("unba", '/', false, &local).is_empty());
+        assert_eq!(command_keywords("make_", '/', true, &local), vec![
+            "/make_block".to_string(),
+            "/make_npc".to_string(),
+            "/make_sprite".to_string(),
+            "/make_volume".to_string()
+        ]);
+    }
+
+    #[test]
+    fn hints_show_the_argument_being_typed() {
+        let local = local();
+        assert_eq!(argument_hint("/mute", '/', &local), None);
+        assert_eq!(
+            argument_hint("/mute ", '/', &local).as_deref(),
+            Some("/mute <PLAYER>: Mutes chat messages from a player.")
+        );
+        assert_eq!(argument_hint("hello", '/', &local), None);
+    }
+}
@@ -1,5 +1,6 @@
 use authc::AuthClientError;
-pub use network::{InitProtocolError, NetworkConnectError, NetworkError};
+use common_net::msg::RegisterError;
+pub use network::{InitProtocolError, NetworkConnectError, NetworkError, VELOREN_NETWORK_VERSION};
 use network::{ParticipantError, StreamError};
 use specs::error::Error as SpecsError;
 
@@ -21,6 +22,8 @@ pub enum Error {
     Banned(String),
     /// Persisted character data is invalid or missing
     InvalidCharacter,
+    /// The session to resume isn't waiting on the server anymore
+    SessionExpired,
     //TODO: InvalidAlias,
     Other(String),
     SpecsErr(SpecsError),
@@ -42,6 +45,19 @@ impl From<StreamError> for Error {
     fn from(err: StreamError) -> Self { Self::StreamErr(err) }
 }
 
+impl From<RegisterError> for Error {
+    fn from(err: RegisterError) -> Self {
+        match err {
+            RegisterError::AuthError(err) => Self::AuthErr(err),
+            RegisterError::InvalidCharacter => Self::InvalidCharacter,
+            RegisterError::NotOnWhitelist => Self::NotOnWhitelist,
+            RegisterError::Kicked(err) => Self::Kicked(err),
+            RegisterError::Banned(reason) => Self::Banned(reason),
+            RegisterError::SessionExpired => Self::SessionExpired,
+        }
+    }
+}
+
 impl From<AuthClientError> for Error {
     fn from(err: AuthClientError) -> Self { Self::AuthClientError(err) }
 }
@@ -3,6 +3,7 @@
 #![feature(label_break_value, option_zip)]
 
 pub mod addr;
+pub mod cmd;
 pub mod error;
 
 // Reexports
@@ -26,29 +27,31 @@ use common::{
         group,
         inventory::item::{modular, tool, ItemKind},
         invite::{InviteKind, InviteResponse},
+        portal::PortalId,
         skills::Skill,
         slot::{EquipSlot, InvSlotId, Slot},
         CharacterState, ChatMode, ControlAction, ControlEvent, Controller, ControllerInputs,
         GroupManip, InputKind, InventoryAction, InventoryEvent, InventoryUpdateEvent,
-        MapMarkerChange, UtteranceKind,
+        MapMarkerChange, MinigameAction, UtteranceKind,
     },
     event::{EventBus, LocalEvent},
     grid::Grid,
     link::Is,
     lod,
     mounting::Rider,
-    outcome::Outcome,
+    outcome::{HealthChangeInfo, Outcome},
     recipe::{ComponentRecipeBook, RecipeBook},
     resources::{PlayerEntity, TimeOfDay},
     spiral::Spiral2d,
     terrain::{
         block::Block, map::MapConfig, neighbors, site::DungeonKindMeta, BiomeKind, SiteKindMeta,
-        SpriteKind, TerrainChunk, TerrainChunkSize,
+        SpriteKind, TerrainChunk, TerrainChunkSize, TerrainGrid,
     },
     trade::{PendingTrade, SitePrices, TradeAction, TradeId, TradeResult},
     uid::{Uid, UidAllocator},
     vol::RectVolSize,
     weather::{Weather, WeatherGrid},
+    world_border::WorldBorder,
 };
 #[cfg(feature = "tracy")] use common_base::plot;
 use common_base::{prof_span, span};
@@ -56,9 +59,10 @@ use common_net::{
     msg::{
         self,
         world_msg::{EconomyInfo, PoiInfo, SiteId, SiteInfo},
-        ClientGeneral, ClientMsg, ClientRegister, ClientType, DisconnectReason, InviteAnswer,
-        Notification, PingMsg, PlayerInfo, PlayerListUpdate, PresenceKind, RegisterError,
-        ServerGeneral, ServerInit, ServerRegisterAnswer,
+        ClientGeneral, ClientMsg, ClientRegister, ClientType, ClientVoiceMsg, DisconnectReason,
+        InviteAnswer, LoginQueuePosition, Notification, PingMsg, PlayerInfo, PlayerListUpdate,
+        PresenceKind, ServerGeneral, ServerInit, ServerRegisterMsg, ServerVoiceMsg, SessionToken,
+        VoiceFrame,
     },
     sync::WorldSyncExt,
 };
@@ -82,6 +86,8 @@ use tracing::{debug, error, trace, warn};
 use vek::*;
 
 pub const MAX_SELECTABLE_VIEW_DISTANCE: u32 = 65;
+/// Shown in place of names and addresses that should stay private
+pub const HIDDEN_NAME: &str = "*****";
 
 const PING_ROLLING_AVERAGE_SECS: usize = 10;
 
@@ -104,12 +110,34 @@ pub enum Event {
     Notification(Notification),
     SetViewDistance(u32),
     Outcome(Outcome),
+    /// A player logged in, their info is already in the player list
+    PlayerJoined(Uid),
+    /// A player logged out, they are kept in the player list as offline
+    PlayerLeft(Uid),
+    /// An entity was created on the server and synced to this client
+    EntitySpawned(Uid),
+    /// The entity controlled by this client was damaged, also sent as
+    /// `Event::Outcome`
+    DamageTaken(HealthChangeInfo),
+    /// Damage this client dealt to a training dummy
+    DamageReport(comp::DamageReport),
     CharacterCreated(CharacterId),
     CharacterEdited(CharacterId),
     CharacterError(String),
     MapMarker(comp::MapMarkerUpdate),
     StartSpectate(Vec3<f32>),
     SpectatePosition(Vec3<f32>),
+    /// The spectator camera should follow the player, or stop following
+    /// anyone with `None`
+    SpectatePlayer(Option<Uid>),
+    /// Voice of a player nearby
+    Voice {
+        speaker: Uid,
+        frame: VoiceFrame,
+    },
+    /// The server sent the choices of the dialogue with a NPC, or `None` once
+    /// the dialogue is over
+    Dialogue(Option<Uid>),
 }
 
 pub struct WorldData {
@@ -219,6 +247,8 @@ pub struct Client {
     available_recipes: HashMap<String, Option<SpriteKind>>,
     lod_zones: HashMap<Vec2<i32>, lod::Zone>,
     lod_last_requested: Option<Instant>,
+    /// Text written on the signs of the loaded chunks
+    sign_texts: HashMap<Vec3<i32>, String>,
     force_update_counter: u64,
 
     max_group_size: u32,
@@ -227,6 +257,14 @@ pub struct Client {
     group_leader: Option<Uid>,
     // Note: potentially representable as a client only component
     group_members: HashMap<Uid, group::Role>,
+    // Last known status of the other group members, including the ones outside
+    // of the view distance
+    group_member_status: HashMap<Uid, group::MemberStatus>,
+    // Guild of the played character along with its roster
+    guild: Option<comp::guild::GuildInfo>,
+    // NPC the player is talking to along with the localization keys of the
+    // choices they can pick
+    dialogue: Option<(Uid, Vec<String>)>,
     // Pending invites that this client has sent out
     pending_invites: HashSet<Uid>,
     // The pending trade the client is involved in, and it's id
@@ -234,12 +272,21 @@ pub struct Client {
 
     network: Option<Network>,
     participant: Option<Participant>,
+    /// Where the client connected to, to reconnect there
+    connection_args: ConnectionArgs,
+    /// Session to resume if the connection drops, given by the server once
+    /// registered
+    session: Option<SessionToken>,
     general_stream: Stream,
     ping_stream: Stream,
     register_stream: Stream,
     character_screen_stream: Stream,
     in_game_stream: Stream,
     terrain_stream: Stream,
+    voice_stream: Stream,
+    /// Whether the voice chat of the players nearby is heard, sent again when
+    /// the session is resumed
+    voice_listening: bool,
 
     client_timeout: Duration,
     last_server_ping: f64,
@@ -278,27 +325,7 @@ impl Client {
     ) -> Result<Self, Error> {
         let network = Network::new(Pid::new(), &runtime);
 
-        let participant = match addr {
-            ConnectionArgs::Tcp {
-                hostname,
-                prefer_ipv6,
-            } => addr::try_connect(&network, &hostname, prefer_ipv6, ConnectAddr::Tcp).await?,
-            ConnectionArgs::Quic {
-                hostname,
-                prefer_ipv6,
-            } => {
-                warn!(
-                    "QUIC is enabled. This is experimental and you won't be able to connect to \
-                     TCP servers unless deactivated"
-                );
-                let config = quinn::ClientConfig::with_native_roots();
-                addr::try_connect(&network, &hostname, prefer_ipv6, |a| {
-                    ConnectAddr::Quic(a, config.clone(), hostname.clone())
-                })
-                .await?
-            },
-            ConnectionArgs::Mpsc(id) => network.connect(ConnectAddr::Mpsc(id)).await?,
-        };
+        let participant = Self::connect(&network, &addr).await?;
 
         let stream = participant.opened().await?;
         let mut ping_stream = participant.opened().await?;
@@ -306,6 +333,7 @@ impl Client {
         let character_screen_stream = participant.opened().await?;
         let in_game_stream = participant.opened().await?;
         let terrain_stream = participant.opened().await?;
+        let voice_stream = participant.opened().await?;
 
         register_stream.send(ClientType::Game)?;
         let server_info: ServerInfo = register_stream.recv().await?;
@@ -682,6 +710,7 @@ impl Client {
             chat_mode: ChatMode::default(),
 
             lod_zones: HashMap::new(),
+            sign_texts: HashMap::new(),
             lod_last_requested: None,
 
             force_update_counter: 0,
@@ -690,17 +719,24 @@ impl Client {
             invite: None,
             group_leader: None,
             group_members: HashMap::new(),
+            group_member_status: HashMap::new(),
+            guild: None,
+            dialogue: None,
             pending_invites: HashSet::new(),
             pending_trade: None,
 
             network: Some(network),
             participant: Some(participant),
+            connection_args: addr,
+            session: None,
             general_stream: stream,
             ping_stream,
             register_stream,
             character_screen_stream,
             in_game_stream,
             terrain_stream,
+            voice_stream,
+            voice_listening: false,
 
             client_timeout,
 
@@ -721,12 +757,15 @@ impl Client {
         })
     }
 
-    /// Request a state transition to `ClientState::Registered`.
+    /// Request a state transition to `ClientState::Registered`. `on_queued` is
+    /// called with the position of the client in the login queue whenever it
+    /// changes while the server is full.
     pub async fn register(
         &mut self,
         username: String,
         password: String,
         mut auth_trusted: impl FnMut(&str) -> bool,
+        mut on_queued: impl FnMut(LoginQueuePosition),
     ) -> Result<(), Error> {
         // Authentication
         let token_or_username = match &self.server_info.auth_provider {
@@ -759,19 +798,151 @@ impl Client {
             None => Ok(username),
         }?;
 
-        self.send_msg_err(ClientRegister { token_or_username })?;
+        self.send_msg_err(ClientRegister {
+            token_or_username,
+            session: None,
+        })?;
 
-        match self.register_stream.recv::<ServerRegisterAnswer>().await? {
-            Err(RegisterError::AuthError(err)) => Err(Error::AuthErr(err)),
-            Err(RegisterError::InvalidCharacter) => Err(Error::InvalidCharacter),
-            Err(RegisterError::NotOnWhitelist) => Err(Error::NotOnWhitelist),
-            Err(RegisterError::Kicked(err)) => Err(Error::Kicked(err)),
-            Err(RegisterError::Banned(reason)) => Err(Error::Banned(reason)),
-            Ok(()) => {
-                self.registered = true;
-                Ok(())
+        // Keep pinging the server while waiting in the login queue, for it not to
+        // time the client out
+        let mut ping_interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            tokio::select! {
+                msg = self.register_stream.recv() => match msg? {
+                    ServerRegisterMsg::Queued(position) => on_queued(position),
+                    ServerRegisterMsg::Answer(answer) => break answer?,
+                },
+                _ = ping_interval.tick() => self.ping_stream.send(PingMsg::Ping)?,
+            }
+        }
+        self.registered = true;
+        Ok(())
+    }
+
+    async fn connect(network: &Network, addr: &ConnectionArgs) -> Result<Participant, Error> {
+        Ok(match addr {
+            ConnectionArgs::Tcp {
+                hostname,
+                prefer_ipv6,
+            } => addr::try_connect(network, hostname, *prefer_ipv6, ConnectAddr::Tcp).await?,
+            ConnectionArgs::Quic {
+                hostname,
+                prefer_ipv6,
+            } => {
+                warn!(
+                    "QUIC is enabled. This is experimental and you won't be able to connect to \
+                     TCP servers unless deactivated"
+                );
+                let config = quinn::ClientConfig::with_native_roots();
+                addr::try_connect(network, hostname, *prefer_ipv6, |a| {
+                    ConnectAddr::Quic(a, config.clone(), hostname.clone())
+                })
+                .await?
             },
+            ConnectionArgs::Mpsc(id) => network.connect(ConnectAddr::Mpsc(*id)).await?,
+        })
+    }
+
+    /// Resumes the session of the player from a new connection once the last
+    /// one dropped, like when the address of the client changed. The server
+    /// keeps the character in the world for a while, waiting for it.
+    pub fn reconnect(&mut self) -> Result<(), Error> {
+        // Servers which don't keep the characters of dropped connections, like the
+        // singleplayer one, don't give out sessions
+        let session = self.session.ok_or(Error::SessionExpired)?;
+        let runtime = Arc::clone(&self.runtime);
+        let network = self
+            .network
+            .as_ref()
+            .expect("The network lives as long as the client");
+        let connection_args = &self.connection_args;
+
+        let (participant, streams) = tokio::task::block_in_place(|| {
+            runtime.block_on(async {
+                let participant = Self::connect(network, connection_args).await?;
+                let general_stream = participant.opened().await?;
+                let mut ping_stream = participant.opened().await?;
+                let mut register_stream = participant.opened().await?;
+                let character_screen_stream = participant.opened().await?;
+                let in_game_stream = participant.opened().await?;
+                let terrain_stream = participant.opened().await?;
+                let voice_stream = participant.opened().await?;
+
+                register_stream.send(ClientType::Game)?;
+                let _: ServerInfo = register_stream.recv().await?;
+                // The server syncs the entity of a new player first, the client keeps the one
+                // of its character instead
+                let mut ping_interval = tokio::time::interval(Duration::from_secs(1));
+                let init = loop {
+                    tokio::select! {
+                        res = register_stream.recv() => break res?,
+                        _ = ping_interval.tick() => ping_stream.send(PingMsg::Ping)?,
+                    }
+                };
+                if let ServerInit::TooManyPlayers = init {
+                    return Err(Error::TooManyPlayers);
+                }
+
+                register_stream.send(ClientRegister {
+                    token_or_username: String::new(),
+                    session: Some(session),
+                })?;
+                // Resumed sessions skip the login queue
+                loop {
+                    if let ServerRegisterMsg::Answer(answer) = register_stream.recv().await? {
+                        break answer?;
+                    }
+                }
+
+                Ok::<_, Error>((participant, [
+                    general_stream,
+                    ping_stream,
+                    register_stream,
+                    character_screen_stream,
+                    in_game_stream,
+                    terrain_stream,
+                    voice_stream,
+                ]))
+            })
+        })?;
+
+        if let Some(participant) = self.participant.replace(participant) {
+            self.runtime.spawn(async move {
+                let _ = participant.disconnect().await;
+            });
+        }
+        [
+            self.general_stream,
+            self.ping_stream,
+            self.register_stream,
+            self.character_screen_stream,
+            self.in_game_stream,
+            self.terrain_stream,
+            self.voice_stream,
+        ] = streams;
+        self.last_server_ping = self.state.get_time();
+        self.last_server_pong = self.state.get_time();
+
+        // The server sends everything around the character again
+        let player = self.entity();
+        let others = (
+            &self.state.ecs().entities(),
+            &self.state.ecs().read_storage::<Uid>(),
+        )
+            .join()
+            .filter(|(entity, _)| *entity != player)
+            .map(|(_, uid)| uid.0)
+            .collect::<Vec<_>>();
+        for uid in others {
+            self.state
+                .ecs_mut()
+                .delete_entity_and_clear_from_uid_allocator(uid);
         }
+        if self.voice_listening {
+            self.send_msg(ClientVoiceMsg::Listen(true));
+        }
+        debug!("Resumed session from a new connection");
+        Ok(())
     }
 
     fn send_msg_err<S>(&mut self, msg: S) -> Result<(), network::StreamError>
@@ -826,6 +997,7 @@ impl Client {
                     | ClientGeneral::RequestLossyTerrainCompression { .. }
                     | ClientGeneral::AcknowledgePersistenceLoadError
                     | ClientGeneral::UpdateMapMarker(_)
+                    | ClientGeneral::ShareMapMarkers(_)
                     | ClientGeneral::SpectatePosition(_) => {
                         #[cfg(feature = "tracy")]
                         {
@@ -843,7 +1015,7 @@ impl Client {
                         &mut self.terrain_stream
                     },
                     //Always possible
-                    ClientGeneral::ChatMsg(_)
+                    ClientGeneral::ChatMsg(..)
                     | ClientGeneral::Command(_, _)
                     | ClientGeneral::Terminate => &mut self.general_stream,
                 };
@@ -855,6 +1027,7 @@ impl Client {
                 stream.send(msg)
             },
             ClientMsg::Ping(msg) => self.ping_stream.send(msg),
+            ClientMsg::Voice(msg) => self.voice_stream.send(msg),
         }
     }
 
@@ -1081,6 +1254,21 @@ impl Client {
         }
     }
 
+    /// Pick one of the choices of the dialogue with a NPC, the server answers
+    /// with the choices that follow
+    pub fn choose_dialogue(&mut self, choice: usize) {
+        if self.is_dead() {
+            return;
+        }
+
+        if let Some((npc, _)) = self.dialogue {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::Talk(npc, choice)));
+        }
+    }
+
+    /// Walk away from the NPC the player is talking to
+    pub fn close_dialogue(&mut self) { self.dialogue = None; }
+
     pub fn player_list(&self) -> &HashMap<Uid, PlayerInfo> { &self.player_list }
 
     pub fn character_list(&self) -> &CharacterList { &self.character_list }
@@ -1099,6 +1287,8 @@ impl Client {
 
     pub fn lod_zones(&self) -> &HashMap<Vec2<i32>, lod::Zone> { &self.lod_zones }
 
+    pub fn sign_texts(&self) -> &HashMap<Vec3<i32>, String> { &self.sign_texts }
+
     /// Returns whether the specified recipe can be crafted and the sprite, if
     /// any, that is required to do so.
     pub fn can_craft_recipe(&self, recipe: &str, amount: u32) -> (bool, Option<SpriteKind>) {
@@ -1288,6 +1478,18 @@ impl Client {
 
     pub fn group_members(&self) -> &HashMap<Uid, group::Role> { &self.group_members }
 
+    pub fn group_member_status(&self) -> &HashMap<Uid, group::MemberStatus> {
+        &self.group_member_status
+    }
+
+    pub fn guild(&self) -> Option<&comp::guild::GuildInfo> { self.guild.as_ref() }
+
+    pub fn dialogue(&self) -> Option<(Uid, &[String])> {
+        self.dialogue
+            .as_ref()
+            .map(|(npc, choices)| (*npc, choices.as_slice()))
+    }
+
     pub fn pending_invites(&self) -> &HashSet<Uid> { &self.pending_invites }
 
     pub fn pending_trade(&self) -> &Option<(TradeId, PendingTrade, Option<SitePrices>)> {
@@ -1372,10 +1574,46 @@ impl Client {
         }
     }
 
+    pub fn sleep(&mut self, bed_pos: Vec3<i32>) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::Sleep(bed_pos)));
+    }
+
+    /// Makes the portal a destination the player can travel to
+    pub fn activate_portal(&mut self, portal: EcsEntity) {
+        if let Some(uid) = self.state.read_component_copied(portal) {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::ActivatePortal(
+                uid,
+            )));
+        }
+    }
+
+    pub fn use_portal(&mut self, portal: EcsEntity, destination: PortalId) {
+        if let Some(uid) = self.state.read_component_copied(portal) {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::UsePortal(
+                uid,
+                destination,
+            )));
+        }
+    }
+
+    pub fn minigame_action(&mut self, minigame: EcsEntity, action: MinigameAction) {
+        if let Some(uid) = self.state.read_component_copied(minigame) {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::Minigame(
+                uid, action,
+            )));
+        }
+    }
+
     pub fn map_marker_event(&mut self, event: MapMarkerChange) {
         self.send_msg(ClientGeneral::UpdateMapMarker(event));
     }
 
+    /// Shares the named markers with the group, replacing the ones shared
+    /// before
+    pub fn share_map_markers(&mut self, markers: Vec<comp::NamedMapMarker>) {
+        self.send_msg(ClientGeneral::ShareMapMarkers(markers));
+    }
+
     /// Set the current position to spectate, returns true if the client's
     /// player has a Pos component to write to.
     pub fn spectate_position(&mut self, pos: Vec3<f32>) -> bool {
@@ -1588,8 +1826,24 @@ impl Client {
 
     pub fn inventories(&self) -> ReadStorage<comp::Inventory> { self.state.read_storage() }
 
-    /// Send a chat message to the server.
-    pub fn send_chat(&mut self, message: String) { self.send_msg(ClientGeneral::ChatMsg(message)); }
+    /// Send a chat message to the server. Links written in it as markup are
+    /// sent along the message, over their label.
+    pub fn send_chat(&mut self, message: String) {
+        let (message, links) = comp::chat::resolve_chat_links(&message);
+        self.send_msg(ClientGeneral::ChatMsg(message, links));
+    }
+
+    /// Whether the voice chat of the players nearby is heard, only possible in
+    /// game on servers relaying voice chat
+    pub fn set_voice_listening(&mut self, listen: bool) {
+        if self.voice_listening != listen {
+            self.voice_listening = listen;
+            self.send_msg(ClientVoiceMsg::Listen(listen));
+        }
+    }
+
+    /// Sends a frame of the player's voice to the players nearby
+    pub fn send_voice(&mut self, frame: VoiceFrame) { self.send_msg(ClientVoiceMsg::Frame(frame)); }
 
     /// Send a command to the server.
     pub fn send_command(&mut self, name: String, args: Vec<String>) {
@@ -1616,6 +1870,59 @@ impl Client {
         )));
     }
 
+    /// Places the building material in the slot as a block at the position
+    pub fn place_material(&mut self, slot: InvSlotId, pos: Vec3<i32>) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::PlaceMaterial(slot, pos),
+        )));
+    }
+
+    /// Takes back the building material placed at the position
+    pub fn remove_material(&mut self, pos: Vec3<i32>) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::RemoveMaterial(pos),
+        )));
+    }
+
+    /// Builds the structure of the blueprint in the slot on top of the
+    /// position, out of the materials in the inventory
+    pub fn place_blueprint(&mut self, slot: InvSlotId, pos: Vec3<i32>) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::PlaceBlueprint(slot, pos),
+        )));
+    }
+
+    /// Puts up the sign in the slot at the position, with the text written on
+    /// it
+    pub fn place_sign(&mut self, slot: InvSlotId, pos: Vec3<i32>, text: String) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::PlaceSign(slot, pos, text),
+        )));
+    }
+
+    pub fn write_sign(&mut self, pos: Vec3<i32>, text: String) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::WriteSign(pos, text),
+        )));
+    }
+
+    pub fn write_book(&mut self, slot: InvSlotId, text: String) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+            InventoryEvent::WriteBook(slot, text),
+        )));
+    }
+
+    pub fn till_block(&mut self, pos: Vec3<i32>) {
+        self.control_action(ControlAction::InventoryAction(InventoryAction::Till(pos)));
+    }
+
+    /// Plants the seed in the slot on top of the farmland block
+    pub fn plant_seed(&mut self, slot: InvSlotId, pos: Vec3<i32>) {
+        self.control_action(ControlAction::InventoryAction(InventoryAction::Plant(
+            slot, pos,
+        )));
+    }
+
     pub fn change_ability(&mut self, slot: usize, new_ability: comp::ability::AuxiliaryAbility) {
         let auxiliary_key = self
             .inventories()
@@ -1848,6 +2155,8 @@ impl Client {
                     chunks_to_remove.push(key);
                 }
             });
+            self.sign_texts
+                .retain(|pos, _| !chunks_to_remove.contains(&TerrainGrid::chunk_key(*pos)));
             for key in chunks_to_remove {
                 self.state.remove_chunk(key);
             }
@@ -1982,6 +2291,7 @@ impl Client {
                         player_info.player_alias, uid, old_player_info.player_alias
                     );
                 }
+                frontend_events.push(Event::PlayerJoined(uid));
             },
             ServerGeneral::PlayerListUpdate(PlayerListUpdate::Moderator(uid, moderator)) => {
                 if let Some(player_info) = self.player_list.get_mut(&uid) {
@@ -2040,6 +2350,7 @@ impl Client {
                 if let Some(player_info) = self.player_list.get_mut(&uid) {
                     if player_info.is_online {
                         player_info.is_online = false;
+                        frontend_events.push(Event::PlayerLeft(uid));
                     } else {
                         warn!(
                             "Received msg to remove uid {} from the player list by they were \
@@ -2066,7 +2377,11 @@ impl Client {
                     );
                 }
             },
-            ServerGeneral::ChatMsg(m) => frontend_events.push(Event::Chat(m)),
+            ServerGeneral::ChatMsg(mut m) => {
+                // The labels of the links are written by the sender
+                m.relabel_links();
+                frontend_events.push(Event::Chat(m))
+            },
             ServerGeneral::ChatMode(m) => {
                 self.chat_mode = m;
             },
@@ -2101,9 +2416,10 @@ impl Client {
                     return Err(Error::Other("Failed to find entity from uid.".into()));
                 }
             },
-            ServerGeneral::TimeOfDay(time_of_day, calendar) => {
+            ServerGeneral::TimeOfDay(time_of_day, calendar, day_cycle_factor) => {
                 self.target_time_of_day = Some(time_of_day);
                 *self.state.ecs_mut().write_resource() = calendar;
+                *self.state.ecs_mut().write_resource() = day_cycle_factor;
             },
             ServerGeneral::EntitySync(entity_sync_package) => {
                 self.state
@@ -2117,7 +2433,9 @@ impl Client {
                     .apply_comp_sync_package(comp_sync_package);
             },
             ServerGeneral::CreateEntity(entity_package) => {
+                let uid = Uid(entity_package.uid);
                 self.state.ecs_mut().apply_entity_package(entity_package);
+                frontend_events.push(Event::EntitySpawned(uid));
             },
             ServerGeneral::DeleteEntity(entity) => {
                 if self.uid() != Some(entity) {
@@ -2129,6 +2447,7 @@ impl Client {
             ServerGeneral::Notification(n) => {
                 frontend_events.push(Event::Notification(n));
             },
+            ServerGeneral::SessionStarted(token) => self.session = Some(token),
             _ => unreachable!("Not a general msg"),
         }
         Ok(())
@@ -2187,7 +2506,11 @@ impl Client {
                             frontend_events.push(Event::MapMarker(
                                 comp::MapMarkerUpdate::GroupMember(uid, MapMarkerChange::Remove),
                             ));
+                            frontend_events.push(Event::MapMarker(
+                                comp::MapMarkerUpdate::GroupMemberShared(uid, Vec::new()),
+                            ));
                         }
+                        self.group_member_status.remove(&uid);
                         if self.group_members.remove(&uid).is_none() {
                             warn!(
                                 "Received msg to remove uid {} from group members but by they \
@@ -2214,10 +2537,21 @@ impl Client {
                     NoGroup => {
                         self.group_leader = None;
                         self.group_members = HashMap::new();
+                        self.group_member_status = HashMap::new();
                         frontend_events.push(Event::MapMarker(comp::MapMarkerUpdate::ClearGroup));
                     },
                 }
             },
+            ServerGeneral::GroupMemberStatus(statuses) => {
+                self.group_member_status = statuses.into_iter().collect();
+            },
+            ServerGeneral::GuildUpdate(guild) => {
+                self.guild = guild;
+            },
+            ServerGeneral::Dialogue { npc, choices } => {
+                self.dialogue = choices.map(|choices| (npc, choices));
+                frontend_events.push(Event::Dialogue(self.dialogue.as_ref().map(|_| npc)));
+            },
             ServerGeneral::Invite {
                 inviter,
                 timeout,
@@ -2289,7 +2623,18 @@ impl Client {
                 self.server_view_distance_limit = Some(vd);
             },
             ServerGeneral::Outcomes(outcomes) => {
-                frontend_events.extend(outcomes.into_iter().map(Event::Outcome))
+                let uid = self.uid();
+                for outcome in outcomes {
+                    if let Outcome::HealthChange { info, .. } = outcome {
+                        if info.amount < 0.0 && Some(info.target) == uid {
+                            frontend_events.push(Event::DamageTaken(info));
+                        }
+                    }
+                    frontend_events.push(Event::Outcome(outcome));
+                }
+            },
+            ServerGeneral::DamageReport(report) => {
+                frontend_events.push(Event::DamageReport(report));
             },
             ServerGeneral::Knockback(impulse) => {
                 self.state
@@ -2321,9 +2666,15 @@ impl Client {
             ServerGeneral::WeatherUpdate(weather) => {
                 self.weather.weather_update(weather);
             },
+            ServerGeneral::WorldBorder(border) => {
+                *self.state.ecs().write_resource::<WorldBorder>() = border;
+            },
             ServerGeneral::SpectatePosition(pos) => {
                 frontend_events.push(Event::SpectatePosition(pos));
             },
+            ServerGeneral::SpectatePlayer(uid) => {
+                frontend_events.push(Event::SpectatePlayer(uid));
+            },
             _ => unreachable!("Not a in_game message"),
         }
         Ok(())
@@ -2349,6 +2700,15 @@ impl Client {
                     });
                 }
             },
+            ServerGeneral::SignTexts(texts) => {
+                for (pos, text) in texts {
+                    if text.is_empty() {
+                        self.sign_texts.remove(&pos);
+                    } else {
+                        self.sign_texts.insert(pos, text);
+                    }
+                }
+            },
             _ => unreachable!("Not a terrain message"),
         }
         Ok(())
@@ -2450,6 +2810,13 @@ impl Client {
                 }
                 self.handle_server_terrain_msg(msg)?;
             }
+            while let Some(msg) = self.voice_stream.try_recv::<ServerVoiceMsg>()? {
+                cnt += 1;
+                frontend_events.push(Event::Voice {
+                    speaker: msg.speaker,
+                    frame: msg.frame,
+                });
+            }
 
             if cnt_start == cnt {
                 #[cfg(feature = "tracy")]
@@ -2572,6 +2939,8 @@ impl Client {
     fn clean_state(&mut self) {
         // Clear pending trade
         self.pending_trade = None;
+        // The guild belongs to the character which was played
+        self.guild = None;
 
         let client_uid = self
             .uid()
@@ -2604,8 +2973,14 @@ impl Client {
         }
     }
 
-    /// Format a message for the client (voxygen chat box or chat-cli)
-    pub fn format_message(&self, msg: &comp::ChatMsg, character_name: bool) -> String {
+    /// Format a message for the client (voxygen chat box or chat-cli), whispers
+    /// can be shown without who sent them
+    pub fn format_message(
+        &self,
+        msg: &comp::ChatMsg,
+        character_name: bool,
+        hide_whisper_sender: bool,
+    ) -> String {
         let comp::ChatMsg {
             chat_type, message, ..
         } = &msg;
@@ -2677,6 +3052,7 @@ impl Client {
             comp::ChatType::CommandInfo => message.to_string(),
             comp::ChatType::FactionMeta(_) => message.to_string(),
             comp::ChatType::GroupMeta(_) => message.to_string(),
+            comp::ChatType::GuildMeta(_) => message.to_string(),
             comp::ChatType::Kill(kill_source, victim) => {
                 // Default message formats if no localized message string is set by hud
                 // Needed for cli clients that don't set localization info
@@ -2781,6 +3157,8 @@ impl Client {
                 let to_alias = alias_of_uid(to);
                 if Some(*from) == self.uid() {
                     format!("To [{}]: {}", to_alias, message)
+                } else if hide_whisper_sender {
+                    format!("From [{}]: {}", HIDDEN_NAME, message)
                 } else {
                     format!("From [{}]: {}", from_alias, message)
                 }
@@ -2788,6 +3166,7 @@ impl Client {
             comp::ChatType::Say(uid) => message_format(uid, message, None),
             comp::ChatType::Group(uid, s) => message_format(uid, message, Some(s)),
             comp::ChatType::Faction(uid, s) => message_format(uid, message, Some(s)),
+            comp::ChatType::Guild(uid, s) => message_format(uid, message, Some(s)),
             comp::ChatType::Region(uid) => message_format(uid, message, None),
             comp::ChatType::World(uid) => message_format(uid, message, None),
             // NPCs can't talk. Should be filtered by hud/mod.rs for voxygen and should be filtered
@@ -2931,10 +3310,12 @@ mod tests {
             let username: String = "Foo".to_string();
             let password: String = "Bar".to_string();
             let auth_server: String = "auth.veloren.net".to_string();
-            let _result: Result<(), Error> =
-                runtime.block_on(client.register(username, password, |suggestion: &str| {
-                    suggestion == auth_server
-                }));
+            let _result: Result<(), Error> = runtime.block_on(client.register(
+                username,
+                password,
+                |suggestion: &str| suggestion == auth_server,
+                |_| {},
+            ));
 
             //clock
             let mut clock = Clock::new(Duration::from_secs_f64(SPT));
@@ -2952,7 +3333,7 @@ mod tests {
                     match event {
                         Event::Chat(msg) => {
                             let msg: comp::ChatMsg = msg;
-                            let _s: String = client.format_message(&msg, true);
+                            let _s: String = client.format_message(&msg, true, false);
                         },
                         Event::Disconnect => {},
                         Event::DisconnectionNotification(_) => {
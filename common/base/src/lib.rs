@@ -2,7 +2,7 @@
 
 pub mod userdata_dir;
 
-pub use userdata_dir::userdata_dir;
+pub use userdata_dir::{userdata_dir, DataDirs};
 
 // Panic in debug or tests, warn in release
 #[macro_export]
@@ -1,8 +1,39 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
 use tracing::warn;
 
 const VELOREN_USERDATA_ENV: &str = "VELOREN_USERDATA";
 
+static PORTABLE: AtomicBool = AtomicBool::new(false);
+
+/// Keeps all the user data beside the executable, e.g. for installs on a USB
+/// stick. This has to be set before the user data directory is first looked
+/// up.
+pub fn set_portable(portable: bool) { PORTABLE.store(portable, Ordering::Relaxed); }
+
+pub fn is_portable() -> bool { PORTABLE.load(Ordering::Relaxed) }
+
+fn executable_dir() -> PathBuf {
+    let mut path = std::env::current_exe().expect("Failed to retrieve executable path!");
+    path.pop();
+    path
+}
+
+fn project_dirs() -> directories_next::ProjectDirs {
+    directories_next::ProjectDirs::from("net", "veloren", "veloren")
+        .expect("System's $HOME directory path not found!")
+}
+
+/// Whether the platform specific directories are used, which is only the case
+/// for the "system" strategy when nothing overrides it
+fn uses_system_dirs(strategy: Option<&str>) -> bool {
+    !is_portable()
+        && std::env::var_os(VELOREN_USERDATA_ENV).is_none()
+        && strategy.map_or(false, |s| s.eq_ignore_ascii_case("system"))
+}
+
 // TODO: consider expanding this to a general install strategy variable that is
 // also used for finding assets
 // TODO: Ensure there are no NUL (\0) characters in userdata_dir (possible on
@@ -17,29 +48,28 @@ const VELOREN_USERDATA_ENV: &str = "VELOREN_USERDATA";
 
 /// Determines common user data directory used by veloren frontends
 /// The first specified in this list is used
+/// 0. <executable dir>/userdata in portable mode, see [`set_portable`]
 /// 1. The VELOREN_USERDATA environment variable
 /// 2. The VELOREN_USERDATA_STRATEGY environment variable
 /// 3. The CARGO_MANIFEST_DIR/userdata or CARGO_MANIFEST_DIR/../userdata
 /// depending on if a    workspace if being used
 pub fn userdata_dir(workspace: bool, strategy: Option<&str>, manifest_dir: &str) -> PathBuf {
+    // 0. Portable mode
+    if is_portable() {
+        return executable_dir().join("userdata");
+    }
     // 1. The VELOREN_USERDATA environment variable
     std::env::var_os(VELOREN_USERDATA_ENV)
         .map(PathBuf::from)
         // 2. The VELOREN_USERDATA_STRATEGY environment variable
         .or_else(|| match strategy {
             // "system" => system specific project data directory
-            Some(s) if s.eq_ignore_ascii_case("system") => Some(directories_next::ProjectDirs::from("net", "veloren", "veloren")
-                .expect("System's $HOME directory path not found!")
-                .data_dir()
-                .join("userdata")
-            ),
+            Some(s) if s.eq_ignore_ascii_case("system") => {
+                Some(project_dirs().data_dir().join("userdata"))
+            },
             // "executable" => <executable dir>/userdata
             Some(s) if s.eq_ignore_ascii_case("executable") => {
-                let mut path = std::env::current_exe()
-                    .expect("Failed to retrieve executable path!");
-                path.pop();
-                path.push("userdata");
-                Some(path)
+                Some(executable_dir().join("userdata"))
             },
             Some(s) => {
                 warn!(
@@ -91,6 +121,61 @@ pub fn userdata_dir(workspace: bool, strategy: Option<&str>, manifest_dir: &str)
         })
 }
 
+/// Directories a frontend keeps its files in
+#[derive(Clone, Debug)]
+pub struct DataDirs {
+    /// Common user data directory, see [`userdata_dir`]
+    pub userdata: PathBuf,
+    /// Settings of the frontend
+    pub config: PathBuf,
+    pub logs: PathBuf,
+}
+
+impl DataDirs {
+    /// With the "system" strategy the platform config and cache directories
+    /// are used (e.g. `~/.config/veloren/<frontend>` on Linux), unless the
+    /// settings are already in the user data directory from an older version.
+    /// Otherwise everything is kept in `<userdata>/<frontend>`.
+    pub fn new(
+        workspace: bool,
+        strategy: Option<&str>,
+        manifest_dir: &str,
+        frontend: &str,
+    ) -> Self {
+        let userdata = userdata_dir(workspace, strategy, manifest_dir);
+        let local = userdata.join(frontend);
+        let (config, logs) = if uses_system_dirs(strategy) {
+            let dirs = project_dirs();
+            let config = dirs.config_dir().join(frontend);
+            let config = if !config.exists() && local.exists() {
+                local.clone()
+            } else {
+                config
+            };
+            (config, dirs.cache_dir().join(frontend).join("logs"))
+        } else {
+            (local.clone(), local.join("logs"))
+        };
+        Self {
+            userdata,
+            config,
+            logs,
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! data_dirs_workspace {
+    ($frontend:expr) => {
+        $crate::userdata_dir::DataDirs::new(
+            true,
+            option_env!("VELOREN_USERDATA_STRATEGY"),
+            env!("CARGO_MANIFEST_DIR"),
+            $frontend,
+        )
+    };
+}
+
 #[macro_export]
 macro_rules! userdata_dir_workspace {
     () => {
@@ -1,4 +1,4 @@
-use super::{world_msg::SiteId, PingMsg};
+use super::{voice::ClientVoiceMsg, world_msg::SiteId, PingMsg, SessionToken};
 use common::{
     character::CharacterId,
     comp,
@@ -22,6 +22,8 @@ pub enum ClientMsg {
     ///Msg that can be send ALWAYS as soon as we are registered, e.g. `Chat`
     General(ClientGeneral),
     Ping(PingMsg),
+    ///Voice chat, only sent in game
+    Voice(ClientVoiceMsg),
 }
 
 /*
@@ -42,6 +44,8 @@ pub enum ClientType {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClientRegister {
     pub token_or_username: String,
+    /// Session to resume, if the client lost its connection while playing
+    pub session: Option<SessionToken>,
 }
 
 /// Messages sent from the client to the server
@@ -81,6 +85,8 @@ pub enum ClientGeneral {
     UnlockSkillGroup(SkillGroupKind),
     RequestSiteInfo(SiteId),
     UpdateMapMarker(comp::MapMarkerChange),
+    /// Named markers to share with the group, replacing the ones shared before
+    ShareMapMarkers(Vec<comp::NamedMapMarker>),
 
     SpectatePosition(Vec3<f32>),
     //Only in Game, via terrain stream
@@ -91,7 +97,8 @@ pub enum ClientGeneral {
         key: Vec2<i32>,
     },
     //Always possible
-    ChatMsg(String),
+    /// Message and the links over its text
+    ChatMsg(String, Vec<comp::chat::ChatLinkSpan>),
     Command(String, Vec<String>),
     Terminate,
     RequestPlayerPhysics {
@@ -143,16 +150,18 @@ impl ClientMsg {
                         | ClientGeneral::RequestLossyTerrainCompression { .. }
                         | ClientGeneral::AcknowledgePersistenceLoadError
                         | ClientGeneral::UpdateMapMarker(_)
+                        | ClientGeneral::ShareMapMarkers(_)
                         | ClientGeneral::SpectatePosition(_) => {
                             c_type == ClientType::Game && presence.is_some()
                         },
                         //Always possible
-                        ClientGeneral::ChatMsg(_)
+                        ClientGeneral::ChatMsg(..)
                         | ClientGeneral::Command(_, _)
                         | ClientGeneral::Terminate => true,
                     }
             },
             ClientMsg::Ping(_) => true,
+            ClientMsg::Voice(_) => registered && c_type == ClientType::Game && presence.is_some(),
         }
     }
 }
@@ -176,3 +185,7 @@ impl From<ClientGeneral> for ClientMsg {
 impl From<PingMsg> for ClientMsg {
     fn from(other: PingMsg) -> ClientMsg { ClientMsg::Ping(other) }
 }
+
+impl From<ClientVoiceMsg> for ClientMsg {
+    fn from(other: ClientVoiceMsg) -> ClientMsg { ClientMsg::Voice(other) }
+}
@@ -2,6 +2,7 @@ pub mod client;
 pub mod compression;
 pub mod ecs_packet;
 pub mod server;
+pub mod voice;
 pub mod world_msg;
 
 // Reexports
@@ -13,9 +14,12 @@ pub use self::{
     },
     ecs_packet::EcsCompPacket,
     server::{
-        CharacterInfo, DisconnectReason, InviteAnswer, Notification, PlayerInfo, PlayerListUpdate,
-        RegisterError, SerializedTerrainChunk, ServerGeneral, ServerInfo, ServerInit, ServerMsg,
-        ServerRegisterAnswer,
+        CharacterInfo, DisconnectReason, InviteAnswer, LoginQueuePosition, Notification,
+        PlayerInfo, PlayerListUpdate, RegisterError, SerializedTerrainChunk, ServerGeneral,
+        ServerInfo, ServerInit, ServerMsg, ServerRegisterAnswer, ServerRegisterMsg, SessionToken,
+    },
+    voice::{
+        ClientVoiceMsg, ServerVoiceMsg, VoiceFrame, MAX_VOICE_FRAME_BYTES, VOICE_FRAMES_PER_SECOND,
     },
     world_msg::WorldMapMsg,
 };
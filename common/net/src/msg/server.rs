@@ -1,6 +1,6 @@
 use super::{
-    world_msg::EconomyInfo, ClientType, CompressedData, EcsCompPacket, PingMsg, QuadPngEncoding,
-    TriPngEncoding, WidePacking, WireChonk,
+    voice::ServerVoiceMsg, world_msg::EconomyInfo, ClientType, CompressedData, EcsCompPacket,
+    PingMsg, QuadPngEncoding, TriPngEncoding, WidePacking, WireChonk,
 };
 use crate::sync;
 use common::{
@@ -10,12 +10,13 @@ use common::{
     lod,
     outcome::Outcome,
     recipe::{ComponentRecipeBook, RecipeBook},
-    resources::TimeOfDay,
+    resources::{DayCycleFactor, TimeOfDay},
     terrain::{Block, TerrainChunk, TerrainChunkMeta, TerrainChunkSize},
     trade::{PendingTrade, SitePrices, TradeId, TradeResult},
     uid::Uid,
     uuid::Uuid,
     weather::WeatherGrid,
+    world_border::WorldBorder,
 };
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
@@ -33,9 +34,14 @@ pub enum ServerMsg {
     Init(Box<ServerInit>),
     /// Result to `ClientMsg::Register`. send ONCE
     RegisterAnswer(ServerRegisterAnswer),
+    /// Position of the client in the login queue while the server is full,
+    /// sent before `RegisterAnswer` whenever it changes
+    LoginQueue(LoginQueuePosition),
     ///Msg that can be send ALWAYS as soon as client is registered, e.g. `Chat`
     General(ServerGeneral),
     Ping(PingMsg),
+    ///Voice of the players nearby, only sent in game
+    Voice(ServerVoiceMsg),
 }
 
 /*
@@ -49,6 +55,9 @@ pub struct ServerInfo {
     pub git_hash: String,
     pub git_date: String,
     pub auth_provider: Option<String>,
+    pub world_seed: u32,
+    /// Whether the server relays voice chat
+    pub voice_chat: bool,
 }
 
 /// Reponse To ClientType
@@ -71,6 +80,23 @@ pub enum ServerInit {
 
 pub type ServerRegisterAnswer = Result<(), RegisterError>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LoginQueuePosition {
+    /// Number of clients admitted before this one, starting at 1
+    pub position: u32,
+    /// How long the client will likely wait before being admitted, estimated
+    /// from how often players were admitted lately
+    pub estimated_wait: Option<Duration>,
+}
+
+/// What is sent on the register stream once the client asked to register: any
+/// number of queue positions followed by the answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ServerRegisterMsg {
+    Queued(LoginQueuePosition),
+    Answer(ServerRegisterAnswer),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SerializedTerrainChunk {
     DeflatedChonk(CompressedData<TerrainChunk>),
@@ -143,6 +169,16 @@ pub enum ServerGeneral {
     SpectatorSuccess(Vec3<f32>),
     //Ingame related
     GroupUpdate(comp::group::ChangeNotification<Uid>),
+    /// Status of the other members of the group of the client
+    GroupMemberStatus(Vec<(Uid, comp::group::MemberStatus)>),
+    /// Guild of the client along with its roster, `None` if they left it
+    GuildUpdate(Option<comp::guild::GuildInfo>),
+    /// Localization keys of the choices the client can pick in the dialogue
+    /// with a NPC, `None` once the dialogue is over
+    Dialogue {
+        npc: Uid,
+        choices: Option<Vec<String>>,
+    },
     /// Indicate to the client that they are invited to join a group
     Invite {
         inviter: Uid,
@@ -173,6 +209,8 @@ pub enum ServerGeneral {
     SetViewDistance(u32),
     Outcomes(Vec<Outcome>),
     Knockback(Vec3<f32>),
+    /// Damage the client dealt to a training dummy
+    DamageReport(comp::DamageReport),
     // Ingame related AND terrain stream
     TerrainChunkUpdate {
         key: Vec2<i32>,
@@ -183,6 +221,9 @@ pub enum ServerGeneral {
         zone: lod::Zone,
     },
     TerrainBlockUpdates(CompressedData<HashMap<Vec3<i32>, Block>>),
+    /// Text written on the signs at the positions, an empty text meaning that
+    /// the sign was taken down
+    SignTexts(Vec<(Vec3<i32>, String)>),
     // Always possible
     PlayerListUpdate(PlayerListUpdate),
     /// A message to go into the client chat box. The client is responsible for
@@ -190,7 +231,7 @@ pub enum ServerGeneral {
     ChatMsg(comp::ChatMsg),
     ChatMode(comp::ChatMode),
     SetPlayerEntity(Uid),
-    TimeOfDay(TimeOfDay, Calendar),
+    TimeOfDay(TimeOfDay, Calendar, DayCycleFactor),
     EntitySync(sync::EntitySyncPackage),
     CompSync(sync::CompSyncPackage<EcsCompPacket>, u64),
     CreateEntity(sync::EntityPackage<EcsCompPacket>),
@@ -204,9 +245,16 @@ pub enum ServerGeneral {
     SiteEconomy(EconomyInfo),
     MapMarker(comp::MapMarkerUpdate),
     WeatherUpdate(WeatherGrid),
+    WorldBorder(WorldBorder),
     /// Suggest the client to spectate a position. Called after client has
     /// requested teleport etc.
     SpectatePosition(Vec3<f32>),
+    /// Lock the spectator camera onto the player, or unlock it with `None`.
+    /// Sent to moderators using the spectate command.
+    SpectatePlayer(Option<Uid>),
+    /// Token the client can resume its session with from a new connection,
+    /// sent once it is registered
+    SessionStarted(SessionToken),
 }
 
 impl ServerGeneral {
@@ -258,6 +306,22 @@ pub enum InviteAnswer {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Notification {
     WaypointSaved,
+    /// Asset id of the quest
+    QuestStarted(String),
+    QuestCompleted(String),
+    /// Sleeping is only possible at night
+    CantSleep,
+    /// Number of players sleeping and needed to skip the night
+    Sleeping {
+        sleeping: u32,
+        needed: u32,
+    },
+    /// Woke up in the morning
+    WokeUp,
+    /// Started waiting to be teleported to the portal with the given name
+    Travelling(String),
+    /// Moved away from the portal or died before being teleported
+    TravelInterrupted,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -268,6 +332,12 @@ pub enum DisconnectReason {
     Kicked(String),
 }
 
+/// Identifies the session of a registered client, so that the client can get
+/// it back from a new connection when its address changes (e.g. moving from
+/// Wi-Fi to a mobile network) instead of losing its character mid-game
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionToken(pub u128);
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum RegisterError {
     AuthError(String),
@@ -275,6 +345,8 @@ pub enum RegisterError {
     Kicked(String),
     InvalidCharacter,
     NotOnWhitelist,
+    /// The session the client tried to resume is over
+    SessionExpired,
     //TODO: InvalidAlias,
 }
 
@@ -286,9 +358,10 @@ impl ServerMsg {
         presence: Option<super::PresenceKind>,
     ) -> bool {
         match self {
-            ServerMsg::Info(_) | ServerMsg::Init(_) | ServerMsg::RegisterAnswer(_) => {
-                !registered && presence.is_none()
-            },
+            ServerMsg::Info(_)
+            | ServerMsg::Init(_)
+            | ServerMsg::RegisterAnswer(_)
+            | ServerMsg::LoginQueue(_) => !registered && presence.is_none(),
             ServerMsg::General(g) => {
                 registered
                     && match g {
@@ -305,6 +378,9 @@ impl ServerMsg {
                         },
                         //Ingame related
                         ServerGeneral::GroupUpdate(_)
+                        | ServerGeneral::GroupMemberStatus(_)
+                        | ServerGeneral::GuildUpdate(_)
+                        | ServerGeneral::Dialogue { .. }
                         | ServerGeneral::Invite { .. }
                         | ServerGeneral::InvitePending(_)
                         | ServerGeneral::InviteComplete { .. }
@@ -313,15 +389,19 @@ impl ServerMsg {
                         | ServerGeneral::TerrainChunkUpdate { .. }
                         | ServerGeneral::LodZoneUpdate { .. }
                         | ServerGeneral::TerrainBlockUpdates(_)
+                        | ServerGeneral::SignTexts(_)
                         | ServerGeneral::SetViewDistance(_)
                         | ServerGeneral::Outcomes(_)
                         | ServerGeneral::Knockback(_)
+                        | ServerGeneral::DamageReport(_)
                         | ServerGeneral::UpdatePendingTrade(_, _, _)
                         | ServerGeneral::FinishedTrade(_)
                         | ServerGeneral::SiteEconomy(_)
                         | ServerGeneral::MapMarker(_)
                         | ServerGeneral::WeatherUpdate(_)
-                        | ServerGeneral::SpectatePosition(_) => {
+                        | ServerGeneral::WorldBorder(_)
+                        | ServerGeneral::SpectatePosition(_)
+                        | ServerGeneral::SpectatePlayer(_) => {
                             c_type == ClientType::Game && presence.is_some()
                         },
                         // Always possible
@@ -329,16 +409,18 @@ impl ServerMsg {
                         | ServerGeneral::ChatMsg(_)
                         | ServerGeneral::ChatMode(_)
                         | ServerGeneral::SetPlayerEntity(_)
-                        | ServerGeneral::TimeOfDay(_, _)
+                        | ServerGeneral::TimeOfDay(_, _, _)
                         | ServerGeneral::EntitySync(_)
                         | ServerGeneral::CompSync(_, _)
                         | ServerGeneral::CreateEntity(_)
                         | ServerGeneral::DeleteEntity(_)
                         | ServerGeneral::Disconnect(_)
-                        | ServerGeneral::Notification(_) => true,
+                        | ServerGeneral::Notification(_)
+                        | ServerGeneral::SessionStarted(_) => true,
                     }
             },
             ServerMsg::Ping(_) => true,
+            ServerMsg::Voice(_) => registered && c_type == ClientType::Game && presence.is_some(),
         }
     }
 }
@@ -359,6 +441,10 @@ impl From<ServerRegisterAnswer> for ServerMsg {
     fn from(o: ServerRegisterAnswer) -> ServerMsg { ServerMsg::RegisterAnswer(o) }
 }
 
+impl From<LoginQueuePosition> for ServerMsg {
+    fn from(o: LoginQueuePosition) -> ServerMsg { ServerMsg::LoginQueue(o) }
+}
+
 impl From<ServerGeneral> for ServerMsg {
     fn from(o: ServerGeneral) -> ServerMsg { ServerMsg::General(o) }
 }
@@ -366,3 +452,7 @@ impl From<ServerGeneral> for ServerMsg {
 impl From<PingMsg> for ServerMsg {
     fn from(o: PingMsg) -> ServerMsg { ServerMsg::Ping(o) }
 }
+
+impl From<ServerVoiceMsg> for ServerMsg {
+    fn from(o: ServerVoiceMsg) -> ServerMsg { ServerMsg::Voice(o) }
+}
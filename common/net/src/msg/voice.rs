@@ -0,0 +1,34 @@
+//! Voice chat between nearby players, sent on a stream of its own so that it
+//! never holds back the game messages
+
+use common::uid::Uid;
+use serde::{Deserialize, Serialize};
+
+/// Largest opus packet, bigger frames are dropped by the server
+pub const MAX_VOICE_FRAME_BYTES: usize = 1275;
+/// Frames a client speaks per second, the server drops what it sends beyond
+/// that
+pub const VOICE_FRAMES_PER_SECOND: f32 = 50.0;
+
+/// A frame of opus encoded voice, 20 ms long
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceFrame {
+    /// Counts the frames of a speaker, so that lost ones can be concealed
+    pub seq: u16,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ClientVoiceMsg {
+    /// Whether the player wants to hear the players nearby
+    Listen(bool),
+    /// Voice of the player, sent while push-to-talk is held
+    Frame(VoiceFrame),
+}
+
+/// Voice of a player nearby, relayed by the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerVoiceMsg {
+    pub speaker: Uid,
+    pub frame: VoiceFrame,
+}
@@ -56,13 +56,20 @@ macro_rules! synced_components {
             // remove it from that and then see if it's used for anything else and try to move
             // to only being synced for the client's entity.
             skill_set: SkillSet,
+            portal: Portal,
+            minigame: Minigame,
+            attached: Attached,
 
             // Synced to the client only for its own entity
 
             combo: Combo,
             active_abilities: ActiveAbilities,
+            ability_cooldowns: AbilityCooldowns,
             can_build: CanBuild,
             loot_owner: LootOwner,
+            quest_log: QuestLog,
+            reputation: Reputation,
+            discovered_portals: DiscoveredPortals,
         }
     };
 }
@@ -222,6 +229,18 @@ impl NetSync for SkillSet {
     const SYNC_FROM: SyncFrom = SyncFrom::AnyEntity;
 }
 
+impl NetSync for Portal {
+    const SYNC_FROM: SyncFrom = SyncFrom::AnyEntity;
+}
+
+impl NetSync for Minigame {
+    const SYNC_FROM: SyncFrom = SyncFrom::AnyEntity;
+}
+
+impl NetSync for Attached {
+    const SYNC_FROM: SyncFrom = SyncFrom::AnyEntity;
+}
+
 // These are synced only from the client's own  entity.
 
 impl NetSync for Combo {
@@ -232,6 +251,28 @@ impl NetSync for ActiveAbilities {
     const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
 }
 
+impl NetSync for AbilityCooldowns {
+    const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
+
+    fn pre_insert(&mut self, world: &specs::World) {
+        use common::resources::Time;
+        use specs::WorldExt;
+
+        // Time isn't synced between client and server so move the cooldowns to the
+        // Client's local Time to enable accurate comparison.
+        self.rebase(*world.read_resource::<Time>());
+    }
+
+    fn pre_modify(&mut self, world: &specs::World) {
+        use common::resources::Time;
+        use specs::WorldExt;
+
+        // Time isn't synced between client and server so move the cooldowns to the
+        // Client's local Time to enable accurate comparison.
+        self.rebase(*world.read_resource::<Time>());
+    }
+}
+
 impl NetSync for CanBuild {
     const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
 }
@@ -239,3 +280,15 @@ impl NetSync for CanBuild {
 impl NetSync for LootOwner {
     const SYNC_FROM: SyncFrom = SyncFrom::AnyEntity;
 }
+
+impl NetSync for QuestLog {
+    const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
+}
+
+impl NetSync for Reputation {
+    const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
+}
+
+impl NetSync for DiscoveredPortals {
+    const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
+}
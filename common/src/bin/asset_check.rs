@@ -0,0 +1,337 @@
+//! Loads every asset the game references and reports the broken ones with the
+//! file and line they come from, for content contributors to catch mistakes
+//! without launching the game. It needs neither a GPU nor a server, so it also
+//! runs on CI and on the machines the game is cross-compiled from.
+//!
+//! `cargo asset-check`
+
+use hashbrown::HashMap;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+use veloren_common::{
+    assets::{self, AssetExt, Walk, ASSETS_PATH},
+    comp::{
+        inventory::loadout_builder::LoadoutSpec,
+        item::{self, modular, Item},
+    },
+    generation::{self, EntityConfig},
+    lottery::{LootSpec, Lottery},
+    recipe::{ComponentRecipeBook, RecipeBook},
+};
+
+/// Language the others are checked against
+const REFERENCE_LANG: &str = "en";
+
+struct Problem {
+    file: PathBuf,
+    line: Option<usize>,
+    message: String,
+}
+
+#[derive(Default)]
+struct Checker {
+    problems: Vec<Problem>,
+}
+
+/// Path of the file of the asset, relative to the asset directory
+fn asset_file(specifier: &str) -> PathBuf {
+    PathBuf::from(specifier.replace('.', "/")).with_extension("ron")
+}
+
+/// Line of the first mention of `needle` in the file, starting at 1
+fn line_of(file: &Path, needle: &str) -> Option<usize> {
+    let content = fs::read_to_string(ASSETS_PATH.join(file)).ok()?;
+    content
+        .lines()
+        .position(|line| line.contains(needle))
+        .map(|index| index + 1)
+}
+
+impl Checker {
+    fn report(&mut self, file: PathBuf, line: Option<usize>, message: String) {
+        self.problems.push(Problem {
+            file,
+            line,
+            message,
+        });
+    }
+
+    /// Reports an error loading the asset, either in its own file, where RON
+    /// errors start with the line, or in an asset it references
+    fn report_load_error(&mut self, specifier: &str, error: &assets::Error) {
+        let file = asset_file(specifier);
+        let reason = error.reason().to_string();
+        if error.id() == specifier {
+            let line = reason
+                .split_once(':')
+                .and_then(|(line, _)| line.trim().parse().ok());
+            self.report(file, line, reason);
+        } else {
+            let line = line_of(&file, error.id());
+            let message = format!("Broken reference to {}: {}", error.id(), reason);
+            self.report(file, line, message);
+        }
+    }
+
+    /// Reports an error about a reference to another asset, on the line the
+    /// reference is made
+    fn report_reference(&mut self, specifier: &str, reference: &str, message: String) {
+        let file = asset_file(specifier);
+        let line = line_of(&file, reference);
+        self.report(file, line, message);
+    }
+
+    fn check_loot_spec(&mut self, specifier: &str, spec: &LootSpec<String>) {
+        match spec {
+            LootSpec::Item(item) => {
+                if let Err(e) = Item::new_from_asset(item) {
+                    self.report_reference(specifier, item, format!("Unknown item {item}: {e:?}"));
+                }
+            },
+            LootSpec::ItemQuantity(item, lower, upper) => {
+                if let Err(e) = Item::new_from_asset(item) {
+                    self.report_reference(specifier, item, format!("Unknown item {item}: {e:?}"));
+                } else if *lower == 0 || upper < lower {
+                    self.report_reference(
+                        specifier,
+                        item,
+                        format!("Invalid quantity of {item}: {lower} to {upper}"),
+                    );
+                }
+            },
+            LootSpec::LootTable(table) => {
+                if let Err(e) = Lottery::<LootSpec<String>>::load(table) {
+                    self.report_reference(
+                        specifier,
+                        table,
+                        format!("Broken loot table {table}: {}", e.reason()),
+                    );
+                }
+            },
+            LootSpec::Nothing => {},
+            LootSpec::ModularWeapon {
+                tool,
+                material,
+                hands,
+            } => {
+                let mut rng = rand::thread_rng();
+                if let Err(e) = modular::random_weapon(*tool, *material, *hands, &mut rng) {
+                    self.report_reference(
+                        specifier,
+                        &format!("{tool:?}"),
+                        format!("No modular {tool:?} made of {material:?}: {e:?}"),
+                    );
+                }
+            },
+            LootSpec::ModularWeaponPrimaryComponent {
+                tool,
+                material,
+                hands,
+            } => {
+                let mut rng = rand::thread_rng();
+                if let Err(e) =
+                    modular::random_weapon_primary_component(*tool, *material, *hands, &mut rng)
+                {
+                    self.report_reference(
+                        specifier,
+                        &format!("{tool:?}"),
+                        format!("No modular {tool:?} component made of {material:?}: {e:?}"),
+                    );
+                }
+            },
+        }
+    }
+
+    fn check_items(&mut self) {
+        match item::try_all_item_defs() {
+            Ok(ids) => {
+                for id in ids {
+                    if let Err(e) = Item::new_from_asset(&id) {
+                        self.report_load_error(&id, &e);
+                    }
+                }
+            },
+            Err(e) => self.report_load_error("common.items", &e),
+        }
+    }
+
+    fn check_loot_tables(&mut self) {
+        let ids = match assets::load_dir::<Lottery<LootSpec<String>>>("common.loot_tables", true) {
+            Ok(dir) => dir.ids().map(str::to_owned).collect::<Vec<_>>(),
+            Err(e) => return self.report_load_error("common.loot_tables", &e),
+        };
+        for id in ids {
+            match Lottery::<LootSpec<String>>::load(&id) {
+                Ok(table) => {
+                    for (_, spec) in table.read().iter() {
+                        self.check_loot_spec(&id, spec);
+                    }
+                },
+                Err(e) => self.report_load_error(&id, &e),
+            }
+        }
+    }
+
+    fn check_entity_configs(&mut self) {
+        let ids = match generation::try_all_entity_configs() {
+            Ok(ids) => ids,
+            Err(e) => return self.report_load_error("common.entity", &e),
+        };
+        for id in ids {
+            match EntityConfig::load_owned(&id) {
+                Ok(config) => self.check_loot_spec(&id, &config.loot),
+                Err(e) => self.report_load_error(&id, &e),
+            }
+        }
+    }
+
+    fn check_loadouts(&mut self) {
+        let ids = match assets::load_dir::<LoadoutSpec>("common.loadout", true) {
+            Ok(dir) => dir.ids().map(str::to_owned).collect::<Vec<_>>(),
+            Err(e) => return self.report_load_error("common.loadout", &e),
+        };
+        for id in ids {
+            match LoadoutSpec::load_cloned(&id) {
+                Ok(spec) => {
+                    if let Err(e) = spec.validate(vec![id.clone()]) {
+                        self.report(asset_file(&id), None, format!("{e:?}"));
+                    }
+                },
+                Err(e) => self.report_load_error(&id, &e),
+            }
+        }
+    }
+
+    fn check_recipes(&mut self) {
+        if let Err(e) = RecipeBook::load("common.recipe_book") {
+            self.report_load_error("common.recipe_book", &e);
+        }
+        if let Err(e) = ComponentRecipeBook::load("common.component_recipe_book") {
+            self.report_load_error("common.component_recipe_book", &e);
+        }
+    }
+
+    fn check_voxel_models(&mut self) {
+        let root = ASSETS_PATH.as_path();
+        let tree = match Walk::generate(root) {
+            Ok(tree) => tree,
+            Err(e) => return self.report(PathBuf::new(), None, e.to_string()),
+        };
+        tree.for_each_file(root, &mut |path| {
+            if path.extension().map_or(false, |ext| ext == "vox") {
+                let result = fs::read(path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| dot_vox::load_bytes(&bytes).map(drop).map_err(str::to_owned));
+                if let Err(e) = result {
+                    let file = path.strip_prefix(root).unwrap_or(path).to_owned();
+                    self.report(file, None, format!("Invalid voxel model: {e}"));
+                }
+            }
+        });
+    }
+
+    /// Keys of the translation, with the file and line they are defined on
+    fn localization_keys(&mut self, dir: &Path) -> HashMap<String, (PathBuf, usize)> {
+        let mut keys = HashMap::new();
+        let root = ASSETS_PATH.as_path();
+        let tree = match Walk::generate(dir) {
+            Ok(tree) => tree,
+            Err(e) => {
+                self.report(dir.to_owned(), None, e.to_string());
+                return keys;
+            },
+        };
+        tree.for_each_file(dir, &mut |path| {
+            if path.extension().map_or(true, |ext| ext != "ftl") {
+                return;
+            }
+            let file = path.strip_prefix(root).unwrap_or(path).to_owned();
+            let content = fs::read_to_string(path).unwrap_or_default();
+            for (index, line) in content.lines().enumerate() {
+                // Messages start at the beginning of a line, attributes and multiline values
+                // are indented
+                let key = match line.split_once('=') {
+                    Some((key, _)) if line.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                        key.trim()
+                    },
+                    _ => continue,
+                };
+                if let Some((first_file, first_line)) =
+                    keys.insert(key.to_owned(), (file.clone(), index + 1))
+                {
+                    self.report(
+                        file.clone(),
+                        Some(index + 1),
+                        format!(
+                            "{key} is already defined at {}:{first_line}",
+                            first_file.display()
+                        ),
+                    );
+                }
+            }
+        });
+        keys
+    }
+
+    fn check_localization(&mut self) {
+        let i18n_dir = ASSETS_PATH.join("voxygen/i18n");
+        let reference = self.localization_keys(&i18n_dir.join(REFERENCE_LANG));
+        let languages = match fs::read_dir(&i18n_dir) {
+            Ok(entries) => entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir() && !path.ends_with(REFERENCE_LANG))
+                .collect::<Vec<_>>(),
+            Err(e) => return self.report(i18n_dir, None, e.to_string()),
+        };
+        for language in languages {
+            for (key, (file, line)) in self.localization_keys(&language) {
+                if !reference.contains_key(&key) {
+                    self.report(
+                        file,
+                        Some(line),
+                        format!("{key} is not a key of the {REFERENCE_LANG} translation"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn main() {
+    let mut checker = Checker::default();
+    let checks: [(&str, fn(&mut Checker)); 7] = [
+        ("items", Checker::check_items),
+        ("loot tables", Checker::check_loot_tables),
+        ("entity configs", Checker::check_entity_configs),
+        ("loadouts", Checker::check_loadouts),
+        ("recipes", Checker::check_recipes),
+        ("voxel models", Checker::check_voxel_models),
+        ("localization", Checker::check_localization),
+    ];
+    for (name, check) in checks {
+        let before = checker.problems.len();
+        check(&mut checker);
+        println!(
+            "Checked {name}: {} problems",
+            checker.problems.len() - before
+        );
+    }
+
+    checker
+        .problems
+        .sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    for problem in &checker.problems {
+        match problem.line {
+            Some(line) => eprintln!("{}:{line}: {}", problem.file.display(), problem.message),
+            None => eprintln!("{}: {}", problem.file.display(), problem.message),
+        }
+    }
+    if !checker.problems.is_empty() {
+        eprintln!("\n{} broken assets", checker.problems.len());
+        process::exit(1);
+    }
+}
@@ -0,0 +1,101 @@
+//! Blueprints, which build a whole structure out of the materials in the
+//! inventory once placed
+
+use crate::{
+    assets::{self, AssetExt, AssetHandle},
+    comp::Inventory,
+    terrain::{structure::StructureBlock, Block, BlockKind, Structure},
+    vol::ReadVol,
+};
+use serde::Deserialize;
+use vek::*;
+
+const BLUEPRINT_ITEM_PREFIX: &str = "common.items.blueprint.";
+const BLUEPRINT_PREFIX: &str = "common.blueprints.";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Blueprint {
+    /// Structure manifest built by the blueprint, relative to
+    /// `world.manifests`
+    pub structure: String,
+    /// Items used up when building the structure, with their amounts
+    pub materials: Vec<(String, u32)>,
+}
+
+impl assets::Asset for Blueprint {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+impl Blueprint {
+    /// The blueprint drawn on the item, `None` if it isn't a blueprint
+    pub fn of_item(item_id: &str) -> Option<AssetHandle<Self>> {
+        let name = item_id.strip_prefix(BLUEPRINT_ITEM_PREFIX)?;
+        Self::load(&[BLUEPRINT_PREFIX, name].concat()).ok()
+    }
+
+    /// Whether the inventory holds all the materials the structure is built out
+    /// of
+    pub fn has_materials(&self, inventory: &Inventory) -> bool {
+        self.materials.iter().all(|(item_id, amount)| {
+            inventory
+                .slots()
+                .flatten()
+                .filter(|item| item.persistence_item_id() == *item_id)
+                .map(|item| item.amount())
+                .sum::<u32>()
+                >= *amount
+        })
+    }
+
+    /// Blocks of the structure, relative to the middle of its lowest layer
+    /// which is built on the placed position
+    pub fn blocks(&self) -> Vec<(Vec3<i32>, Block)> {
+        let group = Structure::load_group(&self.structure).read();
+        let structure = match group.first() {
+            Some(structure) => structure,
+            None => return Vec::new(),
+        };
+        let bounds = structure.get_bounds();
+        let mut blocks = Vec::new();
+        for x in bounds.min.x..bounds.max.x {
+            for y in bounds.min.y..bounds.max.y {
+                for z in bounds.min.z..bounds.max.z {
+                    let pos = Vec3::new(x, y, z);
+                    if let Some(block) = structure.get(pos).ok().and_then(|sb| block_of(*sb)) {
+                        blocks.push((pos - Vec3::unit_z() * bounds.min.z, block));
+                    }
+                }
+            }
+        }
+        blocks
+    }
+}
+
+/// The block built in place of the structure block, `None` for the ones left
+/// empty or which only make sense in generated structures
+fn block_of(structure_block: StructureBlock) -> Option<Block> {
+    match structure_block {
+        StructureBlock::Normal(color) => Some(Block::new(BlockKind::Misc, color)),
+        StructureBlock::Filled(BlockKind::Air, _) => None,
+        StructureBlock::Filled(kind, color) => Some(Block::new(kind, color)),
+        StructureBlock::Sprite(kind) => Some(Block::air(kind)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blueprints_build_on_the_ground() {
+        let blueprint = Blueprint::of_item("common.items.blueprint.gnarling_totem")
+            .expect("The blueprint item should have a blueprint");
+        let blocks = blueprint.read().blocks();
+        assert!(!blocks.is_empty());
+        assert_eq!(blocks.iter().map(|(pos, _)| pos.z).min(), Some(0));
+        assert!(Blueprint::of_item("common.items.log.wood").is_none());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::terrain::{Block, BlockKind, SpriteKind};
+use vek::*;
+
+/// Blocks away from the player they can place or remove at most
+pub const MAX_BUILD_RANGE: f32 = 8.0;
+
+/// An item which can be placed in the world as a block, and taken back
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BuildMaterial {
+    pub item: &'static str,
+    pub kind: BlockKind,
+    /// Every block of the material has this colour
+    pub color: Rgb<u8>,
+}
+
+impl BuildMaterial {
+    pub fn block(&self) -> Block { Block::new(self.kind, self.color) }
+}
+
+const fn material(item: &'static str, kind: BlockKind, color: Rgb<u8>) -> BuildMaterial {
+    BuildMaterial { item, kind, color }
+}
+
+pub const MATERIALS: [BuildMaterial; 10] = [
+    material(
+        "common.items.log.wood",
+        BlockKind::Wood,
+        Rgb::new(109, 69, 39),
+    ),
+    material(
+        "common.items.log.hardwood",
+        BlockKind::Wood,
+        Rgb::new(78, 48, 30),
+    ),
+    material(
+        "common.items.log.bamboo",
+        BlockKind::Wood,
+        Rgb::new(164, 158, 82),
+    ),
+    material(
+        "common.items.log.ironwood",
+        BlockKind::Wood,
+        Rgb::new(62, 54, 52),
+    ),
+    material(
+        "common.items.log.frostwood",
+        BlockKind::Wood,
+        Rgb::new(132, 166, 186),
+    ),
+    material(
+        "common.items.log.eldwood",
+        BlockKind::Wood,
+        Rgb::new(96, 62, 124),
+    ),
+    material(
+        "common.items.crafting_ing.rock",
+        BlockKind::Rock,
+        Rgb::new(121, 117, 111),
+    ),
+    material(
+        "common.items.mineral.stone.granite",
+        BlockKind::Rock,
+        Rgb::new(150, 127, 121),
+    ),
+    material(
+        "common.items.mineral.stone.basalt",
+        BlockKind::Rock,
+        Rgb::new(58, 58, 64),
+    ),
+    material(
+        "common.items.mineral.stone.obsidian",
+        BlockKind::Rock,
+        Rgb::new(28, 20, 38),
+    ),
+];
+
+pub fn material_of_item(item: &str) -> Option<&'static BuildMaterial> {
+    MATERIALS.iter().find(|material| material.item == item)
+}
+
+/// The material the block looks like it was placed from. Generated terrain
+/// can look the same, the server keeps track of the blocks which were really
+/// placed.
+pub fn material_of_block(block: Block) -> Option<&'static BuildMaterial> {
+    MATERIALS
+        .iter()
+        .find(|material| block.kind() == material.kind && block.get_color() == Some(material.color))
+}
+
+/// Whether a block can be placed in place of this one
+pub fn can_place_in(block: Block) -> bool {
+    block.is_fluid() && matches!(block.get_sprite(), None | Some(SpriteKind::Empty))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn materials_are_found_from_their_blocks() {
+        for material in MATERIALS.iter() {
+            assert_eq!(material_of_block(material.block()), Some(material));
+            assert_eq!(material_of_item(material.item), Some(material));
+        }
+        let generated = Block::new(BlockKind::Rock, Rgb::new(121, 117, 112));
+        assert_eq!(material_of_block(generated), None);
+    }
+}
@@ -71,6 +71,10 @@ lazy_static! {
         .iter()
         .map(|s| s.to_string())
         .collect();
+    static ref ATTACH_POINTS: Vec<String> = comp::AttachPoint::ALL
+        .iter()
+        .map(|p| p.name().to_string())
+        .collect();
     static ref SKILL_TREES: Vec<String> = vec!["general", "sword", "axe", "hammer", "bow", "staff", "sceptre", "mining"]
         .iter()
         .map(|s| s.to_string())
@@ -126,12 +130,17 @@ lazy_static! {
     .collect();
 
     static ref WEATHERS: Vec<String> = vec![
-        "clear", "cloudy", "rain", "wind", "storm"
+        "clear", "cloudy", "rain", "snow", "wind", "storm"
     ]
     .iter()
     .map(|s| s.to_string())
     .collect();
 
+    static ref BORDER_SHAPES: Vec<String> = vec!["circle", "rect", "off"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
     pub static ref BUFF_PARSER: HashMap<String, BuffKind> = {
         let string_from_buff = |kind| match kind {
             BuffKind::Burning => "burning",
@@ -152,6 +161,7 @@ lazy_static! {
             BuffKind::Ensnared => "ensnared",
             BuffKind::Poisoned => "poisoned",
             BuffKind::Hastened => "hastened",
+            BuffKind::Rested => "rested",
         };
         let mut buff_parser = HashMap::new();
         BuffKind::iter().for_each(|kind| {buff_parser.insert(string_from_buff(kind).to_string(), kind);});
@@ -237,6 +247,8 @@ pub enum ServerChatCommand {
     Airship,
     Alias,
     ApplyBuff,
+    Attach,
+    Audit,
     Ban,
     BattleMode,
     BattleModeForce,
@@ -246,6 +258,8 @@ pub enum ServerChatCommand {
     BuildAreaList,
     BuildAreaRemove,
     Campfire,
+    CharacterExport,
+    CharacterImport,
     DebugColumn,
     DisconnectAllPlayers,
     DropAll,
@@ -259,6 +273,13 @@ pub enum ServerChatCommand {
     GroupKick,
     GroupLeave,
     GroupPromote,
+    Guild,
+    GuildCreate,
+    GuildDemote,
+    GuildInvite,
+    GuildKick,
+    GuildLeave,
+    GuildPromote,
     Health,
     Help,
     Home,
@@ -277,13 +298,16 @@ pub enum ServerChatCommand {
     Object,
     PermitBuild,
     Players,
+    Portal,
     Region,
     ReloadChunks,
     RemoveLights,
     RevokeBuild,
     RevokeBuildAll,
+    Rollback,
     Safezone,
     Say,
+    Schedule,
     ServerPhysics,
     SetMotd,
     Ship,
@@ -291,6 +315,7 @@ pub enum ServerChatCommand {
     SkillPoint,
     SkillPreset,
     Spawn,
+    Spectate,
     Sudo,
     Tell,
     Time,
@@ -307,6 +332,7 @@ pub enum ServerChatCommand {
     DeleteLocation,
     WeatherZone,
     Lightning,
+    WorldBorder,
 }
 
 impl ServerChatCommand {
@@ -341,6 +367,21 @@ impl ServerChatCommand {
                 "Cast a buff on player",
                 Some(Admin),
             ),
+            ServerChatCommand::Attach => cmd(
+                vec![Enum("point", ATTACH_POINTS.clone(), Optional)],
+                "Attaches the nearest NPC or object to a point of your body, or lets go of the \
+                 ones attached to you (if not given)",
+                Some(Admin),
+            ),
+            ServerChatCommand::Audit => cmd(
+                vec![
+                    Enum("kind", vec!["player".to_owned()], Required),
+                    Any("name", Required),
+                    Integer("count", 10, Optional),
+                ],
+                "Shows the latest recorded events caused by or targeting a player",
+                Some(Moderator),
+            ),
             ServerChatCommand::Ban => cmd(
                 vec![
                     PlayerName(Required),
@@ -401,6 +442,17 @@ impl ServerChatCommand {
                 Some(Admin),
             ),
             ServerChatCommand::Campfire => cmd(vec![], "Spawns a campfire", Some(Admin)),
+            ServerChatCommand::CharacterExport => cmd(
+                vec![PlayerName(Optional)],
+                "Exports the current character of a player to a signed file, so that it can be \
+                 imported on another server",
+                Some(Admin),
+            ),
+            ServerChatCommand::CharacterImport => cmd(
+                vec![Any("file", Required)],
+                "Imports one of your characters exported from another server",
+                None,
+            ),
             ServerChatCommand::DebugColumn => cmd(
                 vec![Integer("x", 15000, Required), Integer("y", 15000, Required)],
                 "Prints some debug information about a column",
@@ -416,7 +468,11 @@ impl ServerChatCommand {
                 "Drops all your items on the ground",
                 Some(Moderator),
             ),
-            ServerChatCommand::Dummy => cmd(vec![], "Spawns a training dummy", Some(Admin)),
+            ServerChatCommand::Dummy => cmd(
+                vec![Enum("action", vec!["reset".to_owned()], Optional)],
+                "Spawns a training dummy, or resets the damage meters of the ones around",
+                Some(Admin),
+            ),
             ServerChatCommand::Explosion => cmd(
                 vec![Float("radius", 5.0, Required)],
                 "Explodes the ground around you",
@@ -463,6 +519,33 @@ impl ServerChatCommand {
                 "Promote a player to group leader",
                 None,
             ),
+            ServerChatCommand::Guild => {
+                cmd(vec![Message(Optional)], "Send messages to your guild", None)
+            },
+            ServerChatCommand::GuildCreate => {
+                cmd(vec![Message(Required)], "Found a new guild", None)
+            },
+            ServerChatCommand::GuildDemote => cmd(
+                vec![Any("name", Required)],
+                "Lower the rank of a guild member",
+                None,
+            ),
+            ServerChatCommand::GuildInvite => cmd(
+                vec![PlayerName(Required)],
+                "Invite a player to join your guild",
+                None,
+            ),
+            ServerChatCommand::GuildKick => cmd(
+                vec![Any("name", Required)],
+                "Remove a member from your guild",
+                None,
+            ),
+            ServerChatCommand::GuildLeave => cmd(vec![], "Leave your guild", None),
+            ServerChatCommand::GuildPromote => cmd(
+                vec![Any("name", Required)],
+                "Raise the rank of a guild member, promoting an officer hands over the lead",
+                None,
+            ),
             ServerChatCommand::Health => cmd(
                 vec![Integer("hp", 100, Required)],
                 "Set your current health",
@@ -560,6 +643,23 @@ impl ServerChatCommand {
                 Some(Admin),
             ),
             ServerChatCommand::Players => cmd(vec![], "Lists players currently online", None),
+            ServerChatCommand::Portal => cmd(
+                vec![
+                    Enum(
+                        "action",
+                        ["create", "remove", "link", "unlink"]
+                            .iter()
+                            .map(|s| s.to_string())
+                            .collect(),
+                        Required,
+                    ),
+                    Any("name", Required),
+                    Any("other portal", Optional),
+                ],
+                "Creates a portal at your position, removes one, or links two portals together so \
+                 that players can travel between them",
+                Some(Admin),
+            ),
             ServerChatCommand::ReloadChunks => cmd(
                 vec![],
                 "Reloads all chunks loaded on the server",
@@ -580,6 +680,16 @@ impl ServerChatCommand {
                 "Revokes all build area permissions for player",
                 Some(Admin),
             ),
+            ServerChatCommand::Rollback => cmd(
+                vec![
+                    Any("username", Required),
+                    Any("duration", Required),
+                    Boolean("dry run", "false".to_string(), Optional),
+                ],
+                "Reverts the blocks a player changed in build areas within the given duration \
+                 (e.g. 2h) over the next few seconds, pass true for dry run to only count them",
+                Some(Moderator),
+            ),
             ServerChatCommand::Region => cmd(
                 vec![Message(Optional)],
                 "Send messages to everyone in your region of the world",
@@ -595,6 +705,11 @@ impl ServerChatCommand {
                 "Send messages to everyone within shouting distance",
                 None,
             ),
+            ServerChatCommand::Schedule => cmd(
+                vec![],
+                "Lists the scheduled tasks of the server and when they run next",
+                Some(Admin),
+            ),
             ServerChatCommand::ServerPhysics => cmd(
                 vec![
                     PlayerName(Required),
@@ -643,6 +758,11 @@ impl ServerChatCommand {
                 "Spawn a test entity",
                 Some(Admin),
             ),
+            ServerChatCommand::Spectate => cmd(
+                vec![PlayerName(Optional)],
+                "Follow a player with the spectator camera, or stop following them without a name",
+                Some(Moderator),
+            ),
             ServerChatCommand::Sudo => cmd(
                 vec![PlayerName(Required), SubCommand],
                 "Run command as if you were another player",
@@ -713,6 +833,17 @@ impl ServerChatCommand {
             ServerChatCommand::Lightning => {
                 cmd(vec![], "Lightning strike at current position", Some(Admin))
             },
+            ServerChatCommand::WorldBorder => cmd(
+                vec![
+                    Enum("shape", BORDER_SHAPES.clone(), Required),
+                    Float("radius or width", 1000.0, Optional),
+                    Float("height", 1000.0, Optional),
+                    Float("seconds", 60.0, Optional),
+                ],
+                "Put the world border around you: 'circle <radius> [seconds]' or 'rect <width> \
+                 <height> [seconds]', moving it there over the seconds given. 'off' removes it.",
+                Some(Admin),
+            ),
         }
     }
 
@@ -723,6 +854,8 @@ impl ServerChatCommand {
             ServerChatCommand::Airship => "airship",
             ServerChatCommand::Alias => "alias",
             ServerChatCommand::ApplyBuff => "buff",
+            ServerChatCommand::Attach => "attach",
+            ServerChatCommand::Audit => "audit",
             ServerChatCommand::Ban => "ban",
             ServerChatCommand::BattleMode => "battlemode",
             ServerChatCommand::BattleModeForce => "battlemode_force",
@@ -732,6 +865,8 @@ impl ServerChatCommand {
             ServerChatCommand::BuildAreaList => "build_area_list",
             ServerChatCommand::BuildAreaRemove => "build_area_remove",
             ServerChatCommand::Campfire => "campfire",
+            ServerChatCommand::CharacterExport => "character_export",
+            ServerChatCommand::CharacterImport => "character_import",
             ServerChatCommand::DebugColumn => "debug_column",
             ServerChatCommand::DisconnectAllPlayers => "disconnect_all_players",
             ServerChatCommand::DropAll => "dropall",
@@ -745,6 +880,13 @@ impl ServerChatCommand {
             ServerChatCommand::GroupKick => "group_kick",
             ServerChatCommand::GroupPromote => "group_promote",
             ServerChatCommand::GroupLeave => "group_leave",
+            ServerChatCommand::Guild => "guild",
+            ServerChatCommand::GuildCreate => "guild_create",
+            ServerChatCommand::GuildDemote => "guild_demote",
+            ServerChatCommand::GuildInvite => "guild_invite",
+            ServerChatCommand::GuildKick => "guild_kick",
+            ServerChatCommand::GuildLeave => "guild_leave",
+            ServerChatCommand::GuildPromote => "guild_promote",
             ServerChatCommand::Health => "health",
             ServerChatCommand::JoinFaction => "join_faction",
             ServerChatCommand::Help => "help",
@@ -763,13 +905,16 @@ impl ServerChatCommand {
             ServerChatCommand::Object => "object",
             ServerChatCommand::PermitBuild => "permit_build",
             ServerChatCommand::Players => "players",
+            ServerChatCommand::Portal => "portal",
             ServerChatCommand::Region => "region",
             ServerChatCommand::ReloadChunks => "reload_chunks",
             ServerChatCommand::RemoveLights => "remove_lights",
             ServerChatCommand::RevokeBuild => "revoke_build",
             ServerChatCommand::RevokeBuildAll => "revoke_build_all",
+            ServerChatCommand::Rollback => "rollback",
             ServerChatCommand::Safezone => "safezone",
             ServerChatCommand::Say => "say",
+            ServerChatCommand::Schedule => "schedule",
             ServerChatCommand::ServerPhysics => "server_physics",
             ServerChatCommand::SetMotd => "set_motd",
             ServerChatCommand::Ship => "ship",
@@ -777,6 +922,7 @@ impl ServerChatCommand {
             ServerChatCommand::SkillPoint => "skill_point",
             ServerChatCommand::SkillPreset => "skill_preset",
             ServerChatCommand::Spawn => "spawn",
+            ServerChatCommand::Spectate => "spectate",
             ServerChatCommand::Sudo => "sudo",
             ServerChatCommand::Tell => "tell",
             ServerChatCommand::Time => "time",
@@ -793,6 +939,7 @@ impl ServerChatCommand {
             ServerChatCommand::DeleteLocation => "delete_location",
             ServerChatCommand::WeatherZone => "weather_zone",
             ServerChatCommand::Lightning => "lightning",
+            ServerChatCommand::WorldBorder => "world_border",
         }
     }
 
@@ -802,6 +949,7 @@ impl ServerChatCommand {
         Some(match self {
             ServerChatCommand::Faction => "f",
             ServerChatCommand::Group => "g",
+            ServerChatCommand::Guild => "gu",
             ServerChatCommand::Region => "r",
             ServerChatCommand::Say => "s",
             ServerChatCommand::Tell => "t",
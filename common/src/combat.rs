@@ -3,7 +3,11 @@ use crate::comp::buff::{Buff, BuffChange, BuffData, BuffKind, BuffSource};
 use crate::{
     comp::{
         inventory::{
-            item::{armor::Protection, tool::ToolKind, ItemDesc, ItemKind, MaterialStatManifest},
+            item::{
+                armor::{self, Protection},
+                tool::ToolKind,
+                ItemDesc, ItemKind, MaterialStatManifest,
+            },
             slot::EquipSlot,
         },
         skillset::SkillGroupKind,
@@ -1145,57 +1149,49 @@ pub fn combat_rating(
     combined_rating * body.combat_multiplier()
 }
 
+/// Sums the stats of all equipped armor
 #[cfg(not(target_arch = "wasm32"))]
-pub fn compute_crit_mult(inventory: Option<&Inventory>, msm: &MaterialStatManifest) -> f32 {
-    // Starts with a value of 1.25 when summing the stats from each armor piece, and
-    // defaults to a value of 1.25 if no inventory is equipped
-    inventory.map_or(1.25, |inv| {
+pub fn compute_armor_stats(
+    inventory: Option<&Inventory>,
+    msm: &MaterialStatManifest,
+) -> armor::Stats {
+    inventory.map_or_else(armor::Stats::none, |inv| {
         inv.equipped_items()
             .filter_map(|item| {
                 if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor.stats(msm).crit_power
+                    Some(armor.stats(msm))
                 } else {
                     None
                 }
             })
-            .fold(1.25, |a, b| a + b)
+            .fold(armor::Stats::none(), |a, b| a + b)
     })
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub fn compute_crit_mult(inventory: Option<&Inventory>, msm: &MaterialStatManifest) -> f32 {
+    // Crit power from armor is added to a base value of 1.25
+    1.25 + compute_armor_stats(inventory, msm)
+        .crit_power
+        .unwrap_or(0.0)
+}
+
 /// Computes the energy reward modifier from worn armor
 #[cfg(not(target_arch = "wasm32"))]
 pub fn compute_energy_reward_mod(inventory: Option<&Inventory>, msm: &MaterialStatManifest) -> f32 {
-    // Starts with a value of 1.0 when summing the stats from each armor piece, and
-    // defaults to a value of 1.0 if no inventory is present
-    inventory.map_or(1.0, |inv| {
-        inv.equipped_items()
-            .filter_map(|item| {
-                if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor.stats(msm).energy_reward
-                } else {
-                    None
-                }
-            })
-            .fold(1.0, |a, b| a + b)
-    })
+    // Energy reward from armor is added to a base value of 1.0
+    1.0 + compute_armor_stats(inventory, msm)
+        .energy_reward
+        .unwrap_or(0.0)
 }
 
 /// Computes the additive modifier that should be applied to max energy from the
 /// currently equipped items
 #[cfg(not(target_arch = "wasm32"))]
 pub fn compute_max_energy_mod(inventory: Option<&Inventory>, msm: &MaterialStatManifest) -> f32 {
-    // Defaults to a value of 0 if no inventory is present
-    inventory.map_or(0.0, |inv| {
-        inv.equipped_items()
-            .filter_map(|item| {
-                if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor.stats(msm).energy_max
-                } else {
-                    None
-                }
-            })
-            .sum()
-    })
+    compute_armor_stats(inventory, msm)
+        .energy_max
+        .unwrap_or(0.0)
 }
 
 /// Returns a value to be included as a multiplicative factor in perception
@@ -1221,17 +1217,7 @@ pub fn stealth_multiplier_from_items(
     inventory: Option<&Inventory>,
     msm: &MaterialStatManifest,
 ) -> f32 {
-    let stealth_sum = inventory.map_or(0.0, |inv| {
-        inv.equipped_items()
-            .filter_map(|item| {
-                if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor.stats(msm).stealth
-                } else {
-                    None
-                }
-            })
-            .sum()
-    });
+    let stealth_sum = compute_armor_stats(inventory, msm).stealth.unwrap_or(0.0);
 
     (1.0 / (1.0 + stealth_sum)).clamp(0.0, 1.0)
 }
@@ -1244,19 +1230,145 @@ pub fn compute_protection(
     inventory: Option<&Inventory>,
     msm: &MaterialStatManifest,
 ) -> Option<f32> {
-    inventory.map_or(Some(0.0), |inv| {
-        inv.equipped_items()
-            .filter_map(|item| {
-                if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor.stats(msm).protection
-                } else {
-                    None
-                }
-            })
-            .map(|protection| match protection {
-                Protection::Normal(protection) => Some(protection),
-                Protection::Invincible => None,
-            })
-            .sum::<Option<f32>>()
-    })
+    match compute_armor_stats(inventory, msm).protection {
+        Some(Protection::Normal(protection)) => Some(protection),
+        Some(Protection::Invincible) => None,
+        None => Some(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comp::{
+        inventory::{
+            item::{
+                armor::{Armor, ArmorKind, Friction, StatsSource},
+                tool::AbilityMap,
+                ItemBase, ItemDef, Quality,
+            },
+            slot::ArmorSlot,
+        },
+        Item,
+    };
+    use std::sync::Arc;
+
+    fn equip_armor(
+        inventory: &mut Inventory,
+        slot: ArmorSlot,
+        kind: ArmorKind,
+        stats: armor::Stats,
+    ) {
+        let item_def = ItemDef::new_test(
+            "common.items.testing.test_armor".to_string(),
+            ItemKind::Armor(Armor::new(kind, StatsSource::Direct(stats))),
+            Quality::Common,
+            Vec::new(),
+            0,
+        );
+        let item = Item::new_from_item_base(
+            ItemBase::Simple(Arc::new(item_def)),
+            Vec::new(),
+            &AbilityMap::load().read(),
+            &MaterialStatManifest::load().read(),
+        );
+        inventory.replace_loadout_item(EquipSlot::Armor(slot), Some(item));
+    }
+
+    #[test]
+    fn armor_stats_without_armor_are_none() {
+        let msm = &MaterialStatManifest::load().read();
+
+        assert_eq!(compute_armor_stats(None, msm), armor::Stats::none());
+        assert_eq!(
+            compute_armor_stats(Some(&Inventory::with_empty()), msm),
+            armor::Stats::none()
+        );
+        assert_eq!(compute_crit_mult(None, msm), 1.25);
+        assert_eq!(compute_energy_reward_mod(None, msm), 1.0);
+        assert_eq!(compute_max_energy_mod(None, msm), 0.0);
+        assert_eq!(compute_protection(None, msm), Some(0.0));
+    }
+
+    #[test]
+    fn armor_stats_are_combined() {
+        let msm = &MaterialStatManifest::load().read();
+        let mut inventory = Inventory::with_empty();
+        equip_armor(
+            &mut inventory,
+            ArmorSlot::Head,
+            ArmorKind::Head,
+            armor::Stats {
+                protection: Some(Protection::Normal(10.0)),
+                crit_power: Some(0.5),
+                ..armor::Stats::none()
+            },
+        );
+        equip_armor(
+            &mut inventory,
+            ArmorSlot::Chest,
+            ArmorKind::Chest,
+            armor::Stats {
+                protection: Some(Protection::Normal(20.0)),
+                energy_max: Some(5.0),
+                energy_reward: Some(0.25),
+                ..armor::Stats::none()
+            },
+        );
+        equip_armor(
+            &mut inventory,
+            ArmorSlot::Feet,
+            ArmorKind::Foot,
+            armor::Stats {
+                stealth: Some(1.0),
+                ground_contact: Friction::Ski,
+                ..armor::Stats::none()
+            },
+        );
+
+        assert_eq!(compute_armor_stats(Some(&inventory), msm), armor::Stats {
+            protection: Some(Protection::Normal(30.0)),
+            poise_resilience: None,
+            energy_max: Some(5.0),
+            energy_reward: Some(0.25),
+            crit_power: Some(0.5),
+            stealth: Some(1.0),
+            ground_contact: Friction::Ski,
+        });
+        assert_eq!(compute_crit_mult(Some(&inventory), msm), 1.75);
+        assert_eq!(compute_energy_reward_mod(Some(&inventory), msm), 1.25);
+        assert_eq!(compute_max_energy_mod(Some(&inventory), msm), 5.0);
+        assert_eq!(compute_protection(Some(&inventory), msm), Some(30.0));
+        assert_eq!(stealth_multiplier_from_items(Some(&inventory), msm), 0.5);
+    }
+
+    #[test]
+    fn invincible_armor_piece_makes_the_set_invincible() {
+        let msm = &MaterialStatManifest::load().read();
+        let mut inventory = Inventory::with_empty();
+        equip_armor(
+            &mut inventory,
+            ArmorSlot::Head,
+            ArmorKind::Head,
+            armor::Stats {
+                protection: Some(Protection::Normal(10.0)),
+                ..armor::Stats::none()
+            },
+        );
+        equip_armor(
+            &mut inventory,
+            ArmorSlot::Chest,
+            ArmorKind::Chest,
+            armor::Stats {
+                protection: Some(Protection::Invincible),
+                ..armor::Stats::none()
+            },
+        );
+
+        assert_eq!(
+            compute_armor_stats(Some(&inventory), msm).protection,
+            Some(Protection::Invincible)
+        );
+        assert_eq!(compute_protection(Some(&inventory), msm), None);
+    }
 }
@@ -19,6 +19,7 @@ use crate::{
         },
         Body, CharacterState, LightEmitter, StateUpdate,
     },
+    resources::Time,
     states::{
         behavior::JoinData,
         utils::{AbilityInfo, StageSection},
@@ -32,6 +33,9 @@ use specs::{Component, DerefFlaggedStorage};
 use std::{convert::TryFrom, time::Duration};
 
 pub const MAX_ABILITIES: usize = 5;
+/// Seconds an auxiliary ability can't be used again for after it was used, per
+/// point of energy it costs, so that the strongest ones can't be chained
+pub const COOLDOWN_PER_ENERGY: f32 = 0.05;
 pub type AuxiliaryKey = (Option<ToolKind>, Option<ToolKind>);
 
 // TODO: Potentially look into storing previous ability sets for weapon
@@ -231,6 +235,55 @@ pub enum AbilityInput {
     Auxiliary(usize),
 }
 
+/// When the auxiliary abilities were last used, so that they can't be used
+/// again before their cooldown is over. Only players have them, the abilities
+/// of NPCs have no cooldown.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AbilityCooldowns {
+    /// Time each ability was last used at and how long its cooldown lasts, in
+    /// seconds
+    slots: [Option<(Time, f32)>; MAX_ABILITIES],
+}
+
+impl Component for AbilityCooldowns {
+    type Storage = DerefFlaggedStorage<Self, specs::VecStorage<Self>>;
+}
+
+impl AbilityCooldowns {
+    pub fn start(&mut self, index: usize, time: Time, duration: f32) {
+        if let Some(slot) = self.slots.get_mut(index) {
+            *slot = (duration > 0.0).then(|| (time, duration));
+        }
+    }
+
+    /// Share of the cooldown of the ability still to wait for, `None` if it
+    /// can be used
+    pub fn remaining(&self, index: usize, time: Time) -> Option<f32> {
+        let (used_at, duration) = (*self.slots.get(index)?)?;
+        let left = 1.0 - (time.0 - used_at.0) as f32 / duration;
+        (left > 0.0).then(|| left.min(1.0))
+    }
+
+    /// Moves the cooldowns to another clock, as time isn't synced between the
+    /// client and the server. The latest ability used is taken as used at
+    /// `now`.
+    pub fn rebase(&mut self, now: Time) {
+        let latest = self
+            .slots
+            .iter()
+            .flatten()
+            .map(|(used_at, _)| used_at.0)
+            .fold(None, |latest: Option<f64>, used_at| {
+                Some(latest.map_or(used_at, |latest| latest.max(used_at)))
+            });
+        if let Some(latest) = latest {
+            for (used_at, _) in self.slots.iter_mut().flatten() {
+                used_at.0 += now.0 - latest;
+            }
+        }
+    }
+}
+
 #[derive(Copy, Clone, Serialize, Deserialize, Debug)]
 pub enum Ability {
     ToolPrimary,
@@ -404,7 +457,8 @@ impl From<&CharacterState> for CharacterAbilityType {
             | CharacterState::UseItem(_)
             | CharacterState::SpriteInteract(_)
             | CharacterState::Skate(_)
-            | CharacterState::Wallrun(_) => Self::Other,
+            | CharacterState::Wallrun(_)
+            | CharacterState::Grapple(_) => Self::Other,
         }
     }
 }
@@ -619,6 +673,13 @@ pub enum CharacterAbility {
         recover_duration: f32,
         ori_modifier: f32,
     },
+    Grapple {
+        energy_cost: f32,
+        range: f32,
+        hook_speed: f32,
+        reel_speed: f32,
+        swing_accel: f32,
+    },
 }
 
 impl Default for CharacterAbility {
@@ -669,7 +730,8 @@ impl CharacterAbility {
             | CharacterAbility::ChargedMelee { energy_cost, .. }
             | CharacterAbility::Shockwave { energy_cost, .. }
             | CharacterAbility::BasicBlock { energy_cost, .. }
-            | CharacterAbility::SelfBuff { energy_cost, .. } => {
+            | CharacterAbility::SelfBuff { energy_cost, .. }
+            | CharacterAbility::Grapple { energy_cost, .. } => {
                 update.energy.try_change_by(-*energy_cost).is_ok()
             },
             // Consumes energy within state, so value only checked before entering state
@@ -1082,10 +1144,26 @@ impl CharacterAbility {
                 *play_duration /= stats.speed;
                 *recover_duration /= stats.speed;
             },
+            Grapple {
+                ref mut energy_cost,
+                ref mut range,
+                ref mut hook_speed,
+                ref mut reel_speed,
+                swing_accel: _,
+            } => {
+                *energy_cost /= stats.energy_efficiency;
+                *range *= stats.range;
+                *hook_speed *= stats.speed;
+                *reel_speed *= stats.speed;
+            },
         }
         self
     }
 
+    /// How long the ability can't be used again for once used from an
+    /// auxiliary slot, in seconds
+    pub fn cooldown(&self) -> f32 { self.get_energy_cost() * COOLDOWN_PER_ENERGY }
+
     pub fn get_energy_cost(&self) -> f32 {
         use CharacterAbility::*;
         match self {
@@ -1101,7 +1179,8 @@ impl CharacterAbility {
             | Shockwave { energy_cost, .. }
             | BasicAura { energy_cost, .. }
             | BasicBlock { energy_cost, .. }
-            | SelfBuff { energy_cost, .. } => *energy_cost,
+            | SelfBuff { energy_cost, .. }
+            | Grapple { energy_cost, .. } => *energy_cost,
             BasicBeam { energy_drain, .. } => {
                 if *energy_drain > f32::EPSILON {
                     1.0
@@ -2244,6 +2323,25 @@ impl From<(&CharacterAbility, AbilityInfo, &JoinData<'_>)> for CharacterState {
                 stage_section: StageSection::Buildup,
                 exhausted: false,
             }),
+            CharacterAbility::Grapple {
+                energy_cost: _,
+                range,
+                hook_speed,
+                reel_speed,
+                swing_accel,
+            } => CharacterState::Grapple(grapple::Data {
+                static_data: grapple::StaticData {
+                    range: *range,
+                    hook_speed: *hook_speed,
+                    reel_speed: *reel_speed,
+                    swing_accel: *swing_accel,
+                    ability_info,
+                },
+                timer: Duration::default(),
+                hook: grapple::hand_pos(data.pos.0, data.body),
+                dir: data.inputs.look_dir,
+                rope: None,
+            }),
         }
     }
 }
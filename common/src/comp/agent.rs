@@ -352,6 +352,12 @@ pub enum SoundKind {
     Explosion,
     Beam,
     Shockwave,
+    /// Creatures of the same species as the caller hearing this join the
+    /// fight against the target
+    CallForHelp {
+        caller: Body,
+        target: EcsEntity,
+    },
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -379,6 +385,7 @@ impl Target {
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, EnumIter)]
 pub enum TimerAction {
     Interact,
+    CallForHelp,
 }
 
 /// A time used for managing agent-related timeouts. The timer is designed to
@@ -0,0 +1,90 @@
+//! Entities carried by others: torches held in a hand, crates carried around,
+//! pets sitting on a shoulder or banners worn on the back. The attached entity
+//! follows the point of the body of the one carrying it instead of moving by
+//! itself.
+
+use super::Body;
+use crate::uid::Uid;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DerefFlaggedStorage, VecStorage};
+use vek::*;
+
+/// Point of the body other entities can be attached to
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AttachPoint {
+    /// Where the entity stands
+    Origin,
+    Head,
+    Back,
+    MainHand,
+    OffHand,
+    Shoulder,
+}
+
+impl AttachPoint {
+    pub const ALL: [Self; 6] = [
+        Self::Origin,
+        Self::Head,
+        Self::Back,
+        Self::MainHand,
+        Self::OffHand,
+        Self::Shoulder,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Origin => "origin",
+            Self::Head => "head",
+            Self::Back => "back",
+            Self::MainHand => "main_hand",
+            Self::OffHand => "off_hand",
+            Self::Shoulder => "shoulder",
+        }
+    }
+
+    /// Where the point is on the body, relative to where the entity stands and
+    /// facing forwards. The figures of the clients use the bones of the
+    /// skeleton instead when there is one matching the point.
+    pub fn offset(self, body: &Body) -> Vec3<f32> {
+        let height = body.height();
+        let radius = body.max_radius();
+        match self {
+            Self::Origin => Vec3::zero(),
+            Self::Head => Vec3::new(0.0, 0.0, height),
+            Self::Back => Vec3::new(0.0, -radius * 0.5, height * 0.65),
+            Self::MainHand => Vec3::new(radius * 0.8, radius * 0.3, height * 0.45),
+            Self::OffHand => Vec3::new(-radius * 0.8, radius * 0.3, height * 0.45),
+            Self::Shoulder => Vec3::new(radius * 0.6, 0.0, height * 0.85),
+        }
+    }
+}
+
+/// Anchors the entity to a point of the body of another one
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Attached {
+    /// Entity the entity is attached to
+    pub to: Uid,
+    pub point: AttachPoint,
+    /// Offset from the point, facing forwards
+    pub offset: Vec3<f32>,
+}
+
+impl Attached {
+    pub fn new(to: Uid, point: AttachPoint) -> Self {
+        Self {
+            to,
+            point,
+            offset: Vec3::zero(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_offset(mut self, offset: Vec3<f32>) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+impl Component for Attached {
+    type Storage = DerefFlaggedStorage<Self, VecStorage<Self>>;
+}
@@ -48,6 +48,10 @@ pub enum BuffKind {
     /// Strength scales strength of both effects linearly. 0.5 is a 50%
     /// increase, 1.0 is a 100% increase.
     Hastened,
+    /// Applied when waking up after sleeping in a bed
+    /// Strength scales maximum health and energy linearly. 0.1 is a 10%
+    /// increase.
+    Rested,
     // Debuffs
     /// Does damage to a creature over time
     /// Strength should be the DPS of the debuff
@@ -95,7 +99,8 @@ impl BuffKind {
             | BuffKind::IncreaseMaxHealth
             | BuffKind::Invulnerability
             | BuffKind::ProtectingWard
-            | BuffKind::Hastened => true,
+            | BuffKind::Hastened
+            | BuffKind::Rested => true,
             BuffKind::Bleeding
             | BuffKind::Cursed
             | BuffKind::Burning
@@ -366,6 +371,19 @@ impl Buff {
                 ],
                 data.duration,
             ),
+            BuffKind::Rested => (
+                vec![
+                    BuffEffect::MaxHealthModifier {
+                        value: 1.0 + data.strength,
+                        kind: ModifierKind::Fractional,
+                    },
+                    BuffEffect::MaxEnergyModifier {
+                        value: 1.0 + data.strength,
+                        kind: ModifierKind::Fractional,
+                    },
+                ],
+                data.duration,
+            ),
         };
         Buff {
             kind,
@@ -15,6 +15,7 @@ use serde::{Deserialize, Serialize};
 use specs::{Component, DerefFlaggedStorage};
 use std::collections::BTreeMap;
 use strum::Display;
+use vek::Vec3;
 
 /// Data returned from character behavior fn's to Character Behavior System.
 pub struct StateUpdate {
@@ -28,6 +29,8 @@ pub struct StateUpdate {
     pub should_strafe: bool,
     pub queued_inputs: BTreeMap<InputKind, InputAttr>,
     pub removed_inputs: Vec<InputKind>,
+    /// Auxiliary ability used, whose cooldown starts, with its duration
+    pub started_cooldown: Option<(usize, f32)>,
 }
 
 pub struct OutputEvents<'a> {
@@ -58,6 +61,7 @@ impl From<&JoinData<'_>> for StateUpdate {
             character: data.character.clone(),
             queued_inputs: BTreeMap::new(),
             removed_inputs: Vec::new(),
+            started_cooldown: None,
         }
     }
 }
@@ -127,6 +131,9 @@ pub enum CharacterState {
     Skate(skate::Data),
     /// Play music instrument
     Music(music::Data),
+    /// Throws a grappling hook, then hangs and swings from its rope once it
+    /// caught on the terrain
+    Grapple(grapple::Data),
 }
 
 impl CharacterState {
@@ -150,6 +157,7 @@ impl CharacterState {
                 | CharacterState::SelfBuff(_)
                 | CharacterState::Blink(_)
                 | CharacterState::Music(_)
+                | CharacterState::Grapple(_)
                 | CharacterState::BasicSummon(_)
                 | CharacterState::SpriteSummon(_)
                 | CharacterState::Roll(roll::Data {
@@ -346,6 +354,7 @@ impl CharacterState {
             CharacterState::SpriteInteract(data) => data.behavior(j, output_events),
             CharacterState::Skate(data) => data.behavior(j, output_events),
             CharacterState::Music(data) => data.behavior(j, output_events),
+            CharacterState::Grapple(data) => data.behavior(j, output_events),
         }
     }
 
@@ -394,6 +403,15 @@ impl CharacterState {
             CharacterState::SpriteInteract(data) => data.handle_event(j, output_events, action),
             CharacterState::Skate(data) => data.handle_event(j, output_events, action),
             CharacterState::Music(data) => data.handle_event(j, output_events, action),
+            CharacterState::Grapple(data) => data.handle_event(j, output_events, action),
+        }
+    }
+
+    /// Where the character hangs from a rope, and how long the rope is
+    pub fn rope(&self) -> Option<(Vec3<f32>, f32)> {
+        match self {
+            CharacterState::Grapple(data) => data.rope.map(|rope| (data.hook, rope)),
+            _ => None,
         }
     }
 
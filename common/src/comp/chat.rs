@@ -1,10 +1,15 @@
 use crate::{
-    comp::{group::Group, BuffKind},
+    comp::{group::Group, BuffKind, Item},
     uid::Uid,
 };
 use serde::{Deserialize, Serialize};
 use specs::{Component, DenseVecStorage};
-use std::time::{Duration, Instant};
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+use strum::{EnumIter, IntoEnumIterator};
+use vek::Vec2;
 
 /// A player's current chat mode. These are chat types that can only be sent by
 /// the player.
@@ -20,6 +25,8 @@ pub enum ChatMode {
     Group(Group),
     /// Talk to your faction
     Faction(String),
+    /// Talk to the members of your guild
+    Guild(String),
     /// Talk to every player on the server
     World,
 }
@@ -37,9 +44,14 @@ impl ChatMode {
             ChatMode::Region => ChatType::Region(from),
             ChatMode::Group(group) => ChatType::Group(from, *group),
             ChatMode::Faction(faction) => ChatType::Faction(from, faction.clone()),
+            ChatMode::Guild(guild) => ChatType::Guild(from, guild.clone()),
             ChatMode::World => ChatType::World(from),
         };
-        UnresolvedChatMsg { chat_type, message }
+        UnresolvedChatMsg {
+            chat_type,
+            message,
+            links: Vec::new(),
+        }
     }
 }
 
@@ -89,6 +101,8 @@ pub enum ChatType<G> {
     GroupMeta(G),
     /// Server notifications to a faction, such as player join/leave
     FactionMeta(String),
+    /// Server notifications to a guild, such as members joining or leaving
+    GuildMeta(String),
     /// One-on-one chat (from, to)
     Tell(Uid, Uid),
     /// Chat with nearby players
@@ -97,6 +111,8 @@ pub enum ChatType<G> {
     Group(Uid, G),
     /// Factional chat
     Faction(Uid, String),
+    /// Guild chat
+    Guild(Uid, String),
     /// Regional chat
     Region(Uid),
     /// World chat
@@ -122,6 +138,7 @@ impl<G> ChatType<G> {
         GenericChatMsg {
             chat_type: self,
             message: msg.into(),
+            links: Vec::new(),
         }
     }
 
@@ -132,12 +149,14 @@ impl<G> ChatType<G> {
             ChatType::CommandInfo => None,
             ChatType::CommandError => None,
             ChatType::FactionMeta(_) => None,
+            ChatType::GuildMeta(_) => None,
             ChatType::GroupMeta(_) => None,
             ChatType::Kill(_, _) => None,
             ChatType::Tell(u, _t) => Some(*u),
             ChatType::Say(u) => Some(*u),
             ChatType::Group(u, _s) => Some(*u),
             ChatType::Faction(u, _s) => Some(*u),
+            ChatType::Guild(u, _s) => Some(*u),
             ChatType::Region(u) => Some(*u),
             ChatType::World(u) => Some(*u),
             ChatType::Npc(u, _r) => Some(*u),
@@ -155,23 +174,214 @@ impl<G> ChatType<G> {
             | ChatType::CommandInfo
             | ChatType::CommandError
             | ChatType::FactionMeta(_)
+            | ChatType::GuildMeta(_)
             | ChatType::GroupMeta(_)
             | ChatType::Npc(_, _)
             | ChatType::NpcSay(_, _)
             | ChatType::NpcTell(_, _, _)
             | ChatType::Meta
             | ChatType::Kill(_, _) => None,
-            ChatType::Tell(_, _) | ChatType::Group(_, _) | ChatType::Faction(_, _) => Some(true),
+            ChatType::Tell(_, _)
+            | ChatType::Group(_, _)
+            | ChatType::Faction(_, _)
+            | ChatType::Guild(_, _) => Some(true),
             ChatType::Say(_) | ChatType::Region(_) | ChatType::World(_) => Some(false),
         }
     }
 }
 
+/// Longest chat message players can send, in bytes
+pub const MAX_BYTES_CHAT_MSG: usize = 256;
+
+/// Cleans up a chat message written by a player. Control characters become
+/// spaces or are removed, invisible formatting characters (such as
+/// bidirectional overrides) are removed, and zero-width (non-)joiners, which
+/// some scripts and emoji sequences need, are only kept one at a time between
+/// visible characters.
+pub fn normalize_chat_msg(msg: &str) -> String { normalize_chat_text(msg).trim().to_string() }
+
+/// Same as [`normalize_chat_msg`], without trimming the whitespace around the
+/// text, so parts of a message can be normalized separately
+fn normalize_chat_text(msg: &str) -> String {
+    let mut normalized = String::with_capacity(msg.len());
+    let mut joiner = None;
+    for c in msg.chars() {
+        match c {
+            '\u{200C}' | '\u{200D}' => {
+                if normalized
+                    .chars()
+                    .last()
+                    .map_or(false, |c| !c.is_whitespace())
+                {
+                    joiner.get_or_insert(c);
+                }
+            },
+            c if c.is_control() => {
+                joiner = None;
+                if c.is_whitespace() {
+                    normalized.push(' ');
+                }
+            },
+            c if is_invisible_format(c) => {},
+            c => {
+                if let Some(joiner) = joiner.take().filter(|_| !c.is_whitespace()) {
+                    normalized.push(joiner);
+                }
+                normalized.push(c);
+            },
+        }
+    }
+    normalized
+}
+
+fn is_invisible_format(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{180E}'
+            | '\u{200B}'
+            | '\u{200E}'
+            | '\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206F}'
+            | '\u{FEFF}'
+            | '\u{FFF9}'..='\u{FFFB}'
+    )
+}
+
+/// Part of a chat message players can interact with. Players write it in the
+/// chat input as `[kind:value]`, and it is sent along the message, over its
+/// label.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChatLink {
+    /// Item definition id, hovering the link describes the item
+    Item(String),
+    /// Player alias, clicking the link starts a whisper to them
+    Player(String),
+    /// World position, clicking the link places a map marker there
+    Pos(Vec2<i32>),
+}
+
+impl ChatLink {
+    /// How the link is written in the chat input
+    pub fn markup(&self) -> String {
+        match self {
+            Self::Item(id) => format!("[item:{}]", id),
+            Self::Player(alias) => format!("[player:{}]", alias),
+            Self::Pos(pos) => format!("[pos:{},{}]", pos.x, pos.y),
+        }
+    }
+
+    /// What the link reads as in the message
+    pub fn label(&self) -> String {
+        match self {
+            Self::Item(id) => Item::new_from_asset(id)
+                .map_or_else(|_| id.clone(), |item| item.name().into_owned()),
+            Self::Player(alias) => alias.clone(),
+            Self::Pos(pos) => format!("{}, {}", pos.x, pos.y),
+        }
+    }
+
+    /// Reads the link from the markup between the brackets
+    fn parse(markup: &str) -> Option<Self> {
+        let (kind, value) = markup.split_once(':')?;
+        if value.is_empty() || value.contains('[') {
+            return None;
+        }
+        match kind {
+            "item" if !value.contains(char::is_whitespace) => Some(Self::Item(value.to_owned())),
+            "player" => Some(Self::Player(value.to_owned())),
+            "pos" => {
+                let (x, y) = value.split_once(',')?;
+                Some(Self::Pos(Vec2::new(
+                    x.trim().parse().ok()?,
+                    y.trim().parse().ok()?,
+                )))
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Link over the label written in a chat message
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatLinkSpan {
+    /// Byte range of the label in the message
+    pub range: Range<usize>,
+    pub link: ChatLink,
+}
+
+/// Most links a single message can have, the others are dropped
+pub const MAX_CHAT_LINKS: usize = 8;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChatSpan<'a> {
+    Text(&'a str),
+    Link(ChatLink),
+}
+
+/// Splits a chat input into its text and the links written as markup,
+/// brackets which are not links staying in the text
+pub fn parse_chat_spans(message: &str) -> Vec<ChatSpan<'_>> {
+    let mut spans = Vec::new();
+    // Start of the text not in a span yet
+    let mut text_start = 0;
+    let mut search_start = 0;
+    while let Some(open) = message[search_start..].find('[').map(|i| search_start + i) {
+        let link = message[open + 1..].find(']').and_then(|len| {
+            Some((
+                ChatLink::parse(&message[open + 1..open + 1 + len])?,
+                open + len + 2,
+            ))
+        });
+        match link {
+            Some((link, end)) => {
+                if open > text_start {
+                    spans.push(ChatSpan::Text(&message[text_start..open]));
+                }
+                spans.push(ChatSpan::Link(link));
+                text_start = end;
+                search_start = end;
+            },
+            None => search_start = open + 1,
+        }
+    }
+    if text_start < message.len() {
+        spans.push(ChatSpan::Text(&message[text_start..]));
+    }
+    spans
+}
+
+/// Replaces the links written as markup in a chat input by their label in
+/// brackets, returning the message to send and the links over it
+pub fn resolve_chat_links(input: &str) -> (String, Vec<ChatLinkSpan>) {
+    let mut message = String::with_capacity(input.len());
+    let mut links = Vec::new();
+    for span in parse_chat_spans(input) {
+        match span {
+            ChatSpan::Link(link) if links.len() < MAX_CHAT_LINKS => {
+                let start = message.len();
+                message.push_str(&format!("[{}]", link.label()));
+                links.push(ChatLinkSpan {
+                    range: start..message.len(),
+                    link,
+                });
+            },
+            ChatSpan::Link(link) => message.push_str(&link.markup()),
+            ChatSpan::Text(text) => message.push_str(text),
+        }
+    }
+    (message, links)
+}
+
 // Stores chat text, type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenericChatMsg<G> {
     pub chat_type: ChatType<G>,
     pub message: String,
+    /// Links over parts of the message, in order
+    pub links: Vec<ChatLinkSpan>,
 }
 
 pub type ChatMsg = GenericChatMsg<String>;
@@ -185,17 +395,96 @@ impl<G> GenericChatMsg<G> {
 
     pub fn npc(uid: Uid, message: String) -> Self {
         let chat_type = ChatType::Npc(uid, rand::random());
-        Self { chat_type, message }
+        Self::new(chat_type, message)
     }
 
     pub fn npc_say(uid: Uid, message: String) -> Self {
         let chat_type = ChatType::NpcSay(uid, rand::random());
-        Self { chat_type, message }
+        Self::new(chat_type, message)
     }
 
     pub fn npc_tell(from: Uid, to: Uid, message: String) -> Self {
         let chat_type = ChatType::NpcTell(from, to, rand::random());
-        Self { chat_type, message }
+        Self::new(chat_type, message)
+    }
+
+    pub fn new(chat_type: ChatType<G>, message: String) -> Self {
+        Self {
+            chat_type,
+            message,
+            links: Vec::new(),
+        }
+    }
+
+    pub fn with_links(mut self, links: Vec<ChatLinkSpan>) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Cleans up a message written by a player like [`normalize_chat_msg`],
+    /// keeping its links over the same labels. Links out of the message, over
+    /// another one or past [`MAX_CHAT_LINKS`] are dropped.
+    pub fn normalize(&mut self) {
+        let mut links = std::mem::take(&mut self.links);
+        links.sort_by_key(|span| span.range.start);
+        let mut normalized = String::with_capacity(self.message.len());
+        // End of the message normalized so far
+        let mut end = 0;
+        for ChatLinkSpan { range, link } in links {
+            let label = match self.message.get(range.clone()) {
+                Some(label) if range.start >= end && self.links.len() < MAX_CHAT_LINKS => {
+                    normalize_chat_text(label)
+                },
+                _ => continue,
+            };
+            // The label is trimmed, so trimming the whole message doesn't cut into it
+            let label = label.trim();
+            if label.is_empty() {
+                continue;
+            }
+            normalized.push_str(&normalize_chat_text(&self.message[end..range.start]));
+            let start = normalized.len();
+            normalized.push_str(label);
+            self.links.push(ChatLinkSpan {
+                range: start..normalized.len(),
+                link,
+            });
+            end = range.end;
+        }
+        normalized.push_str(&normalize_chat_text(&self.message[end..]));
+
+        let trimmed = normalized.len() - normalized.trim_start().len();
+        for span in &mut self.links {
+            span.range = span.range.start - trimmed..span.range.end - trimmed;
+        }
+        self.message = normalized.trim().to_string();
+    }
+
+    /// Rewrites the label of each link from what it links to, as the sender
+    /// could otherwise make a link read as something else, like an item
+    /// linked under the name of another one. Links out of the message or over
+    /// another one are dropped.
+    pub fn relabel_links(&mut self) {
+        let mut links = std::mem::take(&mut self.links);
+        links.sort_by_key(|span| span.range.start);
+        let mut relabeled = String::with_capacity(self.message.len());
+        // End of the message relabeled so far
+        let mut end = 0;
+        for ChatLinkSpan { range, link } in links {
+            if range.start < end || self.message.get(range.clone()).is_none() {
+                continue;
+            }
+            relabeled.push_str(&self.message[end..range.start]);
+            let start = relabeled.len();
+            relabeled.push_str(&format!("[{}]", link.label()));
+            self.links.push(ChatLinkSpan {
+                range: start..relabeled.len(),
+                link,
+            });
+            end = range.end;
+        }
+        relabeled.push_str(&self.message[end..]);
+        self.message = relabeled;
     }
 
     pub fn map_group<T>(self, mut f: impl FnMut(G) -> T) -> GenericChatMsg<T> {
@@ -205,12 +494,14 @@ impl<G> GenericChatMsg<G> {
             ChatType::CommandInfo => ChatType::CommandInfo,
             ChatType::CommandError => ChatType::CommandError,
             ChatType::FactionMeta(a) => ChatType::FactionMeta(a),
+            ChatType::GuildMeta(a) => ChatType::GuildMeta(a),
             ChatType::GroupMeta(g) => ChatType::GroupMeta(f(g)),
             ChatType::Kill(a, b) => ChatType::Kill(a, b),
             ChatType::Tell(a, b) => ChatType::Tell(a, b),
             ChatType::Say(a) => ChatType::Say(a),
             ChatType::Group(a, g) => ChatType::Group(a, f(g)),
             ChatType::Faction(a, b) => ChatType::Faction(a, b),
+            ChatType::Guild(a, b) => ChatType::Guild(a, b),
             ChatType::Region(a) => ChatType::Region(a),
             ChatType::World(a) => ChatType::World(a),
             ChatType::Npc(a, b) => ChatType::Npc(a, b),
@@ -222,6 +513,7 @@ impl<G> GenericChatMsg<G> {
         GenericChatMsg {
             chat_type,
             message: self.message,
+            links: self.links,
         }
     }
 
@@ -252,12 +544,14 @@ impl<G> GenericChatMsg<G> {
             ChatType::CommandInfo => SpeechBubbleType::None,
             ChatType::CommandError => SpeechBubbleType::None,
             ChatType::FactionMeta(_) => SpeechBubbleType::None,
+            ChatType::GuildMeta(_) => SpeechBubbleType::None,
             ChatType::GroupMeta(_) => SpeechBubbleType::None,
             ChatType::Kill(_, _) => SpeechBubbleType::None,
             ChatType::Tell(_u, _) => SpeechBubbleType::Tell,
             ChatType::Say(_u) => SpeechBubbleType::Say,
             ChatType::Group(_u, _s) => SpeechBubbleType::Group,
             ChatType::Faction(_u, _s) => SpeechBubbleType::Faction,
+            ChatType::Guild(_u, _s) => SpeechBubbleType::Guild,
             ChatType::Region(_u) => SpeechBubbleType::Region,
             ChatType::World(_u) => SpeechBubbleType::World,
             ChatType::Npc(_u, _r) => SpeechBubbleType::None,
@@ -302,6 +596,7 @@ pub enum SpeechBubbleType {
     Region,
     Group,
     Faction,
+    Guild,
     World,
     // For NPCs
     Quest, // TODO not implemented
@@ -309,6 +604,50 @@ pub enum SpeechBubbleType {
     None,  // No icon (default for npcs)
 }
 
+/// Icon shown next to a speech bubble, written in the message as `:name:`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumIter)]
+pub enum Emote {
+    Alert,
+    Angry,
+    Love,
+    Music,
+    Sad,
+    Threat,
+}
+
+impl Emote {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Alert => "alert",
+            Self::Angry => "angry",
+            Self::Love => "love",
+            Self::Music => "music",
+            Self::Sad => "sad",
+            Self::Threat => "threat",
+        }
+    }
+
+    /// Takes the emotes out of the text of a speech bubble, returning the
+    /// first one with the text left
+    pub fn extract(message: &str) -> (Option<Self>, String) {
+        let mut emote = None;
+        let mut text = message.to_owned();
+        for candidate in Self::iter() {
+            let markup = format!(":{}:", candidate.name());
+            if let Some(i) = text.find(&markup) {
+                if emote.map_or(true, |(_, first)| i < first) {
+                    emote = Some((candidate, i));
+                }
+                text = text.replace(&markup, "");
+            }
+        }
+        (
+            emote.map(|(emote, _)| emote),
+            text.split_whitespace().collect::<Vec<_>>().join(" "),
+        )
+    }
+}
+
 /// Adds a speech bubble above the character
 pub struct SpeechBubble {
     pub message: SpeechBubbleMessage,
@@ -319,6 +658,19 @@ pub struct SpeechBubble {
 impl SpeechBubble {
     /// Default duration in seconds of speech bubbles
     pub const DEFAULT_DURATION: f64 = 5.0;
+    /// Seconds added to the default duration for each character of the
+    /// message, long ones taking longer to read
+    const DURATION_PER_CHAR: f64 = 0.05;
+    /// Longest a speech bubble is shown, however long its message
+    pub const MAX_DURATION: f64 = 12.0;
+
+    /// How long a message is shown, depending on its length
+    pub fn duration_for(message: &str) -> Duration {
+        Duration::from_secs_f64(
+            (Self::DEFAULT_DURATION + message.chars().count() as f64 * Self::DURATION_PER_CHAR)
+                .min(Self::MAX_DURATION),
+        )
+    }
 
     pub fn npc_new(i18n_key: &str, r: u16, icon: SpeechBubbleType) -> Self {
         let message = SpeechBubbleMessage::Localized(i18n_key.to_string(), r);
@@ -331,8 +683,8 @@ impl SpeechBubble {
     }
 
     pub fn player_new(message: &str, icon: SpeechBubbleType) -> Self {
+        let timeout = Instant::now() + Self::duration_for(message);
         let message = SpeechBubbleMessage::Plain(message.to_string());
-        let timeout = Instant::now() + Duration::from_secs_f64(SpeechBubble::DEFAULT_DURATION);
         Self {
             message,
             icon,
@@ -350,3 +702,123 @@ impl SpeechBubble {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_chat_msg_strips_invisible_characters() {
+        assert_eq!(normalize_chat_msg("  hello\tworld\n"), "hello world");
+        assert_eq!(normalize_chat_msg("\u{202E}evil\u{200B}\u{200B}"), "evil");
+        assert_eq!(normalize_chat_msg("\u{200B}\u{FEFF}\u{2060}"), "");
+        // Joiners are kept once between visible characters only
+        assert_eq!(
+            normalize_chat_msg("\u{200D}👨\u{200D}\u{200D}👩 \u{200D}x"),
+            "👨\u{200D}👩 x"
+        );
+        assert_eq!(normalize_chat_msg("日本語のチャット"), "日本語のチャット");
+    }
+
+    #[test]
+    fn emotes_are_taken_out_of_bubbles() {
+        assert_eq!(
+            Emote::extract("watch out :threat: :alert: there"),
+            (Some(Emote::Threat), "watch out there".to_owned())
+        );
+        assert_eq!(
+            Emote::extract("no :emote: here"),
+            (None, "no :emote: here".to_owned())
+        );
+        assert!(
+            SpeechBubble::duration_for(&"a".repeat(1000)).as_secs_f64()
+                <= SpeechBubble::MAX_DURATION
+        );
+    }
+
+    #[test]
+    fn chat_links_are_parsed_from_markup() {
+        let pos = ChatLink::Pos(Vec2::new(120, -45));
+        let message = format!(
+            "meet at {} with [a] [item:x y] {}",
+            pos.markup(),
+            "[player:Tam]"
+        );
+        assert_eq!(parse_chat_spans(&message), vec![
+            ChatSpan::Text("meet at "),
+            ChatSpan::Link(pos),
+            ChatSpan::Text(" with [a] [item:x y] "),
+            ChatSpan::Link(ChatLink::Player("Tam".to_owned())),
+        ]);
+        assert_eq!(parse_chat_spans("[[item:common.items.food.apple]]"), vec![
+            ChatSpan::Text("["),
+            ChatSpan::Link(ChatLink::Item("common.items.food.apple".to_owned())),
+            ChatSpan::Text("]"),
+        ]);
+    }
+
+    #[test]
+    fn chat_links_keep_their_label_when_normalized() {
+        let (message, links) = resolve_chat_links("  meet\u{202E} [player:Tam] at [pos:1,2]\n");
+        assert_eq!(message, "  meet\u{202E} [Tam] at [1, 2]\n");
+        assert_eq!(&message[links[0].range.clone()], "[Tam]");
+
+        let mut msg = ChatType::World(Uid(0))
+            .chat_msg(message)
+            .with_links(links.into_iter().rev().collect());
+        // Links over another one or out of the message are dropped
+        msg.links.push(ChatLinkSpan {
+            range: 10..20,
+            link: ChatLink::Player("Zed".to_owned()),
+        });
+        msg.links.push(ChatLinkSpan {
+            range: 40..50,
+            link: ChatLink::Player("Zed".to_owned()),
+        });
+        msg.normalize();
+        assert_eq!(msg.message, "meet [Tam] at [1, 2]");
+        assert_eq!(
+            msg.links
+                .iter()
+                .map(|span| (&msg.message[span.range.clone()], &span.link))
+                .collect::<Vec<_>>(),
+            vec![
+                ("[Tam]", &ChatLink::Player("Tam".to_owned())),
+                ("[1, 2]", &ChatLink::Pos(Vec2::new(1, 2))),
+            ]
+        );
+    }
+
+    #[test]
+    fn chat_links_are_relabeled_from_what_they_link() {
+        let apple = ChatLink::Item("common.items.food.apple".to_owned());
+        let mut msg = ChatType::World(Uid(0))
+            .chat_msg("selling [Legendary sword] at [9, 9]".to_owned())
+            .with_links(vec![
+                ChatLinkSpan {
+                    range: 29..35,
+                    link: ChatLink::Pos(Vec2::new(1, 2)),
+                },
+                ChatLinkSpan {
+                    range: 8..25,
+                    link: apple.clone(),
+                },
+                ChatLinkSpan {
+                    range: 40..50,
+                    link: ChatLink::Player("Zed".to_owned()),
+                },
+            ]);
+        msg.relabel_links();
+        assert_eq!(msg.message, "selling [Apple] at [1, 2]");
+        assert_eq!(
+            msg.links
+                .iter()
+                .map(|span| (&msg.message[span.range.clone()], &span.link))
+                .collect::<Vec<_>>(),
+            vec![
+                ("[Apple]", &apple),
+                ("[1, 2]", &ChatLink::Pos(Vec2::new(1, 2))),
+            ]
+        );
+    }
+}
@@ -6,7 +6,8 @@ use crate::{
             slot::{EquipSlot, InvSlotId, Slot},
         },
         invite::{InviteKind, InviteResponse},
-        BuffKind,
+        portal::PortalId,
+        BuffKind, MinigameAction,
     },
     trade::{TradeAction, TradeId},
     uid::Uid,
@@ -29,6 +30,20 @@ pub enum InventoryEvent {
         craft_event: CraftEvent,
         craft_sprite: Option<Vec3<i32>>,
     },
+    /// Places the building material in the slot as a block at the position
+    PlaceMaterial(InvSlotId, Vec3<i32>),
+    /// Takes back the building material placed at the position
+    RemoveMaterial(Vec3<i32>),
+    /// Builds the structure of the blueprint in the slot on top of the
+    /// position, out of the materials in the inventory
+    PlaceBlueprint(InvSlotId, Vec3<i32>),
+    /// Puts up the sign in the slot at the position, with the text written on
+    /// it
+    PlaceSign(InvSlotId, Vec3<i32>, String),
+    /// Rewrites the text of the sign at the position
+    WriteSign(Vec3<i32>, String),
+    /// Writes the text in the book in the slot
+    WriteBook(InvSlotId, String),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -38,12 +53,18 @@ pub enum InventoryAction {
     Use(Slot),
     Sort,
     Collect(Vec3<i32>),
+    /// Turns the block into farmland, with the hoe wielded
+    Till(Vec3<i32>),
+    /// Plants the seed in the slot on top of the farmland block
+    Plant(InvSlotId, Vec3<i32>),
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum InventoryManip {
     Pickup(Uid),
     Collect(Vec3<i32>),
+    Till(Vec3<i32>),
+    Plant(InvSlotId, Vec3<i32>),
     Use(Slot),
     Swap(Slot, Slot),
     SplitSwap(Slot, Slot),
@@ -55,6 +76,12 @@ pub enum InventoryManip {
         craft_sprite: Option<Vec3<i32>>,
     },
     SwapEquippedWeapons,
+    PlaceMaterial(InvSlotId, Vec3<i32>),
+    RemoveMaterial(Vec3<i32>),
+    PlaceBlueprint(InvSlotId, Vec3<i32>),
+    PlaceSign(InvSlotId, Vec3<i32>, String),
+    WriteSign(Vec3<i32>, String),
+    WriteBook(InvSlotId, String),
 }
 
 impl From<InventoryAction> for InventoryManip {
@@ -65,6 +92,8 @@ impl From<InventoryAction> for InventoryManip {
             InventoryAction::Drop(equip) => Self::Drop(Slot::Equip(equip)),
             InventoryAction::Sort => Self::Sort,
             InventoryAction::Collect(collect) => Self::Collect(collect),
+            InventoryAction::Till(pos) => Self::Till(pos),
+            InventoryAction::Plant(slot, pos) => Self::Plant(slot, pos),
         }
     }
 }
@@ -89,6 +118,12 @@ impl From<InventoryEvent> for InventoryManip {
                 craft_event,
                 craft_sprite,
             },
+            InventoryEvent::PlaceMaterial(slot, pos) => Self::PlaceMaterial(slot, pos),
+            InventoryEvent::RemoveMaterial(pos) => Self::RemoveMaterial(pos),
+            InventoryEvent::PlaceBlueprint(slot, pos) => Self::PlaceBlueprint(slot, pos),
+            InventoryEvent::PlaceSign(slot, pos, text) => Self::PlaceSign(slot, pos, text),
+            InventoryEvent::WriteSign(pos, text) => Self::WriteSign(pos, text),
+            InventoryEvent::WriteBook(slot, text) => Self::WriteBook(slot, text),
         }
     }
 }
@@ -142,6 +177,8 @@ pub enum ControlEvent {
     EnableLantern,
     DisableLantern,
     Interact(Uid),
+    /// Pick one of the choices of the dialogue with a NPC
+    Talk(Uid, usize),
     InitiateInvite(Uid, InviteKind),
     InviteResponse(InviteResponse),
     PerformTradeAction(TradeId, TradeAction),
@@ -151,6 +188,14 @@ pub enum ControlEvent {
     GroupManip(GroupManip),
     RemoveBuff(BuffKind),
     Respawn,
+    /// Lie down in the bed at the given position
+    Sleep(Vec3<i32>),
+    /// Touch a portal, discovering it
+    ActivatePortal(Uid),
+    /// Travel from a portal to a discovered one
+    UsePortal(Uid, PortalId),
+    /// Join, leave or play the game of a minigame entity
+    Minigame(Uid, MinigameAction),
     Utterance(UtteranceKind),
     ChangeAbility {
         slot: usize,
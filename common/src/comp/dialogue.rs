@@ -1,3 +1,6 @@
+use crate::assets::{self, Asset};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
 use vek::{Vec2, Vec3};
 
 use super::Item;
@@ -60,6 +63,63 @@ pub enum Subject {
     Work,
 }
 
+/// Subjects brought up with a NPC when reaching a node of its dialogue tree
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Topic {
+    Trade,
+    Mood,
+    Work,
+}
+
+impl From<Topic> for Subject {
+    fn from(topic: Topic) -> Self {
+        match topic {
+            Topic::Trade => Subject::Trade,
+            Topic::Mood => Subject::Mood,
+            Topic::Work => Subject::Work,
+        }
+    }
+}
+
+/// Dialogue of a NPC, loaded from `common.dialogue`. The server resolves the
+/// choices picked by players and sends them the ones they can pick next.
+#[derive(Clone, Debug, Deserialize)]
+pub struct DialogueTree {
+    /// Node the dialogue starts at
+    pub start: String,
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DialogueNode {
+    /// Brought up with the NPC when the node is reached
+    #[serde(default)]
+    pub topic: Option<Topic>,
+    /// The dialogue ends at nodes without choices
+    pub choices: Vec<DialogueChoice>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct DialogueChoice {
+    /// Localization key of what the player says
+    pub text: String,
+    /// Node the choice leads to, the dialogue ends without one
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+impl DialogueTree {
+    pub fn choice(&self, node: &str, choice: usize) -> Option<&DialogueChoice> {
+        self.nodes.get(node)?.choices.get(choice)
+    }
+}
+
+impl Asset for DialogueTree {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
 /// Context of why a NPC has a specific mood (good, neutral, bad, ...)
 #[derive(Clone, Debug)]
 pub enum MoodContext {
@@ -122,3 +182,33 @@ impl MoodContext {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::AssetExt;
+
+    #[test]
+    fn dialogue_trees_are_valid() {
+        let trees = assets::load_dir::<DialogueTree>("common.dialogue", true).unwrap();
+        for id in trees.ids() {
+            let tree = DialogueTree::load_expect(id).read();
+            assert!(tree.nodes.contains_key(&tree.start), "{} has no start", id);
+            for (name, node) in tree.nodes.iter() {
+                for next in node
+                    .choices
+                    .iter()
+                    .filter_map(|choice| choice.next.as_ref())
+                {
+                    assert!(
+                        tree.nodes.contains_key(next),
+                        "{}: {} leads to the missing node {}",
+                        id,
+                        name,
+                        next
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use crate::comp::{biped_small, Alignment, Body};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage, DerefFlaggedStorage, VecStorage};
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+
+/// Lowest and highest standing a character can have with a faction
+pub const MIN_STANDING: i32 = -100;
+pub const MAX_STANDING: i32 = 100;
+/// Members of a faction attack characters with a standing below this
+pub const HOSTILE_STANDING: i32 = -30;
+/// Characters with a standing above this are considered friends
+pub const FRIENDLY_STANDING: i32 = 30;
+/// Standing lost with a faction when killing one of its members
+const KILL_PENALTY: i32 = 8;
+/// Standing gained with the enemies of the faction of a killed creature
+const KILL_BONUS: i32 = 2;
+
+/// Group of NPCs that share their friends and enemies
+#[derive(
+    Copy, Clone, Debug, Display, EnumString, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum Faction {
+    Villagers,
+    Cultists,
+    Gnarlings,
+    /// Creatures attacking anyone on sight
+    Beasts,
+}
+
+impl Faction {
+    /// Faction of a NPC spawned with the given alignment, wild, tame and owned
+    /// creatures don't belong to any faction
+    pub fn from_alignment(alignment: Alignment, body: &Body) -> Option<Self> {
+        match alignment {
+            Alignment::Npc => Some(Faction::Villagers),
+            Alignment::Enemy => Some(match body {
+                Body::Humanoid(_) => Faction::Cultists,
+                Body::BipedSmall(body) if body.species == biped_small::Species::Gnarling => {
+                    Faction::Gnarlings
+                },
+                _ => Faction::Beasts,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn hostile_towards(self, other: Faction) -> bool {
+        match (self, other) {
+            (Faction::Villagers, Faction::Villagers) => false,
+            (Faction::Villagers, _) | (_, Faction::Villagers) => true,
+            _ => false,
+        }
+    }
+
+    /// Standing of characters who never dealt with the faction
+    pub fn default_standing(self) -> i32 {
+        match self {
+            Faction::Villagers => 0,
+            Faction::Cultists | Faction::Gnarlings | Faction::Beasts => MIN_STANDING,
+        }
+    }
+}
+
+impl Component for Faction {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Attitude {
+    Hostile,
+    Neutral,
+    Friendly,
+}
+
+/// Standing of a character with each faction, earned through quests and kills
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Reputation {
+    standings: HashMap<Faction, i32>,
+}
+
+impl Reputation {
+    pub fn from_standings(standings: impl IntoIterator<Item = (Faction, i32)>) -> Self {
+        Self {
+            standings: standings
+                .into_iter()
+                .map(|(faction, standing)| (faction, standing.clamp(MIN_STANDING, MAX_STANDING)))
+                .collect(),
+        }
+    }
+
+    /// Standings which were changed from the default ones
+    pub fn standings(&self) -> impl Iterator<Item = (Faction, i32)> + '_ {
+        self.standings
+            .iter()
+            .map(|(faction, standing)| (*faction, *standing))
+    }
+
+    pub fn standing(&self, faction: Faction) -> i32 {
+        self.standings
+            .get(&faction)
+            .copied()
+            .unwrap_or_else(|| faction.default_standing())
+    }
+
+    pub fn change(&mut self, faction: Faction, amount: i32) {
+        let standing = self.standing(faction);
+        self.standings.insert(
+            faction,
+            standing
+                .saturating_add(amount)
+                .clamp(MIN_STANDING, MAX_STANDING),
+        );
+    }
+
+    pub fn attitude(&self, faction: Faction) -> Attitude {
+        let standing = self.standing(faction);
+        if standing < HOSTILE_STANDING {
+            Attitude::Hostile
+        } else if standing > FRIENDLY_STANDING {
+            Attitude::Friendly
+        } else {
+            Attitude::Neutral
+        }
+    }
+
+    /// Whether members of the faction attack this character
+    pub fn is_hostile(&self, faction: Faction) -> bool {
+        self.attitude(faction) == Attitude::Hostile
+    }
+
+    /// Killing a member of a faction angers it and pleases its enemies
+    pub fn record_kill(&mut self, victim: Faction) {
+        self.change(victim, -KILL_PENALTY);
+        for faction in Faction::iter().filter(|faction| faction.hostile_towards(victim)) {
+            self.change(faction, KILL_BONUS);
+        }
+    }
+}
+
+impl Component for Reputation {
+    type Storage = DerefFlaggedStorage<Self, VecStorage<Self>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn killing_villagers_makes_them_hostile() {
+        let mut reputation = Reputation::default();
+        assert!(!reputation.is_hostile(Faction::Villagers));
+        assert!(reputation.is_hostile(Faction::Cultists));
+
+        reputation.record_kill(Faction::Cultists);
+        assert_eq!(reputation.standing(Faction::Villagers), KILL_BONUS);
+        assert_eq!(reputation.standing(Faction::Cultists), MIN_STANDING);
+
+        for _ in 0..5 {
+            reputation.record_kill(Faction::Villagers);
+        }
+        assert!(reputation.is_hostile(Faction::Villagers));
+        // Their enemies like it, but not enough to stop attacking
+        assert_eq!(
+            reputation.standing(Faction::Gnarlings),
+            MIN_STANDING + 5 * KILL_BONUS
+        );
+        assert!(reputation.is_hostile(Faction::Gnarlings));
+    }
+
+    #[test]
+    fn standings_survive_a_round_trip_through_their_names() {
+        let mut reputation = Reputation::default();
+        reputation.record_kill(Faction::Cultists);
+        let names = reputation
+            .standings()
+            .map(|(faction, standing)| (faction.to_string(), standing))
+            .collect::<Vec<_>>();
+        let restored = Reputation::from_standings(
+            names
+                .iter()
+                .map(|(name, standing)| (name.parse().unwrap(), *standing)),
+        );
+        assert_eq!(restored, reputation);
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use slab::Slab;
 use specs::{Component, DerefFlaggedStorage, Join};
 use tracing::{error, warn};
+use vek::*;
 
 // Primitive group system
 // Shortcomings include:
@@ -44,6 +45,17 @@ pub enum Role {
     Pet,
 }
 
+/// State of a group member sent periodically to the rest of the group, so
+/// that members outside of the sync range can still be displayed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberStatus {
+    pub name: String,
+    pub health_fraction: f32,
+    pub energy_fraction: f32,
+    pub is_dead: bool,
+    pub pos: Vec3<f32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ChangeNotification<E> {
     // :D
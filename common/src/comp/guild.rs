@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest name a guild can have, in characters
+pub const MAX_GUILD_NAME_LEN: usize = 24;
+
+/// Rank of a guild member, deciding what they are allowed to do
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GuildRank {
+    Member,
+    Officer,
+    Leader,
+}
+
+impl GuildRank {
+    pub fn can_invite(self) -> bool { self >= GuildRank::Officer }
+
+    /// Officers can kick members, the leader can kick anyone
+    pub fn can_kick(self, other: GuildRank) -> bool { self >= GuildRank::Officer && self > other }
+
+    pub fn can_change_ranks(self) -> bool { self == GuildRank::Leader }
+
+    pub fn promoted(self) -> Self {
+        match self {
+            GuildRank::Member => GuildRank::Officer,
+            GuildRank::Officer | GuildRank::Leader => GuildRank::Leader,
+        }
+    }
+
+    pub fn demoted(self) -> Self {
+        match self {
+            GuildRank::Leader => GuildRank::Officer,
+            GuildRank::Officer | GuildRank::Member => GuildRank::Member,
+        }
+    }
+
+    pub fn i18n_key(self) -> &'static str {
+        match self {
+            GuildRank::Member => "hud-guild-rank-member",
+            GuildRank::Officer => "hud-guild-rank-officer",
+            GuildRank::Leader => "hud-guild-rank-leader",
+        }
+    }
+}
+
+/// Whether the name can be given to a new guild
+pub fn is_valid_guild_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_GUILD_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == ' ' || c == '_' || c == '-')
+        && name.trim() == name
+}
+
+/// Guild member as shown in the roster
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GuildMemberInfo {
+    pub name: String,
+    pub rank: GuildRank,
+    pub online: bool,
+}
+
+/// Guild of a player along with its full roster, sent to the player whenever
+/// it changes
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GuildInfo {
+    pub name: String,
+    pub rank: GuildRank,
+    pub members: Vec<GuildMemberInfo>,
+}
@@ -5,7 +5,7 @@ use crate::{
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
-    ops::{Mul, Sub},
+    ops::{Add, Mul, Sub},
 };
 use strum::{EnumIter, IntoEnumIterator};
 
@@ -99,7 +99,7 @@ pub struct Stats {
 }
 
 impl Stats {
-    fn none() -> Self {
+    pub fn none() -> Self {
         Stats {
             protection: None,
             poise_resilience: None,
@@ -135,6 +135,35 @@ impl Mul<f32> for Stats {
     }
 }
 
+/// Sums two sets of stats, where a missing stat counts as zero. Footwear is
+/// the only source of ground contact, so any non-normal ground contact wins.
+impl Add<Stats> for Stats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        fn add<T: Add<Output = T>>(a: Option<T>, b: Option<T>) -> Option<T> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            }
+        }
+
+        Self {
+            protection: add(self.protection, other.protection),
+            poise_resilience: add(self.poise_resilience, other.poise_resilience),
+            energy_max: add(self.energy_max, other.energy_max),
+            energy_reward: add(self.energy_reward, other.energy_reward),
+            crit_power: add(self.crit_power, other.crit_power),
+            stealth: add(self.stealth, other.stealth),
+            ground_contact: if other.ground_contact == Friction::Normal {
+                self.ground_contact
+            } else {
+                other.ground_contact
+            },
+        }
+    }
+}
+
 impl Sub<Stats> for Stats {
     type Output = Self;
 
@@ -167,6 +196,17 @@ impl Default for Protection {
     fn default() -> Self { Self::Normal(0.0) }
 }
 
+impl Add for Protection {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        match (self, other) {
+            (Protection::Normal(a), Protection::Normal(b)) => Protection::Normal(a + b),
+            _ => Protection::Invincible,
+        }
+    }
+}
+
 impl Sub for Protection {
     type Output = Self;
 
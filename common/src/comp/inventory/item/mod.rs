@@ -350,6 +350,9 @@ pub struct Item {
     slots: Vec<InvSlot>,
     item_config: Option<Box<ItemConfig>>,
     hash: u64,
+    /// Text written in the item by a player, such as the pages of a book
+    #[serde(default)]
+    text: Option<String>,
 }
 
 use std::hash::{Hash, Hasher};
@@ -648,6 +651,7 @@ impl PartialEq for Item {
         {
             self_def.item_definition_id == other_def.item_definition_id
                 && self.components == other.components
+                && self.text == other.text
         } else {
             false
         }
@@ -736,6 +740,7 @@ impl Item {
             // These fields are updated immediately below
             item_config: None,
             hash: 0,
+            text: None,
         };
         item.update_item_state(ability_map, msm);
         item
@@ -836,6 +841,7 @@ impl Item {
             "`new_item` has the same `item_def` and as an invariant, \
              self.set_amount(self.amount()) should always succeed.",
         );
+        new_item.text = self.text.clone();
         new_item.slots_mut().iter_mut().zip(self.slots()).for_each(
             |(new_item_slot, old_item_slot)| {
                 *new_item_slot = old_item_slot
@@ -1038,6 +1044,10 @@ impl Item {
 
     pub fn amount(&self) -> u32 { u32::from(self.amount) }
 
+    pub fn text(&self) -> Option<&str> { self.text.as_deref() }
+
+    pub fn set_text(&mut self, text: Option<String>) { self.text = text; }
+
     pub fn is_stackable(&self) -> bool {
         match &self.item_base {
             ItemBase::Simple(item_def) => item_def.is_stackable(),
@@ -28,7 +28,6 @@ pub enum SpecError {
 }
 
 #[derive(Debug)]
-#[cfg(test)]
 pub enum ValidationError {
     ItemAssetError(assets::Error),
     LoadoutAssetError(assets::Error),
@@ -79,7 +78,6 @@ impl ItemSpec {
     }
 
     // Check if ItemSpec is valid and can be turned into Item
-    #[cfg(test)]
     fn validate(&self) -> Result<(), ValidationError> {
         let mut rng = rand::thread_rng();
         match self {
@@ -143,7 +141,6 @@ impl Hands {
     }
 
     // Check if items in Hand are valid and can be turned into Item
-    #[cfg(test)]
     fn validate(&self) -> Result<(), ValidationError> {
         match self {
             Self::InHands((left, right)) => {
@@ -345,7 +342,6 @@ impl LoadoutSpec {
     //
     // Also we probably don't want garbage entries anyway, even if they are
     // unused.
-    #[cfg(test)]
     pub fn validate(&self, history: Vec<String>) -> Result<(), ValidationError> {
         // Helper function to traverse base.
         //
@@ -412,7 +408,6 @@ impl LoadoutSpec {
     //
     // TODO: add some intelligent checks,
     // e.g. that `head` key corresponds to Item with ItemKind::Head(_)
-    #[cfg(test)]
     fn validate_entries(&self) -> Result<(), ValidationError> {
         // Armor
         if let Some(item) = &self.head {
@@ -5,6 +5,7 @@ use specs::Component;
 pub enum InviteKind {
     Group,
     Trade,
+    Guild,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -54,6 +54,27 @@ impl Component for MapMarker {
     type Storage = specs::VecStorage<Self>;
 }
 
+/// Most named markers a player can share with their group
+pub const MAX_SHARED_MAP_MARKERS: usize = 32;
+/// Longest name of a shared marker, in characters
+pub const MAX_MAP_MARKER_NAME_LEN: usize = 32;
+
+/// Marker placed on the map by a player and given a name
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedMapMarker {
+    pub wpos: Vec2<i32>,
+    pub name: String,
+}
+
+/// Named markers a player shares with their group, kept server side for the
+/// members joining later
+#[derive(Clone, Debug, Default)]
+pub struct SharedMapMarkers(pub Vec<NamedMapMarker>);
+
+impl Component for SharedMapMarkers {
+    type Storage = specs::VecStorage<Self>;
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum MapMarkerChange {
     Update(Vec2<i32>),
@@ -64,5 +85,8 @@ pub enum MapMarkerChange {
 pub enum MapMarkerUpdate {
     Owned(MapMarkerChange),
     GroupMember(Uid, MapMarkerChange),
+    /// Named markers the group member shares, replacing the ones they shared
+    /// before
+    GroupMemberShared(Uid, Vec<NamedMapMarker>),
     ClearGroup,
 }
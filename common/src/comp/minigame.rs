@@ -0,0 +1,265 @@
+//! Games players gather around to play together, whose turn-based state is
+//! validated by the server and synced to the clients. Board games are the
+//! first of them, other interactables with a shared state changed by the
+//! players in turns (locks, puzzles) can be added as new kinds of `Game`.
+
+use crate::uid::Uid;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DerefFlaggedStorage, VecStorage};
+use std::fmt;
+
+/// Cells on each side of the board of three in a row
+pub const BOARD_SIDE: usize = 3;
+
+/// Rows, columns and diagonals of the board of three in a row
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MinigameAction {
+    /// Take a free seat at the game
+    Join,
+    /// Leave the seat, giving up the game in progress
+    Leave,
+    /// Make a move, whose meaning depends on the game
+    Play(u8),
+    /// Start a new game once the last one is over
+    Reset,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MinigameError {
+    NoFreeSeat,
+    AlreadySeated,
+    NotSeated,
+    WaitingForPlayers,
+    NotYourTurn,
+    IllegalMove,
+    GameOver,
+    GameNotOver,
+}
+
+impl fmt::Display for MinigameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NoFreeSeat => "All the seats are taken.",
+            Self::AlreadySeated => "You are already playing.",
+            Self::NotSeated => "You are not playing.",
+            Self::WaitingForPlayers => "Waiting for more players to join.",
+            Self::NotYourTurn => "It is not your turn.",
+            Self::IllegalMove => "This move isn't allowed.",
+            Self::GameOver => "The game is over.",
+            Self::GameNotOver => "The game isn't over yet.",
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    /// The player in the given seat won
+    Won(usize),
+    Draw,
+}
+
+/// The players take turns marking the cells of a square board, the first one
+/// to mark a whole row, column or diagonal wins
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreeInARow {
+    /// Seat of the player who marked each cell, row by row
+    pub cells: [Option<u8>; BOARD_SIDE * BOARD_SIDE],
+}
+
+impl ThreeInARow {
+    fn turn(&self) -> usize { self.cells.iter().flatten().count() % 2 }
+
+    fn outcome(&self) -> Option<Outcome> {
+        LINES
+            .iter()
+            .find_map(|line| {
+                let seat = self.cells[line[0]]?;
+                line.iter()
+                    .all(|cell| self.cells[*cell] == Some(seat))
+                    .then_some(Outcome::Won(seat as usize))
+            })
+            .or_else(|| {
+                self.cells
+                    .iter()
+                    .all(Option::is_some)
+                    .then_some(Outcome::Draw)
+            })
+    }
+
+    fn play(&mut self, seat: usize, cell: u8) -> Result<(), MinigameError> {
+        match self.cells.get_mut(cell as usize) {
+            Some(cell @ None) => {
+                *cell = Some(seat as u8);
+                Ok(())
+            },
+            _ => Err(MinigameError::IllegalMove),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Game {
+    ThreeInARow(ThreeInARow),
+}
+
+impl Game {
+    pub fn seats(&self) -> usize {
+        match self {
+            Self::ThreeInARow(_) => 2,
+        }
+    }
+
+    /// Seat of the player whose turn it is
+    pub fn turn(&self) -> usize {
+        match self {
+            Self::ThreeInARow(game) => game.turn(),
+        }
+    }
+
+    /// How the game ended, `None` while it goes on
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self {
+            Self::ThreeInARow(game) => game.outcome(),
+        }
+    }
+
+    fn play(&mut self, seat: usize, action: u8) -> Result<(), MinigameError> {
+        match self {
+            Self::ThreeInARow(game) => game.play(seat, action),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Self::ThreeInARow(game) => *game = ThreeInARow::default(),
+        }
+    }
+}
+
+/// Entity players gather around to play a game together
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Minigame {
+    pub seats: Vec<Option<Uid>>,
+    pub game: Game,
+}
+
+impl Minigame {
+    pub fn new(game: Game) -> Self {
+        Self {
+            seats: vec![None; game.seats()],
+            game,
+        }
+    }
+
+    pub fn seat_of(&self, player: Uid) -> Option<usize> {
+        self.seats.iter().position(|seat| *seat == Some(player))
+    }
+
+    /// Validates the action of the player and applies it
+    pub fn act(&mut self, player: Uid, action: MinigameAction) -> Result<(), MinigameError> {
+        let seat = self.seat_of(player);
+        match action {
+            MinigameAction::Join => {
+                if seat.is_some() {
+                    return Err(MinigameError::AlreadySeated);
+                }
+                let free = self
+                    .seats
+                    .iter_mut()
+                    .find(|seat| seat.is_none())
+                    .ok_or(MinigameError::NoFreeSeat)?;
+                *free = Some(player);
+            },
+            MinigameAction::Leave => {
+                let seat = seat.ok_or(MinigameError::NotSeated)?;
+                self.seats[seat] = None;
+                if self.game.outcome().is_none() {
+                    self.game.reset();
+                }
+            },
+            MinigameAction::Play(action) => {
+                let seat = seat.ok_or(MinigameError::NotSeated)?;
+                if self.game.outcome().is_some() {
+                    return Err(MinigameError::GameOver);
+                }
+                if self.seats.iter().any(Option::is_none) {
+                    return Err(MinigameError::WaitingForPlayers);
+                }
+                if self.game.turn() != seat {
+                    return Err(MinigameError::NotYourTurn);
+                }
+                self.game.play(seat, action)?;
+            },
+            MinigameAction::Reset => {
+                seat.ok_or(MinigameError::NotSeated)?;
+                if self.game.outcome().is_none() {
+                    return Err(MinigameError::GameNotOver);
+                }
+                self.game.reset();
+            },
+        }
+        Ok(())
+    }
+}
+
+impl Component for Minigame {
+    type Storage = DerefFlaggedStorage<Self, VecStorage<Self>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn players_take_turns_until_a_line_is_complete() {
+        let (a, b, c) = (Uid(1), Uid(2), Uid(3));
+        let mut minigame = Minigame::new(Game::ThreeInARow(ThreeInARow::default()));
+
+        assert_eq!(minigame.act(a, MinigameAction::Join), Ok(()));
+        assert_eq!(
+            minigame.act(a, MinigameAction::Play(4)),
+            Err(MinigameError::WaitingForPlayers)
+        );
+        assert_eq!(minigame.act(b, MinigameAction::Join), Ok(()));
+        assert_eq!(
+            minigame.act(c, MinigameAction::Join),
+            Err(MinigameError::NoFreeSeat)
+        );
+
+        assert_eq!(
+            minigame.act(b, MinigameAction::Play(4)),
+            Err(MinigameError::NotYourTurn)
+        );
+        for (player, cell) in [(a, 0), (b, 4), (a, 1), (b, 8)] {
+            assert_eq!(minigame.act(player, MinigameAction::Play(cell)), Ok(()));
+        }
+        assert_eq!(
+            minigame.act(a, MinigameAction::Play(8)),
+            Err(MinigameError::IllegalMove)
+        );
+        assert_eq!(
+            minigame.act(a, MinigameAction::Reset),
+            Err(MinigameError::GameNotOver)
+        );
+        assert_eq!(minigame.act(a, MinigameAction::Play(2)), Ok(()));
+        assert_eq!(minigame.game.outcome(), Some(Outcome::Won(0)));
+        assert_eq!(
+            minigame.act(b, MinigameAction::Play(5)),
+            Err(MinigameError::GameOver)
+        );
+
+        assert_eq!(minigame.act(b, MinigameAction::Reset), Ok(()));
+        assert_eq!(minigame.game.outcome(), None);
+    }
+}
@@ -4,6 +4,8 @@ pub mod ability;
 #[cfg(not(target_arch = "wasm32"))] pub mod agent;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod anchor;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod attachment;
 #[cfg(not(target_arch = "wasm32"))] pub mod aura;
 #[cfg(not(target_arch = "wasm32"))] pub mod beam;
 #[cfg(not(target_arch = "wasm32"))] pub mod body;
@@ -19,8 +21,11 @@ pub mod controller;
 pub mod dialogue;
 #[cfg(not(target_arch = "wasm32"))] mod energy;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod faction;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod fluid_dynamics;
 #[cfg(not(target_arch = "wasm32"))] pub mod group;
+#[cfg(not(target_arch = "wasm32"))] pub mod guild;
 mod health;
 #[cfg(not(target_arch = "wasm32"))] mod inputs;
 #[cfg(not(target_arch = "wasm32"))]
@@ -31,32 +36,38 @@ pub mod invite;
 #[cfg(not(target_arch = "wasm32"))] mod location;
 pub mod loot_owner;
 #[cfg(not(target_arch = "wasm32"))] pub mod melee;
+pub mod minigame;
 #[cfg(not(target_arch = "wasm32"))] mod misc;
 #[cfg(not(target_arch = "wasm32"))] pub mod ori;
 #[cfg(not(target_arch = "wasm32"))] pub mod pet;
 #[cfg(not(target_arch = "wasm32"))] mod phys;
 #[cfg(not(target_arch = "wasm32"))] mod player;
 #[cfg(not(target_arch = "wasm32"))] pub mod poise;
+pub mod portal;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod projectile;
+#[cfg(not(target_arch = "wasm32"))] pub mod quest;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod shockwave;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod skillset;
 #[cfg(not(target_arch = "wasm32"))] mod stats;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod training_dummy;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod visual;
 
 // Reexports
 #[cfg(not(target_arch = "wasm32"))]
 pub use self::{
     ability::{
-        Ability, AbilityInput, ActiveAbilities, CharacterAbility, CharacterAbilityType,
-        MAX_ABILITIES,
+        Ability, AbilityCooldowns, AbilityInput, ActiveAbilities, CharacterAbility,
+        CharacterAbilityType, MAX_ABILITIES,
     },
     admin::{Admin, AdminRole},
     agent::{Agent, Alignment, Behavior, BehaviorCapability, BehaviorState, PidController},
     anchor::Anchor,
+    attachment::{AttachPoint, Attached},
     aura::{Aura, AuraChange, AuraKind, Auras},
     beam::{Beam, BeamSegment},
     body::{
@@ -70,7 +81,8 @@ pub use self::{
     },
     character_state::{CharacterState, StateUpdate},
     chat::{
-        ChatMode, ChatMsg, ChatType, Faction, SpeechBubble, SpeechBubbleType, UnresolvedChatMsg,
+        ChatMode, ChatMsg, ChatType, Emote, Faction, SpeechBubble, SpeechBubbleType,
+        UnresolvedChatMsg,
     },
     combo::Combo,
     controller::{
@@ -78,6 +90,7 @@ pub use self::{
         InputKind, InventoryAction, InventoryEvent, InventoryManip, UtteranceKind,
     },
     energy::Energy,
+    faction::Reputation,
     fluid_dynamics::Fluid,
     group::Group,
     inputs::CanBuild,
@@ -91,26 +104,33 @@ pub use self::{
         slot, CollectFailedReason, Inventory, InventoryUpdate, InventoryUpdateEvent,
     },
     last::Last,
-    location::{MapMarker, MapMarkerChange, MapMarkerUpdate, Waypoint, WaypointArea},
+    location::{
+        MapMarker, MapMarkerChange, MapMarkerUpdate, NamedMapMarker, SharedMapMarkers, Waypoint,
+        WaypointArea, MAX_MAP_MARKER_NAME_LEN, MAX_SHARED_MAP_MARKERS,
+    },
     loot_owner::LootOwner,
     melee::{Melee, MeleeConstructor},
+    minigame::{Minigame, MinigameAction},
     misc::Object,
     ori::Ori,
     pet::Pet,
     phys::{
-        Collider, Density, ForceUpdate, Immovable, Mass, PhysicsState, Pos, PosVelOriDefer,
+        Asleep, Collider, Density, ForceUpdate, Immovable, Mass, PhysicsState, Pos, PosVelOriDefer,
         PreviousPhysCache, Scale, Sticky, Vel,
     },
     player::DisconnectReason,
     player::{AliasError, Player, MAX_ALIAS_LEN},
     poise::{Poise, PoiseChange, PoiseState},
+    portal::{DiscoveredPortals, Portal},
     projectile::{Projectile, ProjectileConstructor},
+    quest::QuestLog,
     shockwave::{Shockwave, ShockwaveHitEntities},
     skillset::{
         skills::{self, Skill},
         SkillGroup, SkillGroupKind, SkillSet,
     },
     stats::{Stats, StatsModifier},
+    training_dummy::{DamageReport, TrainingDummy},
     visual::{LightAnimation, LightEmitter},
 };
 
@@ -63,12 +63,23 @@ pub struct PreviousPhysCache {
     pub origins: Option<(Vec2<f32>, Vec2<f32>)>,
     pub pos: Option<Pos>,
     pub ori: Quaternion<f32>,
+    /// Ticks the entity has been at rest for in a row
+    pub rest_ticks: u32,
 }
 
 impl Component for PreviousPhysCache {
     type Storage = VecStorage<Self>;
 }
 
+/// Marks the entities left at rest away from the players, which the physics
+/// skips until something disturbs them
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct Asleep;
+
+impl Component for Asleep {
+    type Storage = NullStorage<Self>;
+}
+
 // Scale
 #[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Scale(pub f32);
@@ -0,0 +1,87 @@
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use specs::{Component, DerefFlaggedStorage, VecStorage};
+use std::collections::VecDeque;
+use vek::*;
+
+pub type PortalId = u32;
+
+/// Time players have to wait next to a portal before being teleported
+pub const PORTAL_CHANNEL_TIME: f64 = 5.0;
+/// Players moving further than this while waiting to be teleported stay where
+/// they are
+pub const MAX_CHANNEL_MOVEMENT: f32 = 1.0;
+
+/// Entity teleporting players to the portals linked to it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Portal {
+    pub id: PortalId,
+    pub name: String,
+}
+
+impl Component for Portal {
+    type Storage = DerefFlaggedStorage<Self, VecStorage<Self>>;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PortalInfo {
+    pub name: String,
+    pub pos: Vec3<f32>,
+    pub links: Vec<PortalId>,
+}
+
+/// Portals the player found, along with how they are linked together
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiscoveredPortals(pub HashMap<PortalId, PortalInfo>);
+
+impl DiscoveredPortals {
+    /// Portals which can be travelled to from the given one, going through
+    /// discovered portals only
+    pub fn destinations(&self, from: PortalId) -> HashSet<PortalId> {
+        let mut reached = HashSet::new();
+        if !self.0.contains_key(&from) {
+            return reached;
+        }
+        reached.insert(from);
+        let mut queue = VecDeque::from([from]);
+        while let Some(id) = queue.pop_front() {
+            for link in self.0.get(&id).into_iter().flat_map(|info| &info.links) {
+                if self.0.contains_key(link) && reached.insert(*link) {
+                    queue.push_back(*link);
+                }
+            }
+        }
+        reached.remove(&from);
+        reached
+    }
+}
+
+impl Component for DiscoveredPortals {
+    type Storage = DerefFlaggedStorage<Self, VecStorage<Self>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn travel_goes_through_discovered_portals_only() {
+        let portal = |links: &[PortalId]| PortalInfo {
+            name: String::new(),
+            pos: Vec3::zero(),
+            links: links.to_vec(),
+        };
+        // 1 - 2 - 3 - 4, 3 wasn't discovered
+        let mut discovered = DiscoveredPortals::default();
+        discovered.0.insert(1, portal(&[2]));
+        discovered.0.insert(2, portal(&[1, 3]));
+        discovered.0.insert(4, portal(&[3]));
+
+        assert_eq!(discovered.destinations(1), HashSet::from_iter([2]));
+        assert!(discovered.destinations(4).is_empty());
+        assert!(discovered.destinations(3).is_empty());
+
+        discovered.0.insert(3, portal(&[2, 4]));
+        assert_eq!(discovered.destinations(1), HashSet::from_iter([2, 3, 4]));
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{
+    assets::{self, Asset},
+    comp::faction::Faction,
+};
+use serde::{Deserialize, Serialize};
+use specs::{Component, DerefFlaggedStorage, VecStorage};
+use vek::*;
+
+/// How many quests a character can work on at the same time
+pub const MAX_ACTIVE_QUESTS: usize = 5;
+
+/// Something that has to be done to complete a quest
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    /// Kill `count` creatures with the given name
+    Kill { name: String, count: u32 },
+    /// Carry `count` of the item in the inventory, they are taken once the
+    /// quest is completed
+    Fetch { item: String, count: u32 },
+    /// Come within `radius` blocks of `pos`
+    Reach { pos: Vec2<f32>, radius: f32 },
+}
+
+impl Objective {
+    /// Progress needed to complete the objective
+    pub fn required(&self) -> u32 {
+        match self {
+            Objective::Kill { count, .. } | Objective::Fetch { count, .. } => *count,
+            Objective::Reach { .. } => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Reward {
+    #[serde(default)]
+    pub exp: u32,
+    /// Item definition ids and amounts
+    #[serde(default)]
+    pub items: Vec<(String, u32)>,
+    /// Change of standing with factions
+    #[serde(default)]
+    pub reputation: Vec<(Faction, i32)>,
+}
+
+/// Quest definition, loaded from `common.quests`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuestDef {
+    pub title: String,
+    pub description: String,
+    pub objectives: Vec<Objective>,
+    #[serde(default)]
+    pub reward: Reward,
+}
+
+impl Asset for QuestDef {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+/// Quest a character is working on
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActiveQuest {
+    /// Asset id of the `QuestDef`
+    pub id: String,
+    pub objectives: Vec<Objective>,
+    /// Progress for each objective
+    pub progress: Vec<u32>,
+}
+
+impl ActiveQuest {
+    pub fn new(id: String, def: &QuestDef) -> Self {
+        Self {
+            id,
+            objectives: def.objectives.clone(),
+            progress: vec![0; def.objectives.len()],
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.objectives
+            .iter()
+            .zip(&self.progress)
+            .all(|(objective, progress)| *progress >= objective.required())
+    }
+
+    /// Set the progress of every objective for which `f` returns some,
+    /// returning whether anything changed
+    fn update(&mut self, mut f: impl FnMut(&Objective, u32) -> Option<u32>) -> bool {
+        let mut changed = false;
+        for (objective, progress) in self.objectives.iter().zip(&mut self.progress) {
+            if let Some(new_progress) = f(objective, *progress) {
+                let new_progress = new_progress.min(objective.required());
+                changed |= new_progress != *progress;
+                *progress = new_progress;
+            }
+        }
+        changed
+    }
+}
+
+/// Quests of a character
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuestLog {
+    pub active: Vec<ActiveQuest>,
+    /// Ids of completed quests, they can't be started again
+    pub completed: Vec<String>,
+}
+
+impl QuestLog {
+    /// Whether the quest with the given id can be started
+    pub fn can_start(&self, id: &str) -> bool {
+        self.active.len() < MAX_ACTIVE_QUESTS
+            && !self.active.iter().any(|quest| quest.id == id)
+            && !self.completed.iter().any(|completed| completed == id)
+    }
+
+    /// Returns false if the quest can't be started
+    pub fn start(&mut self, id: String, def: &QuestDef) -> bool {
+        if self.can_start(&id) {
+            self.active.push(ActiveQuest::new(id, def));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Count a kill of a creature with the given name, returns whether any
+    /// quest progressed
+    pub fn record_kill(&mut self, victim: &str) -> bool {
+        self.active.iter_mut().fold(false, |changed, quest| {
+            quest.update(|objective, progress| match objective {
+                Objective::Kill { name, .. } if name == victim => Some(progress + 1),
+                _ => None,
+            }) || changed
+        })
+    }
+
+    /// Update fetch objectives with the amount of each item carried
+    pub fn update_fetch(&mut self, mut item_count: impl FnMut(&str) -> u32) -> bool {
+        self.active.iter_mut().fold(false, |changed, quest| {
+            quest.update(|objective, _| match objective {
+                Objective::Fetch { item, .. } => Some(item_count(item)),
+                _ => None,
+            }) || changed
+        })
+    }
+
+    /// Complete reach objectives close to `pos`
+    pub fn update_reach(&mut self, pos: Vec2<f32>) -> bool {
+        self.active.iter_mut().fold(false, |changed, quest| {
+            quest.update(|objective, progress| match objective {
+                Objective::Reach {
+                    pos: target,
+                    radius,
+                } if progress == 0 && target.distance_squared(pos) < radius.powi(2) => Some(1),
+                _ => None,
+            }) || changed
+        })
+    }
+
+    /// Remove completed quests from the active ones, remembering them as
+    /// completed
+    pub fn take_completed(&mut self) -> Vec<ActiveQuest> {
+        let (completed, active) = self
+            .active
+            .drain(..)
+            .partition::<Vec<_>, _>(ActiveQuest::is_complete);
+        self.active = active;
+        self.completed
+            .extend(completed.iter().map(|quest| quest.id.clone()));
+        completed
+    }
+}
+
+impl Component for QuestLog {
+    type Storage = DerefFlaggedStorage<Self, VecStorage<Self>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def() -> QuestDef {
+        QuestDef {
+            title: String::new(),
+            description: String::new(),
+            objectives: vec![
+                Objective::Kill {
+                    name: "Wolf".to_owned(),
+                    count: 2,
+                },
+                Objective::Fetch {
+                    item: "common.items.crafting_ing.leather.simple_leather".to_owned(),
+                    count: 3,
+                },
+                Objective::Reach {
+                    pos: Vec2::zero(),
+                    radius: 10.0,
+                },
+            ],
+            reward: Reward::default(),
+        }
+    }
+
+    #[test]
+    fn objectives_progress() {
+        let mut log = QuestLog::default();
+        assert!(log.start("test".to_owned(), &def()));
+        assert!(!log.start("test".to_owned(), &def()));
+
+        assert!(log.record_kill("Wolf"));
+        assert!(!log.record_kill("Bear"));
+        assert!(log.record_kill("Wolf"));
+        // Progress doesn't go past what's required
+        assert!(!log.record_kill("Wolf"));
+        assert!(log.update_fetch(|_| 5));
+        assert!(!log.update_reach(Vec2::new(20.0, 0.0)));
+        assert!(log.take_completed().is_empty());
+
+        assert!(log.update_reach(Vec2::new(5.0, 0.0)));
+        assert_eq!(log.take_completed().len(), 1);
+        assert!(log.active.is_empty());
+        assert!(!log.can_start("test"));
+    }
+
+    #[test]
+    fn quests_are_valid() {
+        use crate::{assets::AssetExt, comp::Item};
+
+        let quests = assets::load_dir::<QuestDef>("common.quests", true).unwrap();
+        for id in quests.ids() {
+            let def = QuestDef::load_expect(id).read();
+            assert!(!def.objectives.is_empty(), "{} has no objectives", id);
+            let items = def
+                .objectives
+                .iter()
+                .filter_map(|objective| match objective {
+                    Objective::Fetch { item, .. } => Some(item),
+                    _ => None,
+                })
+                .chain(def.reward.items.iter().map(|(item, _)| item));
+            for item in items {
+                assert!(
+                    Item::new_from_asset(item).is_ok(),
+                    "{} uses invalid item {}",
+                    id,
+                    item
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{resources::Time, uid::Uid};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use specs::{Component, HashMapStorage};
+use std::collections::VecDeque;
+
+/// Seconds over which the damage per second is measured
+pub const DPS_WINDOW: f64 = 10.0;
+/// Seconds without hitting the dummy after which the meter of an attacker
+/// starts over
+pub const RESET_DELAY: f64 = 15.0;
+
+/// Damage dealt to a training dummy by an attacker, sent to them while they
+/// keep hitting it
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DamageReport {
+    pub dummy: Uid,
+    /// Damage dealt since the attacker started hitting the dummy
+    pub total: f32,
+    /// Damage per second over the last `window` seconds
+    pub dps: f32,
+    pub window: f32,
+    pub hits: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+struct DamageMeter {
+    /// Time and damage of the hits within the window
+    recent: VecDeque<(f64, f32)>,
+    total: f32,
+    hits: u32,
+    started_at: f64,
+    last_hit: f64,
+    /// Whether the attacker was told about their latest hits
+    reported: bool,
+}
+
+/// A dummy which can't be killed, measuring the damage each attacker deals to
+/// it instead
+#[derive(Clone, Debug, Default)]
+pub struct TrainingDummy {
+    meters: HashMap<Uid, DamageMeter>,
+}
+
+impl TrainingDummy {
+    pub fn record(&mut self, attacker: Uid, time: Time, damage: f32) {
+        let meter = self.meters.entry(attacker).or_default();
+        if meter.hits == 0 || time.0 - meter.last_hit > RESET_DELAY {
+            *meter = DamageMeter {
+                started_at: time.0,
+                ..Default::default()
+            };
+        }
+        meter.recent.push_back((time.0, damage));
+        meter.total += damage;
+        meter.hits += 1;
+        meter.last_hit = time.0;
+        meter.reported = false;
+    }
+
+    /// Reports for the attackers who hit the dummy since they were last told,
+    /// forgetting the ones who stopped hitting it
+    pub fn reports(&mut self, dummy: Uid, time: Time) -> Vec<(Uid, DamageReport)> {
+        self.meters
+            .retain(|_, meter| time.0 - meter.last_hit <= RESET_DELAY);
+        self.meters
+            .iter_mut()
+            .filter(|(_, meter)| !meter.reported)
+            .map(|(attacker, meter)| {
+                meter.reported = true;
+                while meter
+                    .recent
+                    .front()
+                    .map_or(false, |(hit_time, _)| time.0 - hit_time > DPS_WINDOW)
+                {
+                    meter.recent.pop_front();
+                }
+                // Attacks which only just started are measured over the time they
+                // lasted so far
+                let window = (time.0 - meter.started_at).clamp(1.0, DPS_WINDOW);
+                let damage = meter.recent.iter().map(|(_, damage)| damage).sum::<f32>();
+                (*attacker, DamageReport {
+                    dummy,
+                    total: meter.total,
+                    dps: damage / window as f32,
+                    window: window as f32,
+                    hits: meter.hits,
+                })
+            })
+            .collect()
+    }
+
+    /// Forgets the damage dealt so far, so that every attacker starts over
+    pub fn reset(&mut self) { self.meters.clear(); }
+}
+
+impl Component for TrainingDummy {
+    type Storage = HashMapStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damage_is_measured_over_the_window() {
+        let (dummy, attacker) = (Uid(1), Uid(2));
+        let mut training_dummy = TrainingDummy::default();
+        for i in 0..5 {
+            training_dummy.record(attacker, Time(100.0 + i as f64), 20.0);
+        }
+        let reports = training_dummy.reports(dummy, Time(105.0));
+        assert_eq!(reports.len(), 1);
+        let (to, report) = reports[0];
+        assert_eq!(to, attacker);
+        assert_eq!(report.total, 100.0);
+        assert_eq!(report.hits, 5);
+        assert!((report.dps - 20.0).abs() < f32::EPSILON);
+        // Nothing new to report until the dummy is hit again
+        assert!(training_dummy.reports(dummy, Time(106.0)).is_empty());
+
+        // The meter starts over once the attacker stopped for long enough
+        training_dummy.record(attacker, Time(200.0), 10.0);
+        let (_, report) = training_dummy.reports(dummy, Time(200.0))[0];
+        assert_eq!(report.total, 10.0);
+        assert_eq!(report.hits, 1);
+    }
+}
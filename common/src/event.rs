@@ -4,7 +4,8 @@ use crate::{
         self,
         agent::Sound,
         invite::{InviteKind, InviteResponse},
-        DisconnectReason, Ori, Pos,
+        portal::PortalId,
+        DisconnectReason, MinigameAction, Ori, Pos,
     },
     lottery::LootSpec,
     outcome::Outcome,
@@ -97,6 +98,8 @@ pub enum ServerEvent {
     EnableLantern(EcsEntity),
     DisableLantern(EcsEntity),
     NpcInteract(EcsEntity, EcsEntity),
+    /// The player picked a choice of the dialogue with the NPC
+    NpcTalk(EcsEntity, EcsEntity, usize),
     InviteResponse(EcsEntity, InviteResponse),
     InitiateInvite(EcsEntity, Uid, InviteKind),
     ProcessTradeAction(EcsEntity, TradeId, TradeAction),
@@ -121,6 +124,8 @@ pub enum ServerEvent {
             Vec<(comp::Pet, comp::Body, comp::Stats)>,
             comp::ActiveAbilities,
             Option<comp::MapMarker>,
+            comp::QuestLog,
+            comp::Reputation,
         ),
     },
     ExitIngame {
@@ -151,8 +156,15 @@ pub enum ServerEvent {
         rtsim_entity: Option<RtSimEntity>,
     },
     CreateWaypoint(Vec3<f32>),
+    CreateMinigame(Vec3<f32>),
     ClientDisconnect(EcsEntity, DisconnectReason),
     ClientDisconnectWithoutPersistence(EcsEntity),
+    /// Hand the session waiting in `session_entity` over to the client that
+    /// registered in `entity`
+    ResumeSession {
+        entity: EcsEntity,
+        session_entity: EcsEntity,
+    },
     Command(EcsEntity, String, Vec<String>),
     /// Send a chat message to the player from an npc or other player
     Chat(comp::UnresolvedChatMsg),
@@ -219,6 +231,37 @@ pub enum ServerEvent {
         entity: EcsEntity,
         update: comp::MapMarkerChange,
     },
+    ShareMapMarkers {
+        entity: EcsEntity,
+        markers: Vec<comp::NamedMapMarker>,
+    },
+    /// NPC was asked for work and offers a quest to the target
+    OfferQuest {
+        npc: EcsEntity,
+        target: EcsEntity,
+    },
+    CompleteQuest {
+        entity: EcsEntity,
+        quest: comp::quest::ActiveQuest,
+    },
+    Sleep {
+        entity: EcsEntity,
+        bed_pos: Vec3<i32>,
+    },
+    ActivatePortal {
+        entity: EcsEntity,
+        portal: EcsEntity,
+    },
+    UsePortal {
+        entity: EcsEntity,
+        portal: EcsEntity,
+        destination: PortalId,
+    },
+    Minigame {
+        entity: EcsEntity,
+        minigame: EcsEntity,
+        action: MinigameAction,
+    },
 }
 
 pub struct EventBus<E> {
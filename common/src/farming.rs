@@ -0,0 +1,113 @@
+use crate::terrain::{Block, BlockKind, SpriteKind};
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+/// In-game seconds a crop spends in each stage before growing into the next
+/// one, a planted crop being ready to harvest after two of them
+pub const STAGE_DURATION: f64 = 60.0 * 60.0 * 12.0;
+/// Seedling, sprout and fully grown crop
+pub const STAGES: u32 = 3;
+/// Colour of freshly tilled soil
+pub const FARMLAND_COLOR: Rgb<u8> = Rgb::new(58, 36, 20);
+
+/// Crops which can be grown from seeds
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Crop {
+    Carrot,
+    Tomato,
+    Cabbage,
+}
+
+impl Crop {
+    pub const ALL: [Self; 3] = [Self::Carrot, Self::Tomato, Self::Cabbage];
+
+    pub fn seed_item(&self) -> &'static str {
+        match self {
+            Self::Carrot => "common.items.crafting_ing.seeds.carrot",
+            Self::Tomato => "common.items.crafting_ing.seeds.tomato",
+            Self::Cabbage => "common.items.crafting_ing.seeds.cabbage",
+        }
+    }
+
+    pub fn from_seed(item_id: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|crop| crop.seed_item() == item_id)
+    }
+
+    /// The sprite of the fully grown crop, harvested for food
+    pub fn mature_sprite(&self) -> SpriteKind {
+        match self {
+            Self::Carrot => SpriteKind::Carrot,
+            Self::Tomato => SpriteKind::Tomato,
+            Self::Cabbage => SpriteKind::Cabbage,
+        }
+    }
+
+    pub fn stage_sprite(&self, stage: u32) -> SpriteKind {
+        match stage {
+            0 => SpriteKind::Seedling,
+            1 => SpriteKind::Sprout,
+            _ => self.mature_sprite(),
+        }
+    }
+
+    /// The stage of this crop the sprite is, `None` if the crop was harvested
+    /// or replaced
+    pub fn stage_of(&self, sprite: SpriteKind) -> Option<u32> {
+        (0..STAGES).find(|stage| self.stage_sprite(*stage) == sprite)
+    }
+}
+
+/// Stage a crop should be at after growing since `planted_at`, both being times
+/// of day
+pub fn stage_at(planted_at: f64, time_of_day: f64) -> u32 {
+    (((time_of_day - planted_at) / STAGE_DURATION).max(0.0) as u32).min(STAGES - 1)
+}
+
+/// Whether the block can be tilled into farmland with a hoe, the grass growing
+/// on top of it being dug up along with it
+pub fn can_till(block: Block, above: Block) -> bool {
+    matches!(block.kind(), BlockKind::Grass | BlockKind::Earth)
+        && above.is_air()
+        && matches!(
+            above.get_sprite(),
+            None | Some(
+                SpriteKind::Empty
+                    | SpriteKind::ShortGrass
+                    | SpriteKind::MediumGrass
+                    | SpriteKind::LongGrass
+                    | SpriteKind::LargeGrass
+                    | SpriteKind::GrassSnow
+                    | SpriteKind::GrassBlue
+                    | SpriteKind::SavannaGrass
+            )
+        )
+}
+
+/// Whether a seed can be planted on top of the block
+pub fn can_plant(block: Block, above: Block) -> bool {
+    block.kind() == BlockKind::Farmland
+        && above.is_air()
+        && matches!(above.get_sprite(), None | Some(SpriteKind::Empty))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crops_grow_in_stages() {
+        assert_eq!(stage_at(100.0, 50.0), 0);
+        assert_eq!(stage_at(100.0, 100.0), 0);
+        assert_eq!(stage_at(100.0, 100.0 + STAGE_DURATION), 1);
+        assert_eq!(stage_at(100.0, 100.0 + STAGE_DURATION * 5.0), STAGES - 1);
+
+        let crop = Crop::Tomato;
+        assert_eq!(crop.stage_sprite(STAGES - 1), SpriteKind::Tomato);
+        assert_eq!(crop.stage_of(SpriteKind::Sprout), Some(1));
+        assert_eq!(crop.stage_of(SpriteKind::Carrot), None);
+        assert_eq!(Crop::from_seed(crop.seed_item()), Some(crop));
+    }
+}
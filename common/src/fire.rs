@@ -0,0 +1,122 @@
+//! Fire spreading across the flammable blocks of the world
+
+use crate::terrain::{Block, BlockKind, SpriteKind};
+use vek::*;
+
+/// Seconds between two spreading steps of the fire
+pub const SPREAD_INTERVAL: f64 = 1.0;
+/// Blocks catching fire in a chunk at most on each spreading step, so that a
+/// burning forest doesn't take up the whole tick
+pub const MAX_IGNITIONS_PER_CHUNK: usize = 8;
+/// Blocks burning at once in a chunk at most
+pub const MAX_FIRES_PER_CHUNK: usize = 64;
+/// Damage per second dealt to the entities standing in the flames
+pub const BURN_STRENGTH: f32 = 10.0;
+/// Seconds the entities which stepped in the flames keep burning for
+pub const BURN_DURATION: f64 = 3.0;
+/// Chance for a burning entity to set the block it stands on on fire on each
+/// spreading step
+pub const ENTITY_IGNITE_CHANCE: f64 = 0.3;
+/// Chance for a fire to be put out on each spreading step in the heaviest rain,
+/// lighter rain putting it out less often
+pub const RAIN_EXTINGUISH_CHANCE: f32 = 0.5;
+/// Colour of the charred wood left behind by the fire
+const CHARRED_COLOR: Rgb<u8> = Rgb::new(38, 30, 26);
+
+/// Neighbours the fire spreads to, the ones above first as flames rise
+pub const SPREAD_DIRS: [Vec3<i32>; 6] = [
+    Vec3::new(0, 0, 1),
+    Vec3::new(1, 0, 0),
+    Vec3::new(-1, 0, 0),
+    Vec3::new(0, 1, 0),
+    Vec3::new(0, -1, 0),
+    Vec3::new(0, 0, -1),
+];
+
+/// Chance for the block to catch fire from a burning neighbour on each
+/// spreading step, `None` if it doesn't burn
+pub fn flammability(block: Block) -> Option<f32> {
+    match block.kind() {
+        BlockKind::Wood => Some(0.15),
+        BlockKind::Leaves => Some(0.4),
+        BlockKind::Air => match block.get_sprite()? {
+            SpriteKind::LongGrass
+            | SpriteKind::MediumGrass
+            | SpriteKind::ShortGrass
+            | SpriteKind::SavannaGrass
+            | SpriteKind::TallSavannaGrass
+            | SpriteKind::LargeGrass
+            | SpriteKind::DeadBush
+            | SpriteKind::Wood => Some(0.6),
+            SpriteKind::BlueFlower
+            | SpriteKind::PinkFlower
+            | SpriteKind::PurpleFlower
+            | SpriteKind::RedFlower
+            | SpriteKind::WhiteFlower
+            | SpriteKind::YellowFlower
+            | SpriteKind::Fern
+            | SpriteKind::JungleFern
+            | SpriteKind::SavannaBush
+            | SpriteKind::Reed => Some(0.3),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Seconds the block burns for before it is burnt out
+pub fn burn_time(block: Block) -> f64 {
+    match block.kind() {
+        BlockKind::Wood => 30.0,
+        BlockKind::Leaves => 8.0,
+        _ => 4.0,
+    }
+}
+
+/// What is left of the block once it burnt out: wood chars, anything else burns
+/// away
+pub fn burnt_out(block: Block) -> Block {
+    match block.kind() {
+        BlockKind::Wood => Block::new(BlockKind::Misc, CHARRED_COLOR),
+        _ => block.into_vacant(),
+    }
+}
+
+/// Where the flames of the burning block at `pos` are, `None` if there is no
+/// room for them. Burning sprites are replaced by the flames, while filled
+/// blocks burn with flames on top of them.
+pub fn flames_pos(block: Block, above: Block, pos: Vec3<i32>) -> Option<Vec3<i32>> {
+    if !block.is_filled() {
+        Some(pos)
+    } else if above.is_air() && matches!(above.get_sprite(), None | Some(SpriteKind::Empty)) {
+        Some(pos + Vec3::unit_z())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_plants_and_wood_burn() {
+        let wood = Block::new(BlockKind::Wood, Rgb::zero());
+        let rock = Block::new(BlockKind::Rock, Rgb::zero());
+        let grass = Block::air(SpriteKind::LongGrass);
+        assert!(flammability(wood).is_some());
+        assert!(flammability(grass).is_some());
+        assert!(flammability(rock).is_none());
+        assert!(flammability(Block::empty()).is_none());
+        assert!(flammability(Block::air(SpriteKind::Ember)).is_none());
+        assert!(flammability(burnt_out(wood)).is_none());
+
+        let pos = Vec3::new(3, 4, 5);
+        assert_eq!(flames_pos(grass, Block::empty(), pos), Some(pos));
+        assert_eq!(
+            flames_pos(wood, Block::empty(), pos),
+            Some(pos + Vec3::unit_z())
+        );
+        assert_eq!(flames_pos(wood, wood, pos), None);
+    }
+}
@@ -152,6 +152,7 @@ pub fn try_all_entity_configs() -> Result<Vec<String>, Error> {
 pub struct EntityInfo {
     pub pos: Vec3<f32>,
     pub is_waypoint: bool, // Edge case, overrides everything else
+    pub is_minigame: bool, // Same as above
     // Agent
     pub has_agency: bool,
     pub alignment: Alignment,
@@ -184,6 +185,7 @@ impl EntityInfo {
         Self {
             pos,
             is_waypoint: false,
+            is_minigame: false,
             has_agency: true,
             alignment: Alignment::Wild,
             agent_mark: None,
@@ -348,6 +350,12 @@ impl EntityInfo {
         self
     }
 
+    #[must_use]
+    pub fn into_minigame(mut self) -> Self {
+        self.is_minigame = true;
+        self
+    }
+
     #[must_use]
     pub fn with_alignment(mut self, alignment: Alignment) -> Self {
         self.alignment = alignment;
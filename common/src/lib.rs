@@ -44,6 +44,8 @@ pub mod uid;
 // NOTE: Comment out macro to get rustfmt to re-order these as needed.
 cfg_not_wasm! {
     pub mod astar;
+    pub mod blueprint;
+    pub mod building;
     pub mod calendar;
     pub mod character;
     pub mod clock;
@@ -52,6 +54,8 @@ cfg_not_wasm! {
     pub mod effect;
     pub mod event;
     pub mod explosion;
+    pub mod farming;
+    pub mod fire;
     pub mod figure;
     pub mod generation;
     pub mod grid;
@@ -78,6 +82,8 @@ cfg_not_wasm! {
     pub mod vol;
     pub mod volumes;
     pub mod weather;
+    pub mod world_border;
+    pub mod writing;
 
     mod cached_spatial_grid;
     mod view_distances;
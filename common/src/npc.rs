@@ -119,6 +119,28 @@ pub fn get_npc_name(npc_type: NpcKind, body_type: Option<BodyType>) -> String {
     }
 }
 
+/// Name metadata of the species of a body, `None` for bodies without species
+/// like objects and ships.
+pub fn species_names<'a>(npc_names: &'a NpcNames, body: &Body) -> Option<&'a SpeciesNames> {
+    Some(match body {
+        Body::Humanoid(body) => &npc_names.humanoid.species[&body.species],
+        Body::QuadrupedSmall(body) => &npc_names.quadruped_small.species[&body.species],
+        Body::QuadrupedMedium(body) => &npc_names.quadruped_medium.species[&body.species],
+        Body::QuadrupedLow(body) => &npc_names.quadruped_low.species[&body.species],
+        Body::BirdMedium(body) => &npc_names.bird_medium.species[&body.species],
+        Body::BirdLarge(body) => &npc_names.bird_large.species[&body.species],
+        Body::FishMedium(body) => &npc_names.fish_medium.species[&body.species],
+        Body::FishSmall(body) => &npc_names.fish_small.species[&body.species],
+        Body::BipedLarge(body) => &npc_names.biped_large.species[&body.species],
+        Body::BipedSmall(body) => &npc_names.biped_small.species[&body.species],
+        Body::Dragon(body) => &npc_names.dragon.species[&body.species],
+        Body::Golem(body) => &npc_names.golem.species[&body.species],
+        Body::Theropod(body) => &npc_names.theropod.species[&body.species],
+        Body::Arthropod(body) => &npc_names.arthropod.species[&body.species],
+        Body::Object(_) | Body::ItemDrop(_) | Body::Ship(_) => return None,
+    })
+}
+
 /// Randomly generates a body associated with this NPC kind.
 pub fn kind_to_body(kind: NpcKind) -> Body {
     match kind {
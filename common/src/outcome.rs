@@ -65,6 +65,14 @@ pub enum Outcome {
         pos: Vec3<i32>,
         color: Option<Rgb<u8>>,
     },
+    /// A block caught fire, its flames being at `pos`
+    Ignite {
+        pos: Vec3<i32>,
+    },
+    /// The flames at `pos` died out
+    BurnOut {
+        pos: Vec3<i32>,
+    },
     SummonedCreature {
         pos: Vec3<f32>,
         body: comp::Body,
@@ -75,6 +83,9 @@ pub enum Outcome {
     },
     Death {
         pos: Vec3<f32>,
+        uid: Uid,
+        /// Entity which dealt the last hit
+        by: Option<Uid>,
     },
     Block {
         pos: Vec3<f32>,
@@ -116,7 +127,9 @@ impl Outcome {
             | Outcome::GroundSlam { pos }
             | Outcome::Utterance { pos, .. }
             | Outcome::Glider { pos, .. } => Some(*pos),
-            Outcome::BreakBlock { pos, .. } => Some(pos.map(|e| e as f32 + 0.5)),
+            Outcome::BreakBlock { pos, .. }
+            | Outcome::Ignite { pos }
+            | Outcome::BurnOut { pos } => Some(pos.map(|e| e as f32 + 0.5)),
             Outcome::ExpChange { .. }
             | Outcome::ComboChange { .. }
             | Outcome::Lightning { .. }
@@ -322,8 +322,23 @@ impl Route {
     }
 }
 
+/// Horizontal size of the cells of the coarse search, in blocks
+const COARSE_CELL_SIZE: i32 = 8;
+/// Targets further away than this are first searched for on the coarse grid
+const COARSE_SEARCH_DIST: f32 = 64.0;
+/// Waypoints of the coarse path closer than this are skipped
+const COARSE_WAYPOINT_DIST: f32 = 20.0;
+/// Highest difference of ground height between neighbouring coarse cells that
+/// is considered walkable
+const COARSE_MAX_STEP: i32 = 6;
+
 /// A self-contained system that attempts to chase a moving target, only
 /// performing pathfinding if necessary
+///
+/// Far away targets are first searched for on a grid of coarse cells, the
+/// detailed search then only has to find its way to the next few cells. Both
+/// searches are spread over several calls so that no single tick has to pay
+/// for a long search.
 #[derive(Default, Clone, Debug)]
 pub struct Chaser {
     last_search_tgt: Option<Vec3<f32>>,
@@ -334,6 +349,12 @@ pub struct Chaser {
     /// (2) we don't care about determinism across computers (we can use
     /// AAHash).
     astar: Option<Astar<Vec3<i32>, DefaultHashBuilder>>,
+    /// Path over coarse cells towards a far away target, kept around while
+    /// the target stays within the same area. Nodes are cells with the height
+    /// of their ground.
+    coarse_route: Option<(Vec2<i32>, Path<Vec3<i32>>)>,
+    /// Unfinished coarse search, along with the cell of its target
+    coarse_astar: Option<(Vec2<i32>, Astar<Vec3<i32>, DefaultHashBuilder>)>,
 }
 
 impl Chaser {
@@ -352,6 +373,7 @@ impl Chaser {
         V: BaseVol<Vox = Block> + ReadVol,
     {
         span!(_guard, "chase", "Chaser::chase");
+        let tgt = self.waypoint(vol, pos, tgt, &traversal_cfg);
         let pos_to_tgt = pos.distance(tgt);
 
         // If we're already close to the target then there's nothing to do
@@ -462,11 +484,21 @@ impl Chaser {
                     .unwrap_or(false)
                 });
 
+                // Walls too high to jump over would only get us stuck
+                let walking_into_wall = (1..3).all(|z| {
+                    vol.get(
+                        (pos + Vec3::<f32>::from(tgt_dir) * 1.5).map(|e| e.floor() as i32)
+                            + Vec3::unit_z() * z,
+                    )
+                    .map(|b| b.is_solid())
+                    .unwrap_or(false)
+                });
+
                 // Enable when airbraking/flight is figured out
                 /*if traversal_cfg.can_fly {
                     Some(((tgt - pos) , 1.0))
                 } else */
-                if !walking_towards_edge || traversal_cfg.can_fly {
+                if traversal_cfg.can_fly || !(walking_towards_edge || walking_into_wall) {
                     Some(((tgt - pos) * Vec3::new(1.0, 1.0, 0.0), 1.0))
                 } else {
                     // This is unfortunately where an NPC will stare blankly
@@ -478,6 +510,164 @@ impl Chaser {
             }
         }
     }
+
+    /// Position to head towards on the way to the target: the target itself
+    /// when it's close, or the next waypoint of the coarse path to it
+    fn waypoint<V>(
+        &mut self,
+        vol: &V,
+        pos: Vec3<f32>,
+        tgt: Vec3<f32>,
+        traversal_cfg: &TraversalConfig,
+    ) -> Vec3<f32>
+    where
+        V: BaseVol<Vox = Block> + ReadVol,
+    {
+        if pos.xy().distance(tgt.xy()) < COARSE_SEARCH_DIST {
+            self.coarse_route = None;
+            self.coarse_astar = None;
+            return tgt;
+        }
+
+        let tgt_cell = coarse_cell(tgt);
+        let pos_cell = coarse_cell(pos);
+        // The cached path is only good as long as the target stays around the same
+        // place and we don't stray away from it
+        if self.coarse_route.as_ref().map_or(false, |(cell, path)| {
+            cell.distance_squared(tgt_cell) > 4
+                || path
+                    .iter()
+                    .all(|node| node.xy().distance_squared(pos_cell) > 4)
+        }) {
+            self.coarse_route = None;
+        }
+
+        if self.coarse_route.is_none() {
+            match find_coarse_path(&mut self.coarse_astar, vol, pos, tgt, traversal_cfg) {
+                Some(path) => self.coarse_route = Some((tgt_cell, path)),
+                // Head straight for the target until a coarse path is found
+                None => return tgt,
+            }
+        }
+
+        let path = match &self.coarse_route {
+            Some((_, path)) => path,
+            None => return tgt,
+        };
+        let closest = path
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, node)| node.xy().distance_squared(pos_cell))
+            .map_or(0, |(i, _)| i);
+        path.iter()
+            .skip(closest)
+            .map(|node| coarse_cell_center(*node))
+            .find(|waypoint| waypoint.xy().distance(pos.xy()) > COARSE_WAYPOINT_DIST)
+            .unwrap_or(tgt)
+    }
+}
+
+fn coarse_cell(wpos: Vec3<f32>) -> Vec2<i32> {
+    wpos.xy()
+        .map(|e| (e.floor() as i32).div_euclid(COARSE_CELL_SIZE))
+}
+
+fn coarse_cell_center(node: Vec3<i32>) -> Vec3<f32> {
+    (node.xy() * COARSE_CELL_SIZE + COARSE_CELL_SIZE / 2)
+        .map(|e| e as f32 + 0.5)
+        .with_z(node.z as f32)
+}
+
+/// Walkable position closest to the given one in the same column, checking
+/// `range` blocks in total, alternating above and below
+fn nearest_walkable<V>(vol: &V, pos: Vec3<i32>, range: usize) -> Option<Vec3<i32>>
+where
+    V: BaseVol<Vox = Block> + ReadVol,
+{
+    let mut z_incr = 0;
+    for _ in 0..range {
+        let test_pos = pos + Vec3::unit_z() * z_incr;
+        if walkable(vol, test_pos) {
+            return Some(test_pos);
+        }
+        z_incr = -z_incr + if z_incr <= 0 { 1 } else { 0 };
+    }
+    None
+}
+
+/// Search for a path over coarse cells, returning `None` while the search
+/// isn't done yet. When no path reaches the target, the path getting the
+/// closest to it is returned.
+fn find_coarse_path<V>(
+    astar: &mut Option<(Vec2<i32>, Astar<Vec3<i32>, DefaultHashBuilder>)>,
+    vol: &V,
+    startf: Vec3<f32>,
+    endf: Vec3<f32>,
+    traversal_cfg: &TraversalConfig,
+) -> Option<Path<Vec3<i32>>>
+where
+    V: BaseVol<Vox = Block> + ReadVol,
+{
+    let end_cell = coarse_cell(endf);
+    // The target moved too far away from what we were searching for
+    if astar
+        .as_ref()
+        .map_or(false, |(cell, _)| cell.distance_squared(end_cell) > 4)
+    {
+        *astar = None;
+    }
+
+    let ground = |cell: Vec2<i32>, z: i32, range| {
+        nearest_walkable(
+            vol,
+            (cell * COARSE_CELL_SIZE + COARSE_CELL_SIZE / 2).with_z(z),
+            range,
+        )
+        .map(|pos| cell.with_z(pos.z))
+    };
+    let heuristic = |node: &Vec3<i32>| {
+        (node.xy().distance_squared(end_cell) as f32).sqrt() * COARSE_CELL_SIZE as f32
+    };
+    let neighbors = |node: &Vec3<i32>| {
+        let node = *node;
+        DIAGONALS.iter().filter_map(move |dir| {
+            ground(node.xy() + *dir, node.z, COARSE_MAX_STEP as usize * 2 + 1)
+                .filter(|next| traversal_cfg.can_fly || (next.z - node.z).abs() <= COARSE_MAX_STEP)
+        })
+    };
+    let transition = |a: &Vec3<i32>, b: &Vec3<i32>| {
+        let in_liquid = vol
+            .get(coarse_cell_center(*b).map(|e| e.floor() as i32))
+            .map_or(false, |b| b.is_liquid());
+        (a.xy().distance_squared(b.xy()) as f32).sqrt() * COARSE_CELL_SIZE as f32
+            + (b.z - a.z).max(0) as f32 * 0.5
+            // Going around lakes is better than swimming through them
+            + if in_liquid && !traversal_cfg.can_fly {
+                COARSE_CELL_SIZE as f32 * 2.0
+            } else {
+                0.0
+            }
+    };
+    let satisfied = |node: &Vec3<i32>| node.xy() == end_cell;
+
+    let (_, mut coarse_astar) = match astar.take() {
+        Some(astar) => astar,
+        None => {
+            let start = ground(coarse_cell(startf), startf.z.floor() as i32, 32)?;
+            (
+                end_cell,
+                Astar::new(4_000, start, heuristic, DefaultHashBuilder::default()),
+            )
+        },
+    };
+
+    match coarse_astar.poll(50, heuristic, neighbors, transition, satisfied) {
+        PathResult::Path(path) | PathResult::None(path) | PathResult::Exhausted(path) => Some(path),
+        PathResult::Pending => {
+            *astar = Some((end_cell, coarse_astar));
+            None
+        },
+    }
 }
 
 fn walkable<V>(vol: &V, pos: Vec3<i32>) -> bool
@@ -514,17 +704,7 @@ where
     V: BaseVol<Vox = Block> + ReadVol,
 {
     let is_walkable = |pos: &Vec3<i32>| walkable(vol, *pos);
-    let get_walkable_z = |pos| {
-        let mut z_incr = 0;
-        for _ in 0..32 {
-            let test_pos = pos + Vec3::unit_z() * z_incr;
-            if is_walkable(&test_pos) {
-                return Some(test_pos);
-            }
-            z_incr = -z_incr + if z_incr <= 0 { 1 } else { 0 };
-        }
-        None
-    };
+    let get_walkable_z = |pos| nearest_walkable(vol, pos, 32);
 
     let (start, end) = match (
         get_walkable_z(startf.map(|e| e.floor() as i32)),
@@ -635,6 +815,15 @@ where
         // straight line toward our target. This means we get smoother movement.
         1.0 + crow_line.distance_to_point(b.xy().map(|e| e as f32)) * 0.025
             + (b.z - a.z - 1).max(0) as f32 * 10.0
+            // Avoid getting into water when there's a way around it
+            + if !traversal_cfg.in_liquid
+                && !traversal_cfg.can_fly
+                && vol.get(*b).map_or(false, |b| b.is_liquid())
+            {
+                2.0
+            } else {
+                0.0
+            }
     };
     let satisfied = |pos: &Vec3<i32>| pos == &end;
 
@@ -1058,3 +1247,93 @@ pub fn point_on_prolate_spheroid(
     // let global_coords = midpoint + rot_2_mat * (rot_z_mat * point);
     midpoint + rot_2_mat * point
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{terrain::BlockKind, vol::WriteVol, volumes::dyna::Dyna};
+
+    /// Flat ground of 12 by 3 coarse cells, with walls too high to climb
+    /// filling the given blocks of the x = 6 column of cells
+    fn terrain_with_wall(wall_ys: std::ops::Range<i32>) -> Dyna<Block, ()> {
+        let mut terrain = Dyna::filled(Vec3::new(96, 24, 32), Block::empty(), ());
+        let rock = Block::new(BlockKind::Rock, Rgb::zero());
+        for x in 0..96 {
+            for y in 0..24 {
+                let height = if (48..56).contains(&x) && wall_ys.contains(&y) {
+                    32
+                } else {
+                    1
+                };
+                for z in 0..height {
+                    terrain.set(Vec3::new(x, y, z), rock).unwrap();
+                }
+            }
+        }
+        terrain
+    }
+
+    fn traversal_cfg() -> TraversalConfig {
+        TraversalConfig {
+            node_tolerance: 1.0,
+            slow_factor: 1.0,
+            on_ground: true,
+            in_liquid: false,
+            min_tgt_dist: 1.0,
+            can_climb: false,
+            can_fly: false,
+        }
+    }
+
+    fn coarse_path(terrain: &Dyna<Block, ()>, start: Vec3<f32>, end: Vec3<f32>) -> Path<Vec3<i32>> {
+        let mut astar = None;
+        (0..1000)
+            .find_map(|_| find_coarse_path(&mut astar, terrain, start, end, &traversal_cfg()))
+            .expect("coarse search didn't finish")
+    }
+
+    #[test]
+    fn coarse_path_reaches_target() {
+        let terrain = terrain_with_wall(0..0);
+        let path = coarse_path(
+            &terrain,
+            Vec3::new(4.5, 4.5, 1.0),
+            Vec3::new(92.5, 4.5, 1.0),
+        );
+
+        assert_eq!(path.start(), Some(&Vec3::new(0, 0, 1)));
+        assert_eq!(path.end(), Some(&Vec3::new(11, 0, 1)));
+        assert_eq!(path.len(), 12);
+    }
+
+    #[test]
+    fn coarse_path_to_unreachable_target_gets_closest() {
+        let terrain = terrain_with_wall(0..24);
+        let path = coarse_path(
+            &terrain,
+            Vec3::new(4.5, 4.5, 1.0),
+            Vec3::new(92.5, 4.5, 1.0),
+        );
+
+        assert!(path.len() > 1);
+        assert!(path.iter().all(|node| node.x < 6));
+    }
+
+    #[test]
+    fn coarse_path_goes_through_gap_in_wall() {
+        let terrain = terrain_with_wall(0..16);
+        let path = coarse_path(
+            &terrain,
+            Vec3::new(4.5, 4.5, 1.0),
+            Vec3::new(92.5, 4.5, 1.0),
+        );
+
+        assert_eq!(path.end(), Some(&Vec3::new(11, 0, 1)));
+        assert!(
+            path.iter()
+                .filter(|node| node.x == 6)
+                .all(|node| node.xy() == Vec2::new(6, 2))
+        );
+        assert!(path.iter().any(|node| node.x == 6));
+    }
+}
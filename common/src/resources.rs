@@ -8,6 +8,20 @@ use specs::Entity;
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, Default)]
 pub struct TimeOfDay(pub f64);
 
+/// A resource that stores how much faster the time of day passes than real
+/// time.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DayCycleFactor(pub f64);
+
+impl DayCycleFactor {
+    /// Factor for an in-game day lasting the given amount of real time minutes
+    pub fn from_day_length(minutes: f64) -> Self { Self(24.0 * 60.0 / minutes.max(1.0)) }
+}
+
+impl Default for DayCycleFactor {
+    fn default() -> Self { Self(24.0 * 2.0) }
+}
+
 /// A resource that stores the tick (i.e: physics) time.
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
 pub struct Time(pub f64);
@@ -1,13 +1,13 @@
 use crate::{
     comp::{
-        self, character_state::OutputEvents, item::MaterialStatManifest, ActiveAbilities, Beam,
-        Body, CharacterState, Combo, ControlAction, Controller, ControllerInputs, Density, Energy,
-        Health, InputAttr, InputKind, Inventory, InventoryAction, Mass, Melee, Ori, PhysicsState,
-        Pos, SkillSet, StateUpdate, Stats, Vel,
+        self, character_state::OutputEvents, item::MaterialStatManifest, AbilityCooldowns,
+        ActiveAbilities, Beam, Body, CharacterState, Combo, ControlAction, Controller,
+        ControllerInputs, Density, Energy, Health, InputAttr, InputKind, Inventory,
+        InventoryAction, Mass, Melee, Ori, PhysicsState, Pos, SkillSet, StateUpdate, Stats, Vel,
     },
     link::Is,
     mounting::Rider,
-    resources::DeltaTime,
+    resources::{DeltaTime, Time},
     terrain::TerrainGrid,
     uid::Uid,
 };
@@ -121,6 +121,7 @@ pub struct JoinData<'a> {
     pub mass: &'a Mass,
     pub density: &'a Density,
     pub dt: &'a DeltaTime,
+    pub time: &'a Time,
     pub controller: &'a Controller,
     pub inputs: &'a ControllerInputs,
     pub health: Option<&'a Health>,
@@ -133,6 +134,7 @@ pub struct JoinData<'a> {
     pub stats: &'a Stats,
     pub skill_set: &'a SkillSet,
     pub active_abilities: Option<&'a ActiveAbilities>,
+    pub cooldowns: Option<&'a AbilityCooldowns>,
     pub msm: &'a MaterialStatManifest,
     pub combo: Option<&'a Combo>,
     pub alignment: Option<&'a comp::Alignment>,
@@ -160,6 +162,7 @@ pub struct JoinStruct<'a> {
     pub stat: &'a Stats,
     pub skill_set: &'a SkillSet,
     pub active_abilities: Option<&'a ActiveAbilities>,
+    pub cooldowns: Option<FlaggedAccessMut<'a, &'a mut AbilityCooldowns, AbilityCooldowns>>,
     pub combo: Option<&'a Combo>,
     pub alignment: Option<&'a comp::Alignment>,
     pub terrain: &'a TerrainGrid,
@@ -171,6 +174,7 @@ impl<'a> JoinData<'a> {
         j: &'a JoinStruct<'a>,
         updater: &'a LazyUpdate,
         dt: &'a DeltaTime,
+        time: &'a Time,
         msm: &'a MaterialStatManifest,
     ) -> Self {
         Self {
@@ -194,11 +198,13 @@ impl<'a> JoinData<'a> {
             skill_set: j.skill_set,
             updater,
             dt,
+            time,
             msm,
             combo: j.combo,
             alignment: j.alignment,
             terrain: j.terrain,
             active_abilities: j.active_abilities,
+            cooldowns: j.cooldowns.as_deref(),
             mount_data: j.mount_data,
         }
     }
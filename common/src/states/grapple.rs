@@ -0,0 +1,132 @@
+use crate::{
+    comp::{character_state::OutputEvents, Body, CharacterState, Climb, InputKind, StateUpdate},
+    states::{
+        behavior::{CharacterBehavior, JoinData},
+        utils::*,
+        wielding,
+    },
+    terrain::Block,
+    util::Dir,
+    vol::ReadVol,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use vek::*;
+
+/// Height of the hand holding the rope, relative to the height of the body
+const HAND_HEIGHT: f32 = 0.6;
+/// Shortest the rope can be reeled in to
+const MIN_ROPE_LENGTH: f32 = 1.0;
+
+/// Separated out to condense update portions of character state
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// How far the hook flies, and how long the rope can be let out to
+    pub range: f32,
+    /// Speed the hook flies at
+    pub hook_speed: f32,
+    /// Speed the rope is reeled in and let out at
+    pub reel_speed: f32,
+    /// Acceleration of the swing while hanging from the rope
+    pub swing_accel: f32,
+    /// What key is used to press ability
+    pub ability_info: AbilityInfo,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// Struct containing data that does not change over the course of the
+    /// character state
+    pub static_data: StaticData,
+    /// Timer for the state
+    pub timer: Duration,
+    /// Where the hook is, flying away until it catches on the terrain
+    pub hook: Vec3<f32>,
+    /// Direction the hook was thrown in
+    pub dir: Dir,
+    /// Length of the rope once the hook caught on the terrain, `None` while it
+    /// flies. The physics keep the feet of the character within it of the
+    /// hook.
+    pub rope: Option<f32>,
+}
+
+/// Position of the hand holding the rope of a character at `pos`
+pub fn hand_pos(pos: Vec3<f32>, body: &Body) -> Vec3<f32> {
+    pos + Vec3::unit_z() * body.height() * HAND_HEIGHT
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData, output_events: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        handle_orientation(data, &mut update, 1.0, None);
+        let timer = tick_attack_or_default(data, self.timer, None);
+        let held = input_is_pressed(data, self.static_data.ability_info.input);
+
+        match self.rope {
+            None => {
+                handle_move(data, &mut update, 0.7);
+                let travel = self.static_data.hook_speed * data.dt.0;
+                let (dist, hit) = data
+                    .terrain
+                    .ray(self.hook, self.hook + *self.dir * travel)
+                    .until(Block::is_solid)
+                    .cast();
+                let hand = hand_pos(data.pos.0, data.body);
+                if let Ok(Some(_)) = hit {
+                    let anchor = self.hook + *self.dir * dist;
+                    update.character = CharacterState::Grapple(Data {
+                        timer,
+                        hook: anchor,
+                        rope: Some(data.pos.0.distance(anchor).min(self.static_data.range)),
+                        ..*self
+                    });
+                } else if !held || hand.distance(self.hook) + travel > self.static_data.range {
+                    // The hook caught on nothing, or was pulled back
+                    update.character =
+                        CharacterState::Wielding(wielding::Data { is_sneaking: false });
+                } else {
+                    update.character = CharacterState::Grapple(Data {
+                        timer,
+                        hook: self.hook + *self.dir * travel,
+                        ..*self
+                    });
+                }
+            },
+            Some(rope) => {
+                if input_is_pressed(data, InputKind::Jump) && data.physics.on_ground.is_none() {
+                    // Letting go with a jump opens the glider, if there is one
+                    update.character =
+                        CharacterState::Wielding(wielding::Data { is_sneaking: false });
+                    attempt_glide_wield(data, &mut update, output_events);
+                } else if !held || data.physics.in_liquid().is_some() {
+                    // Letting go of the rope falls
+                    update.character =
+                        CharacterState::Wielding(wielding::Data { is_sneaking: false });
+                } else {
+                    if data.physics.on_ground.is_some() {
+                        handle_move(data, &mut update, 1.0);
+                    } else {
+                        // Swinging around the hook
+                        update.vel.0 += Vec3::from(data.inputs.move_dir)
+                            * self.static_data.swing_accel
+                            * data.dt.0;
+                    }
+                    let reel = self.static_data.reel_speed * data.dt.0;
+                    let rope = match data.inputs.climb {
+                        Some(Climb::Up) => rope - reel,
+                        Some(Climb::Down) => rope + reel,
+                        Some(Climb::Hold) | None => rope,
+                    };
+                    update.character = CharacterState::Grapple(Data {
+                        timer,
+                        rope: Some(rope.clamp(MIN_ROPE_LENGTH, self.static_data.range)),
+                        ..*self
+                    });
+                }
+            },
+        }
+
+        update
+    }
+}
@@ -16,6 +16,7 @@ pub mod dash_melee;
 pub mod equipping;
 pub mod glide;
 pub mod glide_wield;
+pub mod grapple;
 pub mod idle;
 pub mod leap_melee;
 pub mod music;
@@ -157,7 +157,10 @@ impl From<SpriteKind> for Option<SpriteInteractKind> {
             | SpriteKind::Hardwood
             | SpriteKind::Ironwood
             | SpriteKind::Frostwood
-            | SpriteKind::Eldwood => Some(SpriteInteractKind::Harvestable),
+            | SpriteKind::Eldwood
+            | SpriteKind::Carrot
+            | SpriteKind::Tomato
+            | SpriteKind::Cabbage => Some(SpriteInteractKind::Harvestable),
             SpriteKind::Stones
             | SpriteKind::Twigs
             | SpriteKind::VialEmpty
@@ -8,8 +8,8 @@ use crate::{
         item::{armor::Friction, Hands, ItemKind, ToolKind},
         quadruped_low, quadruped_medium, quadruped_small,
         skills::{Skill, SwimSkill, SKILL_MODIFIERS},
-        theropod, Body, CharacterAbility, CharacterState, Density, InputAttr, InputKind,
-        InventoryAction, StateUpdate,
+        theropod, AbilityInput, Body, CharacterAbility, CharacterState, Density, InputAttr,
+        InputKind, InventoryAction, StateUpdate,
     },
     consts::{FRIC_GROUND, GRAVITY, MAX_PICKUP_RANGE},
     event::{LocalEvent, ServerEvent},
@@ -937,6 +937,18 @@ pub fn handle_jump(
 
 fn handle_ability(data: &JoinData<'_>, update: &mut StateUpdate, input: InputKind) {
     if let Some(ability_input) = input.into() {
+        // Auxiliary abilities can't be used again before their cooldown is over
+        let slot = match ability_input {
+            AbilityInput::Auxiliary(index) => Some(index),
+            _ => None,
+        };
+        if slot.map_or(false, |index| {
+            data.cooldowns.map_or(false, |cooldowns| {
+                cooldowns.remaining(index, *data.time).is_some()
+            })
+        }) {
+            return;
+        }
         if let Some((ability, from_offhand)) = data
             .active_abilities
             .and_then(|a| {
@@ -954,6 +966,7 @@ fn handle_ability(data: &JoinData<'_>, update: &mut StateUpdate, input: InputKin
                 AbilityInfo::from_input(data, from_offhand, input),
                 data,
             ));
+            update.started_cooldown = slot.map(|index| (index, ability.cooldown()));
         }
     }
 }
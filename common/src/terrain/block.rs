@@ -1,4 +1,7 @@
-use super::SpriteKind;
+use super::{
+    fluid::{Flow, FlowDir},
+    SpriteKind,
+};
 use crate::{
     comp::{fluid_dynamics::LiquidKind, tool::ToolKind},
     consts::FRIC_GROUND,
@@ -46,7 +49,8 @@ make_case_elim!(
         // 0x21 <= x < 0x30 is reserved for future grasses
         Earth = 0x30,
         Sand = 0x31,
-        // 0x32 <= x < 0x40 is reserved for future earths/muds/gravels/sands/etc.
+        Farmland = 0x32, // Tilled earth crops can be planted in
+        // 0x33 <= x < 0x40 is reserved for future earths/muds/gravels/sands/etc.
         Wood = 0x40,
         Leaves = 0x41,
         GlowingMushroom = 0x42,
@@ -106,10 +110,14 @@ impl BlockKind {
                 | BlockKind::Grass
                 | BlockKind::Earth
                 | BlockKind::Sand
+                | BlockKind::Farmland
         )
     }
 }
 
+/// Bit of the last attribute set for flowing liquids
+const FLOWING: u8 = 0b1000;
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct Block {
     kind: BlockKind,
@@ -243,6 +251,27 @@ impl Block {
         }
     }
 
+    /// How the liquid flows, `None` if it isn't a flowing liquid
+    #[inline]
+    pub fn get_flow(&self) -> Option<Flow> {
+        if !self.can_flow() || self.attr[2] & FLOWING == 0 {
+            return None;
+        }
+        Some(Flow {
+            dir: FlowDir::from_u8(self.attr[2] & 0b111)?,
+            distance: self.attr[1] & 0b111,
+        })
+    }
+
+    /// Whether the liquid can flow. The flow is kept in the low bits of the
+    /// last two attributes, which are the colour of lava, left alone by the
+    /// world, but also the orientation of the sprites in water, so water only
+    /// flows where there is no sprite.
+    #[inline]
+    fn can_flow(&self) -> bool {
+        self.liquid_kind().is_some() && matches!(self.get_sprite(), None | Some(SpriteKind::Empty))
+    }
+
     // minimum block, attenuation
     #[inline]
     pub fn get_max_sunlight(&self) -> (u8, f32) {
@@ -274,7 +303,7 @@ impl Block {
         // so all is good for empty fluids.
         match self.kind() {
             BlockKind::Leaves => Some(0.25),
-            BlockKind::Grass => Some(0.5),
+            BlockKind::Grass | BlockKind::Farmland => Some(0.5),
             BlockKind::WeakRock => Some(0.75),
             BlockKind::Snow => Some(0.1),
             BlockKind::Ice => Some(0.5),
@@ -382,6 +411,11 @@ impl Block {
     #[must_use]
     pub fn with_sprite(mut self, sprite: SpriteKind) -> Self {
         if !self.is_filled() {
+            // A sprite stops the liquid from flowing, its orientation taking the
+            // place of the flow
+            if sprite != SpriteKind::Empty {
+                self = self.with_flow(None);
+            }
             self.attr[0] = sprite as u8;
         }
         self
@@ -399,6 +433,22 @@ impl Block {
         }
     }
 
+    /// If this block is a liquid without a sprite, make it flow, or stop it
+    /// from flowing
+    #[inline]
+    #[must_use]
+    pub fn with_flow(mut self, flow: Option<Flow>) -> Self {
+        if self.can_flow() {
+            let (dir, distance) = match flow {
+                Some(flow) => (FLOWING | flow.dir as u8, flow.distance.min(0b111)),
+                None => (0, 0),
+            };
+            self.attr[1] = (self.attr[1] & !0b111) | distance;
+            self.attr[2] = (self.attr[2] & !0b1111) | dir;
+        }
+        self
+    }
+
     /// Remove the terrain sprite or solid aspects of a block
     #[inline]
     #[must_use]
@@ -454,4 +504,24 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn flow_keeps_the_orientation_of_sprites_in_water() {
+        let flow = Flow {
+            dir: FlowDir::NegY,
+            distance: 5,
+        };
+        let flowing = Block::water(SpriteKind::Empty).with_flow(Some(flow));
+        assert_eq!(flowing.get_flow(), Some(flow));
+
+        let oriented = Block::water(SpriteKind::SeaDecorEmblem)
+            .with_ori(3)
+            .unwrap();
+        assert_eq!(oriented.with_flow(Some(flow)).get_ori(), Some(3));
+        assert_eq!(oriented.with_flow(Some(flow)).get_flow(), None);
+
+        let placed = flowing.with_sprite(SpriteKind::SeaDecorEmblem);
+        assert_eq!(placed.get_flow(), None);
+        assert_eq!(placed.get_ori(), Some(0));
+    }
 }
@@ -0,0 +1,188 @@
+//! Water and lava flowing out of their sources, one block at a time, like a
+//! cellular automaton. Flowing liquid blocks remember which way they flow and
+//! how far they are from the block feeding them, and dry up once nothing
+//! feeds them anymore.
+
+use super::Block;
+use crate::comp::fluid_dynamics::LiquidKind;
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+/// Seconds between two flowing steps of water
+pub const FLOW_INTERVAL: f64 = 0.25;
+/// Lava only flows on every this many steps, as it is much thicker than water
+pub const LAVA_SLOWDOWN: u64 = 4;
+/// Speed of the current, in blocks per second, of the flowing liquids
+pub const CURRENT_SPEED: f32 = 4.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum FlowDir {
+    PosX = 0,
+    NegX = 1,
+    PosY = 2,
+    NegY = 3,
+    Down = 4,
+}
+
+impl FlowDir {
+    pub const HORIZONTAL: [FlowDir; 4] =
+        [FlowDir::PosX, FlowDir::NegX, FlowDir::PosY, FlowDir::NegY];
+
+    pub fn from_u8(x: u8) -> Option<Self> {
+        Some(match x {
+            0 => FlowDir::PosX,
+            1 => FlowDir::NegX,
+            2 => FlowDir::PosY,
+            3 => FlowDir::NegY,
+            4 => FlowDir::Down,
+            _ => return None,
+        })
+    }
+
+    pub fn to_vec(self) -> Vec3<i32> {
+        match self {
+            FlowDir::PosX => Vec3::unit_x(),
+            FlowDir::NegX => -Vec3::unit_x(),
+            FlowDir::PosY => Vec3::unit_y(),
+            FlowDir::NegY => -Vec3::unit_y(),
+            FlowDir::Down => -Vec3::unit_z(),
+        }
+    }
+}
+
+/// How a flowing liquid block flows. Sources, and the liquids generated with
+/// the world, have none.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Flow {
+    pub dir: FlowDir,
+    /// Blocks flowed sideways since the liquid last fell or left its source
+    pub distance: u8,
+}
+
+impl Flow {
+    /// Velocity of the current of the flowing liquid
+    pub fn current(&self) -> Vec3<f32> { self.dir.to_vec().map(|e| e as f32) * CURRENT_SPEED }
+}
+
+/// How far a liquid flows sideways from its source before it stops
+pub fn max_distance(kind: LiquidKind) -> u8 {
+    match kind {
+        LiquidKind::Water => 7,
+        LiquidKind::Lava => 3,
+    }
+}
+
+/// What the block at `pos` becomes on the next flowing step, `None` if it
+/// stays the same. Only empty air and flowing liquids change: liquid flows
+/// down into them first, and sideways from the liquids resting on something.
+pub fn next_block(pos: Vec3<i32>, get: impl Fn(Vec3<i32>) -> Option<Block>) -> Option<Block> {
+    let block = get(pos)?;
+    let kind = match block.liquid_kind() {
+        Some(_) if block.get_flow().is_none() => return None,
+        Some(kind) => Some(kind),
+        None if block == Block::empty() => None,
+        None => return None,
+    };
+    // Liquids don't mix, a flowing liquid is only fed by the same one
+    let feeds = |feeder: Block| {
+        feeder
+            .liquid_kind()
+            .filter(|feeder_kind| kind.map_or(true, |kind| kind == *feeder_kind))
+    };
+
+    let above = get(pos + Vec3::unit_z())?;
+    let inflow = if feeds(above).is_some() {
+        Some((above, Flow {
+            dir: FlowDir::Down,
+            distance: 0,
+        }))
+    } else {
+        FlowDir::HORIZONTAL
+            .iter()
+            .filter_map(|dir| {
+                let from = pos - dir.to_vec();
+                let feeder = get(from)?;
+                let distance = feeder.get_flow().map_or(0, |flow| flow.distance);
+                // Liquid with nothing below it falls instead of spreading
+                let supported = get(from - Vec3::unit_z())? != Block::empty();
+                (supported && distance < max_distance(feeds(feeder)?)).then(|| {
+                    (feeder, Flow {
+                        dir: *dir,
+                        distance: distance + 1,
+                    })
+                })
+            })
+            .min_by_key(|(_, flow)| flow.distance)
+    };
+
+    let next = match inflow {
+        Some((feeder, flow)) => {
+            // Lava keeps the colour of the lava feeding it
+            let liquid = if feeder.is_filled() {
+                feeder
+            } else {
+                feeder.into_vacant()
+            };
+            liquid.with_flow(Some(flow))
+        },
+        None => Block::empty(),
+    };
+    Some(next).filter(|next| *next != block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::{BlockKind, SpriteKind};
+    use hashbrown::HashMap;
+
+    #[test]
+    fn water_flows_down_then_sideways_and_dries_up() {
+        let rock = Block::new(BlockKind::Rock, Rgb::zero());
+        let source = Block::water(SpriteKind::Empty);
+        let mut blocks = HashMap::new();
+        for x in -10..=10 {
+            blocks.insert(Vec3::new(x, 0, 0), rock);
+        }
+        blocks.insert(Vec3::new(0, 0, 2), source);
+        let get = |blocks: &HashMap<Vec3<i32>, Block>, pos: Vec3<i32>| {
+            Some(blocks.get(&pos).copied().unwrap_or_else(Block::empty))
+        };
+
+        let below = next_block(Vec3::new(0, 0, 1), |pos| get(&blocks, pos)).unwrap();
+        assert_eq!(
+            below.get_flow(),
+            Some(Flow {
+                dir: FlowDir::Down,
+                distance: 0
+            })
+        );
+        blocks.insert(Vec3::new(0, 0, 1), below);
+
+        let side = next_block(Vec3::new(1, 0, 1), |pos| get(&blocks, pos)).unwrap();
+        assert_eq!(side.liquid_kind(), Some(LiquidKind::Water));
+        assert_eq!(
+            side.get_flow(),
+            Some(Flow {
+                dir: FlowDir::PosX,
+                distance: 1
+            })
+        );
+        // Sources and the world don't change
+        assert_eq!(
+            next_block(Vec3::new(0, 0, 2), |pos| get(&blocks, pos)),
+            None
+        );
+        assert_eq!(
+            next_block(Vec3::new(0, 0, 0), |pos| get(&blocks, pos)),
+            None
+        );
+
+        blocks.remove(&Vec3::new(0, 0, 2));
+        assert_eq!(
+            next_block(Vec3::new(0, 0, 1), |pos| get(&blocks, pos)),
+            Some(Block::empty())
+        );
+    }
+}
@@ -1,6 +1,7 @@
 pub mod biome;
 pub mod block;
 pub mod chonk;
+pub mod fluid;
 pub mod map;
 pub mod site;
 pub mod sprite;
@@ -10,6 +11,7 @@ pub mod structure;
 pub use self::{
     biome::BiomeKind,
     block::{Block, BlockKind},
+    fluid::{Flow, FlowDir},
     map::MapSizeLg,
     site::SiteKindMeta,
     sprite::SpriteKind,
@@ -226,6 +226,9 @@ make_case_elim!(
         SeaDecorPillar = 0xC7,
         SeashellLantern = 0xC8,
         Rope = 0xC9,
+        Seedling = 0xCA,
+        Sprout = 0xCB,
+        Sign = 0xCC,
     }
 );
 
@@ -428,6 +431,9 @@ impl SpriteKind {
             SpriteKind::Ironwood => item("common.items.log.ironwood"),
             SpriteKind::Frostwood => item("common.items.log.frostwood"),
             SpriteKind::Eldwood => item("common.items.log.eldwood"),
+            SpriteKind::Carrot => item("common.items.food.carrot"),
+            SpriteKind::Tomato => item("common.items.food.tomato"),
+            SpriteKind::Cabbage => item("common.items.food.lettuce"),
             _ => return None,
         })
     }
@@ -496,6 +502,7 @@ impl SpriteKind {
                 | SpriteKind::DungeonWallDecor
                 | SpriteKind::HangingBasket
                 | SpriteKind::HangingSign
+                | SpriteKind::Sign
                 | SpriteKind::WallLamp
                 | SpriteKind::WallLampSmall
                 | SpriteKind::WallSconce
@@ -30,3 +30,80 @@ impl DayPeriod {
 
     pub fn is_light(&self) -> bool { !self.is_dark() }
 }
+
+/// In-game days each season lasts
+pub const DAYS_PER_SEASON: f64 = 7.0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+/// A resource that stores how far into the year the world is, derived from the
+/// time of day.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct SeasonState {
+    /// From 0 at the start of spring to 1 at the end of winter
+    pub year: f32,
+}
+
+impl SeasonState {
+    pub fn from_time_of_day(time_of_day: f64) -> Self {
+        let days = time_of_day / (60.0 * 60.0 * 24.0);
+        Self {
+            year: (days / (DAYS_PER_SEASON * 4.0)).rem_euclid(1.0) as f32,
+        }
+    }
+
+    pub fn season(&self) -> Season {
+        match (self.year * 4.0) as u32 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    /// 0 in the middle of summer, 1 in the middle of winter
+    pub fn coldness(&self) -> f32 {
+        0.5 - 0.5 * ((self.year - 0.375) * std::f32::consts::TAU).cos()
+    }
+
+    /// How far the leaves have turned, peaking towards the end of autumn
+    pub fn foliage_turning(&self) -> f32 { (1.0 - (self.year - 0.7).abs() / 0.2).clamp(0.0, 1.0) }
+
+    /// Rounds down to one of `steps` points of the year, so that what depends
+    /// on the season only has to be updated once per step
+    pub fn quantized(&self, steps: u32) -> Self {
+        Self {
+            year: (self.year * steps as f32).floor() / steps as f32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seasons_follow_the_time_of_day() {
+        let day = 60.0 * 60.0 * 24.0;
+        let season_at = |days: f64| SeasonState::from_time_of_day(days * day);
+
+        assert_eq!(season_at(0.0).season(), Season::Spring);
+        assert_eq!(season_at(DAYS_PER_SEASON * 1.5).season(), Season::Summer);
+        assert_eq!(season_at(DAYS_PER_SEASON * 2.5).season(), Season::Autumn);
+        assert_eq!(season_at(DAYS_PER_SEASON * 3.5).season(), Season::Winter);
+        assert_eq!(season_at(DAYS_PER_SEASON * 4.5).season(), Season::Spring);
+        assert!(season_at(DAYS_PER_SEASON * 1.5).coldness() < 0.01);
+        assert!(season_at(DAYS_PER_SEASON * 3.5).coldness() > 0.99);
+        assert_eq!(
+            season_at(DAYS_PER_SEASON * 3.5).quantized(4),
+            season_at(DAYS_PER_SEASON * 3.0).quantized(4)
+        );
+        assert_eq!(season_at(DAYS_PER_SEASON * 3.5).quantized(4).year, 0.75);
+    }
+}
@@ -276,6 +276,15 @@ impl Trades {
         to_notify
     }
 
+    /// Cancel a trade on behalf of neither party (e.g. because they moved too
+    /// far apart), returning both parties so they can be notified
+    pub fn cancel_trade(&mut self, id: TradeId) -> Option<[Uid; 2]> {
+        let trade = self.trades.remove(&id)?;
+        self.entity_trades.remove(&trade.parties[0]);
+        self.entity_trades.remove(&trade.parties[1]);
+        Some(trade.parties)
+    }
+
     /// See the doc comment on `common::trade::PendingTrade` for the
     /// significance of these checks
     pub fn in_trade_with_property<F: FnOnce(&PendingTrade) -> bool>(
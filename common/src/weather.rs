@@ -12,17 +12,28 @@ pub struct Weather {
     pub cloud: f32,
     /// Rain per time, between 0 and 1
     pub rain: f32,
+    /// Snow per time, between 0 and 1. Cold areas get snow instead of rain.
+    pub snow: f32,
     /// Wind velocity in block / second
     pub wind: Vec2<f32>,
 }
 
 impl Weather {
-    pub fn new(cloud: f32, rain: f32, wind: Vec2<f32>) -> Self { Self { cloud, rain, wind } }
+    pub fn new(cloud: f32, rain: f32, snow: f32, wind: Vec2<f32>) -> Self {
+        Self {
+            cloud,
+            rain,
+            snow,
+            wind,
+        }
+    }
 
     pub fn get_kind(&self) -> WeatherKind {
         // Over 24.5 m/s wind is a storm
         if self.wind.magnitude_squared() >= 24.5f32.powi(2) {
             WeatherKind::Storm
+        } else if (0.1..=1.0).contains(&self.snow) {
+            WeatherKind::Snow
         } else if (0.1..=1.0).contains(&self.rain) {
             WeatherKind::Rain
         } else if (0.2..=1.0).contains(&self.cloud) {
@@ -36,6 +47,7 @@ impl Weather {
         Self {
             cloud: f32::lerp_unclamped(from.cloud, to.cloud, t),
             rain: f32::lerp_unclamped(from.rain, to.rain, t),
+            snow: f32::lerp_unclamped(from.snow, to.snow, t),
             wind: Vec2::<f32>::lerp_unclamped(from.wind, to.wind, t),
         }
     }
@@ -45,6 +57,24 @@ impl Weather {
         const FALL_RATE: f32 = 30.0;
         Vec3::new(self.wind.x, self.wind.y, -FALL_RATE)
     }
+
+    // Get the snow velocity for this weather, snowflakes drift with the wind
+    pub fn snow_vel(&self) -> Vec3<f32> {
+        const FALL_RATE: f32 = 3.0;
+        Vec3::new(self.wind.x, self.wind.y, -FALL_RATE)
+    }
+
+    /// Whether snow rather than rain is falling here
+    pub fn is_snowing(&self) -> bool { self.snow > self.rain }
+
+    /// Density and velocity of the rain or snow falling here
+    pub fn precipitation(&self) -> (f32, Vec3<f32>) {
+        if self.is_snowing() {
+            (self.snow, self.snow_vel())
+        } else {
+            (self.rain, self.rain_vel())
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -52,6 +82,7 @@ pub enum WeatherKind {
     Clear,
     Cloudy,
     Rain,
+    Snow,
     Storm,
 }
 
@@ -61,6 +92,7 @@ impl fmt::Display for WeatherKind {
             WeatherKind::Clear => write!(f, "Clear"),
             WeatherKind::Cloudy => write!(f, "Cloudy"),
             WeatherKind::Rain => write!(f, "Rain"),
+            WeatherKind::Snow => write!(f, "Snow"),
             WeatherKind::Storm => write!(f, "Storm"),
         }
     }
@@ -153,6 +185,7 @@ impl WeatherGrid {
             .reduce(|a, b| Weather {
                 cloud: a.cloud.max(b.cloud),
                 rain: a.rain.max(b.rain),
+                snow: a.snow.max(b.snow),
                 wind: a.wind.map2(b.wind, |a, b| a.max(b)),
             })
             // There will always be 9 elements in locality
@@ -0,0 +1,241 @@
+//! Edge of the playable area. The server configures it, pushes back or hurts
+//! the players beyond it and can move it around over time, for shrinking zones
+//! in event game modes. Clients show a warning as players get close to it.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use vek::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BorderShape {
+    Circle { center: Vec2<f32>, radius: f32 },
+    Rect { min: Vec2<f32>, max: Vec2<f32> },
+}
+
+impl BorderShape {
+    /// Distance from the position to the edge, negative inside the border
+    pub fn distance(&self, pos: Vec2<f32>) -> f32 {
+        match self {
+            Self::Circle { center, radius } => pos.distance(*center) - radius,
+            Self::Rect { min, max } => {
+                let outside = (*min - pos).map2(pos - *max, f32::max);
+                if outside.x > 0.0 || outside.y > 0.0 {
+                    outside.map(|e| e.max(0.0)).magnitude()
+                } else {
+                    outside.reduce_partial_max()
+                }
+            },
+        }
+    }
+
+    /// Closest position inside the border
+    pub fn clamp(&self, pos: Vec2<f32>) -> Vec2<f32> {
+        match self {
+            Self::Circle { center, radius } => {
+                let offset = pos - *center;
+                if offset.magnitude_squared() > radius.powi(2) {
+                    *center + offset.try_normalized().unwrap_or_default() * *radius
+                } else {
+                    pos
+                }
+            },
+            Self::Rect { min, max } => pos.clamped(*min, *max),
+        }
+    }
+
+    /// Closest position on the edge
+    pub fn edge_point(&self, pos: Vec2<f32>) -> Vec2<f32> {
+        match self {
+            Self::Circle { center, radius } => {
+                *center
+                    + (pos - *center)
+                        .try_normalized()
+                        .unwrap_or_else(Vec2::unit_y)
+                        * *radius
+            },
+            Self::Rect { min, max } => {
+                let pos = pos.clamped(*min, *max);
+                [
+                    (pos.x - min.x, Vec2::new(min.x, pos.y)),
+                    (max.x - pos.x, Vec2::new(max.x, pos.y)),
+                    (pos.y - min.y, Vec2::new(pos.x, min.y)),
+                    (max.y - pos.y, Vec2::new(pos.x, max.y)),
+                ]
+                .into_iter()
+                .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                .map_or(pos, |(_, edge)| edge)
+            },
+        }
+    }
+
+    /// The furthest any edge moves to turn into `target`, shapes of different
+    /// kinds being too different to say
+    pub fn max_change(&self, target: &Self) -> Option<f32> {
+        match (self, target) {
+            (
+                Self::Circle { center, radius },
+                Self::Circle {
+                    center: target_center,
+                    radius: target_radius,
+                },
+            ) => Some(center.distance(*target_center) + (radius - target_radius).abs()),
+            (
+                Self::Rect { min, max },
+                Self::Rect {
+                    min: target_min,
+                    max: target_max,
+                },
+            ) => Some(
+                (*min - *target_min)
+                    .map(f32::abs)
+                    .reduce_partial_max()
+                    .max((*max - *target_max).map(f32::abs).reduce_partial_max()),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Moves the edges towards `target` by at most `step`, returning whether
+    /// the target was reached
+    fn approach(&mut self, target: &Self, step: f32) -> bool {
+        match (&mut *self, target) {
+            (
+                Self::Circle { center, radius },
+                Self::Circle {
+                    center: target_center,
+                    radius: target_radius,
+                },
+            ) => {
+                let offset = *target_center - *center;
+                *center += if offset.magnitude() <= step {
+                    offset
+                } else {
+                    offset.normalized() * step
+                };
+                *radius += (target_radius - *radius).clamp(-step, step);
+            },
+            (
+                Self::Rect { min, max },
+                Self::Rect {
+                    min: target_min,
+                    max: target_max,
+                },
+            ) => {
+                *min += (*target_min - *min).map(|e| e.clamp(-step, step));
+                *max += (*target_max - *max).map(|e| e.clamp(-step, step));
+            },
+            (shape, target) => *shape = *target,
+        }
+        *self == *target
+    }
+}
+
+/// Border of the world, there is none when `shape` is `None`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldBorder {
+    pub shape: Option<BorderShape>,
+    /// Shape the border is moving towards
+    pub target: Option<BorderShape>,
+    /// Blocks per second the edges move at towards the target
+    pub speed: f32,
+    /// Health lost each second by the players beyond the border
+    pub damage: f32,
+    /// Whether the players beyond the border are put back inside it
+    pub push_back: bool,
+}
+
+impl Default for WorldBorder {
+    fn default() -> Self {
+        Self {
+            shape: None,
+            target: None,
+            speed: 0.0,
+            damage: 10.0,
+            push_back: false,
+        }
+    }
+}
+
+impl WorldBorder {
+    /// Distance from the position to the edge, negative inside the border
+    pub fn distance(&self, pos: Vec2<f32>) -> Option<f32> {
+        self.shape.as_ref().map(|shape| shape.distance(pos))
+    }
+
+    /// Moves the border to `target` over `time` seconds, or right away without
+    /// a time or a border to start from
+    pub fn move_to(&mut self, target: BorderShape, time: Option<f32>) {
+        let change = self
+            .shape
+            .as_ref()
+            .zip(time.filter(|time| *time > 0.0))
+            .and_then(|(shape, time)| Some(shape.max_change(&target)? / time));
+        match change {
+            Some(speed) => {
+                self.target = Some(target);
+                self.speed = speed;
+            },
+            None => {
+                self.shape = Some(target);
+                self.target = None;
+            },
+        }
+    }
+
+    pub fn remove(&mut self) {
+        self.shape = None;
+        self.target = None;
+    }
+
+    /// Moves the border towards its target
+    pub fn tick(&mut self, dt: f32) {
+        let reached = match (&mut self.shape, &self.target) {
+            (Some(shape), Some(target)) => shape.approach(target, self.speed * dt),
+            _ => false,
+        };
+        if reached {
+            self.target = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn border_shrinks_over_time() {
+        let mut border = WorldBorder::default();
+        border.move_to(
+            BorderShape::Circle {
+                center: Vec2::zero(),
+                radius: 100.0,
+            },
+            Some(10.0),
+        );
+        // Without a border yet, the first one is there right away
+        assert_eq!(border.distance(Vec2::new(150.0, 0.0)), Some(50.0));
+
+        border.move_to(
+            BorderShape::Circle {
+                center: Vec2::zero(),
+                radius: 50.0,
+            },
+            Some(10.0),
+        );
+        border.tick(5.0);
+        assert_eq!(border.distance(Vec2::new(75.0, 0.0)), Some(0.0));
+        border.tick(10.0);
+        assert_eq!(border.target, None);
+        assert_eq!(border.distance(Vec2::new(75.0, 0.0)), Some(25.0));
+
+        let rect = BorderShape::Rect {
+            min: Vec2::new(-10.0, -10.0),
+            max: Vec2::new(10.0, 10.0),
+        };
+        assert_eq!(rect.distance(Vec2::new(0.0, 5.0)), -5.0);
+        assert_eq!(rect.distance(Vec2::new(13.0, 14.0)), 5.0);
+        assert_eq!(rect.clamp(Vec2::new(13.0, 14.0)), Vec2::new(10.0, 10.0));
+    }
+}
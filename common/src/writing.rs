@@ -0,0 +1,58 @@
+//! Text written by players on signs and in books
+
+/// Characters a sign fits at most
+pub const MAX_SIGN_LEN: usize = 120;
+/// Characters a book fits at most
+pub const MAX_BOOK_LEN: usize = 2000;
+/// Blocks away from a sign its text can be read from
+pub const SIGN_READ_RANGE: f32 = 16.0;
+
+pub const SIGN_ITEM: &str = "common.items.utility.sign";
+pub const BOOK_ITEM: &str = "common.items.utility.book";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TextError {
+    Empty,
+    TooLong,
+}
+
+/// Trims the text and strips the control characters out of it, line breaks
+/// being kept in books only
+pub fn sanitize(text: &str, max_len: usize, multiline: bool) -> Result<String, TextError> {
+    let text = text
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control() || (multiline && *c == '\n'))
+        .collect::<String>();
+    if text.is_empty() {
+        Err(TextError::Empty)
+    } else if text.chars().count() > max_len {
+        Err(TextError::TooLong)
+    } else {
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_sanitized() {
+        assert_eq!(
+            sanitize("  Inn\u{7} north ", MAX_SIGN_LEN, false),
+            Ok("Inn north".to_owned())
+        );
+        assert_eq!(sanitize("a\nb", MAX_SIGN_LEN, false), Ok("ab".to_owned()));
+        assert_eq!(sanitize("a\nb", MAX_BOOK_LEN, true), Ok("a\nb".to_owned()));
+        assert_eq!(sanitize(" \n", MAX_BOOK_LEN, true), Err(TextError::Empty));
+        assert_eq!(
+            sanitize(&"ä".repeat(MAX_SIGN_LEN), MAX_SIGN_LEN, false).map(|t| t.chars().count()),
+            Ok(MAX_SIGN_LEN)
+        );
+        assert_eq!(
+            sanitize(&"a".repeat(MAX_SIGN_LEN + 1), MAX_SIGN_LEN, false),
+            Err(TextError::TooLong)
+        );
+    }
+}
@@ -26,6 +26,19 @@ impl BuildAreas {
 
     pub fn area_names(&self) -> &HashMap<String, Id<Aabb<i32>>> { &self.area_names }
 
+    /// Whether the position is in one of the areas protected from the world,
+    /// that is any of them but the one covering the whole world
+    pub fn protects(&self, pos: Vec3<i32>) -> bool {
+        self.area_names
+            .iter()
+            .filter(|(name, _)| name.as_str() != "world")
+            .any(|(_, id)| {
+                self.areas
+                    .get(*id)
+                    .map_or(false, |area| area.contains_point(pos))
+            })
+    }
+
     /// If the area_name is already in the map, returns Err(area_name).
     pub fn insert(&mut self, area_name: String, area: Aabb<i32>) -> Result<Id<Aabb<i32>>, String> {
         let area_name_entry = match self.area_names.entry(area_name) {
@@ -13,15 +13,16 @@ use common::{
     outcome::Outcome,
     region::RegionMap,
     resources::{
-        DeltaTime, EntitiesDiedLastTick, GameMode, PlayerEntity, PlayerPhysicsSettings, Time,
-        TimeOfDay,
+        DayCycleFactor, DeltaTime, EntitiesDiedLastTick, GameMode, PlayerEntity,
+        PlayerPhysicsSettings, Time, TimeOfDay,
     },
     slowjob::SlowJobPool,
     terrain::{Block, TerrainChunk, TerrainGrid},
-    time::DayPeriod,
+    time::{DayPeriod, SeasonState},
     trade::Trades,
     vol::{ReadVol, WriteVol},
     weather::{Weather, WeatherGrid},
+    world_border::WorldBorder,
 };
 use common_base::span;
 use common_ecs::{PhysicsMetrics, SysMetrics};
@@ -38,10 +39,6 @@ use specs::{
 use std::sync::Arc;
 use vek::*;
 
-/// How much faster should an in-game day be compared to a real day?
-// TODO: Don't hard-code this.
-const DAY_CYCLE_FACTOR: f64 = 24.0 * 2.0;
-
 /// At what point should we stop speeding up physics to compensate for lag? If
 /// we speed physics up too fast, we'd skip important physics events like
 /// collisions. This constant determines the upper limit. If delta time exceeds
@@ -134,6 +131,7 @@ impl State {
         ecs.register::<comp::Stats>();
         ecs.register::<comp::SkillSet>();
         ecs.register::<comp::ActiveAbilities>();
+        ecs.register::<comp::AbilityCooldowns>();
         ecs.register::<comp::Buffs>();
         ecs.register::<comp::Auras>();
         ecs.register::<comp::Energy>();
@@ -141,6 +139,12 @@ impl State {
         ecs.register::<comp::Health>();
         ecs.register::<comp::Poise>();
         ecs.register::<comp::CanBuild>();
+        ecs.register::<comp::QuestLog>();
+        ecs.register::<comp::Reputation>();
+        ecs.register::<comp::Portal>();
+        ecs.register::<comp::DiscoveredPortals>();
+        ecs.register::<comp::Minigame>();
+        ecs.register::<comp::Attached>();
         ecs.register::<comp::LightEmitter>();
         ecs.register::<comp::Item>();
         ecs.register::<comp::Scale>();
@@ -174,6 +178,7 @@ impl State {
 
         // Register common unsynced components
         ecs.register::<comp::PreviousPhysCache>();
+        ecs.register::<comp::Asleep>();
         ecs.register::<comp::PosVelOriDefer>();
 
         // Register client-local components
@@ -195,22 +200,28 @@ impl State {
         ecs.register::<comp::Admin>();
         ecs.register::<comp::Waypoint>();
         ecs.register::<comp::MapMarker>();
+        ecs.register::<comp::SharedMapMarkers>();
         ecs.register::<comp::Projectile>();
         ecs.register::<comp::Melee>();
         ecs.register::<comp::ItemDrop>();
         ecs.register::<comp::ChatMode>();
         ecs.register::<comp::Faction>();
+        ecs.register::<comp::faction::Faction>();
         ecs.register::<comp::invite::Invite>();
         ecs.register::<comp::invite::PendingInvites>();
         ecs.register::<comp::Beam>();
+        ecs.register::<comp::TrainingDummy>();
 
         // Register synced resources used by the ECS.
         ecs.insert(TimeOfDay(0.0));
+        ecs.insert(DayCycleFactor::default());
         ecs.insert(Calendar::default());
         ecs.insert(WeatherGrid::new(Vec2::zero()));
+        ecs.insert(WorldBorder::default());
 
         // Register unsynced resources used by the ECS.
         ecs.insert(Time(0.0));
+        ecs.insert(SeasonState::default());
         ecs.insert(DeltaTime(0.0));
         ecs.insert(PlayerEntity(None));
         ecs.insert(TerrainGrid::new().unwrap());
@@ -373,6 +384,9 @@ impl State {
     /// Get the current in-game day period (period of the day/night cycle)
     pub fn get_day_period(&self) -> DayPeriod { self.get_time_of_day().into() }
 
+    /// Get how far into the year the world is
+    pub fn get_season(&self) -> SeasonState { *self.ecs.read_resource::<SeasonState>() }
+
     /// Get the current in-game time.
     ///
     /// Note that this does not correspond to the time of day.
@@ -510,8 +524,14 @@ impl State {
     ) {
         span!(_guard, "tick", "State::tick");
         // Change the time accordingly.
-        self.ecs.write_resource::<TimeOfDay>().0 += dt.as_secs_f64() * DAY_CYCLE_FACTOR;
+        let day_cycle_factor = self.ecs.read_resource::<DayCycleFactor>().0;
+        self.ecs.write_resource::<TimeOfDay>().0 += dt.as_secs_f64() * day_cycle_factor;
+        *self.ecs.write_resource::<SeasonState>() =
+            SeasonState::from_time_of_day(self.get_time_of_day());
         self.ecs.write_resource::<Time>().0 += dt.as_secs_f64();
+        self.ecs
+            .write_resource::<WorldBorder>()
+            .tick(dt.as_secs_f32());
 
         // Update delta time.
         // Beyond a delta time of MAX_DELTA_TIME, start lagging to avoid skipping
@@ -0,0 +1,69 @@
+use common::{
+    comp::{Attached, Body, Ori, Pos, Scale, Vel},
+    uid::UidAllocator,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{
+    saveload::{Marker, MarkerAllocator},
+    Entities, Join, Read, ReadStorage, WriteStorage,
+};
+use vek::*;
+
+/// This system moves the attached entities along with the point of the body
+/// of the entity they are attached to
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, UidAllocator>,
+        Entities<'a>,
+        ReadStorage<'a, Attached>,
+        ReadStorage<'a, Body>,
+        ReadStorage<'a, Scale>,
+        WriteStorage<'a, Pos>,
+        WriteStorage<'a, Vel>,
+        WriteStorage<'a, Ori>,
+    );
+
+    const NAME: &'static str = "attach";
+    const ORIGIN: Origin = Origin::Common;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            uid_allocator,
+            entities,
+            attacheds,
+            bodies,
+            scales,
+            mut positions,
+            mut velocities,
+            mut orientations,
+        ): Self::SystemData,
+    ) {
+        for (entity, attached) in (&entities, &attacheds).join() {
+            // The server lets go of the entities attached to the ones it deletes, the
+            // clients may just not know about the other entity yet
+            let Some(anchor) = uid_allocator
+                .retrieve_entity_internal(attached.to.id())
+                .filter(|anchor| *anchor != entity)
+            else { continue };
+
+            let pos = positions.get(anchor).copied();
+            let ori = orientations.get(anchor).copied();
+            let vel = velocities.get(anchor).copied();
+            if let (Some(pos), Some(ori), Some(vel)) = (pos, ori, vel) {
+                let scale = scales.get(anchor).map_or(1.0, |s| s.0);
+                let offset = bodies
+                    .get(anchor)
+                    .map_or(Vec3::zero(), |body| attached.point.offset(body))
+                    * scale
+                    + attached.offset;
+                let _ = positions.insert(entity, Pos(pos.0 + ori.to_quat() * offset));
+                let _ = orientations.insert(entity, ori);
+                let _ = velocities.insert(entity, vel);
+            }
+        }
+    }
+}
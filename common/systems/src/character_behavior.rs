@@ -6,9 +6,9 @@ use specs::{
 use common::{
     comp::{
         self, character_state::OutputEvents, inventory::item::MaterialStatManifest,
-        ActiveAbilities, Beam, Body, CharacterState, Combo, Controller, Density, Energy, Health,
-        Inventory, InventoryManip, Mass, Melee, Ori, PhysicsState, Poise, Pos, SkillSet,
-        StateUpdate, Stats, Vel,
+        AbilityCooldowns, ActiveAbilities, Beam, Body, CharacterState, Combo, Controller, Density,
+        Energy, Health, Inventory, InventoryManip, Mass, Melee, Ori, PhysicsState, Poise, Pos,
+        SkillSet, StateUpdate, Stats, Vel,
     },
     event::{EventBus, LocalEvent, ServerEvent},
     link::Is,
@@ -67,6 +67,7 @@ impl<'a> System<'a> for Sys {
         WriteStorage<'a, Energy>,
         WriteStorage<'a, Controller>,
         WriteStorage<'a, Poise>,
+        WriteStorage<'a, AbilityCooldowns>,
         Read<'a, EventBus<Outcome>>,
     );
 
@@ -86,6 +87,7 @@ impl<'a> System<'a> for Sys {
             mut energies,
             mut controllers,
             mut poises,
+            mut ability_cooldowns,
             outcomes,
         ): Self::SystemData,
     ) {
@@ -112,7 +114,7 @@ impl<'a> System<'a> for Sys {
             health,
             body,
             physics,
-            (stat, skill_set, active_abilities, is_rider),
+            (stat, skill_set, active_abilities, cooldowns, is_rider),
             combo,
         ) in (
             &read_data.entities,
@@ -133,6 +135,7 @@ impl<'a> System<'a> for Sys {
                 &read_data.stats,
                 &read_data.skill_sets,
                 read_data.active_abilities.maybe(),
+                (&mut ability_cooldowns).maybe(),
                 read_data.is_riders.maybe(),
             ),
             read_data.combos.maybe(),
@@ -192,6 +195,7 @@ impl<'a> System<'a> for Sys {
                 stat,
                 skill_set,
                 active_abilities,
+                cooldowns,
                 combo,
                 alignment: read_data.alignments.get(entity),
                 terrain: &read_data.terrain,
@@ -203,10 +207,16 @@ impl<'a> System<'a> for Sys {
                     &join_struct,
                     &read_data.lazy_update,
                     &read_data.dt,
+                    &read_data.time,
                     &read_data.msm,
                 );
                 let state_update = j.character.handle_event(&j, &mut output_events, action);
-                Self::publish_state_update(&mut join_struct, state_update, &mut output_events);
+                Self::publish_state_update(
+                    &mut join_struct,
+                    state_update,
+                    *read_data.time,
+                    &mut output_events,
+                );
             }
 
             // Mounted occurs after control actions have been handled
@@ -221,11 +231,17 @@ impl<'a> System<'a> for Sys {
                 &join_struct,
                 &read_data.lazy_update,
                 &read_data.dt,
+                &read_data.time,
                 &read_data.msm,
             );
 
             let state_update = j.character.behavior(&j, &mut output_events);
-            Self::publish_state_update(&mut join_struct, state_update, &mut output_events);
+            Self::publish_state_update(
+                &mut join_struct,
+                state_update,
+                *read_data.time,
+                &mut output_events,
+            );
         }
 
         local_emitter.append_vec(local_events);
@@ -237,6 +253,7 @@ impl Sys {
     fn publish_state_update(
         join: &mut JoinStruct,
         mut state_update: StateUpdate,
+        time: Time,
         output_events: &mut OutputEvents,
     ) {
         // Here we check for equality with the previous value of these components before
@@ -255,6 +272,11 @@ impl Sys {
         if *join.energy != state_update.energy {
             *join.energy = state_update.energy;
         };
+        if let (Some((index, duration)), Some(cooldowns)) =
+            (state_update.started_cooldown, join.cooldowns.as_mut())
+        {
+            cooldowns.start(index, time, duration);
+        }
 
         // These components use a different type of change detection.
         *join.pos = state_update.pos;
@@ -72,6 +72,14 @@ impl<'a> System<'a> for Sys {
                             server_emitter.emit(ServerEvent::NpcInteract(entity, npc_entity));
                         }
                     },
+                    ControlEvent::Talk(npc_uid, choice) => {
+                        if let Some(npc_entity) = read_data
+                            .uid_allocator
+                            .retrieve_entity_internal(npc_uid.id())
+                        {
+                            server_emitter.emit(ServerEvent::NpcTalk(entity, npc_entity, choice));
+                        }
+                    },
                     ControlEvent::InitiateInvite(inviter_uid, kind) => {
                         server_emitter.emit(ServerEvent::InitiateInvite(entity, inviter_uid, kind));
                     },
@@ -89,6 +97,41 @@ impl<'a> System<'a> for Sys {
                         server_emitter.emit(ServerEvent::GroupManip(entity, manip))
                     },
                     ControlEvent::Respawn => server_emitter.emit(ServerEvent::Respawn(entity)),
+                    ControlEvent::Sleep(bed_pos) => {
+                        server_emitter.emit(ServerEvent::Sleep { entity, bed_pos })
+                    },
+                    ControlEvent::ActivatePortal(portal_uid) => {
+                        if let Some(portal) = read_data
+                            .uid_allocator
+                            .retrieve_entity_internal(portal_uid.id())
+                        {
+                            server_emitter.emit(ServerEvent::ActivatePortal { entity, portal });
+                        }
+                    },
+                    ControlEvent::UsePortal(portal_uid, destination) => {
+                        if let Some(portal) = read_data
+                            .uid_allocator
+                            .retrieve_entity_internal(portal_uid.id())
+                        {
+                            server_emitter.emit(ServerEvent::UsePortal {
+                                entity,
+                                portal,
+                                destination,
+                            });
+                        }
+                    },
+                    ControlEvent::Minigame(minigame_uid, action) => {
+                        if let Some(minigame) = read_data
+                            .uid_allocator
+                            .retrieve_entity_internal(minigame_uid.id())
+                        {
+                            server_emitter.emit(ServerEvent::Minigame {
+                                entity,
+                                minigame,
+                                action,
+                            });
+                        }
+                    },
                     ControlEvent::Utterance(kind) => {
                         if let (Some(pos), Some(body)) = (
                             read_data.positions.get(entity),
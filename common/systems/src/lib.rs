@@ -1,6 +1,7 @@
 #![feature(bool_to_option, let_else, btree_drain_filter)]
 #![allow(clippy::option_map_unit_fn)]
 
+pub mod attach;
 mod aura;
 mod beam;
 mod buff;
@@ -32,6 +33,7 @@ pub fn add_local_systems(dispatch_builder: &mut DispatcherBuilder) {
         &mount::Sys::sys_name(),
         &stats::Sys::sys_name(),
     ]);
+    dispatch::<attach::Sys>(dispatch_builder, &[&phys::Sys::sys_name()]);
     dispatch::<projectile::Sys>(dispatch_builder, &[&phys::Sys::sys_name()]);
     dispatch::<shockwave::Sys>(dispatch_builder, &[&phys::Sys::sys_name()]);
     dispatch::<beam::Sys>(dispatch_builder, &[&phys::Sys::sys_name()]);
@@ -3,8 +3,9 @@ use common::{
         body::ship::figuredata::{VoxelCollider, VOXEL_COLLIDER_MANIFEST},
         fluid_dynamics::{Fluid, LiquidKind, Wings},
         inventory::item::armor::Friction,
-        Body, CharacterState, Collider, Density, Immovable, Mass, Ori, PhysicsState, Pos,
-        PosVelOriDefer, PreviousPhysCache, Projectile, Scale, Stats, Sticky, Vel,
+        Asleep, Attached, Body, CharacterState, Collider, Density, Immovable, Mass, Ori,
+        PhysicsState, Player, Pos, PosVelOriDefer, PreviousPhysCache, Projectile, Scale, Stats,
+        Sticky, Vel,
     },
     consts::{AIR_DENSITY, FRIC_GROUND, GRAVITY},
     event::{EventBus, ServerEvent},
@@ -29,6 +30,16 @@ use specs::{
 use std::ops::Range;
 use vek::*;
 
+/// Ticks an entity has to stay at rest for before it falls asleep
+const TICKS_BEFORE_SLEEP: u32 = 60;
+/// Entities this close to a player don't sleep
+const SLEEP_PLAYER_DISTANCE: f32 = 64.0;
+/// Speed under which an entity is at rest
+const REST_SPEED: f32 = 0.1;
+/// How fast a stretched rope pulls back the entity hanging from it, as the
+/// fraction of its stretch per second
+const ROPE_STIFFNESS: f32 = 10.0;
+
 /// The density of the fluid as a function of submersion ratio in given fluid
 /// where it is assumed that any unsubmersed part is is air.
 // TODO: Better suited partial submersion curve?
@@ -115,12 +126,14 @@ pub struct PhysicsRead<'a> {
     masses: ReadStorage<'a, Mass>,
     colliders: ReadStorage<'a, Collider>,
     is_ridings: ReadStorage<'a, Is<Rider>>,
+    attacheds: ReadStorage<'a, Attached>,
     projectiles: ReadStorage<'a, Projectile>,
     char_states: ReadStorage<'a, CharacterState>,
     bodies: ReadStorage<'a, Body>,
     character_states: ReadStorage<'a, CharacterState>,
     densities: ReadStorage<'a, Density>,
     stats: ReadStorage<'a, Stats>,
+    players: ReadStorage<'a, Player>,
 }
 
 #[derive(SystemData)]
@@ -133,6 +146,7 @@ pub struct PhysicsWrite<'a> {
     pos_vel_ori_defers: WriteStorage<'a, PosVelOriDefer>,
     orientations: WriteStorage<'a, Ori>,
     previous_phys_cache: WriteStorage<'a, PreviousPhysCache>,
+    asleeps: WriteStorage<'a, Asleep>,
     outcomes: Read<'a, EventBus<Outcome>>,
 }
 
@@ -163,6 +177,103 @@ impl<'a> PhysicsData<'a> {
         }
     }
 
+    /// Puts to sleep the entities left at rest away from the players, and wakes
+    /// up the sleeping ones once something disturbs them: a player comes
+    /// close, something pushes them, or the ground under them is gone
+    fn maintain_sleep(&mut self) {
+        span!(_guard, "Maintain physics sleep");
+        let PhysicsData {
+            ref read,
+            ref mut write,
+        } = self;
+        let player_positions = (&write.positions, &read.players)
+            .join()
+            .map(|(pos, _)| pos.0)
+            .collect::<Vec<_>>();
+
+        let mut fall_asleep = Vec::new();
+        let mut wake_up = Vec::new();
+        for (entity, pos, vel, collider, physics_state, phys_cache, asleep, _) in (
+            &read.entities,
+            &write.positions,
+            &write.velocities,
+            &read.colliders,
+            &write.physics_states,
+            &mut write.previous_phys_cache,
+            write.asleeps.maybe(),
+            !&read.players,
+        )
+            .join()
+        {
+            let supported = physics_state.on_ground.is_some()
+                && read
+                    .terrain
+                    .get((pos.0 - Vec3::unit_z() * 0.5).map(|e| e.floor() as i32))
+                    .map_or(false, |block| block.is_solid());
+            let at_rest = supported
+                && !collider.is_voxel()
+                && physics_state.in_liquid().is_none()
+                && vel.0.magnitude_squared() < REST_SPEED.powi(2);
+            phys_cache.rest_ticks = if at_rest {
+                phys_cache.rest_ticks.saturating_add(1)
+            } else {
+                0
+            };
+
+            let near_player = player_positions.iter().any(|player_pos| {
+                player_pos.distance_squared(pos.0) < SLEEP_PLAYER_DISTANCE.powi(2)
+            });
+            let sleeps = !near_player && phys_cache.rest_ticks >= TICKS_BEFORE_SLEEP;
+            match (asleep.is_some(), sleeps) {
+                (false, true) => fall_asleep.push(entity),
+                (true, false) => wake_up.push(entity),
+                _ => {},
+            }
+        }
+
+        for entity in fall_asleep {
+            let _ = write.asleeps.insert(entity, Asleep);
+        }
+        for entity in wake_up {
+            write.asleeps.remove(entity);
+        }
+    }
+
+    /// Keeps the entities hanging from a rope within its length of the point it
+    /// is tied to, which turns their fall into a swing
+    fn apply_rope_constraints(&mut self) {
+        span!(_guard, "Apply rope constraints");
+        let PhysicsData {
+            ref read,
+            ref mut write,
+        } = self;
+        for (pos, vel, character_state) in (
+            &write.positions,
+            &mut write.velocities,
+            &read.character_states,
+        )
+            .join()
+        {
+            let (anchor, length) = match character_state.rope() {
+                Some(rope) => rope,
+                None => continue,
+            };
+            let to_entity = pos.0 - anchor;
+            let dist = to_entity.magnitude();
+            if dist <= length || dist < f32::EPSILON {
+                continue;
+            }
+            let dir = to_entity / dist;
+            // The taut rope stops the entity moving away from the anchor, and pulls it
+            // back in like a stiff spring
+            let outward = vel.0.dot(dir);
+            if outward > 0.0 {
+                vel.0 -= dir * outward;
+            }
+            vel.0 -= dir * (dist - length) * ROPE_STIFFNESS;
+        }
+    }
+
     fn maintain_pushback_cache(&mut self) {
         span!(_guard, "Maintain pushback cache");
         // Add PreviousPhysCache for all relevant entities
@@ -190,6 +301,7 @@ impl<'a> PhysicsData<'a> {
                     origins: None,
                     pos: None,
                     ori: Quaternion::identity(),
+                    rest_ticks: 0,
                 });
         }
 
@@ -336,6 +448,8 @@ impl<'a> PhysicsData<'a> {
             // or into a separate component.
             read.projectiles.maybe(),
             read.char_states.maybe(),
+            !&read.attacheds,
+            !&write.asleeps,
         )
             .par_join()
             .map_init(
@@ -357,6 +471,8 @@ impl<'a> PhysicsData<'a> {
                     physics,
                     projectile,
                     char_state_maybe,
+                    _,
+                    _,
                 )| {
                     let is_sticky = sticky.is_some();
                     let is_immovable = immovable.is_some();
@@ -389,6 +505,8 @@ impl<'a> PhysicsData<'a> {
 
                     spatial_grid
                         .in_circle_aabr(query_center, query_radius)
+                        // Attached entities move along with the one they are attached to
+                        .filter(|entity| !read.attacheds.contains(*entity))
                         .filter_map(|entity| {
                             let uid = read.uids.get(entity)?;
                             let pos = positions.get(entity)?;
@@ -580,6 +698,8 @@ impl<'a> PhysicsData<'a> {
             !&write.pos_vel_ori_defers, // This is the one we are adding
             write.previous_phys_cache.mask(),
             !&read.is_ridings,
+            !&read.attacheds,
+            !&write.asleeps,
         )
             .join()
             .map(|t| (t.0, *t.2, *t.3, *t.4))
@@ -611,6 +731,8 @@ impl<'a> PhysicsData<'a> {
             &read.masses,
             &read.densities,
             !&read.is_ridings,
+            !&read.attacheds,
+            !&write.asleeps,
         )
             .par_join()
             .for_each_init(
@@ -629,6 +751,8 @@ impl<'a> PhysicsData<'a> {
                     mass,
                     density,
                     _,
+                    _,
+                    _,
                 )| {
                     let in_loaded_chunk = read
                         .terrain
@@ -740,6 +864,8 @@ impl<'a> PhysicsData<'a> {
             &mut write.pos_vel_ori_defers,
             previous_phys_cache,
             !&read.is_ridings,
+            !&read.attacheds,
+            !&write.asleeps,
         )
             .par_join()
             .filter(|tuple| tuple.3.is_voxel() == terrain_like_entities)
@@ -763,6 +889,8 @@ impl<'a> PhysicsData<'a> {
                     pos_vel_ori_defer,
                     previous_cache,
                     _,
+                    _,
+                    _,
                 )| {
                     let mut land_on_ground = None;
                     let mut outcomes = Vec::new();
@@ -984,7 +1112,7 @@ impl<'a> PhysicsData<'a> {
                                     vox.liquid_kind().map(|kind| Fluid::Liquid {
                                         kind,
                                         depth: 1.0,
-                                        vel: Vel::zero(),
+                                        vel: liquid_current(*vox),
                                     })
                                 })
                                 .or_else(|| match physics_state.in_fluid {
@@ -1307,6 +1435,8 @@ impl<'a> System<'a> for Sys {
 
     fn run(job: &mut Job<Self>, mut physics_data: Self::SystemData) {
         physics_data.reset();
+        physics_data.maintain_sleep();
+        physics_data.apply_rope_constraints();
 
         // Apply pushback
         //
@@ -1807,7 +1937,9 @@ fn box_voxel_collision<'a, T: BaseVol<Vox = Block> + ReadVol>(
             Fluid::Liquid {
                 kind,
                 depth: new_depth,
-                vel: Vel::zero(),
+                vel: terrain
+                    .get(pos.0.map(|e| e.floor() as i32))
+                    .map_or_else(|_| Vel::zero(), |block| liquid_current(*block)),
             }
         })
         .or_else(|| match physics_state.in_fluid {
@@ -1819,6 +1951,14 @@ fn box_voxel_collision<'a, T: BaseVol<Vox = Block> + ReadVol>(
         });
 }
 
+/// Velocity of the current of the liquid block, which pushes the entities in
+/// it along
+fn liquid_current(block: Block) -> Vel {
+    Vel(block
+        .get_flow()
+        .map_or_else(Vec3::zero, |flow| flow.current()))
+}
+
 fn voxel_collider_bounding_sphere(
     voxel_collider: &VoxelCollider,
     pos: &Pos,
@@ -203,6 +203,7 @@ impl<'a> System<'a> for Sys {
                 // Abilities that temporarily stall energy gain, but preserve regen_rate.
                 CharacterState::Roll { .. }
                 | CharacterState::Wallrun { .. }
+                | CharacterState::Grapple { .. }
                 | CharacterState::Climb { .. }
                 | CharacterState::Stunned { .. }
                 | CharacterState::BasicBlock { .. }
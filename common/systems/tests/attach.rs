@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use common::{
+        comp::{object, AttachPoint, Attached, Body, Ori, Pos, Vel},
+        resources::{DeltaTime, GameMode, Time},
+    };
+    use common_ecs::dispatch;
+    use common_net::sync::WorldSyncExt;
+    use common_state::State;
+    use specs::{Builder, Entity, WorldExt};
+    use std::{f32::consts::PI, time::Duration};
+    use vek::{approx::AbsDiffEq, Vec3};
+    use veloren_common_systems::attach;
+
+    fn setup() -> State {
+        let mut state = State::new(GameMode::Server);
+        state.ecs_mut().read_resource::<Time>();
+        state.ecs_mut().read_resource::<DeltaTime>();
+        state
+    }
+
+    fn create_entity(state: &mut State, pos: Vec3<f32>, ori: Ori) -> Entity {
+        state
+            .ecs_mut()
+            .create_entity_synced()
+            .with(Pos(pos))
+            .with(Vel(Vec3::new(1.0, 2.0, 0.0)))
+            .with(ori)
+            .with(Body::Object(object::Body::Crate))
+            .build()
+    }
+
+    fn tick(state: &mut State) {
+        state.tick(
+            Duration::from_millis(33),
+            |dispatch_builder| {
+                dispatch::<attach::Sys>(dispatch_builder, &[]);
+            },
+            false,
+        );
+    }
+
+    fn pos(state: &State, entity: Entity) -> Vec3<f32> {
+        state.ecs().read_storage::<Pos>().get(entity).unwrap().0
+    }
+
+    #[test]
+    fn attached_entities_follow_the_point_they_are_attached_to() {
+        let mut state = setup();
+        let anchor_pos = Vec3::new(10.0, 20.0, 30.0);
+        let anchor = create_entity(&mut state, anchor_pos, Ori::default().yawed_left(PI / 2.0));
+        let carried = create_entity(&mut state, Vec3::zero(), Ori::default());
+        let anchor_uid = state.ecs().uid_from_entity(anchor).unwrap();
+        let offset = Vec3::new(0.0, 0.0, 0.5);
+        state
+            .ecs_mut()
+            .write_storage::<Attached>()
+            .insert(
+                carried,
+                Attached::new(anchor_uid, AttachPoint::MainHand).with_offset(offset),
+            )
+            .unwrap();
+
+        tick(&mut state);
+
+        let body = Body::Object(object::Body::Crate);
+        let ori = Ori::default().yawed_left(PI / 2.0);
+        let expected = anchor_pos + ori.to_quat() * (AttachPoint::MainHand.offset(&body) + offset);
+        assert!(pos(&state, carried).abs_diff_eq(&expected, 0.0001));
+        assert_eq!(state.ecs().read_storage::<Ori>().get(carried), Some(&ori));
+        assert_eq!(
+            state.ecs().read_storage::<Vel>().get(carried).unwrap().0,
+            Vec3::new(1.0, 2.0, 0.0)
+        );
+
+        // Moving the anchor brings the attached entity along
+        let moved = anchor_pos + Vec3::new(5.0, 0.0, 0.0);
+        state
+            .ecs_mut()
+            .write_storage::<Pos>()
+            .insert(anchor, Pos(moved))
+            .unwrap();
+        tick(&mut state);
+        assert!(pos(&state, carried).abs_diff_eq(&(expected + Vec3::new(5.0, 0.0, 0.0)), 0.0001));
+    }
+
+    #[test]
+    fn attached_entities_stay_put_without_their_anchor() {
+        let mut state = setup();
+        let anchor = create_entity(&mut state, Vec3::new(10.0, 20.0, 30.0), Ori::default());
+        let carried = create_entity(&mut state, Vec3::new(1.0, 1.0, 1.0), Ori::default());
+        let anchor_uid = state.ecs().uid_from_entity(anchor).unwrap();
+        state
+            .ecs_mut()
+            .write_storage::<Attached>()
+            .insert(carried, Attached::new(anchor_uid, AttachPoint::Back))
+            .unwrap();
+        state.ecs_mut().delete_entity(anchor).unwrap();
+
+        tick(&mut state);
+
+        assert_eq!(pos(&state, carried), Vec3::new(1.0, 1.0, 1.0));
+    }
+}
@@ -112,4 +112,4 @@ pub use api::{
     ParticipantError, ParticipantEvent, Stream, StreamError, StreamParams,
 };
 pub use message::Message;
-pub use network_protocol::{InitProtocolError, Pid, Promises};
+pub use network_protocol::{InitProtocolError, Pid, Promises, VELOREN_NETWORK_VERSION};
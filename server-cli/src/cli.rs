@@ -67,6 +67,15 @@ pub enum Message {
     },
     /// Disconnects all connected clients
     DisconnectAllClients,
+    /// Runs an in-game chat command with every permission, `/<name> [args]`
+    /// is a shorthand for it
+    Command {
+        /// Name of the command, without the leading '/'
+        name: String,
+        /// Arguments of the command
+        #[structopt(allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
 }
 
 #[derive(StructOpt)]
@@ -112,12 +121,24 @@ pub struct ArgvApp {
     #[structopt(default_value_t, long, short, possible_values = SqlLogMode::variants())]
     /// Enables SQL logging
     pub sql_log_mode: SqlLogMode,
+    #[structopt(long)]
+    /// Keeps the settings and saves beside the executable
+    pub portable: bool,
     #[structopt(subcommand)]
     pub command: Option<ArgvCommand>,
 }
 
 pub fn parse_command(input: &str, msg_s: &mut Sender<Message>) {
-    match TuiApp::try_parse_from(shell_words::split(input).unwrap_or_default()) {
+    let words = shell_words::split(input).unwrap_or_default();
+    // Chat commands can be typed like in game
+    let words = match words.split_first() {
+        Some((name, args)) if name.starts_with('/') => std::iter::once("command".to_string())
+            .chain(std::iter::once(name[1..].to_string()))
+            .chain(args.iter().cloned())
+            .collect(),
+        _ => words,
+    };
+    match TuiApp::try_parse_from(words) {
         Ok(message) => {
             msg_s
                 .send(message.command)
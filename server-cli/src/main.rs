@@ -22,7 +22,10 @@ use crate::{
 use common::{clock::Clock, consts::MIN_RECOMMENDED_TOKIO_THREADS};
 use common_base::span;
 use core::sync::atomic::{AtomicUsize, Ordering};
-use server::{persistence::DatabaseSettings, settings::Protocol, Event, Input, Server};
+use server::{
+    persistence::DatabaseSettings, settings::Protocol, tick_catch_up::TickCatchUp, Event, Input,
+    Server,
+};
 use std::{
     io,
     sync::{atomic::AtomicBool, mpsc, Arc},
@@ -46,6 +49,7 @@ fn main() -> io::Result<()> {
     let noninteractive = app.non_interactive;
     let no_auth = app.no_auth;
     let sql_log_mode = app.sql_log_mode;
+    common_base::userdata_dir::set_portable(app.portable);
 
     // noninteractive implies basic
     let basic = basic || noninteractive;
@@ -195,6 +199,7 @@ fn main() -> io::Result<()> {
 
     // Set up an fps clock
     let mut clock = Clock::new(Duration::from_secs_f64(1.0 / TPS as f64));
+    let mut catch_up = TickCatchUp::new(Duration::from_secs_f64(1.0 / TPS as f64));
     // Wait for a tick so we don't start with a zero dt
 
     let mut tick_no = 0u64;
@@ -206,20 +211,30 @@ fn main() -> io::Result<()> {
             break;
         }
 
-        let events = server
-            .tick(Input::default(), clock.dt())
-            .expect("Failed to tick server");
-
-        for event in events {
-            match event {
-                Event::ClientConnected { entity: _ } => info!("Client connected!"),
-                Event::ClientDisconnected { entity: _ } => info!("Client disconnected!"),
-                Event::Chat { entity: _, msg } => info!("[Client] {}", msg),
+        // Run a few more ticks in a row to catch up after slow ones
+        let steps = catch_up.steps(clock.dt());
+        for _ in 0..steps.count {
+            let events = server
+                .tick(Input::default(), catch_up.target_dt())
+                .expect("Failed to tick server");
+
+            for event in events {
+                match event {
+                    Event::ClientConnected { entity: _ } => info!("Client connected!"),
+                    Event::ClientDisconnected { entity: _ } => info!("Client disconnected!"),
+                    Event::Chat { entity: _, msg } => info!("[Client] {}", msg),
+                    Event::Restart { countdown, message } => {
+                        shutdown_coordinator.initiate_shutdown(&mut server, countdown, message)
+                    },
+                }
             }
-        }
 
-        // Clean up the server after a tick.
-        server.cleanup();
+            // Clean up the server after a tick.
+            server.cleanup();
+        }
+        if let Some(dropped) = steps.dropped {
+            server.notify_overloaded(dropped);
+        }
 
         if tick_no.rem_euclid(1000) == 0 {
             trace!(?tick_no, "keepalive")
@@ -266,6 +281,12 @@ fn main() -> io::Result<()> {
                     Message::DisconnectAllClients => {
                         server.disconnect_all_clients();
                     },
+                    Message::Command { name, args } => {
+                        info!("Running /{} {}", name, args.join(" "));
+                        for reply in server.execute_console_command(name, args) {
+                            info!("{}", reply);
+                        }
+                    },
                 },
                 Err(mpsc::TryRecvError::Empty) | Err(mpsc::TryRecvError::Disconnected) => {},
             }
@@ -62,14 +62,10 @@ impl Settings {
     }
 
     pub fn get_settings_path() -> PathBuf {
-        let mut path = data_dir();
+        let mut path = config_dir();
         path.push("settings.ron");
         path
     }
 }
 
-pub fn data_dir() -> PathBuf {
-    let mut path = common_base::userdata_dir_workspace!();
-    path.push("server-cli");
-    path
-}
+pub fn config_dir() -> PathBuf { common_base::data_dirs_workspace!("server-cli").config }
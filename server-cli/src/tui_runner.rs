@@ -5,6 +5,7 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::{
+    collections::VecDeque,
     io,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -21,6 +22,98 @@ use tui::{
     Terminal,
 };
 
+/// Most commands kept in the history of the tui
+const MAX_HISTORY: usize = 100;
+
+/// Command being typed in the tui, along with the ones entered before
+#[derive(Default)]
+struct InputLine {
+    text: String,
+    /// Position of the cursor, in chars
+    cursor: usize,
+    /// Entered commands, newest first
+    history: VecDeque<String>,
+    /// Entry of the history being shown, if browsing it
+    history_pos: Option<usize>,
+}
+
+impl InputLine {
+    fn byte_pos(&self) -> usize {
+        self.text
+            .char_indices()
+            .nth(self.cursor)
+            .map_or(self.text.len(), |(i, _)| i)
+    }
+
+    fn before_cursor(&self) -> &str { &self.text[..self.byte_pos()] }
+
+    fn set_text(&mut self, text: String) {
+        self.cursor = text.chars().count();
+        self.text = text;
+    }
+
+    fn insert(&mut self, c: char) {
+        let pos = self.byte_pos();
+        self.text.insert(pos, c);
+        self.cursor += 1;
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let pos = self.byte_pos();
+            self.text.remove(pos);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.text.chars().count() {
+            let pos = self.byte_pos();
+            self.text.remove(pos);
+        }
+    }
+
+    fn move_cursor(&mut self, offset: isize) {
+        self.cursor = (self.cursor as isize)
+            .saturating_add(offset)
+            .clamp(0, self.text.chars().count() as isize) as usize;
+    }
+
+    fn history_prev(&mut self) {
+        let pos = self.history_pos.map_or(0, |pos| pos + 1);
+        if let Some(entry) = self.history.get(pos).cloned() {
+            self.history_pos = Some(pos);
+            self.set_text(entry);
+        }
+    }
+
+    fn history_next(&mut self) {
+        match self.history_pos {
+            Some(0) => {
+                self.history_pos = None;
+                self.set_text(String::new());
+            },
+            Some(pos) => {
+                self.history_pos = Some(pos - 1);
+                self.set_text(self.history[pos - 1].clone());
+            },
+            None => {},
+        }
+    }
+
+    /// Clears the line and returns it, adding it to the history
+    fn submit(&mut self) -> String {
+        let line = std::mem::take(&mut self.text);
+        self.cursor = 0;
+        self.history_pos = None;
+        if !line.trim().is_empty() && self.history.front() != Some(&line) {
+            self.history.push_front(line.clone());
+            self.history.truncate(MAX_HISTORY);
+        }
+        line
+    }
+}
+
 pub struct Tui {
     pub msg_r: mpsc::Receiver<Message>,
     background: Option<std::thread::JoinHandle<()>>,
@@ -29,7 +122,7 @@ pub struct Tui {
 }
 
 impl Tui {
-    fn handle_events(input: &mut String, msg_s: &mut mpsc::Sender<Message>) {
+    fn handle_events(input: &mut InputLine, msg_s: &mut mpsc::Sender<Message>) {
         use crossterm::event::*;
         if let Event::Key(event) = read().unwrap() {
             match event.code {
@@ -41,18 +134,22 @@ impl Tui {
                             })
                             .unwrap()
                     } else {
-                        input.push('c');
+                        input.insert('c');
                     }
                 },
-                KeyCode::Char(c) => input.push(c),
-                KeyCode::Backspace => {
-                    input.pop();
-                },
+                KeyCode::Char(c) => input.insert(c),
+                KeyCode::Backspace => input.backspace(),
+                KeyCode::Delete => input.delete(),
+                KeyCode::Left => input.move_cursor(-1),
+                KeyCode::Right => input.move_cursor(1),
+                KeyCode::Home => input.cursor = 0,
+                KeyCode::End => input.move_cursor(isize::MAX),
+                KeyCode::Up => input.history_prev(),
+                KeyCode::Down => input.history_next(),
                 KeyCode::Enter => {
-                    debug!(?input, "tui mode: command entered");
-                    cli::parse_command(input, msg_s);
-
-                    *input = String::new();
+                    let line = input.submit();
+                    debug!(?line, "tui mode: command entered");
+                    cli::parse_command(&line, msg_s);
                 },
                 _ => {},
             }
@@ -117,7 +214,7 @@ impl Tui {
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend).unwrap();
 
-        let mut input = String::new();
+        let mut input = InputLine::default();
 
         if let Err(e) = terminal.clear() {
             error!(?e, "couldn't clean terminal");
@@ -154,12 +251,13 @@ impl Tui {
                     .wrap(wrap);
                 f.render_widget(logger, log_rect);
 
-                let text: Text = input.as_str().into();
+                let text: Text = input.text.as_str().into();
 
                 let block = Block::default().borders(Borders::ALL);
                 let size = block.inner(input_rect);
 
-                let x = (size.x + text.width() as u16).min(size.width);
+                let before_cursor: Text = input.before_cursor().into();
+                let x = (size.x + before_cursor.width() as u16).min(size.width);
 
                 let input_field = Paragraph::new(text).block(block);
                 f.render_widget(input_field, input_rect);
@@ -190,3 +288,34 @@ impl Drop for Tui {
         Tui::shutdown(self.basic);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_line_editing_and_history() {
+        let mut input = InputLine::default();
+        "/kil".chars().for_each(|c| input.insert(c));
+        input.move_cursor(-3);
+        input.delete();
+        input.insert('k');
+        input.move_cursor(isize::MAX);
+        input.insert('l');
+        assert_eq!(input.submit(), "/kill");
+
+        input.set_text("help".to_string());
+        input.submit();
+        input.submit();
+        assert_eq!(input.history, ["help", "/kill"]);
+
+        input.history_prev();
+        input.history_prev();
+        input.history_prev();
+        assert_eq!(input.text, "/kill");
+        input.history_next();
+        assert_eq!(input.text, "help");
+        input.history_next();
+        assert_eq!(input.text, "");
+    }
+}
@@ -0,0 +1,429 @@
+use crate::console::ConsoleOutput;
+use chrono::{DateTime, Utc};
+use common::{
+    comp::Player,
+    terrain::{Block, TerrainGrid},
+    vol::ReadVol,
+};
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use specs::{Entity as EcsEntity, World, WorldExt};
+use std::{
+    collections::VecDeque,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    thread::{self, JoinHandle},
+};
+use tracing::{error, warn};
+use vek::*;
+
+const AUDIT_FILENAME: &str = "audit.log";
+/// Size the audit log grows to before it is rotated, the previous one being
+/// kept as `audit.log.1`
+const MAX_LOG_SIZE: u64 = 64 * 1024 * 1024;
+/// Name the server console is recorded with
+const CONSOLE_NAME: &str = "<console>";
+/// Item stacks of at least this size changing hands between players are
+/// recorded
+pub const LARGE_TRANSFER_AMOUNT: u32 = 50;
+
+/// Significant events kept to investigate griefing and duping reports
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum AuditEvent {
+    /// A command which needs a role was used
+    AdminCommand { command: String, args: Vec<String> },
+    Ban {
+        target: String,
+        reason: String,
+        end_date: Option<DateTime<Utc>>,
+    },
+    /// A block was changed in a build area
+    TerrainEdit {
+        pos: Vec3<i32>,
+        old: Block,
+        new: Block,
+    },
+    /// A large amount of an item was given to another player
+    ItemTransfer {
+        to: String,
+        item: String,
+        amount: u32,
+    },
+}
+
+impl AuditEvent {
+    /// Whether the player is the target of the event
+    fn concerns(&self, player: &str) -> bool {
+        match self {
+            AuditEvent::Ban { target, .. } => target.eq_ignore_ascii_case(player),
+            AuditEvent::ItemTransfer { to, .. } => to.eq_ignore_ascii_case(player),
+            AuditEvent::AdminCommand { .. } | AuditEvent::TerrainEdit { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for AuditEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuditEvent::AdminCommand { command, args } => {
+                write!(f, "used /{} {}", command, args.join(" "))
+            },
+            AuditEvent::Ban {
+                target,
+                reason,
+                end_date,
+            } => {
+                write!(f, "banned {}", target)?;
+                if let Some(end_date) = end_date {
+                    write!(f, " until {}", end_date.format("%Y-%m-%d %H:%M"))?;
+                }
+                write!(f, ": {}", reason)
+            },
+            AuditEvent::TerrainEdit { pos, old, new } => write!(
+                f,
+                "changed the block at {} from {:?} to {:?}",
+                pos,
+                old.kind(),
+                new.kind()
+            ),
+            AuditEvent::ItemTransfer { to, item, amount } => {
+                write!(f, "gave {} x {} to {}", amount, item, to)
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub time: DateTime<Utc>,
+    /// Username of the player who caused the event
+    pub actor: String,
+    pub event: AuditEvent,
+}
+
+impl AuditEntry {
+    pub fn involves(&self, player: &str) -> bool {
+        self.actor.eq_ignore_ascii_case(player) || self.event.concerns(player)
+    }
+}
+
+/// Append-only log of the audit events, one json entry per line in the data
+/// directory. The log is written by a background thread so that recording
+/// events never waits on the disk, and is rotated once it gets too large.
+pub struct AuditLog {
+    path: PathBuf,
+    sender: Option<Sender<String>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl AuditLog {
+    pub fn new(data_dir: &Path) -> Self {
+        let path = data_dir.join(AUDIT_FILENAME);
+        let (sender, receiver) = unbounded();
+        let writer_path = path.clone();
+        let writer = thread::Builder::new()
+            .name("audit_log".to_string())
+            .spawn(move || write_lines(&writer_path, receiver))
+            .map_err(|err| {
+                error!(
+                    ?err,
+                    "Failed to start the audit log writer, events won't be recorded"
+                )
+            })
+            .ok();
+        Self {
+            path,
+            sender: writer.as_ref().map(|_| sender),
+            writer,
+        }
+    }
+
+    pub fn record(&mut self, actor: String, event: AuditEvent) {
+        self.record_all(actor, std::iter::once(event));
+    }
+
+    /// Records events caused together, written to the log at once
+    pub fn record_all(&mut self, actor: String, events: impl IntoIterator<Item = AuditEvent>) {
+        let sender = match self.sender.as_ref() {
+            Some(sender) => sender,
+            None => return,
+        };
+        let time = Utc::now();
+        let mut lines = String::new();
+        for event in events {
+            let entry = AuditEntry {
+                time,
+                actor: actor.clone(),
+                event,
+            };
+            match serde_json::to_string(&entry) {
+                Ok(line) => {
+                    lines.push_str(&line);
+                    lines.push('\n');
+                },
+                Err(err) => error!(?err, ?entry, "Failed to serialize audit entry"),
+            }
+        }
+        if !lines.is_empty() {
+            let _ = sender.send(lines);
+        }
+    }
+
+    /// Goes through the entries of the log and the rotated one, oldest first.
+    /// Both are capped in size, which bounds the scan.
+    fn scan(&self, mut f: impl FnMut(AuditEntry)) -> io::Result<()> {
+        for path in [rotated_path(&self.path), self.path.clone()] {
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            for line in BufReader::new(file).lines() {
+                match serde_json::from_str::<AuditEntry>(&line?) {
+                    Ok(entry) => f(entry),
+                    Err(err) => warn!(?err, "Skipping malformed audit log entry"),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Most recent entries the player caused or was the target of, oldest
+    /// first
+    pub fn query(&self, player: &str, limit: usize) -> io::Result<Vec<AuditEntry>> {
+        let mut entries = VecDeque::with_capacity(limit);
+        self.scan(|entry| {
+            if limit > 0 && entry.involves(player) {
+                if entries.len() == limit {
+                    entries.pop_front();
+                }
+                entries.push_back(entry);
+            }
+        })?;
+        Ok(entries.into())
+    }
+
+    /// Blocks the player changed since the given time, oldest first
+    pub fn terrain_edits(
+        &self,
+        player: &str,
+        since: DateTime<Utc>,
+    ) -> io::Result<Vec<TerrainEdit>> {
+        let mut edits = Vec::new();
+        self.scan(|entry| {
+            if entry.time >= since && entry.actor.eq_ignore_ascii_case(player) {
+                if let AuditEvent::TerrainEdit { pos, old, new } = entry.event {
+                    edits.push(TerrainEdit { pos, old, new });
+                }
+            }
+        })?;
+        Ok(edits)
+    }
+}
+
+impl Drop for AuditLog {
+    fn drop(&mut self) {
+        // The writer stops once it wrote everything it was sent
+        self.sender = None;
+        if let Some(writer) = self.writer.take() {
+            if writer.join().is_err() {
+                error!("The audit log writer panicked");
+            }
+        }
+    }
+}
+
+fn rotated_path(path: &Path) -> PathBuf { path.with_extension("log.1") }
+
+/// Appends the lines it receives to the log, until the log is dropped. Once
+/// the log is larger than `MAX_LOG_SIZE` it replaces the rotated one and a new
+/// log is started.
+fn write_lines(path: &Path, lines: Receiver<String>) {
+    let open = || -> io::Result<(BufWriter<File>, u64)> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok((BufWriter::new(file), size))
+    };
+    let (mut file, mut size) = match open() {
+        Ok(opened) => opened,
+        Err(err) => {
+            error!(
+                ?err,
+                "Failed to open the audit log at {}, events won't be recorded",
+                path.display()
+            );
+            return;
+        },
+    };
+    while let Ok(first) = lines.recv() {
+        for lines in std::iter::once(first).chain(lines.try_iter()) {
+            if let Err(err) = file.write_all(lines.as_bytes()) {
+                error!(?err, "Failed to write to the audit log");
+            }
+            size += lines.len() as u64;
+        }
+        if let Err(err) = file.flush() {
+            error!(?err, "Failed to write to the audit log");
+        }
+        if size >= MAX_LOG_SIZE {
+            if let Err(err) = fs::rename(path, rotated_path(path)) {
+                error!(?err, "Failed to rotate the audit log");
+                continue;
+            }
+            match open() {
+                Ok((new_file, new_size)) => {
+                    file = new_file;
+                    size = new_size;
+                },
+                Err(err) => {
+                    error!(
+                        ?err,
+                        "Failed to open a new audit log, events won't be recorded"
+                    );
+                    return;
+                },
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TerrainEdit {
+    pub pos: Vec3<i32>,
+    pub old: Block,
+    pub new: Block,
+}
+
+/// Groups the edits to undo by chunk, so that they can be reverted one chunk
+/// at a time. The edits of each chunk are in the order they have to be undone
+/// in, latest first.
+pub fn rollback_plan(edits: Vec<TerrainEdit>) -> Vec<(Vec2<i32>, Vec<TerrainEdit>)> {
+    let mut by_chunk: HashMap<Vec2<i32>, Vec<TerrainEdit>> = HashMap::new();
+    for edit in edits.into_iter().rev() {
+        by_chunk
+            .entry(TerrainGrid::chunk_key(edit.pos))
+            .or_default()
+            .push(edit);
+    }
+    let mut chunks = by_chunk.into_iter().collect::<Vec<_>>();
+    chunks.sort_by_key(|(key, _)| (key.x, key.y));
+    chunks
+}
+
+/// Rollback of the blocks a player changed, reverted a few chunks every tick so
+/// that large ones don't stall the server
+pub struct Rollback {
+    /// Who asked for the rollback, told about its progress
+    pub requester: EcsEntity,
+    pub username: String,
+    chunks: VecDeque<Vec<TerrainEdit>>,
+    chunk_count: usize,
+    /// Blocks as they will be once the changes made so far are applied
+    reverted: HashMap<Vec3<i32>, Block>,
+    pub reverted_count: usize,
+    pub skipped_count: usize,
+}
+
+impl Rollback {
+    pub fn new(requester: EcsEntity, username: String, edits: Vec<TerrainEdit>) -> Self {
+        let chunks = rollback_plan(edits)
+            .into_iter()
+            .map(|(_, edits)| edits)
+            .collect::<VecDeque<_>>();
+        Self {
+            requester,
+            username,
+            chunk_count: chunks.len(),
+            chunks,
+            reverted: HashMap::new(),
+            reverted_count: 0,
+            skipped_count: 0,
+        }
+    }
+
+    pub fn chunk_count(&self) -> usize { self.chunk_count }
+
+    pub fn chunks_done(&self) -> usize { self.chunk_count - self.chunks.len() }
+
+    pub fn is_done(&self) -> bool { self.chunks.is_empty() }
+
+    /// Blocks to revert in the next chunk. Blocks someone changed afterwards
+    /// are left alone, the ones in unloaded chunks can't be checked and are
+    /// reverted anyway.
+    pub fn next_chunk(&mut self, terrain: &TerrainGrid) -> Vec<(Vec3<i32>, Block)> {
+        let mut blocks = Vec::new();
+        for edit in self.chunks.pop_front().unwrap_or_default() {
+            let current = self
+                .reverted
+                .get(&edit.pos)
+                .copied()
+                .or_else(|| terrain.get(edit.pos).ok().copied());
+            if current.map_or(false, |current| current != edit.new) {
+                self.skipped_count += 1;
+                continue;
+            }
+            self.reverted.insert(edit.pos, edit.old);
+            self.reverted_count += 1;
+            blocks.push((edit.pos, edit.old));
+        }
+        blocks
+    }
+}
+
+/// Rollbacks in progress
+#[derive(Default)]
+pub struct Rollbacks(pub Vec<Rollback>);
+
+/// Name an entity is recorded with in the audit log
+pub fn actor_name(ecs: &World, entity: EcsEntity) -> Option<String> {
+    if ecs.read_storage::<ConsoleOutput>().contains(entity) {
+        Some(CONSOLE_NAME.to_string())
+    } else {
+        ecs.read_storage::<Player>()
+            .get(entity)
+            .map(|player| player.alias.clone())
+    }
+}
+
+/// Records an event caused by the entity, entities which aren't players are
+/// ignored
+pub fn record(ecs: &World, entity: EcsEntity, event: AuditEvent) {
+    record_all(ecs, entity, std::iter::once(event));
+}
+
+/// Records events caused together by the entity, like the blocks of a
+/// blueprint
+pub fn record_all(ecs: &World, entity: EcsEntity, events: impl IntoIterator<Item = AuditEvent>) {
+    if let Some(actor) = actor_name(ecs, entity) {
+        ecs.write_resource::<AuditLog>().record_all(actor, events);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::terrain::BlockKind;
+
+    #[test]
+    fn rollback_undoes_latest_edits_first() {
+        let block = |kind| Block::new(kind, Rgb::zero());
+        let edit = |pos, old, new| TerrainEdit {
+            pos,
+            old: block(old),
+            new: block(new),
+        };
+        let edits = vec![
+            edit(Vec3::new(1, 1, 0), BlockKind::Air, BlockKind::Rock),
+            edit(Vec3::new(100, 1, 0), BlockKind::Grass, BlockKind::Air),
+            edit(Vec3::new(1, 1, 0), BlockKind::Rock, BlockKind::Wood),
+        ];
+        let plan = rollback_plan(edits.clone());
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].0, TerrainGrid::chunk_key(Vec3::new(1, 1, 0)));
+        assert_eq!(plan[0].1, vec![edits[2], edits[0]]);
+        assert_eq!(plan[1].1, vec![edits[1]]);
+    }
+}
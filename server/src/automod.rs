@@ -1,7 +1,7 @@
 use crate::settings::ModerationSettings;
 use authc::Uuid;
 use censor::Censor;
-use common::comp::{AdminRole, ChatType, Group};
+use common::comp::{chat::MAX_BYTES_CHAT_MSG, AdminRole, ChatType, Group};
 use hashbrown::HashMap;
 use std::{
     fmt,
@@ -10,8 +10,6 @@ use std::{
 };
 use tracing::info;
 
-pub const MAX_BYTES_CHAT_MSG: usize = 256;
-
 pub enum ActionNote {
     SpamWarn,
 }
@@ -111,15 +109,43 @@ impl AutoMod {
         } else if self.censor.check(msg) {
             Err(ActionErr::BannedWord)
         } else {
-            let volume = self.player_mut(player).enforce_message_volume(now);
-
-            if let Some(until) = self.player_mut(player).muted_until {
-                Err(ActionErr::SpamMuted(until.saturating_duration_since(now)))
-            } else if volume > 0.75 {
-                Ok(Some(ActionNote::SpamWarn))
-            } else {
-                Ok(None)
-            }
+            self.enforce_volume(player, now)
+        }
+    }
+
+    /// Checks the names of the map markers shared with the group. Unlike chat
+    /// messages, they are always checked for banned words since they stay on
+    /// the map of the other members, and sharing them counts as sending a
+    /// message.
+    pub fn validate_map_markers<'a>(
+        &mut self,
+        player: Uuid,
+        role: Option<AdminRole>,
+        now: Instant,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Option<ActionNote>, ActionErr> {
+        if names.into_iter().any(|name| self.censor.check(name)) {
+            Err(ActionErr::BannedWord)
+        } else if !self.settings.automod || (role.is_some() && self.settings.admins_exempt) {
+            Ok(None)
+        } else {
+            self.enforce_volume(player, now)
+        }
+    }
+
+    fn enforce_volume(
+        &mut self,
+        player: Uuid,
+        now: Instant,
+    ) -> Result<Option<ActionNote>, ActionErr> {
+        let volume = self.player_mut(player).enforce_message_volume(now);
+
+        if let Some(until) = self.player_mut(player).muted_until {
+            Err(ActionErr::SpamMuted(until.saturating_duration_since(now)))
+        } else if volume > 0.75 {
+            Ok(Some(ActionNote::SpamWarn))
+        } else {
+            Ok(None)
         }
     }
 }
@@ -75,6 +75,8 @@ pub fn create_character(
         pets: Vec::new(),
         active_abilities: Default::default(),
         map_marker,
+        quest_log: Default::default(),
+        reputation: Default::default(),
     });
     Ok(())
 }
@@ -0,0 +1,324 @@
+use crate::persistence::PersistedComponents;
+use common::comp::{
+    inventory::{
+        item::{tool::AbilityMap, MaterialStatManifest, Quality},
+        loadout_builder::LoadoutBuilder,
+    },
+    Body, Inventory, Item, SkillSet, Stats,
+};
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Directory of the data directory exported characters are written to, and
+/// imported from
+const TRANSFERS_DIR: &str = "character_transfers";
+/// Extension given to the files of the characters which were imported, so that
+/// they can't be imported twice
+const IMPORTED_EXTENSION: &str = "imported";
+/// Bumped whenever the exported data changes in a way older servers can't read
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum TransferError {
+    Io(io::Error),
+    Serialization(serde_json::Error),
+    InvalidFileName,
+    UnsupportedVersion(u32),
+    /// The file wasn't signed with the key of this server, or was edited
+    BadSignature,
+    InvalidBody,
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Serialization(err) => write!(f, "Malformed character file: {}", err),
+            Self::InvalidFileName => write!(f, "Invalid character file name"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported character file version {}", version)
+            },
+            Self::BadSignature => write!(
+                f,
+                "The character file wasn't signed with the transfer key of this server"
+            ),
+            Self::InvalidBody => write!(f, "Only humanoid characters can be imported"),
+        }
+    }
+}
+
+impl From<io::Error> for TransferError {
+    fn from(err: io::Error) -> Self { Self::Io(err) }
+}
+
+impl From<serde_json::Error> for TransferError {
+    fn from(err: serde_json::Error) -> Self { Self::Serialization(err) }
+}
+
+/// Item referred to by its definition, items are rebuilt from the assets of the
+/// server they are imported on
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransferItem {
+    pub definition_id: String,
+    pub amount: u32,
+    pub components: Vec<TransferItem>,
+}
+
+impl TransferItem {
+    fn new(item: &Item) -> Self {
+        Self {
+            definition_id: item.persistence_item_id().to_string(),
+            amount: item.amount(),
+            components: item.components().iter().map(Self::new).collect(),
+        }
+    }
+
+    /// Rebuilds the item, refusing the ones which don't exist on this server or
+    /// can't be obtained legitimately
+    fn to_item(&self) -> Result<Item, String> {
+        let mut item = Item::new_from_asset(&self.definition_id)
+            .map_err(|_| format!("{} doesn't exist", self.definition_id))?;
+        if item.quality() == Quality::Debug {
+            return Err(format!("{} is a debug item", self.definition_id));
+        }
+        item.set_amount(self.amount)
+            .map_err(|_| format!("{} can't stack to {}", self.definition_id, self.amount))?;
+        for component in &self.components {
+            item.persistence_access_add_component(component.to_item()?);
+        }
+        Ok(item)
+    }
+}
+
+/// Character exported from a server, to be imported on another one
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CharacterTransfer {
+    pub version: u32,
+    /// Account the character belongs to, only this player can import it
+    pub player_uuid: String,
+    pub alias: String,
+    pub body: Body,
+    pub skill_set: SkillSet,
+    /// Equipped items along with the loadout slot they are in
+    pub loadout: Vec<(String, TransferItem)>,
+    pub inventory: Vec<TransferItem>,
+}
+
+impl CharacterTransfer {
+    pub fn new(
+        player_uuid: String,
+        stats: &Stats,
+        body: Body,
+        skill_set: &SkillSet,
+        inventory: &Inventory,
+    ) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            player_uuid,
+            alias: stats.name.clone(),
+            body,
+            skill_set: skill_set.clone(),
+            loadout: inventory
+                .loadout_items_with_persistence_key()
+                .filter_map(|(slot, item)| Some((slot.to_string(), TransferItem::new(item?))))
+                .collect(),
+            inventory: inventory.slots().flatten().map(TransferItem::new).collect(),
+        }
+    }
+
+    /// Builds the components of the character to create, along with the
+    /// reasons the items which didn't make it were refused for
+    pub fn into_components(
+        self,
+        ability_map: &AbilityMap,
+        msm: &MaterialStatManifest,
+    ) -> Result<(PersistedComponents, Vec<String>), TransferError> {
+        if !matches!(self.body, Body::Humanoid(_)) {
+            return Err(TransferError::InvalidBody);
+        }
+        let mut refused = Vec::new();
+        let mut overflow = Vec::new();
+
+        let mut loadout = LoadoutBuilder::empty().build();
+        for (slot, transfer_item) in &self.loadout {
+            match transfer_item.to_item() {
+                Ok(item) => {
+                    // Items which can't be equipped anymore go to the inventory instead
+                    if loadout
+                        .set_item_at_slot_using_persistence_key(slot, item)
+                        .is_err()
+                    {
+                        overflow.extend(transfer_item.to_item());
+                    }
+                },
+                Err(reason) => refused.push(reason),
+            }
+        }
+        loadout.persistence_update_all_item_states(ability_map, msm);
+
+        let mut inventory = Inventory::with_loadout_humanoid(loadout);
+        for transfer_item in &self.inventory {
+            match transfer_item.to_item() {
+                Ok(item) => overflow.push(item),
+                Err(reason) => refused.push(reason),
+            }
+        }
+        for item in overflow {
+            if let Err(item) = inventory.push(item) {
+                refused.push(format!("{} didn't fit", item.persistence_item_id()));
+            }
+        }
+        inventory.persistence_update_all_item_states(ability_map, msm);
+
+        Ok((
+            PersistedComponents {
+                body: self.body,
+                stats: Stats::new(self.alias),
+                skill_set: self.skill_set,
+                inventory,
+                waypoint: None,
+                pets: Vec::new(),
+                active_abilities: Default::default(),
+                map_marker: None,
+                quest_log: Default::default(),
+                reputation: Default::default(),
+            },
+            refused,
+        ))
+    }
+}
+
+/// Signed character file, as written to the disk
+#[derive(Serialize, Deserialize)]
+struct SignedTransfer {
+    /// The character, serialized separately so that the signature doesn't
+    /// depend on how the file gets formatted
+    character: String,
+    signature: String,
+}
+
+fn sign(character: &str, key: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key.as_bytes());
+    hmac::sign(&key, character.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn verify(signed: &SignedTransfer, key: &str) -> Result<(), TransferError> {
+    let signature = (0..signed.signature.len())
+        .step_by(2)
+        .map(|i| {
+            signed
+                .signature
+                .get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or(TransferError::BadSignature)?;
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key.as_bytes());
+    hmac::verify(&key, signed.character.as_bytes(), &signature)
+        .map_err(|_| TransferError::BadSignature)
+}
+
+fn transfers_dir(data_dir: &Path) -> PathBuf { data_dir.join(TRANSFERS_DIR) }
+
+/// Writes the signed character to the transfers directory, returning the name
+/// of the file
+pub fn export(
+    data_dir: &Path,
+    key: &str,
+    transfer: &CharacterTransfer,
+) -> Result<String, TransferError> {
+    let character = serde_json::to_string(transfer)?;
+    let signed = SignedTransfer {
+        signature: sign(&character, key),
+        character,
+    };
+    let dir = transfers_dir(data_dir);
+    fs::create_dir_all(&dir)?;
+    let file_name = format!("{}_{}.json", transfer.player_uuid, transfer.alias)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    fs::write(dir.join(&file_name), serde_json::to_string_pretty(&signed)?)?;
+    Ok(file_name)
+}
+
+/// Reads the character from the file in the transfers directory, after
+/// checking it was signed with the given key
+pub fn read(
+    data_dir: &Path,
+    key: &str,
+    file_name: &str,
+) -> Result<CharacterTransfer, TransferError> {
+    let path = transfer_path(data_dir, file_name)?;
+    let signed: SignedTransfer = serde_json::from_slice(&fs::read(path)?)?;
+    verify(&signed, key)?;
+    let transfer: CharacterTransfer = serde_json::from_str(&signed.character)?;
+    if transfer.version != FORMAT_VERSION {
+        return Err(TransferError::UnsupportedVersion(transfer.version));
+    }
+    Ok(transfer)
+}
+
+/// Renames the file of an imported character so that it can't be imported
+/// again
+pub fn mark_imported(data_dir: &Path, file_name: &str) -> Result<(), TransferError> {
+    let path = transfer_path(data_dir, file_name)?;
+    fs::rename(&path, path.with_extension(IMPORTED_EXTENSION))?;
+    Ok(())
+}
+
+fn transfer_path(data_dir: &Path, file_name: &str) -> Result<PathBuf, TransferError> {
+    // Don't let players read files outside of the transfers directory
+    if file_name.is_empty()
+        || !file_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_')
+        || file_name.starts_with('.')
+        || !file_name.ends_with(".json")
+    {
+        return Err(TransferError::InvalidFileName);
+    }
+    Ok(transfers_dir(data_dir).join(file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_rejects_edits_and_other_keys() {
+        let character = r#"{"alias":"test"}"#.to_string();
+        let signed = SignedTransfer {
+            signature: sign(&character, "secret"),
+            character,
+        };
+        assert!(verify(&signed, "secret").is_ok());
+        assert!(matches!(
+            verify(&signed, "other"),
+            Err(TransferError::BadSignature)
+        ));
+
+        let edited = SignedTransfer {
+            character: r#"{"alias":"admin"}"#.to_string(),
+            signature: signed.signature.clone(),
+        };
+        assert!(matches!(
+            verify(&edited, "secret"),
+            Err(TransferError::BadSignature)
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use common_net::msg::{ClientType, ServerGeneral, ServerMsg};
+use common_net::msg::{ClientType, ServerGeneral, ServerMsg, ServerRegisterMsg};
 use network::{Message, Participant, Stream, StreamError, StreamParams};
 use serde::{de::DeserializeOwned, Serialize};
 use specs::Component;
@@ -15,6 +15,8 @@ pub struct Client {
     pub participant: Option<Participant>,
     pub last_ping: Mutex<f64>,
     pub login_msg_sent: AtomicBool,
+    /// Whether the player wants to hear the voice chat of the players nearby
+    pub voice_listening: AtomicBool,
 
     //TODO: improve network crate so that `send` is no longer `&mut self` and we can get rid of
     // this Mutex. This Mutex is just to please the compiler as we do not get into contention
@@ -24,6 +26,7 @@ pub struct Client {
     character_screen_stream: Mutex<Stream>,
     in_game_stream: Mutex<Stream>,
     terrain_stream: Mutex<Stream>,
+    voice_stream: Mutex<Stream>,
 
     general_stream_params: StreamParams,
     ping_stream_params: StreamParams,
@@ -31,6 +34,7 @@ pub struct Client {
     character_screen_stream_params: StreamParams,
     in_game_stream_params: StreamParams,
     terrain_stream_params: StreamParams,
+    voice_stream_params: StreamParams,
 }
 
 pub struct PreparedMsg {
@@ -53,6 +57,7 @@ impl Client {
         character_screen_stream: Stream,
         in_game_stream: Stream,
         terrain_stream: Stream,
+        voice_stream: Stream,
     ) -> Self {
         let general_stream_params = general_stream.params();
         let ping_stream_params = ping_stream.params();
@@ -60,23 +65,27 @@ impl Client {
         let character_screen_stream_params = character_screen_stream.params();
         let in_game_stream_params = in_game_stream.params();
         let terrain_stream_params = terrain_stream.params();
+        let voice_stream_params = voice_stream.params();
         Client {
             client_type,
             participant: Some(participant),
             last_ping: Mutex::new(last_ping),
             login_msg_sent: AtomicBool::new(false),
+            voice_listening: AtomicBool::new(false),
             general_stream: Mutex::new(general_stream),
             ping_stream: Mutex::new(ping_stream),
             register_stream: Mutex::new(register_stream),
             character_screen_stream: Mutex::new(character_screen_stream),
             in_game_stream: Mutex::new(in_game_stream),
             terrain_stream: Mutex::new(terrain_stream),
+            voice_stream: Mutex::new(voice_stream),
             general_stream_params,
             ping_stream_params,
             register_stream_params,
             character_screen_stream_params,
             in_game_stream_params,
             terrain_stream_params,
+            voice_stream_params,
         }
     }
 
@@ -89,6 +98,7 @@ impl Client {
             ServerMsg::Info(m) => self.register_stream.lock().unwrap().send(m),
             ServerMsg::Init(m) => self.register_stream.lock().unwrap().send(m),
             ServerMsg::RegisterAnswer(m) => self.register_stream.lock().unwrap().send(m),
+            ServerMsg::LoginQueue(m) => self.register_stream.lock().unwrap().send(m),
             ServerMsg::General(g) => {
                 match g {
                     //Character Screen related
@@ -102,6 +112,8 @@ impl Client {
                     },
                     //In-game related
                     ServerGeneral::GroupUpdate(_)
+                    | ServerGeneral::GroupMemberStatus(_)
+                    | ServerGeneral::GuildUpdate(_)
                     | ServerGeneral::Invite { .. }
                     | ServerGeneral::InvitePending(_)
                     | ServerGeneral::InviteComplete { .. }
@@ -111,6 +123,7 @@ impl Client {
                     | ServerGeneral::SiteEconomy(_)
                     | ServerGeneral::Outcomes(_)
                     | ServerGeneral::Knockback(_)
+                    | ServerGeneral::DamageReport(_)
                     | ServerGeneral::UpdatePendingTrade(_, _, _)
                     | ServerGeneral::FinishedTrade(_)
                     | ServerGeneral::WeatherUpdate(_) => {
@@ -119,7 +132,8 @@ impl Client {
                     //Ingame related, terrain
                     ServerGeneral::TerrainChunkUpdate { .. }
                     | ServerGeneral::LodZoneUpdate { .. }
-                    | ServerGeneral::TerrainBlockUpdates(_) => {
+                    | ServerGeneral::TerrainBlockUpdates(_)
+                    | ServerGeneral::SignTexts(_) => {
                         self.terrain_stream.lock().unwrap().send(g)
                     },
                     // Always possible
@@ -127,7 +141,7 @@ impl Client {
                     | ServerGeneral::ChatMsg(_)
                     | ServerGeneral::ChatMode(_)
                     | ServerGeneral::SetPlayerEntity(_)
-                    | ServerGeneral::TimeOfDay(_, _)
+                    | ServerGeneral::TimeOfDay(_, _, _)
                     | ServerGeneral::EntitySync(_)
                     | ServerGeneral::CompSync(_)
                     | ServerGeneral::CreateEntity(_)
@@ -155,6 +169,7 @@ impl Client {
             3 => self.general_stream.lock().unwrap().send_raw(&msg.message),
             4 => self.ping_stream.lock().unwrap().send_raw(&msg.message),
             5 => self.terrain_stream.lock().unwrap().send_raw(&msg.message),
+            6 => self.voice_stream.lock().unwrap().send_raw(&msg.message),
             _ => unreachable!("invalid stream id"),
         }
     }
@@ -163,7 +178,16 @@ impl Client {
         match msg.into() {
             ServerMsg::Info(m) => PreparedMsg::new(0, &m, &self.register_stream_params),
             ServerMsg::Init(m) => PreparedMsg::new(0, &m, &self.register_stream_params),
-            ServerMsg::RegisterAnswer(m) => PreparedMsg::new(0, &m, &self.register_stream_params),
+            ServerMsg::RegisterAnswer(m) => PreparedMsg::new(
+                0,
+                &ServerRegisterMsg::Answer(m),
+                &self.register_stream_params,
+            ),
+            ServerMsg::LoginQueue(m) => PreparedMsg::new(
+                0,
+                &ServerRegisterMsg::Queued(m),
+                &self.register_stream_params,
+            ),
             ServerMsg::General(g) => {
                 match g {
                     //Character Screen related
@@ -178,6 +202,8 @@ impl Client {
                     },
                     //In-game related
                     ServerGeneral::GroupUpdate(_)
+                    | ServerGeneral::GroupMemberStatus(_)
+                    | ServerGeneral::GuildUpdate(_)
                     | ServerGeneral::Invite { .. }
                     | ServerGeneral::InvitePending(_)
                     | ServerGeneral::InviteComplete { .. }
@@ -186,18 +212,22 @@ impl Client {
                     | ServerGeneral::SetViewDistance(_)
                     | ServerGeneral::Outcomes(_)
                     | ServerGeneral::Knockback(_)
+                    | ServerGeneral::DamageReport(_)
                     | ServerGeneral::SiteEconomy(_)
                     | ServerGeneral::UpdatePendingTrade(_, _, _)
                     | ServerGeneral::FinishedTrade(_)
                     | ServerGeneral::MapMarker(_)
                     | ServerGeneral::WeatherUpdate(_)
-                    | ServerGeneral::SpectatePosition(_) => {
+                    | ServerGeneral::WorldBorder(_)
+                    | ServerGeneral::SpectatePosition(_)
+                    | ServerGeneral::SpectatePlayer(_) => {
                         PreparedMsg::new(2, &g, &self.in_game_stream_params)
                     },
                     //In-game related, terrain
                     ServerGeneral::TerrainChunkUpdate { .. }
                     | ServerGeneral::LodZoneUpdate { .. }
-                    | ServerGeneral::TerrainBlockUpdates(_) => {
+                    | ServerGeneral::TerrainBlockUpdates(_)
+                    | ServerGeneral::SignTexts(_) => {
                         PreparedMsg::new(5, &g, &self.terrain_stream_params)
                     },
                     // Always possible
@@ -205,18 +235,20 @@ impl Client {
                     | ServerGeneral::ChatMsg(_)
                     | ServerGeneral::ChatMode(_)
                     | ServerGeneral::SetPlayerEntity(_)
-                    | ServerGeneral::TimeOfDay(_, _)
+                    | ServerGeneral::TimeOfDay(_, _, _)
                     | ServerGeneral::EntitySync(_)
                     | ServerGeneral::CompSync(_, _)
                     | ServerGeneral::CreateEntity(_)
                     | ServerGeneral::DeleteEntity(_)
                     | ServerGeneral::Disconnect(_)
-                    | ServerGeneral::Notification(_) => {
+                    | ServerGeneral::Notification(_)
+                    | ServerGeneral::SessionStarted(_) => {
                         PreparedMsg::new(3, &g, &self.general_stream_params)
                     },
                 }
             },
             ServerMsg::Ping(m) => PreparedMsg::new(4, &m, &self.ping_stream_params),
+            ServerMsg::Voice(m) => PreparedMsg::new(6, &m, &self.voice_stream_params),
         }
     }
 
@@ -249,6 +281,7 @@ impl Client {
             3 => self.general_stream.lock().unwrap().try_recv(),
             4 => self.ping_stream.lock().unwrap().try_recv(),
             5 => self.terrain_stream.lock().unwrap().try_recv(),
+            6 => self.voice_stream.lock().unwrap().try_recv(),
             _ => unreachable!("invalid stream id"),
         }
     }
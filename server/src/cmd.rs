@@ -3,14 +3,22 @@
 //! in [do_command].
 
 use crate::{
+    audit::{self, AuditEvent, AuditLog},
+    character_transfer::{self, CharacterTransfer},
     client::Client,
+    console::{ConsoleOutput, CONSOLE_UUID},
+    fire::Fires,
+    guild::{self, GuildChange, GuildError, Guilds},
     location::Locations,
     login_provider::LoginProvider,
+    persistence::character_updater::CharacterUpdater,
+    portal::PortalNetwork,
     presence::Presence,
+    schedule::Scheduler,
     settings::{
         Ban, BanAction, BanInfo, EditableSetting, SettingError, WhitelistInfo, WhitelistRecord,
     },
-    sys::terrain::NpcData,
+    sys::{spectate::Spectating, terrain::NpcData},
     weather::WeatherSim,
     wiring,
     wiring::OutputFormula,
@@ -22,6 +30,7 @@ use chrono::{NaiveTime, Timelike, Utc};
 use common::{
     assets,
     calendar::Calendar,
+    character::CharacterId,
     cmd::{
         KitSpec, ServerChatCommand, BUFF_PACK, BUFF_PARSER, ITEM_SPECS, KIT_MANIFEST_PATH,
         PRESET_MANIFEST_PATH,
@@ -43,11 +52,13 @@ use common::{
     npc::{self, get_npc_name},
     outcome::Outcome,
     parse_cmd_args,
-    resources::{BattleMode, PlayerPhysicsSettings, Time, TimeOfDay},
+    resources::{BattleMode, DayCycleFactor, PlayerPhysicsSettings, Time, TimeOfDay},
     terrain::{Block, BlockKind, SpriteKind, TerrainChunkSize},
     uid::{Uid, UidAllocator},
     vol::{ReadVol, RectVolSize},
-    weather, Damage, DamageKind, DamageSource, Explosion, LoadoutBuilder, RadiusEffect,
+    weather,
+    world_border::{BorderShape, WorldBorder},
+    Damage, DamageKind, DamageSource, Explosion, LoadoutBuilder, RadiusEffect,
 };
 use common_net::{
     msg::{DisconnectReason, Notification, PlayerListUpdate, PresenceKind, ServerGeneral},
@@ -120,12 +131,20 @@ fn do_command(
             cmd.keyword()
         ));
     }
+    if cmd.needs_role().is_some() {
+        audit::record(server.state.ecs(), client, AuditEvent::AdminCommand {
+            command: cmd.keyword().to_string(),
+            args: args.clone(),
+        });
+    }
 
     let handler: CommandHandler = match cmd {
         ServerChatCommand::Adminify => handle_adminify,
         ServerChatCommand::Airship => handle_spawn_airship,
         ServerChatCommand::Alias => handle_alias,
         ServerChatCommand::ApplyBuff => handle_apply_buff,
+        ServerChatCommand::Attach => handle_attach,
+        ServerChatCommand::Audit => handle_audit,
         ServerChatCommand::Ban => handle_ban,
         ServerChatCommand::BattleMode => handle_battlemode,
         ServerChatCommand::BattleModeForce => handle_battlemode_force,
@@ -135,6 +154,8 @@ fn do_command(
         ServerChatCommand::BuildAreaList => handle_build_area_list,
         ServerChatCommand::BuildAreaRemove => handle_build_area_remove,
         ServerChatCommand::Campfire => handle_spawn_campfire,
+        ServerChatCommand::CharacterExport => handle_character_export,
+        ServerChatCommand::CharacterImport => handle_character_import,
         ServerChatCommand::DebugColumn => handle_debug_column,
         ServerChatCommand::DisconnectAllPlayers => handle_disconnect_all_players,
         ServerChatCommand::DropAll => handle_drop_all,
@@ -148,6 +169,13 @@ fn do_command(
         ServerChatCommand::GroupKick => handle_group_kick,
         ServerChatCommand::GroupLeave => handle_group_leave,
         ServerChatCommand::GroupPromote => handle_group_promote,
+        ServerChatCommand::Guild => handle_guild,
+        ServerChatCommand::GuildCreate => handle_guild_create,
+        ServerChatCommand::GuildDemote => handle_guild_demote,
+        ServerChatCommand::GuildInvite => handle_guild_invite,
+        ServerChatCommand::GuildKick => handle_guild_kick,
+        ServerChatCommand::GuildLeave => handle_guild_leave,
+        ServerChatCommand::GuildPromote => handle_guild_promote,
         ServerChatCommand::Health => handle_health,
         ServerChatCommand::Help => handle_help,
         ServerChatCommand::Home => handle_home,
@@ -166,13 +194,16 @@ fn do_command(
         ServerChatCommand::Object => handle_object,
         ServerChatCommand::PermitBuild => handle_permit_build,
         ServerChatCommand::Players => handle_players,
+        ServerChatCommand::Portal => handle_portal,
         ServerChatCommand::Region => handle_region,
         ServerChatCommand::ReloadChunks => handle_reload_chunks,
         ServerChatCommand::RemoveLights => handle_remove_lights,
         ServerChatCommand::RevokeBuild => handle_revoke_build,
         ServerChatCommand::RevokeBuildAll => handle_revoke_build_all,
+        ServerChatCommand::Rollback => handle_rollback,
         ServerChatCommand::Safezone => handle_safezone,
         ServerChatCommand::Say => handle_say,
+        ServerChatCommand::Schedule => handle_schedule,
         ServerChatCommand::ServerPhysics => handle_server_physics,
         ServerChatCommand::SetMotd => handle_set_motd,
         ServerChatCommand::Ship => handle_spawn_ship,
@@ -180,6 +211,7 @@ fn do_command(
         ServerChatCommand::SkillPoint => handle_skill_point,
         ServerChatCommand::SkillPreset => handle_skill_preset,
         ServerChatCommand::Spawn => handle_spawn,
+        ServerChatCommand::Spectate => handle_spectate,
         ServerChatCommand::Sudo => handle_sudo,
         ServerChatCommand::Tell => handle_tell,
         ServerChatCommand::Time => handle_time,
@@ -196,6 +228,7 @@ fn do_command(
         ServerChatCommand::DeleteLocation => handle_delete_location,
         ServerChatCommand::WeatherZone => handle_weather_zone,
         ServerChatCommand::Lightning => handle_lightning,
+        ServerChatCommand::WorldBorder => handle_world_border,
     };
 
     handler(server, client, target, args, cmd)
@@ -285,6 +318,14 @@ fn insert_or_replace_component<C: specs::Component>(
 }
 
 fn uuid(server: &Server, entity: EcsEntity, descriptor: &str) -> CmdResult<Uuid> {
+    if server
+        .state
+        .ecs()
+        .read_storage::<ConsoleOutput>()
+        .contains(entity)
+    {
+        return Ok(CONSOLE_UUID);
+    }
     server
         .state
         .ecs()
@@ -295,6 +336,9 @@ fn uuid(server: &Server, entity: EcsEntity, descriptor: &str) -> CmdResult<Uuid>
 }
 
 fn real_role(server: &Server, uuid: Uuid, descriptor: &str) -> CmdResult<AdminRole> {
+    if uuid == CONSOLE_UUID {
+        return Ok(AdminRole::Admin);
+    }
     server
         .editable_settings()
         .admins
@@ -363,6 +407,11 @@ fn verify_above_role(
         .get(&client_uuid)
         .map(|record| record.role);
 
+    // Whoever has access to the server console is above any role
+    if client_uuid == CONSOLE_UUID {
+        return Ok(());
+    }
+
     let player_temp = server.entity_admin_role(player);
     let player_perm = server
         .editable_settings()
@@ -408,6 +457,9 @@ fn uuid_to_username(
     uuid: Uuid,
 ) -> CmdResult<String> {
     let make_err = || format!("Unable to determine username for UUID {:?}", uuid);
+    if uuid == CONSOLE_UUID {
+        return Ok("Server console".to_string());
+    }
     let player_storage = server.state.ecs().read_storage::<comp::Player>();
 
     let fallback_alias = &player_storage
@@ -652,6 +704,9 @@ fn handle_make_npc(
             NpcData::Waypoint(_) => {
                 return Err("Waypoint spawning is not implemented".to_owned());
             },
+            NpcData::Minigame(_) => {
+                return Err("Minigame spawning is not implemented".to_owned());
+            },
             NpcData::Data {
                 inventory,
                 pos,
@@ -1037,11 +1092,13 @@ fn handle_time(
     let mut tod_lazymsg = None;
     let clients = server.state.ecs().read_storage::<Client>();
     let calendar = server.state.ecs().read_resource::<Calendar>();
+    let day_cycle_factor = *server.state.ecs().read_resource::<DayCycleFactor>();
     for client in (&clients).join() {
         let msg = tod_lazymsg.unwrap_or_else(|| {
             client.prepare(ServerGeneral::TimeOfDay(
                 TimeOfDay(new_time),
                 (*calendar).clone(),
+                day_cycle_factor,
             ))
         });
         let _ = client.send_prepared(&msg);
@@ -1265,10 +1322,42 @@ fn handle_spawn_training_dummy(
     server: &mut Server,
     client: EcsEntity,
     target: EcsEntity,
-    _args: Vec<String>,
-    _action: &ServerChatCommand,
+    args: Vec<String>,
+    action: &ServerChatCommand,
 ) -> CmdResult<()> {
+    const RESET_RADIUS: f32 = 30.0;
+
     let pos = position(server, target, "target")?;
+    match parse_cmd_args!(args, String).as_deref() {
+        Some("reset") => {
+            let ecs = server.state.ecs();
+            let mut reset = 0;
+            for (_, training_dummy, health) in (
+                &ecs.read_storage::<comp::Pos>(),
+                &mut ecs.write_storage::<comp::TrainingDummy>(),
+                &mut ecs.write_storage::<comp::Health>(),
+            )
+                .join()
+                .filter(|(dummy_pos, _, _)| {
+                    dummy_pos.0.distance_squared(pos.0) < RESET_RADIUS.powi(2)
+                })
+            {
+                training_dummy.reset();
+                health.revive();
+                reset += 1;
+            }
+            server.notify_client(
+                client,
+                ServerGeneral::server_msg(
+                    ChatType::CommandInfo,
+                    format!("Reset {} training dummies", reset),
+                ),
+            );
+            return Ok(());
+        },
+        Some(_) => return Err(action.help_string()),
+        None => {},
+    }
     let vel = Vec3::new(
         thread_rng().gen_range(-2.0..3.0),
         thread_rng().gen_range(-2.0..3.0),
@@ -1490,6 +1579,125 @@ fn handle_spawn_campfire(
     Ok(())
 }
 
+fn handle_character_export(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let target = if let Some(alias) = parse_cmd_args!(args, String) {
+        find_alias(server.state.ecs(), &alias)?.0
+    } else {
+        target
+    };
+    let key = server
+        .settings()
+        .character_transfer
+        .key
+        .clone()
+        .ok_or("No character transfer key is set in the server settings")?;
+    let transfer = {
+        let ecs = server.state.ecs();
+        if !matches!(
+            ecs.read_storage::<Presence>().get(target).map(|p| p.kind),
+            Some(PresenceKind::Character(_))
+        ) {
+            return Err("The player isn't playing a character".into());
+        }
+        let uuid = uuid(server, target, "target")?;
+        match (
+            ecs.read_storage::<comp::Stats>().get(target),
+            ecs.read_storage::<comp::Body>().get(target),
+            ecs.read_storage::<comp::SkillSet>().get(target),
+            ecs.read_storage::<Inventory>().get(target),
+        ) {
+            (Some(stats), Some(body), Some(skill_set), Some(inventory)) => {
+                CharacterTransfer::new(uuid.to_string(), stats, *body, skill_set, inventory)
+            },
+            _ => return Err("The player isn't playing a character".into()),
+        }
+    };
+    let file_name = character_transfer::export(&server.data_dir().path, &key, &transfer)
+        .map_err(|err| format!("Failed to export the character: {}", err))?;
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            format!("Exported {} to {}", transfer.alias, file_name),
+        ),
+    );
+    Ok(())
+}
+
+fn handle_character_import(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+    let file_name = parse_cmd_args!(args, String).ok_or_else(|| action.help_string())?;
+    let key = {
+        let settings = server.settings();
+        if !settings.character_transfer.allow_import {
+            return Err("This server doesn't allow importing characters".into());
+        }
+        settings
+            .character_transfer
+            .key
+            .clone()
+            .ok_or("No character transfer key is set in the server settings")?
+    };
+    let data_dir = server.data_dir().path.clone();
+    let transfer = character_transfer::read(&data_dir, &key, &file_name)
+        .map_err(|err| format!("Failed to import the character: {}", err))?;
+    let uuid = uuid(server, client, "client")?;
+    if transfer.player_uuid != uuid.to_string() {
+        return Err("This character belongs to another player".into());
+    }
+    let alias = transfer.alias.clone();
+    if server
+        .state
+        .ecs()
+        .read_resource::<Arc<censor::Censor>>()
+        .check(&alias)
+    {
+        return Err(format!("Alias '{}' contains a banned word", alias));
+    }
+    let (components, refused) = {
+        let ecs = server.state.ecs();
+        transfer.into_components(
+            &ecs.read_resource::<AbilityMap>(),
+            &ecs.read_resource::<MaterialStatManifest>(),
+        )
+    }
+    .map_err(|err| format!("Failed to import the character: {}", err))?;
+    // Mark the file before creating the character so that it can't be imported
+    // twice
+    character_transfer::mark_imported(&data_dir, &file_name)
+        .map_err(|err| format!("Failed to import the character: {}", err))?;
+    server
+        .state
+        .ecs()
+        .write_resource::<CharacterUpdater>()
+        .create_character(client, uuid.to_string(), alias.clone(), components);
+
+    let mut msg = format!("Importing {}", alias);
+    if !refused.is_empty() {
+        msg.push_str(", the following items were left behind:");
+        for reason in refused {
+            msg.push_str(&format!("\n{}", reason));
+        }
+    }
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, msg),
+    );
+    Ok(())
+}
+
 fn handle_safezone(
     server: &mut Server,
     client: EcsEntity,
@@ -1641,6 +1849,67 @@ fn handle_players(
     Ok(())
 }
 
+fn handle_portal(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (subcommand, name, other) = match parse_cmd_args!(args, String, String, String) {
+        (Some(subcommand), Some(name), other) => (subcommand, name, other),
+        _ => return Err(action.help_string()),
+    };
+    let msg = match subcommand.as_str() {
+        "create" => {
+            let pos = position(server, target, "target")?;
+            server
+                .state
+                .ecs()
+                .write_resource::<PortalNetwork>()
+                .create(name.clone(), pos.0)
+                .map_err(|err| err.to_string())?;
+            format!("Created portal {}", name)
+        },
+        "remove" => {
+            let entity = {
+                let mut network = server.state.ecs().write_resource::<PortalNetwork>();
+                let id = network.find(&name).map_err(|err| err.to_string())?;
+                network.remove(id)
+            };
+            if let Some(entity) = entity {
+                if let Err(e) = server.state.delete_entity_recorded(entity) {
+                    error!(?e, ?entity, "Failed to delete portal");
+                }
+            }
+            format!("Removed portal {}", name)
+        },
+        "link" | "unlink" => {
+            let other = other.ok_or_else(|| action.help_string())?;
+            let linked = subcommand == "link";
+            let mut network = server.state.ecs().write_resource::<PortalNetwork>();
+            let a = network.find(&name).map_err(|err| err.to_string())?;
+            let b = network.find(&other).map_err(|err| err.to_string())?;
+            if a == b {
+                return Err("A portal can't be linked to itself".into());
+            }
+            network.set_linked(a, b, linked);
+            format!(
+                "{} portals {} and {}",
+                if linked { "Linked" } else { "Unlinked" },
+                name,
+                other
+            )
+        },
+        _ => return Err(action.help_string()),
+    };
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, msg),
+    );
+    Ok(())
+}
+
 fn handle_build(
     server: &mut Server,
     client: EcsEntity,
@@ -2610,6 +2879,222 @@ fn handle_group_promote(
     }
 }
 
+/// Character played by the target along with its name
+fn guild_character(server: &Server, target: EcsEntity) -> CmdResult<(CharacterId, String)> {
+    let character_id = guild::character_of(server.state.ecs(), target)
+        .ok_or("You have to play a character to use guilds")?;
+    let name = server
+        .state
+        .ecs()
+        .read_storage::<comp::Stats>()
+        .get(target)
+        .map_or_else(String::new, |stats| stats.name.clone());
+    Ok((character_id, name))
+}
+
+fn notify_guild(server: &Server, guild: &str, msg: String) {
+    server
+        .state
+        .send_chat(ChatType::GuildMeta(guild.to_string()).chat_msg(msg));
+}
+
+fn handle_guild(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+
+    let guild = guild::character_of(server.state.ecs(), target).and_then(|character_id| {
+        server
+            .state
+            .ecs()
+            .read_resource::<Guilds>()
+            .guild_of(character_id)
+            .map(|guild| guild.name.clone())
+    });
+    if let Some(guild) = guild {
+        let mode = comp::ChatMode::Guild(guild);
+        insert_or_replace_component(server, target, mode.clone(), "target")?;
+        let msg = args.join(" ");
+        if !msg.is_empty() {
+            if let Some(uid) = server.state.ecs().read_storage().get(target) {
+                server.state.send_chat(mode.new_message(*uid, msg));
+            }
+        }
+        server.notify_client(target, ServerGeneral::ChatMode(mode));
+        Ok(())
+    } else {
+        Err("Please join a guild or found one with /guild_create".into())
+    }
+}
+
+fn handle_guild_create(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let guild = args.join(" ");
+    if guild.is_empty() {
+        return Err(action.help_string());
+    }
+    let (character_id, name) = guild_character(server, target)?;
+    server
+        .state
+        .mut_resource::<Guilds>()
+        .create(&guild, character_id, name)
+        .map_err(|err| err.to_string())?;
+    guild::update_guild(server.state.ecs(), GuildChange::Updated(guild.clone()));
+    notify_guild(
+        server,
+        &guild,
+        format!("The guild {} has been founded.", guild),
+    );
+    Ok(())
+}
+
+fn handle_guild_invite(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    if let Some(target_alias) = parse_cmd_args!(args, String) {
+        let target_player = find_alias(server.state.ecs(), &target_alias)?.0;
+        let uid = uid(server, target_player, "player")?;
+
+        // Rank and guild checks are done when handling the invite
+        server
+            .state
+            .mut_resource::<EventBus<ServerEvent>>()
+            .emit_now(ServerEvent::InitiateInvite(target, uid, InviteKind::Guild));
+        Ok(())
+    } else {
+        Err(action.help_string())
+    }
+}
+
+fn handle_guild_kick(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let member = args.join(" ");
+    if member.is_empty() {
+        return Err(action.help_string());
+    }
+    let (character_id, name) = guild_character(server, target)?;
+    let guilds = server.state.mut_resource::<Guilds>();
+    let guild = guilds
+        .guild_of(character_id)
+        .map(|guild| guild.name.clone())
+        .ok_or_else(|| GuildError::NotInGuild.to_string())?;
+    let kicked = guilds
+        .kick(character_id, &member)
+        .map_err(|err| err.to_string())?;
+
+    if let Some(entity) = guild::online_characters(server.state.ecs()).get(&kicked) {
+        server.notify_client(*entity, ServerGeneral::GuildUpdate(None));
+        server.notify_client(
+            *entity,
+            ServerGeneral::server_msg(
+                ChatType::CommandInfo,
+                format!("You have been kicked from {}.", guild),
+            ),
+        );
+    }
+    guild::update_guild(server.state.ecs(), GuildChange::Updated(guild.clone()));
+    notify_guild(
+        server,
+        &guild,
+        format!("{} has been kicked from the guild by {}.", member, name),
+    );
+    Ok(())
+}
+
+fn handle_guild_leave(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (character_id, name) = guild_character(server, target)?;
+    let change = server
+        .state
+        .mut_resource::<Guilds>()
+        .leave(character_id)
+        .map_err(|err| err.to_string())?;
+
+    server.notify_client(target, ServerGeneral::GuildUpdate(None));
+    let guild = match &change {
+        GuildChange::Updated(guild) => Some(guild.clone()),
+        GuildChange::Disbanded(_) => None,
+    };
+    guild::update_guild(server.state.ecs(), change);
+    if let Some(guild) = guild {
+        notify_guild(server, &guild, format!("{} left the guild.", name));
+    }
+    Ok(())
+}
+
+fn handle_guild_promote(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    change_guild_rank(server, target, args, action, true)
+}
+
+fn handle_guild_demote(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    change_guild_rank(server, target, args, action, false)
+}
+
+fn change_guild_rank(
+    server: &mut Server,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+    promote: bool,
+) -> CmdResult<()> {
+    let member = args.join(" ");
+    if member.is_empty() {
+        return Err(action.help_string());
+    }
+    let (character_id, _) = guild_character(server, target)?;
+    let guilds = server.state.mut_resource::<Guilds>();
+    let rank = guilds
+        .change_rank(character_id, &member, promote)
+        .map_err(|err| err.to_string())?;
+    let guild = guilds
+        .guild_of(character_id)
+        .map(|guild| guild.name.clone())
+        .ok_or_else(|| GuildError::NoSuchGuild.to_string())?;
+
+    guild::update_guild(server.state.ecs(), GuildChange::Updated(guild.clone()));
+    notify_guild(
+        server,
+        &guild,
+        format!("The rank of {} is now {:?}.", member, rank),
+    );
+    Ok(())
+}
+
 fn handle_region(
     server: &mut Server,
     client: EcsEntity,
@@ -2652,6 +3137,40 @@ fn handle_say(
     Ok(())
 }
 
+fn handle_schedule(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let msg = {
+        let ecs = server.state.ecs();
+        let settings = ecs.read_resource::<Settings>();
+        let scheduler = ecs.read_resource::<Scheduler>();
+        if settings.schedule.is_empty() {
+            "No tasks are scheduled.".to_string()
+        } else {
+            settings.schedule.iter().enumerate().fold(
+                "Scheduled tasks, with their next run in UTC:".to_string(),
+                |mut msg, (i, task)| {
+                    let next_run = scheduler.next_run(i).map_or_else(
+                        || "unknown".to_string(),
+                        |time| time.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    );
+                    msg.push_str(&format!("\n{}: {}", task.name, next_run));
+                    msg
+                },
+            )
+        }
+    };
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, msg),
+    );
+    Ok(())
+}
+
 fn handle_world(
     server: &mut Server,
     client: EcsEntity,
@@ -2955,6 +3474,67 @@ fn handle_remove_lights(
     Ok(())
 }
 
+fn handle_spectate(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+    let is_spectator = server
+        .state
+        .ecs()
+        .read_storage::<Presence>()
+        .get(client)
+        .map_or(false, |presence| presence.kind == PresenceKind::Spectator);
+    if !is_spectator {
+        return Err("You need to be in spectator mode to spectate a player.".into());
+    }
+
+    let alias = match parse_cmd_args!(args, String) {
+        Some(alias) => alias,
+        None => {
+            return if server
+                .state
+                .ecs()
+                .write_storage::<Spectating>()
+                .remove(client)
+                .is_some()
+            {
+                server.notify_client(client, ServerGeneral::SpectatePlayer(None));
+                server.notify_client(
+                    client,
+                    ServerGeneral::server_msg(ChatType::CommandInfo, "Stopped spectating."),
+                );
+                Ok(())
+            } else {
+                Err("You aren't spectating anyone.".into())
+            };
+        },
+    };
+    let (player, _) = find_alias(server.state.ecs(), &alias)?;
+    if player == client {
+        return Err("You can't spectate yourself.".into());
+    }
+    let player_uid = uid(server, player, "player")?;
+    let player_pos = position(server, player, "player")?;
+    insert_or_replace_component(server, client, Spectating { target: player_uid }, "client")?;
+    position_mut(server, client, "client", |pos| *pos = player_pos)?;
+    server.notify_client(client, ServerGeneral::SpectatePlayer(Some(player_uid)));
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, format!("Spectating {}.", alias)),
+    );
+    if server.settings().moderation.notify_spectated {
+        server.notify_client(
+            player,
+            ServerGeneral::server_msg(ChatType::CommandInfo, "A moderator is spectating you."),
+        );
+    }
+    Ok(())
+}
+
 fn handle_sudo(
     server: &mut Server,
     client: EcsEntity,
@@ -3196,6 +3776,11 @@ fn handle_ban(
         edit_setting_feedback(server, client, edit, || {
             format!("{} is already on the banlist", username)
         })?;
+        audit::record(server.state.ecs(), client, AuditEvent::Ban {
+            target: username.clone(),
+            reason: reason.clone(),
+            end_date,
+        });
         // If the player is online kick them (this may fail if the player is a hardcoded
         // admin; we don't care about that case because hardcoded admins can log on even
         // if they're on the ban list).
@@ -3214,6 +3799,181 @@ fn handle_ban(
     }
 }
 
+fn handle_attach(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    /// Distance within which the nearest entity is attached
+    const ATTACH_RANGE: f32 = 5.0;
+
+    let target_uid = uid(server, target, "target")?;
+    let ecs = server.state.ecs();
+    let entities = ecs.entities();
+    let mut attacheds = ecs.write_storage::<comp::Attached>();
+    let text = if let Some(point) = parse_cmd_args!(args, String) {
+        let point = comp::AttachPoint::ALL
+            .into_iter()
+            .find(|p| p.name() == point)
+            .ok_or_else(|| action.help_string())?;
+        let pos = position(server, target, "target")?;
+        // Not what the target is itself attached to, directly or through other
+        // entities, which would have them follow each other in a loop
+        let mut anchors = Vec::new();
+        let mut current = target;
+        while let Some(anchor) = attacheds
+            .get(current)
+            .and_then(|attached| ecs.entity_from_uid(attached.to.0))
+            .filter(|anchor| *anchor != target && !anchors.contains(anchor))
+        {
+            anchors.push(anchor);
+            current = anchor;
+        }
+        let nearest = (
+            &entities,
+            &ecs.read_storage::<comp::Pos>(),
+            !&ecs.read_storage::<comp::Player>(),
+            !&attacheds,
+        )
+            .join()
+            .filter(|(entity, _, _, _)| *entity != target && !anchors.contains(entity))
+            .map(|(entity, other_pos, _, _)| (entity, other_pos.0.distance_squared(pos.0)))
+            .filter(|(_, dist_sqr)| *dist_sqr < ATTACH_RANGE.powi(2))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(entity, _)| entity)
+            .ok_or("No NPC or object nearby to attach")?;
+        attacheds
+            .insert(nearest, comp::Attached::new(target_uid, point))
+            .map_err(|_| "Failed to attach the entity")?;
+        format!("Attached the nearest entity to the {} point", point.name())
+    } else {
+        let detached = (&entities, &attacheds)
+            .join()
+            .filter(|(_, attached)| attached.to == target_uid)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        for entity in &detached {
+            attacheds.remove(*entity);
+        }
+        format!("Let go of {} attached entities", detached.len())
+    };
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, text),
+    );
+    Ok(())
+}
+
+fn handle_audit(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    if let (Some(kind), Some(name), count) = parse_cmd_args!(args, String, String, u32) {
+        if kind != "player" {
+            return Err(action.help_string());
+        }
+        let entries = server
+            .state
+            .ecs()
+            .read_resource::<AuditLog>()
+            .query(&name, count.unwrap_or(10) as usize)
+            .map_err(|err| format!("Failed to read the audit log: {}", err))?;
+        let msg = entries.iter().fold(
+            format!("Latest recorded events involving {}:", name),
+            |mut msg, entry| {
+                msg.push_str(&format!(
+                    "\n[{}] {} {}",
+                    entry.time.format("%Y-%m-%d %H:%M:%S"),
+                    entry.actor,
+                    entry.event
+                ));
+                msg
+            },
+        );
+        server.notify_client(
+            client,
+            ServerGeneral::server_msg(ChatType::CommandInfo, msg),
+        );
+        Ok(())
+    } else {
+        Err(action.help_string())
+    }
+}
+
+fn handle_rollback(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    if let (Some(username), Some(duration), dry_run) =
+        parse_cmd_args!(args, String, HumanDuration, bool)
+    {
+        let dry_run = dry_run.unwrap_or(false);
+        let since = chrono::Duration::from_std(duration.into())
+            .ok()
+            .and_then(|duration| Utc::now().checked_sub_signed(duration))
+            // Durations going back further than anything recorded revert everything
+            .unwrap_or(chrono::MIN_DATETIME);
+        let edits = server
+            .state
+            .ecs()
+            .read_resource::<AuditLog>()
+            .terrain_edits(&username, since)
+            .map_err(|err| format!("Failed to read the audit log: {}", err))?;
+        let mut rollback = audit::Rollback::new(client, username, edits);
+        if dry_run {
+            let terrain = server.state.terrain();
+            while !rollback.is_done() {
+                rollback.next_chunk(&terrain);
+            }
+            drop(terrain);
+            server.notify_client(
+                client,
+                ServerGeneral::server_msg(
+                    ChatType::CommandInfo,
+                    format!(
+                        "Would revert {} blocks changed by {} in {} chunks, {} blocks changed \
+                         since are left alone",
+                        rollback.reverted_count,
+                        rollback.username,
+                        rollback.chunk_count(),
+                        rollback.skipped_count
+                    ),
+                ),
+            );
+        } else {
+            server.notify_client(
+                client,
+                ServerGeneral::server_msg(
+                    ChatType::CommandInfo,
+                    format!(
+                        "Rolling back {} chunks changed by {}...",
+                        rollback.chunk_count(),
+                        rollback.username
+                    ),
+                ),
+            );
+            server
+                .state
+                .ecs()
+                .write_resource::<audit::Rollbacks>()
+                .0
+                .push(rollback);
+        }
+        Ok(())
+    } else {
+        Err(action.help_string())
+    }
+}
+
 fn handle_battlemode(
     server: &mut Server,
     client: EcsEntity,
@@ -3655,6 +4415,7 @@ fn handle_weather_zone(
                 add_zone(weather::Weather {
                     cloud: 0.0,
                     rain: 0.0,
+                    snow: 0.0,
                     wind: Vec2::zero(),
                 });
                 Ok(())
@@ -3663,6 +4424,7 @@ fn handle_weather_zone(
                 add_zone(weather::Weather {
                     cloud: 0.4,
                     rain: 0.0,
+                    snow: 0.0,
                     wind: Vec2::zero(),
                 });
                 Ok(())
@@ -3671,14 +4433,25 @@ fn handle_weather_zone(
                 add_zone(weather::Weather {
                     cloud: 0.1,
                     rain: 0.15,
+                    snow: 0.0,
                     wind: Vec2::new(1.0, -1.0),
                 });
                 Ok(())
             },
+            "snow" => {
+                add_zone(weather::Weather {
+                    cloud: 0.2,
+                    rain: 0.0,
+                    snow: 0.3,
+                    wind: Vec2::new(2.0, -1.0),
+                });
+                Ok(())
+            },
             "wind" => {
                 add_zone(weather::Weather {
                     cloud: 0.0,
                     rain: 0.0,
+                    snow: 0.0,
                     wind: Vec2::new(10.0, 10.0),
                 });
                 Ok(())
@@ -3687,11 +4460,14 @@ fn handle_weather_zone(
                 add_zone(weather::Weather {
                     cloud: 0.3,
                     rain: 0.3,
+                    snow: 0.0,
                     wind: Vec2::new(15.0, 20.0),
                 });
                 Ok(())
             },
-            _ => Err("Valid values are 'clear', 'rain', 'wind', 'storm'".to_string()),
+            _ => Err(
+                "Valid values are 'clear', 'cloudy', 'rain', 'snow', 'wind', 'storm'".to_string(),
+            ),
         }
     } else {
         Err(action.help_string())
@@ -3706,6 +4482,11 @@ fn handle_lightning(
     _action: &ServerChatCommand,
 ) -> CmdResult<()> {
     let pos = position(server, client, "player")?.0;
+    server
+        .state
+        .ecs()
+        .write_resource::<Fires>()
+        .ignite(pos.map(|e| e.floor() as i32));
     server
         .state
         .ecs()
@@ -3714,6 +4495,57 @@ fn handle_lightning(
     Ok(())
 }
 
+fn handle_world_border(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let center = position(server, client, "player")?.0.xy();
+    let (shape, time) = match parse_cmd_args!(args, String, f32, f32, f32) {
+        (Some(kind), Some(radius), time, _) if kind == "circle" && radius > 0.0 => {
+            (Some(BorderShape::Circle { center, radius }), time)
+        },
+        (Some(kind), Some(width), Some(height), time)
+            if kind == "rect" && width > 0.0 && height > 0.0 =>
+        {
+            let half_size = Vec2::new(width, height) / 2.0;
+            (
+                Some(BorderShape::Rect {
+                    min: center - half_size,
+                    max: center + half_size,
+                }),
+                time,
+            )
+        },
+        (Some(kind), ..) if kind == "off" => (None, None),
+        _ => return Err(action.help_string()),
+    };
+
+    let border = {
+        let mut border = server.state.ecs().write_resource::<WorldBorder>();
+        match shape {
+            Some(shape) => border.move_to(shape, time),
+            None => border.remove(),
+        }
+        border.clone()
+    };
+    let msg = match (&border.shape, &border.target) {
+        (None, _) => "World border removed.".to_string(),
+        (Some(_), Some(_)) => format!("World border moving over {} seconds.", time.unwrap_or(0.0)),
+        (Some(_), None) => "World border set.".to_string(),
+    };
+    server
+        .state
+        .notify_in_game_clients(ServerGeneral::WorldBorder(border));
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(ChatType::CommandInfo, msg),
+    );
+    Ok(())
+}
+
 fn handle_body(
     server: &mut Server,
     _client: EcsEntity,
@@ -107,6 +107,8 @@ impl ConnectionHandler {
         let character_screen_stream = participant.open(3, reliablec, 500).await?;
         let in_game_stream = participant.open(3, reliablec, 100_000).await?;
         let terrain_stream = participant.open(4, reliable, 20_000).await?;
+        // Late voice frames are concealed by the listener rather than waited for
+        let voice_stream = participant.open(2, Promises::CONSISTENCY, 2_000).await?;
 
         let server_data = receiver.recv()?;
 
@@ -134,6 +136,7 @@ impl ConnectionHandler {
             character_screen_stream,
             in_game_stream,
             terrain_stream,
+            voice_stream,
         );
 
         client_sender.send(client)?;
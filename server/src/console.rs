@@ -0,0 +1,15 @@
+use authc::Uuid;
+use specs::{Component, HashMapStorage};
+
+/// Stands in for the account of the virtual superuser running the commands
+/// typed in the server console
+pub const CONSOLE_UUID: Uuid = Uuid::nil();
+
+/// Replaces the client of the entity the server console runs commands as,
+/// collecting the replies to show them in the console
+#[derive(Default)]
+pub struct ConsoleOutput(pub Vec<String>);
+
+impl Component for ConsoleOutput {
+    type Storage = HashMapStorage<Self>;
+}
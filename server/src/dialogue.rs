@@ -0,0 +1,81 @@
+use crate::client::Client;
+use common::{
+    assets::AssetExt,
+    comp::dialogue::{DialogueTree, Topic},
+};
+use common_net::msg::ServerGeneral;
+use common_state::State;
+use specs::{Component, Entity as EcsEntity, VecStorage, WorldExt};
+
+/// Dialogue of the villagers and merchants
+pub const VILLAGER_DIALOGUE: &str = "common.dialogue.villager";
+
+/// Node reached by a player in the dialogue with a NPC
+#[derive(Clone, Debug)]
+pub struct Dialogue {
+    pub npc: EcsEntity,
+    pub tree: &'static str,
+    pub node: String,
+}
+
+impl Component for Dialogue {
+    type Storage = VecStorage<Self>;
+}
+
+fn send_choices(state: &State, player: EcsEntity, npc: EcsEntity, choices: Option<Vec<String>>) {
+    if let (Some(client), Some(npc)) = (
+        state.ecs().read_storage::<Client>().get(player),
+        state.ecs().uid_from_entity(npc),
+    ) {
+        client.send_fallible(ServerGeneral::Dialogue { npc, choices });
+    }
+}
+
+/// Starts the dialogue of a player with a NPC, replacing the one they had
+pub fn start(state: &State, player: EcsEntity, npc: EcsEntity, tree: &'static str) {
+    let dialogue = DialogueTree::load_expect(tree).read();
+    let choices = dialogue.nodes.get(&dialogue.start).map(|node| {
+        node.choices
+            .iter()
+            .map(|choice| choice.text.clone())
+            .collect()
+    });
+    let _ = state.ecs().write_storage().insert(player, Dialogue {
+        npc,
+        tree,
+        node: dialogue.start.clone(),
+    });
+    send_choices(state, player, npc, choices);
+}
+
+/// Picks a choice of the dialogue of a player with a NPC, returns the topic to
+/// bring up with the NPC at the node it leads to. The dialogue is over once it
+/// reaches a node without choices.
+pub fn choose(state: &State, player: EcsEntity, npc: EcsEntity, choice: usize) -> Option<Topic> {
+    let mut dialogues = state.ecs().write_storage::<Dialogue>();
+    let dialogue = dialogues.get_mut(player).filter(|d| d.npc == npc)?;
+    let tree = DialogueTree::load_expect(dialogue.tree).read();
+    let next = tree
+        .choice(&dialogue.node, choice)?
+        .next
+        .as_ref()
+        .and_then(|next| tree.nodes.get_key_value(next));
+    let (topic, choices) = match next {
+        Some((name, node)) if !node.choices.is_empty() => {
+            dialogue.node = name.clone();
+            let choices = node
+                .choices
+                .iter()
+                .map(|choice| choice.text.clone())
+                .collect();
+            (node.topic, Some(choices))
+        },
+        next => {
+            dialogues.remove(player);
+            (next.and_then(|(_, node)| node.topic), None)
+        },
+    };
+    drop(dialogues);
+    send_choices(state, player, npc, choices);
+    topic
+}
@@ -7,8 +7,10 @@ use common::{
         aura::{Aura, AuraKind, AuraTarget},
         beam,
         buff::{BuffCategory, BuffData, BuffKind, BuffSource},
+        faction::Faction,
+        minigame::{Game, ThreeInARow},
         shockwave, Agent, Alignment, Anchor, Body, Health, Inventory, ItemDrop, LightEmitter,
-        Object, Ori, PidController, Poise, Pos, Projectile, Scale, SkillSet, Stats, Vel,
+        Minigame, Object, Ori, PidController, Poise, Pos, Projectile, Scale, SkillSet, Stats, Vel,
         WaypointArea,
     },
     event::EventBus,
@@ -92,12 +94,19 @@ pub fn handle_create_npc(
     rtsim_entity: Option<RtSimEntity>,
     projectile: Option<Projectile>,
 ) {
+    let faction = Faction::from_alignment(alignment, &body);
     let entity = server
         .state
         .create_npc(pos, stats, skill_set, health, poise, inventory, body)
         .with(scale)
         .with(alignment);
 
+    let entity = if let Some(faction) = faction {
+        entity.with(faction)
+    } else {
+        entity
+    };
+
     let entity = if let Some(agent) = agent.into() {
         entity.with(agent)
     } else {
@@ -138,6 +147,7 @@ pub fn handle_create_npc(
         let mut group_manager = state.ecs().write_resource::<comp::group::GroupManager>();
         if let Some(owner) = state.ecs().entity_from_uid(owner_uid.into()) {
             let map_markers = state.ecs().read_storage::<comp::MapMarker>();
+            let shared_map_markers = state.ecs().read_storage::<comp::SharedMapMarkers>();
             group_manager.new_pet(
                 new_entity,
                 owner,
@@ -156,7 +166,13 @@ pub fn handle_create_npc(
                         .map(|(g, c)| {
                             // Might be unnecessary, but maybe pets can somehow have map
                             // markers in the future
-                            update_map_markers(&map_markers, &uids, c, &group_change);
+                            update_map_markers(
+                                &map_markers,
+                                &shared_map_markers,
+                                &uids,
+                                c,
+                                &group_change,
+                            );
                             c.send_fallible(ServerGeneral::GroupUpdate(g));
                         });
                 },
@@ -295,3 +311,13 @@ pub fn handle_create_waypoint(server: &mut Server, pos: Vec3<f32>) {
         ]))
         .build();
 }
+
+/// Board game for the players around to play together
+pub fn handle_create_minigame(server: &mut Server, pos: Vec3<f32>) {
+    server
+        .state
+        .create_object(Pos(pos), comp::object::Body::CarpetHumanSquare)
+        .with(comp::Immovable)
+        .with(Minigame::new(Game::ThreeInARow(ThreeInARow::default())))
+        .build();
+}
@@ -7,6 +7,7 @@ use crate::{
         skillset::SkillGroupKind,
         BuffKind, BuffSource, PhysicsState,
     },
+    fire::Fires,
     rtsim::RtSim,
     sys::terrain::SAFE_ZONE_RADIUS,
     Server, SpawnPoint, StateExt,
@@ -17,7 +18,7 @@ use common::{
     comp::{
         self, aura, buff,
         chat::{KillSource, KillType},
-        inventory::item::MaterialStatManifest,
+        inventory::item::{MaterialStatManifest, Reagent},
         loot_owner::LootOwnerKind,
         Alignment, Auras, Body, CharacterState, Energy, Group, Health, HealthChange, Inventory,
         Player, Poise, Pos, SkillSet, Stats,
@@ -33,7 +34,7 @@ use common::{
     Damage, DamageKind, DamageSource, Explosion, GroupTarget, RadiusEffect,
 };
 use common_net::{msg::ServerGeneral, sync::WorldSyncExt};
-use common_state::BlockChange;
+use common_state::{BlockChange, BuildAreas};
 use comp::chat::GenericChatMsg;
 use hashbrown::HashSet;
 use rand::{distributions::WeightedIndex, Rng};
@@ -69,6 +70,14 @@ pub fn handle_health_change(server: &Server, entity: EcsEntity, change: HealthCh
     if let Some(mut health) = ecs.write_storage::<Health>().get_mut(entity) {
         // If the change amount was not zero
         let changed = health.change_by(change);
+        // Training dummies can't be killed, the damage dealt to them is measured
+        // instead
+        if let Some(training_dummy) = ecs.write_storage::<comp::TrainingDummy>().get_mut(entity) {
+            if let Some(attacker) = change.by.filter(|_| change.amount < 0.0) {
+                training_dummy.record(attacker.uid(), change.time, -change.amount);
+            }
+            health.revive();
+        }
         if let (Some(pos), Some(uid)) = (
             ecs.read_storage::<Pos>().get(entity),
             ecs.read_storage::<Uid>().get(entity),
@@ -167,11 +176,18 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, last_change: Healt
         .get(entity)
         .is_some()
     {
-        if let Some(pos) = state.ecs().read_storage::<Pos>().get(entity) {
+        if let (Some(pos), Some(uid)) = (
+            state.ecs().read_storage::<Pos>().get(entity),
+            state.ecs().read_storage::<Uid>().get(entity),
+        ) {
             state
                 .ecs()
                 .read_resource::<EventBus<Outcome>>()
-                .emit_now(Outcome::Death { pos: pos.0 });
+                .emit_now(Outcome::Death {
+                    pos: pos.0,
+                    uid: *uid,
+                    by: last_change.by.map(|by| by.uid()),
+                });
         }
     }
 
@@ -200,6 +216,7 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, last_change: Healt
             state.send_chat(GenericChatMsg {
                 chat_type: comp::ChatType::Kill(kill_source, *uid),
                 message: "".to_string(),
+                links: Vec::new(),
             });
         }
     }
@@ -387,6 +404,41 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, last_change: Healt
         });
     })();
 
+    // Count the kill towards the quests of everyone who got exp for it
+    if let Some(victim_name) = state
+        .ecs()
+        .read_storage::<Stats>()
+        .get(entity)
+        .map(|stats| stats.name.clone())
+    {
+        let mut quest_logs = state.ecs().write_storage::<comp::QuestLog>();
+        for (attacker, _, _) in exp_awards.iter() {
+            if let Some(mut quest_log) = quest_logs.get_mut(*attacker) {
+                quest_log.record_kill(&victim_name);
+            }
+        }
+    }
+
+    // Killing members of a faction changes the standing of the players involved
+    if let Some(victim_faction) = state
+        .ecs()
+        .read_storage::<comp::faction::Faction>()
+        .get(entity)
+        .copied()
+    {
+        let players = state.ecs().read_storage::<Player>();
+        let mut reputations = state.ecs().write_storage::<comp::Reputation>();
+        for (attacker, _, _) in exp_awards.iter() {
+            if players.contains(*attacker) {
+                if let Ok(entry) = reputations.entry(*attacker) {
+                    entry
+                        .or_insert_with(comp::Reputation::default)
+                        .record_kill(victim_faction);
+                }
+            }
+        }
+    }
+
     let should_delete = if state
         .ecs()
         .write_storage::<Client>()
@@ -673,6 +725,11 @@ pub fn handle_explosion(server: &Server, pos: Vec3<f32>, explosion: Explosion, o
             .any(|e| matches!(e, RadiusEffect::Attack(_))),
         reagent: explosion.reagent,
     });
+    // Fiery explosions set what they hit on fire
+    if explosion.reagent == Some(Reagent::Red) {
+        ecs.write_resource::<Fires>()
+            .ignite(pos.map(|e| e.floor() as i32));
+    }
     let groups = ecs.read_storage::<Group>();
 
     // Used to get strength of explosion effects as they falloff over distance
@@ -748,6 +805,9 @@ pub fn handle_explosion(server: &Server, pos: Vec3<f32>, explosion: Explosion, o
                     continue 'effects;
                 }
 
+                // Leave the protected areas, like towns, unharmed
+                let build_areas = ecs.read_resource::<BuildAreas>();
+
                 // Color terrain
                 let mut touched_blocks = Vec::new();
                 let color_range = power * 2.7;
@@ -770,6 +830,9 @@ pub fn handle_explosion(server: &Server, pos: Vec3<f32>, explosion: Explosion, o
                 let terrain = ecs.read_resource::<TerrainGrid>();
                 let mut block_change = ecs.write_resource::<BlockChange>();
                 for block_pos in touched_blocks {
+                    if build_areas.protects(block_pos) {
+                        continue;
+                    }
                     if let Ok(block) = terrain.get(block_pos) {
                         if !matches!(block.kind(), BlockKind::Lava | BlockKind::GlowingRock)
                             && settings.gameplay.explosion_burn_marks
@@ -832,7 +895,7 @@ pub fn handle_explosion(server: &Server, pos: Vec3<f32>, explosion: Explosion, o
                             stop
                         })
                         .for_each(|block: &Block, pos| {
-                            if block.explode_power().is_some() {
+                            if block.explode_power().is_some() && !build_areas.protects(pos) {
                                 block_change.set(pos, block.into_vacant());
                             }
                         })
@@ -1338,8 +1401,53 @@ pub fn handle_update_map_marker(
             server.state.delete_component::<MapMarker>(entity);
         },
     }
-    let ecs = server.state.ecs_mut();
     // Send updated waypoint to group members
+    send_to_other_group_members(server, entity, |uid| {
+        comp::MapMarkerUpdate::GroupMember(uid, update)
+    });
+}
+
+pub fn handle_share_map_markers(
+    server: &mut Server,
+    entity: EcsEntity,
+    mut markers: Vec<comp::NamedMapMarker>,
+) {
+    markers.truncate(comp::MAX_SHARED_MAP_MARKERS);
+    for marker in &mut markers {
+        if let Some((end, _)) = marker
+            .name
+            .char_indices()
+            .nth(comp::MAX_MAP_MARKER_NAME_LEN)
+        {
+            marker.name.truncate(end);
+        }
+    }
+    // Taking the markers down is always allowed
+    if !markers.is_empty() && !server.state.validate_map_markers(entity, &markers) {
+        return;
+    }
+    if markers.is_empty() {
+        server
+            .state
+            .delete_component::<comp::SharedMapMarkers>(entity);
+    } else {
+        server
+            .state
+            .write_component_ignore_entity_dead(entity, comp::SharedMapMarkers(markers.clone()));
+    }
+    send_to_other_group_members(server, entity, |uid| {
+        comp::MapMarkerUpdate::GroupMemberShared(uid, markers.clone())
+    });
+}
+
+/// Sends the update about the markers of `entity` to the other members of its
+/// group
+fn send_to_other_group_members(
+    server: &mut Server,
+    entity: EcsEntity,
+    update: impl Fn(Uid) -> comp::MapMarkerUpdate,
+) {
+    let ecs = server.state.ecs_mut();
     let groups = ecs.read_storage();
     let uids = ecs.read_storage();
     if let Some((group_id, uid)) = groups.get(entity).zip(uids.get(entity)) {
@@ -1353,9 +1461,7 @@ pub fn handle_update_map_marker(
         )
         .filter_map(|(e, _)| if e != entity { clients.get(e) } else { None })
         {
-            client.send_fallible(ServerGeneral::MapMarker(
-                comp::MapMarkerUpdate::GroupMember(*uid, update),
-            ));
+            client.send_fallible(ServerGeneral::MapMarker(update(*uid)));
         }
     }
 }
@@ -83,13 +83,15 @@ pub fn can_invite(
 
 pub fn update_map_markers<'a>(
     map_markers: &ReadStorage<'a, comp::MapMarker>,
+    shared_map_markers: &ReadStorage<'a, comp::SharedMapMarkers>,
     uids: &ReadStorage<'a, Uid>,
     client: &Client,
     change: &ChangeNotification<Entity>,
 ) {
     use comp::group::ChangeNotification::*;
     let send_update = |entity| {
-        if let (Some(map_marker), Some(uid)) = (map_markers.get(entity), uids.get(entity)) {
+        let Some(uid) = uids.get(entity) else { return };
+        if let Some(map_marker) = map_markers.get(entity) {
             client.send_fallible(ServerGeneral::MapMarker(
                 comp::MapMarkerUpdate::GroupMember(
                     *uid,
@@ -97,6 +99,11 @@ pub fn update_map_markers<'a>(
                 ),
             ));
         }
+        if let Some(shared) = shared_map_markers.get(entity) {
+            client.send_fallible(ServerGeneral::MapMarker(
+                comp::MapMarkerUpdate::GroupMemberShared(*uid, shared.0.clone()),
+            ));
+        }
     };
     match change {
         &Added(entity, _) => {
@@ -121,6 +128,7 @@ pub fn handle_group(server: &mut Server, entity: Entity, manip: GroupManip) {
             let uids = state.ecs().read_storage::<Uid>();
             let mut group_manager = state.ecs().write_resource::<GroupManager>();
             let map_markers = state.ecs().read_storage::<comp::MapMarker>();
+            let shared_map_markers = state.ecs().read_storage::<comp::SharedMapMarkers>();
             group_manager.leave_group(
                 entity,
                 &mut state.ecs().write_storage(),
@@ -136,7 +144,13 @@ pub fn handle_group(server: &mut Server, entity: Entity, manip: GroupManip) {
                                 .map(|g| (g, c))
                         })
                         .map(|(g, c)| {
-                            update_map_markers(&map_markers, &uids, c, &group_change);
+                            update_map_markers(
+                                &map_markers,
+                                &shared_map_markers,
+                                &uids,
+                                c,
+                                &group_change,
+                            );
                             c.send_fallible(ServerGeneral::GroupUpdate(g));
                         });
                 },
@@ -187,6 +201,7 @@ pub fn handle_group(server: &mut Server, entity: Entity, manip: GroupManip) {
             let mut groups = state.ecs().write_storage::<Group>();
             let mut group_manager = state.ecs().write_resource::<GroupManager>();
             let map_markers = state.ecs().read_storage::<comp::MapMarker>();
+            let shared_map_markers = state.ecs().read_storage::<comp::SharedMapMarkers>();
             // Make sure kicker is the group leader
             match groups
                 .get(target)
@@ -209,7 +224,13 @@ pub fn handle_group(server: &mut Server, entity: Entity, manip: GroupManip) {
                                         .map(|g| (g, c))
                                 })
                                 .map(|(g, c)| {
-                                    update_map_markers(&map_markers, &uids, c, &group_change);
+                                    update_map_markers(
+                                        &map_markers,
+                                        &shared_map_markers,
+                                        &uids,
+                                        c,
+                                        &group_change,
+                                    );
                                     c.send_fallible(ServerGeneral::GroupUpdate(g));
                                 });
                         },
@@ -270,6 +291,7 @@ pub fn handle_group(server: &mut Server, entity: Entity, manip: GroupManip) {
             let groups = state.ecs().read_storage::<Group>();
             let mut group_manager = state.ecs().write_resource::<GroupManager>();
             let map_markers = state.ecs().read_storage::<comp::MapMarker>();
+            let shared_map_markers = state.ecs().read_storage::<comp::SharedMapMarkers>();
             // Make sure assigner is the group leader
             match groups
                 .get(target)
@@ -292,7 +314,13 @@ pub fn handle_group(server: &mut Server, entity: Entity, manip: GroupManip) {
                                         .map(|g| (g, c))
                                 })
                                 .map(|(g, c)| {
-                                    update_map_markers(&map_markers, &uids, c, &group_change);
+                                    update_map_markers(
+                                        &map_markers,
+                                        &shared_map_markers,
+                                        &uids,
+                                        c,
+                                        &group_change,
+                                    );
                                     c.send_fallible(ServerGeneral::GroupUpdate(g));
                                 });
                         },
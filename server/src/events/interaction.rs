@@ -1,4 +1,4 @@
-use specs::{world::WorldExt, Builder, Entity as EcsEntity, Join};
+use specs::{saveload::MarkerAllocator, world::WorldExt, Builder, Entity as EcsEntity, Join};
 use vek::*;
 
 use common::{
@@ -9,21 +9,36 @@ use common::{
         dialogue::Subject,
         inventory::slot::EquipSlot,
         loot_owner::LootOwnerKind,
+        portal::{PortalId, PORTAL_CHANNEL_TIME},
         tool::ToolKind,
-        Inventory, LootOwner, Pos, SkillGroupKind,
+        DiscoveredPortals, Inventory, LootOwner, Minigame, MinigameAction, Pos, SkillGroupKind,
     },
-    consts::{MAX_MOUNT_RANGE, SOUND_TRAVEL_DIST_PER_VOLUME},
+    consts::{MAX_MOUNT_RANGE, MAX_PICKUP_RANGE, SOUND_TRAVEL_DIST_PER_VOLUME},
     event::EventBus,
     link::Is,
     mounting::{Mount, Mounting, Rider},
     outcome::Outcome,
+    resources::Time,
     terrain::{Block, SpriteKind},
-    uid::Uid,
+    uid::{Uid, UidAllocator},
     vol::ReadVol,
 };
-use common_net::sync::WorldSyncExt;
+use common_net::{
+    msg::{Notification, ServerGeneral},
+    sync::WorldSyncExt,
+};
 
-use crate::{state_ext::StateExt, Server};
+use crate::{
+    client::Client,
+    dialogue,
+    portal::PortalNetwork,
+    state_ext::StateExt,
+    sys::{
+        portal::PortalChannel,
+        sleep::{sleepers_needed, Sleeping},
+    },
+    Server,
+};
 
 use crate::pet::tame_pet;
 use hashbrown::{HashMap, HashSet};
@@ -83,11 +98,52 @@ pub fn handle_npc_interaction(server: &mut Server, interactor: EcsEntity, npc_en
         .write_storage::<comp::Agent>()
         .get_mut(npc_entity)
     {
-        if agent.target.is_none() {
+        if agent.target.is_some() {
+            return;
+        }
+        if let Some(interactor_uid) = state.ecs().uid_from_entity(interactor) {
+            agent
+                .inbox
+                .push_back(AgentEvent::Talk(interactor_uid, Subject::Regular));
+        }
+    } else {
+        return;
+    }
+    // Villagers and merchants have something to talk about
+    if matches!(
+        state.ecs().read_storage::<comp::Body>().get(npc_entity),
+        Some(comp::Body::Humanoid(_))
+    ) {
+        dialogue::start(state, interactor, npc_entity, dialogue::VILLAGER_DIALOGUE);
+    }
+}
+
+/// Picks a choice of the dialogue with a NPC, the topic of the node it leads
+/// to is only brought up if the NPC isn't busy with someone else
+pub fn handle_npc_talk(
+    server: &mut Server,
+    interactor: EcsEntity,
+    npc_entity: EcsEntity,
+    choice: usize,
+) {
+    let state = server.state_mut();
+    let topic = match dialogue::choose(state, interactor, npc_entity, choice) {
+        Some(topic) => topic,
+        None => return,
+    };
+    if let Some(agent) = state
+        .ecs()
+        .write_storage::<comp::Agent>()
+        .get_mut(npc_entity)
+    {
+        if agent
+            .target
+            .map_or(true, |target| target.target == interactor)
+        {
             if let Some(interactor_uid) = state.ecs().uid_from_entity(interactor) {
                 agent
                     .inbox
-                    .push_back(AgentEvent::Talk(interactor_uid, Subject::Regular));
+                    .push_back(AgentEvent::Talk(interactor_uid, topic.into()));
             }
         }
     }
@@ -312,3 +368,209 @@ pub fn handle_tame_pet(server: &mut Server, pet_entity: EcsEntity, owner_entity:
     // showing taming success?
     tame_pet(server.state.ecs(), pet_entity, owner_entity);
 }
+
+/// Lying down in a bed makes it the respawn point, and at night the player
+/// falls asleep until enough players are sleeping to skip it (see
+/// `sys::sleep`)
+pub fn handle_sleep(server: &mut Server, entity: EcsEntity, bed_pos: Vec3<i32>) {
+    let vote_fraction = server.settings().gameplay.night.sleep_vote_fraction;
+    let state = server.state();
+    let ecs = state.ecs();
+    let is_bed = state
+        .terrain()
+        .get(bed_pos)
+        .ok()
+        .and_then(|block| block.get_sprite())
+        == Some(SpriteKind::Bed);
+    let in_reach = ecs.read_storage::<Pos>().get(entity).map_or(false, |pos| {
+        pos.0.distance_squared(bed_pos.as_() + 0.5) < MAX_PICKUP_RANGE.powi(2)
+    });
+    let players = ecs.read_storage::<comp::Player>();
+    if !is_bed || !in_reach || !players.contains(entity) {
+        return;
+    }
+
+    let clients = ecs.read_storage::<Client>();
+    let time = *ecs.read_resource::<Time>();
+    let _ = ecs.write_storage::<comp::Waypoint>().insert(
+        entity,
+        comp::Waypoint::new(bed_pos.as_() + Vec3::new(0.5, 0.5, 1.0), time),
+    );
+    if let Some(client) = clients.get(entity) {
+        client.send_fallible(ServerGeneral::Notification(Notification::WaypointSaved));
+    }
+
+    if state.get_day_period().is_light() {
+        if let Some(client) = clients.get(entity) {
+            client.send_fallible(ServerGeneral::Notification(Notification::CantSleep));
+        }
+        return;
+    }
+
+    let mut sleepings = ecs.write_storage::<Sleeping>();
+    let _ = sleepings.insert(entity, Sleeping { bed_pos });
+    // Let everyone know how many more players have to go to bed
+    let needed = sleepers_needed(players.join().count(), vote_fraction);
+    if needed > 1 {
+        let sleeping = (&sleepings, &players).join().count() as u32;
+        for (client, _) in (&clients, &players).join() {
+            client.send_fallible(ServerGeneral::Notification(Notification::Sleeping {
+                sleeping,
+                needed,
+            }));
+        }
+    }
+}
+
+/// Id of the portal if the player is close enough to use it
+fn portal_in_reach(ecs: &specs::World, entity: EcsEntity, portal: EcsEntity) -> Option<PortalId> {
+    let positions = ecs.read_storage::<Pos>();
+    let in_reach = positions
+        .get(entity)
+        .zip(positions.get(portal))
+        .map_or(false, |(pos, portal_pos)| {
+            pos.0.distance_squared(portal_pos.0) < MAX_PICKUP_RANGE.powi(2)
+        });
+    if !in_reach || !ecs.read_storage::<comp::Player>().contains(entity) {
+        return None;
+    }
+    ecs.read_storage::<comp::Portal>()
+        .get(portal)
+        .map(|portal| portal.id)
+}
+
+/// Touching a portal lets the player travel to it from the portals linked to it
+pub fn handle_activate_portal(server: &mut Server, entity: EcsEntity, portal: EcsEntity) {
+    let ecs = server.state.ecs();
+    if let Some(id) = portal_in_reach(ecs, entity, portal) {
+        let network = ecs.read_resource::<PortalNetwork>();
+        let mut discovered = ecs
+            .read_storage::<DiscoveredPortals>()
+            .get(entity)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(info) = network.info(id) {
+            discovered.0.insert(id, info);
+        }
+        network.refresh(&mut discovered);
+        let _ = ecs
+            .write_storage::<DiscoveredPortals>()
+            .insert(entity, discovered);
+    }
+}
+
+/// Players wait next to the portal for a while before being teleported (see
+/// `sys::portal`), meanwhile the destination gets loaded
+pub fn handle_use_portal(
+    server: &mut Server,
+    entity: EcsEntity,
+    portal: EcsEntity,
+    destination: PortalId,
+) {
+    let to_load = {
+        let state = server.state();
+        let ecs = state.ecs();
+        let from = match portal_in_reach(ecs, entity, portal) {
+            Some(from) => from,
+            None => return,
+        };
+        let network = ecs.read_resource::<PortalNetwork>();
+        let can_travel = ecs
+            .write_storage::<DiscoveredPortals>()
+            .get_mut(entity)
+            .map_or(false, |discovered| {
+                network.refresh(discovered);
+                discovered.destinations(from).contains(&destination)
+            });
+        let (node, pos) = match (
+            network.get(destination),
+            ecs.read_storage::<Pos>().get(entity).copied(),
+        ) {
+            (Some(node), Some(pos)) if can_travel => (node, pos),
+            _ => return,
+        };
+
+        let destination_chunk = state.terrain().pos_key(node.pos.map(|e| e.floor() as i32));
+        let time = ecs.read_resource::<Time>().0;
+        let _ = ecs
+            .write_storage::<PortalChannel>()
+            .insert(entity, PortalChannel {
+                destination,
+                destination_chunk,
+                start_pos: pos.0,
+                end_time: time + PORTAL_CHANNEL_TIME,
+            });
+        if let Some(client) = ecs.read_storage::<Client>().get(entity) {
+            client.send_fallible(ServerGeneral::Notification(Notification::Travelling(
+                node.name.clone(),
+            )));
+        }
+        state
+            .terrain()
+            .get_key(destination_chunk)
+            .is_none()
+            .then_some(destination_chunk)
+    };
+    if let Some(key) = to_load {
+        server.generate_chunk(entity, key);
+    }
+}
+
+/// Actions on minigames are validated against the rules of the game, the
+/// players being told why the ones which aren't allowed were refused
+pub fn handle_minigame(
+    server: &mut Server,
+    entity: EcsEntity,
+    minigame: EcsEntity,
+    action: MinigameAction,
+) {
+    let ecs = server.state.ecs();
+    let positions = ecs.read_storage::<Pos>();
+    let in_reach = |entity| {
+        positions
+            .get(entity)
+            .zip(positions.get(minigame))
+            .map_or(false, |(pos, minigame_pos)| {
+                pos.0.distance_squared(minigame_pos.0) < MAX_PICKUP_RANGE.powi(2)
+            })
+    };
+    // Leaving is always allowed, so that players walking away free their seat
+    if !in_reach(entity) && action != MinigameAction::Leave {
+        return;
+    }
+    let uid = match ecs.read_storage::<Uid>().get(entity) {
+        Some(uid) if ecs.read_storage::<comp::Player>().contains(entity) => *uid,
+        _ => return,
+    };
+    let mut minigames = ecs.write_storage::<Minigame>();
+    let minigame = match minigames.get_mut(minigame) {
+        Some(minigame) => minigame,
+        None => return,
+    };
+    if action == MinigameAction::Join {
+        // The seats of the players who went away without leaving are freed for
+        // the newcomers
+        let uid_allocator = ecs.read_resource::<UidAllocator>();
+        let gone = minigame
+            .seats
+            .iter()
+            .flatten()
+            .filter(|seated| {
+                uid_allocator
+                    .retrieve_entity_internal(seated.0)
+                    .map_or(true, |seated| !in_reach(seated))
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        for seated in gone {
+            let _ = minigame.act(seated, MinigameAction::Leave);
+        }
+    }
+    let result = minigame.act(uid, action);
+    if let (Err(err), Some(client)) = (result, ecs.read_storage::<Client>().get(entity)) {
+        client.send_fallible(ServerGeneral::server_msg(
+            comp::ChatType::Meta,
+            err.to_string(),
+        ));
+    }
+}
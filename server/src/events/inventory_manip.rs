@@ -1,28 +1,49 @@
+use censor::Censor;
 use rand::Rng;
 use specs::{join::Join, world::WorldExt, Builder, Entity as EcsEntity, WriteStorage};
+use std::sync::Arc;
 use tracing::{debug, error, warn};
 use vek::{Rgb, Vec3};
 
 use common::{
+    blueprint::Blueprint,
+    building,
     comp::{
         self,
         group::members,
-        item::{self, tool::AbilityMap, MaterialStatManifest},
+        item::{
+            self,
+            tool::{AbilityMap, ToolKind},
+            MaterialStatManifest,
+        },
         slot::{self, Slot},
     },
     consts::MAX_PICKUP_RANGE,
+    farming::{self, Crop},
     recipe::{self, default_component_recipe_book, default_recipe_book},
-    terrain::SpriteKind,
+    resources::TimeOfDay,
+    terrain::{Block, BlockKind, SpriteKind},
     trade::Trades,
     uid::Uid,
     util::find_dist::{self, FindDist},
     vol::ReadVol,
+    writing::{self, TextError},
 };
 use common_net::sync::WorldSyncExt;
-use common_state::State;
+use common_state::{BuildAreas, State};
 use comp::LightEmitter;
 
-use crate::{client::Client, Server, StateExt};
+use super::quest::grant_general_exp;
+#[cfg(feature = "persistent_world")]
+use crate::TerrainPersistence;
+use crate::{
+    audit::{self, AuditEvent},
+    client::Client,
+    farming::{Farms, PlantedCrop},
+    placed_blocks::PlacedBlocks,
+    signs::{self, SignText, Signs},
+    Server, StateExt,
+};
 use common::{
     comp::{
         pet::is_tameable, Alignment, Body, ChatType, CollectFailedReason, Group,
@@ -47,8 +68,22 @@ pub fn snuff_lantern(storage: &mut WriteStorage<LightEmitter>, entity: EcsEntity
     storage.remove(entity);
 }
 
+/// Experience given to the general skill group for each crafted item
+const CRAFT_EXP_PER_ITEM: u32 = 2;
+/// Cap on the experience given for a single craft, so crafting a large batch of
+/// cheap items isn't the fastest way to level up
+const MAX_CRAFT_EXP: u32 = 20;
+
+fn grant_craft_exp(state: &State, entity: EcsEntity, crafted_items: usize) {
+    let exp = (crafted_items as u32)
+        .saturating_mul(CRAFT_EXP_PER_ITEM)
+        .min(MAX_CRAFT_EXP);
+    grant_general_exp(state, entity, exp);
+}
+
 #[allow(clippy::blocks_in_if_conditions)]
 pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::InventoryManip) {
+    let survival_building = server.settings().gameplay.survival_building;
     let state = server.state_mut();
 
     let uid = if let Some(uid) = state.ecs().uid_from_entity(entity) {
@@ -307,6 +342,399 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                     .build();
             }
         },
+        comp::InventoryManip::Till(pos) => {
+            let wields_hoe = inventory
+                .equipped(slot::EquipSlot::ActiveMainhand)
+                .map_or(false, |item| {
+                    matches!(&*item.kind(), item::ItemKind::Tool(tool) if tool.kind == ToolKind::Farming)
+                });
+            drop(inventories);
+            let above_pos = pos + Vec3::unit_z();
+            let blocks = {
+                let terrain = state.terrain();
+                terrain
+                    .get(pos)
+                    .ok()
+                    .copied()
+                    .zip(terrain.get(above_pos).ok().copied())
+            };
+            match blocks {
+                Some((block, above))
+                    if wields_hoe
+                        && within_block_range(state, entity, pos)
+                        && farming::can_till(block, above)
+                        && state.can_set_block(pos) =>
+                {
+                    if let Err(reason) =
+                        check_build_permission(state, entity, pos, survival_building)
+                    {
+                        notify_refused(state, entity, reason);
+                        return;
+                    }
+                    let farmland = Block::new(BlockKind::Farmland, farming::FARMLAND_COLOR);
+                    set_built_block(state, entity, pos, block, farmland);
+                    if above.get_sprite() != Some(SpriteKind::Empty)
+                        && state.can_set_block(above_pos)
+                    {
+                        set_built_block(state, entity, above_pos, above, above.into_vacant());
+                    }
+                },
+                _ => debug!("Can't till the block at pos={}", pos),
+            }
+        },
+        comp::InventoryManip::Plant(slot, pos) => {
+            let crop = inventory
+                .get(slot)
+                .and_then(|item| Crop::from_seed(item.persistence_item_id()));
+            let above_pos = pos + Vec3::unit_z();
+            let above = {
+                let terrain = state.terrain();
+                terrain
+                    .get(pos)
+                    .ok()
+                    .zip(terrain.get(above_pos).ok())
+                    .filter(|(block, above)| farming::can_plant(**block, **above))
+                    .map(|(_, above)| *above)
+            };
+            let can_plant =
+                within_block_range(state, entity, pos) && state.can_set_block(above_pos);
+            match (crop, above) {
+                (Some(crop), Some(above)) if can_plant => {
+                    if let Err(reason) =
+                        check_build_permission(state, entity, above_pos, survival_building)
+                    {
+                        drop(inventories);
+                        notify_refused(state, entity, reason);
+                        return;
+                    }
+                    inventory.take(
+                        slot,
+                        &state.ecs().read_resource::<AbilityMap>(),
+                        &state.ecs().read_resource::<MaterialStatManifest>(),
+                    );
+                    drop(inventories);
+                    state
+                        .ecs()
+                        .write_storage()
+                        .insert(
+                            entity,
+                            comp::InventoryUpdate::new(InventoryUpdateEvent::Used),
+                        )
+                        .expect("We know entity exists since we got its inventory.");
+                    set_built_block(
+                        state,
+                        entity,
+                        above_pos,
+                        above,
+                        Block::air(SpriteKind::Seedling),
+                    );
+                    let planted = PlantedCrop {
+                        crop,
+                        planted_at: state.ecs().read_resource::<TimeOfDay>().0,
+                    };
+                    let chunk_key = state.terrain().pos_key(above_pos);
+                    state
+                        .ecs()
+                        .write_resource::<Farms>()
+                        .plant(chunk_key, above_pos, planted);
+                },
+                _ => debug!("Can't plant the item in slot {:?} at pos={}", slot, pos),
+            }
+        },
+        comp::InventoryManip::PlaceMaterial(slot, pos) => {
+            let material = inventory
+                .get(slot)
+                .and_then(|item| building::material_of_item(item.persistence_item_id()));
+            let old_block = state.terrain().get(pos).ok().copied();
+            let placeable = old_block.map_or(false, building::can_place_in)
+                && has_solid_neighbour(state, pos)
+                && state.can_set_block(pos);
+            match (material, old_block) {
+                (Some(material), Some(old_block)) if placeable => {
+                    if let Err(reason) =
+                        check_build_permission(state, entity, pos, survival_building)
+                    {
+                        drop(inventories);
+                        notify_refused(state, entity, reason);
+                        return;
+                    }
+                    if is_occupied(state, pos) {
+                        drop(inventories);
+                        notify_refused(state, entity, "Something is in the way");
+                        return;
+                    }
+                    inventory.take(
+                        slot,
+                        &state.ecs().read_resource::<AbilityMap>(),
+                        &state.ecs().read_resource::<MaterialStatManifest>(),
+                    );
+                    drop(inventories);
+                    set_built_block(state, entity, pos, old_block, material.block());
+                    let chunk_key = state.terrain().pos_key(pos);
+                    state
+                        .ecs()
+                        .write_resource::<PlacedBlocks>()
+                        .place(chunk_key, pos, material);
+                },
+                _ => debug!("Can't place the item in slot {:?} at pos={}", slot, pos),
+            }
+        },
+        comp::InventoryManip::RemoveMaterial(pos) => {
+            let old_block = state.terrain().get(pos).ok().copied();
+            let chunk_key = state.terrain().pos_key(pos);
+            // Only the blocks placed by players give their material back
+            let material = state
+                .ecs()
+                .read_resource::<PlacedBlocks>()
+                .material_at(chunk_key, pos)
+                .filter(|material| old_block == Some(material.block()));
+            match (material, old_block) {
+                (Some(material), Some(old_block)) if state.can_set_block(pos) => {
+                    if let Err(reason) =
+                        check_build_permission(state, entity, pos, survival_building)
+                    {
+                        drop(inventories);
+                        notify_refused(state, entity, reason);
+                        return;
+                    }
+                    let item = match comp::Item::new_from_asset(material.item) {
+                        Ok(item) => item,
+                        Err(err) => {
+                            error!(?err, "Failed to load building material {}", material.item);
+                            return;
+                        },
+                    };
+                    if inventory.push(item).is_err() {
+                        drop(inventories);
+                        notify_refused(state, entity, "Your inventory is full");
+                        return;
+                    }
+                    drop(inventories);
+                    set_built_block(state, entity, pos, old_block, old_block.into_vacant());
+                    state
+                        .ecs()
+                        .write_resource::<PlacedBlocks>()
+                        .remove(chunk_key, pos);
+                },
+                _ => debug!("Can't take back a building material at pos={}", pos),
+            }
+        },
+        comp::InventoryManip::PlaceBlueprint(slot, pos) => {
+            let blueprint = match inventory
+                .get(slot)
+                .and_then(|item| Blueprint::of_item(item.persistence_item_id()))
+            {
+                Some(blueprint) => blueprint.cloned(),
+                None => {
+                    debug!("Can't build the item in slot {:?} at pos={}", slot, pos);
+                    return;
+                },
+            };
+            if let Err(reason) = check_build_permission(state, entity, pos, survival_building) {
+                drop(inventories);
+                notify_refused(state, entity, reason);
+                return;
+            }
+            let blocks = blueprint.blocks();
+            let placeable = {
+                let terrain = state.terrain();
+                blocks.iter().all(|(offset, _)| {
+                    terrain
+                        .get(pos + offset)
+                        .map_or(false, |block| building::can_place_in(*block))
+                        && state.can_set_block(pos + offset)
+                })
+            };
+            let reason = if !placeable {
+                Some("There is no room to build it there")
+            } else if blocks
+                .iter()
+                .any(|(offset, block)| block.is_solid() && is_occupied(state, pos + offset))
+            {
+                Some("Something is in the way")
+            } else if blocks
+                .iter()
+                .any(|(offset, _)| is_protected(state, entity, pos + offset))
+            {
+                Some("This area is protected")
+            } else if !blueprint.has_materials(inventory) {
+                Some("You don't have the materials to build it")
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                drop(inventories);
+                notify_refused(state, entity, reason);
+                return;
+            }
+            for (item, amount) in blueprint.materials.iter() {
+                for _ in 0..*amount {
+                    let slot = inventory.slots_with_id().find_map(|(slot, slot_item)| {
+                        slot_item
+                            .as_ref()
+                            .filter(|slot_item| slot_item.persistence_item_id() == *item)
+                            .map(|_| slot)
+                    });
+                    if let Some(slot) = slot {
+                        inventory.take(
+                            slot,
+                            &state.ecs().read_resource::<AbilityMap>(),
+                            &state.ecs().read_resource::<MaterialStatManifest>(),
+                        );
+                    }
+                }
+            }
+            drop(inventories);
+            let changes = {
+                let terrain = state.terrain();
+                blocks
+                    .into_iter()
+                    .filter_map(|(offset, block)| {
+                        let old_block = terrain.get(pos + offset).ok().copied()?;
+                        Some((pos + offset, old_block, block))
+                    })
+                    .collect()
+            };
+            set_built_blocks(state, entity, changes);
+        },
+        comp::InventoryManip::PlaceSign(slot, pos, text) => {
+            let is_sign = inventory.get(slot).map_or(false, |item| {
+                item.persistence_item_id() == writing::SIGN_ITEM
+            });
+            let old_block = {
+                let terrain = state.terrain();
+                terrain
+                    .get(pos)
+                    .ok()
+                    .copied()
+                    .filter(|block| building::can_place_in(*block))
+                    .filter(|_| {
+                        terrain
+                            .get(pos - Vec3::unit_z())
+                            .map_or(false, |below| below.is_filled())
+                    })
+            };
+            let author = state
+                .ecs()
+                .read_storage::<Player>()
+                .get(entity)
+                .map(|player| player.uuid());
+            match (old_block, author) {
+                (Some(old_block), Some(author))
+                    if is_sign
+                        && within_block_range(state, entity, pos)
+                        && state.can_set_block(pos) =>
+                {
+                    if let Err(reason) =
+                        check_build_permission(state, entity, pos, survival_building)
+                    {
+                        drop(inventories);
+                        notify_refused(state, entity, reason);
+                        return;
+                    }
+                    let text = match writable_text(state, &text, writing::MAX_SIGN_LEN, false) {
+                        Ok(text) => text,
+                        Err(reason) => {
+                            drop(inventories);
+                            notify_refused(state, entity, reason);
+                            return;
+                        },
+                    };
+                    inventory.take(
+                        slot,
+                        &state.ecs().read_resource::<AbilityMap>(),
+                        &state.ecs().read_resource::<MaterialStatManifest>(),
+                    );
+                    drop(inventories);
+                    let sign = old_block.with_sprite(SpriteKind::Sign);
+                    let sign = sign.with_ori(facing_ori(state, entity)).unwrap_or(sign);
+                    set_built_block(state, entity, pos, old_block, sign);
+                    write_sign(state, pos, SignText { text, author });
+                },
+                _ => debug!("Can't put up the item in slot {:?} at pos={}", slot, pos),
+            }
+        },
+        comp::InventoryManip::WriteSign(pos, text) => {
+            drop(inventories);
+            let is_sign = state
+                .terrain()
+                .get(pos)
+                .ok()
+                .and_then(|block| block.get_sprite())
+                == Some(SpriteKind::Sign);
+            let author = state
+                .ecs()
+                .read_storage::<Player>()
+                .get(entity)
+                .map(|player| player.uuid());
+            let chunk_key = state.terrain().pos_key(pos);
+            // Signs which weren't put up by a player can be written by anyone allowed to
+            // build there
+            let permitted = state
+                .ecs()
+                .read_resource::<Signs>()
+                .get(chunk_key, pos)
+                .map_or_else(
+                    || !is_protected(state, entity, pos),
+                    |sign| Some(sign.author) == author,
+                );
+            match author {
+                Some(author) if is_sign && within_block_range(state, entity, pos) => {
+                    if !permitted {
+                        notify_refused(state, entity, "You aren't allowed to rewrite this sign");
+                        return;
+                    }
+                    match writable_text(state, &text, writing::MAX_SIGN_LEN, false) {
+                        Ok(text) => write_sign(state, pos, SignText { text, author }),
+                        Err(reason) => notify_refused(state, entity, reason),
+                    }
+                },
+                _ => debug!("Can't write on a sign at pos={}", pos),
+            }
+        },
+        comp::InventoryManip::WriteBook(slot, text) => {
+            let is_book = inventory.get(slot).map_or(false, |item| {
+                item.persistence_item_id() == writing::BOOK_ITEM
+            });
+            if !is_book {
+                debug!("Can't write in the item in slot {:?}", slot);
+                return;
+            }
+            let text = match writable_text(state, &text, writing::MAX_BOOK_LEN, true) {
+                Ok(text) => text,
+                Err(reason) => {
+                    drop(inventories);
+                    notify_refused(state, entity, reason);
+                    return;
+                },
+            };
+            // A single book of the stack is written in, which then no longer stacks
+            // with the others
+            if let Some(mut book) = inventory.take(
+                slot,
+                &state.ecs().read_resource::<AbilityMap>(),
+                &state.ecs().read_resource::<MaterialStatManifest>(),
+            ) {
+                book.set_text(Some(text));
+                // The book stays in its slot, unless the rest of the stack is left there
+                let overflow = if inventory.get(slot).is_none() {
+                    inventory.insert_at(slot, book).err()
+                } else {
+                    inventory.push(book).err()
+                };
+                drop(inventories);
+                if let Some(book) = overflow {
+                    let pos = state.ecs().read_storage::<comp::Pos>().get(entity).copied();
+                    if let Some(pos) = pos {
+                        state
+                            .create_item_drop(Default::default(), book)
+                            .with(comp::Pos(pos.0 + Vec3::unit_z() * 0.25))
+                            .with(comp::Vel(Vec3::zero()))
+                            .build();
+                    }
+                }
+            }
+        },
         comp::InventoryManip::Use(slot) => {
             let mut maybe_effect = None;
 
@@ -542,26 +970,31 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
             // into another, ensure that they are the same type of item. If they are
             // the same type do nothing, as you don't want to overwrite the existing item.
 
-            if let (Slot::Inventory(source_inv_slot_id), Slot::Inventory(target_inv_slot_id)) =
-                (slot, target)
+            // Stacks can only be split between inventory slots, anything else would take
+            // half of the stack without having anywhere to put it.
+            let (source, target) = match (slot, target) {
+                (Slot::Inventory(source), Slot::Inventory(target)) => (source, target),
+                _ => return,
+            };
+
+            if let (Some(source_item), Some(target_item)) =
+                (inventory.get(source), inventory.get(target))
             {
-                if let Some(source_item) = inventory.get(source_inv_slot_id) {
-                    if let Some(target_item) = inventory.get(target_inv_slot_id) {
-                        if source_item != target_item {
-                            return;
-                        }
-                    }
+                if source_item != target_item {
+                    return;
                 }
             }
 
-            let item = match slot {
-                Slot::Inventory(slot) => inventory.take_half(slot, &ability_map, &msm),
-                Slot::Equip(_) => None,
-            };
-
-            if let Some(item) = item {
-                if let Slot::Inventory(target) = target {
-                    inventory.insert_or_stack_at(target, item).ok();
+            if let Some(item) = inventory.take_half(source, &ability_map, &msm) {
+                // Put back whatever could not be stacked into the target slot
+                let leftover = match inventory.insert_or_stack_at(target, item) {
+                    Ok(leftover) => leftover,
+                    Err(item) => Some(item),
+                };
+                if let Some(leftover) = leftover {
+                    if let Err(leftover) = inventory.insert_or_stack_at(source, leftover) {
+                        warn!(?leftover, "Failed to return split stack to its source slot");
+                    }
                 }
             }
             drop(msm);
@@ -646,6 +1079,8 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
             let recipe_book = default_recipe_book().read();
             let ability_map = &state.ecs().read_resource::<AbilityMap>();
             let msm = state.ecs().read_resource::<MaterialStatManifest>();
+            // Salvaging only takes items apart, so it doesn't teach anything
+            let grants_exp = !matches!(craft_event, CraftEvent::Salvage(_));
 
             let get_craft_sprite = |state, sprite_pos: Option<Vec3<i32>>| {
                 sprite_pos
@@ -776,6 +1211,7 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
 
             // Attempt to insert items into inventory, dropping them if there is not enough
             // space
+            let crafted_count = crafted_items.as_ref().map_or(0, Vec::len);
             let items_were_crafted = if let Some(crafted_items) = crafted_items {
                 for item in crafted_items {
                     if let Err(item) = inventory.push(item) {
@@ -803,6 +1239,9 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                     entity,
                     comp::InventoryUpdate::new(InventoryUpdateEvent::Craft),
                 );
+                if grants_exp {
+                    grant_craft_exp(state, entity, crafted_count);
+                }
             }
         },
         comp::InventoryManip::Sort => {
@@ -878,7 +1317,11 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                     });
             },
             item::Throwable::TrainingDummy => {
-                new_entity = new_entity.with(comp::Stats::new("Training Dummy".to_string()));
+                let body = comp::Body::Object(comp::object::Body::TrainingDummy);
+                new_entity = new_entity
+                    .with(comp::Stats::new("Training Dummy".to_string()))
+                    .with(comp::Health::new(body, 0))
+                    .with(comp::TrainingDummy::default());
             },
         };
 
@@ -892,6 +1335,190 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
     }
 }
 
+/// Whether the player may place or remove a block at the position, outside of
+/// build mode
+fn check_build_permission(
+    state: &State,
+    entity: EcsEntity,
+    pos: Vec3<i32>,
+    survival_building: bool,
+) -> Result<(), &'static str> {
+    if !survival_building {
+        return Err("Building is disabled on this server");
+    }
+    let in_range =
+        state
+            .ecs()
+            .read_storage::<comp::Pos>()
+            .get(entity)
+            .map_or(false, |entity_pos| {
+                entity_pos.0.distance_squared(pos.as_() + 0.5) < building::MAX_BUILD_RANGE.powi(2)
+            });
+    if !in_range {
+        return Err("You are too far away to build there");
+    }
+    if is_protected(state, entity, pos) {
+        Err("This area is protected")
+    } else {
+        Ok(())
+    }
+}
+
+/// Whether an entity stands where a block would be placed at the position
+fn is_occupied(state: &State, pos: Vec3<i32>) -> bool {
+    let ecs = state.ecs();
+    let positions = ecs.read_storage::<comp::Pos>();
+    let colliders = ecs.read_storage::<comp::Collider>();
+    let scales = ecs.read_storage::<comp::Scale>();
+    let char_states = ecs.read_storage::<comp::CharacterState>();
+    // A bit smaller than the block, the entities only touching it aren't in the way
+    let block = find_dist::Cube {
+        min: pos.as_::<f32>() + 0.01,
+        side_length: 0.98,
+    };
+    for (entity_pos, collider, scale, char_state) in
+        (&positions, &colliders, scales.maybe(), char_states.maybe()).join()
+    {
+        let cylinder = find_dist::Cylinder::from_components(
+            entity_pos.0,
+            scale.copied(),
+            Some(collider),
+            char_state,
+        );
+        if block.approx_in_range(cylinder, 0.0) && block.min_distance(cylinder) <= 0.0 {
+            return true;
+        }
+    }
+    false
+}
+
+/// Build areas other than the whole world are protected from the players who
+/// weren't given access to them
+fn is_protected(state: &State, entity: EcsEntity, pos: Vec3<i32>) -> bool {
+    let build_areas = state.ecs().read_resource::<BuildAreas>();
+    let can_build = state.ecs().read_storage::<comp::CanBuild>();
+    let permitted = can_build.get(entity);
+    build_areas
+        .area_names()
+        .iter()
+        .filter(|(name, _)| name.as_str() != "world")
+        .any(|(_, id)| {
+            build_areas
+                .areas()
+                .get(*id)
+                .map_or(false, |area| area.contains_point(pos))
+                && !permitted.map_or(false, |cb| cb.build_areas.contains(id))
+        })
+}
+
+fn has_solid_neighbour(state: &State, pos: Vec3<i32>) -> bool {
+    let terrain = state.terrain();
+    [
+        Vec3::unit_x(),
+        -Vec3::unit_x(),
+        Vec3::unit_y(),
+        -Vec3::unit_y(),
+        Vec3::unit_z(),
+        -Vec3::unit_z(),
+    ]
+    .iter()
+    .any(|dir| {
+        terrain
+            .get(pos + dir)
+            .map_or(false, |block| block.is_filled())
+    })
+}
+
+fn notify_refused(state: &State, entity: EcsEntity, reason: &str) {
+    if let Some(client) = state.ecs().read_storage::<Client>().get(entity) {
+        client.send_fallible(ServerGeneral::server_msg(ChatType::CommandError, reason));
+    }
+}
+
+/// Changes a block built by a player, keeping track of it so that it can be
+/// rolled back
+fn set_built_block(state: &State, entity: EcsEntity, pos: Vec3<i32>, old: Block, new: Block) {
+    set_built_blocks(state, entity, vec![(pos, old, new)]);
+}
+
+/// Changes the blocks built at once by a player, like the ones of a blueprint,
+/// recording them all together
+fn set_built_blocks(state: &State, entity: EcsEntity, blocks: Vec<(Vec3<i32>, Block, Block)>) {
+    for (pos, _, new) in blocks.iter() {
+        state.set_block(*pos, *new);
+    }
+    #[cfg(feature = "persistent_world")]
+    if let Some(mut terrain_persistence) = state.ecs().try_fetch_mut::<TerrainPersistence>() {
+        for (pos, _, new) in blocks.iter() {
+            terrain_persistence.set_block(*pos, *new);
+        }
+    }
+    audit::record_all(
+        state.ecs(),
+        entity,
+        blocks
+            .into_iter()
+            .map(|(pos, old, new)| AuditEvent::TerrainEdit { pos, old, new }),
+    );
+}
+
+/// Sanitises the text written by a player, refusing it if it is blank, too
+/// long or contains banned words
+fn writable_text(
+    state: &State,
+    text: &str,
+    max_len: usize,
+    multiline: bool,
+) -> Result<String, &'static str> {
+    let text = writing::sanitize(text, max_len, multiline).map_err(|err| match err {
+        TextError::Empty => "Nothing was written",
+        TextError::TooLong => "The text is too long",
+    })?;
+    if state.ecs().read_resource::<Arc<Censor>>().check(&text) {
+        Err("The text contains banned words")
+    } else {
+        Ok(text)
+    }
+}
+
+/// Stores the text of the sign and shows it to the players around
+fn write_sign(state: &State, pos: Vec3<i32>, sign: SignText) {
+    let chunk_key = state.terrain().pos_key(pos);
+    let text = sign.text.clone();
+    let ecs = state.ecs();
+    ecs.write_resource::<Signs>().write(chunk_key, pos, sign);
+    signs::send_texts(
+        &state.terrain(),
+        &ecs.read_storage(),
+        &ecs.read_storage(),
+        &ecs.read_storage(),
+        &[(pos, text)],
+    );
+}
+
+/// Orientation of a sprite put up by the entity, facing it
+fn facing_ori(state: &State, entity: EcsEntity) -> u8 {
+    state
+        .ecs()
+        .read_storage::<comp::Ori>()
+        .get(entity)
+        .map_or(0, |ori| {
+            let dir = -ori.look_vec();
+            ((dir.y.atan2(dir.x) / (std::f32::consts::PI * 0.25)).round() as i32).rem_euclid(8)
+                as u8
+        })
+}
+
+fn within_block_range(state: &State, entity: EcsEntity, pos: Vec3<i32>) -> bool {
+    state
+        .ecs()
+        .read_storage::<comp::Pos>()
+        .get(entity)
+        .map_or(false, |entity_pos| {
+            entity_pos.0.distance_squared(pos.as_() + 0.5) < MAX_PICKUP_RANGE.powi(2)
+        })
+}
+
 fn within_pickup_range<S: FindDist<find_dist::Cylinder>>(
     entity_cylinder: Option<find_dist::Cylinder>,
     shape_fn: impl FnOnce() -> Option<S>,
@@ -1,5 +1,10 @@
 use super::group_manip::{self, update_map_markers};
-use crate::{client::Client, Server};
+use crate::{
+    client::Client,
+    guild::{self, GuildChange, GuildError, Guilds},
+    state_ext::StateExt,
+    Server,
+};
 use common::{
     comp::{
         self,
@@ -78,6 +83,13 @@ pub fn handle_invite(server: &mut Server, inviter: Entity, invitee_uid: Uid, kin
         ) {
             return;
         }
+    } else if let InviteKind::Guild = kind {
+        if let Err(err) = can_invite_to_guild(state.ecs(), inviter, invitee) {
+            if let Some(client) = clients.get(inviter) {
+                client.send_fallible(ServerGeneral::server_msg(ChatType::Meta, err));
+            }
+            return;
+        }
     } else {
         // cancel current trades for inviter before inviting someone else to trade
         let mut trades = state.ecs().write_resource::<Trades>();
@@ -194,6 +206,7 @@ pub fn handle_invite_accept(server: &mut Server, entity: Entity) {
         match kind {
             InviteKind::Group => {
                 let map_markers = state.ecs().read_storage::<comp::MapMarker>();
+                let shared_map_markers = state.ecs().read_storage::<comp::SharedMapMarkers>();
                 let mut group_manager = state.ecs().write_resource::<GroupManager>();
                 group_manager.add_group_member(
                     inviter,
@@ -211,12 +224,53 @@ pub fn handle_invite_accept(server: &mut Server, entity: Entity) {
                                     .map(|g| (g, c))
                             })
                             .map(|(g, c)| {
-                                update_map_markers(&map_markers, &uids, c, &group_change);
+                                update_map_markers(
+                                    &map_markers,
+                                    &shared_map_markers,
+                                    &uids,
+                                    c,
+                                    &group_change,
+                                );
                                 c.send_fallible(ServerGeneral::GroupUpdate(g));
                             });
                     },
                 );
             },
+            InviteKind::Guild => {
+                let (Some(inviter_char), Some(invitee_char)) =
+                    (guild::character_of(state.ecs(), inviter), guild::character_of(state.ecs(), entity))
+                else {
+                    return;
+                };
+                let name = state
+                    .ecs()
+                    .read_storage::<comp::Stats>()
+                    .get(entity)
+                    .map_or_else(String::new, |stats| stats.name.clone());
+                let joined = {
+                    let mut guilds = state.ecs().write_resource::<Guilds>();
+                    guilds
+                        .guild_of(inviter_char)
+                        .map(|guild| guild.name.clone())
+                        .and_then(|guild| {
+                            guilds
+                                .join(&guild, invitee_char, name.clone())
+                                .ok()
+                                .map(|_| guild)
+                        })
+                };
+                if let Some(guild) = joined {
+                    guild::update_guild(state.ecs(), GuildChange::Updated(guild.clone()));
+                    state.send_chat(
+                        ChatType::GuildMeta(guild).chat_msg(format!("{} joined the guild.", name)),
+                    );
+                } else if let Some(client) = clients.get(entity) {
+                    client.send_fallible(ServerGeneral::server_msg(
+                        ChatType::Meta,
+                        "Could not join the guild.",
+                    ));
+                }
+            },
             InviteKind::Trade => {
                 if let (Some(inviter_uid), Some(invitee_uid)) =
                     (uids.get(inviter).copied(), uids.get(entity).copied())
@@ -341,6 +395,22 @@ pub fn handle_invite_decline(server: &mut Server, entity: Entity) {
     }
 }
 
+/// Only officers can invite players who aren't in a guild yet
+fn can_invite_to_guild(ecs: &specs::World, inviter: Entity, invitee: Entity) -> Result<(), String> {
+    let guilds = ecs.read_resource::<Guilds>();
+    let rank = guild::character_of(ecs, inviter)
+        .and_then(|character_id| guilds.rank_of(character_id))
+        .ok_or_else(|| GuildError::NotInGuild.to_string())?;
+    if !rank.can_invite() {
+        return Err(GuildError::NotAllowed.to_string());
+    }
+    match guild::character_of(ecs, invitee) {
+        Some(character_id) if guilds.guild_of(character_id).is_none() => Ok(()),
+        Some(_) => Err("This player already is in a guild.".into()),
+        None => Err("Invite failed, target is not playing a character.".into()),
+    }
+}
+
 fn within_trading_range(requester_position: Option<&Pos>, invitee_position: Option<&Pos>) -> bool {
     match (requester_position, invitee_position) {
         (Some(rpos), Some(ipos)) => rpos.0.distance_squared(ipos.0) < MAX_TRADE_RANGE.powi(2),
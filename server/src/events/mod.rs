@@ -5,26 +5,30 @@ use crate::{
 use common::event::{EventBus, ServerEvent, ServerEventDiscriminants};
 use common_base::span;
 use entity_creation::{
-    handle_beam, handle_create_npc, handle_create_ship, handle_create_waypoint,
-    handle_initialize_character, handle_initialize_spectator, handle_loaded_character_data,
-    handle_shockwave, handle_shoot,
+    handle_beam, handle_create_minigame, handle_create_npc, handle_create_ship,
+    handle_create_waypoint, handle_initialize_character, handle_initialize_spectator,
+    handle_loaded_character_data, handle_shockwave, handle_shoot,
 };
 use entity_manipulation::{
     handle_aura, handle_bonk, handle_buff, handle_change_ability, handle_combo_change,
     handle_delete, handle_destroy, handle_energy_change, handle_entity_attacked_hook,
     handle_explosion, handle_health_change, handle_knockback, handle_land_on_ground, handle_parry,
-    handle_poise, handle_respawn, handle_teleport_to, handle_update_map_marker,
+    handle_poise, handle_respawn, handle_share_map_markers, handle_teleport_to,
+    handle_update_map_marker,
 };
 use group_manip::handle_group;
 use information::handle_site_info;
 use interaction::{
-    handle_create_sprite, handle_lantern, handle_mine_block, handle_mount, handle_npc_interaction,
-    handle_sound, handle_unmount,
+    handle_activate_portal, handle_create_sprite, handle_lantern, handle_mine_block,
+    handle_minigame, handle_mount, handle_npc_interaction, handle_npc_talk, handle_sleep,
+    handle_sound, handle_unmount, handle_use_portal,
 };
 use inventory_manip::handle_inventory;
 use invite::{handle_invite, handle_invite_response};
-use player::{handle_client_disconnect, handle_exit_ingame, handle_possess};
+use player::{handle_client_disconnect, handle_exit_ingame, handle_possess, handle_resume_session};
+use quest::{handle_complete_quest, handle_offer_quest};
 use specs::{Builder, Entity as EcsEntity, WorldExt};
+use std::time::Duration;
 use trade::handle_process_trade_action;
 
 pub use group_manip::update_map_markers;
@@ -38,6 +42,7 @@ mod interaction;
 mod inventory_manip;
 mod invite;
 mod player;
+mod quest;
 mod trade;
 
 pub enum Event {
@@ -51,6 +56,12 @@ pub enum Event {
         entity: Option<EcsEntity>,
         msg: String,
     },
+    /// A scheduled restart, for the frontend to shut the server down after
+    /// the countdown
+    Restart {
+        countdown: Duration,
+        message: String,
+    },
 }
 
 impl Server {
@@ -123,6 +134,9 @@ impl Server {
                 ServerEvent::NpcInteract(interactor, target) => {
                     handle_npc_interaction(self, interactor, target)
                 },
+                ServerEvent::NpcTalk(interactor, target, choice) => {
+                    handle_npc_talk(self, interactor, target, choice)
+                },
                 ServerEvent::InitiateInvite(interactor, target, kind) => {
                     handle_invite(self, interactor, target, kind)
                 },
@@ -160,6 +174,8 @@ impl Server {
                         pets,
                         active_abilities,
                         map_marker,
+                        quest_log,
+                        reputation,
                     ) = components;
                     let components = PersistedComponents {
                         body,
@@ -170,6 +186,8 @@ impl Server {
                         pets,
                         active_abilities,
                         map_marker,
+                        quest_log,
+                        reputation,
                     };
                     handle_loaded_character_data(self, entity, components);
                 },
@@ -216,6 +234,7 @@ impl Server {
                     rtsim_entity,
                 } => handle_create_ship(self, pos, ship, mountable, agent, rtsim_entity),
                 ServerEvent::CreateWaypoint(pos) => handle_create_waypoint(self, pos),
+                ServerEvent::CreateMinigame(pos) => handle_create_minigame(self, pos),
                 ServerEvent::ClientDisconnect(entity, reason) => {
                     frontend_events.push(handle_client_disconnect(self, entity, reason, false))
                 },
@@ -227,6 +246,10 @@ impl Server {
                         true,
                     ))
                 },
+                ServerEvent::ResumeSession {
+                    entity,
+                    session_entity,
+                } => handle_resume_session(self, entity, session_entity),
                 ServerEvent::Command(entity, name, args) => {
                     commands.push((entity, name, args));
                 },
@@ -283,6 +306,27 @@ impl Server {
                 ServerEvent::UpdateMapMarker { entity, update } => {
                     handle_update_map_marker(self, entity, update)
                 },
+                ServerEvent::ShareMapMarkers { entity, markers } => {
+                    handle_share_map_markers(self, entity, markers)
+                },
+                ServerEvent::OfferQuest { npc, target } => handle_offer_quest(self, npc, target),
+                ServerEvent::CompleteQuest { entity, quest } => {
+                    handle_complete_quest(self, entity, quest)
+                },
+                ServerEvent::Sleep { entity, bed_pos } => handle_sleep(self, entity, bed_pos),
+                ServerEvent::ActivatePortal { entity, portal } => {
+                    handle_activate_portal(self, entity, portal)
+                },
+                ServerEvent::UsePortal {
+                    entity,
+                    portal,
+                    destination,
+                } => handle_use_portal(self, entity, portal, destination),
+                ServerEvent::Minigame {
+                    entity,
+                    minigame,
+                    action,
+                } => handle_minigame(self, entity, minigame, action),
             }
         }
 
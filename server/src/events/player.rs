@@ -1,17 +1,21 @@
 use super::Event;
 use crate::{
     client::Client, metrics::PlayerMetrics, persistence::character_updater::CharacterUpdater,
-    presence::Presence, state_ext::StateExt, BattleModeBuffer, Server,
+    presence::Presence, session::Session, settings::Settings, state_ext::StateExt, sys,
+    BattleModeBuffer, Server,
 };
 use common::{
     comp,
     comp::{group, pet::is_tameable},
+    resources::Time,
     uid::{Uid, UidAllocator},
 };
 use common_base::span;
-use common_net::msg::{PlayerListUpdate, PresenceKind, ServerGeneral};
+use common_net::msg::{PlayerListUpdate, PresenceKind, ServerGeneral, ServerMsg};
 use common_state::State;
 use specs::{saveload::MarkerAllocator, Builder, Entity as EcsEntity, Join, WorldExt};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
 use tracing::{debug, error, trace, warn, Instrument};
 
 pub fn handle_exit_ingame(server: &mut Server, entity: EcsEntity) {
@@ -140,34 +144,18 @@ pub fn handle_client_disconnect(
             .with_label_values(&[get_reason_str(&reason)])
             .inc();
 
-        if let Some(participant) = client.participant.take() {
-            let pid = participant.remote_pid();
-            server.runtime.spawn(
-                async {
-                    let now = std::time::Instant::now();
-                    debug!("Start handle disconnect of client");
-                    if let Err(e) = participant.disconnect().await {
-                        debug!(
-                            ?e,
-                            "Error when disconnecting client, maybe the pipe already broke"
-                        );
-                    };
-                    trace!("finished disconnect");
-                    let elapsed = now.elapsed();
-                    if elapsed.as_millis() > 100 {
-                        warn!(?elapsed, "disconnecting took quite long");
-                    } else {
-                        debug!(?elapsed, "disconnecting took");
-                    }
-                }
-                .instrument(tracing::debug_span!(
-                    "client_disconnect",
-                    ?pid,
-                    ?entity,
-                    ?reason,
-                )),
-            );
-        }
+        disconnect_participant(&server.runtime, client, entity, reason);
+    }
+
+    // Keep the character of a player whose connection dropped in the world for a
+    // while, for their client to resume the session from a new connection
+    if matches!(
+        reason,
+        comp::DisconnectReason::NetworkError | comp::DisconnectReason::Timeout
+    ) && suspend_session(server, entity)
+    {
+        debug!(?entity, "Suspended session of disconnected client");
+        return Event::ClientDisconnected { entity };
     }
 
     let state = server.state_mut();
@@ -198,6 +186,109 @@ pub fn handle_client_disconnect(
     Event::ClientDisconnected { entity }
 }
 
+/// Closes the connection of the client in the background
+fn disconnect_participant(
+    runtime: &Runtime,
+    client: &mut Client,
+    entity: EcsEntity,
+    reason: comp::DisconnectReason,
+) {
+    if let Some(participant) = client.participant.take() {
+        let pid = participant.remote_pid();
+        runtime.spawn(
+            async {
+                let now = std::time::Instant::now();
+                debug!("Start handle disconnect of client");
+                if let Err(e) = participant.disconnect().await {
+                    debug!(
+                        ?e,
+                        "Error when disconnecting client, maybe the pipe already broke"
+                    );
+                };
+                trace!("finished disconnect");
+                let elapsed = now.elapsed();
+                if elapsed.as_millis() > 100 {
+                    warn!(?elapsed, "disconnecting took quite long");
+                } else {
+                    debug!(?elapsed, "disconnecting took");
+                }
+            }
+            .instrument(tracing::debug_span!(
+                "client_disconnect",
+                ?pid,
+                ?entity,
+                ?reason,
+            )),
+        );
+    }
+}
+
+/// Suspends the session of the player if they are in game and the server lets
+/// sessions be resumed, returns whether it did
+fn suspend_session(server: &mut Server, entity: EcsEntity) -> bool {
+    let ecs = server.state.ecs();
+    let grace = ecs.read_resource::<Settings>().session_grace;
+    let in_game = ecs
+        .read_storage::<Presence>()
+        .get(entity)
+        .map_or(false, |presence| {
+            matches!(presence.kind, PresenceKind::Character(_))
+        });
+    match ecs.write_storage::<Session>().get_mut(entity) {
+        Some(session)
+            if in_game
+                && !grace.is_zero()
+                && session.dropped_at.is_none()
+                && ecs.write_storage::<Client>().remove(entity).is_some() =>
+        {
+            session.dropped_at = Some(ecs.read_resource::<Time>().0);
+            true
+        },
+        _ => false,
+    }
+}
+
+pub fn handle_resume_session(server: &mut Server, entity: EcsEntity, session_entity: EcsEntity) {
+    span!(_guard, "handle_resume_session");
+    let runtime = Arc::clone(&server.runtime);
+    let state = server.state_mut();
+    let client = state.ecs().write_storage::<Client>().remove(entity);
+    // The client may resume its session before the server noticed that its last
+    // connection dropped, that stale connection is closed
+    if let Some(mut stale_client) = state.ecs().write_storage::<Client>().remove(session_entity) {
+        disconnect_participant(
+            &runtime,
+            &mut stale_client,
+            session_entity,
+            comp::DisconnectReason::NewerLogin,
+        );
+    }
+    // The entity the client registered in was only there for the login
+    if let Err(e) = state.delete_entity_recorded(entity) {
+        error!(?e, ?entity, "Failed to delete entity of resuming client");
+    }
+
+    let ecs = state.ecs();
+    let token = match (
+        client,
+        ecs.write_storage::<Session>().get_mut(session_entity),
+    ) {
+        (Some(client), Some(session)) => {
+            session.dropped_at = None;
+            client.send_fallible(ServerMsg::RegisterAnswer(Ok(())));
+            client.send_fallible(ServerGeneral::SessionStarted(session.token));
+            let _ = ecs.write_storage().insert(session_entity, client);
+            session.token
+        },
+        _ => return,
+    };
+    debug!(?session_entity, ?token, "Resumed session");
+
+    // Send the client everything around its character again, as it missed all the
+    // updates while its connection was down
+    sys::subscription::initialize_region_subscription(ecs, session_entity);
+}
+
 // When a player logs out, their data is queued for persistence in the next tick
 // of the persistence batch update. The player will be
 // temporarily unable to log in during this period to avoid
@@ -237,6 +328,18 @@ fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity {
                     .read_storage::<comp::MapMarker>()
                     .get(entity)
                     .cloned();
+                let quest_log = state
+                    .ecs()
+                    .read_storage::<comp::QuestLog>()
+                    .get(entity)
+                    .cloned()
+                    .unwrap_or_default();
+                let reputation = state
+                    .ecs()
+                    .read_storage::<comp::Reputation>()
+                    .get(entity)
+                    .cloned()
+                    .unwrap_or_default();
                 // Store last battle mode change
                 if let Some(change) = player_info.last_battlemode_change {
                     let mode = player_info.battle_mode;
@@ -264,6 +367,8 @@ fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity {
                     })
                     .collect();
 
+                crate::guild::notify_logout(state.ecs(), char_id);
+
                 character_updater.add_pending_logout_update(
                     char_id,
                     (
@@ -273,6 +378,8 @@ fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity {
                         waypoint,
                         active_abilities.clone(),
                         map_marker,
+                        quest_log,
+                        reputation,
                     ),
                 );
             },
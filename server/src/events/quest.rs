@@ -0,0 +1,204 @@
+use crate::{client::Client, Server, StateExt};
+use common::{
+    assets::{self, AssetExt},
+    comp::{
+        self,
+        inventory::item::ItemDefinitionId,
+        quest::{ActiveQuest, Objective, QuestDef},
+        skillset::SkillGroupKind,
+        Inventory, InventoryUpdateEvent, Item, QuestLog, Reputation, UnresolvedChatMsg,
+    },
+    event::EventBus,
+    outcome::Outcome,
+};
+use common_net::{
+    msg::{Notification, ServerGeneral},
+    sync::WorldSyncExt,
+};
+use common_state::State;
+use hashbrown::HashSet;
+use rand::seq::SliceRandom;
+use specs::{world::WorldExt, Builder, Entity as EcsEntity};
+use std::iter::FromIterator;
+use tracing::warn;
+
+/// Speech of the NPC when it has no quest left for the target
+const NO_WORK_MSG: &str = "npc-speech-villager_no_work";
+
+fn npc_say(state: &State, npc: EcsEntity, msg: String) {
+    if let Some(uid) = state.ecs().uid_from_entity(npc) {
+        state.send_chat(UnresolvedChatMsg::npc(uid, msg));
+    }
+}
+
+/// Give a random quest the target hasn't done yet
+pub fn handle_offer_quest(server: &mut Server, npc: EcsEntity, target: EcsEntity) {
+    let state = server.state_mut();
+    if state.ecs().read_storage::<Client>().get(target).is_none() {
+        return;
+    }
+
+    let quests = match assets::load_dir::<QuestDef>("common.quests", true) {
+        Ok(quests) => quests,
+        Err(err) => {
+            warn!(?err, "Failed to load quests");
+            return;
+        },
+    };
+
+    let offered = {
+        let mut quest_logs = state.ecs().write_storage::<QuestLog>();
+        let quest_log = match quest_logs.entry(target) {
+            Ok(entry) => entry.or_insert_with(QuestLog::default),
+            Err(_) => return,
+        };
+        let available = quests
+            .ids()
+            .filter(|id| quest_log.can_start(id))
+            .collect::<Vec<_>>();
+        available.choose(&mut rand::thread_rng()).map(|id| {
+            let def = QuestDef::load_expect_cloned(id);
+            quest_log.start(id.to_string(), &def);
+            (id.to_string(), def)
+        })
+    };
+
+    match offered {
+        Some((id, def)) => {
+            npc_say(state, npc, def.description);
+            if let Some(client) = state.ecs().read_storage::<Client>().get(target) {
+                client.send_fallible(ServerGeneral::Notification(Notification::QuestStarted(id)));
+            }
+        },
+        None => npc_say(state, npc, NO_WORK_MSG.to_owned()),
+    }
+}
+
+/// Remove up to `amount` items with the given definition id from the
+/// inventory
+fn take_items(inventory: &mut Inventory, item: &str, mut amount: u32) {
+    let slots = inventory
+        .slots_with_id()
+        .filter(|(_, slot)| {
+            slot.as_ref().map_or(
+                false,
+                |it| matches!(it.item_definition_id(), ItemDefinitionId::Simple(id) if id == item),
+            )
+        })
+        .map(|(slot, _)| slot)
+        .collect::<Vec<_>>();
+
+    for slot in slots {
+        if amount == 0 {
+            break;
+        }
+        if let Some(Some(it)) = inventory.slot_mut(slot) {
+            if it.amount() > amount {
+                // Can't fail, the amount stays above zero
+                let _ = it.decrease_amount(amount);
+                amount = 0;
+            } else {
+                amount -= it.amount();
+                inventory.remove(slot);
+            }
+        }
+    }
+}
+
+/// Give experience to the general skill group
+pub fn grant_general_exp(state: &State, entity: EcsEntity, exp: u32) {
+    let uid = state.ecs().uid_from_entity(entity);
+    if let (Some(mut skill_set), Some(uid)) = (
+        state
+            .ecs()
+            .write_storage::<comp::SkillSet>()
+            .get_mut(entity),
+        uid,
+    ) {
+        let skill_group = SkillGroupKind::General;
+        let outcome_bus = state.ecs().read_resource::<EventBus<Outcome>>();
+        if let Some(level_outcome) = skill_set.add_experience(skill_group, exp) {
+            outcome_bus.emit_now(Outcome::SkillPointGain {
+                uid,
+                skill_tree: skill_group,
+                total_points: level_outcome,
+            });
+        }
+        outcome_bus.emit_now(Outcome::ExpChange {
+            uid,
+            exp,
+            xp_pools: HashSet::from_iter(vec![skill_group]),
+        });
+    }
+}
+
+/// Take the fetched items and hand out the reward
+pub fn handle_complete_quest(server: &mut Server, entity: EcsEntity, quest: ActiveQuest) {
+    let state = server.state_mut();
+    let def = match QuestDef::load(&quest.id) {
+        Ok(def) => def.read().clone(),
+        Err(err) => {
+            warn!(?err, "Completed unknown quest {}", quest.id);
+            return;
+        },
+    };
+
+    let mut reward_items = Vec::new();
+    for (item_id, amount) in def.reward.items.iter() {
+        match Item::new_from_asset(item_id) {
+            Ok(mut item) => {
+                if item.is_stackable() {
+                    let _ = item.set_amount(*amount);
+                    reward_items.push(item);
+                } else {
+                    reward_items.push(item);
+                    reward_items
+                        .extend((1..*amount).filter_map(|_| Item::new_from_asset(item_id).ok()));
+                }
+            },
+            Err(err) => warn!(?err, "Invalid quest reward {}", item_id),
+        }
+    }
+
+    let mut dropped_items = Vec::new();
+    if let Some(mut inventory) = state.ecs().write_storage::<Inventory>().get_mut(entity) {
+        for objective in quest.objectives.iter() {
+            if let Objective::Fetch { item, count } = objective {
+                take_items(&mut inventory, item, *count);
+            }
+        }
+        for item in reward_items {
+            if let Err(item) = inventory.push(item) {
+                dropped_items.push(item);
+            }
+        }
+    }
+    state.write_component_ignore_entity_dead(
+        entity,
+        comp::InventoryUpdate::new(InventoryUpdateEvent::Given),
+    );
+
+    let pos = state.ecs().read_storage::<comp::Pos>().get(entity).copied();
+    if let Some(pos) = pos {
+        for item in dropped_items {
+            state.create_item_drop(pos, item).build();
+        }
+    }
+
+    grant_general_exp(state, entity, def.reward.exp);
+
+    if !def.reward.reputation.is_empty() {
+        if let Ok(entry) = state.ecs().write_storage::<Reputation>().entry(entity) {
+            let reputation = entry.or_insert_with(Reputation::default);
+            for (faction, amount) in def.reward.reputation.iter() {
+                reputation.change(*faction, *amount);
+            }
+        }
+    }
+
+    if let Some(client) = state.ecs().read_storage::<Client>().get(entity) {
+        client.send_fallible(ServerGeneral::Notification(Notification::QuestCompleted(
+            quest.id,
+        )));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::Server;
+use crate::{
+    audit::{self, AuditEvent, LARGE_TRANSFER_AMOUNT},
+    Server,
+};
 use common::{
     comp::{
         agent::{Agent, AgentEvent},
@@ -6,6 +9,7 @@ use common::{
             item::{tool::AbilityMap, ItemDefinitionIdOwned, MaterialStatManifest},
             Inventory,
         },
+        Player,
     },
     trade::{PendingTrade, ReducedInventory, TradeAction, TradeId, TradeResult, Trades},
 };
@@ -91,7 +95,13 @@ pub(super) fn handle_process_trade_action(
             if let Entry::Occupied(entry) = trades.trades.entry(trade_id) {
                 let parties = entry.get().parties;
                 if entry.get().should_commit() {
+                    let transfers = large_transfers(server.state.ecs(), entry.get());
                     let result = commit_trade(server.state.ecs(), entry.get());
+                    if let TradeResult::Completed = result {
+                        for (giver, event) in transfers {
+                            audit::record(server.state.ecs(), giver, event);
+                        }
+                    }
                     entry.remove();
                     for party in parties.iter() {
                         if let Some(e) = server.state.ecs().entity_from_uid(party.0) {
@@ -195,6 +205,38 @@ pub(crate) fn cancel_trades_for(state: &mut common_state::State, entity: EcsEnti
     }
 }
 
+/// Large stacks of items the players of a trade give each other, to be
+/// recorded once the trade is committed
+fn large_transfers(ecs: &specs::World, trade: &PendingTrade) -> Vec<(EcsEntity, AuditEvent)> {
+    let players = ecs.read_storage::<Player>();
+    let inventories = ecs.read_storage::<Inventory>();
+    let entities = trade.parties.map(|party| ecs.entity_from_uid(party.0));
+    let mut transfers = Vec::new();
+    for who in [0, 1] {
+        let (giver, receiver) = match (entities[who], entities[1 - who]) {
+            (Some(giver), Some(receiver)) => (giver, receiver),
+            _ => continue,
+        };
+        let (receiver, inventory) = match (players.get(receiver), inventories.get(giver)) {
+            (Some(receiver), Some(inventory)) => (receiver, inventory),
+            _ => continue,
+        };
+        for (slot, quantity) in trade.offers[who].iter() {
+            if *quantity < LARGE_TRANSFER_AMOUNT {
+                continue;
+            }
+            if let Some(item) = inventory.get(*slot) {
+                transfers.push((giver, AuditEvent::ItemTransfer {
+                    to: receiver.alias.clone(),
+                    item: item.name().into_owned(),
+                    amount: *quantity,
+                }));
+            }
+        }
+    }
+    transfers
+}
+
 /// Commit a trade that both parties have agreed to, modifying their respective
 /// inventories
 fn commit_trade(ecs: &specs::World, trade: &PendingTrade) -> TradeResult {
@@ -0,0 +1,56 @@
+use crate::ron_file;
+use common::farming::Crop;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use vek::*;
+
+const FARMS_FILENAME: &str = "farms.ron";
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct PlantedCrop {
+    pub crop: Crop,
+    /// Time of day the seed was planted at
+    pub planted_at: f64,
+}
+
+/// Crops planted by players, grouped by the chunk they are in. They are saved
+/// in the data directory so that they keep growing while their chunk is
+/// unloaded, and across restarts.
+#[derive(Default)]
+pub struct Farms {
+    path: PathBuf,
+    chunks: HashMap<Vec2<i32>, HashMap<Vec3<i32>, PlantedCrop>>,
+}
+
+impl Farms {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(FARMS_FILENAME);
+        let chunks = ron_file::load(&path, "farms");
+        Self { path, chunks }
+    }
+
+    pub fn save(&self) { ron_file::save(&self.path, "farms", &self.chunks); }
+
+    pub fn plant(&mut self, chunk_key: Vec2<i32>, pos: Vec3<i32>, crop: PlantedCrop) {
+        self.chunks.entry(chunk_key).or_default().insert(pos, crop);
+        self.save();
+    }
+
+    pub fn chunks(&self) -> impl Iterator<Item = (&Vec2<i32>, &HashMap<Vec3<i32>, PlantedCrop>)> {
+        self.chunks.iter()
+    }
+
+    /// Forgets the crops which were harvested or destroyed
+    pub fn remove(&mut self, positions: impl IntoIterator<Item = (Vec2<i32>, Vec3<i32>)>) {
+        for (chunk_key, pos) in positions {
+            if let Some(chunk) = self.chunks.get_mut(&chunk_key) {
+                chunk.remove(&pos);
+                if chunk.is_empty() {
+                    self.chunks.remove(&chunk_key);
+                }
+            }
+        }
+        self.save();
+    }
+}
@@ -0,0 +1,61 @@
+use hashbrown::HashMap;
+use vek::*;
+
+/// A block on fire
+#[derive(Copy, Clone, Debug)]
+pub struct Burning {
+    /// Where the flames are, the block burning without any if there was no
+    /// room for them
+    pub flames: Option<Vec3<i32>>,
+    /// Time the block is burnt out at
+    pub burns_out_at: f64,
+}
+
+/// Blocks on fire, grouped by the chunk they are in, along with the places set
+/// on fire since the last spreading step
+#[derive(Default)]
+pub struct Fires {
+    chunks: HashMap<Vec2<i32>, HashMap<Vec3<i32>, Burning>>,
+    ignitions: Vec<Vec3<i32>>,
+}
+
+impl Fires {
+    /// Sets the first block below the position on fire, if it burns
+    pub fn ignite(&mut self, pos: Vec3<i32>) { self.ignitions.push(pos); }
+
+    pub fn take_ignitions(&mut self) -> Vec<Vec3<i32>> { std::mem::take(&mut self.ignitions) }
+
+    pub fn chunks(&self) -> impl Iterator<Item = (&Vec2<i32>, &HashMap<Vec3<i32>, Burning>)> {
+        self.chunks.iter()
+    }
+
+    pub fn is_burning(&self, chunk_key: Vec2<i32>, pos: Vec3<i32>) -> bool {
+        self.chunks
+            .get(&chunk_key)
+            .map_or(false, |chunk| chunk.contains_key(&pos))
+    }
+
+    /// Number of blocks burning in the chunk
+    pub fn burning_in(&self, chunk_key: Vec2<i32>) -> usize {
+        self.chunks.get(&chunk_key).map_or(0, |chunk| chunk.len())
+    }
+
+    pub fn insert(&mut self, chunk_key: Vec2<i32>, pos: Vec3<i32>, burning: Burning) {
+        self.chunks
+            .entry(chunk_key)
+            .or_default()
+            .insert(pos, burning);
+    }
+
+    /// Forgets the fires which burnt out or were put out
+    pub fn remove(&mut self, positions: impl IntoIterator<Item = (Vec2<i32>, Vec3<i32>)>) {
+        for (chunk_key, pos) in positions {
+            if let Some(chunk) = self.chunks.get_mut(&chunk_key) {
+                chunk.remove(&pos);
+                if chunk.is_empty() {
+                    self.chunks.remove(&chunk_key);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use common::terrain::fluid;
+use hashbrown::HashSet;
+use vek::*;
+
+/// Blocks which may start or stop flowing on the next flowing step, because
+/// they or one of their neighbours changed
+#[derive(Default)]
+pub struct Fluids {
+    active: HashSet<Vec3<i32>>,
+    step: u64,
+}
+
+impl Fluids {
+    /// Wakes up the block and its neighbours after it changed
+    pub fn wake(&mut self, pos: Vec3<i32>) {
+        self.active.insert(pos);
+        self.active.insert(pos + Vec3::unit_z());
+        self.active.insert(pos - Vec3::unit_z());
+        for dir in fluid::FlowDir::HORIZONTAL {
+            self.active.insert(pos + dir.to_vec());
+        }
+    }
+
+    /// Keeps the block awake for the next flowing step
+    pub fn defer(&mut self, pos: Vec3<i32>) { self.active.insert(pos); }
+
+    /// Takes the blocks woken up since the last step, at most `max` of them,
+    /// and tells whether lava flows on this step
+    pub fn next_step(&mut self, max: usize) -> (Vec<Vec3<i32>>, bool) {
+        self.step += 1;
+        let active = if self.active.len() > max {
+            let taken = self.active.iter().take(max).copied().collect::<Vec<_>>();
+            for pos in taken.iter() {
+                self.active.remove(pos);
+            }
+            taken
+        } else {
+            self.active.drain().collect()
+        };
+        (active, self.step % fluid::LAVA_SLOWDOWN == 0)
+    }
+}
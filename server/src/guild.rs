@@ -0,0 +1,369 @@
+use crate::{client::Client, persistence::character_updater::CharacterUpdater, presence::Presence};
+use common::{
+    character::CharacterId,
+    comp::guild::{is_valid_guild_name, GuildInfo, GuildMemberInfo, GuildRank},
+};
+use common_net::msg::{PresenceKind, ServerGeneral};
+use hashbrown::{HashMap, HashSet};
+use specs::{Join, World, WorldExt};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GuildMember {
+    /// Name of the character, kept so that offline members can be listed
+    pub name: String,
+    pub rank: GuildRank,
+}
+
+/// Persistent group of characters with its own chat channel
+#[derive(Clone, Debug, PartialEq)]
+pub struct Guild {
+    pub name: String,
+    pub members: HashMap<CharacterId, GuildMember>,
+}
+
+impl Guild {
+    /// Finds a member by character name, since offline members have no
+    /// entity to refer to them
+    pub fn find_member(&self, name: &str) -> Option<CharacterId> {
+        self.members
+            .iter()
+            .find(|(_, member)| member.name.eq_ignore_ascii_case(name))
+            .map(|(character_id, _)| *character_id)
+    }
+
+    /// Roster as seen by one of the members
+    pub fn info_for(
+        &self,
+        character_id: CharacterId,
+        online: &HashSet<CharacterId>,
+    ) -> Option<GuildInfo> {
+        let rank = self.members.get(&character_id)?.rank;
+        let mut members = self
+            .members
+            .iter()
+            .map(|(character_id, member)| GuildMemberInfo {
+                name: member.name.clone(),
+                rank: member.rank,
+                online: online.contains(character_id),
+            })
+            .collect::<Vec<_>>();
+        members.sort_by(|a, b| {
+            b.rank
+                .cmp(&a.rank)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        Some(GuildInfo {
+            name: self.name.clone(),
+            rank,
+            members,
+        })
+    }
+
+    /// Makes the highest ranking member the leader if there is none
+    fn ensure_leader(&mut self) {
+        if self
+            .members
+            .values()
+            .all(|member| member.rank != GuildRank::Leader)
+        {
+            if let Some(successor) = self
+                .members
+                .iter_mut()
+                .max_by_key(|(character_id, member)| (member.rank, -**character_id))
+                .map(|(_, member)| member)
+            {
+                successor.rank = GuildRank::Leader;
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GuildError {
+    InvalidName,
+    NameTaken,
+    AlreadyInGuild,
+    NotInGuild,
+    NoSuchGuild,
+    NoSuchMember,
+    NotAllowed,
+}
+
+impl fmt::Display for GuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            GuildError::InvalidName => {
+                "Guild names can only contain letters, numbers, spaces, '_' and '-', and must be \
+                 at most 24 characters long."
+            },
+            GuildError::NameTaken => "A guild with this name already exists.",
+            GuildError::AlreadyInGuild => "You already are in a guild.",
+            GuildError::NotInGuild => "You are not in a guild.",
+            GuildError::NoSuchGuild => "This guild doesn't exist anymore.",
+            GuildError::NoSuchMember => "There is no member with this name in your guild.",
+            GuildError::NotAllowed => "Your rank doesn't allow you to do this.",
+        })
+    }
+}
+
+/// What happened to a guild after someone left it
+#[derive(Debug, PartialEq)]
+pub enum GuildChange {
+    Updated(String),
+    /// The last member left
+    Disbanded(String),
+}
+
+/// All the guilds of the server, loaded from the database on startup
+#[derive(Default)]
+pub struct Guilds {
+    guilds: HashMap<String, Guild>,
+    by_character: HashMap<CharacterId, String>,
+}
+
+impl Guilds {
+    pub fn new(mut guilds: Vec<Guild>) -> Self {
+        // The leader's character might have been deleted
+        guilds.iter_mut().for_each(Guild::ensure_leader);
+        let by_character = guilds
+            .iter()
+            .flat_map(|guild| {
+                guild
+                    .members
+                    .keys()
+                    .map(move |character_id| (*character_id, guild.name.clone()))
+            })
+            .collect();
+        Self {
+            guilds: guilds
+                .into_iter()
+                .map(|guild| (guild.name.clone(), guild))
+                .collect(),
+            by_character,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Guild> { self.guilds.get(name) }
+
+    pub fn guild_of(&self, character_id: CharacterId) -> Option<&Guild> {
+        self.by_character
+            .get(&character_id)
+            .and_then(|name| self.guilds.get(name))
+    }
+
+    pub fn rank_of(&self, character_id: CharacterId) -> Option<GuildRank> {
+        self.guild_of(character_id)?
+            .members
+            .get(&character_id)
+            .map(|member| member.rank)
+    }
+
+    pub fn create(
+        &mut self,
+        name: &str,
+        founder: CharacterId,
+        founder_name: String,
+    ) -> Result<&Guild, GuildError> {
+        if !is_valid_guild_name(name) {
+            return Err(GuildError::InvalidName);
+        }
+        if self.by_character.contains_key(&founder) {
+            return Err(GuildError::AlreadyInGuild);
+        }
+        if self.guilds.keys().any(|n| n.eq_ignore_ascii_case(name)) {
+            return Err(GuildError::NameTaken);
+        }
+        let mut members = HashMap::new();
+        members.insert(founder, GuildMember {
+            name: founder_name,
+            rank: GuildRank::Leader,
+        });
+        self.by_character.insert(founder, name.to_string());
+        Ok(self.guilds.entry(name.to_string()).or_insert(Guild {
+            name: name.to_string(),
+            members,
+        }))
+    }
+
+    pub fn join(
+        &mut self,
+        name: &str,
+        character_id: CharacterId,
+        character_name: String,
+    ) -> Result<&Guild, GuildError> {
+        if self.by_character.contains_key(&character_id) {
+            return Err(GuildError::AlreadyInGuild);
+        }
+        let guild = self.guilds.get_mut(name).ok_or(GuildError::NoSuchGuild)?;
+        guild.members.insert(character_id, GuildMember {
+            name: character_name,
+            rank: GuildRank::Member,
+        });
+        self.by_character.insert(character_id, name.to_string());
+        Ok(guild)
+    }
+
+    /// Removes the character from its guild, handing the lead over to the
+    /// highest ranking member left if needed
+    pub fn leave(&mut self, character_id: CharacterId) -> Result<GuildChange, GuildError> {
+        let name = self
+            .by_character
+            .remove(&character_id)
+            .ok_or(GuildError::NotInGuild)?;
+        let guild = self.guilds.get_mut(&name).ok_or(GuildError::NoSuchGuild)?;
+        guild.members.remove(&character_id);
+        if guild.members.is_empty() {
+            self.guilds.remove(&name);
+            return Ok(GuildChange::Disbanded(name));
+        }
+        guild.ensure_leader();
+        Ok(GuildChange::Updated(name))
+    }
+
+    /// Removes the member with the given name from the guild of `by`, returns
+    /// the character which was kicked
+    pub fn kick(&mut self, by: CharacterId, target: &str) -> Result<CharacterId, GuildError> {
+        let guild = self.guild_of(by).ok_or(GuildError::NotInGuild)?;
+        let target = guild.find_member(target).ok_or(GuildError::NoSuchMember)?;
+        let (by_rank, target_rank) = (guild.members[&by].rank, guild.members[&target].rank);
+        if !by_rank.can_kick(target_rank) {
+            return Err(GuildError::NotAllowed);
+        }
+        self.leave(target)?;
+        Ok(target)
+    }
+
+    /// Changes the rank of a member of the guild of `by`. Promoting someone
+    /// to leader hands over the lead, the former leader becoming an officer.
+    pub fn change_rank(
+        &mut self,
+        by: CharacterId,
+        target: &str,
+        promote: bool,
+    ) -> Result<GuildRank, GuildError> {
+        let name = self
+            .by_character
+            .get(&by)
+            .ok_or(GuildError::NotInGuild)?
+            .clone();
+        let guild = self.guilds.get_mut(&name).ok_or(GuildError::NoSuchGuild)?;
+        let target = guild.find_member(target).ok_or(GuildError::NoSuchMember)?;
+        if !guild.members[&by].rank.can_change_ranks() || target == by {
+            return Err(GuildError::NotAllowed);
+        }
+        let member = guild
+            .members
+            .get_mut(&target)
+            .ok_or(GuildError::NoSuchMember)?;
+        member.rank = if promote {
+            member.rank.promoted()
+        } else {
+            member.rank.demoted()
+        };
+        let rank = member.rank;
+        if rank == GuildRank::Leader {
+            if let Some(former_leader) = guild.members.get_mut(&by) {
+                former_leader.rank = GuildRank::Officer;
+            }
+        }
+        Ok(rank)
+    }
+}
+
+/// Characters currently played, along with their entity
+pub fn online_characters(ecs: &World) -> HashMap<CharacterId, specs::Entity> {
+    (&ecs.entities(), &ecs.read_storage::<Presence>())
+        .join()
+        .filter_map(|(entity, presence)| match presence.kind {
+            PresenceKind::Character(character_id) => Some((character_id, entity)),
+            PresenceKind::Spectator | PresenceKind::Possessor => None,
+        })
+        .collect()
+}
+
+/// Character played by the entity, guilds being tied to characters
+pub fn character_of(ecs: &World, entity: specs::Entity) -> Option<CharacterId> {
+    match ecs.read_storage::<Presence>().get(entity)?.kind {
+        PresenceKind::Character(character_id) => Some(character_id),
+        PresenceKind::Spectator | PresenceKind::Possessor => None,
+    }
+}
+
+/// Saves the guild and sends the new roster to its online members. Members
+/// who just left have to be told separately.
+pub fn update_guild(ecs: &World, change: GuildChange) {
+    let guilds = ecs.read_resource::<Guilds>();
+    let mut character_updater = ecs.write_resource::<CharacterUpdater>();
+    match change {
+        GuildChange::Updated(name) => {
+            if let Some(guild) = guilds.get(&name) {
+                character_updater.update_guild(guild.clone());
+                send_guild_info(ecs, guild);
+            }
+        },
+        GuildChange::Disbanded(name) => character_updater.disband_guild(name),
+    }
+}
+
+/// Sends the roster of the guild to its online members
+pub fn send_guild_info(ecs: &World, guild: &Guild) { send_guild_info_without(ecs, guild, None) }
+
+/// Tells the guild of the character that they went offline, has to be called
+/// before their presence is removed
+pub fn notify_logout(ecs: &World, character_id: CharacterId) {
+    if let Some(guild) = ecs.read_resource::<Guilds>().guild_of(character_id) {
+        send_guild_info_without(ecs, guild, Some(character_id));
+    }
+}
+
+fn send_guild_info_without(ecs: &World, guild: &Guild, offline: Option<CharacterId>) {
+    let mut online = online_characters(ecs);
+    if let Some(offline) = offline {
+        online.remove(&offline);
+    }
+    let online_ids = online.keys().copied().collect::<HashSet<_>>();
+    let clients = ecs.read_storage::<Client>();
+    for character_id in guild.members.keys() {
+        if let Some(client) = online
+            .get(character_id)
+            .and_then(|entity| clients.get(*entity))
+        {
+            client.send_fallible(ServerGeneral::GuildUpdate(
+                guild.info_for(*character_id, &online_ids),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lead_is_handed_over_when_the_leader_leaves() {
+        let mut guilds = Guilds::default();
+        guilds.create("Wanderers", 1, "Alice".into()).unwrap();
+        guilds.join("Wanderers", 2, "Bob".into()).unwrap();
+        guilds.join("Wanderers", 3, "Carol".into()).unwrap();
+        assert_eq!(
+            guilds.create("wanderers", 4, "Dave".into()).err(),
+            Some(GuildError::NameTaken)
+        );
+
+        assert_eq!(guilds.change_rank(1, "carol", true), Ok(GuildRank::Officer));
+        assert_eq!(guilds.kick(2, "Carol"), Err(GuildError::NotAllowed));
+        assert_eq!(
+            guilds.leave(1),
+            Ok(GuildChange::Updated("Wanderers".into()))
+        );
+        assert_eq!(guilds.rank_of(3), Some(GuildRank::Leader));
+        assert_eq!(guilds.kick(3, "Bob"), Ok(2));
+        assert_eq!(guilds.guild_of(2), None);
+        assert_eq!(
+            guilds.leave(3),
+            Ok(GuildChange::Disbanded("Wanderers".into()))
+        );
+        assert!(guilds.get("Wanderers").is_none());
+    }
+}
@@ -13,31 +13,48 @@
     unwrap_infallible
 )]
 
+pub mod audit;
 pub mod automod;
 mod character_creator;
+pub mod character_transfer;
 pub mod chunk_generator;
 mod chunk_serialize;
 pub mod client;
 pub mod cmd;
 pub mod connection_handler;
+pub mod console;
 mod data_dir;
+pub mod dialogue;
 pub mod error;
 pub mod events;
+pub mod farming;
+pub mod fire;
+pub mod fluid;
+pub mod guild;
 pub mod input;
 pub mod location;
 pub mod lod;
 pub mod login_provider;
+pub mod login_queue;
+pub mod map_tiles;
 pub mod metrics;
 pub mod persistence;
 mod pet;
+pub mod placed_blocks;
+pub mod portal;
 pub mod presence;
+mod ron_file;
 pub mod rtsim;
+pub mod schedule;
+pub mod session;
 pub mod settings;
+pub mod signs;
 pub mod state_ext;
 pub mod sys;
 #[cfg(feature = "persistent_world")]
 pub mod terrain_persistence;
 #[cfg(not(feature = "worldgen"))] mod test_world;
+pub mod tick_catch_up;
 
 mod weather;
 
@@ -80,11 +97,12 @@ use common::{
     comp,
     event::{EventBus, ServerEvent},
     recipe::{default_component_recipe_book, default_recipe_book},
-    resources::{BattleMode, Time, TimeOfDay},
+    resources::{BattleMode, DayCycleFactor, Time, TimeOfDay},
     rtsim::RtSimEntity,
     slowjob::SlowJobPool,
     terrain::{TerrainChunk, TerrainChunkSize},
     vol::RectRasterableVol,
+    world_border::WorldBorder,
 };
 use common_ecs::run_now;
 use common_net::{
@@ -210,6 +228,9 @@ pub struct Server {
     metrics_shutdown: Arc<Notify>,
     database_settings: Arc<RwLock<DatabaseSettings>>,
     disconnect_all_clients_requested: bool,
+    /// Entity running the commands typed in the server console, created on
+    /// the first one
+    console_entity: Option<EcsEntity>,
 }
 
 impl Server {
@@ -265,6 +286,7 @@ impl Server {
             settings.auth_server_address.clone(),
             Arc::clone(&runtime),
         ));
+        state.ecs_mut().insert(login_queue::LoginQueue::default());
         state.ecs_mut().insert(HwStats {
             hardware_threads: num_cpus::get() as u32,
             rayon_threads: num_cpus::get() as u32,
@@ -301,6 +323,7 @@ impl Server {
             let pool = state.ecs_mut().write_resource::<SlowJobPool>();
             pool.configure("CHUNK_GENERATOR", |n| n / 2 + n / 4);
             pool.configure("CHUNK_SERIALIZER", |n| n / 2);
+            pool.configure("MAP_TILES", |_| 1);
         }
         state
             .ecs_mut()
@@ -312,9 +335,35 @@ impl Server {
             state.ecs_mut().insert(receiver);
         }
 
+        state.ecs_mut().insert(audit::AuditLog::new(data_dir));
+        state.ecs_mut().insert(audit::Rollbacks::default());
+        state
+            .ecs_mut()
+            .insert(portal::PortalNetwork::load(data_dir));
+        state.ecs_mut().insert(farming::Farms::load(data_dir));
+        state.ecs_mut().insert(fire::Fires::default());
+        state.ecs_mut().insert(fluid::Fluids::default());
+        state.ecs_mut().insert(signs::Signs::load(data_dir));
+        // Blocks placed before a restart are gone unless the terrain is persisted
+        state.ecs_mut().insert(
+            if cfg!(feature = "persistent_world") && settings.experimental_terrain_persistence {
+                placed_blocks::PlacedBlocks::load(data_dir)
+            } else {
+                placed_blocks::PlacedBlocks::default()
+            },
+        );
+        state.ecs_mut().insert(schedule::Scheduler::new(
+            &settings.schedule,
+            chrono::Utc::now(),
+        ));
         state.ecs_mut().insert(CharacterUpdater::new(
             Arc::<RwLock<DatabaseSettings>>::clone(&database_settings),
         )?);
+        state
+            .ecs_mut()
+            .insert(guild::Guilds::new(persistence::guild::load_guilds(
+                &database_settings.read().unwrap(),
+            )?));
 
         let ability_map = comp::item::tool::AbilityMap::<comp::AbilityItem>::load_expect_cloned(
             "common.abilities.ability_set_manifest",
@@ -332,6 +381,31 @@ impl Server {
         state
             .ecs_mut()
             .insert(sys::PersistenceScheduler::every(Duration::from_secs(10)));
+        state
+            .ecs_mut()
+            .insert(sys::FarmingScheduler::every(Duration::from_secs(5)));
+        state
+            .ecs_mut()
+            .insert(sys::FireScheduler::every(Duration::from_secs_f64(
+                common::fire::SPREAD_INTERVAL,
+            )));
+        state
+            .ecs_mut()
+            .insert(sys::FluidScheduler::every(Duration::from_secs_f64(
+                common::terrain::fluid::FLOW_INTERVAL,
+            )));
+        state
+            .ecs_mut()
+            .insert(sys::CleanupScheduler::every(Duration::from_secs(10)));
+        state
+            .ecs_mut()
+            .insert(sys::WorldBorderScheduler::every(Duration::from_secs(1)));
+        state
+            .ecs_mut()
+            .insert(sys::PlacedBlocksScheduler::every(Duration::from_secs(30)));
+        state
+            .ecs_mut()
+            .insert(sys::SignsScheduler::every(Duration::from_secs(30)));
 
         // Server-only components
         state.ecs_mut().register::<RegionSubscription>();
@@ -342,7 +416,15 @@ impl Server {
         state.ecs_mut().register::<Anchor>();
         state.ecs_mut().register::<comp::Pet>();
         state.ecs_mut().register::<login_provider::PendingLogin>();
+        state.ecs_mut().register::<session::Session>();
+        state.ecs_mut().register::<session::ResumeRequest>();
         state.ecs_mut().register::<RepositionOnChunkLoad>();
+        state.ecs_mut().register::<sys::sleep::Sleeping>();
+        state.ecs_mut().register::<sys::spectate::Spectating>();
+        state.ecs_mut().register::<sys::cleanup::DespawnTimer>();
+        state.ecs_mut().register::<sys::portal::PortalChannel>();
+        state.ecs_mut().register::<console::ConsoleOutput>();
+        state.ecs_mut().register::<dialogue::Dialogue>();
 
         // Load banned words list
         let banned_words = settings.moderation.load_banned_words(data_dir);
@@ -458,6 +540,9 @@ impl Server {
 
         // Set starting time for the server.
         state.ecs_mut().write_resource::<TimeOfDay>().0 = settings.start_time;
+        *state.ecs_mut().write_resource::<DayCycleFactor>() =
+            DayCycleFactor::from_day_length(settings.gameplay.day_length);
+        *state.ecs_mut().write_resource::<WorldBorder>() = settings.world_border.clone();
 
         // Register trackers
         sys::sentinel::UpdateTrackers::register(state.ecs_mut());
@@ -558,6 +643,17 @@ impl Server {
         #[cfg(not(feature = "worldgen"))]
         rtsim::init(&mut state);
 
+        if settings.map_tiles.enabled {
+            state
+                .ecs_mut()
+                .insert(map_tiles::MapTiles::load(data_dir, &map));
+        }
+        state
+            .ecs_mut()
+            .insert(sys::MapTileScheduler::every(Duration::from_secs(
+                settings.map_tiles.interval,
+            )));
+
         let this = Self {
             state,
             world,
@@ -570,6 +666,7 @@ impl Server {
             metrics_shutdown,
             database_settings,
             disconnect_all_clients_requested: false,
+            console_entity: None,
         };
 
         debug!(?settings, "created veloren server with");
@@ -593,6 +690,8 @@ impl Server {
             git_hash: common::util::GIT_HASH.to_string(),
             git_date: common::util::GIT_DATE.to_string(),
             auth_provider: settings.auth_server_address.clone(),
+            world_seed: settings.world_seed,
+            voice_chat: settings.voice_chat.enabled,
         }
     }
 
@@ -715,6 +814,9 @@ impl Server {
         // Handle game events
         frontend_events.append(&mut self.handle_events());
 
+        // Run the scheduled tasks that are due
+        frontend_events.append(&mut self.run_scheduled_tasks());
+
         let before_update_terrain_and_regions = Instant::now();
 
         // Apply terrain changes and update the region map after processing server
@@ -821,6 +923,9 @@ impl Server {
             }
         }
 
+        // Portals come back along with their chunk
+        portal::spawn_loaded_portals(&mut self.state);
+
         if let Some(DisconnectType::WithoutPersistence) = disconnect_type {
             info!(
                 "Disconnection of all players without persistence complete, signalling to \
@@ -839,6 +944,9 @@ impl Server {
 
         let character_updater = self.state.ecs().read_resource::<CharacterUpdater>();
 
+        // Characters deleted since the last tick, which have to leave their guild
+        let mut deleted_characters = Vec::new();
+
         // Get character-related database responses and notify the requesting client
         character_loader
             .messages()
@@ -870,6 +978,19 @@ impl Server {
                         ServerGeneral::CharacterActionError(error.to_string()),
                     ),
                 },
+                CharacterLoaderResponseKind::CharacterDeletion(result) => match result {
+                    Ok((character_id, list)) => {
+                        deleted_characters.push(character_id);
+                        self.notify_client(
+                            query_result.entity,
+                            ServerGeneral::CharacterListUpdate(list),
+                        );
+                    },
+                    Err(error) => self.notify_client(
+                        query_result.entity,
+                        ServerGeneral::CharacterActionError(error.to_string()),
+                    ),
+                },
                 CharacterLoaderResponseKind::CharacterEdit(result) => match result {
                     Ok((character_id, list)) => {
                         self.notify_client(
@@ -898,6 +1019,8 @@ impl Server {
                                 pets,
                                 active_abilities,
                                 map_marker,
+                                quest_log,
+                                reputation,
                             } = character_data;
                             let character_data = (
                                 body,
@@ -908,6 +1031,8 @@ impl Server {
                                 pets,
                                 active_abilities,
                                 map_marker,
+                                quest_log,
+                                reputation,
                             );
                             ServerEvent::UpdateCharacterData {
                                 entity: query_result.entity,
@@ -940,6 +1065,17 @@ impl Server {
         drop(character_loader);
         drop(character_updater);
 
+        for character_id in deleted_characters {
+            let change = self
+                .state
+                .ecs()
+                .write_resource::<guild::Guilds>()
+                .leave(character_id);
+            if let Ok(change) = change {
+                guild::update_guild(self.state.ecs(), change);
+            }
+        }
+
         {
             // Check for new chunks; cancel and regenerate all chunks if the asset has been
             // reloaded. Note that all of these assignments are no-ops, so the
@@ -1033,7 +1169,11 @@ impl Server {
         &mut self,
         client: connection_handler::IncomingClient,
     ) -> Result<Option<Entity>, Error> {
-        if self.settings().max_players <= self.state.ecs().read_storage::<Client>().join().count() {
+        let max_clients = {
+            let settings = self.settings();
+            settings.max_players + settings.login_queue_size
+        };
+        if max_clients <= self.state.ecs().read_storage::<Client>().join().count() {
             trace!(
                 ?client.participant,
                 "to many players, wont allow participant to connect"
@@ -1174,11 +1314,44 @@ impl Server {
     {
         if let Some(client) = self.state.ecs().read_storage::<Client>().get(entity) {
             client.send_fallible(msg);
+        } else if let Some(output) = self
+            .state
+            .ecs()
+            .write_storage::<console::ConsoleOutput>()
+            .get_mut(entity)
+        {
+            if let ServerMsg::General(ServerGeneral::ChatMsg(msg)) = msg.into() {
+                output.0.push(msg.message);
+            }
         }
     }
 
     pub fn notify_players(&mut self, msg: ServerGeneral) { self.state.notify_players(msg); }
 
+    /// Tells the admins the server can't keep up, after giving up on a backlog
+    /// of `dropped` ticks
+    pub fn notify_overloaded(&mut self, dropped: u32) {
+        warn!(
+            ?dropped,
+            "Server overloaded, gave up on catching up with the backlog of ticks"
+        );
+        let msg = ServerGeneral::server_msg(
+            comp::ChatType::CommandInfo,
+            format!(
+                "The server is overloaded: it fell {} ticks behind and skipped them.",
+                dropped
+            ),
+        );
+        for (client, _) in (
+            &self.state.ecs().read_storage::<Client>(),
+            &self.state.ecs().read_storage::<comp::Admin>(),
+        )
+            .join()
+        {
+            client.send_fallible(msg.clone());
+        }
+    }
+
     pub fn generate_chunk(&mut self, entity: EcsEntity, key: Vec2<i32>) {
         let ecs = self.state.ecs();
         let slow_jobs = ecs.read_resource::<SlowJobPool>();
@@ -1402,6 +1575,37 @@ impl Server {
         info!("Disconnecting all clients due to local console command");
         self.disconnect_all_clients_requested = true;
     }
+
+    /// Runs a chat command typed in the server console as a virtual superuser.
+    /// It isn't part of the world, so commands acting on the position or
+    /// character of the one running them fail. Returns the replies to the
+    /// command.
+    ///
+    /// NOTE: Do *not* allow this to be called from anything that doesn't go
+    /// through the CLI!
+    pub fn execute_console_command(&mut self, name: String, args: Vec<String>) -> Vec<String> {
+        let console = match self.console_entity {
+            Some(console) => console,
+            None => {
+                let console = self
+                    .state
+                    .ecs_mut()
+                    .create_entity()
+                    .with(comp::Admin(comp::AdminRole::Admin))
+                    .with(console::ConsoleOutput::default())
+                    .build();
+                self.console_entity = Some(console);
+                console
+            },
+        };
+        self.process_command(console, name, args);
+        self.state
+            .ecs()
+            .write_storage::<console::ConsoleOutput>()
+            .get_mut(console)
+            .map(|output| std::mem::take(&mut output.0))
+            .unwrap_or_default()
+    }
 }
 
 impl Drop for Server {
@@ -1411,6 +1615,12 @@ impl Drop for Server {
         self.state
             .notify_players(ServerGeneral::Disconnect(DisconnectReason::Shutdown));
 
+        self.state
+            .ecs()
+            .write_resource::<placed_blocks::PlacedBlocks>()
+            .save();
+        self.state.ecs().write_resource::<signs::Signs>().save();
+
         #[cfg(feature = "persistent_world")]
         self.state
             .ecs()
@@ -40,6 +40,13 @@ impl PendingLogin {
 
         Self { pending_r }
     }
+
+    pub(crate) fn new_failure(err: RegisterError) -> Self {
+        let (pending_s, pending_r) = oneshot::channel();
+        let _ = pending_s.send(Err(err));
+
+        Self { pending_r }
+    }
 }
 
 impl Component for PendingLogin {
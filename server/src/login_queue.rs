@@ -0,0 +1,120 @@
+//! Players waiting for a slot while the server is full. They are let in in the
+//! order they logged in as other players leave, and told their position in the
+//! queue and how long they will likely wait in the meantime.
+
+use common_net::msg::LoginQueuePosition;
+use specs::Entity;
+use std::{collections::VecDeque, time::Duration};
+
+/// Seconds after which the position is sent again even when it didn't change,
+/// for the estimated wait to stay up to date
+const RESEND_INTERVAL: f64 = 10.0;
+/// Number of the last players let in from the queue the wait is estimated from
+const ADMISSION_HISTORY: usize = 10;
+
+struct QueuedLogin {
+    entity: Entity,
+    /// Position last sent to the client, and when
+    last_sent: Option<(u32, f64)>,
+}
+
+#[derive(Default)]
+pub struct LoginQueue {
+    queued: Vec<QueuedLogin>,
+    /// When the last players who had to wait were let in
+    admissions: VecDeque<f64>,
+}
+
+impl LoginQueue {
+    /// Forgets the clients for which `keep` returns false, like the ones which
+    /// disconnected
+    pub fn retain(&mut self, mut keep: impl FnMut(Entity) -> bool) {
+        self.queued.retain(|login| keep(login.entity));
+    }
+
+    fn index(&mut self, entity: Entity) -> usize {
+        match self.queued.iter().position(|login| login.entity == entity) {
+            Some(index) => index,
+            None => {
+                self.queued.push(QueuedLogin {
+                    entity,
+                    last_sent: None,
+                });
+                self.queued.len() - 1
+            },
+        }
+    }
+
+    /// Lets the client in if it is among the first `free_slots` of the queue,
+    /// adding it at the end of the queue otherwise. Returns whether it was let
+    /// in.
+    pub fn admit(&mut self, entity: Entity, free_slots: usize, time: f64) -> bool {
+        let index = self.index(entity);
+        if index >= free_slots {
+            return false;
+        }
+        if self.queued.remove(index).last_sent.is_some() {
+            if self.admissions.len() >= ADMISSION_HISTORY {
+                self.admissions.pop_front();
+            }
+            self.admissions.push_back(time);
+        }
+        true
+    }
+
+    /// Position to send to a client held in the queue, when it changed or was
+    /// last sent a while ago
+    pub fn position_update(&mut self, entity: Entity, time: f64) -> Option<LoginQueuePosition> {
+        let index = self.index(entity);
+        let position = index as u32 + 1;
+        // Players were let in once every `interval` seconds lately
+        let estimated_wait = self.admissions.front().map(|oldest| {
+            let interval = (time - oldest) / self.admissions.len() as f64;
+            Duration::from_secs_f64(interval.max(0.0) * position as f64)
+        });
+        let login = &mut self.queued[index];
+        let due = login.last_sent.map_or(true, |(last_position, sent_at)| {
+            last_position != position || time - sent_at >= RESEND_INTERVAL
+        });
+        due.then(|| {
+            login.last_sent = Some((position, time));
+            LoginQueuePosition {
+                position,
+                estimated_wait,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::{Builder, World, WorldExt};
+
+    #[test]
+    fn players_are_let_in_in_order() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.create_entity().build());
+        let mut queue = LoginQueue::default();
+
+        assert!(!queue.admit(a, 0, 0.0));
+        assert!(!queue.admit(b, 0, 0.0));
+        assert_eq!(queue.position_update(a, 0.0).map(|q| q.position), Some(1));
+        let b_position = queue.position_update(b, 0.0);
+        assert_eq!(b_position.map(|q| q.position), Some(2));
+        assert_eq!(b_position.and_then(|q| q.estimated_wait), None);
+        assert_eq!(queue.position_update(b, 1.0), None);
+
+        // A slot frees up, only the first in the queue gets it
+        assert!(!queue.admit(b, 1, 30.0));
+        assert!(queue.admit(a, 1, 30.0));
+        let b_position = queue.position_update(b, 40.0).unwrap();
+        assert_eq!(b_position.position, 1);
+        assert_eq!(b_position.estimated_wait, Some(Duration::from_secs(10)));
+
+        // Players who didn't have to wait don't count towards the estimate
+        queue.retain(|entity| entity != b);
+        assert!(queue.admit(c, 1, 50.0));
+        assert_eq!(queue.admissions.len(), 1);
+    }
+}
@@ -0,0 +1,209 @@
+use common::{grid::Grid, terrain::TerrainChunkSize, vol::RectVolSize};
+use common_net::msg::{world_msg::SiteKind, WorldMapMsg};
+use hashbrown::{HashMap, HashSet};
+use image::{Rgba, RgbaImage};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{error, info};
+use vek::*;
+
+const MAP_TILES_DIR: &str = "map_tiles";
+const EXPLORED_FILENAME: &str = "explored.ron";
+/// Chunks along each side of a tile
+pub const TILE_CHUNKS: i32 = 32;
+/// Pixels along each side of a chunk in the tiles
+pub const CHUNK_PIXELS: u32 = 8;
+
+/// What the tiles are drawn from, taken from the world map sent to the clients
+struct MapSource {
+    size: Vec2<i32>,
+    max_height: f32,
+    rgba: Grid<u32>,
+    alt: Grid<u32>,
+    /// Chunk and kind of the sites
+    sites: Vec<(Vec2<i32>, SiteKind)>,
+}
+
+impl MapSource {
+    fn color(&self, chunk: Vec2<i32>) -> Option<Rgb<u8>> {
+        let [r, g, b, _] = self.rgba.get(chunk)?.to_le_bytes();
+        Some(Rgb::new(r, g, b))
+    }
+
+    /// Altitude of the chunk in blocks, relative to the lowest one possible
+    fn alt(&self, chunk: Vec2<i32>) -> Option<f32> {
+        self.alt
+            .get(chunk)
+            .map(|alt| ((alt >> 3) & 0x1FFF) as f32 / 8191.0 * self.max_height)
+    }
+}
+
+/// Renders the parts of the world explored by the players to PNG images of
+/// `TILE_CHUNKS` chunks each in `map_tiles/` of the data directory, named
+/// `<x>_<y>.png` after their position counted from the north-west corner of
+/// the world. Chunks which weren't explored yet are left transparent.
+pub struct MapTiles {
+    dir: PathBuf,
+    source: Arc<MapSource>,
+    explored: HashSet<Vec2<i32>>,
+    /// Tiles with chunks explored since they were last rendered
+    stale: HashSet<Vec2<i32>>,
+}
+
+impl MapTiles {
+    pub fn load(data_dir: &Path, map: &WorldMapMsg) -> Self {
+        let dir = data_dir.join(MAP_TILES_DIR);
+        let explored: HashSet<Vec2<i32>> = match fs::File::open(dir.join(EXPLORED_FILENAME)) {
+            Ok(file) => ron::de::from_reader(file).unwrap_or_else(|err| {
+                error!(?err, "Failed to parse the explored chunks, starting over");
+                HashSet::new()
+            }),
+            Err(_) => {
+                info!("No explored chunks found in {}", dir.display());
+                HashSet::new()
+            },
+        };
+        let source = MapSource {
+            size: map.rgba.size(),
+            max_height: map.max_height,
+            rgba: map.rgba.clone(),
+            alt: map.alt.clone(),
+            sites: map
+                .sites
+                .iter()
+                .map(|site| {
+                    (
+                        site.wpos / TerrainChunkSize::RECT_SIZE.map(|e| e as i32),
+                        site.kind.clone(),
+                    )
+                })
+                .collect(),
+        };
+        // Tiles are rendered again on startup, in case the world changed
+        let mut map_tiles = Self {
+            dir,
+            source: Arc::new(source),
+            explored: HashSet::new(),
+            stale: HashSet::new(),
+        };
+        for chunk in explored {
+            map_tiles.explore(chunk);
+        }
+        map_tiles
+    }
+
+    pub fn explore(&mut self, chunk: Vec2<i32>) {
+        if self.explored.insert(chunk) {
+            let tile = self.tile_of(chunk);
+            self.stale.insert(tile);
+        }
+    }
+
+    /// The tile the chunk is drawn in
+    fn tile_of(&self, chunk: Vec2<i32>) -> Vec2<i32> {
+        image_pos(self.source.size, chunk).map(|e| e.div_euclid(TILE_CHUNKS))
+    }
+
+    /// Renders the stale tiles and saves the explored chunks, to be run as a
+    /// slow job. `None` if no tile is stale.
+    pub fn render_job(&mut self) -> Option<impl FnOnce() + Send + Sync + 'static> {
+        if self.stale.is_empty() {
+            return None;
+        }
+        let mut tiles = HashMap::<Vec2<i32>, Vec<Vec2<i32>>>::new();
+        for chunk in self.explored.iter() {
+            let tile = self.tile_of(*chunk);
+            if self.stale.contains(&tile) {
+                tiles.entry(tile).or_default().push(*chunk);
+            }
+        }
+        self.stale.clear();
+        let dir = self.dir.clone();
+        let source = Arc::clone(&self.source);
+        let explored = self.explored.iter().copied().collect::<Vec<_>>();
+        Some(move || {
+            if let Err(err) = fs::create_dir_all(&dir) {
+                error!(?err, "Failed to create the map tiles directory");
+                return;
+            }
+            for (tile, chunks) in tiles {
+                let path = dir.join(format!("{}_{}.png", tile.x, tile.y));
+                if let Err(err) = render_tile(&source, tile, &chunks).save(&path) {
+                    error!(?err, "Failed to save the map tile {}", path.display());
+                }
+            }
+            let result = ron::ser::to_string(&explored)
+                .map_err(|err| err.to_string())
+                .and_then(|ron| {
+                    fs::write(dir.join(EXPLORED_FILENAME), ron).map_err(|err| err.to_string())
+                });
+            if let Err(err) = result {
+                error!(?err, "Failed to save the explored chunks");
+            }
+        })
+    }
+}
+
+/// Position of the chunk in the map image, whose y axis points south
+fn image_pos(size: Vec2<i32>, chunk: Vec2<i32>) -> Vec2<i32> {
+    Vec2::new(chunk.x, size.y - 1 - chunk.y)
+}
+
+fn render_tile(source: &MapSource, tile: Vec2<i32>, chunks: &[Vec2<i32>]) -> RgbaImage {
+    let side = TILE_CHUNKS as u32 * CHUNK_PIXELS;
+    let mut image = RgbaImage::new(side, side);
+    let mut fill = |chunk: Vec2<i32>, color: Rgb<u8>, inset: u32| {
+        let pixel =
+            (image_pos(source.size, chunk) - tile * TILE_CHUNKS).map(|e| e as u32) * CHUNK_PIXELS;
+        for x in inset..CHUNK_PIXELS - inset {
+            for y in inset..CHUNK_PIXELS - inset {
+                image.put_pixel(
+                    pixel.x + x,
+                    pixel.y + y,
+                    Rgba([color.r, color.g, color.b, 255]),
+                );
+            }
+        }
+    };
+    for chunk in chunks {
+        let color = match source.color(*chunk) {
+            Some(color) => color,
+            None => continue,
+        };
+        // Slopes facing the light from the north-west are lit, the others in the
+        // shade
+        let shade = match (source.alt(*chunk), source.alt(*chunk + Vec2::new(-1, 1))) {
+            (Some(alt), Some(lit_from)) => (1.0 + (alt - lit_from) / 32.0).clamp(0.6, 1.4),
+            _ => 1.0,
+        };
+        fill(
+            *chunk,
+            color.map(|e| (e as f32 * shade).min(255.0) as u8),
+            0,
+        );
+    }
+    let chunks = chunks.iter().collect::<HashSet<_>>();
+    for (chunk, kind) in source
+        .sites
+        .iter()
+        .filter(|(chunk, _)| chunks.contains(chunk))
+    {
+        fill(*chunk, site_color(kind), CHUNK_PIXELS / 4);
+    }
+    image
+}
+
+fn site_color(kind: &SiteKind) -> Rgb<u8> {
+    match kind {
+        SiteKind::Town => Rgb::new(255, 220, 80),
+        SiteKind::Dungeon { .. } => Rgb::new(200, 40, 40),
+        SiteKind::Castle => Rgb::new(160, 160, 180),
+        SiteKind::Cave => Rgb::new(90, 60, 40),
+        SiteKind::Tree => Rgb::new(40, 160, 60),
+        SiteKind::Gnarling => Rgb::new(150, 90, 30),
+        SiteKind::ChapelSite => Rgb::new(120, 200, 255),
+    }
+}
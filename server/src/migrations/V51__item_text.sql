@@ -0,0 +1,2 @@
+-- Adds the text written in items by players, such as books
+ALTER TABLE item ADD COLUMN text TEXT;
@@ -0,0 +1,13 @@
+-- Creates the quest_log table, holding the active and completed quests of
+-- each character
+CREATE TABLE "quest_log" (
+      "character_id" INT NOT NULL,
+      "quest_log" TEXT NOT NULL,
+      PRIMARY KEY("character_id"),
+      FOREIGN KEY("character_id") REFERENCES "character"("character_id")
+);
+
+-- Inserts an empty quest log for everyone
+INSERT INTO quest_log
+SELECT c.character_id, '{"active":[],"completed":[]}'
+FROM character c
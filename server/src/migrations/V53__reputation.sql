@@ -0,0 +1,13 @@
+-- Creates the reputation table, holding the standing of each character with
+-- the factions
+CREATE TABLE "reputation" (
+      "character_id" INT NOT NULL,
+      "reputation" TEXT NOT NULL,
+      PRIMARY KEY("character_id"),
+      FOREIGN KEY("character_id") REFERENCES "character"("character_id")
+);
+
+-- Inserts the default reputation for everyone
+INSERT INTO reputation
+SELECT c.character_id, '{"standings":[]}'
+FROM character c
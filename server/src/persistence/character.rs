@@ -15,6 +15,8 @@ use crate::{
             convert_body_from_database, convert_body_to_database_json,
             convert_character_from_database, convert_inventory_from_database_items,
             convert_items_to_database_items, convert_loadout_from_database_items,
+            convert_quest_log_from_database, convert_quest_log_to_database,
+            convert_reputation_from_database, convert_reputation_to_database,
             convert_skill_groups_to_database, convert_skill_set_from_database,
             convert_stats_from_database, convert_waypoint_from_database_json,
             convert_waypoint_to_database_json,
@@ -67,13 +69,15 @@ pub fn load_items(connection: &Connection, root: i64) -> Result<Vec<Item>, Persi
             parent_container_item_id,
             item_definition_id,
             stack_size,
-            position
+            position,
+            text
         ) AS (
             SELECT  item_id,
                     parent_container_item_id,
                     item_definition_id,
                     stack_size,
-                    position
+                    position,
+                    text
             FROM item
             WHERE parent_container_item_id = ?1
             UNION ALL
@@ -81,7 +85,8 @@ pub fn load_items(connection: &Connection, root: i64) -> Result<Vec<Item>, Persi
                     item.parent_container_item_id,
                     item.item_definition_id,
                     item.stack_size,
-                    item.position
+                    item.position,
+                    item.text
             FROM item, items_tree
             WHERE item.parent_container_item_id = items_tree.item_id
         )
@@ -97,6 +102,7 @@ pub fn load_items(connection: &Connection, root: i64) -> Result<Vec<Item>, Persi
                 item_definition_id: row.get(2)?,
                 stack_size: row.get(3)?,
                 position: row.get(4)?,
+                text: row.get(5)?,
             })
         })?
         .filter_map(Result::ok)
@@ -254,6 +260,34 @@ pub fn load_character_data(
         })
     })?;
 
+    let mut stmt = connection.prepare_cached(
+        "
+            SELECT  quest_log
+            FROM    quest_log
+            WHERE   character_id = ?1",
+    )?;
+
+    let quest_log_data = stmt.query_row(&[char_id], |row| {
+        Ok(QuestLog {
+            character_id: char_id,
+            quest_log: row.get(0)?,
+        })
+    })?;
+
+    let mut stmt = connection.prepare_cached(
+        "
+            SELECT  reputation
+            FROM    reputation
+            WHERE   character_id = ?1",
+    )?;
+
+    let reputation_data = stmt.query_row(&[char_id], |row| {
+        Ok(Reputation {
+            character_id: char_id,
+            reputation: row.get(0)?,
+        })
+    })?;
+
     Ok(PersistedComponents {
         body: convert_body_from_database(&body_data.variant, &body_data.body_data)?,
         stats: convert_stats_from_database(character_data.alias),
@@ -268,6 +302,8 @@ pub fn load_character_data(
         pets,
         active_abilities: convert_active_abilities_from_database(&ability_set_data),
         map_marker: char_map_marker,
+        quest_log: convert_quest_log_from_database(&quest_log_data),
+        reputation: convert_reputation_from_database(&reputation_data),
     })
 }
 
@@ -362,6 +398,8 @@ pub fn create_character(
         pets: _,
         active_abilities,
         map_marker,
+        quest_log,
+        reputation,
     } = persisted_components;
 
     // Fetch new entity IDs for character, inventory and loadout
@@ -379,6 +417,7 @@ pub fn create_character(
             parent_container_item_id: WORLD_PSEUDO_CONTAINER_ID,
             item_definition_id: CHARACTER_PSEUDO_CONTAINER_DEF_ID.to_owned(),
             position: character_id.to_string(),
+            text: None,
         },
         Item {
             stack_size: 1,
@@ -386,6 +425,7 @@ pub fn create_character(
             parent_container_item_id: character_id,
             item_definition_id: INVENTORY_PSEUDO_CONTAINER_DEF_ID.to_owned(),
             position: INVENTORY_PSEUDO_CONTAINER_POSITION.to_owned(),
+            text: None,
         },
         Item {
             stack_size: 1,
@@ -393,6 +433,7 @@ pub fn create_character(
             parent_container_item_id: character_id,
             item_definition_id: LOADOUT_PSEUDO_CONTAINER_DEF_ID.to_owned(),
             position: LOADOUT_PSEUDO_CONTAINER_POSITION.to_owned(),
+            text: None,
         },
     ];
 
@@ -490,6 +531,36 @@ pub fn create_character(
     ])?;
     drop(stmt);
 
+    let db_quest_log = convert_quest_log_to_database(character_id, &quest_log);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO quest_log (character_id,
+                               quest_log)
+        VALUES (?1, ?2)",
+    )?;
+
+    stmt.execute(&[
+        &character_id as &dyn ToSql,
+        &db_quest_log.quest_log as &dyn ToSql,
+    ])?;
+    drop(stmt);
+
+    let db_reputation = convert_reputation_to_database(character_id, &reputation);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO reputation (character_id,
+                                reputation)
+        VALUES (?1, ?2)",
+    )?;
+
+    stmt.execute(&[
+        &character_id as &dyn ToSql,
+        &db_reputation.reputation as &dyn ToSql,
+    ])?;
+    drop(stmt);
+
     // Insert default inventory and loadout item records
     let mut inserts = Vec::new();
 
@@ -510,8 +581,9 @@ pub fn create_character(
                           parent_container_item_id,
                           item_definition_id,
                           stack_size,
-                          position)
-        VALUES (?1, ?2, ?3, ?4, ?5)",
+                          position,
+                          text)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
     )?;
 
     for item in inserts {
@@ -521,6 +593,7 @@ pub fn create_character(
             &item.model.item_definition_id,
             &item.model.stack_size,
             &item.model.position,
+            &item.model.text,
         ])?;
     }
     drop(stmt);
@@ -623,6 +696,17 @@ pub fn delete_character(
         delete_pets(transaction, char_id, Rc::new(pet_ids))?;
     }
 
+    // Delete guild membership
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    guild_member
+        WHERE   character_id = ?1",
+    )?;
+
+    stmt.execute(&[&char_id])?;
+    drop(stmt);
+
     // Delete ability sets
     let mut stmt = transaction.prepare_cached(
         "
@@ -634,6 +718,28 @@ pub fn delete_character(
     stmt.execute(&[&char_id])?;
     drop(stmt);
 
+    // Delete quest log
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    quest_log
+        WHERE   character_id = ?1",
+    )?;
+
+    stmt.execute(&[&char_id])?;
+    drop(stmt);
+
+    // Delete reputation
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    reputation
+        WHERE   character_id = ?1",
+    )?;
+
+    stmt.execute(&[&char_id])?;
+    drop(stmt);
+
     // Delete character
     let mut stmt = transaction.prepare_cached(
         "
@@ -951,6 +1057,8 @@ pub fn update(
     char_waypoint: Option<comp::Waypoint>,
     active_abilities: comp::ability::ActiveAbilities,
     map_marker: Option<comp::MapMarker>,
+    quest_log: comp::QuestLog,
+    reputation: comp::Reputation,
     transaction: &mut Transaction,
 ) -> Result<(), PersistenceError> {
     // Run pet persistence
@@ -1035,8 +1143,9 @@ pub fn update(
                           parent_container_item_id,
                           item_definition_id,
                           stack_size,
-                          position)
-            VALUES  (?1, ?2, ?3, ?4, ?5)",
+                          position,
+                          text)
+            VALUES  (?1, ?2, ?3, ?4, ?5, ?6)",
         )?;
 
         for item in upserted_items.iter() {
@@ -1046,6 +1155,7 @@ pub fn update(
                 &item.item_definition_id,
                 &item.stack_size,
                 &item.position,
+                &item.text,
             ])?;
         }
     }
@@ -1116,5 +1226,49 @@ pub fn update(
         )));
     }
 
+    let db_quest_log = convert_quest_log_to_database(char_id, &quest_log);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        UPDATE  quest_log
+        SET     quest_log = ?1
+        WHERE   character_id = ?2
+    ",
+    )?;
+
+    let quest_log_count = stmt.execute(&[
+        &db_quest_log.quest_log as &dyn ToSql,
+        &char_id as &dyn ToSql,
+    ])?;
+
+    if quest_log_count != 1 {
+        return Err(PersistenceError::OtherError(format!(
+            "Error updating quest_log table for char_id {}",
+            char_id,
+        )));
+    }
+
+    let db_reputation = convert_reputation_to_database(char_id, &reputation);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        UPDATE  reputation
+        SET     reputation = ?1
+        WHERE   character_id = ?2
+    ",
+    )?;
+
+    let reputation_count = stmt.execute(&[
+        &db_reputation.reputation as &dyn ToSql,
+        &char_id as &dyn ToSql,
+    ])?;
+
+    if reputation_count != 1 {
+        return Err(PersistenceError::OtherError(format!(
+            "Error updating reputation table for char_id {}",
+            char_id,
+        )));
+    }
+
     Ok(())
 }
@@ -1,11 +1,17 @@
 use crate::persistence::{
     character::EntityId,
-    models::{AbilitySets, Character, Item, SkillGroup},
+    models::{
+        AbilitySets, Character, Item, QuestLog as DbQuestLog, Reputation as DbReputation,
+        SkillGroup,
+    },
 };
 
 use crate::persistence::{
     error::PersistenceError,
-    json_models::{self, CharacterPosition, DatabaseAbilitySet, GenericBody, HumanoidBody},
+    json_models::{
+        self, CharacterPosition, DatabaseAbilitySet, DatabaseQuestLog, DatabaseReputation,
+        GenericBody, HumanoidBody,
+    },
 };
 use common::{
     character::CharacterId,
@@ -176,6 +182,7 @@ pub fn convert_items_to_database_items(
                     } else {
                         1
                     },
+                    text: item.text().map(String::from),
                 },
                 // Continue to remember the atomic, in case we detect an error later and want
                 // to roll back to preserve liveness.
@@ -385,6 +392,8 @@ pub fn convert_inventory_from_database_items(
             })?;
         }
 
+        item.set_text(db_item.text.clone());
+
         // Insert item into inventory
 
         // Slot position
@@ -722,3 +731,54 @@ pub fn convert_active_abilities_from_database(ability_sets: &AbilitySets) -> Act
         });
     json_models::active_abilities_from_db_model(ability_sets)
 }
+
+pub fn convert_quest_log_to_database(
+    character_id: CharacterId,
+    quest_log: &QuestLog,
+) -> DbQuestLog {
+    let quest_log = json_models::quest_log_to_db_model(quest_log);
+    DbQuestLog {
+        character_id,
+        quest_log: serde_json::to_string(&quest_log).unwrap_or_default(),
+    }
+}
+
+pub fn convert_quest_log_from_database(quest_log: &DbQuestLog) -> QuestLog {
+    let db_quest_log = serde_json::from_str::<DatabaseQuestLog>(&quest_log.quest_log)
+        .unwrap_or_else(|err| {
+            common_base::dev_panic!(format!(
+                "Failed to parse quest log. Error: {:#?}\nQuest log:\n{:#?}",
+                err, quest_log.quest_log
+            ));
+            DatabaseQuestLog {
+                active: Vec::new(),
+                completed: Vec::new(),
+            }
+        });
+    json_models::quest_log_from_db_model(db_quest_log)
+}
+
+pub fn convert_reputation_to_database(
+    character_id: CharacterId,
+    reputation: &Reputation,
+) -> DbReputation {
+    let reputation = json_models::reputation_to_db_model(reputation);
+    DbReputation {
+        character_id,
+        reputation: serde_json::to_string(&reputation).unwrap_or_default(),
+    }
+}
+
+pub fn convert_reputation_from_database(reputation: &DbReputation) -> Reputation {
+    let db_reputation = serde_json::from_str::<DatabaseReputation>(&reputation.reputation)
+        .unwrap_or_else(|err| {
+            common_base::dev_panic!(format!(
+                "Failed to parse reputation. Error: {:#?}\nReputation:\n{:#?}",
+                err, reputation.reputation
+            ));
+            DatabaseReputation {
+                standings: Vec::new(),
+            }
+        });
+    json_models::reputation_from_db_model(db_reputation)
+}
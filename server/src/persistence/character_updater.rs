@@ -1,4 +1,4 @@
-use crate::comp;
+use crate::{comp, guild::Guild};
 use common::character::CharacterId;
 
 use crate::persistence::{
@@ -26,6 +26,8 @@ pub type CharacterUpdateData = (
     Option<comp::Waypoint>,
     comp::ability::ActiveAbilities,
     Option<comp::MapMarker>,
+    comp::QuestLog,
+    comp::Reputation,
 );
 
 pub type PetPersistenceData = (comp::Pet, comp::Body, comp::Stats);
@@ -51,6 +53,8 @@ pub enum CharacterUpdaterEvent {
         requesting_player_uuid: String,
         character_id: CharacterId,
     },
+    UpdateGuild(Guild),
+    DisbandGuild(String),
     DisconnectedSuccess,
 }
 
@@ -192,6 +196,16 @@ impl CharacterUpdater {
                                 ),
                             }
                         },
+                        CharacterUpdaterEvent::UpdateGuild(guild) => {
+                            if let Err(e) = execute_guild_update(&guild, &mut conn) {
+                                error!("Error updating guild {}, error: {:?}", guild.name, e);
+                            }
+                        },
+                        CharacterUpdaterEvent::DisbandGuild(name) => {
+                            if let Err(e) = execute_guild_disband(&name, &mut conn) {
+                                error!("Error disbanding guild {}, error: {:?}", name, e);
+                            }
+                        },
                         CharacterUpdaterEvent::DisconnectedSuccess => {
                             info!(
                                 "CharacterUpdater received DisconnectedSuccess event, resuming \
@@ -322,6 +336,29 @@ impl CharacterUpdater {
         }
     }
 
+    /// Saves the roster of a guild, creating the guild if needed
+    pub fn update_guild(&mut self, guild: Guild) {
+        if let Err(e) = self
+            .update_tx
+            .as_ref()
+            .unwrap()
+            .send(CharacterUpdaterEvent::UpdateGuild(guild))
+        {
+            error!(?e, "Could not send guild update");
+        }
+    }
+
+    pub fn disband_guild(&mut self, name: String) {
+        if let Err(e) = self
+            .update_tx
+            .as_ref()
+            .unwrap()
+            .send(CharacterUpdaterEvent::DisbandGuild(name))
+        {
+            error!(?e, "Could not send guild disbanding");
+        }
+    }
+
     /// Updates a collection of characters based on their id and components
     pub fn batch_update<'a>(
         &mut self,
@@ -334,6 +371,8 @@ impl CharacterUpdater {
                 Option<&'a comp::Waypoint>,
                 &'a comp::ability::ActiveAbilities,
                 Option<&'a comp::MapMarker>,
+                Option<&'a comp::QuestLog>,
+                Option<&'a comp::Reputation>,
             ),
         >,
     ) {
@@ -347,6 +386,8 @@ impl CharacterUpdater {
                     waypoint,
                     active_abilities,
                     map_marker,
+                    quest_log,
+                    reputation,
                 )| {
                     (
                         character_id,
@@ -357,6 +398,8 @@ impl CharacterUpdater {
                             waypoint.cloned(),
                             active_abilities.clone(),
                             map_marker.cloned(),
+                            quest_log.cloned().unwrap_or_default(),
+                            reputation.cloned().unwrap_or_default(),
                         ),
                     )
                 },
@@ -399,7 +442,10 @@ fn execute_batch_update(
     transaction.set_drop_behavior(DropBehavior::Rollback);
     trace!("Transaction started for character batch update");
     updates.into_iter().try_for_each(
-        |(character_id, (stats, inventory, pets, waypoint, active_abilities, map_marker))| {
+        |(
+            character_id,
+            (stats, inventory, pets, waypoint, active_abilities, map_marker, quest_log, reputation),
+        )| {
             super::character::update(
                 character_id,
                 stats,
@@ -408,6 +454,8 @@ fn execute_batch_update(
                 waypoint,
                 active_abilities,
                 map_marker,
+                quest_log,
+                reputation,
                 &mut transaction,
             )
         },
@@ -462,14 +510,33 @@ fn execute_character_delete(
     connection: &mut VelorenConnection,
 ) -> Result<CharacterLoaderResponse, PersistenceError> {
     let mut transaction = connection.connection.transaction()?;
-    let result = CharacterLoaderResponseKind::CharacterList(super::character::delete_character(
-        requesting_player_uuid,
-        character_id,
-        &mut transaction,
-    ));
+    let result = CharacterLoaderResponseKind::CharacterDeletion(
+        super::character::delete_character(requesting_player_uuid, character_id, &mut transaction)
+            .map(|list| (character_id, list)),
+    );
     check_response(entity, transaction, result)
 }
 
+fn execute_guild_update(
+    guild: &Guild,
+    connection: &mut VelorenConnection,
+) -> Result<(), PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    super::guild::update_guild(guild, &mut transaction)?;
+    transaction.commit()?;
+    Ok(())
+}
+
+fn execute_guild_disband(
+    name: &str,
+    connection: &mut VelorenConnection,
+) -> Result<(), PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    super::guild::delete_guild(name, &mut transaction)?;
+    transaction.commit()?;
+    Ok(())
+}
+
 fn check_response(
     entity: Entity,
     transaction: Transaction,
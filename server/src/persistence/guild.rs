@@ -0,0 +1,132 @@
+//! Database operations for guilds and their rosters
+
+use super::{error::PersistenceError, establish_connection, ConnectionMode, DatabaseSettings};
+use crate::guild::{Guild, GuildMember};
+use common::{character::CharacterId, comp::guild::GuildRank};
+use hashbrown::HashMap;
+use rusqlite::{ToSql, Transaction, NO_PARAMS};
+use tracing::warn;
+
+fn rank_to_database(rank: GuildRank) -> &'static str {
+    match rank {
+        GuildRank::Member => "Member",
+        GuildRank::Officer => "Officer",
+        GuildRank::Leader => "Leader",
+    }
+}
+
+fn rank_from_database(rank: &str) -> GuildRank {
+    match rank {
+        "Leader" => GuildRank::Leader,
+        "Officer" => GuildRank::Officer,
+        "Member" => GuildRank::Member,
+        _ => {
+            warn!("Unknown guild rank {}, treating it as a member", rank);
+            GuildRank::Member
+        },
+    }
+}
+
+/// Loads all the guilds, executed once during server startup
+pub fn load_guilds(settings: &DatabaseSettings) -> Result<Vec<Guild>, PersistenceError> {
+    let mut conn = establish_connection(settings, ConnectionMode::ReadOnly);
+    let transaction = conn.connection.transaction()?;
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        SELECT  guild.name,
+                guild_member.character_id,
+                character.alias,
+                guild_member.rank
+        FROM    guild_member
+        JOIN    guild ON guild.guild_id = guild_member.guild_id
+        JOIN    character ON character.character_id = guild_member.character_id",
+    )?;
+
+    let mut guilds = HashMap::<String, Guild>::new();
+    let rows = stmt.query_map(NO_PARAMS, |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, CharacterId>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+    for row in rows {
+        let (guild_name, character_id, alias, rank) = row?;
+        guilds
+            .entry(guild_name.clone())
+            .or_insert_with(|| Guild {
+                name: guild_name,
+                members: HashMap::new(),
+            })
+            .members
+            .insert(character_id, GuildMember {
+                name: alias,
+                rank: rank_from_database(&rank),
+            });
+    }
+
+    Ok(guilds.into_values().collect())
+}
+
+/// Creates the guild if needed and replaces its roster
+pub fn update_guild(guild: &Guild, transaction: &mut Transaction) -> Result<(), PersistenceError> {
+    let mut stmt = transaction.prepare_cached("INSERT OR IGNORE INTO guild (name) VALUES (?1)")?;
+    stmt.execute(&[&guild.name])?;
+    drop(stmt);
+
+    let mut stmt = transaction.prepare_cached("SELECT guild_id FROM guild WHERE name = ?1")?;
+    let guild_id = stmt.query_row(&[&guild.name], |row| row.get::<_, i64>(0))?;
+    drop(stmt);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    guild_member
+        WHERE   guild_id = ?1",
+    )?;
+    stmt.execute(&[&guild_id])?;
+    drop(stmt);
+
+    // Members whose character was deleted in the meantime are skipped
+    let mut stmt = transaction.prepare_cached(
+        "
+        REPLACE
+        INTO    guild_member (character_id,
+                              guild_id,
+                              rank)
+        SELECT  ?1, ?2, ?3
+        WHERE   EXISTS (SELECT 1 FROM character WHERE character_id = ?1)",
+    )?;
+    for (character_id, member) in guild.members.iter() {
+        stmt.execute(&[
+            character_id as &dyn ToSql,
+            &guild_id,
+            &rank_to_database(member.rank),
+        ])?;
+    }
+
+    Ok(())
+}
+
+pub fn delete_guild(name: &str, transaction: &mut Transaction) -> Result<(), PersistenceError> {
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    guild_member
+        WHERE   guild_id IN (SELECT guild_id FROM guild WHERE name = ?1)",
+    )?;
+    stmt.execute(&[&name])?;
+    drop(stmt);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    guild
+        WHERE   name = ?1",
+    )?;
+    stmt.execute(&[&name])?;
+
+    Ok(())
+}
@@ -3,6 +3,7 @@ use common_base::dev_panic;
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use std::string::ToString;
+use tracing::warn;
 use vek::{Vec2, Vec3};
 
 #[derive(Serialize, Deserialize)]
@@ -285,3 +286,89 @@ pub fn active_abilities_from_db_model(
         .collect::<HashMap<_, _>>();
     comp::ability::ActiveAbilities::new(ability_sets)
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct DatabaseActiveQuest {
+    /// Asset id of the quest definition, the objectives are loaded from it
+    pub id: String,
+    pub progress: Vec<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DatabaseQuestLog {
+    pub active: Vec<DatabaseActiveQuest>,
+    pub completed: Vec<String>,
+}
+
+pub fn quest_log_to_db_model(quest_log: &comp::QuestLog) -> DatabaseQuestLog {
+    DatabaseQuestLog {
+        active: quest_log
+            .active
+            .iter()
+            .map(|quest| DatabaseActiveQuest {
+                id: quest.id.clone(),
+                progress: quest.progress.clone(),
+            })
+            .collect(),
+        completed: quest_log.completed.clone(),
+    }
+}
+
+pub fn quest_log_from_db_model(quest_log: DatabaseQuestLog) -> comp::QuestLog {
+    use common::assets::AssetExt;
+
+    comp::QuestLog {
+        active: quest_log
+            .active
+            .into_iter()
+            .filter_map(|DatabaseActiveQuest { id, progress }| {
+                // Quests removed from the assets are dropped
+                let def = comp::quest::QuestDef::load(&id)
+                    .map_err(|err| warn!(?err, ?id, "Dropping quest which can't be loaded"))
+                    .ok()?;
+                let mut quest = comp::quest::ActiveQuest::new(id, &def.read());
+                for (progress, saved) in quest.progress.iter_mut().zip(progress) {
+                    *progress = saved;
+                }
+                Some(quest)
+            })
+            .collect(),
+        completed: quest_log.completed,
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DatabaseStanding {
+    pub faction: String,
+    pub standing: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DatabaseReputation {
+    pub standings: Vec<DatabaseStanding>,
+}
+
+pub fn reputation_to_db_model(reputation: &comp::Reputation) -> DatabaseReputation {
+    DatabaseReputation {
+        standings: reputation
+            .standings()
+            .map(|(faction, standing)| DatabaseStanding {
+                faction: faction.to_string(),
+                standing,
+            })
+            .collect(),
+    }
+}
+
+pub fn reputation_from_db_model(reputation: DatabaseReputation) -> comp::Reputation {
+    comp::Reputation::from_standings(reputation.standings.into_iter().filter_map(
+        |DatabaseStanding { faction, standing }| {
+            // Standings with factions removed from the game are dropped
+            let faction = faction
+                .parse::<comp::faction::Faction>()
+                .map_err(|err| warn!(?err, ?faction, "Dropping standing with unknown faction"))
+                .ok()?;
+            Some((faction, standing))
+        },
+    ))
+}
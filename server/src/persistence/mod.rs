@@ -5,6 +5,7 @@ pub mod character_loader;
 pub mod character_updater;
 mod diesel_to_rusqlite;
 pub mod error;
+pub mod guild;
 mod json_models;
 mod models;
 
@@ -32,6 +33,8 @@ pub struct PersistedComponents {
     pub pets: Vec<PetPersistenceData>,
     pub active_abilities: comp::ActiveAbilities,
     pub map_marker: Option<comp::MapMarker>,
+    pub quest_log: comp::QuestLog,
+    pub reputation: comp::Reputation,
 }
 
 pub type EditableComponents = (comp::Body,);
@@ -184,6 +187,54 @@ pub fn vacuum_database(settings: &DatabaseSettings) {
     info!("Database vacuumed");
 }
 
+/// Copies the database into the `backups` directory next to it, returning the
+/// path of the copy. Unlike copying the file, this gives a consistent copy
+/// while the server keeps writing to the database.
+pub fn backup_database(settings: &DatabaseSettings) -> Result<PathBuf, error::PersistenceError> {
+    let backups_dir = settings.db_dir.join("backups");
+    fs::create_dir_all(&backups_dir)
+        .map_err(|err| error::PersistenceError::OtherError(err.to_string()))?;
+    let path = backups_dir.join(format!(
+        "db-{}.sqlite",
+        chrono::Utc::now().format("%Y-%m-%d-%H%M%S")
+    ));
+
+    let conn = establish_connection(settings, ConnectionMode::ReadOnly);
+    conn.execute("VACUUM INTO ?1", &[&path.to_string_lossy().into_owned()])?;
+
+    Ok(path)
+}
+
+/// Deletes the oldest backups made by [`backup_database`], keeping the `keep`
+/// newest ones, and always at least the newest. Returns how many were
+/// deleted.
+pub fn prune_backups(
+    settings: &DatabaseSettings,
+    keep: usize,
+) -> Result<usize, error::PersistenceError> {
+    let to_error = |err: std::io::Error| error::PersistenceError::OtherError(err.to_string());
+    let mut backups = fs::read_dir(settings.db_dir.join("backups"))
+        .map_err(to_error)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| {
+                    name.starts_with("db-") && name.ends_with(".sqlite")
+                })
+        })
+        .collect::<Vec<_>>();
+    // The names hold the time of the backup, so they sort from oldest to newest
+    backups.sort();
+
+    let old = backups.len().saturating_sub(keep.max(1));
+    for path in &backups[..old] {
+        fs::remove_file(path).map_err(to_error)?;
+    }
+
+    Ok(old)
+}
+
 // These callbacks use info logging because they are never enabled by default,
 // only when explicitly turned on via CLI arguments or interactive CLI commands.
 // Setting them to anything other than info would remove the ability to get SQL
@@ -12,6 +12,8 @@ pub struct Item {
     pub item_definition_id: String,
     pub stack_size: i32,
     pub position: String,
+    /// Text written in the item by a player
+    pub text: Option<String>,
 }
 
 pub struct Body {
@@ -40,3 +42,13 @@ pub struct AbilitySets {
     pub entity_id: i64,
     pub ability_sets: String,
 }
+
+pub struct QuestLog {
+    pub character_id: i64,
+    pub quest_log: String,
+}
+
+pub struct Reputation {
+    pub character_id: i64,
+    pub reputation: String,
+}
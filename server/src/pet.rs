@@ -1,6 +1,6 @@
 use crate::{client::Client, events::update_map_markers};
 use common::{
-    comp::{self, anchor::Anchor, group::GroupManager, Agent, Alignment, Pet},
+    comp::{self, anchor::Anchor, faction::Faction, group::GroupManager, Agent, Alignment, Pet},
     uid::Uid,
 };
 use common_net::msg::ServerGeneral;
@@ -37,6 +37,8 @@ fn tame_pet_internal(ecs: &specs::World, pet_entity: Entity, owner: Entity, pet:
     let _ = ecs
         .write_storage()
         .insert(pet_entity, common::comp::Alignment::Owned(owner_uid));
+    // Pets side with their owner instead of their former faction
+    ecs.write_storage::<Faction>().remove(pet_entity);
 
     // Anchor the pet to the player to prevent it de-spawning
     // when its chunk is unloaded if its owner is still logged
@@ -49,17 +51,21 @@ fn tame_pet_internal(ecs: &specs::World, pet_entity: Entity, owner: Entity, pet:
         .write_storage()
         .insert(pet_entity, pet.unwrap_or_default());
 
-    // Create an agent for this entity using its body
+    // Create an agent for this entity using its body. Either way the pet
+    // follows its owner now, rather than staying around where it spawned.
+    let mut agents = ecs.write_storage::<Agent>();
     if let Some(body) = ecs.read_storage().get(pet_entity) {
-        let _ = ecs
-            .write_storage()
-            .insert(pet_entity, Agent::from_body(body));
+        let _ = agents.insert(pet_entity, Agent::from_body(body));
+    } else if let Some(agent) = agents.get_mut(pet_entity) {
+        agent.patrol_origin = None;
     }
+    drop(agents);
 
     // Add to group system
     let clients = ecs.read_storage::<Client>();
     let mut group_manager = ecs.write_resource::<GroupManager>();
     let map_markers = ecs.read_storage::<comp::MapMarker>();
+    let shared_map_markers = ecs.read_storage::<comp::SharedMapMarkers>();
     group_manager.new_pet(
         pet_entity,
         owner,
@@ -78,7 +84,7 @@ fn tame_pet_internal(ecs: &specs::World, pet_entity: Entity, owner: Entity, pet:
                 .map(|(g, c)| {
                     // Might be unneccessary, but maybe pets can somehow have map
                     // markers in the future
-                    update_map_markers(&map_markers, &uids, c, &group_change);
+                    update_map_markers(&map_markers, &shared_map_markers, &uids, c, &group_change);
                     c.send_fallible(ServerGeneral::GroupUpdate(g));
                 });
         },
@@ -0,0 +1,74 @@
+use crate::ron_file;
+use common::building::{self, BuildMaterial};
+use hashbrown::HashMap;
+use std::path::{Path, PathBuf};
+use vek::*;
+
+const PLACED_BLOCKS_FILENAME: &str = "placed_blocks.ron";
+
+/// Blocks placed by players from the building materials in their inventory,
+/// grouped by the chunk they are in. Only these give their material back when
+/// taken down, never the generated terrain looking the same.
+///
+/// They are only saved in the data directory when the terrain is persisted,
+/// the blocks being gone after a restart otherwise.
+#[derive(Default)]
+pub struct PlacedBlocks {
+    path: Option<PathBuf>,
+    /// Item of the material each block was placed from
+    chunks: HashMap<Vec2<i32>, HashMap<Vec3<i32>, String>>,
+    /// Whether there are changes which weren't saved yet
+    dirty: bool,
+}
+
+impl PlacedBlocks {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(PLACED_BLOCKS_FILENAME);
+        let chunks = ron_file::load(&path, "placed blocks");
+        Self {
+            path: Some(path),
+            chunks,
+            dirty: false,
+        }
+    }
+
+    /// Saves the changes made since the last time
+    pub fn save(&mut self) {
+        let path = match &self.path {
+            Some(path) if self.dirty => path,
+            _ => return,
+        };
+        ron_file::save(path, "placed blocks", &self.chunks);
+        self.dirty = false;
+    }
+
+    pub fn place(&mut self, chunk_key: Vec2<i32>, pos: Vec3<i32>, material: &BuildMaterial) {
+        self.chunks
+            .entry(chunk_key)
+            .or_default()
+            .insert(pos, material.item.to_string());
+        self.dirty = true;
+    }
+
+    /// The material the block at the position was placed from, `None` if it
+    /// wasn't placed by a player
+    pub fn material_at(
+        &self,
+        chunk_key: Vec2<i32>,
+        pos: Vec3<i32>,
+    ) -> Option<&'static BuildMaterial> {
+        building::material_of_item(self.chunks.get(&chunk_key)?.get(&pos)?)
+    }
+
+    /// Forgets the block, once it was taken down or replaced
+    pub fn remove(&mut self, chunk_key: Vec2<i32>, pos: Vec3<i32>) {
+        if let Some(chunk) = self.chunks.get_mut(&chunk_key) {
+            if chunk.remove(&pos).is_some() {
+                self.dirty = true;
+            }
+            if chunk.is_empty() {
+                self.chunks.remove(&chunk_key);
+            }
+        }
+    }
+}
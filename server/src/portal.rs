@@ -0,0 +1,191 @@
+use crate::{ron_file, state_ext::StateExt};
+use common::comp::{
+    self, object,
+    portal::{DiscoveredPortals, Portal, PortalId, PortalInfo},
+    LightEmitter,
+};
+use common_state::State;
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use specs::{Builder, Entity as EcsEntity, WorldExt};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+use vek::*;
+
+const PORTALS_FILENAME: &str = "portals.ron";
+
+#[derive(Debug)]
+pub enum PortalError<'a> {
+    InvalidName(String),
+    DuplicateName(String),
+    DoesNotExist(&'a str),
+}
+
+impl<'a> fmt::Display for PortalError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(
+                f,
+                "Portal name '{}' is invalid. Names may only contain lowercase ASCII and \
+                 underscores",
+                name
+            ),
+            Self::DuplicateName(name) => write!(f, "Portal '{}' already exists", name),
+            Self::DoesNotExist(name) => write!(f, "Portal '{}' does not exist", name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PortalNode {
+    pub name: String,
+    pub pos: Vec3<f32>,
+    pub links: HashSet<PortalId>,
+}
+
+/// Portals placed by admins and the links between them, making up the fast
+/// travel graph. It is saved in the data directory since portals are entities
+/// which get removed along with the chunk they are in.
+#[derive(Default)]
+pub struct PortalNetwork {
+    path: PathBuf,
+    portals: BTreeMap<PortalId, PortalNode>,
+    /// Entities of the portals which are currently loaded
+    entities: HashMap<PortalId, EcsEntity>,
+}
+
+impl PortalNetwork {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(PORTALS_FILENAME);
+        let portals = ron_file::load(&path, "portals");
+        Self {
+            path,
+            portals,
+            entities: HashMap::new(),
+        }
+    }
+
+    fn save(&self) { ron_file::save(&self.path, "portals", &self.portals); }
+
+    pub fn get(&self, id: PortalId) -> Option<&PortalNode> { self.portals.get(&id) }
+
+    pub fn find<'a>(&self, name: &'a str) -> Result<PortalId, PortalError<'a>> {
+        self.portals
+            .iter()
+            .find(|(_, portal)| portal.name == name)
+            .map(|(id, _)| *id)
+            .ok_or(PortalError::DoesNotExist(name))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&PortalId, &PortalNode)> { self.portals.iter() }
+
+    pub fn create(
+        &mut self,
+        name: String,
+        pos: Vec3<f32>,
+    ) -> Result<PortalId, PortalError<'static>> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+            return Err(PortalError::InvalidName(name));
+        }
+        if self.find(&name).is_ok() {
+            return Err(PortalError::DuplicateName(name));
+        }
+        let id = self.portals.keys().next_back().map_or(0, |id| id + 1);
+        self.portals.insert(id, PortalNode {
+            name,
+            pos,
+            links: HashSet::new(),
+        });
+        self.save();
+        Ok(id)
+    }
+
+    /// Removes the portal, returning its entity if it is loaded
+    pub fn remove(&mut self, id: PortalId) -> Option<EcsEntity> {
+        self.portals.remove(&id);
+        for portal in self.portals.values_mut() {
+            portal.links.remove(&id);
+        }
+        self.save();
+        self.entities.remove(&id)
+    }
+
+    /// Links or unlinks two portals, both ways
+    pub fn set_linked(&mut self, a: PortalId, b: PortalId, linked: bool) {
+        for (from, to) in [(a, b), (b, a)] {
+            if let Some(portal) = self.portals.get_mut(&from) {
+                if linked {
+                    portal.links.insert(to);
+                } else {
+                    portal.links.remove(&to);
+                }
+            }
+        }
+        self.save();
+    }
+
+    pub fn info(&self, id: PortalId) -> Option<PortalInfo> {
+        self.portals.get(&id).map(|portal| PortalInfo {
+            name: portal.name.clone(),
+            pos: portal.pos,
+            links: portal.links.iter().copied().collect(),
+        })
+    }
+
+    /// Brings what the player knows about their portals up to date, forgetting
+    /// the removed ones
+    pub fn refresh(&self, discovered: &mut DiscoveredPortals) {
+        let ids = discovered.0.keys().copied().collect::<Vec<_>>();
+        for id in ids {
+            match self.info(id) {
+                Some(info) => {
+                    discovered.0.insert(id, info);
+                },
+                None => {
+                    discovered.0.remove(&id);
+                },
+            }
+        }
+    }
+}
+
+/// Spawns the entities of the portals whose chunk got loaded
+pub fn spawn_loaded_portals(state: &mut State) {
+    let to_spawn = {
+        let network = state.ecs().read_resource::<PortalNetwork>();
+        let terrain = state.terrain();
+        network
+            .iter()
+            .filter(|(id, portal)| {
+                network
+                    .entities
+                    .get(id)
+                    .map_or(true, |entity| !state.ecs().is_alive(*entity))
+                    && terrain
+                        .get_key(terrain.pos_key(portal.pos.map(|e| e.floor() as i32)))
+                        .is_some()
+            })
+            .map(|(id, portal)| (*id, portal.name.clone(), portal.pos))
+            .collect::<Vec<_>>()
+    };
+    for (id, name, pos) in to_spawn {
+        let entity = state
+            .create_object(comp::Pos(pos), object::Body::SeaLantern)
+            .with(Portal { id, name })
+            .with(LightEmitter {
+                col: Rgb::new(0.6, 0.4, 1.0),
+                strength: 4.0,
+                flicker: 1.0,
+                animated: true,
+            })
+            .build();
+        state
+            .ecs()
+            .write_resource::<PortalNetwork>()
+            .entities
+            .insert(id, entity);
+    }
+}
@@ -0,0 +1,31 @@
+//! Saving and loading of the server data kept in RON files of the data
+//! directory, such as the farms, portals and signs.
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fs, path::Path};
+use tracing::{error, info};
+
+/// Loads the data saved at the path, falling back to the default when there is
+/// no file yet or it can't be parsed. `what` names the data in the logs.
+pub fn load<T: DeserializeOwned + Default>(path: &Path, what: &str) -> T {
+    match fs::File::open(path) {
+        Ok(file) => ron::de::from_reader(file).unwrap_or_else(|err| {
+            error!(?err, "Failed to parse the {}, starting without any", what);
+            T::default()
+        }),
+        Err(_) => {
+            info!("No {} file found at {}", what, path.display());
+            T::default()
+        },
+    }
+}
+
+/// Saves the data at the path, logging the error when it fails
+pub fn save<T: Serialize>(path: &Path, what: &str, data: &T) {
+    let result = ron::ser::to_string_pretty(data, ron::ser::PrettyConfig::default())
+        .map_err(|err| err.to_string())
+        .and_then(|ron| fs::write(path, ron).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        error!(?err, "Failed to save the {}", what);
+    }
+}
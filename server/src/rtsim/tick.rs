@@ -12,6 +12,7 @@ use common::{
 use common_ecs::{Job, Origin, Phase, System};
 use specs::{Join, Read, ReadExpect, ReadStorage, WriteExpect, WriteStorage};
 use std::sync::Arc;
+use tracing::warn;
 
 #[derive(Default)]
 pub struct Sys;
@@ -172,10 +173,16 @@ impl<'a> System<'a> for Sys {
                         rtsim_entity,
                         projectile: None,
                     },
-                    // EntityConfig can't represent Waypoints at all
-                    // as of now, and if someone will try to spawn
-                    // rtsim waypoint it is definitely error.
-                    NpcData::Waypoint(_) => unimplemented!(),
+                    // EntityConfig can't represent Waypoints or Minigames at all
+                    // as of now, so the entity is left simulated by rtsim
+                    NpcData::Waypoint(_) | NpcData::Minigame(_) => {
+                        warn!(
+                            "Rtsim entity {} can't be spawned as a waypoint or minigame",
+                            id
+                        );
+                        rtsim.assimilate_entity(id);
+                        continue;
+                    },
                 }
             };
             server_emitter.emit(event);
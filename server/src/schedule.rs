@@ -0,0 +1,204 @@
+//! Recurring tasks the server runs on the schedule set in its settings:
+//! messages broadcast to the players, restarts announced with a countdown,
+//! world events started through chat commands and backups of the database.
+
+use crate::{events::Event, persistence, settings::Settings, state_ext::StateExt, Server};
+use chrono::{DateTime, NaiveTime, Utc};
+use common::comp::ChatType;
+use common_net::msg::ServerGeneral;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tracing::{info, warn};
+
+/// When a task runs
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Schedule {
+    /// Every given number of seconds, starting from when the server starts
+    Every { secs: u64 },
+    /// Once a day, at the given time in UTC
+    Daily { hour: u32, minute: u32 },
+}
+
+impl Schedule {
+    /// First time the task runs after `now`
+    pub fn next_after(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Schedule::Every { secs } => now + chrono::Duration::seconds((*secs).max(1) as i64),
+            Schedule::Daily { hour, minute } => {
+                let time = NaiveTime::from_hms_opt(*hour, *minute, 0)
+                    .unwrap_or_else(|| NaiveTime::from_hms(0, 0, 0));
+                let today = DateTime::from_utc(now.naive_utc().date().and_time(time), Utc);
+                if today > now {
+                    today
+                } else {
+                    today + chrono::Duration::days(1)
+                }
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TaskAction {
+    /// Sends a message to every player
+    Broadcast(String),
+    /// Shuts the server down after warning the players during the countdown,
+    /// for the process supervisor to start it again
+    Restart {
+        countdown_secs: u64,
+        message: String,
+    },
+    /// Runs a chat command with every permission, like the server console
+    /// does, to start world events for instance
+    Command { name: String, args: Vec<String> },
+    /// Copies the database into the `backups` directory next to it, deleting
+    /// the oldest backups so that only the `keep` newest ones are left
+    Backup {
+        #[serde(default = "default_backups_kept")]
+        keep: usize,
+    },
+}
+
+fn default_backups_kept() -> usize { 7 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledTask {
+    /// Name of the task, shown by the `schedule` command
+    pub name: String,
+    pub schedule: Schedule,
+    pub action: TaskAction,
+}
+
+/// When each of the scheduled tasks of the settings runs next
+#[derive(Default)]
+pub struct Scheduler {
+    next_runs: Vec<DateTime<Utc>>,
+}
+
+impl Scheduler {
+    pub fn new(tasks: &[ScheduledTask], now: DateTime<Utc>) -> Self {
+        Self {
+            next_runs: tasks
+                .iter()
+                .map(|task| task.schedule.next_after(now))
+                .collect(),
+        }
+    }
+
+    /// Tasks due at `now`, whose next run is scheduled
+    pub fn due(&mut self, tasks: &[ScheduledTask], now: DateTime<Utc>) -> Vec<ScheduledTask> {
+        if self.next_runs.len() != tasks.len() {
+            *self = Self::new(tasks, now);
+        }
+        self.next_runs
+            .iter_mut()
+            .zip(tasks)
+            .filter(|(next_run, _)| **next_run <= now)
+            .map(|(next_run, task)| {
+                *next_run = task.schedule.next_after(now);
+                task.clone()
+            })
+            .collect()
+    }
+
+    /// When the task at the given index of the settings runs next
+    pub fn next_run(&self, task: usize) -> Option<DateTime<Utc>> {
+        self.next_runs.get(task).copied()
+    }
+}
+
+impl Server {
+    /// Runs the scheduled tasks that are due, returning the restarts for the
+    /// frontend to carry out
+    pub(crate) fn run_scheduled_tasks(&mut self) -> Vec<Event> {
+        let due = {
+            let ecs = self.state.ecs();
+            let settings = ecs.read_resource::<Settings>();
+            ecs.write_resource::<Scheduler>()
+                .due(&settings.schedule, Utc::now())
+        };
+
+        let mut frontend_events = Vec::new();
+        for task in due {
+            info!(?task.name, "Running scheduled task");
+            match task.action {
+                TaskAction::Broadcast(msg) => self
+                    .state
+                    .notify_players(ServerGeneral::server_msg(ChatType::Meta, msg)),
+                TaskAction::Restart {
+                    countdown_secs,
+                    message,
+                } => frontend_events.push(Event::Restart {
+                    countdown: Duration::from_secs(countdown_secs),
+                    message,
+                }),
+                TaskAction::Command { name, args } => {
+                    for reply in self.execute_console_command(name, args) {
+                        info!("{}", reply);
+                    }
+                },
+                TaskAction::Backup { keep } => {
+                    let database_settings = Arc::clone(&self.database_settings);
+                    self.runtime.spawn_blocking(move || {
+                        let settings = database_settings
+                            .read()
+                            .expect("DatabaseSettings RwLock was poisoned")
+                            .clone();
+                        match persistence::backup_database(&settings) {
+                            Ok(path) => info!(?path, "Backed up the database"),
+                            Err(error) => {
+                                warn!(?error, "Failed to back up the database");
+                                // Don't delete the old backups when the new one is missing
+                                return;
+                            },
+                        }
+                        match persistence::prune_backups(&settings, keep) {
+                            Ok(0) => {},
+                            Ok(deleted) => info!(?deleted, "Deleted old database backups"),
+                            Err(error) => warn!(?error, "Failed to delete old database backups"),
+                        }
+                    });
+                },
+            }
+        }
+        frontend_events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn tasks_run_again_after_their_schedule() {
+        let now = Utc.ymd(2022, 5, 1).and_hms(12, 0, 0);
+        let task = |schedule| ScheduledTask {
+            name: String::new(),
+            schedule,
+            action: TaskAction::Backup { keep: 7 },
+        };
+        let tasks = [
+            task(Schedule::Every { secs: 60 }),
+            task(Schedule::Daily { hour: 4, minute: 0 }),
+        ];
+
+        let mut scheduler = Scheduler::new(&tasks, now);
+        assert_eq!(
+            scheduler.next_run(0),
+            Some(now + chrono::Duration::minutes(1))
+        );
+        assert_eq!(
+            scheduler.next_run(1),
+            Some(Utc.ymd(2022, 5, 2).and_hms(4, 0, 0))
+        );
+
+        assert!(scheduler.due(&tasks, now).is_empty());
+        let later = now + chrono::Duration::minutes(1);
+        assert_eq!(scheduler.due(&tasks, later).len(), 1);
+        assert_eq!(
+            scheduler.next_run(0),
+            Some(later + chrono::Duration::minutes(1))
+        );
+    }
+}
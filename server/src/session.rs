@@ -0,0 +1,69 @@
+//! Sessions of the registered clients. A player whose connection drops while
+//! playing, like when their address changes as they move from Wi-Fi to a
+//! mobile network, doesn't lose their character right away: it stays in the
+//! world for a grace window, during which the client can resume the session
+//! from a new connection with the token it got when it registered.
+
+use common_net::msg::SessionToken;
+use specs::{Component, VecStorage};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct Session {
+    pub token: SessionToken,
+    /// Time the connection of the client dropped at, while the session waits
+    /// to be resumed
+    pub dropped_at: Option<f64>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self {
+            token: SessionToken(rand::random()),
+            dropped_at: None,
+        }
+    }
+
+    /// Whether a new connection may resume the session at the given time. The
+    /// connection of a client may drop without the server noticing before it
+    /// times out, so a session still connected can be taken over too.
+    pub fn resumable(&self, time: f64, grace: Duration) -> bool {
+        self.dropped_at
+            .map_or(true, |dropped_at| time - dropped_at < grace.as_secs_f64())
+    }
+
+    /// Whether the connection of the client dropped longer ago than the grace
+    /// window, ending the session
+    pub fn expired(&self, time: f64, grace: Duration) -> bool { !self.resumable(time, grace) }
+}
+
+impl Component for Session {
+    type Storage = VecStorage<Self>;
+}
+
+/// Session a client asked to resume when it registered, kept while its login
+/// is pending
+#[derive(Debug)]
+pub struct ResumeRequest(pub SessionToken);
+
+impl Component for ResumeRequest {
+    type Storage = VecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_are_resumable_within_the_grace_window() {
+        let grace = Duration::from_secs(60);
+        let mut session = Session::new();
+        // A connected session can be taken over by a new connection
+        assert!(session.resumable(100.0, grace));
+        assert!(!session.expired(100.0, grace));
+
+        session.dropped_at = Some(100.0);
+        assert!(session.resumable(159.0, grace));
+        assert!(session.expired(160.0, grace));
+    }
+}
@@ -13,10 +13,13 @@ pub use banlist::{
 pub use server_description::ServerDescription;
 pub use whitelist::{Whitelist, WhitelistInfo, WhitelistRecord};
 
+use crate::schedule::ScheduledTask;
 use chrono::Utc;
 use common::{
     calendar::{Calendar, CalendarEvent},
+    comp::BuffKind,
     resources::BattleMode,
+    world_border::WorldBorder,
 };
 use core::time::Duration;
 use portpicker::pick_unused_port;
@@ -63,6 +66,81 @@ impl ServerBattleMode {
     }
 }
 
+/// Moving characters between servers, e.g. when a community changes hosts
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CharacterTransferSettings {
+    /// Secret shared by the servers characters are moved between, exported
+    /// characters are signed with it. Characters can't be exported or
+    /// imported without it.
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Whether players can import their characters exported from another
+    /// server
+    #[serde(default)]
+    pub allow_import: bool,
+}
+
+/// Rendering the explored parts of the world to image tiles in the data
+/// directory, which can be served to host a live map of the server
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapTileSettings {
+    pub enabled: bool,
+    /// Real time seconds between two renders of the tiles explored in between
+    pub interval: u64,
+}
+
+impl Default for MapTileSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 300,
+        }
+    }
+}
+
+/// How long the entities left behind in the world are kept before despawning,
+/// in seconds, `None` keeping them until their chunk unloads
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CleanupSettings {
+    /// Items dropped on the ground, counted from when they were dropped
+    pub item_drops: Option<f64>,
+    /// Projectiles, like arrows, stuck in the terrain, counted from their
+    /// impact
+    pub stuck_projectiles: Option<f64>,
+    /// Summoned creatures and pets whose owner despawned or logged out
+    pub orphaned_summons: Option<f64>,
+}
+
+impl Default for CleanupSettings {
+    fn default() -> Self {
+        Self {
+            item_drops: Some(30.0 * 60.0),
+            stuck_projectiles: Some(30.0),
+            orphaned_summons: Some(60.0),
+        }
+    }
+}
+
+/// Voice chat between nearby players, relayed by the server
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoiceChatSettings {
+    pub enabled: bool,
+    /// Distance within which players hear each other, in blocks
+    pub range: f32,
+}
+
+impl Default for VoiceChatSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            range: 40.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Protocol {
     Quic {
@@ -83,14 +161,51 @@ pub struct GameplaySettings {
     pub safe_spawn: bool,
     #[serde(default)]
     pub explosion_burn_marks: bool,
+    /// Whether players can place and remove blocks using the materials in
+    /// their inventory, without build permissions
+    #[serde(default)]
+    pub survival_building: bool,
+    #[serde(default)]
+    pub night: NightSettings,
+    /// Real time minutes an in-game day lasts
+    #[serde(default = "default_day_length")]
+    pub day_length: f64,
 }
 
+fn default_day_length() -> f64 { 30.0 }
+
 impl Default for GameplaySettings {
     fn default() -> Self {
         Self {
             battle_mode: ServerBattleMode::default(),
             safe_spawn: false,
             explosion_burn_marks: true,
+            survival_building: false,
+            night: NightSettings::default(),
+            day_length: default_day_length(),
+        }
+    }
+}
+
+/// Changes to hostile creatures while it's night, and how players can skip it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NightSettings {
+    /// Buffs and their strength given to hostile creatures
+    pub hostile_buffs: Vec<(BuffKind, f32)>,
+    /// Fraction of the online players that have to sleep in a bed to skip the
+    /// night
+    pub sleep_vote_fraction: f32,
+}
+
+impl Default for NightSettings {
+    fn default() -> Self {
+        Self {
+            hostile_buffs: vec![
+                (BuffKind::Frenzied, 0.2),
+                (BuffKind::IncreaseMaxHealth, 50.0),
+            ],
+            sleep_vote_fraction: 0.5,
         }
     }
 }
@@ -103,6 +218,9 @@ pub struct ModerationSettings {
     pub automod: bool,
     #[serde(default)]
     pub admins_exempt: bool,
+    /// Whether players are told when a moderator starts spectating them
+    #[serde(default)]
+    pub notify_spectated: bool,
 }
 
 impl ModerationSettings {
@@ -129,6 +247,7 @@ impl Default for ModerationSettings {
             banned_words_files: Vec::new(),
             automod: false,
             admins_exempt: true,
+            notify_spectated: false,
         }
     }
 }
@@ -163,6 +282,9 @@ pub struct Settings {
     pub metrics_address: SocketAddr,
     pub auth_server_address: Option<String>,
     pub max_players: usize,
+    /// How many players can wait in the login queue for a slot once
+    /// `max_players` are online, admins and whitelisted players skipping it
+    pub login_queue_size: usize,
     pub world_seed: u32,
     pub server_name: String,
     pub start_time: f64,
@@ -172,6 +294,10 @@ pub struct Settings {
     pub max_view_distance: Option<u32>,
     pub max_player_group_size: u32,
     pub client_timeout: Duration,
+    /// How long the character of a player whose connection dropped stays in
+    /// the world, waiting for the client to resume the session from a new
+    /// connection. Zero disables resuming sessions.
+    pub session_grace: Duration,
     pub spawn_town: Option<String>,
     pub max_player_for_kill_broadcast: Option<usize>,
     pub calendar_mode: CalendarMode,
@@ -185,6 +311,20 @@ pub struct Settings {
     pub gameplay: GameplaySettings,
     #[serde(default)]
     pub moderation: ModerationSettings,
+    #[serde(default)]
+    pub character_transfer: CharacterTransferSettings,
+    #[serde(default)]
+    pub map_tiles: MapTileSettings,
+    #[serde(default)]
+    pub cleanup: CleanupSettings,
+    /// Recurring tasks, like broadcasts, restarts and backups
+    #[serde(default)]
+    pub schedule: Vec<ScheduledTask>,
+    /// Edge of the playable area, none by default
+    #[serde(default)]
+    pub world_border: WorldBorder,
+    #[serde(default)]
+    pub voice_chat: VoiceChatSettings,
 }
 
 impl Default for Settings {
@@ -203,17 +343,25 @@ impl Default for Settings {
             world_seed: DEFAULT_WORLD_SEED,
             server_name: "Veloren Server".into(),
             max_players: 100,
+            login_queue_size: 50,
             start_time: 9.0 * 3600.0,
             map_file: None,
             max_view_distance: Some(65),
             max_player_group_size: 6,
             calendar_mode: CalendarMode::Auto,
             client_timeout: Duration::from_secs(40),
+            session_grace: Duration::from_secs(60),
             spawn_town: None,
             max_player_for_kill_broadcast: None,
             experimental_terrain_persistence: false,
             gameplay: GameplaySettings::default(),
             moderation: ModerationSettings::default(),
+            character_transfer: CharacterTransferSettings::default(),
+            map_tiles: MapTileSettings::default(),
+            cleanup: CleanupSettings::default(),
+            schedule: Vec::new(),
+            world_border: WorldBorder::default(),
+            voice_chat: VoiceChatSettings::default(),
         }
     }
 }
@@ -293,6 +441,8 @@ impl Settings {
             start_time: 9.0 * 3600.0,
             max_view_distance: None,
             client_timeout: Duration::from_secs(180),
+            // The singleplayer connection can't move to another address
+            session_grace: Duration::ZERO,
             ..load // Fill in remaining fields from server_settings.ron.
         }
     }
@@ -0,0 +1,111 @@
+use crate::{client::Client, presence::Presence, ron_file, sys::terrain::chunk_in_vd};
+use authc::Uuid;
+use common::{comp::Pos, terrain::TerrainGrid};
+use common_net::msg::ServerGeneral;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use specs::{Join, ReadStorage};
+use std::path::{Path, PathBuf};
+use vek::*;
+
+const SIGNS_FILENAME: &str = "signs.ron";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignText {
+    pub text: String,
+    /// Player who put up the sign, the only one allowed to rewrite it
+    pub author: Uuid,
+}
+
+/// Text written on the signs put up by players, grouped by the chunk they are
+/// in and saved in the data directory
+#[derive(Default)]
+pub struct Signs {
+    path: PathBuf,
+    chunks: HashMap<Vec2<i32>, HashMap<Vec3<i32>, SignText>>,
+    /// Whether there are changes which weren't saved yet
+    dirty: bool,
+}
+
+impl Signs {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(SIGNS_FILENAME);
+        let chunks = ron_file::load(&path, "signs");
+        Self {
+            path,
+            chunks,
+            dirty: false,
+        }
+    }
+
+    /// Saves the changes made since the last time
+    pub fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        ron_file::save(&self.path, "signs", &self.chunks);
+    }
+
+    pub fn get(&self, chunk_key: Vec2<i32>, pos: Vec3<i32>) -> Option<&SignText> {
+        self.chunks.get(&chunk_key)?.get(&pos)
+    }
+
+    pub fn write(&mut self, chunk_key: Vec2<i32>, pos: Vec3<i32>, sign: SignText) {
+        self.chunks.entry(chunk_key).or_default().insert(pos, sign);
+        self.dirty = true;
+    }
+
+    /// Forgets the text of a sign which was taken down, returning whether
+    /// there was any
+    pub fn remove(&mut self, chunk_key: Vec2<i32>, pos: Vec3<i32>) -> bool {
+        let signs = match self.chunks.get_mut(&chunk_key) {
+            Some(signs) => signs,
+            None => return false,
+        };
+        let removed = signs.remove(&pos).is_some();
+        if signs.is_empty() {
+            self.chunks.remove(&chunk_key);
+        }
+        self.dirty |= removed;
+        removed
+    }
+
+    /// Texts of the signs in the chunk, to be sent along with it
+    pub fn texts_in(&self, chunk_key: Vec2<i32>) -> Vec<(Vec3<i32>, String)> {
+        self.chunks.get(&chunk_key).map_or_else(Vec::new, |signs| {
+            signs
+                .iter()
+                .map(|(pos, sign)| (*pos, sign.text.clone()))
+                .collect()
+        })
+    }
+}
+
+/// Sends the texts of the signs to the clients which have them in view, an
+/// empty text for the signs which were taken down
+pub fn send_texts(
+    terrain: &TerrainGrid,
+    clients: &ReadStorage<Client>,
+    presences: &ReadStorage<Presence>,
+    positions: &ReadStorage<Pos>,
+    texts: &[(Vec3<i32>, String)],
+) {
+    for (client, presence, pos) in (clients, presences, positions).join() {
+        let in_view = texts
+            .iter()
+            .filter(|(sign_pos, _)| {
+                chunk_in_vd(
+                    pos.0,
+                    terrain.pos_key(*sign_pos),
+                    terrain,
+                    presence.terrain_view_distance.current(),
+                )
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if !in_view.is_empty() {
+            client.send_fallible(ServerGeneral::SignTexts(in_view));
+        }
+    }
+}
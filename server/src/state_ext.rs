@@ -1,7 +1,8 @@
 use crate::{
-    automod::AutoMod,
+    automod::{ActionErr, ActionNote, AutoMod},
     client::Client,
     events::{self, update_map_markers},
+    guild::{self, Guilds},
     persistence::PersistedComponents,
     pet::restore_pet,
     presence::{Presence, RepositionOnChunkLoad},
@@ -33,6 +34,7 @@ use common_net::{
     sync::WorldSyncExt,
 };
 use common_state::State;
+use hashbrown::HashMap;
 use rand::prelude::*;
 use specs::{
     saveload::MarkerAllocator, Builder, Entity as EcsEntity, EntityBuilder as EcsEntityBuilder,
@@ -126,6 +128,9 @@ pub trait StateExt {
         chat_type: &comp::ChatType<comp::Group>,
         msg: &str,
     ) -> bool;
+    /// Checks the names of the map markers the player shares with their
+    /// group, like chat messages
+    fn validate_map_markers(&self, player: EcsEntity, markers: &[comp::NamedMapMarker]) -> bool;
     fn send_chat(&self, msg: comp::UnresolvedChatMsg);
     fn notify_players(&self, msg: ServerGeneral);
     fn notify_in_game_clients(&self, msg: ServerGeneral);
@@ -272,6 +277,10 @@ impl StateExt for State {
             .with(comp::Buffs::default())
             .with(comp::Combo::default())
             .with(comp::Auras::default())
+            .maybe_with(
+                (body == comp::Body::Object(comp::object::Body::TrainingDummy))
+                    .then(comp::TrainingDummy::default),
+            )
     }
 
     fn create_object(&mut self, pos: comp::Pos, object: comp::object::Body) -> EcsEntityBuilder {
@@ -532,6 +541,7 @@ impl StateExt for State {
             self.write_component_ignore_entity_dead(entity, comp::Buffs::default());
             self.write_component_ignore_entity_dead(entity, comp::Auras::default());
             self.write_component_ignore_entity_dead(entity, comp::Combo::default());
+            self.write_component_ignore_entity_dead(entity, comp::AbilityCooldowns::default());
 
             // Make sure physics components are updated
             self.write_component_ignore_entity_dead(entity, comp::ForceUpdate::forced());
@@ -583,6 +593,8 @@ impl StateExt for State {
             pets,
             active_abilities,
             map_marker,
+            quest_log,
+            reputation,
         } = components;
 
         if let Some(player_uid) = self.read_component_copied::<Uid>(entity) {
@@ -614,6 +626,8 @@ impl StateExt for State {
             self.write_component_ignore_entity_dead(entity, Poise::new(body));
             self.write_component_ignore_entity_dead(entity, stats);
             self.write_component_ignore_entity_dead(entity, active_abilities);
+            self.write_component_ignore_entity_dead(entity, quest_log);
+            self.write_component_ignore_entity_dead(entity, reputation);
             self.write_component_ignore_entity_dead(entity, skill_set);
             self.write_component_ignore_entity_dead(entity, inventory);
             self.write_component_ignore_entity_dead(
@@ -692,6 +706,11 @@ impl StateExt for State {
                         player_info.last_battlemode_change = None;
                     }
                 }
+
+                // Tell the guild that this member is now online
+                if let Some(guild) = self.ecs().read_resource::<Guilds>().guild_of(*char_id) {
+                    guild::send_guild_info(self.ecs(), guild);
+                }
             }
         }
     }
@@ -706,38 +725,51 @@ impl StateExt for State {
         let Some(client) = self.ecs().read_storage::<Client>().get(entity) else { return true };
         let Some(player) = self.ecs().read_storage::<Player>().get(entity) else { return true };
 
-        match automod.validate_chat_msg(
-            player.uuid(),
-            self.ecs()
-                .read_storage::<comp::Admin>()
-                .get(entity)
-                .map(|a| a.0),
-            Instant::now(),
-            chat_type,
-            msg,
-        ) {
-            Ok(note) => {
-                if let Some(note) = note {
-                    let _ = client.send(ServerGeneral::server_msg(
-                        ChatType::CommandInfo,
-                        format!("{}", note),
-                    ));
-                }
-                true
-            },
-            Err(err) => {
-                let _ = client.send(ServerGeneral::server_msg(
-                    ChatType::CommandError,
-                    format!("{}", err),
-                ));
-                false
-            },
-        }
+        notify_automod_result(
+            client,
+            automod.validate_chat_msg(
+                player.uuid(),
+                self.ecs()
+                    .read_storage::<comp::Admin>()
+                    .get(entity)
+                    .map(|a| a.0),
+                Instant::now(),
+                chat_type,
+                msg,
+            ),
+        )
+    }
+
+    fn validate_map_markers(&self, entity: EcsEntity, markers: &[comp::NamedMapMarker]) -> bool {
+        let mut automod = self.ecs().write_resource::<AutoMod>();
+        let Some(client) = self.ecs().read_storage::<Client>().get(entity) else { return true };
+        let Some(player) = self.ecs().read_storage::<Player>().get(entity) else { return true };
+
+        notify_automod_result(
+            client,
+            automod.validate_map_markers(
+                player.uuid(),
+                self.ecs()
+                    .read_storage::<comp::Admin>()
+                    .get(entity)
+                    .map(|a| a.0),
+                Instant::now(),
+                markers.iter().map(|marker| marker.name.as_str()),
+            ),
+        )
     }
 
     /// Send the chat message to the proper players. Say and region are limited
     /// by location. Faction and group are limited by component.
-    fn send_chat(&self, msg: comp::UnresolvedChatMsg) {
+    fn send_chat(&self, mut msg: comp::UnresolvedChatMsg) {
+        // Don't trust what was written to be displayable
+        if msg.chat_type.uid().is_some() {
+            msg.normalize();
+            if msg.message.is_empty() {
+                return;
+            }
+        }
+
         let ecs = self.ecs();
         let is_within =
             |target, a: &comp::Pos, b: &comp::Pos| a.0.distance_squared(b.0) < target * target;
@@ -912,6 +944,7 @@ impl StateExt for State {
                             message: "You are using group chat but do not belong to a group. Use \
                                       /world or /region to change chat."
                                 .into(),
+                            links: Vec::new(),
                         };
 
                         if let Some((client, _)) =
@@ -928,6 +961,37 @@ impl StateExt for State {
                 comp::ChatType::GroupMeta(g) => {
                     send_to_group(g, ecs, &resolved_msg);
                 },
+                comp::ChatType::Guild(from, g) => {
+                    let online = guild::online_characters(ecs);
+                    let guilds = ecs.read_resource::<Guilds>();
+                    let sender_entity =
+                        (*ecs.read_resource::<UidAllocator>()).retrieve_entity_internal(from.0);
+                    let in_guild = sender_entity
+                        .and_then(|e| guild::character_of(ecs, e))
+                        .and_then(|char_id| guilds.guild_of(char_id))
+                        .map_or(false, |guild| &guild.name == g);
+                    if !in_guild {
+                        // guild not found, reply with command error
+                        let reply = comp::ChatMsg {
+                            chat_type: comp::ChatType::CommandError,
+                            message: "You are using guild chat but do not belong to a guild. Use \
+                                      /world or /region to change chat."
+                                .into(),
+                            links: Vec::new(),
+                        };
+                        let clients = ecs.read_storage::<Client>();
+                        if let Some(client) = sender_entity.and_then(|e| clients.get(e)) {
+                            client.send_fallible(ServerGeneral::ChatMsg(reply));
+                        }
+                        return;
+                    }
+                    send_to_guild(g, &guilds, &online, ecs, &resolved_msg);
+                },
+                comp::ChatType::GuildMeta(g) => {
+                    let online = guild::online_characters(ecs);
+                    let guilds = ecs.read_resource::<Guilds>();
+                    send_to_guild(g, &guilds, &online, ecs, &resolved_msg);
+                },
             }
         }
     }
@@ -1006,6 +1070,7 @@ impl StateExt for State {
             let uids = self.ecs().read_storage::<Uid>();
             let mut group_manager = self.ecs().write_resource::<comp::group::GroupManager>();
             let map_markers = self.ecs().read_storage::<comp::MapMarker>();
+            let shared_map_markers = self.ecs().read_storage::<comp::SharedMapMarkers>();
             group_manager.entity_deleted(
                 entity,
                 &mut self.ecs().write_storage(),
@@ -1021,7 +1086,13 @@ impl StateExt for State {
                                 .map(|g| (g, c))
                         })
                         .map(|(g, c)| {
-                            update_map_markers(&map_markers, &uids, c, &group_change);
+                            update_map_markers(
+                                &map_markers,
+                                &shared_map_markers,
+                                &uids,
+                                c,
+                                &group_change,
+                            );
                             c.send_fallible(ServerGeneral::GroupUpdate(g));
                         });
                 },
@@ -1036,6 +1107,19 @@ impl StateExt for State {
             self.ecs().read_storage::<comp::Pos>().get(entity).copied(),
         );
 
+        // Let go of the entities attached to this one
+        if let Some(uid) = maybe_uid {
+            let mut attacheds = self.ecs().write_storage::<comp::Attached>();
+            let detached = (&self.ecs().entities(), &attacheds)
+                .join()
+                .filter(|(_, attached)| attached.to == uid)
+                .map(|(entity, _)| entity)
+                .collect::<Vec<_>>();
+            for entity in detached {
+                attacheds.remove(entity);
+            }
+        }
+
         let res = self.ecs_mut().delete_entity(entity);
         if res.is_ok() {
             if let (Some(uid), Some(pos)) = (maybe_uid, maybe_pos) {
@@ -1065,6 +1149,29 @@ impl StateExt for State {
     }
 }
 
+/// Tells the player why the automod refused what they sent or warns them
+/// about it, returning whether it was accepted
+fn notify_automod_result(client: &Client, result: Result<Option<ActionNote>, ActionErr>) -> bool {
+    match result {
+        Ok(note) => {
+            if let Some(note) = note {
+                let _ = client.send(ServerGeneral::server_msg(
+                    ChatType::CommandInfo,
+                    format!("{}", note),
+                ));
+            }
+            true
+        },
+        Err(err) => {
+            let _ = client.send(ServerGeneral::server_msg(
+                ChatType::CommandError,
+                format!("{}", err),
+            ));
+            false
+        },
+    }
+}
+
 fn send_to_group(g: &Group, ecs: &specs::World, msg: &comp::ChatMsg) {
     for (client, group) in (&ecs.read_storage::<Client>(), &ecs.read_storage::<Group>()).join() {
         if g == group {
@@ -1072,3 +1179,22 @@ fn send_to_group(g: &Group, ecs: &specs::World, msg: &comp::ChatMsg) {
         }
     }
 }
+
+fn send_to_guild(
+    g: &str,
+    guilds: &Guilds,
+    online: &HashMap<CharacterId, EcsEntity>,
+    ecs: &specs::World,
+    msg: &comp::ChatMsg,
+) {
+    let clients = ecs.read_storage::<Client>();
+    for character_id in guilds
+        .get(g)
+        .into_iter()
+        .flat_map(|guild| guild.members.keys())
+    {
+        if let Some(client) = online.get(character_id).and_then(|e| clients.get(*e)) {
+            client.send_fallible(ServerGeneral::ChatMsg(msg.clone()));
+        }
+    }
+}
@@ -7,7 +7,7 @@ pub mod util;
 use crate::{
     rtsim::RtSim,
     sys::agent::{
-        behavior_tree::{BehaviorData, BehaviorTree},
+        behavior_tree::{BehaviorData, BehaviorTree, BEHAVIOR_PROFILES},
         consts::{
             AVG_FOLLOW_DIST, DEFAULT_ATTACK_RANGE, IDLE_HEALING_ITEM_THRESHOLD, PARTIAL_PATH_DIST,
             SEPARATION_BIAS, SEPARATION_DIST,
@@ -15,8 +15,8 @@ use crate::{
         data::{AgentData, AttackData, Path, ReadData, Tactic, TargetData},
         util::{
             aim_projectile, are_our_owners_hostile, entities_have_line_of_sight, get_attacker,
-            get_entity_by_id, is_dead_or_invulnerable, is_dressed_as_cultist, is_invulnerable,
-            is_village_guard, is_villager,
+            get_entity_by_id, is_dead_or_invulnerable, is_dressed_as_cultist, is_faction_hostile,
+            is_invulnerable, is_village_guard, is_villager,
         },
     },
 };
@@ -246,6 +246,7 @@ impl<'a> System<'a> for Sys {
                     // also methods on the `AgentData` struct. Action nodes
                     // are the only parts of this tree that should provide
                     // inputs.
+                    let behavior_profiles = BEHAVIOR_PROFILES.read();
                     let mut behavior_data = BehaviorData {
                         agent,
                         agent_data: data,
@@ -253,6 +254,7 @@ impl<'a> System<'a> for Sys {
                         event_emitter: &mut event_emitter,
                         controller,
                         rng: &mut rng,
+                        profile: behavior_profiles.get(body),
                     };
 
                     BehaviorTree::root().run(&mut behavior_data);
@@ -1686,8 +1688,9 @@ impl<'a> AgentData<'a> {
 
         (entity != *self.entity)
             && !self.passive_towards(entity, read_data)
-            && (are_our_owners_hostile(self.alignment, other_alignment, read_data)
-                || self.remembers_fight_with(entity, read_data)
+            && (is_faction_hostile(*self.entity, entity, read_data).unwrap_or_else(|| {
+                are_our_owners_hostile(self.alignment, other_alignment, read_data)
+            }) || self.remembers_fight_with(entity, read_data)
                 || (is_villager(self.alignment) && is_dressed_as_cultist(entity, read_data)))
     }
 
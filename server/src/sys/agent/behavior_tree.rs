@@ -1,13 +1,16 @@
 use common::{
     comp::{
         agent::{
-            AgentEvent, Target, TimerAction, DEFAULT_INTERACTION_TIME, TRADE_INTERACTION_TIME,
+            AgentEvent, Sound, SoundKind, Target, TimerAction, DEFAULT_INTERACTION_TIME,
+            TRADE_INTERACTION_TIME,
         },
-        Agent, Alignment, BehaviorCapability, BehaviorState, Body, BuffKind, ControlAction,
-        ControlEvent, Controller, InputKind, InventoryEvent, UtteranceKind,
+        Agent, Alignment, BehaviorCapability, BehaviorState, Body, BuffKind, CharacterState,
+        ControlAction, ControlEvent, Controller, InputKind, InventoryEvent, Pos, UtteranceKind,
     },
+    consts::SOUND_TRAVEL_DIST_PER_VOLUME,
     event::{Emitter, ServerEvent},
     path::TraversalConfig,
+    time::DayPeriod,
 };
 use rand::{prelude::ThreadRng, Rng};
 use specs::saveload::{Marker, MarkerAllocator};
@@ -21,14 +24,17 @@ use self::interaction::{
 
 use super::{
     consts::{
-        DAMAGE_MEMORY_DURATION, FLEE_DURATION, HEALING_ITEM_THRESHOLD, MAX_FLEE_DIST,
-        MAX_FOLLOW_DIST, NPC_PICKUP_RANGE, RETARGETING_THRESHOLD_SECONDS,
+        CALL_FOR_HELP_COOLDOWN, DAMAGE_MEMORY_DURATION, FLEE_DURATION, HEALING_ITEM_THRESHOLD,
+        MAX_FLEE_DIST, MAX_FOLLOW_DIST, NPC_PICKUP_RANGE, RETARGETING_THRESHOLD_SECONDS,
     },
     data::{AgentData, ReadData, TargetData},
     util::{get_entity_by_id, is_dead, is_dead_or_invulnerable, is_invulnerable, stop_pursuing},
 };
 
 mod interaction;
+mod profile;
+
+pub use profile::{BehaviorProfile, BEHAVIOR_PROFILES};
 
 /// Struct containing essential data for running a behavior tree
 pub struct BehaviorData<'a, 'b, 'c> {
@@ -38,6 +44,8 @@ pub struct BehaviorData<'a, 'b, 'c> {
     pub event_emitter: &'a mut Emitter<'c, ServerEvent>,
     pub controller: &'a mut Controller,
     pub rng: &'b mut ThreadRng,
+    /// Species specific trees used while idle or fighting
+    pub profile: &'a BehaviorProfile,
 }
 
 /// Behavior function
@@ -120,22 +128,6 @@ impl BehaviorTree {
         }
     }
 
-    /// Hostile BehaviorTree
-    ///
-    /// Attack the target, and heal self if applicable
-    pub fn hostile() -> Self {
-        Self {
-            tree: vec![heal_self_if_hurt, hurt_utterance, do_combat],
-        }
-    }
-
-    /// Idle BehaviorTree
-    pub fn idle() -> Self {
-        Self {
-            tree: vec![set_owner_if_no_target, handle_timed_events],
-        }
-    }
-
     /// Run the behavior tree until an event has been handled
     pub fn run(&self, behavior_data: &mut BehaviorData) -> bool {
         for behavior_fn in self.tree.iter() {
@@ -264,15 +256,18 @@ fn do_target_tree_if_target_else_do_idle_tree(bdata: &mut BehaviorData) -> bool
     if bdata.agent.target.is_some() {
         BehaviorTree::target().run(bdata);
     } else {
-        BehaviorTree::idle().run(bdata);
+        do_idle_tree(bdata);
     }
     false
 }
 
-/// Run the Idle BehaviorTree
+/// Run the idle tree of the agent's behavior profile
 ///
 /// This function can stop the BehaviorTree
-fn do_idle_tree(bdata: &mut BehaviorData) -> bool { BehaviorTree::idle().run(bdata) }
+fn do_idle_tree(bdata: &mut BehaviorData) -> bool {
+    let profile = bdata.profile;
+    profile.run_idle(bdata)
+}
 
 /// If target is dead, forget them
 fn untarget_if_dead(bdata: &mut BehaviorData) -> bool {
@@ -299,7 +294,8 @@ fn untarget_if_dead(bdata: &mut BehaviorData) -> bool {
 fn do_hostile_tree_if_hostile(bdata: &mut BehaviorData) -> bool {
     if let Some(Target { hostile, .. }) = bdata.agent.target {
         if hostile {
-            BehaviorTree::hostile().run(bdata);
+            let profile = bdata.profile;
+            profile.run_hostile(bdata);
             return true;
         }
     }
@@ -315,7 +311,7 @@ fn do_pet_tree_if_owned(bdata: &mut BehaviorData) -> bool {
             BehaviorTree::pet().run(bdata);
         } else {
             bdata.agent.target = None;
-            BehaviorTree::idle().run(bdata);
+            do_idle_tree(bdata);
         }
         return true;
     }
@@ -447,33 +443,80 @@ fn handle_timed_events(bdata: &mut BehaviorData) -> bool {
                     .look_toward(bdata.controller, bdata.read_data, *target);
                 bdata.controller.push_action(ControlAction::Talk);
             }
+            true
         },
         Some(just_ended) => {
             if just_ended {
                 bdata.agent.target = None;
                 bdata.controller.push_action(ControlAction::Stand);
             }
-
-            if bdata.rng.gen::<f32>() < 0.1 {
-                bdata.agent_data.choose_target(
-                    bdata.agent,
-                    bdata.controller,
-                    bdata.read_data,
-                    bdata.event_emitter,
-                );
-            } else {
-                bdata.agent_data.handle_sounds_heard(
-                    bdata.agent,
-                    bdata.controller,
-                    bdata.read_data,
-                    bdata.rng,
-                );
-            }
+            false
         },
     }
+}
+
+/// Walk back home if we wandered or were lured too far away
+fn return_to_patrol(bdata: &mut BehaviorData, max_dist: f32) -> bool {
+    if bdata.agent.rtsim_controller.travel_to.is_some() {
+        return false;
+    }
+    if let Some(patrol_origin) = bdata.agent.patrol_origin {
+        if bdata.agent_data.pos.0.distance_squared(patrol_origin) > max_dist.powi(2) {
+            bdata.agent_data.follow(
+                bdata.agent,
+                bdata.controller,
+                &bdata.read_data.terrain,
+                &Pos(patrol_origin),
+            );
+            return true;
+        }
+    }
+    false
+}
+
+/// Villagers head home at night and rest there until the morning
+fn rest_at_night(bdata: &mut BehaviorData, home_dist: f32) -> bool {
+    if !matches!(bdata.agent_data.alignment, Some(Alignment::Npc))
+        || bdata.agent.rtsim_controller.travel_to.is_some()
+        || DayPeriod::from(bdata.read_data.time_of_day.0).is_light()
+    {
+        return false;
+    }
+    if return_to_patrol(bdata, home_dist) {
+        return true;
+    }
+    bdata.controller.inputs.move_dir = Vec2::zero();
+    if !matches!(
+        bdata.read_data.char_states.get(*bdata.agent_data.entity),
+        Some(CharacterState::Sit)
+    ) {
+        bdata.controller.push_action(ControlAction::Sit);
+    }
+    true
+}
+
+/// Look around for a target once in a while
+fn choose_target(bdata: &mut BehaviorData, chance: f32) -> bool {
+    if bdata.rng.gen::<f32>() < chance {
+        bdata.agent_data.choose_target(
+            bdata.agent,
+            bdata.controller,
+            bdata.read_data,
+            bdata.event_emitter,
+        );
+        return true;
+    }
     false
 }
 
+/// Investigate or flee from the last sound heard, or just idle around
+fn react_to_sounds(bdata: &mut BehaviorData) -> bool {
+    bdata
+        .agent_data
+        .handle_sounds_heard(bdata.agent, bdata.controller, bdata.read_data, bdata.rng);
+    true
+}
+
 /// Try to heal self if our damage went below a certain threshold
 fn heal_self_if_hurt(bdata: &mut BehaviorData) -> bool {
     if bdata.agent_data.damage < HEALING_ITEM_THRESHOLD
@@ -498,6 +541,91 @@ fn hurt_utterance(bdata: &mut BehaviorData) -> bool {
     false
 }
 
+/// Run away from the target while below the flee health of our psyche
+fn flee_if_low_health(bdata: &mut BehaviorData) -> bool {
+    let BehaviorData {
+        agent,
+        agent_data,
+        read_data,
+        event_emitter,
+        controller,
+        rng,
+        ..
+    } = bdata;
+
+    if !agent_data.below_flee_health(agent) {
+        return false;
+    }
+    if let Some(tgt_pos) = agent
+        .target
+        .and_then(|target| read_data.positions.get(target.target))
+    {
+        let dist_sqrd = agent_data.pos.0.distance_squared(tgt_pos.0);
+        let has_opportunity_to_flee = agent.action_state.timer < FLEE_DURATION;
+        let within_flee_distance = dist_sqrd < MAX_FLEE_DIST.powi(2);
+
+        // FIXME: Using action state timer to see if allowed to speak is a hack.
+        if agent.action_state.timer == 0.0 {
+            agent_data.cry_out(agent, event_emitter, read_data);
+            agent.action_state.timer = 0.01;
+        } else if within_flee_distance && has_opportunity_to_flee {
+            agent_data.flee(agent, controller, tgt_pos, &read_data.terrain);
+            agent.action_state.timer += read_data.dt.0;
+        } else {
+            agent.action_state.timer = 0.0;
+            agent.target = None;
+            agent_data.idle(agent, controller, read_data, rng);
+        }
+        return true;
+    }
+    false
+}
+
+/// Alert creatures of the same species nearby, so they join the fight
+///
+/// This function will never stop the BehaviorTree
+fn call_for_help(bdata: &mut BehaviorData, dist: f32) -> bool {
+    if let (Some(Target { target, .. }), Some(body)) = (bdata.agent.target, bdata.agent_data.body) {
+        let time = bdata.read_data.time.0;
+        if bdata.agent.timer.time_since_exceeds(
+            time,
+            TimerAction::CallForHelp,
+            CALL_FOR_HELP_COOLDOWN,
+        ) {
+            bdata.agent.timer.start(time, TimerAction::CallForHelp);
+            bdata.event_emitter.emit(ServerEvent::Sound {
+                sound: Sound::new(
+                    SoundKind::CallForHelp {
+                        caller: *body,
+                        target,
+                    },
+                    bdata.agent_data.pos.0,
+                    dist / SOUND_TRAVEL_DIST_PER_VOLUME,
+                    time,
+                ),
+            });
+        }
+    }
+    false
+}
+
+/// Give up on the target and head back once too far away from home
+fn leash_to_spawn(bdata: &mut BehaviorData, leash_dist: f32) -> bool {
+    if let Some(patrol_origin) = bdata.agent.patrol_origin {
+        if bdata.agent_data.pos.0.distance_squared(patrol_origin) > leash_dist.powi(2) {
+            bdata.agent.target = None;
+            bdata.agent_data.follow(
+                bdata.agent,
+                bdata.controller,
+                &bdata.read_data.terrain,
+                &Pos(patrol_origin),
+            );
+            return true;
+        }
+    }
+    false
+}
+
 fn do_combat(bdata: &mut BehaviorData) -> bool {
     let BehaviorData {
         agent,
@@ -506,6 +634,7 @@ fn do_combat(bdata: &mut BehaviorData) -> bool {
         event_emitter,
         controller,
         rng,
+        ..
     } = bdata;
 
     if let Some(Target {
@@ -543,23 +672,7 @@ fn do_combat(bdata: &mut BehaviorData) -> bool {
             }
             let aggro_on = *aggro_on;
 
-            if agent_data.below_flee_health(agent) {
-                let has_opportunity_to_flee = agent.action_state.timer < FLEE_DURATION;
-                let within_flee_distance = dist_sqrd < MAX_FLEE_DIST.powi(2);
-
-                // FIXME: Using action state timer to see if allowed to speak is a hack.
-                if agent.action_state.timer == 0.0 {
-                    agent_data.cry_out(agent, event_emitter, read_data);
-                    agent.action_state.timer = 0.01;
-                } else if within_flee_distance && has_opportunity_to_flee {
-                    agent_data.flee(agent, controller, tgt_pos, &read_data.terrain);
-                    agent.action_state.timer += read_data.dt.0;
-                } else {
-                    agent.action_state.timer = 0.0;
-                    agent.target = None;
-                    agent_data.idle(agent, controller, read_data, rng);
-                }
-            } else if is_dead(target, read_data) {
+            if is_dead(target, read_data) {
                 agent_data.exclaim_relief_about_enemy_dead(agent, event_emitter);
                 agent.target = None;
                 agent_data.idle(agent, controller, read_data, rng);
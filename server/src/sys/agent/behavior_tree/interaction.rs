@@ -1,6 +1,6 @@
 use common::{
     comp::{
-        agent::{AgentEvent, Target, TimerAction},
+        agent::{AgentEvent, SoundKind, Target, TimerAction},
         compass::{Direction, Distance},
         dialogue::{MoodContext, MoodState, Subject},
         invite::{InviteKind, InviteResponse},
@@ -13,7 +13,10 @@ use common::{
 use rand::{thread_rng, Rng};
 use specs::saveload::Marker;
 
-use crate::{rtsim::entity::PersonalityTrait, sys::agent::util::get_entity_by_id};
+use crate::{
+    rtsim::entity::PersonalityTrait,
+    sys::agent::util::{get_entity_by_id, is_dead_or_invulnerable},
+};
 
 use super::{BehaviorData, BehaviorTree};
 
@@ -27,7 +30,22 @@ pub fn process_inbox_sound_and_hurt(bdata: &mut BehaviorData) -> bool {
             let sound = bdata.agent.inbox.pop_front();
             match sound {
                 Some(AgentEvent::ServerSound(sound)) => {
-                    bdata.agent.sounds_heard.push(sound);
+                    if let SoundKind::CallForHelp { caller, target } = sound.kind {
+                        // Join the fight if we aren't already busy with one
+                        let answers_call = bdata.agent.target.map_or(true, |t| !t.hostile)
+                            && bdata
+                                .agent_data
+                                .body
+                                .map_or(false, |body| body.is_same_species_as(&caller))
+                            && !is_dead_or_invulnerable(target, bdata.read_data)
+                            && bdata.agent_data.is_enemy(target, bdata.read_data);
+                        if answers_call {
+                            bdata.agent.target =
+                                Some(Target::new(target, true, bdata.read_data.time.0, true));
+                        }
+                    } else {
+                        bdata.agent.sounds_heard.push(sound);
+                    }
                 },
                 Some(AgentEvent::Hurt) => {
                     // Hurt utterances at random upon receiving damage
@@ -344,7 +362,12 @@ pub fn handle_inbox_talk(bdata: &mut BehaviorData) -> bool {
                                 agent_data.chat_npc(msg, event_emitter);
                             }
                         },
-                        Subject::Work => {},
+                        Subject::Work => {
+                            event_emitter.emit(ServerEvent::OfferQuest {
+                                npc: *agent_data.entity,
+                                target,
+                            });
+                        },
                     }
                 }
             }
@@ -0,0 +1,144 @@
+use common::{
+    assets::{self, AssetExt, AssetHandle},
+    comp::Body,
+    npc::{species_names, NPC_NAMES},
+};
+use hashbrown::HashMap;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+use super::{
+    call_for_help, choose_target, do_combat, flee_if_low_health, handle_timed_events,
+    heal_self_if_hurt, hurt_utterance, leash_to_spawn, react_to_sounds, rest_at_night,
+    return_to_patrol, set_owner_if_no_target, BehaviorData,
+};
+
+lazy_static! {
+    pub static ref BEHAVIOR_PROFILES: AssetHandle<BehaviorProfiles> =
+        BehaviorProfiles::load_expect("server.manifests.agent_behaviors");
+}
+
+/// Node of the tree run while the agent has a hostile target
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub enum HostileNode {
+    /// Use a healing item when badly hurt
+    HealSelf,
+    /// Cry out in pain when hit
+    HurtUtterance,
+    /// Run away from the target when below the flee health of the psyche
+    FleeIfLowHealth,
+    /// Get creatures of the same species within the given distance (limited
+    /// by how far they can hear) to join the fight
+    CallForHelp(f32),
+    /// Give up on the target and head back when further than the given
+    /// distance from where the agent spawned
+    LeashToSpawn(f32),
+    /// Chase and attack the target
+    Combat,
+}
+
+impl HostileNode {
+    fn run(self, bdata: &mut BehaviorData) -> bool {
+        match self {
+            HostileNode::HealSelf => heal_self_if_hurt(bdata),
+            HostileNode::HurtUtterance => hurt_utterance(bdata),
+            HostileNode::FleeIfLowHealth => flee_if_low_health(bdata),
+            HostileNode::CallForHelp(dist) => call_for_help(bdata, dist),
+            HostileNode::LeashToSpawn(dist) => leash_to_spawn(bdata, dist),
+            HostileNode::Combat => do_combat(bdata),
+        }
+    }
+}
+
+/// Node of the tree run while the agent has nothing better to do
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub enum IdleNode {
+    /// Pets go back to their owner once in a while
+    FollowOwner,
+    /// Keep looking at whoever the agent is talking or trading with
+    Interact,
+    /// Walk back when further than the given distance from where the agent
+    /// spawned
+    ReturnToPatrol(f32),
+    /// Villagers go back within the given distance from where they spawned at
+    /// night and sit there until the morning
+    RestAtNight(f32),
+    /// Look around for a target with the given chance each tick
+    ChooseTarget(f32),
+    /// Investigate or flee from noises, wander around otherwise
+    ReactToSounds,
+}
+
+impl IdleNode {
+    fn run(self, bdata: &mut BehaviorData) -> bool {
+        match self {
+            IdleNode::FollowOwner => set_owner_if_no_target(bdata),
+            IdleNode::Interact => handle_timed_events(bdata),
+            IdleNode::ReturnToPatrol(dist) => return_to_patrol(bdata, dist),
+            IdleNode::RestAtNight(dist) => rest_at_night(bdata, dist),
+            IdleNode::ChooseTarget(chance) => choose_target(bdata, chance),
+            IdleNode::ReactToSounds => react_to_sounds(bdata),
+        }
+    }
+}
+
+/// Trees an agent runs depending on its situation, each node is tried in
+/// order until one of them acts
+#[derive(Clone, Debug, Deserialize)]
+pub struct BehaviorProfile {
+    pub hostile: Vec<HostileNode>,
+    pub idle: Vec<IdleNode>,
+}
+
+impl BehaviorProfile {
+    pub fn run_hostile(&self, bdata: &mut BehaviorData) -> bool {
+        self.hostile.iter().any(|node| node.run(bdata))
+    }
+
+    pub fn run_idle(&self, bdata: &mut BehaviorData) -> bool {
+        self.idle.iter().any(|node| node.run(bdata))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BehaviorProfiles {
+    /// Profile of creatures whose species doesn't have one
+    default: BehaviorProfile,
+    /// Profiles by species keyword, as used by the `/spawn` command
+    species: HashMap<String, BehaviorProfile>,
+}
+
+impl assets::Asset for BehaviorProfiles {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+impl BehaviorProfiles {
+    pub fn get(&self, body: Option<&Body>) -> &BehaviorProfile {
+        body.and_then(|body| {
+            species_names(&NPC_NAMES.read(), body)
+                .and_then(|names| self.species.get(&names.keyword))
+        })
+        .unwrap_or(&self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::npc::NpcBody;
+    use std::str::FromStr;
+
+    #[test]
+    fn behavior_profiles_are_valid() {
+        let profiles = BehaviorProfiles::load_expect_cloned("server.manifests.agent_behaviors");
+        for species in profiles.species.keys() {
+            assert!(
+                NpcBody::from_str(species).is_ok(),
+                "Unknown species: {}",
+                species
+            );
+        }
+    }
+}
@@ -9,6 +9,7 @@ pub const SEPARATION_BIAS: f32 = 0.8;
 pub const MAX_FLEE_DIST: f32 = 20.0;
 pub const AVG_FOLLOW_DIST: f32 = 6.0;
 pub const RETARGETING_THRESHOLD_SECONDS: f64 = 10.0;
+pub const CALL_FOR_HELP_COOLDOWN: f64 = 5.0;
 pub const HEALING_ITEM_THRESHOLD: f32 = 0.5;
 pub const IDLE_HEALING_ITEM_THRESHOLD: f32 = 0.999;
 pub const DEFAULT_ATTACK_RANGE: f32 = 2.0;
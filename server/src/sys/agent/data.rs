@@ -1,9 +1,9 @@
 use crate::rtsim::Entity as RtSimData;
 use common::{
     comp::{
-        buff::Buffs, group, item::MaterialStatManifest, ActiveAbilities, Alignment, Body,
-        CharacterState, Combo, Energy, Health, Inventory, LightEmitter, LootOwner, Ori,
-        PhysicsState, Pos, Scale, SkillSet, Stats, Vel,
+        buff::Buffs, faction::Faction, group, item::MaterialStatManifest, ActiveAbilities,
+        Alignment, Body, CharacterState, Combo, Energy, Health, Inventory, LightEmitter, LootOwner,
+        Ori, PhysicsState, Pos, Reputation, Scale, SkillSet, Stats, Vel,
     },
     link::Is,
     mounting::Mount,
@@ -147,6 +147,8 @@ pub struct ReadData<'a> {
     pub groups: ReadStorage<'a, group::Group>,
     pub terrain: ReadExpect<'a, TerrainGrid>,
     pub alignments: ReadStorage<'a, Alignment>,
+    pub factions: ReadStorage<'a, Faction>,
+    pub reputations: ReadStorage<'a, Reputation>,
     pub bodies: ReadStorage<'a, Body>,
     pub is_mounts: ReadStorage<'a, Is<Mount>>,
     pub time_of_day: Read<'a, TimeOfDay>,
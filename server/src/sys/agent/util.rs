@@ -2,7 +2,7 @@ use crate::sys::agent::{AgentData, ReadData};
 use common::{
     comp::{
         agent::Psyche, buff::BuffKind, inventory::item::ItemTag, item::ItemDesc, Alignment, Body,
-        Pos,
+        Pos, Reputation,
     },
     consts::GRAVITY,
     terrain::Block,
@@ -150,6 +150,36 @@ pub fn are_our_owners_hostile(
     })
 }
 
+/// Hostility decided by the factions of both entities, or by the reputation
+/// of the other entity (or its owner) with our faction. `None` if we or they
+/// aren't part of this, in which case alignments decide.
+pub fn is_faction_hostile(
+    entity: EcsEntity,
+    other: EcsEntity,
+    read_data: &ReadData,
+) -> Option<bool> {
+    let faction = *read_data.factions.get(entity)?;
+    if let Some(other_faction) = read_data.factions.get(other) {
+        return Some(faction.hostile_towards(*other_faction));
+    }
+    // Players are owned by themselves, pets by their owner
+    let owner_uid = match read_data.alignments.get(other) {
+        Some(Alignment::Owned(owner_uid)) => *owner_uid,
+        _ => return None,
+    };
+    let owner = get_entity_by_id(owner_uid.id(), read_data)?;
+    if let Some(owner_faction) = read_data.factions.get(owner) {
+        Some(faction.hostile_towards(*owner_faction))
+    } else if let Some(reputation) = read_data.reputations.get(owner) {
+        Some(reputation.is_hostile(faction))
+    } else if read_data.alignments.get(owner) == Some(&Alignment::Owned(owner_uid)) {
+        // Player who didn't earn any reputation yet
+        Some(Reputation::default().is_hostile(faction))
+    } else {
+        None
+    }
+}
+
 pub fn entities_have_line_of_sight(
     pos: &Pos,
     body: Option<&Body>,
@@ -3,6 +3,7 @@ use crate::{
     client::Client,
     metrics::NetworkRequestMetrics,
     presence::Presence,
+    signs::Signs,
     Tick,
 };
 use common::{event::EventBus, slowjob::SlowJobPool, terrain::TerrainGrid};
@@ -28,6 +29,7 @@ impl<'a> System<'a> for Sys {
         ReadExpect<'a, SlowJobPool>,
         ReadExpect<'a, TerrainGrid>,
         ReadExpect<'a, crossbeam_channel::Sender<SerializedChunk>>,
+        Read<'a, Signs>,
     );
 
     const NAME: &'static str = "chunk_serialize";
@@ -45,6 +47,7 @@ impl<'a> System<'a> for Sys {
             slow_jobs,
             terrain,
             chunk_sender,
+            signs,
         ): Self::SystemData,
     ) {
         // Only operate twice per second
@@ -95,6 +98,16 @@ impl<'a> System<'a> for Sys {
             requests += 1;
         }
 
+        // The text of signs isn't part of the chunk, it is sent separately
+        for (chunk_key, meta) in chunks.iter() {
+            let texts = signs.texts_in(*chunk_key);
+            if !texts.is_empty() {
+                for client in meta.recipients.iter().filter_map(|e| clients.get(*e)) {
+                    client.send_fallible(ServerGeneral::SignTexts(texts.clone()));
+                }
+            }
+        }
+
         network_metrics
             .chunks_serialisation_requests
             .inc_by(requests);
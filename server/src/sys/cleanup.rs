@@ -0,0 +1,116 @@
+use crate::{settings::Settings, sys::SysScheduler};
+use common::{
+    comp::{Alignment, Health, ItemDrop, PhysicsState, Projectile},
+    event::{EventBus, ServerEvent},
+    resources::Time,
+    uid::UidAllocator,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{
+    saveload::MarkerAllocator, Component, Entities, Entity, Join, Read, ReadStorage, VecStorage,
+    Write, WriteStorage,
+};
+
+/// Time an entity started being subject to a despawn policy at
+#[derive(Copy, Clone, Debug)]
+pub struct DespawnTimer {
+    pub since: f64,
+}
+
+impl Component for DespawnTimer {
+    type Storage = VecStorage<Self>;
+}
+
+/// This system despawns the entities left behind in the world according to the
+/// cleanup policies of the server settings, so that they don't pile up on
+/// long-running servers: dropped items, projectiles stuck in the terrain and
+/// summons whose summoner is gone
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Time>,
+        Read<'a, Settings>,
+        Read<'a, UidAllocator>,
+        Read<'a, EventBus<ServerEvent>>,
+        ReadStorage<'a, ItemDrop>,
+        ReadStorage<'a, Projectile>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, PhysicsState>,
+        ReadStorage<'a, Alignment>,
+        WriteStorage<'a, DespawnTimer>,
+        Write<'a, SysScheduler<Self>>,
+    );
+
+    const NAME: &'static str = "cleanup";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            time,
+            settings,
+            uid_allocator,
+            server_bus,
+            item_drops,
+            projectiles,
+            healths,
+            physics_states,
+            alignments,
+            mut timers,
+            mut scheduler,
+        ): Self::SystemData,
+    ) {
+        if !scheduler.should_run() {
+            return;
+        }
+        let policies = &settings.cleanup;
+
+        // How long each entity is kept for, `None` if it isn't subject to any policy
+        // right now
+        let despawn_after = |entity: Entity| {
+            if item_drops.contains(entity) {
+                policies.item_drops
+            } else if projectiles.contains(entity) && !healths.contains(entity) {
+                // Projectiles in flight are left to the projectile system
+                physics_states
+                    .get(entity)
+                    .and_then(|physics| physics.on_surface())
+                    .and(policies.stuck_projectiles)
+            } else if let Some(Alignment::Owned(owner)) = alignments.get(entity) {
+                let orphaned = uid_allocator
+                    .retrieve_entity_internal(owner.0)
+                    .map_or(true, |owner| !entities.is_alive(owner));
+                orphaned.then_some(()).and(policies.orphaned_summons)
+            } else {
+                None
+            }
+        };
+
+        let mut expired = Vec::new();
+        let mut started = Vec::new();
+        let mut stopped = Vec::new();
+        for (entity, timer) in (&entities, timers.maybe()).join() {
+            match (despawn_after(entity), timer) {
+                (Some(secs), Some(timer)) if time.0 - timer.since >= secs => expired.push(entity),
+                (Some(_), None) => started.push(entity),
+                (None, Some(_)) => stopped.push(entity),
+                _ => {},
+            }
+        }
+
+        for entity in started {
+            let _ = timers.insert(entity, DespawnTimer { since: time.0 });
+        }
+        for entity in stopped {
+            timers.remove(entity);
+        }
+        let mut server_emitter = server_bus.emitter();
+        for entity in expired {
+            server_emitter.emit(ServerEvent::Delete(entity));
+        }
+    }
+}
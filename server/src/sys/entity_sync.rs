@@ -1,4 +1,7 @@
-use super::sentinel::{DeletedEntities, TrackedStorages, UpdateTrackers};
+use super::{
+    sentinel::{DeletedEntities, TrackedStorages, UpdateTrackers},
+    spectate::Spectating,
+};
 use crate::{
     client::Client,
     presence::{Presence, RegionSubscription},
@@ -10,7 +13,7 @@ use common::{
     event::EventBus,
     outcome::Outcome,
     region::{Event as RegionEvent, RegionMap},
-    resources::{PlayerPhysicsSettings, TimeOfDay},
+    resources::{DayCycleFactor, PlayerPhysicsSettings, TimeOfDay},
     terrain::TerrainChunkSize,
     uid::Uid,
     vol::RectVolSize,
@@ -33,6 +36,7 @@ impl<'a> System<'a> for Sys {
         Read<'a, PlayerPhysicsSettings>,
         TrackedStorages<'a>,
         ReadExpect<'a, TimeOfDay>,
+        Read<'a, DayCycleFactor>,
         ReadExpect<'a, Calendar>,
         ReadExpect<'a, RegionMap>,
         ReadExpect<'a, UpdateTrackers>,
@@ -42,6 +46,7 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, RegionSubscription>,
         ReadStorage<'a, Presence>,
         ReadStorage<'a, Client>,
+        ReadStorage<'a, Spectating>,
         WriteStorage<'a, Last<Pos>>,
         WriteStorage<'a, Last<Vel>>,
         WriteStorage<'a, Last<Ori>>,
@@ -63,6 +68,7 @@ impl<'a> System<'a> for Sys {
             player_physics_settings,
             tracked_storages,
             time_of_day,
+            day_cycle_factor,
             calendar,
             region_map,
             trackers,
@@ -72,6 +78,7 @@ impl<'a> System<'a> for Sys {
             subscriptions,
             presences,
             clients,
+            spectatings,
             mut last_pos,
             mut last_vel,
             mut last_ori,
@@ -261,6 +268,12 @@ impl<'a> System<'a> for Sys {
                             // stable physics so we always send updated
                             // for these where we can.
                             true
+                        } else if spectatings
+                            .get(*client_entity)
+                            .map_or(false, |spectating| spectating.target == uid)
+                        {
+                            // Moderators spectating a player see everything they do
+                            true
                         } else {
                             // Throttle update rates for all other entities based on distance to
                             // client
@@ -415,7 +428,11 @@ impl<'a> System<'a> for Sys {
             let mut tod_lazymsg = None;
             for client in (&clients).join() {
                 let msg = tod_lazymsg.unwrap_or_else(|| {
-                    client.prepare(ServerGeneral::TimeOfDay(*time_of_day, (*calendar).clone()))
+                    client.prepare(ServerGeneral::TimeOfDay(
+                        *time_of_day,
+                        (*calendar).clone(),
+                        *day_cycle_factor,
+                    ))
                 });
                 // We don't care much about stream errors here since they could just represent
                 // network disconnection, which is handled elsewhere.
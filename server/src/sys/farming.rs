@@ -0,0 +1,78 @@
+use crate::{
+    farming::Farms,
+    sys::{terrain::TerrainPersistenceData, SysScheduler},
+};
+use common::{farming, resources::TimeOfDay, terrain::TerrainGrid, vol::ReadVol};
+use common_ecs::{Job, Origin, Phase, System};
+use common_state::BlockChange;
+use specs::{Read, ReadExpect, Write};
+
+/// This system grows the crops planted in loaded chunks into the stage they
+/// should be at, and forgets the ones which were harvested or destroyed
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, TimeOfDay>,
+        ReadExpect<'a, TerrainGrid>,
+        Write<'a, Farms>,
+        Write<'a, BlockChange>,
+        Write<'a, SysScheduler<Self>>,
+        TerrainPersistenceData<'a>,
+    );
+
+    const NAME: &'static str = "farming";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            time_of_day,
+            terrain,
+            mut farms,
+            mut block_change,
+            mut scheduler,
+            mut _terrain_persistence,
+        ): Self::SystemData,
+    ) {
+        if !scheduler.should_run() {
+            return;
+        }
+
+        let mut gone = Vec::new();
+        for (chunk_key, crops) in farms.chunks() {
+            // Crops of unloaded chunks catch up once they get loaded again
+            if terrain.get_key(*chunk_key).is_none() {
+                continue;
+            }
+            for (pos, planted) in crops {
+                let block = terrain.get(*pos).ok().copied();
+                let stage = match block.and_then(|block| planted.crop.stage_of(block.get_sprite()?))
+                {
+                    Some(stage) => stage,
+                    None => {
+                        gone.push((*chunk_key, *pos));
+                        continue;
+                    },
+                };
+                // Crops never shrink back, even if the time of day was set back
+                let grown = farming::stage_at(planted.planted_at, time_of_day.0);
+                if let Some(block) = block.filter(|_| grown > stage) {
+                    let new_block = block.with_sprite(planted.crop.stage_sprite(grown));
+                    // The growth is persisted like the farmland and the seedling, so the
+                    // crop isn't back to its previous stage once the chunk is reloaded
+                    if block_change.try_set(*pos, new_block).is_some() {
+                        #[cfg(feature = "persistent_world")]
+                        if let Some(terrain_persistence) = _terrain_persistence.as_mut() {
+                            terrain_persistence.set_block(*pos, new_block);
+                        }
+                    }
+                }
+            }
+        }
+        if !gone.is_empty() {
+            farms.remove(gone);
+        }
+    }
+}
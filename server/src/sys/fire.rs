@@ -0,0 +1,226 @@
+use crate::{
+    fire::{Burning, Fires},
+    sys::SysScheduler,
+};
+use common::{
+    comp::{Buff, BuffCategory, BuffChange, BuffData, BuffKind, BuffSource, Buffs, Health, Pos},
+    event::{EventBus, ServerEvent},
+    fire,
+    outcome::Outcome,
+    resources::Time,
+    terrain::{Block, SpriteKind, TerrainGrid},
+    vol::ReadVol,
+    weather::WeatherGrid,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_state::{BlockChange, BuildAreas};
+use hashbrown::{HashMap, HashSet};
+use rand::Rng;
+use specs::{Entities, Join, Read, ReadExpect, ReadStorage, Write};
+use std::time::Duration;
+use vek::*;
+
+/// How far below the place set on fire a block to burn is looked for
+const IGNITE_DEPTH: i32 = 48;
+
+/// This system spreads fire to the flammable blocks next to the burning ones
+/// and those burning entities stand on, burns them out, lets the rain put them
+/// out and sets the entities standing in the flames on fire
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Time>,
+        ReadExpect<'a, TerrainGrid>,
+        ReadExpect<'a, WeatherGrid>,
+        Read<'a, BuildAreas>,
+        Read<'a, EventBus<ServerEvent>>,
+        Read<'a, EventBus<Outcome>>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Buffs>,
+        Write<'a, Fires>,
+        Write<'a, BlockChange>,
+        Write<'a, SysScheduler<Self>>,
+    );
+
+    const NAME: &'static str = "fire";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            time,
+            terrain,
+            weather,
+            build_areas,
+            server_bus,
+            outcome_bus,
+            positions,
+            healths,
+            buffs,
+            mut fires,
+            mut block_change,
+            mut scheduler,
+        ): Self::SystemData,
+    ) {
+        if !scheduler.should_run() {
+            return;
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut outcome_emitter = outcome_bus.emitter();
+
+        let mut candidates = fires
+            .take_ignitions()
+            .into_iter()
+            .filter_map(|pos| fuel_below(&terrain, pos))
+            .collect::<Vec<_>>();
+        // Burning entities set what they stand on on fire, so that fire abilities burn
+        // the ground under their targets
+        candidates.extend(
+            (&positions, &buffs)
+                .join()
+                .filter(|(_, buffs)| buffs.contains(BuffKind::Burning))
+                .filter(|_| rng.gen_bool(fire::ENTITY_IGNITE_CHANCE))
+                .filter_map(|(pos, _)| fuel_below(&terrain, pos.0.map(|e| e.floor() as i32))),
+        );
+        let mut gone = Vec::new();
+        let mut in_flames = HashSet::new();
+        for (chunk_key, burning) in fires.chunks() {
+            // Fires of unloaded chunks die out with them
+            if terrain.get_key(*chunk_key).is_none() {
+                gone.extend(burning.keys().map(|pos| (*chunk_key, *pos)));
+                continue;
+            }
+            for (pos, burning) in burning {
+                let fuel = terrain.get(*pos).ok().copied();
+                let flames = burning.flames.filter(|flames| {
+                    terrain
+                        .get(*flames)
+                        .ok()
+                        .and_then(|block| block.get_sprite())
+                        == Some(SpriteKind::Ember)
+                });
+                // The fire was put out if the block or its flames were removed
+                let put_out = (burning.flames.is_some() && flames.is_none())
+                    || (burning.flames != Some(*pos)
+                        && fuel.and_then(fire::flammability).is_none());
+                let rain = weather.get_interpolated(pos.xy().as_()).rain;
+                let rained_out = rain > 0.0
+                    && rng.gen_bool((rain * fire::RAIN_EXTINGUISH_CHANCE).min(1.0) as f64);
+                if put_out || rained_out {
+                    if rained_out {
+                        if let Some(flames) = flames {
+                            block_change.set(flames, Block::empty());
+                            outcome_emitter.emit(Outcome::BurnOut { pos: flames });
+                        }
+                    }
+                    gone.push((*chunk_key, *pos));
+                } else if time.0 >= burning.burns_out_at {
+                    if let Some(flames) = flames {
+                        block_change.set(flames, Block::empty());
+                        outcome_emitter.emit(Outcome::BurnOut { pos: flames });
+                    }
+                    if let Some(fuel) = fuel.filter(|fuel| fuel.is_filled()) {
+                        block_change.set(*pos, fire::burnt_out(fuel));
+                    }
+                    gone.push((*chunk_key, *pos));
+                } else {
+                    in_flames.insert(*pos);
+                    in_flames.extend(flames);
+                    candidates.extend(fire::SPREAD_DIRS.iter().map(|dir| *pos + *dir).filter(
+                        |neighbour| {
+                            terrain
+                                .get(*neighbour)
+                                .ok()
+                                .and_then(|block| fire::flammability(*block))
+                                .map_or(false, |chance| rng.gen_bool(chance as f64))
+                        },
+                    ));
+                }
+            }
+        }
+        fires.remove(gone);
+
+        let mut ignited = HashMap::<Vec2<i32>, usize>::new();
+        for pos in candidates {
+            let chunk_key = terrain.pos_key(pos);
+            let ignited_in_chunk = ignited.entry(chunk_key).or_default();
+            if *ignited_in_chunk >= fire::MAX_IGNITIONS_PER_CHUNK
+                || fires.burning_in(chunk_key) >= fire::MAX_FIRES_PER_CHUNK
+                || fires.is_burning(chunk_key, pos)
+                // Fire doesn't spread into the build areas, so that it can't burn down
+                // protected buildings
+                || build_areas.protects(pos)
+            {
+                continue;
+            }
+            let (block, above) = match (terrain.get(pos), terrain.get(pos + Vec3::unit_z())) {
+                (Ok(block), Ok(above)) => (*block, *above),
+                _ => continue,
+            };
+            if fire::flammability(block).is_none() {
+                continue;
+            }
+            let flames = fire::flames_pos(block, above, pos);
+            if let Some(flames) = flames {
+                let flames_block = if flames == pos { block } else { above };
+                if block_change
+                    .try_set(flames, flames_block.with_sprite(SpriteKind::Ember))
+                    .is_none()
+                {
+                    continue;
+                }
+                outcome_emitter.emit(Outcome::Ignite { pos: flames });
+            }
+            fires.insert(chunk_key, pos, Burning {
+                flames,
+                burns_out_at: time.0 + fire::burn_time(block),
+            });
+            *ignited_in_chunk += 1;
+        }
+
+        if in_flames.is_empty() {
+            return;
+        }
+        let mut server_emitter = server_bus.emitter();
+        for (entity, pos, _) in (&entities, &positions, &healths).join() {
+            let block_pos = pos.0.map(|e| e.floor() as i32);
+            // Entities standing on a burning block catch fire too
+            if in_flames.contains(&block_pos) || in_flames.contains(&(block_pos - Vec3::unit_z())) {
+                server_emitter.emit(ServerEvent::Buff {
+                    entity,
+                    buff_change: BuffChange::Add(Buff::new(
+                        BuffKind::Burning,
+                        BuffData::new(
+                            fire::BURN_STRENGTH,
+                            Some(Duration::from_secs_f64(fire::BURN_DURATION)),
+                        ),
+                        vec![BuffCategory::Natural],
+                        BuffSource::World,
+                    )),
+                });
+            }
+        }
+    }
+}
+
+/// The first block below the position if it burns, `None` if it doesn't or
+/// there is nothing there
+fn fuel_below(terrain: &TerrainGrid, pos: Vec3<i32>) -> Option<Vec3<i32>> {
+    (0..IGNITE_DEPTH)
+        .map(|depth| pos - Vec3::unit_z() * depth)
+        .find_map(|pos| {
+            let block = terrain.get(pos).ok()?;
+            if block.is_air() && matches!(block.get_sprite(), Some(SpriteKind::Empty) | None) {
+                None
+            } else {
+                Some(Some(pos).filter(|_| fire::flammability(*block).is_some()))
+            }
+        })
+        .flatten()
+}
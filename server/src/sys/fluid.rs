@@ -0,0 +1,77 @@
+use crate::{fire::Fires, fluid::Fluids, sys::SysScheduler};
+use common::{
+    comp::fluid_dynamics::LiquidKind,
+    fire,
+    terrain::{fluid, TerrainGrid},
+    vol::ReadVol,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_state::{BlockChange, TerrainChanges};
+use specs::{Read, ReadExpect, Write};
+
+/// Blocks looked at on each flowing step at most, the others waiting for the
+/// next one, so that a flood doesn't take up the whole tick
+const MAX_FLOWS_PER_STEP: usize = 4096;
+
+/// This system makes water and lava flow into the blocks next to them which
+/// were dug out, dries up the flowing liquids cut off from their source and
+/// sets the flammable blocks flowing lava reaches on fire
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        ReadExpect<'a, TerrainGrid>,
+        Read<'a, TerrainChanges>,
+        Write<'a, Fluids>,
+        Write<'a, Fires>,
+        Write<'a, BlockChange>,
+        Write<'a, SysScheduler<Self>>,
+    );
+
+    const NAME: &'static str = "fluid";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (terrain, terrain_changes, mut fluids, mut fires, mut block_change, mut scheduler): Self::SystemData,
+    ) {
+        // The changes of the last tick, the ones made by the last step included,
+        // are only seen once
+        for pos in terrain_changes.modified_blocks.keys() {
+            fluids.wake(*pos);
+        }
+        if !scheduler.should_run() {
+            return;
+        }
+
+        let (active, lava_flows) = fluids.next_step(MAX_FLOWS_PER_STEP);
+        for pos in active {
+            let next = match fluid::next_block(pos, |pos| terrain.get(pos).ok().copied()) {
+                Some(next) => next,
+                None => continue,
+            };
+            let is_lava = |kind| kind == Some(LiquidKind::Lava);
+            let involves_lava = terrain
+                .get(pos)
+                .map_or(false, |block| is_lava(block.liquid_kind()))
+                || is_lava(next.liquid_kind());
+            if involves_lava && !lava_flows {
+                fluids.defer(pos);
+            } else {
+                // If something else changes the block first, that change wakes it
+                // up again
+                if block_change.try_set(pos, next).is_some() && is_lava(next.liquid_kind()) {
+                    for neighbour in fire::SPREAD_DIRS.iter().map(|dir| pos + *dir) {
+                        if terrain
+                            .get(neighbour)
+                            .map_or(false, |block| fire::flammability(*block).is_some())
+                        {
+                            fires.ignite(neighbour);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{client::Client, Tick};
+use common::{
+    comp::{
+        group::{Group, MemberStatus},
+        Energy, Health, Pos, Stats,
+    },
+    uid::Uid,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use hashbrown::HashMap;
+use specs::{Join, Read, ReadStorage};
+
+/// This system sends the health and position of group members to the rest of
+/// their group, since members further away than the view distance aren't
+/// synced to the client
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, Tick>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Group>,
+        ReadStorage<'a, Client>,
+        ReadStorage<'a, Stats>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Energy>,
+        ReadStorage<'a, Pos>,
+    );
+
+    const NAME: &'static str = "group_status";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (tick, uids, groups, clients, stats, healths, energies, positions): Self::SystemData,
+    ) {
+        // Only operate once per second
+        if tick.0.rem_euclid(30) != 0 {
+            return;
+        }
+
+        let mut members = HashMap::<Group, Vec<(Uid, MemberStatus)>>::new();
+        for (uid, group, _, stats, health, energy, pos) in (
+            &uids, &groups, &clients, &stats, &healths, &energies, &positions,
+        )
+            .join()
+        {
+            members
+                .entry(*group)
+                .or_default()
+                .push((*uid, MemberStatus {
+                    name: stats.name.clone(),
+                    health_fraction: health.fraction(),
+                    energy_fraction: energy.fraction(),
+                    is_dead: health.is_dead,
+                    pos: pos.0,
+                }));
+        }
+
+        for (uid, group, client) in (&uids, &groups, &clients).join() {
+            if let Some(members) = members.get(group) {
+                let statuses = members
+                    .iter()
+                    .filter(|(member, _)| member != uid)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if !statuses.is_empty() {
+                    client.send_fallible(ServerGeneral::GroupMemberStatus(statuses));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{map_tiles::MapTiles, sys::SysScheduler};
+use common::slowjob::SlowJobPool;
+use common_ecs::{Job, Origin, Phase, System};
+use common_state::TerrainChanges;
+use specs::{Read, ReadExpect, Write};
+
+/// This system keeps track of the chunks explored by the players, and
+/// periodically renders the map tiles they are in to disk when enabled
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, TerrainChanges>,
+        ReadExpect<'a, SlowJobPool>,
+        Option<Write<'a, MapTiles>>,
+        Write<'a, SysScheduler<Self>>,
+    );
+
+    const NAME: &'static str = "map_tiles";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (terrain_changes, slow_jobs, map_tiles, mut scheduler): Self::SystemData,
+    ) {
+        let mut map_tiles = match map_tiles {
+            Some(map_tiles) => map_tiles,
+            None => return,
+        };
+        for chunk_key in terrain_changes.new_chunks.iter() {
+            map_tiles.explore(*chunk_key);
+        }
+        if scheduler.should_run() {
+            if let Some(render) = map_tiles.render_job() {
+                slow_jobs.spawn("MAP_TILES", render);
+            }
+        }
+    }
+}
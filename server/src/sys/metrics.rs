@@ -2,10 +2,23 @@ use crate::{
     metrics::{EcsSystemMetrics, JobMetrics, PhysicsMetrics, TickMetrics},
     HwStats, Tick, TickStart,
 };
-use common::{resources::TimeOfDay, slowjob::SlowJobPool, terrain::TerrainGrid};
+use common::{region::RegionMap, resources::TimeOfDay, slowjob::SlowJobPool, terrain::TerrainGrid};
 use common_ecs::{Job, Origin, Phase, SysMetrics, System};
-use specs::{Entities, Join, Read, ReadExpect};
-use std::time::Instant;
+use specs::{Entities, Join, Read, ReadExpect, Write};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Ticks taking longer than this are logged along with what took the longest
+const SLOW_TICK: Duration = Duration::from_millis(100);
+/// Slow ticks are logged at most this often, so that an overloaded server
+/// doesn't flood its logs
+const SLOW_TICK_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+/// When a slow tick was last logged
+#[derive(Default)]
+pub struct SlowTickLog {
+    last: Option<Instant>,
+}
 
 /// This system exports metrics
 #[derive(Default)]
@@ -25,6 +38,8 @@ impl<'a> System<'a> for Sys {
         ReadExpect<'a, TickMetrics>,
         ReadExpect<'a, PhysicsMetrics>,
         ReadExpect<'a, JobMetrics>,
+        ReadExpect<'a, RegionMap>,
+        Write<'a, SlowTickLog>,
     );
 
     const NAME: &'static str = "metrics";
@@ -47,6 +62,8 @@ impl<'a> System<'a> for Sys {
             export_tick,
             export_physics,
             export_jobs,
+            region_map,
+            mut slow_tick_log,
         ): Self::SystemData,
     ) {
         const NANOSEC_PER_SEC: f64 = std::time::Duration::from_secs(1).as_nanos() as f64;
@@ -56,6 +73,7 @@ impl<'a> System<'a> for Sys {
         let mut state = sys_metrics.stats.lock().unwrap();
         //this system hasn't run yet
         state.remove(Self::NAME);
+        let mut system_lengths = Vec::new();
 
         for (name, stat) in common_ecs::gen_stats(
             &state,
@@ -72,6 +90,7 @@ impl<'a> System<'a> for Sys {
                 .with_label_values(&[&name])
                 .set(stat.avg_threads() as f64);
             let len = stat.length_ns();
+            system_lengths.push((name.clone(), len));
             export_ecs
                 .system_length_time
                 .with_label_values(&[&name])
@@ -135,6 +154,39 @@ impl<'a> System<'a> for Sys {
             }
         }
 
+        // Attribute slow ticks to the systems which took the longest and the regions
+        // with the most entities
+        let tick_len = start.duration_since(tick_start.0);
+        if tick_len > SLOW_TICK
+            && slow_tick_log
+                .last
+                .map_or(true, |last| last.elapsed() >= SLOW_TICK_LOG_INTERVAL)
+        {
+            slow_tick_log.last = Some(start);
+            system_lengths.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+            let systems = system_lengths
+                .iter()
+                .take(5)
+                .map(|(name, len)| format!("{} {:.1}ms", name, *len as f64 / 1_000_000.0))
+                .collect::<Vec<_>>();
+            let mut regions = region_map
+                .iter()
+                .map(|(key, region)| (RegionMap::key_pos(key), region.entities().join().count()))
+                .collect::<Vec<_>>();
+            regions.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+            let regions = regions
+                .iter()
+                .take(3)
+                .map(|(pos, entities)| format!("{} entities at {}", entities, pos))
+                .collect::<Vec<_>>();
+            warn!(
+                tick_ms = tick_len.as_secs_f64() * 1000.0,
+                ?systems,
+                ?regions,
+                "Slow tick"
+            );
+        }
+
         // export self time as best as possible
         export_ecs
             .system_start_time
@@ -1,20 +1,38 @@
 pub mod agent;
 pub mod chunk_send;
 pub mod chunk_serialize;
+pub mod cleanup;
 pub mod entity_sync;
+pub mod farming;
+pub mod fire;
+pub mod fluid;
+pub mod group_status;
 pub mod invite_timeout;
 pub mod loot;
+pub mod map_tiles;
 pub mod metrics;
 pub mod msg;
+pub mod night;
 pub mod object;
 pub mod persistence;
 pub mod pets;
+pub mod placed_blocks;
+pub mod portal;
+pub mod quest;
+pub mod rollback;
 pub mod sentinel;
+pub mod session;
+pub mod signs;
+pub mod sleep;
+pub mod spectate;
 pub mod subscription;
 pub mod terrain;
 pub mod terrain_sync;
+pub mod trade_range;
+pub mod training_dummy;
 pub mod waypoint;
 pub mod wiring;
+pub mod world_border;
 
 use common_ecs::{dispatch, run_now, System};
 use common_systems::{melee, projectile};
@@ -25,6 +43,14 @@ use std::{
 };
 
 pub type PersistenceScheduler = SysScheduler<persistence::Sys>;
+pub type FarmingScheduler = SysScheduler<farming::Sys>;
+pub type FireScheduler = SysScheduler<fire::Sys>;
+pub type FluidScheduler = SysScheduler<fluid::Sys>;
+pub type MapTileScheduler = SysScheduler<map_tiles::Sys>;
+pub type CleanupScheduler = SysScheduler<cleanup::Sys>;
+pub type WorldBorderScheduler = SysScheduler<world_border::Sys>;
+pub type PlacedBlocksScheduler = SysScheduler<placed_blocks::Sys>;
+pub type SignsScheduler = SysScheduler<signs::Sys>;
 
 pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch::<melee::Sys>(dispatch_builder, &[&projectile::Sys::sys_name()]);
@@ -33,9 +59,27 @@ pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch::<terrain::Sys>(dispatch_builder, &[&msg::terrain::Sys::sys_name()]);
     dispatch::<waypoint::Sys>(dispatch_builder, &[]);
     dispatch::<invite_timeout::Sys>(dispatch_builder, &[]);
+    dispatch::<trade_range::Sys>(dispatch_builder, &[]);
     dispatch::<persistence::Sys>(dispatch_builder, &[]);
     dispatch::<object::Sys>(dispatch_builder, &[]);
     dispatch::<wiring::Sys>(dispatch_builder, &[]);
+    dispatch::<quest::Sys>(dispatch_builder, &[]);
+    dispatch::<night::Sys>(dispatch_builder, &[]);
+    dispatch::<sleep::Sys>(dispatch_builder, &[]);
+    dispatch::<spectate::Sys>(dispatch_builder, &[]);
+    dispatch::<group_status::Sys>(dispatch_builder, &[]);
+    dispatch::<portal::Sys>(dispatch_builder, &[]);
+    dispatch::<farming::Sys>(dispatch_builder, &[]);
+    dispatch::<rollback::Sys>(dispatch_builder, &[]);
+    dispatch::<fire::Sys>(dispatch_builder, &[]);
+    dispatch::<fluid::Sys>(dispatch_builder, &[]);
+    dispatch::<placed_blocks::Sys>(dispatch_builder, &[]);
+    dispatch::<signs::Sys>(dispatch_builder, &[]);
+    dispatch::<training_dummy::Sys>(dispatch_builder, &[]);
+    dispatch::<cleanup::Sys>(dispatch_builder, &[]);
+    dispatch::<session::Sys>(dispatch_builder, &[]);
+    dispatch::<world_border::Sys>(dispatch_builder, &[]);
+    dispatch::<map_tiles::Sys>(dispatch_builder, &[&terrain::Sys::sys_name()]);
     // no dependency, as we only work once per sec anyway.
     dispatch::<chunk_serialize::Sys>(dispatch_builder, &[]);
     // don't depend on chunk_serialize, as we assume everything is done in a SlowJow
@@ -55,6 +55,7 @@ impl Sys {
                     server_emitter.emit(ServerEvent::Chat(UnresolvedChatMsg {
                         chat_type: ChatType::Online(*player_uid),
                         message: "".to_string(),
+                        links: Vec::new(),
                     }));
 
                     client.login_msg_sent.store(true, Ordering::Relaxed);
@@ -21,13 +21,15 @@ impl Sys {
         msg: ClientGeneral,
     ) -> Result<(), crate::error::Error> {
         match msg {
-            ClientGeneral::ChatMsg(message) => {
+            ClientGeneral::ChatMsg(message, links) => {
                 if player.is_some() {
                     if let Some(from) = uids.get(entity) {
                         const CHAT_MODE_DEFAULT: &ChatMode = &ChatMode::default();
                         let mode = chat_modes.get(entity).unwrap_or(CHAT_MODE_DEFAULT);
                         // Send chat message
-                        server_emitter.emit(ServerEvent::Chat(mode.new_message(*from, message)));
+                        server_emitter.emit(ServerEvent::Chat(
+                            mode.new_message(*from, message).with_links(links),
+                        ));
                     } else {
                         error!("Could not send message. Missing player uid");
                     }
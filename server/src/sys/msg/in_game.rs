@@ -1,6 +1,11 @@
 #[cfg(feature = "persistent_world")]
 use crate::TerrainPersistence;
-use crate::{client::Client, presence::Presence, Settings};
+use crate::{
+    audit::{AuditEvent, AuditLog},
+    client::Client,
+    presence::Presence,
+    Settings,
+};
 use common::{
     comp::{
         Admin, AdminRole, CanBuild, ControlEvent, Controller, ForceUpdate, Health, Ori, Player,
@@ -16,7 +21,7 @@ use common::{
 use common_ecs::{Job, Origin, Phase, System};
 use common_net::msg::{ClientGeneral, PresenceKind, ServerGeneral};
 use common_state::{BlockChange, BuildAreas};
-use specs::{Entities, Join, Read, ReadExpect, ReadStorage, Write, WriteStorage};
+use specs::{Entities, Join, Read, ReadExpect, ReadStorage, Write, WriteExpect, WriteStorage};
 use std::time::Instant;
 use tracing::{debug, trace, warn};
 use vek::*;
@@ -48,6 +53,7 @@ impl Sys {
         build_areas: &Read<'_, BuildAreas>,
         player_physics_settings: &mut Write<'_, PlayerPhysicsSettings>,
         _terrain_persistence: &mut TerrainPersistenceData<'_>,
+        audit_log: &mut WriteExpect<'_, AuditLog>,
         maybe_player: &Option<&Player>,
         maybe_admin: &Option<&Admin>,
         time_for_vd_changes: Instant,
@@ -207,9 +213,18 @@ impl Sys {
                                 .and_then(|_| terrain.get(pos).ok())
                             {
                                 let new_block = old_block.into_vacant();
-                                let _was_set = block_changes.try_set(pos, new_block).is_some();
-                                #[cfg(feature = "persistent_world")]
-                                if _was_set {
+                                if block_changes.try_set(pos, new_block).is_some() {
+                                    if let Some(player) = maybe_player {
+                                        audit_log.record(
+                                            player.alias.clone(),
+                                            AuditEvent::TerrainEdit {
+                                                pos,
+                                                old: *old_block,
+                                                new: new_block,
+                                            },
+                                        );
+                                    }
+                                    #[cfg(feature = "persistent_world")]
                                     if let Some(terrain_persistence) = _terrain_persistence.as_mut()
                                     {
                                         terrain_persistence.set_block(pos, new_block);
@@ -224,17 +239,27 @@ impl Sys {
                 if let Some(comp_can_build) = can_build.get(entity) {
                     if comp_can_build.enabled {
                         for area in comp_can_build.build_areas.iter() {
-                            if build_areas
+                            if let Some(old_block) = build_areas
                                 .areas()
                                 .get(*area)
                                 // TODO: Make this an exclusive check on the upper bound of the AABB
                                 // Vek defaults to inclusive which is not optimal
                                 .filter(|aabb| aabb.contains_point(pos))
-                                .is_some()
+                                .and_then(|_| terrain.get(pos).ok())
                             {
-                                let _was_set = block_changes.try_set(pos, new_block).is_some();
-                                #[cfg(feature = "persistent_world")]
-                                if _was_set {
+                                let old_block = *old_block;
+                                if block_changes.try_set(pos, new_block).is_some() {
+                                    if let Some(player) = maybe_player {
+                                        audit_log.record(
+                                            player.alias.clone(),
+                                            AuditEvent::TerrainEdit {
+                                                pos,
+                                                old: old_block,
+                                                new: new_block,
+                                            },
+                                        );
+                                    }
+                                    #[cfg(feature = "persistent_world")]
                                     if let Some(terrain_persistence) = _terrain_persistence.as_mut()
                                     {
                                         terrain_persistence.set_block(pos, new_block);
@@ -284,6 +309,9 @@ impl Sys {
             ClientGeneral::UpdateMapMarker(update) => {
                 server_emitter.emit(ServerEvent::UpdateMapMarker { entity, update });
             },
+            ClientGeneral::ShareMapMarkers(markers) => {
+                server_emitter.emit(ServerEvent::ShareMapMarkers { entity, markers });
+            },
             ClientGeneral::SpectatePosition(pos) => {
                 if let Some(admin) = maybe_admin && admin.0 >= AdminRole::Moderator && presence.kind == PresenceKind::Spectator {
                     if let Some(position) = positions.get_mut(entity) {
@@ -299,7 +327,7 @@ impl Sys {
             | ClientGeneral::Spectate(_)
             | ClientGeneral::TerrainChunkRequest { .. }
             | ClientGeneral::LodZoneRequest { .. }
-            | ClientGeneral::ChatMsg(_)
+            | ClientGeneral::ChatMsg(..)
             | ClientGeneral::Command(..)
             | ClientGeneral::Terminate => {
                 debug!("Kicking possibly misbehaving client due to invalid client in game request");
@@ -338,6 +366,7 @@ impl<'a> System<'a> for Sys {
         Read<'a, BuildAreas>,
         Write<'a, PlayerPhysicsSettings>,
         TerrainPersistenceData<'a>,
+        WriteExpect<'a, AuditLog>,
         ReadStorage<'a, Player>,
         ReadStorage<'a, Admin>,
     );
@@ -368,6 +397,7 @@ impl<'a> System<'a> for Sys {
             build_areas,
             mut player_physics_settings,
             mut terrain_persistence,
+            mut audit_log,
             players,
             admins,
         ): Self::SystemData,
@@ -409,6 +439,7 @@ impl<'a> System<'a> for Sys {
                     &build_areas,
                     &mut player_physics_settings,
                     &mut terrain_persistence,
+                    &mut audit_log,
                     &player,
                     &maybe_admin,
                     time_for_vd_changes,
@@ -4,6 +4,7 @@ pub mod in_game;
 pub mod ping;
 pub mod register;
 pub mod terrain;
+pub mod voice;
 
 use crate::{
     client::Client,
@@ -22,6 +23,7 @@ pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch::<ping::Sys>(dispatch_builder, &[&general::Sys::sys_name()]);
     dispatch::<register::Sys>(dispatch_builder, &[]);
     dispatch::<terrain::Sys>(dispatch_builder, &[]);
+    dispatch::<voice::Sys>(dispatch_builder, &[]);
     dispatch::<pets::Sys>(dispatch_builder, &[]);
     dispatch::<loot::Sys>(dispatch_builder, &[]);
 }
@@ -1,12 +1,15 @@
 use crate::{
     client::Client,
     login_provider::{LoginProvider, PendingLogin},
+    login_queue::LoginQueue,
     metrics::PlayerMetrics,
+    session::{ResumeRequest, Session},
     EditableSettings, Settings,
 };
 use common::{
     comp::{Admin, Player, Stats},
     event::{EventBus, ServerEvent},
+    resources::Time,
     uid::{Uid, UidAllocator},
 };
 use common_ecs::{Job, Origin, Phase, System};
@@ -36,6 +39,7 @@ pub struct ReadData<'a> {
     stats: ReadStorage<'a, Stats>,
     uids: ReadStorage<'a, Uid>,
     clients: ReadStorage<'a, Client>,
+    time: Read<'a, Time>,
     server_event_bus: Read<'a, EventBus<ServerEvent>>,
     player_metrics: ReadExpect<'a, PlayerMetrics>,
     settings: ReadExpect<'a, Settings>,
@@ -54,7 +58,10 @@ impl<'a> System<'a> for Sys {
         WriteStorage<'a, Player>,
         WriteStorage<'a, Admin>,
         WriteStorage<'a, PendingLogin>,
+        WriteStorage<'a, Session>,
+        WriteStorage<'a, ResumeRequest>,
         WriteExpect<'a, LoginProvider>,
+        WriteExpect<'a, LoginQueue>,
     );
 
     const NAME: &'static str = "msg::register";
@@ -68,7 +75,10 @@ impl<'a> System<'a> for Sys {
             mut players,
             mut admins,
             mut pending_logins,
+            mut sessions,
+            mut resume_requests,
             mut login_provider,
+            mut login_queue,
         ): Self::SystemData,
     ) {
         let mut server_emitter = read_data.server_event_bus.emitter();
@@ -99,12 +109,34 @@ impl<'a> System<'a> for Sys {
         for (entity, client) in (&read_data.entities, &read_data.clients).join() {
             let _ = super::try_recv_all(client, 0, |_, msg: ClientRegister| {
                 trace!(?msg.token_or_username, "defer auth lockup");
-                let pending = login_provider.verify(&msg.token_or_username);
+                let pending = match msg.session {
+                    // The session token stands in for the credentials of the player it was
+                    // given to, the login still goes through the ban list and whitelist
+                    Some(token) => {
+                        let _ = resume_requests.insert(entity, ResumeRequest(token));
+                        match (&players, &sessions)
+                            .join()
+                            .find(|(_, session)| session.token == token)
+                        {
+                            Some((player, _)) => {
+                                PendingLogin::new_success(player.alias.clone(), player.uuid())
+                            },
+                            None => PendingLogin::new_failure(RegisterError::SessionExpired),
+                        }
+                    },
+                    None => {
+                        resume_requests.remove(entity);
+                        login_provider.verify(&msg.token_or_username)
+                    },
+                };
                 let _ = pending_logins.insert(entity, pending);
                 Ok(())
             });
         }
 
+        // Forget the queued clients which disconnected
+        login_queue.retain(|entity| pending_logins.contains(entity));
+
         let mut finished_pending = vec![];
         let mut retries = vec![];
         for (entity, client, pending) in
@@ -148,9 +180,39 @@ impl<'a> System<'a> for Sys {
                     },
                 };
 
-                // Check if user is already logged-in
+                // Resume the session the client asked for, taking it over from its last
+                // connection if the server didn't notice yet that it dropped
+                if let Some(ResumeRequest(token)) = resume_requests.remove(entity) {
+                    let grace = read_data.settings.session_grace;
+                    match (&read_data.entities, &players, &sessions).join().find(
+                        |(session_entity, player, session)| {
+                            *session_entity != entity
+                                && !grace.is_zero()
+                                && player.uuid() == uuid
+                                && session.token == token
+                                && session.resumable(read_data.time.0, grace)
+                        },
+                    ) {
+                        Some((session_entity, _, _)) => {
+                            server_emitter.emit(ServerEvent::ResumeSession {
+                                entity,
+                                session_entity,
+                            });
+                        },
+                        None => {
+                            server_emitter.emit(ServerEvent::ClientDisconnect(
+                                entity,
+                                common::comp::DisconnectReason::Kicked,
+                            ));
+                            client.send(Err(RegisterError::SessionExpired))?;
+                        },
+                    }
+                    return Ok(());
+                }
+
+                // Check if user is already logged-in, or has a session waiting to be resumed
                 if let Some((old_entity, old_client, _)) =
-                    (&read_data.entities, &read_data.clients, &players)
+                    (&read_data.entities, read_data.clients.maybe(), &players)
                         .join()
                         .find(|(_, _, old_player)| old_player.uuid() == uuid)
                 {
@@ -159,9 +221,12 @@ impl<'a> System<'a> for Sys {
                         old_entity,
                         common::comp::DisconnectReason::NewerLogin,
                     ));
-                    let _ = old_client.send(ServerGeneral::Disconnect(DisconnectReason::Kicked(
-                        String::from("You have logged in from another location."),
-                    )));
+                    if let Some(old_client) = old_client {
+                        let _ =
+                            old_client.send(ServerGeneral::Disconnect(DisconnectReason::Kicked(
+                                String::from("You have logged in from another location."),
+                            )));
+                    }
                     // We can't login the new client right now as the
                     // removal of the old client and player occurs later in
                     // the tick, so we instead setup the new login to be
@@ -187,6 +252,27 @@ impl<'a> System<'a> for Sys {
                     return Ok(());
                 }
 
+                // Hold the player in the login queue while the server is full, unless they are
+                // an admin or on the whitelist
+                if admin.is_none() && !read_data.editable_settings.whitelist.contains_key(&uuid) {
+                    let free_slots = read_data
+                        .settings
+                        .max_players
+                        .saturating_sub(players.join().count());
+                    if !login_queue.admit(entity, free_slots, read_data.time.0) {
+                        if let Some(position) =
+                            login_queue.position_update(entity, read_data.time.0)
+                        {
+                            client.send(position)?;
+                        }
+                        retries.push((
+                            entity,
+                            PendingLogin::new_success(player.alias.clone(), uuid),
+                        ));
+                        return Ok(());
+                    }
+                }
+
                 if let Ok(StorageEntry::Vacant(v)) = players.entry(entity) {
                     // Add Player component to this client, if the entity exists.
                     v.insert(player);
@@ -203,6 +289,13 @@ impl<'a> System<'a> for Sys {
                     // Tell the client its request was successful.
                     client.send(Ok(()))?;
 
+                    // Give it a session to resume if its connection drops
+                    let session = Session::new();
+                    if !read_data.settings.session_grace.is_zero() {
+                        client.send(ServerGeneral::SessionStarted(session.token))?;
+                    }
+                    let _ = sessions.insert(entity, session);
+
                     // Send initial player list
                     client.send(ServerGeneral::PlayerListUpdate(PlayerListUpdate::Init(
                         player_list.clone(),
@@ -0,0 +1,85 @@
+use crate::{client::Client, presence::Presence, Settings};
+use common::{comp::Pos, resources::DeltaTime, uid::Uid};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::{
+    ClientVoiceMsg, ServerVoiceMsg, VoiceFrame, MAX_VOICE_FRAME_BYTES, VOICE_FRAMES_PER_SECOND,
+};
+use specs::{Entities, Join, Read, ReadStorage};
+use std::sync::atomic::Ordering;
+use tracing::debug;
+
+/// This system relays the voice of the players to the ones nearby
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, DeltaTime>,
+        Read<'a, Settings>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Client>,
+    );
+
+    const NAME: &'static str = "msg::voice";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, dt, settings, uids, positions, presences, clients): Self::SystemData,
+    ) {
+        // A frame more than what is spoken during the tick is let through, for the
+        // ones delayed by the network
+        let max_frames = (VOICE_FRAMES_PER_SECOND * dt.0).ceil() as usize + 1;
+        let mut frames: Vec<(Uid, Pos, VoiceFrame)> = Vec::new();
+        for (entity, client, uid, pos, _) in
+            (&entities, &clients, &uids, &positions, &presences).join()
+        {
+            let mut received = 0;
+            let res = super::try_recv_all(client, 6, |client, msg: ClientVoiceMsg| {
+                match msg {
+                    ClientVoiceMsg::Listen(listen) => {
+                        client.voice_listening.store(listen, Ordering::Relaxed)
+                    },
+                    ClientVoiceMsg::Frame(frame) => {
+                        // Frames are still read while voice chat is disabled or the client
+                        // sends too many, so that they don't pile up in the stream
+                        received += 1;
+                        if settings.voice_chat.enabled
+                            && frame.data.len() <= MAX_VOICE_FRAME_BYTES
+                            && received <= max_frames
+                        {
+                            frames.push((*uid, *pos, frame));
+                        }
+                    },
+                }
+                Ok(())
+            });
+            // Network errors disconnect the client in the other message systems
+            if let Err(e) = res {
+                debug!(
+                    ?entity,
+                    ?e,
+                    "network error with client while receiving voice"
+                );
+            }
+        }
+
+        let range_squared = settings.voice_chat.range.powi(2);
+        for (speaker, speaker_pos, frame) in frames {
+            for (client, uid, pos, _) in (&clients, &uids, &positions, &presences).join() {
+                if *uid != speaker
+                    && client.voice_listening.load(Ordering::Relaxed)
+                    && pos.0.distance_squared(speaker_pos.0) <= range_squared
+                {
+                    client.send_fallible(ServerVoiceMsg {
+                        speaker,
+                        frame: frame.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{settings::Settings, Tick};
+use common::{
+    comp::{Agent, Alignment, Buff, BuffChange, BuffData, BuffSource, Buffs},
+    event::{EventBus, ServerEvent},
+    resources::TimeOfDay,
+    time::DayPeriod,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{Entities, Join, Read, ReadStorage};
+use std::time::Duration;
+
+/// How often (in ticks) the night buffs are refreshed
+const REFRESH_TICKS: u64 = 150;
+/// Night buffs last a bit longer than the refresh interval so they don't run
+/// out in between, and wear off by themselves once it's day again
+const NIGHT_BUFF_DURATION: Duration = Duration::from_secs(10);
+
+/// This system gives hostile creatures the buffs configured for the night.
+///
+/// Aggressive wildlife spawns with `Alignment::Enemy` as well, so only those
+/// are buffed: `Alignment::Wild` is left to peaceful animals, which don't get
+/// any more dangerous at night.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Tick>,
+        Read<'a, TimeOfDay>,
+        Read<'a, Settings>,
+        Read<'a, EventBus<ServerEvent>>,
+        ReadStorage<'a, Alignment>,
+        ReadStorage<'a, Agent>,
+        ReadStorage<'a, Buffs>,
+    );
+
+    const NAME: &'static str = "night";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, tick, time_of_day, settings, server_bus, alignments, agents, buffs): Self::SystemData,
+    ) {
+        let hostile_buffs = &settings.gameplay.night.hostile_buffs;
+        if tick.0.rem_euclid(REFRESH_TICKS) != 0
+            || hostile_buffs.is_empty()
+            || DayPeriod::from(time_of_day.0).is_light()
+        {
+            return;
+        }
+
+        let mut server_emitter = server_bus.emitter();
+        for (entity, _, _, _) in (&entities, &alignments, &agents, &buffs)
+            .join()
+            .filter(|(_, alignment, _, _)| matches!(alignment, Alignment::Enemy))
+        {
+            for (kind, strength) in hostile_buffs.iter() {
+                server_emitter.emit(ServerEvent::Buff {
+                    entity,
+                    buff_change: BuffChange::Add(Buff::new(
+                        *kind,
+                        BuffData::new(*strength, Some(NIGHT_BUFF_DURATION)),
+                        Vec::new(),
+                        BuffSource::World,
+                    )),
+                });
+            }
+        }
+    }
+}
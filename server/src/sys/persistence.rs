@@ -2,7 +2,8 @@ use crate::{persistence::character_updater, presence::Presence, sys::SysSchedule
 use common::{
     comp::{
         pet::{is_tameable, Pet},
-        ActiveAbilities, Alignment, Body, Inventory, MapMarker, SkillSet, Stats, Waypoint,
+        ActiveAbilities, Alignment, Body, Inventory, MapMarker, QuestLog, Reputation, SkillSet,
+        Stats, Waypoint,
     },
     uid::Uid,
 };
@@ -26,6 +27,8 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Pet>,
         ReadStorage<'a, Stats>,
         ReadStorage<'a, ActiveAbilities>,
+        ReadStorage<'a, QuestLog>,
+        ReadStorage<'a, Reputation>,
         WriteExpect<'a, character_updater::CharacterUpdater>,
         Write<'a, SysScheduler<Self>>,
     );
@@ -48,6 +51,8 @@ impl<'a> System<'a> for Sys {
             pets,
             stats,
             active_abilities,
+            quest_logs,
+            reputations,
             mut updater,
             mut scheduler,
         ): Self::SystemData,
@@ -62,6 +67,8 @@ impl<'a> System<'a> for Sys {
                     player_waypoints.maybe(),
                     &active_abilities,
                     map_markers.maybe(),
+                    quest_logs.maybe(),
+                    reputations.maybe(),
                 )
                     .join()
                     .filter_map(
@@ -73,6 +80,8 @@ impl<'a> System<'a> for Sys {
                             waypoint,
                             active_abilities,
                             map_marker,
+                            quest_log,
+                            reputation,
                         )| match presence.kind {
                             PresenceKind::Character(id) => {
                                 let pets = (&alignments, &bodies, &stats, &pets)
@@ -98,6 +107,8 @@ impl<'a> System<'a> for Sys {
                                     waypoint,
                                     active_abilities,
                                     map_marker,
+                                    quest_log,
+                                    reputation,
                                 ))
                             },
                             PresenceKind::Spectator | PresenceKind::Possessor => None,
@@ -0,0 +1,41 @@
+use crate::{placed_blocks::PlacedBlocks, sys::SysScheduler};
+use common::terrain::TerrainGrid;
+use common_ecs::{Job, Origin, Phase, System};
+use common_state::TerrainChanges;
+use specs::{Read, ReadExpect, Write};
+
+/// This system forgets the blocks placed by players which were replaced by
+/// anything else than their material, and saves the ones left from time to
+/// time
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        ReadExpect<'a, TerrainGrid>,
+        Read<'a, TerrainChanges>,
+        Write<'a, PlacedBlocks>,
+        Write<'a, SysScheduler<Self>>,
+    );
+
+    const NAME: &'static str = "placed_blocks";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (terrain, terrain_changes, mut placed_blocks, mut scheduler): Self::SystemData,
+    ) {
+        for (pos, block) in terrain_changes.modified_blocks.iter() {
+            let chunk_key = terrain.pos_key(*pos);
+            if placed_blocks
+                .material_at(chunk_key, *pos)
+                .map_or(false, |material| material.block() != *block)
+            {
+                placed_blocks.remove(chunk_key, *pos);
+            }
+        }
+        if scheduler.should_run() {
+            placed_blocks.save();
+        }
+    }
+}
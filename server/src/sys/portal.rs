@@ -0,0 +1,119 @@
+use crate::{client::Client, portal::PortalNetwork};
+use common::{
+    comp::{
+        portal::{PortalId, MAX_CHANNEL_MOVEMENT},
+        ForceUpdate, Health, Pos, Vel,
+    },
+    resources::Time,
+    terrain::TerrainGrid,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::{Notification, ServerGeneral};
+use specs::{Component, Entities, Join, Read, ReadExpect, ReadStorage, VecStorage, WriteStorage};
+use vek::*;
+
+/// Players still there this long after the channel ended give up on waiting
+/// for the destination to be loaded
+const MAX_LOAD_WAIT: f64 = 10.0;
+
+/// A player waiting next to a portal to be teleported
+#[derive(Copy, Clone, Debug)]
+pub struct PortalChannel {
+    pub destination: PortalId,
+    /// Chunk the player will be teleported to, kept loaded while they wait
+    pub destination_chunk: Vec2<i32>,
+    pub start_pos: Vec3<f32>,
+    pub end_time: f64,
+}
+
+impl Component for PortalChannel {
+    type Storage = VecStorage<Self>;
+}
+
+/// This system teleports the players who waited long enough next to a portal,
+/// once their destination is loaded
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Time>,
+        ReadExpect<'a, TerrainGrid>,
+        ReadExpect<'a, PortalNetwork>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, Pos>,
+        WriteStorage<'a, Vel>,
+        WriteStorage<'a, ForceUpdate>,
+        WriteStorage<'a, PortalChannel>,
+    );
+
+    const NAME: &'static str = "portal";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            time,
+            terrain,
+            network,
+            healths,
+            clients,
+            mut positions,
+            mut velocities,
+            mut force_updates,
+            mut channels,
+        ): Self::SystemData,
+    ) {
+        let mut finished = Vec::new();
+        for (entity, channel, pos, health, client) in (
+            &entities,
+            &channels,
+            &mut positions,
+            healths.maybe(),
+            clients.maybe(),
+        )
+            .join()
+        {
+            let destination = network.get(channel.destination);
+            let interrupted = pos.0.distance_squared(channel.start_pos)
+                > MAX_CHANNEL_MOVEMENT.powi(2)
+                || health.map_or(false, |h| h.is_dead)
+                || destination.is_none()
+                || time.0 > channel.end_time + MAX_LOAD_WAIT;
+            if interrupted {
+                if let Some(client) = client {
+                    client.send_fallible(ServerGeneral::Notification(
+                        Notification::TravelInterrupted,
+                    ));
+                }
+                finished.push(entity);
+                continue;
+            }
+
+            if time.0 < channel.end_time {
+                continue;
+            }
+            // Don't drop players into the void, wait for the destination to be loaded
+            if let (Some(destination), Some(_)) =
+                (destination, terrain.get_key(channel.destination_chunk))
+            {
+                pos.0 = terrain
+                    .try_find_space(destination.pos.as_::<i32>() + Vec3::unit_x() * 2)
+                    .map_or(destination.pos, |pos| pos.as_::<f32>());
+                if let Some(vel) = velocities.get_mut(entity) {
+                    vel.0 = Vec3::zero();
+                }
+                if let Some(force_update) = force_updates.get_mut(entity) {
+                    force_update.update();
+                }
+                finished.push(entity);
+            }
+        }
+        for entity in finished {
+            channels.remove(entity);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use common::{
+    comp::{inventory::item::ItemDefinitionId, quest::QuestLog, Inventory, Item, Pos},
+    event::{EventBus, ServerEvent},
+};
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{Entities, Join, Read, ReadStorage, WriteStorage};
+
+/// Amount of the item with the given definition id in the inventory
+pub fn item_count(inventory: &Inventory, item: &str) -> u32 {
+    inventory
+        .slots()
+        .flatten()
+        .filter(|it| matches!(it.item_definition_id(), ItemDefinitionId::Simple(id) if id == item))
+        .map(Item::amount)
+        .sum()
+}
+
+/// This system updates fetch and reach objectives of quests and hands in
+/// completed quests. Kill objectives are counted when the kill happens.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, EventBus<ServerEvent>>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Inventory>,
+        WriteStorage<'a, QuestLog>,
+    );
+
+    const NAME: &'static str = "quest";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, server_bus, positions, inventories, mut quest_logs): Self::SystemData,
+    ) {
+        let mut server_emitter = server_bus.emitter();
+
+        // Work on copies so that unchanged quest logs aren't flagged for sync
+        let updated = (&entities, &positions, &quest_logs, inventories.maybe())
+            .join()
+            .filter_map(|(entity, pos, quest_log, inventory)| {
+                let mut quest_log = quest_log.clone();
+                let mut changed = quest_log.update_reach(pos.0.xy());
+                if let Some(inventory) = inventory {
+                    changed |= quest_log.update_fetch(|item| item_count(inventory, item));
+                }
+                let completed = quest_log.take_completed();
+                (changed || !completed.is_empty()).then(|| (entity, quest_log, completed))
+            })
+            .collect::<Vec<_>>();
+
+        for (entity, quest_log, completed) in updated {
+            for quest in completed {
+                server_emitter.emit(ServerEvent::CompleteQuest { entity, quest });
+            }
+            if let Some(mut old) = quest_logs.get_mut(entity) {
+                *old = quest_log;
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    audit::Rollbacks, client::Client, console::ConsoleOutput, sys::terrain::TerrainPersistenceData,
+};
+use common::{comp::ChatType, terrain::TerrainGrid};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use common_state::BlockChange;
+use specs::{Entity as EcsEntity, ReadExpect, ReadStorage, Write, WriteStorage};
+
+/// Chunks reverted by each rollback every tick
+const CHUNKS_PER_TICK: usize = 4;
+
+fn notify(
+    clients: &ReadStorage<Client>,
+    consoles: &mut WriteStorage<ConsoleOutput>,
+    entity: EcsEntity,
+    message: String,
+) {
+    if let Some(client) = clients.get(entity) {
+        client.send_fallible(ServerGeneral::server_msg(ChatType::CommandInfo, message));
+    } else if let Some(output) = consoles.get_mut(entity) {
+        output.0.push(message);
+    }
+}
+
+/// This system reverts the blocks of the rollbacks in progress a few chunks at
+/// a time, telling whoever asked for them how far along they are
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        ReadExpect<'a, TerrainGrid>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, ConsoleOutput>,
+        Write<'a, Rollbacks>,
+        Write<'a, BlockChange>,
+        TerrainPersistenceData<'a>,
+    );
+
+    const NAME: &'static str = "rollback";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            terrain,
+            clients,
+            mut consoles,
+            mut rollbacks,
+            mut block_change,
+            mut _terrain_persistence,
+        ): Self::SystemData,
+    ) {
+        for rollback in rollbacks.0.iter_mut() {
+            let report_every = (rollback.chunk_count() / 10).max(1);
+            let done_before = rollback.chunks_done();
+            for _ in 0..CHUNKS_PER_TICK {
+                if rollback.is_done() {
+                    break;
+                }
+                for (pos, block) in rollback.next_chunk(&terrain) {
+                    block_change.set(pos, block);
+                    #[cfg(feature = "persistent_world")]
+                    if let Some(terrain_persistence) = _terrain_persistence.as_mut() {
+                        terrain_persistence.set_block(pos, block);
+                    }
+                }
+            }
+            let done = rollback.chunks_done();
+            if !rollback.is_done() && done / report_every > done_before / report_every {
+                notify(
+                    &clients,
+                    &mut consoles,
+                    rollback.requester,
+                    format!("Rolled back {}/{} chunks...", done, rollback.chunk_count()),
+                );
+            }
+        }
+
+        rollbacks.0.retain(|rollback| {
+            if rollback.is_done() {
+                notify(
+                    &clients,
+                    &mut consoles,
+                    rollback.requester,
+                    format!(
+                        "Reverted {} blocks changed by {} in {} chunks, {} blocks changed since \
+                         are left alone",
+                        rollback.reverted_count,
+                        rollback.username,
+                        rollback.chunk_count(),
+                        rollback.skipped_count
+                    ),
+                );
+            }
+            !rollback.is_done()
+        });
+    }
+}
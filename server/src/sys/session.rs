@@ -0,0 +1,42 @@
+use crate::{session::Session, settings::Settings};
+use common::{
+    comp::DisconnectReason,
+    event::{EventBus, ServerEvent},
+    resources::Time,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{Entities, Join, Read, ReadStorage};
+
+/// This system ends the sessions that weren't resumed within the grace window
+/// after the connection of their client dropped, removing the characters left
+/// waiting in the world
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Time>,
+        Read<'a, Settings>,
+        Read<'a, EventBus<ServerEvent>>,
+        ReadStorage<'a, Session>,
+    );
+
+    const NAME: &'static str = "session";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, time, settings, server_bus, sessions): Self::SystemData,
+    ) {
+        let mut server_emitter = server_bus.emitter();
+        for (entity, session) in (&entities, &sessions).join() {
+            if session.expired(time.0, settings.session_grace) {
+                server_emitter.emit(ServerEvent::ClientDisconnect(
+                    entity,
+                    DisconnectReason::Timeout,
+                ));
+            }
+        }
+    }
+}
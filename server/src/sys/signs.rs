@@ -0,0 +1,54 @@
+use crate::{
+    client::Client,
+    presence::Presence,
+    signs::{send_texts, Signs},
+    sys::SysScheduler,
+};
+use common::{
+    comp::Pos,
+    terrain::{SpriteKind, TerrainGrid},
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_state::TerrainChanges;
+use specs::{Read, ReadExpect, ReadStorage, Write};
+
+/// This system forgets the text of the signs which were taken down or
+/// replaced, so that it doesn't show up on the next block put there, and saves
+/// the signs from time to time
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        ReadExpect<'a, TerrainGrid>,
+        Read<'a, TerrainChanges>,
+        Write<'a, Signs>,
+        Write<'a, SysScheduler<Self>>,
+        ReadStorage<'a, Client>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Pos>,
+    );
+
+    const NAME: &'static str = "signs";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (terrain, terrain_changes, mut signs, mut scheduler, clients, presences, positions): Self::SystemData,
+    ) {
+        let mut taken_down = Vec::new();
+        for (pos, block) in terrain_changes.modified_blocks.iter() {
+            if block.get_sprite() != Some(SpriteKind::Sign)
+                && signs.remove(terrain.pos_key(*pos), *pos)
+            {
+                taken_down.push((*pos, String::new()));
+            }
+        }
+        if !taken_down.is_empty() {
+            send_texts(&terrain, &clients, &presences, &positions, &taken_down);
+        }
+        if scheduler.should_run() {
+            signs.save();
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{client::Client, settings::Settings};
+use common::{
+    calendar::Calendar,
+    comp::{Buff, BuffChange, BuffData, BuffKind, BuffSource, Health, Player, Pos},
+    event::{EventBus, ServerEvent},
+    resources::{DayCycleFactor, TimeOfDay},
+    time::DayPeriod,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::{Notification, ServerGeneral};
+use specs::{
+    Component, Entities, Join, Read, ReadExpect, ReadStorage, VecStorage, Write, WriteStorage,
+};
+use std::time::Duration;
+use vek::*;
+
+const DAY: f64 = 60.0 * 60.0 * 24.0;
+/// Time of day sleeping players wake up at
+const WAKE_UP_TIME: f64 = 60.0 * 60.0 * 7.0;
+/// Players who move further than this from their bed wake up
+const MAX_BED_DIST: f32 = 3.0;
+const RESTED_STRENGTH: f32 = 0.1;
+const RESTED_DURATION: Duration = Duration::from_secs(600);
+
+/// A player sleeping in a bed, waiting for the night to be skipped
+#[derive(Copy, Clone, Debug)]
+pub struct Sleeping {
+    pub bed_pos: Vec3<i32>,
+}
+
+impl Component for Sleeping {
+    type Storage = VecStorage<Self>;
+}
+
+/// Number of sleeping players needed to skip the night, always at least one
+pub fn sleepers_needed(players: usize, vote_fraction: f32) -> u32 {
+    ((players as f32 * vote_fraction).ceil() as u32).max(1)
+}
+
+/// This system skips the night once enough players are sleeping, and wakes
+/// them up well rested in the morning
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Settings>,
+        Write<'a, TimeOfDay>,
+        Read<'a, DayCycleFactor>,
+        ReadExpect<'a, Calendar>,
+        Read<'a, EventBus<ServerEvent>>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, Sleeping>,
+    );
+
+    const NAME: &'static str = "sleep";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            settings,
+            mut time_of_day,
+            day_cycle_factor,
+            calendar,
+            server_bus,
+            players,
+            positions,
+            healths,
+            clients,
+            mut sleepings,
+        ): Self::SystemData,
+    ) {
+        // Players wake up when they leave their bed or die
+        let restless = (&entities, &sleepings, positions.maybe(), healths.maybe())
+            .join()
+            .filter(|(_, sleeping, pos, health)| {
+                pos.map_or(true, |pos| {
+                    pos.0.distance_squared(sleeping.bed_pos.as_() + 0.5) > MAX_BED_DIST.powi(2)
+                }) || health.map_or(false, |h| h.is_dead)
+            })
+            .map(|(entity, _, _, _)| entity)
+            .collect::<Vec<_>>();
+        for entity in restless {
+            sleepings.remove(entity);
+        }
+
+        let sleeping = (&sleepings, &players).join().count();
+        if sleeping == 0 {
+            return;
+        }
+        let needed = sleepers_needed(
+            players.join().count(),
+            settings.gameplay.night.sleep_vote_fraction,
+        );
+        let is_night = DayPeriod::from(time_of_day.0).is_dark();
+        if is_night && (sleeping as u32) < needed {
+            return;
+        }
+
+        if is_night {
+            let day_start = (time_of_day.0 / DAY).floor() * DAY;
+            time_of_day.0 = day_start
+                + WAKE_UP_TIME
+                + if time_of_day.0 - day_start > WAKE_UP_TIME {
+                    DAY
+                } else {
+                    0.0
+                };
+
+            // Update all clients right away instead of waiting for the next sync
+            let mut tod_lazymsg = None;
+            for client in (&clients).join() {
+                let msg = tod_lazymsg.unwrap_or_else(|| {
+                    client.prepare(ServerGeneral::TimeOfDay(
+                        *time_of_day,
+                        (*calendar).clone(),
+                        *day_cycle_factor,
+                    ))
+                });
+                let _ = client.send_prepared(&msg);
+                tod_lazymsg = Some(msg);
+            }
+        }
+
+        let mut server_emitter = server_bus.emitter();
+        for (entity, _, client) in (&entities, &sleepings, clients.maybe()).join() {
+            server_emitter.emit(ServerEvent::Buff {
+                entity,
+                buff_change: BuffChange::Add(Buff::new(
+                    BuffKind::Rested,
+                    BuffData::new(RESTED_STRENGTH, Some(RESTED_DURATION)),
+                    Vec::new(),
+                    BuffSource::World,
+                )),
+            });
+            if let Some(client) = client {
+                client.send_fallible(ServerGeneral::Notification(Notification::WokeUp));
+            }
+        }
+        sleepings.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_least_one_sleeper_is_needed() {
+        assert_eq!(sleepers_needed(1, 0.5), 1);
+        assert_eq!(sleepers_needed(0, 0.5), 1);
+        assert_eq!(sleepers_needed(3, 0.5), 2);
+        assert_eq!(sleepers_needed(4, 0.0), 1);
+        assert_eq!(sleepers_needed(4, 1.0), 4);
+    }
+}
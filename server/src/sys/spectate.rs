@@ -0,0 +1,75 @@
+use crate::{client::Client, presence::Presence};
+use common::{
+    comp::{ChatType, Pos},
+    uid::{Uid, UidAllocator},
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::{PresenceKind, ServerGeneral};
+use specs::{
+    saveload::MarkerAllocator, Component, Entities, Join, Read, ReadStorage, VecStorage,
+    WriteStorage,
+};
+
+/// A moderator spectating a player, with the camera following them
+#[derive(Copy, Clone, Debug)]
+pub struct Spectating {
+    pub target: Uid,
+}
+
+impl Component for Spectating {
+    type Storage = VecStorage<Self>;
+}
+
+/// This system keeps the spectators next to the player they follow, so that
+/// they are sent everything around them, and stops following players who left
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, UidAllocator>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, Pos>,
+        WriteStorage<'a, Spectating>,
+    );
+
+    const NAME: &'static str = "spectate";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, uid_allocator, presences, clients, mut positions, mut spectatings): Self::SystemData,
+    ) {
+        let mut stopped = Vec::new();
+        for (entity, spectating, presence, client) in
+            (&entities, &spectatings, &presences, &clients).join()
+        {
+            // Spectators who picked a character stop following anyone
+            if presence.kind != PresenceKind::Spectator {
+                client.send_fallible(ServerGeneral::SpectatePlayer(None));
+                stopped.push(entity);
+                continue;
+            }
+            let target_pos = uid_allocator
+                .retrieve_entity_internal(spectating.target.into())
+                .and_then(|target| positions.get(target).copied());
+            if let Some(target_pos) = target_pos {
+                if let Some(pos) = positions.get_mut(entity) {
+                    *pos = target_pos;
+                }
+            } else {
+                client.send_fallible(ServerGeneral::SpectatePlayer(None));
+                client.send_fallible(ServerGeneral::server_msg(
+                    ChatType::CommandInfo,
+                    "The player you were spectating is gone.",
+                ));
+                stopped.push(entity);
+            }
+        }
+        for entity in stopped {
+            spectatings.remove(entity);
+        }
+    }
+}
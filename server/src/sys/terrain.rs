@@ -12,6 +12,7 @@ use crate::{
     presence::{Presence, RepositionOnChunkLoad},
     rtsim::RtSim,
     settings::Settings,
+    sys::portal::PortalChannel,
     ChunkRequest, SpawnPoint, Tick,
 };
 use common::{
@@ -78,6 +79,7 @@ impl<'a> System<'a> for Sys {
         WriteStorage<'a, ForceUpdate>,
         WriteStorage<'a, Waypoint>,
         ReadExpect<'a, Time>,
+        ReadStorage<'a, PortalChannel>,
     );
 
     const NAME: &'static str = "terrain";
@@ -111,6 +113,7 @@ impl<'a> System<'a> for Sys {
             mut force_update,
             mut waypoints,
             time,
+            portal_channels,
         ): Self::SystemData,
     ) {
         let mut server_emitter = server_event_bus.emitter();
@@ -189,6 +192,9 @@ impl<'a> System<'a> for Sys {
                     NpcData::Waypoint(pos) => {
                         server_emitter.emit(ServerEvent::CreateWaypoint(pos));
                     },
+                    NpcData::Minigame(pos) => {
+                        server_emitter.emit(ServerEvent::CreateMinigame(pos));
+                    },
                     NpcData::Data {
                         pos,
                         stats,
@@ -299,6 +305,11 @@ impl<'a> System<'a> for Sys {
                     }
                 }
 
+                // Keep the destination of players about to travel through a portal loaded
+                if portal_channels.join().any(|channel| channel.destination_chunk == chunk_key) {
+                    should_drop = false;
+                }
+
                 if should_drop {
                     chunks_to_remove.push(chunk_key);
                 }
@@ -342,6 +353,7 @@ pub enum NpcData {
         loot: LootSpec<String>,
     },
     Waypoint(Vec3<f32>),
+    Minigame(Vec3<f32>),
 }
 
 impl NpcData {
@@ -349,6 +361,7 @@ impl NpcData {
         let EntityInfo {
             // flags
             is_waypoint,
+            is_minigame,
             has_agency,
             agent_mark,
             alignment,
@@ -372,6 +385,9 @@ impl NpcData {
         if is_waypoint {
             return Self::Waypoint(pos);
         }
+        if is_minigame {
+            return Self::Minigame(pos);
+        }
 
         let name = name.unwrap_or_else(|| "Unnamed".to_string());
         let stats = comp::Stats::new(name);
@@ -0,0 +1,70 @@
+use crate::client::Client;
+use common::{
+    comp::{
+        agent::{Agent, AgentEvent},
+        Pos,
+    },
+    consts::MAX_TRADE_RANGE,
+    trade::{TradeResult, Trades},
+    uid::UidAllocator,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use specs::{saveload::MarkerAllocator, Read, ReadStorage, Write, WriteStorage};
+
+/// Extra leeway on top of `MAX_TRADE_RANGE` so that trades aren't cancelled by
+/// small movements of parties that started trading at the edge of the range
+const TRADE_RANGE_LEEWAY: f32 = 5.0;
+
+/// This system cancels trades whose parties moved too far apart
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, UidAllocator>,
+        Write<'a, Trades>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, Agent>,
+    );
+
+    const NAME: &'static str = "trade_range";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (uid_allocator, mut trades, positions, clients, mut agents): Self::SystemData,
+    ) {
+        let max_dist_sqr = (MAX_TRADE_RANGE + TRADE_RANGE_LEEWAY).powi(2);
+        let out_of_range = trades
+            .trades
+            .iter()
+            .filter(|(_, trade)| {
+                let [a, b] = trade.parties.map(|uid| {
+                    uid_allocator
+                        .retrieve_entity_internal(uid.into())
+                        .and_then(|entity| positions.get(entity))
+                });
+                // Trades with parties that no longer exist are cleaned up elsewhere
+                matches!((a, b), (Some(a), Some(b)) if a.0.distance_squared(b.0) > max_dist_sqr)
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        for id in out_of_range {
+            for uid in trades.cancel_trade(id).into_iter().flatten() {
+                if let Some(entity) = uid_allocator.retrieve_entity_internal(uid.into()) {
+                    if let Some(client) = clients.get(entity) {
+                        client.send_fallible(ServerGeneral::FinishedTrade(TradeResult::Declined));
+                    }
+                    if let Some(agent) = agents.get_mut(entity) {
+                        agent
+                            .inbox
+                            .push_back(AgentEvent::FinishedTrade(TradeResult::Declined));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{client::Client, Tick};
+use common::{
+    comp::TrainingDummy,
+    resources::Time,
+    uid::{Uid, UidAllocator},
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use specs::{saveload::MarkerAllocator, Join, Read, ReadStorage, WriteStorage};
+
+/// This system tells the players hitting training dummies how much damage they
+/// dealt to them
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, Tick>,
+        Read<'a, Time>,
+        Read<'a, UidAllocator>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, TrainingDummy>,
+    );
+
+    const NAME: &'static str = "training_dummy";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (tick, time, uid_allocator, uids, clients, mut training_dummies): Self::SystemData,
+    ) {
+        // Only operate once per second
+        if tick.0.rem_euclid(30) != 0 {
+            return;
+        }
+
+        for (uid, training_dummy) in (&uids, &mut training_dummies).join() {
+            for (attacker, report) in training_dummy.reports(*uid, *time) {
+                if let Some(client) = uid_allocator
+                    .retrieve_entity_internal(attacker.0)
+                    .and_then(|attacker| clients.get(attacker))
+                {
+                    client.send_fallible(ServerGeneral::DamageReport(report));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{client::Client, sys::SysScheduler};
+use common::{
+    comp::{ForceUpdate, Health, HealthChange, Pos},
+    event::{EventBus, ServerEvent},
+    resources::Time,
+    world_border::WorldBorder,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use specs::{Entities, Join, Read, ReadExpect, ReadStorage, Write, WriteStorage};
+
+/// This system puts the players beyond the world border back inside it, or
+/// hurts them every second they stay there, and keeps the clients up to date
+/// with where the border is
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, WorldBorder>,
+        Read<'a, Time>,
+        Read<'a, EventBus<ServerEvent>>,
+        Write<'a, SysScheduler<Self>>,
+        ReadStorage<'a, Client>,
+        ReadStorage<'a, Health>,
+        WriteStorage<'a, Pos>,
+        WriteStorage<'a, ForceUpdate>,
+    );
+
+    const NAME: &'static str = "world_border";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            border,
+            time,
+            server_bus,
+            mut scheduler,
+            clients,
+            healths,
+            mut positions,
+            mut force_updates,
+        ): Self::SystemData,
+    ) {
+        let Some(shape) = border.shape else { return };
+        let every_second = scheduler.should_run();
+        let mut server_emitter = server_bus.emitter();
+
+        let mut lazy_msg = None;
+        for (entity, client, health, pos, force_update) in (
+            &entities,
+            &clients,
+            &healths,
+            &mut positions,
+            &mut force_updates,
+        )
+            .join()
+        {
+            // The clients move the border along by themselves, this only corrects them
+            if every_second {
+                if lazy_msg.is_none() {
+                    lazy_msg = Some(client.prepare(ServerGeneral::WorldBorder(border.clone())));
+                }
+                lazy_msg.as_ref().map(|msg| client.send_prepared(msg));
+            }
+
+            if health.is_dead || shape.distance(pos.0.xy()) <= 0.0 {
+                continue;
+            }
+            if border.push_back {
+                pos.0 = shape.clamp(pos.0.xy()).with_z(pos.0.z);
+                force_update.update();
+            } else if every_second && border.damage > 0.0 {
+                server_emitter.emit(ServerEvent::HealthChange {
+                    entity,
+                    change: HealthChange {
+                        amount: -border.damage,
+                        by: None,
+                        cause: None,
+                        time: *time,
+                        crit: false,
+                        instance: rand::random(),
+                    },
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+/// Most ticks run in a row to catch up after a slow one
+pub const MAX_CATCH_UP_STEPS: u32 = 3;
+/// Backlog, in ticks, past which the server is considered overloaded and the
+/// backlog is given up on
+pub const OVERLOADED_BACKLOG: u32 = 30;
+
+/// Ticks to run after the time elapsed since the last ones
+pub struct CatchUpSteps {
+    pub count: u32,
+    /// Ticks of backlog given up on because the server couldn't keep up, if
+    /// any
+    pub dropped: Option<u32>,
+}
+
+/// Keeps the simulation in step with real time when ticks overrun, by running
+/// a few more fixed length ticks in a row instead of stretching the next one.
+/// The frontend runs the server as many times as `steps` says, with
+/// `target_dt` as the dt.
+pub struct TickCatchUp {
+    target_dt: Duration,
+    backlog: Duration,
+}
+
+impl TickCatchUp {
+    pub fn new(target_dt: Duration) -> Self {
+        Self {
+            target_dt,
+            backlog: Duration::ZERO,
+        }
+    }
+
+    pub fn target_dt(&self) -> Duration { self.target_dt }
+
+    pub fn steps(&mut self, elapsed: Duration) -> CatchUpSteps {
+        self.backlog += elapsed;
+        let due = (self.backlog.as_secs_f64() / self.target_dt.as_secs_f64()) as u32;
+        let count = due.clamp(1, 1 + MAX_CATCH_UP_STEPS);
+        self.backlog = self.backlog.saturating_sub(self.target_dt * count);
+
+        let backlog = (self.backlog.as_secs_f64() / self.target_dt.as_secs_f64()) as u32;
+        let dropped = (backlog > OVERLOADED_BACKLOG).then(|| {
+            self.backlog = Duration::ZERO;
+            backlog
+        });
+        CatchUpSteps { count, dropped }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Exactly representable, so that the step counts don't depend on rounding
+    const TARGET_DT: Duration = Duration::from_millis(125);
+
+    #[test]
+    fn runs_one_step_per_tick_on_time() {
+        let mut catch_up = TickCatchUp::new(TARGET_DT);
+        for _ in 0..10 {
+            let steps = catch_up.steps(TARGET_DT);
+            assert_eq!(steps.count, 1);
+            assert_eq!(steps.dropped, None);
+        }
+        // Ticks that were faster than needed still run once
+        assert_eq!(catch_up.steps(TARGET_DT / 2).count, 1);
+    }
+
+    #[test]
+    fn catches_up_a_stall_at_most_a_few_steps_at_a_time() {
+        let mut catch_up = TickCatchUp::new(TARGET_DT);
+        let steps = catch_up.steps(TARGET_DT * 3);
+        assert_eq!(steps.count, 3);
+        assert_eq!(steps.dropped, None);
+
+        // 10 ticks late: the rest of the backlog is caught up on the next ticks
+        let steps = catch_up.steps(TARGET_DT * 10);
+        assert_eq!(steps.count, 1 + MAX_CATCH_UP_STEPS);
+        assert_eq!(steps.dropped, None);
+        assert_eq!(catch_up.steps(TARGET_DT).count, 1 + MAX_CATCH_UP_STEPS);
+        assert_eq!(catch_up.steps(Duration::ZERO).count, 3);
+        assert_eq!(catch_up.steps(TARGET_DT).count, 1);
+    }
+
+    #[test]
+    fn gives_up_on_a_backlog_too_large_to_catch_up() {
+        let mut catch_up = TickCatchUp::new(TARGET_DT);
+        let steps = catch_up.steps(TARGET_DT * 100);
+        assert_eq!(steps.count, 1 + MAX_CATCH_UP_STEPS);
+        assert_eq!(steps.dropped, Some(100 - 1 - MAX_CATCH_UP_STEPS));
+        // Back in step right away
+        let steps = catch_up.steps(TARGET_DT);
+        assert_eq!(steps.count, 1);
+        assert_eq!(steps.dropped, None);
+    }
+
+    #[test]
+    fn carries_the_remainder_over_to_the_next_ticks() {
+        let mut catch_up = TickCatchUp::new(TARGET_DT);
+        let one_and_a_half = TARGET_DT * 3 / 2;
+        assert_eq!(catch_up.steps(one_and_a_half).count, 1);
+        assert_eq!(catch_up.steps(one_and_a_half).count, 2);
+        assert_eq!(catch_up.steps(one_and_a_half).count, 1);
+        assert_eq!(catch_up.steps(one_and_a_half).count, 2);
+    }
+}
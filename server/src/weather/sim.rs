@@ -8,9 +8,13 @@ use common::{
 use noise::{NoiseFn, SuperSimplex, Turbulence};
 use rand::prelude::*;
 use vek::*;
-use world::World;
+use world::{World, CONFIG};
 
-use crate::weather::WEATHER_DT;
+use crate::{fire::Fires, weather::WEATHER_DT};
+
+/// Lightning strikes the tops of the trees, which reach above the altitude of
+/// the terrain
+const LIGHTNING_STRIKE_HEIGHT: f32 = 32.0;
 
 fn cell_to_wpos_center(p: Vec2<i32>) -> Vec2<i32> { p * CELL_SIZE as i32 + CELL_SIZE as i32 / 2 }
 
@@ -23,8 +27,15 @@ struct WeatherZone {
 
 struct CellConsts {
     humidity: f32,
+    /// Average temperature of the cell, snow falls instead of rain in cold
+    /// cells
+    temperature: f32,
 }
 
+/// Cells colder than this on average get snow rather than rain, it is a bit
+/// above the temperature the ground starts being covered in snow at
+const SNOWFALL_TEMP: f32 = CONFIG.snow_temp + 0.3;
+
 pub struct WeatherSim {
     size: Vec2<u32>,
     consts: Grid<CellConsts>,
@@ -41,6 +52,7 @@ impl WeatherSim {
                     .map(|i| Vec2::new(i % size.x, i / size.x))
                     .map(|p| {
                         let mut humid_sum = 0.0;
+                        let mut temp_sum = 0.0;
 
                         for y in 0..CHUNKS_PER_CELL {
                             for x in 0..CHUNKS_PER_CELL {
@@ -48,12 +60,15 @@ impl WeatherSim {
                                 if let Some(chunk) = world.sim().get(chunk_pos.as_()) {
                                     let env = chunk.get_environment();
                                     humid_sum += env.humid;
+                                    temp_sum += env.temp;
                                 }
                             }
                         }
-                        let average_humid = humid_sum / (CHUNKS_PER_CELL * CHUNKS_PER_CELL) as f32;
+                        let chunk_count = (CHUNKS_PER_CELL * CHUNKS_PER_CELL) as f32;
+                        let average_humid = humid_sum / chunk_count;
                         CellConsts {
                             humidity: average_humid.powf(0.2).min(1.0),
+                            temperature: temp_sum / chunk_count,
                         }
                     })
                     .collect::<Vec<_>>(),
@@ -90,6 +105,7 @@ impl WeatherSim {
         &mut self,
         time_of_day: &TimeOfDay,
         outcomes: &EventBus<Outcome>,
+        fires: &mut Fires,
         out: &mut WeatherGrid,
         world: &World,
     ) {
@@ -140,10 +156,17 @@ impl WeatherSim {
 
                 const RAIN_CLOUD_THRESHOLD: f32 = 0.25;
                 cell.cloud = (1.0 - pressure).max(0.0) * 0.5;
-                cell.rain = ((1.0 - pressure - RAIN_CLOUD_THRESHOLD).max(0.0)
+                let precipitation = ((1.0 - pressure - RAIN_CLOUD_THRESHOLD).max(0.0)
                     * self.consts[point].humidity
                     * 2.5)
                     .powf(0.75);
+                if self.consts[point].temperature < SNOWFALL_TEMP {
+                    cell.rain = 0.0;
+                    cell.snow = precipitation;
+                } else {
+                    cell.rain = precipitation;
+                    cell.snow = 0.0;
+                }
                 cell.wind = Vec2::new(
                     rain_nz.get(spos.into_array()).powi(3) as f32,
                     rain_nz.get((spos + 1.0).into_array()).powi(3) as f32,
@@ -154,9 +177,9 @@ impl WeatherSim {
                     let wpos = wpos.map(|e| {
                         e as f32 + thread_rng().gen_range(-1.0..1.0) * CELL_SIZE as f32 * 0.5
                     });
-                    outcomes.emit_now(Outcome::Lightning {
-                        pos: wpos.with_z(world.sim().get_alt_approx(wpos.as_()).unwrap_or(0.0)),
-                    });
+                    let pos = wpos.with_z(world.sim().get_alt_approx(wpos.as_()).unwrap_or(0.0));
+                    fires.ignite((pos + Vec3::unit_z() * LIGHTNING_STRIKE_HEIGHT).as_());
+                    outcomes.emit_now(Outcome::Lightning { pos });
                 }
             }
         }
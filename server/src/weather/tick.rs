@@ -4,7 +4,7 @@ use specs::{Read, ReadExpect, Write, WriteExpect};
 use std::sync::Arc;
 use world::World;
 
-use crate::sys::SysScheduler;
+use crate::{fire::Fires, sys::SysScheduler};
 
 use super::sim::WeatherSim;
 
@@ -18,6 +18,7 @@ impl<'a> System<'a> for Sys {
         WriteExpect<'a, WeatherGrid>,
         Write<'a, SysScheduler<Self>>,
         ReadExpect<'a, EventBus<Outcome>>,
+        Write<'a, Fires>,
         ReadExpect<'a, Arc<World>>,
     );
 
@@ -27,13 +28,13 @@ impl<'a> System<'a> for Sys {
 
     fn run(
         _job: &mut common_ecs::Job<Self>,
-        (game_time, mut sim, mut grid, mut scheduler, outcomes, world): Self::SystemData,
+        (game_time, mut sim, mut grid, mut scheduler, outcomes, mut fires, world): Self::SystemData,
     ) {
         if scheduler.should_run() {
             if grid.size() != sim.size() {
                 *grid = WeatherGrid::new(sim.size());
             }
-            sim.tick(&game_time, &outcomes, &mut grid, &world);
+            sim.tick(&game_time, &outcomes, &mut fires, &mut grid, &world);
         }
     }
 }
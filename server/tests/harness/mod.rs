@@ -0,0 +1,282 @@
+//! In-process server with clients connected over the mpsc transport. The
+//! server and all clients are ticked together with a fixed `dt`, so tests can
+//! advance the game tick by tick and assert on the state synced between them.
+//!
+//! The servers share a small world, generated by the first test and loaded by
+//! the others instead of the full size default map.
+
+use common::{
+    comp::{self, inventory::Inventory},
+    event::{EventBus, ServerEvent},
+    uid::Uid,
+    ViewDistances,
+};
+use common_net::sync::WorldSyncExt;
+use lazy_static::lazy_static;
+use specs::Builder;
+use std::{
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc, Mutex, MutexGuard},
+    thread,
+    time::Duration,
+};
+use tokio::runtime::Runtime;
+use vek::*;
+use veloren_client::{addr::ConnectionArgs, Client, Event};
+use veloren_server::{
+    persistence::{DatabaseSettings, SqlLogMode},
+    state_ext::StateExt,
+    EditableSettings, Input, Server, Settings,
+};
+use world::sim::{FileOpts, SizeOpts};
+
+/// The server always listens on this mpsc channel
+const MPSC_CHANNEL: u64 = 14004;
+const DT: Duration = Duration::from_millis(33);
+/// Upper bound for `TestServer::tick_until` before the test fails
+const MAX_TICKS: u32 = 3000;
+/// Give the network tasks a chance to deliver messages between ticks
+const NETWORK_WAIT: Duration = Duration::from_millis(1);
+/// Unlike the singleplayer server, the test server lets clients resume their
+/// session
+const SESSION_GRACE: Duration = Duration::from_secs(60);
+/// Chunks along each side of the shared world, as a power of two
+const WORLD_SIZE_LG: u32 = 7;
+
+lazy_static! {
+    /// Servers can't run in parallel since they listen on the same mpsc channel
+    static ref SERVER_LOCK: Mutex<()> = Mutex::new(());
+}
+
+pub struct TestClient {
+    pub client: Client,
+    /// Inputs used for every tick, e.g. to move the character
+    pub inputs: comp::ControllerInputs,
+    /// All events received since connecting
+    pub events: Vec<Event>,
+}
+
+impl TestClient {
+    fn tick(&mut self) {
+        let events = self
+            .client
+            .tick(self.inputs.clone(), DT, |_| {})
+            .expect("Failed to tick test client");
+        self.events.extend(events);
+        self.client.cleanup();
+    }
+}
+
+pub struct TestServer {
+    server: Server,
+    runtime: Arc<Runtime>,
+    data_dir: PathBuf,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl TestServer {
+    pub fn new() -> Self {
+        // A panicking test poisons the lock, that shouldn't fail the other tests
+        let lock = SERVER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let data_dir = std::env::temp_dir().join(format!("veloren-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        std::fs::create_dir_all(&data_dir).expect("Failed to create test data dir");
+
+        let settings = Settings {
+            session_grace: SESSION_GRACE,
+            // Kept with the build artifacts, so it is only generated again once
+            // the map format changes
+            map_file: Some(FileOpts::LoadOrGenerate {
+                name: Path::new(env!("CARGO_TARGET_TMPDIR"))
+                    .join("test-world")
+                    .to_string_lossy()
+                    .into_owned(),
+                opts: SizeOpts::new(WORLD_SIZE_LG, WORLD_SIZE_LG, 2.0),
+                overwrite: true,
+            }),
+            ..Settings::singleplayer(&data_dir)
+        };
+        let editable_settings = EditableSettings::singleplayer(&data_dir);
+        let database_settings = DatabaseSettings {
+            db_dir: data_dir.join("saves"),
+            sql_log_mode: SqlLogMode::Disabled,
+        };
+        let runtime = Arc::new(Runtime::new().unwrap());
+        let server = Server::new(
+            settings,
+            editable_settings,
+            database_settings,
+            &data_dir,
+            Arc::clone(&runtime),
+        )
+        .expect("Failed to create test server");
+
+        Self {
+            server,
+            runtime,
+            data_dir,
+            _lock: lock,
+        }
+    }
+
+    pub fn server(&self) -> &Server { &self.server }
+
+    fn tick_server(&mut self) {
+        self.server
+            .tick(Input::default(), DT)
+            .expect("Failed to tick test server");
+        self.server.cleanup();
+    }
+
+    /// Connect and register a new client. The server keeps ticking while the
+    /// client connects, other clients are not ticked.
+    pub fn connect(&mut self, username: &str) -> TestClient {
+        let (tx, rx) = mpsc::channel();
+        let runtime = Arc::clone(&self.runtime);
+        let username = username.to_owned();
+        thread::spawn(move || {
+            let result = runtime.block_on(async {
+                let mut client = Client::new(
+                    ConnectionArgs::Mpsc(MPSC_CHANNEL),
+                    Arc::clone(&runtime),
+                    &mut None,
+                )
+                .await?;
+                client.register(username, String::new(), |_| true).await?;
+                Ok::<_, veloren_client::Error>(client)
+            });
+            let _ = tx.send(result);
+        });
+
+        for _ in 0..MAX_TICKS {
+            self.tick_server();
+            match rx.try_recv() {
+                Ok(result) => {
+                    return TestClient {
+                        client: result.expect("Failed to connect test client"),
+                        inputs: comp::ControllerInputs::default(),
+                        events: Vec::new(),
+                    };
+                },
+                Err(mpsc::TryRecvError::Empty) => thread::sleep(NETWORK_WAIT),
+                Err(mpsc::TryRecvError::Disconnected) => panic!("Client thread panicked"),
+            }
+        }
+        panic!("Client didn't connect after {} ticks", MAX_TICKS);
+    }
+
+    /// Resume the session of the client from a new connection. The server
+    /// keeps ticking while the client reconnects.
+    pub fn reconnect(&mut self, mut client: TestClient) -> TestClient {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let result = client.client.reconnect();
+            let _ = tx.send(result.map(|()| client));
+        });
+
+        for _ in 0..MAX_TICKS {
+            self.tick_server();
+            match rx.try_recv() {
+                Ok(result) => return result.expect("Failed to reconnect test client"),
+                Err(mpsc::TryRecvError::Empty) => thread::sleep(NETWORK_WAIT),
+                Err(mpsc::TryRecvError::Disconnected) => panic!("Client thread panicked"),
+            }
+        }
+        panic!("Client didn't reconnect after {} ticks", MAX_TICKS);
+    }
+
+    /// Make the server time out the connection of the client, as if it dropped
+    pub fn drop_connection(&mut self, client: &TestClient) {
+        let ecs = self.server.state().ecs();
+        let uid = client.client.uid().expect("client has no uid");
+        let entity = ecs
+            .entity_from_uid(uid.into())
+            .expect("client has no entity on the server");
+        ecs.read_resource::<EventBus<ServerEvent>>()
+            .emit_now(ServerEvent::ClientDisconnect(
+                entity,
+                comp::DisconnectReason::Timeout,
+            ));
+    }
+
+    /// Tick the server, then each client once
+    pub fn tick(&mut self, clients: &mut [&mut TestClient]) {
+        self.tick_server();
+        for client in clients.iter_mut() {
+            client.tick();
+        }
+        thread::sleep(NETWORK_WAIT);
+    }
+
+    /// Tick until `done` holds, failing the test if it takes too long
+    pub fn tick_until(
+        &mut self,
+        clients: &mut [&mut TestClient],
+        what: &str,
+        mut done: impl FnMut(&mut [&mut TestClient]) -> bool,
+    ) {
+        for _ in 0..MAX_TICKS {
+            self.tick(clients);
+            if done(clients) {
+                return;
+            }
+        }
+        panic!("Timed out after {} ticks waiting for {}", MAX_TICKS, what);
+    }
+
+    /// Spawn a training dummy, which stands still and can be hit by anyone
+    pub fn spawn_dummy(&mut self, pos: Vec3<f32>) -> Uid {
+        let body = comp::Body::Object(comp::object::Body::TrainingDummy);
+        let entity = self
+            .server
+            .state_mut()
+            .create_npc(
+                comp::Pos(pos),
+                comp::Stats::new("Training Dummy".to_string()),
+                comp::SkillSet::default(),
+                Some(comp::Health::new(body, 0)),
+                comp::Poise::new(body),
+                Inventory::with_empty(),
+                body,
+            )
+            .build();
+        self.server
+            .state()
+            .ecs()
+            .uid_from_entity(entity)
+            .expect("dummy has no uid")
+    }
+
+    /// Create a character for the client and spawn it into the world
+    pub fn join_game(&mut self, client: &mut TestClient, alias: &str) {
+        client.client.create_character(
+            alias.to_owned(),
+            Some("common.items.weapons.sword.starter".to_owned()),
+            None,
+            comp::Body::Humanoid(comp::humanoid::Body::random()),
+        );
+        let mut character_id = None;
+        self.tick_until(&mut [&mut *client], "character creation", |clients| {
+            character_id = clients[0].events.iter().find_map(|event| match event {
+                Event::CharacterCreated(id) => Some(*id),
+                _ => None,
+            });
+            character_id.is_some()
+        });
+
+        client
+            .client
+            .request_character(character_id.unwrap(), ViewDistances {
+                terrain: 5,
+                entity: 5,
+            });
+        self.tick_until(&mut [&mut *client], "the character to spawn", |clients| {
+            clients[0].client.position().is_some()
+        });
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) { let _ = std::fs::remove_dir_all(&self.data_dir); }
+}
@@ -0,0 +1,171 @@
+mod harness;
+
+use common::{
+    comp::{self, InputKind},
+    util::Dir,
+};
+use common_net::sync::WorldSyncExt;
+use harness::TestServer;
+use vek::*;
+use veloren_client::Event;
+
+#[test]
+fn chat_is_delivered_to_other_clients() {
+    let mut server = TestServer::new();
+    let mut alice = server.connect("alice");
+    let mut bob = server.connect("bob");
+
+    alice.client.send_chat("hello bob".to_owned());
+    server.tick_until(&mut [&mut alice, &mut bob], "the chat message", |clients| {
+        clients[1]
+            .events
+            .iter()
+            .any(|event| matches!(event, Event::Chat(msg) if msg.message == "hello bob"))
+    });
+}
+
+#[test]
+fn player_list_is_synced() {
+    let mut server = TestServer::new();
+    let mut alice = server.connect("alice");
+    let mut bob = server.connect("bob");
+
+    let bob_uid = bob.client.uid().expect("bob has no uid");
+    server.tick_until(&mut [&mut alice, &mut bob], "bob to join", |clients| {
+        clients[0]
+            .events
+            .iter()
+            .any(|event| matches!(event, Event::PlayerJoined(uid) if *uid == bob_uid))
+    });
+    assert_eq!(
+        alice
+            .client
+            .player_list()
+            .get(&bob_uid)
+            .map(|info| info.player_alias.as_str()),
+        Some("bob")
+    );
+
+    drop(bob);
+    server.tick_until(&mut [&mut alice], "bob to leave", |clients| {
+        clients[0]
+            .events
+            .iter()
+            .any(|event| matches!(event, Event::PlayerLeft(uid) if *uid == bob_uid))
+    });
+    assert_eq!(server.server().number_of_players(), 1);
+}
+
+#[test]
+fn movement_is_synced() {
+    let mut server = TestServer::new();
+    let mut alice = server.connect("alice");
+    let mut bob = server.connect("bob");
+    server.join_game(&mut alice, "alice");
+    server.join_game(&mut bob, "bob");
+
+    let alice_uid = alice.client.uid().expect("alice has no uid");
+    let start = alice.client.position().unwrap();
+    alice.inputs.move_dir = Vec2::unit_x();
+    server.tick_until(
+        &mut [&mut alice, &mut bob],
+        "bob to see alice move",
+        |clients| {
+            let ecs = clients[1].client.state().ecs();
+            ecs.entity_from_uid(alice_uid.into())
+                .and_then(|entity| ecs.read_storage::<comp::Pos>().get(entity).copied())
+                .map_or(false, |pos| pos.0.xy().distance(start.xy()) > 2.0)
+        },
+    );
+}
+
+#[test]
+fn combat_is_resolved_on_the_server_and_synced() {
+    let mut server = TestServer::new();
+    let mut alice = server.connect("alice");
+    let mut bob = server.connect("bob");
+    server.join_game(&mut alice, "alice");
+    server.join_game(&mut bob, "bob");
+
+    let start = alice.client.position().unwrap();
+    let dummy = server.spawn_dummy(start + Vec3::unit_x() * 1.5);
+    alice.inputs.look_dir = Dir::new(Vec3::unit_x());
+    alice
+        .client
+        .handle_input(InputKind::Primary, true, None, None);
+    let is_hurt = |client: &veloren_client::Client| {
+        let ecs = client.state().ecs();
+        ecs.entity_from_uid(dummy.into())
+            .and_then(|entity| ecs.read_storage::<comp::Health>().get(entity).cloned())
+            .map_or(false, |health| health.current() < health.maximum())
+    };
+    // Both clients see the damage the server dealt
+    server.tick_until(
+        &mut [&mut alice, &mut bob],
+        "alice and bob to see the dummy hit",
+        |clients| clients.iter().all(|client| is_hurt(&client.client)),
+    );
+    let ecs = server.server().state().ecs();
+    let health = ecs
+        .entity_from_uid(dummy.into())
+        .and_then(|entity| ecs.read_storage::<comp::Health>().get(entity).cloned())
+        .expect("the dummy has no health on the server");
+    assert!(health.current() < health.maximum());
+}
+
+#[test]
+fn session_is_resumed_after_the_connection_drops() {
+    let mut server = TestServer::new();
+    let mut alice = server.connect("alice");
+    let mut bob = server.connect("bob");
+    server.join_game(&mut alice, "alice");
+
+    let alice_uid = alice.client.uid().expect("alice has no uid");
+    server.drop_connection(&alice);
+    server.tick(&mut [&mut bob]);
+    // The character waits in the world for alice to come back
+    let ecs = server.server().state().ecs();
+    assert!(ecs.entity_from_uid(alice_uid.into()).is_some());
+    assert_eq!(server.server().number_of_players(), 1);
+
+    let mut alice = server.reconnect(alice);
+    assert_eq!(alice.client.uid(), Some(alice_uid));
+    assert_eq!(server.server().number_of_players(), 2);
+
+    bob.client.send_chat("welcome back".to_owned());
+    server.tick_until(&mut [&mut alice, &mut bob], "the chat message", |clients| {
+        clients[0]
+            .events
+            .iter()
+            .any(|event| matches!(event, Event::Chat(msg) if msg.message == "welcome back"))
+    });
+}
+
+#[test]
+fn session_is_taken_over_before_the_connection_times_out() {
+    let mut server = TestServer::new();
+    let mut alice = server.connect("alice");
+    server.join_game(&mut alice, "alice");
+
+    // The server didn't notice yet that the last connection of alice dropped
+    let alice_uid = alice.client.uid().expect("alice has no uid");
+    let mut alice = server.reconnect(alice);
+    assert_eq!(alice.client.uid(), Some(alice_uid));
+
+    let start = alice.client.position().unwrap();
+    alice.inputs.move_dir = Vec2::unit_x();
+    server.tick_until(&mut [&mut alice], "alice to move", |clients| {
+        clients[0]
+            .client
+            .position()
+            .map_or(false, |pos| pos.xy().distance(start.xy()) > 2.0)
+    });
+    // The inputs of the new connection move the same character on the server
+    let ecs = server.server().state().ecs();
+    let server_pos = ecs
+        .entity_from_uid(alice_uid.into())
+        .and_then(|entity| ecs.read_storage::<comp::Pos>().get(entity).copied())
+        .expect("alice has no position on the server");
+    assert!(server_pos.0.xy().distance(start.xy()) > 1.0);
+    assert_eq!(server.server().number_of_players(), 1);
+}
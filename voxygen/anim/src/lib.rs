@@ -49,6 +49,37 @@ macro_rules! skeleton_impls {
                 }
             }
         }
+
+        impl $Skeleton {
+            /// Names of the bones, in the order of their index in a `BoneMask`
+            pub const BONES: &'static [&'static str] = &[$(stringify!($bone)),*];
+
+            /// Mask of the bones with the given names, unknown names are ignored
+            pub fn mask(bones: &[&str]) -> $crate::BoneMask {
+                $crate::BoneMask(
+                    Self::BONES
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, bone)| bones.contains(bone))
+                        .fold(0, |mask, (index, _)| mask | (1 << index)),
+                )
+            }
+
+            /// Blends the bones in the mask towards those of `layer`, leaving the
+            /// others as they are
+            #[allow(unused_assignments)]
+            pub fn layered(&self, layer: &Self, mask: $crate::BoneMask, factor: f32) -> Self {
+                let mut skeleton = self.clone();
+                let mut index = 0;
+                $(
+                    if mask.contains(index) {
+                        skeleton.$bone = Lerp::lerp(self.$bone, layer.$bone, factor);
+                    }
+                    index += 1;
+                )*
+                skeleton
+            }
+        }
     }
 }
 
@@ -96,6 +127,17 @@ pub fn make_bone(mat: Mat4<f32>) -> FigureBoneData {
 
 pub type Bone = Transform<f32, f32, f32>;
 
+/// Set of the bones of a skeleton, by their index in `Skeleton::BONES`, that an
+/// animation layer applies to
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BoneMask(pub u64);
+
+impl BoneMask {
+    pub const ALL: Self = Self(u64::MAX);
+
+    pub fn contains(&self, index: usize) -> bool { index < 64 && self.0 & (1 << index) != 0 }
+}
+
 #[cfg(feature = "use-dyn-lib")]
 lazy_static! {
     static ref LIB: Arc<Mutex<Option<LoadedLib>>> =
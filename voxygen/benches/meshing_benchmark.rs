@@ -1,5 +1,6 @@
 use common::{
     terrain::{Block, SpriteKind, TerrainGrid},
+    time::SeasonState,
     vol::SampleVol,
 };
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
@@ -143,7 +144,12 @@ pub fn criterion_benchmark(c: &mut Criterion) {
                 b.iter(|| {
                     generate_mesh(
                         black_box(&volume),
-                        black_box((range, Vec2::new(8192, 8192), &BlocksOfInterest::default())),
+                        black_box((
+                            range,
+                            Vec2::new(8192, 8192),
+                            &BlocksOfInterest::default(),
+                            SeasonState::default(),
+                        )),
                     )
                 })
             });
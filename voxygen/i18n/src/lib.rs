@@ -6,7 +6,11 @@ use error::ResourceErr;
 #[cfg(any(feature = "bin", feature = "stat", test))]
 pub mod analysis;
 
-use fluent_bundle::{bundle::FluentBundle, FluentResource};
+use fluent_bundle::{
+    bundle::FluentBundle,
+    types::{FluentNumber, FluentNumberOptions},
+    FluentResource, FluentValue,
+};
 use intl_memoizer::concurrent::IntlLangMemoizer;
 use unic_langid::LanguageIdentifier;
 
@@ -63,6 +67,42 @@ impl Font {
 /// Store font metadata
 pub type Fonts = HashMap<String, Font>;
 
+/// Grammatical gender of who a message talks about, passed as an argument so
+/// that translations can select their variants with it, like
+/// `{ $gender -> [feminine] ... *[other] ... }`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Gender {
+    Masculine,
+    Feminine,
+    Other,
+}
+
+impl Gender {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Gender::Masculine => "masculine",
+            Gender::Feminine => "feminine",
+            Gender::Other => "other",
+        }
+    }
+}
+
+impl<'a> From<Gender> for FluentValue<'a> {
+    fn from(gender: Gender) -> Self { FluentValue::from(gender.as_str()) }
+}
+
+/// Number argument rounded to the given count of decimals, which translations
+/// can select plural variants with like with any other number
+pub fn fluent_number<'a>(value: f64, decimals: usize) -> FluentValue<'a> {
+    let scale = 10_f64.powi(decimals as i32);
+    let options = FluentNumberOptions {
+        // Integers would get a trailing dot with zero fraction digits
+        minimum_fraction_digits: (decimals > 0).then(|| decimals),
+        ..Default::default()
+    };
+    FluentValue::Number(FluentNumber::new((value * scale).round() / scale, options))
+}
+
 /// Store internationalization data
 struct Language {
     /// The bundle storing all localized texts
@@ -493,6 +533,52 @@ pub fn list_localizations() -> Vec<LanguageMetadata> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn plurals_and_genders_select_variants() {
+        let mut bundle = FluentBundle::new_concurrent(vec!["fr".parse().unwrap()]);
+        bundle.set_use_isolating(false);
+        let src = "died = { $name } est { $gender ->\n    [feminine] morte\n   *[other] \
+                   mort\n}\nonline = { $count ->\n    [one] { $count } joueur\n   *[other] { \
+                   $count } joueurs\n} en ligne\n";
+        bundle
+            .add_resource(FluentResource::try_new(src.to_owned()).unwrap())
+            .unwrap();
+        let lang = Language {
+            bundle,
+            fonts: Fonts::default(),
+            metadata: LanguageMetadata {
+                language_name: "Français".to_owned(),
+                language_identifier: "fr".to_owned(),
+            },
+        };
+        let msg =
+            |key: &str, args: FluentArgs| lang.try_msg(key, Some(&args)).unwrap().into_owned();
+
+        assert_eq!(
+            msg(
+                "died",
+                fluent_args! { "name" => "Zoé", "gender" => Gender::Feminine }
+            ),
+            "Zoé est morte"
+        );
+        assert_eq!(
+            msg(
+                "died",
+                fluent_args! { "name" => "Hugo", "gender" => Gender::Masculine }
+            ),
+            "Hugo est mort"
+        );
+        // French counts zero as singular
+        assert_eq!(
+            msg("online", fluent_args! { "count" => 0 }),
+            "0 joueur en ligne"
+        );
+        assert_eq!(
+            msg("online", fluent_args! { "count" => 2 }),
+            "2 joueurs en ligne"
+        );
+    }
+
     #[test]
     // Test that localization list is loaded (not empty)
     fn check_localization_list() {
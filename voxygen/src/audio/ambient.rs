@@ -146,9 +146,11 @@ impl AmbientChannelTag {
                 // weathersim
                 // Client wind speed is a float approx. -30.0 to 30.0 (polarity depending on
                 // direction)
-                let wind_speed_multiplier = (client.weather_at_player().wind.magnitude_squared()
-                    / 15.0_f32.powi(2))
-                .min(1.33);
+                let weather = client.weather_at_player();
+                let wind_speed_multiplier =
+                    (weather.wind.magnitude_squared() / 15.0_f32.powi(2)).min(1.33);
+                // Snowstorms howl louder than the wind alone would
+                let wind_speed_multiplier = wind_speed_multiplier * (1.0 + weather.snow);
 
                 (alt_multiplier
                     * tree_multiplier
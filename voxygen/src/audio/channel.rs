@@ -18,6 +18,7 @@
 
 use crate::audio::{
     fader::{FadeDirection, Fader},
+    occlusion::Occluded,
     Listener,
 };
 use rodio::{OutputStreamHandle, Sample, Sink, Source, SpatialSink};
@@ -231,6 +232,9 @@ impl AmbientChannel {
 pub struct SfxChannel {
     sink: SpatialSink,
     pub pos: Vec3<f32>,
+    volume: f32,
+    /// Volume left by the terrain between the sound and the listener
+    attenuation: f32,
 }
 
 impl SfxChannel {
@@ -239,6 +243,8 @@ impl SfxChannel {
             sink: SpatialSink::try_new(stream, [0.0; 3], [1.0, 0.0, 0.0], [-1.0, 0.0, 0.0])
                 .unwrap(),
             pos: Vec3::zero(),
+            volume: 1.0,
+            attenuation: 1.0,
         }
     }
 
@@ -263,7 +269,15 @@ impl SfxChannel {
         self.sink.append(source);
     }
 
-    pub fn set_volume(&mut self, volume: f32) { self.sink.set_volume(volume); }
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+        self.sink.set_volume(self.volume * self.attenuation);
+    }
+
+    pub fn set_occlusion(&mut self, occluded: Occluded) {
+        self.attenuation = occluded.attenuation();
+        self.sink.set_volume(self.volume * self.attenuation);
+    }
 
     pub fn stop(&mut self) { self.sink.stop(); }
 
@@ -4,20 +4,29 @@ pub mod ambient;
 pub mod channel;
 pub mod fader;
 pub mod music;
+pub mod occlusion;
 pub mod sfx;
 pub mod soundcache;
+#[cfg(feature = "voice-chat")] pub mod voice;
 
 use channel::{
     AmbientChannel, AmbientChannelTag, MusicChannel, MusicChannelTag, SfxChannel, UiChannel,
 };
 use fader::Fader;
 use music::MusicTransitionManifest;
-use sfx::{SfxEvent, SfxTriggerItem};
+use occlusion::OcclusionCache;
+use sfx::{SfxEvent, SfxTriggerItem, SfxTriggers};
 use soundcache::load_ogg;
 use std::time::Duration;
-use tracing::{debug, error, warn};
+use tracing::{debug, error, info, warn};
 
-use common::assets::{AssetExt, AssetHandle};
+use common::{
+    assets::{AssetExt, AssetHandle},
+    terrain::Block,
+    vol::ReadVol,
+};
+use cpal::traits::{DeviceTrait, HostTrait};
+use crossbeam_channel::Receiver;
 use rodio::{source::Source, OutputStream, OutputStreamHandle, StreamError};
 use vek::*;
 
@@ -26,6 +35,10 @@ use vek::*;
 /// used on average.
 const MIN_HEARABLE_VOLUME: f32 = 0.003;
 
+/// How often the output devices are listed to notice the ones plugged in or
+/// removed
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
 #[derive(Default, Clone)]
 pub struct Listener {
     pos: Vec3<f32>,
@@ -40,10 +53,15 @@ pub struct Listener {
 /// Voxygen's [`GlobalState`](../struct.GlobalState.html#structfield.audio) to
 /// provide access to devices and playback control in-game
 pub struct AudioFrontend {
-    // The following is for the disabled device switcher
-    //pub device: String,
-    //pub device_list: Vec<String>,
-    //pub audio_device: Option<Device>,
+    /// Output device chosen by the player, the default device being used when
+    /// it is `None` or unavailable
+    device: Option<String>,
+    /// Output device the stream is open on
+    active_device: Option<String>,
+    device_list: Vec<String>,
+    /// Lists of the output devices, sent whenever they change. `None` in
+    /// `no-audio` mode, where rodio must not be called
+    device_watcher: Option<Receiver<Vec<String>>>,
     pub stream: Option<OutputStream>,
     audio_stream: Option<OutputStreamHandle>,
 
@@ -56,24 +74,27 @@ pub struct AudioFrontend {
     music_volume: f32,
     master_volume: f32,
     music_spacing: f32,
+    /// Multiplier of the music volume, lowered while talking to an NPC
+    music_ducking: f32,
+    music_ducked: bool,
     listener: Listener,
+    occlusion: OcclusionCache,
+    #[cfg(feature = "voice-chat")]
+    voice: Option<voice::VoiceChat>,
+    #[cfg(feature = "voice-chat")]
+    voice_volume: f32,
 
     mtm: AssetHandle<MusicTransitionManifest>,
 }
 
 impl AudioFrontend {
-    /// Construct with given device
-    pub fn new(/* dev: String, */ num_sfx_channels: usize, num_ui_channels: usize) -> Self {
-        // Commented out until audio device switcher works
-        //let audio_device = get_device_raw(&dev);
-
-        //let device = match get_default_device() {
-        //    Some(d) => d,
-        //    None => "".to_string(),
-        //};
-
-        let (stream, audio_stream) = match get_default_stream() {
-            Ok(s) => (Some(s.0), Some(s.1)),
+    /// Construct with given device, the default device being used when it is
+    /// `None` or unavailable
+    pub fn new(device: Option<String>, num_sfx_channels: usize, num_ui_channels: usize) -> Self {
+        let (stream, audio_stream, active_device) = match get_stream(device.as_deref()) {
+            Ok((stream, audio_stream, active_device)) => {
+                (Some(stream), Some(audio_stream), active_device)
+            },
             Err(e) => {
                 #[cfg(unix)]
                 error!(
@@ -82,7 +103,7 @@ impl AudioFrontend {
                 );
                 #[cfg(not(unix))]
                 error!(?e, "failed to construct audio frontend.");
-                (None, None)
+                (None, None, None)
             },
         };
 
@@ -94,10 +115,10 @@ impl AudioFrontend {
         };
 
         Self {
-            // The following is for the disabled device switcher
-            //device,
-            //device_list: list_devices(),
-            //audio_device,
+            device,
+            active_device,
+            device_list: list_devices(),
+            device_watcher: Some(watch_devices()),
             stream,
             audio_stream,
             music_channels: Vec::new(),
@@ -109,7 +130,14 @@ impl AudioFrontend {
             music_volume: 1.0,
             master_volume: 1.0,
             music_spacing: 1.0,
+            music_ducking: 1.0,
+            music_ducked: false,
             listener: Listener::default(),
+            occlusion: OcclusionCache::default(),
+            #[cfg(feature = "voice-chat")]
+            voice: None,
+            #[cfg(feature = "voice-chat")]
+            voice_volume: 1.0,
             mtm: AssetExt::load_expect("voxygen.audio.music_transition_manifest"),
         }
     }
@@ -126,10 +154,10 @@ impl AudioFrontend {
         });
 
         Self {
-            // The following is for the disabled device switcher
-            //device: "".to_string(),
-            //device_list: Vec::new(),
-            //audio_device: None,
+            device: None,
+            active_device: None,
+            device_list: Vec::new(),
+            device_watcher: None,
             stream: None,
             audio_stream: None,
             music_channels: Vec::new(),
@@ -141,13 +169,22 @@ impl AudioFrontend {
             music_volume: 1.0,
             master_volume: 1.0,
             music_spacing: 1.0,
+            music_ducking: 1.0,
+            music_ducked: false,
             listener: Listener::default(),
+            occlusion: OcclusionCache::default(),
+            #[cfg(feature = "voice-chat")]
+            voice: None,
+            #[cfg(feature = "voice-chat")]
+            voice_volume: 1.0,
             mtm,
         }
     }
 
     /// Drop any unused music channels, and update their faders
     pub fn maintain(&mut self, dt: Duration) {
+        self.maintain_devices();
+        self.maintain_ducking(dt);
         self.music_channels.retain(|c| !c.is_done());
 
         for channel in self.music_channels.iter_mut() {
@@ -190,6 +227,62 @@ impl AudioFrontend {
         }
     }
 
+    /// Crossfades the music to another track of the same kind, e.g. when the
+    /// player went to another biome
+    fn crossfade_music(&mut self, sound: &str, channel_tag: MusicChannelTag) {
+        if !self.music_enabled() {
+            return;
+        }
+        if let Some(audio_stream) = &self.audio_stream {
+            let (fade_out, fade_in) = self.mtm.read().biome_crossfade;
+            let music_volume = self.get_music_volume();
+            for channel in self.music_channels.iter_mut() {
+                channel.set_fader(Fader::fade_out(
+                    Duration::from_secs_f32(fade_out),
+                    music_volume,
+                ));
+            }
+
+            let mut next_music_channel = MusicChannel::new(audio_stream);
+            next_music_channel.set_fader(Fader::fade_in(
+                Duration::from_secs_f32(fade_in),
+                music_volume,
+            ));
+            next_music_channel.play(load_ogg(sound), channel_tag);
+            self.music_channels.push(next_music_channel);
+        }
+    }
+
+    /// Lowers the music while talking to an NPC, the volume fading over a
+    /// moment
+    pub fn duck_music(&mut self, ducked: bool) { self.music_ducked = ducked; }
+
+    fn maintain_ducking(&mut self, dt: Duration) {
+        let (dialogue_ducking, ducking_fade) = {
+            let mtm = self.mtm.read();
+            (mtm.dialogue_ducking, mtm.ducking_fade)
+        };
+        let target = if self.music_ducked {
+            dialogue_ducking
+        } else {
+            1.0
+        };
+        if self.music_ducking == target {
+            return;
+        }
+        let step = (1.0 - dialogue_ducking).abs() * dt.as_secs_f32() / ducking_fade.max(0.01);
+        self.music_ducking = if self.music_ducking < target {
+            (self.music_ducking + step).min(target)
+        } else {
+            (self.music_ducking - step).max(target)
+        };
+
+        let music_volume = self.get_music_volume();
+        for channel in self.music_channels.iter_mut() {
+            channel.set_volume(music_volume);
+        }
+    }
+
     /// Retrieve a music channel from the channel list. This inspects the
     /// MusicChannelTag to determine whether we are transitioning between
     /// music types and acts accordingly. For example transitioning between
@@ -272,11 +365,15 @@ impl AudioFrontend {
                 let sound = load_ogg(sfx_file).amplify(volume.unwrap_or(1.0));
 
                 let listener = self.listener.clone();
+                let occluded = self.occlusion.get(position);
                 if let Some(channel) = self.get_sfx_channel() {
                     channel.set_pos(position);
                     channel.update(&listener);
+                    channel.set_occlusion(occluded);
                     if underwater {
                         channel.play_with_low_pass_filter(sound.convert_samples(), 300);
+                    } else if let Some(cutoff) = occluded.cutoff() {
+                        channel.play_with_low_pass_filter(sound.convert_samples(), cutoff);
                     } else {
                         channel.play(sound);
                     }
@@ -306,13 +403,16 @@ impl AudioFrontend {
                 let sound = load_ogg(sfx_file).amplify(volume.unwrap_or(1.0));
 
                 let listener = self.listener.clone();
+                let occluded = self.occlusion.get(position);
                 if let Some(channel) = self.get_sfx_channel() {
                     channel.set_pos(position);
                     channel.update(&listener);
+                    channel.set_occlusion(occluded);
                     if !underwater {
+                        let freq = freq.unwrap_or(20000);
                         channel.play_with_low_pass_filter(
                             sound.convert_samples(),
-                            freq.unwrap_or(20000),
+                            occluded.cutoff().map_or(freq, |cutoff| cutoff.min(freq)),
                         )
                     } else {
                         channel.play_with_low_pass_filter(sound.convert_samples(), 300)
@@ -349,6 +449,16 @@ impl AudioFrontend {
         }
     }
 
+    /// Whether an output device could be opened
+    pub fn has_output_device(&self) -> bool { self.audio_stream.is_some() }
+
+    /// Plays a short sound through the ui channels, used to check that the
+    /// output device works
+    pub fn play_test_sound(&mut self) {
+        let triggers = SfxTriggers::load_expect("voxygen.audio.sfx").read();
+        self.emit_ui_sfx(triggers.get_key_value(&SfxEvent::SkillPointGain), Some(1.0));
+    }
+
     /// Plays a file at a given volume in the channel with a given tag
     fn play_ambient(&mut self, channel_tag: AmbientChannelTag, sound: &str, volume: Option<f32>) {
         if self.audio_stream.is_some() {
@@ -460,6 +570,22 @@ impl AudioFrontend {
         }
     }
 
+    /// Muffles the playing sfx which have terrain between them and the
+    /// listener, a few of them each frame
+    pub fn maintain_occlusion(&mut self, terrain: &impl ReadVol<Vox = Block>) {
+        let positions = self
+            .sfx_channels
+            .iter()
+            .map(|channel| (!channel.is_done()).then(|| channel.pos))
+            .collect::<Vec<_>>();
+        for (i, occluded) in self
+            .occlusion
+            .maintain(terrain, self.listener.pos, &positions)
+        {
+            self.sfx_channels[i].set_occlusion(occluded);
+        }
+    }
+
     /// Switches the playing music to the title music, which is pinned to a
     /// specific sound file (veloren_title_tune.ogg)
     pub fn play_title_music(&mut self) {
@@ -478,7 +604,9 @@ impl AudioFrontend {
     pub fn get_ambience_volume(&self) -> f32 { self.ambience_volume * self.master_volume }
 
     /// Retrieves the current setting for music volume
-    pub fn get_music_volume(&self) -> f32 { self.music_volume * self.master_volume }
+    pub fn get_music_volume(&self) -> f32 {
+        self.music_volume * self.master_volume * self.music_ducking
+    }
 
     pub fn sfx_enabled(&self) -> bool { self.get_sfx_volume() > 0.0 }
 
@@ -537,6 +665,69 @@ impl AudioFrontend {
         for channel in self.ambient_channels.iter_mut() {
             channel.set_volume(ambience_volume)
         }
+        #[cfg(feature = "voice-chat")]
+        {
+            let voice_volume = self.get_voice_volume();
+            if let Some(voice) = &mut self.voice {
+                voice.set_volume(voice_volume);
+            }
+        }
+    }
+
+    /// Retrieves the current setting for voice chat volume
+    #[cfg(feature = "voice-chat")]
+    pub fn get_voice_volume(&self) -> f32 { self.voice_volume * self.master_volume }
+
+    #[cfg(feature = "voice-chat")]
+    pub fn set_voice_volume(&mut self, voice_volume: f32) {
+        self.voice_volume = voice_volume;
+
+        let voice_volume = self.get_voice_volume();
+        if let Some(voice) = &mut self.voice {
+            voice.set_volume(voice_volume);
+        }
+    }
+
+    /// Opens or closes the microphone and the voices of the other players
+    #[cfg(feature = "voice-chat")]
+    pub fn set_voice_chat(&mut self, enabled: bool) {
+        if !enabled {
+            self.voice = None;
+        } else if self.voice.is_none() {
+            let voice_volume = self.get_voice_volume();
+            self.voice = self
+                .audio_stream
+                .clone()
+                .and_then(|audio_stream| voice::VoiceChat::new(audio_stream, voice_volume));
+        }
+    }
+
+    #[cfg(feature = "voice-chat")]
+    pub fn voice_chat(&mut self) -> Option<&mut voice::VoiceChat> { self.voice.as_mut() }
+
+    /// Plays a frame of the voice of a player at their position
+    #[cfg(feature = "voice-chat")]
+    pub fn receive_voice(
+        &mut self,
+        speaker: common::uid::Uid,
+        frame: common_net::msg::VoiceFrame,
+        pos: Vec3<f32>,
+    ) {
+        if let Some(voice) = &mut self.voice {
+            voice.receive(speaker, frame, pos, &self.listener);
+        }
+    }
+
+    /// Moves the voices being heard along with the players speaking
+    #[cfg(feature = "voice-chat")]
+    pub fn maintain_voice(
+        &mut self,
+        dt: Duration,
+        positions: impl FnMut(common::uid::Uid) -> Option<Vec3<f32>>,
+    ) {
+        if let Some(voice) = &mut self.voice {
+            voice.maintain(dt.as_secs_f32(), &self.listener, positions);
+        }
     }
 
     pub fn stop_all_ambience(&mut self) { self.ambient_channels.retain(|x| Some(x).is_none()) }
@@ -553,58 +744,163 @@ impl AudioFrontend {
         };
     }
 
-    // The following is for the disabled device switcher
-    //// TODO: figure out how badly this will break things when it is called
-    //pub fn set_device(&mut self, name: String) {
-    //    self.device = name.clone();
-    //    self.audio_device = get_device_raw(&name);
-    //}
+    /// Output device chosen by the player, `None` for the default device
+    pub fn device(&self) -> Option<&str> { self.device.as_deref() }
+
+    /// Names of the output devices available
+    pub fn device_list(&self) -> &[String] { &self.device_list }
+
+    /// Switches to another output device, `None` for the default device. The
+    /// sounds playing are stopped, the music and ambience starting again on
+    /// their own. Does nothing in `no-audio` mode.
+    pub fn set_device(&mut self, device: Option<String>) {
+        if self.device_watcher.is_none() {
+            return;
+        }
+        self.device = device;
+        self.reopen_stream();
+    }
+
+    /// Follows the output devices being plugged in and removed, falling back
+    /// to the default device when the one in use is removed and going back to
+    /// the chosen one when it is plugged in again
+    fn maintain_devices(&mut self) {
+        let devices = match self
+            .device_watcher
+            .as_ref()
+            .and_then(|watcher| watcher.try_iter().last())
+        {
+            Some(devices) => devices,
+            None => return,
+        };
+        self.device_list = devices;
+
+        let chosen_device_back = self.device.as_ref().map_or(false, |device| {
+            self.active_device.as_ref() != Some(device) && self.device_list.contains(device)
+        });
+        let active_device_removed = match &self.active_device {
+            Some(active_device) => !self.device_list.contains(active_device),
+            // A device might have been plugged in after none could be opened
+            None => self.audio_stream.is_none() && !self.device_list.is_empty(),
+        };
+        if chosen_device_back {
+            info!(device = ?self.device, "Audio device plugged in again, switching back to it");
+            self.reopen_stream();
+        } else if active_device_removed {
+            warn!(device = ?self.active_device, "Audio device removed, falling back to the default device");
+            self.reopen_stream();
+        }
+    }
+
+    /// Opens a new stream on the chosen device, moving all the channels to it
+    fn reopen_stream(&mut self) {
+        // The old stream has to be closed before the device can be opened again
+        self.stream = None;
+        self.audio_stream = None;
+        self.active_device = None;
+        match get_stream(self.device.as_deref()) {
+            Ok((stream, audio_stream, active_device)) => {
+                self.stream = Some(stream);
+                self.audio_stream = Some(audio_stream);
+                self.active_device = active_device;
+            },
+            Err(e) => error!(?e, "Failed to open an audio device"),
+        }
+
+        self.music_channels.clear();
+        self.ambient_channels.clear();
+        let num_sfx_channels = self.sfx_channels.len();
+        let num_ui_channels = self.ui_channels.len();
+        self.sfx_channels.clear();
+        self.ui_channels.clear();
+        if let Some(audio_stream) = &self.audio_stream {
+            self.sfx_channels
+                .resize_with(num_sfx_channels, || SfxChannel::new(audio_stream));
+            self.ui_channels
+                .resize_with(num_ui_channels, || UiChannel::new(audio_stream));
+        }
+
+        #[cfg(feature = "voice-chat")]
+        if self.voice.take().is_some() {
+            self.set_voice_chat(true);
+        }
+    }
+}
+
+/// Returns the name of the default audio device.
+/// Does not return rodio Device struct in case our audio backend changes.
+fn get_default_device() -> Option<String> {
+    cpal::default_host().default_output_device()?.name().ok()
+}
+
+/// Returns a stream on the specified device, or on the default device if it
+/// isn't available, along with the name of the device used
+fn get_stream(
+    device: Option<&str>,
+) -> Result<(OutputStream, OutputStreamHandle, Option<String>), StreamError> {
+    if let Some(device) = device {
+        match get_device_raw(device).map(|raw| OutputStream::try_from_device(&raw)) {
+            Some(Ok((stream, audio_stream))) => {
+                return Ok((stream, audio_stream, Some(device.to_owned())));
+            },
+            Some(Err(e)) => warn!(?e, ?device, "Failed to open the audio device"),
+            None => warn!(?device, "Audio device not found"),
+        }
+    }
+    let (stream, audio_stream) = OutputStream::try_default()?;
+    Ok((stream, audio_stream, get_default_device()))
 }
 
-// The following is for the disabled device switcher
-///// Returns the default audio device.
-///// Does not return rodio Device struct in case our audio backend changes.
-//pub fn get_default_device() -> Option<String> {
-//    match cpal::default_host().default_output_device() {
-//        Some(x) => Some(x.name().ok()?),
-//        None => None,
-//    }
-//}
-
-/// Returns the default stream
-fn get_default_stream() -> Result<(OutputStream, OutputStreamHandle), StreamError> {
-    OutputStream::try_default()
+fn list_devices_raw() -> Vec<cpal::Device> {
+    match cpal::default_host().output_devices() {
+        Ok(devices) => devices.filter(|d| d.name().is_ok()).collect(),
+        Err(_) => {
+            warn!("Failed to enumerate audio output devices");
+            Vec::new()
+        },
+    }
+}
+
+/// Returns a vec of the audio devices available.
+/// Does not return rodio Device struct in case our audio backend changes.
+fn list_devices() -> Vec<String> {
+    list_devices_raw()
+        .iter()
+        .filter_map(|x| x.name().ok())
+        .collect()
 }
 
-// The following is for the disabled device switcher
-///// Returns a stream on the specified device
-//pub fn get_stream(
-//    device: &rodio::Device,
-//) -> Result<(OutputStream, OutputStreamHandle), StreamError> {
-//    rodio::OutputStream::try_from_device(device)
-//}
-//
-//fn list_devices_raw() -> Vec<cpal::Device> {
-//    match cpal::default_host().devices() {
-//        Ok(devices) => devices.filter(|d| d.name().is_ok()).collect(),
-//        Err(_) => {
-//            warn!("Failed to enumerate audio output devices, audio will not be
-// available");            Vec::new()
-//        },
-//    }
-//}
-//
-///// Returns a vec of the audio devices available.
-///// Does not return rodio Device struct in case our audio backend changes.
-//fn list_devices() -> Vec<String> {
-//    list_devices_raw()
-//        .iter()
-//        .map(|x| x.name().unwrap())
-//        .collect()
-//}
-//
-//fn get_device_raw(device: &str) -> Option<Device> {
-//    list_devices_raw()
-//        .into_iter()
-//        .find(|d| d.name().unwrap() == device)
-//}
+fn get_device_raw(device: &str) -> Option<cpal::Device> {
+    list_devices_raw()
+        .into_iter()
+        .find(|d| d.name().map_or(false, |name| name == device))
+}
+
+/// Lists the output devices every few seconds on another thread, as that can
+/// take a while, sending the list whenever it changed
+fn watch_devices() -> Receiver<Vec<String>> {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let spawned = std::thread::Builder::new()
+        .name("audio-devices".to_owned())
+        .spawn(move || {
+            let mut devices = list_devices();
+            loop {
+                std::thread::sleep(DEVICE_POLL_INTERVAL);
+                let new_devices = list_devices();
+                if new_devices != devices {
+                    // The frontend is gone
+                    if sender.send(new_devices.clone()).is_err() {
+                        break;
+                    }
+                    devices = new_devices;
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        warn!(
+            ?e,
+            "Failed to watch the audio devices, the ones plugged in won't be noticed"
+        );
+    }
+    receiver
+}
@@ -97,6 +97,14 @@ pub struct SoundtrackItem {
     artist: String,
 }
 
+impl SoundtrackItem {
+    /// Whether the track can play in the biome and site
+    fn fits(&self, biome: BiomeKind, site: SiteKindMeta) -> bool {
+        self.sites.iter().any(|s| s == &site)
+            && (self.biomes.is_empty() || self.biomes.iter().any(|b| b.0 == biome))
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 enum RawSoundtrackItem {
     Individual(SoundtrackItem),
@@ -162,6 +170,12 @@ pub struct MusicMgr {
     last_interrupt: Instant,
     /// The previous track's activity kind, for transitions
     last_activity: MusicState,
+    /// The exploration track playing, to notice when the player left the
+    /// biome or site it fits
+    exploration_track: Option<SoundtrackItem>,
+    /// Instant at which the player left the biome or site the exploration
+    /// track fits
+    left_track_surroundings: Option<Instant>,
     // For debug menu
     pub current_track: String,
     pub current_artist: String,
@@ -170,7 +184,8 @@ pub struct MusicMgr {
 
 #[derive(Deserialize)]
 pub struct MusicTransitionManifest {
-    /// Within what radius do enemies count towards combat music?
+    /// Within what radius do enemies with their weapons out count towards
+    /// combat music?
     combat_nearby_radius: f32,
     /// Each multiple of this factor that an enemy has health counts as an extra
     /// enemy
@@ -183,6 +198,22 @@ pub struct MusicTransitionManifest {
     pub fade_timings: HashMap<(MusicChannelTag, MusicChannelTag), (f32, f32)>,
     /// How many seconds between interrupt checks
     pub interrupt_delay: f32,
+    /// How many seconds the player has to stay in a biome or site the
+    /// exploration track doesn't fit before it crossfades to one that does
+    biome_change_delay: f32,
+    /// Fade out and fade in timings of the crossfade between exploration
+    /// tracks
+    pub biome_crossfade: (f32, f32),
+    /// Multiplier of the music volume while talking to an NPC
+    pub dialogue_ducking: f32,
+    /// How many seconds the music takes to duck and to come back
+    pub ducking_fade: f32,
+    /// Range of the seconds of silence between exploration tracks in the
+    /// wilderness, settlements, dungeons and caves
+    explore_silence: (f32, f32),
+    settlement_silence: (f32, f32),
+    dungeon_silence: (f32, f32),
+    cave_silence: (f32, f32),
 }
 
 impl Default for MusicTransitionManifest {
@@ -194,6 +225,14 @@ impl Default for MusicTransitionManifest {
             combat_nearby_low_thresh: 1,
             fade_timings: HashMap::new(),
             interrupt_delay: 5.0,
+            biome_change_delay: 10.0,
+            biome_crossfade: (6.0, 6.0),
+            dialogue_ducking: 0.4,
+            ducking_fade: 1.0,
+            explore_silence: (120.0, 240.0),
+            settlement_silence: (120.0, 180.0),
+            dungeon_silence: (10.0, 20.0),
+            cave_silence: (20.0, 40.0),
         }
     }
 }
@@ -213,6 +252,8 @@ impl Default for MusicMgr {
             last_track: String::from("None"),
             last_interrupt: Instant::now(),
             last_activity: MusicState::Activity(MusicActivity::Explore),
+            exploration_track: None,
+            left_track_surroundings: None,
             current_track: String::from("None"),
             current_artist: String::from("None"),
             track_length: 0.0,
@@ -236,7 +277,7 @@ impl MusicMgr {
         //    player_alt = position.0.z;
         //}
 
-        use common::comp::{group::ENEMY, Group, Health, Pos};
+        use common::comp::{group::ENEMY, CharacterState, Group, Health, Pos};
         use specs::{Join, WorldExt};
         // Checks if the music volume is set to zero or audio is disabled
         // This prevents us from running all the following code unnecessarily
@@ -252,26 +293,30 @@ impl MusicMgr {
         let positions = ecs.read_component::<Pos>();
         let healths = ecs.read_component::<Health>();
         let groups = ecs.read_component::<Group>();
+        let character_states = ecs.read_component::<CharacterState>();
         let mtm = audio.mtm.read();
 
         if let Some(player_pos) = positions.get(player) {
             // TODO: `group::ENEMY` will eventually be moved server-side with an
             // alignment/faction rework, so this will need an alternative way to measure
-            // "in-combat-ness"
-            let num_nearby_entities: u32 = (&entities, &positions, &healths, &groups)
-                .join()
-                .map(|(entity, pos, health, group)| {
-                    if entity != player
-                        && group == &ENEMY
-                        && (player_pos.0 - pos.0).magnitude_squared()
-                            < mtm.combat_nearby_radius.powf(2.0)
-                    {
-                        (health.maximum() / mtm.combat_health_factor).ceil() as u32
-                    } else {
-                        0
-                    }
-                })
-                .sum();
+            // "in-combat-ness". Until then, enemies engage once they have their weapons
+            // out.
+            let num_nearby_entities: u32 =
+                (&entities, &positions, &healths, &groups, &character_states)
+                    .join()
+                    .map(|(entity, pos, health, group, character_state)| {
+                        if entity != player
+                            && group == &ENEMY
+                            && character_state.is_wield()
+                            && (player_pos.0 - pos.0).magnitude_squared()
+                                < mtm.combat_nearby_radius.powf(2.0)
+                        {
+                            (health.maximum() / mtm.combat_health_factor).ceil() as u32
+                        } else {
+                            0
+                        }
+                    })
+                    .sum();
 
             if num_nearby_entities >= mtm.combat_nearby_high_thresh {
                 activity_state = MusicActivity::Combat(CombatIntensity::High);
@@ -304,6 +349,28 @@ impl MusicMgr {
         let interrupt = matches!(music_state, MusicState::Transition(_, _))
             && self.last_interrupt.elapsed().as_secs_f32() > mtm.interrupt_delay;
 
+        // Crossfades to a track fitting the biome or site the player went to, once they
+        // stayed there for a moment
+        let (biome, site) = (client.current_biome(), client.current_site());
+        let left_track_surroundings =
+            matches!(music_state, MusicState::Activity(MusicActivity::Explore))
+                && self.began_playing.elapsed().as_secs_f32() < self.track_length
+                && self
+                    .exploration_track
+                    .as_ref()
+                    .map_or(false, |track| !track.fits(biome, site));
+        let crossfade = if left_track_surroundings {
+            let left_at = *self
+                .left_track_surroundings
+                .get_or_insert_with(Instant::now);
+            left_at.elapsed().as_secs_f32() > mtm.biome_change_delay
+        } else {
+            self.left_track_surroundings = None;
+            false
+        };
+        // The manifest is read again when choosing the track
+        drop(mtm);
+
         // When the current track ends, clear the debug values
         if self.began_playing.elapsed().as_secs_f32() > self.track_length {
             self.current_track = String::from("None");
@@ -312,17 +379,23 @@ impl MusicMgr {
 
         if audio.music_enabled()
             && !self.soundtrack.read().tracks.is_empty()
-            && (self.began_playing.elapsed().as_secs_f32() > self.next_track_change || interrupt)
+            && (self.began_playing.elapsed().as_secs_f32() > self.next_track_change
+                || interrupt
+                || crossfade)
         {
             if interrupt {
                 self.last_interrupt = Instant::now();
             }
+            // Waits before trying again if no track fits
+            self.left_track_surroundings = None;
             trace!(
                 "pre-play_random_track: {:?} {:?}",
                 self.last_activity,
                 music_state
             );
-            if let Ok(next_activity) = self.play_random_track(audio, state, client, &music_state) {
+            if let Ok(next_activity) =
+                self.play_random_track(audio, state, client, &music_state, crossfade)
+            {
                 self.last_activity = next_activity;
             }
         }
@@ -334,8 +407,11 @@ impl MusicMgr {
         state: &State,
         client: &Client,
         music_state: &MusicState,
+        crossfade: bool,
     ) -> Result<MusicState, ()> {
         let mut rng = thread_rng();
+        let current_biome = client.current_biome();
+        let current_site = client.current_site();
 
         // Adds a bit of randomness between plays, depending on whether the player is in
         // a town, or exploring.
@@ -343,37 +419,23 @@ impl MusicMgr {
         // it begins
         let spacing_multiplier = audio.music_spacing;
         let mut silence_between_tracks_seconds: f32 = 0.0;
-        if spacing_multiplier > f32::EPSILON {
+        if spacing_multiplier > f32::EPSILON
+            && matches!(music_state, MusicState::Activity(MusicActivity::Explore))
+        {
+            let mtm = audio.mtm.read();
+            let (min, max) = match current_site {
+                SiteKindMeta::Settlement(_) => mtm.settlement_silence,
+                SiteKindMeta::Dungeon(_) => mtm.dungeon_silence,
+                SiteKindMeta::Cave => mtm.cave_silence,
+                SiteKindMeta::Castle | SiteKindMeta::Void => mtm.explore_silence,
+            };
             silence_between_tracks_seconds =
-                if matches!(music_state, MusicState::Activity(MusicActivity::Explore))
-                    && matches!(client.current_site(), SiteKindMeta::Settlement(_))
-                {
-                    rng.gen_range(120.0 * spacing_multiplier..180.0 * spacing_multiplier)
-                } else if matches!(music_state, MusicState::Activity(MusicActivity::Explore))
-                    && matches!(client.current_site(), SiteKindMeta::Dungeon(_))
-                {
-                    rng.gen_range(10.0 * spacing_multiplier..20.0 * spacing_multiplier)
-                } else if matches!(music_state, MusicState::Activity(MusicActivity::Explore))
-                    && matches!(client.current_site(), SiteKindMeta::Cave)
-                {
-                    rng.gen_range(20.0 * spacing_multiplier..40.0 * spacing_multiplier)
-                } else if matches!(music_state, MusicState::Activity(MusicActivity::Explore)) {
-                    rng.gen_range(120.0 * spacing_multiplier..240.0 * spacing_multiplier)
-                } else if matches!(
-                    music_state,
-                    MusicState::Activity(MusicActivity::Combat(_)) | MusicState::Transition(_, _)
-                ) {
-                    0.0
-                } else {
-                    rng.gen_range(30.0 * spacing_multiplier..60.0 * spacing_multiplier)
-                };
+                rng.gen_range(min * spacing_multiplier..=max * spacing_multiplier);
         }
 
         let is_dark = (state.get_day_period().is_dark()) as bool;
         let current_period_of_day = Self::get_current_day_period(is_dark);
         let current_weather = client.weather_at_player();
-        let current_biome = client.current_biome();
-        let current_site = client.current_site();
 
         // Filter the soundtrack in stages, so that we don't overprune it if there are
         // too many constraints. Returning Err(()) signals that we couldn't find
@@ -394,10 +456,7 @@ impl MusicMgr {
                     None => true,
                 }
             })
-            .filter(|track| track.sites.iter().any(|s| s == &current_site))
-            .filter(|track| {
-                track.biomes.is_empty() || track.biomes.iter().any(|b| b.0 == current_biome)
-            })
+            .filter(|track| track.fits(current_biome, current_site))
             .filter(|track| &track.music_state == music_state)
             .collect::<Vec<&SoundtrackItem>>();
         if maybe_tracks.is_empty() {
@@ -464,11 +523,17 @@ impl MusicMgr {
             }
 
             let tag = if matches!(music_state, MusicState::Activity(MusicActivity::Explore)) {
+                self.exploration_track = Some((*track).clone());
                 MusicChannelTag::Exploration
             } else {
+                self.exploration_track = None;
                 MusicChannelTag::Combat
             };
-            audio.play_music(&track.path, tag);
+            if crossfade {
+                audio.crossfade_music(&track.path, tag);
+            } else {
+                audio.play_music(&track.path, tag);
+            }
 
             if let Some(state) = track.activity_override {
                 Ok(MusicState::Activity(state))
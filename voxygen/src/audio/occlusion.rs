@@ -0,0 +1,104 @@
+//! Muffles positional sound effects which have thick terrain between them and
+//! the listener
+//!
+//! Rays are cast through the terrain from the listener to the playing sfx
+//! channels, a limited number of them each frame. The results are cached by
+//! area so that new sounds coming from an area known to be occluded are
+//! low-pass filtered from the start, the filter of a playing sound not being
+//! adjustable.
+
+use common::{terrain::Block, vol::ReadVol};
+use hashbrown::HashMap;
+use vek::*;
+
+/// Rays cast through the terrain each frame at most
+const RAY_BUDGET: usize = 8;
+/// Blocks sampled along a ray at most, further sounds are sampled more sparsely
+const MAX_SAMPLES: usize = 48;
+/// Solid blocks between the sound and the listener for it to be fully occluded
+const FULL_OCCLUSION_BLOCKS: f32 = 6.0;
+/// Volume left to fully occluded sounds
+const MIN_ATTENUATION: f32 = 0.25;
+/// Low-pass cutoff of fully occluded sounds, in Hz
+const MIN_CUTOFF: f32 = 500.0;
+const MAX_CUTOFF: f32 = 20000.0;
+/// Size in blocks of the areas occlusion is cached for
+const CELL_SIZE: i32 = 4;
+/// Frames after which cached occlusion is forgotten
+const CELL_LIFETIME: u32 = 120;
+
+/// How much of the sound is blocked by the terrain, from 0 to 1
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Occluded(pub f32);
+
+impl Occluded {
+    pub fn attenuation(self) -> f32 { 1.0 - (1.0 - MIN_ATTENUATION) * self.0 }
+
+    /// Cutoff of the low-pass filter to play the sound through, if it needs one
+    pub fn cutoff(self) -> Option<u32> {
+        (self.0 > 0.0).then(|| Lerp::lerp(MAX_CUTOFF, MIN_CUTOFF, self.0.sqrt()) as u32)
+    }
+}
+
+/// Counts the solid blocks between the two positions
+pub fn occluded(terrain: &impl ReadVol<Vox = Block>, from: Vec3<f32>, to: Vec3<f32>) -> Occluded {
+    let samples = (from.distance(to).ceil() as usize).clamp(1, MAX_SAMPLES);
+    let block_len = from.distance(to) / samples as f32;
+    let solid = (1..samples)
+        .map(|i| Lerp::lerp(from, to, i as f32 / samples as f32))
+        .filter(|pos| {
+            terrain
+                .get(pos.map(|e| e.floor() as i32))
+                .map_or(false, |block| block.is_opaque())
+        })
+        .count();
+    Occluded((solid as f32 * block_len.max(1.0) / FULL_OCCLUSION_BLOCKS).min(1.0))
+}
+
+#[derive(Default)]
+pub struct OcclusionCache {
+    cells: HashMap<Vec3<i32>, (Occluded, u32)>,
+    frame: u32,
+    /// Channel the next ray is cast to, so that all of them get their turn
+    next_channel: usize,
+}
+
+impl OcclusionCache {
+    fn cell(pos: Vec3<f32>) -> Vec3<i32> { pos.map(|e| (e.floor() as i32).div_euclid(CELL_SIZE)) }
+
+    /// Last known occlusion of sounds coming from around this position
+    pub fn get(&self, pos: Vec3<f32>) -> Occluded {
+        self.cells
+            .get(&Self::cell(pos))
+            .map_or(Occluded::default(), |(occluded, _)| *occluded)
+    }
+
+    /// Casts rays to some of the sound positions, returning the occlusion of
+    /// the ones which were updated
+    pub fn maintain(
+        &mut self,
+        terrain: &impl ReadVol<Vox = Block>,
+        listener: Vec3<f32>,
+        positions: &[Option<Vec3<f32>>],
+    ) -> Vec<(usize, Occluded)> {
+        self.frame = self.frame.wrapping_add(1);
+        let frame = self.frame;
+        self.cells
+            .retain(|_, (_, updated)| frame.wrapping_sub(*updated) < CELL_LIFETIME);
+
+        let mut updated = Vec::new();
+        let (start, len) = (self.next_channel, positions.len());
+        for i in (0..len).map(|i| (start + i) % len) {
+            if updated.len() >= RAY_BUDGET {
+                self.next_channel = i;
+                break;
+            }
+            if let Some(pos) = positions[i] {
+                let occluded = occluded(terrain, listener, pos);
+                self.cells.insert(Self::cell(pos), (occluded, frame));
+                updated.push((i, occluded));
+            }
+        }
+        updated
+    }
+}
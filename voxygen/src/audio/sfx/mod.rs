@@ -396,6 +396,7 @@ impl SfxMgr {
         // Sets the listener position to the camera position facing the
         // same direction as the camera
         audio.set_listener_pos(cam_pos, camera.dependents().cam_dir);
+        audio.maintain_occlusion(&*state.terrain());
 
         let triggers = self.triggers.read();
 
@@ -607,9 +608,19 @@ impl SfxMgr {
                     audio.emit_sfx(sfx_trigger_item, *pos, Some(1.0), underwater);
                 }
             },
+            Outcome::Ignite { pos } => {
+                let sfx_trigger_item = triggers.get_key_value(&SfxEvent::FireShot);
+                audio.emit_sfx(
+                    sfx_trigger_item,
+                    pos.map(|e| e as f32 + 0.5),
+                    Some(0.5),
+                    underwater,
+                );
+            },
             Outcome::ExpChange { .. }
             | Outcome::ComboChange { .. }
-            | Outcome::SummonedCreature { .. } => {},
+            | Outcome::SummonedCreature { .. }
+            | Outcome::BurnOut { .. } => {},
         }
     }
 
@@ -0,0 +1,317 @@
+//! Voice chat: the microphone is captured and opus encoded while push-to-talk
+//! is held, and the voices of the players nearby are decoded and played at
+//! their position
+
+use super::Listener;
+use common::uid::Uid;
+use common_net::msg::{VoiceFrame, MAX_VOICE_FRAME_BYTES};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use hashbrown::HashMap;
+use rodio::{buffer::SamplesBuffer, OutputStreamHandle, SpatialSink};
+use tracing::warn;
+use vek::*;
+
+/// Sample rate voice is encoded at
+const SAMPLE_RATE: u32 = 48_000;
+/// Samples in a 20 ms frame
+const FRAME_SAMPLES: usize = 960;
+/// Lost frames concealed by the decoder, longer gaps are left silent
+const MAX_CONCEALED_FRAMES: u16 = 3;
+/// Seconds after which the decoder of a silent speaker is dropped
+const SPEAKER_TIMEOUT: f32 = 10.0;
+
+/// Microphone input, downmixed to mono
+struct Capture {
+    stream: cpal::Stream,
+    samples: Receiver<Vec<f32>>,
+    sample_rate: u32,
+}
+
+impl Capture {
+    fn new() -> Option<Self> {
+        let device = cpal::default_host().default_input_device()?;
+        let config = device
+            .default_input_config()
+            .map_err(|err| warn!(?err, "Failed to get the configuration of the microphone"))
+            .ok()?;
+        let sample_format = config.sample_format();
+        let config: cpal::StreamConfig = config.into();
+        let (sender, samples) = unbounded();
+        let stream = match sample_format {
+            cpal::SampleFormat::F32 => build_capture::<f32>(&device, &config, sender),
+            cpal::SampleFormat::I16 => build_capture::<i16>(&device, &config, sender),
+            cpal::SampleFormat::U16 => build_capture::<u16>(&device, &config, sender),
+        }
+        .map_err(|err| warn!(?err, "Failed to open the microphone"))
+        .ok()?;
+        // The microphone is only recorded while push-to-talk is held
+        if let Err(err) = stream.pause() {
+            warn!(?err, "Failed to pause the microphone");
+        }
+        Some(Self {
+            stream,
+            samples,
+            sample_rate: config.sample_rate.0,
+        })
+    }
+}
+
+fn build_capture<T: cpal::Sample>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sender: Sender<Vec<f32>>,
+) -> Result<cpal::Stream, cpal::BuildStreamError> {
+    let channels = config.channels.max(1) as usize;
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            let samples = data
+                .chunks(channels)
+                .map(|frame| frame.iter().map(|s| s.to_f32()).sum::<f32>() / channels as f32)
+                .collect();
+            let _ = sender.send(samples);
+        },
+        |err| warn!(?err, "Failed to capture the microphone"),
+    )
+}
+
+/// Linear resampling of the microphone to the rate voice is encoded at
+fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let len = (samples.len() as u64 * to as u64 / from as u64) as usize;
+    (0..len)
+        .map(|i| {
+            let pos = i as f32 * from as f32 / to as f32;
+            let index = pos as usize;
+            let next = samples.get(index + 1).copied().unwrap_or(samples[index]);
+            Lerp::lerp(samples[index], next, pos.fract())
+        })
+        .collect()
+}
+
+/// A player heard nearby
+struct Speaker {
+    decoder: opus::Decoder,
+    sink: SpatialSink,
+    pos: Vec3<f32>,
+    /// Sequence number of the frame expected next
+    next_seq: u16,
+    /// Seconds since the last frame
+    silence: f32,
+}
+
+impl Speaker {
+    fn update(&mut self, listener: &Listener) {
+        // Same falloff as the sfx
+        const FALLOFF: f32 = 0.13;
+
+        self.sink
+            .set_emitter_position(((self.pos - listener.pos) * FALLOFF).into_array());
+        self.sink
+            .set_left_ear_position(listener.ear_left_rpos.into_array());
+        self.sink
+            .set_right_ear_position(listener.ear_right_rpos.into_array());
+    }
+
+    fn play(&mut self, data: &[u8]) {
+        let mut samples = vec![0.0; FRAME_SAMPLES];
+        match self.decoder.decode_float(data, &mut samples, false) {
+            Ok(len) => {
+                samples.truncate(len);
+                self.sink
+                    .append(SamplesBuffer::new(1, SAMPLE_RATE, samples));
+            },
+            Err(err) => warn!(?err, "Failed to decode a voice frame"),
+        }
+    }
+}
+
+pub struct VoiceChat {
+    audio_stream: OutputStreamHandle,
+    capture: Option<Capture>,
+    encoder: opus::Encoder,
+    transmitting: bool,
+    /// Captured samples not encoded yet
+    captured: Vec<f32>,
+    seq: u16,
+    speakers: HashMap<Uid, Speaker>,
+    volume: f32,
+}
+
+impl VoiceChat {
+    pub fn new(audio_stream: OutputStreamHandle, volume: f32) -> Option<Self> {
+        let encoder =
+            opus::Encoder::new(SAMPLE_RATE, opus::Channels::Mono, opus::Application::Voip)
+                .map_err(|err| warn!(?err, "Failed to create the voice encoder"))
+                .ok()?;
+        let capture = Capture::new();
+        if capture.is_none() {
+            warn!("No microphone found, voice chat can only be listened to");
+        }
+        Some(Self {
+            audio_stream,
+            capture,
+            encoder,
+            transmitting: false,
+            captured: Vec::new(),
+            seq: 0,
+            speakers: HashMap::new(),
+            volume,
+        })
+    }
+
+    /// Records the microphone while push-to-talk is held
+    pub fn set_transmitting(&mut self, transmitting: bool) {
+        if self.transmitting == transmitting {
+            return;
+        }
+        self.transmitting = transmitting;
+        if let Some(capture) = &self.capture {
+            let res = if transmitting {
+                capture.stream.play()
+            } else {
+                capture.stream.pause()
+            };
+            if let Err(err) = res {
+                warn!(?err, "Failed to start or stop the microphone");
+            }
+        }
+    }
+
+    pub fn is_transmitting(&self) -> bool { self.transmitting && self.capture.is_some() }
+
+    /// Encodes what was recorded since the last call, in frames to send
+    pub fn take_frames(&mut self) -> Vec<VoiceFrame> {
+        let capture = match &self.capture {
+            Some(capture) => capture,
+            None => return Vec::new(),
+        };
+        for samples in capture.samples.try_iter() {
+            if self.transmitting {
+                self.captured
+                    .extend(resample(&samples, capture.sample_rate, SAMPLE_RATE));
+            }
+        }
+        if !self.transmitting {
+            self.captured.clear();
+        }
+
+        let mut frames = Vec::new();
+        while self.captured.len() >= FRAME_SAMPLES {
+            let samples = self.captured.drain(..FRAME_SAMPLES).collect::<Vec<_>>();
+            match self
+                .encoder
+                .encode_vec_float(&samples, MAX_VOICE_FRAME_BYTES)
+            {
+                Ok(data) => {
+                    frames.push(VoiceFrame {
+                        seq: self.seq,
+                        data,
+                    });
+                    self.seq = self.seq.wrapping_add(1);
+                },
+                Err(err) => warn!(?err, "Failed to encode a voice frame"),
+            }
+        }
+        frames
+    }
+
+    /// Plays a frame of the voice of a player at their position
+    pub fn receive(
+        &mut self,
+        speaker: Uid,
+        frame: VoiceFrame,
+        pos: Vec3<f32>,
+        listener: &Listener,
+    ) {
+        let speaker = match self.speakers.entry(speaker) {
+            hashbrown::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            hashbrown::hash_map::Entry::Vacant(entry) => {
+                let decoder = match opus::Decoder::new(SAMPLE_RATE, opus::Channels::Mono) {
+                    Ok(decoder) => decoder,
+                    Err(err) => {
+                        warn!(?err, "Failed to create a voice decoder");
+                        return;
+                    },
+                };
+                let sink =
+                    match SpatialSink::try_new(&self.audio_stream, [0.0; 3], [1.0, 0.0, 0.0], [
+                        -1.0, 0.0, 0.0,
+                    ]) {
+                        Ok(sink) => sink,
+                        Err(err) => {
+                            warn!(?err, "Failed to create a voice sink");
+                            return;
+                        },
+                    };
+                sink.set_volume(self.volume);
+                entry.insert(Speaker {
+                    decoder,
+                    sink,
+                    pos,
+                    next_seq: frame.seq,
+                    silence: 0.0,
+                })
+            },
+        };
+
+        // Frames arriving after later ones were played are dropped
+        let gap = frame.seq.wrapping_sub(speaker.next_seq);
+        if gap > u16::MAX / 2 {
+            return;
+        }
+        if gap <= MAX_CONCEALED_FRAMES {
+            for _ in 0..gap {
+                speaker.play(&[]);
+            }
+        }
+        speaker.pos = pos;
+        speaker.update(listener);
+        speaker.play(&frame.data);
+        speaker.next_seq = frame.seq.wrapping_add(1);
+        speaker.silence = 0.0;
+    }
+
+    /// Moves the voices along with the players, dropping the ones silent for
+    /// a while
+    pub fn maintain(
+        &mut self,
+        dt: f32,
+        listener: &Listener,
+        mut positions: impl FnMut(Uid) -> Option<Vec3<f32>>,
+    ) {
+        self.speakers.retain(|uid, speaker| {
+            speaker.silence += dt;
+            if let Some(pos) = positions(*uid) {
+                speaker.pos = pos;
+            }
+            speaker.update(listener);
+            speaker.silence < SPEAKER_TIMEOUT
+        });
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+        for speaker in self.speakers.values() {
+            speaker.sink.set_volume(volume);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn microphone_is_resampled_to_the_encoding_rate() {
+        let samples = [0.0, 1.0, 0.0, -1.0];
+        assert_eq!(resample(&samples, SAMPLE_RATE, SAMPLE_RATE), samples);
+        assert_eq!(resample(&samples, 24_000, SAMPLE_RATE), [
+            0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -1.0, -1.0
+        ]);
+        assert_eq!(resample(&samples, 96_000, SAMPLE_RATE), [0.0, 0.0]);
+    }
+}
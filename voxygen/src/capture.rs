@@ -0,0 +1,208 @@
+//! Screenshots and short clips of the game. Both are saved on background
+//! threads, the screenshots as PNG files telling where and when in the world
+//! they were taken, the clips as GIF files made of the frames recorded between
+//! two presses of the clip key.
+
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::FilterType,
+    Delay, DynamicImage, Frame, RgbaImage,
+};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+use vek::*;
+
+/// Frames per second of the clips
+pub const CLIP_FPS: u32 = 10;
+/// Width the frames of the clips are scaled down to, to keep the buffer and
+/// the files small
+const CLIP_WIDTH: u32 = 480;
+
+/// Where and when in the world a screenshot was taken
+#[derive(Clone, Debug, Default)]
+pub struct CaptureMetadata {
+    pub server: String,
+    pub world_seed: u32,
+    pub pos: Option<Vec3<f32>>,
+    /// Time of day in the world, in seconds since the first day
+    pub time_of_day: f64,
+}
+
+impl CaptureMetadata {
+    /// Keywords and texts of the text chunks of the PNG file
+    fn text_chunks(&self) -> Vec<(&'static str, String)> {
+        let secs = self.time_of_day.rem_euclid(24.0 * 3600.0) as u32;
+        let mut chunks = vec![
+            (
+                "Software",
+                format!("Veloren {}", *common::util::DISPLAY_VERSION_LONG),
+            ),
+            ("Server", self.server.clone()),
+            ("World seed", self.world_seed.to_string()),
+            (
+                "Time of day",
+                format!("{:02}:{:02}", secs / 3600, secs / 60 % 60),
+            ),
+        ];
+        if let Some(pos) = self.pos {
+            chunks.push((
+                "Position",
+                format!("{:.0}, {:.0}, {:.0}", pos.x, pos.y, pos.z),
+            ));
+        }
+        chunks
+    }
+}
+
+/// Path of a new capture in `dir`, creating the directory if needed
+fn capture_path(dir: &Path, prefix: &str, extension: &str) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Couldn't create folder: {}", e))?;
+    Ok(dir.join(format!(
+        "{}_{}.{}",
+        prefix,
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0),
+        extension
+    )))
+}
+
+/// Saves the screenshot in `dir`, with the metadata in the text chunks of the
+/// PNG file
+pub fn save_screenshot(
+    image: DynamicImage,
+    dir: &Path,
+    metadata: Option<&CaptureMetadata>,
+) -> Result<PathBuf, String> {
+    let path = capture_path(dir, "screenshot", "png")?;
+    let image = image.into_rgba8();
+    let file = File::create(&path).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), image.width(), image.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    for (keyword, text) in metadata
+        .map(CaptureMetadata::text_chunks)
+        .unwrap_or_default()
+    {
+        encoder
+            .add_text_chunk(keyword.to_string(), text)
+            .map_err(|e| e.to_string())?;
+    }
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&image))
+        .map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Frames of a clip being recorded, up to a given length. Clones share the
+/// same frames, for the screenshot threads to add them.
+#[derive(Clone)]
+pub struct ClipBuffer {
+    frames: Arc<Mutex<VecDeque<RgbaImage>>>,
+    capacity: usize,
+    last_frame: Instant,
+}
+
+impl ClipBuffer {
+    pub fn new(secs: u32) -> Self {
+        let capacity = (secs * CLIP_FPS) as usize;
+        Self {
+            frames: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+            last_frame: Instant::now(),
+        }
+    }
+
+    /// Whether the clip reached its length, when it should stop recording
+    pub fn is_full(&self) -> bool {
+        self.frames
+            .lock()
+            .expect("Clip buffer mutex was poisoned")
+            .len()
+            >= self.capacity
+    }
+
+    /// Whether it is time to add the next frame, in which case it is expected
+    /// to be added
+    pub fn frame_due(&mut self) -> bool {
+        let due = self.last_frame.elapsed() >= Duration::from_secs(1) / CLIP_FPS;
+        if due {
+            self.last_frame = Instant::now();
+        }
+        due
+    }
+
+    /// Adds a frame, unless the clip is already full
+    pub fn push(&self, image: DynamicImage) {
+        let image = if image.width() > CLIP_WIDTH {
+            image.resize(CLIP_WIDTH, u32::MAX, FilterType::Triangle)
+        } else {
+            image
+        };
+        let mut frames = self.frames.lock().expect("Clip buffer mutex was poisoned");
+        if frames.len() < self.capacity {
+            frames.push_back(image.into_rgba8());
+        }
+    }
+
+    /// Saves the frames in the buffer as a looping GIF in `dir`
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, String> {
+        let frames = self
+            .frames
+            .lock()
+            .expect("Clip buffer mutex was poisoned")
+            .clone();
+        if frames.is_empty() {
+            return Err("No frames were recorded yet".to_string());
+        }
+        let path = capture_path(dir, "clip", "gif")?;
+        let file = File::create(&path).map_err(|e| e.to_string())?;
+        let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), 10);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+        let delay = Delay::from_numer_denom_ms(1000, CLIP_FPS);
+        encoder
+            .encode_frames(
+                frames
+                    .into_iter()
+                    .map(|frame| Frame::from_parts(frame, 0, 0, delay)),
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clip_buffer_stops_once_full() {
+        let clip = ClipBuffer::new(1);
+        for i in 0..CLIP_FPS + 5 {
+            assert_eq!(clip.is_full(), i >= CLIP_FPS);
+            clip.push(DynamicImage::ImageRgba8(RgbaImage::new(i + 1, 1)));
+        }
+        let frames = clip.frames.lock().unwrap();
+        assert_eq!(frames.len(), CLIP_FPS as usize);
+        assert_eq!(frames.back().map(|frame| frame.width()), Some(CLIP_FPS));
+
+        let metadata = CaptureMetadata {
+            time_of_day: 24.0 * 3600.0 * 3.0 + 13.5 * 3600.0,
+            pos: Some(Vec3::new(1.0, 2.0, 3.0)),
+            ..Default::default()
+        };
+        let chunks = metadata.text_chunks();
+        assert!(chunks.contains(&("Time of day", "13:30".to_string())));
+        assert!(chunks.contains(&("Position", "1, 2, 3".to_string())));
+    }
+}
@@ -0,0 +1,110 @@
+//! Chat messages of the past sessions on each server, kept in the userdata
+//! directory so that the chat box isn't empty after logging in again.
+
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::warn;
+
+const HISTORY_DIR: &str = "chat_history";
+
+/// The last lines of chat seen on a server, already formatted as shown in the
+/// chat box
+pub struct ChatHistory {
+    /// File the history is saved to, `None` when keeping it is disabled
+    path: Option<PathBuf>,
+    lines: VecDeque<String>,
+    max_lines: usize,
+}
+
+impl ChatHistory {
+    /// Loads the history of the server, keeping up to `max_lines` of it, or
+    /// none at all when `max_lines` is 0
+    pub fn load(userdata_dir: &Path, server_name: &str, max_lines: usize) -> Self {
+        let path = (max_lines > 0).then(|| history_path(userdata_dir, server_name));
+        let mut lines: VecDeque<String> = path
+            .as_ref()
+            .and_then(|path| fs::File::open(path).ok())
+            .and_then(|file| {
+                ron::de::from_reader(file)
+                    .map_err(|e| warn!(?e, "Failed to read the chat history"))
+                    .ok()
+            })
+            .unwrap_or_default();
+        while lines.len() > max_lines {
+            lines.pop_front();
+        }
+        Self {
+            path,
+            lines,
+            max_lines,
+        }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> { self.lines.iter().map(String::as_str) }
+
+    pub fn push(&mut self, line: String) {
+        if self.path.is_none() {
+            return;
+        }
+        if self.lines.len() >= self.max_lines {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        ron::ser::to_string_pretty(&self.lines, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|ron| {
+                path.parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(path, ron))
+                    .map_err(|err| err.to_string())
+            })
+    }
+}
+
+impl Drop for ChatHistory {
+    fn drop(&mut self) {
+        // The history is kept however the session ends
+        if let Err(e) = self.save() {
+            warn!(?e, "Failed to save the chat history");
+        }
+    }
+}
+
+/// Server names end up in file paths, so anything but letters, digits, '_'
+/// and '-' is replaced
+fn history_path(userdata_dir: &Path, server_name: &str) -> PathBuf {
+    let name: String = server_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    userdata_dir.join(HISTORY_DIR).join(format!("{}.ron", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_names_make_safe_file_names() {
+        assert_eq!(
+            history_path(Path::new("userdata"), "../My Server: EU"),
+            Path::new("userdata/chat_history/___My_Server__EU.ron")
+        );
+    }
+}
@@ -16,4 +16,10 @@ pub struct Args {
     /// This allows passing in server selection performed in airshipper.
     #[clap(short, long)]
     pub server: Option<String>,
+
+    /// Keep the settings, logs, screenshots and saves beside the executable.
+    ///
+    /// Useful to carry the game around on a USB stick.
+    #[clap(long)]
+    pub portable: bool,
 }
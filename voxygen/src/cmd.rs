@@ -1,13 +1,18 @@
 use std::str::FromStr;
 
-use crate::GlobalState;
-use client::Client;
+use crate::{
+    scene::camera_path::{CameraPath, Cutscene},
+    GlobalState,
+};
+use client::{cmd::LocalCommand, Client};
 use common::{cmd::*, parse_cmd_args, uuid::Uuid};
 
 // Please keep this sorted alphabetically, same as with server commands :-)
 #[derive(Clone, Copy, strum::EnumIter)]
 pub enum ClientChatCommand {
+    Cutscene,
     Mute,
+    Record,
     Unmute,
 }
 
@@ -17,11 +22,37 @@ impl ClientChatCommand {
         use Requirement::*;
         let cmd = ChatCommandData::new;
         match self {
+            ClientChatCommand::Cutscene => cmd(
+                vec![
+                    Enum(
+                        "action",
+                        vec!["play".to_owned(), "stop".to_owned()],
+                        Required,
+                    ),
+                    Any("name", Optional),
+                ],
+                "Flies the camera along a camera path with the HUD hidden, or stops the one being \
+                 played. Escape stops it too.",
+                None,
+            ),
             ClientChatCommand::Mute => cmd(
                 vec![PlayerName(Required)],
                 "Mutes chat messages from a player.",
                 None,
             ),
+            ClientChatCommand::Record => cmd(
+                vec![
+                    Enum(
+                        "action",
+                        vec!["start".to_owned(), "stop".to_owned(), "replay".to_owned()],
+                        Required,
+                    ),
+                    Any("name", Optional),
+                ],
+                "Records your inputs, or replays a recording in singleplayer. Only available in \
+                 builds with the input-recording feature.",
+                None,
+            ),
             ClientChatCommand::Unmute => cmd(
                 vec![PlayerName(Required)],
                 "Unmutes a player muted with the 'mute' command.",
@@ -32,7 +63,9 @@ impl ClientChatCommand {
 
     pub fn keyword(&self) -> &'static str {
         match self {
+            ClientChatCommand::Cutscene => "cutscene",
             ClientChatCommand::Mute => "mute",
+            ClientChatCommand::Record => "record",
             ClientChatCommand::Unmute => "unmute",
         }
     }
@@ -77,6 +110,16 @@ impl ClientChatCommand {
     pub fn iter_with_keywords() -> impl Iterator<Item = (&'static str, Self)> {
         Self::iter().map(|c| (c.keyword(), c))
     }
+
+    /// The commands as completed by the client, next to the server ones
+    pub fn local_commands() -> Vec<LocalCommand> {
+        Self::iter()
+            .map(|c| LocalCommand {
+                keyword: c.keyword(),
+                data: c.data(),
+            })
+            .collect()
+    }
 }
 
 impl FromStr for ClientChatCommand {
@@ -146,11 +189,43 @@ fn run_client_command(
     args: Vec<String>,
 ) -> Result<String, String> {
     match command {
+        ClientChatCommand::Cutscene => handle_cutscene(client, global_state, args),
         ClientChatCommand::Mute => handle_mute(client, global_state, args),
+        ClientChatCommand::Record => handle_record(global_state, args),
         ClientChatCommand::Unmute => handle_unmute(client, global_state, args),
     }
 }
 
+fn handle_cutscene(
+    client: &Client,
+    global_state: &mut GlobalState,
+    args: Vec<String>,
+) -> Result<String, String> {
+    match parse_cmd_args!(args, String, String) {
+        (Some(action), Some(name)) if action == "play" => {
+            let path = CameraPath::load(&name)?;
+            let player_pos = client.position().unwrap_or_default();
+            let mut cutscene = Cutscene::new(path, player_pos);
+            // Return to the camera of the game, not the one of the cutscene replaced
+            cutscene.previous_mode = global_state
+                .cutscene
+                .take()
+                .and_then(|cutscene| cutscene.previous_mode);
+            global_state.cutscene = Some(cutscene);
+            Ok(format!("Playing the camera path {}.", name))
+        },
+        (Some(action), _) if action == "stop" => {
+            global_state
+                .cutscene
+                .as_mut()
+                .ok_or_else(|| "No camera path is being played.".to_string())?
+                .stop();
+            Ok("Stopped the camera path.".to_string())
+        },
+        _ => Err(ClientChatCommand::Cutscene.help_string()),
+    }
+}
+
 fn handle_mute(
     client: &Client,
     global_state: &mut GlobalState,
@@ -215,175 +290,49 @@ fn handle_unmute(
     }
 }
 
-/// A helper function to get the Uuid of a player with a given alias
-pub fn get_player_uuid(client: &Client, alias: &String) -> Option<Uuid> {
-    client
-        .player_list()
-        .values()
-        .find(|p| p.player_alias == *alias)
-        .map(|p| p.uuid)
-}
-
-trait TabComplete {
-    fn complete(&self, part: &str, client: &Client) -> Vec<String>;
-}
-
-impl TabComplete for ArgumentSpec {
-    fn complete(&self, part: &str, client: &Client) -> Vec<String> {
-        match self {
-            ArgumentSpec::PlayerName(_) => complete_player(part, client),
-            ArgumentSpec::SiteName(_) => complete_site(part, client),
-            ArgumentSpec::Float(_, x, _) => {
-                if part.is_empty() {
-                    vec![format!("{:.1}", x)]
-                } else {
-                    vec![]
-                }
-            },
-            ArgumentSpec::Integer(_, x, _) => {
-                if part.is_empty() {
-                    vec![format!("{}", x)]
-                } else {
-                    vec![]
-                }
-            },
-            ArgumentSpec::Any(_, _) => vec![],
-            ArgumentSpec::Command(_) => complete_command(part, ' '),
-            ArgumentSpec::Message(_) => complete_player(part, client),
-            ArgumentSpec::SubCommand => complete_command(part, ' '),
-            ArgumentSpec::Enum(_, strings, _) => strings
-                .iter()
-                .filter(|string| string.starts_with(part))
-                .map(|c| c.to_string())
-                .collect(),
-            ArgumentSpec::Boolean(_, part, _) => vec!["true", "false"]
-                .iter()
-                .filter(|string| string.starts_with(part))
-                .map(|c| c.to_string())
-                .collect(),
-        }
+#[cfg(feature = "input-recording")]
+fn handle_record(global_state: &mut GlobalState, args: Vec<String>) -> Result<String, String> {
+    let local = global_state.is_local();
+    let recorder = &mut global_state.input_recorder;
+    match parse_cmd_args!(args, String, String) {
+        (Some(action), Some(name)) if action == "start" => recorder
+            .start_recording(&name)
+            .map(|_| format!("Recording inputs as {}.", name)),
+        (Some(action), _) if action == "stop" => recorder.stop(&global_state.userdata_dir),
+        (Some(action), Some(name)) if action == "replay" => recorder
+            .replay(&global_state.userdata_dir, &name, local)
+            .map(|len| format!("Replaying {} inputs from {}.", len, name)),
+        _ => Err(ClientChatCommand::Record.help_string()),
     }
 }
 
-fn complete_player(part: &str, client: &Client) -> Vec<String> {
-    client
-        .player_list()
-        .values()
-        .map(|player_info| &player_info.player_alias)
-        .filter(|alias| alias.starts_with(part))
-        .cloned()
-        .collect()
+#[cfg(not(feature = "input-recording"))]
+fn handle_record(_global_state: &mut GlobalState, _args: Vec<String>) -> Result<String, String> {
+    Err("This build doesn't support recording inputs.".to_string())
 }
 
-fn complete_site(mut part: &str, client: &Client) -> Vec<String> {
-    if let Some(p) = part.strip_prefix('"') {
-        part = p;
-    }
+/// A helper function to get the Uuid of a player with a given alias
+pub fn get_player_uuid(client: &Client, alias: &String) -> Option<Uuid> {
     client
-        .sites()
+        .player_list()
         .values()
-        .filter_map(|site| match site.site.kind {
-            common_net::msg::world_msg::SiteKind::Cave => None,
-            _ => site.site.name.as_ref(),
-        })
-        .filter(|name| name.starts_with(part))
-        .map(|name| {
-            if name.contains(' ') {
-                format!("\"{}\"", name)
-            } else {
-                name.clone()
-            }
-        })
-        .collect()
-}
-
-// Get the byte index of the nth word. Used in completing "/sudo p /subcmd"
-fn nth_word(line: &str, n: usize) -> Option<usize> {
-    let mut is_space = false;
-    let mut j = 0;
-    for (i, c) in line.char_indices() {
-        match (is_space, c.is_whitespace()) {
-            (true, true) => {},
-            (true, false) => {
-                is_space = false;
-                j += 1;
-            },
-            (false, true) => {
-                is_space = true;
-            },
-            (false, false) => {},
-        }
-        if j == n {
-            return Some(i);
-        }
-    }
-    None
-}
-
-fn complete_command(part: &str, prefix: char) -> Vec<String> {
-    ServerChatCommand::iter_with_keywords()
-        .map(|(kwd, _)| kwd)
-        .chain(ClientChatCommand::iter_with_keywords().map(|(kwd, _)| kwd))
-        .filter(|kwd| kwd.starts_with(part))
-        .map(|kwd| format!("{}{}", prefix, kwd))
-        .collect()
+        .find(|p| p.player_alias == *alias)
+        .map(|p| p.uuid)
 }
 
+/// The words the last word of the line could be completed to
 pub fn complete(line: &str, client: &Client, cmd_prefix: char) -> Vec<String> {
-    let word = if line.chars().last().map_or(true, char::is_whitespace) {
-        ""
-    } else {
-        line.split_whitespace().last().unwrap_or("")
-    };
-
-    if line.starts_with(cmd_prefix) {
-        let line = line.strip_prefix(cmd_prefix).unwrap_or(line);
-        let mut iter = line.split_whitespace();
-        let cmd = iter.next().unwrap_or("");
-        let i = iter.count() + if word.is_empty() { 1 } else { 0 };
-        if i == 0 {
-            // Completing chat command name. This is the start of the line so the prefix
-            // will be part of it
-            let word = word.strip_prefix(cmd_prefix).unwrap_or(word);
-            return complete_command(word, cmd_prefix);
-        }
-
-        let args = {
-            if let Ok(cmd) = cmd.parse::<ServerChatCommand>() {
-                Some(cmd.data().args)
-            } else if let Ok(cmd) = cmd.parse::<ClientChatCommand>() {
-                Some(cmd.data().args)
-            } else {
-                None
-            }
-        };
+    client::cmd::complete(
+        line,
+        client,
+        cmd_prefix,
+        &ClientChatCommand::local_commands(),
+    )
+}
 
-        if let Some(args) = args {
-            if let Some(arg) = args.get(i - 1) {
-                // Complete ith argument
-                arg.complete(word, client)
-            } else {
-                // Complete past the last argument
-                match args.last() {
-                    Some(ArgumentSpec::SubCommand) => {
-                        if let Some(index) = nth_word(line, args.len()) {
-                            complete(&line[index..], client, cmd_prefix)
-                        } else {
-                            vec![]
-                        }
-                    },
-                    Some(ArgumentSpec::Message(_)) => complete_player(word, client),
-                    _ => vec![], // End of command. Nothing to complete
-                }
-            }
-        } else {
-            // Completing for unknown chat command
-            complete_player(word, client)
-        }
-    } else {
-        // Not completing a command
-        complete_player(word, client)
-    }
+/// Usage of the command being typed, if it is one
+pub fn argument_hint(line: &str, cmd_prefix: char) -> Option<String> {
+    client::cmd::argument_hint(line, cmd_prefix, &ClientChatCommand::local_commands())
 }
 
 #[test]
@@ -397,15 +346,3 @@ fn verify_cmd_list_sorted() {
     list.sort_unstable();
     assert_eq!(list, list2);
 }
-
-#[test]
-fn test_complete_command() {
-    assert_eq!(complete_command("mu", '/'), vec!["/mute".to_string()]);
-    assert_eq!(complete_command("unba", '/'), vec!["/unban".to_string()]);
-    assert_eq!(complete_command("make_", '/'), vec![
-        "/make_block".to_string(),
-        "/make_npc".to_string(),
-        "/make_sprite".to_string(),
-        "/make_volume".to_string()
-    ]);
-}
@@ -1,10 +1,17 @@
 //! Module containing controller-specific abstractions allowing complex
 //! keybindings
 
-use crate::{game_input::GameInput, window::MenuInput};
+use crate::{
+    game_input::GameInput, settings::gamepad::con_settings::GameButtons, window::MenuInput,
+};
 use gilrs::{ev::Code as GilCode, Axis as GilAxis, Button as GilButton};
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+use vek::*;
+
+/// How far the stick has to be pushed for the radial menu to select a slot
+const RADIAL_MENU_THRESHOLD: f32 = 0.5;
 
 /// Contains all controller related settings and keymaps
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -22,11 +29,14 @@ pub struct ControllerSettings {
     pub button_deadzones: HashMap<AnalogButton, f32>,
     pub mouse_emulation_sensitivity: u32,
     pub inverted_axes: Vec<Axis>,
+    pub radial_menu_button: Button,
+    /// Dead zone of the axes without one of their own
+    pub stick_deadzone: f32,
 }
 
 impl ControllerSettings {
     pub fn apply_axis_deadzone(&self, k: &Axis, input: f32) -> f32 {
-        let threshold = *self.axis_deadzones.get(k).unwrap_or(&0.2);
+        let threshold = *self.axis_deadzones.get(k).unwrap_or(&self.stick_deadzone);
 
         // This could be one comparison per handled event faster if threshold was
         // guaranteed to fall into <0, 1) range
@@ -60,160 +70,15 @@ impl From<&crate::settings::GamepadSettings> for ControllerSettings {
         Self {
             game_button_map: {
                 let mut map: HashMap<_, Vec<_>> = HashMap::new();
-                map.entry(settings.game_buttons.primary)
-                    .or_default()
-                    .push(GameInput::Primary);
-                map.entry(settings.game_buttons.secondary)
-                    .or_default()
-                    .push(GameInput::Secondary);
-                map.entry(settings.game_buttons.block)
-                    .or_default()
-                    .push(GameInput::Block);
-                map.entry(settings.game_buttons.slot1)
-                    .or_default()
-                    .push(GameInput::Slot1);
-                map.entry(settings.game_buttons.slot2)
-                    .or_default()
-                    .push(GameInput::Slot2);
-                map.entry(settings.game_buttons.slot3)
-                    .or_default()
-                    .push(GameInput::Slot3);
-                map.entry(settings.game_buttons.slot4)
-                    .or_default()
-                    .push(GameInput::Slot4);
-                map.entry(settings.game_buttons.slot5)
-                    .or_default()
-                    .push(GameInput::Slot5);
-                map.entry(settings.game_buttons.slot6)
-                    .or_default()
-                    .push(GameInput::Slot6);
-                map.entry(settings.game_buttons.slot7)
-                    .or_default()
-                    .push(GameInput::Slot7);
-                map.entry(settings.game_buttons.slot8)
-                    .or_default()
-                    .push(GameInput::Slot8);
-                map.entry(settings.game_buttons.slot9)
-                    .or_default()
-                    .push(GameInput::Slot9);
-                map.entry(settings.game_buttons.slot10)
-                    .or_default()
-                    .push(GameInput::Slot10);
-                map.entry(settings.game_buttons.toggle_cursor)
-                    .or_default()
-                    .push(GameInput::ToggleCursor);
-                map.entry(settings.game_buttons.escape)
-                    .or_default()
-                    .push(GameInput::Escape);
-                map.entry(settings.game_buttons.enter)
-                    .or_default()
-                    .push(GameInput::Chat);
-                map.entry(settings.game_buttons.command)
-                    .or_default()
-                    .push(GameInput::Command);
-                map.entry(settings.game_buttons.move_forward)
-                    .or_default()
-                    .push(GameInput::MoveForward);
-                map.entry(settings.game_buttons.move_left)
-                    .or_default()
-                    .push(GameInput::MoveLeft);
-                map.entry(settings.game_buttons.move_back)
-                    .or_default()
-                    .push(GameInput::MoveBack);
-                map.entry(settings.game_buttons.move_right)
-                    .or_default()
-                    .push(GameInput::MoveRight);
-                map.entry(settings.game_buttons.jump)
-                    .or_default()
-                    .push(GameInput::Jump);
-                map.entry(settings.game_buttons.sit)
-                    .or_default()
-                    .push(GameInput::Sit);
-                map.entry(settings.game_buttons.dance)
-                    .or_default()
-                    .push(GameInput::Dance);
-                map.entry(settings.game_buttons.glide)
-                    .or_default()
-                    .push(GameInput::Glide);
-                map.entry(settings.game_buttons.climb)
-                    .or_default()
-                    .push(GameInput::Climb);
-                map.entry(settings.game_buttons.climb_down)
-                    .or_default()
-                    .push(GameInput::ClimbDown);
-                map.entry(settings.game_buttons.swimup)
-                    .or_default()
-                    .push(GameInput::SwimUp);
-                map.entry(settings.game_buttons.swimdown)
-                    .or_default()
-                    .push(GameInput::SwimDown);
-                map.entry(settings.game_buttons.sneak)
-                    .or_default()
-                    .push(GameInput::Sneak);
-                map.entry(settings.game_buttons.toggle_lantern)
-                    .or_default()
-                    .push(GameInput::ToggleLantern);
-                map.entry(settings.game_buttons.mount)
-                    .or_default()
-                    .push(GameInput::Mount);
-                map.entry(settings.game_buttons.map)
-                    .or_default()
-                    .push(GameInput::Map);
-                map.entry(settings.game_buttons.bag)
-                    .or_default()
-                    .push(GameInput::Bag);
-                map.entry(settings.game_buttons.social)
-                    .or_default()
-                    .push(GameInput::Social);
-                map.entry(settings.game_buttons.crafting)
-                    .or_default()
-                    .push(GameInput::Crafting);
-                map.entry(settings.game_buttons.spellbook)
-                    .or_default()
-                    .push(GameInput::Spellbook);
-                map.entry(settings.game_buttons.settings)
-                    .or_default()
-                    .push(GameInput::Settings);
-                map.entry(settings.game_buttons.help)
-                    .or_default()
-                    .push(GameInput::Help);
-                map.entry(settings.game_buttons.toggle_interface)
-                    .or_default()
-                    .push(GameInput::ToggleInterface);
-                map.entry(settings.game_buttons.toggle_debug)
-                    .or_default()
-                    .push(GameInput::ToggleDebug);
+                for &input in GameButtons::INPUTS {
+                    if let Some(button) = settings.game_buttons.get(input) {
+                        map.entry(button).or_default().push(input);
+                    }
+                }
                 #[cfg(feature = "egui-ui")]
                 map.entry(settings.game_buttons.toggle_debug)
                     .or_default()
                     .push(GameInput::ToggleEguiDebug);
-                map.entry(settings.game_buttons.toggle_chat)
-                    .or_default()
-                    .push(GameInput::ToggleChat);
-                map.entry(settings.game_buttons.fullscreen)
-                    .or_default()
-                    .push(GameInput::Fullscreen);
-                map.entry(settings.game_buttons.screenshot)
-                    .or_default()
-                    .push(GameInput::Screenshot);
-                map.entry(settings.game_buttons.toggle_ingame_ui)
-                    .or_default()
-                    .push(GameInput::ToggleIngameUi);
-                map.entry(settings.game_buttons.roll)
-                    .or_default()
-                    .push(GameInput::Roll);
-                map.entry(settings.game_buttons.respawn)
-                    .or_default()
-                    .push(GameInput::Respawn);
-                map.entry(settings.game_buttons.interact)
-                    .or_default()
-                    .push(GameInput::Interact);
-                map.entry(settings.game_buttons.toggle_wield)
-                    .or_default()
-                    .push(GameInput::ToggleWield);
-                map.entry(settings.game_buttons.swap_loadout)
-                    .or_default()
-                    .push(GameInput::SwapLoadout);
                 map
             },
             menu_button_map: {
@@ -299,10 +164,54 @@ impl From<&crate::settings::GamepadSettings> for ControllerSettings {
             button_deadzones: settings.button_deadzones.clone(),
             mouse_emulation_sensitivity: settings.mouse_emulation_sensitivity,
             inverted_axes: settings.inverted_axes.clone(),
+            radial_menu_button: settings.game_buttons.radial_menu,
+            stick_deadzone: settings.stick_deadzone,
         }
     }
 }
 
+/// Something a button can be bound to from the settings, the radial menu only
+/// being available on gamepads
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GamepadBinding {
+    Game(GameInput),
+    RadialMenu,
+}
+
+/// Skillbar slot picked with the camera stick while the radial menu button is
+/// held, used when the button is released
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RadialMenu {
+    /// Direction the stick is pushed in, y pointing up
+    pub dir: Vec2<f32>,
+}
+
+impl RadialMenu {
+    /// Slots of the menu, going clockwise from the top
+    pub const SLOTS: [GameInput; 10] = [
+        GameInput::Slot1,
+        GameInput::Slot2,
+        GameInput::Slot3,
+        GameInput::Slot4,
+        GameInput::Slot5,
+        GameInput::Slot6,
+        GameInput::Slot7,
+        GameInput::Slot8,
+        GameInput::Slot9,
+        GameInput::Slot10,
+    ];
+
+    /// Index of the slot the stick points to, if it is pushed far enough
+    pub fn selected(&self) -> Option<usize> {
+        if self.dir.magnitude_squared() < RADIAL_MENU_THRESHOLD.powi(2) {
+            return None;
+        }
+        let angle = self.dir.x.atan2(self.dir.y).rem_euclid(TAU);
+        let sector = TAU / Self::SLOTS.len() as f32;
+        Some((angle / sector).round() as usize % Self::SLOTS.len())
+    }
+}
+
 /// All the menu actions you can bind to an Axis
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum AxisMenuAction {
@@ -341,6 +250,17 @@ impl Default for Button {
     fn default() -> Self { Button::Simple(GilButton::Unknown) }
 }
 
+impl Button {
+    /// Name of the button shown in the settings, `None` when nothing is bound
+    pub fn display_string(&self) -> Option<String> {
+        match self {
+            Self::Simple(GilButton::Unknown) => None,
+            Self::Simple(button) => Some(format!("{:?}", button)),
+            Self::EventCode(code) => Some(format!("#{}", code)),
+        }
+    }
+}
+
 /// AnalogButton::Simple(GilButton::Unknown) is invalid and equal to mapping an
 /// action to nothing
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -391,3 +311,25 @@ impl From<(GilButton, GilCode)> for AnalogButton {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radial_menu_slots_go_clockwise_from_the_top() {
+        let selected = |x, y| {
+            RadialMenu {
+                dir: Vec2::new(x, y),
+            }
+            .selected()
+        };
+        assert_eq!(selected(0.0, 0.2), None);
+        assert_eq!(selected(0.0, 1.0), Some(0));
+        assert_eq!(selected(0.95, 0.31), Some(2));
+        assert_eq!(selected(0.0, -1.0), Some(5));
+        assert_eq!(selected(-0.95, -0.31), Some(7));
+        // Just left of the top
+        assert_eq!(selected(-0.1, 1.0), Some(0));
+    }
+}
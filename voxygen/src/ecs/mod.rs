@@ -14,6 +14,8 @@ pub fn init(world: &mut World) {
         pool.configure("IMAGE_PROCESSING", |n| n / 2);
         pool.configure("FIGURE_MESHING", |n| n / 2);
         pool.configure("TERRAIN_MESHING", |n| n / 2);
+        // One at a time, so that the saves reach the disk in order
+        pool.configure("PROFILE_SAVING", |_| 1);
     }
 
     // Voxygen event buses
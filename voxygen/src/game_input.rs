@@ -79,10 +79,15 @@ pub enum GameInput {
     Sneak,
     #[strum(serialize = "gameinput-togglelantern")]
     ToggleLantern,
+    #[strum(serialize = "gameinput-togglebuilding")]
+    ToggleBuilding,
     #[strum(serialize = "gameinput-mount")]
     Mount,
     #[strum(serialize = "gameinput-chat")]
     Chat,
+    #[cfg(feature = "voice-chat")]
+    #[strum(serialize = "gameinput-pushtotalk")]
+    PushToTalk,
     #[strum(serialize = "gameinput-command")]
     Command,
     #[strum(serialize = "gameinput-escape")]
@@ -95,6 +100,12 @@ pub enum GameInput {
     Trade,
     #[strum(serialize = "gameinput-social")]
     Social,
+    #[strum(serialize = "gameinput-guild")]
+    Guild,
+    #[strum(serialize = "gameinput-combatlog")]
+    CombatLog,
+    #[strum(serialize = "gameinput-questlog")]
+    QuestLog,
     #[strum(serialize = "gameinput-crafting")]
     Crafting,
     #[strum(serialize = "gameinput-spellbook")]
@@ -116,6 +127,8 @@ pub enum GameInput {
     Fullscreen,
     #[strum(serialize = "gameinput-screenshot")]
     Screenshot,
+    #[strum(serialize = "gameinput-recordclip")]
+    RecordClip,
     #[strum(serialize = "gameinput-toggleingameui")]
     ToggleIngameUi,
     #[strum(serialize = "gameinput-roll")]
@@ -136,6 +149,14 @@ pub enum GameInput {
     CameraClamp,
     #[strum(serialize = "gameinput-cyclecamera")]
     CycleCamera,
+    #[strum(serialize = "gameinput-zoomin")]
+    ZoomIn,
+    #[strum(serialize = "gameinput-zoomout")]
+    ZoomOut,
+    #[strum(serialize = "gameinput-firstperson")]
+    FirstPerson,
+    #[strum(serialize = "gameinput-photomode")]
+    PhotoMode,
     #[strum(serialize = "gameinput-select")]
     Select,
     #[strum(serialize = "gameinput-acceptgroupinvite")]
@@ -178,6 +199,12 @@ impl GameInput {
             GameInput::FreeLook => GameInput::FreeLook,
             GameInput::AutoWalk => GameInput::FreeLook,
 
+            // The map is only zoomed while the camera can't be
+            GameInput::ZoomIn => GameInput::ZoomIn,
+            GameInput::MapZoomIn => GameInput::ZoomIn,
+            GameInput::ZoomOut => GameInput::ZoomOut,
+            GameInput::MapZoomOut => GameInput::ZoomOut,
+
             _ => *self,
         }
     }
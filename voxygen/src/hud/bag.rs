@@ -3,7 +3,7 @@ use super::{
     img_ids::{Imgs, ImgsRot},
     item_imgs::ItemImgs,
     slots::{ArmorSlot, EquipSlot, InventorySlot, SlotManager},
-    HudInfo, Show, CRITICAL_HP_COLOR, LOW_HP_COLOR, TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
+    HudInfo, Show, CRITICAL_HP_COLOR, HP_COLOR, LOW_HP_COLOR, TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
 };
 use crate::{
     game_input::GameInput,
@@ -20,9 +20,10 @@ use common::{
     assets::AssetExt,
     combat::{combat_rating, perception_dist_multiplier_from_stealth, Damage},
     comp::{
+        faction::{Attitude, Faction},
         inventory::InventorySortOrder,
         item::{ItemDef, ItemDesc, MaterialStatManifest, Quality},
-        Body, Energy, Health, Inventory, Poise, SkillSet, Stats,
+        Body, Energy, Health, Inventory, Poise, Reputation, SkillSet, Stats,
     },
 };
 use conrod_core::{
@@ -36,6 +37,7 @@ use std::borrow::Cow;
 use crate::hud::slots::SlotKind;
 use specs::Entity as EcsEntity;
 use std::{borrow::Borrow, sync::Arc};
+use strum::IntoEnumIterator;
 use vek::Vec2;
 
 widget_ids! {
@@ -557,6 +559,9 @@ widget_ids! {
         // Stats
         stat_icons[],
         stat_txts[],
+        // Reputation
+        reputation_title,
+        faction_txts[],
     }
 }
 
@@ -1012,6 +1017,58 @@ impl<'a> Widget for Bag<'a> {
                 .graphics_for(state.ids.stat_icons[i.0])
                 .set(state.ids.stat_txts[i.0], ui);
             }
+            // Standing with each faction
+            let reputation = self
+                .client
+                .state()
+                .ecs()
+                .read_storage::<Reputation>()
+                .get(self.info.viewpoint_entity)
+                .cloned()
+                .unwrap_or_default();
+            let faction_count = Faction::iter().count();
+            if state.ids.faction_txts.len() < faction_count {
+                state.update(|s| {
+                    s.ids
+                        .faction_txts
+                        .resize(faction_count, &mut ui.widget_id_generator())
+                });
+            }
+            Text::new(&i18n.get_msg("hud-bag-reputation"))
+                .down_from(state.ids.stat_icons[STATS.len() - 1], 15.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR)
+                .set(state.ids.reputation_title, ui);
+            for (i, faction) in Faction::iter().enumerate() {
+                let faction_name = i18n.get_msg(match faction {
+                    Faction::Villagers => "hud-bag-faction-villagers",
+                    Faction::Cultists => "hud-bag-faction-cultists",
+                    Faction::Gnarlings => "hud-bag-faction-gnarlings",
+                    Faction::Beasts => "hud-bag-faction-beasts",
+                });
+                let color = match reputation.attitude(faction) {
+                    Attitude::Hostile => CRITICAL_HP_COLOR,
+                    Attitude::Neutral => TEXT_COLOR,
+                    Attitude::Friendly => HP_COLOR,
+                };
+                let txt = Text::new(&i18n.get_msg_ctx(
+                    "hud-bag-faction_standing",
+                    &i18n::fluent_args! {
+                        "faction" => faction_name,
+                        "standing" => reputation.standing(faction),
+                    },
+                ));
+                let txt = if i == 0 {
+                    txt.down_from(state.ids.reputation_title, 5.0)
+                } else {
+                    txt.down_from(state.ids.faction_txts[i - 1], 3.0)
+                };
+                txt.font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(12))
+                    .color(color)
+                    .set(state.ids.faction_txts[i], ui);
+            }
             // Loadout Slots
             //  Head
             let item_slot = EquipSlot::Armor(ArmorSlot::Head);
@@ -16,6 +16,7 @@ use conrod_core::{
     widget::{self, Button, Image, Rectangle, Text},
     widget_ids, Color, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
 };
+use std::borrow::Cow;
 widget_ids! {
     struct Ids {
         align,
@@ -28,6 +29,8 @@ widget_ids! {
         debuffs[],
         debuff_timers[],
         buff_txts[],
+        buff_stacks[],
+        debuff_stacks[],
     }
 }
 
@@ -177,6 +180,12 @@ impl<'a> Widget for BuffsBar<'a> {
             if state.ids.debuff_timers.len() < debuff_count {
                 state.update(|state| state.ids.debuff_timers.resize(debuff_count, gen));
             };
+            if state.ids.buff_stacks.len() < buff_count {
+                state.update(|state| state.ids.buff_stacks.resize(buff_count, gen));
+            };
+            if state.ids.debuff_stacks.len() < debuff_count {
+                state.update(|state| state.ids.debuff_stacks.resize(debuff_count, gen));
+            };
 
             // Create Buff Widgets
             let mut buff_vec = state
@@ -228,7 +237,10 @@ impl<'a> Widget for BuffsBar<'a> {
                         .set(*id, ui);
                     // Create Buff tooltip
                     let title = hud::get_buff_title(buff.kind, localized_strings);
-                    let desc_txt = hud::get_buff_desc(buff.kind, buff.data, localized_strings);
+                    let desc_txt = self.with_stacks(
+                        hud::get_buff_desc(buff.kind, buff.data, localized_strings),
+                        buff.kind,
+                    );
                     let remaining_time = hud::get_buff_time(*buff);
                     let click_to_remove =
                         format!("<{}>", &localized_strings.get_msg("buff-remove"));
@@ -249,6 +261,7 @@ impl<'a> Widget for BuffsBar<'a> {
                     {
                         event.push(Event::RemoveBuff(buff.kind));
                     };
+                    self.stack_count(buff.kind, *timer_id, state.ids.buff_stacks[i], ui);
                 });
 
             // Create Debuff Widgets
@@ -301,7 +314,10 @@ impl<'a> Widget for BuffsBar<'a> {
                         .set(*id, ui);
                     // Create Debuff tooltip
                     let title = hud::get_buff_title(debuff.kind, localized_strings);
-                    let desc_txt = hud::get_buff_desc(debuff.kind, debuff.data, localized_strings);
+                    let desc_txt = self.with_stacks(
+                        hud::get_buff_desc(debuff.kind, debuff.data, localized_strings),
+                        debuff.kind,
+                    );
                     let remaining_time = hud::get_buff_time(*debuff);
                     let desc = format!("{}\n\n{}", desc_txt, remaining_time);
                     Image::new(self.get_duration_image(duration_percentage))
@@ -315,6 +331,7 @@ impl<'a> Widget for BuffsBar<'a> {
                             DEBUFF_COLOR,
                         )
                         .set(*timer_id, ui);
+                    self.stack_count(debuff.kind, *timer_id, state.ids.debuff_stacks[i], ui);
                 });
         }
 
@@ -339,6 +356,9 @@ impl<'a> Widget for BuffsBar<'a> {
             if state.ids.buff_txts.len() < buff_count {
                 state.update(|state| state.ids.buff_txts.resize(buff_count, gen));
             };
+            if state.ids.buff_stacks.len() < buff_count {
+                state.update(|state| state.ids.buff_stacks.resize(buff_count, gen));
+            };
 
             // Create Buff Widgets
 
@@ -387,7 +407,10 @@ impl<'a> Widget for BuffsBar<'a> {
                         .set(*id, ui);
                     // Create Buff tooltip
                     let title = hud::get_buff_title(buff.kind, localized_strings);
-                    let desc_txt = hud::get_buff_desc(buff.kind, buff.data, localized_strings);
+                    let desc_txt = self.with_stacks(
+                        hud::get_buff_desc(buff.kind, buff.data, localized_strings),
+                        buff.kind,
+                    );
                     let remaining_time = hud::get_buff_time(*buff);
                     let click_to_remove =
                         format!("<{}>", &localized_strings.get_msg("buff-remove"));
@@ -423,6 +446,7 @@ impl<'a> Widget for BuffsBar<'a> {
                         .graphics_for(*timer_id)
                         .color(TEXT_COLOR)
                         .set(*txt_id, ui);
+                    self.stack_count(buff.kind, *timer_id, state.ids.buff_stacks[i], ui);
                 });
         }
         event
@@ -430,6 +454,41 @@ impl<'a> Widget for BuffsBar<'a> {
 }
 
 impl<'a> BuffsBar<'a> {
+    /// Times the buff is applied, the strongest one being shown
+    fn stacks(&self, kind: BuffKind) -> usize { self.buffs.kinds.get(&kind).map_or(0, Vec::len) }
+
+    fn with_stacks<'b>(&self, desc: Cow<'b, str>, kind: BuffKind) -> Cow<'b, str> {
+        match self.stacks(kind) {
+            count @ 2.. => Cow::Owned(format!(
+                "{}\n\n{}",
+                desc,
+                self.localized_strings
+                    .get_msg_ctx("buff-stacks", &i18n::fluent_args! { "count" => count })
+            )),
+            _ => desc,
+        }
+    }
+
+    /// Number of stacks in the corner of the icon, when there are several
+    fn stack_count(
+        &self,
+        kind: BuffKind,
+        icon: widget::Id,
+        id: widget::Id,
+        ui: &mut conrod_core::UiCell,
+    ) {
+        let count = self.stacks(kind);
+        if count > 1 {
+            Text::new(&count.to_string())
+                .bottom_right_with_margins_on(icon, 1.0, 3.0)
+                .font_size(self.fonts.cyri.scale(12))
+                .font_id(self.fonts.cyri.conrod_id)
+                .graphics_for(icon)
+                .color(TEXT_COLOR)
+                .set(id, ui);
+        }
+    }
+
     fn get_duration_image(&self, duration_percentage: u32) -> Id {
         match duration_percentage as u64 {
             875..=1000 => self.imgs.nothing, // 8/8
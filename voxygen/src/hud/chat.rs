@@ -1,28 +1,49 @@
 use super::{
-    img_ids::Imgs, ChatTab, ERROR_COLOR, FACTION_COLOR, GROUP_COLOR, INFO_COLOR, KILL_COLOR,
-    OFFLINE_COLOR, ONLINE_COLOR, REGION_COLOR, SAY_COLOR, TELL_COLOR, TEXT_COLOR, WORLD_COLOR,
+    img_ids::Imgs, ChatTab, ERROR_COLOR, FACTION_COLOR, GROUP_COLOR, GUILD_COLOR, INFO_COLOR,
+    KILL_COLOR, OFFLINE_COLOR, ONLINE_COLOR, REGION_COLOR, SAY_COLOR, TELL_COLOR, TEXT_COLOR,
+    WORLD_COLOR,
 };
-use crate::{cmd::complete, settings::chat::MAX_CHAT_TABS, ui::fonts::Fonts, GlobalState};
-use client::Client;
-use common::comp::{
-    chat::{KillSource, KillType},
-    group::Role,
-    BuffKind, ChatMode, ChatMsg, ChatType,
+use crate::{
+    chat_history::ChatHistory,
+    cmd::{argument_hint, complete},
+    settings::chat::MAX_CHAT_TABS,
+    ui::fonts::Fonts,
+    GlobalState,
 };
+use client::{Client, HIDDEN_NAME};
+use common::{
+    comp::{
+        chat::{
+            normalize_chat_msg, resolve_chat_links, ChatLink, ChatLinkSpan, KillSource, KillType,
+            MAX_BYTES_CHAT_MSG,
+        },
+        group::Role,
+        humanoid, Body, BuffKind, ChatMode, ChatMsg, ChatType, Item,
+    },
+    uid::Uid,
+};
+use common_net::sync::WorldSyncExt;
 use conrod_core::{
     color,
     input::Key,
-    position::Dimension,
+    position::{Align, Dimension, Rect},
     text::{
         self,
         cursor::{self, Index},
+        Justify,
     },
     widget::{self, Button, Id, Image, List, Rectangle, Text, TextEdit},
     widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Ui, UiCell, Widget,
     WidgetCommon,
 };
-use i18n::Localization;
-use std::collections::{HashSet, VecDeque};
+use hashbrown::HashMap;
+use i18n::{Gender, Localization};
+use specs::WorldExt;
+use std::{
+    collections::{HashSet, VecDeque},
+    ops::Range,
+};
+use vek::Vec2;
 
 widget_ids! {
     struct Ids {
@@ -31,6 +52,8 @@ widget_ids! {
         chat_input,
         chat_input_bg,
         chat_input_icon,
+        chat_hint_bg,
+        chat_hint_text,
         chat_arrow,
         chat_icon_align,
         chat_icons[],
@@ -41,6 +64,14 @@ widget_ids! {
         chat_tabs[],
         chat_tab_tooltip_bg,
         chat_tab_tooltip_text,
+
+        link_tooltip_bg,
+        link_tooltip_text,
+
+        search_btn,
+        search_bg,
+        search_close,
+        search_input,
     }
 }
 /*#[const_tweaker::tweak(min = 0.0, max = 60.0, step = 1.0)]
@@ -53,14 +84,23 @@ const CHAT_ICON_HEIGHT: f64 = 16.0;
 const CHAT_BOX_WIDTH: f64 = 470.0;
 const CHAT_BOX_INPUT_WIDTH: f64 = 460.0 - CHAT_ICON_WIDTH - 1.0;
 const CHAT_BOX_HEIGHT: f64 = 154.0;
+const MESSAGE_LINE_SPACING: f64 = 2.0;
+
+/// Words with more characters than this are wrapped anywhere, text in
+/// languages without spaces between words would overflow the chat box
+/// otherwise
+const MAX_WRAPPED_WORD_CHARS: usize = 24;
 
 const CHAT_TAB_HEIGHT: f64 = 20.0;
 const CHAT_TAB_ALL_WIDTH: f64 = 40.0;
+/// Label colour of the chat tabs with unread messages
+const UNREAD_COLOR: Color = Color::Rgba(1.0, 0.82, 0.27, 1.0);
 
 #[derive(WidgetCommon)]
 pub struct Chat<'a> {
     pulse: f32,
     new_messages: &'a mut VecDeque<ChatMsg>,
+    chat_history: &'a mut ChatHistory,
     client: &'a Client,
     force_input: Option<String>,
     force_cursor: Option<Index>,
@@ -82,6 +122,7 @@ pub struct Chat<'a> {
 impl<'a> Chat<'a> {
     pub fn new(
         new_messages: &'a mut VecDeque<ChatMsg>,
+        chat_history: &'a mut ChatHistory,
         client: &'a Client,
         global_state: &'a GlobalState,
         pulse: f32,
@@ -92,6 +133,7 @@ impl<'a> Chat<'a> {
         Self {
             pulse,
             new_messages,
+            chat_history,
             client,
             force_input: None,
             force_cursor: None,
@@ -128,6 +170,56 @@ impl<'a> Chat<'a> {
         self
     }
 
+    /// The message with the localized text of its kind, which the client
+    /// fills in when formatting it
+    fn localize(&self, message: &ChatMsg) -> ChatMsg {
+        let mut message = message.clone();
+        if let Some(template_key) = get_chat_template_key(&message.chat_type) {
+            let (victim_gender, attacker_gender) = match &message.chat_type {
+                ChatType::Kill(KillSource::Player(attacker, _), victim) => (
+                    gender_of(self.client, *victim),
+                    gender_of(self.client, *attacker),
+                ),
+                ChatType::Kill(_, uid) | ChatType::Online(uid) | ChatType::Offline(uid) => {
+                    (gender_of(self.client, *uid), Gender::Other)
+                },
+                _ => (Gender::Other, Gender::Other),
+            };
+            // FIXME (i18n death messages):
+            // Death message is half localized in voxygen, half in client.
+            // Make this not.
+            message.message = self
+                .localized_strings
+                .get_msg_ctx(template_key, &i18n::fluent_args! {
+                    "attacker" => "{attacker}",
+                    "attacker_gender" => attacker_gender,
+                    "name" => "{name}",
+                    "gender" => victim_gender,
+                    "died_of_buff" => "{died_of_buff}",
+                    "victim" => "{victim}",
+                    "victim_gender" => victim_gender,
+                    "environment" => "{environment}",
+                })
+                .into_owned();
+
+            if let ChatType::Kill(kill_source, _) = &message.chat_type {
+                match kill_source {
+                    KillSource::Player(_, KillType::Buff(buffkind))
+                    | KillSource::NonExistent(KillType::Buff(buffkind))
+                    | KillSource::NonPlayer(_, KillType::Buff(buffkind)) => {
+                        message.message = insert_killing_buff(
+                            *buffkind,
+                            self.localized_strings,
+                            &message.message,
+                        );
+                    },
+                    _ => {},
+                }
+            }
+        }
+        message
+    }
+
     pub fn scrolled_to_bottom(state: &State, ui: &UiCell) -> bool {
         // Might be more efficient to cache result and update it when a scroll event has
         // occurred instead of every frame.
@@ -165,8 +257,16 @@ pub struct State {
     tabs_last_hover_pulse: Option<f32>,
     // last chat_tab (used to see if chat tab has been changed)
     prev_chat_tab: Option<ChatTab>,
+    // Messages received by each chat tab since it was last open
+    unread: Vec<usize>,
     //whether or not a scroll action is queued
     scroll_next: bool,
+    // whether the cursor of the input is moved to its end on the next frame
+    cursor_to_end: bool,
+    // text the shown messages are searched for, while the search bar is open
+    search: Option<String>,
+    // description of the items linked in messages, by item definition id
+    linked_items: HashMap<String, Option<String>>,
 }
 
 pub enum Event {
@@ -176,6 +276,7 @@ pub enum Event {
     Focus(Id),
     ChangeChatTab(Option<usize>),
     ShowChatTabSettings(usize),
+    SetLocationMarker(Vec2<i32>),
 }
 
 impl<'a> Widget for Chat<'a> {
@@ -189,7 +290,12 @@ impl<'a> Widget for Chat<'a> {
                 message: "".to_owned(),
                 mode: ChatMode::default(),
             },
-            messages: VecDeque::new(),
+            // The chat of the past sessions on this server
+            messages: self
+                .chat_history
+                .lines()
+                .map(|line| ChatType::Meta.chat_msg(line))
+                .collect(),
             history: VecDeque::new(),
             history_pos: 0,
             completions: Vec::new(),
@@ -198,7 +304,11 @@ impl<'a> Widget for Chat<'a> {
             ids: Ids::new(id_gen),
             tabs_last_hover_pulse: None,
             prev_chat_tab: None,
+            unread: Vec::new(),
             scroll_next: false,
+            cursor_to_end: false,
+            search: None,
+            linked_items: HashMap::new(),
         }
     }
 
@@ -215,6 +325,15 @@ impl<'a> Widget for Chat<'a> {
 
         let chat_tabs = &chat_settings.chat_tabs;
         let current_chat_tab = chat_settings.chat_tab_index.and_then(|i| chat_tabs.get(i));
+        let group_members = self
+            .client
+            .group_members()
+            .iter()
+            .filter_map(|(u, r)| match r {
+                Role::Member => Some(u),
+                Role::Pet => None,
+            })
+            .collect::<HashSet<_>>();
 
         // Empty old messages
         state.update(|s| {
@@ -225,6 +344,32 @@ impl<'a> Widget for Chat<'a> {
 
         // Maintain scrolling //
         if !self.new_messages.is_empty() {
+            // The tabs not open keep count of the new messages they would show
+            state.update(|s| {
+                s.unread.resize(chat_tabs.len(), 0);
+                for (i, (chat_tab, unread)) in chat_tabs.iter().zip(&mut s.unread).enumerate() {
+                    if chat_settings.chat_tab_index != Some(i) {
+                        *unread += self
+                            .new_messages
+                            .iter()
+                            .filter(|m| chat_tab.filter.satisfies(m, &group_members))
+                            .count();
+                    }
+                }
+            });
+            // Kept for the next sessions as they are shown
+            let show_char_name = chat_settings.chat_character_name;
+            let lines = self
+                .new_messages
+                .iter()
+                .map(|m| {
+                    self.client
+                        .format_message(&self.localize(m), show_char_name, false)
+                })
+                .collect::<Vec<_>>();
+            for line in lines {
+                self.chat_history.push(line);
+            }
             //new messages - update chat w/ them & scroll down if at bottom of chat
             state.update(|s| s.messages.extend(self.new_messages.drain(..)));
             // Prevent automatic scroll upon new messages if not already scrolled to bottom
@@ -242,6 +387,12 @@ impl<'a> Widget for Chat<'a> {
         // Queue scroll event if switching from a different tab
         if current_chat_tab != state.prev_chat_tab.as_ref() {
             state.update(|s| s.prev_chat_tab = current_chat_tab.cloned());
+            if let Some(i) = chat_settings
+                .chat_tab_index
+                .filter(|i| state.unread.get(*i).map_or(false, |unread| *unread > 0))
+            {
+                state.update(|s| s.unread[i] = 0);
+            }
             state.update(|s| s.scroll_next = true); //make scroll happen only once any filters to the messages have already been applied
         }
 
@@ -250,6 +401,15 @@ impl<'a> Widget for Chat<'a> {
         }
 
         let mut force_cursor = self.force_cursor;
+        if state.cursor_to_end {
+            force_cursor = cursor_offset_to_index(
+                state.input.message.chars().count(),
+                &state.input.message,
+                ui,
+                self.fonts,
+            );
+            state.update(|s| s.cursor_to_end = false);
+        }
 
         // If up or down are pressed: move through history
         // If any key other than up, down, or tab is pressed: stop completion.
@@ -297,7 +457,7 @@ impl<'a> Widget for Chat<'a> {
                 });
             }
             false
-        } else if let Some(cursor) = state.input.message.find('\t') {
+        } else if let Some(cursor) = state.input.message.chars().position(|c| c == '\t') {
             // Begin tab completion
             state.update(|s| s.completion_cursor = Some(cursor));
             true
@@ -319,7 +479,7 @@ impl<'a> Widget for Chat<'a> {
                 if s.history_pos > 0 {
                     s.input.message = s.history.get(s.history_pos - 1).unwrap().to_owned();
                     force_cursor = cursor_offset_to_index(
-                        s.input.message.len(),
+                        s.input.message.chars().count(),
                         &s.input.message,
                         ui,
                         self.fonts,
@@ -387,6 +547,22 @@ impl<'a> Widget for Chat<'a> {
                 input.retain(|c| c != '\n');
                 state.update(|s| s.input.message = input);
             }
+
+            // Usage of the command being typed, next to the input
+            if let Some(hint) = argument_hint(&state.input.message, chat_settings.chat_cmd_prefix) {
+                let hint_text = Text::new(&hint)
+                    .font_size(self.fonts.cyri.scale(12))
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .color(TEXT_COLOR);
+                let [w, h] = hint_text.get_wh(ui).unwrap_or([CHAT_BOX_WIDTH, 14.0]);
+                Rectangle::fill([w + 10.0, h + 8.0])
+                    .rgba(0.0, 0.0, 0.0, chat_settings.chat_opacity + 0.1)
+                    .bottom_right_with_margins_on(state.ids.chat_input_bg, 0.0, -(w + 15.0))
+                    .set(state.ids.chat_hint_bg, ui);
+                hint_text
+                    .middle_of(state.ids.chat_hint_bg)
+                    .set(state.ids.chat_hint_text, ui);
+            }
         }
 
         // Message box
@@ -408,52 +584,30 @@ impl<'a> Widget for Chat<'a> {
                     .resize(s.messages.len(), &mut ui.widget_id_generator())
             });
         }
-        let group_members = self
-            .client
-            .group_members()
-            .iter()
-            .filter_map(|(u, r)| match r {
-                Role::Member => Some(u),
-                Role::Pet => None,
-            })
-            .collect::<HashSet<_>>();
         let show_char_name = chat_settings.chat_character_name;
+        let streamer_mode = self.global_state.settings.interface.streamer_mode;
+        // Streamers don't show their account name, even in what the server says
+        // about them, but the name of their character
+        let hidden_alias = streamer_mode
+            .then(|| self.client.uid())
+            .flatten()
+            .and_then(|uid| self.client.player_list().get(&uid))
+            .map(|player| {
+                let name = player
+                    .character
+                    .as_ref()
+                    .map_or(HIDDEN_NAME, |character| character.name.as_str());
+                (player.player_alias.as_str(), name)
+            });
+        let search_key = state.search.as_deref().unwrap_or("").to_lowercase();
         let messages = &state
             .messages
             .iter()
             .map(|m| {
-                let mut message = m.clone();
-                if let Some(template_key) = get_chat_template_key(&message.chat_type) {
-                    // FIXME (i18n death messages):
-                    // Death message is half localized in voxygen, half in client.
-                    // Make this not.
-                    message.message = self
-                        .localized_strings
-                        .get_msg_ctx(template_key, &i18n::fluent_args! {
-                            "attacker" => "{attacker}",
-                            "name" => "{name}",
-                            "died_of_buff" => "{died_of_buff}",
-                            "victim" => "{victim}",
-                            "environment" => "{environment}",
-                        })
-                        .into_owned();
-
-                    if let ChatType::Kill(kill_source, _) = &message.chat_type {
-                        match kill_source {
-                            KillSource::Player(_, KillType::Buff(buffkind))
-                            | KillSource::NonExistent(KillType::Buff(buffkind))
-                            | KillSource::NonPlayer(_, KillType::Buff(buffkind)) => {
-                                message.message = insert_killing_buff(
-                                    *buffkind,
-                                    self.localized_strings,
-                                    &message.message,
-                                );
-                            },
-                            _ => {},
-                        }
-                    }
+                let mut message = self.localize(m);
+                if let Some((alias, name)) = hidden_alias {
+                    hide_alias(&mut message, alias, name);
                 }
-                message.message = self.client.format_message(&message, show_char_name);
                 message
             })
             .filter(|m| {
@@ -463,6 +617,15 @@ impl<'a> Widget for Chat<'a> {
                     true
                 }
             })
+            // The sender is part of the formatted message, so it can be searched for too
+            .filter(|m| {
+                search_key.is_empty()
+                    || self
+                        .client
+                        .format_message(m, show_char_name, false)
+                        .to_lowercase()
+                        .contains(&search_key)
+            })
             .collect::<Vec<_>>();
         Rectangle::fill_with([CHAT_ICON_WIDTH, CHAT_BOX_HEIGHT], color::TRANSPARENT)
             .top_left_with_margins_on(state.ids.message_box_bg, 0.0, 0.0)
@@ -474,6 +637,8 @@ impl<'a> Widget for Chat<'a> {
             .scroll_kids_vertically()
             .set(state.ids.message_box, ui);
 
+        // Row and item definition of the item link in the hovered message
+        let mut hovered_item = None;
         while let Some(item) = items.next(ui) {
             // This would be easier if conrod used the v-metrics from rusttype.
             if item.i < messages.len() {
@@ -484,12 +649,62 @@ impl<'a> Widget for Chat<'a> {
                 // provided in ChatType in the client/src/mod.rs
                 // fn format_message called below
 
-                let text = Text::new(&message.message)
+                // Streamers only see who whispers to them while hovering the message
+                let hide_whisper_sender = streamer_mode
+                    && ui
+                        .widget_input(item.widget_id)
+                        .mouse()
+                        .map_or(true, |m| !m.is_over());
+                let formatted =
+                    self.client
+                        .format_message(message, show_char_name, hide_whisper_sender);
+                let links = message_links(self.client, message, &formatted);
+                let wrap_by_character = has_long_words(&formatted);
+
+                // Only the links react to the mouse, not the rest of the message
+                let row_input = ui.widget_input(item.widget_id);
+                let hovered = row_input.mouse().map_or(false, |m| m.is_over());
+                let clicked = row_input.clicks().left().next().is_some();
+                let mut hovered_link = None;
+                if let Some(rect) = ui.rect_of(item.widget_id).filter(|_| hovered) {
+                    let mouse = ui.global_input().current.mouse.xy;
+                    for (chars, link) in &links {
+                        let rects =
+                            text_rects(ui, self.fonts, &formatted, chars, rect, wrap_by_character);
+                        if rects.iter().any(|r| r.is_over(mouse)) {
+                            hovered_link = Some(link.clone());
+                            break;
+                        }
+                    }
+                }
+                match hovered_link {
+                    Some(ChatLink::Item(id)) => hovered_item = Some((item.widget_id, id)),
+                    // Whispering never replaces what is being written
+                    Some(ChatLink::Player(alias))
+                        if clicked && state.input.message.trim().is_empty() =>
+                    {
+                        state.update(|s| {
+                            s.input.message =
+                                format!("{}tell {} ", chat_settings.chat_cmd_prefix, alias);
+                            s.cursor_to_end = true;
+                        });
+                        events.push(Event::Focus(state.ids.chat_input));
+                    },
+                    Some(ChatLink::Pos(pos)) if clicked => {
+                        events.push(Event::SetLocationMarker(pos))
+                    },
+                    _ => {},
+                }
+
+                let mut text = Text::new(&formatted)
                     .font_size(self.fonts.opensans.scale(15))
                     .font_id(self.fonts.opensans.conrod_id)
                     .w(CHAT_BOX_WIDTH - 17.0)
                     .color(color)
-                    .line_spacing(2.0);
+                    .line_spacing(MESSAGE_LINE_SPACING);
+                if wrap_by_character {
+                    text = text.wrap_by_character();
+                }
                 // Add space between messages.
                 let y = match text.get_y_dimension(ui) {
                     Dimension::Absolute(y) => y + 2.0,
@@ -515,6 +730,76 @@ impl<'a> Widget for Chat<'a> {
             };
         }
 
+        // Description of the hovered item link, items are only loaded once
+        if let Some((_, id)) = &hovered_item {
+            if !state.linked_items.contains_key(id) {
+                let desc = Item::new_from_asset(id)
+                    .ok()
+                    .map(|linked| format!("{}\n{}", linked.name(), linked.description()));
+                state.update(|s| {
+                    s.linked_items.insert(id.clone(), desc);
+                });
+            }
+        }
+        if let Some((row, desc)) =
+            hovered_item.and_then(|(row, id)| Some((row, state.linked_items.get(&id)?.clone()?)))
+        {
+            let tooltip = Text::new(&desc)
+                .w(CHAT_BOX_WIDTH / 2.0)
+                .font_size(self.fonts.cyri.scale(12))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR);
+            let [w, h] = tooltip.get_wh(ui).unwrap_or([CHAT_BOX_WIDTH / 2.0, 30.0]);
+            Rectangle::fill([w + 10.0, h + 10.0])
+                .rgba(0.0, 0.0, 0.0, 0.9)
+                .top_left_with_margins_on(row, -(h + 14.0), 0.0)
+                .parent(id)
+                .set(state.ids.link_tooltip_bg, ui);
+            tooltip
+                .top_left_with_margins_on(state.ids.link_tooltip_bg, 5.0, 5.0)
+                .set(state.ids.link_tooltip_text, ui);
+        }
+
+        // Search bar, filtering the messages by their text and sender
+        if let Some(search) = state.search.clone() {
+            Rectangle::fill([CHAT_BOX_WIDTH, CHAT_TAB_HEIGHT])
+                .rgba(0.0, 0.0, 0.0, chat_settings.chat_opacity + 0.1)
+                .up_from(state.ids.message_box_bg, 0.0)
+                .set(state.ids.search_bg, ui);
+            if Button::image(self.imgs.close_btn)
+                .w_h(14.0, 14.0)
+                .hover_image(self.imgs.close_btn_hover)
+                .press_image(self.imgs.close_btn_press)
+                .mid_left_with_margin_on(state.ids.search_bg, 3.0)
+                .set(state.ids.search_close, ui)
+                .was_clicked()
+            {
+                state.update(|s| s.search = None);
+                events.push(Event::Focus(id));
+            } else if let Some(mut search) = TextEdit::new(&search)
+                .w_h(CHAT_BOX_WIDTH - 24.0, CHAT_TAB_HEIGHT)
+                .right_from(state.ids.search_close, 4.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.search_input, ui)
+            {
+                search.retain(|c| c != '\n');
+                state.update(|s| s.search = Some(search));
+            }
+        } else if Button::image(self.imgs.search_btn)
+            .w_h(16.0, 16.0)
+            .hover_image(self.imgs.search_btn_hover)
+            .press_image(self.imgs.search_btn_press)
+            .bottom_right_with_margins_on(state.ids.message_box_bg, 2.0, -20.0)
+            .parent(id)
+            .set(state.ids.search_btn, ui)
+            .was_clicked()
+        {
+            state.update(|s| s.search = Some(String::new()));
+            events.push(Event::Focus(state.ids.search_input));
+        }
+
         //Chat tabs
         if ui
             .rect_of(state.ids.message_box_bg)
@@ -523,17 +808,30 @@ impl<'a> Widget for Chat<'a> {
             state.update(|s| s.tabs_last_hover_pulse = Some(self.pulse));
         }
 
-        if let Some(time_since_hover) = state
-            .tabs_last_hover_pulse
-            .map(|t| self.pulse - t)
-            .filter(|t| t <= &1.5)
-        {
-            let alpha = 1.0 - (time_since_hover / 1.5).powi(4);
+        // The tabs stay shown while some have unread messages
+        let has_unread = state.unread.iter().any(|unread| *unread > 0);
+        let tabs_alpha = if has_unread {
+            Some(1.0)
+        } else {
+            state
+                .tabs_last_hover_pulse
+                .map(|t| self.pulse - t)
+                .filter(|t| t <= &1.5)
+                .map(|time_since_hover| 1.0 - (time_since_hover / 1.5).powi(4))
+        };
+        if let Some(alpha) = tabs_alpha {
             let shading = color::rgba(1.0, 0.82, 0.27, (chat_settings.chat_opacity + 0.1) * alpha);
 
             Rectangle::fill([CHAT_BOX_WIDTH, CHAT_TAB_HEIGHT])
                 .rgba(0.0, 0.0, 0.0, (chat_settings.chat_opacity + 0.1) * alpha)
-                .up_from(state.ids.message_box_bg, 0.0)
+                .up_from(
+                    if state.search.is_some() {
+                        state.ids.search_bg
+                    } else {
+                        state.ids.message_box_bg
+                    },
+                    0.0,
+                )
                 .set(state.ids.chat_tab_align, ui);
             if ui
                 .rect_of(state.ids.chat_tab_align)
@@ -572,6 +870,12 @@ impl<'a> Widget for Chat<'a> {
                 });
             }
             for (i, chat_tab) in chat_tabs.iter().enumerate() {
+                let unread = state.unread.get(i).copied().unwrap_or(0);
+                let label = if unread > 0 {
+                    format!("{} ({})", chat_tab.label, unread)
+                } else {
+                    chat_tab.label.clone()
+                };
                 if Button::image(if chat_settings.chat_tab_index == Some(i) {
                     self.imgs.selection
                 } else {
@@ -581,10 +885,10 @@ impl<'a> Widget for Chat<'a> {
                 .hover_image(self.imgs.selection_hover)
                 .press_image(self.imgs.selection_press)
                 .image_color(shading)
-                .label(chat_tab.label.as_str())
+                .label(&label)
                 .label_font_size(self.fonts.cyri.scale(14))
                 .label_font_id(self.fonts.cyri.conrod_id)
-                .label_color(TEXT_COLOR.alpha(alpha))
+                .label_color(if unread > 0 { UNREAD_COLOR } else { TEXT_COLOR }.alpha(alpha))
                 .right_from(
                     if i == 0 {
                         state.ids.chat_tab_all
@@ -662,6 +966,23 @@ impl<'a> Widget for Chat<'a> {
             |key_press| matches!(key_press.key, Key::Return if !state.input.message.is_empty()),
         ) {
             let msg = state.input.message.clone();
+            if msg.strip_prefix(chat_settings.chat_cmd_prefix).is_none()
+                && normalize_chat_msg(&resolve_chat_links(&msg).0).len() > MAX_BYTES_CHAT_MSG
+            {
+                // Keep the message around so it can be shortened
+                self.new_messages.push_back(ChatMsg {
+                    chat_type: ChatType::CommandError,
+                    message: self
+                        .localized_strings
+                        .get_msg_ctx(
+                            "hud-chat-message_too_long",
+                            &i18n::fluent_args! { "max" => MAX_BYTES_CHAT_MSG },
+                        )
+                        .into_owned(),
+                    links: Vec::new(),
+                });
+                return events;
+            }
             state.update(|s| {
                 s.input.message.clear();
                 // Update the history
@@ -678,6 +999,7 @@ impl<'a> Widget for Chat<'a> {
                     Err(err) => self.new_messages.push_back(ChatMsg {
                         chat_type: ChatType::CommandError,
                         message: err,
+                        links: Vec::new(),
                     }),
                 }
             } else {
@@ -731,6 +1053,152 @@ fn do_tab_completion(cursor: usize, input: &str, word: &str) -> (String, usize)
     }
 }
 
+/// Links of the formatted message, as character ranges, the name of whoever
+/// sent it being a link to whisper to them
+fn message_links(
+    client: &Client,
+    message: &ChatMsg,
+    formatted: &str,
+) -> Vec<(Range<usize>, ChatLink)> {
+    // The text of the message ends the formatted message
+    let offset = match formatted.strip_suffix(message.message.as_str()) {
+        Some(prefix) => prefix.len(),
+        None => return Vec::new(),
+    };
+    let chars = |range: Range<usize>| {
+        let start = formatted[..range.start].chars().count();
+        start..start + formatted[range].chars().count()
+    };
+    let mut links = Vec::new();
+    if let Some(alias) = sender_alias(client, message) {
+        if let Some(start) = formatted[..offset].find(&format!("{}]", alias)) {
+            let range = chars(start..start + alias.len());
+            links.push((range, ChatLink::Player(alias)));
+        }
+    }
+    links.extend(message.links.iter().filter_map(|span| {
+        let range = offset + span.range.start..offset + span.range.end;
+        formatted.get(range.clone())?;
+        Some((chars(range), span.link.clone()))
+    }));
+    links
+}
+
+/// Rectangles the characters of a message are drawn in, laid out like in the
+/// message box
+fn text_rects(
+    ui: &Ui,
+    fonts: &Fonts,
+    text: &str,
+    chars: &Range<usize>,
+    rect: Rect,
+    wrap_by_character: bool,
+) -> Vec<Rect> {
+    // Font and wrapping must match that of the messages
+    let font = match ui.fonts.get(fonts.opensans.conrod_id) {
+        Some(font) => font,
+        None => return Vec::new(),
+    };
+    let font_size = fonts.opensans.scale(15);
+    let infos = text::line::infos(text, font, font_size);
+    let infos = if wrap_by_character {
+        infos.wrap_by_character(rect.w()).collect::<Vec<_>>()
+    } else {
+        infos.wrap_by_whitespace(rect.w()).collect::<Vec<_>>()
+    };
+    let start = cursor::index_before_char(infos.iter().cloned(), chars.start);
+    let end = cursor::index_before_char(infos.iter().cloned(), chars.end);
+    let (start, end) = match start.zip(end) {
+        Some(indices) => indices,
+        None => return Vec::new(),
+    };
+    let lines = infos.iter().map(|info| &text[info.byte_range()]);
+    let line_rects = text::line::rects(
+        infos.iter(),
+        font_size,
+        rect,
+        Justify::Left,
+        Align::End,
+        MESSAGE_LINE_SPACING,
+    );
+    text::line::selected_rects(lines.zip(line_rects), font, font_size, start, end).collect()
+}
+
+/// The player who wrote the message, unless it is us
+fn sender_alias(client: &Client, message: &ChatMsg) -> Option<String> {
+    let uid = match message.chat_type {
+        ChatType::Tell(uid, _)
+        | ChatType::Say(uid)
+        | ChatType::Group(uid, _)
+        | ChatType::Faction(uid, _)
+        | ChatType::Guild(uid, _)
+        | ChatType::Region(uid)
+        | ChatType::World(uid) => uid,
+        _ => return None,
+    };
+    (client.uid() != Some(uid))
+        .then(|| client.player_list().get(&uid))
+        .flatten()
+        .map(|player| player.player_alias.clone())
+}
+
+/// Replaces the alias with the name where it is a whole word of the text of
+/// the message, the links over it are dropped and the others moved along with
+/// their label
+fn hide_alias(message: &mut ChatMsg, alias: &str, name: &str) {
+    if alias.is_empty() {
+        return;
+    }
+    // Characters allowed in aliases, see `Player::alias_validate`
+    let is_alias_char = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    let text = &message.message;
+    let matches = text
+        .match_indices(alias)
+        .map(|(start, _)| start..start + alias.len())
+        .filter(|m| {
+            !text[..m.start]
+                .chars()
+                .next_back()
+                .map_or(false, is_alias_char)
+                && !text[m.end..].chars().next().map_or(false, is_alias_char)
+        })
+        .collect::<Vec<_>>();
+    if matches.is_empty() {
+        return;
+    }
+    let shift = name.len() as isize - alias.len() as isize;
+    message.links = core::mem::take(&mut message.links)
+        .into_iter()
+        .filter(|span| {
+            !matches
+                .iter()
+                .any(|m| m.start < span.range.end && span.range.start < m.end)
+        })
+        .map(|span| {
+            let before = matches.iter().filter(|m| m.end <= span.range.start).count() as isize;
+            let offset = |i: usize| (i as isize + before * shift) as usize;
+            ChatLinkSpan {
+                range: offset(span.range.start)..offset(span.range.end),
+                link: span.link,
+            }
+        })
+        .collect();
+    let mut hidden = String::with_capacity(message.message.len());
+    let mut end = 0;
+    for m in &matches {
+        hidden.push_str(&message.message[end..m.start]);
+        hidden.push_str(name);
+        end = m.end;
+    }
+    hidden.push_str(&message.message[end..]);
+    message.message = hidden;
+}
+
+fn has_long_words(text: &str) -> bool {
+    text.split_whitespace()
+        .any(|word| word.chars().nth(MAX_WRAPPED_WORD_CHARS).is_some())
+}
+
 fn cursor_offset_to_index(offset: usize, text: &str, ui: &Ui, fonts: &Fonts) -> Option<Index> {
     // This moves the cursor to the given offset. Conrod is a pain.
     //
@@ -750,6 +1218,7 @@ fn render_chat_mode(chat_mode: &ChatMode, imgs: &Imgs) -> (Color, conrod_core::i
         ChatMode::Region => (REGION_COLOR, imgs.chat_region_small),
         ChatMode::Faction(_) => (FACTION_COLOR, imgs.chat_faction_small),
         ChatMode::Group(_) => (GROUP_COLOR, imgs.chat_group_small),
+        ChatMode::Guild(_) => (GUILD_COLOR, imgs.chat_faction_small),
         ChatMode::Tell(_) => (TELL_COLOR, imgs.chat_tell_small),
     }
 }
@@ -763,11 +1232,13 @@ fn render_chat_line(chat_type: &ChatType<String>, imgs: &Imgs) -> (Color, conrod
         ChatType::CommandInfo => (INFO_COLOR, imgs.chat_command_info_small),
         ChatType::GroupMeta(_) => (GROUP_COLOR, imgs.chat_group_small),
         ChatType::FactionMeta(_) => (FACTION_COLOR, imgs.chat_faction_small),
+        ChatType::GuildMeta(_) => (GUILD_COLOR, imgs.chat_faction_small),
         ChatType::Kill(_, _) => (KILL_COLOR, imgs.chat_kill_small),
         ChatType::Tell(_from, _to) => (TELL_COLOR, imgs.chat_tell_small),
         ChatType::Say(_uid) => (SAY_COLOR, imgs.chat_say_small),
         ChatType::Group(_uid, _s) => (GROUP_COLOR, imgs.chat_group_small),
         ChatType::Faction(_uid, _s) => (FACTION_COLOR, imgs.chat_faction_small),
+        ChatType::Guild(_uid, _s) => (GUILD_COLOR, imgs.chat_faction_small),
         ChatType::Region(_uid) => (REGION_COLOR, imgs.chat_region_small),
         ChatType::World(_uid) => (WORLD_COLOR, imgs.chat_world_small),
         ChatType::Npc(_uid, _r) => panic!("NPCs can't talk!"), // Should be filtered by hud/mod.rs
@@ -777,6 +1248,22 @@ fn render_chat_line(chat_type: &ChatType<String>, imgs: &Imgs) -> (Color, conrod
     }
 }
 
+/// Grammatical gender of the character of a player, for the translations of
+/// the messages about them
+fn gender_of(client: &Client, uid: Uid) -> Gender {
+    let ecs = client.state().ecs();
+    let body = ecs
+        .entity_from_uid(uid.0)
+        .and_then(|entity| ecs.read_storage::<Body>().get(entity).copied());
+    match body {
+        Some(Body::Humanoid(body)) => match body.body_type {
+            humanoid::BodyType::Male => Gender::Masculine,
+            humanoid::BodyType::Female => Gender::Feminine,
+        },
+        _ => Gender::Other,
+    }
+}
+
 fn insert_killing_buff(buff: BuffKind, localized_strings: &Localization, template: &str) -> String {
     let buff_outcome = match buff {
         BuffKind::Burning => "hud-outcome-burning",
@@ -793,7 +1280,8 @@ fn insert_killing_buff(buff: BuffKind, localized_strings: &Localization, templat
         | BuffKind::Invulnerability
         | BuffKind::ProtectingWard
         | BuffKind::Frenzied
-        | BuffKind::Hastened => {
+        | BuffKind::Hastened
+        | BuffKind::Rested => {
             tracing::error!("Player was killed by a positive buff!");
             "hud-outcome-mysterious"
         },
@@ -897,4 +1385,30 @@ mod tests {
             Ok(("say".to_string(), vec!["Hello\nWorld".to_string()]));
         assert_eq!(parse_cmd(r#"say "Hello\nWorld""#), expected);
     }
+
+    #[test]
+    fn hidden_alias_keeps_the_links_on_their_label() {
+        let mut message = ChatType::CommandInfo.chat_msg("Streamer gave Streamer a Sword");
+        message.links = vec![
+            ChatLinkSpan {
+                range: 0..8,
+                link: ChatLink::Player("Streamer".to_string()),
+            },
+            ChatLinkSpan {
+                range: 25..30,
+                link: ChatLink::Item("common.items.weapons.sword.starter".to_string()),
+            },
+        ];
+        hide_alias(&mut message, "Streamer", "Hero");
+        assert_eq!(message.message, "Hero gave Hero a Sword");
+        assert_eq!(message.links.len(), 1);
+        assert_eq!(&message.message[message.links[0].range.clone()], "Sword");
+    }
+
+    #[test]
+    fn hidden_alias_only_replaces_whole_words() {
+        let mut message = ChatType::CommandInfo.chat_msg("Al: Also ask Al_2 and Al.");
+        hide_alias(&mut message, "Al", "Hero");
+        assert_eq!(message.message, "Hero: Also ask Al_2 and Hero.");
+    }
 }
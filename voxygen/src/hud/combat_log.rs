@@ -0,0 +1,399 @@
+//! Log of the combat the character took part in: the damage dealt and taken,
+//! the healing and the kills, kept for the session alongside the floating
+//! numbers
+
+use super::{img_ids::Imgs, TEXT_COLOR, TEXT_GRAY_COLOR, UI_HIGHLIGHT_0, UI_MAIN};
+use crate::ui::{fonts::Fonts, ToggleButton};
+use chrono::{DateTime, Local};
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Scrollbar, Text},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use std::collections::VecDeque;
+
+/// Oldest entries are forgotten past this many
+const MAX_ENTRIES: usize = 500;
+
+const DAMAGE_DEALT_COLOR: Color = Color::Rgba(1.0, 0.9, 0.6, 1.0);
+const DAMAGE_TAKEN_COLOR: Color = Color::Rgba(1.0, 0.45, 0.4, 1.0);
+const HEALING_COLOR: Color = Color::Rgba(0.4, 1.0, 0.45, 1.0);
+const KILLS_COLOR: Color = Color::Rgba(0.85, 0.6, 1.0, 1.0);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CombatLogCategory {
+    DamageDealt,
+    DamageTaken,
+    Healing,
+    Kills,
+}
+
+impl CombatLogCategory {
+    pub const ALL: [Self; 4] = [
+        Self::DamageDealt,
+        Self::DamageTaken,
+        Self::Healing,
+        Self::Kills,
+    ];
+
+    fn i18n_key(self) -> &'static str {
+        match self {
+            Self::DamageDealt => "hud-combat_log-damage_dealt",
+            Self::DamageTaken => "hud-combat_log-damage_taken",
+            Self::Healing => "hud-combat_log-healing",
+            Self::Kills => "hud-combat_log-kills",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::DamageDealt => DAMAGE_DEALT_COLOR,
+            Self::DamageTaken => DAMAGE_TAKEN_COLOR,
+            Self::Healing => HEALING_COLOR,
+            Self::Kills => KILLS_COLOR,
+        }
+    }
+}
+
+/// What happened, with the name of the other entity involved when it is known
+#[derive(Clone, Debug, PartialEq)]
+pub enum CombatEvent {
+    DamageDealt { target: Option<String>, amount: f32 },
+    DamageTaken { source: Option<String>, amount: f32 },
+    HealingDone { target: Option<String>, amount: f32 },
+    HealingReceived { source: Option<String>, amount: f32 },
+    Kill { victim: Option<String> },
+    Death { killer: Option<String> },
+}
+
+impl CombatEvent {
+    pub fn category(&self) -> CombatLogCategory {
+        match self {
+            Self::DamageDealt { .. } => CombatLogCategory::DamageDealt,
+            Self::DamageTaken { .. } => CombatLogCategory::DamageTaken,
+            Self::HealingDone { .. } | Self::HealingReceived { .. } => CombatLogCategory::Healing,
+            Self::Kill { .. } | Self::Death { .. } => CombatLogCategory::Kills,
+        }
+    }
+
+    fn text(&self, i18n: &Localization) -> String {
+        let (key, name, amount) = match self {
+            Self::DamageDealt { target, amount } => ("hud-combat_log-hit", target, *amount),
+            Self::DamageTaken { source, amount } => ("hud-combat_log-hit_by", source, *amount),
+            Self::HealingDone { target, amount } => ("hud-combat_log-healed", target, *amount),
+            Self::HealingReceived { source, amount } => {
+                ("hud-combat_log-healed_by", source, *amount)
+            },
+            Self::Kill { victim } => ("hud-combat_log-killed", victim, 0.0),
+            Self::Death { killer } => ("hud-combat_log-killed_by", killer, 0.0),
+        };
+        let name = name
+            .clone()
+            .unwrap_or_else(|| i18n.get_msg("hud-combat_log-unknown").into_owned());
+        i18n.get_msg_ctx(key, &i18n::fluent_args! {
+            "name" => name,
+            "amount" => i18n::fluent_number(amount.abs() as f64, 0),
+        })
+        .into_owned()
+    }
+}
+
+pub struct CombatLogEntry {
+    time: DateTime<Local>,
+    event: CombatEvent,
+}
+
+/// Entries of the session, the most recent last, along with the categories
+/// shown in the window
+#[derive(Default)]
+pub struct CombatLog {
+    entries: VecDeque<CombatLogEntry>,
+    hidden: Vec<CombatLogCategory>,
+}
+
+impl CombatLog {
+    pub fn record(&mut self, event: CombatEvent) {
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(CombatLogEntry {
+            time: Local::now(),
+            event,
+        });
+    }
+
+    pub fn is_shown(&self, category: CombatLogCategory) -> bool { !self.hidden.contains(&category) }
+
+    pub fn toggle(&mut self, category: CombatLogCategory) {
+        if self.is_shown(category) {
+            self.hidden.push(category);
+        } else {
+            self.hidden.retain(|hidden| *hidden != category);
+        }
+    }
+
+    pub fn clear(&mut self) { self.entries.clear(); }
+
+    /// Entries of the categories shown, the most recent last
+    fn shown(&self) -> impl Iterator<Item = &CombatLogEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| self.is_shown(entry.event.category()))
+    }
+}
+
+widget_ids! {
+    pub struct Ids {
+        frame,
+        close,
+        title,
+        bg,
+        icon,
+        filter_buttons[],
+        filter_texts[],
+        entries_align,
+        scrollbar,
+        entries[],
+        no_entries_txt,
+        clear_button,
+    }
+}
+
+pub struct State {
+    ids: Ids,
+    /// Entries listed last frame, to follow the new ones
+    listed: usize,
+}
+
+#[derive(WidgetCommon)]
+pub struct CombatLogWindow<'a> {
+    combat_log: &'a CombatLog,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> CombatLogWindow<'a> {
+    pub fn new(
+        combat_log: &'a CombatLog,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+    ) -> Self {
+        Self {
+            combat_log,
+            imgs,
+            fonts,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub enum Event {
+    Close,
+    ToggleCategory(CombatLogCategory),
+    Clear,
+}
+
+impl<'a> Widget for CombatLogWindow<'a> {
+    type Event = Vec<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        Self::State {
+            ids: Ids::new(id_gen),
+            listed: 0,
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("CombatLogWindow::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let i18n = self.localized_strings;
+        let mut events = Vec::new();
+
+        // Window BG
+        Image::new(self.imgs.social_bg_on)
+            .bottom_left_with_margins_on(ui.window, 308.0, 25.0)
+            .color(Some(UI_MAIN))
+            .w_h(280.0, 460.0)
+            .set(state.ids.bg, ui);
+        // Window frame
+        Image::new(self.imgs.social_frame_on)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .w_h(280.0, 460.0)
+            .set(state.ids.frame, ui);
+        // Icon
+        Image::new(self.imgs.chat_kill_small)
+            .w_h(30.0, 30.0)
+            .top_left_with_margins_on(state.ids.frame, 6.0, 6.0)
+            .set(state.ids.icon, ui);
+        // X-Button
+        if Button::image(self.imgs.close_button)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.ids.frame, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            events.push(Event::Close);
+        }
+
+        // Title
+        Text::new(&i18n.get_msg("hud-combat_log"))
+            .mid_top_with_margin_on(state.ids.frame, 9.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // Category filters, two on each row
+        let categories = CombatLogCategory::ALL;
+        if state.ids.filter_buttons.len() < categories.len() {
+            state.update(|s| {
+                s.ids
+                    .filter_buttons
+                    .resize(categories.len(), &mut ui.widget_id_generator());
+                s.ids
+                    .filter_texts
+                    .resize(categories.len(), &mut ui.widget_id_generator());
+            });
+        }
+        for (i, category) in categories.iter().enumerate() {
+            let shown = self.combat_log.is_shown(*category);
+            let button = ToggleButton::new(shown, self.imgs.checkbox, self.imgs.checkbox_checked)
+                .w_h(18.0, 18.0)
+                .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+                .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked);
+            let button = match i {
+                0 => button.top_left_with_margins_on(state.ids.frame, 48.0, 10.0),
+                1 => button.top_left_with_margins_on(state.ids.frame, 48.0, 140.0),
+                _ => button.down_from(state.ids.filter_buttons[i - 2], 6.0),
+            };
+            if button.set(state.ids.filter_buttons[i], ui) != shown {
+                events.push(Event::ToggleCategory(*category));
+            }
+            Text::new(&i18n.get_msg(category.i18n_key()))
+                .right_from(state.ids.filter_buttons[i], 6.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(category.color())
+                .graphics_for(state.ids.filter_buttons[i])
+                .set(state.ids.filter_texts[i], ui);
+        }
+
+        // Entries, scrolled to the most recent ones
+        Rectangle::fill_with([266.0, 320.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.ids.frame, 100.0)
+            .scroll_kids_vertically()
+            .set(state.ids.entries_align, ui);
+        Scrollbar::y_axis(state.ids.entries_align)
+            .thickness(4.0)
+            .color(Color::Rgba(0.79, 1.09, 1.09, 0.0))
+            .set(state.ids.scrollbar, ui);
+
+        let entries = self.combat_log.shown().collect::<Vec<_>>();
+        if entries.is_empty() {
+            Text::new(&i18n.get_msg("hud-combat_log-empty"))
+                .mid_top_with_margin_on(state.ids.entries_align, 10.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_GRAY_COLOR)
+                .set(state.ids.no_entries_txt, ui);
+        }
+        if state.ids.entries.len() < entries.len() {
+            state.update(|s| {
+                s.ids
+                    .entries
+                    .resize(entries.len(), &mut ui.widget_id_generator())
+            });
+        }
+        for (i, entry) in entries.iter().enumerate() {
+            let text = format!(
+                "[{}] {}",
+                entry.time.format("%H:%M:%S"),
+                entry.event.text(i18n)
+            );
+            let text = Text::new(&text)
+                .w(256.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(13))
+                .color(entry.event.category().color());
+            match i {
+                0 => text.top_left_with_margins_on(state.ids.entries_align, 2.0, 4.0),
+                _ => text.down_from(state.ids.entries[i - 1], 3.0),
+            }
+            .set(state.ids.entries[i], ui);
+        }
+        // Follow the new entries unless scrolled up to older ones
+        if entries.len() != state.listed {
+            let at_bottom = ui
+                .widget_graph()
+                .widget(state.ids.entries_align)
+                .and_then(|widget| widget.maybe_y_scroll_state)
+                .map_or(true, |scroll| {
+                    scroll.offset + 50.0 >= scroll.offset_bounds.start
+                });
+            if at_bottom {
+                ui.scroll_widget(state.ids.entries_align, [0.0, f64::MAX]);
+            }
+            let listed = entries.len();
+            state.update(|s| s.listed = listed);
+        }
+
+        // Clear
+        if Button::image(self.imgs.button)
+            .w_h(84.0, 26.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .bottom_right_with_margins_on(state.ids.frame, 9.0, 7.0)
+            .label(&i18n.get_msg("hud-combat_log-clear"))
+            .label_y(conrod_core::position::Relative::Scalar(3.0))
+            .label_color(TEXT_COLOR)
+            .label_font_size(self.fonts.cyri.scale(15))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .set(state.ids.clear_button, ui)
+            .was_clicked()
+        {
+            events.push(Event::Clear);
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_categories_are_filtered_out() {
+        let mut log = CombatLog::default();
+        log.record(CombatEvent::DamageDealt {
+            target: Some("Wolf".to_owned()),
+            amount: -12.0,
+        });
+        log.record(CombatEvent::Kill {
+            victim: Some("Wolf".to_owned()),
+        });
+        assert_eq!(log.shown().count(), 2);
+
+        log.toggle(CombatLogCategory::DamageDealt);
+        let shown = log.shown().collect::<Vec<_>>();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].event.category(), CombatLogCategory::Kills);
+
+        log.toggle(CombatLogCategory::DamageDealt);
+        assert_eq!(log.shown().count(), 2);
+    }
+}
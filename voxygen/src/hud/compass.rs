@@ -0,0 +1,259 @@
+use super::{img_ids::Imgs, placed_markers::PLACED_MARKER_COLOR, MapMarkers, TEXT_BG, TEXT_COLOR};
+use crate::ui::fonts::Fonts;
+use client::Client;
+use common::{
+    assets::AssetExt,
+    comp::quest::{Objective, QuestDef, QuestLog},
+};
+use conrod_core::{
+    color, image, position,
+    widget::{self, Image, Rectangle, Text},
+    widget_ids, Color, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use specs::WorldExt;
+use std::{borrow::Cow, f32::consts::PI};
+use vek::*;
+
+widget_ids! {
+    struct Ids {
+        bg,
+        center,
+        cardinals[],
+        markers[],
+        labels[],
+    }
+}
+
+const WIDTH: f64 = 400.0;
+/// Angle between the middle and the edges of the compass, the markers further
+/// to the side aren't shown
+const HALF_SPAN: f32 = PI / 2.0;
+/// Markers closer to the middle than this show their name
+const NAME_SPAN: f32 = PI / 24.0;
+/// Tint of the places the active quests ask to reach
+const QUEST_COLOR: Color = Color::Rgba(1.0, 0.8, 0.25, 1.0);
+
+struct CompassMarker<'a> {
+    wpos: Vec2<f32>,
+    image: image::Id,
+    color: Color,
+    name: Option<Cow<'a, str>>,
+}
+
+/// Bar at the top of the screen showing the cardinal directions and in which
+/// direction the markers and quest objectives are, relative to the camera
+#[derive(WidgetCommon)]
+pub struct Compass<'a> {
+    client: &'a Client,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    /// Orientation of the camera
+    ori: Vec3<f32>,
+    location_markers: &'a MapMarkers,
+    opacity: f32,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Compass<'a> {
+    pub fn new(
+        client: &'a Client,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        ori: Vec3<f32>,
+        location_markers: &'a MapMarkers,
+        opacity: f32,
+    ) -> Self {
+        Self {
+            client,
+            imgs,
+            fonts,
+            ori,
+            location_markers,
+            opacity,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+
+    /// Angle from the camera to the direction, positive to the right and
+    /// between -PI and PI
+    fn relative_angle(&self, dir: Vec2<f32>) -> f32 {
+        let angle = dir.x.atan2(dir.y) - self.ori.x;
+        (angle + PI).rem_euclid(2.0 * PI) - PI
+    }
+
+    fn markers(&self) -> Vec<CompassMarker<'a>> {
+        let markers = self.location_markers;
+        let owned = markers.owned.map(|wpos| CompassMarker {
+            wpos: wpos.as_(),
+            image: self.imgs.location_marker,
+            color: color::WHITE,
+            name: None,
+        });
+        let group = markers.group.values().map(|wpos| CompassMarker {
+            wpos: wpos.as_(),
+            image: self.imgs.location_marker_group,
+            color: color::WHITE,
+            name: None,
+        });
+        let placed = markers.placed.iter().map(|placed| CompassMarker {
+            wpos: placed.marker.wpos.as_(),
+            image: self.imgs.location_marker,
+            color: PLACED_MARKER_COLOR,
+            name: Some(Cow::Borrowed(placed.marker.name.as_str())),
+        });
+        let group_shared = markers
+            .group_shared
+            .values()
+            .flatten()
+            .map(|marker| CompassMarker {
+                wpos: marker.wpos.as_(),
+                image: self.imgs.location_marker_group,
+                color: PLACED_MARKER_COLOR,
+                name: Some(Cow::Borrowed(marker.name.as_str())),
+            });
+        // Places to reach for the objectives of the active quests not done yet
+        let quest_logs = self.client.state().ecs().read_storage::<QuestLog>();
+        let quests: Vec<_> = quest_logs
+            .get(self.client.entity())
+            .into_iter()
+            .flat_map(|quest_log| &quest_log.active)
+            .flat_map(|quest| {
+                let title = QuestDef::load(&quest.id)
+                    .map_or_else(|_| quest.id.clone(), |def| def.read().title.clone());
+                quest.objectives.iter().zip(&quest.progress).filter_map(
+                    move |(objective, progress)| match objective {
+                        Objective::Reach { pos, .. } if *progress < objective.required() => {
+                            Some(CompassMarker {
+                                wpos: *pos,
+                                image: self.imgs.location_marker,
+                                color: QUEST_COLOR,
+                                name: Some(Cow::Owned(title.clone())),
+                            })
+                        },
+                        _ => None,
+                    },
+                )
+            })
+            .collect();
+        owned
+            .into_iter()
+            .chain(group)
+            .chain(placed)
+            .chain(group_shared)
+            .chain(quests)
+            .collect()
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+impl<'a> Widget for Compass<'a> {
+    type Event = ();
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Compass::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        let markers = self.markers();
+        let player_pos = match self.client.position() {
+            Some(pos) => pos,
+            None => return,
+        };
+
+        let cardinals = [
+            (Vec2::unit_y(), "N"),
+            (Vec2::unit_x(), "E"),
+            (-Vec2::unit_y(), "S"),
+            (-Vec2::unit_x(), "W"),
+        ];
+        if state.ids.cardinals.len() < cardinals.len() || state.ids.markers.len() < markers.len() {
+            state.update(|s| {
+                s.ids
+                    .cardinals
+                    .resize(cardinals.len(), &mut ui.widget_id_generator());
+                s.ids
+                    .markers
+                    .resize(markers.len(), &mut ui.widget_id_generator());
+                s.ids
+                    .labels
+                    .resize(markers.len(), &mut ui.widget_id_generator());
+            });
+        }
+
+        let opacity = self.opacity;
+        let fade = |color: Color| color.alpha(color.alpha() * opacity);
+        Rectangle::fill_with([WIDTH, 24.0], TEXT_BG.alpha(0.3 * opacity))
+            .mid_top_with_margin_on(ui.window, 14.0)
+            .set(state.ids.bg, ui);
+        Rectangle::fill_with([2.0, 24.0], TEXT_COLOR.alpha(0.5 * opacity))
+            .middle_of(state.ids.bg)
+            .set(state.ids.center, ui);
+
+        // Position along the compass, if it is on it
+        let x_of = |angle: f32| {
+            (angle.abs() <= HALF_SPAN).then(|| (angle / HALF_SPAN) as f64 * WIDTH / 2.0)
+        };
+
+        for ((dir, name), id) in cardinals.iter().zip(state.ids.cardinals.iter()) {
+            if let Some(x) = x_of(self.relative_angle(*dir)) {
+                Text::new(name)
+                    .x_y_position_relative_to(
+                        state.ids.bg,
+                        position::Relative::Scalar(x),
+                        position::Relative::Scalar(0.0),
+                    )
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(14))
+                    .color(fade(if *name == "N" {
+                        Color::Rgba(0.75, 0.0, 0.0, 1.0)
+                    } else {
+                        TEXT_COLOR
+                    }))
+                    .set(*id, ui);
+            }
+        }
+
+        for (i, marker) in markers.iter().enumerate() {
+            let offset = marker.wpos - player_pos.xy();
+            let angle = self.relative_angle(offset);
+            let x = match x_of(angle) {
+                Some(x) => x,
+                None => continue,
+            };
+            Image::new(marker.image)
+                .x_y_position_relative_to(
+                    state.ids.bg,
+                    position::Relative::Scalar(x),
+                    position::Relative::Scalar(4.0),
+                )
+                .w_h(14.0, 14.0)
+                .color(Some(fade(marker.color)))
+                .set(state.ids.markers[i], ui);
+            let distance = offset.magnitude().round() as u32;
+            let label = match &marker.name {
+                Some(name) if angle.abs() <= NAME_SPAN => format!("{name} {distance}m"),
+                _ => format!("{distance}m"),
+            };
+            Text::new(&label)
+                .mid_bottom_with_margin_on(state.ids.markers[i], -12.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(10))
+                .color(fade(TEXT_COLOR))
+                .set(state.ids.labels[i], ui);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Arcs around the crosshair pointing to where the hits the character took
+//! came from
+
+use conrod_core::{
+    widget::{self, Rectangle},
+    widget_ids, Color, Positionable, Widget, WidgetCommon,
+};
+use std::f32::consts::PI;
+use vek::*;
+
+/// Seconds an arc stays on screen
+const LIFETIME: f32 = 1.5;
+/// Most arcs shown at once, the oldest ones make way for new hits
+const MAX_INDICATORS: usize = 8;
+/// Distance of the arcs from the middle of the screen
+const RADIUS: f32 = 160.0;
+/// Dots each arc is drawn with
+const ARC_DOTS: usize = 7;
+/// Angle covered by each arc
+const ARC_SPAN: f32 = PI / 10.0;
+const COLOR: Color = Color::Rgba(0.85, 0.1, 0.05, 1.0);
+
+widget_ids! {
+    struct Ids {
+        dots[],
+    }
+}
+
+struct DamageDirection {
+    /// Where the attacker stood when the hit landed
+    from: Vec2<f32>,
+    timer: f32,
+}
+
+/// Recent hits the character took and where they came from
+#[derive(Default)]
+pub struct DamageDirections {
+    directions: Vec<DamageDirection>,
+}
+
+impl DamageDirections {
+    pub fn hit_from(&mut self, from: Vec2<f32>) {
+        if self.directions.len() >= MAX_INDICATORS {
+            self.directions.remove(0);
+        }
+        self.directions.push(DamageDirection {
+            from,
+            timer: LIFETIME,
+        });
+    }
+
+    pub fn maintain(&mut self, dt: f32) {
+        for direction in &mut self.directions {
+            direction.timer -= dt;
+        }
+        self.directions.retain(|direction| direction.timer > 0.0);
+    }
+}
+
+#[derive(WidgetCommon)]
+pub struct DamageIndicators<'a> {
+    directions: &'a DamageDirections,
+    player_pos: Vec2<f32>,
+    /// Yaw of the camera
+    yaw: f32,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> DamageIndicators<'a> {
+    pub fn new(directions: &'a DamageDirections, player_pos: Vec2<f32>, yaw: f32) -> Self {
+        Self {
+            directions,
+            player_pos,
+            yaw,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+impl<'a> Widget for DamageIndicators<'a> {
+    type Event = ();
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("DamageIndicators::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        let dot_count = self.directions.directions.len() * ARC_DOTS;
+        if state.ids.dots.len() < dot_count {
+            state.update(|s| s.ids.dots.resize(dot_count, &mut ui.widget_id_generator()));
+        }
+
+        let dots = self.directions.directions.iter().flat_map(|direction| {
+            let dir = direction.from - self.player_pos;
+            // Angle from the camera, the top of the screen being in front of it
+            let angle = dir.x.atan2(dir.y) - self.yaw;
+            let fade = (direction.timer / LIFETIME).min(1.0);
+            (0..ARC_DOTS).map(move |i| {
+                let t = i as f32 / (ARC_DOTS - 1) as f32 - 0.5;
+                // The middle of the arc is the most visible
+                let alpha = fade * (1.0 - t.abs());
+                (angle + t * ARC_SPAN, alpha)
+            })
+        });
+        for ((angle, alpha), id) in dots.zip(state.ids.dots.iter()) {
+            let pos = Vec2::new(angle.sin(), angle.cos()) * RADIUS;
+            Rectangle::fill_with([6.0, 6.0], COLOR.alpha(alpha))
+                .x_y_relative_to(ui.window, pos.x as f64, pos.y as f64)
+                .graphics_for(ui.window)
+                .set(*id, ui);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use super::{img_ids::Imgs, TEXT_COLOR, UI_HIGHLIGHT_0};
+use crate::ui::fonts::Fonts;
+use conrod_core::{
+    widget::{self, Button, Image, Text},
+    widget_ids, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+
+widget_ids! {
+    struct Ids {
+        top,
+        mid,
+        bot,
+        name,
+        choice_buttons[],
+    }
+}
+
+#[derive(WidgetCommon)]
+pub struct Dialogue<'a> {
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    npc_name: &'a str,
+    /// Localization keys of the choices sent by the server
+    choices: &'a [String],
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Dialogue<'a> {
+    pub fn new(
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        npc_name: &'a str,
+        choices: &'a [String],
+    ) -> Self {
+        Self {
+            imgs,
+            fonts,
+            localized_strings,
+            npc_name,
+            choices,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    Choose(usize),
+}
+
+impl<'a> Widget for Dialogue<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Dialogue::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut event = None;
+
+        if state.ids.choice_buttons.len() < self.choices.len() {
+            state.update(|s| {
+                s.ids
+                    .choice_buttons
+                    .resize(self.choices.len(), &mut ui.widget_id_generator())
+            });
+        }
+
+        // Window
+        Image::new(self.imgs.prompt_top)
+            .w_h(276.0, 24.0)
+            .mid_bottom_with_margin_on(ui.window, 320.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.top, ui);
+        Image::new(self.imgs.prompt_mid)
+            .w_h(276.0, 40.0 * self.choices.len() as f64 + 30.0)
+            .down_from(state.ids.top, 0.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.mid, ui);
+        Image::new(self.imgs.prompt_bot)
+            .w_h(276.0, 35.0)
+            .down_from(state.ids.mid, 0.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.bot, ui);
+
+        Text::new(self.npc_name)
+            .mid_top_with_margin_on(state.ids.mid, 0.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(18))
+            .color(TEXT_COLOR)
+            .set(state.ids.name, ui);
+
+        // Choices
+        for (i, key) in self.choices.iter().enumerate() {
+            if Button::image(self.imgs.button)
+                .w_h(210.0, 36.0)
+                .mid_top_with_margin_on(state.ids.mid, 30.0 + 40.0 * i as f64)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .label(&self.localized_strings.get_msg(key))
+                .label_y(conrod_core::position::Relative::Scalar(2.0))
+                .label_color(TEXT_COLOR)
+                .label_font_size(self.fonts.cyri.scale(16))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .set(state.ids.choice_buttons[i], ui)
+                .was_clicked()
+            {
+                event = Some(Event::Choose(i));
+            }
+        }
+
+        event
+    }
+}
@@ -0,0 +1,149 @@
+//! Parts of the world the character explored, kept with the character in the
+//! profile. The rest of the world map is covered by fog.
+
+use super::{Graphic, Ui};
+use ::image::{DynamicImage, Rgba, RgbaImage};
+use common::{terrain::TerrainChunkSize, vol::RectVolSize};
+use conrod_core::{image, position};
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use vek::*;
+
+/// Chunks along each side of the cells the world is explored by
+pub const CELL_CHUNKS: i32 = 4;
+/// Blocks around the character which are explored
+const REVEAL_RADIUS: f32 = 320.0;
+/// Colour of the fog over the cells which weren't explored yet
+const FOG_COLOR: [u8; 4] = [24, 20, 14, 230];
+
+/// Cells explored by a character
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExploredMap {
+    cells: HashSet<Vec2<i32>>,
+}
+
+impl ExploredMap {
+    fn cell(wpos: Vec2<f32>) -> Vec2<i32> {
+        wpos.map2(TerrainChunkSize::RECT_SIZE, |e, sz| {
+            (e / (sz as i32 * CELL_CHUNKS) as f32).floor() as i32
+        })
+    }
+
+    /// Explores the cells around `wpos`, returning the ones which weren't
+    /// explored yet
+    pub fn explore(&mut self, wpos: Vec2<f32>) -> Vec<Vec2<i32>> {
+        let cell_size = TerrainChunkSize::RECT_SIZE.map(|e| (e as i32 * CELL_CHUNKS) as f32);
+        let radius = (REVEAL_RADIUS / cell_size.reduce_partial_min()).ceil() as i32;
+        let center = Self::cell(wpos);
+        let mut explored = Vec::new();
+        for x in -radius..=radius {
+            for y in -radius..=radius {
+                let cell = center + Vec2::new(x, y);
+                let cell_center = (cell.as_::<f32>() + 0.5) * cell_size;
+                if cell_center.distance(wpos) <= REVEAL_RADIUS && self.cells.insert(cell) {
+                    explored.push(cell);
+                }
+            }
+        }
+        explored
+    }
+
+    /// Adds cells explored elsewhere, like the ones reported by
+    /// [`FogOfWar::maintain`]
+    pub fn extend(&mut self, cells: impl IntoIterator<Item = Vec2<i32>>) {
+        self.cells.extend(cells);
+    }
+
+    pub fn is_explored(&self, wpos: Vec2<f32>) -> bool { self.cells.contains(&Self::cell(wpos)) }
+}
+
+/// Fog drawn over the world map, one pixel per cell
+pub struct FogOfWar {
+    pub explored: ExploredMap,
+    image_id: image::Id,
+    /// Cells along each side of the world
+    size: Vec2<u32>,
+}
+
+impl FogOfWar {
+    /// `world_size` is in chunks
+    pub fn new(ui: &mut Ui, explored: ExploredMap, world_size: Vec2<u32>) -> Self {
+        let size = world_size.map(|e| (e + CELL_CHUNKS as u32 - 1) / CELL_CHUNKS as u32);
+        let image = Self::render(&explored, size);
+        Self {
+            explored,
+            image_id: ui.add_graphic(image),
+            size,
+        }
+    }
+
+    fn render(explored: &ExploredMap, size: Vec2<u32>) -> Graphic {
+        let is_explored = |x: i32, y: i32| explored.cells.contains(&Vec2::new(x, y));
+        let image = RgbaImage::from_fn(size.x, size.y, |x, row| {
+            // The north of the world is at the top of the image
+            let (x, y) = (x as i32, (size.y - row - 1) as i32);
+            let [r, g, b, a] = FOG_COLOR;
+            if is_explored(x, y) {
+                Rgba([r, g, b, 0])
+            } else if [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .iter()
+                .any(|(dx, dy)| is_explored(x + dx, y + dy))
+            {
+                // Soften the edge of the fog
+                Rgba([r, g, b, a / 2])
+            } else {
+                Rgba(FOG_COLOR)
+            }
+        });
+        Graphic::Image(
+            Arc::new(DynamicImage::ImageRgba8(image)),
+            Some(Rgba::from([0.0, 0.0, 0.0, 0.0])),
+        )
+    }
+
+    /// Explores the world around the character, returning the cells the fog
+    /// lifted from
+    pub fn maintain(&mut self, wpos: Vec2<f32>, ui: &mut Ui) -> Vec<Vec2<i32>> {
+        let explored = self.explored.explore(wpos);
+        if !explored.is_empty() {
+            ui.replace_graphic(self.image_id, Self::render(&self.explored, self.size));
+        }
+        explored
+    }
+
+    pub fn image_id(&self) -> image::Id { self.image_id }
+
+    /// Part of the fog image matching `rect` of the world map image, which has
+    /// one pixel per chunk
+    pub fn source_rect(&self, rect: position::Rect) -> position::Rect {
+        let scale = 1.0 / CELL_CHUNKS as f64;
+        position::Rect::from_xy_dim([rect.x() * scale, rect.y() * scale], [
+            rect.w() * scale,
+            rect.h() * scale,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exploring_reveals_the_cells_around() {
+        let mut explored = ExploredMap::default();
+        let wpos = Vec2::new(1000.0, 1000.0);
+        assert!(!explored.is_explored(wpos));
+        let cells = explored.explore(wpos);
+        assert!(cells.contains(&ExploredMap::cell(wpos)));
+        assert!(explored.explore(wpos).is_empty());
+        assert!(explored.is_explored(wpos));
+
+        let mut saved = ExploredMap::default();
+        saved.extend(cells);
+        assert_eq!(saved, explored);
+        assert!(explored.is_explored(wpos + Vec2::new(REVEAL_RADIUS - 64.0, 0.0)));
+        assert!(!explored.is_explored(wpos + Vec2::new(REVEAL_RADIUS + 256.0, 0.0)));
+    }
+}
@@ -1,9 +1,8 @@
 use super::{
     cr_color,
     img_ids::{Imgs, ImgsRot},
-    Show, BLACK, BUFF_COLOR, DEBUFF_COLOR, ERROR_COLOR, GROUP_COLOR, HP_COLOR, KILL_COLOR,
-    LOW_HP_COLOR, QUALITY_EPIC, STAMINA_COLOR, TEXT_COLOR, TEXT_COLOR_GREY, UI_HIGHLIGHT_0,
-    UI_MAIN,
+    HealthColors, Show, BLACK, BUFF_COLOR, DEBUFF_COLOR, ERROR_COLOR, GROUP_COLOR, KILL_COLOR,
+    QUALITY_EPIC, STAMINA_COLOR, TEXT_COLOR, TEXT_COLOR_GREY, UI_HIGHLIGHT_0, UI_MAIN,
 };
 
 use crate::{
@@ -142,8 +141,6 @@ impl<'a> Widget for Group<'a> {
     fn style(&self) -> Self::Style {}
 
     //TODO: Disband groups when there's only one member in them
-    //TODO: Always send health, energy, level and position of group members to the
-    // client
     #[allow(clippy::blocks_in_if_conditions)] // TODO: Pending review in #587
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         common_base::prof_span!("Group::update");
@@ -153,6 +150,8 @@ impl<'a> Widget for Group<'a> {
         let key_layout = &self.global_state.window.key_layout;
         let buff_ani = ((self.pulse * 4.0/* speed factor */).cos() * 0.5 + 0.8) + 0.5; //Animation timer
         let debug_on = self.global_state.settings.interface.toggle_debug;
+        let health_colors =
+            HealthColors::of(self.global_state.settings.accessibility.color_palette);
         let offset = if debug_on { 270.0 } else { 0.0 };
         let buffs_tooltip = Tooltip::new({
             // Edge images [t, b, r, l]
@@ -355,6 +354,7 @@ impl<'a> Widget for Group<'a> {
             let uid_allocator = client_state.ecs().read_resource::<UidAllocator>();
             let bodies = client_state.ecs().read_storage::<common::comp::Body>();
             let poises = client_state.ecs().read_storage::<common::comp::Poise>();
+            let member_status = self.client.group_member_status();
 
             // Keep track of the total number of widget ids we are using for buffs
             let mut total_buff_count = 0;
@@ -395,11 +395,11 @@ impl<'a> Widget for Group<'a> {
                         10.0 + x as f64 * 180.0,
                     );
                     let hp_ani = (self.pulse * 4.0/* speed factor */).cos() * 0.5 + 0.8; //Animation timer
-                    let crit_hp_color: Color = Color::Rgba(0.79, 0.19, 0.17, hp_ani);
+                    let crit_hp_color: Color = health_colors.critical_hp.alpha(hp_ani);
                     let health_col = match (health_perc * 100.0) as u8 {
                         0..=20 => crit_hp_color,
-                        21..=40 => LOW_HP_COLOR,
-                        _ => HP_COLOR,
+                        21..=40 => health_colors.low_hp,
+                        _ => health_colors.hp,
                     };
                     // Don't show panel for the player!
                     // Panel BG
@@ -618,6 +618,74 @@ impl<'a> Widget for Group<'a> {
                             .color(TEXT_COLOR)
                             .set(state.ids.dead_txt[i], ui);
                     }
+                } else if let Some(status) = member_status.get(&uid) {
+                    // Member outside of the view distance, only show what the server sent us
+                    let x = if debug_on { i / 8 } else { i / 12 };
+                    let y = if debug_on { i % 8 } else { i % 12 };
+                    Image::new(self.imgs.member_bg)
+                        .top_left_with_margins_on(
+                            ui.window,
+                            50.0 + offset + y as f64 * 77.0,
+                            10.0 + x as f64 * 180.0,
+                        )
+                        .w_h(152.0, 36.0)
+                        .color(if is_leader {
+                            Some(ERROR_COLOR)
+                        } else {
+                            Some(TEXT_COLOR)
+                        })
+                        .set(state.ids.member_panels_bg[i], ui);
+                    // Health
+                    Image::new(self.imgs.bar_content)
+                        .w_h(148.0 * f64::from(status.health_fraction), 22.0)
+                        .color(Some(if status.health_fraction > 0.4 {
+                            health_colors.hp
+                        } else {
+                            health_colors.low_hp
+                        }))
+                        .top_left_with_margins_on(state.ids.member_panels_bg[i], 2.0, 2.0)
+                        .set(state.ids.member_health[i], ui);
+                    Text::new(&self.localized_strings.get_msg(if status.is_dead {
+                        "hud-group-dead"
+                    } else {
+                        "hud-group-out_of_range"
+                    }))
+                    .mid_top_with_margin_on(state.ids.member_panels_bg[i], 3.0)
+                    .font_size(16)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .color(if status.is_dead {
+                        KILL_COLOR
+                    } else {
+                        Color::Rgba(1.0, 1.0, 1.0, 0.5)
+                    })
+                    .set(state.ids.dead_txt[i], ui);
+                    // Panel Frame
+                    Image::new(self.imgs.member_frame)
+                        .w_h(152.0, 36.0)
+                        .middle_of(state.ids.member_panels_bg[i])
+                        .color(Some(UI_HIGHLIGHT_0))
+                        .set(state.ids.member_panels_frame[i], ui);
+                    // Panel Text
+                    Text::new(&status.name)
+                        .top_left_with_margins_on(state.ids.member_panels_frame[i], -22.0, 22.0)
+                        .font_size(20)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .color(BLACK)
+                        .w(300.0) // limit name length display
+                        .set(state.ids.member_panels_txt_bg[i], ui);
+                    Text::new(&status.name)
+                        .bottom_left_with_margins_on(state.ids.member_panels_txt_bg[i], 2.0, 2.0)
+                        .font_size(20)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .color(if is_leader { ERROR_COLOR } else { GROUP_COLOR })
+                        .w(300.0) // limit name length display
+                        .set(state.ids.member_panels_txt[i], ui);
+                    // Energy
+                    Image::new(self.imgs.bar_content)
+                        .w_h(100.0 * f64::from(status.energy_fraction), 8.0)
+                        .color(Some(STAMINA_COLOR))
+                        .top_left_with_margins_on(state.ids.member_panels_bg[i], 26.0, 2.0)
+                        .set(state.ids.member_energy[i], ui);
                 }
             }
 
@@ -798,6 +866,12 @@ impl<'a> Widget for Group<'a> {
                         "name" => name,
                     },
                 ),
+                InviteKind::Guild => self.localized_strings.get_msg_ctx(
+                    "hud-group-invite_to_guild",
+                    &i18n::fluent_args! {
+                        "name" => &name,
+                    },
+                ),
                 InviteKind::Trade => self.localized_strings.get_msg_ctx(
                     "hud-group-invite_to_trade",
                     &i18n::fluent_args! {
@@ -0,0 +1,368 @@
+use super::{
+    img_ids::Imgs, GUILD_COLOR, TEXT_COLOR, TEXT_COLOR_3, TEXT_GRAY_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
+};
+use crate::ui::fonts::Fonts;
+use client::{self, Client};
+use common::{comp::guild::GuildRank, uid::Uid};
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Scrollbar, Text},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use std::time::Instant;
+
+widget_ids! {
+    pub struct Ids {
+        frame,
+        close,
+        title_align,
+        title,
+        bg,
+        icon,
+        scrollbar,
+        members_align,
+        member_names[],
+        member_ranks[],
+        no_guild_txt,
+        members_txt,
+        members_no,
+        promote_button,
+        demote_button,
+        kick_button,
+        leave_button,
+        invite_button,
+    }
+}
+
+pub struct State {
+    ids: Ids,
+    /// Name of the selected member, members are referred to by name since
+    /// offline ones have no uid
+    selected: Option<String>,
+}
+
+#[derive(WidgetCommon)]
+pub struct Guild<'a> {
+    client: &'a Client,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    selected_entity: Option<(specs::Entity, Instant)>,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Guild<'a> {
+    pub fn new(
+        client: &'a Client,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        selected_entity: Option<(specs::Entity, Instant)>,
+    ) -> Self {
+        Self {
+            client,
+            imgs,
+            fonts,
+            localized_strings,
+            selected_entity,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub enum Event {
+    Close,
+    Invite(Uid),
+    /// Chat command managing the guild along with its arguments
+    Command(String, Vec<String>),
+}
+
+impl<'a> Widget for Guild<'a> {
+    type Event = Vec<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        Self::State {
+            ids: Ids::new(id_gen),
+            selected: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Guild::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut events = Vec::new();
+
+        // Window BG
+        Image::new(self.imgs.social_bg_on)
+            .bottom_left_with_margins_on(ui.window, 308.0, 25.0)
+            .color(Some(UI_MAIN))
+            .w_h(280.0, 460.0)
+            .set(state.ids.bg, ui);
+        // Window frame
+        Image::new(self.imgs.social_frame_on)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .w_h(280.0, 460.0)
+            .set(state.ids.frame, ui);
+        // Icon
+        Image::new(self.imgs.social)
+            .w_h(30.0, 30.0)
+            .top_left_with_margins_on(state.ids.frame, 6.0, 6.0)
+            .set(state.ids.icon, ui);
+        // X-Button
+        if Button::image(self.imgs.close_button)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.ids.frame, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            events.push(Event::Close);
+        }
+
+        // Title
+        Rectangle::fill_with([212.0, 42.0], color::TRANSPARENT)
+            .top_left_with_margins_on(state.ids.frame, 2.0, 44.0)
+            .set(state.ids.title_align, ui);
+        let title = self.client.guild().map_or_else(
+            || self.localized_strings.get_msg("hud-guild"),
+            |guild| guild.name.clone().into(),
+        );
+        Text::new(&title)
+            .middle_of(state.ids.title_align)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(GUILD_COLOR)
+            .set(state.ids.title, ui);
+
+        let guild = match self.client.guild() {
+            Some(guild) => guild,
+            None => {
+                Text::new(&self.localized_strings.get_msg("hud-guild-none"))
+                    .mid_top_with_margin_on(state.ids.frame, 74.0)
+                    .w(250.0)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(14))
+                    .color(TEXT_COLOR)
+                    .set(state.ids.no_guild_txt, ui);
+                return events;
+            },
+        };
+
+        // Forget the selection once the member is gone
+        if state.selected.as_ref().map_or(false, |selected| {
+            guild.members.iter().all(|member| &member.name != selected)
+        }) {
+            state.update(|s| s.selected = None);
+        }
+
+        // Content Alignment
+        Rectangle::fill_with([270.0, 296.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.ids.frame, 74.0)
+            .scroll_kids_vertically()
+            .set(state.ids.members_align, ui);
+        Scrollbar::y_axis(state.ids.members_align)
+            .thickness(4.0)
+            .color(Color::Rgba(0.79, 1.09, 1.09, 0.0))
+            .set(state.ids.scrollbar, ui);
+
+        // Members Text
+        let online_count = guild.members.iter().filter(|member| member.online).count();
+        Text::new(&self.localized_strings.get_msg("hud-guild-members"))
+            .top_left_with_margins_on(state.ids.frame, 52.0, 10.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.members_txt, ui);
+        Text::new(&format!("{}/{}", online_count, guild.members.len()))
+            .right_from(state.ids.members_txt, 5.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.members_no, ui);
+        // Adjust widget_id struct vec length to member count
+        let member_count = guild.members.len();
+        if state.ids.member_names.len() < member_count {
+            state.update(|s| {
+                s.ids
+                    .member_names
+                    .resize(member_count, &mut ui.widget_id_generator());
+                s.ids
+                    .member_ranks
+                    .resize(member_count, &mut ui.widget_id_generator());
+            })
+        };
+
+        // Members are sorted by rank by the server
+        for (i, member) in guild.members.iter().enumerate() {
+            let selected = state.selected.as_ref() == Some(&member.name);
+            let button = Button::image(if !selected {
+                self.imgs.nothing
+            } else {
+                self.imgs.selection
+            })
+            .hover_image(if selected {
+                self.imgs.selection
+            } else {
+                self.imgs.selection_hover
+            })
+            .press_image(if selected {
+                self.imgs.selection
+            } else {
+                self.imgs.selection_press
+            })
+            .w_h(260.0, 20.0)
+            .image_color(color::rgba(1.0, 0.82, 0.27, 1.0));
+            let button = if i == 0 {
+                button.mid_top_with_margin_on(state.ids.members_align, 1.0)
+            } else {
+                button.down_from(state.ids.member_names[i - 1], 1.0)
+            };
+            let text_color = if member.online {
+                TEXT_COLOR
+            } else {
+                TEXT_GRAY_COLOR
+            };
+            if button
+                .label(&member.name)
+                .label_font_size(self.fonts.cyri.scale(14))
+                .label_y(conrod_core::position::Relative::Scalar(1.0))
+                .label_x(conrod_core::position::Relative::Place(
+                    conrod_core::position::Place::Start(Some(5.0)),
+                ))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_color(text_color)
+                .set(state.ids.member_names[i], ui)
+                .was_clicked()
+            {
+                let name = member.name.clone();
+                state.update(|s| s.selected = Some(name));
+            }
+            Text::new(&self.localized_strings.get_msg(member.rank.i18n_key()))
+                .mid_right_with_margin_on(state.ids.member_names[i], 5.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(12))
+                .color(text_color)
+                .graphics_for(state.ids.member_names[i])
+                .set(state.ids.member_ranks[i], ui);
+        }
+
+        // Managing the selected member depends on both ranks
+        let selected = state
+            .selected
+            .as_ref()
+            .and_then(|selected| guild.members.iter().find(|member| &member.name == selected));
+        let can_change_rank = selected.map_or(false, |member| {
+            guild.rank.can_change_ranks() && member.rank < guild.rank
+        });
+        let can_promote = can_change_rank;
+        let can_demote =
+            can_change_rank && selected.map_or(false, |member| member.rank != GuildRank::Member);
+        let can_kick = selected.map_or(false, |member| guild.rank.can_kick(member.rank));
+        let invite_target = guild
+            .rank
+            .can_invite()
+            .then(|| {
+                self.selected_entity
+                    .and_then(|s| self.client.state().read_component_copied::<Uid>(s.0))
+                    .filter(|uid| {
+                        self.client
+                            .player_list()
+                            .get(uid)
+                            .map_or(false, |player| player.character.is_some())
+                    })
+            })
+            .flatten();
+
+        let action_button = |enabled: bool, label: &str| {
+            Button::image(self.imgs.button)
+                .hover_image(if enabled {
+                    self.imgs.button_hover
+                } else {
+                    self.imgs.button
+                })
+                .press_image(if enabled {
+                    self.imgs.button_press
+                } else {
+                    self.imgs.button
+                })
+                .label(label)
+                .label_y(conrod_core::position::Relative::Scalar(3.0))
+                .label_color(if enabled { TEXT_COLOR } else { TEXT_COLOR_3 })
+                .image_color(if enabled { TEXT_COLOR } else { TEXT_COLOR_3 })
+                .label_font_size(self.fonts.cyri.scale(15))
+                .label_font_id(self.fonts.cyri.conrod_id)
+        };
+        let selected_name = || selected.map(|member| member.name.clone());
+
+        if action_button(
+            can_promote,
+            &self.localized_strings.get_msg("hud-guild-promote"),
+        )
+        .w_h(84.0, 26.0)
+        .bottom_left_with_margins_on(state.ids.frame, 44.0, 7.0)
+        .set(state.ids.promote_button, ui)
+        .was_clicked()
+            && can_promote
+        {
+            events.extend(
+                selected_name().map(|name| Event::Command("guild_promote".into(), vec![name])),
+            );
+        }
+        if action_button(
+            can_demote,
+            &self.localized_strings.get_msg("hud-guild-demote"),
+        )
+        .w_h(84.0, 26.0)
+        .right_from(state.ids.promote_button, 6.0)
+        .set(state.ids.demote_button, ui)
+        .was_clicked()
+            && can_demote
+        {
+            events.extend(
+                selected_name().map(|name| Event::Command("guild_demote".into(), vec![name])),
+            );
+        }
+        if action_button(can_kick, &self.localized_strings.get_msg("hud-guild-kick"))
+            .w_h(84.0, 26.0)
+            .right_from(state.ids.demote_button, 6.0)
+            .set(state.ids.kick_button, ui)
+            .was_clicked()
+            && can_kick
+        {
+            events.extend(
+                selected_name().map(|name| Event::Command("guild_kick".into(), vec![name])),
+            );
+            state.update(|s| s.selected = None);
+        }
+        if action_button(
+            invite_target.is_some(),
+            &self.localized_strings.get_msg("hud-group-invite"),
+        )
+        .w_h(84.0, 26.0)
+        .bottom_right_with_margins_on(state.ids.frame, 9.0, 7.0)
+        .set(state.ids.invite_button, ui)
+        .was_clicked()
+        {
+            events.extend(invite_target.map(Event::Invite));
+        }
+        if action_button(true, &self.localized_strings.get_msg("hud-guild-leave"))
+            .w_h(84.0, 26.0)
+            .left_from(state.ids.invite_button, 6.0)
+            .set(state.ids.leave_button, ui)
+            .was_clicked()
+        {
+            events.push(Event::Command("guild_leave".into(), Vec::new()));
+        }
+
+        events
+    }
+}
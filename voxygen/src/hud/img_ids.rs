@@ -608,6 +608,20 @@ image_ids! {
         dark_bubble_tail: "voxygen.element.ui.generic.frames.bubble_dark.tail",
         dark_bubble_icon_frame: "voxygen.element.ui.generic.frames.bubble_dark.icon_frame",
 
+        // Speech bubble portraits
+        portrait_danari_f: "voxygen.element.ui.char_select.portraits.danari_f",
+        portrait_danari_m: "voxygen.element.ui.char_select.portraits.danari_m",
+        portrait_draugr_f: "voxygen.element.ui.char_select.portraits.ud_f",
+        portrait_draugr_m: "voxygen.element.ui.char_select.portraits.ud_m",
+        portrait_dwarf_f: "voxygen.element.ui.char_select.portraits.dwarf_f",
+        portrait_dwarf_m: "voxygen.element.ui.char_select.portraits.dwarf_m",
+        portrait_elf_f: "voxygen.element.ui.char_select.portraits.elf_f",
+        portrait_elf_m: "voxygen.element.ui.char_select.portraits.elf_m",
+        portrait_human_f: "voxygen.element.ui.char_select.portraits.human_f",
+        portrait_human_m: "voxygen.element.ui.char_select.portraits.human_m",
+        portrait_orc_f: "voxygen.element.ui.char_select.portraits.orc_f",
+        portrait_orc_m: "voxygen.element.ui.char_select.portraits.orc_m",
+
 
         // Chat icons
         chat_faction_small: "voxygen.element.ui.chat.icons.faction_small",
@@ -1,5 +1,7 @@
 use super::{
+    exploration::FogOfWar,
     img_ids::{Imgs, ImgsRot},
+    placed_markers::PLACED_MARKER_COLOR,
     MapMarkers, QUALITY_COMMON, QUALITY_EPIC, QUALITY_HIGH, QUALITY_LOW, QUALITY_MODERATE, TEXT_BG,
     TEXT_BLUE_COLOR, TEXT_COLOR, TEXT_GRAY_COLOR, TEXT_VELORITE, UI_HIGHLIGHT_0, UI_MAIN,
 };
@@ -11,13 +13,20 @@ use crate::{
     GlobalState,
 };
 use client::{self, Client, SiteInfoRich};
-use common::{comp, comp::group::Role, terrain::TerrainChunkSize, trade::Good, vol::RectVolSize};
+use common::{
+    comp,
+    comp::{group::Role, portal::PortalId},
+    terrain::TerrainChunkSize,
+    trade::Good,
+    uid::Uid,
+    vol::RectVolSize,
+};
 use common_net::msg::world_msg::{PoiKind, SiteId, SiteKind};
 use conrod_core::{
     color,
     input::MouseButton as ConrodMouseButton,
     position,
-    widget::{self, Button, Image, Rectangle, Text},
+    widget::{self, Button, Image, Rectangle, Text, TextEdit},
     widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, UiCell, Widget, WidgetCommon,
 };
 use i18n::Localization;
@@ -39,6 +48,7 @@ widget_ids! {
         indicator,
         indicator_overlay,
         map_layers[],
+        map_fog,
         map_title,
         qlog_title,
         zoom_slider,
@@ -53,6 +63,16 @@ widget_ids! {
         member_height_indicators[],
         location_marker,
         location_marker_group[],
+        placed_markers[],
+        group_shared_markers[],
+        portal_icons[],
+        markers_title,
+        marker_name_bg,
+        marker_name_input,
+        place_marker_btn,
+        marker_list_shares[],
+        marker_list_names[],
+        marker_list_removes[],
         map_settings_align,
         show_towns_img,
         show_towns_box,
@@ -75,9 +95,15 @@ widget_ids! {
         show_peaks_img,
         show_peaks_box,
         show_peaks_text,
+        show_portals_img,
+        show_portals_box,
+        show_portals_text,
         show_biomes_img,
         show_biomes_box,
         show_biomes_text,
+        show_entities_img,
+        show_entities_box,
+        show_entities_text,
         show_voxel_map_img,
         show_voxel_map_box,
         show_voxel_map_text,
@@ -100,6 +126,8 @@ widget_ids! {
 }
 
 const SHOW_ECONOMY: bool = false; // turn this display off (for 0.9) until we have an improved look
+/// Most named markers listed next to the map, the ones after still show on it
+const MAX_LISTED_MARKERS: usize = 24;
 
 #[derive(WidgetCommon)]
 pub struct Map<'a> {
@@ -116,6 +144,9 @@ pub struct Map<'a> {
     tooltip_manager: &'a mut TooltipManager,
     location_markers: &'a MapMarkers,
     map_drag: Vec2<f64>,
+    /// Portal the map was opened from to pick a destination
+    travel_from: Option<PortalId>,
+    fog_of_war: &'a FogOfWar,
 }
 impl<'a> Map<'a> {
     pub fn new(
@@ -130,6 +161,8 @@ impl<'a> Map<'a> {
         tooltip_manager: &'a mut TooltipManager,
         location_markers: &'a MapMarkers,
         map_drag: Vec2<f64>,
+        travel_from: Option<PortalId>,
+        fog_of_war: &'a FogOfWar,
     ) -> Self {
         Self {
             imgs,
@@ -144,12 +177,16 @@ impl<'a> Map<'a> {
             tooltip_manager,
             location_markers,
             map_drag,
+            travel_from,
+            fog_of_war,
         }
     }
 }
 
 pub struct State {
     ids: Ids,
+    /// Name typed for the next named marker
+    marker_name: String,
 }
 
 pub enum Event {
@@ -159,6 +196,11 @@ pub enum Event {
     SetLocationMarker(Vec2<i32>),
     MapDrag(Vec2<f64>),
     RemoveMarker,
+    /// Turn the waypoint into a named marker
+    PlaceMarker(String),
+    RemovePlacedMarker(usize),
+    TogglePlacedMarkerShared(usize),
+    UsePortal(PortalId),
 }
 
 fn get_site_economy(site_rich: &SiteInfoRich) -> String {
@@ -202,6 +244,7 @@ impl<'a> Widget for Map<'a> {
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             ids: Ids::new(id_gen),
+            marker_name: String::new(),
         }
     }
 
@@ -218,8 +261,10 @@ impl<'a> Widget for Map<'a> {
         let show_caves = self.global_state.settings.interface.map_show_caves;
         let show_trees = self.global_state.settings.interface.map_show_trees;
         let show_peaks = self.global_state.settings.interface.map_show_peaks;
+        let show_portals = self.global_state.settings.interface.map_show_portals;
         let show_biomes = self.global_state.settings.interface.map_show_biomes;
         let show_voxel_map = self.global_state.settings.interface.map_show_voxel_map;
+        let show_entities = self.global_state.settings.interface.minimap_show_entities;
         let show_topo_map = self.global_state.settings.interface.map_show_topo_map;
         let location_marker_binding = self
             .global_state
@@ -476,6 +521,151 @@ impl<'a> Widget for Map<'a> {
             }
         }
 
+        // Fog over the parts of the world which weren't explored yet
+        Image::new(self.fog_of_war.image_id())
+            .mid_top_with_margin_on(state.ids.map_align, 10.0)
+            .w_h(map_size.x, map_size.y)
+            .parent(state.ids.bg)
+            .source_rectangle(self.fog_of_war.source_rect(rect_src))
+            .graphics_for(state.ids.map_layers[0])
+            .set(state.ids.map_fog, ui);
+
+        // Named markers
+        Text::new(&i18n.get_msg("hud-map-markers"))
+            .mid_top_with_margin_on(state.ids.qlog_align, 50.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(16))
+            .color(TEXT_COLOR)
+            .set(state.ids.markers_title, ui);
+        Rectangle::fill_with([212.0, 24.0], color::rgba(0.0, 0.0, 0.0, 0.7))
+            .down_from(state.ids.markers_title, 10.0)
+            .align_middle_x_of(state.ids.qlog_align)
+            .set(state.ids.marker_name_bg, ui);
+        if let Some(name) = TextEdit::new(&state.marker_name)
+            .mid_left_with_margin_on(state.ids.marker_name_bg, 5.0)
+            .w_h(202.0, 20.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.marker_name_input, ui)
+        {
+            state.update(|s| {
+                s.marker_name = name.chars().take(comp::MAX_MAP_MARKER_NAME_LEN).collect()
+            });
+        }
+        let has_waypoint = self.location_markers.owned.is_some();
+        if Button::image(self.imgs.button)
+            .w_h(212.0, 28.0)
+            .down_from(state.ids.marker_name_bg, 5.0)
+            .hover_image(if has_waypoint {
+                self.imgs.button_hover
+            } else {
+                self.imgs.button
+            })
+            .press_image(if has_waypoint {
+                self.imgs.button_press
+            } else {
+                self.imgs.button
+            })
+            .label(&i18n.get_msg("hud-map-name_waypoint"))
+            .label_y(position::Relative::Scalar(1.0))
+            .label_color(if has_waypoint {
+                TEXT_COLOR
+            } else {
+                TEXT_GRAY_COLOR
+            })
+            .image_color(if has_waypoint {
+                TEXT_COLOR
+            } else {
+                TEXT_GRAY_COLOR
+            })
+            .label_font_size(self.fonts.cyri.scale(12))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .set(state.ids.place_marker_btn, ui)
+            .was_clicked()
+            && has_waypoint
+        {
+            events.push(Event::PlaceMarker(state.marker_name.clone()));
+            state.update(|s| s.marker_name.clear());
+        }
+        let listed_markers = self.location_markers.placed.len().min(MAX_LISTED_MARKERS);
+        if state.ids.marker_list_names.len() < listed_markers {
+            state.update(|s| {
+                let gen = &mut ui.widget_id_generator();
+                s.ids.marker_list_shares.resize(listed_markers, gen);
+                s.ids.marker_list_names.resize(listed_markers, gen);
+                s.ids.marker_list_removes.resize(listed_markers, gen);
+            });
+        }
+        for (i, placed) in self
+            .location_markers
+            .placed
+            .iter()
+            .take(MAX_LISTED_MARKERS)
+            .enumerate()
+        {
+            let above = match i {
+                0 => state.ids.place_marker_btn,
+                _ => state.ids.marker_list_shares[i - 1],
+            };
+            if Button::image(if placed.shared {
+                self.imgs.checkbox_checked
+            } else {
+                self.imgs.checkbox
+            })
+            .w_h(18.0, 18.0)
+            .hover_image(if placed.shared {
+                self.imgs.checkbox_checked_mo
+            } else {
+                self.imgs.checkbox_mo
+            })
+            .press_image(if placed.shared {
+                self.imgs.checkbox_checked
+            } else {
+                self.imgs.checkbox_press
+            })
+            .down_from(above, 8.0)
+            .align_left_of(state.ids.place_marker_btn)
+            .with_tooltip(
+                self.tooltip_manager,
+                &i18n.get_msg("hud-map-share_marker"),
+                &i18n.get_msg("hud-map-share_marker_desc"),
+                &site_tooltip,
+                TEXT_COLOR,
+            )
+            .set(state.ids.marker_list_shares[i], ui)
+            .was_clicked()
+            {
+                events.push(Event::TogglePlacedMarkerShared(i));
+            }
+            if Button::image(self.imgs.nothing)
+                .w_h(160.0, 18.0)
+                .right_from(state.ids.marker_list_shares[i], 6.0)
+                .label(&placed.marker.name)
+                .label_x(position::Relative::Scalar(0.0))
+                .label_color(TEXT_COLOR)
+                .label_font_size(self.fonts.cyri.scale(14))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .set(state.ids.marker_list_names[i], ui)
+                .was_clicked()
+            {
+                // Center the map on the marker
+                let offset = (player_pos.xy() - placed.marker.wpos.as_())
+                    .map2(TerrainChunkSize::RECT_SIZE, |e, sz| e as f64 / sz as f64);
+                events.push(Event::MapDrag(offset));
+            }
+            if Button::image(self.imgs.close_btn)
+                .w_h(16.0, 16.0)
+                .hover_image(self.imgs.close_btn_hover)
+                .press_image(self.imgs.close_btn_press)
+                .right_from(state.ids.marker_list_names[i], 6.0)
+                .set(state.ids.marker_list_removes[i], ui)
+                .was_clicked()
+            {
+                events.push(Event::RemovePlacedMarker(i));
+            }
+        }
+
         // Icon settings
         // Alignment
         Rectangle::fill_with([150.0, 200.0], color::TRANSPARENT)
@@ -754,12 +944,82 @@ impl<'a> Widget for Map<'a> {
             .graphics_for(state.ids.show_peaks_box)
             .color(TEXT_COLOR)
             .set(state.ids.show_peaks_text, ui);
+        // Portals
+        Image::new(self.imgs.mmap_site_sea_chapel)
+            .down_from(state.ids.show_peaks_img, 10.0)
+            .w_h(20.0, 20.0)
+            .color(Some(TEXT_VELORITE))
+            .set(state.ids.show_portals_img, ui);
+        if Button::image(if show_portals {
+            self.imgs.checkbox_checked
+        } else {
+            self.imgs.checkbox
+        })
+        .w_h(18.0, 18.0)
+        .hover_image(if show_portals {
+            self.imgs.checkbox_checked_mo
+        } else {
+            self.imgs.checkbox_mo
+        })
+        .press_image(if show_portals {
+            self.imgs.checkbox_checked
+        } else {
+            self.imgs.checkbox_press
+        })
+        .right_from(state.ids.show_portals_img, 10.0)
+        .set(state.ids.show_portals_box, ui)
+        .was_clicked()
+        {
+            events.push(Event::SettingsChange(MapShowPortals(!show_portals)));
+        }
+        Text::new(&i18n.get_msg("hud-map-portals"))
+            .right_from(state.ids.show_portals_box, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .graphics_for(state.ids.show_portals_box)
+            .color(TEXT_COLOR)
+            .set(state.ids.show_portals_text, ui);
+        // Creatures and players on the minimap
+        Image::new(self.imgs.indicator_group)
+            .down_from(state.ids.show_portals_img, 10.0)
+            .w_h(20.0, 20.0)
+            .color(Some(TEXT_COLOR))
+            .set(state.ids.show_entities_img, ui);
+        if Button::image(if show_entities {
+            self.imgs.checkbox_checked
+        } else {
+            self.imgs.checkbox
+        })
+        .w_h(18.0, 18.0)
+        .hover_image(if show_entities {
+            self.imgs.checkbox_checked_mo
+        } else {
+            self.imgs.checkbox_mo
+        })
+        .press_image(if show_entities {
+            self.imgs.checkbox_checked
+        } else {
+            self.imgs.checkbox_press
+        })
+        .right_from(state.ids.show_entities_img, 10.0)
+        .set(state.ids.show_entities_box, ui)
+        .was_clicked()
+        {
+            events.push(Event::SettingsChange(MinimapShowEntities(!show_entities)));
+        }
+        Text::new(&i18n.get_msg("hud-map-minimap_entities"))
+            .right_from(state.ids.show_entities_box, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .graphics_for(state.ids.show_entities_box)
+            .color(TEXT_COLOR)
+            .set(state.ids.show_entities_text, ui);
         // Voxel map (TODO: enable this once Pfau approves the final UI, and once
         // there's a non-placeholder graphic for the checkbox)
         const EXPOSE_VOXEL_MAP_TOGGLE_IN_UI: bool = false;
         if EXPOSE_VOXEL_MAP_TOGGLE_IN_UI {
             Image::new(self.imgs.mmap_poi_peak)
-                .down_from(state.ids.show_peaks_img, 10.0)
+                .down_from(state.ids.show_entities_img, 10.0)
                 .w_h(20.0, 20.0)
                 .set(state.ids.show_voxel_map_img, ui);
             if Button::image(if show_voxel_map {
@@ -856,8 +1116,12 @@ impl<'a> Widget for Map<'a> {
                 }
             };
 
+        let explored = &self.fog_of_war.explored;
         for (i, site_rich) in self.client.sites().values().enumerate() {
             let site = &site_rich.site;
+            if !explored.is_explored(site.wpos.as_()) {
+                continue;
+            }
 
             let rside = zoom as f32 * 8.0 * 1.2;
 
@@ -1066,6 +1330,9 @@ impl<'a> Widget for Map<'a> {
             }
         }
         for (i, poi) in self.client.pois().iter().enumerate() {
+            if !explored.is_explored(poi.wpos.as_()) {
+                continue;
+            }
             // TODO: computation of text size to pass to wpos_to_rpos_fade, so it can
             // determine when it's going past the edge of the map screen
             let (rpos, fade) = match wpos_to_rpos_fade(
@@ -1164,6 +1431,80 @@ impl<'a> Widget for Map<'a> {
                 },
             }
         }
+        // Discovered portals, the ones reachable from the portal the map was opened
+        // from can be travelled to. They are always shown when travelling.
+        let discovered_portals = self
+            .client
+            .state()
+            .read_component_cloned::<comp::DiscoveredPortals>(self.client.entity())
+            .unwrap_or_default();
+        let destinations = self
+            .travel_from
+            .map(|from| discovered_portals.destinations(from))
+            .unwrap_or_default();
+        let mut portals = discovered_portals.0.iter().collect::<Vec<_>>();
+        portals.sort_by_key(|(id, _)| **id);
+        if state.ids.portal_icons.len() < portals.len() {
+            state.update(|state| {
+                state
+                    .ids
+                    .portal_icons
+                    .resize(portals.len(), &mut ui.widget_id_generator())
+            });
+        }
+        if show_portals || self.travel_from.is_some() {
+            let rside = zoom as f32 * 8.0 * 1.2;
+            for (i, (id, portal)) in portals.into_iter().enumerate() {
+                let (rpos, fade) = match wpos_to_rpos_fade(
+                    portal.pos.xy(),
+                    Vec2::from(rside / 2.0),
+                    rside / 2.0,
+                ) {
+                    Some(rpos) => rpos,
+                    None => continue,
+                };
+                let reachable = destinations.contains(id);
+                let desc = if reachable {
+                    i18n.get_msg("hud-portal-travel")
+                } else {
+                    Cow::Borrowed("")
+                };
+                if Button::image(self.imgs.mmap_site_sea_chapel)
+                    .x_y_position_relative_to(
+                        state.ids.map_layers[0],
+                        position::Relative::Scalar(rpos.x as f64),
+                        position::Relative::Scalar(rpos.y as f64),
+                    )
+                    .w_h(rside as f64, rside as f64)
+                    .hover_image(if reachable {
+                        self.imgs.mmap_site_sea_chapel_hover
+                    } else {
+                        self.imgs.mmap_site_sea_chapel
+                    })
+                    .image_color(
+                        if reachable || self.travel_from.is_none() {
+                            TEXT_VELORITE
+                        } else {
+                            TEXT_GRAY_COLOR
+                        }
+                        .alpha(fade),
+                    )
+                    .with_tooltip(
+                        self.tooltip_manager,
+                        &portal.name,
+                        &desc,
+                        &site_tooltip,
+                        TEXT_VELORITE,
+                    )
+                    .set(state.ids.portal_icons[i], ui)
+                    .was_clicked()
+                    && reachable
+                {
+                    events.push(Event::UsePortal(*id));
+                }
+            }
+        }
+
         // Group member indicators
         let client_state = self.client.state();
         let stats = client_state.ecs().read_storage::<comp::Stats>();
@@ -1191,10 +1532,18 @@ impl<'a> Widget for Map<'a> {
         };
         for (i, &uid) in group_members.iter().copied().enumerate() {
             let entity = uid_allocator.retrieve_entity_internal(uid.into());
-            let member_pos = entity.and_then(|entity| member_pos.get(entity));
+            let status = self.client.group_member_status().get(&uid);
+            // Fall back to the last status sent by the server for members outside
+            // of the view distance
+            let member_pos = entity
+                .and_then(|entity| member_pos.get(entity))
+                .map(|pos| pos.0)
+                .or_else(|| status.map(|status| status.pos));
             let stats = entity.and_then(|entity| stats.get(entity));
             let name = if let Some(stats) = stats {
                 stats.name.to_string()
+            } else if let Some(status) = status {
+                status.name.clone()
             } else {
                 "".to_string()
             };
@@ -1204,7 +1553,7 @@ impl<'a> Widget for Map<'a> {
                 let side_length = 20.0 * factor;
 
                 let (rpos, fade) = match wpos_to_rpos_fade(
-                    member_pos.0.xy().map(|e| e as f32),
+                    member_pos.xy().map(|e| e as f32),
                     Vec2::from(side_length / 2.0),
                     side_length / 2.0,
                 ) {
@@ -1212,7 +1561,7 @@ impl<'a> Widget for Map<'a> {
                     None => continue,
                 };
 
-                let z_comparison = (member_pos.0.z - player_pos.z) as i32;
+                let z_comparison = (member_pos.z - player_pos.z) as i32;
 
                 Button::image(match z_comparison {
                     10..=i32::MAX => self.imgs.indicator_group_up,
@@ -1232,7 +1581,7 @@ impl<'a> Widget for Map<'a> {
 
                 handle_widget_mouse_events(
                     state.ids.member_indicators[i],
-                    MarkerChange::Pos(member_pos.0.xy().map(|e| e as f32)),
+                    MarkerChange::Pos(member_pos.xy().map(|e| e as f32)),
                     ui,
                     &mut events,
                     state.ids.map_layers[0],
@@ -1242,6 +1591,23 @@ impl<'a> Widget for Map<'a> {
 
         let factor = 1.4;
         let side_length = 20.0 * factor;
+        let member_name = |uid: Uid| {
+            self.client
+                .player_list()
+                .get(&uid)
+                .filter(|_| {
+                    !(self.global_state.settings.interface.streamer_mode
+                        && Some(uid) == self.client.uid())
+                })
+                .map(|info| info.player_alias.as_str())
+                .or_else(|| {
+                    uid_allocator
+                        .retrieve_entity_internal(uid.into())
+                        .and_then(|entity| stats.get(entity))
+                        .map(|stats| stats.name.as_str())
+                })
+                .unwrap_or("")
+        };
         // Groups location markers
         if state.ids.location_marker_group.len() < self.location_markers.group.len() {
             state.update(|s| {
@@ -1256,18 +1622,7 @@ impl<'a> Widget for Map<'a> {
             if let Some((rpos, fade)) =
                 wpos_to_rpos_fade(lm, Vec2::from(side_length / 2.0), side_length / 2.0)
             {
-                let name = self
-                    .client
-                    .player_list()
-                    .get(&uid)
-                    .map(|info| info.player_alias.as_str())
-                    .or_else(|| {
-                        uid_allocator
-                            .retrieve_entity_internal(uid.into())
-                            .and_then(|entity| stats.get(entity))
-                            .map(|stats| stats.name.as_str())
-                    })
-                    .unwrap_or("");
+                let name = member_name(uid);
 
                 let image_id = match self.client.group_info().map(|info| info.1) {
                     Some(leader) if leader == uid => self.imgs.location_marker_group_leader,
@@ -1351,6 +1706,103 @@ impl<'a> Widget for Map<'a> {
                 state.ids.map_layers[0],
             );
         }
+        // Named markers placed by the player
+        if state.ids.placed_markers.len() < self.location_markers.placed.len() {
+            state.update(|s| {
+                s.ids.placed_markers.resize(
+                    self.location_markers.placed.len(),
+                    &mut ui.widget_id_generator(),
+                )
+            })
+        };
+        for (i, placed) in self.location_markers.placed.iter().enumerate() {
+            let lm = placed.marker.wpos.as_();
+            if let Some((rpos, fade)) =
+                wpos_to_rpos_fade(lm, Vec2::from(side_length / 2.0), side_length / 2.0)
+            {
+                let shared = if placed.shared {
+                    i18n.get_msg("hud-map-shared_marker")
+                } else {
+                    Cow::Borrowed("")
+                };
+                Button::image(self.imgs.location_marker)
+                    .x_y_position_relative_to(
+                        state.ids.map_layers[0],
+                        position::Relative::Scalar(rpos.x as f64),
+                        position::Relative::Scalar(rpos.y as f64 + 10.0 * factor as f64),
+                    )
+                    .w_h(side_length as f64, side_length as f64)
+                    .image_color(PLACED_MARKER_COLOR.alpha(fade))
+                    .floating(true)
+                    .with_tooltip(
+                        self.tooltip_manager,
+                        &placed.marker.name,
+                        &format!("X: {}, Y: {}\n\n{}", lm.x as i32, lm.y as i32, shared),
+                        &site_tooltip,
+                        TEXT_VELORITE,
+                    )
+                    .set(state.ids.placed_markers[i], ui);
+                handle_widget_mouse_events(
+                    state.ids.placed_markers[i],
+                    MarkerChange::Pos(lm),
+                    ui,
+                    &mut events,
+                    state.ids.map_layers[0],
+                );
+            }
+        }
+        // Named markers shared by the group members
+        let group_shared = self
+            .location_markers
+            .group_shared
+            .iter()
+            .flat_map(|(uid, markers)| markers.iter().map(move |marker| (*uid, marker)))
+            .collect::<Vec<_>>();
+        if state.ids.group_shared_markers.len() < group_shared.len() {
+            state.update(|s| {
+                s.ids
+                    .group_shared_markers
+                    .resize(group_shared.len(), &mut ui.widget_id_generator())
+            })
+        };
+        for (i, (uid, marker)) in group_shared.into_iter().enumerate() {
+            let lm = marker.wpos.as_();
+            if let Some((rpos, fade)) =
+                wpos_to_rpos_fade(lm, Vec2::from(side_length / 2.0), side_length / 2.0)
+            {
+                Button::image(self.imgs.location_marker_group)
+                    .x_y_position_relative_to(
+                        state.ids.map_layers[0],
+                        position::Relative::Scalar(rpos.x as f64),
+                        position::Relative::Scalar(rpos.y as f64 + 10.0 * factor as f64),
+                    )
+                    .w_h(side_length as f64, side_length as f64)
+                    .image_color(PLACED_MARKER_COLOR.alpha(fade))
+                    .floating(true)
+                    .with_tooltip(
+                        self.tooltip_manager,
+                        &marker.name,
+                        &format!(
+                            "X: {}, Y: {}\n\n{}",
+                            lm.x as i32,
+                            lm.y as i32,
+                            i18n.get_msg_ctx("hud-map-placed_by", &i18n::fluent_args! {
+                                "name" => member_name(uid)
+                            }),
+                        ),
+                        &site_tooltip,
+                        TEXT_VELORITE,
+                    )
+                    .set(state.ids.group_shared_markers[i], ui);
+                handle_widget_mouse_events(
+                    state.ids.group_shared_markers[i],
+                    MarkerChange::Pos(lm),
+                    ui,
+                    &mut events,
+                    state.ids.map_layers[0],
+                );
+            }
+        }
 
         // Cursor pos relative to playerpos and widget size
         // Cursor stops moving on an axis as soon as it's position exceeds the maximum
@@ -0,0 +1,231 @@
+use super::{img_ids::Imgs, TEXT_COLOR, UI_HIGHLIGHT_0};
+use crate::ui::fonts::Fonts;
+use common::comp::minigame::{Game, Minigame, MinigameAction, Outcome, BOARD_SIDE};
+use conrod_core::{
+    position::Relative,
+    widget::{self, Button, Image, Text},
+    widget_ids, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+
+widget_ids! {
+    struct Ids {
+        top,
+        mid,
+        bot,
+        title,
+        status,
+        cells[],
+        seat_button,
+        reset_button,
+        close_button,
+    }
+}
+
+const CELL_SIZE: f64 = 48.0;
+/// Marks of the players in each seat
+const MARKS: [&str; 2] = ["X", "O"];
+
+#[derive(WidgetCommon)]
+pub struct MinigameWindow<'a> {
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    minigame: &'a Minigame,
+    /// Names of the players in each seat
+    seat_names: &'a [Option<String>],
+    /// Seat of the player, if they are playing
+    own_seat: Option<usize>,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> MinigameWindow<'a> {
+    pub fn new(
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        minigame: &'a Minigame,
+        seat_names: &'a [Option<String>],
+        own_seat: Option<usize>,
+    ) -> Self {
+        Self {
+            imgs,
+            fonts,
+            localized_strings,
+            minigame,
+            seat_names,
+            own_seat,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+
+    fn status(&self) -> String {
+        let i18n = self.localized_strings;
+        let name = |seat: usize| {
+            self.seat_names
+                .get(seat)
+                .cloned()
+                .flatten()
+                .unwrap_or_default()
+        };
+        match self.minigame.game.outcome() {
+            Some(Outcome::Won(seat)) => i18n
+                .get_msg_ctx("hud-minigame-won", &i18n::fluent_args! {
+                    "player" => name(seat),
+                })
+                .into_owned(),
+            Some(Outcome::Draw) => i18n.get_msg("hud-minigame-draw").into_owned(),
+            None if self.minigame.seats.iter().any(Option::is_none) => {
+                i18n.get_msg("hud-minigame-waiting").into_owned()
+            },
+            None => i18n
+                .get_msg_ctx("hud-minigame-turn", &i18n::fluent_args! {
+                    "player" => name(self.minigame.game.turn()),
+                })
+                .into_owned(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    Act(MinigameAction),
+    Close,
+}
+
+impl<'a> Widget for MinigameWindow<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("MinigameWindow::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut event = None;
+        let i18n = self.localized_strings;
+
+        let (title, cells) = match &self.minigame.game {
+            Game::ThreeInARow(game) => (i18n.get_msg("hud-minigame-three_in_a_row"), &game.cells),
+        };
+        if state.ids.cells.len() < cells.len() {
+            state.update(|s| {
+                s.ids
+                    .cells
+                    .resize(cells.len(), &mut ui.widget_id_generator())
+            });
+        }
+
+        // Window
+        let board_height = CELL_SIZE * BOARD_SIDE as f64;
+        Image::new(self.imgs.prompt_top)
+            .w_h(276.0, 24.0)
+            .mid_bottom_with_margin_on(ui.window, 320.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.top, ui);
+        Image::new(self.imgs.prompt_mid)
+            .w_h(276.0, board_height + 3.0 * 40.0 + 60.0)
+            .down_from(state.ids.top, 0.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.mid, ui);
+        Image::new(self.imgs.prompt_bot)
+            .w_h(276.0, 35.0)
+            .down_from(state.ids.mid, 0.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.bot, ui);
+
+        Text::new(&title)
+            .mid_top_with_margin_on(state.ids.mid, 0.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(18))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+        Text::new(&self.status())
+            .mid_top_with_margin_on(state.ids.mid, 26.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.status, ui);
+
+        // Board
+        let board_left = -CELL_SIZE * (BOARD_SIDE as f64 - 1.0) / 2.0;
+        for (i, cell) in cells.iter().enumerate() {
+            let (x, y) = (i % BOARD_SIDE, i / BOARD_SIDE);
+            let mark = cell
+                .and_then(|seat| MARKS.get(seat as usize))
+                .unwrap_or(&"");
+            if Button::image(self.imgs.button)
+                .w_h(CELL_SIZE - 4.0, CELL_SIZE - 4.0)
+                .mid_top_with_margin_on(state.ids.mid, 50.0 + CELL_SIZE * y as f64)
+                .x_relative_to(state.ids.mid, board_left + CELL_SIZE * x as f64)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .label(mark)
+                .label_y(Relative::Scalar(2.0))
+                .label_color(TEXT_COLOR)
+                .label_font_size(self.fonts.cyri.scale(24))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .set(state.ids.cells[i], ui)
+                .was_clicked()
+            {
+                event = Some(Event::Act(MinigameAction::Play(i as u8)));
+            }
+        }
+
+        // Seat, new game and close buttons
+        let buttons_top = 60.0 + board_height;
+        let button = |row: usize| {
+            Button::image(self.imgs.button)
+                .w_h(210.0, 36.0)
+                .mid_top_with_margin_on(state.ids.mid, buttons_top + 40.0 * row as f64)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .label_y(Relative::Scalar(2.0))
+                .label_color(TEXT_COLOR)
+                .label_font_size(self.fonts.cyri.scale(16))
+                .label_font_id(self.fonts.cyri.conrod_id)
+        };
+        let (seat_key, seat_action) = if self.own_seat.is_some() {
+            ("hud-minigame-leave", MinigameAction::Leave)
+        } else {
+            ("hud-minigame-join", MinigameAction::Join)
+        };
+        if button(0)
+            .label(&i18n.get_msg(seat_key))
+            .set(state.ids.seat_button, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Act(seat_action));
+        }
+        if self.own_seat.is_some()
+            && self.minigame.game.outcome().is_some()
+            && button(1)
+                .label(&i18n.get_msg("hud-minigame-new_game"))
+                .set(state.ids.reset_button, ui)
+                .was_clicked()
+        {
+            event = Some(Event::Act(MinigameAction::Reset));
+        }
+        if button(2)
+            .label(&i18n.get_msg("common-close"))
+            .set(state.ids.close_button, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        event
+    }
+}
@@ -1,5 +1,6 @@
 use super::{
     img_ids::{Imgs, ImgsRot},
+    placed_markers::PLACED_MARKER_COLOR,
     MapMarkers, QUALITY_COMMON, QUALITY_DEBUG, QUALITY_EPIC, QUALITY_HIGH, QUALITY_LOW,
     QUALITY_MODERATE, TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
 };
@@ -9,6 +10,7 @@ use crate::{
     ui::{fonts::Fonts, img_ids, KeyedJobs},
     GlobalState,
 };
+use chrono::NaiveTime;
 use client::{self, Client};
 use common::{
     comp,
@@ -16,6 +18,8 @@ use common::{
     grid::Grid,
     slowjob::SlowJobPool,
     terrain::{Block, BlockKind, TerrainChunk, TerrainChunkSize, TerrainGrid},
+    time::Season,
+    uid::Uid,
     vol::{ReadVol, RectVolSize},
 };
 use common_net::msg::world_msg::SiteKind;
@@ -26,7 +30,7 @@ use conrod_core::{
 };
 use hashbrown::HashMap;
 use image::{DynamicImage, RgbaImage};
-use specs::{saveload::MarkerAllocator, WorldExt};
+use specs::{saveload::MarkerAllocator, Join, WorldExt};
 use std::sync::Arc;
 use vek::*;
 
@@ -52,6 +56,13 @@ pub struct VoxelMinimap {
 
 const VOXEL_MINIMAP_SIDELENGTH: u32 = 256;
 
+/// Colours of the dots of the players, the creatures hostile to everyone, the
+/// wild ones and the rest of the creatures nearby
+const PLAYER_DOT_COLOR: Color = Color::Rgba(0.4, 0.75, 1.0, 1.0);
+const ENEMY_DOT_COLOR: Color = Color::Rgba(0.9, 0.15, 0.15, 1.0);
+const WILD_DOT_COLOR: Color = Color::Rgba(0.95, 0.55, 0.1, 1.0);
+const NPC_DOT_COLOR: Color = Color::Rgba(0.95, 0.9, 0.3, 1.0);
+
 impl VoxelMinimap {
     pub fn new(ui: &mut Ui) -> Self {
         let composited = RgbaImage::from_pixel(
@@ -355,6 +366,7 @@ widget_ids! {
         mmap_frame_2,
         mmap_frame_bg,
         mmap_location,
+        mmap_clock,
         mmap_button,
         mmap_plus,
         mmap_minus,
@@ -368,8 +380,10 @@ widget_ids! {
         mmap_site_icons_bgs[],
         mmap_site_icons[],
         member_indicators[],
+        entity_indicators[],
         location_marker,
         location_marker_group[],
+        placed_markers[],
         voxel_minimap,
     }
 }
@@ -740,6 +754,62 @@ impl<'a> Widget for MiniMap<'a> {
                 .set(state.ids.mmap_site_icons[i], ui);
             }
 
+            // Nearby creatures and players, the group members having their own indicators
+            let entity_dots = if self.global_state.settings.interface.minimap_show_entities {
+                let ecs = self.client.state().ecs();
+                let group_members = self.client.group_members();
+                (
+                    &ecs.entities(),
+                    &ecs.read_storage::<comp::Pos>(),
+                    &ecs.read_storage::<comp::Body>(),
+                    ecs.read_storage::<comp::Health>().maybe(),
+                    ecs.read_storage::<comp::Alignment>().maybe(),
+                    ecs.read_storage::<comp::Player>().maybe(),
+                    ecs.read_storage::<Uid>().maybe(),
+                )
+                    .join()
+                    .filter(|(entity, _, body, health, _, _, uid)| {
+                        *entity != self.client.entity()
+                            && !matches!(
+                                body,
+                                comp::Body::Object(_)
+                                    | comp::Body::Ship(_)
+                                    | comp::Body::ItemDrop(_)
+                            )
+                            && health.map_or(true, |health| !health.is_dead)
+                            && uid.map_or(true, |uid| !group_members.contains_key(uid))
+                    })
+                    .filter_map(|(_, pos, _, _, alignment, player, _)| {
+                        let rpos = wpos_to_rpos(pos.0.xy(), false)?;
+                        let color = match (player, alignment) {
+                            (Some(_), _) => PLAYER_DOT_COLOR,
+                            (None, Some(comp::Alignment::Enemy)) => ENEMY_DOT_COLOR,
+                            (None, Some(comp::Alignment::Wild)) => WILD_DOT_COLOR,
+                            (None, _) => NPC_DOT_COLOR,
+                        };
+                        Some((rpos, color))
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                Vec::new()
+            };
+            if state.ids.entity_indicators.len() < entity_dots.len() {
+                state.update(|s| {
+                    s.ids
+                        .entity_indicators
+                        .resize(entity_dots.len(), &mut ui.widget_id_generator())
+                })
+            };
+            for (i, (rpos, color)) in entity_dots.into_iter().enumerate() {
+                Rectangle::fill_with([4.0, 4.0], color)
+                    .x_y_position_relative_to(
+                        state.ids.map_layers[0],
+                        position::Relative::Scalar(rpos.x as f64),
+                        position::Relative::Scalar(rpos.y as f64),
+                    )
+                    .set(state.ids.entity_indicators[i], ui);
+            }
+
             // Group member indicators
             let client_state = self.client.state();
             let member_pos = client_state.ecs().read_storage::<comp::Pos>();
@@ -766,16 +836,24 @@ impl<'a> Widget for MiniMap<'a> {
             };
             for (i, &uid) in group_members.iter().copied().enumerate() {
                 let entity = uid_allocator.retrieve_entity_internal(uid.into());
-                let member_pos = entity.and_then(|entity| member_pos.get(entity));
+                let member_pos = entity
+                    .and_then(|entity| member_pos.get(entity))
+                    .map(|pos| pos.0)
+                    .or_else(|| {
+                        self.client
+                            .group_member_status()
+                            .get(&uid)
+                            .map(|status| status.pos)
+                    });
 
                 if let Some(member_pos) = member_pos {
-                    let rpos = match wpos_to_rpos(member_pos.0.xy().map(|e| e as f32), false) {
+                    let rpos = match wpos_to_rpos(member_pos.xy().map(|e| e as f32), false) {
                         Some(rpos) => rpos,
                         None => continue,
                     };
 
                     let factor = 1.2;
-                    let z_comparison = (member_pos.0.z - player_pos.z) as i32;
+                    let z_comparison = (member_pos.z - player_pos.z) as i32;
                     Button::image(match z_comparison {
                         10..=i32::MAX => self.imgs.indicator_group_up,
                         i32::MIN..=-10 => self.imgs.indicator_group_down,
@@ -841,6 +919,42 @@ impl<'a> Widget for MiniMap<'a> {
                     .parent(ui.window)
                     .set(state.ids.location_marker, ui)
             }
+
+            // Named markers, placed by the player or shared by the group members
+            let placed_markers = self
+                .location_markers
+                .placed
+                .iter()
+                .map(|placed| (&placed.marker, self.imgs.location_marker))
+                .chain(
+                    self.location_markers
+                        .group_shared
+                        .values()
+                        .flatten()
+                        .map(|marker| (marker, self.imgs.location_marker_group)),
+                )
+                .collect::<Vec<_>>();
+            if state.ids.placed_markers.len() < placed_markers.len() {
+                state.update(|s| {
+                    s.ids
+                        .placed_markers
+                        .resize(placed_markers.len(), &mut ui.widget_id_generator())
+                })
+            };
+            for (i, (marker, image_id)) in placed_markers.into_iter().enumerate() {
+                if let Some(rpos) = wpos_to_rpos(marker.wpos.as_(), true) {
+                    Image::new(image_id)
+                        .x_y_position_relative_to(
+                            state.ids.map_layers[0],
+                            position::Relative::Scalar(rpos.x as f64),
+                            position::Relative::Scalar(rpos.y as f64 + 8.0),
+                        )
+                        .w_h(16.0, 16.0)
+                        .color(Some(PLACED_MARKER_COLOR))
+                        .parent(ui.window)
+                        .set(state.ids.placed_markers[i], ui)
+                }
+            }
             // Indicator
             let ind_scale = 0.4;
             let ind_rotation = if is_facing_north {
@@ -915,6 +1029,29 @@ impl<'a> Widget for MiniMap<'a> {
             events.push(Event::SettingsChange(MinimapShow(!show_minimap)));
         }
 
+        // In-game time
+        let current_time = NaiveTime::from_num_seconds_from_midnight(
+            // Wraps around back to 0s if it exceeds 24 hours (24 hours = 86400s)
+            (self.client.state().get_time_of_day() as u64 % 86400) as u32,
+            0,
+        );
+        let season = match self.client.state().get_season().season() {
+            Season::Spring => "hud-map-season-spring",
+            Season::Summer => "hud-map-season-summer",
+            Season::Autumn => "hud-map-season-autumn",
+            Season::Winter => "hud-map-season-winter",
+        };
+        Text::new(&format!(
+            "{} - {}",
+            current_time.format("%H:%M"),
+            self.global_state.i18n.read().get_msg(season)
+        ))
+        .top_left_with_margins_on(state.ids.mmap_frame, 4.0, 8.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(TEXT_COLOR)
+        .set(state.ids.mmap_clock, ui);
+
         // TODO: Subregion name display
 
         // Title
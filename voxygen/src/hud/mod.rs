@@ -3,56 +3,85 @@ mod bag;
 mod buffs;
 mod buttons;
 mod chat;
+mod combat_log;
+mod compass;
 mod crafting;
+mod damage_indicators;
+mod dialogue;
 mod diary;
 mod esc_menu;
+mod exploration;
 mod group;
+mod guild;
 mod hotbar;
 pub mod img_ids;
 pub mod item_imgs;
 mod loot_scroller;
 mod map;
+mod minigame;
 mod minimap;
 mod overhead;
 mod overitem;
+mod photo_mode;
+mod placed_markers;
 mod popup;
 mod prompt_dialog;
+mod quest_log;
+mod quest_tracker;
 mod settings_window;
 mod skillbar;
 mod slots;
 mod social;
+mod subtitles;
 mod trade;
 pub mod util;
+mod writing;
 
 pub use crafting::CraftingTab;
+pub use exploration::ExploredMap;
 pub use hotbar::{SlotContents as HotbarSlotContents, State as HotbarState};
 pub use item_imgs::animate_by_pulse;
 pub use loot_scroller::LootMessage;
+pub use placed_markers::PlacedMarkers;
 pub use settings_window::ScaleChange;
+pub use writing::Writable;
 
 use bag::Bag;
 use buffs::BuffsBar;
 use buttons::Buttons;
 use chat::Chat;
 use chrono::NaiveTime;
+use combat_log::{CombatEvent, CombatLog, CombatLogWindow};
+use compass::Compass;
 use crafting::Crafting;
+use damage_indicators::{DamageDirections, DamageIndicators};
+use dialogue::Dialogue;
 use diary::{Diary, SelectedSkillTree};
 use esc_menu::EscMenu;
+use exploration::FogOfWar;
 use group::Group;
+use guild::Guild;
 use img_ids::Imgs;
 use item_imgs::ItemImgs;
 use loot_scroller::LootScroller;
 use map::Map;
+use minigame::MinigameWindow;
 use minimap::{MiniMap, VoxelMinimap};
+use photo_mode::PhotoModeWindow;
 use popup::Popup;
 use prompt_dialog::PromptDialog;
+use quest_log::QuestLogWindow;
+use quest_tracker::QuestTracker;
 use serde::{Deserialize, Serialize};
 use settings_window::{SettingsTab, SettingsWindow};
 use skillbar::Skillbar;
 use social::Social;
+use subtitles::{SubtitleList, Subtitles};
 use trade::Trade;
+use writing::Writing;
 
 use crate::{
+    chat_history::ChatHistory,
     cmd::get_player_uuid,
     ecs::{
         comp as vcomp,
@@ -69,7 +98,7 @@ use crate::{
         interactable::Interactable,
         settings_change::{Chat as ChatChange, Interface as InterfaceChange, SettingsChange},
     },
-    settings::chat::ChatFilter,
+    settings::{chat::ChatFilter, ColorPalette},
     ui::{
         self, fonts::Fonts, img_ids::Rotations, slot, slot::SlotKey, Graphic, Ingameable,
         ScaleMode, Ui,
@@ -88,10 +117,11 @@ use common::{
         item::{tool::ToolKind, ItemDesc, MaterialStatManifest, Quality},
         loot_owner::LootOwnerKind,
         pet::is_mountable,
+        portal::PortalId,
         skillset::{skills::Skill, SkillGroupKind},
-        BuffData, BuffKind, Health, Item, MapMarkerChange,
+        BuffData, BuffKind, Health, Item, MapMarkerChange, NamedMapMarker,
     },
-    consts::MAX_PICKUP_RANGE,
+    consts::{MAX_PICKUP_RANGE, MAX_TRADE_RANGE},
     link::Is,
     mounting::Mount,
     outcome::Outcome,
@@ -100,7 +130,9 @@ use common::{
     trade::{ReducedInventory, TradeAction},
     uid::Uid,
     util::{srgba_to_linear, Dir},
-    vol::RectRasterableVol,
+    vol::{ReadVol, RectRasterableVol},
+    world_border::WorldBorder,
+    writing::SIGN_READ_RANGE,
 };
 use common_base::{prof_span, span};
 use common_net::{
@@ -176,6 +208,7 @@ const SAY_COLOR: Color = Color::Rgba(1.0, 0.8, 0.8, 1.0);
 const GROUP_COLOR: Color = Color::Rgba(0.47, 0.84, 1.0, 1.0);
 /// Color for factional chat
 const FACTION_COLOR: Color = Color::Rgba(0.24, 1.0, 0.48, 1.0);
+const GUILD_COLOR: Color = Color::Rgba(1.0, 0.78, 0.27, 1.0);
 /// Color for regional chat
 const REGION_COLOR: Color = Color::Rgba(0.8, 1.0, 0.8, 1.0);
 /// Color for death messagesw
@@ -186,6 +219,8 @@ const WORLD_COLOR: Color = Color::Rgba(0.95, 1.0, 0.95, 1.0);
 //Nametags
 const GROUP_MEMBER: Color = Color::Rgba(0.47, 0.84, 1.0, 1.0);
 const DEFAULT_NPC: Color = Color::Rgba(1.0, 1.0, 1.0, 1.0);
+const HOSTILE_NPC: Color = Color::Rgba(1.0, 0.35, 0.3, 1.0);
+const FRIENDLY_NPC: Color = Color::Rgba(0.5, 1.0, 0.45, 1.0);
 
 // UI Color-Theme
 const UI_MAIN: Color = Color::Rgba(0.61, 0.70, 0.70, 1.0); // Greenish Blue
@@ -195,6 +230,60 @@ const UI_HIGHLIGHT_0: Color = Color::Rgba(0.79, 1.09, 1.09, 1.0);
 const MENU_BG: Color = Color::Rgba(0.1, 0.12, 0.12, 1.0);
 //const UI_DARK_0: Color = Color::Rgba(0.25, 0.37, 0.37, 1.0);
 
+/// Colours of the health bars and damage numbers in the colour palette chosen
+/// in the accessibility settings
+#[derive(Copy, Clone)]
+pub struct HealthColors {
+    pub hp: Color,
+    pub low_hp: Color,
+    pub critical_hp: Color,
+    pub enemy_hp: Color,
+    pub crit: Rgb<f32>,
+    pub heal: Rgb<f32>,
+}
+
+impl HealthColors {
+    pub fn of(palette: ColorPalette) -> Self {
+        match palette {
+            ColorPalette::Default => Self {
+                hp: HP_COLOR,
+                low_hp: LOW_HP_COLOR,
+                critical_hp: CRITICAL_HP_COLOR,
+                enemy_hp: ENEMY_HP_COLOR,
+                crit: Rgb::new(1.0, 0.9, 0.0),
+                heal: Rgb::new(0.1, 1.0, 0.1),
+            },
+            // Red and green look alike, so blue takes the place of green
+            ColorPalette::Deuteranopia => Self {
+                hp: Color::Rgba(0.0, 0.45, 0.7, 1.0),
+                low_hp: Color::Rgba(0.94, 0.89, 0.26, 1.0),
+                critical_hp: Color::Rgba(0.84, 0.37, 0.0, 1.0),
+                enemy_hp: Color::Rgba(0.8, 0.47, 0.65, 1.0),
+                crit: Rgb::new(1.0, 0.9, 0.0),
+                heal: Rgb::new(0.34, 0.71, 0.91),
+            },
+            // Reds look darker on top of that, so they are brightened
+            ColorPalette::Protanopia => Self {
+                hp: Color::Rgba(0.0, 0.45, 0.7, 1.0),
+                low_hp: Color::Rgba(0.94, 0.89, 0.26, 1.0),
+                critical_hp: Color::Rgba(0.9, 0.6, 0.0, 1.0),
+                enemy_hp: Color::Rgba(0.95, 0.6, 0.8, 1.0),
+                crit: Rgb::new(1.0, 0.9, 0.0),
+                heal: Rgb::new(0.34, 0.71, 0.91),
+            },
+            // Blue and yellow look alike, so the warnings go from teal to pink and red
+            ColorPalette::Tritanopia => Self {
+                hp: Color::Rgba(0.0, 0.62, 0.62, 1.0),
+                low_hp: Color::Rgba(0.95, 0.55, 0.65, 1.0),
+                critical_hp: Color::Rgba(0.85, 0.1, 0.1, 1.0),
+                enemy_hp: ENEMY_HP_COLOR,
+                crit: Rgb::new(1.0, 0.3, 0.6),
+                heal: Rgb::new(0.1, 1.0, 0.1),
+            },
+        }
+    }
+}
+
 /// Distance at which nametags are visible for group members
 const NAMETAG_GROUP_RANGE: f32 = 1000.0;
 /// Distance at which nametags are visible for merchants
@@ -206,10 +295,23 @@ const NAMETAG_RANGE: f32 = 40.0;
 const NAMETAG_DMG_TIME: f32 = 60.0;
 /// Range damaged triggered nametags can be seen
 const NAMETAG_DMG_RANGE: f32 = 120.0;
+/// Part of their range after which nametags start fading out
+const NAMETAG_FADE_START: f32 = 0.6;
+/// Opacity of the nametags at the edge of their range
+const NAMETAG_MIN_OPACITY: f32 = 0.25;
+/// Seconds the health bar flashes for after a hit
+const NAMETAG_DMG_FLASH_TIME: f32 = 0.3;
 /// Range to display speech-bubbles at
 const SPEECH_BUBBLE_RANGE: f32 = NAMETAG_RANGE;
+/// Size of the text of the furthest speech bubbles
+const SPEECH_BUBBLE_MIN_SCALE: f32 = 0.6;
 const EXP_FLOATER_LIFETIME: f32 = 2.0;
 const EXP_ACCUMULATION_DURATION: f32 = 0.5;
+/// Seconds the screen stays black after sleeping, and then fades in
+const WAKE_UP_HOLD: f32 = 1.0;
+const WAKE_UP_FADE: f32 = 2.0;
+/// Distance from the world border at which the players are warned about it
+const WORLD_BORDER_WARN_DISTANCE: f32 = 32.0;
 
 widget_ids! {
     struct Ids {
@@ -233,12 +335,17 @@ widget_ids! {
         sct_lvl_bg,
         sct_lvl,
         hurt_bg,
+        damage_indicators,
+        subtitles,
         death_bg,
+        respawn_button,
+        wake_up_bg,
         sct_bgs[],
         scts[],
 
         overheads[],
         overitems[],
+        sign_texts[],
 
         // Alpha Disclaimer
         alpha_text,
@@ -295,7 +402,14 @@ widget_ids! {
         character_window,
         popup,
         minimap,
+        compass,
+        world_border_warning,
+        quest_tracker,
         prompt_dialog,
+        dialogue,
+        minigame,
+        photo_mode,
+        writing,
         bag,
         trade,
         social,
@@ -307,6 +421,9 @@ widget_ids! {
         esc_menu,
         small_window,
         social_window,
+        guild_window,
+        combat_log_window,
+        quest_log_window,
         crafting_window,
         settings_window,
         group_window,
@@ -320,6 +437,10 @@ widget_ids! {
         auto_walk_txt,
         auto_walk_bg,
 
+        // Training dummy damage meter
+        damage_report_txt,
+        damage_report_bg,
+
         // Camera clamp indicator
         camera_clamp_txt,
         camera_clamp_bg,
@@ -505,6 +626,7 @@ pub struct HudInfo {
     pub mutable_viewpoint: bool,
     pub target_entity: Option<specs::Entity>,
     pub selected_entity: Option<(specs::Entity, Instant)>,
+    pub adaptive_quality_level: u32,
 }
 
 #[derive(Clone)]
@@ -532,7 +654,19 @@ pub enum Event {
     SplitDropSlot(comp::slot::Slot),
     SortInventory,
     ChangeHotbarState(Box<HotbarState>),
+    /// The character explored cells of the world it hadn't yet
+    Explored(Vec<Vec2<i32>>),
+    /// The player placed, removed or shared one of their named markers
+    ChangePlacedMarkers(PlacedMarkers),
     TradeAction(TradeAction),
+    /// Pick one of the choices of the dialogue with a NPC
+    Talk(usize),
+    /// Walk away from the NPC the player is talking to
+    CloseDialogue,
+    Minigame(specs::Entity, comp::MinigameAction),
+    Write(Writable, String),
+    TakePhoto,
+    LeavePhotoMode,
     Ability(usize, bool),
     Logout,
     Quit,
@@ -557,7 +691,9 @@ pub enum Event {
         modifier: Option<InvSlotId>,
         craft_sprite: Option<Vec3<i32>>,
     },
+    Respawn,
     InviteMember(Uid),
+    InviteToGuild(Uid),
     AcceptInvite,
     DeclineInvite,
     KickMember(Uid),
@@ -566,6 +702,8 @@ pub enum Event {
     RemoveBuff(BuffKind),
     UnlockSkill(Skill),
     RequestSiteInfo(SiteId),
+    /// Travel from the portal to the one with the given id
+    UsePortal(specs::Entity, PortalId),
     ChangeAbility(usize, AuxiliaryAbility),
 
     SettingsChange(SettingsChange),
@@ -667,6 +805,10 @@ impl PressBehavior {
 pub struct MapMarkers {
     owned: Option<Vec2<i32>>,
     group: HashMap<Uid, Vec2<i32>>,
+    /// Named markers placed by the player
+    placed: PlacedMarkers,
+    /// Named markers shared by the group members
+    group_shared: HashMap<Uid, Vec<NamedMapMarker>>,
 }
 
 /// (target slot, input value, inventory quantity, is our inventory, error,
@@ -699,6 +841,10 @@ impl TradeAmountInput {
 
 pub struct Show {
     ui: bool,
+    /// Whether a cutscene is being played, which hides the whole HUD
+    cutscene: bool,
+    /// Whether the player is in photo mode, which shows only its own window
+    photo_mode: bool,
     intro: bool,
     help: bool,
     crafting: bool,
@@ -706,6 +852,9 @@ pub struct Show {
     bag_inv: bool,
     trade: bool,
     social: bool,
+    guild: bool,
+    combat_log: bool,
+    quest_log: bool,
     diary: bool,
     group: bool,
     group_menu: bool,
@@ -718,12 +867,24 @@ pub struct Show {
     diary_fields: diary::DiaryShow,
     crafting_fields: crafting::CraftingShow,
     social_search_key: Option<String>,
+    /// Players whose voice isn't played
+    #[cfg(feature = "voice-chat")]
+    muted_voices: HashSet<Uid>,
     want_grab: bool,
     stats: bool,
     free_look: bool,
     auto_walk: bool,
     camera_clamp: bool,
     prompt_dialog: Option<PromptDialogSettings>,
+    /// NPC the dialogue window is open for
+    dialogue: Option<specs::Entity>,
+    /// Table or board the minigame window is open for
+    minigame: Option<specs::Entity>,
+    /// Sign or book the writing window is open for, with the text written so
+    /// far
+    writing: Option<(Writable, String)>,
+    /// Portal the map was opened from to travel
+    travel_portal: Option<specs::Entity>,
     location_markers: MapMarkers,
     trade_amount_input_key: Option<TradeAmountInput>,
 }
@@ -753,10 +914,14 @@ impl Show {
     fn map(&mut self, open: bool) {
         if !self.esc_menu {
             self.map = open;
+            self.travel_portal = None;
             self.bag = false;
             self.crafting = false;
             self.crafting_fields.salvage = false;
             self.social = false;
+            self.guild = false;
+            self.combat_log = false;
+            self.quest_log = false;
             self.diary = false;
             self.want_grab = !self.any_window_requires_cursor();
         }
@@ -769,6 +934,42 @@ impl Show {
                 self.search_social_players(None);
             }
             self.social = open;
+            self.guild = false;
+            self.combat_log = false;
+            self.quest_log = false;
+            self.diary = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn guild(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.guild = open;
+            self.social = false;
+            self.combat_log = false;
+            self.quest_log = false;
+            self.diary = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn combat_log(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.combat_log = open;
+            self.quest_log = false;
+            self.social = false;
+            self.guild = false;
+            self.diary = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn quest_log(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.quest_log = open;
+            self.combat_log = false;
+            self.social = false;
+            self.guild = false;
             self.diary = false;
             self.want_grab = !self.any_window_requires_cursor();
         }
@@ -806,6 +1007,9 @@ impl Show {
     fn diary(&mut self, open: bool) {
         if !self.esc_menu {
             self.social = false;
+            self.guild = false;
+            self.combat_log = false;
+            self.quest_log = false;
             self.crafting = false;
             self.crafting_fields.salvage = false;
             self.bag = false;
@@ -825,6 +1029,9 @@ impl Show {
             };
             self.bag = false;
             self.social = false;
+            self.guild = false;
+            self.combat_log = false;
+            self.quest_log = false;
             self.crafting = false;
             self.crafting_fields.salvage = false;
             self.diary = false;
@@ -832,12 +1039,62 @@ impl Show {
         }
     }
 
+    /// Opens the map to pick a destination to travel to from the portal
+    pub fn open_travel_map(&mut self, portal: specs::Entity) {
+        self.map(true);
+        if self.map {
+            self.travel_portal = Some(portal);
+        }
+    }
+
+    pub fn open_dialogue(&mut self, npc: specs::Entity) {
+        if !self.esc_menu {
+            self.dialogue = Some(npc);
+            self.want_grab = false;
+        }
+    }
+
+    pub fn close_dialogue(&mut self) {
+        self.dialogue = None;
+        self.want_grab = !self.any_window_requires_cursor();
+    }
+
+    pub fn open_minigame(&mut self, minigame: specs::Entity) {
+        if !self.esc_menu {
+            self.minigame = Some(minigame);
+            self.want_grab = false;
+        }
+    }
+
+    fn close_minigame(&mut self) {
+        self.minigame = None;
+        self.want_grab = !self.any_window_requires_cursor();
+    }
+
+    pub fn open_writing(&mut self, writable: Writable, text: String) {
+        if !self.esc_menu {
+            self.writing = Some((writable, text));
+            self.want_grab = false;
+        }
+    }
+
+    fn close_writing(&mut self) {
+        self.writing = None;
+        self.want_grab = !self.any_window_requires_cursor();
+    }
+
     fn toggle_trade(&mut self) { self.trade(!self.trade); }
 
     fn toggle_map(&mut self) { self.map(!self.map) }
 
     fn toggle_social(&mut self) { self.social(!self.social); }
 
+    fn toggle_guild(&mut self) { self.guild(!self.guild); }
+
+    fn toggle_combat_log(&mut self) { self.combat_log(!self.combat_log); }
+
+    fn toggle_quest_log(&mut self) { self.quest_log(!self.quest_log); }
+
     fn toggle_crafting(&mut self) { self.crafting(!self.crafting) }
 
     fn toggle_spell(&mut self) { self.diary(!self.diary) }
@@ -869,9 +1126,15 @@ impl Show {
     fn any_window_requires_cursor(&self) -> bool {
         self.bag
             || self.trade
+            || self.dialogue.is_some()
+            || self.minigame.is_some()
+            || self.writing.is_some()
             || self.esc_menu
             || self.map
             || self.social
+            || self.guild
+            || self.combat_log
+            || self.quest_log
             || self.crafting
             || self.diary
             || self.help
@@ -883,11 +1146,17 @@ impl Show {
         if self.any_window_requires_cursor() {
             self.bag = false;
             self.trade = false;
+            self.dialogue = None;
+            self.minigame = None;
+            self.writing = None;
             self.esc_menu = false;
             self.help = false;
             self.intro = false;
             self.map = false;
             self.social = false;
+            self.guild = false;
+            self.combat_log = false;
+            self.quest_log = false;
             self.diary = false;
             self.crafting = false;
             self.open_windows = Windows::None;
@@ -919,6 +1188,9 @@ impl Show {
     fn open_skill_tree(&mut self, tree_sel: SelectedSkillTree) {
         self.diary_fields.skilltreetab = tree_sel;
         self.social = false;
+        self.guild = false;
+        self.combat_log = false;
+        self.quest_log = false;
     }
 
     fn selected_crafting_tab(&mut self, sel_cat: CraftingTab) {
@@ -938,9 +1210,15 @@ impl Show {
     fn toggle_cursor_on_menu_close(&self, global_state: &mut GlobalState, ui: &mut Ui) {
         if !self.bag
             && !self.trade
+            && self.dialogue.is_none()
+            && self.minigame.is_none()
+            && self.writing.is_none()
             && !self.esc_menu
             && !self.map
             && !self.social
+            && !self.guild
+            && !self.combat_log
+            && !self.quest_log
             && !self.crafting
             && !self.diary
             && !self.help
@@ -968,8 +1246,16 @@ impl Show {
                     self.location_markers.group.remove(&user);
                 },
             },
+            comp::MapMarkerUpdate::GroupMemberShared(user, markers) => {
+                if markers.is_empty() {
+                    self.location_markers.group_shared.remove(&user);
+                } else {
+                    self.location_markers.group_shared.insert(user, markers);
+                }
+            },
             comp::MapMarkerUpdate::ClearGroup => {
                 self.location_markers.group.clear();
+                self.location_markers.group_shared.clear();
             },
         }
     }
@@ -1082,6 +1368,9 @@ pub struct Hud {
     new_loot_messages: VecDeque<LootMessage>,
     new_messages: VecDeque<comp::ChatMsg>,
     new_notifications: VecDeque<Notification>,
+    woke_up: Option<Instant>,
+    /// Damage last dealt to a training dummy, and when it was reported
+    damage_report: Option<(comp::DamageReport, Instant)>,
     speech_bubbles: HashMap<Uid, comp::SpeechBubble>,
     pub show: Show,
     //never_show: bool,
@@ -1089,6 +1378,9 @@ pub struct Hud {
     //intro_2: bool,
     to_focus: Option<Option<widget::Id>>,
     force_ungrab: bool,
+    /// Whether the player was dead last frame, the cursor is released while
+    /// the death screen is shown
+    was_dead: bool,
     force_chat_input: Option<String>,
     force_chat_cursor: Option<Index>,
     tab_complete: Option<String>,
@@ -1101,6 +1393,11 @@ pub struct Hud {
     floaters: Floaters,
     voxel_minimap: VoxelMinimap,
     map_drag: Vec2<f64>,
+    fog_of_war: FogOfWar,
+    damage_directions: DamageDirections,
+    subtitles: SubtitleList,
+    combat_log: CombatLog,
+    chat_history: ChatHistory,
 }
 
 impl Hud {
@@ -1130,8 +1427,9 @@ impl Hud {
         // Load item images.
         let item_imgs = ItemImgs::new(&mut ui, imgs.not_found);
         // Load fonts.
-        let fonts = Fonts::load(global_state.i18n.read().fonts(), &mut ui)
+        let mut fonts = Fonts::load(global_state.i18n.read().fonts(), &mut ui)
             .expect("Impossible to load fonts!");
+        fonts.set_text_scale(global_state.settings.accessibility.text_scale);
         // Get the server name.
         let server = &client.server_info().name;
         // Get the id, unwrap is safe because this CANNOT be None at this
@@ -1146,6 +1444,17 @@ impl Hud {
         // Create a new HotbarState from the persisted slots.
         let hotbar_state =
             HotbarState::new(global_state.profile.get_hotbar_slots(server, character_id));
+        let fog_of_war = FogOfWar::new(
+            &mut ui,
+            global_state.profile.get_explored_map(server, character_id),
+            world_map.1,
+        );
+
+        let chat_history = ChatHistory::load(
+            &global_state.userdata_dir,
+            server,
+            global_state.settings.chat.chat_history_lines as usize,
+        );
 
         let slot_manager = slots::SlotManager::new(
             ui.id_generator(),
@@ -1172,6 +1481,8 @@ impl Hud {
             new_loot_messages: VecDeque::new(),
             new_messages: VecDeque::new(),
             new_notifications: VecDeque::new(),
+            woke_up: None,
+            damage_report: None,
             speech_bubbles: HashMap::new(),
             //intro: false,
             //intro_2: false,
@@ -1187,6 +1498,9 @@ impl Hud {
                 crafting: false,
                 ui: true,
                 social: false,
+                guild: false,
+                combat_log: false,
+                quest_log: false,
                 diary: false,
                 group: false,
                 group_menu: false,
@@ -1195,6 +1509,8 @@ impl Hud {
                 diary_fields: diary::DiaryShow::default(),
                 crafting_fields: crafting::CraftingShow::default(),
                 social_search_key: None,
+                #[cfg(feature = "voice-chat")]
+                muted_voices: HashSet::new(),
                 want_grab: true,
                 ingame: true,
                 stats: false,
@@ -1202,12 +1518,24 @@ impl Hud {
                 auto_walk: false,
                 camera_clamp: false,
                 prompt_dialog: None,
-                location_markers: MapMarkers::default(),
+                dialogue: None,
+                minigame: None,
+                writing: None,
+                travel_portal: None,
+                location_markers: MapMarkers {
+                    placed: global_state
+                        .profile
+                        .get_placed_markers(server, character_id),
+                    ..MapMarkers::default()
+                },
                 trade_amount_input_key: None,
+                cutscene: false,
+                photo_mode: false,
             },
             to_focus: None,
             //never_show: false,
             force_ungrab: false,
+            was_dead: false,
             force_chat_input: None,
             force_chat_cursor: None,
             tab_complete: None,
@@ -1224,6 +1552,11 @@ impl Hud {
                 block_floaters: Vec::new(),
             },
             map_drag: Vec2::zero(),
+            fog_of_war,
+            damage_directions: DamageDirections::default(),
+            subtitles: SubtitleList::default(),
+            combat_log: CombatLog::default(),
+            chat_history,
         }
     }
 
@@ -1231,10 +1564,14 @@ impl Hud {
         self.show.prompt_dialog = Some(prompt_dialog);
     }
 
-    pub fn update_fonts(&mut self, i18n: &Localization) {
+    pub fn update_fonts(&mut self, i18n: &Localization, text_scale: f32) {
         self.fonts = Fonts::load(i18n.fonts(), &mut self.ui).expect("Impossible to load fonts!");
+        self.fonts.set_text_scale(text_scale);
     }
 
+    /// Scales the text of the HUD, independently from the UI scale
+    pub fn set_text_scale(&mut self, text_scale: f32) { self.fonts.set_text_scale(text_scale); }
+
     #[allow(clippy::single_match)] // TODO: Pending review in #587
     fn update_layout(
         &mut self,
@@ -1251,6 +1588,12 @@ impl Hud {
         if global_state.settings.interface.map_show_voxel_map {
             self.voxel_minimap.maintain(client, &mut self.ui);
         }
+        if let Some(pos) = client.position() {
+            let explored = self.fog_of_war.maintain(pos.xy(), &mut self.ui);
+            if !explored.is_empty() {
+                events.push(Event::Explored(explored));
+            }
+        }
         let (ref mut ui_widgets, ref mut item_tooltip_manager, ref mut tooltip_manager) =
             &mut self.ui.set_widgets();
         // self.ui.set_item_widgets(); pulse time for pulsating elements
@@ -1260,6 +1603,28 @@ impl Hud {
         let version = common::util::DISPLAY_VERSION_LONG.clone();
         let i18n = &global_state.i18n.read();
         let key_layout = &global_state.window.key_layout;
+        let health_colors = HealthColors::of(global_state.settings.accessibility.color_palette);
+
+        // Photo mode replaces the rest of the HUD with its own window
+        if self.show.photo_mode {
+            for event in PhotoModeWindow::new(
+                &self.imgs,
+                &self.fonts,
+                i18n,
+                &global_state.settings.photo_mode,
+            )
+            .set(self.ids.photo_mode, ui_widgets)
+            {
+                events.push(match event {
+                    photo_mode::Event::ChangeSetting(change) => {
+                        Event::SettingsChange(change.into())
+                    },
+                    photo_mode::Event::TakePhoto => Event::TakePhoto,
+                    photo_mode::Event::Close => Event::LeavePhotoMode,
+                });
+            }
+            return events;
+        }
 
         if self.show.ingame {
             prof_span!("ingame elements");
@@ -1338,7 +1703,10 @@ impl Hud {
                 // Hurt Frame
                 let hp_percentage = health.current() / health.maximum() * 100.0;
                 self.hp_pulse += dt.as_secs_f32() * 10.0 / hp_percentage.max(3.0).min(7.0);
-                if hp_percentage < 10.0 && !health.is_dead {
+                if hp_percentage < 10.0
+                    && !health.is_dead
+                    && global_state.settings.interface.low_health_vignette
+                {
                     let hurt_fade = (self.hp_pulse).sin() * 0.5 + 0.6; //Animation timer
                     Image::new(self.imgs.hurt_bg)
                         .wh_of(ui_widgets.window)
@@ -1347,6 +1715,30 @@ impl Hud {
                         .color(Some(Color::Rgba(1.0, 1.0, 1.0, hurt_fade)))
                         .set(self.ids.hurt_bg, ui_widgets);
                 }
+                // Where the hits come from
+                self.damage_directions.maintain(dt.as_secs_f32());
+                if let Some(pos) = client.position() {
+                    DamageIndicators::new(
+                        &self.damage_directions,
+                        pos.xy(),
+                        camera.get_orientation().x,
+                    )
+                    .set(self.ids.damage_indicators, ui_widgets);
+                }
+                // Captions of the sounds around
+                self.subtitles.maintain(dt.as_secs_f32());
+                if global_state.settings.accessibility.subtitles {
+                    if let Some(pos) = client.position() {
+                        Subtitles::new(
+                            &self.subtitles,
+                            pos.xy(),
+                            camera.get_orientation().x,
+                            &self.fonts,
+                            i18n,
+                        )
+                        .set(self.ids.subtitles, ui_widgets);
+                    }
+                }
                 // Alpha Disclaimer
                 Text::new(&format!("Veloren {}", &version))
                     .font_id(self.fonts.cyri.conrod_id)
@@ -1363,7 +1755,39 @@ impl Hud {
                         .graphics_for(ui_widgets.window)
                         .color(Some(Color::Rgba(0.0, 0.0, 0.0, 1.0)))
                         .set(self.ids.death_bg, ui_widgets);
-                } // Crosshair
+                    if Button::image(self.imgs.button)
+                        .w_h(220.0, 40.0)
+                        .x_y_relative_to(ui_widgets.window, 0.0, -220.0)
+                        .hover_image(self.imgs.button_hover)
+                        .press_image(self.imgs.button_press)
+                        .label(&i18n.get_msg("hud-respawn_at_waypoint"))
+                        .label_y(conrod_core::position::Relative::Scalar(3.0))
+                        .label_color(TEXT_COLOR)
+                        .label_font_size(self.fonts.cyri.scale(18))
+                        .label_font_id(self.fonts.cyri.conrod_id)
+                        .set(self.ids.respawn_button, ui_widgets)
+                        .was_clicked()
+                    {
+                        events.push(Event::Respawn);
+                    }
+                }
+                // Fade in after sleeping through the night
+                if let Some(woke_up) = self.woke_up {
+                    let fade = (1.0
+                        - (woke_up.elapsed().as_secs_f32() - WAKE_UP_HOLD) / WAKE_UP_FADE)
+                        .min(1.0);
+                    if fade > 0.0 {
+                        Image::new(self.imgs.death_bg)
+                            .wh_of(ui_widgets.window)
+                            .middle_of(ui_widgets.window)
+                            .graphics_for(ui_widgets.window)
+                            .color(Some(Color::Rgba(0.0, 0.0, 0.0, fade)))
+                            .set(self.ids.wake_up_bg, ui_widgets);
+                    } else {
+                        self.woke_up = None;
+                    }
+                }
+                // Crosshair
                 let show_crosshair = (info.is_aiming || info.is_first_person) && !health.is_dead;
                 self.crosshair_opacity = Lerp::lerp(
                     self.crosshair_opacity,
@@ -1421,7 +1845,7 @@ impl Hud {
                 ) {
                     let player_font_col = |crit: bool| {
                         if crit {
-                            Rgb::new(1.0, 0.9, 0.0)
+                            health_colors.crit
                         } else {
                             Rgb::new(1.0, 0.1, 0.0)
                         }
@@ -1452,12 +1876,21 @@ impl Hud {
                             / health.maximum();
                         let hp_dmg_text = if floater.info.amount.abs() < 0.1 {
                             String::new()
-                        } else if global_state.settings.interface.sct_damage_rounding
-                            && floater.info.amount.abs() >= 1.0
-                        {
-                            format!("{:.0}", floater.info.amount.abs())
                         } else {
-                            format!("{:.1}", floater.info.amount.abs())
+                            let decimals = if global_state.settings.interface.sct_damage_rounding
+                                && floater.info.amount.abs() >= 1.0
+                            {
+                                0
+                            } else {
+                                1
+                            };
+                            i18n.get_msg_ctx("hud-sct-damage", &i18n::fluent_args! {
+                                "amount" => i18n::fluent_number(
+                                    floater.info.amount.abs() as f64,
+                                    decimals,
+                                ),
+                            })
+                            .into_owned()
                         };
                         let crit = floater.info.crit;
 
@@ -1516,7 +1949,8 @@ impl Hud {
                             .color(if floater.info.amount < 0.0 {
                                 Color::Rgba(font_col.r, font_col.g, font_col.b, hp_fade)
                             } else {
-                                Color::Rgba(0.1, 1.0, 0.1, hp_fade)
+                                let heal = health_colors.heal;
+                                Color::Rgba(heal.r, heal.g, heal.b, hp_fade)
                             })
                             .x_y(x, y)
                             .set(player_sct_id, ui_widgets);
@@ -1760,7 +2194,16 @@ impl Hud {
 
             // Push speech bubbles
             for msg in self.new_messages.iter() {
-                if let Some((bubble, uid)) = msg.to_bubble() {
+                if let Some((mut bubble, uid)) = msg.to_bubble() {
+                    // The length of NPC messages is only known once localized
+                    if let comp::chat::SpeechBubbleMessage::Localized(key, variation) =
+                        &bubble.message
+                    {
+                        bubble.timeout = now
+                            + comp::SpeechBubble::duration_for(
+                                &i18n.get_variation(key, *variation),
+                            );
+                    }
                     self.speech_bubbles.insert(uid, bubble);
                 }
             }
@@ -1839,6 +2282,7 @@ impl Hud {
                     active: true,
                     pickup_failed_pulse: self.failed_block_pickups.get(&pos).cloned(),
                 };
+                let sign_text = client.sign_texts().get(&pos);
                 let pos = pos.map(|e| e as f32 + 0.5);
                 let over_pos = pos + Vec3::unit_z() * 0.7;
 
@@ -1880,10 +2324,18 @@ impl Hud {
                             Interaction::Mine => {
                                 vec![(GameInput::Primary, i18n.get_msg("hud-mine").to_string())]
                             },
+                            Interaction::Sleep => {
+                                vec![(GameInput::Interact, i18n.get_msg("hud-sleep").to_string())]
+                            },
+                            Interaction::Write => {
+                                vec![(GameInput::Interact, i18n.get_msg("hud-write").to_string())]
+                            },
                         },
                     )
                     .set(overitem_id, ui_widgets);
-                } else if let Some(desc) = block.get_sprite().and_then(|s| get_sprite_desc(s, i18n))
+                } else if let Some(desc) = sign_text
+                    .map(|text| Cow::Borrowed(text.as_str()))
+                    .or_else(|| block.get_sprite().and_then(|s| get_sprite_desc(s, i18n)))
                 {
                     overitem::Overitem::new(
                         desc,
@@ -1895,7 +2347,15 @@ impl Hud {
                         overitem_properties,
                         self.pulse,
                         &global_state.window.key_layout,
-                        vec![(GameInput::Interact, i18n.get_msg("hud-use").to_string())],
+                        vec![(
+                            GameInput::Interact,
+                            i18n.get_msg(match interaction {
+                                Interaction::Sleep => "hud-sleep",
+                                Interaction::Write => "hud-write",
+                                _ => "hud-use",
+                            })
+                            .to_string(),
+                        )],
                     )
                     .x_y(0.0, 100.0)
                     .position_ingame(over_pos)
@@ -1942,9 +2402,118 @@ impl Hud {
                     .x_y(0.0, 100.0)
                     .position_ingame(over_pos)
                     .set(overitem_id, ui_widgets);
+                } else if let Some(portal) = client.state().read_component_cloned::<comp::Portal>(e)
+                {
+                    let overitem_id = overitem_walker.next(
+                        &mut self.ids.overitems,
+                        &mut ui_widgets.widget_id_generator(),
+                    );
+
+                    let overitem_properties = overitem::OveritemProperties {
+                        active: true,
+                        pickup_failed_pulse: None,
+                    };
+                    let pos = client
+                        .state()
+                        .ecs()
+                        .read_storage::<comp::Pos>()
+                        .get(e)
+                        .map_or(Vec3::zero(), |e| e.0);
+                    let over_pos = pos + Vec3::unit_z() * 1.5;
+
+                    overitem::Overitem::new(
+                        portal.name.into(),
+                        overitem::TEXT_COLOR,
+                        pos.distance_squared(player_pos),
+                        &self.fonts,
+                        i18n,
+                        &global_state.settings.controls,
+                        overitem_properties,
+                        self.pulse,
+                        &global_state.window.key_layout,
+                        vec![(
+                            GameInput::Interact,
+                            i18n.get_msg("hud-portal-use").to_string(),
+                        )],
+                    )
+                    .x_y(0.0, 100.0)
+                    .position_ingame(over_pos)
+                    .set(overitem_id, ui_widgets);
+                } else if let Some(minigame) =
+                    client.state().read_component_cloned::<comp::Minigame>(e)
+                {
+                    let overitem_id = overitem_walker.next(
+                        &mut self.ids.overitems,
+                        &mut ui_widgets.widget_id_generator(),
+                    );
+
+                    let overitem_properties = overitem::OveritemProperties {
+                        active: true,
+                        pickup_failed_pulse: None,
+                    };
+                    let pos = client
+                        .state()
+                        .ecs()
+                        .read_storage::<comp::Pos>()
+                        .get(e)
+                        .map_or(Vec3::zero(), |e| e.0);
+                    let over_pos = pos + Vec3::unit_z() * 1.5;
+                    let title = match minigame.game {
+                        comp::minigame::Game::ThreeInARow(_) => {
+                            i18n.get_msg("hud-minigame-three_in_a_row")
+                        },
+                    };
+
+                    overitem::Overitem::new(
+                        title,
+                        overitem::TEXT_COLOR,
+                        pos.distance_squared(player_pos),
+                        &self.fonts,
+                        i18n,
+                        &global_state.settings.controls,
+                        overitem_properties,
+                        self.pulse,
+                        &global_state.window.key_layout,
+                        vec![(
+                            GameInput::Interact,
+                            i18n.get_msg("hud-minigame-play").to_string(),
+                        )],
+                    )
+                    .x_y(0.0, 100.0)
+                    .position_ingame(over_pos)
+                    .set(overitem_id, ui_widgets);
                 }
             }
 
+            // Text written on the signs around, the targeted one being shown above
+            let targeted_sign = match interactable {
+                Some(Interactable::Block(_, pos, Interaction::Write)) => Some(pos),
+                _ => None,
+            };
+            let terrain = client.state().terrain();
+            let mut sign_walker = self.ids.sign_texts.walk();
+            for (pos, text) in client.sign_texts().iter().filter(|(pos, _)| {
+                Some(**pos) != targeted_sign
+                    && pos.map(|e| e as f32 + 0.5).distance_squared(player_pos)
+                        < SIGN_READ_RANGE.powi(2)
+                    && terrain.get(**pos).ok().and_then(|block| block.get_sprite())
+                        == Some(SpriteKind::Sign)
+            }) {
+                let sign_id = sign_walker.next(
+                    &mut self.ids.sign_texts,
+                    &mut ui_widgets.widget_id_generator(),
+                );
+                Text::new(text)
+                    .w(200.0)
+                    .wrap_by_word()
+                    .center_justify()
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(14))
+                    .color(TEXT_COLOR)
+                    .position_ingame(pos.map(|e| e as f32 + 0.5) + Vec3::unit_z() * 1.2)
+                    .set(sign_id, ui_widgets);
+            }
+
             let speech_bubbles = &self.speech_bubbles;
 
             // Render overhead name tags and health bars
@@ -2015,6 +2584,18 @@ impl Hud {
                         // text radius relative to other NPCs
                         let is_merchant = stats.name == "Merchant" && player.is_none();
                         let dist_sqr = pos.distance_squared(player_pos);
+                        let range = if in_group {
+                            NAMETAG_GROUP_RANGE
+                        } else if is_merchant {
+                            NAMETAG_MERCHANT_RANGE
+                        } else if hpfl
+                            .time_since_last_dmg_by_me
+                            .map_or(false, |t| t < NAMETAG_DMG_TIME)
+                        {
+                            NAMETAG_DMG_RANGE
+                        } else {
+                            NAMETAG_RANGE
+                        };
                         // Determine whether to display nametag and healthbar based on whether the
                         // entity has been damaged, is targeted/selected, or is in your group
                         // Note: even if this passes the healthbar can be hidden in some cases if it
@@ -2025,20 +2606,7 @@ impl Hud {
                                 || health.map_or(true, overhead::should_show_healthbar)
                                 || in_group
                                 || is_merchant)
-                            && dist_sqr
-                                < (if in_group {
-                                    NAMETAG_GROUP_RANGE
-                                } else if is_merchant {
-                                    NAMETAG_MERCHANT_RANGE
-                                } else if hpfl
-                                    .time_since_last_dmg_by_me
-                                    .map_or(false, |t| t < NAMETAG_DMG_TIME)
-                                {
-                                    NAMETAG_DMG_RANGE
-                                } else {
-                                    NAMETAG_RANGE
-                                })
-                                .powi(2);
+                            && dist_sqr < range.powi(2);
 
                         let info = display_overhead_info.then(|| overhead::Info {
                             name: &stats.name,
@@ -2054,6 +2622,27 @@ impl Hud {
                             } else {
                                 0.0
                             },
+                            is_player: player.is_some(),
+                            hostility: overhead::Hostility::of(alignment, |owner| {
+                                Some(*owner) == client.uid()
+                                    || client.group_members().contains_key(owner)
+                            }),
+                            damage_flash: hpfl
+                                .floaters
+                                .iter()
+                                .rev()
+                                .find(|floater| floater.info.amount < 0.0)
+                                .filter(|_| !global_state.settings.accessibility.reduce_flashes)
+                                .map_or(0.0, |floater| {
+                                    (1.0 - floater.jump_timer / NAMETAG_DMG_FLASH_TIME).max(0.0)
+                                }),
+                            opacity: if global_state.settings.interface.nameplate_distance_fade {
+                                let faded = (dist_sqr.sqrt() / range - NAMETAG_FADE_START)
+                                    / (1.0 - NAMETAG_FADE_START);
+                                (1.0 - faded).clamp(NAMETAG_MIN_OPACITY, 1.0)
+                            } else {
+                                1.0
+                            },
                         });
                         // Only render bubble if nearby or if its me and setting is on
                         let bubble = if (dist_sqr < SPEECH_BUBBLE_RANGE.powi(2) && !is_me)
@@ -2083,9 +2672,20 @@ impl Hud {
                 // Speech bubble, name, level, and hp bars
                 overhead::Overhead::new(
                     info,
-                    bubble,
+                    bubble.map(|bubble| overhead::BubbleInfo {
+                        bubble,
+                        portrait: if global_state.settings.interface.speech_bubble_portrait {
+                            overhead::portrait(body, &self.imgs)
+                        } else {
+                            None
+                        },
+                        scale: 1.0
+                            - (1.0 - SPEECH_BUBBLE_MIN_SCALE)
+                                * (dist_sqr.sqrt() / SPEECH_BUBBLE_RANGE).min(1.0),
+                    }),
                     in_group,
                     &global_state.settings.interface,
+                    health_colors,
                     self.pulse,
                     i18n,
                     &global_state.settings.controls,
@@ -2152,7 +2752,7 @@ impl Hud {
                     // every 5
                     let font_col = |font_size: u32, crit: bool| {
                         if crit {
-                            Rgb::new(1.0, 0.9, 0.0)
+                            health_colors.crit
                         } else {
                             DAMAGE_COLORS[(font_size.saturating_sub(36) / 5).min(5) as usize]
                         }
@@ -2173,12 +2773,21 @@ impl Hud {
                             / health.map_or(1.0, |h| h.maximum());
                         let hp_dmg_text = if floater.info.amount.abs() < 0.1 {
                             String::new()
-                        } else if global_state.settings.interface.sct_damage_rounding
-                            && floater.info.amount.abs() >= 1.0
-                        {
-                            format!("{:.0}", floater.info.amount.abs())
                         } else {
-                            format!("{:.1}", floater.info.amount.abs())
+                            let decimals = if global_state.settings.interface.sct_damage_rounding
+                                && floater.info.amount.abs() >= 1.0
+                            {
+                                0
+                            } else {
+                                1
+                            };
+                            i18n.get_msg_ctx("hud-sct-damage", &i18n::fluent_args! {
+                                "amount" => i18n::fluent_number(
+                                    floater.info.amount.abs() as f64,
+                                    decimals,
+                                ),
+                            })
+                            .into_owned()
                         };
                         let crit = floater.info.crit;
                         // Timer sets text transparency
@@ -2235,7 +2844,8 @@ impl Hud {
                             .color(if floater.info.amount < 0.0 {
                                 Color::Rgba(font_col.r, font_col.g, font_col.b, fade)
                             } else {
-                                Color::Rgba(0.1, 1.0, 0.1, 1.0)
+                                let heal = health_colors.heal;
+                                Color::Rgba(heal.r, heal.g, heal.b, 1.0)
                             })
                             .position_ingame(ingame_pos)
                             .set(sct_id, ui_widgets);
@@ -2400,11 +3010,12 @@ impl Hud {
             // Weather
             let weather = client.weather_at_player();
             Text::new(&format!(
-                "Weather({kind}): {{cloud: {cloud:.2}, rain: {rain:.2}, wind: <{wind_x:.0}, \
-                 {wind_y:.0}>}}",
+                "Weather({kind}): {{cloud: {cloud:.2}, rain: {rain:.2}, snow: {snow:.2}, wind: \
+                 <{wind_x:.0}, {wind_y:.0}>}}",
                 kind = weather.get_kind(),
                 cloud = weather.cloud,
                 rain = weather.rain,
+                snow = weather.snow,
                 wind_x = weather.wind.x,
                 wind_y = weather.wind.y
             ))
@@ -2677,6 +3288,50 @@ impl Hud {
             }
         }
 
+        // Cardinal directions and direction of the markers
+        if global_state.settings.interface.compass_show {
+            Compass::new(
+                client,
+                &self.imgs,
+                &self.fonts,
+                camera.get_orientation(),
+                &self.show.location_markers,
+                global_state.settings.interface.compass_opacity,
+            )
+            .set(self.ids.compass, ui_widgets);
+        }
+
+        // Warning when getting close to the world border or beyond it
+        if let Some(distance) = client
+            .position()
+            .and_then(|pos| ecs.read_resource::<WorldBorder>().distance(pos.xy()))
+            .filter(|distance| *distance > -WORLD_BORDER_WARN_DISTANCE)
+        {
+            let (key, alpha) = if distance > 0.0 {
+                ("hud-world_border-beyond", 1.0)
+            } else {
+                (
+                    "hud-world_border-near",
+                    1.0 + distance / WORLD_BORDER_WARN_DISTANCE,
+                )
+            };
+            Text::new(&i18n.get_msg(key))
+                .mid_top_with_margin_on(ui_widgets.window, 70.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(18))
+                .color(CRITICAL_HP_COLOR.alpha(alpha))
+                .set(self.ids.world_border_warning, ui_widgets);
+        }
+
+        // Quest objectives
+        if let Some(quest_log) = ecs
+            .read_storage::<comp::QuestLog>()
+            .get(client.entity())
+            .filter(|quest_log| !quest_log.active.is_empty())
+        {
+            QuestTracker::new(quest_log, &self.fonts, i18n).set(self.ids.quest_tracker, ui_widgets);
+        }
+
         if let Some(prompt_dialog_settings) = &self.show.prompt_dialog {
             // Prompt Dialog
             match PromptDialog::new(
@@ -2706,6 +3361,108 @@ impl Hud {
             }
         }
 
+        // Dialogue with a NPC, closed once they are out of range
+        if let Some(npc) = self.show.dialogue {
+            let ecs = client.state().ecs();
+            let choices = client
+                .dialogue()
+                .filter(|(uid, _)| ecs.entity_from_uid(uid.0) == Some(npc))
+                .map(|(_, choices)| choices);
+            let positions = ecs.read_storage::<comp::Pos>();
+            let npc_name = ecs
+                .read_storage::<comp::Stats>()
+                .get(npc)
+                .map(|stats| stats.name.clone());
+            let in_range = positions
+                .get(npc)
+                .zip(positions.get(info.viewpoint_entity))
+                .map_or(false, |(npc_pos, pos)| {
+                    npc_pos.0.distance_squared(pos.0) < MAX_TRADE_RANGE.powi(2)
+                });
+            match (npc_name, choices) {
+                (Some(npc_name), Some(choices)) if in_range => {
+                    if let Some(dialogue::Event::Choose(choice)) =
+                        Dialogue::new(&self.imgs, &self.fonts, i18n, &npc_name, choices)
+                            .set(self.ids.dialogue, ui_widgets)
+                    {
+                        events.push(Event::Talk(choice));
+                    }
+                },
+                (_, Some(_)) => {
+                    events.push(Event::CloseDialogue);
+                    self.show.close_dialogue();
+                },
+                _ => self.show.close_dialogue(),
+            }
+        }
+
+        // Minigame played at a table, closed once it is out of reach. Players
+        // leave their seat when closing the window
+        if let Some(entity) = self.show.minigame {
+            let ecs = client.state().ecs();
+            let positions = ecs.read_storage::<comp::Pos>();
+            let minigame = ecs.read_storage::<comp::Minigame>().get(entity).cloned();
+            let in_range = positions
+                .get(entity)
+                .zip(positions.get(info.viewpoint_entity))
+                .map_or(false, |(minigame_pos, pos)| {
+                    minigame_pos.0.distance_squared(pos.0) < MAX_PICKUP_RANGE.powi(2)
+                });
+            let own_seat = minigame
+                .as_ref()
+                .zip(client.uid())
+                .and_then(|(minigame, uid)| minigame.seat_of(uid));
+            match minigame {
+                Some(minigame) if in_range => {
+                    let seat_names = minigame
+                        .seats
+                        .iter()
+                        .map(|seat| {
+                            seat.and_then(|uid| client.player_list().get(&uid))
+                                .map(|info| info.player_alias.clone())
+                        })
+                        .collect::<Vec<_>>();
+                    match MinigameWindow::new(
+                        &self.imgs,
+                        &self.fonts,
+                        i18n,
+                        &minigame,
+                        &seat_names,
+                        own_seat,
+                    )
+                    .set(self.ids.minigame, ui_widgets)
+                    {
+                        Some(minigame::Event::Act(action)) => {
+                            events.push(Event::Minigame(entity, action))
+                        },
+                        Some(minigame::Event::Close) => {
+                            if own_seat.is_some() {
+                                events.push(Event::Minigame(entity, comp::MinigameAction::Leave));
+                            }
+                            self.show.close_minigame();
+                        },
+                        None => {},
+                    }
+                },
+                _ => self.show.close_minigame(),
+            }
+        }
+
+        // Writing on a sign or in a book
+        if let Some((writable, text)) = self.show.writing.clone() {
+            match Writing::new(&self.imgs, &self.fonts, i18n, writable, &text)
+                .set(self.ids.writing, ui_widgets)
+            {
+                Some(writing::Event::Edit(text)) => self.show.writing = Some((writable, text)),
+                Some(writing::Event::Write) => {
+                    events.push(Event::Write(writable, text));
+                    self.show.close_writing();
+                },
+                Some(writing::Event::Close) => self.show.close_writing(),
+                None => {},
+            }
+        }
+
         // Skillbar
         // Get player stats
         let ecs = client.state().ecs();
@@ -2715,6 +3472,7 @@ impl Hud {
         let energies = ecs.read_storage::<comp::Energy>();
         let skillsets = ecs.read_storage::<comp::SkillSet>();
         let active_abilities = ecs.read_storage::<comp::ActiveAbilities>();
+        let ability_cooldowns = ecs.read_storage::<comp::AbilityCooldowns>();
         let bodies = ecs.read_storage::<comp::Body>();
         let poises = ecs.read_storage::<comp::Poise>();
         // Combo floater stuffs
@@ -2744,6 +3502,7 @@ impl Hud {
                 energy,
                 skillset,
                 active_abilities.get(entity),
+                ability_cooldowns.get(entity),
                 body,
                 //&character_state,
                 self.pulse,
@@ -2833,6 +3592,7 @@ impl Hud {
                 &msm,
                 self.pulse,
                 &mut self.show,
+                global_state,
             )
             .set(self.ids.trade, ui_widgets)
             {
@@ -2988,6 +3748,7 @@ impl Hud {
         if global_state.settings.interface.toggle_chat {
             for event in Chat::new(
                 &mut self.new_messages,
+                &mut self.chat_history,
                 client,
                 global_state,
                 self.pulse,
@@ -3023,6 +3784,9 @@ impl Hud {
                         self.show.settings_tab = SettingsTab::Chat;
                         self.show.settings(true);
                     },
+                    chat::Event::SetLocationMarker(wpos) => {
+                        events.push(Event::MapMarkerEvent(MapMarkerChange::Update(wpos)));
+                    },
                 }
             }
         }
@@ -3065,6 +3829,7 @@ impl Hud {
                 i18n,
                 client.server_view_distance_limit(),
                 fps as f32,
+                info.adaptive_quality_level,
             )
             .set(self.ids.settings_window, ui_widgets)
             {
@@ -3114,6 +3879,7 @@ impl Hud {
                 info.selected_entity,
                 &self.rot_imgs,
                 tooltip_manager,
+                global_state.settings.interface.streamer_mode,
             )
             .set(self.ids.social_window, ui_widgets)
             {
@@ -3134,10 +3900,81 @@ impl Hud {
                     social::Event::SearchPlayers(search_key) => {
                         self.show.search_social_players(search_key)
                     },
+                    #[cfg(feature = "voice-chat")]
+                    social::Event::ToggleVoiceMute(uid) => {
+                        if !self.show.muted_voices.remove(&uid) {
+                            self.show.muted_voices.insert(uid);
+                        }
+                    },
                 }
             }
         }
 
+        // Guild Window
+        if self.show.guild {
+            for event in Guild::new(client, &self.imgs, &self.fonts, i18n, info.selected_entity)
+                .set(self.ids.guild_window, ui_widgets)
+            {
+                match event {
+                    guild::Event::Close => {
+                        self.show.guild(false);
+                        if !self.show.bag {
+                            self.show.want_grab = true;
+                            self.force_ungrab = false;
+                        } else {
+                            self.force_ungrab = true
+                        };
+                    },
+                    guild::Event::Invite(uid) => events.push(Event::InviteToGuild(uid)),
+                    guild::Event::Command(name, args) => {
+                        events.push(Event::SendCommand(name, args))
+                    },
+                }
+            }
+        }
+
+        // Combat Log
+        if self.show.combat_log {
+            for event in CombatLogWindow::new(&self.combat_log, &self.imgs, &self.fonts, i18n)
+                .set(self.ids.combat_log_window, ui_widgets)
+            {
+                match event {
+                    combat_log::Event::Close => {
+                        self.show.combat_log(false);
+                        if !self.show.bag {
+                            self.show.want_grab = true;
+                            self.force_ungrab = false;
+                        } else {
+                            self.force_ungrab = true
+                        };
+                    },
+                    combat_log::Event::ToggleCategory(category) => self.combat_log.toggle(category),
+                    combat_log::Event::Clear => self.combat_log.clear(),
+                }
+            }
+        }
+
+        // Quest Log
+        if self.show.quest_log {
+            let quest_logs = ecs.read_storage::<comp::QuestLog>();
+            if let Some(quest_log::Event::Close) = QuestLogWindow::new(
+                quest_logs.get(client.entity()),
+                &self.imgs,
+                &self.fonts,
+                i18n,
+            )
+            .set(self.ids.quest_log_window, ui_widgets)
+            {
+                self.show.quest_log(false);
+                if !self.show.bag {
+                    self.show.want_grab = true;
+                    self.force_ungrab = false;
+                } else {
+                    self.force_ungrab = true
+                };
+            }
+        }
+
         // Diary
         if self.show.diary {
             let entity = info.viewpoint_entity;
@@ -3211,6 +4048,11 @@ impl Hud {
                 tooltip_manager,
                 &self.show.location_markers,
                 self.map_drag,
+                self.show
+                    .travel_portal
+                    .and_then(|portal| client.state().read_component_cloned::<comp::Portal>(portal))
+                    .map(|portal| portal.id),
+                &self.fog_of_war,
             )
             .set(self.ids.map, ui_widgets)
             {
@@ -3237,6 +4079,37 @@ impl Hud {
                         self.show.location_markers.owned = None;
                         events.push(Event::MapMarkerEvent(MapMarkerChange::Remove));
                     },
+                    map::Event::PlaceMarker(name) => {
+                        // The waypoint turns into the named marker
+                        if let Some(wpos) = self.show.location_markers.owned.take() {
+                            let placed = &mut self.show.location_markers.placed;
+                            let default_name = i18n.get_msg_ctx(
+                                "hud-map-default_marker_name",
+                                &i18n::fluent_args! {
+                                    "number" => placed.len() + 1,
+                                },
+                            );
+                            placed.place(wpos, &name, &default_name);
+                            events.push(Event::MapMarkerEvent(MapMarkerChange::Remove));
+                            events.push(Event::ChangePlacedMarkers(placed.clone()));
+                        }
+                    },
+                    map::Event::RemovePlacedMarker(index) => {
+                        let placed = &mut self.show.location_markers.placed;
+                        placed.remove(index);
+                        events.push(Event::ChangePlacedMarkers(placed.clone()));
+                    },
+                    map::Event::TogglePlacedMarkerShared(index) => {
+                        let placed = &mut self.show.location_markers.placed;
+                        placed.toggle_shared(index);
+                        events.push(Event::ChangePlacedMarkers(placed.clone()));
+                    },
+                    map::Event::UsePortal(destination) => {
+                        if let Some(portal) = self.show.travel_portal {
+                            events.push(Event::UsePortal(portal, destination));
+                        }
+                        self.show.map(false);
+                    },
                 }
             }
         } else {
@@ -3322,6 +4195,33 @@ impl Hud {
                 .set(self.ids.auto_walk_txt, ui_widgets);
         }
 
+        // Training dummy damage meter, shown for a while after the last hit
+        const DAMAGE_REPORT_DURATION: Duration = Duration::from_secs(6);
+        if let Some((report, _)) = self
+            .damage_report
+            .filter(|(_, reported_at)| reported_at.elapsed() < DAMAGE_REPORT_DURATION)
+        {
+            let msg = i18n.get_msg_ctx("hud-damage_report", &i18n::fluent_args! {
+                "dps" => format!("{:.1}", report.dps),
+                "window" => format!("{:.0}", report.window),
+                "total" => format!("{:.0}", report.total),
+                "hits" => report.hits,
+            });
+            Text::new(&msg)
+                .color(TEXT_BG)
+                .mid_top_with_margin_on(ui_widgets.window, indicator_offset)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(20))
+                .set(self.ids.damage_report_bg, ui_widgets);
+            indicator_offset += 30.0;
+            Text::new(&msg)
+                .color(TEXT_COLOR)
+                .top_left_with_margins_on(self.ids.damage_report_bg, -1.0, -1.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(20))
+                .set(self.ids.damage_report_txt, ui_widgets);
+        }
+
         // Camera clamp indicator
         if let Some(cameraclamp_key) = global_state
             .settings
@@ -3925,7 +4825,12 @@ impl Hud {
 
     pub fn new_message(&mut self, msg: comp::ChatMsg) { self.new_messages.push_back(msg); }
 
-    pub fn new_notification(&mut self, msg: Notification) { self.new_notifications.push_back(msg); }
+    pub fn new_notification(&mut self, msg: Notification) {
+        if let Notification::WokeUp = msg {
+            self.woke_up = Some(Instant::now());
+        }
+        self.new_notifications.push_back(msg);
+    }
 
     pub fn set_scaling_mode(&mut self, scale_mode: ScaleMode) {
         self.ui.set_scaling_mode(scale_mode);
@@ -4144,6 +5049,18 @@ impl Hud {
                         self.show.toggle_social();
                         true
                     },
+                    GameInput::Guild if state => {
+                        self.show.toggle_guild();
+                        true
+                    },
+                    GameInput::CombatLog if state => {
+                        self.show.toggle_combat_log();
+                        true
+                    },
+                    GameInput::QuestLog if state => {
+                        self.show.toggle_quest_log();
+                        true
+                    },
                     GameInput::Crafting if state => {
                         self.show.toggle_crafting();
                         true
@@ -4278,13 +5195,23 @@ impl Hud {
             });
 
         // Optimization: skip maintaining UI when it's off.
-        if !self.show.ui {
+        self.show.cutscene = global_state.cutscene.is_some();
+        if !self.show.ui || self.show.cutscene {
             return std::mem::take(&mut self.events);
         }
 
         if let Some(maybe_id) = self.to_focus.take() {
             self.ui.focus_widget(maybe_id);
         }
+        let is_dead = client
+            .current::<comp::Health>()
+            .map_or(false, |health| health.is_dead);
+        if is_dead != self.was_dead {
+            self.was_dead = is_dead;
+            global_state
+                .window
+                .grab_cursor(!is_dead && !self.force_ungrab && self.show.want_grab);
+        }
         let events = self.update_layout(
             client,
             global_state,
@@ -4321,7 +5248,7 @@ impl Hud {
     pub fn render<'a>(&'a self, drawer: &mut UiDrawer<'_, 'a>) {
         span!(_guard, "render", "Hud::render");
         // Don't show anything if the UI is toggled off.
-        if self.show.ui {
+        if self.show.ui && !self.show.cutscene {
             self.ui.render(drawer);
         }
     }
@@ -4330,8 +5257,25 @@ impl Hud {
 
     pub fn auto_walk(&mut self, auto_walk: bool) { self.show.auto_walk = auto_walk; }
 
+    pub fn photo_mode(&mut self, photo_mode: bool) { self.show.photo_mode = photo_mode; }
+
+    /// Whether the dialogue window of an NPC is open
+    pub fn in_dialogue(&self) -> bool { self.show.dialogue.is_some() }
+
+    #[cfg(feature = "voice-chat")]
+    pub fn voice_muted(&self, uid: Uid) -> bool { self.show.muted_voices.contains(&uid) }
+
+    pub fn new_damage_report(&mut self, report: comp::DamageReport) {
+        self.damage_report = Some((report, Instant::now()));
+    }
+
     pub fn camera_clamp(&mut self, camera_clamp: bool) { self.show.camera_clamp = camera_clamp; }
 
+    /// Named markers the player shares with their group
+    pub fn shared_map_markers(&self) -> Vec<NamedMapMarker> {
+        self.show.location_markers.placed.shared()
+    }
+
     pub fn handle_outcome(
         &mut self,
         outcome: &Outcome,
@@ -4339,6 +5283,11 @@ impl Hud {
         global_state: &GlobalState,
     ) {
         let interface = &global_state.settings.interface;
+        if global_state.settings.accessibility.subtitles {
+            if let Some(pos) = client.position() {
+                self.subtitles.handle_outcome(outcome, pos);
+            }
+        }
         match outcome {
             Outcome::ExpChange { uid, exp, xp_pools } => {
                 let ecs = client.state().ecs();
@@ -4416,6 +5365,44 @@ impl Hud {
                 let me = client.entity();
                 let my_uid = uids.get(me);
 
+                if let Some(&my_uid) = my_uid {
+                    let by = info.by.map(|by| by.uid());
+                    let amount = info.amount;
+                    let event = if info.target == my_uid {
+                        let source = by.and_then(|by| name_of(client, by));
+                        if amount < 0.0 {
+                            Some(CombatEvent::DamageTaken { source, amount })
+                        } else {
+                            Some(CombatEvent::HealingReceived { source, amount })
+                        }
+                    } else if by == Some(my_uid) {
+                        let target = name_of(client, info.target);
+                        if amount < 0.0 {
+                            Some(CombatEvent::DamageDealt { target, amount })
+                        } else {
+                            Some(CombatEvent::HealingDone { target, amount })
+                        }
+                    } else {
+                        None
+                    };
+                    if let Some(event) = event.filter(|_| amount != 0.0) {
+                        self.combat_log.record(event);
+                    }
+                }
+
+                if interface.damage_indicators
+                    && info.amount < 0.0
+                    && my_uid.map_or(false, |&uid| info.target == uid)
+                {
+                    if let Some(pos) = info
+                        .by
+                        .and_then(|by| ecs.entity_from_uid(by.uid().0))
+                        .and_then(|attacker| ecs.read_storage::<comp::Pos>().get(attacker).copied())
+                    {
+                        self.damage_directions.hit_from(pos.0.xy());
+                    }
+                }
+
                 if let Some(entity) = ecs.entity_from_uid(info.target.0) {
                     if let Some(floater_list) = hp_floater_lists.get_mut(entity) {
                         let hit_me = my_uid.map_or(false, |&uid| {
@@ -4491,11 +5478,36 @@ impl Hud {
                     }
                 }
             },
+            Outcome::Death { uid, by, .. } => {
+                let my_uid = client.uid();
+                if my_uid == Some(*uid) {
+                    self.combat_log.record(CombatEvent::Death {
+                        killer: by.and_then(|by| name_of(client, by)),
+                    });
+                } else if by.is_some() && *by == my_uid {
+                    self.combat_log.record(CombatEvent::Kill {
+                        victim: name_of(client, *uid),
+                    });
+                }
+            },
 
             _ => {},
         }
     }
 }
+/// Name of the player or creature, if the client knows about it
+fn name_of(client: &Client, uid: Uid) -> Option<String> {
+    client
+        .player_list()
+        .get(&uid)
+        .map(|info| info.player_alias.clone())
+        .or_else(|| {
+            let ecs = client.state().ecs();
+            let entity = ecs.entity_from_uid(uid.0)?;
+            let stats = ecs.read_storage::<comp::Stats>();
+            stats.get(entity).map(|stats| stats.name.clone())
+        })
+}
 // Get item qualities of equipped items and assign a tooltip title/frame color
 pub fn get_quality_col<I: ItemDesc + ?Sized>(item: &I) -> Color {
     match item.quality() {
@@ -4570,6 +5582,7 @@ pub fn get_buff_image(buff: BuffKind, imgs: &Imgs) -> conrod_core::image::Id {
         BuffKind::ProtectingWard => imgs.buff_dmg_red_0,
         BuffKind::Frenzied { .. } => imgs.buff_frenzy_0,
         BuffKind::Hastened { .. } => imgs.buff_haste_0,
+        BuffKind::Rested { .. } => imgs.buff_healthplus_0,
         //  Debuffs
         BuffKind::Bleeding { .. } => imgs.debuff_bleed_0,
         BuffKind::Cursed { .. } => imgs.debuff_skull_0,
@@ -4597,6 +5610,7 @@ pub fn get_buff_title(buff: BuffKind, localized_strings: &Localization) -> Cow<s
         BuffKind::ProtectingWard => localized_strings.get_msg("buff-title-protectingward"),
         BuffKind::Frenzied => localized_strings.get_msg("buff-title-frenzied"),
         BuffKind::Hastened => localized_strings.get_msg("buff-title-hastened"),
+        BuffKind::Rested => localized_strings.get_msg("buff-title-rested"),
         // Debuffs
         BuffKind::Bleeding { .. } => localized_strings.get_msg("buff-title-bleed"),
         BuffKind::Cursed { .. } => localized_strings.get_msg("buff-title-cursed"),
@@ -4630,6 +5644,7 @@ pub fn get_buff_desc(buff: BuffKind, data: BuffData, localized_strings: &Localiz
         BuffKind::ProtectingWard => localized_strings.get_msg("buff-desc-protectingward"),
         BuffKind::Frenzied => localized_strings.get_msg("buff-desc-frenzied"),
         BuffKind::Hastened => localized_strings.get_msg("buff-desc-hastened"),
+        BuffKind::Rested => localized_strings.get_msg("buff-desc-rested"),
         // Debuffs
         BuffKind::Bleeding { .. } => localized_strings.get_msg("buff-desc-bleed"),
         BuffKind::Cursed { .. } => localized_strings.get_msg("buff-desc-cursed"),
@@ -4655,6 +5670,7 @@ pub fn get_sprite_desc(sprite: SpriteKind, localized_strings: &Localization) ->
         SpriteKind::SpinningWheel => "hud-crafting-spinning_wheel",
         SpriteKind::TanningRack => "hud-crafting-tanning_rack",
         SpriteKind::DismantlingBench => "hud-crafting-salvaging_station",
+        SpriteKind::Bed => "common-sprite-bed",
         SpriteKind::ChestBuried
         | SpriteKind::Chest
         | SpriteKind::CoralChest
@@ -1,7 +1,7 @@
 use super::{
-    cr_color, img_ids::Imgs, DEFAULT_NPC, ENEMY_HP_COLOR, FACTION_COLOR, GROUP_COLOR, GROUP_MEMBER,
-    HP_COLOR, LOW_HP_COLOR, QUALITY_EPIC, REGION_COLOR, SAY_COLOR, STAMINA_COLOR, TELL_COLOR,
-    TEXT_BG, TEXT_COLOR,
+    cr_color, img_ids::Imgs, HealthColors, DEFAULT_NPC, FACTION_COLOR, FRIENDLY_NPC, GROUP_COLOR,
+    GROUP_MEMBER, GUILD_COLOR, HOSTILE_NPC, QUALITY_EPIC, REGION_COLOR, SAY_COLOR, STAMINA_COLOR,
+    TELL_COLOR, TEXT_BG, TEXT_COLOR,
 };
 use crate::{
     game_input::GameInput,
@@ -9,9 +9,15 @@ use crate::{
     settings::{ControlSettings, InterfaceSettings},
     ui::{fonts::Fonts, Ingameable},
 };
-use common::comp::{Buffs, Energy, Health, SpeechBubble, SpeechBubbleType};
+use common::{
+    comp::{
+        humanoid::{BodyType, Species},
+        Alignment, Body, Buffs, Emote, Energy, Health, SpeechBubble, SpeechBubbleType,
+    },
+    uid::Uid,
+};
 use conrod_core::{
-    color,
+    color, image,
     position::Align,
     widget::{self, Image, Rectangle, RoundedRectangle, Text},
     widget_ids, Color, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
@@ -36,6 +42,8 @@ widget_ids! {
         speech_bubble_bottom_right,
         speech_bubble_tail,
         speech_bubble_icon,
+        speech_bubble_emote,
+        speech_bubble_portrait,
 
         // Name
         name_bg,
@@ -45,6 +53,7 @@ widget_ids! {
         level,
         level_skull,
         health_bar,
+        health_flash,
         decay_bar,
         health_bar_bg,
         health_txt,
@@ -69,6 +78,44 @@ pub struct Info<'a> {
     pub buffs: &'a Buffs,
     pub energy: Option<&'a Energy>,
     pub combat_rating: f32,
+    /// Whether the entity is controlled by a player
+    pub is_player: bool,
+    pub hostility: Hostility,
+    /// Strength of the flash on the health bar after a hit, from 0 to 1
+    pub damage_flash: f32,
+    /// Lowered as the entity gets further away
+    pub opacity: f32,
+}
+
+/// A speech bubble and how it is shown
+#[derive(Clone, Copy)]
+pub struct BubbleInfo<'a> {
+    pub bubble: &'a SpeechBubble,
+    /// Face of the speaker, shown next to the bubble
+    pub portrait: Option<image::Id>,
+    /// Size of the text, lowered as the speaker gets further away
+    pub scale: f32,
+}
+
+/// How an entity is disposed towards the player, for the colour of its name
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hostility {
+    Friendly,
+    Neutral,
+    Hostile,
+}
+
+impl Hostility {
+    /// `is_friend` tells whether the owner of a pet is the player or one of
+    /// their group members
+    pub fn of(alignment: Option<&Alignment>, is_friend: impl Fn(&Uid) -> bool) -> Self {
+        match alignment {
+            Some(Alignment::Enemy) => Self::Hostile,
+            Some(Alignment::Npc | Alignment::Tame | Alignment::Passive) => Self::Friendly,
+            Some(Alignment::Owned(owner)) if is_friend(owner) => Self::Friendly,
+            Some(Alignment::Owned(_) | Alignment::Wild) | None => Self::Neutral,
+        }
+    }
 }
 
 /// Determines whether to show the healthbar
@@ -85,9 +132,10 @@ pub fn decayed_health_displayed(health: &Health) -> bool {
 #[derive(WidgetCommon)]
 pub struct Overhead<'a> {
     info: Option<Info<'a>>,
-    bubble: Option<&'a SpeechBubble>,
+    bubble: Option<BubbleInfo<'a>>,
     in_group: bool,
     settings: &'a InterfaceSettings,
+    health_colors: HealthColors,
     pulse: f32,
     i18n: &'a Localization,
     controls: &'a ControlSettings,
@@ -104,9 +152,10 @@ impl<'a> Overhead<'a> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         info: Option<Info<'a>>,
-        bubble: Option<&'a SpeechBubble>,
+        bubble: Option<BubbleInfo<'a>>,
         in_group: bool,
         settings: &'a InterfaceSettings,
+        health_colors: HealthColors,
         pulse: f32,
         i18n: &'a Localization,
         controls: &'a ControlSettings,
@@ -120,6 +169,7 @@ impl<'a> Overhead<'a> {
             bubble,
             in_group,
             settings,
+            health_colors,
             pulse,
             i18n,
             controls,
@@ -130,6 +180,29 @@ impl<'a> Overhead<'a> {
             common: widget::CommonBuilder::default(),
         }
     }
+
+    fn shows_name(&self, info: &Info) -> bool {
+        if info.is_player {
+            self.settings.nameplate_player_names
+        } else {
+            self.settings.nameplate_npc_names
+        }
+    }
+
+    /// The emote and the text left of the message of the speech bubble
+    fn bubble_text(&self) -> Option<(Option<Emote>, String)> {
+        self.bubble.map(|BubbleInfo { bubble, .. }| {
+            let localizer = |s: &str, i| -> String { self.i18n.get_variation(s, i).to_string() };
+            Emote::extract(&bubble.message(localizer))
+        })
+    }
+
+    fn shows_health_bar(&self, info: &Info) -> bool {
+        self.settings.nameplate_health_bars
+            && info.health.map_or(false, |health| {
+                should_show_healthbar(health) || decayed_health_displayed(health)
+            })
+    }
 }
 
 pub struct State {
@@ -140,15 +213,18 @@ impl<'a> Ingameable for Overhead<'a> {
     fn prim_count(&self) -> usize {
         // Number of conrod primitives contained in the overhead display. TODO maybe
         // this could be done automatically?
-        // - 2 Text::new for name
+        // - 2 Text::new for name, if shown
         //
         // If HP Info is shown:
-        // - 1 for level: either Text or Image <-- Not used currently, will be replaced
-        //   by something else
+        // - 1 for level: either Text or Image, if shown
         // - 3 for HP + fg + bg
         // - 1 for HP text
         // - If there's mana
         //   - 1 Rect::new for mana
+        // - If the health decayed
+        //   - 1 Image::new for the decayed part
+        // - If the entity was just hit
+        //   - 1 Rect::new for the flash
         // If there are Buffs
         // - 1 Alignment Rectangle
         // - 10 + 10 Buffs and Timer Overlays (only if there is no speech bubble)
@@ -156,25 +232,29 @@ impl<'a> Ingameable for Overhead<'a> {
         // - 2 Text::new for speech bubble
         // - 1 Image::new for icon
         // - 10 Image::new for speech bubble (9-slice + tail)
+        // - 1 Image::new for the emote, if there is one
+        // - 1 Image::new for the portrait, if there is one
         self.info.map_or(0, |info| {
-            2 + 1
+            self.shows_name(&info) as usize * 2
+                + 1
                 + if self.bubble.is_none() {
                     info.buffs.kinds.len().min(10) * 2
                 } else {
                     0
                 }
-                + if info.health.map_or(false, should_show_healthbar) {
-                    5 + if info.energy.is_some() { 1 } else { 0 }
-                } else {
-                    0
-                }
-                + if info.health.map_or(false, decayed_health_displayed) {
-                    1
+                + if self.shows_health_bar(&info) {
+                    4 + self.settings.nameplate_levels as usize
+                        + info.energy.is_some() as usize
+                        + info.health.map_or(false, decayed_health_displayed) as usize
+                        + (info.damage_flash > 0.0) as usize
                 } else {
                     0
                 }
                 + (!self.interaction_options.is_empty()) as usize * 2
-        }) + if self.bubble.is_some() { 13 } else { 0 }
+        }) + self.bubble_text().map_or(0, |(emote, _)| {
+            13 + emote.is_some() as usize
+                + self.bubble.map_or(false, |b| b.portrait.is_some()) as usize
+        })
     }
 }
 
@@ -196,14 +276,21 @@ impl<'a> Widget for Overhead<'a> {
         const BARSIZE: f64 = 2.0; // Scaling
         const MANA_BAR_HEIGHT: f64 = BARSIZE * 1.5;
         const MANA_BAR_Y: f64 = MANA_BAR_HEIGHT / 2.0;
-        if let Some(Info {
-            name,
-            health,
-            buffs,
-            energy,
-            combat_rating,
-        }) = self.info
+        if let Some(
+            info @ Info {
+                name,
+                health,
+                buffs,
+                energy,
+                combat_rating,
+                hostility,
+                damage_flash,
+                opacity,
+                ..
+            },
+        ) = self.info
         {
+            let fade = |color: Color| color.alpha(color.alpha() * opacity);
             // Used to set healthbar colours based on hp_percentage
             let hp_percentage = health.map_or(100.0, |h| {
                 f64::from(h.current() / h.base_max().max(h.maximum()) * 100.0)
@@ -246,8 +333,8 @@ impl<'a> Widget for Overhead<'a> {
             };
 
             let buff_ani = ((self.pulse * 4.0).cos() * 0.5 + 0.8) + 0.5; //Animation timer
-            let pulsating_col = Color::Rgba(1.0, 1.0, 1.0, buff_ani);
-            let norm_col = Color::Rgba(1.0, 1.0, 1.0, 1.0);
+            let pulsating_col = fade(Color::Rgba(1.0, 1.0, 1.0, buff_ani));
+            let norm_col = fade(Color::Rgba(1.0, 1.0, 1.0, 1.0));
             // Create Buff Widgets
             if self.bubble.is_none() {
                 state
@@ -300,46 +387,47 @@ impl<'a> Widget for Overhead<'a> {
                         })
                         .w_h(20.0, 20.0)
                         .middle_of(id)
+                        .color(Some(norm_col))
                         .set(timer_id, ui);
                     });
             }
+            let hostility_colors = self.settings.nameplate_hostility_colors;
             // Name
-            Text::new(name)
-                //Text::new(&format!("{} [{:?}]", name, combat_rating)) // <- Uncomment to debug combat ratings
-                .font_id(self.fonts.cyri.conrod_id)
-                .font_size(font_size)
-                .color(Color::Rgba(0.0, 0.0, 0.0, 1.0))
-                .x_y(-1.0, name_y)
-                .parent(id)
-                .set(state.ids.name_bg, ui);
-            Text::new(name)
-                //Text::new(&format!("{} [{:?}]", name, combat_rating)) // <- Uncomment to debug combat ratings
-                .font_id(self.fonts.cyri.conrod_id)
-                .font_size(font_size)
-                .color(if self.in_group {
-                    GROUP_MEMBER
-                /*} else if targets player { //TODO: Add a way to see if the entity is trying to attack the player, their pet(s) or a member of their group and recolour their nametag accordingly
-                DEFAULT_NPC*/
-                } else {
-                    DEFAULT_NPC
-                })
-                .x_y(0.0, name_y + 1.0)
-                .parent(id)
-                .set(state.ids.name, ui);
+            if self.shows_name(&info) {
+                Text::new(name)
+                    //Text::new(&format!("{} [{:?}]", name, combat_rating)) // <- Uncomment to debug combat ratings
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(font_size)
+                    .color(fade(Color::Rgba(0.0, 0.0, 0.0, 1.0)))
+                    .x_y(-1.0, name_y)
+                    .parent(id)
+                    .set(state.ids.name_bg, ui);
+                Text::new(name)
+                    //Text::new(&format!("{} [{:?}]", name, combat_rating)) // <- Uncomment to debug combat ratings
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(font_size)
+                    .color(fade(match hostility {
+                        _ if self.in_group => GROUP_MEMBER,
+                        Hostility::Hostile if hostility_colors => HOSTILE_NPC,
+                        Hostility::Friendly if hostility_colors => FRIENDLY_NPC,
+                        _ => DEFAULT_NPC,
+                    }))
+                    .x_y(0.0, name_y + 1.0)
+                    .parent(id)
+                    .set(state.ids.name, ui);
+            }
 
             match health {
-                Some(health)
-                    if should_show_healthbar(health) || decayed_health_displayed(health) =>
-                {
+                Some(health) if self.shows_health_bar(&info) => {
                     // Show HP Bar
                     let hp_ani = (self.pulse * 4.0/* speed factor */).cos() * 0.5 + 1.0; //Animation timer
-                    let crit_hp_color: Color = Color::Rgba(0.93, 0.59, 0.03, hp_ani);
+                    let crit_hp_color: Color = self.health_colors.low_hp.alpha(hp_ani);
                     let decayed_health = f64::from(1.0 - health.maximum() / health.base_max());
                     // Background
                     Image::new(if self.in_group {self.imgs.health_bar_group_bg} else {self.imgs.enemy_health_bg})
                         .w_h(84.0 * BARSIZE, 10.0 * BARSIZE)
                         .x_y(0.0, MANA_BAR_Y + 6.5) //-25.5)
-                        .color(Some(Color::Rgba(0.1, 0.1, 0.1, 0.8)))
+                        .color(Some(fade(Color::Rgba(0.1, 0.1, 0.1, 0.8))))
                         .parent(id)
                         .set(state.ids.health_bar_bg, ui);
 
@@ -359,19 +447,32 @@ impl<'a> Widget for Overhead<'a> {
                     Image::new(self.imgs.enemy_bar)
                         .w_h(w, h)
                         .x_y(x, MANA_BAR_Y + 8.0)
-                        .color(if self.in_group {
+                        .color(Some(fade(if self.in_group {
                             // Different HP bar colors only for group members
-                            Some(match hp_percentage {
+                            match hp_percentage {
                                 x if (0.0..25.0).contains(&x) => crit_hp_color,
-                                x if (25.0..50.0).contains(&x) => LOW_HP_COLOR,
-                                _ => HP_COLOR,
-                            })
+                                x if (25.0..50.0).contains(&x) => self.health_colors.low_hp,
+                                _ => self.health_colors.hp,
+                            }
+                        } else if hostility_colors && hostility == Hostility::Friendly {
+                            self.health_colors.hp
                         } else {
-                            Some(ENEMY_HP_COLOR)
-                        })
+                            self.health_colors.enemy_hp
+                        })))
                         .parent(id)
                         .set(state.ids.health_bar, ui);
 
+                    // Flash over the remaining health right after a hit
+                    if damage_flash > 0.0 {
+                        Rectangle::fill_with(
+                            [w, h],
+                            fade(Color::Rgba(1.0, 1.0, 1.0, 0.8 * damage_flash)),
+                        )
+                        .x_y(x, MANA_BAR_Y + 8.0)
+                        .parent(id)
+                        .set(state.ids.health_flash, ui);
+                    }
+
                     if decayed_health > 0.0 {
                         let x_decayed = if self.in_group {
                             (0.0 - (decayed_health * 41.0 - 41.0)) * BARSIZE
@@ -388,7 +489,7 @@ impl<'a> Widget for Overhead<'a> {
                         Image::new(self.imgs.enemy_bar)
                             .w_h(decay_bar_len, h)
                             .x_y(x_decayed, MANA_BAR_Y + 8.0)
-                            .color(Some(QUALITY_EPIC))
+                            .color(Some(fade(QUALITY_EPIC)))
                             .parent(id)
                             .set(state.ids.decay_bar, ui);
                     }
@@ -400,7 +501,7 @@ impl<'a> Widget for Overhead<'a> {
                         .mid_top_with_margin_on(state.ids.health_bar_bg, 2.0)
                         .font_size(10)
                         .font_id(self.fonts.cyri.conrod_id)
-                        .color(TEXT_COLOR)
+                        .color(fade(TEXT_COLOR))
                         .parent(id)
                         .set(state.ids.health_txt, ui);
 
@@ -418,7 +519,7 @@ impl<'a> Widget for Overhead<'a> {
                         } else {
                             ((3.5 + (energy_factor * 36.5)) - 36.45) * BARSIZE
                         };
-                        Rectangle::fill_with([w, MANA_BAR_HEIGHT], STAMINA_COLOR)
+                        Rectangle::fill_with([w, MANA_BAR_HEIGHT], fade(STAMINA_COLOR))
                             .x_y(
                                 x, MANA_BAR_Y, //-32.0,
                             )
@@ -430,37 +531,40 @@ impl<'a> Widget for Overhead<'a> {
                     Image::new(if self.in_group {self.imgs.health_bar_group} else {self.imgs.enemy_health})
                 .w_h(84.0 * BARSIZE, 10.0 * BARSIZE)
                 .x_y(0.0, MANA_BAR_Y + 6.5) //-25.5)
-                .color(Some(Color::Rgba(1.0, 1.0, 1.0, 0.99)))
+                .color(Some(fade(Color::Rgba(1.0, 1.0, 1.0, 0.99))))
                 .parent(id)
                 .set(state.ids.health_bar_fg, ui);
 
                     let indicator_col = cr_color(combat_rating);
                     let artifact_diffculty = 122.0;
 
-                    if combat_rating > artifact_diffculty && !self.in_group {
-                        let skull_ani =
-                            ((self.pulse * 0.7/* speed factor */).cos() * 0.5 + 0.5) * 10.0; //Animation timer
-                        Image::new(if skull_ani as i32 == 1 && rand::random::<f32>() < 0.9 {
-                            self.imgs.skull_2
-                        } else {
-                            self.imgs.skull
-                        })
-                        .w_h(18.0 * BARSIZE, 18.0 * BARSIZE)
-                        .x_y(-39.0 * BARSIZE, MANA_BAR_Y + 7.0)
-                        .color(Some(Color::Rgba(1.0, 1.0, 1.0, 1.0)))
-                        .parent(id)
-                        .set(state.ids.level_skull, ui);
-                    } else {
-                        Image::new(if self.in_group {
-                            self.imgs.nothing
+                    // The combat rating stands in for a level
+                    if self.settings.nameplate_levels {
+                        if combat_rating > artifact_diffculty && !self.in_group {
+                            let skull_ani =
+                                ((self.pulse * 0.7/* speed factor */).cos() * 0.5 + 0.5) * 10.0; //Animation timer
+                            Image::new(if skull_ani as i32 == 1 && rand::random::<f32>() < 0.9 {
+                                self.imgs.skull_2
+                            } else {
+                                self.imgs.skull
+                            })
+                            .w_h(18.0 * BARSIZE, 18.0 * BARSIZE)
+                            .x_y(-39.0 * BARSIZE, MANA_BAR_Y + 7.0)
+                            .color(Some(fade(Color::Rgba(1.0, 1.0, 1.0, 1.0))))
+                            .parent(id)
+                            .set(state.ids.level_skull, ui);
                         } else {
-                            self.imgs.combat_rating_ico
-                        })
-                        .w_h(7.0 * BARSIZE, 7.0 * BARSIZE)
-                        .x_y(-37.0 * BARSIZE, MANA_BAR_Y + 6.0)
-                        .color(Some(indicator_col))
-                        .parent(id)
-                        .set(state.ids.level, ui);
+                            Image::new(if self.in_group {
+                                self.imgs.nothing
+                            } else {
+                                self.imgs.combat_rating_ico
+                            })
+                            .w_h(7.0 * BARSIZE, 7.0 * BARSIZE)
+                            .x_y(-37.0 * BARSIZE, MANA_BAR_Y + 6.0)
+                            .color(Some(fade(indicator_col)))
+                            .parent(id)
+                            .set(state.ids.level, ui);
+                        }
                     }
                 },
                 _ => {},
@@ -490,27 +594,28 @@ impl<'a> Widget for Overhead<'a> {
                 let btn_radius = btn_rect_size / 5.0;
                 let btn_color = Color::Rgba(0.0, 0.0, 0.0, 0.8);
 
+                // The lowest of the name and bars that are shown
+                let above = self.info.and_then(|info| {
+                    if self.shows_health_bar(&info) {
+                        Some(if info.energy.is_some() {
+                            state.ids.mana_bar
+                        } else {
+                            state.ids.health_bar
+                        })
+                    } else {
+                        self.shows_name(&info).then_some(state.ids.name)
+                    }
+                });
                 let hints_text = Text::new(&text)
                     .font_id(self.fonts.cyri.conrod_id)
                     .font_size(btn_font_size as u32)
                     .color(TEXT_COLOR)
                     .parent(id)
-                    .down_from(
-                        self.info.map_or(state.ids.name, |info| {
-                            if info.health.map_or(false, should_show_healthbar) {
-                                if info.energy.is_some() {
-                                    state.ids.mana_bar
-                                } else {
-                                    state.ids.health_bar
-                                }
-                            } else {
-                                state.ids.name
-                            }
-                        }),
-                        12.0,
-                    )
-                    .align_middle_x_of(state.ids.name)
                     .depth(1.0);
+                let hints_text = match above {
+                    Some(above) => hints_text.down_from(above, 12.0).align_middle_x_of(above),
+                    None => hints_text.x_y(0.0, MANA_BAR_Y),
+                };
 
                 let [w, h] = hints_text.get_wh(ui).unwrap_or([btn_rect_size; 2]);
 
@@ -529,15 +634,22 @@ impl<'a> Widget for Overhead<'a> {
             }
         }
         // Speech bubble
-        if let Some(bubble) = self.bubble {
+        if let (
+            Some(BubbleInfo {
+                bubble,
+                portrait,
+                scale,
+            }),
+            Some((emote, bubble_contents)),
+        ) = (self.bubble, self.bubble_text())
+        {
             let dark_mode = self.settings.speech_bubble_dark_mode;
-            let localizer = |s: &str, i| -> String { self.i18n.get_variation(s, i).to_string() };
-            let bubble_contents: String = bubble.message(localizer);
+            let font_size = (18.0 * scale).round().max(10.0) as u32;
             let (text_color, shadow_color) = bubble_color(bubble, dark_mode);
             let mut text = Text::new(&bubble_contents)
                 .color(text_color)
                 .font_id(self.fonts.cyri.conrod_id)
-                .font_size(18)
+                .font_size(font_size)
                 .up_from(state.ids.name, 26.0)
                 .x_align_to(state.ids.name, Align::Middle)
                 .parent(id);
@@ -650,7 +762,7 @@ impl<'a> Widget for Overhead<'a> {
             let mut text_shadow = Text::new(&bubble_contents)
                 .color(shadow_color)
                 .font_id(self.fonts.cyri.conrod_id)
-                .font_size(18)
+                .font_size(font_size)
                 .x_relative_to(state.ids.speech_bubble_text, 1.0)
                 .y_relative_to(state.ids.speech_bubble_text, -1.0)
                 .parent(id);
@@ -674,10 +786,58 @@ impl<'a> Widget for Overhead<'a> {
                     // TODO: Figure out whether this should be parented.
                     // .parent(id)
                     .set(state.ids.speech_bubble_icon, ui);
+            if let Some(emote) = emote {
+                Image::new(emote_image(emote, self.imgs))
+                    .w_h(24.0, 24.0)
+                    .top_right_with_margins_on(state.ids.speech_bubble_text, -32.0, -32.0)
+                    .parent(id)
+                    .set(state.ids.speech_bubble_emote, ui);
+            }
+            if let Some(portrait) = portrait {
+                Image::new(portrait)
+                    .w_h(32.0, 32.0)
+                    .mid_left_with_margin_on(state.ids.speech_bubble_text, -58.0)
+                    .parent(id)
+                    .set(state.ids.speech_bubble_portrait, ui);
+            }
         }
     }
 }
 
+/// Face of the speaker for their speech bubbles, only humanoids have one
+pub fn portrait(body: &Body, imgs: &Imgs) -> Option<image::Id> {
+    let body = match body {
+        Body::Humanoid(body) => body,
+        _ => return None,
+    };
+    let female = body.body_type == BodyType::Female;
+    Some(match body.species {
+        Species::Danari if female => imgs.portrait_danari_f,
+        Species::Danari => imgs.portrait_danari_m,
+        Species::Draugr if female => imgs.portrait_draugr_f,
+        Species::Draugr => imgs.portrait_draugr_m,
+        Species::Dwarf if female => imgs.portrait_dwarf_f,
+        Species::Dwarf => imgs.portrait_dwarf_m,
+        Species::Elf if female => imgs.portrait_elf_f,
+        Species::Elf => imgs.portrait_elf_m,
+        Species::Human if female => imgs.portrait_human_f,
+        Species::Human => imgs.portrait_human_m,
+        Species::Orc if female => imgs.portrait_orc_f,
+        Species::Orc => imgs.portrait_orc_m,
+    })
+}
+
+fn emote_image(emote: Emote, imgs: &Imgs) -> image::Id {
+    match emote {
+        Emote::Alert => imgs.mmap_site_excl,
+        Emote::Angry => imgs.debuff_burning_0,
+        Emote::Love => imgs.buff_healthplus_0,
+        Emote::Music => imgs.instrument,
+        Emote::Sad => imgs.debuff_wet_0,
+        Emote::Threat => imgs.skull,
+    }
+}
+
 fn bubble_color(bubble: &SpeechBubble, dark_mode: bool) -> (Color, Color) {
     let light_color = match bubble.icon {
         SpeechBubbleType::Tell => TELL_COLOR,
@@ -685,6 +845,7 @@ fn bubble_color(bubble: &SpeechBubble, dark_mode: bool) -> (Color, Color) {
         SpeechBubbleType::Region => REGION_COLOR,
         SpeechBubbleType::Group => GROUP_COLOR,
         SpeechBubbleType::Faction => FACTION_COLOR,
+        SpeechBubbleType::Guild => GUILD_COLOR,
         SpeechBubbleType::World
         | SpeechBubbleType::Quest
         | SpeechBubbleType::Trade
@@ -705,6 +866,7 @@ fn bubble_icon(sb: &SpeechBubble, imgs: &Imgs) -> conrod_core::image::Id {
         SpeechBubbleType::Region => imgs.chat_region_small,
         SpeechBubbleType::Group => imgs.chat_group_small,
         SpeechBubbleType::Faction => imgs.chat_faction_small,
+        SpeechBubbleType::Guild => imgs.chat_faction_small,
         SpeechBubbleType::World => imgs.chat_world_small,
         SpeechBubbleType::Quest => imgs.nothing, // TODO not implemented
         SpeechBubbleType::Trade => imgs.nothing, // TODO not implemented
@@ -0,0 +1,238 @@
+use super::{img_ids::Imgs, TEXT_COLOR, UI_HIGHLIGHT_0};
+use crate::{
+    session::settings_change::PhotoMode as PhotoModeChange,
+    settings::PhotoModeSettings,
+    ui::{fonts::Fonts, ImageSlider},
+};
+use conrod_core::{
+    position::Relative,
+    widget::{self, Button, Image, Text},
+    widget_ids, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+
+widget_ids! {
+    struct Ids {
+        top,
+        mid,
+        bot,
+        title,
+        focus_text,
+        focus_slider,
+        focus_number,
+        blur_text,
+        blur_slider,
+        blur_number,
+        filter_button,
+        resolution_button,
+        photo_button,
+        close_button,
+    }
+}
+
+/// Sizes the pictures can be taken at, in times the size of the window
+const SUPERSAMPLING: [u32; 4] = [1, 2, 4, 8];
+
+#[derive(WidgetCommon)]
+pub struct PhotoModeWindow<'a> {
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    settings: &'a PhotoModeSettings,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> PhotoModeWindow<'a> {
+    pub fn new(
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        settings: &'a PhotoModeSettings,
+    ) -> Self {
+        Self {
+            imgs,
+            fonts,
+            localized_strings,
+            settings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    ChangeSetting(PhotoModeChange),
+    TakePhoto,
+    Close,
+}
+
+impl<'a> Widget for PhotoModeWindow<'a> {
+    type Event = Vec<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("PhotoModeWindow::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut events = Vec::new();
+        let i18n = self.localized_strings;
+
+        // Window, in a corner to leave the view free
+        Image::new(self.imgs.prompt_top)
+            .w_h(276.0, 24.0)
+            .top_right_with_margins_on(ui.window, 20.0, 20.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.top, ui);
+        Image::new(self.imgs.prompt_mid)
+            .w_h(276.0, 330.0)
+            .down_from(state.ids.top, 0.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.mid, ui);
+        Image::new(self.imgs.prompt_bot)
+            .w_h(276.0, 35.0)
+            .down_from(state.ids.mid, 0.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.bot, ui);
+
+        Text::new(&i18n.get_msg("hud-photo_mode-title"))
+            .mid_top_with_margin_on(state.ids.mid, 0.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(18))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // Focus distance
+        Text::new(&i18n.get_msg("hud-photo_mode-focus"))
+            .top_left_with_margins_on(state.ids.mid, 36.0, 30.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.focus_text, ui);
+        if let Some(new_val) = ImageSlider::continuous(
+            self.settings.focus_distance,
+            1.0,
+            200.0,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(160.0, 22.0)
+        .down_from(state.ids.focus_text, 8.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.focus_slider, ui)
+        {
+            events.push(Event::ChangeSetting(PhotoModeChange::AdjustFocusDistance(
+                new_val,
+            )));
+        }
+        Text::new(&format!("{:.0}", self.settings.focus_distance))
+            .right_from(state.ids.focus_slider, 8.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.focus_number, ui);
+
+        // Blur of what is out of focus
+        Text::new(&i18n.get_msg("hud-photo_mode-blur"))
+            .down_from(state.ids.focus_slider, 10.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.blur_text, ui);
+        if let Some(new_val) = ImageSlider::continuous(
+            self.settings.aperture,
+            0.0,
+            1.0,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(160.0, 22.0)
+        .down_from(state.ids.blur_text, 8.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.blur_slider, ui)
+        {
+            events.push(Event::ChangeSetting(PhotoModeChange::AdjustAperture(
+                new_val,
+            )));
+        }
+        Text::new(&format!("{:2.0}%", self.settings.aperture * 100.0))
+            .right_from(state.ids.blur_slider, 8.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.blur_number, ui);
+
+        // Filter, resolution, photo and close buttons
+        let button = |row: usize| {
+            Button::image(self.imgs.button)
+                .w_h(210.0, 36.0)
+                .mid_top_with_margin_on(state.ids.mid, 150.0 + 40.0 * row as f64)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .label_y(Relative::Scalar(2.0))
+                .label_color(TEXT_COLOR)
+                .label_font_size(self.fonts.cyri.scale(16))
+                .label_font_id(self.fonts.cyri.conrod_id)
+        };
+        let filter = self.settings.filter;
+        if button(0)
+            .label(&i18n.get_msg(filter.i18n_key()))
+            .set(state.ids.filter_button, ui)
+            .was_clicked()
+        {
+            events.push(Event::ChangeSetting(PhotoModeChange::ChangeFilter(
+                filter.next(),
+            )));
+        }
+        let supersampling = self.settings.supersampling;
+        if button(1)
+            .label(
+                &i18n.get_msg_ctx("hud-photo_mode-resolution", &i18n::fluent_args! {
+                    "factor" => supersampling,
+                }),
+            )
+            .set(state.ids.resolution_button, ui)
+            .was_clicked()
+        {
+            let next = SUPERSAMPLING
+                .iter()
+                .position(|factor| *factor == supersampling)
+                .map_or(0, |index| (index + 1) % SUPERSAMPLING.len());
+            events.push(Event::ChangeSetting(PhotoModeChange::ChangeSupersampling(
+                SUPERSAMPLING[next],
+            )));
+        }
+        if button(2)
+            .label(&i18n.get_msg("hud-photo_mode-take_photo"))
+            .set(state.ids.photo_button, ui)
+            .was_clicked()
+        {
+            events.push(Event::TakePhoto);
+        }
+        if button(3)
+            .label(&i18n.get_msg("common-close"))
+            .set(state.ids.close_button, ui)
+            .was_clicked()
+        {
+            events.push(Event::Close);
+        }
+
+        events
+    }
+}
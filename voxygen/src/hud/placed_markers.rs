@@ -0,0 +1,93 @@
+//! Markers the player places on the map and names, kept with the character in
+//! the profile. The ones marked as shared are shown to the group as well.
+
+use common::comp::{NamedMapMarker, MAX_MAP_MARKER_NAME_LEN, MAX_SHARED_MAP_MARKERS};
+use conrod_core::Color;
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+/// Tint of the icons of the named markers, telling them apart from waypoints
+pub const PLACED_MARKER_COLOR: Color = Color::Rgba(0.45, 0.85, 1.0, 1.0);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlacedMarker {
+    pub marker: NamedMapMarker,
+    /// Whether the group members see the marker too
+    pub shared: bool,
+}
+
+/// Markers placed by a character, in the order they were placed
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlacedMarkers {
+    markers: Vec<PlacedMarker>,
+}
+
+impl PlacedMarkers {
+    /// Places a marker at `wpos`, named `default_name` when `name` is blank
+    pub fn place(&mut self, wpos: Vec2<i32>, name: &str, default_name: &str) {
+        let name = match name.trim() {
+            "" => default_name,
+            name => name,
+        };
+        self.markers.push(PlacedMarker {
+            marker: NamedMapMarker {
+                wpos,
+                name: name.chars().take(MAX_MAP_MARKER_NAME_LEN).collect(),
+            },
+            shared: false,
+        });
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.markers.len() {
+            self.markers.remove(index);
+        }
+    }
+
+    pub fn toggle_shared(&mut self, index: usize) {
+        if let Some(marker) = self.markers.get_mut(index) {
+            marker.shared ^= true;
+        }
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &PlacedMarker> { self.markers.iter() }
+
+    pub fn len(&self) -> usize { self.markers.len() }
+
+    pub fn is_empty(&self) -> bool { self.markers.is_empty() }
+
+    /// Markers to send to the group members, as many as the server accepts
+    pub fn shared(&self) -> Vec<NamedMapMarker> {
+        self.markers
+            .iter()
+            .filter(|placed| placed.shared)
+            .map(|placed| placed.marker.clone())
+            .take(MAX_SHARED_MAP_MARKERS)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_shared_markers_are_sent() {
+        let mut markers = PlacedMarkers::default();
+        markers.place(Vec2::new(10, 20), "  ", "Marker");
+        markers.place(Vec2::new(30, 40), &"Camp".repeat(20), "Marker");
+        assert_eq!(markers.iter().next().unwrap().marker.name, "Marker");
+        assert!(markers.shared().is_empty());
+
+        markers.toggle_shared(1);
+        let shared = markers.shared();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].wpos, Vec2::new(30, 40));
+        assert_eq!(shared[0].name.chars().count(), MAX_MAP_MARKER_NAME_LEN);
+
+        markers.remove(1);
+        assert!(markers.shared().is_empty());
+        assert_eq!(markers.len(), 1);
+    }
+}
@@ -1,6 +1,7 @@
 use super::Show;
 use crate::ui::fonts::Fonts;
 use client::{self, Client};
+use common::{assets::AssetExt, comp::quest::QuestDef, time::DayPeriod};
 use common_net::msg::Notification;
 use conrod_core::{
     widget::{self, Text},
@@ -32,7 +33,7 @@ pub struct Popup<'a> {
 }
 
 /// Popup notifications for messages such as <Chunk Name>, Waypoint Saved,
-/// Dungeon Cleared (TODO), and Quest Completed (TODO)
+/// Dungeon Cleared (TODO), and Quest Started/Completed
 impl<'a> Popup<'a> {
     pub fn new(
         i18n: &'a Localization,
@@ -61,6 +62,7 @@ pub struct State {
     last_info_update: Instant,
     last_message_update: Instant,
     last_region_name: Option<String>,
+    last_day_period: Option<DayPeriod>,
 }
 
 impl<'a> Widget for Popup<'a> {
@@ -78,6 +80,7 @@ impl<'a> Widget for Popup<'a> {
             last_info_update: Instant::now(),
             last_message_update: Instant::now(),
             last_region_name: None,
+            last_day_period: None,
         }
     }
 
@@ -119,15 +122,82 @@ impl<'a> Widget for Popup<'a> {
             }
         }
 
-        // Push waypoint to message queue
+        // Warn at dusk that hostile creatures get stronger during the night
+        let day_period = self.client.state().get_day_period();
+        if state.last_day_period != Some(day_period) {
+            let dusk = state.last_day_period == Some(DayPeriod::Evening) && day_period.is_dark();
+            state.update(|s| {
+                if dusk {
+                    if s.infos.is_empty() {
+                        s.last_info_update = Instant::now();
+                    }
+                    let text = self.i18n.get_msg("hud-night_falls");
+                    s.infos.push_back(text.to_string());
+                }
+                s.last_day_period = Some(day_period);
+            });
+        }
+
+        // Push waypoint and quest notifications to message queue
         for notification in self.new_notifications {
             match notification {
-                Notification::WaypointSaved => {
+                Notification::WaypointSaved
+                | Notification::CantSleep
+                | Notification::WokeUp
+                | Notification::TravelInterrupted => {
+                    let key = match notification {
+                        Notification::WaypointSaved => "hud-waypoint_saved",
+                        Notification::CantSleep => "hud-cant_sleep",
+                        Notification::TravelInterrupted => "hud-travel_interrupted",
+                        _ => "hud-woke_up",
+                    };
+                    state.update(|s| {
+                        if s.infos.is_empty() {
+                            s.last_info_update = Instant::now();
+                        }
+                        let text = self.i18n.get_msg(key);
+                        s.infos.push_back(text.to_string());
+                    });
+                },
+                Notification::Sleeping { sleeping, needed } => {
+                    state.update(|s| {
+                        if s.infos.is_empty() {
+                            s.last_info_update = Instant::now();
+                        }
+                        let text = self.i18n.get_msg_ctx("hud-sleeping", &i18n::fluent_args! {
+                            "sleeping" => *sleeping,
+                            "needed" => *needed,
+                        });
+                        s.infos.push_back(text.to_string());
+                    });
+                },
+                Notification::Travelling(destination) => {
+                    state.update(|s| {
+                        if s.infos.is_empty() {
+                            s.last_info_update = Instant::now();
+                        }
+                        let text = self
+                            .i18n
+                            .get_msg_ctx("hud-travelling", &i18n::fluent_args! {
+                                "destination" => destination.clone(),
+                            });
+                        s.infos.push_back(text.to_string());
+                    });
+                },
+                Notification::QuestStarted(id) | Notification::QuestCompleted(id) => {
+                    let key = match notification {
+                        Notification::QuestStarted(_) => "hud-quest-started",
+                        _ => "hud-quest-completed",
+                    };
+                    let title = QuestDef::load(id)
+                        .map_or_else(|_| id.clone(), |def| def.read().title.clone());
                     state.update(|s| {
                         if s.infos.is_empty() {
                             s.last_info_update = Instant::now();
                         }
-                        let text = self.i18n.get_msg("hud-waypoint_saved");
+                        let text = self.i18n.get_msg_ctx(key, &i18n::fluent_args! {
+                            "title" => title,
+                        });
                         s.infos.push_back(text.to_string());
                     });
                 },
@@ -0,0 +1,271 @@
+//! Quests of the character: the active ones with their description,
+//! objectives and reward, and the ones completed before
+
+use super::{
+    img_ids::Imgs,
+    quest_tracker::{item_name, objective_text, quest_title},
+    TEXT_COLOR, TEXT_GRAY_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
+};
+use crate::ui::fonts::Fonts;
+use common::{
+    assets::AssetExt,
+    comp::quest::{QuestDef, QuestLog, Reward},
+};
+use conrod_core::{
+    color,
+    widget::{self, Button, Image, Rectangle, Scrollbar, Text},
+    widget_ids, Color, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+
+const COMPLETED_COLOR: Color = Color::Rgba(0.4, 1.0, 0.45, 1.0);
+
+widget_ids! {
+    pub struct Ids {
+        frame,
+        close,
+        title,
+        bg,
+        icon,
+        quests_align,
+        scrollbar,
+        quest_titles[],
+        quest_descriptions[],
+        objectives[],
+        rewards[],
+        no_quests_txt,
+        completed_header,
+        completed[],
+    }
+}
+
+fn reward_text(i18n: &Localization, reward: &Reward) -> Option<String> {
+    let exp = (reward.exp > 0).then(|| {
+        i18n.get_msg_ctx("hud-quest_log-reward_exp", &i18n::fluent_args! {
+            "exp" => reward.exp,
+        })
+        .into_owned()
+    });
+    let items = reward
+        .items
+        .iter()
+        .map(|(item, amount)| format!("{} x{}", item_name(item), amount));
+    let rewards = exp.into_iter().chain(items).collect::<Vec<_>>();
+    (!rewards.is_empty()).then(|| {
+        i18n.get_msg_ctx("hud-quest_log-reward", &i18n::fluent_args! {
+            "rewards" => rewards.join(", "),
+        })
+        .into_owned()
+    })
+}
+
+#[derive(WidgetCommon)]
+pub struct QuestLogWindow<'a> {
+    quest_log: Option<&'a QuestLog>,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> QuestLogWindow<'a> {
+    pub fn new(
+        quest_log: Option<&'a QuestLog>,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+    ) -> Self {
+        Self {
+            quest_log,
+            imgs,
+            fonts,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    Close,
+}
+
+impl<'a> Widget for QuestLogWindow<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        Self::State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("QuestLogWindow::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let i18n = self.localized_strings;
+        let mut event = None;
+
+        // Window BG
+        Image::new(self.imgs.social_bg_on)
+            .bottom_left_with_margins_on(ui.window, 308.0, 25.0)
+            .color(Some(UI_MAIN))
+            .w_h(280.0, 460.0)
+            .set(state.ids.bg, ui);
+        // Window frame
+        Image::new(self.imgs.social_frame_on)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .w_h(280.0, 460.0)
+            .set(state.ids.frame, ui);
+        // Icon
+        Image::new(self.imgs.spellbook_ico)
+            .w_h(30.0, 30.0)
+            .top_left_with_margins_on(state.ids.frame, 6.0, 6.0)
+            .set(state.ids.icon, ui);
+        // X-Button
+        if Button::image(self.imgs.close_button)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_button_hover)
+            .press_image(self.imgs.close_button_press)
+            .top_right_with_margins_on(state.ids.frame, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        // Title
+        Text::new(&i18n.get_msg("hud-quest_log"))
+            .mid_top_with_margin_on(state.ids.frame, 9.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        Rectangle::fill_with([266.0, 400.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.ids.frame, 48.0)
+            .scroll_kids_vertically()
+            .set(state.ids.quests_align, ui);
+        Scrollbar::y_axis(state.ids.quests_align)
+            .thickness(4.0)
+            .color(Color::Rgba(0.79, 1.09, 1.09, 0.0))
+            .set(state.ids.scrollbar, ui);
+
+        let (active, completed) = self.quest_log.map_or((&[][..], &[][..]), |quest_log| {
+            (&quest_log.active[..], &quest_log.completed[..])
+        });
+        if active.is_empty() && completed.is_empty() {
+            Text::new(&i18n.get_msg("hud-quest_log-empty"))
+                .w(256.0)
+                .mid_top_with_margin_on(state.ids.quests_align, 10.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_GRAY_COLOR)
+                .set(state.ids.no_quests_txt, ui);
+            return event;
+        }
+
+        let objective_count = active.iter().map(|q| q.objectives.len()).sum::<usize>();
+        if state.ids.quest_titles.len() < active.len()
+            || state.ids.objectives.len() < objective_count
+            || state.ids.completed.len() < completed.len()
+        {
+            state.update(|s| {
+                let id_gen = &mut ui.widget_id_generator();
+                s.ids.quest_titles.resize(active.len(), id_gen);
+                s.ids.quest_descriptions.resize(active.len(), id_gen);
+                s.ids.rewards.resize(active.len(), id_gen);
+                s.ids.objectives.resize(objective_count, id_gen);
+                s.ids.completed.resize(completed.len(), id_gen);
+            });
+        }
+
+        let mut last = None;
+        let mut objective_idx = 0;
+        for (i, quest) in active.iter().enumerate() {
+            let def = QuestDef::load_cloned(&quest.id).ok();
+            let title = def
+                .as_ref()
+                .map_or_else(|| quest.id.clone(), |def| def.title.clone());
+            let text = Text::new(&title)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(16))
+                .color(TEXT_COLOR);
+            match last {
+                Some(last) => text.down_from(last, 14.0),
+                None => text.top_left_with_margins_on(state.ids.quests_align, 2.0, 4.0),
+            }
+            .set(state.ids.quest_titles[i], ui);
+
+            Text::new(def.as_ref().map_or("", |def| def.description.as_str()))
+                .w(256.0)
+                .down_from(state.ids.quest_titles[i], 4.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(13))
+                .color(TEXT_GRAY_COLOR)
+                .set(state.ids.quest_descriptions[i], ui);
+            let mut prev = state.ids.quest_descriptions[i];
+
+            for (objective, progress) in quest.objectives.iter().zip(&quest.progress) {
+                let id = state.ids.objectives[objective_idx];
+                objective_idx += 1;
+                let done = *progress >= objective.required();
+                Text::new(&objective_text(i18n, objective, *progress))
+                    .w(256.0)
+                    .down_from(prev, 4.0)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(13))
+                    .color(if done { COMPLETED_COLOR } else { TEXT_COLOR })
+                    .set(id, ui);
+                prev = id;
+            }
+
+            if let Some(reward) = def.and_then(|def| reward_text(i18n, &def.reward)) {
+                Text::new(&reward)
+                    .w(256.0)
+                    .down_from(prev, 4.0)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(13))
+                    .color(TEXT_GRAY_COLOR)
+                    .set(state.ids.rewards[i], ui);
+                prev = state.ids.rewards[i];
+            }
+            last = Some(prev);
+        }
+
+        if !completed.is_empty() {
+            let text = Text::new(&i18n.get_msg("hud-quest_log-completed"))
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(16))
+                .color(TEXT_COLOR);
+            match last {
+                Some(last) => text.down_from(last, 14.0),
+                None => text.top_left_with_margins_on(state.ids.quests_align, 2.0, 4.0),
+            }
+            .set(state.ids.completed_header, ui);
+            let mut prev = state.ids.completed_header;
+            for (id, quest) in completed.iter().enumerate() {
+                let widget = state.ids.completed[id];
+                Text::new(&quest_title(quest))
+                    .w(256.0)
+                    .down_from(prev, 4.0)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(13))
+                    .color(COMPLETED_COLOR)
+                    .set(widget, ui);
+                prev = widget;
+            }
+        }
+
+        event
+    }
+}
@@ -0,0 +1,158 @@
+use super::TEXT_COLOR;
+use crate::ui::fonts::Fonts;
+use common::{
+    assets::AssetExt,
+    comp::{
+        item::ItemDef,
+        quest::{Objective, QuestDef, QuestLog},
+    },
+};
+use conrod_core::{
+    color,
+    widget::{self, Text},
+    widget_ids, Colorable, Positionable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use std::sync::Arc;
+
+widget_ids! {
+    struct Ids {
+        titles[],
+        objectives[],
+    }
+}
+
+/// Lists the active quests and the progress of their objectives below the
+/// minimap
+#[derive(WidgetCommon)]
+pub struct QuestTracker<'a> {
+    quest_log: &'a QuestLog,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> QuestTracker<'a> {
+    pub fn new(
+        quest_log: &'a QuestLog,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+    ) -> Self {
+        Self {
+            quest_log,
+            fonts,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+/// Name of the item with the given definition id, or the id if it has none
+pub fn item_name(item: &str) -> String {
+    Arc::<ItemDef>::load_cloned(item)
+        .map_or_else(|_| item.to_owned(), |def| def.name().into_owned())
+}
+
+/// Title of the quest with the given asset id, or the id if it has none
+pub fn quest_title(id: &str) -> String {
+    QuestDef::load(id).map_or_else(|_| id.to_owned(), |def| def.read().title.clone())
+}
+
+pub fn objective_text(i18n: &Localization, objective: &Objective, progress: u32) -> String {
+    let required = objective.required();
+    match objective {
+        Objective::Kill { name, .. } => i18n
+            .get_msg_ctx("hud-quest-kill", &i18n::fluent_args! {
+                "name" => name.as_str(),
+                "progress" => progress,
+                "required" => required,
+            })
+            .into_owned(),
+        Objective::Fetch { item, .. } => i18n
+            .get_msg_ctx("hud-quest-fetch", &i18n::fluent_args! {
+                "item" => item_name(item),
+                "progress" => progress,
+                "required" => required,
+            })
+            .into_owned(),
+        Objective::Reach { pos, .. } => i18n
+            .get_msg_ctx("hud-quest-reach", &i18n::fluent_args! {
+                "x" => pos.x as i32,
+                "y" => pos.y as i32,
+            })
+            .into_owned(),
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+impl<'a> Widget for QuestTracker<'a> {
+    type Event = ();
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("QuestTracker::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        let quests = &self.quest_log.active;
+        let objective_count = quests.iter().map(|q| q.objectives.len()).sum::<usize>();
+        if state.ids.titles.len() < quests.len() || state.ids.objectives.len() < objective_count {
+            state.update(|s| {
+                s.ids
+                    .titles
+                    .resize(quests.len(), &mut ui.widget_id_generator());
+                s.ids
+                    .objectives
+                    .resize(objective_count, &mut ui.widget_id_generator());
+            });
+        }
+
+        let mut last = None;
+        let mut objective_idx = 0;
+        for (quest, title_id) in quests.iter().zip(state.ids.titles.iter()) {
+            let title = quest_title(&quest.id);
+            let text = Text::new(&title)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(16))
+                .color(TEXT_COLOR);
+            match last {
+                Some(last) => text.down_from(last, 10.0).align_right_of(last),
+                None => text.top_right_with_margins_on(ui.window, 280.0, 10.0),
+            }
+            .set(*title_id, ui);
+            let mut prev = *title_id;
+
+            for (objective, progress) in quest.objectives.iter().zip(&quest.progress) {
+                let id = state.ids.objectives[objective_idx];
+                objective_idx += 1;
+                let done = *progress >= objective.required();
+                Text::new(&objective_text(
+                    self.localized_strings,
+                    objective,
+                    *progress,
+                ))
+                .down_from(prev, 4.0)
+                .align_right_of(*title_id)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(13))
+                .color(if done { color::GREY } else { TEXT_COLOR })
+                .set(id, ui);
+                prev = id;
+            }
+            last = Some(prev);
+        }
+    }
+}
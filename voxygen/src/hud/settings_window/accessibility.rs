@@ -0,0 +1,284 @@
+use super::{label_color, RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
+
+use crate::{
+    hud::{img_ids::Imgs, MENU_BG, TEXT_COLOR},
+    session::settings_change::{Accessibility as AccessibilityChange, Accessibility::*},
+    settings::ColorPalette,
+    ui::{fonts::Fonts, ImageSlider, ToggleButton},
+    GlobalState,
+};
+use conrod_core::{
+    color,
+    position::Relative,
+    widget::{self, Button, DropDownList, Rectangle, Scrollbar, Text},
+    widget_ids, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use strum::IntoEnumIterator;
+
+widget_ids! {
+    struct Ids {
+        window,
+        window_r,
+        window_scrollbar,
+        reset_accessibility_button,
+        palette_text,
+        palette_list,
+        text_scale_text,
+        text_scale_slider,
+        text_scale_value,
+        reduce_camera_shake_button,
+        reduce_camera_shake_label,
+        reduce_flashes_button,
+        reduce_flashes_label,
+        subtitles_button,
+        subtitles_label,
+    }
+}
+
+/// Bounds of the text scale slider, in percent
+const MIN_TEXT_SCALE: u32 = 75;
+const MAX_TEXT_SCALE: u32 = 200;
+
+#[derive(WidgetCommon)]
+pub struct Accessibility<'a> {
+    global_state: &'a GlobalState,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    search_matches: &'a [String],
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+impl<'a> Accessibility<'a> {
+    pub fn new(
+        global_state: &'a GlobalState,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        search_matches: &'a [String],
+    ) -> Self {
+        Self {
+            global_state,
+            imgs,
+            fonts,
+            localized_strings,
+            search_matches,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+impl<'a> Widget for Accessibility<'a> {
+    type Event = Vec<AccessibilityChange>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Accessibility::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        let mut events = Vec::new();
+        let settings = &self.global_state.settings.accessibility;
+
+        Rectangle::fill_with(args.rect.dim(), color::TRANSPARENT)
+            .xy(args.rect.xy())
+            .graphics_for(args.id)
+            .scroll_kids()
+            .scroll_kids_vertically()
+            .set(state.ids.window, ui);
+        Rectangle::fill_with([args.rect.w() / 2.0, args.rect.h()], color::TRANSPARENT)
+            .top_right()
+            .parent(state.ids.window)
+            .set(state.ids.window_r, ui);
+        Scrollbar::y_axis(state.ids.window)
+            .thickness(5.0)
+            .rgba(0.33, 0.33, 0.33, 1.0)
+            .set(state.ids.window_scrollbar, ui);
+
+        // Colour palette of the health bars and damage numbers
+        Text::new(&self.localized_strings.get_msg("hud-settings-color_palette"))
+            .top_left_with_margins_on(state.ids.window, 10.0, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-color_palette",
+            ))
+            .set(state.ids.palette_text, ui);
+
+        let palettes = ColorPalette::iter().collect::<Vec<_>>();
+        let palette_names = palettes
+            .iter()
+            .map(|palette| self.localized_strings.get_msg(palette.name_key()))
+            .collect::<Vec<_>>();
+        let selected = palettes
+            .iter()
+            .position(|palette| *palette == settings.color_palette);
+
+        if let Some(clicked) = DropDownList::new(&palette_names, selected)
+            .w_h(200.0, 30.0)
+            .color(MENU_BG)
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .down_from(state.ids.palette_text, 8.0)
+            .set(state.ids.palette_list, ui)
+        {
+            events.push(ChangeColorPalette(palettes[clicked]));
+        }
+
+        // Text scale
+        Text::new(&self.localized_strings.get_msg("hud-settings-text_scale"))
+            .down_from(state.ids.palette_list, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(self.search_matches, "hud-settings-text_scale"))
+            .set(state.ids.text_scale_text, ui);
+
+        let text_scale = (settings.text_scale * 100.0).round() as u32;
+        if let Some(new_val) = ImageSlider::discrete(
+            text_scale,
+            MIN_TEXT_SCALE,
+            MAX_TEXT_SCALE,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(550.0, 22.0)
+        .down_from(state.ids.text_scale_text, 10.0)
+        .track_breadth(30.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.text_scale_slider, ui)
+        {
+            events.push(AdjustTextScale(new_val as f32 / 100.0));
+        }
+
+        Text::new(&format!("{}%", text_scale))
+            .right_from(state.ids.text_scale_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.text_scale_value, ui);
+
+        // Reduce camera shake
+        let reduce_camera_shake = ToggleButton::new(
+            settings.reduce_camera_shake,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.text_scale_slider, 20.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.reduce_camera_shake_button, ui);
+
+        if settings.reduce_camera_shake != reduce_camera_shake {
+            events.push(ToggleReduceCameraShake(!settings.reduce_camera_shake));
+        }
+
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-reduce_camera_shake"),
+        )
+        .right_from(state.ids.reduce_camera_shake_button, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .graphics_for(state.ids.reduce_camera_shake_button)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-reduce_camera_shake",
+        ))
+        .set(state.ids.reduce_camera_shake_label, ui);
+
+        // Reduce flashes
+        let reduce_flashes = ToggleButton::new(
+            settings.reduce_flashes,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.reduce_camera_shake_button, 8.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.reduce_flashes_button, ui);
+
+        if settings.reduce_flashes != reduce_flashes {
+            events.push(ToggleReduceFlashes(!settings.reduce_flashes));
+        }
+
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-reduce_flashes"),
+        )
+        .right_from(state.ids.reduce_flashes_button, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .graphics_for(state.ids.reduce_flashes_button)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-reduce_flashes",
+        ))
+        .set(state.ids.reduce_flashes_label, ui);
+
+        // Subtitles
+        let subtitles = ToggleButton::new(
+            settings.subtitles,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.reduce_flashes_button, 8.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.subtitles_button, ui);
+
+        if settings.subtitles != subtitles {
+            events.push(ToggleSubtitles(!settings.subtitles));
+        }
+
+        Text::new(&self.localized_strings.get_msg("hud-settings-subtitles"))
+            .right_from(state.ids.subtitles_button, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .graphics_for(state.ids.subtitles_button)
+            .color(label_color(self.search_matches, "hud-settings-subtitles"))
+            .set(state.ids.subtitles_label, ui);
+
+        // Reset the accessibility settings to the default settings
+        if Button::image(self.imgs.button)
+            .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .down_from(state.ids.subtitles_button, 20.0)
+            .label(
+                &self
+                    .localized_strings
+                    .get_msg("hud-settings-reset_accessibility"),
+            )
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_y(Relative::Scalar(2.0))
+            .set(state.ids.reset_accessibility_button, ui)
+            .was_clicked()
+        {
+            events.push(ResetAccessibilitySettings);
+        }
+
+        events
+    }
+}
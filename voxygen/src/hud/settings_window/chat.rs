@@ -1,4 +1,4 @@
-use super::{RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
+use super::{label_color, RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
 
 use crate::{
     hud::{img_ids::Imgs, ChatTab, Show, TEXT_COLOR, TEXT_GRAY_COLOR, UI_HIGHLIGHT_0, UI_MAIN},
@@ -26,6 +26,9 @@ widget_ids! {
         transp_value,
         char_name_text,
         char_name_button,
+        history_text,
+        history_slider,
+        history_value,
         reset_chat_button,
 
         //Tabs
@@ -62,12 +65,20 @@ widget_ids! {
         btn_messages_say,
         text_messages_say,
         icon_messages_say,
+        btn_messages_guild,
+        text_messages_guild,
+        icon_messages_guild,
 
         text_activity,
         list_activity,
 
         text_death,
         list_death,
+
+        btn_messages_tell,
+        text_messages_tell,
+        btn_system,
+        text_system,
     }
 }
 
@@ -78,6 +89,7 @@ pub struct Chat<'a> {
     imgs: &'a Imgs,
     fonts: &'a Fonts,
     localized_strings: &'a Localization,
+    search_matches: &'a [String],
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
 }
@@ -88,6 +100,7 @@ impl<'a> Chat<'a> {
         imgs: &'a Imgs,
         fonts: &'a Fonts,
         localized_strings: &'a Localization,
+        search_matches: &'a [String],
     ) -> Self {
         Self {
             global_state,
@@ -95,6 +108,7 @@ impl<'a> Chat<'a> {
             imgs,
             fonts,
             localized_strings,
+            search_matches,
             common: widget::CommonBuilder::default(),
         }
     }
@@ -145,7 +159,7 @@ impl<'a> Widget for Chat<'a> {
             .top_left_with_margins_on(state.ids.window, 5.0, 5.0)
             .font_size(self.fonts.cyri.scale(18))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-general"))
             .set(state.ids.general_txt, ui);
 
         // Chat Transp
@@ -157,7 +171,10 @@ impl<'a> Widget for Chat<'a> {
         .down_from(state.ids.general_txt, 20.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-background_opacity",
+        ))
         .set(state.ids.transp_text, ui);
         if let Some(new_val) = ImageSlider::continuous(
             chat_settings.chat_opacity,
@@ -193,7 +210,10 @@ impl<'a> Widget for Chat<'a> {
         .down_from(state.ids.transp_slider, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-chat_character_name",
+        ))
         .set(state.ids.char_name_text, ui);
 
         if chat_settings.chat_character_name
@@ -213,12 +233,47 @@ impl<'a> Widget for Chat<'a> {
             )));
         }
 
+        // Lines of chat kept for the next sessions
+        Text::new(&self.localized_strings.get_msg("hud-settings-chat_history"))
+            .down_from(state.ids.char_name_text, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-chat_history",
+            ))
+            .set(state.ids.history_text, ui);
+        if let Some(new_val) = ImageSlider::discrete(
+            chat_settings.chat_history_lines,
+            0,
+            500,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(104.0, 22.0)
+        .down_from(state.ids.history_text, 10.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.history_slider, ui)
+        {
+            events.push(Event::ChatChange(HistoryLines(new_val)));
+        }
+
+        Text::new(&chat_settings.chat_history_lines.to_string())
+            .right_from(state.ids.history_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .graphics_for(state.ids.history_slider)
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.history_value, ui);
+
         // Reset the chat settings to the default settings
         if Button::image(self.imgs.button)
             .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
             .hover_image(self.imgs.button_hover)
             .press_image(self.imgs.button_press)
-            .down_from(state.ids.char_name_text, 20.0)
+            .down_from(state.ids.history_slider, 20.0)
             .label(&self.localized_strings.get_msg("hud-settings-reset_chat"))
             .label_font_size(self.fonts.cyri.scale(14))
             .label_color(TEXT_COLOR)
@@ -235,7 +290,7 @@ impl<'a> Widget for Chat<'a> {
             .top_left_with_margins_on(state.ids.window_r, 5.0, 5.0)
             .font_size(self.fonts.cyri.scale(18))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-chat_tabs"))
             .set(state.ids.tabs_text, ui);
 
         // bg and frame
@@ -339,7 +394,7 @@ impl<'a> Widget for Chat<'a> {
                 .top_left_with_margins_on(state.ids.tab_content_align, 5.0, 25.0)
                 .font_size(self.fonts.cyri.scale(16))
                 .font_id(self.fonts.cyri.conrod_id)
-                .color(TEXT_COLOR)
+                .color(label_color(self.search_matches, "hud-settings-label"))
                 .set(state.ids.tab_label_text, ui);
 
             Rectangle::fill([90.0, 20.0])
@@ -421,7 +476,7 @@ impl<'a> Widget for Chat<'a> {
             Text::new(&self.localized_strings.get_msg("hud-settings-messages"))
                 .font_id(self.fonts.cyri.conrod_id)
                 .font_size(self.fonts.cyri.scale(16))
-                .color(TEXT_COLOR)
+                .color(label_color(self.search_matches, "hud-settings-messages"))
                 .top_left_with_margins_on(state.ids.tab_content_align, 35.0, 15.0)
                 .set(state.ids.text_messages, ui);
 
@@ -444,7 +499,7 @@ impl<'a> Widget for Chat<'a> {
             Text::new(&self.localized_strings.get_msg("hud-settings-show_all"))
                 .font_id(self.fonts.cyri.conrod_id)
                 .font_size(self.fonts.cyri.scale(16))
-                .color(TEXT_COLOR)
+                .color(label_color(self.search_matches, "hud-settings-show_all"))
                 .right_from(state.ids.btn_messages_all, 5.0)
                 .set(state.ids.text_messages_all, ui);
 
@@ -546,13 +601,32 @@ impl<'a> Widget for Chat<'a> {
                 .right_from(state.ids.text_messages_say, 5.0)
                 .set(state.ids.icon_messages_say, ui);
 
+            //Messages - guild
+            if chat_tab.filter.message_guild
+                != create_toggle(chat_tab.filter.message_guild, !chat_tab.filter.message_all)
+                    .down_from(state.ids.btn_messages_say, 10.0)
+                    .set(state.ids.btn_messages_guild, ui)
+                && !chat_tab.filter.message_all
+            {
+                updated_chat_tab.filter.message_guild = !chat_tab.filter.message_guild;
+            }
+
+            let guild_text = self.localized_strings.get_msg("hud-settings-guild");
+            create_toggle_text(&guild_text, !chat_tab.filter.message_all)
+                .right_from(state.ids.btn_messages_guild, 5.0)
+                .set(state.ids.text_messages_guild, ui);
+
+            create_toggle_icon(self.imgs.chat_faction_small, !chat_tab.filter.message_all)
+                .right_from(state.ids.text_messages_guild, 5.0)
+                .set(state.ids.icon_messages_guild, ui);
+
             //Activity
             Text::new(&self.localized_strings.get_msg("hud-settings-activity"))
                 .top_left_with_margins_on(state.ids.tab_content_align_r, 0.0, 5.0)
                 .align_middle_y_of(state.ids.text_messages)
                 .font_size(self.fonts.cyri.scale(16))
                 .font_id(self.fonts.cyri.conrod_id)
-                .color(TEXT_COLOR)
+                .color(label_color(self.search_matches, "hud-settings-activity"))
                 .set(state.ids.text_activity, ui);
 
             if let Some(clicked) = DropDownList::new(
@@ -602,7 +676,7 @@ impl<'a> Widget for Chat<'a> {
                 .down_from(state.ids.list_activity, 20.0)
                 .font_size(self.fonts.cyri.scale(16))
                 .font_id(self.fonts.cyri.conrod_id)
-                .color(TEXT_COLOR)
+                .color(label_color(self.search_matches, "hud-settings-death"))
                 .set(state.ids.text_death, ui);
 
             if let Some(clicked) = DropDownList::new(
@@ -647,6 +721,33 @@ impl<'a> Widget for Chat<'a> {
                 }
             }
 
+            //Whispers and system messages
+            if chat_tab.filter.message_tell
+                != create_toggle(chat_tab.filter.message_tell, true)
+                    .down_from(state.ids.list_death, 20.0)
+                    .set(state.ids.btn_messages_tell, ui)
+            {
+                updated_chat_tab.filter.message_tell = !chat_tab.filter.message_tell;
+            }
+
+            let tell_text = self.localized_strings.get_msg("hud-settings-whispers");
+            create_toggle_text(&tell_text, true)
+                .right_from(state.ids.btn_messages_tell, 5.0)
+                .set(state.ids.text_messages_tell, ui);
+
+            if chat_tab.filter.system
+                != create_toggle(chat_tab.filter.system, true)
+                    .down_from(state.ids.btn_messages_tell, 8.0)
+                    .set(state.ids.btn_system, ui)
+            {
+                updated_chat_tab.filter.system = !chat_tab.filter.system;
+            }
+
+            let system_text = self.localized_strings.get_msg("hud-settings-system");
+            create_toggle_text(&system_text, true)
+                .right_from(state.ids.btn_system, 5.0)
+                .set(state.ids.text_system, ui);
+
             if chat_tab != &updated_chat_tab {
                 //insert to front to avoid errors where the tab is moved or removed
                 events.insert(0, Event::ChatChange(ChatTabUpdate(index, updated_chat_tab)));
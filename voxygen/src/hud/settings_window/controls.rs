@@ -1,16 +1,17 @@
-use super::{RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
+use super::{gamepad::Gamepad, label_color, RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
 
 use crate::{
     game_input::GameInput,
-    hud::{img_ids::Imgs, ERROR_COLOR, TEXT_BIND_CONFLICT_COLOR, TEXT_COLOR},
-    session::settings_change::{Control as ControlChange, Control::*},
+    hud::{img_ids::Imgs, ERROR_COLOR, MENU_BG, TEXT_BIND_CONFLICT_COLOR, TEXT_COLOR},
+    session::settings_change::{Control as ControlChange, Control::*, Gamepad as GamepadChange},
+    settings::KeybindingScheme,
     ui::fonts::Fonts,
     GlobalState,
 };
 use conrod_core::{
     color,
     position::Relative,
-    widget::{self, Button, Rectangle, Scrollbar, Text},
+    widget::{self, Button, DropDownList, Rectangle, Scrollbar, Text},
     widget_ids, Borderable, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
 };
 use i18n::Localization;
@@ -21,6 +22,14 @@ widget_ids! {
         window,
         window_r,
         window_scrollbar,
+        keyboard_tab,
+        gamepad_tab,
+        gamepad,
+        scheme_text,
+        scheme_list,
+        conflict_text,
+        swap_bindings_button,
+        keep_bindings_button,
         reset_controls_button,
         keybinding_mode_button,
         controls_alignment_rectangle,
@@ -35,6 +44,7 @@ pub struct Controls<'a> {
     imgs: &'a Imgs,
     fonts: &'a Fonts,
     localized_strings: &'a Localization,
+    search_matches: &'a [String],
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
 }
@@ -44,29 +54,39 @@ impl<'a> Controls<'a> {
         imgs: &'a Imgs,
         fonts: &'a Fonts,
         localized_strings: &'a Localization,
+        search_matches: &'a [String],
     ) -> Self {
         Self {
             global_state,
             imgs,
             fonts,
             localized_strings,
+            search_matches,
             common: widget::CommonBuilder::default(),
         }
     }
 }
 
+pub enum Event {
+    ControlChange(ControlChange),
+    GamepadChange(GamepadChange),
+}
+
 pub struct State {
     ids: Ids,
+    /// Whether the gamepad bindings are shown instead of the keyboard ones
+    gamepad_tab: bool,
 }
 
 impl<'a> Widget for Controls<'a> {
-    type Event = Vec<ControlChange>;
+    type Event = Vec<Event>;
     type State = State;
     type Style = ();
 
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             ids: Ids::new(id_gen),
+            gamepad_tab: false,
         }
     }
 
@@ -94,12 +114,182 @@ impl<'a> Widget for Controls<'a> {
             .rgba(0.33, 0.33, 0.33, 1.0)
             .set(state.ids.window_scrollbar, ui);
 
+        // Keyboard and mouse or gamepad bindings
+        for (gamepad_tab, id, key) in [
+            (false, state.ids.keyboard_tab, "hud-settings-keyboard_mouse"),
+            (true, state.ids.gamepad_tab, "hud-settings-controller"),
+        ] {
+            let label = self.localized_strings.get_msg(key);
+            let button = Button::image(if state.gamepad_tab == gamepad_tab {
+                self.imgs.button_press
+            } else {
+                self.imgs.button
+            })
+            .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .label(&label)
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_color(label_color(self.search_matches, key))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_y(Relative::Scalar(2.0));
+            let button = if gamepad_tab {
+                button.right_from(state.ids.keyboard_tab, 10.0)
+            } else {
+                button.top_left_with_margins_on(state.ids.window, 10.0, 5.0)
+            };
+            if button.set(id, ui).was_clicked() {
+                state.update(|s| s.gamepad_tab = gamepad_tab);
+            }
+        }
+
+        let offset = ui
+            .widget_graph()
+            .widget(state.ids.window)
+            .and_then(|widget| {
+                widget
+                    .maybe_y_scroll_state
+                    .as_ref()
+                    .map(|scroll| scroll.offset)
+            })
+            .unwrap_or(0.0);
+
+        let toggle_widget = Button::new()
+            .label(if self.global_state.window.keybinding_mode {
+                "remap"
+            } else {
+                "clear"
+            })
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_font_size(self.fonts.cyri.scale(15))
+            .w(100.0)
+            .rgba(0.0, 0.0, 0.0, 0.0)
+            .border_rgba(0.0, 0.0, 0.0, 255.0)
+            .label_y(Relative::Scalar(1.0));
+        if toggle_widget
+            .top_right_with_margins_on(state.ids.window, offset + 10.0, 15.0)
+            .set(state.ids.keybinding_mode_button, ui)
+            .was_clicked()
+        {
+            events.push(Event::ControlChange(ToggleKeybindingMode));
+        }
+
+        if state.gamepad_tab {
+            for change in Gamepad::new(
+                self.global_state,
+                self.imgs,
+                self.fonts,
+                self.localized_strings,
+                self.search_matches,
+            )
+            .w_h(args.rect.w(), args.rect.h() - RESET_BUTTONS_HEIGHT - 20.0)
+            .mid_bottom_of(state.ids.window)
+            .set(state.ids.gamepad, ui)
+            {
+                events.push(Event::GamepadChange(change));
+            }
+            return events;
+        }
+
+        let controls = &self.global_state.settings.controls;
+
+        // Keybinding scheme
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-keybinding_scheme"),
+        )
+        .top_left_with_margins_on(state.ids.window, RESET_BUTTONS_HEIGHT + 20.0, 5.0)
+        .font_size(self.fonts.cyri.scale(18))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-keybinding_scheme",
+        ))
+        .set(state.ids.scheme_text, ui);
+
+        let scheme_labels = KeybindingScheme::ALL
+            .iter()
+            .map(|scheme| self.localized_strings.get_msg(scheme.i18n_key()))
+            .collect::<Vec<_>>();
+        let selected = KeybindingScheme::ALL
+            .iter()
+            .position(|scheme| *scheme == controls.scheme);
+        if let Some(clicked) = DropDownList::new(&scheme_labels, selected)
+            .w_h(400.0, 22.0)
+            .color(MENU_BG)
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .down_from(state.ids.scheme_text, 8.0)
+            .set(state.ids.scheme_list, ui)
+        {
+            events.push(Event::ControlChange(ChangeScheme(
+                KeybindingScheme::ALL[clicked],
+            )));
+        }
+
         // Used for sequential placement in a flow-down pattern
-        let mut previous_element_id = None;
+        let mut previous_element_id = Some(state.ids.scheme_list);
+
+        // Offer to swap the bindings when a key was taken from other game inputs
+        let conflict = self.global_state.window.binding_conflict.as_ref();
+        if let Some(conflict) = conflict {
+            let others = conflict
+                .others
+                .iter()
+                .map(|other| self.localized_strings.get_msg(other.get_localization_key()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Text::new(&self.localized_strings.get_msg_ctx(
+                "hud-settings-binding_conflict",
+                &i18n::fluent_args! {
+                    "key" => conflict.key_mouse.display_string(key_layout),
+                    "inputs" => others,
+                },
+            ))
+            .down_from(state.ids.scheme_list, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_BIND_CONFLICT_COLOR)
+            .set(state.ids.conflict_text, ui);
+
+            for (id, key, change) in [
+                (
+                    state.ids.swap_bindings_button,
+                    "hud-settings-swap_bindings",
+                    SwapBindings,
+                ),
+                (
+                    state.ids.keep_bindings_button,
+                    "hud-settings-keep_bindings",
+                    KeepBindings,
+                ),
+            ] {
+                let label = self.localized_strings.get_msg(key);
+                let button = Button::image(self.imgs.button)
+                    .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
+                    .hover_image(self.imgs.button_hover)
+                    .press_image(self.imgs.button_press)
+                    .label(&label)
+                    .label_font_size(self.fonts.cyri.scale(14))
+                    .label_color(TEXT_COLOR)
+                    .label_font_id(self.fonts.cyri.conrod_id)
+                    .label_y(Relative::Scalar(2.0));
+                let button = if id == state.ids.swap_bindings_button {
+                    button.down_from(state.ids.conflict_text, 8.0)
+                } else {
+                    button.right_from(state.ids.swap_bindings_button, 10.0)
+                };
+                if button.set(id, ui).was_clicked() {
+                    events.push(Event::ControlChange(change));
+                }
+            }
+            previous_element_id = Some(state.ids.swap_bindings_button);
+        }
         let mut keybindings_vec: Vec<GameInput> = GameInput::iter().collect();
         keybindings_vec.sort();
 
-        let controls = &self.global_state.settings.controls;
         if keybindings_vec.len() > state.ids.controls_texts.len()
             || keybindings_vec.len() > state.ids.controls_buttons.len()
         {
@@ -154,8 +344,15 @@ impl<'a> Widget for Controls<'a> {
             let loc_key = self
                 .localized_strings
                 .get_msg(game_input.get_localization_key());
+            let in_conflict = conflict.map_or(false, |conflict| {
+                conflict.game_input == game_input || conflict.others.contains(&game_input)
+            });
             let text_widget = Text::new(&loc_key)
-                .color(TEXT_COLOR)
+                .color(if in_conflict {
+                    TEXT_BIND_CONFLICT_COLOR
+                } else {
+                    label_color(self.search_matches, game_input.get_localization_key())
+                })
                 .font_id(self.fonts.cyri.conrod_id)
                 .font_size(self.fonts.cyri.scale(18));
             let button_widget = Button::new()
@@ -180,9 +377,9 @@ impl<'a> Widget for Controls<'a> {
                 .was_clicked()
             {
                 if self.global_state.window.keybinding_mode {
-                    events.push(ChangeBinding(game_input));
+                    events.push(Event::ControlChange(ChangeBinding(game_input)));
                 } else {
-                    events.push(RemoveBinding(game_input));
+                    events.push(Event::ControlChange(RemoveBinding(game_input)));
                 }
             }
             // Set the previous id to the current one for the next cycle
@@ -208,43 +405,11 @@ impl<'a> Widget for Controls<'a> {
                 .set(state.ids.reset_controls_button, ui)
                 .was_clicked()
             {
-                events.push(ResetKeyBindings);
+                events.push(Event::ControlChange(ResetKeyBindings));
             }
             previous_element_id = Some(state.ids.reset_controls_button)
         }
 
-        let offset = ui
-            .widget_graph()
-            .widget(state.ids.window)
-            .and_then(|widget| {
-                widget
-                    .maybe_y_scroll_state
-                    .as_ref()
-                    .map(|scroll| scroll.offset)
-            })
-            .unwrap_or(0.0);
-
-        let toggle_widget = Button::new()
-            .label(if self.global_state.window.keybinding_mode {
-                "remap"
-            } else {
-                "clear"
-            })
-            .label_color(TEXT_COLOR)
-            .label_font_id(self.fonts.cyri.conrod_id)
-            .label_font_size(self.fonts.cyri.scale(15))
-            .w(100.0)
-            .rgba(0.0, 0.0, 0.0, 0.0)
-            .border_rgba(0.0, 0.0, 0.0, 255.0)
-            .label_y(Relative::Scalar(1.0));
-        if toggle_widget
-            .top_right_with_margins_on(state.ids.window, offset + 10.0, 15.0)
-            .set(state.ids.keybinding_mode_button, ui)
-            .was_clicked()
-        {
-            events.push(ToggleKeybindingMode);
-        }
-
         // Add an empty text widget to simulate some bottom margin, because conrod sucks
         if let Some(prev_id) = previous_element_id {
             Rectangle::fill_with([1.0, 1.0], color::TRANSPARENT)
@@ -0,0 +1,331 @@
+use super::{label_color, RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
+
+use crate::{
+    controller::GamepadBinding,
+    hud::{img_ids::Imgs, ERROR_COLOR, TEXT_COLOR},
+    session::settings_change::{Gamepad as GamepadChange, Gamepad::*},
+    settings::gamepad::con_settings::GameButtons,
+    ui::{fonts::Fonts, ImageSlider},
+    GlobalState,
+};
+use conrod_core::{
+    color,
+    position::Relative,
+    widget::{self, Button, Rectangle, Scrollbar, Text},
+    widget_ids, Borderable, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use std::iter::once;
+
+widget_ids! {
+    struct Ids {
+        window,
+        window_scrollbar,
+        pan_sensitivity_text,
+        pan_sensitivity_slider,
+        pan_sensitivity_value,
+        stick_deadzone_text,
+        stick_deadzone_slider,
+        stick_deadzone_value,
+        mouse_emulation_text,
+        mouse_emulation_slider,
+        mouse_emulation_value,
+        bindings_texts[],
+        bindings_buttons[],
+        reset_gamepad_button,
+        bottom_margin,
+    }
+}
+
+#[derive(WidgetCommon)]
+pub struct Gamepad<'a> {
+    global_state: &'a GlobalState,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    search_matches: &'a [String],
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+impl<'a> Gamepad<'a> {
+    pub fn new(
+        global_state: &'a GlobalState,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        search_matches: &'a [String],
+    ) -> Self {
+        Self {
+            global_state,
+            imgs,
+            fonts,
+            localized_strings,
+            search_matches,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+impl<'a> Widget for Gamepad<'a> {
+    type Event = Vec<GamepadChange>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Gamepad::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        let mut events = Vec::new();
+        let settings = &self.global_state.settings.controller;
+
+        Rectangle::fill_with(args.rect.dim(), color::TRANSPARENT)
+            .xy(args.rect.xy())
+            .graphics_for(args.id)
+            .scroll_kids()
+            .scroll_kids_vertically()
+            .set(state.ids.window, ui);
+        Scrollbar::y_axis(state.ids.window)
+            .thickness(5.0)
+            .rgba(0.33, 0.33, 0.33, 1.0)
+            .set(state.ids.window_scrollbar, ui);
+
+        // Camera Pan Sensitivity
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-controller_pan_sensitivity"),
+        )
+        .top_left_with_margins_on(state.ids.window, 10.0, 5.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-controller_pan_sensitivity",
+        ))
+        .set(state.ids.pan_sensitivity_text, ui);
+
+        if let Some(new_val) = ImageSlider::discrete(
+            settings.pan_sensitivity,
+            1,
+            100,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(550.0, 22.0)
+        .down_from(state.ids.pan_sensitivity_text, 10.0)
+        .track_breadth(30.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.pan_sensitivity_slider, ui)
+        {
+            events.push(AdjustPanSensitivity(new_val));
+        }
+
+        Text::new(&format!("{}", settings.pan_sensitivity))
+            .right_from(state.ids.pan_sensitivity_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.pan_sensitivity_value, ui);
+
+        // Stick Dead Zone
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-stick_deadzone"),
+        )
+        .down_from(state.ids.pan_sensitivity_slider, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-stick_deadzone",
+        ))
+        .set(state.ids.stick_deadzone_text, ui);
+
+        let stick_deadzone = (settings.stick_deadzone * 100.0).round() as u32;
+        if let Some(new_val) = ImageSlider::discrete(
+            stick_deadzone,
+            0,
+            50,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(550.0, 22.0)
+        .down_from(state.ids.stick_deadzone_text, 10.0)
+        .track_breadth(30.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.stick_deadzone_slider, ui)
+        {
+            events.push(AdjustStickDeadzone(new_val as f32 / 100.0));
+        }
+
+        Text::new(&format!("{}%", stick_deadzone))
+            .right_from(state.ids.stick_deadzone_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.stick_deadzone_value, ui);
+
+        // Cursor Sensitivity in the menus
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-mouse_emulation_sensitivity"),
+        )
+        .down_from(state.ids.stick_deadzone_slider, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-mouse_emulation_sensitivity",
+        ))
+        .set(state.ids.mouse_emulation_text, ui);
+
+        if let Some(new_val) = ImageSlider::discrete(
+            settings.mouse_emulation_sensitivity,
+            1,
+            50,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(550.0, 22.0)
+        .down_from(state.ids.mouse_emulation_text, 10.0)
+        .track_breadth(30.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.mouse_emulation_slider, ui)
+        {
+            events.push(AdjustMouseEmulationSensitivity(new_val));
+        }
+
+        Text::new(&format!("{}", settings.mouse_emulation_sensitivity))
+            .right_from(state.ids.mouse_emulation_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.mouse_emulation_value, ui);
+
+        // Button bindings, the radial menu of the skillbar first
+        let bindings = once(GamepadBinding::RadialMenu)
+            .chain(
+                GameButtons::INPUTS
+                    .iter()
+                    .copied()
+                    .map(GamepadBinding::Game),
+            )
+            .collect::<Vec<_>>();
+        if bindings.len() > state.ids.bindings_texts.len() {
+            state.update(|s| {
+                s.ids
+                    .bindings_texts
+                    .resize(bindings.len(), &mut ui.widget_id_generator());
+                s.ids
+                    .bindings_buttons
+                    .resize(bindings.len(), &mut ui.widget_id_generator());
+            });
+        }
+
+        let mut previous_element_id = state.ids.mouse_emulation_slider;
+        for (binding, (&text_id, &button_id)) in bindings.into_iter().zip(
+            state
+                .ids
+                .bindings_texts
+                .iter()
+                .zip(state.ids.bindings_buttons.iter()),
+        ) {
+            let button = match binding {
+                GamepadBinding::Game(input) => settings.game_buttons.get(input),
+                GamepadBinding::RadialMenu => Some(settings.game_buttons.radial_menu),
+            };
+            let (button_string, button_color) =
+                if self.global_state.window.remapping_gamepad == Some(binding) {
+                    (
+                        self.localized_strings
+                            .get_msg("hud-settings-awaitingkey")
+                            .into_owned(),
+                        TEXT_COLOR,
+                    )
+                } else if let Some(name) = button.and_then(|button| button.display_string()) {
+                    (name, TEXT_COLOR)
+                } else {
+                    (
+                        self.localized_strings
+                            .get_msg("hud-settings-unbound")
+                            .into_owned(),
+                        ERROR_COLOR,
+                    )
+                };
+            let loc_key = match binding {
+                GamepadBinding::Game(input) => input.get_localization_key(),
+                GamepadBinding::RadialMenu => "hud-settings-radial_menu",
+            };
+            let label = self.localized_strings.get_msg(loc_key);
+            let text_widget = Text::new(&label)
+                .color(label_color(self.search_matches, loc_key))
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(18))
+                .down_from(previous_element_id, 10.0);
+            let text_width = text_widget.get_w(ui).unwrap_or(0.0);
+            text_widget.set(text_id, ui);
+            if Button::new()
+                .label(&button_string)
+                .label_color(button_color)
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_font_size(self.fonts.cyri.scale(15))
+                .w(150.0)
+                .rgba(0.0, 0.0, 0.0, 0.0)
+                .border_rgba(0.0, 0.0, 0.0, 255.0)
+                .label_y(Relative::Scalar(3.0))
+                .right_from(text_id, 350.0 - text_width)
+                .set(button_id, ui)
+                .was_clicked()
+            {
+                // The remap/clear toggle of the keybindings applies to the buttons too
+                if self.global_state.window.keybinding_mode {
+                    events.push(ChangeBinding(binding));
+                } else {
+                    events.push(RemoveBinding(binding));
+                }
+            }
+            previous_element_id = text_id;
+        }
+
+        // Reset the gamepad settings to the default settings
+        if Button::image(self.imgs.button)
+            .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .down_from(previous_element_id, 20.0)
+            .label(&self.localized_strings.get_msg("hud-settings-reset_gamepad"))
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_y(Relative::Scalar(2.0))
+            .set(state.ids.reset_gamepad_button, ui)
+            .was_clicked()
+        {
+            events.push(ResetGamepadSettings);
+        }
+
+        // Empty rectangle as bottom margin
+        Rectangle::fill_with([1.0, 1.0], color::TRANSPARENT)
+            .down_from(state.ids.reset_gamepad_button, 10.0)
+            .set(state.ids.bottom_margin, ui);
+
+        events
+    }
+}
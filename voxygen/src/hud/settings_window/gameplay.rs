@@ -1,4 +1,4 @@
-use super::{RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
+use super::{label_color, RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
 
 use crate::{
     hud::{img_ids::Imgs, PressBehavior, MENU_BG, TEXT_COLOR},
@@ -50,6 +50,8 @@ widget_ids! {
         auto_camera_label,
         bow_zoom_button,
         bow_zoom_label,
+        session_history_button,
+        session_history_label,
     }
 }
 
@@ -59,6 +61,7 @@ pub struct Gameplay<'a> {
     imgs: &'a Imgs,
     fonts: &'a Fonts,
     localized_strings: &'a Localization,
+    search_matches: &'a [String],
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
 }
@@ -68,12 +71,14 @@ impl<'a> Gameplay<'a> {
         imgs: &'a Imgs,
         fonts: &'a Fonts,
         localized_strings: &'a Localization,
+        search_matches: &'a [String],
     ) -> Self {
         Self {
             global_state,
             imgs,
             fonts,
             localized_strings,
+            search_matches,
             common: widget::CommonBuilder::default(),
         }
     }
@@ -130,7 +135,10 @@ impl<'a> Widget for Gameplay<'a> {
         .top_left_with_margins_on(state.ids.window, 10.0, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-pan_sensitivity",
+        ))
         .set(state.ids.mouse_pan_label, ui);
 
         if let Some(new_val) = ImageSlider::discrete(
@@ -166,7 +174,10 @@ impl<'a> Widget for Gameplay<'a> {
         .down_from(state.ids.mouse_pan_slider, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-zoom_sensitivity",
+        ))
         .set(state.ids.mouse_zoom_label, ui);
 
         if let Some(new_val) = ImageSlider::discrete(
@@ -202,7 +213,10 @@ impl<'a> Widget for Gameplay<'a> {
         .down_from(state.ids.mouse_zoom_slider, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-camera_clamp_angle",
+        ))
         .set(state.ids.camera_clamp_label, ui);
 
         if let Some(new_val) = ImageSlider::discrete(
@@ -256,7 +270,10 @@ impl<'a> Widget for Gameplay<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.mouse_zoom_invert_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-invert_scroll_zoom",
+        ))
         .set(state.ids.mouse_zoom_invert_label, ui);
 
         // Mouse Y Inversion
@@ -286,7 +303,10 @@ impl<'a> Widget for Gameplay<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.mouse_y_invert_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-invert_mouse_y_axis",
+        ))
         .set(state.ids.mouse_y_invert_label, ui);
 
         // Controller Y Pan Inversion
@@ -316,7 +336,10 @@ impl<'a> Widget for Gameplay<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.controller_y_invert_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-invert_controller_y_axis",
+        ))
         .set(state.ids.controller_y_invert_label, ui);
 
         // Mouse Smoothing Toggle
@@ -346,7 +369,10 @@ impl<'a> Widget for Gameplay<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.smooth_pan_toggle_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-enable_mouse_smoothing",
+        ))
         .set(state.ids.smooth_pan_toggle_label, ui);
 
         // Free look behaviour
@@ -358,7 +384,10 @@ impl<'a> Widget for Gameplay<'a> {
         .down_from(state.ids.mouse_zoom_invert_button, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-free_look_behavior",
+        ))
         .set(state.ids.free_look_behavior_text, ui);
 
         let mode_label_list = [
@@ -396,7 +425,10 @@ impl<'a> Widget for Gameplay<'a> {
         .right_from(state.ids.free_look_behavior_text, 150.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-auto_walk_behavior",
+        ))
         .set(state.ids.auto_walk_behavior_text, ui);
 
         let auto_walk_selected = self.global_state.settings.gameplay.auto_walk_behavior as usize;
@@ -425,7 +457,10 @@ impl<'a> Widget for Gameplay<'a> {
         .down_from(state.ids.free_look_behavior_list, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-camera_clamp_behavior",
+        ))
         .set(state.ids.camera_clamp_behavior_text, ui);
 
         let camera_clamp_selected =
@@ -475,7 +510,10 @@ impl<'a> Widget for Gameplay<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.stop_auto_walk_on_input_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-stop_auto_walk_on_input",
+        ))
         .set(state.ids.stop_auto_walk_on_input_label, ui);
 
         // Auto-camera toggle
@@ -501,7 +539,7 @@ impl<'a> Widget for Gameplay<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.auto_camera_button)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-auto_camera"))
             .set(state.ids.auto_camera_label, ui);
 
         // Charging bow zoom toggle
@@ -525,9 +563,42 @@ impl<'a> Widget for Gameplay<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.bow_zoom_button)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-bow_zoom"))
             .set(state.ids.bow_zoom_label, ui);
 
+        // Session history toggle
+        let session_history_toggle = ToggleButton::new(
+            self.global_state.settings.gameplay.save_session_history,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.bow_zoom_button, 8.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.session_history_button, ui);
+
+        if self.global_state.settings.gameplay.save_session_history != session_history_toggle {
+            events.push(ChangeSaveSessionHistory(
+                !self.global_state.settings.gameplay.save_session_history,
+            ));
+        }
+
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-save_session_history"),
+        )
+        .right_from(state.ids.session_history_button, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .graphics_for(state.ids.session_history_button)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-save_session_history",
+        ))
+        .set(state.ids.session_history_label, ui);
+
         // Reset the gameplay settings to the default settings
         if Button::image(self.imgs.button)
             .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
@@ -1,4 +1,4 @@
-use super::{ScaleChange, RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
+use super::{label_color, ScaleChange, RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
 
 use crate::{
     hud::{
@@ -42,6 +42,8 @@ widget_ids! {
         chat_button_label,
         hotkey_hints_button,
         hotkey_hints_button_label,
+        streamer_mode_button,
+        streamer_mode_button_label,
         ch_title,
         ch_transp_slider,
         ch_transp_value,
@@ -95,10 +97,31 @@ widget_ids! {
         speech_bubble_dark_mode_button,
         speech_bubble_icon_text,
         speech_bubble_icon_button,
+        speech_bubble_portrait_text,
+        speech_bubble_portrait_button,
         //
         experience_numbers_title,
         accum_experience_text,
         accum_experience_button,
+        //
+        combat_effects_title,
+        damage_indicators_text,
+        damage_indicators_button,
+        low_health_vignette_text,
+        low_health_vignette_button,
+        hit_shake_text,
+        hit_shake_button,
+        //
+        compass_title,
+        compass_show_text,
+        compass_show_button,
+        compass_opacity_text,
+        compass_opacity_slider,
+        compass_opacity_value,
+        //
+        nameplates_title,
+        nameplate_buttons[],
+        nameplate_texts[],
     }
 }
 
@@ -109,6 +132,7 @@ pub struct Interface<'a> {
     imgs: &'a Imgs,
     fonts: &'a Fonts,
     localized_strings: &'a Localization,
+    search_matches: &'a [String],
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
 }
@@ -119,6 +143,7 @@ impl<'a> Interface<'a> {
         imgs: &'a Imgs,
         fonts: &'a Fonts,
         localized_strings: &'a Localization,
+        search_matches: &'a [String],
     ) -> Self {
         Self {
             global_state,
@@ -126,6 +151,7 @@ impl<'a> Interface<'a> {
             imgs,
             fonts,
             localized_strings,
+            search_matches,
             common: widget::CommonBuilder::default(),
         }
     }
@@ -178,7 +204,7 @@ impl<'a> Widget for Interface<'a> {
             .top_left_with_margins_on(state.ids.window, 5.0, 5.0)
             .font_size(self.fonts.cyri.scale(18))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-general"))
             .set(state.ids.general_txt, ui);
 
         // Help
@@ -202,7 +228,7 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.button_help)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-help_window"))
             .set(state.ids.show_help_label, ui);
 
         // Loading Screen Tips
@@ -228,7 +254,10 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.load_tips_button)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-loading_tips",
+            ))
             .set(state.ids.load_tips_button_label, ui);
 
         // Debug
@@ -252,7 +281,7 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.debug_button)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-debug_info"))
             .set(state.ids.debug_button_label, ui);
 
         // Hitboxes
@@ -276,7 +305,10 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.hitboxes_button)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-show_hitboxes",
+            ))
             .set(state.ids.hitboxes_button_label, ui);
 
         // Chat
@@ -300,7 +332,7 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.chat_button)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-show_chat"))
             .set(state.ids.chat_button_label, ui);
 
         // Hotkey hints
@@ -328,15 +360,45 @@ impl<'a> Widget for Interface<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.hotkey_hints_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-show_hotkey_hints",
+        ))
         .set(state.ids.hotkey_hints_button_label, ui);
 
+        // Streamer mode
+        let streamer_mode = ToggleButton::new(
+            self.global_state.settings.interface.streamer_mode,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.hotkey_hints_button, 8.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.streamer_mode_button, ui);
+
+        if self.global_state.settings.interface.streamer_mode != streamer_mode {
+            events.push(ToggleStreamerMode(streamer_mode));
+        }
+
+        Text::new(&self.localized_strings.get_msg("hud-settings-streamer_mode"))
+            .right_from(state.ids.streamer_mode_button, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .graphics_for(state.ids.streamer_mode_button)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-streamer_mode",
+            ))
+            .set(state.ids.streamer_mode_button_label, ui);
+
         // Ui Scale
         Text::new(&self.localized_strings.get_msg("hud-settings-ui_scale"))
-            .down_from(state.ids.hotkey_hints_button, 20.0)
+            .down_from(state.ids.streamer_mode_button, 20.0)
             .font_size(self.fonts.cyri.scale(18))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-ui_scale"))
             .set(state.ids.ui_scale_label, ui);
 
         // Relative Scaling Button
@@ -375,7 +437,10 @@ impl<'a> Widget for Interface<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.relative_to_win_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-relative_scaling",
+        ))
         .set(state.ids.relative_to_win_text, ui);
 
         // Absolute Scaling Button
@@ -414,7 +479,10 @@ impl<'a> Widget for Interface<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.absolute_scale_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-custom_scaling",
+        ))
         .set(state.ids.absolute_scale_text, ui);
 
         // Slider -> Inactive when "Relative to window" is selected
@@ -590,13 +658,13 @@ impl<'a> Widget for Interface<'a> {
             .down_from(state.ids.absolute_scale_button, 20.0)
             .font_size(self.fonts.cyri.scale(18))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-crosshair"))
             .set(state.ids.ch_title, ui);
         Text::new(&self.localized_strings.get_msg("hud-settings-opacity"))
             .right_from(state.ids.ch_3_bg, 20.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-opacity"))
             .set(state.ids.ch_transp_text, ui);
 
         if let Some(new_val) = ImageSlider::continuous(
@@ -629,7 +697,7 @@ impl<'a> Widget for Interface<'a> {
             .down_from(state.ids.ch_1_bg, 20.0)
             .font_size(self.fonts.cyri.scale(18))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-hotbar"))
             .set(state.ids.hotbar_title, ui);
         // Show Shortcut Numbers
         if Button::image(
@@ -669,7 +737,10 @@ impl<'a> Widget for Interface<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.show_shortcuts_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-toggle_shortcuts",
+        ))
         .set(state.ids.show_shortcuts_text, ui);
         // Buff Position
         // Buffs above skills
@@ -701,7 +772,10 @@ impl<'a> Widget for Interface<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.show_shortcuts_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-buffs_skillbar",
+        ))
         .set(state.ids.buff_pos_bar_text, ui);
         // Buffs left from minimap
         if Button::image(match self.global_state.settings.interface.buff_position {
@@ -728,7 +802,7 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.show_shortcuts_button)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-buffs_mmap"))
             .set(state.ids.buff_pos_map_text, ui);
 
         // Content Right Side
@@ -752,7 +826,10 @@ impl<'a> Widget for Interface<'a> {
         .top_left_with_margins_on(state.ids.window_r, 5.0, 5.0)
         .font_size(self.fonts.cyri.scale(18))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-scrolling_combat_text",
+        ))
         .set(state.ids.sct_title, ui);
         // Generally toggle the SCT
         let show_sct = ToggleButton::new(
@@ -778,7 +855,10 @@ impl<'a> Widget for Interface<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.sct_show_radio)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-scrolling_combat_text",
+        ))
         .set(state.ids.sct_show_text, ui);
         if self.global_state.settings.interface.sct {
             let sct_dmg_accum_duration =
@@ -798,7 +878,10 @@ impl<'a> Widget for Interface<'a> {
             .right_from(state.ids.sct_show_radio, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-damage_accumulation_duration",
+            ))
             .set(state.ids.sct_dmg_accum_duration_text, ui);
 
             if let Some(new_val) = ImageSlider::continuous(
@@ -852,7 +935,10 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.sct_show_inc_dmg_radio)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-incoming_damage",
+            ))
             .set(state.ids.sct_show_inc_dmg_text, ui);
             if self.global_state.settings.interface.sct_inc_dmg {
                 Text::new(
@@ -864,7 +950,10 @@ impl<'a> Widget for Interface<'a> {
                 .right_from(state.ids.sct_show_inc_dmg_radio, 10.0)
                 .font_size(self.fonts.cyri.scale(14))
                 .font_id(self.fonts.cyri.conrod_id)
-                .color(TEXT_COLOR)
+                .color(label_color(
+                    self.search_matches,
+                    "hud-settings-incoming_damage_accumulation_duration",
+                ))
                 .set(state.ids.sct_inc_dmg_accum_duration_text, ui);
 
                 if let Some(new_val) = ImageSlider::continuous(
@@ -924,7 +1013,10 @@ impl<'a> Widget for Interface<'a> {
                 .font_size(self.fonts.cyri.scale(14))
                 .font_id(self.fonts.cyri.conrod_id)
                 .graphics_for(state.ids.sct_round_dmg_radio)
-                .color(TEXT_COLOR)
+                .color(label_color(
+                    self.search_matches,
+                    "hud-settings-round_damage",
+                ))
                 .set(state.ids.sct_round_dmg_text, ui);
         }
 
@@ -942,7 +1034,10 @@ impl<'a> Widget for Interface<'a> {
             .x_relative_to(state.ids.sct_show_text, -40.0)
             .font_size(self.fonts.cyri.scale(18))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-speech_bubble",
+            ))
             .set(state.ids.speech_bubble_text, ui);
 
         // Show own speech bubbles
@@ -967,7 +1062,10 @@ impl<'a> Widget for Interface<'a> {
         .right_from(state.ids.speech_bubble_self_button, 10.0)
         .font_size(self.fonts.cyri.scale(15))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-speech_bubble_self",
+        ))
         .set(state.ids.speech_bubble_self_text, ui);
 
         // Speech bubble dark mode
@@ -992,7 +1090,10 @@ impl<'a> Widget for Interface<'a> {
         .right_from(state.ids.speech_bubble_dark_mode_button, 10.0)
         .font_size(self.fonts.cyri.scale(15))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-speech_bubble_dark_mode",
+        ))
         .set(state.ids.speech_bubble_dark_mode_text, ui);
         // Speech bubble icon
         let speech_bubble_icon = ToggleButton::new(
@@ -1016,8 +1117,38 @@ impl<'a> Widget for Interface<'a> {
         .right_from(state.ids.speech_bubble_icon_button, 10.0)
         .font_size(self.fonts.cyri.scale(15))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-speech_bubble_icon",
+        ))
         .set(state.ids.speech_bubble_icon_text, ui);
+        // Speech bubble portrait
+        let speech_bubble_portrait = ToggleButton::new(
+            self.global_state.settings.interface.speech_bubble_portrait,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .down_from(state.ids.speech_bubble_icon_button, 10.0)
+        .w_h(18.0, 18.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.speech_bubble_portrait_button, ui);
+        if self.global_state.settings.interface.speech_bubble_portrait != speech_bubble_portrait {
+            events.push(SpeechBubblePortrait(speech_bubble_portrait));
+        }
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-speech_bubble_portrait"),
+        )
+        .right_from(state.ids.speech_bubble_portrait_button, 10.0)
+        .font_size(self.fonts.cyri.scale(15))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-speech_bubble_portrait",
+        ))
+        .set(state.ids.speech_bubble_portrait_text, ui);
 
         // Energybars Numbers
         // Hotbar text
@@ -1026,10 +1157,13 @@ impl<'a> Widget for Interface<'a> {
                 .localized_strings
                 .get_msg("hud-settings-energybar_numbers"),
         )
-        .down_from(state.ids.speech_bubble_icon_button, 20.0)
+        .down_from(state.ids.speech_bubble_portrait_button, 20.0)
         .font_size(self.fonts.cyri.scale(18))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-energybar_numbers",
+        ))
         .set(state.ids.bar_numbers_title, ui);
 
         // None
@@ -1060,7 +1194,7 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.show_bar_numbers_none_button)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-none"))
             .set(state.ids.show_bar_numbers_none_text, ui);
 
         // Values
@@ -1091,7 +1225,7 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.show_bar_numbers_values_button)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-values"))
             .set(state.ids.show_bar_numbers_values_text, ui);
 
         // Percentages
@@ -1122,7 +1256,7 @@ impl<'a> Widget for Interface<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.show_bar_numbers_percentage_button)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-percentages"))
             .set(state.ids.show_bar_numbers_percentage_text, ui);
 
         // Always show energy bars
@@ -1150,7 +1284,10 @@ impl<'a> Widget for Interface<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.always_show_bars_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-always_show_bars",
+        ))
         .set(state.ids.always_show_bars_label, ui);
 
         // Experience Numbers
@@ -1162,7 +1299,10 @@ impl<'a> Widget for Interface<'a> {
         .down_from(state.ids.always_show_bars_button, 20.0)
         .font_size(self.fonts.cyri.scale(18))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-experience_numbers",
+        ))
         .set(state.ids.experience_numbers_title, ui);
 
         // Accumulate Experience Gained
@@ -1192,9 +1332,257 @@ impl<'a> Widget for Interface<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.accum_experience_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-accumulate_experience",
+        ))
         .set(state.ids.accum_experience_text, ui);
 
+        // Combat effects
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-combat_effects"),
+        )
+        .down_from(state.ids.accum_experience_button, 20.0)
+        .font_size(self.fonts.cyri.scale(18))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-combat_effects",
+        ))
+        .set(state.ids.combat_effects_title, ui);
+
+        let damage_indicators = ToggleButton::new(
+            self.global_state.settings.interface.damage_indicators,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.combat_effects_title, 8.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.damage_indicators_button, ui);
+
+        if self.global_state.settings.interface.damage_indicators != damage_indicators {
+            events.push(ToggleDamageIndicators(damage_indicators));
+        }
+
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-damage_indicators"),
+        )
+        .right_from(state.ids.damage_indicators_button, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .graphics_for(state.ids.damage_indicators_button)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-damage_indicators",
+        ))
+        .set(state.ids.damage_indicators_text, ui);
+
+        let low_health_vignette = ToggleButton::new(
+            self.global_state.settings.interface.low_health_vignette,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.damage_indicators_button, 8.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.low_health_vignette_button, ui);
+
+        if self.global_state.settings.interface.low_health_vignette != low_health_vignette {
+            events.push(ToggleLowHealthVignette(low_health_vignette));
+        }
+
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-low_health_vignette"),
+        )
+        .right_from(state.ids.low_health_vignette_button, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .graphics_for(state.ids.low_health_vignette_button)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-low_health_vignette",
+        ))
+        .set(state.ids.low_health_vignette_text, ui);
+
+        let hit_shake = ToggleButton::new(
+            self.global_state.settings.interface.hit_shake,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.low_health_vignette_button, 8.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.hit_shake_button, ui);
+
+        if self.global_state.settings.interface.hit_shake != hit_shake {
+            events.push(ToggleHitShake(hit_shake));
+        }
+
+        Text::new(&self.localized_strings.get_msg("hud-settings-hit_shake"))
+            .right_from(state.ids.hit_shake_button, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .graphics_for(state.ids.hit_shake_button)
+            .color(label_color(self.search_matches, "hud-settings-hit_shake"))
+            .set(state.ids.hit_shake_text, ui);
+
+        // Compass
+        Text::new(&self.localized_strings.get_msg("hud-settings-compass"))
+            .down_from(state.ids.hit_shake_button, 20.0)
+            .font_size(self.fonts.cyri.scale(18))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(self.search_matches, "hud-settings-compass"))
+            .set(state.ids.compass_title, ui);
+
+        let compass_show = ToggleButton::new(
+            self.global_state.settings.interface.compass_show,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .down_from(state.ids.compass_title, 8.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.compass_show_button, ui);
+
+        if self.global_state.settings.interface.compass_show != compass_show {
+            events.push(CompassShow(compass_show));
+        }
+
+        Text::new(&self.localized_strings.get_msg("hud-settings-compass_show"))
+            .right_from(state.ids.compass_show_button, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .graphics_for(state.ids.compass_show_button)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-compass_show",
+            ))
+            .set(state.ids.compass_show_text, ui);
+
+        let compass_opacity = self.global_state.settings.interface.compass_opacity;
+        Text::new(&self.localized_strings.get_msg("hud-settings-opacity"))
+            .down_from(state.ids.compass_show_button, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(self.search_matches, "hud-settings-opacity"))
+            .set(state.ids.compass_opacity_text, ui);
+
+        if let Some(new_val) = ImageSlider::continuous(
+            compass_opacity,
+            0.0,
+            1.0,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(104.0, 22.0)
+        .down_from(state.ids.compass_opacity_text, 8.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.compass_opacity_slider, ui)
+        {
+            events.push(CompassOpacity(new_val));
+        }
+
+        Text::new(&format!("{:.2}", compass_opacity))
+            .right_from(state.ids.compass_opacity_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .graphics_for(state.ids.compass_opacity_slider)
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.compass_opacity_value, ui);
+
+        // Nameplates
+        Text::new(&self.localized_strings.get_msg("hud-settings-nameplates"))
+            .down_from(state.ids.compass_opacity_slider, 20.0)
+            .font_size(self.fonts.cyri.scale(18))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(self.search_matches, "hud-settings-nameplates"))
+            .set(state.ids.nameplates_title, ui);
+
+        let interface = &self.global_state.settings.interface;
+        let nameplate_toggles: [(bool, &str, fn(bool) -> InterfaceChange); 6] = [
+            (
+                interface.nameplate_player_names,
+                "hud-settings-nameplate_player_names",
+                NameplatePlayerNames,
+            ),
+            (
+                interface.nameplate_npc_names,
+                "hud-settings-nameplate_npc_names",
+                NameplateNpcNames,
+            ),
+            (
+                interface.nameplate_levels,
+                "hud-settings-nameplate_levels",
+                NameplateLevels,
+            ),
+            (
+                interface.nameplate_health_bars,
+                "hud-settings-nameplate_health_bars",
+                NameplateHealthBars,
+            ),
+            (
+                interface.nameplate_distance_fade,
+                "hud-settings-nameplate_distance_fade",
+                NameplateDistanceFade,
+            ),
+            (
+                interface.nameplate_hostility_colors,
+                "hud-settings-nameplate_hostility_colors",
+                NameplateHostilityColors,
+            ),
+        ];
+        if state.ids.nameplate_buttons.len() < nameplate_toggles.len() {
+            state.update(|s| {
+                s.ids
+                    .nameplate_buttons
+                    .resize(nameplate_toggles.len(), &mut ui.widget_id_generator());
+                s.ids
+                    .nameplate_texts
+                    .resize(nameplate_toggles.len(), &mut ui.widget_id_generator());
+            });
+        }
+        for (i, (shown, key, change)) in nameplate_toggles.into_iter().enumerate() {
+            let button_id = state.ids.nameplate_buttons[i];
+            let toggled = ToggleButton::new(shown, self.imgs.checkbox, self.imgs.checkbox_checked)
+                .w_h(18.0, 18.0)
+                .down_from(
+                    if i == 0 {
+                        state.ids.nameplates_title
+                    } else {
+                        state.ids.nameplate_buttons[i - 1]
+                    },
+                    8.0,
+                )
+                .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+                .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+                .set(button_id, ui);
+
+            if toggled != shown {
+                events.push(change(toggled));
+            }
+
+            Text::new(&self.localized_strings.get_msg(key))
+                .right_from(button_id, 10.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .graphics_for(button_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.nameplate_texts[i], ui);
+        }
+
         // Reset the interface settings to the default settings
         if Button::image(self.imgs.button)
             .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
@@ -1,3 +1,4 @@
+use super::label_color;
 use crate::{
     hud::{img_ids::Imgs, TEXT_COLOR},
     session::settings_change::{Language as LanguageChange, Language::*},
@@ -26,6 +27,7 @@ widget_ids! {
 pub struct Language<'a> {
     global_state: &'a GlobalState,
     localized_strings: &'a Localization,
+    search_matches: &'a [String],
     imgs: &'a Imgs,
     fonts: &'a Fonts,
     #[conrod(common_builder)]
@@ -37,10 +39,12 @@ impl<'a> Language<'a> {
         imgs: &'a Imgs,
         fonts: &'a Fonts,
         localized_strings: &'a Localization,
+        search_matches: &'a [String],
     ) -> Self {
         Self {
             global_state,
             localized_strings,
+            search_matches,
             imgs,
             fonts,
             common: widget::CommonBuilder::default(),
@@ -158,7 +162,10 @@ impl<'a> Widget for Language<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .graphics_for(state.ids.english_fallback_button)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-english_fallback",
+        ))
         .set(state.ids.english_fallback_button_label, ui);
 
         events
@@ -1,22 +1,26 @@
+mod accessibility;
 mod chat;
 mod controls;
+mod gamepad;
 mod gameplay;
 mod interface;
 mod language;
 mod networking;
+mod profiles;
 mod sound;
 mod video;
 
 use crate::{
-    hud::{img_ids::Imgs, Show, TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN},
+    game_input::GameInput,
+    hud::{img_ids::Imgs, Show, TEXT_COLOR, TEXT_GRAY_COLOR, UI_HIGHLIGHT_0, UI_MAIN},
     session::settings_change::SettingsChange,
     ui::fonts::Fonts,
     GlobalState,
 };
 use conrod_core::{
     color,
-    widget::{self, Button, Image, Rectangle, Text},
-    widget_ids, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+    widget::{self, Button, Image, Rectangle, Text, TextEdit},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
 };
 use i18n::Localization;
 
@@ -32,6 +36,14 @@ widget_ids! {
         settings_title,
         settings_content_align,
 
+        search_bg,
+        search_icon,
+        search_placeholder,
+        search_input,
+        search_clear,
+        search_results[],
+        search_no_results,
+
         tabs[],
         interface,
         gameplay,
@@ -41,13 +53,26 @@ widget_ids! {
         language,
         chat,
         networking,
+        accessibility,
+        profiles,
     }
 }
 
 const RESET_BUTTONS_HEIGHT: f64 = 34.0;
 const RESET_BUTTONS_WIDTH: f64 = 155.0;
+/// Colour of the labels of the settings matching the search
+const SEARCH_HIGHLIGHT_COLOR: Color = Color::Rgba(1.0, 0.82, 0.27, 1.0);
+
+/// Colour of the label of a setting, the ones matching the search standing out
+fn label_color(search_matches: &[String], key: &str) -> Color {
+    if search_matches.iter().any(|matching| matching == key) {
+        SEARCH_HIGHLIGHT_COLOR
+    } else {
+        TEXT_COLOR
+    }
+}
 
-#[derive(Debug, EnumIter, PartialEq)]
+#[derive(Clone, Copy, Debug, EnumIter, PartialEq)]
 pub enum SettingsTab {
     Interface,
     Chat,
@@ -57,6 +82,8 @@ pub enum SettingsTab {
     Controls,
     Lang,
     Networking,
+    Accessibility,
+    Profiles,
 }
 impl SettingsTab {
     fn name_key(&self) -> &str {
@@ -69,6 +96,8 @@ impl SettingsTab {
             SettingsTab::Sound => "common-sound",
             SettingsTab::Lang => "common-languages",
             SettingsTab::Networking => "common-networking",
+            SettingsTab::Accessibility => "common-accessibility",
+            SettingsTab::Profiles => "common-profiles",
         }
     }
 
@@ -82,6 +111,194 @@ impl SettingsTab {
             SettingsTab::Sound => "common-sound_settings",
             SettingsTab::Lang => "common-language_settings",
             SettingsTab::Networking => "common-networking_settings",
+            SettingsTab::Accessibility => "common-accessibility_settings",
+            SettingsTab::Profiles => "common-profiles_settings",
+        }
+    }
+
+    /// Localization keys of the labels of the settings in the tab, which the
+    /// search field looks through
+    fn setting_keys(&self) -> Vec<String> {
+        let labels: &[&str] = match self {
+            SettingsTab::Interface => &[
+                "hud-settings-general",
+                "hud-settings-help_window",
+                "hud-settings-loading_tips",
+                "hud-settings-debug_info",
+                "hud-settings-show_hitboxes",
+                "hud-settings-show_chat",
+                "hud-settings-show_hotkey_hints",
+                "hud-settings-streamer_mode",
+                "hud-settings-ui_scale",
+                "hud-settings-relative_scaling",
+                "hud-settings-custom_scaling",
+                "hud-settings-crosshair",
+                "hud-settings-opacity",
+                "hud-settings-hotbar",
+                "hud-settings-toggle_shortcuts",
+                "hud-settings-buffs_skillbar",
+                "hud-settings-buffs_mmap",
+                "hud-settings-scrolling_combat_text",
+                "hud-settings-damage_accumulation_duration",
+                "hud-settings-incoming_damage",
+                "hud-settings-incoming_damage_accumulation_duration",
+                "hud-settings-round_damage",
+                "hud-settings-speech_bubble",
+                "hud-settings-speech_bubble_self",
+                "hud-settings-speech_bubble_dark_mode",
+                "hud-settings-speech_bubble_icon",
+                "hud-settings-speech_bubble_portrait",
+                "hud-settings-energybar_numbers",
+                "hud-settings-always_show_bars",
+                "hud-settings-experience_numbers",
+                "hud-settings-accumulate_experience",
+                "hud-settings-combat_effects",
+                "hud-settings-damage_indicators",
+                "hud-settings-low_health_vignette",
+                "hud-settings-hit_shake",
+                "hud-settings-compass",
+                "hud-settings-compass_show",
+                "hud-settings-nameplates",
+                "hud-settings-nameplate_player_names",
+                "hud-settings-nameplate_npc_names",
+                "hud-settings-nameplate_levels",
+                "hud-settings-nameplate_health_bars",
+                "hud-settings-nameplate_distance_fade",
+                "hud-settings-nameplate_hostility_colors",
+            ],
+            SettingsTab::Chat => &[
+                "hud-settings-general",
+                "hud-settings-background_opacity",
+                "hud-settings-chat_character_name",
+                "hud-settings-chat_history",
+                "hud-settings-chat_tabs",
+                "hud-settings-label",
+                "hud-settings-messages",
+                "hud-settings-show_all",
+                "hud-settings-activity",
+                "hud-settings-death",
+                "hud-settings-say",
+                "hud-settings-group",
+                "hud-settings-guild",
+                "hud-settings-faction",
+                "hud-settings-region",
+                "hud-settings-world",
+                "hud-settings-whispers",
+                "hud-settings-system",
+            ],
+            SettingsTab::Video => &[
+                "hud-settings-view_distance",
+                "hud-settings-entity_view_distance",
+                "hud-settings-sprites_view_distance",
+                "hud-settings-lod_distance",
+                "hud-settings-entities_detail_distance",
+                "hud-settings-sprite_density",
+                "hud-settings-maximum_fps",
+                "hud-settings-background_fps",
+                "hud-settings-present_mode",
+                "hud-settings-fov",
+                "hud-settings-lod_detail",
+                "hud-settings-gamma",
+                "hud-settings-exposure",
+                "hud-settings-ambiance",
+                "hud-settings-color_grading",
+                "hud-settings-antialiasing_mode",
+                "hud-settings-bloom",
+                "hud-settings-point_glow",
+                "hud-settings-ambient_occlusion",
+                "hud-settings-upscale_factor",
+                "hud-settings-cloud_rendering_mode",
+                "hud-settings-volumetric_fog",
+                "hud-settings-fluid_rendering_mode",
+                "hud-settings-screen_space_reflections",
+                "hud-settings-lighting_rendering_mode",
+                "hud-settings-tonemapping",
+                "hud-settings-shadow_rendering_mode",
+                "hud-settings-shadow_rendering_mode-map-resolution",
+                "hud-settings-shadow_rendering_mode-map-soft",
+                "hud-settings-rain_occlusion-resolution",
+                "hud-settings-shadow_rendering_mode-map-point_lights",
+                "hud-settings-gpu_profiler",
+                "hud-settings-particles",
+                "hud-settings-weapon_trails",
+                "hud-settings-flashing_lights",
+                "hud-settings-flashing_lights_info",
+                "hud-settings-adaptive_quality",
+                "hud-settings-adaptive_min_fps",
+                "hud-settings-adaptive_min_view_distance",
+                "hud-settings-adaptive_particles",
+                "hud-settings-resolution",
+                "hud-settings-bit_depth",
+                "hud-settings-refresh_rate",
+                "hud-settings-fullscreen",
+                "hud-settings-fullscreen_mode",
+                "hud-settings-save_window_size",
+            ],
+            SettingsTab::Sound => &[
+                "hud-settings-master_volume",
+                "hud-settings-inactive_master_volume_perc",
+                "hud-settings-music_volume",
+                "hud-settings-sound_effect_volume",
+                "hud-settings-ambience_volume",
+                "hud-settings-music_spacing",
+                "hud-settings-voice_chat",
+                "hud-settings-voice_volume",
+                "hud-settings-audio_device",
+            ],
+            SettingsTab::Gameplay => &[
+                "hud-settings-pan_sensitivity",
+                "hud-settings-zoom_sensitivity",
+                "hud-settings-camera_clamp_angle",
+                "hud-settings-invert_scroll_zoom",
+                "hud-settings-invert_mouse_y_axis",
+                "hud-settings-invert_controller_y_axis",
+                "hud-settings-enable_mouse_smoothing",
+                "hud-settings-free_look_behavior",
+                "hud-settings-auto_walk_behavior",
+                "hud-settings-camera_clamp_behavior",
+                "hud-settings-stop_auto_walk_on_input",
+                "hud-settings-auto_camera",
+                "hud-settings-bow_zoom",
+                "hud-settings-save_session_history",
+            ],
+            SettingsTab::Controls => &[
+                "hud-settings-keyboard_mouse",
+                "hud-settings-controller",
+                "hud-settings-keybinding_scheme",
+                "hud-settings-controller_pan_sensitivity",
+                "hud-settings-stick_deadzone",
+                "hud-settings-mouse_emulation_sensitivity",
+                "hud-settings-radial_menu",
+            ],
+            SettingsTab::Lang => &["hud-settings-english_fallback"],
+            SettingsTab::Networking => &[
+                "hud-settings-view_distance",
+                "hud-settings-entity_view_distance",
+                "hud-settings-player_physics_behavior",
+                "hud-settings-lossy_terrain_compression",
+                "hud-settings-third_party_integrations",
+                "hud-settings-enable_discord_integration",
+            ],
+            SettingsTab::Accessibility => &[
+                "hud-settings-color_palette",
+                "hud-settings-text_scale",
+                "hud-settings-reduce_camera_shake",
+                "hud-settings-reduce_flashes",
+                "hud-settings-subtitles",
+            ],
+            SettingsTab::Profiles => &[
+                "hud-settings-saved_profiles",
+                "hud-settings-profile_name",
+                "hud-settings-profile_file",
+            ],
+        };
+        let keys = labels.iter().map(|key| key.to_string());
+        if *self == SettingsTab::Controls {
+            // Each binding is a setting of its own
+            keys.chain(GameInput::iter().map(|input| input.get_localization_key().to_owned()))
+                .collect()
+        } else {
+            keys.collect()
         }
     }
 }
@@ -95,6 +312,7 @@ pub struct SettingsWindow<'a> {
     localized_strings: &'a Localization,
     server_view_distance_limit: Option<u32>,
     fps: f32,
+    adaptive_quality_level: u32,
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
 }
@@ -108,6 +326,7 @@ impl<'a> SettingsWindow<'a> {
         localized_strings: &'a Localization,
         server_view_distance_limit: Option<u32>,
         fps: f32,
+        adaptive_quality_level: u32,
     ) -> Self {
         Self {
             global_state,
@@ -117,6 +336,7 @@ impl<'a> SettingsWindow<'a> {
             localized_strings,
             server_view_distance_limit,
             fps,
+            adaptive_quality_level,
             common: widget::CommonBuilder::default(),
         }
     }
@@ -124,6 +344,8 @@ impl<'a> SettingsWindow<'a> {
 
 pub struct State {
     ids: Ids,
+    /// Text the settings are searched for, the tabs being listed when empty
+    search: String,
 }
 
 pub enum Event {
@@ -148,6 +370,7 @@ impl<'a> Widget for SettingsWindow<'a> {
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             ids: Ids::new(id_gen),
+            search: String::new(),
         }
     }
 
@@ -214,38 +437,156 @@ impl<'a> Widget for SettingsWindow<'a> {
             events.push(Event::Close);
         }
 
-        // Tabs
-        if state.ids.tabs.len() < SettingsTab::iter().len() {
-            state.update(|s| {
-                s.ids
-                    .tabs
-                    .resize(SettingsTab::iter().len(), &mut ui.widget_id_generator())
-            });
+        // Search field, the tabs make way for the settings matching the search
+        Rectangle::fill([226.0, 24.0])
+            .rgba(0.0, 0.0, 0.0, 0.5)
+            .top_left_with_margins_on(state.ids.frame, 46.0, 5.0)
+            .set(state.ids.search_bg, ui);
+        Image::new(self.imgs.search_btn)
+            .w_h(16.0, 16.0)
+            .mid_left_with_margin_on(state.ids.search_bg, 4.0)
+            .graphics_for(state.ids.search_input)
+            .set(state.ids.search_icon, ui);
+        if state.search.is_empty() {
+            Text::new(&self.localized_strings.get_msg("hud-settings-search"))
+                .mid_left_with_margin_on(state.ids.search_bg, 26.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_GRAY_COLOR)
+                .graphics_for(state.ids.search_input)
+                .set(state.ids.search_placeholder, ui);
+        } else if Button::image(self.imgs.close_btn)
+            .w_h(14.0, 14.0)
+            .hover_image(self.imgs.close_btn_hover)
+            .press_image(self.imgs.close_btn_press)
+            .mid_right_with_margin_on(state.ids.search_bg, 5.0)
+            .set(state.ids.search_clear, ui)
+            .was_clicked()
+        {
+            state.update(|s| s.search.clear());
+        }
+        let search = state.search.clone();
+        let mut jump_to_result = false;
+        if let Some(mut search) = TextEdit::new(&search)
+            .w_h(176.0, 20.0)
+            .mid_left_with_margin_on(state.ids.search_bg, 26.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.search_input, ui)
+        {
+            // Enter jumps to the tab of the first match
+            jump_to_result = search.contains('\n');
+            search.retain(|c| c != '\n');
+            state.update(|s| s.search = search);
+        }
+
+        let query = state.search.trim().to_lowercase();
+        let search_results = if query.is_empty() {
+            Vec::new()
+        } else {
+            SettingsTab::iter()
+                .flat_map(|tab| tab.setting_keys().into_iter().map(move |key| (tab, key)))
+                .filter_map(|(tab, key)| {
+                    let label = self.localized_strings.get_msg(&key).into_owned();
+                    label
+                        .to_lowercase()
+                        .contains(&query)
+                        .then(|| (tab, key, label))
+                })
+                .collect::<Vec<_>>()
+        };
+        let search_matches = search_results
+            .iter()
+            .map(|(_, key, _)| key.clone())
+            .collect::<Vec<_>>();
+        if jump_to_result {
+            if let Some((tab, _, _)) = search_results.first() {
+                events.push(Event::ChangeTab(*tab));
+            }
         }
-        for (i, settings_tab) in SettingsTab::iter().enumerate() {
-            let tab_name = self.localized_strings.get_msg(settings_tab.name_key());
-            let mut button = Button::image(if self.show.settings_tab == settings_tab {
-                self.imgs.selection
-            } else {
-                self.imgs.nothing
-            })
-            .w_h(230.0, 48.0)
-            .hover_image(self.imgs.selection_hover)
-            .press_image(self.imgs.selection_press)
-            .image_color(color::rgba(1.0, 0.82, 0.27, 1.0))
-            .label(&tab_name)
-            .label_font_size(self.fonts.cyri.scale(tab_font_scale))
-            .label_font_id(self.fonts.cyri.conrod_id)
-            .label_color(TEXT_COLOR);
-
-            button = if i == 0 {
-                button.mid_top_with_margin_on(state.ids.tabs_align, 28.0)
-            } else {
-                button.down_from(state.ids.tabs[i - 1], 0.0)
-            };
-
-            if button.set(state.ids.tabs[i], ui).was_clicked() {
-                events.push(Event::ChangeTab(settings_tab));
+
+        // Search results
+        if !query.is_empty() {
+            if state.ids.search_results.len() < search_results.len() {
+                state.update(|s| {
+                    s.ids
+                        .search_results
+                        .resize(search_results.len(), &mut ui.widget_id_generator())
+                });
+            }
+            for (i, (tab, _, label)) in search_results.iter().enumerate() {
+                let tab_name = self.localized_strings.get_msg(tab.name_key());
+                let mut button = Button::image(if self.show.settings_tab == *tab {
+                    self.imgs.selection
+                } else {
+                    self.imgs.nothing
+                })
+                .w_h(230.0, 36.0)
+                .hover_image(self.imgs.selection_hover)
+                .press_image(self.imgs.selection_press)
+                .image_color(color::rgba(1.0, 0.82, 0.27, 1.0))
+                .label(&format!("{} ({})", label, tab_name))
+                .label_font_size(self.fonts.cyri.scale(14))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_color(TEXT_COLOR);
+
+                button = if i == 0 {
+                    button.mid_top_with_margin_on(state.ids.tabs_align, 28.0)
+                } else {
+                    button.down_from(state.ids.search_results[i - 1], 0.0)
+                };
+
+                if button.set(state.ids.search_results[i], ui).was_clicked() {
+                    events.push(Event::ChangeTab(*tab));
+                }
+            }
+            if search_results.is_empty() {
+                Text::new(
+                    &self
+                        .localized_strings
+                        .get_msg("hud-settings-search_no_results"),
+                )
+                .mid_top_with_margin_on(state.ids.tabs_align, 36.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_GRAY_COLOR)
+                .set(state.ids.search_no_results, ui);
+            }
+        } else {
+            // Tabs
+            if state.ids.tabs.len() < SettingsTab::iter().len() {
+                state.update(|s| {
+                    s.ids
+                        .tabs
+                        .resize(SettingsTab::iter().len(), &mut ui.widget_id_generator())
+                });
+            }
+            for (i, settings_tab) in SettingsTab::iter().enumerate() {
+                let tab_name = self.localized_strings.get_msg(settings_tab.name_key());
+                let mut button = Button::image(if self.show.settings_tab == settings_tab {
+                    self.imgs.selection
+                } else {
+                    self.imgs.nothing
+                })
+                .w_h(230.0, 48.0)
+                .hover_image(self.imgs.selection_hover)
+                .press_image(self.imgs.selection_press)
+                .image_color(color::rgba(1.0, 0.82, 0.27, 1.0))
+                .label(&tab_name)
+                .label_font_size(self.fonts.cyri.scale(tab_font_scale))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_color(TEXT_COLOR);
+
+                button = if i == 0 {
+                    button.mid_top_with_margin_on(state.ids.tabs_align, 28.0)
+                } else {
+                    button.down_from(state.ids.tabs[i - 1], 0.0)
+                };
+
+                if button.set(state.ids.tabs[i], ui).was_clicked() {
+                    events.push(Event::ChangeTab(settings_tab));
+                }
             }
         }
 
@@ -255,23 +596,36 @@ impl<'a> Widget for SettingsWindow<'a> {
         let imgs = self.imgs;
         let fonts = self.fonts;
         let localized_strings = self.localized_strings;
+        let search_matches = search_matches.as_slice();
         match self.show.settings_tab {
             SettingsTab::Interface => {
-                for change in
-                    interface::Interface::new(global_state, show, imgs, fonts, localized_strings)
-                        .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
-                        .wh_of(state.ids.settings_content_align)
-                        .set(state.ids.interface, ui)
+                for change in interface::Interface::new(
+                    global_state,
+                    show,
+                    imgs,
+                    fonts,
+                    localized_strings,
+                    search_matches,
+                )
+                .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
+                .wh_of(state.ids.settings_content_align)
+                .set(state.ids.interface, ui)
                 {
                     events.push(Event::SettingsChange(change.into()));
                 }
             },
             SettingsTab::Chat => {
-                for event in
-                    chat::Chat::new(global_state, self.show, imgs, fonts, localized_strings)
-                        .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
-                        .wh_of(state.ids.settings_content_align)
-                        .set(state.ids.chat, ui)
+                for event in chat::Chat::new(
+                    global_state,
+                    self.show,
+                    imgs,
+                    fonts,
+                    localized_strings,
+                    search_matches,
+                )
+                .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
+                .wh_of(state.ids.settings_content_align)
+                .set(state.ids.chat, ui)
                 {
                     match event {
                         chat::Event::ChatChange(change) => {
@@ -284,21 +638,40 @@ impl<'a> Widget for SettingsWindow<'a> {
                 }
             },
             SettingsTab::Gameplay => {
-                for change in gameplay::Gameplay::new(global_state, imgs, fonts, localized_strings)
-                    .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
-                    .wh_of(state.ids.settings_content_align)
-                    .set(state.ids.gameplay, ui)
+                for change in gameplay::Gameplay::new(
+                    global_state,
+                    imgs,
+                    fonts,
+                    localized_strings,
+                    search_matches,
+                )
+                .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
+                .wh_of(state.ids.settings_content_align)
+                .set(state.ids.gameplay, ui)
                 {
                     events.push(Event::SettingsChange(change.into()));
                 }
             },
             SettingsTab::Controls => {
-                for change in controls::Controls::new(global_state, imgs, fonts, localized_strings)
-                    .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
-                    .wh_of(state.ids.settings_content_align)
-                    .set(state.ids.controls, ui)
+                for event in controls::Controls::new(
+                    global_state,
+                    imgs,
+                    fonts,
+                    localized_strings,
+                    search_matches,
+                )
+                .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
+                .wh_of(state.ids.settings_content_align)
+                .set(state.ids.controls, ui)
                 {
-                    events.push(Event::SettingsChange(change.into()));
+                    match event {
+                        controls::Event::ControlChange(change) => {
+                            events.push(Event::SettingsChange(change.into()));
+                        },
+                        controls::Event::GamepadChange(change) => {
+                            events.push(Event::SettingsChange(change.into()));
+                        },
+                    }
                 }
             },
             SettingsTab::Video => {
@@ -307,8 +680,10 @@ impl<'a> Widget for SettingsWindow<'a> {
                     imgs,
                     fonts,
                     localized_strings,
+                    search_matches,
                     self.server_view_distance_limit,
                     self.fps,
+                    self.adaptive_quality_level,
                 )
                 .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
                 .wh_of(state.ids.settings_content_align)
@@ -318,19 +693,26 @@ impl<'a> Widget for SettingsWindow<'a> {
                 }
             },
             SettingsTab::Sound => {
-                for change in sound::Sound::new(global_state, imgs, fonts, localized_strings)
-                    .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
-                    .wh_of(state.ids.settings_content_align)
-                    .set(state.ids.sound, ui)
+                for change in
+                    sound::Sound::new(global_state, imgs, fonts, localized_strings, search_matches)
+                        .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
+                        .wh_of(state.ids.settings_content_align)
+                        .set(state.ids.sound, ui)
                 {
                     events.push(Event::SettingsChange(change.into()));
                 }
             },
             SettingsTab::Lang => {
-                for change in language::Language::new(global_state, imgs, fonts, localized_strings)
-                    .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
-                    .wh_of(state.ids.settings_content_align)
-                    .set(state.ids.language, ui)
+                for change in language::Language::new(
+                    global_state,
+                    imgs,
+                    fonts,
+                    localized_strings,
+                    search_matches,
+                )
+                .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
+                .wh_of(state.ids.settings_content_align)
+                .set(state.ids.language, ui)
                 {
                     events.push(Event::SettingsChange(change.into()));
                 }
@@ -341,6 +723,7 @@ impl<'a> Widget for SettingsWindow<'a> {
                     imgs,
                     fonts,
                     localized_strings,
+                    search_matches,
                     self.server_view_distance_limit,
                 )
                 .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
@@ -350,8 +733,115 @@ impl<'a> Widget for SettingsWindow<'a> {
                     events.push(Event::SettingsChange(change.into()));
                 }
             },
+            SettingsTab::Accessibility => {
+                for change in accessibility::Accessibility::new(
+                    global_state,
+                    imgs,
+                    fonts,
+                    localized_strings,
+                    search_matches,
+                )
+                .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
+                .wh_of(state.ids.settings_content_align)
+                .set(state.ids.accessibility, ui)
+                {
+                    events.push(Event::SettingsChange(change.into()));
+                }
+            },
+            SettingsTab::Profiles => {
+                for change in profiles::Profiles::new(
+                    global_state,
+                    imgs,
+                    fonts,
+                    localized_strings,
+                    search_matches,
+                )
+                .top_left_with_margins_on(state.ids.settings_content_align, 0.0, 0.0)
+                .wh_of(state.ids.settings_content_align)
+                .set(state.ids.profiles, ui)
+                {
+                    events.push(Event::SettingsChange(change.into()));
+                }
+            },
         }
 
         events
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keys of the buttons and the values picked from the lists, which aren't
+    /// settings of their own
+    const NOT_SETTINGS: &[&str] = &[
+        "hud-settings-none",
+        "hud-settings-all",
+        "hud-settings-group_only",
+        "hud-settings-values",
+        "hud-settings-percentages",
+        "hud-settings-press_behavior-toggle",
+        "hud-settings-press_behavior-hold",
+        "hud-settings-delete",
+        "hud-settings-adaptive_quality_off",
+        "hud-settings-adaptive_quality_lowered",
+        "hud-settings-adaptive_quality_full",
+        "hud-settings-default_audio_device",
+        "hud-settings-unavailable_audio_device",
+        "hud-settings-awaitingkey",
+        "hud-settings-unbound",
+        "hud-settings-binding_conflict",
+        "hud-settings-keep_bindings",
+        "hud-settings-swap_bindings",
+        "hud-settings-save_profile",
+        "hud-settings-load_profile",
+        "hud-settings-delete_profile",
+        "hud-settings-export_profile",
+        "hud-settings-import_profile",
+    ];
+
+    fn tab_source(tab: SettingsTab) -> &'static str {
+        match tab {
+            SettingsTab::Interface => include_str!("interface.rs"),
+            SettingsTab::Chat => include_str!("chat.rs"),
+            SettingsTab::Video => include_str!("video.rs"),
+            SettingsTab::Sound => include_str!("sound.rs"),
+            SettingsTab::Gameplay => include_str!("gameplay.rs"),
+            SettingsTab::Controls => include_str!("controls.rs"),
+            SettingsTab::Lang => include_str!("language.rs"),
+            SettingsTab::Networking => include_str!("networking.rs"),
+            SettingsTab::Accessibility => include_str!("accessibility.rs"),
+            SettingsTab::Profiles => include_str!("profiles.rs"),
+        }
+    }
+
+    #[test]
+    fn every_rendered_setting_can_be_searched() {
+        for tab in SettingsTab::iter() {
+            let keys = tab.setting_keys();
+            for key in tab_source(tab)
+                .split('"')
+                .skip(1)
+                .step_by(2)
+                .filter(|literal| literal.starts_with("hud-settings-"))
+            {
+                let name = &key["hud-settings-".len()..];
+                // Options of the drop down lists are named after the setting they
+                // belong to, and resetting a tab isn't a setting
+                let is_option = keys
+                    .iter()
+                    .any(|setting| key.starts_with(&format!("{}-", setting)));
+                assert!(
+                    keys.iter().any(|setting| setting == key)
+                        || is_option
+                        || name.starts_with("reset_")
+                        || NOT_SETTINGS.contains(&key),
+                    "{} is rendered in the {:?} settings but can't be searched",
+                    key,
+                    tab
+                );
+            }
+        }
+    }
+}
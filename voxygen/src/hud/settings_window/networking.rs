@@ -1,3 +1,4 @@
+use super::label_color;
 use crate::{
     hud::{img_ids::Imgs, MENU_BG, TEXT_COLOR},
     session::settings_change::{Networking as NetworkingChange, Networking::*},
@@ -37,6 +38,7 @@ pub struct Networking<'a> {
     imgs: &'a Imgs,
     fonts: &'a Fonts,
     localized_strings: &'a Localization,
+    search_matches: &'a [String],
     server_view_distance_limit: Option<u32>,
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
@@ -47,6 +49,7 @@ impl<'a> Networking<'a> {
         imgs: &'a Imgs,
         fonts: &'a Fonts,
         localized_strings: &'a Localization,
+        search_matches: &'a [String],
         server_view_distance_limit: Option<u32>,
     ) -> Self {
         Self {
@@ -54,6 +57,7 @@ impl<'a> Networking<'a> {
             imgs,
             fonts,
             localized_strings,
+            search_matches,
             server_view_distance_limit,
             common: widget::CommonBuilder::default(),
         }
@@ -99,7 +103,10 @@ impl<'a> Widget for Networking<'a> {
             .top_left_with_margins_on(state.ids.window, 10.0, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-view_distance",
+            ))
             .set(state.ids.terrain_vd_text, ui);
 
         let terrain_view_distance = self.global_state.settings.graphics.terrain_view_distance;
@@ -142,7 +149,10 @@ impl<'a> Widget for Networking<'a> {
         .down_from(state.ids.terrain_vd_slider, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-entity_view_distance",
+        ))
         .set(state.ids.entity_vd_text, ui);
 
         let soft_entity_vd_max = self
@@ -188,7 +198,10 @@ impl<'a> Widget for Networking<'a> {
         .down_from(state.ids.entity_vd_slider, 8.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-player_physics_behavior",
+        ))
         .set(state.ids.player_physics_behavior_text, ui);
 
         let player_physics_selected = self
@@ -227,7 +240,10 @@ impl<'a> Widget for Networking<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .right_from(state.ids.player_physics_behavior_text, 64.0)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-lossy_terrain_compression",
+        ))
         .set(state.ids.lossy_terrain_compression_label, ui);
 
         let lossy_terrain_compression = ToggleButton::new(
@@ -267,7 +283,10 @@ impl<'a> Widget for Networking<'a> {
             .down_from(state.ids.player_physics_behavior_list, 16.0)
             .font_size(self.fonts.cyri.scale(18))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-third_party_integrations",
+            ))
             .set(state.ids.third_party_integrations_title, ui);
 
             // Toggle Discord integration
@@ -310,7 +329,10 @@ impl<'a> Widget for Networking<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .graphics_for(state.ids.enable_discord_integration_button)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-enable_discord_integration",
+            ))
             .set(state.ids.enable_discord_integration_text, ui);
         }
 
@@ -0,0 +1,318 @@
+use super::label_color;
+
+use crate::{
+    hud::{img_ids::Imgs, MENU_BG, TEXT_COLOR},
+    session::settings_change::{Profile as ProfileChange, Profile::*},
+    settings::{profiles::profile_name, SettingsProfile},
+    ui::fonts::Fonts,
+    GlobalState,
+};
+use conrod_core::{
+    color,
+    position::Relative,
+    widget::{self, Button, DropDownList, Rectangle, Scrollbar, Text, TextEdit},
+    widget_ids, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use directories_next::UserDirs;
+use i18n::Localization;
+use std::path::PathBuf;
+
+widget_ids! {
+    struct Ids {
+        window,
+        window_scrollbar,
+        profiles_text,
+        profile_list,
+        load_button,
+        delete_button,
+        name_text,
+        name_bg,
+        name_input,
+        save_button,
+        file_text,
+        file_bg,
+        file_input,
+        import_button,
+        export_button,
+    }
+}
+
+const BUTTON_WIDTH: f64 = 120.0;
+const BUTTON_HEIGHT: f64 = 30.0;
+
+#[derive(WidgetCommon)]
+pub struct Profiles<'a> {
+    global_state: &'a GlobalState,
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    search_matches: &'a [String],
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+impl<'a> Profiles<'a> {
+    pub fn new(
+        global_state: &'a GlobalState,
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        search_matches: &'a [String],
+    ) -> Self {
+        Self {
+            global_state,
+            imgs,
+            fonts,
+            localized_strings,
+            search_matches,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+
+    fn button<'b>(&'b self, label: &'b str) -> Button<'b, widget::button::Image> {
+        Button::image(self.imgs.button)
+            .w_h(BUTTON_WIDTH, BUTTON_HEIGHT)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .label(label)
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_y(Relative::Scalar(2.0))
+    }
+}
+
+pub struct State {
+    ids: Ids,
+    /// Names of the saved profiles, read again after the list changed
+    profiles: Option<Vec<String>>,
+    selected: Option<String>,
+    /// Name the current settings are saved under
+    name: String,
+    /// File profiles are imported from and exported to
+    file: String,
+}
+
+impl<'a> Widget for Profiles<'a> {
+    type Event = Vec<ProfileChange>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            profiles: None,
+            selected: None,
+            name: String::new(),
+            file: UserDirs::new()
+                .map(|dirs| {
+                    dirs.home_dir()
+                        .join("veloren_settings_profile.ron")
+                        .display()
+                        .to_string()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Profiles::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        let mut events = Vec::new();
+
+        if state.profiles.is_none() {
+            let profiles = SettingsProfile::list(&self.global_state.config_dir);
+            state.update(|s| {
+                if s.selected
+                    .as_ref()
+                    .map_or(false, |selected| !profiles.contains(selected))
+                {
+                    s.selected = None;
+                }
+                s.profiles = Some(profiles);
+            });
+        }
+        let profiles = state.profiles.clone().unwrap_or_default();
+
+        Rectangle::fill_with(args.rect.dim(), color::TRANSPARENT)
+            .xy(args.rect.xy())
+            .graphics_for(args.id)
+            .scroll_kids()
+            .scroll_kids_vertically()
+            .set(state.ids.window, ui);
+        Scrollbar::y_axis(state.ids.window)
+            .thickness(5.0)
+            .rgba(0.33, 0.33, 0.33, 1.0)
+            .set(state.ids.window_scrollbar, ui);
+
+        // Saved profiles
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-saved_profiles"),
+        )
+        .top_left_with_margins_on(state.ids.window, 10.0, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-saved_profiles",
+        ))
+        .set(state.ids.profiles_text, ui);
+
+        let selected = state
+            .selected
+            .as_ref()
+            .and_then(|selected| profiles.iter().position(|name| name == selected));
+        if let Some(clicked) = DropDownList::new(&profiles, selected)
+            .w_h(250.0, BUTTON_HEIGHT)
+            .color(MENU_BG)
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .down_from(state.ids.profiles_text, 8.0)
+            .set(state.ids.profile_list, ui)
+        {
+            let name = profiles[clicked].clone();
+            state.update(|s| {
+                s.name = name.clone();
+                s.selected = Some(name);
+            });
+        }
+
+        if self
+            .button(&self.localized_strings.get_msg("hud-settings-load_profile"))
+            .right_from(state.ids.profile_list, 10.0)
+            .set(state.ids.load_button, ui)
+            .was_clicked()
+        {
+            if let Some(selected) = &state.selected {
+                events.push(LoadProfile(selected.clone()));
+            }
+        }
+
+        if self
+            .button(
+                &self
+                    .localized_strings
+                    .get_msg("hud-settings-delete_profile"),
+            )
+            .right_from(state.ids.load_button, 10.0)
+            .set(state.ids.delete_button, ui)
+            .was_clicked()
+        {
+            if let Some(selected) = &state.selected {
+                events.push(DeleteProfile(selected.clone()));
+                state.update(|s| s.profiles = None);
+            }
+        }
+
+        // Saving the current settings
+        Text::new(&self.localized_strings.get_msg("hud-settings-profile_name"))
+            .down_from(state.ids.profile_list, 20.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-profile_name",
+            ))
+            .set(state.ids.name_text, ui);
+
+        Rectangle::fill([250.0, BUTTON_HEIGHT])
+            .down_from(state.ids.name_text, 8.0)
+            .color(color::rgba(0.0, 0.0, 0.0, 0.7))
+            .set(state.ids.name_bg, ui);
+
+        if let Some(name) = TextEdit::new(&state.name)
+            .top_left_with_margins_on(state.ids.name_bg, 6.0, 6.0)
+            .w_h(238.0, 20.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.name_input, ui)
+        {
+            state.update(|s| s.name = name);
+        }
+
+        if self
+            .button(&self.localized_strings.get_msg("hud-settings-save_profile"))
+            .right_from(state.ids.name_bg, 10.0)
+            .set(state.ids.save_button, ui)
+            .was_clicked()
+        {
+            let name = profile_name(&state.name);
+            if !name.is_empty() {
+                events.push(SaveProfile(name.clone()));
+                state.update(|s| {
+                    s.selected = Some(name);
+                    s.profiles = None;
+                });
+            }
+        }
+
+        // Sharing profiles
+        Text::new(&self.localized_strings.get_msg("hud-settings-profile_file"))
+            .down_from(state.ids.name_bg, 20.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-profile_file",
+            ))
+            .set(state.ids.file_text, ui);
+
+        Rectangle::fill([520.0, BUTTON_HEIGHT])
+            .down_from(state.ids.file_text, 8.0)
+            .color(color::rgba(0.0, 0.0, 0.0, 0.7))
+            .set(state.ids.file_bg, ui);
+
+        if let Some(file) = TextEdit::new(&state.file)
+            .top_left_with_margins_on(state.ids.file_bg, 6.0, 6.0)
+            .w_h(508.0, 20.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.file_input, ui)
+        {
+            state.update(|s| s.file = file);
+        }
+
+        if self
+            .button(
+                &self
+                    .localized_strings
+                    .get_msg("hud-settings-import_profile"),
+            )
+            .down_from(state.ids.file_bg, 10.0)
+            .set(state.ids.import_button, ui)
+            .was_clicked()
+            && !state.file.trim().is_empty()
+        {
+            events.push(ImportProfile(PathBuf::from(state.file.trim())));
+            state.update(|s| s.profiles = None);
+        }
+
+        // Exports the selected profile
+        if self
+            .button(
+                &self
+                    .localized_strings
+                    .get_msg("hud-settings-export_profile"),
+            )
+            .right_from(state.ids.import_button, 10.0)
+            .set(state.ids.export_button, ui)
+            .was_clicked()
+            && !state.file.trim().is_empty()
+        {
+            if let Some(selected) = &state.selected {
+                events.push(ExportProfile(
+                    selected.clone(),
+                    PathBuf::from(state.file.trim()),
+                ));
+            }
+        }
+
+        events
+    }
+}
@@ -1,7 +1,9 @@
-use super::{RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
+use super::{label_color, RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
 
+#[cfg(feature = "voice-chat")]
+use crate::ui::ToggleButton;
 use crate::{
-    hud::{img_ids::Imgs, TEXT_COLOR},
+    hud::{img_ids::Imgs, MENU_BG, TEXT_COLOR},
     session::settings_change::{Audio as AudioChange, Audio::*},
     ui::{fonts::Fonts, ImageSlider},
     GlobalState,
@@ -9,7 +11,7 @@ use crate::{
 use conrod_core::{
     color,
     position::Relative,
-    widget::{self, Button, Rectangle, Scrollbar, Text},
+    widget::{self, Button, DropDownList, Rectangle, Scrollbar, Text},
     widget_ids, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
 };
 use i18n::Localization;
@@ -37,8 +39,13 @@ widget_ids! {
         music_spacing_text,
         music_spacing_slider,
         music_spacing_number,
-        //audio_device_list,
-        //audio_device_text,
+        voice_chat_button,
+        voice_chat_label,
+        voice_volume_text,
+        voice_volume_slider,
+        voice_volume_number,
+        audio_device_list,
+        audio_device_text,
         reset_sound_button,
     }
 }
@@ -49,6 +56,7 @@ pub struct Sound<'a> {
     imgs: &'a Imgs,
     fonts: &'a Fonts,
     localized_strings: &'a Localization,
+    search_matches: &'a [String],
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
 }
@@ -58,12 +66,14 @@ impl<'a> Sound<'a> {
         imgs: &'a Imgs,
         fonts: &'a Fonts,
         localized_strings: &'a Localization,
+        search_matches: &'a [String],
     ) -> Self {
         Self {
             global_state,
             imgs,
             fonts,
             localized_strings,
+            search_matches,
             common: widget::CommonBuilder::default(),
         }
     }
@@ -112,7 +122,10 @@ impl<'a> Widget for Sound<'a> {
             .top_left_with_margins_on(state.ids.window, 10.0, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-master_volume",
+            ))
             .set(state.ids.master_volume_text, ui);
         // Master Volume Slider
         if let Some(new_val) = ImageSlider::continuous(
@@ -151,7 +164,10 @@ impl<'a> Widget for Sound<'a> {
         .down_from(state.ids.master_volume_slider, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-inactive_master_volume_perc",
+        ))
         .set(state.ids.inactive_master_volume_text, ui);
         // Master Volume (inactive window) Slider
         if let Some(new_val) = ImageSlider::continuous(
@@ -186,7 +202,10 @@ impl<'a> Widget for Sound<'a> {
             .down_from(state.ids.inactive_master_volume_slider, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-music_volume",
+            ))
             .set(state.ids.music_volume_text, ui);
         // Music Volume Slider
         if let Some(new_val) = ImageSlider::continuous(
@@ -225,7 +244,10 @@ impl<'a> Widget for Sound<'a> {
         .down_from(state.ids.music_volume_slider, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-sound_effect_volume",
+        ))
         .set(state.ids.sfx_volume_text, ui);
         // SFX Volume Slider
         if let Some(new_val) = ImageSlider::continuous(
@@ -263,7 +285,10 @@ impl<'a> Widget for Sound<'a> {
         .down_from(state.ids.sfx_volume_slider, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-ambience_volume",
+        ))
         .set(state.ids.ambience_volume_text, ui);
         // Ambience Volume Slider
         if let Some(new_val) = ImageSlider::continuous(
@@ -298,7 +323,10 @@ impl<'a> Widget for Sound<'a> {
             .down_from(state.ids.ambience_volume_slider, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-music_spacing",
+            ))
             .set(state.ids.music_spacing_text, ui);
         // Music spacing Slider
         if let Some(new_val) = ImageSlider::continuous(
@@ -328,39 +356,150 @@ impl<'a> Widget for Sound<'a> {
         .color(TEXT_COLOR)
         .set(state.ids.music_spacing_number, ui);
 
-        // Audio Device Selector
-        // --------------------------------------------
-        // let device = &self.global_state.audio.device;
-        //let device_list = &self.global_state.audio.device_list;
-        //Text::new(self.localized_strings.get("hud.settings.audio_device"
-        // ))    .down_from(state.ids.sfx_volume_slider, 10.0)
-        //    .font_size(self.fonts.cyri.scale(14))
-        //    .font_id(self.fonts.cyri.conrod_id)
-        //    .color(TEXT_COLOR)
-        //    .set(state.ids.audio_device_text, ui);
+        #[cfg(feature = "voice-chat")]
+        {
+            // Voice chat
+            let voice_chat = ToggleButton::new(
+                self.global_state.settings.audio.voice_chat,
+                self.imgs.checkbox,
+                self.imgs.checkbox_checked,
+            )
+            .w_h(18.0, 18.0)
+            .down_from(state.ids.music_spacing_slider, 12.0)
+            .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+            .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+            .set(state.ids.voice_chat_button, ui);
+
+            if self.global_state.settings.audio.voice_chat != voice_chat {
+                events.push(ToggleVoiceChat(voice_chat));
+            }
+
+            Text::new(&self.localized_strings.get_msg("hud-settings-voice_chat"))
+                .right_from(state.ids.voice_chat_button, 10.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .graphics_for(state.ids.voice_chat_button)
+                .color(label_color(self.search_matches, "hud-settings-voice_chat"))
+                .set(state.ids.voice_chat_label, ui);
+
+            // Voice chat volume
+            Text::new(&self.localized_strings.get_msg("hud-settings-voice_volume"))
+                .down_from(state.ids.voice_chat_button, 10.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(label_color(
+                    self.search_matches,
+                    "hud-settings-voice_volume",
+                ))
+                .set(state.ids.voice_volume_text, ui);
+            if let Some(new_val) = ImageSlider::continuous(
+                self.global_state.settings.audio.voice_volume,
+                0.0,
+                1.0,
+                self.imgs.slider_indicator,
+                self.imgs.slider,
+            )
+            .w_h(104.0, 22.0)
+            .down_from(state.ids.voice_volume_text, 10.0)
+            .track_breadth(12.0)
+            .slider_length(10.0)
+            .pad_track((5.0, 5.0))
+            .set(state.ids.voice_volume_slider, ui)
+            {
+                events.push(AdjustVoiceVolume(new_val));
+            }
+            Text::new(&format!(
+                "{:2.0}%",
+                self.global_state.settings.audio.voice_volume * 100.0
+            ))
+            .right_from(state.ids.voice_volume_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.voice_volume_number, ui);
+        }
+        #[cfg(feature = "voice-chat")]
+        let last_id = state.ids.voice_volume_slider;
+        #[cfg(not(feature = "voice-chat"))]
+        let last_id = state.ids.music_spacing_slider;
+
+        // Audio Device Selector, not shown while audio is off
+        let last_id = if self.global_state.settings.audio.output.is_enabled() {
+            Text::new(&self.localized_strings.get_msg("hud-settings-audio_device"))
+                .down_from(last_id, 10.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(label_color(
+                    self.search_matches,
+                    "hud-settings-audio_device",
+                ))
+                .set(state.ids.audio_device_text, ui);
 
-        //// Get which device is currently selected
-        //let selected = device_list.iter().position(|x|
-        // x.contains(device));
+            // The default device first, then the ones available and the one chosen if it
+            // was removed
+            let device = self.global_state.audio.device();
+            let mut devices = vec![None];
+            devices.extend(
+                self.global_state
+                    .audio
+                    .device_list()
+                    .iter()
+                    .map(|name| Some(name.as_str())),
+            );
+            if device.map_or(false, |device| !devices.contains(&Some(device))) {
+                devices.push(device);
+            }
+            let device_names = devices
+                .iter()
+                .map(|name| match name {
+                    None => self
+                        .localized_strings
+                        .get_msg("hud-settings-default_audio_device")
+                        .into_owned(),
+                    Some(name)
+                        if self
+                            .global_state
+                            .audio
+                            .device_list()
+                            .iter()
+                            .any(|n| n == name) =>
+                    {
+                        (*name).to_owned()
+                    },
+                    Some(name) => self
+                        .localized_strings
+                        .get_msg_ctx(
+                            "hud-settings-unavailable_audio_device",
+                            &i18n::fluent_args! { "device" => *name },
+                        )
+                        .into_owned(),
+                })
+                .collect::<Vec<_>>();
+            let selected = devices.iter().position(|name| *name == device);
 
-        //if let Some(clicked) = DropDownList::new(&device_list, selected)
-        //    .w_h(400.0, 22.0)
-        //    .color(MENU_BG)
-        //    .label_color(TEXT_COLOR)
-        //    .label_font_id(self.fonts.opensans.conrod_id)
-        //    .down_from(state.ids.audio_device_text, 10.0)
-        //    .set(state.ids.audio_device_list, ui)
-        //{
-        //    let new_val = device_list[clicked].clone();
-        //    events.push(ChangeAudioDevice(new_val));
-        //}
+            if let Some(clicked) = DropDownList::new(&device_names, selected)
+                .w_h(400.0, 22.0)
+                .color(MENU_BG)
+                .label_color(TEXT_COLOR)
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .down_from(state.ids.audio_device_text, 10.0)
+                .set(state.ids.audio_device_list, ui)
+            {
+                if Some(clicked) != selected {
+                    events.push(ChangeAudioDevice(devices[clicked].map(str::to_owned)));
+                }
+            }
+            state.ids.audio_device_list
+        } else {
+            last_id
+        };
 
         // Reset the sound settings to the default settings
         if Button::image(self.imgs.button)
             .w_h(RESET_BUTTONS_WIDTH, RESET_BUTTONS_HEIGHT)
             .hover_image(self.imgs.button_hover)
             .press_image(self.imgs.button_press)
-            .down_from(state.ids.music_spacing_slider, 12.0)
+            .down_from(last_id, 12.0)
             .label(&self.localized_strings.get_msg("hud-settings-reset_sound"))
             .label_font_size(self.fonts.cyri.scale(14))
             .label_color(TEXT_COLOR)
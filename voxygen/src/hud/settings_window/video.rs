@@ -1,4 +1,4 @@
-use super::{RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
+use super::{label_color, RESET_BUTTONS_HEIGHT, RESET_BUTTONS_WIDTH};
 
 use crate::{
     hud::{
@@ -6,11 +6,12 @@ use crate::{
         TEXT_COLOR,
     },
     render::{
-        AaMode, BloomConfig, BloomFactor, BloomMode, CloudMode, FluidMode, LightingMode,
-        PresentMode, RenderMode, ShadowMapMode, ShadowMode, UpscaleMode,
+        AaMode, AmbientOcclusionMode, BloomConfig, BloomFactor, BloomMode, CloudMode, FluidMode,
+        LightingMode, PresentMode, RenderMode, ShadowMapMode, ShadowMode, TonemapMode, UpscaleMode,
+        VolumetricFogMode, MAX_POINT_SHADOWS,
     },
-    session::settings_change::Graphics as GraphicsChange,
-    settings::Fps,
+    session::{settings_change::Graphics as GraphicsChange, MAX_ADAPTIVE_QUALITY_LEVEL},
+    settings::{AdaptiveQualitySettings, Fps},
     ui::{fonts::Fonts, ImageSlider, ToggleButton},
     window::{FullScreenSettings, FullscreenMode},
     GlobalState,
@@ -54,6 +55,9 @@ widget_ids! {
         figure_dist_slider,
         figure_dist_text,
         figure_dist_value,
+        sprite_density_slider,
+        sprite_density_text,
+        sprite_density_value,
         max_fps_slider,
         max_fps_text,
         max_fps_value,
@@ -74,6 +78,9 @@ widget_ids! {
         ambiance_slider,
         ambiance_text,
         ambiance_value,
+        color_grading_slider,
+        color_grading_text,
+        color_grading_value,
         aa_mode_text,
         aa_mode_list,
         //
@@ -83,13 +90,23 @@ widget_ids! {
         point_glow_text,
         point_glow_slider,
         point_glow_value,
+        ambient_occlusion_text,
+        ambient_occlusion_button,
+        ambient_occlusion_slider,
+        ambient_occlusion_value,
         //
         upscale_factor_text,
         upscale_factor_list,
         cloud_mode_text,
         cloud_mode_list,
+        volumetric_fog_text,
+        volumetric_fog_button,
+        volumetric_fog_slider,
+        volumetric_fog_value,
         fluid_mode_text,
         fluid_mode_list,
+        reflections_text,
+        reflections_button,
         fullscreen_mode_text,
         fullscreen_mode_list,
         //
@@ -110,16 +127,34 @@ widget_ids! {
         flashing_lights_button,
         flashing_lights_label,
         flashing_lights_info_label,
+        adaptive_quality_button,
+        adaptive_quality_label,
+        adaptive_quality_status,
+        adaptive_min_fps_slider,
+        adaptive_min_fps_text,
+        adaptive_min_fps_value,
+        adaptive_min_vd_slider,
+        adaptive_min_vd_text,
+        adaptive_min_vd_value,
+        adaptive_particles_button,
+        adaptive_particles_label,
         //
         fullscreen_button,
         fullscreen_label,
         lighting_mode_text,
         lighting_mode_list,
+        tonemap_mode_text,
+        tonemap_mode_list,
         shadow_mode_text,
         shadow_mode_list,
         shadow_mode_map_resolution_text,
         shadow_mode_map_resolution_slider,
         shadow_mode_map_resolution_value,
+        shadow_mode_map_soft_label,
+        shadow_mode_map_soft_button,
+        shadow_mode_map_point_lights_text,
+        shadow_mode_map_point_lights_slider,
+        shadow_mode_map_point_lights_value,
         rain_map_resolution_text,
         rain_map_resolution_slider,
         rain_map_resolution_value,
@@ -134,8 +169,10 @@ pub struct Video<'a> {
     imgs: &'a Imgs,
     fonts: &'a Fonts,
     localized_strings: &'a Localization,
+    search_matches: &'a [String],
     server_view_distance_limit: Option<u32>,
     fps: f32,
+    adaptive_quality_level: u32,
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
 }
@@ -145,16 +182,20 @@ impl<'a> Video<'a> {
         imgs: &'a Imgs,
         fonts: &'a Fonts,
         localized_strings: &'a Localization,
+        search_matches: &'a [String],
         server_view_distance_limit: Option<u32>,
         fps: f32,
+        adaptive_quality_level: u32,
     ) -> Self {
         Self {
             global_state,
             imgs,
             fonts,
             localized_strings,
+            search_matches,
             server_view_distance_limit,
             fps,
+            adaptive_quality_level,
             common: widget::CommonBuilder::default(),
         }
     }
@@ -287,7 +328,10 @@ impl<'a> Widget for Video<'a> {
             .top_left_with_margins_on(state.ids.window, 10.0, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-view_distance",
+            ))
             .set(state.ids.terrain_vd_text, ui);
 
         let terrain_view_distance = self.global_state.settings.graphics.terrain_view_distance;
@@ -353,7 +397,10 @@ impl<'a> Widget for Video<'a> {
         .up_from(state.ids.entity_vd_slider, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-entity_view_distance",
+        ))
         .set(state.ids.entity_vd_text, ui);
 
         Text::new(&if entity_view_distance <= soft_entity_vd_max {
@@ -392,7 +439,10 @@ impl<'a> Widget for Video<'a> {
         .up_from(state.ids.sprite_dist_slider, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-sprites_view_distance",
+        ))
         .set(state.ids.sprite_dist_text, ui);
 
         Text::new(&format!(
@@ -410,7 +460,10 @@ impl<'a> Widget for Video<'a> {
             .down_from(state.ids.terrain_vd_slider, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-lod_distance",
+            ))
             .set(state.ids.ld_text, ui);
 
         if let Some(new_val) = ImageSlider::discrete(
@@ -468,7 +521,10 @@ impl<'a> Widget for Video<'a> {
         .up_from(state.ids.figure_dist_slider, 8.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-entities_detail_distance",
+        ))
         .set(state.ids.figure_dist_text, ui);
 
         Text::new(&format!(
@@ -484,12 +540,51 @@ impl<'a> Widget for Video<'a> {
         .color(TEXT_COLOR)
         .set(state.ids.figure_dist_value, ui);
 
+        // Sprite density
+        let sprite_density = self.global_state.settings.graphics.sprite_density;
+        if let Some(new_val) = ImageSlider::continuous(
+            sprite_density,
+            0.0,
+            1.0,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(104.0, 22.0)
+        .right_from(state.ids.figure_dist_slider, 70.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.sprite_density_slider, ui)
+        {
+            events.push(GraphicsChange::AdjustSpriteDensity(new_val));
+        }
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-sprite_density"),
+        )
+        .up_from(state.ids.sprite_density_slider, 8.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-sprite_density",
+        ))
+        .set(state.ids.sprite_density_text, ui);
+
+        Text::new(&format!("{}%", (sprite_density * 100.0).round() as i32))
+            .right_from(state.ids.sprite_density_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.sprite_density_value, ui);
+
         // Max FPS
         Text::new(&self.localized_strings.get_msg("hud-settings-maximum_fps"))
             .down_from(state.ids.ld_slider, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-maximum_fps"))
             .set(state.ids.max_fps_text, ui);
 
         if let Some(which) = ImageSlider::discrete(
@@ -529,7 +624,10 @@ impl<'a> Widget for Video<'a> {
         .right_from(state.ids.max_fps_value, 44.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-background_fps",
+        ))
         .set(state.ids.max_background_fps_text, ui);
 
         if let Some(which) = ImageSlider::discrete(
@@ -577,7 +675,10 @@ impl<'a> Widget for Video<'a> {
             .right_from(state.ids.max_background_fps_value, 40.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-present_mode",
+            ))
             .set(state.ids.present_mode_text, ui);
 
         let mode_list = [
@@ -617,7 +718,7 @@ impl<'a> Widget for Video<'a> {
             .down_from(state.ids.max_fps_slider, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-fov"))
             .set(state.ids.fov_text, ui);
 
         if let Some(new_val) = ImageSlider::discrete(
@@ -649,7 +750,7 @@ impl<'a> Widget for Video<'a> {
             .down_from(state.ids.fov_slider, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-lod_detail"))
             .set(state.ids.lod_detail_text, ui);
 
         if let Some(new_val) = ImageSlider::discrete(
@@ -687,7 +788,7 @@ impl<'a> Widget for Video<'a> {
             .down_from(state.ids.lod_detail_slider, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-gamma"))
             .set(state.ids.gamma_text, ui);
 
         if let Some(new_val) = ImageSlider::discrete(
@@ -738,7 +839,7 @@ impl<'a> Widget for Video<'a> {
             .up_from(state.ids.exposure_slider, 8.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-exposure"))
             .set(state.ids.exposure_text, ui);
 
         Text::new(&format!(
@@ -772,7 +873,7 @@ impl<'a> Widget for Video<'a> {
             .up_from(state.ids.ambiance_slider, 8.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-ambiance"))
             .set(state.ids.ambiance_text, ui);
         Text::new(&format!(
             "{:.0}%",
@@ -784,6 +885,42 @@ impl<'a> Widget for Video<'a> {
         .color(TEXT_COLOR)
         .set(state.ids.ambiance_value, ui);
 
+        // Colour grading
+        if let Some(new_val) = ImageSlider::discrete(
+            (self.global_state.settings.graphics.color_grading * 100.0).round() as i32,
+            0,
+            100,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(104.0, 22.0)
+        .right_from(state.ids.ambiance_slider, 50.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.color_grading_slider, ui)
+        {
+            events.push(GraphicsChange::ChangeColorGrading(new_val as f32 / 100.0));
+        }
+        Text::new(&self.localized_strings.get_msg("hud-settings-color_grading"))
+            .up_from(state.ids.color_grading_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-color_grading",
+            ))
+            .set(state.ids.color_grading_text, ui);
+        Text::new(&format!(
+            "{:.0}%",
+            (self.global_state.settings.graphics.color_grading * 100.0).round()
+        ))
+        .right_from(state.ids.color_grading_slider, 8.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(TEXT_COLOR)
+        .set(state.ids.color_grading_value, ui);
+
         // AaMode
         Text::new(
             &self
@@ -793,7 +930,10 @@ impl<'a> Widget for Video<'a> {
         .down_from(state.ids.gamma_slider, 8.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-antialiasing_mode",
+        ))
         .set(state.ids.aa_mode_text, ui);
 
         // NOTE: MSAA modes are currently disabled from the UI due to poor
@@ -841,7 +981,7 @@ impl<'a> Widget for Video<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .down_from(state.ids.aa_mode_list, 10.0)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-bloom"))
             .set(state.ids.bloom_intensity_text, ui);
         if let Some(new_val) = ImageSlider::continuous(
             bloom_intensity,
@@ -894,7 +1034,7 @@ impl<'a> Widget for Video<'a> {
             .font_id(self.fonts.cyri.conrod_id)
             .down_from(state.ids.aa_mode_list, 10.0)
             .right_from(state.ids.bloom_intensity_value, 10.0)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-point_glow"))
             .set(state.ids.point_glow_text, ui);
         if let Some(new_val) = ImageSlider::continuous(
             render_mode.point_glow,
@@ -927,6 +1067,77 @@ impl<'a> Widget for Video<'a> {
         .color(TEXT_COLOR)
         .set(state.ids.point_glow_value, ui);
 
+        // Ambient Occlusion
+        let ambient_occlusion = render_mode.ambient_occlusion;
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-ambient_occlusion"),
+        )
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .down_from(state.ids.aa_mode_list, 10.0)
+        .right_from(state.ids.point_glow_value, 10.0)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-ambient_occlusion",
+        ))
+        .set(state.ids.ambient_occlusion_text, ui);
+
+        let enabled = ToggleButton::new(
+            ambient_occlusion.enabled,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .right_from(state.ids.ambient_occlusion_text, 10.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.ambient_occlusion_button, ui);
+        if enabled != ambient_occlusion.enabled {
+            events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
+                ambient_occlusion: AmbientOcclusionMode {
+                    enabled,
+                    ..ambient_occlusion
+                },
+                ..render_mode.clone()
+            })));
+        }
+
+        // Quality, in steps of four samples
+        if let Some(new_val) = ImageSlider::discrete(
+            ambient_occlusion.samples() / 4,
+            1,
+            8,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(104.0, 22.0)
+        .down_from(state.ids.ambient_occlusion_text, 8.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.ambient_occlusion_slider, ui)
+        {
+            events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
+                ambient_occlusion: AmbientOcclusionMode {
+                    samples: new_val * 4,
+                    ..ambient_occlusion
+                },
+                ..render_mode.clone()
+            })));
+        }
+        Text::new(&if ambient_occlusion.enabled {
+            format!("{}", ambient_occlusion.samples())
+        } else {
+            "Off".to_string()
+        })
+        .right_from(state.ids.ambient_occlusion_slider, 8.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(TEXT_COLOR)
+        .set(state.ids.ambient_occlusion_value, ui);
+
         // Upscaling factor
         Text::new(
             &self
@@ -936,7 +1147,10 @@ impl<'a> Widget for Video<'a> {
         .down_from(state.ids.bloom_intensity_slider, 8.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-upscale_factor",
+        ))
         .set(state.ids.upscale_factor_text, ui);
 
         let upscale_factors = [
@@ -982,7 +1196,10 @@ impl<'a> Widget for Video<'a> {
         .down_from(state.ids.upscale_factor_list, 8.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-cloud_rendering_mode",
+        ))
         .set(state.ids.cloud_mode_text, ui);
 
         let mode_list = [
@@ -1024,6 +1241,76 @@ impl<'a> Widget for Video<'a> {
             })));
         }
 
+        // Volumetric Fog
+        let volumetric_fog = render_mode.volumetric_fog;
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-volumetric_fog"),
+        )
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .right_from(state.ids.cloud_mode_list, 10.0)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-volumetric_fog",
+        ))
+        .set(state.ids.volumetric_fog_text, ui);
+
+        let enabled = ToggleButton::new(
+            volumetric_fog.enabled,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .right_from(state.ids.volumetric_fog_text, 10.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.volumetric_fog_button, ui);
+        if enabled != volumetric_fog.enabled {
+            events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
+                volumetric_fog: VolumetricFogMode {
+                    enabled,
+                    ..volumetric_fog
+                },
+                ..render_mode.clone()
+            })));
+        }
+
+        // Quality, in steps of four ray-marching steps
+        if let Some(new_val) = ImageSlider::discrete(
+            volumetric_fog.steps() / 4,
+            1,
+            8,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(104.0, 22.0)
+        .right_from(state.ids.volumetric_fog_button, 10.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.volumetric_fog_slider, ui)
+        {
+            events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
+                volumetric_fog: VolumetricFogMode {
+                    steps: new_val * 4,
+                    ..volumetric_fog
+                },
+                ..render_mode.clone()
+            })));
+        }
+        Text::new(&if volumetric_fog.enabled {
+            format!("{}", volumetric_fog.steps())
+        } else {
+            "Off".to_string()
+        })
+        .right_from(state.ids.volumetric_fog_slider, 8.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(TEXT_COLOR)
+        .set(state.ids.volumetric_fog_value, ui);
+
         // FluidMode
         Text::new(
             &self
@@ -1033,7 +1320,10 @@ impl<'a> Widget for Video<'a> {
         .down_from(state.ids.cloud_mode_list, 8.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-fluid_rendering_mode",
+        ))
         .set(state.ids.fluid_mode_text, ui);
 
         let mode_list = [FluidMode::Cheap, FluidMode::Shiny];
@@ -1061,6 +1351,40 @@ impl<'a> Widget for Video<'a> {
             })));
         }
 
+        // Screen-space reflections, only used by the shiny fluids
+        if render_mode.fluid == FluidMode::Shiny {
+            Text::new(
+                &self
+                    .localized_strings
+                    .get_msg("hud-settings-screen_space_reflections"),
+            )
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .right_from(state.ids.fluid_mode_list, 10.0)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-screen_space_reflections",
+            ))
+            .set(state.ids.reflections_text, ui);
+
+            let screen_space_reflections = ToggleButton::new(
+                render_mode.screen_space_reflections,
+                self.imgs.checkbox,
+                self.imgs.checkbox_checked,
+            )
+            .w_h(18.0, 18.0)
+            .right_from(state.ids.reflections_text, 10.0)
+            .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+            .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+            .set(state.ids.reflections_button, ui);
+            if screen_space_reflections != render_mode.screen_space_reflections {
+                events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
+                    screen_space_reflections,
+                    ..render_mode.clone()
+                })));
+            }
+        }
+
         // LightingMode
         Text::new(
             &self
@@ -1070,13 +1394,17 @@ impl<'a> Widget for Video<'a> {
         .down_from(state.ids.fluid_mode_list, 8.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-lighting_rendering_mode",
+        ))
         .set(state.ids.lighting_mode_text, ui);
 
         let mode_list = [
             LightingMode::Ashikhmin,
             LightingMode::BlinnPhong,
             LightingMode::Lambertian,
+            LightingMode::Flat,
         ];
         let mode_label_list = [
             self.localized_strings
@@ -1085,6 +1413,8 @@ impl<'a> Widget for Video<'a> {
                 .get_msg("hud-settings-lighting_rendering_mode-blinnphong"),
             self.localized_strings
                 .get_msg("hud-settings-lighting_rendering_mode-lambertian"),
+            self.localized_strings
+                .get_msg("hud-settings-lighting_rendering_mode-flat"),
         ];
 
         // Get which lighting rendering mode is currently active
@@ -1104,16 +1434,58 @@ impl<'a> Widget for Video<'a> {
             })));
         }
 
+        // TonemapMode
+        Text::new(&self.localized_strings.get_msg("hud-settings-tonemapping"))
+            .down_from(state.ids.lighting_mode_list, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(self.search_matches, "hud-settings-tonemapping"))
+            .set(state.ids.tonemap_mode_text, ui);
+
+        let mode_list = [
+            TonemapMode::Exponential,
+            TonemapMode::Reinhard,
+            TonemapMode::Aces,
+        ];
+        let mode_label_list = [
+            self.localized_strings
+                .get_msg("hud-settings-tonemapping-exponential"),
+            self.localized_strings
+                .get_msg("hud-settings-tonemapping-reinhard"),
+            self.localized_strings
+                .get_msg("hud-settings-tonemapping-aces"),
+        ];
+
+        // Get which tonemapping operator is currently active
+        let selected = mode_list.iter().position(|x| *x == render_mode.tonemap);
+
+        if let Some(clicked) = DropDownList::new(&mode_label_list, selected)
+            .w_h(400.0, 22.0)
+            .color(MENU_BG)
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .down_from(state.ids.tonemap_mode_text, 8.0)
+            .set(state.ids.tonemap_mode_list, ui)
+        {
+            events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
+                tonemap: mode_list[clicked],
+                ..render_mode.clone()
+            })));
+        }
+
         // ShadowMode
         Text::new(
             &self
                 .localized_strings
                 .get_msg("hud-settings-shadow_rendering_mode"),
         )
-        .down_from(state.ids.lighting_mode_list, 8.0)
+        .down_from(state.ids.tonemap_mode_list, 8.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-shadow_rendering_mode",
+        ))
         .set(state.ids.shadow_mode_text, ui);
 
         let shadow_map_mode = ShadowMapMode::try_from(render_mode.shadow).ok();
@@ -1158,7 +1530,10 @@ impl<'a> Widget for Video<'a> {
             .right_from(state.ids.shadow_mode_list, 10.0)
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-shadow_rendering_mode-map-resolution",
+            ))
             .set(state.ids.shadow_mode_map_resolution_text, ui);
 
             if let Some(new_val) = ImageSlider::discrete(
@@ -1178,6 +1553,7 @@ impl<'a> Widget for Video<'a> {
                 events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
                     shadow: ShadowMode::Map(ShadowMapMode {
                         resolution: 2.0f32.powf(f32::from(new_val) / 4.0),
+                        ..shadow_map_mode
                     }),
                     ..render_mode.clone()
                 })));
@@ -1191,6 +1567,41 @@ impl<'a> Widget for Video<'a> {
                 .font_id(self.fonts.cyri.conrod_id)
                 .color(TEXT_COLOR)
                 .set(state.ids.shadow_mode_map_resolution_value, ui);
+
+            Text::new(
+                &self
+                    .localized_strings
+                    .get_msg("hud-settings-shadow_rendering_mode-map-soft"),
+            )
+            .right_from(state.ids.shadow_mode_map_resolution_value, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-shadow_rendering_mode-map-soft",
+            ))
+            .set(state.ids.shadow_mode_map_soft_label, ui);
+
+            let soft = ToggleButton::new(
+                shadow_map_mode.soft,
+                self.imgs.checkbox,
+                self.imgs.checkbox_checked,
+            )
+            .w_h(18.0, 18.0)
+            .right_from(state.ids.shadow_mode_map_soft_label, 10.0)
+            .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+            .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+            .set(state.ids.shadow_mode_map_soft_button, ui);
+
+            if soft != shadow_map_mode.soft {
+                events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
+                    shadow: ShadowMode::Map(ShadowMapMode {
+                        soft,
+                        ..shadow_map_mode
+                    }),
+                    ..render_mode.clone()
+                })));
+            }
         }
 
         // Rain occlusion texture size
@@ -1203,7 +1614,10 @@ impl<'a> Widget for Video<'a> {
         .down_from(state.ids.shadow_mode_list, 10.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-rain_occlusion-resolution",
+        ))
         .set(state.ids.rain_map_resolution_text, ui);
 
         if let Some(new_val) = ImageSlider::discrete(
@@ -1223,6 +1637,7 @@ impl<'a> Widget for Video<'a> {
             events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
                 rain_occlusion: ShadowMapMode {
                     resolution: 2.0f32.powf(f32::from(new_val) / 4.0),
+                    ..render_mode.rain_occlusion
                 },
                 ..render_mode.clone()
             })));
@@ -1234,12 +1649,62 @@ impl<'a> Widget for Video<'a> {
             .color(TEXT_COLOR)
             .set(state.ids.rain_map_resolution_value, ui);
 
+        if let Some(shadow_map_mode) = shadow_map_mode {
+            // Number of point lights casting shadows
+            Text::new(
+                &self
+                    .localized_strings
+                    .get_msg("hud-settings-shadow_rendering_mode-map-point_lights"),
+            )
+            .right_from(state.ids.rain_map_resolution_value, 10.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-shadow_rendering_mode-map-point_lights",
+            ))
+            .set(state.ids.shadow_mode_map_point_lights_text, ui);
+
+            if let Some(new_val) = ImageSlider::discrete(
+                shadow_map_mode.point_lights(),
+                0,
+                MAX_POINT_SHADOWS,
+                self.imgs.slider_indicator,
+                self.imgs.slider,
+            )
+            .w_h(52.0, 22.0)
+            .right_from(state.ids.shadow_mode_map_point_lights_text, 8.0)
+            .track_breadth(12.0)
+            .slider_length(10.0)
+            .pad_track((5.0, 5.0))
+            .set(state.ids.shadow_mode_map_point_lights_slider, ui)
+            {
+                events.push(GraphicsChange::ChangeRenderMode(Box::new(RenderMode {
+                    shadow: ShadowMode::Map(ShadowMapMode {
+                        point_lights: new_val,
+                        ..shadow_map_mode
+                    }),
+                    ..render_mode.clone()
+                })));
+            }
+
+            Text::new(&format!("{}", shadow_map_mode.point_lights()))
+                .right_from(state.ids.shadow_mode_map_point_lights_slider, 8.0)
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR)
+                .set(state.ids.shadow_mode_map_point_lights_value, ui);
+        }
+
         // GPU Profiler
         Text::new(&self.localized_strings.get_msg("hud-settings-gpu_profiler"))
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .down_from(state.ids.rain_map_resolution_text, 8.0)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-gpu_profiler",
+            ))
             .set(state.ids.gpu_profiler_label, ui);
 
         let gpu_profiler_enabled = ToggleButton::new(
@@ -1265,7 +1730,7 @@ impl<'a> Widget for Video<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .down_from(state.ids.gpu_profiler_label, 8.0)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-particles"))
             .set(state.ids.particles_label, ui);
 
         let particles_enabled = ToggleButton::new(
@@ -1288,7 +1753,10 @@ impl<'a> Widget for Video<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .right_from(state.ids.particles_label, 64.0)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-weapon_trails",
+            ))
             .set(state.ids.weapon_trails_label, ui);
 
         let weapon_trails_enabled = ToggleButton::new(
@@ -1317,7 +1785,10 @@ impl<'a> Widget for Video<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .down_from(state.ids.particles_label, 25.0)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-flashing_lights",
+        ))
         .set(state.ids.flashing_lights_label, ui);
 
         let flashing_lights_enabled = ToggleButton::new(
@@ -1343,7 +1814,10 @@ impl<'a> Widget for Video<'a> {
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
         .right_from(state.ids.flashing_lights_label, 32.0)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-flashing_lights_info",
+        ))
         .set(state.ids.flashing_lights_info_label, ui);
 
         if self
@@ -1360,6 +1834,191 @@ impl<'a> Widget for Video<'a> {
             })));
         }
 
+        // Adaptive quality
+        let adaptive_quality = self.global_state.settings.graphics.adaptive_quality;
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-adaptive_quality"),
+        )
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .down_from(state.ids.flashing_lights_label, 25.0)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-adaptive_quality",
+        ))
+        .set(state.ids.adaptive_quality_label, ui);
+
+        let adaptive_quality_enabled = ToggleButton::new(
+            adaptive_quality.enabled,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .right_from(state.ids.adaptive_quality_label, 10.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.adaptive_quality_button, ui);
+
+        if adaptive_quality.enabled != adaptive_quality_enabled {
+            events.push(GraphicsChange::ChangeAdaptiveQuality(
+                AdaptiveQualitySettings {
+                    enabled: adaptive_quality_enabled,
+                    ..adaptive_quality
+                },
+            ));
+        }
+
+        let adaptive_quality_status = if !adaptive_quality.enabled {
+            self.localized_strings
+                .get_msg("hud-settings-adaptive_quality_off")
+        } else if self.adaptive_quality_level == 0 {
+            self.localized_strings
+                .get_msg("hud-settings-adaptive_quality_full")
+        } else {
+            self.localized_strings.get_msg_ctx(
+                "hud-settings-adaptive_quality_lowered",
+                &i18n::fluent_args! {
+                    "level" => self.adaptive_quality_level,
+                    "max" => MAX_ADAPTIVE_QUALITY_LEVEL,
+                },
+            )
+        };
+        Text::new(&adaptive_quality_status)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .right_from(state.ids.adaptive_quality_label, 32.0)
+            .color(if self.adaptive_quality_level == 0 {
+                TEXT_COLOR
+            } else {
+                LOW_HP_COLOR
+            })
+            .set(state.ids.adaptive_quality_status, ui);
+
+        // Adaptive quality minimum FPS
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-adaptive_min_fps"),
+        )
+        .down_from(state.ids.adaptive_quality_label, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-adaptive_min_fps",
+        ))
+        .set(state.ids.adaptive_min_fps_text, ui);
+
+        if let Some(min_fps) = ImageSlider::discrete(
+            adaptive_quality.min_fps,
+            15,
+            120,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(104.0, 22.0)
+        .down_from(state.ids.adaptive_min_fps_text, 8.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.adaptive_min_fps_slider, ui)
+        {
+            events.push(GraphicsChange::ChangeAdaptiveQuality(
+                AdaptiveQualitySettings {
+                    min_fps,
+                    ..adaptive_quality
+                },
+            ));
+        }
+
+        Text::new(&format!("{}", adaptive_quality.min_fps))
+            .right_from(state.ids.adaptive_min_fps_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.adaptive_min_fps_value, ui);
+
+        // Adaptive quality minimum view distance
+        if let Some(min_terrain_view_distance) = ImageSlider::discrete(
+            adaptive_quality.min_terrain_view_distance,
+            1,
+            client::MAX_SELECTABLE_VIEW_DISTANCE,
+            self.imgs.slider_indicator,
+            self.imgs.slider,
+        )
+        .w_h(104.0, 22.0)
+        .right_from(state.ids.adaptive_min_fps_slider, 70.0)
+        .track_breadth(12.0)
+        .slider_length(10.0)
+        .pad_track((5.0, 5.0))
+        .set(state.ids.adaptive_min_vd_slider, ui)
+        {
+            events.push(GraphicsChange::ChangeAdaptiveQuality(
+                AdaptiveQualitySettings {
+                    min_terrain_view_distance,
+                    ..adaptive_quality
+                },
+            ));
+        }
+
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-adaptive_min_view_distance"),
+        )
+        .up_from(state.ids.adaptive_min_vd_slider, 10.0)
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-adaptive_min_view_distance",
+        ))
+        .set(state.ids.adaptive_min_vd_text, ui);
+
+        Text::new(&format!("{}", adaptive_quality.min_terrain_view_distance))
+            .right_from(state.ids.adaptive_min_vd_slider, 8.0)
+            .font_size(self.fonts.cyri.scale(14))
+            .font_id(self.fonts.cyri.conrod_id)
+            .color(TEXT_COLOR)
+            .set(state.ids.adaptive_min_vd_value, ui);
+
+        // Let adaptive quality disable particles
+        Text::new(
+            &self
+                .localized_strings
+                .get_msg("hud-settings-adaptive_particles"),
+        )
+        .font_size(self.fonts.cyri.scale(14))
+        .font_id(self.fonts.cyri.conrod_id)
+        .down_from(state.ids.adaptive_min_fps_slider, 8.0)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-adaptive_particles",
+        ))
+        .set(state.ids.adaptive_particles_label, ui);
+
+        let allow_disabling_particles = ToggleButton::new(
+            adaptive_quality.allow_disabling_particles,
+            self.imgs.checkbox,
+            self.imgs.checkbox_checked,
+        )
+        .w_h(18.0, 18.0)
+        .right_from(state.ids.adaptive_particles_label, 10.0)
+        .hover_images(self.imgs.checkbox_mo, self.imgs.checkbox_checked_mo)
+        .press_images(self.imgs.checkbox_press, self.imgs.checkbox_checked)
+        .set(state.ids.adaptive_particles_button, ui);
+
+        if adaptive_quality.allow_disabling_particles != allow_disabling_particles {
+            events.push(GraphicsChange::ChangeAdaptiveQuality(
+                AdaptiveQualitySettings {
+                    allow_disabling_particles,
+                    ..adaptive_quality
+                },
+            ));
+        }
+
         // Resolution
         let resolutions: Vec<[u16; 2]> = state
             .video_modes
@@ -1373,8 +2032,8 @@ impl<'a> Widget for Video<'a> {
         Text::new(&self.localized_strings.get_msg("hud-settings-resolution"))
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .down_from(state.ids.flashing_lights_label, 25.0)
-            .color(TEXT_COLOR)
+            .down_from(state.ids.adaptive_particles_label, 25.0)
+            .color(label_color(self.search_matches, "hud-settings-resolution"))
             .set(state.ids.resolution_label, ui);
 
         if let Some(clicked) = DropDownList::new(
@@ -1432,9 +2091,9 @@ impl<'a> Widget for Video<'a> {
         Text::new(&self.localized_strings.get_msg("hud-settings-bit_depth"))
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .down_from(state.ids.flashing_lights_label, 25.0)
+            .down_from(state.ids.adaptive_particles_label, 25.0)
             .right_from(state.ids.resolution, 8.0)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-bit_depth"))
             .set(state.ids.bit_depth_label, ui);
 
         if let Some(clicked) = DropDownList::new(
@@ -1488,9 +2147,12 @@ impl<'a> Widget for Video<'a> {
         Text::new(&self.localized_strings.get_msg("hud-settings-refresh_rate"))
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
-            .down_from(state.ids.flashing_lights_label, 25.0)
+            .down_from(state.ids.adaptive_particles_label, 25.0)
             .right_from(state.ids.bit_depth, 8.0)
-            .color(TEXT_COLOR)
+            .color(label_color(
+                self.search_matches,
+                "hud-settings-refresh_rate",
+            ))
             .set(state.ids.refresh_rate_label, ui);
 
         if let Some(clicked) = DropDownList::new(
@@ -1531,7 +2193,7 @@ impl<'a> Widget for Video<'a> {
             .font_size(self.fonts.cyri.scale(14))
             .font_id(self.fonts.cyri.conrod_id)
             .down_from(state.ids.resolution, 8.0)
-            .color(TEXT_COLOR)
+            .color(label_color(self.search_matches, "hud-settings-fullscreen"))
             .set(state.ids.fullscreen_label, ui);
 
         let enabled = ToggleButton::new(
@@ -1561,7 +2223,10 @@ impl<'a> Widget for Video<'a> {
         .down_from(state.ids.fullscreen_label, 8.0)
         .font_size(self.fonts.cyri.scale(14))
         .font_id(self.fonts.cyri.conrod_id)
-        .color(TEXT_COLOR)
+        .color(label_color(
+            self.search_matches,
+            "hud-settings-fullscreen_mode",
+        ))
         .set(state.ids.fullscreen_mode_text, ui);
 
         let mode_list = [FullscreenMode::Exclusive, FullscreenMode::Borderless];
@@ -2,8 +2,8 @@ use super::{
     hotbar,
     img_ids::{Imgs, ImgsRot},
     item_imgs::ItemImgs,
-    slots, util, BarNumbers, HudInfo, ShortcutNumbers, BLACK, CRITICAL_HP_COLOR, HP_COLOR,
-    LOW_HP_COLOR, QUALITY_EPIC, STAMINA_COLOR, TEXT_COLOR, UI_HIGHLIGHT_0,
+    slots, util, BarNumbers, HealthColors, HudInfo, ShortcutNumbers, BLACK, CRITICAL_HP_COLOR,
+    QUALITY_EPIC, STAMINA_COLOR, TEXT_COLOR, UI_HIGHLIGHT_0,
 };
 use crate::{
     game_input::GameInput,
@@ -17,14 +17,17 @@ use crate::{
     GlobalState,
 };
 use i18n::Localization;
-use std::borrow::Cow;
+use std::{borrow::Cow, f64::consts::TAU};
 
 use client::{self, Client};
-use common::comp::{
-    self,
-    ability::AbilityInput,
-    item::{ItemDesc, MaterialStatManifest},
-    Ability, ActiveAbilities, Body, Energy, Health, Inventory, SkillSet,
+use common::{
+    comp::{
+        self,
+        ability::AbilityInput,
+        item::{ItemDesc, MaterialStatManifest},
+        Ability, AbilityCooldowns, ActiveAbilities, Body, Energy, Health, Inventory, SkillSet,
+    },
+    resources::Time,
 };
 use conrod_core::{
     color,
@@ -96,6 +99,8 @@ widget_ids! {
         m2_text_bg,
         m2_slot_act,
         m2_content,
+        m2_recharge,
+        slot_recharges[],
         slot1,
         slot1_text,
         slot1_text_bg,
@@ -126,9 +131,19 @@ widget_ids! {
         slot10,
         slot10_text,
         slot10_text_bg,
+        // Radial menu
+        radial_align,
+        radial_slots[],
+        radial_selected,
     }
 }
 
+/// Overlay on abilities that are still cooling down or waiting for enough
+/// energy
+const RECHARGE_COLOR: Color = Color::Rgba(0.0, 0.0, 0.0, 0.6);
+/// Distance of the slots of the radial menu from the middle of the screen
+const RADIAL_MENU_RADIUS: f64 = 120.0;
+
 #[derive(Clone, Copy)]
 struct SlotEntry {
     slot: hotbar::Slot,
@@ -253,6 +268,7 @@ pub struct Skillbar<'a> {
     energy: &'a Energy,
     skillset: &'a SkillSet,
     active_abilities: Option<&'a ActiveAbilities>,
+    ability_cooldowns: Option<&'a AbilityCooldowns>,
     body: &'a Body,
     // character_state: &'a CharacterState,
     // controller: &'a ControllerInputs,
@@ -283,6 +299,7 @@ impl<'a> Skillbar<'a> {
         energy: &'a Energy,
         skillset: &'a SkillSet,
         active_abilities: Option<&'a ActiveAbilities>,
+        ability_cooldowns: Option<&'a AbilityCooldowns>,
         body: &'a Body,
         // character_state: &'a CharacterState,
         pulse: f32,
@@ -308,6 +325,7 @@ impl<'a> Skillbar<'a> {
             energy,
             skillset,
             active_abilities,
+            ability_cooldowns,
             body,
             common: widget::CommonBuilder::default(),
             // character_state,
@@ -378,7 +396,9 @@ impl<'a> Skillbar<'a> {
 
         // Animation timer
         let hp_ani = (self.pulse * 4.0/* speed factor */).cos() * 0.5 + 0.8;
-        let crit_hp_color: Color = Color::Rgba(0.79, 0.19, 0.17, hp_ani);
+        let health_colors =
+            HealthColors::of(self.global_state.settings.accessibility.color_palette);
+        let crit_hp_color: Color = health_colors.critical_hp.alpha(hp_ani);
         let bar_values = self.global_state.settings.interface.bar_numbers;
         let show_health = self.global_state.settings.interface.always_show_bars
             || (self.health.current() - self.health.maximum()).abs() > Health::HEALTH_EPSILON;
@@ -397,8 +417,8 @@ impl<'a> Skillbar<'a> {
                 .set(state.ids.hp_alignment, ui);
             let health_col = match hp_percentage as u8 {
                 0..=20 => crit_hp_color,
-                21..=40 => LOW_HP_COLOR,
-                _ => HP_COLOR,
+                21..=40 => health_colors.low_hp,
+                _ => health_colors.hp,
             };
             Image::new(self.imgs.bar_content)
                 .w_h(480.0 * hp_percentage / 100.0, 18.0)
@@ -625,7 +645,7 @@ impl<'a> Skillbar<'a> {
         slot_maker.selected_slot = self.imgs.skillbar_slot;
 
         let slots = slot_entries(state, slot_offset);
-        for entry in slots {
+        for (entry, recharge_id) in slots.iter().zip(state.ids.slot_recharges.iter()) {
             let slot = slot_maker
                 .fabricate(entry.slot, [40.0; 2])
                 .filled_slot(self.imgs.skillbar_slot)
@@ -648,6 +668,21 @@ impl<'a> Skillbar<'a> {
                 slot.set(entry.widget_id, ui);
             }
 
+            // Sweep down as the cooldown of the ability runs out
+            if let Some(remaining) = self
+                .hotbar
+                .get(entry.slot)
+                .and_then(|content| match content {
+                    hotbar::SlotContents::Ability(i) => self.remaining_cooldown(i),
+                    hotbar::SlotContents::Inventory(..) => None,
+                })
+            {
+                Rectangle::fill_with([40.0, 40.0 * f64::from(remaining)], RECHARGE_COLOR)
+                    .mid_top_of(entry.widget_id)
+                    .graphics_for(entry.widget_id)
+                    .set(*recharge_id, ui);
+            }
+
             // shortcuts
             if let ShortcutNumbers::On = shortcuts {
                 if let Some(key) = &self
@@ -678,6 +713,34 @@ impl<'a> Skillbar<'a> {
                 }
             }
         }
+        // Radial menu of the gamepads, the slots going clockwise from the top
+        if let Some(radial_menu) = self.global_state.window.radial_menu() {
+            let selected = radial_menu.selected();
+            slot_maker.slot_manager = None;
+            Rectangle::fill_with([10.0, 10.0], color::TRANSPARENT)
+                .middle_of(ui.window)
+                .set(state.ids.radial_align, ui);
+            for (i, (entry, &id)) in slots.iter().zip(state.ids.radial_slots.iter()).enumerate() {
+                let angle = i as f64 * TAU / slots.len() as f64;
+                slot_maker
+                    .fabricate(entry.slot, [48.0; 2])
+                    .filled_slot(self.imgs.skillbar_slot)
+                    .x_y_relative_to(
+                        state.ids.radial_align,
+                        angle.sin() * RADIAL_MENU_RADIUS,
+                        angle.cos() * RADIAL_MENU_RADIUS,
+                    )
+                    .set(id, ui);
+                if selected == Some(i) {
+                    Image::new(self.imgs.inv_slot_sel)
+                        .w_h(52.0, 52.0)
+                        .middle_of(id)
+                        .graphics_for(id)
+                        .set(state.ids.radial_selected, ui);
+                }
+            }
+        }
+
         // Slot M1
         Image::new(self.imgs.skillbar_slot)
             .w_h(40.0, 40.0)
@@ -709,26 +772,18 @@ impl<'a> Skillbar<'a> {
         )
         .w_h(36.0, 36.0)
         .middle_of(state.ids.m2_slot_bg)
-        .image_color(
-            if self.energy.current()
-                >= self
-                    .active_abilities
-                    .and_then(|a| {
-                        a.activate_ability(
-                            AbilityInput::Secondary,
-                            Some(self.inventory),
-                            self.skillset,
-                            Some(self.body),
-                        )
-                    })
-                    .map_or(0.0, |(a, _)| a.get_energy_cost())
-            {
-                Color::Rgba(1.0, 1.0, 1.0, 1.0)
-            } else {
-                Color::Rgba(0.3, 0.3, 0.3, 0.8)
-            },
-        )
+        .image_color(if self.missing_energy(AbilityInput::Secondary).is_none() {
+            Color::Rgba(1.0, 1.0, 1.0, 1.0)
+        } else {
+            Color::Rgba(0.3, 0.3, 0.3, 0.8)
+        })
         .set(state.ids.m2_content, ui);
+        if let Some(missing) = self.missing_energy(AbilityInput::Secondary) {
+            Rectangle::fill_with([36.0, 36.0 * f64::from(missing)], RECHARGE_COLOR)
+                .mid_top_of(state.ids.m2_content)
+                .graphics_for(state.ids.m2_content)
+                .set(state.ids.m2_recharge, ui);
+        }
 
         // M1 and M2 icons
         Image::new(self.imgs.m1_ico)
@@ -741,6 +796,25 @@ impl<'a> Skillbar<'a> {
             .set(state.ids.m2_ico, ui);
     }
 
+    /// Share of the cooldown of an auxiliary ability still to wait for, `None`
+    /// if it can be used again
+    fn remaining_cooldown(&self, index: usize) -> Option<f32> {
+        let time = Time(self.client.state().get_time());
+        self.ability_cooldowns?.remaining(index, time)
+    }
+
+    /// Fraction of the energy cost of an ability that still has to regenerate
+    /// before it can be used, `None` if it can be used right away
+    fn missing_energy(&self, input: AbilityInput) -> Option<f32> {
+        let cost = self
+            .active_abilities
+            .and_then(|a| {
+                a.activate_ability(input, Some(self.inventory), self.skillset, Some(self.body))
+            })
+            .map(|(a, _)| a.get_energy_cost())?;
+        (self.energy.current() < cost).then(|| 1.0 - self.energy.current() / cost)
+    }
+
     fn show_combo_counter(&self, combo: ComboFloater, state: &State, ui: &mut UiCell) {
         if combo.combo > 0 {
             let combo_txt = format!("{} Combo", combo.combo);
@@ -821,6 +895,14 @@ impl<'a> Widget for Skillbar<'a> {
         self.show_stat_bars(state, ui);
 
         // Slots
+        if state.ids.slot_recharges.len() < 10 {
+            let gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.slot_recharges.resize(10, gen));
+        }
+        if state.ids.radial_slots.len() < 10 {
+            let gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.radial_slots.resize(10, gen));
+        }
         self.show_slotbar(state, ui, slot_offset);
 
         // Combo Counter
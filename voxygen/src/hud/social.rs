@@ -26,8 +26,8 @@ widget_ids! {
         online_align,
         player_names[],
         online_txt,
-        online_no,
         invite_button,
+        voice_mute_button,
         player_search_icon,
         player_search_input,
         player_search_input_bg,
@@ -52,6 +52,7 @@ pub struct Social<'a> {
     selected_entity: Option<(specs::Entity, Instant)>,
     rot_imgs: &'a ImgsRot,
     tooltip_manager: &'a mut TooltipManager,
+    streamer_mode: bool,
 
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
@@ -67,6 +68,7 @@ impl<'a> Social<'a> {
         selected_entity: Option<(specs::Entity, Instant)>,
         rot_imgs: &'a ImgsRot,
         tooltip_manager: &'a mut TooltipManager,
+        streamer_mode: bool,
     ) -> Self {
         Self {
             show,
@@ -77,6 +79,7 @@ impl<'a> Social<'a> {
             localized_strings,
             tooltip_manager,
             selected_entity,
+            streamer_mode,
             common: widget::CommonBuilder::default(),
         }
     }
@@ -87,6 +90,8 @@ pub enum Event {
     Invite(Uid),
     Focus(widget::Id),
     SearchPlayers(Option<String>),
+    #[cfg(feature = "voice-chat")]
+    ToggleVoiceMute(Uid),
 }
 
 impl<'a> Widget for Social<'a> {
@@ -184,18 +189,17 @@ impl<'a> Widget for Social<'a> {
             .set(state.ids.scrollbar, ui);
 
         // Online Text
-        Text::new(&self.localized_strings.get_msg("hud-social-online"))
-            .bottom_left_with_margins_on(state.ids.frame, 18.0, 10.0)
-            .font_id(self.fonts.cyri.conrod_id)
-            .font_size(self.fonts.cyri.scale(14))
-            .color(TEXT_COLOR)
-            .set(state.ids.online_txt, ui);
-        Text::new(&player_count.to_string())
-            .right_from(state.ids.online_txt, 5.0)
-            .font_id(self.fonts.cyri.conrod_id)
-            .font_size(self.fonts.cyri.scale(14))
-            .color(TEXT_COLOR)
-            .set(state.ids.online_no, ui);
+        Text::new(&self.localized_strings.get_msg_ctx(
+            "hud-social-play_online_fmt",
+            &i18n::fluent_args! {
+                "nb_player" => player_count,
+            },
+        ))
+        .bottom_left_with_margins_on(state.ids.frame, 18.0, 10.0)
+        .font_id(self.fonts.cyri.conrod_id)
+        .font_size(self.fonts.cyri.scale(14))
+        .color(TEXT_COLOR)
+        .set(state.ids.online_txt, ui);
         // Adjust widget_id struct vec length to player count
         if state.ids.player_names.len() < player_count {
             state.update(|s| {
@@ -243,7 +247,15 @@ impl<'a> Widget for Social<'a> {
         for (i, (&uid, player_info)) in player_list.into_iter().enumerate() {
             let hide_username = true;
             let selected = state.selected_uid.map_or(false, |u| u.0 == uid);
-            let alias = &player_info.player_alias;
+            // Streamers show the name of their character in place of their account name
+            let alias = if self.streamer_mode && self.client.uid() == Some(uid) {
+                player_info
+                    .character
+                    .as_ref()
+                    .map_or(client::HIDDEN_NAME, |character| character.name.as_str())
+            } else {
+                player_info.player_alias.as_str()
+            };
             let name_text = match &player_info.character {
                 Some(character) => {
                     if hide_username {
@@ -254,7 +266,7 @@ impl<'a> Widget for Social<'a> {
                 },
                 None => format!(
                     "{} [{}]",
-                    alias.clone(),
+                    alias,
                     self.localized_strings.get_msg("hud-group-in_menu")
                 ), // character select or spectating
             };
@@ -405,6 +417,64 @@ impl<'a> Widget for Social<'a> {
             }
         }
 
+        #[cfg(feature = "voice-chat")]
+        {
+            // Muting the voice of the selected player
+            let selected_to_mute = state
+                .selected_uid
+                .as_ref()
+                .map(|(s, _)| *s)
+                .or_else(|| {
+                    self.selected_entity
+                        .and_then(|s| self.client.state().read_component_copied(s.0))
+                })
+                .filter(|selected| {
+                    Some(*selected) != self.client.uid()
+                        && self.client.player_list().contains_key(selected)
+                });
+            let mute_text = self.localized_strings.get_msg(
+                if selected_to_mute.map_or(false, |uid| self.show.muted_voices.contains(&uid)) {
+                    "hud-social-unmute"
+                } else {
+                    "hud-social-mute"
+                },
+            );
+            if Button::image(self.imgs.button)
+                .w_h(106.0, 26.0)
+                .left_from(state.ids.invite_button, 5.0)
+                .hover_image(if selected_to_mute.is_some() {
+                    self.imgs.button_hover
+                } else {
+                    self.imgs.button
+                })
+                .press_image(if selected_to_mute.is_some() {
+                    self.imgs.button_press
+                } else {
+                    self.imgs.button
+                })
+                .label(&mute_text)
+                .label_y(conrod_core::position::Relative::Scalar(3.0))
+                .label_color(if selected_to_mute.is_some() {
+                    TEXT_COLOR
+                } else {
+                    TEXT_COLOR_3
+                })
+                .image_color(if selected_to_mute.is_some() {
+                    TEXT_COLOR
+                } else {
+                    TEXT_COLOR_3
+                })
+                .label_font_size(self.fonts.cyri.scale(15))
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .set(state.ids.voice_mute_button, ui)
+                .was_clicked()
+            {
+                if let Some(uid) = selected_to_mute {
+                    events.push(Event::ToggleVoiceMute(uid));
+                }
+            }
+        }
+
         // Player Search
         if Button::image(self.imgs.search_btn)
             .top_left_with_margins_on(state.ids.frame, 54.0, 9.0)
@@ -0,0 +1,211 @@
+//! Captions of the sound cues heard around the character, for players who
+//! can't hear them, with the side they came from
+
+use super::TEXT_COLOR;
+use crate::ui::fonts::Fonts;
+use common::outcome::Outcome;
+use conrod_core::{
+    color,
+    widget::{self, Rectangle, Text},
+    widget_ids, Colorable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use vek::*;
+
+/// Seconds a caption stays on screen after the sound was last heard
+const LIFETIME: f32 = 3.0;
+/// Most captions shown at once, the oldest ones make way for new sounds
+const MAX_SUBTITLES: usize = 6;
+/// Distance within which sounds are captioned, thunder being heard from
+/// anywhere
+const RANGE: f32 = 40.0;
+const LINE_HEIGHT: f64 = 22.0;
+
+widget_ids! {
+    struct Ids {
+        bgs[],
+        texts[],
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum SoundCue {
+    Explosion,
+    Thunder,
+    Projectile,
+    Fire,
+    Block,
+    GroundSlam,
+    Creature,
+    Glider,
+}
+
+impl SoundCue {
+    fn of(outcome: &Outcome) -> Option<(Self, Option<Vec3<f32>>)> {
+        Some(match outcome {
+            Outcome::Explosion { pos, .. } => (Self::Explosion, Some(*pos)),
+            Outcome::Lightning { .. } => (Self::Thunder, None),
+            Outcome::ProjectileShot { pos, .. } => (Self::Projectile, Some(*pos)),
+            Outcome::Ignite { pos } => (Self::Fire, Some(pos.map(|e| e as f32 + 0.5))),
+            Outcome::Block { pos, .. } => (Self::Block, Some(*pos)),
+            Outcome::GroundSlam { pos } => (Self::GroundSlam, Some(*pos)),
+            Outcome::Utterance { pos, .. } => (Self::Creature, Some(*pos)),
+            Outcome::Glider { pos, .. } => (Self::Glider, Some(*pos)),
+            _ => return None,
+        })
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Self::Explosion => "hud-subtitle-explosion",
+            Self::Thunder => "hud-subtitle-thunder",
+            Self::Projectile => "hud-subtitle-projectile",
+            Self::Fire => "hud-subtitle-fire",
+            Self::Block => "hud-subtitle-block",
+            Self::GroundSlam => "hud-subtitle-ground_slam",
+            Self::Creature => "hud-subtitle-creature",
+            Self::Glider => "hud-subtitle-glider",
+        }
+    }
+}
+
+struct Subtitle {
+    cue: SoundCue,
+    /// Where the sound was last heard, none for the ones coming from all
+    /// around
+    pos: Option<Vec3<f32>>,
+    timer: f32,
+}
+
+/// Sounds heard recently, each cue being listed once
+#[derive(Default)]
+pub struct SubtitleList {
+    subtitles: Vec<Subtitle>,
+}
+
+impl SubtitleList {
+    pub fn handle_outcome(&mut self, outcome: &Outcome, player_pos: Vec3<f32>) {
+        let (cue, pos) = match SoundCue::of(outcome) {
+            Some(cue) => cue,
+            None => return,
+        };
+        if pos.map_or(false, |pos| {
+            pos.distance_squared(player_pos) > RANGE.powi(2)
+        }) {
+            return;
+        }
+        // Sounds heard again move to the bottom of the list
+        self.subtitles.retain(|subtitle| subtitle.cue != cue);
+        if self.subtitles.len() >= MAX_SUBTITLES {
+            self.subtitles.remove(0);
+        }
+        self.subtitles.push(Subtitle {
+            cue,
+            pos,
+            timer: LIFETIME,
+        });
+    }
+
+    pub fn maintain(&mut self, dt: f32) {
+        for subtitle in &mut self.subtitles {
+            subtitle.timer -= dt;
+        }
+        self.subtitles.retain(|subtitle| subtitle.timer > 0.0);
+    }
+}
+
+#[derive(WidgetCommon)]
+pub struct Subtitles<'a> {
+    subtitles: &'a SubtitleList,
+    player_pos: Vec2<f32>,
+    /// Yaw of the camera
+    yaw: f32,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Subtitles<'a> {
+    pub fn new(
+        subtitles: &'a SubtitleList,
+        player_pos: Vec2<f32>,
+        yaw: f32,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+    ) -> Self {
+        Self {
+            subtitles,
+            player_pos,
+            yaw,
+            fonts,
+            localized_strings,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+impl<'a> Widget for Subtitles<'a> {
+    type Event = ();
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Subtitles::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+
+        let count = self.subtitles.subtitles.len();
+        if state.ids.texts.len() < count {
+            state.update(|s| {
+                s.ids.bgs.resize(count, &mut ui.widget_id_generator());
+                s.ids.texts.resize(count, &mut ui.widget_id_generator());
+            });
+        }
+
+        // The newest sounds are at the bottom
+        for (i, subtitle) in self.subtitles.subtitles.iter().rev().enumerate() {
+            let caption = self.localized_strings.get_msg(subtitle.cue.key());
+            // Side the sound came from, seen from the camera
+            let side = subtitle.pos.map_or(0.0, |pos| {
+                let dir = pos.xy() - self.player_pos;
+                (dir.x.atan2(dir.y) - self.yaw).sin()
+            });
+            let text = if side < -0.3 {
+                format!("< {}", caption)
+            } else if side > 0.3 {
+                format!("{} >", caption)
+            } else {
+                caption.into_owned()
+            };
+            let alpha = (subtitle.timer / LIFETIME * 2.0).min(1.0);
+
+            Rectangle::fill_with(
+                [260.0, LINE_HEIGHT],
+                color::rgba(0.0, 0.0, 0.0, 0.6 * alpha),
+            )
+            .bottom_right_with_margins_on(ui.window, 250.0 + i as f64 * LINE_HEIGHT, 10.0)
+            .graphics_for(ui.window)
+            .set(state.ids.bgs[i], ui);
+            Text::new(&text)
+                .middle_of(state.ids.bgs[i])
+                .font_size(self.fonts.cyri.scale(14))
+                .font_id(self.fonts.cyri.conrod_id)
+                .color(TEXT_COLOR.alpha(alpha))
+                .graphics_for(ui.window)
+                .set(state.ids.texts[i], ui);
+        }
+    }
+}
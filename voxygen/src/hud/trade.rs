@@ -25,6 +25,7 @@ use crate::{
         slot::{ContentSize, SlotMaker},
         ImageFrame, ItemTooltip, ItemTooltipManager, ItemTooltipable,
     },
+    GlobalState,
 };
 
 use super::{
@@ -88,6 +89,7 @@ pub struct Trade<'a> {
     msm: &'a MaterialStatManifest,
     pulse: f32,
     show: &'a mut Show,
+    global_state: &'a GlobalState,
 }
 
 impl<'a> Trade<'a> {
@@ -104,6 +106,7 @@ impl<'a> Trade<'a> {
         msm: &'a MaterialStatManifest,
         pulse: f32,
         show: &'a mut Show,
+        global_state: &'a GlobalState,
     ) -> Self {
         Self {
             client,
@@ -119,6 +122,7 @@ impl<'a> Trade<'a> {
             msm,
             pulse,
             show,
+            global_state,
         }
     }
 }
@@ -217,6 +221,8 @@ impl<'a> Trade<'a> {
             .client
             .player_list()
             .get(&uid)
+            // Streamers are only known by the name of their character
+            .filter(|_| !(ours && self.global_state.settings.interface.streamer_mode))
             .map(|info| info.player_alias.clone())
             .or_else(|| {
                 self.client
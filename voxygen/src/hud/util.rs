@@ -171,7 +171,8 @@ pub fn consumable_desc(effects: &[Effect], i18n: &Localization) -> Vec<String> {
                 | BuffKind::Wet
                 | BuffKind::Ensnared
                 | BuffKind::Poisoned
-                | BuffKind::Hastened => Cow::Borrowed(""),
+                | BuffKind::Hastened
+                | BuffKind::Rested => Cow::Borrowed(""),
             };
 
             write!(&mut description, "{}", buff_desc).unwrap();
@@ -202,7 +203,8 @@ pub fn consumable_desc(effects: &[Effect], i18n: &Localization) -> Vec<String> {
                     | BuffKind::Wet
                     | BuffKind::Ensnared
                     | BuffKind::Poisoned
-                    | BuffKind::Hastened => Cow::Borrowed(""),
+                    | BuffKind::Hastened
+                    | BuffKind::Rested => Cow::Borrowed(""),
                 }
             } else if let BuffKind::Saturation | BuffKind::Regeneration = buff.kind {
                 i18n.get_msg("buff-text-every_second")
@@ -0,0 +1,198 @@
+use super::{img_ids::Imgs, TEXT_COLOR, UI_HIGHLIGHT_0};
+use crate::ui::fonts::Fonts;
+use common::{comp::inventory::slot::InvSlotId, writing};
+use conrod_core::{
+    widget::{self, Button, Image, Text, TextEdit},
+    widget_ids, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use i18n::Localization;
+use vek::*;
+
+widget_ids! {
+    struct Ids {
+        top,
+        mid,
+        bot,
+        title,
+        input,
+        char_count,
+        write,
+        close,
+    }
+}
+
+/// What is being written in the writing window
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Writable {
+    /// A sign from the inventory, to put up at the position
+    NewSign(InvSlotId, Vec3<i32>),
+    Sign(Vec3<i32>),
+    Book(InvSlotId),
+}
+
+impl Writable {
+    pub fn max_len(&self) -> usize {
+        match self {
+            Self::Book(_) => writing::MAX_BOOK_LEN,
+            Self::NewSign(..) | Self::Sign(_) => writing::MAX_SIGN_LEN,
+        }
+    }
+
+    fn multiline(&self) -> bool { matches!(self, Self::Book(_)) }
+}
+
+#[derive(WidgetCommon)]
+pub struct Writing<'a> {
+    imgs: &'a Imgs,
+    fonts: &'a Fonts,
+    localized_strings: &'a Localization,
+    writable: Writable,
+    text: &'a str,
+
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Writing<'a> {
+    pub fn new(
+        imgs: &'a Imgs,
+        fonts: &'a Fonts,
+        localized_strings: &'a Localization,
+        writable: Writable,
+        text: &'a str,
+    ) -> Self {
+        Self {
+            imgs,
+            fonts,
+            localized_strings,
+            writable,
+            text,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+pub enum Event {
+    Edit(String),
+    Write,
+    Close,
+}
+
+impl<'a> Widget for Writing<'a> {
+    type Event = Option<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Writing::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut event = None;
+        let input_height = if self.writable.multiline() {
+            300.0
+        } else {
+            60.0
+        };
+
+        // Window
+        Image::new(self.imgs.prompt_top)
+            .w_h(276.0, 24.0)
+            .mid_bottom_with_margin_on(ui.window, 320.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.top, ui);
+        Image::new(self.imgs.prompt_mid)
+            .w_h(276.0, input_height + 100.0)
+            .down_from(state.ids.top, 0.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.mid, ui);
+        Image::new(self.imgs.prompt_bot)
+            .w_h(276.0, 35.0)
+            .down_from(state.ids.mid, 0.0)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.bot, ui);
+
+        let title = match self.writable {
+            Writable::NewSign(..) | Writable::Sign(_) => "hud-writing-sign",
+            Writable::Book(_) => "hud-writing-book",
+        };
+        Text::new(&self.localized_strings.get_msg(title))
+            .mid_top_with_margin_on(state.ids.mid, 0.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(18))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        if let Some(mut text) = TextEdit::new(self.text)
+            .w_h(246.0, input_height)
+            .mid_top_with_margin_on(state.ids.mid, 30.0)
+            .wrap_by_word()
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.input, ui)
+        {
+            if !self.writable.multiline() {
+                text.retain(|c| c != '\n');
+            }
+            event = Some(Event::Edit(
+                text.chars().take(self.writable.max_len()).collect(),
+            ));
+        }
+
+        Text::new(&format!(
+            "{}/{}",
+            self.text.chars().count(),
+            self.writable.max_len()
+        ))
+        .bottom_right_with_margins_on(state.ids.mid, 42.0, 15.0)
+        .font_id(self.fonts.cyri.conrod_id)
+        .font_size(self.fonts.cyri.scale(12))
+        .color(TEXT_COLOR)
+        .set(state.ids.char_count, ui);
+
+        if Button::image(self.imgs.button)
+            .w_h(120.0, 36.0)
+            .bottom_left_with_margins_on(state.ids.mid, 0.0, 15.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .label(&self.localized_strings.get_msg("hud-writing-write"))
+            .label_y(conrod_core::position::Relative::Scalar(2.0))
+            .label_color(TEXT_COLOR)
+            .label_font_size(self.fonts.cyri.scale(16))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .set(state.ids.write, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Write);
+        }
+
+        if Button::image(self.imgs.button)
+            .w_h(120.0, 36.0)
+            .bottom_right_with_margins_on(state.ids.mid, 0.0, 15.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .label(&self.localized_strings.get_msg("common-close"))
+            .label_y(conrod_core::position::Relative::Scalar(2.0))
+            .label_color(TEXT_COLOR)
+            .label_font_size(self.fonts.cyri.scale(16))
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            event = Some(Event::Close);
+        }
+
+        event
+    }
+}
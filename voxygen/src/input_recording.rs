@@ -0,0 +1,170 @@
+//! Recording the game inputs of a session with their timing, to replay them
+//! later when reproducing movement and physics bugs. Recordings are only ever
+//! replayed in singleplayer, so that they can't be used to bot on servers.
+
+use crate::game_input::GameInput;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+const RECORDINGS_DIR: &str = "input_recordings";
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct RecordedInput {
+    /// Seconds since the recording started
+    pub at: f64,
+    pub input: GameInput,
+    pub state: bool,
+}
+
+enum Mode {
+    Idle,
+    Recording {
+        name: String,
+        started: Instant,
+        inputs: Vec<RecordedInput>,
+    },
+    Replaying {
+        started: Instant,
+        inputs: VecDeque<RecordedInput>,
+    },
+}
+
+impl Default for Mode {
+    fn default() -> Self { Mode::Idle }
+}
+
+#[derive(Default)]
+pub struct InputRecorder {
+    mode: Mode,
+}
+
+impl InputRecorder {
+    pub fn start_recording(&mut self, name: &str) -> Result<(), String> {
+        if !matches!(self.mode, Mode::Idle) {
+            return Err("Already recording or replaying inputs.".to_string());
+        }
+        check_name(name)?;
+        self.mode = Mode::Recording {
+            name: name.to_string(),
+            started: Instant::now(),
+            inputs: Vec::new(),
+        };
+        Ok(())
+    }
+
+    /// Stops recording, saving the recording in the userdata directory, or
+    /// stops replaying
+    pub fn stop(&mut self, userdata_dir: &Path) -> Result<String, String> {
+        match std::mem::take(&mut self.mode) {
+            Mode::Idle => Err("Not recording or replaying inputs.".to_string()),
+            Mode::Recording { name, inputs, .. } => {
+                let path = recording_path(userdata_dir, &name);
+                ron::ser::to_string_pretty(&inputs, ron::ser::PrettyConfig::default())
+                    .map_err(|err| err.to_string())
+                    .and_then(|ron| {
+                        fs::create_dir_all(userdata_dir.join(RECORDINGS_DIR))
+                            .and_then(|_| fs::write(&path, ron))
+                            .map_err(|err| err.to_string())
+                    })
+                    .map_err(|err| format!("Failed to save the recording: {}", err))?;
+                Ok(format!(
+                    "Saved {} inputs to {}.",
+                    inputs.len(),
+                    path.display()
+                ))
+            },
+            Mode::Replaying { .. } => Ok("Stopped replaying inputs.".to_string()),
+        }
+    }
+
+    /// Starts replaying a recording, which is refused unless the session is
+    /// local
+    pub fn replay(
+        &mut self,
+        userdata_dir: &Path,
+        name: &str,
+        local: bool,
+    ) -> Result<usize, String> {
+        if !local {
+            return Err(
+                "Input recordings can only be replayed in singleplayer, they can't be used on \
+                 servers."
+                    .to_string(),
+            );
+        }
+        if !matches!(self.mode, Mode::Idle) {
+            return Err("Already recording or replaying inputs.".to_string());
+        }
+        check_name(name)?;
+        let file = fs::File::open(recording_path(userdata_dir, name))
+            .map_err(|_| format!("Could not find a recording named {}.", name))?;
+        let inputs: VecDeque<RecordedInput> = ron::de::from_reader(file)
+            .map_err(|err| format!("Failed to read the recording: {}", err))?;
+        let len = inputs.len();
+        self.mode = Mode::Replaying {
+            started: Instant::now(),
+            inputs,
+        };
+        Ok(len)
+    }
+
+    pub fn record(&mut self, input: GameInput, state: bool) {
+        if let Mode::Recording {
+            started, inputs, ..
+        } = &mut self.mode
+        {
+            inputs.push(RecordedInput {
+                at: started.elapsed().as_secs_f64(),
+                input,
+                state,
+            });
+        }
+    }
+
+    /// The replayed inputs which are due, the replay stopping once all of
+    /// them were or as soon as the session isn't local anymore
+    pub fn due(&mut self, local: bool) -> Vec<(GameInput, bool)> {
+        let (started, inputs) = match &mut self.mode {
+            Mode::Replaying { started, inputs } => (started, inputs),
+            _ => return Vec::new(),
+        };
+        if !local {
+            self.mode = Mode::Idle;
+            return Vec::new();
+        }
+        let elapsed = started.elapsed().as_secs_f64();
+        let mut due = Vec::new();
+        while let Some(recorded) = inputs.front().filter(|recorded| recorded.at <= elapsed) {
+            due.push((recorded.input, recorded.state));
+            inputs.pop_front();
+        }
+        if inputs.is_empty() {
+            self.mode = Mode::Idle;
+        }
+        due
+    }
+}
+
+fn recording_path(userdata_dir: &Path, name: &str) -> PathBuf {
+    userdata_dir
+        .join(RECORDINGS_DIR)
+        .join(format!("{}.ron", name))
+}
+
+/// Names end up in file paths, so only simple ones are allowed
+fn check_name(name: &str) -> Result<(), String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        Ok(())
+    } else {
+        Err("Recording names can only contain letters, digits, '_' and '-'.".to_string())
+    }
+}
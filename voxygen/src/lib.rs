@@ -19,6 +19,8 @@
 #[macro_use]
 pub mod ui;
 pub mod audio;
+pub mod capture;
+pub mod chat_history;
 pub mod cmd;
 pub mod controller;
 mod credits;
@@ -27,6 +29,8 @@ mod ecs;
 pub mod error;
 pub mod game_input;
 pub mod hud;
+#[cfg(feature = "input-recording")]
+pub mod input_recording;
 pub mod key_state;
 pub mod menu;
 pub mod mesh;
@@ -38,6 +42,7 @@ pub mod session;
 pub mod settings;
 #[cfg(feature = "singleplayer")]
 pub mod singleplayer;
+pub mod update_check;
 pub mod window;
 
 #[cfg(feature = "singleplayer")]
@@ -49,6 +54,7 @@ use crate::{
     profile::Profile,
     render::{Drawer, GlobalsBindGroup},
     settings::Settings,
+    update_check::UpdateCheck,
     window::{Event, Window},
 };
 use common::clock::Clock;
@@ -75,6 +81,10 @@ pub struct GlobalState {
     pub clock: Clock,
     #[cfg(feature = "singleplayer")]
     pub singleplayer: Option<Singleplayer>,
+    #[cfg(feature = "input-recording")]
+    pub input_recorder: input_recording::InputRecorder,
+    /// Camera path being played, with the HUD hidden
+    pub cutscene: Option<scene::camera_path::Cutscene>,
     // TODO: redo this so that the watcher doesn't have to exist for reloading to occur
     pub i18n: LocalizationHandle,
     pub clipboard: iced_winit::Clipboard,
@@ -84,6 +94,8 @@ pub struct GlobalState {
     pub client_error: Option<String>,
     // Used to clear the shadow textures when entering a PlayState that doesn't utilise shadows
     pub clear_shadows_next_frame: bool,
+    /// Background check for a newer release of the game
+    pub update_check: UpdateCheck,
     /// A channel that sends Discord activity updates to a background task
     #[cfg(feature = "discord")]
     pub discord: crate::discord::Discord,
@@ -95,6 +107,10 @@ impl GlobalState {
     pub fn on_play_state_changed(&mut self) {
         self.window.grab_cursor(false);
         self.window.needs_refresh_resize();
+        self.audio.duck_music(false);
+        // Only a session joins the voice chat
+        #[cfg(feature = "voice-chat")]
+        self.audio.set_voice_chat(false);
     }
 
     pub fn maintain(&mut self, dt: std::time::Duration) {
@@ -113,6 +129,13 @@ impl GlobalState {
     #[cfg(not(feature = "singleplayer"))]
     pub fn paused(&self) -> bool { false }
 
+    /// Whether the session is running on a local singleplayer server
+    #[cfg(feature = "singleplayer")]
+    pub fn is_local(&self) -> bool { self.singleplayer.is_some() }
+
+    #[cfg(not(feature = "singleplayer"))]
+    pub fn is_local(&self) -> bool { false }
+
     #[cfg(feature = "singleplayer")]
     pub fn unpause(&self) { self.singleplayer.as_ref().map(|s| s.pause(false)); }
 
@@ -25,6 +25,7 @@ use veloren_voxygen::{
     run,
     scene::terrain::SpriteRenderContext,
     settings::{get_fps, AudioOutput, Settings},
+    update_check::UpdateCheck,
     window::Window,
     GlobalState,
 };
@@ -42,15 +43,22 @@ fn main() {
     #[cfg(feature = "tracy")]
     common_base::tracy_client::Client::start();
 
-    let userdata_dir = common_base::userdata_dir_workspace!();
+    // Process CLI arguments, before choosing the userdata dir since portable mode
+    // changes it
+    use clap::Parser;
+    let args = cli::Args::parse();
+    common_base::userdata_dir::set_portable(args.portable);
+
+    let data_dirs = common_base::data_dirs_workspace!("voxygen");
+    let userdata_dir = data_dirs.userdata.clone();
 
     // Determine where Voxygen's logs should go
     // Choose a path to store the logs by the following order:
     //  - The VOXYGEN_LOGS environment variable
-    //  - The <userdata>/voxygen/logs
+    //  - The platform cache directory or <userdata>/voxygen/logs, see `DataDirs`
     let logs_dir = std::env::var_os("VOXYGEN_LOGS")
         .map(PathBuf::from)
-        .unwrap_or_else(|| userdata_dir.join("voxygen").join("logs"));
+        .unwrap_or_else(|| data_dirs.logs.clone());
 
     // Init logging and hold the guards.
     let now = Utc::now();
@@ -62,8 +70,8 @@ fn main() {
 
     info!("Using userdata dir at: {}", userdata_dir.display());
 
-    // Determine Voxygen's config directory either by env var or placed in veloren's
-    // userdata folder
+    // Determine Voxygen's config directory either by env var or the platform
+    // config directory or veloren's userdata folder, see `DataDirs`
     let config_dir = std::env::var_os("VOXYGEN_CONFIG")
         .map(PathBuf::from)
         .and_then(|path| {
@@ -74,7 +82,7 @@ fn main() {
                 None
             }
         })
-        .unwrap_or_else(|| userdata_dir.join("voxygen"));
+        .unwrap_or(data_dirs.config);
     info!("Using config dir at: {}", config_dir.display());
 
     // Load the settings
@@ -184,10 +192,6 @@ fn main() {
         default_hook(panic_info);
     }));
 
-    // Process CLI arguments
-    use clap::Parser;
-    let args = cli::Args::parse();
-
     // Setup tokio runtime
     use common::consts::MIN_RECOMMENDED_TOKIO_THREADS;
     use std::sync::{
@@ -229,11 +233,11 @@ fn main() {
     // Setup audio
     let mut audio = match settings.audio.output {
         AudioOutput::Off => AudioFrontend::no_audio(),
-        AudioOutput::Automatic => AudioFrontend::new(
+        AudioOutput::Automatic | AudioOutput::Device(_) => AudioFrontend::new(
+            settings.audio.output.device(),
             settings.audio.num_sfx_channels,
             settings.audio.num_ui_channels,
         ),
-        //    AudioOutput::Device(ref dev) => Some(dev.clone()),
     };
 
     audio.set_master_volume(settings.audio.master_volume);
@@ -241,6 +245,8 @@ fn main() {
     audio.set_sfx_volume(settings.audio.sfx_volume);
     audio.set_ambience_volume(settings.audio.ambience_volume);
     audio.set_music_spacing(settings.audio.music_spacing);
+    #[cfg(feature = "voice-chat")]
+    audio.set_voice_volume(settings.audio.voice_volume);
 
     // Load the profile.
     let profile = Profile::load(&config_dir);
@@ -298,6 +304,12 @@ fn main() {
         veloren_voxygen::discord::Discord::Inactive
     };
 
+    let update_check = if settings.networking.check_for_updates {
+        UpdateCheck::start(&tokio_runtime, &settings.networking.update_feed_url)
+    } else {
+        UpdateCheck::Disabled
+    };
+
     let global_state = GlobalState {
         userdata_dir,
         config_dir,
@@ -315,10 +327,14 @@ fn main() {
         info_message: None,
         #[cfg(feature = "singleplayer")]
         singleplayer: None,
+        #[cfg(feature = "input-recording")]
+        input_recorder: Default::default(),
+        cutscene: None,
         i18n,
         clipboard,
         client_error: None,
         clear_shadows_next_frame: false,
+        update_check,
         #[cfg(feature = "discord")]
         discord,
     };
@@ -1686,6 +1686,13 @@ impl CharSelectionUi {
         #[cfg(not(feature = "singleplayer"))]
         let default_name = global_state.settings.networking.username.clone();
 
+        // Don't suggest the account name to streamers
+        let default_name = if global_state.settings.interface.streamer_mode {
+            String::new()
+        } else {
+            default_name
+        };
+
         let controls = Controls::new(
             fonts,
             Imgs::load(&mut ui).expect("Failed to load images"),
@@ -3,6 +3,7 @@ use client::{
     error::{Error as ClientError, NetworkConnectError, NetworkError},
     Client, ServerInfo,
 };
+use common_net::msg::LoginQueuePosition;
 use crossbeam_channel::{unbounded, Receiver, Sender, TryRecvError};
 use std::{
     sync::{
@@ -29,6 +30,7 @@ pub enum Error {
 #[allow(clippy::large_enum_variant)] // TODO: Pending review in #587
 pub enum Msg {
     IsAuthTrusted(String),
+    Queued(LoginQueuePosition),
     Done(Result<Client, Error>),
 }
 
@@ -65,6 +67,11 @@ impl ClientInit {
                     .unwrap_or(false)
             };
 
+            let queued_tx = tx.clone();
+            let queued_fn = move |position| {
+                let _ = queued_tx.send(Msg::Queued(position));
+            };
+
             let mut last_err = None;
 
             const FOUR_MINUTES_RETRIES: u64 = 48;
@@ -81,7 +88,19 @@ impl ClientInit {
                 .await
                 {
                     Ok(mut client) => {
-                        if let Err(e) = client.register(username, password, trust_fn).await {
+                        // Stop waiting for the answer of the server once cancelled, not to keep
+                        // a place in its login queue
+                        let cancelled = async {
+                            while !cancel2.load(Ordering::Relaxed) {
+                                tokio::time::sleep(Duration::from_millis(100)).await;
+                            }
+                        };
+                        let register = client.register(username, password, trust_fn, queued_fn);
+                        let result = tokio::select! {
+                            result = register => result,
+                            _ = cancelled => break 'tries,
+                        };
+                        if let Err(e) = result {
                             last_err = Some(Error::ClientError {
                                 error: e,
                                 mismatched_server_info: None,
@@ -7,13 +7,14 @@ use super::char_selection::CharSelectionState;
 use crate::singleplayer::Singleplayer;
 use crate::{
     render::{Drawer, GlobalsBindGroup},
-    settings::Settings,
+    settings::{ControlSettings, Settings},
+    update_check::ReleaseInfo,
     window::Event,
     Direction, GlobalState, PlayState, PlayStateResult,
 };
 use client::{
     addr::ConnectionArgs,
-    error::{InitProtocolError, NetworkConnectError, NetworkError},
+    error::{InitProtocolError, NetworkConnectError, NetworkError, VELOREN_NETWORK_VERSION},
     Client, ServerInfo,
 };
 use client_init::{ClientInit, Error as InitError, Msg as InitMsg};
@@ -49,6 +50,9 @@ impl InitState {
 pub struct MainMenuState {
     main_menu_ui: MainMenuUi,
     init: InitState,
+    /// Login waiting for the player to confirm connecting with a client older
+    /// than the latest release
+    pending_login: Option<(String, String, ConnectionArgs)>,
     scene: Scene,
 }
 
@@ -58,6 +62,7 @@ impl MainMenuState {
         Self {
             main_menu_ui: MainMenuUi::new(global_state, server),
             init: InitState::None,
+            pending_login: None,
             scene: Scene::new(global_state.window.renderer_mut()),
         }
     }
@@ -192,7 +197,11 @@ impl PlayState for MainMenuState {
             Some(InitMsg::Done(Err(e))) => {
                 self.init = InitState::None;
                 error!(?e, "Client Init failed raw error");
-                let e = get_client_msg_error(e, &global_state.i18n);
+                let e = get_client_msg_error(
+                    e,
+                    &global_state.i18n,
+                    global_state.update_check.newer_release(),
+                );
                 // Log error for possible additional use later or in case that the error
                 // displayed is cut of.
                 error!(?e, "Client Init failed");
@@ -218,6 +227,7 @@ impl PlayState for MainMenuState {
                     self.main_menu_ui.auth_trust_prompt(auth_server);
                 }
             },
+            Some(InitMsg::Queued(position)) => self.main_menu_ui.login_queued(position),
             None => {},
         }
 
@@ -311,15 +321,44 @@ impl PlayState for MainMenuState {
                             prefer_ipv6: false,
                         }
                     };
-                    attempt_login(
-                        &mut global_state.info_message,
-                        username,
-                        password,
-                        connection_args,
-                        &mut self.init,
-                        &global_state.tokio_runtime,
-                        &global_state.i18n,
-                    );
+                    // Servers running the latest release refuse clients speaking an older
+                    // protocol, ask before connecting if that is our case
+                    let outdated_by = global_state
+                        .update_check
+                        .newer_release()
+                        .filter(|release| release.network_version > VELOREN_NETWORK_VERSION)
+                        .map(|release| release.version.clone());
+                    if let Some(version) = outdated_by {
+                        self.pending_login = Some((username, password, connection_args));
+                        self.main_menu_ui.outdated_version_prompt(&version);
+                    } else {
+                        attempt_login(
+                            &mut global_state.info_message,
+                            username,
+                            password,
+                            connection_args,
+                            &mut self.init,
+                            &global_state.tokio_runtime,
+                            &global_state.i18n,
+                        );
+                    }
+                },
+                MainMenuEvent::ConnectWithOutdatedVersion(connect) => {
+                    if let Some((username, password, connection_args)) = self.pending_login.take() {
+                        if connect {
+                            attempt_login(
+                                &mut global_state.info_message,
+                                username,
+                                password,
+                                connection_args,
+                                &mut self.init,
+                                &global_state.tokio_runtime,
+                                &global_state.i18n,
+                            );
+                        } else {
+                            self.main_menu_ui.cancel_connection();
+                        }
+                    }
                 },
                 MainMenuEvent::CancelLoginAttempt => {
                     // init contains InitState::Client(ClientInit), which spawns a thread which
@@ -330,6 +369,7 @@ impl PlayState for MainMenuState {
                         global_state.singleplayer = None;
                     }
                     self.init = InitState::None;
+                    self.pending_login = None;
                     self.main_menu_ui.cancel_connection();
                 },
                 MainMenuEvent::ChangeLanguage(new_language) => {
@@ -378,6 +418,28 @@ impl PlayState for MainMenuState {
                         .settings
                         .save_to_file_warn(&global_state.config_dir);
                 },
+                MainMenuEvent::PlayTestSound => global_state.audio.play_test_sound(),
+                MainMenuEvent::SetupWizardFinished {
+                    graphics_preset,
+                    keybinding_scheme,
+                } => {
+                    let settings = &mut global_state.settings;
+                    settings.show_setup_wizard = false;
+                    if let Some(preset) = graphics_preset {
+                        settings.graphics.apply_preset(preset);
+                        if let Err(e) = global_state
+                            .window
+                            .renderer_mut()
+                            .set_render_mode(settings.graphics.render_mode.clone())
+                        {
+                            error!(?e, "Failed to apply the render mode of the graphics preset");
+                        }
+                    }
+                    if let Some(scheme) = keybinding_scheme {
+                        settings.controls = ControlSettings::from_scheme(scheme);
+                    }
+                    settings.save_to_file_warn(&global_state.config_dir);
+                },
             }
         }
 
@@ -408,6 +470,7 @@ impl PlayState for MainMenuState {
 fn get_client_msg_error(
     error: client_init::Error,
     localized_strings: &LocalizationHandle,
+    newer_release: Option<&ReleaseInfo>,
 ) -> String {
     let localization = localized_strings.read();
 
@@ -465,6 +528,22 @@ fn get_client_msg_error(
                 format!("{}: {}", localization.get_msg("main-login-banned"), reason)
             },
             Error::InvalidCharacter => localization.get_msg("main-login-invalid_character").into(),
+            Error::SessionExpired => localization.get_msg("main-login-session_expired").into(),
+            // The server speaks a newer protocol, point the player to the update if we know
+            // about it
+            Error::NetworkErr(NetworkError::ConnectFailed(NetworkConnectError::Handshake(
+                InitProtocolError::WrongVersion(server_version),
+            ))) if server_version > VELOREN_NETWORK_VERSION => match newer_release {
+                Some(release) => localization
+                    .get_msg_ctx(
+                        "main-login-server_requires_update",
+                        &i18n::fluent_args! { "version" => release.version.as_str() },
+                    )
+                    .into_owned(),
+                None => localization
+                    .get_msg("main-login-server_requires_newer_version")
+                    .into_owned(),
+            },
             Error::NetworkErr(NetworkError::ConnectFailed(NetworkConnectError::Handshake(
                 InitProtocolError::WrongVersion(_),
             ))) => net_error(
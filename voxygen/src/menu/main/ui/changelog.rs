@@ -0,0 +1,104 @@
+use super::Message;
+use crate::{
+    ui::{
+        fonts::IcedFonts as Fonts,
+        ice::{component::neat_button, style, Element},
+    },
+    update_check::ReleaseInfo,
+};
+use i18n::Localization;
+use iced::{button, scrollable, Column, Container, HorizontalAlignment, Length, Scrollable, Space};
+
+/// Changelog screen for the main menu, showing the notes of a newer release
+pub struct Screen {
+    back_button: button::State,
+    scroll: scrollable::State,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Self {
+            back_button: Default::default(),
+            scroll: Default::default(),
+        }
+    }
+
+    pub(super) fn view(
+        &mut self,
+        fonts: &Fonts,
+        i18n: &Localization,
+        release: Option<&ReleaseInfo>,
+        button_style: style::button::Style,
+    ) -> Element<Message> {
+        let title = match release {
+            Some(release) => i18n
+                .get_msg_ctx("main-changelog-title", &i18n::fluent_args! {
+                    "version" => release.version.as_str(),
+                })
+                .into_owned(),
+            None => i18n.get_msg("main-changelog").into_owned(),
+        };
+
+        let entries = release
+            .into_iter()
+            .flat_map(|release| release.changelog.lines())
+            .map(|line| {
+                iced::Text::new(line)
+                    .font(fonts.cyri.id)
+                    .size(fonts.cyri.scale(20))
+                    .width(Length::Fill)
+                    .into()
+            })
+            .collect::<Vec<_>>();
+
+        Container::new(
+            Container::new(
+                Column::with_children(vec![
+                    iced::Text::new(title)
+                        .font(fonts.alkhemi.id)
+                        .size(fonts.alkhemi.scale(35))
+                        .width(Length::Fill)
+                        .horizontal_alignment(HorizontalAlignment::Center)
+                        .into(),
+                    Space::new(Length::Fill, Length::Units(25)).into(),
+                    Scrollable::new(&mut self.scroll)
+                        .push(Column::with_children(entries).spacing(3))
+                        .height(Length::FillPortion(1))
+                        .width(Length::Fill)
+                        .into(),
+                    Container::new(
+                        Container::new(neat_button(
+                            &mut self.back_button,
+                            i18n.get_msg("common-back"),
+                            0.7,
+                            button_style,
+                            Some(Message::Back),
+                        ))
+                        .height(Length::Units(fonts.cyri.scale(50))),
+                    )
+                    .center_x()
+                    .height(Length::Shrink)
+                    .width(Length::Fill)
+                    .into(),
+                ])
+                .spacing(5)
+                .padding(20)
+                .width(Length::Fill)
+                .height(Length::Fill),
+            )
+            .style(
+                style::container::Style::color_with_double_cornerless_border(
+                    (22, 19, 17, 255).into(),
+                    (11, 11, 11, 255).into(),
+                    (54, 46, 38, 255).into(),
+                ),
+            ),
+        )
+        .center_x()
+        .center_y()
+        .padding(70)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
@@ -98,8 +98,30 @@ impl Screen {
         let frame_id = self.loading_animation.frames[frame_index as usize];
 
         let children = match connection_state {
-            ConnectionState::InProgress => {
-                let tip = if show_tip {
+            ConnectionState::InProgress | ConnectionState::Queued(_) => {
+                let tip = if let ConnectionState::Queued(queue) = connection_state {
+                    let mut text = i18n
+                        .get_msg_ctx("main-login-queue_position", &i18n::fluent_args! {
+                            "position" => queue.position,
+                        })
+                        .into_owned();
+                    if let Some(wait) = queue.estimated_wait {
+                        text.push('\n');
+                        text.push_str(&i18n.get_msg_ctx(
+                            "main-login-queue_wait",
+                            &i18n::fluent_args! {
+                                "minutes" => (wait.as_secs() + 59) / 60,
+                            },
+                        ));
+                    }
+
+                    Container::new(Text::new(text).size(fonts.cyri.scale(25)))
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .center_x()
+                        .align_y(Align::End)
+                        .into()
+                } else if show_tip {
                     let key = |code| match controls.keybindings.get(&code) {
                         Some(Some(key_mouse)) => key_mouse.display_string(key_layout),
                         Some(None) => i18n.get_msg("main-unbound_key_tip").into_owned(),
@@ -185,22 +207,37 @@ impl Screen {
                     bottom_bar.into(),
                 ]
             },
-            ConnectionState::AuthTrustPrompt { msg, .. } => {
+            ConnectionState::AuthTrustPrompt { msg, .. }
+            | ConnectionState::OutdatedVersionPrompt { msg } => {
                 let text = Text::new(msg).size(fonts.cyri.scale(25));
 
+                let (cancel_msg, add_label, add_msg) =
+                    if let ConnectionState::AuthTrustPrompt { .. } = connection_state {
+                        (
+                            Message::TrustPromptCancel,
+                            i18n.get_msg("common-add"),
+                            Message::TrustPromptAdd,
+                        )
+                    } else {
+                        (
+                            Message::OutdatedVersionCancel,
+                            i18n.get_msg("main-login-connect_anyway"),
+                            Message::OutdatedVersionConnect,
+                        )
+                    };
                 let cancel = neat_button(
                     &mut self.cancel_button,
                     i18n.get_msg("common-cancel"),
                     0.7,
                     button_style,
-                    Some(Message::TrustPromptCancel),
+                    Some(cancel_msg),
                 );
                 let add = neat_button(
                     &mut self.add_button,
-                    i18n.get_msg("common-add"),
+                    add_label,
                     0.7,
                     button_style,
-                    Some(Message::TrustPromptAdd),
+                    Some(add_msg),
                 );
 
                 let content = Column::with_children(vec![
@@ -11,6 +11,7 @@ use crate::ui::{
         Element,
     },
 };
+use client::HIDDEN_NAME;
 use i18n::{LanguageMetadata, Localization};
 use iced::{
     button, scrollable, text_input, Align, Button, Column, Container, Length, Row, Scrollable,
@@ -27,6 +28,7 @@ pub struct Screen {
     // settings_button: button::State,
     servers_button: button::State,
     credits_button: button::State,
+    changelog_button: button::State,
     language_select_button: button::State,
 
     error_okay_button: button::State,
@@ -40,6 +42,7 @@ impl Screen {
         Self {
             servers_button: Default::default(),
             credits_button: Default::default(),
+            changelog_button: Default::default(),
             // settings_button: Default::default(),
             quit_button: Default::default(),
             language_select_button: Default::default(),
@@ -56,6 +59,7 @@ impl Screen {
         fonts: &Fonts,
         imgs: &Imgs,
         server_field_locked: bool,
+        streamer_mode: bool,
         login_info: &LoginInfo,
         error: Option<&str>,
         i18n: &Localization,
@@ -64,6 +68,7 @@ impl Screen {
         language_metadatas: &[LanguageMetadata],
         button_style: style::button::Style,
         version: &str,
+        newer_version: Option<&str>,
     ) -> Element<Message> {
         let mut buttons = Vec::new();
         // If the server field is locked, we don't want to show the server selection
@@ -135,7 +140,42 @@ impl Screen {
         .max_width(360)
         .padding(Padding::new().horizontal(20).top(10).bottom(60));
 
-        let left_column = Column::with_children(vec![info_window.into(), buttons.into()])
+        let mut left_children = vec![info_window.into()];
+        // Let the player know a new release is out, with a button to read its changelog
+        if let Some(newer_version) = newer_version {
+            let update_banner = Container::new(
+                Column::with_children(vec![
+                    Text::new(i18n.get_msg_ctx(
+                        "main-login-update_available",
+                        &i18n::fluent_args! { "version" => newer_version },
+                    ))
+                    .size(fonts.cyri.scale(18))
+                    .into(),
+                    neat_button(
+                        &mut self.changelog_button,
+                        i18n.get_msg("main-changelog"),
+                        FILL_FRAC_ONE,
+                        button_style,
+                        Some(Message::ShowChangelog),
+                    ),
+                ])
+                .spacing(5)
+                .max_width(360),
+            )
+            .style(
+                style::container::Style::color_with_double_cornerless_border(
+                    (22, 18, 16, 255).into(),
+                    (11, 11, 11, 255).into(),
+                    (54, 46, 38, 255).into(),
+                ),
+            )
+            .padding(10);
+            left_children.push(Space::new(Length::Fill, Length::Units(10)).into());
+            left_children.push(update_banner.into());
+        }
+        left_children.push(buttons.into());
+
+        let left_column = Column::with_children(left_children)
             .width(Length::Fill)
             .height(Length::Fill)
             .padding(27)
@@ -185,6 +225,7 @@ impl Screen {
                 fonts,
                 imgs,
                 server_field_locked,
+                streamer_mode,
                 login_info,
                 i18n,
                 button_style,
@@ -368,6 +409,7 @@ impl LoginBanner {
         fonts: &Fonts,
         imgs: &Imgs,
         server_field_locked: bool,
+        streamer_mode: bool,
         login_info: &LoginInfo,
         i18n: &Localization,
         button_style: style::button::Style,
@@ -395,26 +437,49 @@ impl LoginBanner {
             };
 
             Row::with_children(vec![
-                Text::new(&login_info.server)
-                    .size(input_text_size)
-                    .width(Length::Fill)
-                    .height(Length::Shrink)
-                    .into(),
+                Text::new(if streamer_mode {
+                    HIDDEN_NAME
+                } else {
+                    login_info.server.as_str()
+                })
+                .size(input_text_size)
+                .width(Length::Fill)
+                .height(Length::Shrink)
+                .into(),
                 container.into(),
             ])
             .align_items(Align::Center)
             .height(Length::Fill)
             .into()
         } else {
-            TextInput::new(
+            let server_input = TextInput::new(
                 &mut self.server,
                 &i18n.get_msg("main-server"),
                 &login_info.server,
                 Message::Server,
             )
             .size(input_text_size)
-            .on_submit(Message::Multiplayer)
-            .into()
+            .on_submit(Message::Multiplayer);
+            if streamer_mode {
+                server_input.password().into()
+            } else {
+                server_input.into()
+            }
+        };
+
+        let username_input = TextInput::new(
+            &mut self.username,
+            &i18n.get_msg("main-username"),
+            &login_info.username,
+            Message::Username,
+        )
+        .size(input_text_size)
+        .on_submit(Message::FocusPassword);
+        // The account name is half of the credentials
+        let username_input = if streamer_mode {
+            username_input.password()
+        } else {
+            username_input
         };
 
         let banner_content = Column::with_children(vec![
@@ -423,14 +488,7 @@ impl LoginBanner {
                     Image::new(imgs.input_bg)
                         .width(Length::Units(INPUT_WIDTH))
                         .fix_aspect_ratio(),
-                    TextInput::new(
-                        &mut self.username,
-                        &i18n.get_msg("main-username"),
-                        &login_info.username,
-                        Message::Username,
-                    )
-                    .size(input_text_size)
-                    .on_submit(Message::FocusPassword),
+                    username_input,
                 )
                 .padding(Padding::new().horizontal(7).top(5))
                 .into(),
@@ -1,9 +1,11 @@
+mod changelog;
 mod connecting;
 // Note: Keeping in case we re-add the disclaimer
 //mod disclaimer;
 mod credits;
 mod login;
 mod servers;
+mod setup_wizard;
 
 use crate::{
     credits::Credits,
@@ -15,14 +17,16 @@ use crate::{
         img_ids::ImageGraphic,
         Graphic,
     },
+    update_check::ReleaseInfo,
     window, GlobalState,
 };
 use i18n::{LanguageMetadata, LocalizationHandle};
 use iced::{text_input, Column, Container, HorizontalAlignment, Length, Row, Space};
 use keyboard_keynames::key_layout::KeyLayout;
 //ImageFrame, Tooltip,
-use crate::settings::Settings;
+use crate::settings::{GraphicsPreset, KeybindingScheme, Settings};
 use common::assets::{self, AssetExt};
+use common_net::msg::LoginQueuePosition;
 use rand::{seq::SliceRandom, thread_rng};
 use std::time::Duration;
 use tracing::warn;
@@ -91,9 +95,17 @@ pub enum Event {
     // Note: Keeping in case we re-add the disclaimer
     //DisclaimerAccepted,
     AuthServerTrust(String, bool),
+    /// Whether to connect despite the latest release speaking a newer protocol
+    ConnectWithOutdatedVersion(bool),
     DeleteServer {
         server_index: usize,
     },
+    PlayTestSound,
+    /// The first launch setup wizard was finished, `None` values were skipped
+    SetupWizardFinished {
+        graphics_preset: Option<GraphicsPreset>,
+        keybinding_scheme: Option<KeybindingScheme>,
+    },
 }
 
 pub struct LoginInfo {
@@ -104,7 +116,16 @@ pub struct LoginInfo {
 
 enum ConnectionState {
     InProgress,
-    AuthTrustPrompt { auth_server: String, msg: String },
+    AuthTrustPrompt {
+        auth_server: String,
+        msg: String,
+    },
+    /// Servers running the latest release will refuse the connection
+    OutdatedVersionPrompt {
+        msg: String,
+    },
+    /// Waiting in the login queue of a full server
+    Queued(LoginQueuePosition),
 }
 
 enum Screen {
@@ -115,6 +136,12 @@ enum Screen {
     Credits {
         screen: credits::Screen,
     },
+    Changelog {
+        screen: changelog::Screen,
+    },
+    SetupWizard {
+        screen: Box<setup_wizard::Screen>, // boxed to avoid large variant
+    },
     Login {
         screen: Box<login::Screen>, // boxed to avoid large variant
         // Error to display in a box
@@ -150,6 +177,10 @@ struct Controls {
     is_selecting_language: bool,
     selected_language_index: Option<usize>,
 
+    // Used by the setup wizard
+    graphics_device_type: wgpu::DeviceType,
+    audio_available: bool,
+
     time: f64,
 
     screen: Screen,
@@ -161,6 +192,7 @@ enum Message {
     Back,
     ShowServers,
     ShowCredits,
+    ShowChangelog,
     #[cfg(feature = "singleplayer")]
     Singleplayer,
     Multiplayer,
@@ -175,8 +207,17 @@ enum Message {
     CancelConnect,
     TrustPromptAdd,
     TrustPromptCancel,
+    OutdatedVersionConnect,
+    OutdatedVersionCancel,
     CloseError,
     DeleteServer,
+    SetupNext,
+    SetupBack,
+    SetupSkip,
+    SetupFinish,
+    SetupPreset(GraphicsPreset),
+    SetupScheme(KeybindingScheme),
+    SetupTestSound,
     /* Note: Keeping in case we re-add the disclaimer
      *AcceptDisclaimer, */
 }
@@ -189,6 +230,8 @@ impl Controls {
         i18n: LocalizationHandle,
         settings: &Settings,
         server: Option<String>,
+        graphics_device_type: wgpu::DeviceType,
+        audio_available: bool,
     ) -> Self {
         let version = common::util::DISPLAY_VERSION_LONG.clone();
         let alpha = format!("Veloren {}", common::util::DISPLAY_VERSION.as_str());
@@ -200,12 +243,16 @@ impl Controls {
             Screen::Disclaimer {
                 screen: disclaimer::Screen::new(),
             }
-        } else { */
+        } else */ if settings.show_setup_wizard {
+            Screen::SetupWizard {
+                screen: Box::new(setup_wizard::Screen::new()),
+            }
+        } else {
             Screen::Login {
                 screen: Box::new(login::Screen::new()),
                 error: None,
-            };
-        //};
+            }
+        };
 
         let server_field_locked = server.is_some();
         let login_info = LoginInfo {
@@ -240,6 +287,9 @@ impl Controls {
             is_selecting_language: false,
             selected_language_index,
 
+            graphics_device_type,
+            audio_available,
+
             time: 0.0,
 
             screen,
@@ -250,6 +300,7 @@ impl Controls {
         &mut self,
         settings: &Settings,
         key_layout: &Option<KeyLayout>,
+        newer_release: Option<&ReleaseInfo>,
         dt: f32,
     ) -> Element<Message> {
         self.time += dt as f64;
@@ -299,10 +350,25 @@ impl Controls {
             Screen::Credits { screen } => {
                 screen.view(&self.fonts, &self.i18n.read(), &self.credits, button_style)
             },
+            Screen::Changelog { screen } => {
+                screen.view(&self.fonts, &self.i18n.read(), newer_release, button_style)
+            },
+            Screen::SetupWizard { screen } => screen.view(
+                &self.fonts,
+                &self.imgs,
+                &self.i18n.read(),
+                &language_metadatas,
+                self.selected_language_index,
+                self.graphics_device_type.clone(),
+                self.audio_available,
+                dt,
+                button_style,
+            ),
             Screen::Login { screen, error } => screen.view(
                 &self.fonts,
                 &self.imgs,
                 self.server_field_locked,
+                settings.interface.streamer_mode,
                 &self.login_info,
                 error.as_deref(),
                 &self.i18n.read(),
@@ -311,12 +377,14 @@ impl Controls {
                 &language_metadatas,
                 button_style,
                 &self.version,
+                newer_release.map(|release| release.version.as_str()),
             ),
             Screen::Servers { screen } => screen.view(
                 &self.fonts,
                 &self.imgs,
                 &settings.networking.servers,
                 self.selected_server_index,
+                settings.interface.streamer_mode,
                 &self.i18n.read(),
                 button_style,
             ),
@@ -378,6 +446,11 @@ impl Controls {
                     screen: credits::Screen::new(),
                 };
             },
+            Message::ShowChangelog => {
+                self.screen = Screen::Changelog {
+                    screen: changelog::Screen::new(),
+                };
+            },
             #[cfg(feature = "singleplayer")]
             Message::Singleplayer => {
                 self.screen = Screen::Connecting {
@@ -436,6 +509,19 @@ impl Controls {
                     }
                 }
             },
+            msg @ Message::OutdatedVersionConnect | msg @ Message::OutdatedVersionCancel => {
+                if let Screen::Connecting {
+                    connection_state, ..
+                } = &mut self.screen
+                {
+                    if let ConnectionState::OutdatedVersionPrompt { .. } = connection_state {
+                        let connect = matches!(msg, Message::OutdatedVersionConnect);
+
+                        *connection_state = ConnectionState::InProgress;
+                        events.push(Event::ConnectWithOutdatedVersion(connect));
+                    }
+                }
+            },
             Message::CloseError => {
                 if let Screen::Login { error, .. } = &mut self.screen {
                     *error = None;
@@ -446,6 +532,51 @@ impl Controls {
                     events.push(Event::DeleteServer { server_index });
                 }
             },
+            Message::SetupNext => {
+                if let Screen::SetupWizard { screen } = &mut self.screen {
+                    if let Some(step) = screen.step.next() {
+                        screen.step = step;
+                    }
+                }
+            },
+            Message::SetupBack => {
+                if let Screen::SetupWizard { screen } = &mut self.screen {
+                    if let Some(step) = screen.step.prev() {
+                        screen.step = step;
+                    }
+                }
+            },
+            Message::SetupPreset(preset) => {
+                if let Screen::SetupWizard { screen } = &mut self.screen {
+                    screen.graphics_preset = Some(preset);
+                }
+            },
+            Message::SetupScheme(scheme) => {
+                if let Screen::SetupWizard { screen } = &mut self.screen {
+                    screen.keybinding_scheme = scheme;
+                }
+            },
+            Message::SetupTestSound => events.push(Event::PlayTestSound),
+            msg @ Message::SetupSkip | msg @ Message::SetupFinish => {
+                if let Screen::SetupWizard { screen } = &self.screen {
+                    events.push(if matches!(msg, Message::SetupFinish) {
+                        Event::SetupWizardFinished {
+                            graphics_preset: screen
+                                .chosen_preset(self.graphics_device_type.clone()),
+                            keybinding_scheme: Some(screen.keybinding_scheme),
+                        }
+                    } else {
+                        Event::SetupWizardFinished {
+                            graphics_preset: None,
+                            keybinding_scheme: None,
+                        }
+                    });
+                    self.screen = Screen::Login {
+                        screen: Box::new(login::Screen::new()),
+                        error: None,
+                    };
+                }
+            },
             /* Note: Keeping in case we re-add the disclaimer */
             /*Message::AcceptDisclaimer => {
                 if let Screen::Disclaimer { .. } = &self.screen {
@@ -486,6 +617,32 @@ impl Controls {
         }
     }
 
+    fn outdated_version_prompt(&mut self, version: &str) {
+        if let Screen::Connecting {
+            connection_state, ..
+        } = &mut self.screen
+        {
+            let msg = self
+                .i18n
+                .read()
+                .get_msg_ctx("main-login-outdated_version_prompt", &i18n::fluent_args! {
+                    "version" => version,
+                })
+                .into_owned();
+
+            *connection_state = ConnectionState::OutdatedVersionPrompt { msg };
+        }
+    }
+
+    fn login_queued(&mut self, position: LoginQueuePosition) {
+        if let Screen::Connecting {
+            connection_state, ..
+        } = &mut self.screen
+        {
+            *connection_state = ConnectionState::Queued(position);
+        }
+    }
+
     fn connection_error(&mut self, error: String) {
         if matches!(&self.screen, Screen::Connecting { .. })
             || matches!(&self.screen, Screen::Login { .. })
@@ -560,6 +717,8 @@ impl MainMenuUi {
             global_state.i18n,
             &global_state.settings,
             server,
+            global_state.window.renderer().graphics_device_type(),
+            global_state.audio.has_output_device(),
         );
 
         Self { ui, controls }
@@ -582,6 +741,14 @@ impl MainMenuUi {
         self.controls.auth_trust_prompt(auth_server);
     }
 
+    pub fn outdated_version_prompt(&mut self, version: &str) {
+        self.controls.outdated_version_prompt(version);
+    }
+
+    pub fn login_queued(&mut self, position: LoginQueuePosition) {
+        self.controls.login_queued(position);
+    }
+
     pub fn show_info(&mut self, msg: String) { self.controls.connection_error(msg); }
 
     pub fn connected(&mut self) { self.controls.exit_connect_screen(); }
@@ -626,10 +793,12 @@ impl MainMenuUi {
     pub fn maintain(&mut self, global_state: &mut GlobalState, dt: Duration) -> Vec<Event> {
         let mut events = Vec::new();
 
+        let newer_release = global_state.update_check.newer_release();
         let (messages, _) = self.ui.maintain(
             self.controls.view(
                 &global_state.settings,
                 &global_state.window.key_layout,
+                newer_release,
                 dt.as_secs_f32(),
             ),
             global_state.window.renderer_mut(),
@@ -3,6 +3,7 @@ use crate::ui::{
     fonts::IcedFonts as Fonts,
     ice::{component::neat_button, style, Element},
 };
+use client::HIDDEN_NAME;
 use i18n::Localization;
 use iced::{
     button, scrollable, Align, Button, Column, Container, Length, Row, Scrollable, Space, Text,
@@ -31,6 +32,7 @@ impl Screen {
         imgs: &Imgs,
         servers: &[impl AsRef<str>],
         selected_server_index: Option<usize>,
+        streamer_mode: bool,
         i18n: &Localization,
         button_style: style::button::Style,
     ) -> Element<Message> {
@@ -91,11 +93,15 @@ impl Screen {
                         state,
                         Row::with_children(vec![
                             Space::new(Length::FillPortion(5), Length::Units(0)).into(),
-                            Text::new(server.as_ref())
-                                .size(fonts.cyri.scale(30))
-                                .width(Length::FillPortion(95))
-                                .vertical_alignment(iced::VerticalAlignment::Center)
-                                .into(),
+                            Text::new(if streamer_mode {
+                                format!("{} {}", i + 1, HIDDEN_NAME)
+                            } else {
+                                server.as_ref().to_string()
+                            })
+                            .size(fonts.cyri.scale(30))
+                            .width(Length::FillPortion(95))
+                            .vertical_alignment(iced::VerticalAlignment::Center)
+                            .into(),
                         ]),
                     )
                     .style(
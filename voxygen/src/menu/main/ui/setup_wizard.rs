@@ -0,0 +1,342 @@
+use super::{Imgs, Message, FILL_FRAC_TWO};
+use crate::{
+    settings::{GraphicsPreset, KeybindingScheme},
+    ui::{
+        fonts::IcedFonts as Fonts,
+        ice::{component::neat_button, style, Element},
+    },
+};
+use i18n::{LanguageMetadata, Localization};
+use iced::{
+    button, scrollable, Align, Button, Column, Container, HorizontalAlignment, Length, Row,
+    Scrollable, Space, Text,
+};
+use strum::IntoEnumIterator;
+use vek::*;
+
+/// How long the main menu is rendered to measure the framerate before a
+/// graphics preset is suggested
+const BENCHMARK_DURATION: f32 = 3.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    Language,
+    Graphics,
+    Audio,
+    Controls,
+}
+
+impl Step {
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Self::Language => Some(Self::Graphics),
+            Self::Graphics => Some(Self::Audio),
+            Self::Audio => Some(Self::Controls),
+            Self::Controls => None,
+        }
+    }
+
+    pub fn prev(self) -> Option<Self> {
+        match self {
+            Self::Language => None,
+            Self::Graphics => Some(Self::Language),
+            Self::Audio => Some(Self::Graphics),
+            Self::Controls => Some(Self::Audio),
+        }
+    }
+}
+
+/// Measures the average framerate of the main menu
+#[derive(Default)]
+struct Benchmark {
+    elapsed: f32,
+    frames: u32,
+}
+
+impl Benchmark {
+    fn done(&self) -> bool { self.elapsed >= BENCHMARK_DURATION }
+
+    fn avg_fps(&self) -> f32 { self.frames as f32 / self.elapsed.max(f32::EPSILON) }
+}
+
+fn option_button<'a>(
+    state: &'a mut button::State,
+    label: String,
+    selected: bool,
+    message: Message,
+    fonts: &Fonts,
+    imgs: &Imgs,
+) -> Element<'a, Message> {
+    let color = if selected {
+        (97, 255, 18)
+    } else {
+        (97, 97, 25)
+    };
+    Button::new(
+        state,
+        Text::new(label)
+            .size(fonts.cyri.scale(22))
+            .width(Length::Fill)
+            .horizontal_alignment(HorizontalAlignment::Center),
+    )
+    .style(
+        style::button::Style::new(imgs.selection)
+            .hover_image(imgs.selection_hover)
+            .press_image(imgs.selection_press)
+            .image_color(Rgba::new(color.0, color.1, color.2, 192)),
+    )
+    .width(Length::Fill)
+    .min_height(46)
+    .on_press(message)
+    .into()
+}
+
+/// First launch setup wizard for the main menu
+pub struct Screen {
+    pub step: Step,
+    benchmark: Benchmark,
+    /// Preset picked by the player, falls back to the suggested one
+    pub graphics_preset: Option<GraphicsPreset>,
+    pub keybinding_scheme: KeybindingScheme,
+
+    language_buttons: Vec<button::State>,
+    language_list: scrollable::State,
+    preset_buttons: Vec<button::State>,
+    scheme_buttons: [button::State; KeybindingScheme::ALL.len()],
+    test_sound_button: button::State,
+    back_button: button::State,
+    skip_button: button::State,
+    next_button: button::State,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Self {
+            step: Step::Language,
+            benchmark: Benchmark::default(),
+            graphics_preset: None,
+            keybinding_scheme: KeybindingScheme::Default,
+
+            language_buttons: Vec::new(),
+            language_list: Default::default(),
+            preset_buttons: vec![Default::default(); GraphicsPreset::iter().count()],
+            scheme_buttons: Default::default(),
+            test_sound_button: Default::default(),
+            back_button: Default::default(),
+            skip_button: Default::default(),
+            next_button: Default::default(),
+        }
+    }
+
+    /// The preset to apply once the wizard is finished, if the benchmark got
+    /// far enough to make a suggestion
+    pub fn chosen_preset(&self, device_type: wgpu::DeviceType) -> Option<GraphicsPreset> {
+        self.graphics_preset.or_else(|| {
+            self.benchmark
+                .done()
+                .then(|| GraphicsPreset::suggest(device_type, self.benchmark.avg_fps()))
+        })
+    }
+
+    pub(super) fn view(
+        &mut self,
+        fonts: &Fonts,
+        imgs: &Imgs,
+        i18n: &Localization,
+        language_metadatas: &[LanguageMetadata],
+        selected_language_index: Option<usize>,
+        device_type: wgpu::DeviceType,
+        audio_available: bool,
+        dt: f32,
+        button_style: style::button::Style,
+    ) -> Element<Message> {
+        let (title_key, body): (_, Element<Message>) = match self.step {
+            Step::Language => {
+                if self.language_buttons.len() != language_metadatas.len() {
+                    self.language_buttons = vec![Default::default(); language_metadatas.len()];
+                }
+                let list = self
+                    .language_buttons
+                    .iter_mut()
+                    .zip(language_metadatas)
+                    .enumerate()
+                    .fold(
+                        Scrollable::new(&mut self.language_list).spacing(8),
+                        |list, (i, (state, lang))| {
+                            list.push(option_button(
+                                state,
+                                lang.language_name.clone(),
+                                Some(i) == selected_language_index,
+                                Message::LanguageChanged(i),
+                                fonts,
+                                imgs,
+                            ))
+                        },
+                    )
+                    .height(Length::Fill);
+                ("main-setup-step_language", list.into())
+            },
+            Step::Graphics => {
+                let status = if self.benchmark.done() {
+                    let suggested =
+                        GraphicsPreset::suggest(device_type.clone(), self.benchmark.avg_fps());
+                    i18n.get_msg_ctx("main-setup-benchmark_result", &i18n::fluent_args! {
+                        "fps" => format!("{:.0}", self.benchmark.avg_fps()),
+                        "device" => format!("{:?}", device_type),
+                        "preset" => i18n.get_msg(suggested.i18n_key()).into_owned(),
+                    })
+                    .into_owned()
+                } else {
+                    self.benchmark.elapsed += dt;
+                    self.benchmark.frames += 1;
+                    i18n.get_msg("main-setup-benchmarking").into_owned()
+                };
+                let chosen = self.chosen_preset(device_type);
+                let presets = self
+                    .preset_buttons
+                    .iter_mut()
+                    .zip(GraphicsPreset::iter())
+                    .map(|(state, preset)| {
+                        option_button(
+                            state,
+                            i18n.get_msg(preset.i18n_key()).into_owned(),
+                            chosen == Some(preset),
+                            Message::SetupPreset(preset),
+                            fonts,
+                            imgs,
+                        )
+                    });
+                (
+                    "main-setup-step_graphics",
+                    Column::with_children(
+                        core::iter::once(Text::new(status).size(fonts.cyri.scale(20)).into())
+                            .chain(presets)
+                            .collect(),
+                    )
+                    .spacing(8)
+                    .into(),
+                )
+            },
+            Step::Audio => {
+                let status = if audio_available {
+                    i18n.get_msg("main-setup-audio_found")
+                } else {
+                    i18n.get_msg("main-setup-audio_missing")
+                };
+                let mut children = vec![Text::new(status).size(fonts.cyri.scale(20)).into()];
+                if audio_available {
+                    children.push(neat_button(
+                        &mut self.test_sound_button,
+                        i18n.get_msg("main-setup-test_sound"),
+                        FILL_FRAC_TWO,
+                        button_style,
+                        Some(Message::SetupTestSound),
+                    ));
+                }
+                (
+                    "main-setup-step_audio",
+                    Column::with_children(children).spacing(8).into(),
+                )
+            },
+            Step::Controls => {
+                let chosen = self.keybinding_scheme;
+                let schemes = self
+                    .scheme_buttons
+                    .iter_mut()
+                    .zip(KeybindingScheme::ALL)
+                    .map(|(state, scheme)| {
+                        option_button(
+                            state,
+                            i18n.get_msg(scheme.i18n_key()).into_owned(),
+                            chosen == scheme,
+                            Message::SetupScheme(scheme),
+                            fonts,
+                            imgs,
+                        )
+                    });
+                (
+                    "main-setup-step_controls",
+                    Column::with_children(schemes.collect()).spacing(8).into(),
+                )
+            },
+        };
+
+        let next_key = if self.step.next().is_some() {
+            "main-setup-next"
+        } else {
+            "main-setup-finish"
+        };
+        let navigation = Row::with_children(vec![
+            neat_button(
+                &mut self.back_button,
+                i18n.get_msg("common-back"),
+                FILL_FRAC_TWO,
+                button_style,
+                self.step.prev().map(|_| Message::SetupBack),
+            ),
+            neat_button(
+                &mut self.skip_button,
+                i18n.get_msg("main-setup-skip"),
+                FILL_FRAC_TWO,
+                button_style,
+                Some(Message::SetupSkip),
+            ),
+            neat_button(
+                &mut self.next_button,
+                i18n.get_msg(next_key),
+                FILL_FRAC_TWO,
+                button_style,
+                Some(if self.step.next().is_some() {
+                    Message::SetupNext
+                } else {
+                    Message::SetupFinish
+                }),
+            ),
+        ])
+        .spacing(10)
+        .height(Length::Units(fonts.cyri.scale(50)));
+
+        Container::new(
+            Container::new(
+                Column::with_children(vec![
+                    Text::new(i18n.get_msg("main-setup"))
+                        .font(fonts.alkhemi.id)
+                        .size(fonts.alkhemi.scale(35))
+                        .width(Length::Fill)
+                        .horizontal_alignment(HorizontalAlignment::Center)
+                        .into(),
+                    Text::new(i18n.get_msg(title_key))
+                        .size(fonts.cyri.scale(25))
+                        .width(Length::Fill)
+                        .horizontal_alignment(HorizontalAlignment::Center)
+                        .into(),
+                    Space::new(Length::Fill, Length::Units(15)).into(),
+                    Container::new(body)
+                        .height(Length::Fill)
+                        .width(Length::Fill)
+                        .into(),
+                    navigation.into(),
+                ])
+                .spacing(5)
+                .padding(20)
+                .align_items(Align::Center)
+                .width(Length::Fill)
+                .height(Length::Fill),
+            )
+            .style(
+                style::container::Style::color_with_double_cornerless_border(
+                    (22, 19, 17, 255).into(),
+                    (11, 11, 11, 255).into(),
+                    (54, 46, 38, 255).into(),
+                ),
+            )
+            .max_width(600),
+        )
+        .center_x()
+        .center_y()
+        .padding(70)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
@@ -9,7 +9,8 @@ use crate::{
     scene::terrain::BlocksOfInterest,
 };
 use common::{
-    terrain::Block,
+    terrain::{Block, BlockKind, FlowDir},
+    time::SeasonState,
     util::either_with,
     vol::{ReadVol, RectRasterableVol},
     volumes::vol_grid_2d::{CachedVolGrid2d, VolGrid2d},
@@ -25,14 +26,60 @@ enum FaceKind {
     /// water (Opaque(true)) or something else (Opaque(false)).
     Opaque(bool),
     /// Fluid face that is facing something non-opaque, non-tangible,
-    /// and non-fluid (most likely air).
-    Fluid,
+    /// and non-fluid (most likely air), along with which way the fluid flows.
+    Fluid(Option<FlowDir>),
 }
 
 pub const SUNLIGHT: u8 = 24;
 pub const SUNLIGHT_INV: f32 = 1.0 / SUNLIGHT as f32;
 pub const MAX_LIGHT_DIST: i32 = SUNLIGHT as i32;
 
+/// Colours broadleaf foliage turns to in autumn
+const AUTUMN_LEAVES: [(u8, u8, u8); 4] =
+    [(216, 185, 32), (142, 164, 0), (168, 81, 0), (205, 85, 15)];
+/// Colour of the last leaves hanging on in winter
+const WINTER_LEAVES: (u8, u8, u8) = (96, 72, 44);
+/// Colours temperate grass dries out to in autumn and winter
+const AUTUMN_GRASS: (u8, u8, u8) = (140, 115, 13);
+const WINTER_GRASS: (u8, u8, u8) = (77, 71, 31);
+
+/// Colour of vegetation at this point of the year. This is done when meshing
+/// rather than when generating the terrain, so that chunks generated or
+/// loaded at different times of the year match.
+fn season_color(block: Block, wpos: Vec3<i32>, season: SeasonState, col: Rgb<u8>) -> Rgb<u8> {
+    let lerp = |col: Rgb<u8>, (r, g, b): (u8, u8, u8), factor: f32| {
+        Rgb::lerp(col.map(f32::from), Rgb::new(r, g, b).map(f32::from), factor).map(|e| e as u8)
+    };
+    match block.kind() {
+        // Broadleaf foliage is told apart from the bluish needles of conifers by its colour
+        BlockKind::Leaves if col.g > col.b.saturating_add(50) => {
+            // Trees turn patch by patch rather than block by block
+            let cell = wpos.map(|e| e.div_euclid(5) as u32);
+            let mut hash = cell.x.wrapping_mul(0x8DA6_B343)
+                ^ cell.y.wrapping_mul(0xD816_3841)
+                ^ cell.z.wrapping_mul(0xCB1A_B31F);
+            hash ^= hash >> 15;
+            hash = hash.wrapping_mul(0x2C1B_3C6D);
+            hash ^= hash >> 12;
+            let col = if season.foliage_turning() > (hash % 256) as f32 / 256.0 {
+                lerp(
+                    col,
+                    AUTUMN_LEAVES[(hash >> 8) as usize % AUTUMN_LEAVES.len()],
+                    0.85,
+                )
+            } else {
+                col
+            };
+            lerp(col, WINTER_LEAVES, (season.coldness() - 0.5).max(0.0) * 1.4)
+        },
+        BlockKind::Grass => {
+            let col = lerp(col, AUTUMN_GRASS, season.foliage_turning() * 0.35);
+            lerp(col, WINTER_GRASS, (season.coldness() - 0.5).max(0.0) * 0.8)
+        },
+        _ => col,
+    }
+}
+
 fn calc_light<V: RectRasterableVol<Vox = Block> + ReadVol + Debug>(
     is_sunlight: bool,
     // When above bounds
@@ -228,7 +275,12 @@ fn calc_light<V: RectRasterableVol<Vox = Block> + ReadVol + Debug>(
 #[allow(clippy::type_complexity)]
 pub fn generate_mesh<'a, V: RectRasterableVol<Vox = Block> + ReadVol + Debug + 'static>(
     vol: &'a VolGrid2d<V>,
-    (range, max_texture_size, _boi): (Aabb<i32>, Vec2<u16>, &'a BlocksOfInterest),
+    (range, max_texture_size, _boi, season): (
+        Aabb<i32>,
+        Vec2<u16>,
+        &'a BlocksOfInterest,
+        SeasonState,
+    ),
 ) -> MeshGen<
     TerrainVertex,
     FluidVertex,
@@ -380,8 +432,12 @@ pub fn generate_mesh<'a, V: RectRasterableVol<Vox = Block> + ReadVol + Debug + '
         if flat_get(pos).is_opaque() { 0.0 } else { 1.0 }
     };
     let get_glow = |_: &mut (), pos: Vec3<i32>| glow(pos + range.min);
-    let get_color =
-        |_: &mut (), pos: Vec3<i32>| flat_get(pos).get_color().unwrap_or_else(Rgb::zero);
+    let get_color = |_: &mut (), pos: Vec3<i32>| {
+        let block = flat_get(pos);
+        block.get_color().map_or_else(Rgb::zero, |col| {
+            season_color(block, pos + range.min, season, col)
+        })
+    };
     let get_opacity = |_: &mut (), pos: Vec3<i32>| !flat_get(pos).is_opaque();
     let should_draw = |_: &mut (), pos: Vec3<i32>, delta: Vec3<i32>, _uv| {
         should_draw_greedy(pos, delta, &flat_get)
@@ -390,7 +446,8 @@ pub fn generate_mesh<'a, V: RectRasterableVol<Vox = Block> + ReadVol + Debug + '
     let mesh_delta = Vec3::new(0.0, 0.0, (z_start + range.min.z) as f32);
     let create_opaque =
         |atlas_pos, pos, norm, meta| TerrainVertex::new(atlas_pos, pos + mesh_delta, norm, meta);
-    let create_transparent = |_atlas_pos, pos, norm| FluidVertex::new(pos + mesh_delta, norm);
+    let create_transparent =
+        |_atlas_pos, pos, norm, flow| FluidVertex::new(pos + mesh_delta, norm, flow);
 
     let mut greedy =
         GreedyMesh::<guillotiere::SimpleAtlasAllocator>::new(max_size, greedy::general_config());
@@ -418,15 +475,15 @@ pub fn generate_mesh<'a, V: RectRasterableVol<Vox = Block> + ReadVol + Debug + '
                     |atlas_pos, pos, norm, &meta| create_opaque(atlas_pos, pos, norm, meta),
                 ));
             },
-            FaceKind::Fluid => {
+            FaceKind::Fluid(flow) => {
                 fluid_mesh.push_quad(greedy::create_quad(
                     atlas_origin,
                     dim,
                     origin,
                     draw_dim,
                     norm,
-                    &(),
-                    |atlas_pos, pos, norm, &_meta| create_transparent(atlas_pos, pos, norm),
+                    flow,
+                    |atlas_pos, pos, norm, &flow| create_transparent(atlas_pos, pos, norm, flow),
                 ));
             },
         },
@@ -475,7 +532,11 @@ fn should_draw_greedy(
             // While liquid is not culled, we still try to keep a consistent orientation as
             // we do for land; if going from liquid to non-liquid,
             // forwards-facing; otherwise, backwards-facing.
-            Some((from_liquid, FaceKind::Fluid))
+            let liquid = if from_liquid { from } else { to };
+            Some((
+                from_liquid,
+                FaceKind::Fluid(liquid.get_flow().map(|flow| flow.dir)),
+            ))
         }
     } else {
         // If going from unfilled to filled, backward facing; otherwise, forward
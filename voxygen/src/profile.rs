@@ -1,5 +1,5 @@
 use crate::hud;
-use common::{character::CharacterId, uuid::Uuid};
+use common::{character::CharacterId, slowjob::SlowJobPool, uuid::Uuid};
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -15,6 +15,11 @@ use tracing::warn;
 pub struct CharacterProfile {
     /// Array representing a character's hotbar.
     pub hotbar_slots: [Option<hud::HotbarSlotContents>; 10],
+    /// Parts of the world the character explored, the rest of the map is
+    /// covered by fog.
+    pub explored: hud::ExploredMap,
+    /// Named markers the character placed on the map.
+    pub placed_markers: hud::PlacedMarkers,
 }
 
 const fn default_slots() -> [Option<hud::HotbarSlotContents>; 10] {
@@ -25,6 +30,8 @@ impl Default for CharacterProfile {
     fn default() -> Self {
         CharacterProfile {
             hotbar_slots: default_slots(),
+            explored: hud::ExploredMap::default(),
+            placed_markers: hud::PlacedMarkers::default(),
         }
     }
 }
@@ -104,6 +111,16 @@ impl Profile {
         }
     }
 
+    /// Save the profile from a slow job, to not hold up the frame with
+    /// serializing and writing it.
+    pub fn save_in_background(&self, config_dir: &Path, slow_jobs: &SlowJobPool) {
+        let profile = self.clone();
+        let config_dir = config_dir.to_path_buf();
+        slow_jobs.spawn("PROFILE_SAVING", move || {
+            profile.save_to_file_warn(&config_dir)
+        });
+    }
+
     /// Get the hotbar_slots for the requested character_id.
     ///
     /// If the server or character does not exist then the default hotbar_slots
@@ -160,6 +177,118 @@ impl Profile {
         .hotbar_slots = slots;
     }
 
+    /// Get the parts of the world explored by the requested character_id.
+    ///
+    /// If the server or character does not exist then nothing was explored.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    pub fn get_explored_map(
+        &self,
+        server: &str,
+        character_id: Option<CharacterId>,
+    ) -> hud::ExploredMap {
+        match character_id {
+            Some(character_id) => self
+                .servers
+                .get(server)
+                .and_then(|s| s.characters.get(&character_id)),
+            None => self.transient_character.as_ref(),
+        }
+        .map(|c| c.explored.clone())
+        .unwrap_or_default()
+    }
+
+    /// Add cells to the parts of the world explored by the requested
+    /// character_id.
+    ///
+    /// If the server or character does not exist then the appropriate fields
+    /// will be initialised and the cells added.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    /// * cells - cells of the world the character just explored.
+    pub fn add_explored_cells(
+        &mut self,
+        server: &str,
+        character_id: Option<CharacterId>,
+        cells: Vec<vek::Vec2<i32>>,
+    ) {
+        match character_id {
+            Some(character_id) => self
+                .servers
+                .entry(server.to_string())
+                .or_insert(ServerProfile::default())
+                .characters
+                .entry(character_id)
+                .or_default(),
+            None => self.transient_character.get_or_insert_default(),
+        }
+        .explored
+        .extend(cells);
+    }
+
+    /// Get the named markers placed by the requested character_id.
+    ///
+    /// If the server or character does not exist then no markers were placed.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    pub fn get_placed_markers(
+        &self,
+        server: &str,
+        character_id: Option<CharacterId>,
+    ) -> hud::PlacedMarkers {
+        match character_id {
+            Some(character_id) => self
+                .servers
+                .get(server)
+                .and_then(|s| s.characters.get(&character_id)),
+            None => self.transient_character.as_ref(),
+        }
+        .map(|c| c.placed_markers.clone())
+        .unwrap_or_default()
+    }
+
+    /// Set the named markers placed by the requested character_id.
+    ///
+    /// If the server or character does not exist then the appropriate fields
+    /// will be initialised and the markers added.
+    ///
+    /// # Arguments
+    ///
+    /// * server - current server the character is on.
+    /// * character_id - id of the character, passing `None` indicates the
+    ///   transient character profile should be used.
+    /// * placed_markers - named markers placed by the character.
+    pub fn set_placed_markers(
+        &mut self,
+        server: &str,
+        character_id: Option<CharacterId>,
+        placed_markers: hud::PlacedMarkers,
+    ) {
+        match character_id {
+            Some(character_id) => self
+                .servers
+                .entry(server.to_string())
+                .or_insert(ServerProfile::default())
+                .characters
+                .entry(character_id)
+                .or_default(),
+            None => self.transient_character.get_or_insert_default(),
+        }
+        .placed_markers = placed_markers;
+    }
+
     /// Get the selected_character for the provided server.
     ///
     /// if the server does not exist then the default selected_character (None)
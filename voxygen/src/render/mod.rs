@@ -26,10 +26,11 @@ pub use self::{
             Locals as FigureLocals,
         },
         fluid::Vertex as FluidVertex,
+        ghost::{BoundLocals as GhostBoundLocals, Locals as GhostLocals, Vertex as GhostVertex},
         lod_object::{Instance as LodObjectInstance, Vertex as LodObjectVertex},
         lod_terrain::{LodData, Vertex as LodTerrainVertex},
         particle::{Instance as ParticleInstance, Vertex as ParticleVertex},
-        postprocess::Locals as PostProcessLocals,
+        postprocess::{identity_lut, Locals as PostProcessLocals, LUT_SIZE},
         rain_occlusion::Locals as RainOcclusionLocals,
         shadow::{Locals as ShadowLocals, PointLightMatrix},
         skybox::{create_mesh as create_skybox_mesh, Vertex as SkyboxVertex},
@@ -49,9 +50,10 @@ pub use self::{
     },
     renderer::{
         drawer::{
-            DebugDrawer, Drawer, FigureDrawer, FigureShadowDrawer, FirstPassDrawer, ParticleDrawer,
-            PreparedUiDrawer, SecondPassDrawer, ShadowPassDrawer, SpriteDrawer, TerrainDrawer,
-            TerrainShadowDrawer, ThirdPassDrawer, TrailDrawer, UiDrawer,
+            DebugDrawer, Drawer, FigureDrawer, FigureShadowDrawer, FirstPassDrawer,
+            FluidPassDrawer, GhostDrawer, ParticleDrawer, PreparedUiDrawer, SecondPassDrawer,
+            ShadowPassDrawer, SpriteDrawer, TerrainDrawer, TerrainShadowDrawer, ThirdPassDrawer,
+            TrailDrawer, UiDrawer,
         },
         ColLightInfo, Renderer,
     },
@@ -177,6 +179,10 @@ pub enum LightingMode {
     /// significant on low-end machines that are bottlenecked on fragment
     /// shading.
     Lambertian,
+    /// Flat shading: each face is lit evenly by the sun and the ambient light,
+    /// without point lights.  Meant for the weakest machines, which can't keep
+    /// up with any of the other models.
+    Flat,
     /// Standard Blinn-Phong shading, combing Lambertian diffuse reflections and
     /// specular highlights.
     #[serde(other)]
@@ -189,15 +195,37 @@ impl Default for LightingMode {
 
 /// Shadow map settings.
 #[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ShadowMapMode {
     /// Multiple of default resolution (default, which is 1.0, is currently
     /// the closest higher power of two above the length of the longest
     /// diagonal of the screen resolution, but this may change).
     pub resolution: f32,
+    /// Whether the edges of the sun shadows are softened by sampling the
+    /// neighbouring texels of the shadow map too (percentage-closer
+    /// filtering), which costs a few more texture lookups per pixel.
+    pub soft: bool,
+    /// Number of point lights casting shadows, picked every frame among the
+    /// strongest ones around the camera.  Each one renders the terrain six
+    /// more times, so it is capped at `MAX_POINT_SHADOWS`.
+    pub point_lights: u32,
 }
 
+/// Maximum number of point lights casting shadows
+pub const MAX_POINT_SHADOWS: u32 = 4;
+
 impl Default for ShadowMapMode {
-    fn default() -> Self { Self { resolution: 1.0 } }
+    fn default() -> Self {
+        Self {
+            resolution: 1.0,
+            soft: false,
+            point_lights: 1,
+        }
+    }
+}
+
+impl ShadowMapMode {
+    pub fn point_lights(&self) -> u32 { self.point_lights.min(MAX_POINT_SHADOWS) }
 }
 
 /// Shadow modes
@@ -335,6 +363,80 @@ impl BloomMode {
     fn is_on(&self) -> bool { matches!(self, BloomMode::On(_)) }
 }
 
+/// Screen-space ambient occlusion settings.
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AmbientOcclusionMode {
+    /// Whether creases, corners and interiors are darkened by the geometry
+    /// around them, found in the depth buffer during the first postprocessing
+    /// pass.
+    pub enabled: bool,
+    /// Depth samples taken around each pixel, more samples giving less noisy
+    /// occlusion at a higher cost.  Between 4 and 32.
+    pub samples: u32,
+}
+
+impl Default for AmbientOcclusionMode {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            samples: 8,
+        }
+    }
+}
+
+impl AmbientOcclusionMode {
+    pub fn samples(&self) -> u32 { self.samples.clamp(4, 32) }
+}
+
+/// Volumetric fog settings.
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VolumetricFogMode {
+    /// Whether fog thickening in valleys and wet places is ray-marched along
+    /// the view, with the sunlight shining through the gaps between the
+    /// terrain as crepuscular rays, during the first postprocessing pass.
+    pub enabled: bool,
+    /// Steps taken along the view and towards the sun for each pixel, more
+    /// steps giving smoother fog and sharper rays at a higher cost.  Between 4
+    /// and 32.
+    pub steps: u32,
+}
+
+impl Default for VolumetricFogMode {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            steps: 12,
+        }
+    }
+}
+
+impl VolumetricFogMode {
+    pub fn steps(&self) -> u32 { self.steps.clamp(4, 32) }
+}
+
+/// Tonemapping operators, mapping the HDR colours of the scene (and of the
+/// bloom added to it) to the displayable range in the final pass.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum TonemapMode {
+    /// Reinhard operator, applied to the luminance so that the hue of bright
+    /// lights is kept.  Rolls off highlights gently, at the cost of some
+    /// contrast.
+    Reinhard,
+    /// Filmic curve fitted to the ACES reference rendering transform.  Gives
+    /// the most contrast, and lets very bright lights, lava and spells wash out
+    /// to white like they would on film.
+    Aces,
+    /// Exponential operator, the one Veloren always used.
+    #[serde(other)]
+    Exponential,
+}
+
+impl Default for TonemapMode {
+    fn default() -> Self { TonemapMode::Exponential }
+}
+
 /// Render modes
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 #[serde(default)]
@@ -348,6 +450,12 @@ pub struct RenderMode {
     pub bloom: BloomMode,
     /// 0.0..1.0
     pub point_glow: f32,
+    pub ambient_occlusion: AmbientOcclusionMode,
+    pub volumetric_fog: VolumetricFogMode,
+    /// Whether the shiny fluids reflect the terrain and the figures around them
+    /// by ray-marching the opaque scene on the screen, instead of only the sky.
+    pub screen_space_reflections: bool,
+    pub tonemap: TonemapMode,
 
     pub flashing_lights_enabled: bool,
 
@@ -369,6 +477,10 @@ impl Default for RenderMode {
             rain_occlusion: ShadowMapMode::default(),
             bloom: BloomMode::default(),
             point_glow: 0.35,
+            ambient_occlusion: AmbientOcclusionMode::default(),
+            volumetric_fog: VolumetricFogMode::default(),
+            screen_space_reflections: false,
+            tonemap: TonemapMode::default(),
             flashing_lights_enabled: true,
             experimental_shaders: HashSet::default(),
             upscale_mode: UpscaleMode::default(),
@@ -390,6 +502,10 @@ impl RenderMode {
                 rain_occlusion: self.rain_occlusion,
                 bloom: self.bloom,
                 point_glow: self.point_glow,
+                ambient_occlusion: self.ambient_occlusion,
+                volumetric_fog: self.volumetric_fog,
+                screen_space_reflections: self.screen_space_reflections,
+                tonemap: self.tonemap,
                 flashing_lights_enabled: self.flashing_lights_enabled,
                 experimental_shaders: self.experimental_shaders,
             },
@@ -409,15 +525,37 @@ pub struct PipelineModes {
     aa: AaMode,
     pub cloud: CloudMode,
     fluid: FluidMode,
-    lighting: LightingMode,
+    pub lighting: LightingMode,
     pub shadow: ShadowMode,
     pub rain_occlusion: ShadowMapMode,
     bloom: BloomMode,
     point_glow: f32,
+    ambient_occlusion: AmbientOcclusionMode,
+    volumetric_fog: VolumetricFogMode,
+    screen_space_reflections: bool,
+    tonemap: TonemapMode,
     flashing_lights_enabled: bool,
     experimental_shaders: HashSet<ExperimentalShader>,
 }
 
+impl PipelineModes {
+    /// Whether the fluids reflect the opaque scene, which only the shiny ones
+    /// do
+    fn screen_space_reflections(&self) -> bool {
+        self.screen_space_reflections && self.fluid == FluidMode::Shiny
+    }
+
+    /// Only the first point light can cast shadows when the device can't
+    /// sample cube map arrays, since its shadows then take a single cube
+    fn limit_point_shadows(&mut self, cube_array_textures: bool) {
+        if let ShadowMode::Map(mode) = &mut self.shadow {
+            if !cube_array_textures {
+                mode.point_lights = mode.point_lights.min(1);
+            }
+        }
+    }
+}
+
 /// Other render modes that don't effect pipelines
 #[derive(PartialEq, Clone, Debug)]
 struct OtherModes {
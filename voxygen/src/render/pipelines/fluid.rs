@@ -1,5 +1,6 @@
 use super::super::{AaMode, GlobalsLayouts, TerrainLayout, Vertex as VertexTrait};
 use bytemuck::{Pod, Zeroable};
+use common::terrain::FlowDir;
 use std::mem;
 use vek::*;
 
@@ -7,10 +8,13 @@ use vek::*;
 #[derive(Copy, Clone, Debug, Zeroable, Pod)]
 pub struct Vertex {
     pos_norm: u32,
+    /// Which way the liquid flows, 0 if it is still and 1 more than the
+    /// direction otherwise
+    flow: u32,
 }
 
 impl Vertex {
-    pub fn new(pos: Vec3<f32>, norm: Vec3<f32>) -> Self {
+    pub fn new(pos: Vec3<f32>, norm: Vec3<f32>, flow: Option<FlowDir>) -> Self {
         let (norm_axis, norm_dir) = norm
             .as_slice()
             .iter()
@@ -27,11 +31,13 @@ impl Vertex {
                 | ((pos.y as u32) & 0x003F) << 6
                 | (((pos.z + EXTRA_NEG_Z).max(0.0).min((1 << 17) as f32) as u32) & 0x1FFFF) << 12
                 | (norm_bits & 0x7) << 29,
+            flow: flow.map_or(0, |dir| dir as u32 + 1),
         }
     }
 
     fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
-        const ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Uint32];
+        const ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+            wgpu::vertex_attr_array![0 => Uint32, 1 => Uint32];
         wgpu::VertexBufferLayout {
             array_stride: Self::STRIDE,
             step_mode: wgpu::InputStepMode::Vertex,
@@ -45,6 +51,102 @@ impl VertexTrait for Vertex {
     const STRIDE: wgpu::BufferAddress = mem::size_of::<Self>() as wgpu::BufferAddress;
 }
 
+pub struct BindGroup {
+    pub(in super::super) bind_group: wgpu::BindGroup,
+}
+
+/// Layout of the copies of the opaque scene the fluids reflect with the
+/// screen-space reflections
+pub struct ReflectionsLayout {
+    pub layout: wgpu::BindGroupLayout,
+}
+
+impl ReflectionsLayout {
+    pub fn new(device: &wgpu::Device) -> Self {
+        Self {
+            layout: device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[
+                    // Color of the opaque scene
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler {
+                            filtering: true,
+                            comparison: false,
+                        },
+                        count: None,
+                    },
+                    // Depth of the opaque scene
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler {
+                            filtering: true,
+                            comparison: false,
+                        },
+                        count: None,
+                    },
+                ],
+            }),
+        }
+    }
+
+    pub fn bind(
+        &self,
+        device: &wgpu::Device,
+        opaque_color: &wgpu::TextureView,
+        opaque_depth: &wgpu::TextureView,
+        sampler: &wgpu::Sampler,
+        depth_sampler: &wgpu::Sampler,
+    ) -> BindGroup {
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(opaque_color),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(opaque_depth),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Sampler(depth_sampler),
+                },
+            ],
+        });
+
+        BindGroup { bind_group }
+    }
+}
+
 pub struct FluidPipeline {
     pub pipeline: wgpu::RenderPipeline,
 }
@@ -56,18 +158,25 @@ impl FluidPipeline {
         fs_module: &wgpu::ShaderModule,
         global_layout: &GlobalsLayouts,
         terrain_layout: &TerrainLayout,
+        reflections_layout: &ReflectionsLayout,
         aa_mode: AaMode,
+        screen_space_reflections: bool,
     ) -> Self {
         common_base::span!(_guard, "FluidPipeline::new");
+        let mut bind_group_layouts = vec![
+            &global_layout.globals,
+            &global_layout.shadow_textures,
+            &terrain_layout.locals,
+        ];
+        // The opaque scene is only copied for the fluids when they reflect it
+        if screen_space_reflections {
+            bind_group_layouts.push(&reflections_layout.layout);
+        }
         let render_pipeline_layout =
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 label: Some("Fluid pipeline layout"),
                 push_constant_ranges: &[],
-                bind_group_layouts: &[
-                    &global_layout.globals,
-                    &global_layout.shadow_textures,
-                    &terrain_layout.locals,
-                ],
+                bind_group_layouts: &bind_group_layouts,
             });
 
         let samples = match aa_mode {
@@ -4,6 +4,7 @@ pub mod clouds;
 pub mod debug;
 pub mod figure;
 pub mod fluid;
+pub mod ghost;
 pub mod lod_object;
 pub mod lod_terrain;
 pub mod particle;
@@ -65,11 +66,14 @@ pub struct Globals {
     select_pos: [i32; 4],
     gamma_exposure: [f32; 4],
     last_lightning: [f32; 4],
+    /// Wind velocity at the camera, in blocks / second
+    wind_vel: [f32; 4],
     ambiance: f32,
     cam_mode: u32,
     sprite_render_distance: f32,
-    // To keep 16-byte-aligned.
-    globals_dummy: [f32; 1],
+    /// Share of the decorative sprites which are rendered, the others are
+    /// thinned out by their density rank
+    sprite_density: f32,
 }
 /// Make sure Globals is 16-byte-aligned.
 const _: () = assert!(core::mem::size_of::<Globals>() % 16 == 0);
@@ -110,9 +114,11 @@ impl Globals {
         gamma: f32,
         exposure: f32,
         last_lightning: (Vec3<f32>, f64),
+        wind_vel: Vec2<f32>,
         ambiance: f32,
         cam_mode: CameraMode,
         sprite_render_distance: f32,
+        sprite_density: f32,
     ) -> Self {
         Self {
             view_mat: view_mat.into_col_arrays(),
@@ -162,10 +168,11 @@ impl Globals {
                 .0
                 .with_w(last_lightning.1 as f32)
                 .into_array(),
+            wind_vel: [wind_vel.x, wind_vel.y, 0.0, 0.0],
             ambiance: ambiance.clamped(0.0, 1.0),
             cam_mode: cam_mode as u32,
             sprite_render_distance,
-            globals_dummy: [0.0; 1],
+            sprite_density: sprite_density.clamped(0.0, 1.0),
         }
     }
 
@@ -212,6 +219,7 @@ impl Default for Globals {
             1.0,
             CameraMode::ThirdPerson,
             250.0,
+            1.0,
         )
     }
 }
@@ -226,6 +234,8 @@ impl Light {
 
     pub fn get_pos(&self) -> Vec3<f32> { Vec3::new(self.pos[0], self.pos[1], self.pos[2]) }
 
+    pub fn get_strength(&self) -> f32 { self.col[0] + self.col[1] + self.col[2] }
+
     #[must_use]
     pub fn with_strength(mut self, strength: f32) -> Self {
         self.col = (Vec4::<f32>::from(self.col) * strength).into_array();
@@ -446,7 +456,8 @@ impl GlobalsLayouts {
         ]
     }
 
-    pub fn new(device: &wgpu::Device) -> Self {
+    /// Without `cube_array_textures` the point shadow maps are a single cube
+    pub fn new(device: &wgpu::Device, cube_array_textures: bool) -> Self {
         let globals = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Globals layout"),
             entries: &Self::base_globals_layout(),
@@ -487,7 +498,11 @@ impl GlobalsLayouts {
                     visibility: wgpu::ShaderStage::VERTEX | wgpu::ShaderStage::FRAGMENT,
                     ty: wgpu::BindingType::Texture {
                         sample_type: wgpu::TextureSampleType::Depth,
-                        view_dimension: wgpu::TextureViewDimension::Cube,
+                        view_dimension: if cube_array_textures {
+                            wgpu::TextureViewDimension::CubeArray
+                        } else {
+                            wgpu::TextureViewDimension::Cube
+                        },
                         multisampled: false,
                     },
                     count: None,
@@ -90,6 +90,11 @@ pub enum ParticleMode {
     BlackSmoke = 37,
     Lightning = 38,
     BarrelOrgan = 39,
+    HitSpark = 40,
+    LevelUp = 41,
+    FootstepDust = 42,
+    Rope = 43,
+    WorldBorder = 44,
 }
 
 impl ParticleMode {
@@ -2,11 +2,31 @@ use super::super::{Consts, GlobalsLayouts, PipelineModes};
 use bytemuck::{Pod, Zeroable};
 use vek::*;
 
+/// Levels of each channel in the colour grading lookup table. The table is a
+/// `LUT_SIZE * LUT_SIZE` by `LUT_SIZE` texture of sRGB colours, made of
+/// `LUT_SIZE` square slices side by side, each for a level of blue. Within a
+/// slice, red grows to the right and green downwards.
+pub const LUT_SIZE: u32 = 16;
+
+/// Colour grading lookup table which leaves the colours unchanged
+pub fn identity_lut() -> Vec<[u8; 4]> {
+    let level = |i: u32| (i * 255 / (LUT_SIZE - 1)) as u8;
+    (0..LUT_SIZE)
+        .flat_map(|g| {
+            (0..LUT_SIZE)
+                .flat_map(move |b| (0..LUT_SIZE).map(move |r| [level(r), level(g), level(b), 255]))
+        })
+        .collect()
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Zeroable, Pod)]
 pub struct Locals {
     proj_mat_inv: [[f32; 4]; 4],
     view_mat_inv: [[f32; 4]; 4],
+    /// Distance in focus and aperture of the depth of field, which is off at
+    /// an aperture of zero
+    depth_of_field: [f32; 4],
 }
 
 impl Default for Locals {
@@ -18,6 +38,16 @@ impl Locals {
         Self {
             proj_mat_inv: proj_mat_inv.into_col_arrays(),
             view_mat_inv: view_mat_inv.into_col_arrays(),
+            depth_of_field: [0.0; 4],
+        }
+    }
+
+    /// Blurs what is nearer or further than `focus_distance`, the more the
+    /// larger the aperture is
+    pub fn with_depth_of_field(self, focus_distance: f32, aperture: f32) -> Self {
+        Self {
+            depth_of_field: [focus_distance, aperture, 0.0, 0.0],
+            ..self
         }
     }
 }
@@ -64,6 +94,37 @@ impl PostProcessLayout {
                 },
                 count: None,
             },
+            // Colour grading lookup table
+            wgpu::BindGroupLayoutEntry {
+                binding: 4,
+                visibility: wgpu::ShaderStage::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            // Depth source, for the depth of field
+            wgpu::BindGroupLayoutEntry {
+                binding: 5,
+                visibility: wgpu::ShaderStage::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 6,
+                visibility: wgpu::ShaderStage::FRAGMENT,
+                ty: wgpu::BindingType::Sampler {
+                    filtering: true,
+                    comparison: false,
+                },
+                count: None,
+            },
         ];
 
         if pipeline_modes.bloom.is_on() {
@@ -95,7 +156,10 @@ impl PostProcessLayout {
         device: &wgpu::Device,
         src_color: &wgpu::TextureView,
         src_bloom: Option<&wgpu::TextureView>,
+        src_depth: &wgpu::TextureView,
+        color_grading_lut: &wgpu::TextureView,
         sampler: &wgpu::Sampler,
+        depth_sampler: &wgpu::Sampler,
         locals: &Consts<Locals>,
     ) -> BindGroup {
         let mut entries = vec![
@@ -111,6 +175,18 @@ impl PostProcessLayout {
                 binding: 2,
                 resource: locals.buf().as_entire_binding(),
             },
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: wgpu::BindingResource::TextureView(color_grading_lut),
+            },
+            wgpu::BindGroupEntry {
+                binding: 5,
+                resource: wgpu::BindingResource::TextureView(src_depth),
+            },
+            wgpu::BindGroupEntry {
+                binding: 6,
+                resource: wgpu::BindingResource::Sampler(depth_sampler),
+            },
         ];
         // Optional bloom source
         if let Some(src_bloom) = src_bloom {
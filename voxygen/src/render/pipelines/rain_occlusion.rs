@@ -15,8 +15,9 @@ pub struct Locals {
     /// A value to offset the rain, to make it move over time.
     integrated_rain_vel: f32,
     rain_density: f32,
+    snow_density: f32,
     // To keep 16-byte-aligned.
-    occlusion_dummy: [f32; 2],
+    occlusion_dummy: [f32; 1],
 }
 /// Make sure Locals is 16-byte-aligned.
 const _: () = assert!(core::mem::size_of::<Locals>() % 16 == 0);
@@ -27,6 +28,7 @@ impl Locals {
         rain_occlusion_texture_mat: Mat4<f32>,
         rain_dir_mat: Mat4<f32>,
         rain_density: f32,
+        snow_density: f32,
         integrated_rain_vel: f32,
     ) -> Self {
         Self {
@@ -35,7 +37,8 @@ impl Locals {
             rain_dir_mat: rain_dir_mat.into_col_arrays(),
             integrated_rain_vel,
             rain_density,
-            occlusion_dummy: [0.0; 2],
+            snow_density,
+            occlusion_dummy: [0.0; 1],
         }
     }
 }
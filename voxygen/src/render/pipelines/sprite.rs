@@ -104,6 +104,10 @@ pub struct Instance {
     inst_glow: f32,
     model_wind_sway: f32,
     model_z_scale: f32,
+    /// Decorative sprites are thinned out when this is above the sprite
+    /// density and fade out further away the lower it is, so the same ones
+    /// always disappear first
+    inst_density_rank: f32,
 }
 
 impl Instance {
@@ -116,6 +120,7 @@ impl Instance {
         light: f32,
         glow: f32,
         vert_page: u32,
+        density_rank: f32,
     ) -> Self {
         const EXTRA_NEG_Z: i32 = 32768;
 
@@ -134,11 +139,12 @@ impl Instance {
             inst_glow: glow,
             model_wind_sway: wind_sway,
             model_z_scale: z_scale,
+            inst_density_rank: density_rank,
         }
     }
 
     fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
-        const ATTRIBUTES: [wgpu::VertexAttribute; 10] = wgpu::vertex_attr_array![
+        const ATTRIBUTES: [wgpu::VertexAttribute; 11] = wgpu::vertex_attr_array![
             0 => Float32x4,
             1 => Float32x4,
             2 => Float32x4,
@@ -149,6 +155,7 @@ impl Instance {
             7 => Float32,
             8 => Float32,
             9 => Float32,
+            10 => Float32,
         ];
         wgpu::VertexBufferLayout {
             array_stride: mem::size_of::<Self>() as wgpu::BufferAddress,
@@ -159,7 +166,19 @@ impl Instance {
 }
 
 impl Default for Instance {
-    fn default() -> Self { Self::new(Mat4::identity(), 0.0, 0.0, Vec3::zero(), 0, 1.0, 0.0, 0) }
+    fn default() -> Self {
+        Self::new(
+            Mat4::identity(),
+            0.0,
+            0.0,
+            Vec3::zero(),
+            0,
+            1.0,
+            0.0,
+            0,
+            0.0,
+        )
+    }
 }
 
 // TODO: ColLightsWrapper instead?
@@ -9,11 +9,14 @@ use std::{
 #[derive(Copy, Clone, Debug, Zeroable, Pod, PartialEq)]
 pub struct Vertex {
     pub pos: [f32; 3],
+    /// Colour of the trail, with how much it has faded in the alpha
+    pub color: [f32; 4],
 }
 
 impl Vertex {
     fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
-        const ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Float32x3];
+        const ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+            wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x4];
         wgpu::VertexBufferLayout {
             array_stride: Self::STRIDE,
             step_mode: wgpu::InputStepMode::Vertex,
@@ -21,7 +24,12 @@ impl Vertex {
         }
     }
 
-    pub fn zero() -> Self { Self { pos: [0.0; 3] } }
+    pub fn zero() -> Self {
+        Self {
+            pos: [0.0; 3],
+            color: [0.0; 4],
+        }
+    }
 }
 
 impl Mul<f32> for Vertex {
@@ -30,6 +38,7 @@ impl Mul<f32> for Vertex {
     fn mul(self, val: f32) -> Self::Output {
         Self {
             pos: self.pos.map(|a| a * val),
+            color: self.color.map(|a| a * val),
         }
     }
 }
@@ -40,6 +49,7 @@ impl Add<Vertex> for Vertex {
     fn add(self, other: Self) -> Self::Output {
         Self {
             pos: self.pos.zip(other.pos).map(|(a, b)| a + b),
+            color: self.color.zip(other.color).map(|(a, b)| a + b),
         }
     }
 }
@@ -120,10 +130,11 @@ impl TrailPipeline {
                 targets: &[wgpu::ColorTargetState {
                     // TODO: use a constant and/or pass in this format on pipeline construction
                     format: wgpu::TextureFormat::Rgba16Float,
+                    // Trails glow, they are added on top of what is behind them
                     blend: Some(wgpu::BlendState {
                         color: wgpu::BlendComponent {
                             src_factor: wgpu::BlendFactor::SrcAlpha,
-                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                            dst_factor: wgpu::BlendFactor::One,
                             operation: wgpu::BlendOperation::Add,
                         },
                         alpha: wgpu::BlendComponent {
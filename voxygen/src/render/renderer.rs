@@ -24,8 +24,8 @@ use super::{
     mesh::Mesh,
     model::{DynamicModel, Model},
     pipelines::{
-        blit, bloom, clouds, debug, figure, postprocess, rain_occlusion, shadow, sprite, terrain,
-        ui, GlobalsBindGroup, GlobalsLayouts, ShadowTexturesBindGroup,
+        blit, bloom, clouds, debug, figure, fluid, ghost, postprocess, rain_occlusion, shadow,
+        sprite, terrain, ui, GlobalsBindGroup, GlobalsLayouts, ShadowTexturesBindGroup,
     },
     texture::Texture,
     AaMode, AddressMode, FilterMode, OtherModes, PipelineModes, RenderError, RenderMode,
@@ -56,10 +56,12 @@ struct ImmutableLayouts {
 
     debug: debug::DebugLayout,
     figure: figure::FigureLayout,
+    ghost: ghost::GhostLayout,
     shadow: shadow::ShadowLayout,
     rain_occlusion: rain_occlusion::RainOcclusionLayout,
     sprite: sprite::SpriteLayout,
     terrain: terrain::TerrainLayout,
+    fluid_reflections: fluid::ReflectionsLayout,
     clouds: clouds::CloudsLayout,
     bloom: bloom::BloomLayout,
     ui: ui::UiLayout,
@@ -87,11 +89,27 @@ struct Views {
     tgt_color: wgpu::TextureView,
     tgt_depth: wgpu::TextureView,
 
+    /// Copies of the opaque scene reflected by the fluids, if screen-space
+    /// reflections are enabled
+    reflections: Option<ReflectionTargets>,
+
     bloom_tgts: Option<[wgpu::TextureView; bloom::NUM_SIZES]>,
     // TODO: rename
     tgt_color_pp: wgpu::TextureView,
 }
 
+/// The opaque scene is copied out of the first pass targets before the fluids
+/// are drawn, since the fluids can't sample the targets they are drawn to
+struct ReflectionTargets {
+    tgt_color: wgpu::Texture,
+    tgt_depth: wgpu::Texture,
+    color: wgpu::Texture,
+    depth: wgpu::Texture,
+    color_view: wgpu::TextureView,
+    depth_view: wgpu::TextureView,
+    size: wgpu::Extent3d,
+}
+
 /// Shadow rendering textures, layouts, pipelines, and bind groups
 struct Shadow {
     rain_map: RainOcclusionMap,
@@ -158,6 +176,8 @@ pub struct Renderer {
     locals: Locals,
     views: Views,
     noise_tex: Texture,
+    /// Colour grading lookup table applied in the final pass
+    color_grading_lut: Texture,
 
     quad_index_buffer_u16: Buffer<u16>,
     quad_index_buffer_u32: Buffer<u32>,
@@ -171,6 +191,11 @@ pub struct Renderer {
 
     // If this is Some then a screenshot will be taken and passed to the handler here
     take_screenshot: Option<screenshot::ScreenshotFn>,
+    // Whether the frame captured is for a clip rather than a screenshot
+    capturing_clip_frame: bool,
+    // Times the resolution of the window the scene is rendered at, for the
+    // screenshot about to be taken
+    supersampling: u32,
 
     profiler: wgpu_profiler::GpuProfiler,
     profile_times: Vec<wgpu_profiler::GpuTimerScopeResult>,
@@ -185,6 +210,11 @@ pub struct Renderer {
 
     // To remember the backend info after initialization for debug purposes
     graphics_backend: String,
+    // Used to suggest a graphics preset on first launch
+    graphics_device_type: wgpu::DeviceType,
+    // Whether several point lights can cast shadows, each into a cube of the
+    // point shadow map array
+    cube_array_textures: bool,
 }
 
 impl Renderer {
@@ -195,7 +225,7 @@ impl Renderer {
         mode: RenderMode,
         runtime: &tokio::runtime::Runtime,
     ) -> Result<Self, RenderError> {
-        let (pipeline_modes, mut other_modes) = mode.split();
+        let (mut pipeline_modes, mut other_modes) = mode.split();
         // Enable seamless cubemaps globally, where available--they are essentially a
         // strict improvement on regular cube maps.
         //
@@ -275,6 +305,16 @@ impl Renderer {
             "selected graphics device"
         );
         let graphics_backend = format!("{:?}", &info.backend);
+        let graphics_device_type = info.device_type.clone();
+
+        let cube_array_textures = adapter
+            .get_downlevel_properties()
+            .flags
+            .contains(wgpu::DownlevelFlags::CUBE_ARRAY_TEXTURES);
+        if !cube_array_textures {
+            info!("Cube map arrays are not supported, only one point light will cast shadows");
+        }
+        pipeline_modes.limit_point_shadows(cube_array_textures);
 
         let limits = wgpu::Limits {
             max_push_constant_size: 64,
@@ -360,6 +400,7 @@ impl Renderer {
             &device,
             (dims.width, dims.height),
             &ShadowMapMode::try_from(pipeline_modes.shadow).unwrap_or_default(),
+            cube_array_textures,
         )
         .map_err(|err| {
             warn!("Could not create shadow map views: {:?}", err);
@@ -377,14 +418,16 @@ impl Renderer {
         let shaders_watcher = shaders.reload_watcher();
 
         let layouts = {
-            let global = GlobalsLayouts::new(&device);
+            let global = GlobalsLayouts::new(&device, cube_array_textures);
 
             let debug = debug::DebugLayout::new(&device);
             let figure = figure::FigureLayout::new(&device);
+            let ghost = ghost::GhostLayout::new(&device);
             let shadow = shadow::ShadowLayout::new(&device);
             let rain_occlusion = rain_occlusion::RainOcclusionLayout::new(&device);
             let sprite = sprite::SpriteLayout::new(&device);
             let terrain = terrain::TerrainLayout::new(&device);
+            let fluid_reflections = fluid::ReflectionsLayout::new(&device);
             let clouds = clouds::CloudsLayout::new(&device);
             let bloom = bloom::BloomLayout::new(&device);
             let postprocess = Arc::new(postprocess::PostProcessLayout::new(
@@ -399,10 +442,12 @@ impl Renderer {
 
                 debug,
                 figure,
+                ghost,
                 shadow,
                 rain_occlusion,
                 sprite,
                 terrain,
+                fluid_reflections,
                 clouds,
                 bloom,
                 ui,
@@ -428,6 +473,7 @@ impl Renderer {
             pipeline_modes.clone(),
             sc_desc.clone(), // Note: cheap clone
             shadow_views.is_some(),
+            cube_array_textures,
         )?;
 
         let state = State::Interface {
@@ -469,6 +515,22 @@ impl Renderer {
             Some(AddressMode::Repeat),
         )?;
 
+        let color_grading_lut = Texture::new_dynamic(
+            &device,
+            &queue,
+            postprocess::LUT_SIZE * postprocess::LUT_SIZE,
+            postprocess::LUT_SIZE,
+        );
+        color_grading_lut.update(
+            &queue,
+            [0, 0],
+            [
+                postprocess::LUT_SIZE * postprocess::LUT_SIZE,
+                postprocess::LUT_SIZE,
+            ],
+            bytemuck::cast_slice(&postprocess::identity_lut()),
+        );
+
         let clouds_locals =
             Self::create_consts_inner(&device, &queue, &[clouds::Locals::default()]);
         let postprocess_locals =
@@ -481,6 +543,10 @@ impl Renderer {
             postprocess_locals,
             &views.tgt_color,
             &views.tgt_depth,
+            views
+                .reflections
+                .as_ref()
+                .map(|r| (&r.color_view, &r.depth_view)),
             views.bloom_tgts.as_ref().map(|tgts| locals::BloomParams {
                 locals: bloom_sizes.map(|size| {
                     Self::create_consts_inner(&device, &queue, &[bloom::Locals::new(size)])
@@ -489,6 +555,7 @@ impl Renderer {
                 final_tgt_view: &tgts[0],
             }),
             &views.tgt_color_pp,
+            &color_grading_lut.view,
             &sampler,
             &depth_sampler,
         );
@@ -523,6 +590,7 @@ impl Renderer {
             sampler,
             depth_sampler,
             noise_tex,
+            color_grading_lut,
 
             quad_index_buffer_u16,
             quad_index_buffer_u32,
@@ -535,6 +603,8 @@ impl Renderer {
             resolution: Vec2::new(dims.width, dims.height),
 
             take_screenshot: None,
+            capturing_clip_frame: false,
+            supersampling: 1,
 
             profiler,
             profile_times: Vec::new(),
@@ -546,12 +616,17 @@ impl Renderer {
             is_minimized: false,
 
             graphics_backend,
+            graphics_device_type,
+            cube_array_textures,
         })
     }
 
     /// Get the graphics backend being used
     pub fn graphics_backend(&self) -> &str { &self.graphics_backend }
 
+    /// Get the type of graphics device being used
+    pub fn graphics_device_type(&self) -> wgpu::DeviceType { self.graphics_device_type.clone() }
+
     /// Check the status of the intial pipeline creation
     /// Returns `None` if complete
     /// Returns `Some((total, complete))` if in progress
@@ -576,7 +651,8 @@ impl Renderer {
 
     /// Change the render mode.
     pub fn set_render_mode(&mut self, mode: RenderMode) -> Result<(), RenderError> {
-        let (pipeline_modes, other_modes) = mode.split();
+        let (mut pipeline_modes, other_modes) = mode.split();
+        pipeline_modes.limit_point_shadows(self.cube_array_textures);
 
         if self.other_modes != other_modes {
             self.other_modes = other_modes;
@@ -658,7 +734,7 @@ impl Renderer {
             // Resize other render targets
             let (views, bloom_sizes) = Self::create_rt_views(
                 &self.device,
-                (dims.x, dims.y),
+                (dims.x * self.supersampling, dims.y * self.supersampling),
                 &self.pipeline_modes,
                 &self.other_modes,
             );
@@ -689,8 +765,13 @@ impl Renderer {
                 &self.layouts,
                 &self.views.tgt_color,
                 &self.views.tgt_depth,
+                self.views
+                    .reflections
+                    .as_ref()
+                    .map(|r| (&r.color_view, &r.depth_view)),
                 bloom_params,
                 &self.views.tgt_color_pp,
+                &self.color_grading_lut.view,
                 &self.sampler,
                 &self.depth_sampler,
             );
@@ -727,7 +808,12 @@ impl Renderer {
             if let (Some((point_depth, directed_depth)), ShadowMode::Map(mode)) =
                 (shadow_views, self.pipeline_modes.shadow)
             {
-                match ShadowMap::create_shadow_views(&self.device, (dims.x, dims.y), &mode) {
+                match ShadowMap::create_shadow_views(
+                    &self.device,
+                    (dims.x, dims.y),
+                    &mode,
+                    self.cube_array_textures,
+                ) {
                     Ok((new_point_depth, new_directed_depth)) => {
                         *point_depth = new_point_depth;
                         *directed_depth = new_directed_depth;
@@ -788,6 +874,15 @@ impl Renderer {
         self.device.poll(wgpu::Maintain::Poll)
     }
 
+    /// Goes back to rendering at the resolution of the window once the
+    /// supersampled screenshot was taken
+    pub fn end_supersampling(&mut self) {
+        if self.supersampling != 1 && self.take_screenshot.is_none() {
+            self.supersampling = 1;
+            self.on_resize(self.resolution);
+        }
+    }
+
     /// Create render target views
     fn create_rt_views(
         device: &wgpu::Device,
@@ -806,8 +901,16 @@ impl Renderer {
         };
         let levels = 1;
 
-        let color_view = |width, height| {
-            let tex = device.create_texture(&wgpu::TextureDescriptor {
+        let screen_space_reflections = pipeline_modes.screen_space_reflections();
+        // The first pass targets are copied for the fluids to reflect
+        let copy_usage = if screen_space_reflections {
+            wgpu::TextureUsage::COPY_SRC
+        } else {
+            wgpu::TextureUsage::empty()
+        };
+
+        let color_tex = |width, height, usage| {
+            device.create_texture(&wgpu::TextureDescriptor {
                 label: None,
                 size: wgpu::Extent3d {
                     width,
@@ -818,9 +921,10 @@ impl Renderer {
                 sample_count,
                 dimension: wgpu::TextureDimension::D2,
                 format: wgpu::TextureFormat::Rgba16Float,
-                usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::RENDER_ATTACHMENT,
-            });
-
+                usage,
+            })
+        };
+        let color_view = |tex: &wgpu::Texture| {
             tex.create_view(&wgpu::TextureViewDescriptor {
                 label: None,
                 format: Some(wgpu::TextureFormat::Rgba16Float),
@@ -834,8 +938,11 @@ impl Renderer {
             })
         };
 
-        let tgt_color_view = color_view(width, height);
-        let tgt_color_pp_view = color_view(width, height);
+        let target_usage = wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::RENDER_ATTACHMENT;
+
+        let tgt_color_tex = color_tex(width, height, target_usage | copy_usage);
+        let tgt_color_view = color_view(&tgt_color_tex);
+        let tgt_color_pp_view = color_view(&color_tex(width, height, target_usage));
 
         let mut size_shift = 0;
         // TODO: skip creating bloom stuff when it is disabled
@@ -849,30 +956,56 @@ impl Renderer {
         let bloom_tgt_views = pipeline_modes
             .bloom
             .is_on()
-            .then(|| bloom_sizes.map(|size| color_view(size.x, size.y)));
+            .then(|| bloom_sizes.map(|size| color_view(&color_tex(size.x, size.y, target_usage))));
 
-        let tgt_depth_tex = device.create_texture(&wgpu::TextureDescriptor {
-            label: None,
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: levels,
-            sample_count,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Depth32Float,
-            usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::RENDER_ATTACHMENT,
-        });
-        let tgt_depth_view = tgt_depth_tex.create_view(&wgpu::TextureViewDescriptor {
-            label: None,
-            format: Some(wgpu::TextureFormat::Depth32Float),
-            dimension: Some(wgpu::TextureViewDimension::D2),
-            aspect: wgpu::TextureAspect::DepthOnly,
-            base_mip_level: 0,
-            mip_level_count: None,
-            base_array_layer: 0,
-            array_layer_count: None,
+        let depth_tex = |usage| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: levels,
+                sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Depth32Float,
+                usage,
+            })
+        };
+        let depth_view = |tex: &wgpu::Texture| {
+            tex.create_view(&wgpu::TextureViewDescriptor {
+                label: None,
+                format: Some(wgpu::TextureFormat::Depth32Float),
+                dimension: Some(wgpu::TextureViewDimension::D2),
+                aspect: wgpu::TextureAspect::DepthOnly,
+                base_mip_level: 0,
+                mip_level_count: None,
+                base_array_layer: 0,
+                array_layer_count: None,
+            })
+        };
+
+        let tgt_depth_tex = depth_tex(target_usage | copy_usage);
+        let tgt_depth_view = depth_view(&tgt_depth_tex);
+
+        let reflections = screen_space_reflections.then(|| {
+            let copy_usage = wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST;
+            let color = color_tex(width, height, copy_usage);
+            let depth = depth_tex(copy_usage);
+            ReflectionTargets {
+                color_view: color_view(&color),
+                depth_view: depth_view(&depth),
+                tgt_color: tgt_color_tex,
+                tgt_depth: tgt_depth_tex,
+                color,
+                depth,
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+            }
         });
 
         let win_depth_tex = device.create_texture(&wgpu::TextureDescriptor {
@@ -904,6 +1037,7 @@ impl Renderer {
             Views {
                 tgt_color: tgt_color_view,
                 tgt_depth: tgt_depth_view,
+                reflections,
                 bloom_tgts: bloom_tgt_views,
                 tgt_color_pp: tgt_color_pp_view,
                 _win_depth: win_depth_view,
@@ -915,6 +1049,9 @@ impl Renderer {
     /// Get the resolution of the render target.
     pub fn resolution(&self) -> Vec2<u32> { self.resolution }
 
+    /// Times the resolution of the window the scene is currently rendered at
+    pub fn supersampling(&self) -> u32 { self.supersampling }
+
     /// Get the resolution of the shadow render target.
     pub fn get_shadow_resolution(&self) -> (Vec2<u32>, Vec2<u32>) {
         match &self.state {
@@ -1022,6 +1159,7 @@ impl Renderer {
                         shadow.directed,
                         shadow.figure,
                         shadow_views,
+                        self.cube_array_textures,
                     );
 
                     let rain_occlusion_map = RainOcclusionMap::new(
@@ -1213,6 +1351,7 @@ impl Renderer {
                         // through the swap chain descriptor)
                         self.sc_desc.clone(), // Note: cheap clone
                         shadow.map.is_enabled(),
+                        self.cube_array_textures,
                     ),
                 ));
             },
@@ -1253,6 +1392,18 @@ impl Renderer {
         self.locals.postprocess.update(&self.queue, &[new_val], 0)
     }
 
+    /// Replace the colour grading lookup table, laid out as described by
+    /// [`postprocess::LUT_SIZE`].
+    pub fn update_color_grading(&mut self, lut: &[[u8; 4]]) {
+        let size = postprocess::LUT_SIZE;
+        self.color_grading_lut.update(
+            &self.queue,
+            [0, 0],
+            [size * size, size],
+            bytemuck::cast_slice(lut),
+        );
+    }
+
     /// Create a new set of instances with the provided values.
     pub fn create_instances<T: Copy + bytemuck::Pod>(
         &mut self,
@@ -1429,6 +1580,31 @@ impl Renderer {
         texture.update(&self.queue, offset, size, bytemuck::cast_slice(data))
     }
 
+    /// Renders the next frame `supersampling` times as large as the window, for
+    /// the screenshot taken on it, as large as the textures can be
+    pub fn supersample_next_frame(&mut self, supersampling: u32) {
+        let max_size = self.device.limits().max_texture_dimension_2d as f32;
+        let render_size = self
+            .resolution
+            .map(|e| e as f32 * self.other_modes.upscale_mode.factor.max(1.0))
+            .reduce_partial_max();
+        let supersampling = supersampling.clamp(1, ((max_size / render_size) as u32).max(1));
+        if supersampling != self.supersampling {
+            self.supersampling = supersampling;
+            self.on_resize(self.resolution);
+        }
+    }
+
+    /// Queue to obtain the image of the next frame render for a clip, without
+    /// the profiler snapshot of screenshots
+    pub fn capture_clip_frame(
+        &mut self,
+        frame_handler: impl FnOnce(Result<image::DynamicImage, String>) + Send + 'static,
+    ) {
+        self.take_screenshot = Some(Box::new(frame_handler));
+        self.capturing_clip_frame = true;
+    }
+
     /// Queue to obtain a screenshot on the next frame render
     pub fn create_screenshot(
         &mut self,
@@ -1436,6 +1612,7 @@ impl Renderer {
     ) {
         // Queue screenshot
         self.take_screenshot = Some(Box::new(screenshot_handler));
+        self.capturing_clip_frame = false;
         // Take profiler snapshot
         if self.other_modes.profiler_enabled {
             let file_name = format!(
@@ -3,7 +3,7 @@ use crate::render::pipelines::rain_occlusion;
 use super::{
     super::{
         pipelines::{
-            debug, figure, lod_terrain, shadow, sprite, terrain, ui, ColLights, GlobalModel,
+            debug, figure, ghost, lod_terrain, shadow, sprite, terrain, ui, ColLights, GlobalModel,
             GlobalsBindGroup,
         },
         texture::Texture,
@@ -42,6 +42,11 @@ impl Renderer {
         self.layouts.debug.bind_locals(&self.device, locals)
     }
 
+    pub fn create_ghost_bound_locals(&mut self, vals: &[ghost::Locals]) -> ghost::BoundLocals {
+        let locals = self.create_consts(vals);
+        self.layouts.ghost.bind_locals(&self.device, locals)
+    }
+
     pub fn create_ui_bound_locals(&mut self, vals: &[ui::Locals]) -> ui::BoundLocals {
         let locals = self.create_consts(vals);
         self.layouts.ui.bind_locals(&self.device, locals)
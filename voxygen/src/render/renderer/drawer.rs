@@ -4,8 +4,8 @@ use super::{
         instances::Instances,
         model::{DynamicModel, Model, SubModel},
         pipelines::{
-            blit, bloom, clouds, debug, figure, fluid, lod_object, lod_terrain, particle, shadow,
-            skybox, sprite, terrain, trail, ui, ColLights, GlobalsBindGroup,
+            blit, bloom, clouds, debug, figure, fluid, ghost, lod_object, lod_terrain, particle,
+            shadow, skybox, sprite, terrain, trail, ui, ColLights, GlobalsBindGroup,
             ShadowTexturesBindGroup,
         },
     },
@@ -78,6 +78,8 @@ pub struct Drawer<'frame> {
     // Texture and other info for taking a screenshot
     // Writes to this instead in the third pass if it is present
     taking_screenshot: Option<super::screenshot::TakeScreenshot>,
+    // Whether the frame is captured for a clip rather than as a screenshot
+    capturing_clip_frame: bool,
 }
 
 impl<'frame> Drawer<'frame> {
@@ -87,12 +89,14 @@ impl<'frame> Drawer<'frame> {
         swap_tex: wgpu::SwapChainTexture,
         globals: &'frame GlobalsBindGroup,
     ) -> Self {
+        let capturing_clip_frame = renderer.capturing_clip_frame;
         let taking_screenshot = renderer.take_screenshot.take().map(|screenshot_fn| {
             super::screenshot::TakeScreenshot::new(
                 &renderer.device,
                 &renderer.layouts.blit,
                 &renderer.sampler,
                 &renderer.sc_desc,
+                renderer.supersampling,
                 screenshot_fn,
             )
         });
@@ -130,6 +134,7 @@ impl<'frame> Drawer<'frame> {
             swap_tex,
             globals,
             taking_screenshot,
+            capturing_clip_frame,
         }
     }
 
@@ -254,6 +259,71 @@ impl<'frame> Drawer<'frame> {
         })
     }
 
+    /// Continues the first pass with the fluids, which are drawn on top of the
+    /// rest of the scene. If screen-space reflections are enabled, the opaque
+    /// scene is copied beforehand for the fluids to reflect it.
+    ///
+    /// Returns None if all the pipelines are not available
+    pub fn fluid_pass(&mut self) -> Option<FluidPassDrawer> {
+        let pipelines = self.borrow.pipelines.all()?;
+        let shadow = self.borrow.shadow?;
+
+        let encoder = self.encoder.as_mut().unwrap();
+        let device = self.borrow.device;
+
+        if let Some(reflections) = &self.borrow.views.reflections {
+            let mut encoder = encoder.scope("copy opaque scene", device);
+            for (src, dst) in [
+                (&reflections.tgt_color, &reflections.color),
+                (&reflections.tgt_depth, &reflections.depth),
+            ] {
+                encoder.copy_texture_to_texture(
+                    wgpu::ImageCopyTexture {
+                        texture: src,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                    },
+                    wgpu::ImageCopyTexture {
+                        texture: dst,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                    },
+                    reflections.size,
+                );
+            }
+        }
+
+        let mut render_pass =
+            encoder.scoped_render_pass("fluid_pass", device, &wgpu::RenderPassDescriptor {
+                label: Some("first pass (fluids)"),
+                color_attachments: &[wgpu::RenderPassColorAttachment {
+                    view: &self.borrow.views.tgt_color,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &self.borrow.views.tgt_depth,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    }),
+                    stencil_ops: None,
+                }),
+            });
+
+        render_pass.set_bind_group(0, &self.globals.bind_group, &[]);
+        render_pass.set_bind_group(1, &shadow.bind.bind_group, &[]);
+
+        Some(FluidPassDrawer {
+            render_pass,
+            borrow: &self.borrow,
+            pipelines,
+        })
+    }
+
     /// Returns None if the clouds pipeline is not available
     pub fn second_pass(&mut self) -> Option<SecondPassDrawer> {
         let pipelines = &self.borrow.pipelines.all()?;
@@ -382,6 +452,12 @@ impl<'frame> Drawer<'frame> {
         });
     }
 
+    /// Whether this frame is captured as a screenshot, the frames captured for
+    /// clips aside
+    pub fn taking_screenshot(&self) -> bool {
+        self.taking_screenshot.is_some() && !self.capturing_clip_frame
+    }
+
     pub fn third_pass(&mut self) -> ThirdPassDrawer {
         let encoder = self.encoder.as_mut().unwrap();
         let device = self.borrow.device;
@@ -475,8 +551,11 @@ impl<'frame> Drawer<'frame> {
                 .scope("point shadows", device);
             const STRIDE: usize = std::mem::size_of::<shadow::PointLightMatrix>();
             let data = bytemuck::cast_slice(matrices);
+            // One cube of six faces per point light casting shadows
+            let layers = shadow_renderer.point_depth.get_dimensions().z;
 
-            for face in 0..6 {
+            for layer in 0..layers {
+                let (point_light, face) = (layer as usize / 6, layer % 6);
                 // TODO: view creation cost?
                 let view =
                     shadow_renderer
@@ -489,11 +568,11 @@ impl<'frame> Drawer<'frame> {
                             aspect: wgpu::TextureAspect::DepthOnly,
                             base_mip_level: 0,
                             mip_level_count: None,
-                            base_array_layer: face,
+                            base_array_layer: layer,
                             array_layer_count: NonZeroU32::new(1),
                         });
 
-                let label = format!("point shadow face-{} pass", face);
+                let label = format!("point shadow {} face-{} pass", point_light, face);
                 let mut render_pass =
                     encoder.scoped_render_pass(&label, device, &wgpu::RenderPassDescriptor {
                         label: Some(&label),
@@ -512,18 +591,16 @@ impl<'frame> Drawer<'frame> {
                 set_quad_index_buffer::<terrain::Vertex>(&mut render_pass, &self.borrow);
                 render_pass.set_bind_group(0, &self.globals.bind_group, &[]);
 
-                (0../*20*/1).for_each(|point_light| {
-                    render_pass.set_push_constants(
-                        wgpu::ShaderStage::all(),
-                        0,
-                        &data[(6 * (point_light + 1) * STRIDE + face as usize * STRIDE)
-                            ..(6 * (point_light + 1) * STRIDE + (face + 1) as usize * STRIDE)],
-                    );
-                    chunks.clone().for_each(|(model, locals)| {
-                        render_pass.set_bind_group(1, &locals.bind_group, &[]);
-                        render_pass.set_vertex_buffer(0, model.buf().slice(..));
-                        render_pass.draw_indexed(0..model.len() as u32 / 4 * 6, 0, 0..1);
-                    });
+                render_pass.set_push_constants(
+                    wgpu::ShaderStage::all(),
+                    0,
+                    &data[(6 * (point_light + 1) * STRIDE + face as usize * STRIDE)
+                        ..(6 * (point_light + 1) * STRIDE + (face + 1) as usize * STRIDE)],
+                );
+                chunks.clone().for_each(|(model, locals)| {
+                    render_pass.set_bind_group(1, &locals.bind_group, &[]);
+                    render_pass.set_vertex_buffer(0, model.buf().slice(..));
+                    render_pass.draw_indexed(0..model.len() as u32 / 4 * 6, 0, 0..1);
                 });
             }
         }
@@ -560,7 +637,7 @@ impl<'frame> Drawer<'frame> {
                 },
             );
 
-            for face in 0..6 {
+            for layer in 0..shadow_renderer.point_depth.get_dimensions().z {
                 // TODO: view creation cost?
                 let view =
                     shadow_renderer
@@ -573,11 +650,11 @@ impl<'frame> Drawer<'frame> {
                             aspect: wgpu::TextureAspect::DepthOnly,
                             base_mip_level: 0,
                             mip_level_count: None,
-                            base_array_layer: face,
+                            base_array_layer: layer,
                             array_layer_count: NonZeroU32::new(1),
                         });
 
-                let label = format!("clear point shadow face-{} pass", face);
+                let label = format!("clear point shadow layer-{} pass", layer);
                 let _ = encoder.scoped_render_pass(&label, device, &wgpu::RenderPassDescriptor {
                     label: Some(&label),
                     color_attachments: &[],
@@ -782,6 +859,18 @@ impl<'pass> FirstPassDrawer<'pass> {
         }
     }
 
+    pub fn draw_ghosts(&mut self) -> GhostDrawer<'_, 'pass> {
+        let mut render_pass = self.render_pass.scope("ghosts", self.borrow.device);
+
+        render_pass.set_pipeline(&self.pipelines.ghost.pipeline);
+        set_quad_index_buffer::<ghost::Vertex>(&mut render_pass, self.borrow);
+
+        GhostDrawer {
+            render_pass,
+            shadows: self.shadows,
+        }
+    }
+
     pub fn draw_lod_terrain<'data: 'pass>(&mut self, model: &'data Model<lod_terrain::Vertex>) {
         let mut render_pass = self.render_pass.scope("lod_terrain", self.borrow.device);
 
@@ -848,12 +937,25 @@ impl<'pass> FirstPassDrawer<'pass> {
 
         LodObjectDrawer { render_pass }
     }
+}
 
+// First pass, continued: fluids
+#[must_use]
+pub struct FluidPassDrawer<'pass> {
+    render_pass: OwningScope<'pass, wgpu::RenderPass<'pass>>,
+    borrow: &'pass RendererBorrow<'pass>,
+    pipelines: &'pass super::Pipelines,
+}
+
+impl<'pass> FluidPassDrawer<'pass> {
     pub fn draw_fluid(&mut self) -> FluidDrawer<'_, 'pass> {
         let mut render_pass = self.render_pass.scope("fluid", self.borrow.device);
 
         render_pass.set_pipeline(&self.pipelines.fluid.pipeline);
         set_quad_index_buffer::<fluid::Vertex>(&mut render_pass, self.borrow);
+        if let Some(reflections) = &self.borrow.locals.fluid_reflections_bind {
+            render_pass.set_bind_group(3, &reflections.bind_group, &[]);
+        }
 
         FluidDrawer { render_pass }
     }
@@ -886,6 +988,34 @@ impl<'pass_ref, 'pass: 'pass_ref> Drop for DebugDrawer<'pass_ref, 'pass> {
     }
 }
 
+#[must_use]
+pub struct GhostDrawer<'pass_ref, 'pass: 'pass_ref> {
+    render_pass: Scope<'pass_ref, wgpu::RenderPass<'pass>>,
+    shadows: &'pass ShadowTexturesBindGroup,
+}
+
+impl<'pass_ref, 'pass: 'pass_ref> GhostDrawer<'pass_ref, 'pass> {
+    pub fn draw<'data: 'pass>(
+        &mut self,
+        model: &'data Model<ghost::Vertex>,
+        locals: &'data ghost::BoundLocals,
+    ) {
+        self.render_pass.set_bind_group(1, &locals.bind_group, &[]);
+        self.render_pass.set_vertex_buffer(0, model.buf().slice(..));
+        self.render_pass
+            .draw_indexed(0..model.len() as u32 / 4 * 6, 0, 0..1);
+    }
+}
+
+impl<'pass_ref, 'pass: 'pass_ref> Drop for GhostDrawer<'pass_ref, 'pass> {
+    fn drop(&mut self) {
+        // Maintain that the shadow bind group is set in
+        // slot 1 by default during the main pass
+        self.render_pass
+            .set_bind_group(1, &self.shadows.bind_group, &[]);
+    }
+}
+
 #[must_use]
 pub struct FigureDrawer<'pass_ref, 'pass: 'pass_ref> {
     render_pass: Scope<'pass_ref, wgpu::RenderPass<'pass>>,
@@ -1,7 +1,7 @@
 use super::{
     super::{
         consts::Consts,
-        pipelines::{bloom, clouds, postprocess},
+        pipelines::{bloom, clouds, fluid, postprocess},
     },
     Layouts,
 };
@@ -16,6 +16,8 @@ pub struct Locals {
     pub clouds: Consts<clouds::Locals>,
     pub clouds_bind: clouds::BindGroup,
 
+    pub fluid_reflections_bind: Option<fluid::BindGroup>,
+
     pub bloom_binds: Option<[bloom::BindGroup; bloom::NUM_SIZES]>,
 
     pub postprocess: Consts<postprocess::Locals>,
@@ -30,8 +32,10 @@ impl Locals {
         postprocess_locals: Consts<postprocess::Locals>,
         tgt_color_view: &wgpu::TextureView,
         tgt_depth_view: &wgpu::TextureView,
+        reflection_views: Option<(&wgpu::TextureView, &wgpu::TextureView)>,
         bloom: Option<BloomParams>,
         tgt_color_pp_view: &wgpu::TextureView,
+        color_grading_lut: &wgpu::TextureView,
         sampler: &wgpu::Sampler,
         depth_sampler: &wgpu::Sampler,
     ) -> Self {
@@ -44,11 +48,20 @@ impl Locals {
             &clouds_locals,
         );
 
+        let fluid_reflections_bind = reflection_views.map(|(color, depth)| {
+            layouts
+                .fluid_reflections
+                .bind(device, color, depth, sampler, depth_sampler)
+        });
+
         let postprocess_bind = layouts.postprocess.bind(
             device,
             tgt_color_pp_view,
             bloom.as_ref().map(|b| b.final_tgt_view),
+            tgt_depth_view,
+            color_grading_lut,
             sampler,
+            depth_sampler,
             &postprocess_locals,
         );
 
@@ -62,6 +75,7 @@ impl Locals {
         Self {
             clouds: clouds_locals,
             clouds_bind,
+            fluid_reflections_bind,
             bloom_binds,
             postprocess: postprocess_locals,
             postprocess_bind,
@@ -76,8 +90,10 @@ impl Locals {
         // e.g. resizing
         tgt_color_view: &wgpu::TextureView,
         tgt_depth_view: &wgpu::TextureView,
+        reflection_views: Option<(&wgpu::TextureView, &wgpu::TextureView)>,
         bloom: Option<BloomParams>,
         tgt_color_pp_view: &wgpu::TextureView,
+        color_grading_lut: &wgpu::TextureView,
         sampler: &wgpu::Sampler,
         depth_sampler: &wgpu::Sampler,
     ) {
@@ -89,11 +105,19 @@ impl Locals {
             depth_sampler,
             &self.clouds,
         );
+        self.fluid_reflections_bind = reflection_views.map(|(color, depth)| {
+            layouts
+                .fluid_reflections
+                .bind(device, color, depth, sampler, depth_sampler)
+        });
         self.postprocess_bind = layouts.postprocess.bind(
             device,
             tgt_color_pp_view,
             bloom.as_ref().map(|b| b.final_tgt_view),
+            tgt_depth_view,
+            color_grading_lut,
             sampler,
+            depth_sampler,
             &self.postprocess,
         );
         self.bloom_binds = bloom.map(|bloom| {
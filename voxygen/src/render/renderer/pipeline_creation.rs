@@ -3,11 +3,11 @@ use crate::render::pipelines::rain_occlusion;
 use super::{
     super::{
         pipelines::{
-            blit, bloom, clouds, debug, figure, fluid, lod_object, lod_terrain, particle,
+            blit, bloom, clouds, debug, figure, fluid, ghost, lod_object, lod_terrain, particle,
             postprocess, shadow, skybox, sprite, terrain, trail, ui,
         },
         AaMode, BloomMode, CloudMode, FluidMode, LightingMode, PipelineModes, RenderError,
-        ShadowMode,
+        ShadowMapMode, ShadowMode, TonemapMode,
     },
     shaders::Shaders,
     ImmutableLayouts, Layouts,
@@ -20,6 +20,7 @@ pub struct Pipelines {
     pub debug: debug::DebugPipeline,
     pub figure: figure::FigurePipeline,
     pub fluid: fluid::FluidPipeline,
+    pub ghost: ghost::GhostPipeline,
     pub lod_terrain: lod_terrain::LodTerrainPipeline,
     pub particle: particle::ParticlePipeline,
     pub trail: trail::TrailPipeline,
@@ -42,6 +43,7 @@ pub struct IngamePipelines {
     debug: debug::DebugPipeline,
     figure: figure::FigurePipeline,
     fluid: fluid::FluidPipeline,
+    ghost: ghost::GhostPipeline,
     lod_terrain: lod_terrain::LodTerrainPipeline,
     particle: particle::ParticlePipeline,
     trail: trail::TrailPipeline,
@@ -87,6 +89,7 @@ impl Pipelines {
             debug: ingame.debug,
             figure: ingame.figure,
             fluid: ingame.fluid,
+            ghost: ingame.ghost,
             lod_terrain: ingame.lod_terrain,
             particle: ingame.particle,
             trail: ingame.trail,
@@ -110,6 +113,8 @@ struct ShaderModules {
     skybox_frag: wgpu::ShaderModule,
     debug_vert: wgpu::ShaderModule,
     debug_frag: wgpu::ShaderModule,
+    ghost_vert: wgpu::ShaderModule,
+    ghost_frag: wgpu::ShaderModule,
     figure_vert: wgpu::ShaderModule,
     figure_frag: wgpu::ShaderModule,
     terrain_vert: wgpu::ShaderModule,
@@ -150,6 +155,7 @@ impl ShaderModules {
         shaders: &Shaders,
         pipeline_modes: &PipelineModes,
         has_shadow_views: bool,
+        cube_array_textures: bool,
     ) -> Result<Self, RenderError> {
         prof_span!(_guard, "ShaderModules::new");
         use shaderc::{CompileOptions, Compiler, OptimizationLevel, ResolvedInclude, ShaderKind};
@@ -175,6 +181,7 @@ impl ShaderModules {
 #define CLOUD_MODE {}
 #define LIGHTING_ALGORITHM {}
 #define SHADOW_MODE {}
+#define TONEMAP {}
 
 "#,
             &constants.0,
@@ -196,14 +203,41 @@ impl ShaderModules {
                 LightingMode::Ashikhmin => "LIGHTING_ALGORITHM_ASHIKHMIN",
                 LightingMode::BlinnPhong => "LIGHTING_ALGORITHM_BLINN_PHONG",
                 LightingMode::Lambertian => "LIGHTING_ALGORITHM_LAMBERTIAN",
+                LightingMode::Flat => "LIGHTING_ALGORITHM_FLAT",
             },
             match pipeline_modes.shadow {
                 ShadowMode::None => "SHADOW_MODE_NONE",
                 ShadowMode::Map(_) if has_shadow_views => "SHADOW_MODE_MAP",
                 ShadowMode::Cheap | ShadowMode::Map(_) => "SHADOW_MODE_CHEAP",
             },
+            match pipeline_modes.tonemap {
+                TonemapMode::Exponential => "TONEMAP_EXPONENTIAL",
+                TonemapMode::Reinhard => "TONEMAP_REINHARD",
+                TonemapMode::Aces => "TONEMAP_ACES",
+            },
+        );
+
+        if has_shadow_views
+            && matches!(
+                pipeline_modes.shadow,
+                ShadowMode::Map(ShadowMapMode { soft: true, .. })
+            )
+        {
+            constants += "#define SHADOW_SOFT\n";
+        }
+
+        constants += &format!(
+            "#define POINT_SHADOW_COUNT {}\n",
+            match pipeline_modes.shadow {
+                ShadowMode::Map(mode) if has_shadow_views => mode.point_lights(),
+                _ => 0,
+            }
         );
 
+        if cube_array_textures {
+            constants += "#define POINT_SHADOW_CUBE_ARRAY\n";
+        }
+
         if pipeline_modes.point_glow > f32::EPSILON {
             constants += &format!(
                 "\n#define POINT_GLOW_FACTOR {}\n",
@@ -211,6 +245,24 @@ impl ShaderModules {
             );
         }
 
+        if pipeline_modes.ambient_occlusion.enabled {
+            constants += &format!(
+                "#define AO_SAMPLES {}\n",
+                pipeline_modes.ambient_occlusion.samples()
+            );
+        }
+
+        if pipeline_modes.volumetric_fog.enabled {
+            constants += &format!(
+                "#define FOG_STEPS {}\n",
+                pipeline_modes.volumetric_fog.steps()
+            );
+        }
+
+        if pipeline_modes.screen_space_reflections() {
+            constants += "#define SCREEN_SPACE_REFLECTIONS\n";
+        }
+
         if pipeline_modes.flashing_lights_enabled {
             constants += "#define FLASHING_LIGHTS_ENABLED\n";
         }
@@ -307,6 +359,8 @@ impl ShaderModules {
             skybox_frag: create_shader("skybox-frag", ShaderKind::Fragment)?,
             debug_vert: create_shader("debug-vert", ShaderKind::Vertex)?,
             debug_frag: create_shader("debug-frag", ShaderKind::Fragment)?,
+            ghost_vert: create_shader("ghost-vert", ShaderKind::Vertex)?,
+            ghost_frag: create_shader("ghost-frag", ShaderKind::Fragment)?,
             figure_vert: create_shader("figure-vert", ShaderKind::Vertex)?,
             figure_frag: create_shader("figure-frag", ShaderKind::Fragment)?,
             terrain_vert: create_shader("terrain-vert", ShaderKind::Vertex)?,
@@ -447,7 +501,7 @@ fn create_ingame_and_shadow_pipelines(
     needs: PipelineNeeds,
     pool: &rayon::ThreadPool,
     // TODO: Reduce the boilerplate in this file
-    tasks: [Task; 18],
+    tasks: [Task; 19],
 ) -> IngameAndShadowPipelines {
     prof_span!(_guard, "create_ingame_and_shadow_pipelines");
 
@@ -461,6 +515,7 @@ fn create_ingame_and_shadow_pipelines(
 
     let [
         debug_task,
+        ghost_task,
         skybox_task,
         figure_task,
         terrain_task,
@@ -501,6 +556,22 @@ fn create_ingame_and_shadow_pipelines(
             "debug pipeline creation",
         )
     };
+    // Pipeline for rendering the ghosts of structures about to be built
+    let create_ghost = || {
+        ghost_task.run(
+            || {
+                ghost::GhostPipeline::new(
+                    device,
+                    &shaders.ghost_vert,
+                    &shaders.ghost_frag,
+                    &layouts.global,
+                    &layouts.ghost,
+                    pipeline_modes.aa,
+                )
+            },
+            "ghost pipeline creation",
+        )
+    };
     // Pipeline for rendering skyboxes
     let create_skybox = || {
         skybox_task.run(
@@ -558,7 +629,9 @@ fn create_ingame_and_shadow_pipelines(
                     &shaders.fluid_frag,
                     &layouts.global,
                     &layouts.terrain,
+                    &layouts.fluid_reflections,
                     pipeline_modes.aa,
+                    pipeline_modes.screen_space_reflections(),
                 )
             },
             "fluid pipeline creation",
@@ -799,7 +872,7 @@ fn create_ingame_and_shadow_pipelines(
 
     let j1 = || pool.join(create_debug, || pool.join(create_skybox, create_figure));
     let j2 = || pool.join(create_terrain, || pool.join(create_fluid, create_bloom));
-    let j3 = || pool.join(create_sprite, create_particle);
+    let j3 = || pool.join(create_sprite, || pool.join(create_particle, create_ghost));
     let j4 = || {
         pool.join(create_lod_terrain, || {
             pool.join(create_clouds, create_trail)
@@ -825,7 +898,7 @@ fn create_ingame_and_shadow_pipelines(
     let (
         (
             ((debug, (skybox, figure)), (terrain, (fluid, bloom))),
-            ((sprite, particle), (lod_terrain, (clouds, trail))),
+            ((sprite, (particle, ghost)), (lod_terrain, (clouds, trail))),
         ),
         (
             ((postprocess, point_shadow), (terrain_directed_shadow, figure_directed_shadow)),
@@ -841,6 +914,7 @@ fn create_ingame_and_shadow_pipelines(
             debug,
             figure,
             fluid,
+            ghost,
             lod_terrain,
             particle,
             trail,
@@ -878,6 +952,7 @@ pub(super) fn initial_create_pipelines(
     pipeline_modes: PipelineModes,
     sc_desc: wgpu::SwapChainDescriptor,
     has_shadow_views: bool,
+    cube_array_textures: bool,
 ) -> Result<
     (
         InterfacePipelines,
@@ -888,7 +963,13 @@ pub(super) fn initial_create_pipelines(
     prof_span!(_guard, "initial_create_pipelines");
 
     // Process shaders into modules
-    let shader_modules = ShaderModules::new(&device, &shaders, &pipeline_modes, has_shadow_views)?;
+    let shader_modules = ShaderModules::new(
+        &device,
+        &shaders,
+        &pipeline_modes,
+        has_shadow_views,
+        cube_array_textures,
+    )?;
 
     // Create threadpool for parallel portion
     let pool = rayon::ThreadPoolBuilder::new()
@@ -950,6 +1031,7 @@ pub(super) fn recreate_pipelines(
     pipeline_modes: PipelineModes,
     sc_desc: wgpu::SwapChainDescriptor,
     has_shadow_views: bool,
+    cube_array_textures: bool,
 ) -> PipelineCreation<
     Result<
         (
@@ -988,14 +1070,19 @@ pub(super) fn recreate_pipelines(
 
         // Process shaders into modules
         let guard = shader_task.start("process shaders");
-        let shader_modules =
-            match ShaderModules::new(&device, &shaders, &pipeline_modes, has_shadow_views) {
-                Ok(modules) => modules,
-                Err(err) => {
-                    result_send.send(Err(err)).expect("Channel disconnected");
-                    return;
-                },
-            };
+        let shader_modules = match ShaderModules::new(
+            &device,
+            &shaders,
+            &pipeline_modes,
+            has_shadow_views,
+            cube_array_textures,
+        ) {
+            Ok(modules) => modules,
+            Err(err) => {
+                result_send.send(Err(err)).expect("Channel disconnected");
+                return;
+            },
+        };
         drop(guard);
 
         // Create new postprocess layouts
@@ -24,15 +24,19 @@ impl TakeScreenshot {
         sampler: &wgpu::Sampler,
         // Used to determine the resolution and texture format
         sc_desc: &wgpu::SwapChainDescriptor,
+        // Times the resolution of the swap chain the screenshot is taken at
+        supersampling: u32,
         // Function that is given the image after downloading it from the GPU
         // This is executed in a background thread
         screenshot_fn: ScreenshotFn,
     ) -> Self {
+        let width = sc_desc.width * supersampling;
+        let height = sc_desc.height * supersampling;
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("screenshot tex"),
             size: wgpu::Extent3d {
-                width: sc_desc.width,
-                height: sc_desc.height,
+                width,
+                height,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
@@ -58,11 +62,11 @@ impl TakeScreenshot {
         let bind_group = blit_layout.bind(device, &view, sampler);
 
         let bytes_per_pixel = sc_desc.format.describe().block_size;
-        let padded_bytes_per_row = padded_bytes_per_row(sc_desc.width, bytes_per_pixel);
+        let padded_bytes_per_row = padded_bytes_per_row(width, bytes_per_pixel);
 
         let buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("screenshot download buffer"),
-            size: (padded_bytes_per_row * sc_desc.height) as u64,
+            size: (padded_bytes_per_row * height) as u64,
             usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
             mapped_at_creation: false,
         });
@@ -73,8 +77,8 @@ impl TakeScreenshot {
             view,
             buffer,
             screenshot_fn,
-            width: sc_desc.width,
-            height: sc_desc.height,
+            width,
+            height,
             bytes_per_pixel,
             tex_format: sc_desc.format,
         }
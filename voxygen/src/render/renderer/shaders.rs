@@ -53,6 +53,8 @@ impl assets::Compound for Shaders {
             "skybox-frag",
             "debug-vert",
             "debug-frag",
+            "ghost-vert",
+            "ghost-frag",
             "figure-frag",
             "terrain-vert",
             "terrain-frag",
@@ -33,6 +33,7 @@ impl ShadowMap {
         directed: Option<shadow::ShadowPipeline>,
         figure: Option<shadow::ShadowFigurePipeline>,
         shadow_views: Option<(Texture, Texture)>,
+        cube_array_textures: bool,
     ) -> Self {
         if let (
             Some(point_pipeline),
@@ -56,7 +57,8 @@ impl ShadowMap {
                 layout,
             })
         } else {
-            let (dummy_point, dummy_directed) = Self::create_dummy_shadow_tex(device, queue);
+            let (dummy_point, dummy_directed) =
+                Self::create_dummy_shadow_tex(device, queue, cube_array_textures);
             Self::Disabled {
                 dummy_point,
                 dummy_directed,
@@ -64,7 +66,11 @@ impl ShadowMap {
         }
     }
 
-    fn create_dummy_shadow_tex(device: &wgpu::Device, queue: &wgpu::Queue) -> (Texture, Texture) {
+    fn create_dummy_shadow_tex(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        cube_array_textures: bool,
+    ) -> (Texture, Texture) {
         let make_tex = |view_dim, depth| {
             let tex = wgpu::TextureDescriptor {
                 label: None,
@@ -106,7 +112,7 @@ impl ShadowMap {
             Texture::new_raw(device, &tex, &view, &sampler_info)
         };
 
-        let cube_tex = make_tex(wgpu::TextureViewDimension::Cube, 6);
+        let cube_tex = make_tex(point_view_dimension(cube_array_textures), 6);
         let tex = make_tex(wgpu::TextureViewDimension::D2, 1);
 
         // Clear to 1.0
@@ -142,6 +148,7 @@ impl ShadowMap {
         device: &wgpu::Device,
         size: (u32, u32),
         mode: &ShadowMapMode,
+        cube_array_textures: bool,
     ) -> Result<(Texture, Texture), RenderError> {
         // (Attempt to) apply resolution factor to shadow map resolution.
         let resolution_factor = mode.resolution.clamped(0.25, 4.0);
@@ -198,7 +205,9 @@ impl ShadowMap {
             size: wgpu::Extent3d {
                 width: diag_two_size / 4,
                 height: diag_two_size / 4,
-                depth_or_array_layers: 6,
+                // One cube per point light casting shadows, with at least one so that the
+                // texture is valid
+                depth_or_array_layers: 6 * mode.point_lights().max(1),
             },
             mip_level_count: levels,
             sample_count: 1,
@@ -210,7 +219,7 @@ impl ShadowMap {
         let point_shadow_view = wgpu::TextureViewDescriptor {
             label: None,
             format: Some(wgpu::TextureFormat::Depth24Plus),
-            dimension: Some(wgpu::TextureViewDimension::Cube),
+            dimension: Some(point_view_dimension(cube_array_textures)),
             aspect: wgpu::TextureAspect::DepthOnly,
             base_mip_level: 0,
             mip_level_count: None,
@@ -279,3 +288,14 @@ impl ShadowMap {
 
     pub fn is_enabled(&self) -> bool { matches!(self, Self::Enabled(_)) }
 }
+
+/// The point shadow maps are an array of cubes, one per point light casting
+/// shadows, unless the device can't sample cube map arrays in which case only
+/// one point light casts shadows into a single cube
+fn point_view_dimension(cube_array_textures: bool) -> wgpu::TextureViewDimension {
+    if cube_array_textures {
+        wgpu::TextureViewDimension::CubeArray
+    } else {
+        wgpu::TextureViewDimension::Cube
+    }
+}
@@ -126,9 +126,11 @@ fn handle_main_events_cleared(
 ) {
     span!(guard, "Handle MainEventsCleared");
     // Screenshot / Fullscreen toggle
-    global_state
-        .window
-        .resolve_deduplicated_events(&mut global_state.settings, &global_state.config_dir);
+    global_state.window.resolve_deduplicated_events(
+        &mut global_state.settings,
+        &global_state.config_dir,
+        &global_state.userdata_dir,
+    );
     // Run tick here
 
     // What's going on here?
@@ -13,6 +13,10 @@ const THIRD_PERSON_INTERP_TIME: f32 = 0.1;
 const FREEFLY_INTERP_TIME: f32 = 0.0;
 const LERP_ORI_RATE: f32 = 15.0;
 const CLIPPING_MODE_DISTANCE: f32 = 20.0;
+/// Largest angle the camera is shaken by, in radians
+const MAX_SHAKE_ANGLE: f32 = 0.03;
+/// Shake lost per second
+const SHAKE_DECAY: f32 = 2.5;
 pub const MIN_ZOOM: f32 = 0.1;
 
 // Possible TODO: Add more modes
@@ -53,6 +57,8 @@ pub struct Camera {
     fixate: f32,
     aspect: f32,
     mode: CameraMode,
+    /// How much the camera is shaken, between 0 and 1
+    shake: f32,
 
     last_time: Option<f64>,
 
@@ -338,6 +344,7 @@ impl Camera {
             fixate: 1.0,
             aspect,
             mode,
+            shake: 0.0,
 
             last_time: None,
 
@@ -467,12 +474,28 @@ impl Camera {
         self.dependents = dependents;
     }
 
+    /// Offset of the orientation of the camera while it is shaken
+    fn shake_offset(&self) -> Vec3<f32> {
+        if self.shake <= 0.0 {
+            return Vec3::zero();
+        }
+        // Overlapping waves of different frequencies look random enough
+        let t = self.last_time.unwrap_or(0.0) as f32;
+        Vec3::new(
+            (t * 47.0).sin() + (t * 23.0).sin() * 0.5,
+            (t * 53.0 + 1.0).sin() + (t * 29.0).sin() * 0.5,
+            (t * 41.0 + 2.0).sin() * 0.5,
+        ) * MAX_SHAKE_ANGLE
+            * self.shake.powi(2)
+    }
+
     fn compute_dependents_helper(&self, dist: f32) -> Dependents {
+        let ori = self.ori + self.shake_offset();
         let view_mat = Mat4::<f32>::identity()
             * Mat4::translation_3d(-Vec3::unit_z() * dist)
-            * Mat4::rotation_z(self.ori.z)
-            * Mat4::rotation_x(self.ori.y)
-            * Mat4::rotation_y(self.ori.x)
+            * Mat4::rotation_z(ori.z)
+            * Mat4::rotation_x(ori.y)
+            * Mat4::rotation_y(ori.x)
             * Mat4::rotation_3d(PI / 2.0, -Vec4::unit_x())
             * Mat4::translation_3d(-self.focus.map(|e| e.fract()));
         let view_mat_inv: Mat4<f32> = view_mat.inverted();
@@ -577,9 +600,14 @@ impl Camera {
     /// Set the distance of the camera from the focus (i.e., zoom).
     pub fn set_distance(&mut self, dist: f32) { self.tgt_dist = dist; }
 
+    /// Shakes the camera, by `amount` between 0 and 1 on top of the shake
+    /// left
+    pub fn shake(&mut self, amount: f32) { self.shake = (self.shake + amount).clamp(0.0, 1.0); }
+
     pub fn update(&mut self, time: f64, dt: f32, smoothing_enabled: bool) {
         // This is horribly frame time dependent, but so is most of the game
         let delta = self.last_time.replace(time).map_or(0.0, |t| time - t);
+        self.shake = (self.shake - SHAKE_DECAY * delta as f32).max(0.0);
         if (self.dist - self.tgt_dist).abs() > 0.01 {
             self.dist = Lerp::lerp(
                 self.dist,
@@ -663,6 +691,14 @@ impl Camera {
         self.focus = focus;
     }
 
+    /// Place the camera at `pos`, looking at `target`, without lerping.
+    pub fn look_from(&mut self, pos: Vec3<f32>, target: Vec3<f32>) {
+        let dir = (target - pos).try_normalized().unwrap_or_else(Vec3::unit_y);
+        self.force_focus_pos(pos);
+        self.dist = self.tgt_dist;
+        self.set_orientation_instant(Vec3::new(dir.x.atan2(dir.y), (-dir.z).asin(), 0.0));
+    }
+
     /// Get the aspect ratio of the camera.
     pub fn get_aspect_ratio(&self) -> f32 { self.aspect }
 
@@ -723,6 +759,16 @@ impl Camera {
         }
     }
 
+    /// Switch between the first and third person views of the player, leaving
+    /// the free camera as it is
+    pub fn toggle_first_person(&mut self) {
+        match self.mode {
+            CameraMode::ThirdPerson => self.set_mode(CameraMode::FirstPerson),
+            CameraMode::FirstPerson => self.set_mode(CameraMode::ThirdPerson),
+            CameraMode::Freefly => {},
+        }
+    }
+
     /// Cycle the camera to its next valid mode. If is_admin is false then only
     /// modes which are accessible without admin access will be cycled to.
     pub fn next_mode(&mut self, is_admin: bool, has_target: bool) {
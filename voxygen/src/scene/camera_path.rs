@@ -0,0 +1,150 @@
+//! Scripted paths for the camera to fly along, for trailers and intro
+//! sequences. A path is a list of keyframes the camera passes through at given
+//! times, each with a point to look at, and curves smoothly between them.
+
+use super::camera::CameraMode;
+use common::assets::{self, AssetExt};
+use serde::Deserialize;
+use vek::*;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Keyframe {
+    /// Seconds since the start of the path
+    pub time: f32,
+    pub pos: (f32, f32, f32),
+    /// Point the camera looks at
+    pub look_at: (f32, f32, f32),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CameraPath {
+    /// Whether the positions are measured from the player rather than from
+    /// the origin of the world
+    #[serde(default)]
+    pub relative: bool,
+    /// Keyframes of the path, in the order of their time
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl assets::Asset for CameraPath {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+/// Catmull-Rom spline through `p1` and `p2`, `p0` and `p3` being the points
+/// before and after them
+fn catmull_rom(p0: Vec3<f32>, p1: Vec3<f32>, p2: Vec3<f32>, p3: Vec3<f32>, t: f32) -> Vec3<f32> {
+    let (t2, t3) = (t * t, t * t * t);
+    (p1 * 2.0
+        + (p2 - p0) * t
+        + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+        + (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+        * 0.5
+}
+
+impl CameraPath {
+    pub fn load(name: &str) -> Result<Self, String> {
+        Self::load_cloned(&format!("voxygen.camera_paths.{}", name))
+            .map_err(|_| format!("Could not find a camera path named {}.", name))
+    }
+
+    /// Seconds the path lasts
+    pub fn duration(&self) -> f32 { self.keyframes.last().map_or(0.0, |key| key.time) }
+
+    /// Position of the camera and the point it looks at `time` seconds into
+    /// the path
+    pub fn sample(&self, time: f32) -> Option<(Vec3<f32>, Vec3<f32>)> {
+        let keys = &self.keyframes;
+        let last = keys.len().checked_sub(1)?;
+        let next = keys
+            .iter()
+            .position(|key| key.time > time)
+            .unwrap_or(last)
+            .max(1)
+            .min(last);
+        let prev = next.saturating_sub(1);
+        let span = keys[next].time - keys[prev].time;
+        let t = if span > 0.0 {
+            ((time - keys[prev].time) / span).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let around = [prev.saturating_sub(1), prev, next, (next + 1).min(last)];
+        let curve = |point: fn(&Keyframe) -> (f32, f32, f32)| {
+            let [p0, p1, p2, p3] = around.map(|i| Vec3::from(point(&keys[i])));
+            catmull_rom(p0, p1, p2, p3, t)
+        };
+        Some((curve(|key| key.pos), curve(|key| key.look_at)))
+    }
+}
+
+/// A camera path being played, with the rest of the HUD hidden
+pub struct Cutscene {
+    path: CameraPath,
+    /// Where the positions of a relative path are measured from
+    origin: Vec3<f32>,
+    time: f32,
+    /// Mode of the camera to return to once the cutscene is over
+    pub previous_mode: Option<CameraMode>,
+}
+
+impl Cutscene {
+    pub fn new(path: CameraPath, player_pos: Vec3<f32>) -> Self {
+        let origin = if path.relative {
+            player_pos
+        } else {
+            Vec3::zero()
+        };
+        Self {
+            path,
+            origin,
+            time: 0.0,
+            previous_mode: None,
+        }
+    }
+
+    /// Advances the cutscene by `dt` seconds, returning the position of the
+    /// camera and the point it looks at, or `None` once it is over
+    pub fn tick(&mut self, dt: f32) -> Option<(Vec3<f32>, Vec3<f32>)> {
+        if self.time > self.path.duration() {
+            return None;
+        }
+        let (pos, look_at) = self.path.sample(self.time)?;
+        self.time += dt;
+        Some((self.origin + pos, self.origin + look_at))
+    }
+
+    /// Ends the cutscene on its next tick
+    pub fn stop(&mut self) { self.time = f32::INFINITY; }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_passes_through_its_keyframes() {
+        let key = |time, x| Keyframe {
+            time,
+            pos: (x, 0.0, 10.0),
+            look_at: (x, 10.0, 0.0),
+        };
+        let path = CameraPath {
+            relative: true,
+            keyframes: vec![key(0.0, 0.0), key(2.0, 4.0), key(3.0, 8.0)],
+        };
+        for key in &path.keyframes {
+            let (pos, look_at) = path.sample(key.time).unwrap();
+            assert!(pos.distance(Vec3::from(key.pos)) < 1e-4);
+            assert!(look_at.distance(Vec3::from(key.look_at)) < 1e-4);
+        }
+        let (halfway, _) = path.sample(1.0).unwrap();
+        assert!(0.0 < halfway.x && halfway.x < 4.0);
+
+        let mut cutscene = Cutscene::new(path, Vec3::new(100.0, 0.0, 0.0));
+        assert_eq!(cutscene.tick(0.0).map(|(pos, _)| pos.x), Some(100.0));
+        cutscene.stop();
+        assert!(cutscene.tick(0.1).is_none());
+    }
+}
@@ -0,0 +1,217 @@
+//! Colour grading of the final image, giving the biomes and the times of day
+//! their own mood: swamps greener and murkier, deserts warm and bright, nights
+//! blue. The lookup tables of the biome the player is in and of the time of day
+//! are blended together on the CPU, as they are tiny, and the result is
+//! uploaded whenever it changes noticeably. The filter of photo mode is applied
+//! over the blended table.
+
+use super::photo_mode::PhotoFilter;
+use crate::render::{identity_lut, Renderer, LUT_SIZE};
+use common::{
+    assets::{self, AssetExt},
+    terrain::BiomeKind,
+};
+use hashbrown::HashMap;
+use serde::Deserialize;
+use tracing::warn;
+use vek::*;
+
+/// Seconds it takes for the grading to mostly change over when entering
+/// another biome
+const TRANSITION_TIME: f32 = 4.0;
+/// Change in the weight of a table past which the blended table is uploaded
+/// again
+const UPLOAD_THRESHOLD: f32 = 0.005;
+
+#[derive(Deserialize)]
+struct ColorGradingManifest {
+    biomes: HashMap<BiomeKind, String>,
+    night: String,
+    /// Used for dawn too
+    dusk: String,
+}
+
+impl assets::Asset for ColorGradingManifest {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+/// Lookup table, stored as how far it moves each colour of the identity table
+struct Layer {
+    offsets: Vec<Rgb<f32>>,
+    weight: f32,
+}
+
+fn to_colors(lut: &[[u8; 4]]) -> Vec<Rgb<f32>> {
+    lut.iter()
+        .map(|[r, g, b, _]| Rgb::new(*r, *g, *b).map(|e| e as f32 / 255.0))
+        .collect()
+}
+
+fn load_lut(specifier: &str) -> Option<Vec<[u8; 4]>> {
+    let image = match assets::Image::load(specifier) {
+        Ok(image) => image.read().to_image().to_rgba8(),
+        Err(err) => {
+            warn!(?err, ?specifier, "Could not load colour grading table");
+            return None;
+        },
+    };
+    if image.dimensions() != (LUT_SIZE * LUT_SIZE, LUT_SIZE) {
+        warn!(?specifier, "Colour grading table of the wrong size");
+        return None;
+    }
+    Some(image.pixels().map(|pixel| pixel.0).collect())
+}
+
+pub struct ColorGrading {
+    identity: Vec<Rgb<f32>>,
+    biomes: Vec<(BiomeKind, Layer)>,
+    night: Layer,
+    dusk: Layer,
+    /// Weights of the tables the uploaded table was blended with, and the
+    /// filter applied over it
+    uploaded: Option<(Vec<f32>, PhotoFilter)>,
+}
+
+impl ColorGrading {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let manifest = ColorGradingManifest::load_expect("voxygen.color_grading.manifest").read();
+        let biomes = manifest
+            .biomes
+            .iter()
+            .filter_map(|(biome, specifier)| Some((*biome, load_lut(specifier)?)))
+            .collect();
+        let identity = identity_lut();
+        Self::from_tables(
+            biomes,
+            &load_lut(&manifest.night).unwrap_or_else(|| identity.clone()),
+            &load_lut(&manifest.dusk).unwrap_or(identity),
+        )
+    }
+
+    fn from_tables(
+        biomes: Vec<(BiomeKind, Vec<[u8; 4]>)>,
+        night: &[[u8; 4]],
+        dusk: &[[u8; 4]],
+    ) -> Self {
+        let identity = to_colors(&identity_lut());
+        let layer = |lut: &[[u8; 4]]| Layer {
+            offsets: to_colors(lut)
+                .into_iter()
+                .zip(&identity)
+                .map(|(color, identity)| color - *identity)
+                .collect(),
+            weight: 0.0,
+        };
+        Self {
+            biomes: biomes
+                .iter()
+                .map(|(biome, lut)| (*biome, layer(lut)))
+                .collect(),
+            night: layer(night),
+            dusk: layer(dusk),
+            identity,
+            uploaded: None,
+        }
+    }
+
+    fn layers(&self) -> impl Iterator<Item = &Layer> {
+        self.biomes
+            .iter()
+            .map(|(_, layer)| layer)
+            .chain([&self.night, &self.dusk])
+    }
+
+    /// Fades the tables towards those of the biome and of the time of day,
+    /// `strength` scaling the whole grading down.
+    pub fn maintain(
+        &mut self,
+        renderer: &mut Renderer,
+        biome: BiomeKind,
+        sun_dir: Vec3<f32>,
+        dt: f32,
+        strength: f32,
+        filter: PhotoFilter,
+    ) {
+        let fade = (dt / TRANSITION_TIME).min(1.0);
+        for (kind, layer) in self.biomes.iter_mut() {
+            let target = if *kind == biome { 1.0 } else { 0.0 };
+            layer.weight += (target - layer.weight) * fade;
+        }
+        // The sun shines downwards during the day
+        let sun_height = -sun_dir.z;
+        self.night.weight = (-sun_height * 4.0).clamp(0.0, 1.0);
+        self.dusk.weight = (1.0 - sun_height.abs() * 4.0).clamp(0.0, 1.0);
+
+        let weights = self
+            .layers()
+            .map(|layer| layer.weight * strength.clamp(0.0, 1.0))
+            .collect::<Vec<_>>();
+        let changed = self
+            .uploaded
+            .as_ref()
+            .map_or(true, |(uploaded, uploaded_filter)| {
+                *uploaded_filter != filter
+                    || uploaded
+                        .iter()
+                        .zip(&weights)
+                        .any(|(uploaded, weight)| (uploaded - weight).abs() > UPLOAD_THRESHOLD)
+            });
+        if changed {
+            renderer.update_color_grading(&self.blend(&weights, filter));
+            self.uploaded = Some((weights, filter));
+        }
+    }
+
+    fn blend(&self, weights: &[f32], filter: PhotoFilter) -> Vec<[u8; 4]> {
+        self.identity
+            .iter()
+            .enumerate()
+            .map(|(i, identity)| {
+                let color = self
+                    .layers()
+                    .zip(weights)
+                    .fold(*identity, |color, (layer, weight)| {
+                        color + layer.offsets[i] * *weight
+                    });
+                let color = filter
+                    .apply(color.map(|e| e.clamp(0.0, 1.0)))
+                    .map(|e| (e * 255.0).round() as u8);
+                [color.r, color.g, color.b, 255]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blending_fades_between_the_tables() {
+        let inverted = identity_lut()
+            .into_iter()
+            .map(|[r, g, b, a]| [255 - r, 255 - g, 255 - b, a])
+            .collect::<Vec<_>>();
+        let grading = ColorGrading::from_tables(
+            vec![(BiomeKind::Swamp, inverted.clone())],
+            &inverted,
+            &inverted,
+        );
+
+        assert_eq!(
+            grading.blend(&[0.0, 0.0, 0.0], PhotoFilter::None),
+            identity_lut()
+        );
+        assert_eq!(grading.blend(&[1.0, 0.0, 0.0], PhotoFilter::None), inverted);
+        // Halfway between a colour and its inverse is grey
+        assert!(
+            grading
+                .blend(&[0.0, 0.5, 0.0], PhotoFilter::None)
+                .iter()
+                .all(|[r, g, b, _]| [r, g, b].iter().all(|e| (127..=128).contains(*e)))
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! How the poses of the animations of the figures blend into each other: the
+//! skeletons ease towards the pose of their current animation, crossfade more
+//! slowly from the previous one when the character state changes, and the
+//! humanoids keep running with their lower body while they attack.
+
+use common::assets::{self, AssetExt};
+use serde::Deserialize;
+
+#[derive(Clone, Deserialize)]
+pub struct AnimationBlending {
+    /// Seconds for a skeleton to settle into the pose of its animation
+    settle_time: f32,
+    /// Seconds to crossfade from the previous animation once the character
+    /// state changes
+    transition_time: f32,
+    /// Bones of the humanoids that keep the pose of their movement while they
+    /// attack on the move
+    pub lower_body: Vec<String>,
+}
+
+impl assets::Asset for AnimationBlending {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+impl AnimationBlending {
+    pub fn load() -> Self { Self::load_expect_cloned("voxygen.animation_blending") }
+
+    /// Factor to lerp a skeleton towards the pose of its animation with after
+    /// `dt` seconds, `state_time` being the time spent in the current character
+    /// state
+    pub fn lerp_factor(&self, state_time: f32, dt: f32) -> f32 {
+        let blend_time = if state_time < self.transition_time {
+            self.transition_time
+        } else {
+            self.settle_time
+        };
+        if blend_time > 0.0 {
+            1.0 - (-dt / blend_time).exp()
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transitions_blend_slower_than_settling() {
+        let blending = AnimationBlending {
+            settle_time: 0.05,
+            transition_time: 0.2,
+            lower_body: Vec::new(),
+        };
+        let dt = 1.0 / 60.0;
+        let transition = blending.lerp_factor(0.0, dt);
+        let settle = blending.lerp_factor(1.0, dt);
+        assert!(0.0 < transition && transition < settle && settle < 1.0);
+        // Blending over two frames is the same as over one frame twice as long
+        let twice = 1.0 - (1.0 - settle) * (1.0 - settle);
+        assert!((blending.lerp_factor(1.0, 2.0 * dt) - twice).abs() < 1e-5);
+    }
+}
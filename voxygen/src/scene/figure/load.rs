@@ -468,16 +468,20 @@ make_vox_spec!(
                 color,
                 hand,
             )),
-            Some(spec.armor_foot.read().0.mesh_left_foot(
-                body,
-                color,
-                foot,
-            )),
-            Some(spec.armor_foot.read().0.mesh_right_foot(
-                body,
-                color,
-                foot,
-            )),
+            third_person.map(|_| {
+                spec.armor_foot.read().0.mesh_left_foot(
+                    body,
+                    color,
+                    foot,
+                )
+            }),
+            third_person.map(|_| {
+                spec.armor_foot.read().0.mesh_right_foot(
+                    body,
+                    color,
+                    foot,
+                )
+            }),
             third_person.map(|loadout| {
                 spec.armor_shoulder.read().0.mesh_left_shoulder(
                     body,
@@ -492,11 +496,13 @@ make_vox_spec!(
                     loadout.shoulder.as_deref(),
                 )
             }),
-            Some(spec.armor_glider.read().0.mesh_glider(
-                body,
-                color,
-                glider,
-            )),
+            third_person.map(|_| {
+                spec.armor_glider.read().0.mesh_glider(
+                    body,
+                    color,
+                    glider,
+                )
+            }),
             tool.and_then(|tool| tool.active.as_ref()).map(|tool| {
                 spec.main_weapon.read().0.mesh_main_weapon(
                     tool,
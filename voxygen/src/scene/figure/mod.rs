@@ -1,7 +1,9 @@
+mod blend;
 mod cache;
 pub mod load;
 mod volume;
 
+use blend::AnimationBlending;
 pub use cache::FigureModelCache;
 pub use load::load_mesh; // TODO: Don't make this public.
 pub use volume::VolumeKey;
@@ -17,6 +19,7 @@ use crate::{
         camera::{Camera, CameraMode, Dependents},
         math,
         terrain::Terrain,
+        trail::trail_color,
         SceneData, TrailMgr, RAIN_THRESHOLD,
     },
 };
@@ -33,8 +36,9 @@ use common::{
     comp::{
         inventory::slot::EquipSlot,
         item::{Hands, ItemKind, ToolKind},
-        Body, CharacterState, Collider, Controller, Health, Inventory, Item, ItemKey, Last,
-        LightAnimation, LightEmitter, Ori, PhysicsState, PoiseState, Pos, Scale, Vel,
+        AttachPoint, Attached, Body, CharacterState, Collider, Controller, Health, Inventory, Item,
+        ItemKey, Last, LightAnimation, LightEmitter, Ori, PhysicsState, PoiseState, Pos, Scale,
+        Vel,
     },
     link::Is,
     mounting::Rider,
@@ -62,6 +66,21 @@ use vek::*;
 const DAMAGE_FADE_COEFFICIENT: f64 = 15.0;
 const MOVING_THRESHOLD: f32 = 0.2;
 const MOVING_THRESHOLD_SQR: f32 = MOVING_THRESHOLD * MOVING_THRESHOLD;
+/// Offset from the eyes of the first person figure, relative to the camera
+/// (right, forward, up), bringing the hands into the bottom of the view
+const VIEWMODEL_OFFSET: [f32; 3] = [0.0, 0.4, 0.3];
+
+/// Bone of the skeleton of the body entities attached to the point follow
+fn attachment_bone(body: &Body, point: AttachPoint) -> Option<usize> {
+    match (body, point) {
+        (Body::Humanoid(_), AttachPoint::Head) => Some(0),
+        (Body::Humanoid(_), AttachPoint::Back) => Some(3),
+        (Body::Humanoid(_), AttachPoint::MainHand) => Some(5),
+        (Body::Humanoid(_), AttachPoint::OffHand) => Some(6),
+        (Body::Humanoid(_), AttachPoint::Shoulder) => Some(10),
+        _ => None,
+    }
+}
 
 /// camera data, figure LOD render distance.
 pub type CameraData<'a> = (&'a Camera, f32);
@@ -515,6 +534,15 @@ impl FigureMgr {
         self.arthropod_model_cache.clean(&mut self.col_lights, tick);
     }
 
+    /// Keeps showing every figure as it was last updated, for photo mode, in
+    /// which the camera moves around figures which are no longer maintained
+    pub fn freeze(&mut self) {
+        self.states.retain(|_, state| {
+            state.visible = true;
+            true
+        });
+    }
+
     pub fn update_lighting(&mut self, scene_data: &SceneData) {
         span!(_guard, "update_lighting", "FigureManager::update_lighting");
         let ecs = scene_data.state.ecs();
@@ -631,7 +659,14 @@ impl FigureMgr {
         let ecs = state.ecs();
         let view_distance = scene_data.entity_view_distance;
         let dt = state.get_delta_time();
-        let dt_lerp = (15.0 * dt).min(1.0);
+        let blending = AnimationBlending::load();
+        let lower_body = CharacterSkeleton::mask(
+            &blending
+                .lower_body
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+        );
         let frustum = camera.frustum();
 
         // Sun shadows--find the bounding box of the shadow map plane (i.e. the bounds
@@ -697,8 +732,8 @@ impl FigureMgr {
             (
                 can_shadow(sun_dir, can_shadow_sun, visible_psr_bounds),
                 can_shadow(
-                    weather.rain_vel(),
-                    weather.rain > RAIN_THRESHOLD,
+                    weather.precipitation().1,
+                    weather.rain.max(weather.snow) > RAIN_THRESHOLD,
                     visible_por_bounds,
                 ),
             )
@@ -725,6 +760,7 @@ impl FigureMgr {
         let uid_allocator = ecs.read_resource::<UidAllocator>();
 
         let bodies = ecs.read_storage::<Body>();
+        let attacheds = ecs.read_storage::<Attached>();
 
         for (
             i,
@@ -921,7 +957,23 @@ impl FigureMgr {
                 let body = *bodies.get(mount)?;
                 let meta = self.states.get_mut(&body, &mount)?;
                 Some((meta.mount_transform, meta.mount_world_pos))
-            })();
+            })()
+            // Attached entities follow the bone of the entity they are attached to, when the
+            // point matches one
+            .or_else(|| {
+                let attached = attacheds.get(entity)?;
+                let anchor = uid_allocator.retrieve_entity_internal(attached.to.into())?;
+                let body = *bodies.get(anchor)?;
+                let meta = self.states.get_mut(&body, &anchor)?;
+                let index = AttachPoint::ALL.iter().position(|p| *p == attached.point)?;
+                let bone_mat = meta.attachment_mats[index]?;
+                let offset = anim::vek::Vec3::<f32>::from(attached.offset.into_array());
+                let transform = anim::vek::Transform {
+                    position: bone_mat.mul_point(anim::vek::Vec3::zero()) + meta.last_ori * offset,
+                    ..Default::default()
+                };
+                Some((transform, meta.mount_world_pos))
+            });
 
             let body = *body;
 
@@ -941,7 +993,7 @@ impl FigureMgr {
                 _lpindex: lpindex,
                 _visible: in_frustum,
                 is_player: is_viewpoint,
-                _camera: camera,
+                camera,
                 terrain,
                 ground_vel: physics.ground_vel,
             };
@@ -1858,10 +1910,31 @@ impl FigureMgr {
                                 skeleton_attr,
                             )
                         },
-                        _ => target_base,
+                        _ => target_base.clone(),
+                    };
+                    // Keep running with the lower body while attacking on the move
+                    let target_bones = if character.is_attack()
+                        && !matches!(
+                            character,
+                            CharacterState::DashMelee(_)
+                                | CharacterState::LeapMelee(_)
+                                | CharacterState::SpinMelee(_)
+                        )
+                        && physics.on_ground.is_some()
+                        && rel_vel.magnitude_squared() > 0.01
+                        && physics.in_liquid().is_none()
+                        && is_rider.is_none()
+                    {
+                        target_bones.layered(&target_base, lower_body, 1.0)
+                    } else {
+                        target_bones
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -2061,7 +2134,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -2378,7 +2455,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -2736,7 +2817,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -2840,7 +2925,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -2923,7 +3012,11 @@ impl FigureMgr {
                         ),
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_base, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_base,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -3450,7 +3543,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -3537,7 +3634,11 @@ impl FigureMgr {
                         ),
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_base, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_base,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -3716,7 +3817,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -4008,7 +4113,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -4331,7 +4440,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -4414,7 +4527,11 @@ impl FigureMgr {
                         ),
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_base, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_base,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -5036,7 +5153,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -5277,7 +5398,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -5398,7 +5523,11 @@ impl FigureMgr {
                         _ => target_base,
                     };
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -5454,7 +5583,11 @@ impl FigureMgr {
                         skeleton_attr,
                     );
 
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -5576,7 +5709,11 @@ impl FigureMgr {
                     };
 
                     let target_bones = target_base;
-                    state.skeleton = Lerp::lerp(&state.skeleton, &target_bones, dt_lerp);
+                    state.skeleton = Lerp::lerp(
+                        &state.skeleton,
+                        &target_bones,
+                        blending.lerp_factor(state.state_time, dt),
+                    );
                     state.update(
                         renderer,
                         trail_mgr,
@@ -6311,6 +6448,9 @@ pub struct FigureStateMeta {
     // mount chains
     // For use if it is mounted by another figure
     mount_world_pos: anim::vek::Vec3<f32>,
+    /// Bones entities attached to this figure follow, relative to its position,
+    /// for each of the points of `AttachPoint::ALL`
+    attachment_mats: [Option<anim::vek::Mat4<f32>>; AttachPoint::ALL.len()],
     state_time: f32,
     last_ori: anim::vek::Quaternion<f32>,
     lpindex: u8,
@@ -6371,8 +6511,7 @@ pub struct FigureUpdateCommonParameters<'a> {
     // TODO: evaluate unused variable
     pub _visible: bool,
     pub is_player: bool,
-    // TODO: evaluate unused variable
-    pub _camera: &'a Camera,
+    pub camera: &'a Camera,
     pub terrain: Option<&'a Terrain>,
     pub ground_vel: Vec3<f32>,
 }
@@ -6390,6 +6529,7 @@ impl<S: Skeleton> FigureState<S> {
                 off_abs_trail_points: None,
                 mount_transform: offsets.mount_bone,
                 mount_world_pos: anim::vek::Vec3::zero(),
+                attachment_mats: [None; AttachPoint::ALL.len()],
                 state_time: 0.0,
                 last_ori: Ori::default().into(),
                 lpindex: 0,
@@ -6425,7 +6565,7 @@ impl<S: Skeleton> FigureState<S> {
             _lpindex,
             _visible,
             is_player,
-            _camera,
+            camera,
             terrain,
             ground_vel,
         }: &FigureUpdateCommonParameters,
@@ -6492,6 +6632,20 @@ impl<S: Skeleton> FigureState<S> {
                     ..transform
                 };
                 anim::vek::Mat4::from(transform) * rider_offset * scale_mat
+            } else if *is_player && camera.get_mode() == CameraMode::FirstPerson {
+                // In first person the figure is turned with the camera around the eyes, so
+                // that the hands and what they hold stay in view wherever it looks
+                let cam_ori = camera.get_orientation();
+                let eye_height = body.map_or(0.0, |body| body.eye_height()) * *scale;
+                anim::vek::Mat4::<f32>::translation_3d(
+                    anim::vek::Vec3::from(camera.get_focus_pos().into_array()) - *pos,
+                ) * anim::vek::Mat4::rotation_z(-cam_ori.x)
+                    * anim::vek::Mat4::rotation_x(-cam_ori.y)
+                    * anim::vek::Mat4::translation_3d(
+                        anim::vek::Vec3::from(VIEWMODEL_OFFSET) * *scale
+                            - anim::vek::Vec3::unit_z() * eye_height,
+                    )
+                    * scale_mat
             } else {
                 let ori_mat = anim::vek::Mat4::from(*ori);
                 ori_mat * scale_mat
@@ -6585,8 +6739,10 @@ impl<S: Skeleton> FigureState<S> {
             {
                 let trail_mgr_offset = trail_mgr.offset();
                 let quad_mesh = trail_mgr.entity_mesh_or_insert(entity, is_main_weapon);
+                let color = trail_color(tool);
                 let vertex = |p: anim::vek::Vec3<f32>| trail::Vertex {
                     pos: p.into_array(),
+                    color,
                 };
                 let quad = Quad::new(vertex(*p1), vertex(*p2), vertex(p3), vertex(p4));
                 quad_mesh.replace_quad(trail_mgr_offset * 4, quad);
@@ -6618,6 +6774,11 @@ impl<S: Skeleton> FigureState<S> {
         // TODO: compute the mount bone only when it is needed
         self.mount_transform = offsets.mount_bone;
         self.mount_world_pos = pos_with_mount_offset;
+        self.attachment_mats = AttachPoint::ALL.map(|point| {
+            body.as_ref()
+                .and_then(|body| attachment_bone(body, point))
+                .map(|bone| anim::vek::Mat4::from_col_arrays(buf[bone].0))
+        });
 
         let smoothing = (5.0 * dt).min(1.0);
         if let Some(last_pos) = self.last_pos {
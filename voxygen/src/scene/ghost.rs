@@ -0,0 +1,151 @@
+use crate::render::{
+    GhostBoundLocals, GhostDrawer, GhostLocals, GhostVertex, Mesh, Model, Quad, Renderer,
+};
+use common::{blueprint::Blueprint, terrain::Block, util::srgb_to_linear};
+use hashbrown::HashSet;
+use vek::*;
+
+/// Opacity of the ghost
+const GHOST_ALPHA: f32 = 0.4;
+/// Tint of the ghost when the structure can't be built where it is
+const BLOCKED_TINT: Rgb<f32> = Rgb::new(1.0, 0.25, 0.2);
+
+/// Faces of a block as the direction they face, an offset to their first
+/// corner and the two edges from it, counter-clockwise seen from the outside
+const FACES: [(Vec3<i32>, Vec3<f32>, Vec3<f32>, Vec3<f32>); 6] = [
+    (
+        Vec3::new(1, 0, 0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+    ),
+    (
+        Vec3::new(-1, 0, 0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ),
+    (
+        Vec3::new(0, 1, 0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec3::new(1.0, 0.0, 0.0),
+    ),
+    (
+        Vec3::new(0, -1, 0),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+    ),
+    (
+        Vec3::new(0, 0, 1),
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ),
+    (
+        Vec3::new(0, 0, -1),
+        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+    ),
+];
+
+/// A structure about to be built, shown where it will be
+#[derive(Clone, Debug, PartialEq)]
+pub struct GhostPreview {
+    /// Item id of the blueprint of the structure
+    pub blueprint: String,
+    pub pos: Vec3<i32>,
+    /// Whether the structure can't be built there
+    pub blocked: bool,
+}
+
+/// Draws the see-through preview of the structure a blueprint builds
+#[derive(Default)]
+pub struct Ghost {
+    preview: Option<GhostPreview>,
+    model: Option<(String, Model<GhostVertex>)>,
+    locals: Option<GhostBoundLocals>,
+}
+
+impl Ghost {
+    pub fn set_preview(&mut self, preview: Option<GhostPreview>) { self.preview = preview; }
+
+    pub fn maintain(&mut self, renderer: &mut Renderer) {
+        let preview = match &self.preview {
+            Some(preview) => preview,
+            None => return,
+        };
+        // The blocks are only meshed again once another blueprint is previewed
+        if self
+            .model
+            .as_ref()
+            .map_or(true, |(blueprint, _)| *blueprint != preview.blueprint)
+        {
+            self.model = Blueprint::of_item(&preview.blueprint)
+                .and_then(|blueprint| renderer.create_model(&mesh(&blueprint.read().blocks())))
+                .map(|model| (preview.blueprint.clone(), model));
+        }
+        let tint = if preview.blocked {
+            BLOCKED_TINT
+        } else {
+            Rgb::broadcast(1.0)
+        };
+        let locals = GhostLocals::new(
+            preview.pos.as_(),
+            Rgba::from_translucent(srgb_to_linear(tint), GHOST_ALPHA),
+        );
+        match &mut self.locals {
+            Some(bound) => renderer.update_consts(bound, &[locals]),
+            None => self.locals = Some(renderer.create_ghost_bound_locals(&[locals])),
+        }
+    }
+
+    pub fn render<'a>(&'a self, drawer: &mut GhostDrawer<'_, 'a>) {
+        if let (Some(preview), Some((blueprint, model)), Some(locals)) =
+            (&self.preview, &self.model, &self.locals)
+        {
+            if *blueprint == preview.blueprint {
+                drawer.draw(model, locals);
+            }
+        }
+    }
+}
+
+/// Meshes the outer faces of the blocks, sprites being shown as smaller boxes
+fn mesh(blocks: &[(Vec3<i32>, Block)]) -> Mesh<GhostVertex> {
+    let filled = blocks
+        .iter()
+        .filter(|(_, block)| block.is_filled())
+        .map(|(pos, _)| *pos)
+        .collect::<HashSet<_>>();
+    let mut mesh = Mesh::new();
+    for &(pos, block) in blocks {
+        let (min, size, color) = match block.get_color() {
+            Some(color) if block.is_filled() => (
+                pos.as_::<f32>(),
+                Vec3::one(),
+                srgb_to_linear(color.map(|e| e as f32 / 255.0)),
+            ),
+            _ => (
+                pos.as_::<f32>() + Vec3::new(0.25, 0.25, 0.0),
+                Vec3::broadcast(0.5),
+                Rgb::broadcast(0.8),
+            ),
+        };
+        for &(dir, offset, u, v) in FACES.iter() {
+            if block.is_filled() && filled.contains(&(pos + dir)) {
+                continue;
+            }
+            let corner = |c: Vec3<f32>| GhostVertex::new(min + c * size, dir.as_(), color);
+            mesh.push_quad(Quad::new(
+                corner(offset),
+                corner(offset + u),
+                corner(offset + u + v),
+                corner(offset + v),
+            ));
+        }
+    }
+    mesh
+}
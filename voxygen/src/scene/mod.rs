@@ -1,9 +1,13 @@
 pub mod camera;
+pub mod camera_path;
+pub mod color_grading;
 pub mod debug;
 pub mod figure;
+pub mod ghost;
 pub mod lod;
 pub mod math;
 pub mod particle;
+pub mod photo_mode;
 pub mod simple;
 pub mod smoke_cycle;
 pub mod terrain;
@@ -11,10 +15,13 @@ pub mod trail;
 
 pub use self::{
     camera::{Camera, CameraMode},
+    color_grading::ColorGrading,
     debug::{Debug, DebugShape, DebugShapeId},
     figure::FigureMgr,
+    ghost::{Ghost, GhostPreview},
     lod::Lod,
     particle::ParticleMgr,
+    photo_mode::PhotoFilter,
     terrain::{SpriteRenderContextLazy, Terrain},
     trail::TrailMgr,
 };
@@ -22,10 +29,10 @@ use crate::{
     audio::{ambient, ambient::AmbientMgr, music::MusicMgr, sfx::SfxMgr, AudioFrontend},
     render::{
         create_skybox_mesh, CloudsLocals, Consts, Drawer, GlobalModel, Globals, GlobalsBindGroup,
-        Light, Model, PointLightMatrix, PostProcessLocals, RainOcclusionLocals, Renderer, Shadow,
-        ShadowLocals, SkyboxVertex,
+        Light, LightingMode, Model, PointLightMatrix, PostProcessLocals, RainOcclusionLocals,
+        Renderer, Shadow, ShadowLocals, ShadowMode, SkyboxVertex,
     },
-    settings::Settings,
+    settings::{PhotoModeSettings, Settings},
     window::{AnalogGameInput, Event},
 };
 use client::Client;
@@ -55,6 +62,8 @@ const MAX_LIGHT_COUNT: usize = 20; // 31 (total shadow_mats is limited to 128 wi
 const MAX_SHADOW_COUNT: usize = 24;
 const NUM_DIRECTED_LIGHTS: usize = 1;
 const LIGHT_DIST_RADIUS: f32 = 64.0; // The distance beyond which lights may not emit light from their origin
+const BURNING_LIGHT_COL: Rgb<f32> = Rgb::new(1.0, 0.4, 0.1);
+const BURNING_LIGHT_STRENGTH: f32 = 2.0;
 const SHADOW_DIST_RADIUS: f32 = 8.0;
 const SHADOW_MAX_DIST: f32 = 96.0; // The distance beyond which shadows may not be visible
 /// The minimum sin γ we will use before switching to uniform mapping.
@@ -93,6 +102,7 @@ pub struct Scene {
     skybox: Skybox,
     terrain: Terrain<TerrainChunk>,
     pub debug: Debug,
+    pub ghost: Ghost,
     pub lod: Lod,
     loaded_distance: f32,
     /// x coordinate is sea level (minimum height for any land chunk), and y
@@ -101,6 +111,7 @@ pub struct Scene {
     map_bounds: Vec2<f32>,
     select_pos: Option<Vec3<i32>>,
     light_data: Vec<Light>,
+    color_grading: ColorGrading,
 
     particle_mgr: ParticleMgr,
     trail_mgr: TrailMgr,
@@ -111,6 +122,8 @@ pub struct Scene {
 
     integrated_rain_vel: f32,
     last_lightning: Option<(Vec3<f32>, f64)>,
+    /// Time of the world the scene was frozen at when entering photo mode
+    frozen_time: Option<f64>,
 }
 
 pub struct SceneData<'a> {
@@ -126,13 +139,19 @@ pub struct SceneData<'a> {
     pub gamma: f32,
     pub exposure: f32,
     pub ambiance: f32,
+    /// Strength of the colour grading of the biomes and times of day
+    pub color_grading: f32,
     pub mouse_smoothing: bool,
     pub sprite_render_distance: f32,
+    /// Share of the decorative sprites, like grass, which are rendered
+    pub sprite_density: f32,
     pub particles_enabled: bool,
     pub weapon_trails_enabled: bool,
     pub flashing_lights_enabled: bool,
     pub figure_lod_render_distance: f32,
     pub is_aiming: bool,
+    /// Depth of field and filter of photo mode, while the player is in it
+    pub photo_mode: Option<&'a PhotoModeSettings>,
 }
 
 impl<'a> SceneData<'a> {
@@ -319,6 +338,7 @@ impl Scene {
             },
             terrain,
             debug: Debug::new(),
+            ghost: Ghost::default(),
             lod,
             loaded_distance: 0.0,
             map_bounds: Vec2::new(
@@ -327,6 +347,7 @@ impl Scene {
             ),
             select_pos: None,
             light_data: Vec::new(),
+            color_grading: ColorGrading::new(),
             particle_mgr: ParticleMgr::new(renderer),
             trail_mgr: TrailMgr::default(),
             figure_mgr: FigureMgr::new(renderer),
@@ -337,6 +358,7 @@ impl Scene {
             },
             integrated_rain_vel: 0.0,
             last_lightning: None,
+            frozen_time: None,
         }
     }
 
@@ -444,7 +466,8 @@ impl Scene {
             .handle_outcome(outcome, audio, scene_data.client, underwater);
 
         match outcome {
-            Outcome::Lightning { pos } => {
+            // The sky lights up with the lightning unless flashes are turned off
+            Outcome::Lightning { pos } if scene_data.flashing_lights_enabled => {
                 self.last_lightning = Some((*pos, scene_data.state.get_time()));
             },
             Outcome::Explosion {
@@ -484,6 +507,11 @@ impl Scene {
                 },
                 fadeout: |timeout| timeout * 2.0,
             }),
+            Outcome::Ignite { pos } => self.event_lights.push(EventLight {
+                light: Light::new(pos.map(|e| e as f32 + 0.5), Rgb::new(1.0, 0.5, 0.0), 3.0),
+                timeout: 0.5,
+                fadeout: |timeout| timeout * 2.0,
+            }),
             Outcome::ProjectileShot { .. } => {},
             _ => {},
         }
@@ -618,16 +646,25 @@ impl Scene {
         let loaded_distance =
             (0.98 * self.loaded_distance + 0.02 * scene_data.loaded_distance).max(0.01);
 
+        // Photo mode freezes the figures, particles and animations as they were
+        // when it was entered, while the camera still moves around them
+        self.frozen_time = scene_data.photo_mode.map(|_| {
+            self.frozen_time
+                .unwrap_or_else(|| scene_data.state.get_time())
+        });
+
         // Reset lights ready for the next tick
         let lights = &mut self.light_data;
         lights.clear();
 
-        // Maintain the particles.
-        self.particle_mgr
-            .maintain(renderer, scene_data, &self.terrain, lights);
+        if self.frozen_time.is_none() {
+            // Maintain the particles.
+            self.particle_mgr
+                .maintain(renderer, scene_data, &self.terrain, lights);
 
-        // Maintain the trails.
-        self.trail_mgr.maintain(renderer, scene_data);
+            // Maintain the trails.
+            self.trail_mgr.maintain(renderer, scene_data);
+        }
 
         // Update light constants
         lights.extend(
@@ -661,6 +698,32 @@ impl Scene {
                     let pos = interpolated.map_or(pos.0, |i| i.pos);
                     Light::new(pos + light_anim.offset, light_anim.col, light_anim.strength)
                 })
+                .chain(
+                    (
+                        &scene_data.state.ecs().read_storage::<comp::Pos>(),
+                        scene_data
+                            .state
+                            .ecs()
+                            .read_storage::<crate::ecs::comp::Interpolated>()
+                            .maybe(),
+                        &scene_data.state.ecs().read_storage::<comp::Buffs>(),
+                    )
+                        .join()
+                        .filter(|(pos, _, buffs)| {
+                            buffs.contains(comp::BuffKind::Burning)
+                                && (pos.0.distance_squared(viewpoint_pos) as f32)
+                                    < loaded_distance.powi(2) + LIGHT_DIST_RADIUS
+                        })
+                        .map(|(pos, interpolated, _)| {
+                            // Entities on fire light up their surroundings with the flames
+                            let pos = interpolated.map_or(pos.0, |i| i.pos);
+                            Light::new(
+                                pos + Vec3::unit_z(),
+                                BURNING_LIGHT_COL,
+                                BURNING_LIGHT_STRENGTH * (0.8 + rand::random::<f32>() * 0.4),
+                            )
+                        }),
+                )
                 .chain(
                     self.event_lights
                         .iter()
@@ -668,7 +731,29 @@ impl Scene {
                 ),
         );
         lights.sort_by_key(|light| light.get_pos().distance_squared(viewpoint_pos) as i32);
+        // Flat shading has no point lights
+        if renderer.pipeline_modes().lighting == LightingMode::Flat {
+            lights.clear();
+        }
         lights.truncate(MAX_LIGHT_COUNT);
+        // The first lights cast shadows, so the ones lighting up the surroundings of
+        // the camera the most, like campfires and lanterns, are moved to the front
+        if let ShadowMode::Map(mode) = renderer.pipeline_modes().shadow {
+            let illuminance = |light: &Light| {
+                light.get_strength() / (1.0 + light.get_pos().distance_squared(viewpoint_pos))
+            };
+            for i in 0..(mode.point_lights() as usize).min(lights.len()) {
+                if let Some((brightest, _)) =
+                    lights.iter().enumerate().skip(i).max_by(|(_, a), (_, b)| {
+                        illuminance(a)
+                            .partial_cmp(&illuminance(b))
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                {
+                    lights.swap(i, brightest);
+                }
+            }
+        }
         renderer.update_consts(&mut self.data.lights, lights);
 
         // Update event lights
@@ -725,8 +810,9 @@ impl Scene {
             self.lod.get_data().tgt_detail as f32,
             self.map_bounds,
             time_of_day,
-            scene_data.state.get_time(),
-            renderer.resolution().as_(),
+            self.frozen_time
+                .unwrap_or_else(|| scene_data.state.get_time()),
+            (renderer.resolution() * renderer.supersampling()).as_(),
             Vec2::new(SHADOW_NEAR, SHADOW_FAR),
             lights.len(),
             shadows.len(),
@@ -744,18 +830,40 @@ impl Scene {
             scene_data.gamma,
             scene_data.exposure,
             self.last_lightning.unwrap_or((Vec3::zero(), -1000.0)),
+            scene_data
+                .state
+                .weather_at(focus_off.xy() + cam_pos.xy())
+                .wind,
             scene_data.ambiance,
             self.camera.get_mode(),
             scene_data.sprite_render_distance as f32 - 20.0,
+            scene_data.sprite_density,
         )]);
         renderer.update_clouds_locals(CloudsLocals::new(proj_mat_inv, view_mat_inv));
-        renderer.update_postprocess_locals(PostProcessLocals::new(proj_mat_inv, view_mat_inv));
+        let postprocess_locals = PostProcessLocals::new(proj_mat_inv, view_mat_inv);
+        renderer.update_postprocess_locals(match scene_data.photo_mode {
+            Some(photo_mode) => postprocess_locals
+                .with_depth_of_field(photo_mode.focus_distance, photo_mode.aperture),
+            None => postprocess_locals,
+        });
+        self.color_grading.maintain(
+            renderer,
+            client.current_biome(),
+            scene_data.get_sun_dir(),
+            dt,
+            scene_data.color_grading,
+            scene_data
+                .photo_mode
+                .map_or(PhotoFilter::None, |photo_mode| photo_mode.filter),
+        );
 
         // Maintain LoD.
         self.lod.maintain(renderer, client, focus_pos, &self.camera);
 
         // Maintain debug shapes
         self.debug.maintain(renderer);
+        // Maintain the preview of the structure about to be built
+        self.ghost.maintain(renderer);
 
         // Maintain the terrain.
         let (
@@ -773,15 +881,19 @@ impl Scene {
         );
 
         // Maintain the figures.
-        let _figure_bounds = self.figure_mgr.maintain(
-            renderer,
-            &mut self.trail_mgr,
-            scene_data,
-            visible_psr_bounds,
-            visible_por_bounds,
-            &self.camera,
-            Some(&self.terrain),
-        );
+        if self.frozen_time.is_some() {
+            self.figure_mgr.freeze();
+        } else {
+            let _figure_bounds = self.figure_mgr.maintain(
+                renderer,
+                &mut self.trail_mgr,
+                scene_data,
+                visible_psr_bounds,
+                visible_por_bounds,
+                &self.camera,
+                Some(&self.terrain),
+            );
+        }
 
         let fov = self.camera.get_effective_fov();
         let aspect_ratio = self.camera.get_aspect_ratio();
@@ -1062,9 +1174,9 @@ impl Scene {
         let weather = client
             .state()
             .max_weather_near(focus_off.xy() + cam_pos.xy());
-        if weather.rain > RAIN_THRESHOLD {
+        if weather.rain.max(weather.snow) > RAIN_THRESHOLD {
             let weather = client.state().weather_at(focus_off.xy() + cam_pos.xy());
-            let rain_vel = weather.rain_vel();
+            let (_, rain_vel) = weather.precipitation();
             let rain_view_mat = math::Mat4::look_at_rh(look_at, look_at + rain_vel, up);
 
             self.integrated_rain_vel += rain_vel.magnitude() * dt;
@@ -1078,6 +1190,7 @@ impl Scene {
                 texture_mat,
                 rain_dir_mat,
                 weather.rain,
+                weather.snow,
                 self.integrated_rain_vel,
             );
 
@@ -1202,7 +1315,8 @@ impl Scene {
         let is_daylight = sun_dir.z < 0.0;
         let focus_pos = self.camera.get_focus_pos();
         let cam_pos = self.camera.dependents().cam_pos + focus_pos.map(|e| e.trunc());
-        let is_rain = state.max_weather_near(cam_pos.xy()).rain > RAIN_THRESHOLD;
+        let weather = state.max_weather_near(cam_pos.xy());
+        let is_rain = weather.rain.max(weather.snow) > RAIN_THRESHOLD;
 
         let camera_data = (&self.camera, scene_data.figure_lod_render_distance);
 
@@ -1275,7 +1389,7 @@ impl Scene {
             // Render the skybox.
             first_pass.draw_skybox(&self.skybox.model);
 
-            // Draws translucent terrain and sprites
+            // Draws sprites
             self.terrain.render_translucent(
                 &mut first_pass,
                 focus_pos,
@@ -1287,9 +1401,17 @@ impl Scene {
             self.particle_mgr
                 .render(&mut first_pass.draw_particles(), scene_data);
 
+            // Render the preview of the structure about to be built
+            self.ghost.render(&mut first_pass.draw_ghosts());
+
             // Render debug shapes
             self.debug.render(&mut first_pass.draw_debug());
         }
+
+        // Fluids are drawn last, so that they can reflect the rest of the scene
+        if let Some(mut fluid_pass) = drawer.fluid_pass() {
+            self.terrain.render_fluids(&mut fluid_pass, focus_pos);
+        }
         drop(guard);
     }
 
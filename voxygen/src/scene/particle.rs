@@ -10,18 +10,20 @@ use crate::{
 };
 use common::{
     assets::{AssetExt, DotVoxAsset},
+    combat::DamageSource,
     comp::{
         self, aura, beam, body, buff, item::Reagent, object, shockwave, BeamSegment, Body,
-        CharacterState, Ori, Pos, Shockwave, Vel,
+        CharacterState, Ori, PhysicsState, Pos, Shockwave, Vel,
     },
     figure::Segment,
     outcome::Outcome,
     resources::DeltaTime,
     spiral::Spiral2d,
-    states::{self, utils::StageSection},
+    states::{self, grapple, utils::StageSection},
     terrain::{Block, TerrainChunk, TerrainGrid},
     uid::UidAllocator,
     vol::{ReadVol, RectRasterableVol, SizedVol},
+    world_border::WorldBorder,
 };
 use common_base::span;
 use hashbrown::HashMap;
@@ -33,6 +35,9 @@ use std::{
 };
 use vek::*;
 
+/// Distance between the particles the ropes are strung out of
+const ROPE_SPACING: f32 = 0.25;
+
 pub struct ParticleMgr {
     /// keep track of lifespans
     particles: Vec<Particle>,
@@ -201,6 +206,26 @@ impl ParticleMgr {
                     )
                 });
             },
+            Outcome::Ignite { pos } => {
+                self.particles.resize_with(self.particles.len() + 20, || {
+                    Particle::new(
+                        Duration::from_millis(rng.gen_range(500..1000)),
+                        time,
+                        ParticleMode::CampfireFire,
+                        pos.map(|e| e as f32 + rng.gen_range(0.0..1.0)),
+                    )
+                });
+            },
+            Outcome::BurnOut { pos } => {
+                self.particles.resize_with(self.particles.len() + 10, || {
+                    Particle::new(
+                        Duration::from_secs(2),
+                        time,
+                        ParticleMode::CampfireSmoke,
+                        pos.map(|e| e as f32 + rng.gen_range(0.0..1.0)),
+                    )
+                });
+            },
             Outcome::SummonedCreature { pos, body } => match body {
                 Body::BipedSmall(b) if matches!(b.species, body::biped_small::Species::Husk) => {
                     self.particles.resize_with(
@@ -291,10 +316,41 @@ impl ParticleMgr {
                     )
                 });
             },
+            // Projectile hits bleed above, melee hits spark
+            Outcome::HealthChange { pos, info }
+                if info.amount < 0.0 && info.cause == Some(DamageSource::Melee) =>
+            {
+                let count = if info.crit { 24 } else { 12 };
+                self.particles
+                    .resize_with(self.particles.len() + count, || {
+                        Particle::new(
+                            Duration::from_millis(300),
+                            time,
+                            ParticleMode::HitSpark,
+                            *pos + Vec3::unit_z(),
+                        )
+                    });
+            },
+            Outcome::SkillPointGain { uid, .. } => {
+                let ecs = scene_data.state.ecs();
+                if let Some(interpolated) = ecs
+                    .read_resource::<UidAllocator>()
+                    .retrieve_entity_internal(uid.0)
+                    .and_then(|entity| ecs.read_storage::<Interpolated>().get(entity).copied())
+                {
+                    self.particles.resize_with(self.particles.len() + 40, || {
+                        Particle::new(
+                            Duration::from_millis(1500),
+                            time,
+                            ParticleMode::LevelUp,
+                            interpolated.pos,
+                        )
+                    });
+                }
+            },
             Outcome::ProjectileShot { .. }
             | Outcome::Beam { .. }
             | Outcome::ExpChange { .. }
-            | Outcome::SkillPointGain { .. }
             | Outcome::ComboChange { .. }
             | Outcome::HealthChange { .. }
             | Outcome::PoiseChange { .. }
@@ -322,11 +378,13 @@ impl ParticleMgr {
             // add new Particle
             self.maintain_body_particles(scene_data);
             self.maintain_char_state_particles(scene_data);
+            self.maintain_footstep_particles(scene_data);
             self.maintain_beam_particles(scene_data, lights);
             self.maintain_block_particles(scene_data, terrain);
             self.maintain_shockwave_particles(scene_data);
             self.maintain_aura_particles(scene_data);
             self.maintain_buff_particles(scene_data);
+            self.maintain_world_border_particles(scene_data);
 
             self.upload_particles(renderer);
         } else {
@@ -607,6 +665,95 @@ impl ParticleMgr {
         }
     }
 
+    fn maintain_footstep_particles(&mut self, scene_data: &SceneData) {
+        span!(
+            _guard,
+            "footstep_particles",
+            "ParticleMgr::maintain_footstep_particles"
+        );
+        let ecs = scene_data.state.ecs();
+        let time = scene_data.state.get_time();
+        let mut rng = thread_rng();
+        let heartbeats = self.scheduler.heartbeats(Duration::from_millis(100));
+
+        for (interpolated, vel, physics, body) in (
+            &ecs.read_storage::<Interpolated>(),
+            &ecs.read_storage::<Vel>(),
+            &ecs.read_storage::<PhysicsState>(),
+            &ecs.read_storage::<Body>(),
+        )
+            .join()
+        {
+            let speed = vel.0.xy().magnitude();
+            if matches!(body, Body::Object(_) | Body::Ship(_) | Body::ItemDrop(_))
+                || physics.on_ground.is_none()
+                || physics.in_liquid().is_some()
+                || speed < 2.0
+            {
+                continue;
+            }
+            // The faster the walker, the more dust its steps stir up
+            for _ in 0..heartbeats {
+                if rng.gen_bool((speed as f64 / 10.0).min(1.0)) {
+                    self.particles.push(Particle::new(
+                        Duration::from_millis(600),
+                        time,
+                        ParticleMode::FootstepDust,
+                        interpolated.pos
+                            + Vec3::new(rng.gen_range(-0.3..0.3), rng.gen_range(-0.3..0.3), 0.0),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Motes rising along the world border near the viewpoint, thicker the
+    /// closer it is
+    fn maintain_world_border_particles(&mut self, scene_data: &SceneData) {
+        span!(
+            _guard,
+            "world_border_particles",
+            "ParticleMgr::maintain_world_border_particles"
+        );
+        /// Distance from the border at which it starts showing
+        const SHOW_DISTANCE: f32 = 48.0;
+        /// Length of the border shown on each side of the viewpoint
+        const SPREAD: f32 = 32.0;
+
+        let ecs = scene_data.state.ecs();
+        let time = scene_data.state.get_time();
+        let border = ecs.read_resource::<WorldBorder>();
+        let (shape, pos) = match (
+            border.shape,
+            ecs.read_storage::<Pos>().get(scene_data.viewpoint_entity),
+        ) {
+            (Some(shape), Some(pos)) => (shape, pos.0),
+            _ => return,
+        };
+        let closeness = 1.0 - shape.distance(pos.xy()).abs() / SHOW_DISTANCE;
+        if closeness <= 0.0 {
+            return;
+        }
+
+        let mut rng = thread_rng();
+        let heartbeats = self.scheduler.heartbeats(Duration::from_millis(50));
+        self.particles.extend(
+            (0..heartbeats as usize * (closeness * 12.0).ceil() as usize).map(|_| {
+                // Points of the edge closest to random places around the viewpoint
+                let near = pos.xy()
+                    + Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)) * SPREAD;
+                Particle::new(
+                    Duration::from_millis(2000),
+                    time,
+                    ParticleMode::WorldBorder,
+                    shape
+                        .edge_point(near)
+                        .with_z(pos.z + rng.gen_range(-4.0..12.0)),
+                )
+            }),
+        );
+    }
+
     fn maintain_char_state_particles(&mut self, scene_data: &SceneData) {
         span!(
             _guard,
@@ -723,6 +870,22 @@ impl ParticleMgr {
                         }
                     }
                 },
+                CharacterState::Grapple(c) => {
+                    // The rope is strung out of particles lasting for a frame, from the
+                    // hand to the hook
+                    let hand = grapple::hand_pos(interpolated.pos, body);
+                    let length = hand.distance(c.hook);
+                    let count = (length / ROPE_SPACING) as usize;
+                    self.particles.reserve(count + 1);
+                    for i in 0..=count {
+                        self.particles.push(Particle::new(
+                            Duration::from_secs_f32(dt * 1.5),
+                            time,
+                            ParticleMode::Rope,
+                            Lerp::lerp(hand, c.hook, i as f32 / count.max(1) as f32),
+                        ));
+                    }
+                },
                 CharacterState::Blink(c) => {
                     self.particles.resize_with(
                         self.particles.len()
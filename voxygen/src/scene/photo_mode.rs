@@ -0,0 +1,138 @@
+//! Photo mode: the world stops moving on screen while the camera flies around
+//! the player, with depth of field and a filter over the image, and the
+//! screenshots are rendered several times larger than the window.
+
+use super::camera::CameraMode;
+use serde::{Deserialize, Serialize};
+use vek::*;
+
+/// Blocks the camera can fly away from where photo mode was entered, so that
+/// it stays among the loaded terrain
+const MAX_CAMERA_DISTANCE: f32 = 64.0;
+
+/// Filter over the colours of the image
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PhotoFilter {
+    None,
+    BlackAndWhite,
+    Sepia,
+    Vivid,
+    Warm,
+    Cold,
+}
+
+impl Default for PhotoFilter {
+    fn default() -> Self { Self::None }
+}
+
+impl PhotoFilter {
+    const ALL: [Self; 6] = [
+        Self::None,
+        Self::BlackAndWhite,
+        Self::Sepia,
+        Self::Vivid,
+        Self::Warm,
+        Self::Cold,
+    ];
+
+    /// Filter after this one, going back to the first after the last
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|filter| *filter == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn i18n_key(self) -> &'static str {
+        match self {
+            Self::None => "hud-photo_mode-filter-none",
+            Self::BlackAndWhite => "hud-photo_mode-filter-black_and_white",
+            Self::Sepia => "hud-photo_mode-filter-sepia",
+            Self::Vivid => "hud-photo_mode-filter-vivid",
+            Self::Warm => "hud-photo_mode-filter-warm",
+            Self::Cold => "hud-photo_mode-filter-cold",
+        }
+    }
+
+    /// Colour the filter turns `color` into, both in sRGB
+    pub fn apply(self, color: Rgb<f32>) -> Rgb<f32> {
+        let luma = Rgb::broadcast(color.r * 0.2126 + color.g * 0.7152 + color.b * 0.0722);
+        match self {
+            Self::None => color,
+            Self::BlackAndWhite => luma,
+            Self::Sepia => Rgb::new(
+                color.r * 0.393 + color.g * 0.769 + color.b * 0.189,
+                color.r * 0.349 + color.g * 0.686 + color.b * 0.168,
+                color.r * 0.272 + color.g * 0.534 + color.b * 0.131,
+            ),
+            Self::Vivid => luma + (color - luma) * 1.5,
+            Self::Warm => color * Rgb::new(1.1, 1.0, 0.85),
+            Self::Cold => color * Rgb::new(0.85, 1.0, 1.1),
+        }
+        .map(|e| e.clamp(0.0, 1.0))
+    }
+}
+
+/// Photo mode the player is in
+pub struct PhotoMode {
+    /// Where the camera was when photo mode was entered
+    anchor: Vec3<f32>,
+    /// Mode of the camera to return to when leaving photo mode
+    pub previous_mode: CameraMode,
+}
+
+impl PhotoMode {
+    pub fn new(anchor: Vec3<f32>, previous_mode: CameraMode) -> Self {
+        Self {
+            anchor,
+            previous_mode,
+        }
+    }
+
+    /// Keeps the camera near where photo mode was entered
+    pub fn clamp_focus(&self, focus_pos: Vec3<f32>) -> Vec3<f32> {
+        let offset = focus_pos - self.anchor;
+        if offset.magnitude() > MAX_CAMERA_DISTANCE {
+            self.anchor + offset.normalized() * MAX_CAMERA_DISTANCE
+        } else {
+            focus_pos
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_cycle_and_keep_greys() {
+        let mut filter = PhotoFilter::None;
+        for _ in 0..PhotoFilter::ALL.len() {
+            let grey = Rgb::broadcast(0.5);
+            if !matches!(
+                filter,
+                PhotoFilter::Sepia | PhotoFilter::Warm | PhotoFilter::Cold
+            ) {
+                let diff = filter.apply(grey) - grey;
+                assert!(diff.into_array().iter().all(|e| e.abs() < 1e-3));
+            }
+            assert!(
+                filter
+                    .apply(Rgb::one())
+                    .into_array()
+                    .iter()
+                    .all(|e| (0.0..=1.0).contains(e))
+            );
+            filter = filter.next();
+        }
+        assert_eq!(filter, PhotoFilter::None);
+
+        let photo_mode = PhotoMode::new(Vec3::zero(), CameraMode::ThirdPerson);
+        let far = Vec3::new(MAX_CAMERA_DISTANCE * 2.0, 0.0, 0.0);
+        assert_eq!(
+            photo_mode.clamp_focus(far),
+            Vec3::new(MAX_CAMERA_DISTANCE, 0.0, 0.0)
+        );
+    }
+}
@@ -1,9 +1,9 @@
 use crate::{
     mesh::{greedy::GreedyMesh, segment::generate_mesh_base_vol_terrain},
     render::{
-        create_skybox_mesh, BoneMeshes, Consts, FigureModel, FirstPassDrawer, GlobalModel, Globals,
-        GlobalsBindGroup, Light, LodData, Mesh, Model, PointLightMatrix, RainOcclusionLocals,
-        Renderer, Shadow, ShadowLocals, SkyboxVertex, TerrainVertex,
+        create_skybox_mesh, identity_lut, BoneMeshes, Consts, FigureModel, FirstPassDrawer,
+        GlobalModel, Globals, GlobalsBindGroup, Light, LodData, Mesh, Model, PointLightMatrix,
+        RainOcclusionLocals, Renderer, Shadow, ShadowLocals, SkyboxVertex, TerrainVertex,
     },
     scene::{
         camera::{self, Camera, CameraMode},
@@ -95,6 +95,8 @@ impl Scene {
     pub fn new(renderer: &mut Renderer, backdrop: Option<&str>, client: &Client) -> Self {
         let start_angle = 90.0f32.to_radians();
         let resolution = renderer.resolution().map(|e| e as f32);
+        // The colour grading of the world left behind doesn't apply here
+        renderer.update_color_grading(&identity_lut());
 
         let map_bounds = Vec2::new(
             client.world_data().min_chunk_alt(),
@@ -165,7 +167,7 @@ impl Scene {
                     _lpindex: 0,
                     _visible: true,
                     is_player: false,
-                    _camera: &camera,
+                    camera: &camera,
                     terrain: None,
                     ground_vel: Vec3::zero(),
                 };
@@ -274,9 +276,11 @@ impl Scene {
             scene_data.gamma,
             scene_data.exposure,
             (Vec3::zero(), -1000.0),
+            Vec2::zero(),
             scene_data.ambiance,
             self.camera.get_mode(),
             250.0,
+            1.0,
         )]);
 
         self.figure_model_cache
@@ -352,7 +356,7 @@ impl Scene {
                 _lpindex: 0,
                 _visible: true,
                 is_player: false,
-                _camera: &self.camera,
+                camera: &self.camera,
                 terrain: None,
                 ground_vel: Vec3::zero(),
             };
@@ -10,9 +10,10 @@ use crate::{
     },
     render::{
         pipelines::{self, ColLights},
-        ColLightInfo, FirstPassDrawer, FluidVertex, GlobalModel, Instances, LodData, Mesh, Model,
-        RenderError, Renderer, SpriteGlobalsBindGroup, SpriteInstance, SpriteVertex, SpriteVerts,
-        TerrainLocals, TerrainShadowDrawer, TerrainVertex, SPRITE_VERT_PAGE_SIZE,
+        ColLightInfo, FirstPassDrawer, FluidPassDrawer, FluidVertex, GlobalModel, Instances,
+        LodData, Mesh, Model, RenderError, Renderer, SpriteGlobalsBindGroup, SpriteInstance,
+        SpriteVertex, SpriteVerts, TerrainLocals, TerrainShadowDrawer, TerrainVertex,
+        SPRITE_VERT_PAGE_SIZE,
     },
 };
 
@@ -25,6 +26,7 @@ use common::{
     figure::Segment,
     spiral::Spiral2d,
     terrain::{Block, SpriteKind, TerrainChunk},
+    time::SeasonState,
     vol::{BaseVol, ReadVol, RectRasterableVol, SampleVol},
     volumes::vol_grid_2d::{VolGrid2d, VolGrid2dError},
 };
@@ -45,6 +47,9 @@ use vek::*;
 
 const SPRITE_SCALE: Vec3<f32> = Vec3::new(1.0 / 11.0, 1.0 / 11.0, 1.0 / 11.0);
 const SPRITE_LOD_LEVELS: usize = 5;
+/// Times a year the terrain is remeshed for the colour of the vegetation to
+/// follow the season
+const SEASON_MESH_STEPS: u32 = 32;
 
 // For rain occlusion we only need to render the closest chunks.
 /// How many chunks are maximally rendered for rain occlusion.
@@ -233,6 +238,7 @@ fn mesh_worker<V: BaseVol<Vox = Block> + RectRasterableVol + ReadVol + Debug + '
     max_texture_size: u16,
     chunk: Arc<TerrainChunk>,
     range: Aabb<i32>,
+    season: SeasonState,
     sprite_data: &HashMap<(SpriteKind, usize), [SpriteData; SPRITE_LOD_LEVELS]>,
     sprite_config: &SpriteSpec,
 ) -> MeshWorkerResponse {
@@ -251,6 +257,7 @@ fn mesh_worker<V: BaseVol<Vox = Block> + RectRasterableVol + ReadVol + Debug + '
                     range,
                     Vec2::new(max_texture_size, max_texture_size),
                     &blocks_of_interest,
+                    season,
                 ),
             );
         mesh = Some(MeshWorkerResponseMesh {
@@ -295,6 +302,19 @@ fn mesh_worker<V: BaseVol<Vox = Block> + RectRasterableVol + ReadVol + Debug + '
                             let seed = wpos.x as u64 * 3
                                 + wpos.y as u64 * 7
                                 + wpos.x as u64 * wpos.y as u64; // Awful PRNG
+                            // Purely decorative sprites are thinned out by the sprite density,
+                            // the ones which can be interacted with are always kept. The rank
+                            // only depends on the position so the same ones disappear first.
+                            let decorative = !sprite.is_collectible()
+                                && !sprite.is_container()
+                                && sprite.solid_height().is_none()
+                                && block.get_glow().is_none();
+                            let density_rank = if decorative {
+                                (seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 40) as f32
+                                    / (1u64 << 24) as f32
+                            } else {
+                                0.0
+                            };
                             let ori = (block.get_ori().unwrap_or((seed % 4) as u8 * 2)) & 0b111;
                             let variation = seed as usize % cfg.variations.len();
                             let key = (sprite, variation);
@@ -329,6 +349,7 @@ fn mesh_worker<V: BaseVol<Vox = Block> + RectRasterableVol + ReadVol + Debug + '
                                         light,
                                         glow,
                                         page,
+                                        density_rank,
                                     );
                                     lod_level.push(instance);
                                 }
@@ -400,6 +421,9 @@ pub struct Terrain<V: RectRasterableVol = TerrainChunk> {
     mesh_todo: HashMap<Vec2<i32>, ChunkMeshState>,
     mesh_todos_active: Arc<AtomicU64>,
     mesh_recv_overflow: f32,
+    /// Point of the year the chunks were meshed for, all of them get remeshed
+    /// when it moves on to the next step
+    mesh_season: SeasonState,
 
     // GPU data
     // Maps sprite kind + variant to data detailing how to render it
@@ -619,6 +643,7 @@ impl<V: RectRasterableVol> Terrain<V> {
             mesh_todo: HashMap::default(),
             mesh_todos_active: Arc::new(AtomicU64::new(0)),
             mesh_recv_overflow: 0.0,
+            mesh_season: SeasonState::default(),
             sprite_data: sprite_render_context.sprite_data,
             sprite_col_lights: sprite_render_context.sprite_col_lights,
             sprite_globals: renderer.bind_sprite_globals(
@@ -739,7 +764,9 @@ impl<V: RectRasterableVol> Terrain<V> {
             // Both blocks are of the same opacity and same liquidity (since these are what we use
             // to determine mesh boundaries).
             new_block.is_liquid() == old_block.is_liquid() &&
-            new_block.is_opaque() == old_block.is_opaque();
+            new_block.is_opaque() == old_block.is_opaque() &&
+            // Fluid faces are animated along the flow of the liquid
+            new_block.get_flow() == old_block.get_flow();
         let skip_lights = same_mesh &&
             // Block glow and sunlight handling are the same (so we don't have to redo
             // lighting).
@@ -909,6 +936,23 @@ impl<V: RectRasterableVol> Terrain<V> {
         }
         drop(guard);
 
+        // Remesh everything when the vegetation changes colour with the season
+        let season = scene_data.state.get_season().quantized(SEASON_MESH_STEPS);
+        if season != self.mesh_season {
+            self.mesh_season = season;
+            for &pos in self.chunks.keys() {
+                let todo = self.mesh_todo.entry(pos).or_insert(ChunkMeshState {
+                    pos,
+                    started_tick: current_tick,
+                    is_worker_active: false,
+                    skip_remesh: false,
+                });
+                todo.skip_remesh = false;
+                todo.is_worker_active = false;
+                todo.started_tick = current_tick;
+            }
+        }
+
         // Add the chunks belonging to recently changed blocks to the list of chunks to
         // be meshed
         span!(guard, "Add chunks with modified blocks to mesh todo list");
@@ -1093,6 +1137,7 @@ impl<V: RectRasterableVol> Terrain<V> {
 
             // Queue the worker thread.
             let started_tick = todo.started_tick;
+            let season = self.mesh_season;
             let sprite_data = Arc::clone(&self.sprite_data);
             let sprite_config = Arc::clone(&self.sprite_config);
             let cnt = Arc::clone(&self.mesh_todos_active);
@@ -1111,6 +1156,7 @@ impl<V: RectRasterableVol> Terrain<V> {
                         max_texture_size as u16,
                         chunk,
                         aabb,
+                        season,
                         &sprite_data,
                         &sprite_config,
                     ));
@@ -1415,42 +1461,43 @@ impl<V: RectRasterableVol> Terrain<V> {
         let max_weather = scene_data
             .state
             .max_weather_near(focus_off.xy() + cam_pos.xy());
-        let (visible_occlusion_volume, visible_por_bounds) = if max_weather.rain > RAIN_THRESHOLD {
-            let visible_bounding_box = math::Aabb::<f32> {
-                min: math::Vec3::from(visible_bounding_box.min - focus_off),
-                max: math::Vec3::from(visible_bounding_box.max - focus_off),
-            };
-            let visible_bounds_fine = math::Aabb {
-                min: visible_bounding_box.min.as_::<f64>(),
-                max: visible_bounding_box.max.as_::<f64>(),
-            };
-            let weather = scene_data.state.weather_at(focus_off.xy() + cam_pos.xy());
-            let ray_direction = math::Vec3::<f32>::from(weather.rain_vel().normalized());
-
-            // NOTE: We use proj_mat_treeculler here because
-            // calc_focused_light_volume_points makes the assumption that the
-            // near plane lies before the far plane.
-            let visible_volume = math::calc_focused_light_volume_points(
-                inv_proj_view,
-                ray_direction.as_::<f64>(),
-                visible_bounds_fine,
-                1e-6,
-            )
-            .map(|v| v.as_::<f32>())
-            .collect::<Vec<_>>();
-            let cam_pos = math::Vec3::from(cam_pos);
-            let ray_mat =
-                math::Mat4::look_at_rh(cam_pos, cam_pos + ray_direction, math::Vec3::unit_y());
-            let visible_bounds = math::Aabr::from(math::fit_psr(
-                ray_mat,
-                visible_volume.iter().copied(),
-                |p| p,
-            ));
+        let (visible_occlusion_volume, visible_por_bounds) =
+            if max_weather.rain.max(max_weather.snow) > RAIN_THRESHOLD {
+                let visible_bounding_box = math::Aabb::<f32> {
+                    min: math::Vec3::from(visible_bounding_box.min - focus_off),
+                    max: math::Vec3::from(visible_bounding_box.max - focus_off),
+                };
+                let visible_bounds_fine = math::Aabb {
+                    min: visible_bounding_box.min.as_::<f64>(),
+                    max: visible_bounding_box.max.as_::<f64>(),
+                };
+                let weather = scene_data.state.weather_at(focus_off.xy() + cam_pos.xy());
+                let ray_direction = math::Vec3::<f32>::from(weather.precipitation().1.normalized());
+
+                // NOTE: We use proj_mat_treeculler here because
+                // calc_focused_light_volume_points makes the assumption that the
+                // near plane lies before the far plane.
+                let visible_volume = math::calc_focused_light_volume_points(
+                    inv_proj_view,
+                    ray_direction.as_::<f64>(),
+                    visible_bounds_fine,
+                    1e-6,
+                )
+                .map(|v| v.as_::<f32>())
+                .collect::<Vec<_>>();
+                let cam_pos = math::Vec3::from(cam_pos);
+                let ray_mat =
+                    math::Mat4::look_at_rh(cam_pos, cam_pos + ray_direction, math::Vec3::unit_y());
+                let visible_bounds = math::Aabr::from(math::fit_psr(
+                    ray_mat,
+                    visible_volume.iter().copied(),
+                    |p| p,
+                ));
 
-            (visible_volume, visible_bounds)
-        } else {
-            (Vec::new(), math::Aabr::default())
-        };
+                (visible_volume, visible_bounds)
+            } else {
+                (Vec::new(), math::Aabr::default())
+            };
 
         drop(guard);
         (
@@ -1630,7 +1677,6 @@ impl<V: RectRasterableVol> Terrain<V> {
 
         let mut sprite_drawer = drawer.draw_sprites(&self.sprite_globals, &self.sprite_col_lights);
         chunk_iter
-            .clone()
             .filter(|(_, c)| c.visible.is_visible())
             .for_each(|(pos, chunk)| {
                 // Skip chunk if it has no sprites
@@ -1665,9 +1711,21 @@ impl<V: RectRasterableVol> Terrain<V> {
             });
         drop(sprite_drawer);
         drop(guard);
+    }
+
+    pub fn render_fluids<'a>(&'a self, drawer: &mut FluidPassDrawer<'a>, focus_pos: Vec3<f32>) {
+        span!(_guard, "render_fluids", "Terrain::render_fluids");
+        let focus_chunk = Vec2::from(focus_pos).map2(TerrainChunk::RECT_SIZE, |e: f32, sz| {
+            (e as i32).div_euclid(sz as i32)
+        });
+
+        let chunk_iter = Spiral2d::new()
+            .filter_map(|rpos| {
+                let pos = focus_chunk + rpos;
+                self.chunks.get(&pos).map(|c| (pos, c))
+            })
+            .take(self.chunks.len());
 
-        // Translucent
-        span!(guard, "Fluid chunks");
         let mut fluid_drawer = drawer.draw_fluid();
         chunk_iter
             .filter(|(_, chunk)| chunk.visible.is_visible())
@@ -1686,7 +1744,5 @@ impl<V: RectRasterableVol> Terrain<V> {
                     locals,
                 )
             });
-        drop(fluid_drawer);
-        drop(guard);
     }
 }
@@ -10,6 +10,8 @@ pub enum Interaction {
     Collect,
     Craft(CraftingTab),
     Mine,
+    Sleep,
+    Write,
 }
 
 pub enum FireplaceType {
@@ -164,6 +166,8 @@ impl BlocksOfInterest {
                         fires.push(pos);
                         interactables.push((pos, Interaction::Craft(CraftingTab::Dismantle)))
                     },
+                    Some(SpriteKind::Bed) => interactables.push((pos, Interaction::Sleep)),
+                    Some(SpriteKind::Sign) => interactables.push((pos, Interaction::Write)),
                     _ => {},
                 },
             }
@@ -1,11 +1,39 @@
 use super::SceneData;
 use crate::render::{DynamicModel, Mesh, Quad, Renderer, TrailDrawer, TrailVertex};
-use common::comp::{object, Body, Pos, Vel};
+use common::{
+    assets::{self, AssetExt},
+    comp::{item::tool::ToolKind, object, Body, Pos, Vel},
+};
 use common_base::span;
+use serde::Deserialize;
 use specs::{Entity as EcsEntity, Join, WorldExt};
 use std::collections::HashMap;
 use vek::*;
 
+/// Colours of the trails left by each kind of weapon
+#[derive(Deserialize)]
+struct TrailColors {
+    default: (f32, f32, f32),
+    tools: HashMap<ToolKind, (f32, f32, f32)>,
+}
+
+impl assets::Asset for TrailColors {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+/// Colour of the trail left by the given weapon, or by projectiles if there is
+/// none, as the colour of a fresh trail vertex
+pub fn trail_color(tool: Option<ToolKind>) -> [f32; 4] {
+    let colors = TrailColors::load_expect("voxygen.trail_colors").read();
+    let (r, g, b) = tool
+        .and_then(|tool| colors.tools.get(&tool))
+        .copied()
+        .unwrap_or(colors.default);
+    [r, g, b, 1.0]
+}
+
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
 struct MeshKey {
     entity: EcsEntity,
@@ -32,6 +60,9 @@ pub struct TrailMgr {
 
 const TRAIL_DYNAMIC_MODEL_SIZE: usize = 15;
 const TRAIL_SHRINKAGE: f32 = 0.8;
+/// How much of its colour each quad keeps every tick, so that the trail fades
+/// out towards its end
+const TRAIL_FADE: f32 = 0.75;
 
 impl TrailMgr {
     pub fn maintain(&mut self, renderer: &mut Renderer, scene_data: &SceneData) {
@@ -68,8 +99,10 @@ impl TrailMgr {
                     let p2 = p1 + Vec3::unit_z() * THICKNESS;
                     let p4 = last_pos.0;
                     let p3 = p4 + Vec3::unit_z() * THICKNESS;
+                    let color = trail_color(None);
                     let vertex = |p: Vec3<f32>| TrailVertex {
                         pos: p.into_array(),
+                        color,
                     };
                     let quad = Quad::new(vertex(p1), vertex(p2), vertex(p3), vertex(p4));
                     quad_mesh.replace_quad(offset * 4, quad);
@@ -99,6 +132,9 @@ impl TrailMgr {
                         // Avoid shrinking edge of most recent quad so that edges of quads align
                         vertices[d] =
                             vertices[d] * TRAIL_SHRINKAGE + vertices[c] * (1.0 - TRAIL_SHRINKAGE);
+                        for vertex in [a, b, c, d] {
+                            vertices[vertex].color[3] *= TRAIL_FADE;
+                        }
                     }
                 }
 
@@ -0,0 +1,152 @@
+use crate::settings::{AdaptiveQualitySettings, GraphicsSettings};
+
+/// Number of steps the quality can be lowered by, the last one reaching the
+/// lowest quality the player allowed
+pub const MAX_LEVEL: u32 = 4;
+/// Seconds the framerate has to stay below the floor before lowering the
+/// quality
+const LOWER_DELAY: f32 = 3.0;
+/// Seconds the framerate has to stay above the floor before raising the
+/// quality back, longer than [`LOWER_DELAY`] to avoid going back and forth
+const RAISE_DELAY: f32 = 10.0;
+/// How far above the floor the framerate has to be, relative to it, to raise
+/// the quality back
+const HEADROOM: f32 = 1.25;
+/// The framerate never quite reaches the cap, so the floor and headroom are
+/// kept under it to avoid lowering the quality of capped games
+const CAP_MARGIN: f32 = 0.95;
+/// The other view distances don't go lower than in the low graphics preset
+const MIN_LOD_DETAIL: u32 = 100;
+const MIN_SPRITE_RENDER_DISTANCE: u32 = 50;
+const MIN_FIGURE_LOD_RENDER_DISTANCE: u32 = 150;
+
+/// Quality settings currently in use, the ones of the player lowered by the
+/// adaptive quality
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EffectiveQuality {
+    pub terrain_view_distance: u32,
+    pub lod_detail: u32,
+    pub sprite_render_distance: u32,
+    pub figure_lod_render_distance: u32,
+    pub particles_enabled: bool,
+}
+
+/// Lowers the view distances and effects when the framerate stays below the
+/// floor set by the player, and raises them back once there is headroom
+#[derive(Default)]
+pub struct AdaptiveQuality {
+    level: u32,
+    /// Seconds the framerate has been below the floor, or above it with
+    /// headroom when positive
+    trend: f32,
+}
+
+impl AdaptiveQuality {
+    /// How many steps the quality is lowered by, 0 being the settings of the
+    /// player
+    pub fn level(&self) -> u32 { self.level }
+
+    /// Returns whether the quality changed
+    pub fn maintain(
+        &mut self,
+        settings: &AdaptiveQualitySettings,
+        fps: f32,
+        max_fps: u32,
+        dt: f32,
+    ) -> bool {
+        if !settings.enabled {
+            self.trend = 0.0;
+            return std::mem::take(&mut self.level) != 0;
+        }
+        let cap = max_fps as f32 * CAP_MARGIN;
+        let floor = (settings.min_fps as f32).min(cap * 0.8);
+        let headroom = (floor * HEADROOM).min(cap);
+        if fps < floor && self.level < MAX_LEVEL {
+            self.trend = self.trend.min(0.0) - dt;
+            if self.trend <= -LOWER_DELAY {
+                self.level += 1;
+                self.trend = 0.0;
+                return true;
+            }
+        } else if fps > headroom && self.level > 0 {
+            self.trend = self.trend.max(0.0) + dt;
+            if self.trend >= RAISE_DELAY {
+                self.level -= 1;
+                self.trend = 0.0;
+                return true;
+            }
+        } else {
+            self.trend = 0.0;
+        }
+        false
+    }
+
+    pub fn effective(&self, graphics: &GraphicsSettings) -> EffectiveQuality {
+        let t = self.level as f32 / MAX_LEVEL as f32;
+        let lower = |value: u32, min: u32| {
+            let min = min.min(value);
+            value - ((value - min) as f32 * t).round() as u32
+        };
+        let adaptive = &graphics.adaptive_quality;
+        EffectiveQuality {
+            terrain_view_distance: lower(
+                graphics.terrain_view_distance,
+                adaptive.min_terrain_view_distance,
+            ),
+            lod_detail: lower(graphics.lod_detail, MIN_LOD_DETAIL),
+            sprite_render_distance: lower(
+                graphics.sprite_render_distance,
+                MIN_SPRITE_RENDER_DISTANCE,
+            ),
+            figure_lod_render_distance: lower(
+                graphics.figure_lod_render_distance,
+                MIN_FIGURE_LOD_RENDER_DISTANCE,
+            ),
+            particles_enabled: graphics.particles_enabled
+                && !(adaptive.allow_disabling_particles && self.level == MAX_LEVEL),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quality_steps_down_and_back_up() {
+        let mut graphics = GraphicsSettings::default();
+        graphics.terrain_view_distance = 14;
+        graphics.adaptive_quality.enabled = true;
+        let settings = graphics.adaptive_quality;
+        let mut adaptive = AdaptiveQuality::default();
+
+        // Short drops are ignored
+        assert!(!adaptive.maintain(&settings, 20.0, u32::MAX, 2.0));
+        assert!(!adaptive.maintain(&settings, 60.0, u32::MAX, 2.0));
+        assert!(!adaptive.maintain(&settings, 20.0, u32::MAX, 2.0));
+        assert_eq!(adaptive.level(), 0);
+
+        for _ in 0..MAX_LEVEL * 2 {
+            adaptive.maintain(&settings, 20.0, u32::MAX, 2.0);
+        }
+        assert_eq!(adaptive.level(), MAX_LEVEL);
+        let lowest = adaptive.effective(&graphics);
+        assert_eq!(
+            lowest.terrain_view_distance,
+            settings.min_terrain_view_distance
+        );
+        assert!(!lowest.particles_enabled);
+
+        // Barely reaching the floor isn't enough to raise the quality back, unless
+        // the framerate is capped
+        assert!(!adaptive.maintain(&settings, 32.0, u32::MAX, RAISE_DELAY));
+        assert!(adaptive.maintain(&settings, 32.0, 33, RAISE_DELAY));
+        assert!(!adaptive.maintain(&settings, 32.0, u32::MAX, RAISE_DELAY));
+        assert!(adaptive.maintain(&settings, 60.0, u32::MAX, RAISE_DELAY));
+        assert_eq!(adaptive.level(), MAX_LEVEL - 2);
+
+        graphics.adaptive_quality.enabled = false;
+        assert!(adaptive.maintain(&graphics.adaptive_quality, 20.0, u32::MAX, 2.0));
+        assert_eq!(adaptive.effective(&graphics).terrain_view_distance, 14);
+    }
+}
@@ -1,5 +1,7 @@
+mod adaptive_quality;
 pub mod interactable;
 pub mod settings_change;
+mod stats;
 mod target;
 
 use std::{cell::RefCell, collections::HashSet, rc::Rc, result::Result, time::Duration};
@@ -8,14 +10,16 @@ use std::{cell::RefCell, collections::HashSet, rc::Rc, result::Result, time::Dur
 use mumble_link::SharedLink;
 use ordered_float::OrderedFloat;
 use specs::{Join, WorldExt};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use vek::*;
 
 use client::{self, Client};
 use common::{
+    blueprint::Blueprint,
+    building::{self, BuildMaterial},
     comp,
     comp::{
-        inventory::slot::{EquipSlot, Slot},
+        inventory::slot::{EquipSlot, InvSlotId, Slot},
         invite::InviteKind,
         item::{
             tool::{AbilityMap, ToolKind},
@@ -24,14 +28,17 @@ use common::{
         ChatMsg, ChatType, InputKind, InventoryUpdateEvent, Pos, Stats, UtteranceKind, Vel,
     },
     consts::MAX_MOUNT_RANGE,
+    farming::Crop,
     link::Is,
     mounting::Mount,
     outcome::Outcome,
     recipe,
     terrain::{Block, BlockKind},
     trade::TradeResult,
+    uid::Uid,
     util::{Dir, Plane},
     vol::ReadVol,
+    writing,
 };
 use common_base::{prof_span, span};
 use common_net::{
@@ -41,28 +48,39 @@ use common_net::{
 
 use crate::{
     audio::sfx::SfxEvent,
+    capture::CaptureMetadata,
     cmd::run_command,
     error::Error,
     game_input::GameInput,
     hud::{
         DebugInfo, Event as HudEvent, Hud, HudCollectFailedReason, HudInfo, LootMessage,
-        PromptDialogSettings,
+        PromptDialogSettings, Writable,
     },
     key_state::KeyState,
     menu::char_selection::CharSelectionState,
     render::{Drawer, GlobalsBindGroup},
-    scene::{camera, terrain::Interaction, CameraMode, DebugShapeId, Scene, SceneData},
-    settings::Settings,
+    scene::{
+        camera, photo_mode::PhotoMode, terrain::Interaction, CameraMode, DebugShapeId,
+        GhostPreview, Scene, SceneData,
+    },
+    settings::{get_fps, Settings},
     window::{AnalogGameInput, Event},
     Direction, GlobalState, PlayState, PlayStateResult,
 };
+use adaptive_quality::AdaptiveQuality;
+pub use adaptive_quality::MAX_LEVEL as MAX_ADAPTIVE_QUALITY_LEVEL;
 use hashbrown::HashMap;
 use interactable::{select_interactable, Interactable};
-use settings_change::Language::ChangeLanguage;
+use settings_change::{Gamepad::SetBinding, Language::ChangeLanguage, SettingsChange};
+use stats::SessionStats;
 use target::targets_under_cursor;
 #[cfg(feature = "egui-ui")]
 use voxygen_egui::EguiDebugInfo;
 
+/// Time the explored cells are kept before saving the profile, which is saved
+/// on exit too
+const EXPLORED_SAVE_DELAY: Duration = Duration::from_secs(30);
+
 /// The action to perform after a tick
 enum TickAction {
     // Continue executing
@@ -79,6 +97,14 @@ pub struct SessionState {
     inputs: comp::ControllerInputs,
     inputs_state: HashSet<GameInput>,
     selected_block: Block,
+    /// Whether the player is building with the materials in their inventory,
+    /// outside of build mode
+    survival_building: bool,
+    /// Material placed when building, the first one in the inventory otherwise
+    selected_material: Option<&'static BuildMaterial>,
+    /// Blueprint built instead of placing materials, with the item id it was
+    /// selected from and the positions of its blocks
+    selected_blueprint: Option<(String, Blueprint, Vec<Vec3<i32>>)>,
     walk_forward_dir: Vec2<f32>,
     walk_right_dir: Vec2<f32>,
     free_look: bool,
@@ -88,10 +114,20 @@ pub struct SessionState {
     target_entity: Option<specs::Entity>,
     selected_entity: Option<(specs::Entity, std::time::Instant)>,
     viewpoint_entity: Option<specs::Entity>,
+    /// Player the server asked the camera to follow, until they are synced to
+    /// this client
+    spectated_player: Option<Uid>,
     interactable: Option<Interactable>,
+    /// Photo mode the player is in, which freezes the world on screen
+    photo_mode: Option<PhotoMode>,
     #[cfg(not(target_os = "macos"))]
     mumble_link: SharedLink,
     hitboxes: HashMap<specs::Entity, DebugShapeId>,
+    /// When the character explored cells of the world which weren't saved to
+    /// the profile yet
+    explored_unsaved_since: Option<std::time::Instant>,
+    stats: SessionStats,
+    adaptive_quality: AdaptiveQuality,
 }
 
 /// Represents an active game session (i.e., the one being played).
@@ -129,6 +165,10 @@ impl SessionState {
             }
         }
         let hud = Hud::new(global_state, &client.borrow());
+        let shared_markers = hud.shared_map_markers();
+        if !shared_markers.is_empty() {
+            client.borrow_mut().share_map_markers(shared_markers);
+        }
         let walk_forward_dir = scene.camera().forward_xy();
         let walk_right_dir = scene.camera().right_xy();
 
@@ -140,6 +180,9 @@ impl SessionState {
             inputs_state: HashSet::new(),
             hud,
             selected_block: Block::new(BlockKind::Misc, Rgb::broadcast(255)),
+            survival_building: false,
+            selected_material: None,
+            selected_blueprint: None,
             walk_forward_dir,
             walk_right_dir,
             free_look: false,
@@ -149,11 +192,52 @@ impl SessionState {
             target_entity: None,
             selected_entity: None,
             viewpoint_entity: None,
+            spectated_player: None,
             interactable: None,
+            photo_mode: None,
             #[cfg(not(target_os = "macos"))]
             mumble_link,
             hitboxes: HashMap::new(),
+            explored_unsaved_since: None,
+            stats: SessionStats::default(),
+            adaptive_quality: AdaptiveQuality::default(),
+        }
+    }
+
+    /// Applies the view distances of the settings, lowered by the adaptive
+    /// quality. The ones only used when rendering are read every frame instead.
+    fn apply_view_distances(&mut self, settings: &Settings) {
+        let quality = self.adaptive_quality.effective(&settings.graphics);
+        self.client
+            .borrow_mut()
+            .set_view_distances(common::ViewDistances {
+                terrain: quality.terrain_view_distance,
+                entity: settings.graphics.entity_view_distance,
+            });
+        self.scene.lod.set_detail(quality.lod_detail);
+    }
+
+    /// Joins the voice chat of the server when it has one and the player
+    /// enabled it, leaves it otherwise
+    #[cfg(feature = "voice-chat")]
+    fn apply_voice_chat(&self, audio: &mut crate::audio::AudioFrontend, enabled: bool) {
+        let mut client = self.client.borrow_mut();
+        audio.set_voice_chat(enabled && client.server_info().voice_chat);
+        client.set_voice_listening(audio.voice_chat().is_some());
+    }
+
+    /// Enters photo mode with the camera flying from where it is, or leaves it
+    /// for the mode the camera was in before
+    fn toggle_photo_mode(&mut self) {
+        let camera = self.scene.camera_mut();
+        match self.photo_mode.take() {
+            Some(photo_mode) => camera.set_mode(photo_mode.previous_mode),
+            None => {
+                self.photo_mode = Some(PhotoMode::new(camera.get_focus_pos(), camera.get_mode()));
+                camera.set_mode(CameraMode::Freefly);
+            },
         }
+        self.hud.photo_mode(self.photo_mode.is_some());
     }
 
     fn stop_auto_walk(&mut self) {
@@ -162,6 +246,28 @@ impl SessionState {
         self.key_state.auto_walk = false;
     }
 
+    /// Text written in the book in the inventory slot, empty if it is blank and
+    /// `None` if the item isn't a book
+    fn book_text(&self, slot: InvSlotId) -> Option<String> {
+        let client = self.client.borrow();
+        let inventories = client.inventories();
+        let item = inventories.get(client.entity())?.get(slot)?;
+        let text = (item.persistence_item_id() == writing::BOOK_ITEM)
+            .then(|| item.text().unwrap_or_default().to_owned());
+        text
+    }
+
+    /// Item id of the blueprint in the inventory slot, `None` if the item isn't
+    /// a blueprint
+    fn blueprint_item(&self, slot: InvSlotId) -> Option<String> {
+        let client = self.client.borrow();
+        let inventories = client.inventories();
+        let item = inventories.get(client.entity())?.get(slot)?;
+        let item_id = Blueprint::of_item(item.persistence_item_id())
+            .map(|_| item.persistence_item_id().to_owned());
+        item_id
+    }
+
     /// Gets the entity that is the current viewpoint, and a bool if the client
     /// is allowed to edit it's data.
     fn viewpoint_entity(&self) -> (specs::Entity, bool) {
@@ -232,6 +338,7 @@ impl SessionState {
                     // not be grammatical in some languages)
                     let kind_str = match kind {
                         InviteKind::Group => "Group",
+                        InviteKind::Guild => "Guild",
                         InviteKind::Trade => "Trade",
                     };
                     let target_name = match client.player_list().get(&target) {
@@ -312,6 +419,7 @@ impl SessionState {
                             }
                         },
                         InventoryUpdateEvent::Collected(item) => {
+                            self.stats.record_loot(item.amount());
                             let ability_map = AbilityMap::load().read();
                             let msm = MaterialStatManifest::load().read();
                             self.hud.new_loot_message(LootMessage {
@@ -338,6 +446,7 @@ impl SessionState {
                     self.hud.new_message(ChatMsg {
                         chat_type: ChatType::CommandError,
                         message,
+                        links: Vec::new(),
                     });
                 },
                 client::Event::Kicked(reason) => {
@@ -352,7 +461,10 @@ impl SessionState {
                     self.hud.new_notification(n);
                 },
                 client::Event::SetViewDistance(_vd) => {},
-                client::Event::Outcome(outcome) => outcomes.push(outcome),
+                client::Event::Outcome(outcome) => {
+                    self.stats.record_outcome(&outcome, client.uid());
+                    outcomes.push(outcome);
+                },
                 client::Event::CharacterCreated(_) => {},
                 client::Event::CharacterEdited(_) => {},
                 client::Event::CharacterError(error) => {
@@ -380,7 +492,65 @@ impl SessionState {
                 client::Event::SpectatePosition(pos) => {
                     self.scene.camera_mut().force_focus_pos(pos);
                 },
+                client::Event::SpectatePlayer(uid) => {
+                    self.spectated_player = uid;
+                    if uid.is_none() && self.viewpoint_entity.take().is_some() {
+                        self.scene.camera_mut().set_mode(CameraMode::Freefly);
+                    }
+                },
+                client::Event::DamageReport(report) => {
+                    self.hud.new_damage_report(report);
+                },
+                #[cfg(feature = "voice-chat")]
+                client::Event::Voice { speaker, frame } => {
+                    if !self.hud.voice_muted(speaker) {
+                        let ecs = client.state().ecs();
+                        let pos = ecs.entity_from_uid(speaker.0).and_then(|entity| {
+                            ecs.read_storage::<comp::Pos>().get(entity).map(|pos| pos.0)
+                        });
+                        if let Some(pos) = pos {
+                            global_state.audio.receive_voice(speaker, frame, pos);
+                        }
+                    }
+                },
+                #[cfg(not(feature = "voice-chat"))]
+                client::Event::Voice { .. } => {},
+                client::Event::Dialogue(npc) => {
+                    match npc.and_then(|npc| client.state().ecs().entity_from_uid(npc.0)) {
+                        Some(npc) => self.hud.show.open_dialogue(npc),
+                        None => self.hud.show.close_dialogue(),
+                    }
+                },
+                client::Event::PlayerJoined(_)
+                | client::Event::PlayerLeft(_)
+                | client::Event::EntitySpawned(_)
+                | client::Event::DamageTaken(_) => {},
+            }
+        }
+
+        global_state.audio.duck_music(self.hud.in_dialogue());
+
+        #[cfg(feature = "voice-chat")]
+        {
+            if let Some(voice) = global_state.audio.voice_chat() {
+                for frame in voice.take_frames() {
+                    client.send_voice(frame);
+                }
             }
+            let ecs = client.state().ecs();
+            let positions = ecs.read_storage::<comp::Pos>();
+            global_state.audio.maintain_voice(dt, |uid| {
+                ecs.entity_from_uid(uid.0)
+                    .and_then(|entity| positions.get(entity))
+                    .map(|pos| pos.0)
+            });
+        }
+
+        if let Some(pos) = client.position() {
+            self.stats.record_position(pos);
+        }
+        if let Some(health) = client.current::<comp::Health>() {
+            self.stats.record_health(health.is_dead);
         }
 
         Ok(TickAction::Continue)
@@ -388,6 +558,31 @@ impl SessionState {
 
     /// Clean up the session (and the client attached to it) after a tick.
     pub fn cleanup(&mut self) { self.client.borrow_mut().cleanup(); }
+
+    /// Show the session summary on the main menu once the session ends, after
+    /// any message explaining why it ended, and optionally keep it in the
+    /// session history.
+    fn show_session_summary(&self, global_state: &mut GlobalState) {
+        let summary = self.stats.summary(&global_state.i18n.read());
+        global_state.info_message = Some(match global_state.info_message.take() {
+            Some(info) => format!("{}\n\n{}", info, summary),
+            None => summary,
+        });
+
+        if global_state.settings.gameplay.save_session_history {
+            let character = self
+                .client
+                .borrow()
+                .current::<Stats>()
+                .map_or_else(String::new, |stats| stats.name);
+            if let Err(e) = self
+                .stats
+                .append_to_history(&global_state.config_dir, &character)
+            {
+                warn!(?e, "Failed to append to the session history");
+            }
+        }
+    }
 }
 
 impl PlayState for SessionState {
@@ -420,6 +615,12 @@ impl PlayState for SessionState {
                     .join_server(self.client.borrow().server_info().name.clone());
             }
         }
+
+        #[cfg(feature = "voice-chat")]
+        self.apply_voice_chat(
+            &mut global_state.audio,
+            global_state.settings.audio.voice_chat,
+        );
     }
 
     fn tick(&mut self, global_state: &mut GlobalState, events: Vec<Event>) -> PlayStateResult {
@@ -515,9 +716,17 @@ impl PlayState for SessionState {
                 .map_or(false, |tool_kind| tool_kind == ToolKind::Pick)
                 && client.is_wielding() == Some(true);
 
-            // Check to see whether we're aiming at anything
+            let is_building = can_build || self.survival_building;
+
+            // Check to see whether we're aiming at anything, from the eyes in first person
+            // as the crosshair is drawn from there
+            let ray_origin = if self.scene.camera().get_mode() == CameraMode::FirstPerson {
+                focus_pos
+            } else {
+                cam_pos
+            };
             let (build_target, collect_target, entity_target, mine_target, terrain_target) =
-                targets_under_cursor(&client, cam_pos, cam_dir, can_build, is_mining);
+                targets_under_cursor(&client, ray_origin, cam_dir, is_building, is_mining);
 
             self.interactable = select_interactable(
                 &client,
@@ -527,6 +736,31 @@ impl PlayState for SessionState {
                 &self.scene,
             );
 
+            // Shows where the selected blueprint would build its structure
+            let ghost = self
+                .selected_blueprint
+                .as_ref()
+                .zip(build_target.filter(|_| self.survival_building && !can_build))
+                .map(|((item_id, blueprint, offsets), bt)| {
+                    let pos = bt.kind.0.map(|p| p.floor() as i32);
+                    let terrain = client.state().terrain();
+                    let has_room = offsets.iter().all(|offset| {
+                        terrain
+                            .get(pos + offset)
+                            .map_or(false, |block| building::can_place_in(*block))
+                    });
+                    let has_materials = client
+                        .inventories()
+                        .get(player_entity)
+                        .map_or(false, |inv| blueprint.has_materials(inv));
+                    GhostPreview {
+                        blueprint: item_id.clone(),
+                        pos,
+                        blocked: !has_room || !has_materials,
+                    }
+                });
+            self.scene.ghost.set_preview(ghost);
+
             drop(client);
 
             if presence == PresenceKind::Spectator {
@@ -543,7 +777,7 @@ impl PlayState for SessionState {
             // Nearest block to consider with GameInput primary or secondary key.
             let nearest_block_dist = find_shortest_distance(&[
                 mine_target.filter(|_| is_mining).map(|t| t.distance),
-                build_target.filter(|_| can_build).map(|t| t.distance),
+                build_target.filter(|_| is_building).map(|t| t.distance),
             ]);
             // Nearest block to be highlighted in the scene (self.scene.set_select_pos).
             let nearest_scene_dist = find_shortest_distance(&[
@@ -557,7 +791,7 @@ impl PlayState for SessionState {
                 self.scene.set_select_pos(Some(mt.position_int()));
                 Some(mt.position)
             } else if let Some(bt) =
-                build_target.filter(|bt| can_build && nearest_scene_dist == Some(bt.distance))
+                build_target.filter(|bt| is_building && nearest_scene_dist == Some(bt.distance))
             {
                 self.scene.set_select_pos(Some(bt.position_int()));
                 None
@@ -577,8 +811,38 @@ impl PlayState for SessionState {
             // Throw out distance info, it will be useful in the future
             self.target_entity = entity_target.map(|t| t.kind.0);
 
+            // Replayed inputs are handled as if they were pressed this frame
+            #[cfg(feature = "input-recording")]
+            let events = {
+                let local = global_state.is_local();
+                let mut events = events;
+                events.extend(
+                    global_state
+                        .input_recorder
+                        .due(local)
+                        .into_iter()
+                        .map(|(input, state)| Event::InputUpdate(input, state)),
+                );
+                events
+            };
+
             // Handle window events.
             for event in events {
+                // Escape skips the cutscene being played
+                if let (Some(cutscene), Event::InputUpdate(GameInput::Escape, true)) =
+                    (&mut global_state.cutscene, &event)
+                {
+                    cutscene.stop();
+                    continue;
+                }
+                // Escape leaves photo mode
+                if self.photo_mode.is_some()
+                    && matches!(event, Event::InputUpdate(GameInput::Escape, true))
+                {
+                    self.toggle_photo_mode();
+                    continue;
+                }
+
                 // Pass all events to the ui first.
                 {
                     let client = self.client.borrow();
@@ -602,6 +866,8 @@ impl PlayState for SessionState {
                         if !self.inputs_state.insert(input) {
                             self.inputs_state.remove(&input);
                         }
+                        #[cfg(feature = "input-recording")]
+                        global_state.input_recorder.record(input, state);
                         match input {
                             GameInput::Primary => {
                                 let mut client = self.client.borrow_mut();
@@ -609,9 +875,13 @@ impl PlayState for SessionState {
                                 // take precedence.
                                 // Order of precedence: build, then mining, then attack.
                                 if let Some(build_target) = build_target.filter(|bt| {
-                                    state && can_build && nearest_block_dist == Some(bt.distance)
+                                    state && is_building && nearest_block_dist == Some(bt.distance)
                                 }) {
-                                    client.remove_block(build_target.position_int());
+                                    if can_build {
+                                        client.remove_block(build_target.position_int());
+                                    } else {
+                                        client.remove_material(build_target.position_int());
+                                    }
                                 } else {
                                     client.handle_input(
                                         InputKind::Primary,
@@ -624,13 +894,41 @@ impl PlayState for SessionState {
                             GameInput::Secondary => {
                                 let mut client = self.client.borrow_mut();
                                 if let Some(build_target) = build_target.filter(|bt| {
-                                    state && can_build && nearest_block_dist == Some(bt.distance)
+                                    state && is_building && nearest_block_dist == Some(bt.distance)
                                 }) {
-                                    let selected_pos = build_target.kind.0;
-                                    client.place_block(
-                                        selected_pos.map(|p| p.floor() as i32),
-                                        self.selected_block,
-                                    );
+                                    let selected_pos =
+                                        build_target.kind.0.map(|p| p.floor() as i32);
+                                    if can_build {
+                                        client.place_block(selected_pos, self.selected_block);
+                                    } else if let Some((item_id, ..)) = &self.selected_blueprint {
+                                        let slot = client
+                                            .inventories()
+                                            .get(player_entity)
+                                            .and_then(|inv| {
+                                                inv.slots_with_id().find_map(|(slot, item)| {
+                                                    item.as_ref()
+                                                        .filter(|item| {
+                                                            item.persistence_item_id() == *item_id
+                                                        })
+                                                        .map(|_| slot)
+                                                })
+                                            });
+                                        match slot {
+                                            Some(slot) => {
+                                                client.place_blueprint(slot, selected_pos)
+                                            },
+                                            // The blueprint is no longer in the inventory
+                                            None => self.selected_blueprint = None,
+                                        }
+                                    } else {
+                                        let slot =
+                                            client.inventories().get(player_entity).and_then(
+                                                |inv| material_slot(inv, self.selected_material),
+                                            );
+                                        if let Some(slot) = slot {
+                                            client.place_material(slot, selected_pos);
+                                        }
+                                    }
                                 } else {
                                     client.handle_input(
                                         InputKind::Secondary,
@@ -650,7 +948,7 @@ impl PlayState for SessionState {
                             },
                             GameInput::Roll => {
                                 let mut client = self.client.borrow_mut();
-                                if can_build {
+                                if is_building {
                                     if state {
                                         if let Some(block) = build_target.and_then(|bt| {
                                             client
@@ -660,7 +958,13 @@ impl PlayState for SessionState {
                                                 .ok()
                                                 .copied()
                                         }) {
-                                            self.selected_block = block;
+                                            if can_build {
+                                                self.selected_block = block;
+                                            } else if let Some(material) =
+                                                building::material_of_block(block)
+                                            {
+                                                self.selected_material = Some(material);
+                                            }
                                         }
                                     }
                                 } else {
@@ -779,6 +1083,22 @@ impl PlayState for SessionState {
                                     self.client.borrow_mut().swap_loadout();
                                 }
                             },
+                            GameInput::ToggleBuilding if state => {
+                                self.survival_building = !self.survival_building;
+                                let i18n = global_state.i18n.read();
+                                let msg = if self.survival_building {
+                                    i18n.get_msg("hud-building-enabled")
+                                } else {
+                                    i18n.get_msg("hud-building-disabled")
+                                };
+                                self.hud.new_message(ChatType::Meta.chat_msg(msg));
+                            },
+                            #[cfg(feature = "voice-chat")]
+                            GameInput::PushToTalk => {
+                                if let Some(voice) = global_state.audio.voice_chat() {
+                                    voice.set_transmitting(state);
+                                }
+                            },
                             GameInput::ToggleLantern if state => {
                                 let mut client = self.client.borrow_mut();
                                 if client.is_lantern_enabled() {
@@ -842,6 +1162,17 @@ impl PlayState for SessionState {
                                                         )
                                                     },
                                                     Interaction::Mine => {},
+                                                    Interaction::Sleep => client.sleep(pos),
+                                                    Interaction::Write => {
+                                                        let text = client
+                                                            .sign_texts()
+                                                            .get(&pos)
+                                                            .cloned()
+                                                            .unwrap_or_default();
+                                                        self.hud
+                                                            .show
+                                                            .open_writing(Writable::Sign(pos), text)
+                                                    },
                                                 }
                                             },
                                             Interactable::Entity(entity) => {
@@ -862,11 +1193,78 @@ impl PlayState for SessionState {
                                                 {
                                                     // TODO: maybe start crafting instead?
                                                     client.toggle_sit();
+                                                } else if client
+                                                    .state()
+                                                    .ecs()
+                                                    .read_storage::<comp::Portal>()
+                                                    .contains(entity)
+                                                {
+                                                    client.activate_portal(entity);
+                                                    self.hud.show.open_travel_map(entity);
+                                                } else if client
+                                                    .state()
+                                                    .ecs()
+                                                    .read_storage::<comp::Minigame>()
+                                                    .contains(entity)
+                                                {
+                                                    self.hud.show.open_minigame(entity);
                                                 } else {
                                                     client.npc_interact(entity);
                                                 }
                                             },
                                         }
+                                    } else if let Some(pos) =
+                                        terrain_target.map(|t| t.position_int())
+                                    {
+                                        // Putting up signs while building, and farming. The
+                                        // server checks whether the block can actually be
+                                        // built on, tilled or planted in
+                                        let mut client = self.client.borrow_mut();
+                                        let block = client.state().terrain().get(pos).ok().copied();
+                                        let inventories = client.inventories();
+                                        let inventory = inventories.get(player_entity);
+                                        let seed_slot = inventory.and_then(|inv| {
+                                            inv.slots_with_id()
+                                                .find(|(_, item)| {
+                                                    item.as_ref().map_or(false, |item| {
+                                                        Crop::from_seed(item.persistence_item_id())
+                                                            .is_some()
+                                                    })
+                                                })
+                                                .map(|(slot, _)| slot)
+                                        });
+                                        let sign_slot = inventory.and_then(|inv| {
+                                            inv.slots_with_id()
+                                                .find(|(_, item)| {
+                                                    item.as_ref().map_or(false, |item| {
+                                                        item.persistence_item_id()
+                                                            == writing::SIGN_ITEM
+                                                    })
+                                                })
+                                                .map(|(slot, _)| slot)
+                                        });
+                                        let wields_hoe = inventory
+                                            .and_then(|inv| inv.equipped(EquipSlot::ActiveMainhand))
+                                            .and_then(|item| item.tool_info())
+                                            .map_or(false, |tool_kind| {
+                                                tool_kind == ToolKind::Farming
+                                            });
+                                        drop(inventories);
+                                        match (block.map(|block| block.kind()), sign_slot) {
+                                            (Some(_), Some(slot)) if self.survival_building => {
+                                                self.hud.show.open_writing(
+                                                    Writable::NewSign(slot, pos + Vec3::unit_z()),
+                                                    String::new(),
+                                                )
+                                            },
+                                            (Some(BlockKind::Farmland), _) => {
+                                                if let Some(slot) = seed_slot {
+                                                    client.plant_seed(slot, pos);
+                                                }
+                                            },
+                                            (Some(_), _) if wields_hoe => client.till_block(pos),
+                                            _ => {},
+                                        }
                                     }
                                 }
                             },
@@ -955,6 +1353,12 @@ impl PlayState for SessionState {
                                     }) || self.viewpoint_entity.is_some(),
                                 );
                             },
+                            GameInput::FirstPerson if state => {
+                                self.scene.camera_mut().toggle_first_person();
+                            },
+                            GameInput::PhotoMode if state => {
+                                self.toggle_photo_mode();
+                            },
                             GameInput::Select => {
                                 if !state {
                                     self.selected_entity =
@@ -1011,7 +1415,12 @@ impl PlayState for SessionState {
                     Event::ScreenshotMessage(screenshot_message) => self.hud.new_message(ChatMsg {
                         chat_type: ChatType::CommandInfo,
                         message: screenshot_message,
+                        links: Vec::new(),
                     }),
+                    Event::GamepadBinding(binding, button) => {
+                        SettingsChange::from(SetBinding(binding, button))
+                            .process(global_state, self);
+                    },
 
                     // Pass all other events to the scene
                     event => {
@@ -1033,6 +1442,18 @@ impl PlayState for SessionState {
                 self.scene.camera_mut().set_mode(CameraMode::Freefly);
             }
 
+            if let Some(entity) = self.spectated_player.and_then(|uid| {
+                self.client
+                    .borrow()
+                    .state()
+                    .ecs()
+                    .entity_from_uid(uid.into())
+            }) {
+                self.spectated_player = None;
+                self.viewpoint_entity = Some(entity);
+                self.scene.camera_mut().set_mode(CameraMode::FirstPerson);
+            }
+
             let (viewpoint_entity, mutable_viewpoint) = self.viewpoint_entity();
 
             // Get the current state of movement related inputs
@@ -1155,16 +1576,55 @@ impl PlayState for SessionState {
                             1.0
                         };
 
-                    let pos = self.scene.camera().get_focus_pos();
-                    self.scene
-                        .camera_mut()
-                        .set_focus_pos(pos + dir * dt * speed);
+                    let pos = self.scene.camera().get_focus_pos() + dir * dt * speed;
+                    // Photo mode keeps the camera among the loaded terrain
+                    let pos = self
+                        .photo_mode
+                        .as_ref()
+                        .map_or(pos, |photo_mode| photo_mode.clamp_focus(pos));
+                    self.scene.camera_mut().set_focus_pos(pos);
 
                     // Do not apply any movement to the player character
                     self.inputs.move_dir = Vec2::zero();
                 },
             };
 
+            // Fly the camera along the path of the cutscene being played
+            if let Some(cutscene) = &mut global_state.cutscene {
+                let camera = self.scene.camera_mut();
+                match cutscene.tick(dt) {
+                    Some((pos, look_at)) => {
+                        cutscene.previous_mode.get_or_insert(camera.get_mode());
+                        camera.set_mode(CameraMode::Freefly);
+                        camera.look_from(pos, look_at);
+                    },
+                    None => {
+                        if let Some(mode) = cutscene.previous_mode {
+                            camera.set_mode(mode);
+                        }
+                        global_state.cutscene = None;
+                    },
+                }
+            }
+
+            // Pictures taken in photo mode are larger than the window
+            let supersampling = self
+                .photo_mode
+                .as_ref()
+                .map_or(1, |_| global_state.settings.photo_mode.supersampling);
+            global_state
+                .window
+                .set_screenshot_supersampling(supersampling);
+            {
+                let client = self.client.borrow();
+                global_state.window.set_capture_metadata(CaptureMetadata {
+                    server: client.server_info().name.clone(),
+                    world_seed: client.server_info().world_seed,
+                    pos: client.position(),
+                    time_of_day: client.state().get_time_of_day(),
+                });
+            }
+
             let mut outcomes = Vec::new();
 
             // Runs if either in a multiplayer server or the singleplayer server is unpaused
@@ -1176,18 +1636,28 @@ impl PlayState for SessionState {
                     &mut outcomes,
                 ) {
                     Ok(TickAction::Continue) => {}, // Do nothing
-                    Ok(TickAction::Disconnect) => return PlayStateResult::Pop, // Go to main menu
+                    Ok(TickAction::Disconnect) => {
+                        self.show_session_summary(global_state);
+                        return PlayStateResult::Pop; // Go to main menu
+                    },
                     Err(err) => {
-                        global_state.info_message = Some(
-                            global_state
-                                .i18n
-                                .read()
-                                .get_msg("common-connection_lost")
-                                .into_owned(),
-                        );
                         error!("[session] Failed to tick the scene: {:?}", err);
+                        // Get back to the character from a new connection if the server still
+                        // waits for it
+                        if let Err(e) = self.client.borrow_mut().reconnect() {
+                            warn!(?e, "[session] Failed to resume the session");
+                            global_state.info_message = Some(
+                                global_state
+                                    .i18n
+                                    .read()
+                                    .get_msg("common-connection_lost")
+                                    .into_owned(),
+                            );
+                            self.show_session_summary(global_state);
 
-                        return PlayStateResult::Pop;
+                            return PlayStateResult::Pop;
+                        }
+                        info!("[session] Resumed the session after losing the connection");
                     },
                 }
             }
@@ -1197,6 +1667,18 @@ impl PlayState for SessionState {
                 .camera_mut()
                 .compute_dependents(&*self.client.borrow().state().terrain());
 
+            // Lower or raise the view distances depending on the framerate
+            if global_state.window.focused
+                && self.adaptive_quality.maintain(
+                    &global_state.settings.graphics.adaptive_quality,
+                    global_state.clock.stats().average_tps as f32,
+                    get_fps(global_state.settings.graphics.max_fps),
+                    global_state.clock.get_stable_dt().as_secs_f32(),
+                )
+            {
+                self.apply_view_distances(&global_state.settings);
+            }
+
             // Generate debug info, if needed
             // (it iterates through enough data that we might
             // as well avoid it unless we need it).
@@ -1258,6 +1740,7 @@ impl PlayState for SessionState {
                     mutable_viewpoint,
                     target_entity: self.target_entity,
                     selected_entity: self.selected_entity,
+                    adaptive_quality_level: self.adaptive_quality.level(),
                 },
                 self.interactable,
             );
@@ -1309,6 +1792,10 @@ impl PlayState for SessionState {
                     HudEvent::CharacterSelection => {
                         self.client.borrow_mut().request_remove_character()
                     },
+                    HudEvent::Respawn => {
+                        self.stop_auto_walk();
+                        self.client.borrow_mut().respawn();
+                    },
                     HudEvent::Logout => {
                         self.client.borrow_mut().logout();
                         // Stop all sounds
@@ -1316,6 +1803,7 @@ impl PlayState for SessionState {
                         // somehow
                         global_state.audio.stop_all_ambience();
                         global_state.audio.stop_all_sfx();
+                        self.show_session_summary(global_state);
                         return PlayStateResult::Pop;
                     },
                     HudEvent::Quit => {
@@ -1332,6 +1820,35 @@ impl PlayState for SessionState {
                         slot,
                         bypass_dialog,
                     } => {
+                        // Books are read and written in rather than used
+                        if let Slot::Inventory(inv_slot) = slot {
+                            if let Some(text) = self.book_text(inv_slot) {
+                                self.hud.show.open_writing(Writable::Book(inv_slot), text);
+                                continue;
+                            }
+                            // Blueprints are selected to build with, or put away again
+                            if let Some(item_id) = self.blueprint_item(inv_slot) {
+                                let selected = self
+                                    .selected_blueprint
+                                    .as_ref()
+                                    .map_or(false, |(selected, ..)| *selected == item_id);
+                                self.selected_blueprint = if selected {
+                                    None
+                                } else {
+                                    Blueprint::of_item(&item_id).map(|blueprint| {
+                                        let blueprint = blueprint.cloned();
+                                        let offsets = blueprint
+                                            .blocks()
+                                            .into_iter()
+                                            .map(|(pos, _)| pos)
+                                            .collect();
+                                        (item_id, blueprint, offsets)
+                                    })
+                                };
+                                continue;
+                            }
+                        }
+
                         let mut move_allowed = true;
 
                         if !bypass_dialog {
@@ -1552,9 +2069,60 @@ impl PlayState for SessionState {
 
                         info!("Event! -> ChangedHotbarState")
                     },
+                    HudEvent::Explored(cells) => {
+                        let client = self.client.borrow();
+                        let character_id = match client.presence() {
+                            Some(PresenceKind::Character(id)) => Some(id),
+                            _ => None,
+                        };
+                        global_state.profile.add_explored_cells(
+                            &client.server_info().name,
+                            character_id,
+                            cells,
+                        );
+                        self.explored_unsaved_since
+                            .get_or_insert_with(std::time::Instant::now);
+                    },
+                    HudEvent::ChangePlacedMarkers(placed_markers) => {
+                        let mut client = self.client.borrow_mut();
+                        let character_id = match client.presence() {
+                            Some(PresenceKind::Character(id)) => Some(id),
+                            _ => None,
+                        };
+                        client.share_map_markers(placed_markers.shared());
+                        global_state.profile.set_placed_markers(
+                            &client.server_info().name,
+                            character_id,
+                            placed_markers,
+                        );
+                        global_state
+                            .profile
+                            .save_to_file_warn(&global_state.config_dir);
+                    },
                     HudEvent::TradeAction(action) => {
                         self.client.borrow_mut().perform_trade_action(action);
                     },
+                    HudEvent::Write(writable, text) => {
+                        let mut client = self.client.borrow_mut();
+                        match writable {
+                            Writable::NewSign(slot, pos) => client.place_sign(slot, pos, text),
+                            Writable::Sign(pos) => client.write_sign(pos, text),
+                            Writable::Book(slot) => client.write_book(slot, text),
+                        }
+                    },
+                    HudEvent::TakePhoto => global_state.window.request_screenshot(),
+                    HudEvent::LeavePhotoMode => {
+                        if self.photo_mode.is_some() {
+                            self.toggle_photo_mode();
+                        }
+                    },
+                    HudEvent::Talk(choice) => {
+                        self.client.borrow_mut().choose_dialogue(choice);
+                    },
+                    HudEvent::CloseDialogue => self.client.borrow_mut().close_dialogue(),
+                    HudEvent::Minigame(minigame, action) => {
+                        self.client.borrow_mut().minigame_action(minigame, action);
+                    },
                     HudEvent::Ability(i, state) => {
                         self.client.borrow_mut().handle_input(
                             InputKind::Ability(i),
@@ -1567,6 +2135,9 @@ impl PlayState for SessionState {
                     HudEvent::RequestSiteInfo(id) => {
                         self.client.borrow_mut().request_site_economy(id);
                     },
+                    HudEvent::UsePortal(portal, destination) => {
+                        self.client.borrow_mut().use_portal(portal, destination);
+                    },
 
                     HudEvent::CraftRecipe {
                         recipe_name: recipe,
@@ -1655,6 +2226,9 @@ impl PlayState for SessionState {
                     HudEvent::InviteMember(uid) => {
                         self.client.borrow_mut().send_invite(uid, InviteKind::Group);
                     },
+                    HudEvent::InviteToGuild(uid) => {
+                        self.client.borrow_mut().send_invite(uid, InviteKind::Guild);
+                    },
                     HudEvent::AcceptInvite => {
                         self.client.borrow_mut().accept_invite();
                     },
@@ -1689,6 +2263,9 @@ impl PlayState for SessionState {
 
             {
                 let client = self.client.borrow();
+                let quality = self
+                    .adaptive_quality
+                    .effective(&global_state.settings.graphics);
                 let scene_data = SceneData {
                     client: &client,
                     state: client.state(),
@@ -1706,22 +2283,24 @@ impl PlayState for SessionState {
                     gamma: global_state.settings.graphics.gamma,
                     exposure: global_state.settings.graphics.exposure,
                     ambiance: global_state.settings.graphics.ambiance,
+                    color_grading: global_state.settings.graphics.color_grading,
                     mouse_smoothing: global_state.settings.gameplay.smooth_pan_enable,
-                    sprite_render_distance: global_state.settings.graphics.sprite_render_distance
-                        as f32,
-                    particles_enabled: global_state.settings.graphics.particles_enabled,
+                    sprite_render_distance: quality.sprite_render_distance as f32,
+                    sprite_density: global_state.settings.graphics.sprite_density,
+                    particles_enabled: quality.particles_enabled,
                     weapon_trails_enabled: global_state.settings.graphics.weapon_trails_enabled,
                     flashing_lights_enabled: global_state
                         .settings
                         .graphics
                         .render_mode
-                        .flashing_lights_enabled,
-                    figure_lod_render_distance: global_state
-                        .settings
-                        .graphics
-                        .figure_lod_render_distance
-                        as f32,
+                        .flashing_lights_enabled
+                        && !global_state.settings.accessibility.reduce_flashes,
+                    figure_lod_render_distance: quality.figure_lod_render_distance as f32,
                     is_aiming,
+                    photo_mode: self
+                        .photo_mode
+                        .as_ref()
+                        .map(|_| &global_state.settings.photo_mode),
                 };
 
                 // Runs if either in a multiplayer server or the singleplayer server is unpaused
@@ -1744,10 +2323,38 @@ impl PlayState for SessionState {
                         );
                         self.hud
                             .handle_outcome(&outcome, scene_data.client, global_state);
+                        if let Outcome::HealthChange { info, .. } = &outcome {
+                            if global_state.settings.interface.hit_shake
+                                && !global_state.settings.accessibility.reduce_camera_shake
+                                && info.amount < 0.0
+                                && client.uid() == Some(info.target)
+                            {
+                                // Hits taking a quarter of the health shake the camera the most
+                                let max_health = client
+                                    .current::<comp::Health>()
+                                    .map_or(100.0, |health| health.maximum());
+                                self.scene
+                                    .camera_mut()
+                                    .shake(-info.amount / max_health * 4.0);
+                            }
+                        }
                     }
                 }
             }
 
+            // Exploring changes the profile every few steps, so it is only saved
+            // once in a while
+            if self
+                .explored_unsaved_since
+                .map_or(false, |since| since.elapsed() >= EXPLORED_SAVE_DELAY)
+            {
+                self.explored_unsaved_since = None;
+                global_state.profile.save_in_background(
+                    &global_state.config_dir,
+                    &self.client.borrow().state().slow_job_pool(),
+                );
+            }
+
             // Clean things up after the tick.
             self.cleanup();
 
@@ -1778,6 +2385,7 @@ impl PlayState for SessionState {
         let client = self.client.borrow();
 
         let (viewpoint_entity, mutable_viewpoint) = self.viewpoint_entity();
+        let quality = self.adaptive_quality.effective(&settings.graphics);
 
         let scene_data = SceneData {
             client: &client,
@@ -1796,13 +2404,17 @@ impl PlayState for SessionState {
             gamma: settings.graphics.gamma,
             exposure: settings.graphics.exposure,
             ambiance: settings.graphics.ambiance,
+            color_grading: settings.graphics.color_grading,
             mouse_smoothing: settings.gameplay.smooth_pan_enable,
-            sprite_render_distance: settings.graphics.sprite_render_distance as f32,
-            figure_lod_render_distance: settings.graphics.figure_lod_render_distance as f32,
-            particles_enabled: settings.graphics.particles_enabled,
+            sprite_render_distance: quality.sprite_render_distance as f32,
+            sprite_density: settings.graphics.sprite_density,
+            figure_lod_render_distance: quality.figure_lod_render_distance as f32,
+            particles_enabled: quality.particles_enabled,
             weapon_trails_enabled: settings.graphics.weapon_trails_enabled,
-            flashing_lights_enabled: settings.graphics.render_mode.flashing_lights_enabled,
+            flashing_lights_enabled: settings.graphics.render_mode.flashing_lights_enabled
+                && !settings.accessibility.reduce_flashes,
             is_aiming: self.is_aiming,
+            photo_mode: self.photo_mode.as_ref().map(|_| &settings.photo_mode),
         };
 
         // Render world
@@ -1838,10 +2450,12 @@ impl PlayState for SessionState {
         // PostProcess and UI
         {
             prof_span!("post-process and ui");
+            // The photo mode window isn't part of the pictures taken
+            let hide_ui = self.photo_mode.is_some() && drawer.taking_screenshot();
             let mut third_pass = drawer.third_pass();
             third_pass.draw_postprocess();
             // Draw the UI to the screen
-            if let Some(mut ui_drawer) = third_pass.draw_ui() {
+            if let Some(mut ui_drawer) = third_pass.draw_ui().filter(|_| !hide_ui) {
                 self.hud.render(&mut ui_drawer);
             }; // Note: this semicolon is needed for the third_pass borrow to be dropped before it's lifetime ends
         }
@@ -1855,3 +2469,23 @@ fn find_shortest_distance(arr: &[Option<f32>]) -> Option<f32> {
         .filter_map(|x| *x)
         .min_by(|d1, d2| OrderedFloat(*d1).cmp(&OrderedFloat(*d2)))
 }
+
+/// Inventory slot of the material to place, falling back to the first material
+/// in the inventory if the selected one ran out
+fn material_slot(
+    inventory: &comp::Inventory,
+    selected: Option<&'static BuildMaterial>,
+) -> Option<InvSlotId> {
+    let materials = || {
+        inventory.slots_with_id().filter_map(|(slot, item)| {
+            Some((
+                slot,
+                building::material_of_item(item.as_ref()?.persistence_item_id())?,
+            ))
+        })
+    };
+    materials()
+        .find(|(_, material)| Some(*material) == selected)
+        .or_else(|| materials().next())
+        .map(|(slot, _)| slot)
+}
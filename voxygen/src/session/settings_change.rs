@@ -1,21 +1,34 @@
 use super::SessionState;
 use crate::{
-    controller::ControllerSettings,
+    controller::{Button as GamepadButton, ControllerSettings, GamepadBinding},
     game_input::GameInput,
     hud::{
         BarNumbers, BuffPosition, ChatTab, CrosshairType, Intro, PressBehavior, ScaleChange,
         ShortcutNumbers, XpBar,
     },
     render::RenderMode,
+    scene::PhotoFilter,
     settings::{
-        AudioSettings, ChatSettings, ControlSettings, Fps, GamepadSettings, GameplaySettings,
-        GraphicsSettings, InterfaceSettings,
+        AccessibilitySettings, AdaptiveQualitySettings, AudioOutput, AudioSettings, ChatSettings,
+        ColorPalette, ControlSettings, Fps, GamepadSettings, GameplaySettings, GraphicsSettings,
+        InterfaceSettings, KeybindingScheme, SettingsProfile,
     },
     window::FullScreenSettings,
     GlobalState,
 };
+use common::comp::ChatType;
 use i18n::{LanguageMetadata, LocalizationHandle};
+use std::path::PathBuf;
 
+#[derive(Clone)]
+pub enum Accessibility {
+    ChangeColorPalette(ColorPalette),
+    AdjustTextScale(f32),
+    ToggleReduceCameraShake(bool),
+    ToggleReduceFlashes(bool),
+    ToggleSubtitles(bool),
+    ResetAccessibilitySettings,
+}
 #[derive(Clone)]
 pub enum Audio {
     AdjustMasterVolume(f32),
@@ -24,13 +37,18 @@ pub enum Audio {
     AdjustSfxVolume(f32),
     AdjustAmbienceVolume(f32),
     AdjustMusicSpacing(f32),
-    //ChangeAudioDevice(String),
+    #[cfg(feature = "voice-chat")]
+    ToggleVoiceChat(bool),
+    #[cfg(feature = "voice-chat")]
+    AdjustVoiceVolume(f32),
+    ChangeAudioDevice(Option<String>),
     ResetAudioSettings,
 }
 #[derive(Clone)]
 pub enum Chat {
     Transp(f32),
     CharName(bool),
+    HistoryLines(u32),
     ChangeChatTab(Option<usize>),
     ChatTabUpdate(usize, ChatTab),
     ChatTabInsert(usize, ChatTab),
@@ -44,9 +62,22 @@ pub enum Control {
     RemoveBinding(GameInput),
     ToggleKeybindingMode,
     ResetKeyBindings,
+    /// Gives the previous binding of the last conflicting game input to the
+    /// ones it took the key from
+    SwapBindings,
+    KeepBindings,
+    ChangeScheme(KeybindingScheme),
 }
 #[derive(Clone)]
-pub enum Gamepad {}
+pub enum Gamepad {
+    ChangeBinding(GamepadBinding),
+    SetBinding(GamepadBinding, GamepadButton),
+    RemoveBinding(GamepadBinding),
+    AdjustPanSensitivity(u32),
+    AdjustStickDeadzone(f32),
+    AdjustMouseEmulationSensitivity(u32),
+    ResetGamepadSettings,
+}
 #[derive(Clone)]
 pub enum Gameplay {
     AdjustMousePan(u32),
@@ -65,6 +96,7 @@ pub enum Gameplay {
     ChangeStopAutoWalkOnInput(bool),
     ChangeAutoCamera(bool),
     ChangeBowZoom(bool),
+    ChangeSaveSessionHistory(bool),
 
     ResetGameplaySettings,
 }
@@ -75,6 +107,7 @@ pub enum Graphics {
     AdjustLodDistance(u32),
     AdjustLodDetail(u32),
     AdjustSpriteRenderDistance(u32),
+    AdjustSpriteDensity(f32),
     AdjustFigureLoDRenderDistance(u32),
 
     ChangeMaxFPS(Fps),
@@ -84,11 +117,13 @@ pub enum Graphics {
     ChangeGamma(f32),
     ChangeExposure(f32),
     ChangeAmbiance(f32),
+    ChangeColorGrading(f32),
 
     ChangeRenderMode(Box<RenderMode>),
 
     ChangeFullscreenMode(FullScreenSettings),
     ToggleParticlesEnabled(bool),
+    ChangeAdaptiveQuality(AdaptiveQualitySettings),
     ToggleWeaponTrailsEnabled(bool),
     AdjustWindowSize([u16; 2]),
 
@@ -104,12 +139,14 @@ pub enum Interface {
     SpeechBubbleSelf(bool),
     SpeechBubbleDarkMode(bool),
     SpeechBubbleIcon(bool),
+    SpeechBubblePortrait(bool),
     ToggleHelp(bool),
     ToggleDebug(bool),
     ToggleHitboxes(bool),
     ToggleChat(bool),
     ToggleTips(bool),
     ToggleHotkeyHints(bool),
+    ToggleStreamerMode(bool),
 
     CrosshairTransp(f32),
     CrosshairType(CrosshairType),
@@ -125,6 +162,7 @@ pub enum Interface {
     MinimapShow(bool),
     MinimapFaceNorth(bool),
     MinimapZoom(f64),
+    MinimapShowEntities(bool),
     //Map settings
     MapZoom(f64),
     MapShowTopoMap(bool),
@@ -135,10 +173,25 @@ pub enum Interface {
     MapShowCaves(bool),
     MapShowTrees(bool),
     MapShowPeaks(bool),
+    MapShowPortals(bool),
     MapShowBiomes(bool),
     MapShowVoxelMap(bool),
     AccumExperience(bool),
 
+    ToggleDamageIndicators(bool),
+    ToggleLowHealthVignette(bool),
+    ToggleHitShake(bool),
+
+    CompassShow(bool),
+    CompassOpacity(f32),
+
+    NameplatePlayerNames(bool),
+    NameplateNpcNames(bool),
+    NameplateLevels(bool),
+    NameplateHealthBars(bool),
+    NameplateDistanceFade(bool),
+    NameplateHostilityColors(bool),
+
     ResetInterfaceSettings,
 }
 #[derive(Clone)]
@@ -160,9 +213,25 @@ pub enum Networking {
     // TODO: reset option (ensure it handles the entity/terrain vd the same as graphics reset
     // option)
 }
+#[derive(Clone)]
+pub enum PhotoMode {
+    AdjustFocusDistance(f32),
+    AdjustAperture(f32),
+    ChangeFilter(PhotoFilter),
+    ChangeSupersampling(u32),
+}
+#[derive(Clone)]
+pub enum Profile {
+    SaveProfile(String),
+    LoadProfile(String),
+    DeleteProfile(String),
+    ImportProfile(PathBuf),
+    ExportProfile(String, PathBuf),
+}
 
 #[derive(Clone)]
 pub enum SettingsChange {
+    Accessibility(Accessibility),
     Audio(Audio),
     Chat(Chat),
     Control(Control),
@@ -172,6 +241,8 @@ pub enum SettingsChange {
     Interface(Interface),
     Language(Language),
     Networking(Networking),
+    PhotoMode(PhotoMode),
+    Profile(Profile),
 }
 
 macro_rules! settings_change_from {
@@ -181,6 +252,7 @@ macro_rules! settings_change_from {
         }
     };
 }
+settings_change_from!(Accessibility);
 settings_change_from!(Audio);
 settings_change_from!(Chat);
 settings_change_from!(Control);
@@ -190,11 +262,37 @@ settings_change_from!(Graphics);
 settings_change_from!(Interface);
 settings_change_from!(Language);
 settings_change_from!(Networking);
+settings_change_from!(PhotoMode);
+settings_change_from!(Profile);
 
 impl SettingsChange {
     pub fn process(self, global_state: &mut GlobalState, session_state: &mut SessionState) {
         let mut settings = &mut global_state.settings;
         match self {
+            SettingsChange::Accessibility(accessibility_change) => match accessibility_change {
+                Accessibility::ChangeColorPalette(color_palette) => {
+                    settings.accessibility.color_palette = color_palette;
+                },
+                Accessibility::AdjustTextScale(text_scale) => {
+                    settings.accessibility.text_scale = text_scale;
+                    session_state.hud.set_text_scale(text_scale);
+                },
+                Accessibility::ToggleReduceCameraShake(reduce_camera_shake) => {
+                    settings.accessibility.reduce_camera_shake = reduce_camera_shake;
+                },
+                Accessibility::ToggleReduceFlashes(reduce_flashes) => {
+                    settings.accessibility.reduce_flashes = reduce_flashes;
+                },
+                Accessibility::ToggleSubtitles(subtitles) => {
+                    settings.accessibility.subtitles = subtitles;
+                },
+                Accessibility::ResetAccessibilitySettings => {
+                    settings.accessibility = AccessibilitySettings::default();
+                    session_state
+                        .hud
+                        .set_text_scale(settings.accessibility.text_scale);
+                },
+            },
             SettingsChange::Audio(audio_change) => {
                 match audio_change {
                     Audio::AdjustMasterVolume(master_volume) => {
@@ -225,16 +323,42 @@ impl SettingsChange {
 
                         settings.audio.music_spacing = multiplier;
                     },
-                    //Audio::ChangeAudioDevice(name) => {
-                    //    global_state.audio.set_device(name.clone());
+                    #[cfg(feature = "voice-chat")]
+                    Audio::ToggleVoiceChat(enabled) => {
+                        session_state.apply_voice_chat(&mut global_state.audio, enabled);
 
-                    //    settings.audio.output = AudioOutput::Device(name);
-                    //},
+                        settings.audio.voice_chat = enabled;
+                    },
+                    #[cfg(feature = "voice-chat")]
+                    Audio::AdjustVoiceVolume(voice_volume) => {
+                        global_state.audio.set_voice_volume(voice_volume);
+
+                        settings.audio.voice_volume = voice_volume;
+                    },
+                    Audio::ChangeAudioDevice(device) => {
+                        // Rodio must not be called while audio is off
+                        if settings.audio.output.is_enabled() {
+                            global_state.audio.set_device(device.clone());
+
+                            settings.audio.output =
+                                device.map_or(AudioOutput::Automatic, AudioOutput::Device);
+                        }
+                    },
                     Audio::ResetAudioSettings => {
+                        let output_enabled = settings.audio.output.is_enabled();
                         settings.audio = AudioSettings::default();
                         let audio = &settings.audio;
+                        if output_enabled {
+                            global_state.audio.set_device(audio.output.device());
+                        }
                         global_state.audio.set_music_volume(audio.music_volume);
                         global_state.audio.set_sfx_volume(audio.sfx_volume);
+                        #[cfg(feature = "voice-chat")]
+                        {
+                            global_state.audio.set_voice_volume(audio.voice_volume);
+                            session_state
+                                .apply_voice_chat(&mut global_state.audio, audio.voice_chat);
+                        }
                     },
                 }
             },
@@ -247,6 +371,9 @@ impl SettingsChange {
                     Chat::CharName(chat_char_name) => {
                         settings.chat.chat_character_name = chat_char_name;
                     },
+                    Chat::HistoryLines(history_lines) => {
+                        settings.chat.chat_history_lines = history_lines;
+                    },
                     Chat::ChangeChatTab(chat_tab_index) => {
                         settings.chat.chat_tab_index =
                             chat_tab_index.filter(|i| *i < chat_tabs.len());
@@ -288,10 +415,53 @@ impl SettingsChange {
                     global_state.window.toggle_keybinding_mode();
                 },
                 Control::ResetKeyBindings => {
-                    settings.controls = ControlSettings::default();
+                    settings.controls = ControlSettings::from_scheme(settings.controls.scheme);
+                    global_state.window.binding_conflict = None;
                 },
+                Control::SwapBindings => {
+                    if let Some(conflict) = global_state.window.binding_conflict.take() {
+                        settings.controls.swap_bindings(&conflict);
+                    }
+                },
+                Control::KeepBindings => {
+                    global_state.window.binding_conflict = None;
+                },
+                Control::ChangeScheme(scheme) => {
+                    settings.controls.set_scheme(scheme);
+                },
+            },
+            SettingsChange::Gamepad(gamepad_change) => {
+                let controller = &mut settings.controller;
+                match gamepad_change {
+                    Gamepad::ChangeBinding(binding) => {
+                        global_state.window.set_gamepad_binding_mode(binding);
+                    },
+                    Gamepad::SetBinding(binding, button) => {
+                        if let Some(bound) = controller.button_mut(binding) {
+                            *bound = button;
+                        }
+                    },
+                    Gamepad::RemoveBinding(binding) => {
+                        if let Some(bound) = controller.button_mut(binding) {
+                            *bound = GamepadButton::default();
+                        }
+                    },
+                    Gamepad::AdjustPanSensitivity(sensitivity) => {
+                        controller.pan_sensitivity = sensitivity;
+                    },
+                    Gamepad::AdjustStickDeadzone(deadzone) => {
+                        controller.stick_deadzone = deadzone;
+                    },
+                    Gamepad::AdjustMouseEmulationSensitivity(sensitivity) => {
+                        controller.mouse_emulation_sensitivity = sensitivity;
+                    },
+                    Gamepad::ResetGamepadSettings => {
+                        *controller = GamepadSettings::default();
+                    },
+                }
+                global_state.window.controller_settings =
+                    ControllerSettings::from(&settings.controller);
             },
-            SettingsChange::Gamepad(gamepad_change) => match gamepad_change {},
             SettingsChange::Gameplay(gameplay_change) => {
                 let mut window = &mut global_state.window;
                 match gameplay_change {
@@ -339,6 +509,9 @@ impl SettingsChange {
                     Gameplay::ChangeBowZoom(state) => {
                         settings.gameplay.bow_zoom = state;
                     },
+                    Gameplay::ChangeSaveSessionHistory(state) => {
+                        settings.gameplay.save_session_history = state;
+                    },
                     Gameplay::ResetGameplaySettings => {
                         // Reset Gameplay Settings
                         settings.gameplay = GameplaySettings::default();
@@ -373,13 +546,15 @@ impl SettingsChange {
                         settings.graphics.lod_distance = lod_distance;
                     },
                     Graphics::AdjustLodDetail(lod_detail) => {
-                        session_state.scene.lod.set_detail(lod_detail);
-
                         settings.graphics.lod_detail = lod_detail;
+                        session_state.apply_view_distances(settings);
                     },
                     Graphics::AdjustSpriteRenderDistance(sprite_render_distance) => {
                         settings.graphics.sprite_render_distance = sprite_render_distance;
                     },
+                    Graphics::AdjustSpriteDensity(sprite_density) => {
+                        settings.graphics.sprite_density = sprite_density;
+                    },
                     Graphics::AdjustFigureLoDRenderDistance(figure_lod_render_distance) => {
                         settings.graphics.figure_lod_render_distance = figure_lod_render_distance;
                     },
@@ -406,6 +581,9 @@ impl SettingsChange {
                     Graphics::ChangeAmbiance(new_ambiance) => {
                         settings.graphics.ambiance = new_ambiance;
                     },
+                    Graphics::ChangeColorGrading(new_color_grading) => {
+                        settings.graphics.color_grading = new_color_grading;
+                    },
                     Graphics::ChangeRenderMode(new_render_mode) => {
                         // Do this first so if it crashes the setting isn't saved :)
                         global_state
@@ -424,6 +602,10 @@ impl SettingsChange {
                     Graphics::ToggleParticlesEnabled(particles_enabled) => {
                         settings.graphics.particles_enabled = particles_enabled;
                     },
+                    Graphics::ChangeAdaptiveQuality(adaptive_quality) => {
+                        settings.graphics.adaptive_quality = adaptive_quality;
+                        session_state.apply_view_distances(settings);
+                    },
                     Graphics::ToggleWeaponTrailsEnabled(weapon_trails_enabled) => {
                         settings.graphics.weapon_trails_enabled = weapon_trails_enabled;
                     },
@@ -434,16 +616,14 @@ impl SettingsChange {
                     Graphics::ResetGraphicsSettings => {
                         settings.graphics = GraphicsSettings::default();
                         let graphics = &settings.graphics;
-                        // View distance
-                        client_set_view_distance(settings, session_state);
+                        // View distance and LoD
+                        session_state.apply_view_distances(settings);
                         // FOV
                         session_state.scene.camera_mut().set_fov_deg(graphics.fov);
                         session_state
                             .scene
                             .camera_mut()
                             .compute_dependents(&*session_state.client.borrow().state().terrain());
-                        // LoD
-                        session_state.scene.lod.set_detail(graphics.lod_detail);
                         // Render mode
                         global_state
                             .window
@@ -483,6 +663,9 @@ impl SettingsChange {
                     Interface::SpeechBubbleIcon(sbi) => {
                         settings.interface.speech_bubble_icon = sbi;
                     },
+                    Interface::SpeechBubblePortrait(sbp) => {
+                        settings.interface.speech_bubble_portrait = sbp;
+                    },
                     Interface::ToggleHelp(_) => {
                         // implemented in hud
                     },
@@ -501,6 +684,9 @@ impl SettingsChange {
                     Interface::ToggleHotkeyHints(toggle_hotkey_hints) => {
                         settings.interface.toggle_hotkey_hints = toggle_hotkey_hints;
                     },
+                    Interface::ToggleStreamerMode(streamer_mode) => {
+                        settings.interface.streamer_mode = streamer_mode;
+                    },
                     Interface::CrosshairTransp(crosshair_opacity) => {
                         settings.interface.crosshair_opacity = crosshair_opacity;
                     },
@@ -537,6 +723,9 @@ impl SettingsChange {
                     Interface::MinimapZoom(minimap_zoom) => {
                         settings.interface.minimap_zoom = minimap_zoom;
                     },
+                    Interface::MinimapShowEntities(state) => {
+                        settings.interface.minimap_show_entities = state;
+                    },
                     Interface::MapZoom(map_zoom) => {
                         settings.interface.map_zoom = map_zoom;
                     },
@@ -564,6 +753,9 @@ impl SettingsChange {
                     Interface::MapShowPeaks(map_show_peaks) => {
                         settings.interface.map_show_peaks = map_show_peaks;
                     },
+                    Interface::MapShowPortals(map_show_portals) => {
+                        settings.interface.map_show_portals = map_show_portals;
+                    },
                     Interface::MapShowBiomes(map_show_biomes) => {
                         settings.interface.map_show_biomes = map_show_biomes;
                     },
@@ -573,6 +765,39 @@ impl SettingsChange {
                     Interface::AccumExperience(accum_experience) => {
                         settings.interface.accum_experience = accum_experience;
                     },
+                    Interface::ToggleDamageIndicators(damage_indicators) => {
+                        settings.interface.damage_indicators = damage_indicators;
+                    },
+                    Interface::ToggleLowHealthVignette(low_health_vignette) => {
+                        settings.interface.low_health_vignette = low_health_vignette;
+                    },
+                    Interface::ToggleHitShake(hit_shake) => {
+                        settings.interface.hit_shake = hit_shake;
+                    },
+                    Interface::CompassShow(compass_show) => {
+                        settings.interface.compass_show = compass_show;
+                    },
+                    Interface::CompassOpacity(compass_opacity) => {
+                        settings.interface.compass_opacity = compass_opacity;
+                    },
+                    Interface::NameplatePlayerNames(show) => {
+                        settings.interface.nameplate_player_names = show;
+                    },
+                    Interface::NameplateNpcNames(show) => {
+                        settings.interface.nameplate_npc_names = show;
+                    },
+                    Interface::NameplateLevels(show) => {
+                        settings.interface.nameplate_levels = show;
+                    },
+                    Interface::NameplateHealthBars(show) => {
+                        settings.interface.nameplate_health_bars = show;
+                    },
+                    Interface::NameplateDistanceFade(fade) => {
+                        settings.interface.nameplate_distance_fade = fade;
+                    },
+                    Interface::NameplateHostilityColors(colors) => {
+                        settings.interface.nameplate_hostility_colors = colors;
+                    },
                     Interface::ResetInterfaceSettings => {
                         // Reset Interface Settings
                         let tmp = settings.interface.intro_show;
@@ -593,7 +818,9 @@ impl SettingsChange {
                     global_state
                         .i18n
                         .set_english_fallback(settings.language.use_english_fallback);
-                    session_state.hud.update_fonts(&global_state.i18n.read());
+                    session_state
+                        .hud
+                        .update_fonts(&global_state.i18n.read(), settings.accessibility.text_scale);
                 },
                 Language::ToggleEnglishFallback(toggle_fallback) => {
                     settings.language.use_english_fallback = toggle_fallback;
@@ -651,6 +878,61 @@ impl SettingsChange {
                     }
                 },
             },
+            SettingsChange::PhotoMode(photo_mode_change) => match photo_mode_change {
+                PhotoMode::AdjustFocusDistance(focus_distance) => {
+                    settings.photo_mode.focus_distance = focus_distance;
+                },
+                PhotoMode::AdjustAperture(aperture) => {
+                    settings.photo_mode.aperture = aperture;
+                },
+                PhotoMode::ChangeFilter(filter) => {
+                    settings.photo_mode.filter = filter;
+                },
+                PhotoMode::ChangeSupersampling(supersampling) => {
+                    settings.photo_mode.supersampling = supersampling;
+                },
+            },
+            SettingsChange::Profile(profile_change) => {
+                let config_dir = global_state.config_dir.clone();
+                let result = match profile_change {
+                    Profile::SaveProfile(name) => SettingsProfile::of(settings)
+                        .save(&config_dir, &name)
+                        .map(|()| ("hud-settings-profile_saved", name)),
+                    Profile::LoadProfile(name) => match SettingsProfile::load(&config_dir, &name) {
+                        Ok(profile) => {
+                            profile.apply_to(settings);
+                            apply_settings_profile(global_state, session_state);
+                            Ok(("hud-settings-profile_loaded", name))
+                        },
+                        Err(err) => Err(err),
+                    },
+                    Profile::DeleteProfile(name) => SettingsProfile::delete(&config_dir, &name)
+                        .map(|()| ("hud-settings-profile_deleted", name)),
+                    Profile::ImportProfile(path) => {
+                        // Imported profiles are saved under the name of their file
+                        let name = path
+                            .file_stem()
+                            .map(|stem| stem.to_string_lossy().into_owned())
+                            .unwrap_or_default();
+                        SettingsProfile::import(&path)
+                            .and_then(|profile| profile.save(&config_dir, &name))
+                            .map(|()| ("hud-settings-profile_imported", name))
+                    },
+                    Profile::ExportProfile(name, path) => SettingsProfile::load(&config_dir, &name)
+                        .and_then(|profile| profile.export(&path))
+                        .map(|()| ("hud-settings-profile_exported", path.display().to_string())),
+                };
+                let i18n = global_state.i18n.read();
+                let msg = match result {
+                    Ok((key, name)) => ChatType::CommandInfo
+                        .chat_msg(i18n.get_msg_ctx(key, &i18n::fluent_args! { "name" => name })),
+                    Err(error) => ChatType::CommandError.chat_msg(i18n.get_msg_ctx(
+                        "hud-settings-profile_error",
+                        &i18n::fluent_args! { "error" => error },
+                    )),
+                };
+                session_state.hud.new_message(msg);
+            },
         }
         global_state
             .settings
@@ -666,7 +948,7 @@ fn adjust_terrain_view_distance(
     session_state: &mut SessionState,
 ) {
     settings.graphics.terrain_view_distance = terrain_vd;
-    client_set_view_distance(settings, session_state);
+    session_state.apply_view_distances(settings);
 }
 
 fn adjust_entity_view_distance(
@@ -675,16 +957,53 @@ fn adjust_entity_view_distance(
     session_state: &mut SessionState,
 ) {
     settings.graphics.entity_view_distance = entity_vd;
-    client_set_view_distance(settings, session_state);
+    session_state.apply_view_distances(settings);
 }
 
-fn client_set_view_distance(settings: &Settings, session_state: &mut SessionState) {
-    let view_distances = common::ViewDistances {
-        terrain: settings.graphics.terrain_view_distance,
-        entity: settings.graphics.entity_view_distance,
-    };
+/// Applies the graphics, audio and interface settings just replaced by a
+/// profile, the keybindings being read from the settings as they are used
+fn apply_settings_profile(global_state: &mut GlobalState, session_state: &mut SessionState) {
+    let settings = &global_state.settings;
+    let graphics = &settings.graphics;
+    // View distance and LoD
+    session_state.apply_view_distances(settings);
+    // FOV
+    session_state.scene.camera_mut().set_fov_deg(graphics.fov);
+    session_state
+        .scene
+        .camera_mut()
+        .compute_dependents(&*session_state.client.borrow().state().terrain());
+    // Render mode
+    global_state
+        .window
+        .renderer_mut()
+        .set_render_mode(graphics.render_mode.clone())
+        .unwrap();
+    // Fullscreen mode
+    global_state.window.set_fullscreen_mode(graphics.fullscreen);
+    // Window size
+    global_state.window.set_size(graphics.window_size.into());
+    // Volumes
+    let audio = &settings.audio;
+    global_state.audio.set_master_volume(audio.master_volume);
+    global_state.audio.set_music_volume(audio.music_volume);
+    global_state.audio.set_sfx_volume(audio.sfx_volume);
+    global_state
+        .audio
+        .set_ambience_volume(audio.ambience_volume);
+    global_state.audio.set_music_spacing(audio.music_spacing);
+    // Output device, audio being turned on or off only on restart
+    let device = audio.output.device();
+    if audio.output.is_enabled() && global_state.audio.device() != device.as_deref() {
+        global_state.audio.set_device(device);
+    }
+    #[cfg(feature = "voice-chat")]
+    {
+        global_state.audio.set_voice_volume(audio.voice_volume);
+        session_state.apply_voice_chat(&mut global_state.audio, audio.voice_chat);
+    }
+    // Scaling mode
     session_state
-        .client
-        .borrow_mut()
-        .set_view_distances(view_distances);
+        .hud
+        .set_scaling_mode(settings.interface.ui_scale);
 }
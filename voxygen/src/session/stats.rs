@@ -0,0 +1,137 @@
+use common::{outcome::Outcome, uid::Uid};
+use i18n::Localization;
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use vek::*;
+
+/// Movement between two ticks above this distance is assumed to be a teleport
+/// (waypoints, respawns, ...) and isn't counted as distance traveled
+const MAX_TICK_TRAVEL_DIST: f32 = 50.0;
+
+/// File inside the config dir that session summaries are appended to
+const HISTORY_FILE: &str = "session_history.log";
+
+/// Statistics of the current play session, tracked client-side and shown to
+/// the player once they log out or get disconnected
+pub struct SessionStats {
+    start: Instant,
+    xp_gained: u64,
+    deaths: u32,
+    items_looted: u32,
+    distance_traveled: f32,
+    last_pos: Option<Vec3<f32>>,
+    was_dead: bool,
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            xp_gained: 0,
+            deaths: 0,
+            items_looted: 0,
+            distance_traveled: 0.0,
+            last_pos: None,
+            was_dead: false,
+        }
+    }
+}
+
+impl SessionStats {
+    pub fn record_outcome(&mut self, outcome: &Outcome, player: Option<Uid>) {
+        if let Outcome::ExpChange { uid, exp, .. } = outcome {
+            if Some(*uid) == player {
+                self.xp_gained += u64::from(*exp);
+            }
+        }
+    }
+
+    pub fn record_loot(&mut self, amount: u32) { self.items_looted += amount; }
+
+    /// Count deaths on the transition from alive to dead
+    pub fn record_health(&mut self, is_dead: bool) {
+        if is_dead && !self.was_dead {
+            self.deaths += 1;
+        }
+        self.was_dead = is_dead;
+    }
+
+    pub fn record_position(&mut self, pos: Vec3<f32>) {
+        if let Some(last_pos) = self.last_pos {
+            let dist = last_pos.distance(pos);
+            if dist < MAX_TICK_TRAVEL_DIST {
+                self.distance_traveled += dist;
+            }
+        }
+        self.last_pos = Some(pos);
+    }
+
+    pub fn time_played(&self) -> Duration { self.start.elapsed() }
+
+    /// Localized, multi-line summary of the session
+    pub fn summary(&self, i18n: &Localization) -> String {
+        let secs = self.time_played().as_secs();
+        i18n.get_msg_ctx("hud-session-summary", &i18n::fluent_args! {
+            "time" => format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60),
+            "xp" => self.xp_gained,
+            "deaths" => self.deaths,
+            "items" => self.items_looted,
+            // Blocks are roughly a meter
+            "distance" => format!("{:.1}", self.distance_traveled / 1000.0),
+        })
+        .into_owned()
+    }
+
+    /// Append a single line summary of the session to the history file in
+    /// `config_dir`
+    pub fn append_to_history(&self, config_dir: &Path, character: &str) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(config_dir.join(HISTORY_FILE))?;
+        writeln!(
+            file,
+            "{}\t{}\ttime={}s\txp={}\tdeaths={}\titems={}\tdistance={:.0}",
+            timestamp,
+            character,
+            self.time_played().as_secs(),
+            self.xp_gained,
+            self.deaths,
+            self.items_looted,
+            self.distance_traveled,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deaths_counted_once() {
+        let mut stats = SessionStats::default();
+        stats.record_health(false);
+        stats.record_health(true);
+        stats.record_health(true);
+        stats.record_health(false);
+        stats.record_health(true);
+        assert_eq!(stats.deaths, 2);
+    }
+
+    #[test]
+    fn teleports_not_counted() {
+        let mut stats = SessionStats::default();
+        stats.record_position(Vec3::zero());
+        stats.record_position(Vec3::new(3.0, 4.0, 0.0));
+        stats.record_position(Vec3::new(1000.0, 4.0, 0.0));
+        stats.record_position(Vec3::new(1000.0, 14.0, 0.0));
+        assert!((stats.distance_traveled - 15.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+/// Colours of the health bars and damage numbers, the other ones being told
+/// apart with the colour vision deficiency
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum ColorPalette {
+    Default,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl ColorPalette {
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            Self::Default => "hud-settings-palette-default",
+            Self::Deuteranopia => "hud-settings-palette-deuteranopia",
+            Self::Protanopia => "hud-settings-palette-protanopia",
+            Self::Tritanopia => "hud-settings-palette-tritanopia",
+        }
+    }
+}
+
+/// `AccessibilitySettings` contains the options making the game easier to see
+/// and to follow.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    pub color_palette: ColorPalette,
+    /// Scale of the HUD text, on top of the UI scale
+    pub text_scale: f32,
+    /// Removes the camera shake on hits and explosions
+    pub reduce_camera_shake: bool,
+    /// Removes the flashes of lightning and of the health bars on hits
+    pub reduce_flashes: bool,
+    /// Shows captions of the sound cues near the player
+    pub subtitles: bool,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            color_palette: ColorPalette::Default,
+            text_scale: 1.0,
+            reduce_camera_shake: false,
+            reduce_flashes: false,
+            subtitles: false,
+        }
+    }
+}
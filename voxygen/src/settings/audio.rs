@@ -8,12 +8,23 @@ pub enum AudioOutput {
     // If this option is disabled, functions in the rodio
     // library MUST NOT be called.
     Off,
+    /// Output device chosen in the settings, the default device being used
+    /// while it is unavailable
+    Device(String),
     #[serde(other)]
     Automatic,
 }
 
 impl AudioOutput {
     pub fn is_enabled(&self) -> bool { !matches!(self, Self::Off) }
+
+    /// Output device chosen in the settings, `None` for the default device
+    pub fn device(&self) -> Option<String> {
+        match self {
+            Self::Device(device) => Some(device.clone()),
+            Self::Off | Self::Automatic => None,
+        }
+    }
 }
 /// `AudioSettings` controls the volume of different audio subsystems and which
 /// device is used.
@@ -29,6 +40,9 @@ pub struct AudioSettings {
     pub num_sfx_channels: usize,
     pub num_ui_channels: usize,
     pub music_spacing: f32,
+    /// Whether to join the voice chat of the servers that have one
+    pub voice_chat: bool,
+    pub voice_volume: f32,
 
     /// Audio Device that Voxygen will use to play audio.
     pub output: AudioOutput,
@@ -45,6 +59,8 @@ impl Default for AudioSettings {
             num_sfx_channels: 60,
             num_ui_channels: 10,
             music_spacing: 1.0,
+            voice_chat: false,
+            voice_volume: 1.0,
             output: AudioOutput::Automatic,
         }
     }
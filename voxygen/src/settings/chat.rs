@@ -9,6 +9,7 @@ use std::collections::HashSet;
 pub const MAX_CHAT_TABS: usize = 5;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct ChatFilter {
     //messages
     pub message_all: bool,
@@ -17,6 +18,11 @@ pub struct ChatFilter {
     pub message_say: bool,
     pub message_group: bool,
     pub message_faction: bool,
+    pub message_guild: bool,
+    //whispers
+    pub message_tell: bool,
+    //command replies and server notices
+    pub system: bool,
     //activity (login/logout)
     pub activity_all: bool,
     pub activity_group: bool,
@@ -30,20 +36,20 @@ impl ChatFilter {
             ChatType::Online(u) | ChatType::Offline(u) => {
                 self.activity_all || (self.activity_group && group_members.contains(u))
             },
-            ChatType::CommandInfo | ChatType::CommandError => true,
+            ChatType::CommandInfo | ChatType::CommandError | ChatType::Meta => self.system,
             ChatType::Kill(_, u) => self.death_all || self.death_group && group_members.contains(u),
             ChatType::GroupMeta(_) => true,   //todo
             ChatType::FactionMeta(_) => true, //todo
-            ChatType::Tell(..) => true,
+            ChatType::GuildMeta(_) => true,
+            ChatType::Tell(..) | ChatType::NpcTell(..) => self.message_tell,
             ChatType::Say(_) => self.message_all || self.message_say,
             ChatType::Group(..) => self.message_all || self.message_group,
             ChatType::Faction(..) => self.message_all || self.message_faction,
+            ChatType::Guild(..) => self.message_all || self.message_guild,
             ChatType::Region(_) => self.message_all || self.message_region,
             ChatType::World(_) => self.message_all || self.message_world,
             ChatType::Npc(..) => true,
             ChatType::NpcSay(..) => true,
-            ChatType::NpcTell(..) => true,
-            ChatType::Meta => true,
         }
     }
 }
@@ -56,6 +62,10 @@ impl Default for ChatFilter {
             message_say: true,
             message_group: true,
             message_faction: true,
+            message_guild: true,
+
+            message_tell: true,
+            system: true,
 
             activity_all: false,
             activity_group: true,
@@ -74,6 +84,8 @@ pub struct ChatSettings {
     pub chat_tabs: Vec<ChatTab>,
     pub chat_tab_index: Option<usize>,
     pub chat_cmd_prefix: char,
+    /// Lines of chat kept between sessions on each server, none are kept at 0
+    pub chat_history_lines: u32,
 }
 
 impl Default for ChatSettings {
@@ -84,6 +96,7 @@ impl Default for ChatSettings {
             chat_tabs: vec![ChatTab::default()],
             chat_tab_index: Some(0),
             chat_cmd_prefix: '/',
+            chat_history_lines: 100,
         }
     }
 }
@@ -1,29 +1,30 @@
-use crate::{game_input::GameInput, window::KeyMouse};
+use crate::{
+    game_input::GameInput,
+    window::{KeyMouse, ScrollDirection},
+};
+use common::assets::{self, AssetExt};
 use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
+use tracing::warn;
 use winit::event::{MouseButton, VirtualKeyCode};
 
 // ControlSetting-like struct used by Serde, to handle not serializing/building
 // post-deserializing the inverse_keybindings hashmap
 #[derive(Serialize, Deserialize)]
 struct ControlSettingsSerde {
+    #[serde(default)]
+    scheme: KeybindingScheme,
     keybindings: HashMap<GameInput, Option<KeyMouse>>,
 }
 
 impl From<ControlSettings> for ControlSettingsSerde {
     fn from(control_settings: ControlSettings) -> Self {
-        let mut user_bindings: HashMap<GameInput, Option<KeyMouse>> = HashMap::new();
-        // Do a delta between default() ControlSettings and the argument, and let
-        // keybindings be only the custom keybindings chosen by the user.
-        for (k, v) in control_settings.keybindings {
-            if Some(ControlSettings::default_binding(k)) != v {
-                // Keybinding chosen by the user
-                user_bindings.insert(k, v);
-            }
-        }
+        // Only the keybindings chosen by the user are saved, so that updates to
+        // the scheme still reach the other ones
         ControlSettingsSerde {
-            keybindings: user_bindings,
+            scheme: control_settings.scheme,
+            keybindings: control_settings.customized_bindings(),
         }
     }
 }
@@ -32,20 +33,16 @@ impl From<ControlSettings> for ControlSettingsSerde {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(from = "ControlSettingsSerde", into = "ControlSettingsSerde")]
 pub struct ControlSettings {
+    /// Scheme the keybindings are based on
+    pub scheme: KeybindingScheme,
     pub keybindings: HashMap<GameInput, Option<KeyMouse>>,
     pub inverse_keybindings: HashMap<KeyMouse, HashSet<GameInput>>, // used in event loop
 }
 
 impl From<ControlSettingsSerde> for ControlSettings {
     fn from(control_serde: ControlSettingsSerde) -> Self {
-        let user_keybindings = control_serde.keybindings;
-        let mut control_settings = ControlSettings::default();
-        for (k, maybe_v) in user_keybindings {
-            match maybe_v {
-                Some(v) => control_settings.modify_binding(k, v),
-                None => control_settings.remove_binding(k),
-            }
-        }
+        let mut control_settings = ControlSettings::from_scheme(control_serde.scheme);
+        control_settings.customize(control_serde.keybindings);
         control_settings
     }
 }
@@ -60,7 +57,144 @@ const MIDDLE_CLICK_KEY: KeyMouse = KeyMouse::Key(VirtualKeyCode::Grave);
 #[cfg(not(target_os = "macos"))]
 const MIDDLE_CLICK_KEY: KeyMouse = KeyMouse::Mouse(MouseButton::Middle);
 
+/// Bindings of a keybinding scheme which differ from the default ones,
+/// loaded from `voxygen.keybinding_schemes`.
+#[derive(Clone, Deserialize)]
+struct SchemeBindings(HashMap<GameInput, Option<KeyMouse>>);
+impl assets::Asset for SchemeBindings {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+/// Built-in keybinding schemes, offered on first launch and in the controls
+/// settings.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeybindingScheme {
+    /// Movement shifted to ESDF, leaving more keys in reach of the left hand
+    Alternate,
+    /// Movement on IJKL and the modifiers on the right side of the keyboard,
+    /// for players using the mouse with their left hand
+    LeftHanded,
+    /// Movement left to a gamepad, the keyboard and mouse being kept for the
+    /// abilities and menus
+    ControllerHybrid,
+    /// The default WASD layout
+    // other variant has to be placed last
+    #[serde(other)]
+    Default,
+}
+
+impl Default for KeybindingScheme {
+    fn default() -> Self { Self::Default }
+}
+
+impl KeybindingScheme {
+    pub const ALL: [Self; 4] = [
+        Self::Default,
+        Self::Alternate,
+        Self::LeftHanded,
+        Self::ControllerHybrid,
+    ];
+
+    pub fn i18n_key(&self) -> &'static str {
+        match self {
+            Self::Default => "main-setup-scheme-default",
+            Self::Alternate => "main-setup-scheme-alternate",
+            Self::LeftHanded => "main-setup-scheme-left_handed",
+            Self::ControllerHybrid => "main-setup-scheme-controller_hybrid",
+        }
+    }
+
+    fn asset_specifier(&self) -> &'static str {
+        match self {
+            Self::Default => "voxygen.keybinding_schemes.default",
+            Self::Alternate => "voxygen.keybinding_schemes.esdf",
+            Self::LeftHanded => "voxygen.keybinding_schemes.left_handed",
+            Self::ControllerHybrid => "voxygen.keybinding_schemes.controller_hybrid",
+        }
+    }
+
+    /// Bindings of every game input in this scheme
+    pub fn bindings(&self) -> HashMap<GameInput, Option<KeyMouse>> {
+        let scheme_bindings = SchemeBindings::load_cloned(self.asset_specifier())
+            .map(|scheme_bindings| scheme_bindings.0)
+            .unwrap_or_else(|err| {
+                warn!(
+                    ?err,
+                    ?self,
+                    "Failed to load the keybinding scheme, using the defaults"
+                );
+                HashMap::new()
+            });
+        GameInput::iter()
+            .map(|game_input| {
+                let binding = scheme_bindings
+                    .get(&game_input)
+                    .copied()
+                    .unwrap_or_else(|| Some(ControlSettings::default_binding(game_input)));
+                (game_input, binding)
+            })
+            .collect()
+    }
+}
+
+/// A game input given a key already used by others, which can't share it,
+/// until the player chose whether to swap the bindings
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindingConflict {
+    pub game_input: GameInput,
+    pub key_mouse: KeyMouse,
+    /// Binding the game input had before, offered to the other ones
+    pub previous: Option<KeyMouse>,
+    pub others: Vec<GameInput>,
+}
+
 impl ControlSettings {
+    /// Create settings with the bindings of the given scheme.
+    pub fn from_scheme(scheme: KeybindingScheme) -> Self {
+        let mut settings = Self {
+            scheme,
+            keybindings: HashMap::new(),
+            inverse_keybindings: HashMap::new(),
+        };
+        for (game_input, binding) in scheme.bindings() {
+            match binding {
+                Some(key_mouse) => settings.insert_binding(game_input, key_mouse),
+                None => {
+                    settings.keybindings.insert(game_input, None);
+                },
+            }
+        }
+        settings
+    }
+
+    /// Switch to another scheme, keeping the keybindings chosen by the user.
+    pub fn set_scheme(&mut self, scheme: KeybindingScheme) {
+        let customized = self.customized_bindings();
+        *self = Self::from_scheme(scheme);
+        self.customize(customized);
+    }
+
+    /// The keybindings chosen by the user, which differ from the scheme's
+    fn customized_bindings(&self) -> HashMap<GameInput, Option<KeyMouse>> {
+        let scheme_bindings = self.scheme.bindings();
+        self.keybindings
+            .iter()
+            .filter(|(game_input, binding)| scheme_bindings.get(game_input) != Some(binding))
+            .map(|(game_input, binding)| (*game_input, *binding))
+            .collect()
+    }
+
+    fn customize(&mut self, bindings: HashMap<GameInput, Option<KeyMouse>>) {
+        for (game_input, binding) in bindings {
+            match binding {
+                Some(key_mouse) => self.modify_binding(game_input, key_mouse),
+                None => self.remove_binding(game_input),
+            }
+        }
+    }
+
     pub fn remove_binding(&mut self, game_input: GameInput) {
         if let Some(inverse) = self
             .keybindings
@@ -106,6 +240,44 @@ impl ControlSettings {
         self.keybindings.insert(game_input, Some(key_mouse));
     }
 
+    /// Binds the key to the game input, returning the conflict if other game
+    /// inputs using the key can't share it
+    pub fn rebind(
+        &mut self,
+        game_input: GameInput,
+        key_mouse: KeyMouse,
+    ) -> Option<BindingConflict> {
+        let previous = self.get_binding(game_input);
+        self.modify_binding(game_input, key_mouse);
+        let mut others = self
+            .get_associated_game_inputs(&key_mouse)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|other| !GameInput::can_share_bindings(game_input, *other))
+            .collect::<Vec<_>>();
+        others.sort();
+        (!others.is_empty() && previous != Some(key_mouse)).then(|| BindingConflict {
+            game_input,
+            key_mouse,
+            previous,
+            others,
+        })
+    }
+
+    /// Gives the previous binding of the conflicting game input to the others
+    pub fn swap_bindings(&mut self, conflict: &BindingConflict) {
+        for other in &conflict.others {
+            if self.get_binding(*other) != Some(conflict.key_mouse) {
+                continue;
+            }
+            match conflict.previous {
+                Some(previous) => self.modify_binding(*other, previous),
+                None => self.remove_binding(*other),
+            }
+        }
+    }
+
     /// Return true if this key is used for multiple GameInputs that aren't
     /// expected to be safe to have bound to the same key at the same time
     pub fn has_conflicting_bindings(&self, key_mouse: KeyMouse) -> bool {
@@ -130,6 +302,8 @@ impl ControlSettings {
             GameInput::ToggleCursor => KeyMouse::Key(VirtualKeyCode::Comma),
             GameInput::Escape => KeyMouse::Key(VirtualKeyCode::Escape),
             GameInput::Chat => KeyMouse::Key(VirtualKeyCode::Return),
+            #[cfg(feature = "voice-chat")]
+            GameInput::PushToTalk => KeyMouse::Key(VirtualKeyCode::Capital),
             GameInput::Command => KeyMouse::Key(VirtualKeyCode::Slash),
             GameInput::MoveForward => KeyMouse::Key(VirtualKeyCode::W),
             GameInput::MoveLeft => KeyMouse::Key(VirtualKeyCode::A),
@@ -147,11 +321,15 @@ impl ControlSettings {
             GameInput::Fly => KeyMouse::Key(VirtualKeyCode::H),
             GameInput::Sneak => KeyMouse::Key(VirtualKeyCode::LShift),
             GameInput::ToggleLantern => KeyMouse::Key(VirtualKeyCode::G),
+            GameInput::ToggleBuilding => KeyMouse::Key(VirtualKeyCode::Z),
             GameInput::Mount => KeyMouse::Key(VirtualKeyCode::F),
             GameInput::Map => KeyMouse::Key(VirtualKeyCode::M),
             GameInput::Bag => KeyMouse::Key(VirtualKeyCode::B),
             GameInput::Trade => KeyMouse::Key(VirtualKeyCode::T),
             GameInput::Social => KeyMouse::Key(VirtualKeyCode::O),
+            GameInput::Guild => KeyMouse::Key(VirtualKeyCode::U),
+            GameInput::CombatLog => KeyMouse::Key(VirtualKeyCode::I),
+            GameInput::QuestLog => KeyMouse::Key(VirtualKeyCode::F9),
             GameInput::Crafting => KeyMouse::Key(VirtualKeyCode::C),
             GameInput::Spellbook => KeyMouse::Key(VirtualKeyCode::P),
             GameInput::Settings => KeyMouse::Key(VirtualKeyCode::F10),
@@ -163,6 +341,7 @@ impl ControlSettings {
             GameInput::ToggleChat => KeyMouse::Key(VirtualKeyCode::F5),
             GameInput::Fullscreen => KeyMouse::Key(VirtualKeyCode::F11),
             GameInput::Screenshot => KeyMouse::Key(VirtualKeyCode::F4),
+            GameInput::RecordClip => KeyMouse::Key(VirtualKeyCode::F12),
             GameInput::ToggleIngameUi => KeyMouse::Key(VirtualKeyCode::F6),
             GameInput::Roll => MIDDLE_CLICK_KEY,
             GameInput::Respawn => KeyMouse::Key(VirtualKeyCode::Space),
@@ -172,6 +351,10 @@ impl ControlSettings {
             GameInput::AutoWalk => KeyMouse::Key(VirtualKeyCode::Period),
             GameInput::CameraClamp => KeyMouse::Key(VirtualKeyCode::Apostrophe),
             GameInput::CycleCamera => KeyMouse::Key(VirtualKeyCode::Key0),
+            GameInput::ZoomIn => KeyMouse::Scroll(ScrollDirection::Up),
+            GameInput::ZoomOut => KeyMouse::Scroll(ScrollDirection::Down),
+            GameInput::FirstPerson => KeyMouse::Key(VirtualKeyCode::Semicolon),
+            GameInput::PhotoMode => KeyMouse::Key(VirtualKeyCode::F8),
             GameInput::Slot1 => KeyMouse::Key(VirtualKeyCode::Key1),
             GameInput::Slot2 => KeyMouse::Key(VirtualKeyCode::Key2),
             GameInput::Slot3 => KeyMouse::Key(VirtualKeyCode::Key3),
@@ -196,15 +379,55 @@ impl ControlSettings {
 }
 
 impl Default for ControlSettings {
-    fn default() -> Self {
-        let mut new_settings = Self {
-            keybindings: HashMap::new(),
-            inverse_keybindings: HashMap::new(),
-        };
-        // Sets the initial keybindings for those GameInputs.
-        for game_input in GameInput::iter() {
-            new_settings.insert_binding(game_input, ControlSettings::default_binding(game_input));
+    fn default() -> Self { Self::from_scheme(KeybindingScheme::default()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::Modifier;
+
+    #[test]
+    fn customized_bindings_survive_scheme_changes() {
+        for scheme in KeybindingScheme::ALL {
+            SchemeBindings::load_expect(scheme.asset_specifier());
         }
-        new_settings
+
+        let mut settings = ControlSettings::from_scheme(KeybindingScheme::Alternate);
+        let custom = KeyMouse::Key(VirtualKeyCode::F13);
+        settings.modify_binding(GameInput::Map, custom);
+        settings.set_scheme(KeybindingScheme::LeftHanded);
+        assert_eq!(settings.get_binding(GameInput::Map), Some(custom));
+        assert_eq!(
+            settings.get_binding(GameInput::MoveForward),
+            KeybindingScheme::LeftHanded.bindings()[&GameInput::MoveForward]
+        );
+    }
+
+    #[test]
+    fn first_person_toggle_is_free_in_every_scheme() {
+        for scheme in KeybindingScheme::ALL {
+            let settings = ControlSettings::from_scheme(scheme);
+            let key = settings.get_binding(GameInput::FirstPerson).unwrap();
+            assert!(!settings.has_conflicting_bindings(key), "{:?}", scheme);
+        }
+    }
+
+    #[test]
+    fn conflicting_bindings_can_be_swapped() {
+        let mut settings = ControlSettings::default();
+        let map_key = settings.get_binding(GameInput::Map);
+        let bag_key = settings.get_binding(GameInput::Bag).unwrap();
+        let conflict = settings.rebind(GameInput::Map, bag_key).unwrap();
+        assert_eq!(conflict.others, vec![GameInput::Bag]);
+        assert!(settings.has_conflicting_bindings(bag_key));
+
+        settings.swap_bindings(&conflict);
+        assert_eq!(settings.get_binding(GameInput::Map), Some(bag_key));
+        assert_eq!(settings.get_binding(GameInput::Bag), map_key);
+        assert!(!settings.has_conflicting_bindings(bag_key));
+
+        let chord = KeyMouse::Chord(Modifier::Ctrl, VirtualKeyCode::M);
+        assert_eq!(settings.rebind(GameInput::Map, chord), None);
     }
 }
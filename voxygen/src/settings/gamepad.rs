@@ -16,6 +16,23 @@ pub struct GamepadSettings {
     pub button_deadzones: HashMap<crate::controller::AnalogButton, f32>,
     pub mouse_emulation_sensitivity: u32,
     pub inverted_axes: Vec<crate::controller::Axis>,
+    /// Dead zone of the sticks, unless set for an axis in `axis_deadzones`
+    pub stick_deadzone: f32,
+}
+
+impl GamepadSettings {
+    /// The button bound to something, `None` if it can't be bound to a button
+    pub fn button_mut(
+        &mut self,
+        binding: crate::controller::GamepadBinding,
+    ) -> Option<&mut crate::controller::Button> {
+        match binding {
+            crate::controller::GamepadBinding::Game(input) => self.game_buttons.get_mut(input),
+            crate::controller::GamepadBinding::RadialMenu => {
+                Some(&mut self.game_buttons.radial_menu)
+            },
+        }
+    }
 }
 
 impl Default for GamepadSettings {
@@ -33,12 +50,13 @@ impl Default for GamepadSettings {
             button_deadzones: HashMap::new(),
             mouse_emulation_sensitivity: 12,
             inverted_axes: Vec::new(),
+            stick_deadzone: 0.2,
         }
     }
 }
 
 pub mod con_settings {
-    use crate::controller::*;
+    use crate::{controller::*, game_input::GameInput};
     use gilrs::{Axis as GilAxis, Button as GilButton};
     use serde::{Deserialize, Serialize};
 
@@ -99,6 +117,85 @@ pub mod con_settings {
         pub interact: Button,
         pub toggle_wield: Button,
         pub swap_loadout: Button,
+        /// Held to pick a skillbar slot with the camera stick
+        pub radial_menu: Button,
+    }
+
+    /// Maps the fields of `GameButtons` to the game inputs they are bound to
+    macro_rules! game_buttons {
+        ($($field:ident => $input:ident),* $(,)?) => {
+            impl GameButtons {
+                /// The game inputs that can be bound to a button
+                pub const INPUTS: &'static [GameInput] = &[$(GameInput::$input),*];
+
+                pub fn get(&self, input: GameInput) -> Option<Button> {
+                    match input {
+                        $(GameInput::$input => Some(self.$field),)*
+                        _ => None,
+                    }
+                }
+
+                pub fn get_mut(&mut self, input: GameInput) -> Option<&mut Button> {
+                    match input {
+                        $(GameInput::$input => Some(&mut self.$field),)*
+                        _ => None,
+                    }
+                }
+            }
+        };
+    }
+
+    game_buttons! {
+        primary => Primary,
+        secondary => Secondary,
+        block => Block,
+        slot1 => Slot1,
+        slot2 => Slot2,
+        slot3 => Slot3,
+        slot4 => Slot4,
+        slot5 => Slot5,
+        slot6 => Slot6,
+        slot7 => Slot7,
+        slot8 => Slot8,
+        slot9 => Slot9,
+        slot10 => Slot10,
+        toggle_cursor => ToggleCursor,
+        escape => Escape,
+        enter => Chat,
+        command => Command,
+        move_forward => MoveForward,
+        move_left => MoveLeft,
+        move_back => MoveBack,
+        move_right => MoveRight,
+        jump => Jump,
+        sit => Sit,
+        dance => Dance,
+        glide => Glide,
+        climb => Climb,
+        climb_down => ClimbDown,
+        swimup => SwimUp,
+        swimdown => SwimDown,
+        sneak => Sneak,
+        toggle_lantern => ToggleLantern,
+        mount => Mount,
+        map => Map,
+        bag => Bag,
+        social => Social,
+        crafting => Crafting,
+        spellbook => Spellbook,
+        settings => Settings,
+        help => Help,
+        toggle_interface => ToggleInterface,
+        toggle_debug => ToggleDebug,
+        toggle_chat => ToggleChat,
+        fullscreen => Fullscreen,
+        screenshot => Screenshot,
+        toggle_ingame_ui => ToggleIngameUi,
+        roll => Roll,
+        respawn => Respawn,
+        interact => Interact,
+        toggle_wield => ToggleWield,
+        swap_loadout => SwapLoadout,
     }
 
     #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -161,7 +258,7 @@ pub mod con_settings {
                 slot7: Button::Simple(GilButton::Unknown),
                 slot8: Button::Simple(GilButton::Unknown),
                 slot9: Button::Simple(GilButton::Unknown),
-                slot10: Button::Simple(GilButton::DPadDown),
+                slot10: Button::Simple(GilButton::Unknown),
                 toggle_cursor: Button::Simple(GilButton::DPadRight),
                 escape: Button::Simple(GilButton::Select),
                 enter: Button::Simple(GilButton::Unknown),
@@ -203,6 +300,7 @@ pub mod con_settings {
                 interact: Button::Simple(GilButton::North),
                 toggle_wield: Button::Simple(GilButton::West),
                 swap_loadout: Button::Simple(GilButton::DPadUp),
+                radial_menu: Button::Simple(GilButton::DPadDown),
             }
         }
     }
@@ -17,6 +17,7 @@ pub struct GameplaySettings {
     pub stop_auto_walk_on_input: bool,
     pub auto_camera: bool,
     pub bow_zoom: bool,
+    pub save_session_history: bool,
 }
 
 impl Default for GameplaySettings {
@@ -34,6 +35,7 @@ impl Default for GameplaySettings {
             stop_auto_walk_on_input: true,
             auto_camera: false,
             bow_zoom: true,
+            save_session_history: false,
         }
     }
 }
@@ -1,6 +1,13 @@
-use crate::{render::RenderMode, window::FullScreenSettings};
+use crate::{
+    render::{
+        AaMode, AmbientOcclusionMode, BloomMode, CloudMode, FluidMode, LightingMode, RenderMode,
+        ShadowMode, VolumetricFogMode,
+    },
+    window::FullScreenSettings,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use strum::EnumIter;
 
 #[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub enum Fps {
@@ -33,6 +40,8 @@ pub struct GraphicsSettings {
     pub entity_view_distance: u32,
     pub lod_distance: u32,
     pub sprite_render_distance: u32,
+    /// Share of the decorative sprites, like grass, which are rendered
+    pub sprite_density: f32,
     pub particles_enabled: bool,
     pub weapon_trails_enabled: bool,
     pub figure_lod_render_distance: u32,
@@ -42,10 +51,14 @@ pub struct GraphicsSettings {
     pub gamma: f32,
     pub exposure: f32,
     pub ambiance: f32,
+    /// Strength of the colour grading of the biomes and times of day, from 0
+    /// to 1
+    pub color_grading: f32,
     pub render_mode: RenderMode,
     pub window_size: [u16; 2],
     pub fullscreen: FullScreenSettings,
     pub lod_detail: u32,
+    pub adaptive_quality: AdaptiveQualitySettings,
 }
 
 impl Default for GraphicsSettings {
@@ -55,6 +68,7 @@ impl Default for GraphicsSettings {
             entity_view_distance: client::MAX_SELECTABLE_VIEW_DISTANCE,
             lod_distance: 200,
             sprite_render_distance: 100,
+            sprite_density: 1.0,
             particles_enabled: true,
             weapon_trails_enabled: true,
             figure_lod_render_distance: 300,
@@ -64,10 +78,177 @@ impl Default for GraphicsSettings {
             gamma: 1.0,
             exposure: 1.0,
             ambiance: 0.5,
+            color_grading: 1.0,
             render_mode: RenderMode::default(),
             window_size: [1280, 720],
             fullscreen: FullScreenSettings::default(),
             lod_detail: 250,
+            adaptive_quality: AdaptiveQualitySettings::default(),
+        }
+    }
+}
+
+/// Bounds within which the view distances and effects get lowered when the
+/// framerate drops
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveQualitySettings {
+    pub enabled: bool,
+    /// Framerate the quality gets lowered below
+    pub min_fps: u32,
+    /// Lowest terrain view distance the quality can go down to
+    pub min_terrain_view_distance: u32,
+    /// Whether particles can be turned off at the lowest quality
+    pub allow_disabling_particles: bool,
+}
+
+impl Default for AdaptiveQualitySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_fps: 30,
+            min_terrain_view_distance: 6,
+            allow_disabling_particles: true,
+        }
+    }
+}
+
+/// Graphics presets, trading visual quality for performance.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum GraphicsPreset {
+    /// For the weakest machines: flat shading, no point lights, shadows or
+    /// postprocessing, and fewer sprites
+    Potato,
+    Low,
+    Medium,
+    High,
+    Ultra,
+}
+
+impl GraphicsPreset {
+    /// Suggest a preset from the kind of graphics device in use and the
+    /// average framerate measured while rendering the main menu.
+    pub fn suggest(device_type: wgpu::DeviceType, avg_fps: f32) -> Self {
+        // The main menu is cheap to render, so not holding a steady framerate there
+        // is a strong hint that the game itself will struggle.
+        let struggling = avg_fps < 45.0;
+        if avg_fps < 20.0 {
+            return Self::Potato;
+        }
+        match device_type {
+            wgpu::DeviceType::DiscreteGpu if struggling => Self::Medium,
+            wgpu::DeviceType::DiscreteGpu => Self::High,
+            wgpu::DeviceType::IntegratedGpu | wgpu::DeviceType::VirtualGpu if !struggling => {
+                Self::Medium
+            },
+            wgpu::DeviceType::Other if !struggling => Self::Medium,
+            _ => Self::Low,
+        }
+    }
+
+    pub fn i18n_key(&self) -> &'static str {
+        match self {
+            Self::Potato => "hud-settings-preset-potato",
+            Self::Low => "hud-settings-preset-low",
+            Self::Medium => "hud-settings-preset-medium",
+            Self::High => "hud-settings-preset-high",
+            Self::Ultra => "hud-settings-preset-ultra",
+        }
+    }
+}
+
+impl GraphicsSettings {
+    /// Apply the view distances and render modes of a preset, leaving the
+    /// window and framerate settings untouched.
+    pub fn apply_preset(&mut self, preset: GraphicsPreset) {
+        let (terrain_view_distance, lod_detail, sprite_render_distance, figure_lod) = match preset {
+            GraphicsPreset::Potato => (4, 50, 30, 100),
+            GraphicsPreset::Low => (6, 100, 50, 150),
+            GraphicsPreset::Medium => (10, 250, 100, 300),
+            GraphicsPreset::High => (16, 500, 150, 450),
+            GraphicsPreset::Ultra => (24, 750, 250, 600),
+        };
+        self.terrain_view_distance = terrain_view_distance;
+        self.lod_detail = lod_detail;
+        self.sprite_render_distance = sprite_render_distance;
+        self.figure_lod_render_distance = figure_lod;
+        self.particles_enabled = !matches!(preset, GraphicsPreset::Potato | GraphicsPreset::Low);
+        self.sprite_density = if preset == GraphicsPreset::Potato {
+            0.3
+        } else {
+            1.0
+        };
+
+        let render_mode = &mut self.render_mode;
+        render_mode.ambient_occlusion = match preset {
+            GraphicsPreset::Potato | GraphicsPreset::Low | GraphicsPreset::Medium => {
+                AmbientOcclusionMode::default()
+            },
+            GraphicsPreset::High => AmbientOcclusionMode {
+                enabled: true,
+                samples: 8,
+            },
+            GraphicsPreset::Ultra => AmbientOcclusionMode {
+                enabled: true,
+                samples: 16,
+            },
+        };
+        render_mode.volumetric_fog = match preset {
+            GraphicsPreset::Potato | GraphicsPreset::Low | GraphicsPreset::Medium => {
+                VolumetricFogMode::default()
+            },
+            GraphicsPreset::High => VolumetricFogMode {
+                enabled: true,
+                steps: 8,
+            },
+            GraphicsPreset::Ultra => VolumetricFogMode {
+                enabled: true,
+                steps: 16,
+            },
+        };
+        render_mode.screen_space_reflections =
+            matches!(preset, GraphicsPreset::High | GraphicsPreset::Ultra);
+        match preset {
+            GraphicsPreset::Potato => {
+                render_mode.aa = AaMode::None;
+                render_mode.cloud = CloudMode::None;
+                render_mode.fluid = FluidMode::Cheap;
+                render_mode.lighting = LightingMode::Flat;
+                render_mode.shadow = ShadowMode::None;
+                render_mode.bloom = BloomMode::Off;
+            },
+            GraphicsPreset::Low => {
+                render_mode.aa = AaMode::None;
+                render_mode.cloud = CloudMode::Minimal;
+                render_mode.fluid = FluidMode::Cheap;
+                render_mode.lighting = LightingMode::Lambertian;
+                render_mode.shadow = ShadowMode::None;
+                render_mode.bloom = BloomMode::Off;
+            },
+            GraphicsPreset::Medium => {
+                render_mode.aa = AaMode::Fxaa;
+                render_mode.cloud = CloudMode::Low;
+                render_mode.fluid = FluidMode::Cheap;
+                render_mode.lighting = LightingMode::BlinnPhong;
+                render_mode.shadow = ShadowMode::Cheap;
+                render_mode.bloom = BloomMode::default();
+            },
+            GraphicsPreset::High => {
+                render_mode.aa = AaMode::Fxaa;
+                render_mode.cloud = CloudMode::High;
+                render_mode.fluid = FluidMode::Shiny;
+                render_mode.lighting = LightingMode::BlinnPhong;
+                render_mode.shadow = ShadowMode::default();
+                render_mode.bloom = BloomMode::default();
+            },
+            GraphicsPreset::Ultra => {
+                render_mode.aa = AaMode::MsaaX4;
+                render_mode.cloud = CloudMode::Ultra;
+                render_mode.fluid = FluidMode::Shiny;
+                render_mode.lighting = LightingMode::Ashikhmin;
+                render_mode.shadow = ShadowMode::default();
+                render_mode.bloom = BloomMode::default();
+            },
         }
     }
 }
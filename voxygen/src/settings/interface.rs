@@ -22,6 +22,7 @@ pub struct InterfaceSettings {
     pub speech_bubble_self: bool,
     pub speech_bubble_dark_mode: bool,
     pub speech_bubble_icon: bool,
+    pub speech_bubble_portrait: bool,
     pub crosshair_opacity: f32,
     pub crosshair_type: CrosshairType,
     pub intro_show: Intro,
@@ -41,12 +42,37 @@ pub struct InterfaceSettings {
     pub map_show_caves: bool,
     pub map_show_trees: bool,
     pub map_show_peaks: bool,
+    pub map_show_portals: bool,
     pub map_show_biomes: bool,
     pub map_show_voxel_map: bool,
     pub minimap_show: bool,
     pub minimap_face_north: bool,
     pub minimap_zoom: f64,
+    /// Shows the creatures and players nearby as dots on the minimap
+    pub minimap_show_entities: bool,
     pub accum_experience: bool,
+    /// Hides the server address, who whispers to you and your account name,
+    /// for people streaming or recording the game
+    pub streamer_mode: bool,
+    /// Shows from which direction the character is being hit
+    pub damage_indicators: bool,
+    /// Pulses a red frame around the screen at low health
+    pub low_health_vignette: bool,
+    /// Shakes the camera when the character is hit
+    pub hit_shake: bool,
+    /// Shows the compass strip at the top of the screen
+    pub compass_show: bool,
+    pub compass_opacity: f32,
+    /// Which parts of the nameplates over the heads of other entities are
+    /// shown
+    pub nameplate_player_names: bool,
+    pub nameplate_npc_names: bool,
+    pub nameplate_levels: bool,
+    pub nameplate_health_bars: bool,
+    /// Fades out the nameplates of far away entities
+    pub nameplate_distance_fade: bool,
+    /// Colours the names of hostile and friendly creatures
+    pub nameplate_hostility_colors: bool,
 }
 
 impl Default for InterfaceSettings {
@@ -65,6 +91,7 @@ impl Default for InterfaceSettings {
             speech_bubble_self: true,
             speech_bubble_dark_mode: false,
             speech_bubble_icon: true,
+            speech_bubble_portrait: true,
             crosshair_opacity: 0.6,
             crosshair_type: CrosshairType::Round,
             intro_show: Intro::Show,
@@ -84,12 +111,26 @@ impl Default for InterfaceSettings {
             map_show_caves: true,
             map_show_trees: false,
             map_show_peaks: false,
+            map_show_portals: true,
             map_show_biomes: false,
             map_show_voxel_map: true,
             minimap_show: true,
             minimap_face_north: true,
             minimap_zoom: 160.0,
+            minimap_show_entities: true,
             accum_experience: true,
+            streamer_mode: false,
+            damage_indicators: true,
+            low_health_vignette: true,
+            hit_shake: false,
+            compass_show: true,
+            compass_opacity: 0.8,
+            nameplate_player_names: true,
+            nameplate_npc_names: true,
+            nameplate_levels: true,
+            nameplate_health_bars: true,
+            nameplate_distance_fade: true,
+            nameplate_hostility_colors: true,
         }
     }
 }
@@ -6,6 +6,7 @@ use std::{
 };
 use tracing::warn;
 
+pub mod accessibility;
 pub mod audio;
 pub mod chat;
 pub mod control;
@@ -15,16 +16,21 @@ pub mod graphics;
 pub mod interface;
 pub mod language;
 pub mod networking;
+pub mod photo_mode;
+pub mod profiles;
 
+pub use accessibility::{AccessibilitySettings, ColorPalette};
 pub use audio::{AudioOutput, AudioSettings};
 pub use chat::ChatSettings;
-pub use control::ControlSettings;
+pub use control::{BindingConflict, ControlSettings, KeybindingScheme};
 pub use gamepad::GamepadSettings;
 pub use gameplay::GameplaySettings;
-pub use graphics::{get_fps, Fps, GraphicsSettings};
+pub use graphics::{get_fps, AdaptiveQualitySettings, Fps, GraphicsPreset, GraphicsSettings};
 pub use interface::InterfaceSettings;
 pub use language::LanguageSettings;
 pub use networking::NetworkingSettings;
+pub use photo_mode::PhotoModeSettings;
+pub use profiles::SettingsProfile;
 
 /// `Settings` contains everything that can be configured in the settings.ron
 /// file.
@@ -38,13 +44,23 @@ pub struct Settings {
     pub networking: NetworkingSettings,
     pub graphics: GraphicsSettings,
     pub audio: AudioSettings,
+    pub photo_mode: PhotoModeSettings,
+    pub accessibility: AccessibilitySettings,
     pub show_disclaimer: bool,
     pub send_logon_commands: bool,
     // TODO: Remove at a later date, for dev testing
     pub logon_commands: Vec<String>,
     pub language: LanguageSettings,
+    /// Relative paths are relative to the userdata directory
     pub screenshots_path: PathBuf,
+    /// Longest clip recorded, in seconds, clips being disabled when zero
+    #[serde(default)]
+    pub clip_secs: u32,
     pub controller: GamepadSettings,
+    /// Only fresh installs go through the setup wizard, existing settings files
+    /// without this field skip it.
+    #[serde(default)]
+    pub show_setup_wizard: bool,
 }
 
 impl Default for Settings {
@@ -53,11 +69,16 @@ impl Default for Settings {
 
         // Chooses a path to store the screenshots by the following order:
         //  - The VOXYGEN_SCREENSHOT environment variable
+        //  - The userdata directory in portable mode, as a relative path so that it
+        //    still works when the drive is mounted elsewhere
         //  - The user's picture directory
         //  - The executable's directory
         // This only selects if there isn't already an entry in the settings file
         let screenshots_path = std::env::var_os("VOXYGEN_SCREENSHOT")
             .map(PathBuf::from)
+            .or_else(|| {
+                common_base::userdata_dir::is_portable().then(|| PathBuf::from("screenshots"))
+            })
             .or_else(|| user_dirs.picture_dir().map(|dir| dir.join("veloren")))
             .or_else(|| {
                 std::env::current_exe()
@@ -74,12 +95,16 @@ impl Default for Settings {
             networking: NetworkingSettings::default(),
             graphics: GraphicsSettings::default(),
             audio: AudioSettings::default(),
+            photo_mode: PhotoModeSettings::default(),
+            accessibility: AccessibilitySettings::default(),
             show_disclaimer: true,
             send_logon_commands: false,
             logon_commands: Vec::new(),
             language: LanguageSettings::default(),
             screenshots_path,
+            clip_secs: 0,
             controller: GamepadSettings::default(),
+            show_setup_wizard: true,
         }
     }
 }
@@ -13,6 +13,8 @@ pub struct NetworkingSettings {
     pub player_physics_behavior: bool,
     pub lossy_terrain_compression: bool,
     pub enable_discord_integration: bool,
+    pub check_for_updates: bool,
+    pub update_feed_url: String,
 }
 
 impl Default for NetworkingSettings {
@@ -29,6 +31,8 @@ impl Default for NetworkingSettings {
             player_physics_behavior: false,
             lossy_terrain_compression: false,
             enable_discord_integration: true,
+            check_for_updates: true,
+            update_feed_url: "https://download.veloren.net/latest_release.ron".to_string(),
         }
     }
 }
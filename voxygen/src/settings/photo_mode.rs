@@ -0,0 +1,27 @@
+use crate::scene::photo_mode::PhotoFilter;
+use serde::{Deserialize, Serialize};
+
+/// `PhotoModeSettings` contains the effects and the size of the pictures taken
+/// in photo mode.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhotoModeSettings {
+    /// Distance, in blocks, of what is in focus
+    pub focus_distance: f32,
+    /// Strength of the blur of what is out of focus, none at zero
+    pub aperture: f32,
+    pub filter: PhotoFilter,
+    /// Times the size of the window the pictures are taken at
+    pub supersampling: u32,
+}
+
+impl Default for PhotoModeSettings {
+    fn default() -> Self {
+        Self {
+            focus_distance: 20.0,
+            aperture: 0.0,
+            filter: PhotoFilter::None,
+            supersampling: 4,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Named sets of the graphics, audio, interface and keybinding settings, each
+//! kept in its own file of the config directory so that players can switch
+//! between setups and share them.
+
+use super::{AudioSettings, ControlSettings, GraphicsSettings, InterfaceSettings, Settings};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const PROFILES_DIR: &str = "settings_profiles";
+
+/// `SettingsProfile` contains the parts of the settings that make up a setup,
+/// the rest (chat tabs, language, ...) being kept when switching
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsProfile {
+    pub graphics: GraphicsSettings,
+    pub audio: AudioSettings,
+    pub interface: InterfaceSettings,
+    pub controls: ControlSettings,
+}
+
+impl SettingsProfile {
+    pub fn of(settings: &Settings) -> Self {
+        Self {
+            graphics: settings.graphics.clone(),
+            audio: settings.audio.clone(),
+            interface: settings.interface.clone(),
+            controls: settings.controls.clone(),
+        }
+    }
+
+    pub fn apply_to(self, settings: &mut Settings) {
+        // Whether the intro was seen isn't part of a setup
+        let intro_show = settings.interface.intro_show;
+        settings.graphics = self.graphics;
+        settings.audio = self.audio;
+        settings.interface = self.interface;
+        settings.interface.intro_show = intro_show;
+        settings.controls = self.controls;
+    }
+
+    /// Names of the saved profiles, sorted
+    pub fn list(config_dir: &Path) -> Vec<String> {
+        let mut names = fs::read_dir(config_dir.join(PROFILES_DIR))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.extension().map_or(false, |ext| ext == "ron"))
+                    .filter_map(|path| Some(path.file_stem()?.to_str()?.to_owned()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    pub fn load(config_dir: &Path, name: &str) -> Result<Self, String> {
+        Self::import(&profile_path(config_dir, name))
+    }
+
+    pub fn save(&self, config_dir: &Path, name: &str) -> Result<(), String> {
+        self.export(&profile_path(config_dir, name))
+    }
+
+    pub fn delete(config_dir: &Path, name: &str) -> Result<(), String> {
+        fs::remove_file(profile_path(config_dir, name)).map_err(|err| err.to_string())
+    }
+
+    /// Reads a profile from any file, e.g. one shared by another player
+    pub fn import(path: &Path) -> Result<Self, String> {
+        fs::File::open(path)
+            .map_err(|err| err.to_string())
+            .and_then(|file| ron::de::from_reader(file).map_err(|err| err.to_string()))
+    }
+
+    /// Writes the profile to any file, e.g. to share it with another player
+    pub fn export(&self, path: &Path) -> Result<(), String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|ron| {
+                path.parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(path, ron))
+                    .map_err(|err| err.to_string())
+            })
+    }
+}
+
+/// Profile names end up in file paths, so anything but letters, digits, ' ',
+/// '_' and '-' is replaced
+pub fn profile_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == ' ' || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn profile_path(config_dir: &Path, name: &str) -> PathBuf {
+    config_dir
+        .join(PROFILES_DIR)
+        .join(format!("{}.ron", profile_name(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_names_make_safe_file_names() {
+        assert_eq!(profile_name(" max quality "), "max quality");
+        assert_eq!(
+            profile_path(Path::new("config"), "../potato"),
+            Path::new("config/settings_profiles/___potato.ron")
+        );
+    }
+}
@@ -2,6 +2,7 @@ use common::clock::Clock;
 use crossbeam_channel::{bounded, unbounded, Receiver, Sender, TryRecvError};
 use server::{
     persistence::{DatabaseSettings, SqlLogMode},
+    tick_catch_up::TickCatchUp,
     Error as ServerError, Event, Input, Server,
 };
 use std::{
@@ -162,6 +163,7 @@ fn run_server(mut server: Server, stop_server_r: Receiver<()>, paused: Arc<Atomi
 
     // Set up an fps clock
     let mut clock = Clock::new(Duration::from_secs_f64(1.0 / TPS as f64));
+    let mut catch_up = TickCatchUp::new(Duration::from_secs_f64(1.0 / TPS as f64));
 
     loop {
         // Check any event such as stopping and pausing
@@ -181,19 +183,28 @@ fn run_server(mut server: Server, stop_server_r: Receiver<()>, paused: Arc<Atomi
             paused.store(false, Ordering::SeqCst);
         }
 
-        let events = server
-            .tick(Input::default(), clock.dt())
-            .expect("Failed to tick server!");
-
-        for event in events {
-            match event {
-                Event::ClientConnected { .. } => info!("Client connected!"),
-                Event::ClientDisconnected { .. } => info!("Client disconnected!"),
-                Event::Chat { entity: _, msg } => info!("[Client] {}", msg),
+        // Run a few more ticks in a row to catch up after slow ones
+        let steps = catch_up.steps(clock.dt());
+        for _ in 0..steps.count {
+            let events = server
+                .tick(Input::default(), catch_up.target_dt())
+                .expect("Failed to tick server!");
+
+            for event in events {
+                match event {
+                    Event::ClientConnected { .. } => info!("Client connected!"),
+                    Event::ClientDisconnected { .. } => info!("Client disconnected!"),
+                    Event::Chat { entity: _, msg } => info!("[Client] {}", msg),
+                    // Singleplayer servers stop with the game, they aren't restarted
+                    Event::Restart { .. } => {},
+                }
             }
-        }
 
-        // Clean up the server after a tick.
-        server.cleanup();
+            // Clean up the server after a tick.
+            server.cleanup();
+        }
+        if let Some(dropped) = steps.dropped {
+            server.notify_overloaded(dropped);
+        }
     }
 }
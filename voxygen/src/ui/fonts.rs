@@ -3,6 +3,8 @@ use common::assets::{self, AssetExt};
 
 pub struct Font {
     metadata: i18n::Font,
+    /// Scale of the text on top of the UI scale, see the accessibility settings
+    text_scale: f32,
     pub conrod_id: conrod_core::text::font::Id,
 }
 
@@ -12,12 +14,15 @@ impl Font {
 
         Ok(Self {
             metadata: font.clone(),
+            text_scale: 1.0,
             conrod_id: ui.new_font(raw_font),
         })
     }
 
     /// Scale input size to final UI size
-    pub fn scale(&self, value: u32) -> u32 { self.metadata.scale(value) }
+    pub fn scale(&self, value: u32) -> u32 {
+        (self.metadata.scale(value) as f32 * self.text_scale).round() as u32
+    }
 }
 
 macro_rules! conrod_fonts {
@@ -33,6 +38,10 @@ macro_rules! conrod_fonts {
                         $( $name: Font::new(fonts.get(stringify!($name)).unwrap(), ui)?, )*
                     })
                 }
+
+                pub fn set_text_scale(&mut self, text_scale: f32) {
+                    $( self.$name.text_scale = text_scale; )*
+                }
             }
         )*
     };
@@ -248,17 +248,27 @@ where
             }
 
             if let mouse::ButtonPosition::Up = input.mouse.buttons.left() {
+                // Releasing a stack while holding shift only moves half of it
+                let split = drag_amount.is_some() && input.modifiers.contains(ModifierKey::SHIFT);
                 // Get widget under the mouse
                 if let Some(id) = input.widget_under_mouse {
                     // If over the window widget drop the contents
                     if id == ui.window {
-                        self.events.push(Event::Dropped(*slot));
+                        self.events.push(if split {
+                            Event::SplitDropped(*slot)
+                        } else {
+                            Event::Dropped(*slot)
+                        });
                     } else if let Some(idx) = slot_ids.iter().position(|slot_id| *slot_id == id) {
                         // If widget is a slot widget swap with it
                         let (from, to) = (*slot, slots[idx]);
                         // Don't drag if it is the same slot
                         if from != to {
-                            self.events.push(Event::Dragged(from, to));
+                            self.events.push(if split {
+                                Event::SplitDragged(from, to)
+                            } else {
+                                Event::Dragged(from, to)
+                            });
                         }
                     }
                 }
@@ -0,0 +1,144 @@
+use crossbeam_channel::{bounded, Receiver, TryRecvError};
+use hyper::{body, Body, Client, Uri};
+use serde::Deserialize;
+use tokio::runtime::Runtime;
+use tracing::{debug, info, warn};
+
+/// Information about the latest release, as published in the release feed.
+///
+/// The feed is a RON document of the following shape:
+/// ```ron
+/// (
+///     version: "0.14.0",
+///     network_version: (0, 7, 0),
+///     changelog: "- Added ...\n- Fixed ...",
+/// )
+/// ```
+#[derive(Clone, Debug, Deserialize)]
+pub struct ReleaseInfo {
+    /// Version of the release, in `major.minor.patch` form
+    pub version: String,
+    /// Network protocol version spoken by the release
+    pub network_version: [u32; 3],
+    /// Changelog of the release, one entry per line
+    pub changelog: String,
+}
+
+impl ReleaseInfo {
+    /// Whether this release is newer than the running client.
+    pub fn is_newer_than_current(&self) -> bool {
+        match (
+            parse_version(&self.version),
+            parse_version(env!("CARGO_PKG_VERSION")),
+        ) {
+            (Some(latest), Some(current)) => latest > current,
+            _ => false,
+        }
+    }
+}
+
+fn parse_version(version: &str) -> Option<[u32; 3]> {
+    let mut parts = version.trim().trim_start_matches('v').split('.');
+    let mut parse_next = || -> Option<u32> { parts.next()?.parse().ok() };
+    let version = [parse_next()?, parse_next()?, parse_next().unwrap_or(0)];
+    Some(version)
+}
+
+#[derive(Debug)]
+enum Error {
+    Http(hyper::Error),
+    Status(hyper::StatusCode),
+    Parse(ron::Error),
+}
+
+async fn fetch_release_info(url: Uri) -> Result<ReleaseInfo, Error> {
+    let https = hyper_rustls::HttpsConnector::with_native_roots();
+    let client = Client::builder().build::<_, Body>(https);
+
+    let response = client.get(url).await.map_err(Error::Http)?;
+    if !response.status().is_success() {
+        return Err(Error::Status(response.status()));
+    }
+    let bytes = body::to_bytes(response.into_body())
+        .await
+        .map_err(Error::Http)?;
+
+    ron::de::from_bytes(&bytes).map_err(Error::Parse)
+}
+
+/// Queries the release feed in the background and holds on to the result.
+pub enum UpdateCheck {
+    /// Update checks are disabled in the settings
+    Disabled,
+    /// Waiting for the release feed to respond
+    Pending(Receiver<Option<ReleaseInfo>>),
+    /// The check finished, containing the latest release if it could be
+    /// retrieved
+    Done(Option<ReleaseInfo>),
+}
+
+impl UpdateCheck {
+    /// Start fetching the release feed at `url` on the provided runtime.
+    pub fn start(runtime: &Runtime, url: &str) -> Self {
+        let url = match url.parse::<Uri>() {
+            Ok(url) => url,
+            Err(e) => {
+                warn!(?e, "Invalid update feed url, skipping update check");
+                return Self::Done(None);
+            },
+        };
+
+        let (sender, receiver) = bounded(1);
+        runtime.spawn(async move {
+            let release = match fetch_release_info(url).await {
+                Ok(release) => {
+                    debug!(?release.version, "Retrieved release feed");
+                    Some(release)
+                },
+                Err(e) => {
+                    warn!(?e, "Failed to retrieve release feed");
+                    None
+                },
+            };
+            let _ = sender.send(release);
+        });
+
+        Self::Pending(receiver)
+    }
+
+    /// Poll the check, returning the latest release if it is newer than the
+    /// running client.
+    pub fn newer_release(&mut self) -> Option<&ReleaseInfo> {
+        if let Self::Pending(receiver) = self {
+            match receiver.try_recv() {
+                Ok(release) => {
+                    if let Some(release) = release.as_ref().filter(|r| r.is_newer_than_current()) {
+                        info!(?release.version, "A new version of Veloren is available");
+                    }
+                    *self = Self::Done(release);
+                },
+                Err(TryRecvError::Empty) => {},
+                Err(TryRecvError::Disconnected) => *self = Self::Done(None),
+            }
+        }
+
+        match self {
+            Self::Done(Some(release)) if release.is_newer_than_current() => Some(release),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_version;
+
+    #[test]
+    fn parse_versions() {
+        assert_eq!(parse_version("0.14.0"), Some([0, 14, 0]));
+        assert_eq!(parse_version("v1.2"), Some([1, 2, 0]));
+        assert_eq!(parse_version(" 0.13.1 "), Some([0, 13, 1]));
+        assert_eq!(parse_version("nightly"), None);
+        assert!(parse_version("0.14.0") > parse_version("0.13.9"));
+    }
+}
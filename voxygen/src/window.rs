@@ -1,9 +1,10 @@
 use crate::{
+    capture::{CaptureMetadata, ClipBuffer},
     controller::*,
     error::Error,
     game_input::GameInput,
     render::Renderer,
-    settings::{ControlSettings, Settings},
+    settings::{BindingConflict, ControlSettings, Settings},
     ui,
 };
 use common_base::span;
@@ -93,17 +94,96 @@ pub enum Event {
     AnalogGameInput(AnalogGameInput),
     /// We tried to save a screenshot
     ScreenshotMessage(String),
+    /// A gamepad button was pressed to be bound from the settings
+    GamepadBinding(GamepadBinding, Button),
 }
 
 pub type MouseButton = winit::event::MouseButton;
 pub type PressState = winit::event::ElementState;
 pub type EventLoop = winit::event_loop::EventLoop<()>;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ScrollDirection {
+    Up,
+    Down,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum KeyMouse {
     Key(winit::event::VirtualKeyCode),
     Mouse(winit::event::MouseButton),
     ScanKey(winit::event::ScanCode),
+    /// A single step of the mouse wheel
+    Scroll(ScrollDirection),
+    /// A key pressed while holding a modifier, e.g. Shift+K
+    Chord(Modifier, winit::event::VirtualKeyCode),
+}
+
+/// Modifier of a chord, the left and right keys being the same
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Modifier {
+    Shift,
+    Ctrl,
+    Alt,
+}
+
+impl Modifier {
+    /// Modifiers held down, in the order chords are looked up
+    fn held(modifiers: winit::event::ModifiersState) -> impl Iterator<Item = Self> {
+        [
+            (Self::Ctrl, modifiers.ctrl()),
+            (Self::Alt, modifiers.alt()),
+            (Self::Shift, modifiers.shift()),
+        ]
+        .into_iter()
+        .filter_map(|(modifier, held)| held.then(|| modifier))
+    }
+
+    /// Whether the key is a modifier itself, which can't be part of a chord
+    fn is_modifier_key(key: winit::event::VirtualKeyCode) -> bool {
+        use winit::event::VirtualKeyCode::*;
+        matches!(key, LShift | RShift | LControl | RControl | LAlt | RAlt)
+    }
+
+    fn display_string(&self) -> &'static str {
+        match self {
+            Self::Shift => "Shift",
+            Self::Ctrl => {
+                if cfg!(macos) {
+                    "Cmd ⌘"
+                } else {
+                    "Ctrl"
+                }
+            },
+            Self::Alt => {
+                if cfg!(macos) {
+                    "Option ⌥"
+                } else {
+                    "Alt"
+                }
+            },
+        }
+    }
+}
+
+/// Number printed on mice for the additional mouse button, so that the back
+/// and forward buttons are called 4 and 5 on every platform
+fn mouse_button_number(button: u16) -> u16 {
+    if cfg!(windows) {
+        // XBUTTON1 and XBUTTON2 are 1 and 2
+        button + 3
+    } else if cfg!(target_os = "macos") {
+        // Button numbers start at 0 for left click
+        button + 1
+    } else {
+        match button {
+            // Evdev codes on Wayland, starting at BTN_LEFT
+            0x110.. => button - 0x10F,
+            // X11 buttons, 4 to 7 being used by the scroll wheel
+            8.. => button - 4,
+            _ => button + 3,
+        }
+    }
 }
 
 impl KeyMouse {
@@ -234,9 +314,9 @@ impl KeyMouse {
             Key(NavigateBackward) => "Navigate Backward",
             Key(NavigateForward) => "Navigate Forward",
             Key(NoConvert) => "Non Convert",
-            Key(NumpadComma) => "Num ,",
-            Key(NumpadEnter) => "Num Enter",
-            Key(NumpadEquals) => "Num =",
+            Key(NumpadComma) => "Numpad ,",
+            Key(NumpadEnter) => "Numpad Enter",
+            Key(NumpadEquals) => "Numpad =",
             Key(OEM102) => "<",
             Key(Period) => ".",
             Key(Power) => "Power",
@@ -305,7 +385,7 @@ impl KeyMouse {
             Key(Slash) => "/",
             Key(Sleep) => "Sleep",
             Key(Stop) => "Media Stop",
-            Key(NumpadSubtract) => "Num -",
+            Key(NumpadSubtract) => "Numpad -",
             Key(Sysrq) => "Sysrq",
             Key(Tab) => "Tab",
             Key(Underline) => "_",
@@ -329,9 +409,10 @@ impl KeyMouse {
             Mouse(MouseButton::Right) => "Right Click",
             Mouse(MouseButton::Middle) => "Middle Click",
             Mouse(MouseButton::Other(button)) => {
-                // Additional mouse buttons after middle click start at 1
-                return format!("Mouse {}", button + 3);
+                return format!("Mouse {}", mouse_button_number(*button));
             },
+            Scroll(ScrollDirection::Up) => "Scroll Up",
+            Scroll(ScrollDirection::Down) => "Scroll Down",
             ScanKey(scancode) => {
                 return if let Some(layout) = key_layout {
                     layout.get_key_as_string(*scancode)
@@ -339,6 +420,13 @@ impl KeyMouse {
                     format!("Unknown (0x{:X})", scancode)
                 };
             },
+            Chord(modifier, key) => {
+                return format!(
+                    "{}+{}",
+                    modifier.display_string(),
+                    Key(*key).display_string(key_layout)
+                );
+            },
         };
 
         key_string.to_owned()
@@ -346,7 +434,7 @@ impl KeyMouse {
 
     /// If it exists, returns the shortened version of a key name
     /// (e.g. Left Click -> M1)
-    pub fn try_shortened(&self, _key_layout: &Option<KeyLayout>) -> Option<String> {
+    pub fn try_shortened(&self, key_layout: &Option<KeyLayout>) -> Option<String> {
         use self::KeyMouse::*;
         use winit::event::{MouseButton, VirtualKeyCode::*};
         let key_string = match self {
@@ -354,12 +442,25 @@ impl KeyMouse {
             Mouse(MouseButton::Right) => "M2",
             Mouse(MouseButton::Middle) => "M3",
             Mouse(MouseButton::Other(button)) => {
-                // Additional mouse buttons after middle click start at 1
-                return Some(format!("M{}", button + 3));
+                return Some(format!("M{}", mouse_button_number(*button)));
             },
+            Scroll(ScrollDirection::Up) => "MWUp",
+            Scroll(ScrollDirection::Down) => "MWDn",
             Key(Back) => "Back",
             Key(LShift) => "LShft",
             Key(RShift) => "RShft",
+            Chord(modifier, key) => {
+                let modifier = match modifier {
+                    Modifier::Shift => "Shft",
+                    Modifier::Ctrl => "Ctrl",
+                    Modifier::Alt => "Alt",
+                };
+                return Some(format!(
+                    "{}+{}",
+                    modifier,
+                    Key(*key).display_shortest(key_layout)
+                ));
+            },
             _ => return None,
         };
 
@@ -393,12 +494,23 @@ pub struct Window {
     needs_refresh_resize: bool,
     keypress_map: HashMap<GameInput, winit::event::ElementState>,
     pub remapping_keybindings: Option<GameInput>,
+    /// Last binding given a key used by other game inputs, offering to swap
+    pub binding_conflict: Option<BindingConflict>,
+    /// Binding each held key was pressed as, so that chords are released even
+    /// if their modifier is let go first
+    held_keys: HashMap<winit::event::VirtualKeyCode, KeyMouse>,
+    /// Modifier key pressed while remapping, bound alone if it is released
+    /// before another key is pressed with it
+    remapping_modifier: Option<winit::event::VirtualKeyCode>,
     //true for remapping keybinds, false for clearing keybinds
     pub keybinding_mode: bool,
     events: Vec<Event>,
     pub focused: bool,
     gilrs: Option<Gilrs>,
     pub controller_settings: ControllerSettings,
+    /// Waiting for a gamepad button to bind to this
+    pub remapping_gamepad: Option<GamepadBinding>,
+    radial_menu: Option<RadialMenu>,
     cursor_position: winit::dpi::PhysicalPosition<f64>,
     mouse_emulation_vec: Vec2<f32>,
     // Currently used to send and receive screenshot result messages
@@ -406,7 +518,15 @@ pub struct Window {
     message_receiver: channel::Receiver<String>,
     // Used for screenshots & fullscreen toggle to deduplicate/postpone to after event handler
     take_screenshot: bool,
+    toggle_clip_recording: bool,
     toggle_fullscreen: bool,
+    // Times the size of the window the screenshots are taken at
+    screenshot_supersampling: u32,
+    // Where the player is in the world, for the screenshots of the next frame
+    capture_metadata: Option<CaptureMetadata>,
+    // Frames of the clip being recorded, the frames are only read back while
+    // recording
+    recording_clip: Option<ClipBuffer>,
     pub key_layout: Option<KeyLayout>,
 }
 
@@ -496,18 +616,27 @@ impl Window {
             needs_refresh_resize: false,
             keypress_map,
             remapping_keybindings: None,
+            binding_conflict: None,
+            held_keys: HashMap::new(),
+            remapping_modifier: None,
             keybinding_mode: true,
             events: Vec::new(),
             focused: true,
             gilrs,
             controller_settings,
+            remapping_gamepad: None,
+            radial_menu: None,
             cursor_position: winit::dpi::PhysicalPosition::new(0.0, 0.0),
             mouse_emulation_vec: Vec2::zero(),
             // Currently used to send and receive screenshot result messages
             message_sender,
             message_receiver,
             take_screenshot: false,
+            toggle_clip_recording: false,
             toggle_fullscreen: false,
+            screenshot_supersampling: 1,
+            capture_metadata: None,
+            recording_clip: None,
             key_layout,
         };
 
@@ -524,12 +653,35 @@ impl Window {
         &mut self,
         settings: &mut Settings,
         config_dir: &std::path::Path,
+        userdata_dir: &std::path::Path,
     ) {
-        // Handle screenshots and toggling fullscreen
+        self.renderer.end_supersampling();
+        // Handle screenshots, clips and toggling fullscreen
         if self.take_screenshot {
             self.take_screenshot = false;
-            self.take_screenshot(settings);
+            self.take_screenshot(settings, userdata_dir);
+        } else if let Some(clip) = self.recording_clip.as_mut().filter(|clip| clip.frame_due()) {
+            // Frames are left out of the clip when a screenshot is taken instead
+            let clip = clip.clone();
+            self.renderer.capture_clip_frame(move |image| {
+                if let Ok(image) = image {
+                    clip.push(image);
+                }
+            });
+        }
+        if self.toggle_clip_recording {
+            self.toggle_clip_recording = false;
+            self.toggle_clip_recording(settings, userdata_dir);
+        } else if self
+            .recording_clip
+            .as_ref()
+            .map_or(false, ClipBuffer::is_full)
+        {
+            // The clip is saved by itself once it is long enough
+            self.toggle_clip_recording(settings, userdata_dir);
         }
+        // The session sets the metadata again for the next frame
+        self.capture_metadata = None;
         if self.toggle_fullscreen {
             self.toggle_fullscreen = false;
             self.toggle_fullscreen(settings, config_dir);
@@ -610,22 +762,55 @@ impl Window {
                 }
 
                 match event.event {
-                    EventType::ButtonPressed(button, code)
-                    | EventType::ButtonRepeated(button, code) => {
-                        handle_buttons(
-                            &self.controller_settings,
-                            &mut self.events,
-                            &Button::from((button, code)),
-                            true,
-                        );
+                    EventType::ButtonPressed(button, code) => {
+                        let button = Button::from((button, code));
+                        if let Some(binding) = self.remapping_gamepad.take() {
+                            self.events.push(Event::GamepadBinding(binding, button));
+                        } else if self.cursor_grabbed
+                            && button == self.controller_settings.radial_menu_button
+                        {
+                            self.radial_menu = Some(RadialMenu::default());
+                        } else {
+                            handle_buttons(
+                                &self.controller_settings,
+                                &mut self.events,
+                                &button,
+                                true,
+                            );
+                        }
+                    },
+                    EventType::ButtonRepeated(button, code) => {
+                        let button = Button::from((button, code));
+                        if button != self.controller_settings.radial_menu_button {
+                            handle_buttons(
+                                &self.controller_settings,
+                                &mut self.events,
+                                &button,
+                                true,
+                            );
+                        }
                     },
                     EventType::ButtonReleased(button, code) => {
-                        handle_buttons(
-                            &self.controller_settings,
-                            &mut self.events,
-                            &Button::from((button, code)),
-                            false,
-                        );
+                        let button = Button::from((button, code));
+                        if self.radial_menu.is_some()
+                            && button == self.controller_settings.radial_menu_button
+                        {
+                            // The selected slot is used as if its key was pressed
+                            if let Some(slot) =
+                                self.radial_menu.take().and_then(|menu| menu.selected())
+                            {
+                                let input = RadialMenu::SLOTS[slot];
+                                self.events.push(Event::InputUpdate(input, true));
+                                self.events.push(Event::InputUpdate(input, false));
+                            }
+                        } else {
+                            handle_buttons(
+                                &self.controller_settings,
+                                &mut self.events,
+                                &button,
+                                false,
+                            );
+                        }
                     },
                     EventType::ButtonChanged(button, _value, code) => {
                         if let Some(actions) = self
@@ -682,6 +867,10 @@ impl Window {
                                             ));
                                         },
                                         AxisGameAction::CameraX => {
+                                            if let Some(radial_menu) = &mut self.radial_menu {
+                                                radial_menu.dir.x = value;
+                                                continue;
+                                            }
                                             self.events.push(Event::AnalogGameInput(
                                                 AnalogGameInput::CameraX(
                                                     value
@@ -692,6 +881,10 @@ impl Window {
                                             ));
                                         },
                                         AxisGameAction::CameraY => {
+                                            if let Some(radial_menu) = &mut self.radial_menu {
+                                                radial_menu.dir.y = value;
+                                                continue;
+                                            }
                                             let pan_invert_y =
                                                 match self.controller_settings.pan_invert_y {
                                                     true => -1.0,
@@ -854,14 +1047,20 @@ impl Window {
                             KeyMouse::Mouse(button),
                             controls,
                             &mut self.remapping_keybindings,
+                            &mut self.binding_conflict,
                         ),
                     )
                 {
+                    let pressed = state == winit::event::ElementState::Pressed;
                     for game_input in game_inputs {
-                        self.events.push(Event::InputUpdate(
-                            *game_input,
-                            state == winit::event::ElementState::Pressed,
-                        ));
+                        match self.zoom_event(*game_input, 1.0) {
+                            Some(zoom) => {
+                                if pressed {
+                                    self.events.push(zoom);
+                                }
+                            },
+                            None => self.events.push(Event::InputUpdate(*game_input, pressed)),
+                        }
                     }
                 }
                 self.events.push(Event::MouseButton(button, state));
@@ -892,14 +1091,57 @@ impl Window {
                     return;
                 }
 
+                // While remapping, modifier keys are only bound once released, so that they can
+                // be held down to bind a chord
+                if let (Some(key), true) =
+                    (input.virtual_keycode, self.remapping_keybindings.is_some())
+                {
+                    if Modifier::is_modifier_key(key) {
+                        if input.state == winit::event::ElementState::Pressed {
+                            self.remapping_modifier = Some(key);
+                        } else if self.remapping_modifier.take() == Some(key) {
+                            Window::map_input(
+                                KeyMouse::Key(key),
+                                controls,
+                                &mut self.remapping_keybindings,
+                                &mut self.binding_conflict,
+                            );
+                        } else if let Some(game_inputs) =
+                            controls.get_associated_game_inputs(&KeyMouse::Key(key))
+                        {
+                            // Held since before remapping started
+                            self.events.extend(
+                                game_inputs
+                                    .iter()
+                                    .map(|game_input| Event::InputUpdate(*game_input, false)),
+                            );
+                        }
+                        return;
+                    }
+                    self.remapping_modifier = None;
+                }
+
                 let input_key = match input.virtual_keycode {
-                    Some(key) => KeyMouse::Key(key),
+                    Some(key) if input.state == winit::event::ElementState::Pressed => {
+                        let key_mouse = Window::key_with_modifiers(
+                            key,
+                            self.modifiers,
+                            controls,
+                            self.remapping_keybindings.is_some(),
+                        );
+                        self.held_keys.insert(key, key_mouse);
+                        key_mouse
+                    },
+                    Some(key) => self.held_keys.remove(&key).unwrap_or(KeyMouse::Key(key)),
                     None => KeyMouse::ScanKey(input.scancode),
                 };
 
-                if let Some(game_inputs) =
-                    Window::map_input(input_key, controls, &mut self.remapping_keybindings)
-                {
+                if let Some(game_inputs) = Window::map_input(
+                    input_key,
+                    controls,
+                    &mut self.remapping_keybindings,
+                    &mut self.binding_conflict,
+                ) {
                     for game_input in game_inputs {
                         match game_input {
                             GameInput::Fullscreen => {
@@ -930,6 +1172,29 @@ impl Window {
                                     input.state,
                                 );
                             },
+                            GameInput::ZoomIn | GameInput::ZoomOut => {
+                                // Keys only zoom while playing, not while typing in the chat
+                                if let (true, Some(zoom)) = (
+                                    self.cursor_grabbed
+                                        && input.state == winit::event::ElementState::Pressed,
+                                    self.zoom_event(*game_input, 1.0),
+                                ) {
+                                    self.events.push(zoom);
+                                }
+                            },
+                            GameInput::RecordClip => {
+                                self.toggle_clip_recording = input.state
+                                    == winit::event::ElementState::Pressed
+                                    && !Self::is_pressed(
+                                        &mut self.keypress_map,
+                                        GameInput::RecordClip,
+                                    );
+                                Self::set_pressed(
+                                    &mut self.keypress_map,
+                                    GameInput::RecordClip,
+                                    input.state,
+                                );
+                            },
                             _ => self.events.push(Event::InputUpdate(
                                 *game_input,
                                 input.state == winit::event::ElementState::Pressed,
@@ -949,22 +1214,48 @@ impl Window {
                     self.cursor_position = position;
                 }
             },
-            WindowEvent::MouseWheel { delta, .. } if self.cursor_grabbed && self.focused => {
-                const DIFFERENCE_FROM_DEVICE_EVENT_ON_X11: f32 = -15.0;
-                self.events.push(Event::Zoom({
-                    let y = match delta {
-                        winit::event::MouseScrollDelta::LineDelta(_x, y) => y,
-                        // TODO: Check to see if there is a better way to find the "line
-                        // height" than just hardcoding 16.0 pixels.  Alternately we could
-                        // get rid of this and have the user set zoom sensitivity, since
-                        // it's unlikely people would expect a configuration file to work
-                        // across operating systems.
-                        winit::event::MouseScrollDelta::PixelDelta(pos) => (pos.y / 16.0) as f32,
-                    };
-                    y * (self.zoom_sensitivity as f32 / 100.0)
-                        * if self.zoom_inversion { -1.0 } else { 1.0 }
-                        * DIFFERENCE_FROM_DEVICE_EVENT_ON_X11
-                }))
+            WindowEvent::MouseWheel { delta, .. } => {
+                let y = match delta {
+                    winit::event::MouseScrollDelta::LineDelta(_x, y) => y,
+                    // TODO: Check to see if there is a better way to find the "line
+                    // height" than just hardcoding 16.0 pixels.  Alternately we could
+                    // get rid of this and have the user set zoom sensitivity, since
+                    // it's unlikely people would expect a configuration file to work
+                    // across operating systems.
+                    winit::event::MouseScrollDelta::PixelDelta(pos) => (pos.y / 16.0) as f32,
+                };
+                if y != 0.0 {
+                    let key_mouse = KeyMouse::Scroll(if y > 0.0 {
+                        ScrollDirection::Up
+                    } else {
+                        ScrollDirection::Down
+                    });
+                    // Scrolling can be bound while the settings are open
+                    if self.remapping_keybindings.is_some() {
+                        Window::map_input(
+                            key_mouse,
+                            controls,
+                            &mut self.remapping_keybindings,
+                            &mut self.binding_conflict,
+                        );
+                        return;
+                    }
+                    if let (true, Some(game_inputs)) = (
+                        self.cursor_grabbed && self.focused,
+                        controls.get_associated_game_inputs(&key_mouse),
+                    ) {
+                        for game_input in game_inputs {
+                            if let Some(zoom) = self.zoom_event(*game_input, y.abs()) {
+                                self.events.push(zoom);
+                                continue;
+                            }
+                            // There is no release for the wheel, so press and release right
+                            // away
+                            self.events.push(Event::InputUpdate(*game_input, true));
+                            self.events.push(Event::InputUpdate(*game_input, false));
+                        }
+                    }
+                }
             },
             _ => {},
         }
@@ -1265,48 +1556,80 @@ impl Window {
 
     pub fn send_event(&mut self, event: Event) { self.events.push(event) }
 
-    pub fn take_screenshot(&mut self, settings: &Settings) {
+    /// Takes a screenshot at the start of the next frame, as the screenshot key
+    /// does
+    pub fn request_screenshot(&mut self) { self.take_screenshot = true; }
+
+    /// Takes the next screenshot `supersampling` times as large as the window,
+    /// the ones after it being the size of the window again
+    pub fn set_screenshot_supersampling(&mut self, supersampling: u32) {
+        self.screenshot_supersampling = supersampling;
+    }
+
+    /// Where the player is in the world, written into the screenshots taken on
+    /// the next frame
+    pub fn set_capture_metadata(&mut self, metadata: CaptureMetadata) {
+        self.capture_metadata = Some(metadata);
+    }
+
+    pub fn take_screenshot(&mut self, settings: &Settings, userdata_dir: &std::path::Path) {
         let sender = self.message_sender.clone();
-        let mut path = settings.screenshots_path.clone();
+        let path = userdata_dir.join(&settings.screenshots_path);
+        let metadata = self.capture_metadata.take();
+        self.renderer
+            .supersample_next_frame(std::mem::replace(&mut self.screenshot_supersampling, 1));
         self.renderer.create_screenshot(move |image| {
-            use std::time::SystemTime;
-
-            // Handle any error if there was one when generating the image.
-            let image = match image {
-                Ok(i) => i,
+            // Saving happens on the background thread the image was downloaded on
+            let message = match image
+                .and_then(|image| crate::capture::save_screenshot(image, &path, metadata.as_ref()))
+            {
+                Ok(path) => format!("Screenshot saved to {}", path.to_string_lossy()),
                 Err(e) => {
-                    warn!(?e, "Couldn't generate screenshot");
-                    let _result = sender.send(format!("Error when generating screenshot: {}", e));
-                    return;
+                    warn!(?e, "Couldn't save screenshot");
+                    format!("Couldn't save screenshot: {}", e)
                 },
             };
-
-            // Check if folder exists and create it if it does not
-            if !path.exists() {
-                if let Err(e) = std::fs::create_dir_all(&path) {
-                    warn!(?e, "Couldn't create folder for screenshot");
-                    let _result =
-                        sender.send(String::from("Couldn't create folder for screenshot"));
-                }
-            }
-            path.push(format!(
-                "screenshot_{}.png",
-                SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .map(|d| d.as_millis())
-                    .unwrap_or(0)
-            ));
-            // Try to save the image
-            if let Err(e) = image.into_rgba8().save(&path) {
-                warn!(?e, "Couldn't save screenshot");
-                let _result = sender.send(String::from("Couldn't save screenshot"));
-            } else {
-                let _result =
-                    sender.send(format!("Screenshot saved to {}", path.to_string_lossy()));
-            }
+            let _result = sender.send(message);
         });
     }
 
+    /// Starts recording a clip, or stops the one being recorded and saves it
+    /// in the background
+    pub fn toggle_clip_recording(&mut self, settings: &Settings, userdata_dir: &std::path::Path) {
+        let sender = self.message_sender.clone();
+        let clip = match self.recording_clip.take() {
+            Some(clip) => clip,
+            None => {
+                let message = if settings.clip_secs > 0 {
+                    self.recording_clip = Some(ClipBuffer::new(settings.clip_secs));
+                    format!(
+                        "Recording a clip of up to {} seconds...",
+                        settings.clip_secs
+                    )
+                } else {
+                    String::from("Clips are disabled, set clip_secs in the settings to record them")
+                };
+                let _result = sender.send(message);
+                return;
+            },
+        };
+        let path = userdata_dir.join(&settings.screenshots_path);
+        let _result = sender.send(String::from("Saving clip..."));
+        std::thread::Builder::new()
+            .name("clip".into())
+            .spawn(move || {
+                let message = match clip.save(&path) {
+                    Ok(path) => format!("Clip saved to {}", path.to_string_lossy()),
+                    Err(e) => {
+                        warn!(?e, "Couldn't save clip");
+                        format!("Couldn't save clip: {}", e)
+                    },
+                };
+                let _result = sender.send(message);
+            })
+            .expect("Failed to spawn clip thread");
+    }
+
     fn is_pressed(
         map: &mut HashMap<GameInput, winit::event::ElementState>,
         input: GameInput,
@@ -1333,11 +1656,12 @@ impl Window {
         key_mouse: KeyMouse,
         controls: &'a mut ControlSettings,
         remapping: &mut Option<GameInput>,
+        conflict: &mut Option<BindingConflict>,
     ) -> Option<impl Iterator<Item = &'a GameInput>> {
         match *remapping {
             // TODO: save settings
             Some(game_input) => {
-                controls.modify_binding(game_input, key_mouse);
+                *conflict = controls.rebind(game_input, key_mouse);
                 *remapping = None;
                 None
             },
@@ -1347,12 +1671,61 @@ impl Window {
         }
     }
 
+    /// Zoom of the camera by `steps` of the mouse wheel, if the game input is
+    /// one of the zoom ones
+    fn zoom_event(&self, game_input: GameInput, steps: f32) -> Option<Event> {
+        const DIFFERENCE_FROM_DEVICE_EVENT_ON_X11: f32 = 15.0;
+        let direction = match game_input {
+            GameInput::ZoomIn => -1.0,
+            GameInput::ZoomOut => 1.0,
+            _ => return None,
+        };
+        Some(Event::Zoom(
+            direction
+                * steps
+                * (self.zoom_sensitivity as f32 / 100.0)
+                * if self.zoom_inversion { -1.0 } else { 1.0 }
+                * DIFFERENCE_FROM_DEVICE_EVENT_ON_X11,
+        ))
+    }
+
+    /// The chord of the key with a held modifier if it is bound, or being
+    /// remapped, else the key alone
+    fn key_with_modifiers(
+        key: winit::event::VirtualKeyCode,
+        modifiers: winit::event::ModifiersState,
+        controls: &ControlSettings,
+        remapping: bool,
+    ) -> KeyMouse {
+        if Modifier::is_modifier_key(key) {
+            return KeyMouse::Key(key);
+        }
+        Modifier::held(modifiers)
+            .map(|modifier| KeyMouse::Chord(modifier, key))
+            .find(|chord| {
+                remapping
+                    || controls
+                        .get_associated_game_inputs(chord)
+                        .map_or(false, |game_inputs| !game_inputs.is_empty())
+            })
+            .unwrap_or(KeyMouse::Key(key))
+    }
+
     pub fn set_keybinding_mode(&mut self, game_input: GameInput) {
         self.remapping_keybindings = Some(game_input);
+        self.remapping_modifier = None;
+        self.binding_conflict = None;
     }
 
     pub fn toggle_keybinding_mode(&mut self) { self.keybinding_mode = !self.keybinding_mode; }
 
+    pub fn set_gamepad_binding_mode(&mut self, binding: GamepadBinding) {
+        self.remapping_gamepad = Some(binding);
+    }
+
+    /// The radial menu of the skillbar, while its button is held
+    pub fn radial_menu(&self) -> Option<&RadialMenu> { self.radial_menu.as_ref() }
+
     pub fn window(&self) -> &winit::window::Window { &self.window }
 
     pub fn modifiers(&self) -> winit::event::ModifiersState { self.modifiers }
@@ -355,8 +355,17 @@ pub fn apply_wildlife_supplement<'a, R: Rng>(
     time: Option<&(TimeOfDay, Calendar)>,
 ) {
     let scatter = &index.wildlife_spawns;
+    let (current_day_period, calendar) = if let Some((time, calendar)) = time {
+        (DayPeriod::from(time.0), Some(calendar))
+    } else {
+        (DayPeriod::Noon, None)
+    };
     // Configurable density multiplier
-    let wildlife_density_modifier = index.features.wildlife_density;
+    let wildlife_density_modifier = if current_day_period.is_dark() {
+        index.features.wildlife_density * index.features.night_wildlife_density
+    } else {
+        index.features.wildlife_density
+    };
 
     for y in 0..vol.size_xy().y as i32 {
         for x in 0..vol.size_xy().x as i32 {
@@ -373,11 +382,6 @@ pub fn apply_wildlife_supplement<'a, R: Rng>(
 
             let is_underwater = col_sample.water_level > col_sample.alt;
             let is_ice = col_sample.ice_depth > 0.5 && is_underwater;
-            let (current_day_period, calendar) = if let Some((time, calendar)) = time {
-                (DayPeriod::from(time.0), Some(calendar))
-            } else {
-                (DayPeriod::Noon, None)
-            };
 
             let entity_group = scatter
                 .iter()
@@ -1242,6 +1242,7 @@ impl Site {
             match &plot.kind {
                 PlotKind::Dungeon(d) => d.apply_supplement(dynamic_rng, wpos2d, supplement),
                 PlotKind::Gnarling(g) => g.apply_supplement(dynamic_rng, wpos2d, supplement),
+                PlotKind::House(h) => h.apply_supplement(dynamic_rng, wpos2d, supplement),
                 _ => {},
             }
         }
@@ -4,7 +4,10 @@ use crate::{
     util::{RandomField, Sampler, DIRS},
     Land,
 };
-use common::terrain::{Block, BlockKind, SpriteKind};
+use common::{
+    generation::{ChunkSupplement, EntityInfo},
+    terrain::{Block, BlockKind, SpriteKind},
+};
 use rand::prelude::*;
 use vek::*;
 
@@ -86,6 +89,49 @@ impl House {
     }
 
     pub fn z_range(&self) -> Range<i32> { self.alt..self.alt + self.levels as i32 * STOREY }
+
+    /// Position of the table in the living rooms
+    fn table_pos(&self) -> Vec2<i32> {
+        let quarter_x = self.bounds.min.x + (self.bounds.max.x - self.bounds.min.x) / 4;
+        let half_x = self.bounds.min.x + (self.bounds.max.x - self.bounds.min.x) / 2;
+        let half_y = self.bounds.min.y + (self.bounds.max.y - self.bounds.min.y) / 2;
+        let three_quarter_y = self.bounds.min.y + 3 * (self.bounds.max.y - self.bounds.min.y) / 4;
+        if self.is_small() {
+            Vec2::new(half_x, half_y)
+        } else {
+            match self.front {
+                0 => Vec2::new(half_x, three_quarter_y),
+                1 => Vec2::new(half_x, half_y),
+                _ => Vec2::new(quarter_x, half_y),
+            }
+        }
+    }
+
+    fn is_small(&self) -> bool {
+        self.bounds.max.x - self.bounds.min.x < 16 || self.bounds.max.y - self.bounds.min.y < 16
+    }
+
+    pub fn apply_supplement<'a>(
+        &'a self,
+        // NOTE: Used only for dynamic elements like chests and entities!
+        dynamic_rng: &mut impl Rng,
+        wpos2d: Vec2<i32>,
+        supplement: &mut ChunkSupplement,
+    ) {
+        let area = Aabr {
+            min: wpos2d,
+            max: wpos2d + TerrainChunkSize::RECT_SIZE.map(|e| e as i32),
+        };
+        // Some of the ground floor living rooms serve as taverns, with a board game on
+        // the table for the patrons to play
+        let table_pos = self.table_pos();
+        if area.contains_point(table_pos) && dynamic_rng.gen_bool(0.3) {
+            let board_pos = table_pos
+                .map(|e| e as f32 + 0.5)
+                .with_z(self.alt as f32 + 3.0);
+            supplement.add_entity(EntityInfo::at(board_pos).into_minigame());
+        }
+    }
 }
 
 const STOREY: i32 = 5;
@@ -1585,10 +1631,8 @@ impl Structure for House {
                     });
                 }
 
-                if self.bounds.max.x - self.bounds.min.x < 16
-                    || self.bounds.max.y - self.bounds.min.y < 16
-                {
-                    let table_pos = Vec2::new(half_x, half_y);
+                let table_pos = self.table_pos();
+                if self.is_small() {
                     // room is smaller, so use small table
                     painter.sprite(table_pos.with_z(base), SpriteKind::TableDining);
                     for (idx, dir) in CARDINALS.iter().enumerate() {
@@ -1601,11 +1645,6 @@ impl Structure for House {
                     }
                 } else {
                     // room is bigger, so use large table + chair positions
-                    let table_pos = match self.front {
-                        0 => Vec2::new(half_x, three_quarter_y),
-                        1 => Vec2::new(half_x, half_y),
-                        _ => Vec2::new(quarter_x, half_y),
-                    };
                     painter.sprite(table_pos.with_z(base), SpriteKind::TableDouble);
                     for (idx, dir) in CARDINALS.iter().enumerate() {
                         let chair_pos = table_pos + dir * (1 + idx % 2) as i32;